@@ -0,0 +1,73 @@
+//! Build script embedding build provenance metadata
+//!
+//! Exposes the git commit, build timestamp, enabled cargo features, target
+//! triple and profile to the crate as `VAUGHAN_BUILD_*` environment variables,
+//! read by `vaughan::build_info()`.
+//!
+//! For reproducible builds the timestamp honours `SOURCE_DATE_EPOCH`.
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/index");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-env-changed=VAUGHAN_GIT_COMMIT");
+
+    // Packagers building from a tarball can provide the commit explicitly
+    let commit = std::env::var("VAUGHAN_GIT_COMMIT")
+        .ok()
+        .or_else(|| git(&["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"]).is_some();
+
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        });
+
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|f| f.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+
+    println!("cargo:rustc-env=VAUGHAN_BUILD_GIT_COMMIT={commit}");
+    println!("cargo:rustc-env=VAUGHAN_BUILD_GIT_DIRTY={dirty}");
+    println!("cargo:rustc-env=VAUGHAN_BUILD_TIMESTAMP={timestamp}");
+    println!("cargo:rustc-env=VAUGHAN_BUILD_FEATURES={}", features.join(","));
+    println!(
+        "cargo:rustc-env=VAUGHAN_BUILD_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+    println!(
+        "cargo:rustc-env=VAUGHAN_BUILD_PROFILE={}",
+        std::env::var("PROFILE").unwrap_or_default()
+    );
+    println!(
+        "cargo:rustc-env=VAUGHAN_BUILD_RUSTC={}",
+        std::env::var("RUSTC")
+            .ok()
+            .and_then(|rustc| Command::new(rustc).arg("--version").output().ok())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|version| version.trim().to_string())
+            .unwrap_or_default()
+    );
+}
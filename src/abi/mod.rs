@@ -0,0 +1,285 @@
+//! Calldata decoding and human-readable call previews
+//!
+//! Turns `TransactionRequest.input` into previews such as
+//! `approve(spender=0x…, amount=unlimited)` so both the GUI confirmation and
+//! the hardware wallet audit can show what a transaction actually calls.
+//!
+//! Functions are resolved from ABIs registered for the target contract
+//! (typically fetched with [`ExplorerClient::fetch_abi`]) and then from the
+//! built-in [`selectors`] table. Calldata that matches neither is left
+//! undecoded rather than guessed.
+//!
+//! [`ExplorerClient::fetch_abi`]: crate::blockchain::ExplorerClient::fetch_abi
+
+pub mod selectors;
+
+use alloy::dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy::json_abi::{Function, JsonAbi};
+use alloy::primitives::{Address, Selector, U256};
+use alloy::rpc::types::TransactionRequest;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Byte strings longer than this are shortened in previews
+const MAX_PREVIEW_BYTES: usize = 32;
+
+/// Where the function definition used for decoding came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiSource {
+    /// Built-in selector table
+    Builtin,
+    /// ABI registered for the target contract
+    Contract,
+}
+
+/// One decoded argument
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedParam {
+    /// Parameter name, `arg<N>` when the ABI leaves it unnamed
+    pub name: String,
+    /// Solidity type, e.g. `uint256`
+    pub ty: String,
+    pub value: DynSolValue,
+}
+
+impl DecodedParam {
+    /// Value formatted for display
+    pub fn display_value(&self) -> String {
+        format_value(&self.value)
+    }
+}
+
+/// A decoded contract call
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedCall {
+    pub selector: Selector,
+    pub function: String,
+    /// Canonical signature, e.g. `approve(address,uint256)`
+    pub signature: String,
+    pub params: Vec<DecodedParam>,
+    pub source: AbiSource,
+}
+
+impl DecodedCall {
+    /// Argument by name
+    pub fn param(&self, name: &str) -> Option<&DecodedParam> {
+        self.params.iter().find(|p| p.name == name)
+    }
+
+    /// One-line preview, e.g. `approve(spender=0x…, amount=unlimited)`
+    pub fn preview(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for DecodedCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args: Vec<String> = self
+            .params
+            .iter()
+            .map(|p| format!("{}={}", p.name, p.display_value()))
+            .collect();
+        write!(f, "{}({})", self.function, args.join(", "))
+    }
+}
+
+/// Decodes calldata against the built-in table and per-contract ABIs
+#[derive(Debug, Clone, Default)]
+pub struct CalldataDecoder {
+    contracts: HashMap<(u64, Address), HashMap<Selector, Function>>,
+}
+
+impl CalldataDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `abi` for calls to `contract` on `chain_id`
+    pub fn register_abi(&mut self, chain_id: u64, contract: Address, abi: &JsonAbi) {
+        let functions = abi.functions().map(|f| (f.selector(), f.clone())).collect();
+        self.contracts.insert((chain_id, contract), functions);
+    }
+
+    pub fn has_abi(&self, chain_id: u64, contract: Address) -> bool {
+        self.contracts.contains_key(&(chain_id, contract))
+    }
+
+    /// Decode a call to `contract`, preferring its registered ABI
+    pub fn decode_call(&self, chain_id: u64, contract: Option<Address>, input: &[u8]) -> Option<DecodedCall> {
+        let selector = Selector::try_from(input.get(..4)?).ok()?;
+        let registered = contract
+            .and_then(|c| self.contracts.get(&(chain_id, c)))
+            .and_then(|functions| functions.get(&selector));
+
+        registered
+            .and_then(|f| decode_with(f, input, AbiSource::Contract))
+            .or_else(|| decode_with(selectors::lookup(selector)?, input, AbiSource::Builtin))
+    }
+
+    /// Decode the calldata of a transaction request
+    pub fn decode_transaction(&self, tx: &TransactionRequest) -> Option<DecodedCall> {
+        let input = tx.input.input()?;
+        let contract = tx.to.and_then(|to| to.to().copied());
+        self.decode_call(tx.chain_id.unwrap_or_default(), contract, input)
+    }
+}
+
+/// Decode calldata using only the built-in selector table
+pub fn decode(input: &[u8]) -> Option<DecodedCall> {
+    CalldataDecoder::default().decode_call(0, None, input)
+}
+
+/// Decode a transaction's calldata using only the built-in selector table
+pub fn decode_transaction(tx: &TransactionRequest) -> Option<DecodedCall> {
+    CalldataDecoder::default().decode_transaction(tx)
+}
+
+/// Preview of a transaction's calldata, or a note when it cannot be decoded
+///
+/// Returns `None` for plain value transfers without calldata.
+pub fn describe_transaction(tx: &TransactionRequest) -> Option<String> {
+    let input = tx.input.input().filter(|i| !i.is_empty())?;
+    Some(match decode_transaction(tx) {
+        Some(call) => call.preview(),
+        None => format!("unknown call {}", format_bytes(input)),
+    })
+}
+
+fn decode_with(function: &Function, input: &[u8], source: AbiSource) -> Option<DecodedCall> {
+    let values = match function.abi_decode_input(&input[4..]) {
+        Ok(values) => values,
+        Err(e) => {
+            tracing::debug!("Calldata does not match {}: {}", function.signature(), e);
+            return None;
+        }
+    };
+    let params = function
+        .inputs
+        .iter()
+        .zip(values)
+        .enumerate()
+        .map(|(i, (param, value))| DecodedParam {
+            name: if param.name.is_empty() {
+                format!("arg{i}")
+            } else {
+                param.name.clone()
+            },
+            ty: param.selector_type().into_owned(),
+            value,
+        })
+        .collect();
+    Some(DecodedCall {
+        selector: function.selector(),
+        function: function.name.clone(),
+        signature: function.signature(),
+        params,
+        source,
+    })
+}
+
+/// Format a decoded value for display
+///
+/// `uint256` max is shown as `unlimited`, the conventional infinite approval.
+pub fn format_value(value: &DynSolValue) -> String {
+    if let Some(address) = value.as_address() {
+        return address.to_string();
+    }
+    if let Some((uint, bits)) = value.as_uint() {
+        return if bits == 256 && uint == U256::MAX {
+            "unlimited".to_string()
+        } else {
+            uint.to_string()
+        };
+    }
+    if let Some((int, _)) = value.as_int() {
+        return int.to_string();
+    }
+    if let Some(flag) = value.as_bool() {
+        return flag.to_string();
+    }
+    if let Some((word, size)) = value.as_fixed_bytes() {
+        return format!("0x{}", hex::encode(&word[..size.min(word.len())]));
+    }
+    if let Some(bytes) = value.as_bytes() {
+        return format_bytes(bytes);
+    }
+    if let Some(s) = value.as_str() {
+        return format!("{s:?}");
+    }
+    if let Some(items) = value.as_array().or_else(|| value.as_fixed_array()) {
+        return format!("[{}]", items.iter().map(format_value).collect::<Vec<_>>().join(", "));
+    }
+    if let Some(items) = value.as_tuple() {
+        return format!("({})", items.iter().map(format_value).collect::<Vec<_>>().join(", "));
+    }
+    format!("{value:?}")
+}
+
+fn format_bytes(bytes: &[u8]) -> String {
+    if bytes.len() > MAX_PREVIEW_BYTES {
+        format!(
+            "0x{}… ({} bytes)",
+            hex::encode(&bytes[..MAX_PREVIEW_BYTES]),
+            bytes.len()
+        )
+    } else {
+        format!("0x{}", hex::encode(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Bytes;
+
+    #[test]
+    fn test_unlimited_approval_preview() {
+        let spender = Address::repeat_byte(0x11);
+        let approve = selectors::lookup(Selector::from([0x09, 0x5e, 0xa7, 0xb3])).unwrap();
+        let input = approve
+            .abi_encode_input(&[DynSolValue::Address(spender), DynSolValue::Uint(U256::MAX, 256)])
+            .unwrap();
+
+        let call = decode(&input).unwrap();
+        assert_eq!(call.source, AbiSource::Builtin);
+        assert_eq!(call.signature, "approve(address,uint256)");
+        assert_eq!(call.preview(), format!("approve(spender={spender}, amount=unlimited)"));
+    }
+
+    #[test]
+    fn test_token_transfer_and_unknown_calldata() {
+        let to = Address::repeat_byte(0x22);
+        let input = crate::wallet::transaction::erc20::transfer_calldata(to, U256::from(1_500u64));
+        let tx = TransactionRequest::default()
+            .to(Address::repeat_byte(0x70))
+            .input(input.into());
+        assert_eq!(
+            describe_transaction(&tx).unwrap(),
+            format!("transfer(to={to}, amount=1500)")
+        );
+
+        let unknown = TransactionRequest::default().input(Bytes::from(vec![0xde, 0xad, 0xbe, 0xef, 0x00]).into());
+        assert!(decode_transaction(&unknown).is_none());
+        assert_eq!(describe_transaction(&unknown).unwrap(), "unknown call 0xdeadbeef00");
+        assert!(describe_transaction(&TransactionRequest::default()).is_none());
+    }
+
+    #[test]
+    fn test_registered_abi_takes_precedence() {
+        let contract = Address::repeat_byte(0x33);
+        let abi = JsonAbi::parse(["function transfer(address recipient, uint256 value)"]).unwrap();
+        let mut decoder = CalldataDecoder::new();
+        decoder.register_abi(1, contract, &abi);
+
+        let input = crate::wallet::transaction::erc20::transfer_calldata(Address::ZERO, U256::from(7u64));
+        let call = decoder.decode_call(1, Some(contract), &input).unwrap();
+        assert_eq!(call.source, AbiSource::Contract);
+        assert!(call.param("recipient").is_some());
+
+        // Other chains and contracts fall back to the built-in table
+        assert_eq!(
+            decoder.decode_call(56, Some(contract), &input).unwrap().source,
+            AbiSource::Builtin
+        );
+    }
+}
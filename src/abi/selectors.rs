@@ -0,0 +1,106 @@
+//! Built-in function selector database
+//!
+//! Human-readable signatures of functions users commonly sign: token
+//! standards, wrapped native tokens and the main DEX routers. Selectors are
+//! computed from the signatures when the table is first used, so the list
+//! never carries hand-copied 4-byte values.
+//!
+//! ERC-20 and ERC-721 share the selectors of `approve` and `transferFrom`;
+//! the ERC-20 parameter names are used for both.
+
+use alloy::json_abi::Function;
+use alloy::primitives::Selector;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Signatures with parameter names, grouped by standard
+pub const BUILTIN_SIGNATURES: &[&str] = &[
+    // ERC-20
+    "transfer(address to, uint256 amount)",
+    "approve(address spender, uint256 amount)",
+    "transferFrom(address from, address to, uint256 amount)",
+    "increaseAllowance(address spender, uint256 addedValue)",
+    "decreaseAllowance(address spender, uint256 subtractedValue)",
+    "permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s)",
+    // Wrapped native tokens (WETH, WPLS, ...)
+    "deposit()",
+    "withdraw(uint256 amount)",
+    // ERC-721
+    "safeTransferFrom(address from, address to, uint256 tokenId)",
+    "safeTransferFrom(address from, address to, uint256 tokenId, bytes data)",
+    "setApprovalForAll(address operator, bool approved)",
+    // ERC-1155
+    "safeTransferFrom(address from, address to, uint256 id, uint256 amount, bytes data)",
+    "safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] amounts, bytes data)",
+    // Uniswap V2 style routers (PulseX, PancakeSwap, SushiSwap, ...)
+    "swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+    "swapTokensForExactTokens(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline)",
+    "swapExactETHForTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+    "swapETHForExactTokens(uint256 amountOut, address[] path, address to, uint256 deadline)",
+    "swapExactTokensForETH(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+    "swapTokensForExactETH(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline)",
+    "swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+    "swapExactETHForTokensSupportingFeeOnTransferTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+    "swapExactTokensForETHSupportingFeeOnTransferTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+    "addLiquidity(address tokenA, address tokenB, uint256 amountADesired, uint256 amountBDesired, uint256 amountAMin, uint256 amountBMin, address to, uint256 deadline)",
+    "addLiquidityETH(address token, uint256 amountTokenDesired, uint256 amountTokenMin, uint256 amountETHMin, address to, uint256 deadline)",
+    "removeLiquidity(address tokenA, address tokenB, uint256 liquidity, uint256 amountAMin, uint256 amountBMin, address to, uint256 deadline)",
+    "removeLiquidityETH(address token, uint256 liquidity, uint256 amountTokenMin, uint256 amountETHMin, address to, uint256 deadline)",
+    // Uniswap V3 SwapRouter and Universal Router
+    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160) params)",
+    "exactOutputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160) params)",
+    "exactInput((bytes,address,uint256,uint256,uint256) params)",
+    "exactOutput((bytes,address,uint256,uint256,uint256) params)",
+    "multicall(bytes[] data)",
+    "multicall(uint256 deadline, bytes[] data)",
+    "execute(bytes commands, bytes[] inputs, uint256 deadline)",
+    // Disperse
+    "disperseEther(address[] recipients, uint256[] values)",
+    "disperseToken(address token, address[] recipients, uint256[] values)",
+];
+
+static BUILTIN: LazyLock<HashMap<Selector, Function>> = LazyLock::new(|| {
+    BUILTIN_SIGNATURES
+        .iter()
+        .filter_map(|signature| match Function::parse(signature) {
+            Ok(function) => Some((function.selector(), function)),
+            Err(e) => {
+                tracing::error!("Invalid built-in signature `{}`: {}", signature, e);
+                None
+            }
+        })
+        .collect()
+});
+
+/// Built-in function for `selector`, if known
+pub fn lookup(selector: Selector) -> Option<&'static Function> {
+    BUILTIN.get(&selector)
+}
+
+/// Number of distinct selectors in the built-in table
+pub fn len() -> usize {
+    BUILTIN.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_signatures_parse() {
+        assert_eq!(len(), BUILTIN_SIGNATURES.len());
+        // Well-known selectors
+        assert_eq!(
+            lookup(Selector::from([0xa9, 0x05, 0x9c, 0xbb])).unwrap().name,
+            "transfer"
+        );
+        assert_eq!(
+            lookup(Selector::from([0x09, 0x5e, 0xa7, 0xb3])).unwrap().name,
+            "approve"
+        );
+        assert_eq!(
+            lookup(Selector::from([0x38, 0xed, 0x17, 0x39])).unwrap().name,
+            "swapExactTokensForTokens"
+        );
+    }
+}
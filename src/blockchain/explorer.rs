@@ -0,0 +1,425 @@
+//! Etherscan-compatible explorer client
+//!
+//! One [`ExplorerClient`] talks to the explorer API of one network:
+//! Etherscan, BscScan, PolygonScan or PulseChain Scan (Blockscout, which
+//! mirrors the Etherscan query API). It answers whether a contract is
+//! verified, returns its source metadata and ABI, and pages account
+//! transaction history when nothing local covers an address.
+//!
+//! Requests are spaced out to stay inside the explorer's rate limit and
+//! successful responses are cached, so repeated lookups of the same contract
+//! while building a confirmation do not hit the network again. All requests
+//! go through the explorer egress consent.
+
+use super::explorer_apis::ApiTransaction;
+use crate::error::{NetworkError, Result};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient};
+use crate::network::NetworkConfig;
+use alloy::json_abi::JsonAbi;
+use alloy::primitives::Address;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Etherscan-family APIs allow one request every five seconds without a key
+const KEYLESS_INTERVAL: Duration = Duration::from_secs(5);
+/// Free API keys allow five requests per second
+const KEYED_INTERVAL: Duration = Duration::from_millis(200);
+/// Blockscout instances do not require keys and tolerate a few requests per second
+const BLOCKSCOUT_INTERVAL: Duration = Duration::from_millis(350);
+
+/// Verified source rarely changes once published
+const VERIFIED_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Unverified contracts may be verified at any time
+const UNVERIFIED_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+const HISTORY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// `ABI` field of `getsourcecode` for unverified contracts
+const NOT_VERIFIED: &str = "Contract source code not verified";
+
+/// Explorer API endpoint of one network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplorerApi {
+    pub name: String,
+    /// API root, e.g. `https://api.etherscan.io/api`
+    pub api_url: String,
+    pub api_key: Option<String>,
+    /// Minimum time between two requests
+    pub min_interval: Duration,
+}
+
+impl ExplorerApi {
+    /// Endpoint without an API key, limited to the keyless request rate
+    pub fn new(name: impl Into<String>, api_url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            api_url: api_url.into(),
+            api_key: None,
+            min_interval: KEYLESS_INTERVAL,
+        }
+    }
+
+    /// Use `api_key`, raising the request rate to the free-tier limit
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self.min_interval = self.min_interval.min(KEYED_INTERVAL);
+        self
+    }
+
+    /// Explorer of a built-in network
+    ///
+    /// API keys are read from `ETHERSCAN_API_KEY`, `BSCSCAN_API_KEY` and
+    /// `POLYGONSCAN_API_KEY`. Custom networks have no known explorer API.
+    pub fn for_network(network: &NetworkConfig) -> Option<Self> {
+        let (name, api_url, key_var) = match network.chain_id {
+            1 => ("Etherscan", "https://api.etherscan.io/api", Some("ETHERSCAN_API_KEY")),
+            56 => ("BscScan", "https://api.bscscan.com/api", Some("BSCSCAN_API_KEY")),
+            137 => (
+                "PolygonScan",
+                "https://api.polygonscan.com/api",
+                Some("POLYGONSCAN_API_KEY"),
+            ),
+            369 => ("PulseChain Scan", "https://scan.pulsechain.com/api", None),
+            943 => (
+                "PulseChain Testnet Scan",
+                "https://scan.v4.testnet.pulsechain.com/api",
+                None,
+            ),
+            _ => return None,
+        };
+        let api = Self::new(name, api_url);
+        match key_var
+            .and_then(|var| std::env::var(var).ok())
+            .filter(|k| !k.is_empty())
+        {
+            Some(api_key) => Some(api.with_api_key(api_key)),
+            None if key_var.is_none() => Some(Self {
+                min_interval: BLOCKSCOUT_INTERVAL,
+                ..api
+            }),
+            None => Some(api),
+        }
+    }
+}
+
+/// Verified source metadata of a contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractSource {
+    pub contract_name: String,
+    pub compiler_version: String,
+    pub source_code: String,
+    /// ABI JSON as published with the source
+    pub abi: String,
+    /// Implementation address when the explorer recognises a proxy
+    pub implementation: Option<Address>,
+}
+
+impl ContractSource {
+    pub fn parse_abi(&self) -> Result<JsonAbi> {
+        serde_json::from_str(&self.abi).map_err(|e| {
+            NetworkError::NetworkError {
+                message: format!("Explorer returned an invalid ABI: {e}"),
+            }
+            .into()
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExplorerResponse {
+    status: String,
+    message: String,
+    result: serde_json::Value,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    stored: Instant,
+    ttl: Duration,
+    result: serde_json::Value,
+}
+
+/// Rate-limited, caching client for one explorer API
+#[derive(Debug)]
+pub struct ExplorerClient {
+    api: ExplorerApi,
+    client: HttpClient,
+    /// Earliest time the next request may be sent
+    next_request: tokio::sync::Mutex<Instant>,
+    cache: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ExplorerClient {
+    pub fn new(api: ExplorerApi) -> Self {
+        Self {
+            api,
+            client: shared_client(),
+            next_request: tokio::sync::Mutex::new(Instant::now()),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Client for a network's explorer, if it has a known one
+    pub fn for_network(network: &NetworkConfig) -> Option<Self> {
+        ExplorerApi::for_network(network).map(Self::new)
+    }
+
+    pub fn api(&self) -> &ExplorerApi {
+        &self.api
+    }
+
+    /// Verified source of `contract`, `None` when it is not verified
+    pub async fn contract_source(&self, contract: Address) -> Result<Option<ContractSource>> {
+        let query = format!("module=contract&action=getsourcecode&address={contract:#x}");
+        let result = self
+            .query(&query, "look up contract source", |result| {
+                if parse_source(result).is_some() {
+                    VERIFIED_CACHE_TTL
+                } else {
+                    UNVERIFIED_CACHE_TTL
+                }
+            })
+            .await?;
+        Ok(parse_source(&result))
+    }
+
+    pub async fn is_verified(&self, contract: Address) -> Result<bool> {
+        Ok(self.contract_source(contract).await?.is_some())
+    }
+
+    /// ABI of a verified contract
+    pub async fn fetch_abi(&self, contract: Address) -> Result<JsonAbi> {
+        match self.contract_source(contract).await? {
+            Some(source) => source.parse_abi(),
+            None => Err(NetworkError::NetworkError {
+                message: format!("Contract {contract} is not verified on {}", self.api.name),
+            }
+            .into()),
+        }
+    }
+
+    /// Most recent transactions of `account`, newest first
+    pub async fn transactions(&self, account: Address, limit: usize) -> Result<Vec<ApiTransaction>> {
+        let query = format!(
+            "module=account&action=txlist&address={account:#x}&startblock=0&endblock=99999999&sort=desc&page=1&offset={limit}"
+        );
+        let result = self
+            .query(&query, "fetch transaction history", |_| HISTORY_CACHE_TTL)
+            .await?;
+        Ok(result
+            .as_array()
+            .map(|txs| txs.iter().filter_map(parse_transaction).take(limit).collect())
+            .unwrap_or_default())
+    }
+
+    /// Drop all cached responses
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+
+    /// Run `query` and return the response's `result`, from cache if fresh
+    async fn query(
+        &self,
+        query: &str,
+        purpose: &str,
+        ttl: impl Fn(&serde_json::Value) -> Duration,
+    ) -> Result<serde_json::Value> {
+        if let Some(result) = self.cached(query) {
+            return Ok(result);
+        }
+
+        let url = format!("{}?{query}", self.api.api_url);
+        egress().check(EgressCategory::Explorer, &url, purpose)?;
+        self.wait_turn().await;
+
+        let mut request = self.client.get(&url).timeout(REQUEST_TIMEOUT);
+        if let Some(api_key) = &self.api.api_key {
+            request = request.query(&[("apikey", api_key)]);
+        }
+        let response: ExplorerResponse = request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| NetworkError::NetworkError {
+                message: format!("{} request failed: {e}", self.api.name),
+            })?
+            .json()
+            .await
+            .map_err(|e| NetworkError::NetworkError {
+                message: format!("Invalid {} response: {e}", self.api.name),
+            })?;
+
+        let result = into_result(response).map_err(|message| NetworkError::NetworkError {
+            message: format!("{} error: {message}", self.api.name),
+        })?;
+        let entry = CacheEntry {
+            stored: Instant::now(),
+            ttl: ttl(&result),
+            result: result.clone(),
+        };
+        self.cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(query.to_string(), entry);
+        Ok(result)
+    }
+
+    fn cached(&self, query: &str) -> Option<serde_json::Value> {
+        let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        match cache.get(query) {
+            Some(entry) if entry.stored.elapsed() < entry.ttl => Some(entry.result.clone()),
+            Some(_) => {
+                cache.remove(query);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Wait until the rate limit allows another request
+    async fn wait_turn(&self) {
+        let mut next = self.next_request.lock().await;
+        let now = Instant::now();
+        if *next > now {
+            tokio::time::sleep(*next - now).await;
+        }
+        *next = Instant::now() + self.api.min_interval;
+    }
+}
+
+/// `result` of a successful response
+///
+/// Etherscan reports an empty history as status `0`, which is not an error.
+fn into_result(response: ExplorerResponse) -> std::result::Result<serde_json::Value, String> {
+    if response.status == "1" {
+        return Ok(response.result);
+    }
+    if response.message.starts_with("No transactions found") || response.message.starts_with("No records found") {
+        return Ok(serde_json::Value::Array(Vec::new()));
+    }
+    match response.result.as_str() {
+        Some(detail) if !detail.is_empty() => Err(format!("{} ({detail})", response.message)),
+        _ => Err(response.message),
+    }
+}
+
+fn parse_source(result: &serde_json::Value) -> Option<ContractSource> {
+    let entry = result.as_array()?.first()?;
+    let field = |name: &str| entry[name].as_str().unwrap_or_default().to_string();
+    let abi = field("ABI");
+    if abi.is_empty() || abi == NOT_VERIFIED {
+        return None;
+    }
+    Some(ContractSource {
+        contract_name: field("ContractName"),
+        compiler_version: field("CompilerVersion"),
+        source_code: field("SourceCode"),
+        abi,
+        implementation: entry["Implementation"]
+            .as_str()
+            .and_then(|a| a.parse::<Address>().ok())
+            .filter(|a| !a.is_zero()),
+    })
+}
+
+fn parse_transaction(tx: &serde_json::Value) -> Option<ApiTransaction> {
+    let field = |name: &str| tx[name].as_str();
+    let hash = field("hash").filter(|h| !h.is_empty())?;
+    let failed = field("isError") == Some("1") || field("txreceipt_status") == Some("0");
+    Some(ApiTransaction {
+        hash: hash.to_string(),
+        from: field("from").unwrap_or_default().to_string(),
+        to: field("to").unwrap_or_default().to_string(),
+        value: field("value").unwrap_or("0").to_string(),
+        timestamp: field("timeStamp").and_then(|s| s.parse().ok()).unwrap_or(0),
+        block_number: field("blockNumber").and_then(|s| s.parse().ok()).unwrap_or(0),
+        gas_used: field("gasUsed").and_then(|s| s.parse().ok()),
+        gas_price: field("gasPrice").map(str::to_string),
+        status: if failed {
+            "Failed".to_string()
+        } else {
+            "Success".to_string()
+        },
+        method_name: field("functionName")
+            .filter(|f| !f.is_empty())
+            .or_else(|| field("methodId"))
+            .map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_endpoints_per_network() {
+        let eth = ExplorerApi::for_network(&NetworkConfig::ethereum_mainnet()).unwrap();
+        assert_eq!(eth.api_url, "https://api.etherscan.io/api");
+
+        let pulse = ExplorerApi::for_network(&NetworkConfig::pulsechain()).unwrap();
+        assert_eq!(pulse.name, "PulseChain Scan");
+        assert!(pulse.api_key.is_none());
+        assert_eq!(pulse.min_interval, BLOCKSCOUT_INTERVAL);
+
+        let keyed = ExplorerApi::new("BscScan", "https://api.bscscan.com/api").with_api_key("k");
+        assert_eq!(keyed.min_interval, KEYED_INTERVAL);
+
+        let mut custom = NetworkConfig::pulsechain();
+        custom.chain_id = 31337;
+        assert!(ExplorerApi::for_network(&custom).is_none());
+    }
+
+    #[test]
+    fn test_parse_contract_source() {
+        let verified = json!([{
+            "SourceCode": "contract Staking {}",
+            "ABI": r#"[{"type":"function","name":"stake","inputs":[{"name":"amount","type":"uint256"}],"outputs":[],"stateMutability":"nonpayable"}]"#,
+            "ContractName": "Staking",
+            "CompilerVersion": "v0.8.24+commit.e11b9ed9",
+            "Proxy": "0",
+            "Implementation": ""
+        }]);
+        let source = parse_source(&verified).unwrap();
+        assert_eq!(source.contract_name, "Staking");
+        assert!(source.implementation.is_none());
+        assert_eq!(
+            source.parse_abi().unwrap().function("stake").unwrap()[0].inputs[0].name,
+            "amount"
+        );
+
+        let unverified = json!([{ "SourceCode": "", "ABI": NOT_VERIFIED, "ContractName": "" }]);
+        assert!(parse_source(&unverified).is_none());
+    }
+
+    #[test]
+    fn test_history_responses() {
+        let empty = ExplorerResponse {
+            status: "0".into(),
+            message: "No transactions found".into(),
+            result: json!([]),
+        };
+        assert_eq!(into_result(empty).unwrap(), json!([]));
+
+        let rate_limited = ExplorerResponse {
+            status: "0".into(),
+            message: "NOTOK".into(),
+            result: json!("Max rate limit reached"),
+        };
+        assert_eq!(into_result(rate_limited).unwrap_err(), "NOTOK (Max rate limit reached)");
+
+        let tx = parse_transaction(&json!({
+            "hash": "0xabc", "from": "0x1", "to": "0x2", "value": "1000",
+            "timeStamp": "1700000000", "blockNumber": "42", "gasUsed": "21000",
+            "isError": "1", "methodId": "0x", "functionName": ""
+        }))
+        .unwrap();
+        assert_eq!(tx.block_number, 42);
+        assert_eq!(tx.status, "Failed");
+        assert_eq!(tx.method_name.as_deref(), Some("0x"));
+        assert!(parse_transaction(&json!({ "hash": "" })).is_none());
+    }
+}
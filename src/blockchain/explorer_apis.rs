@@ -3,6 +3,8 @@
 //! This module provides a unified interface to multiple block explorer APIs
 //! with automatic fallbacks, rate limiting, and configuration management.
 
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient};
 use crate::network::NetworkId;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -145,23 +147,16 @@ pub struct ExplorerApiManager {
     config: ExplorerApiConfig,
     endpoints: HashMap<NetworkId, Vec<ExplorerEndpoint>>,
     rate_limiters: HashMap<String, RateLimiter>,
-    client: reqwest::Client,
+    client: HttpClient,
 }
 
 impl Clone for ExplorerApiManager {
     fn clone(&self) -> Self {
-        // HTTP client builder should never fail with these basic settings
-        let client = reqwest::Client::builder()
-            .timeout(self.config.request_timeout)
-            .user_agent("Vaughan-Wallet/1.0")
-            .build()
-            .unwrap_or_else(|_| reqwest::Client::new());
-
         let mut manager = Self {
             config: self.config.clone(),
             endpoints: self.endpoints.clone(),
             rate_limiters: HashMap::new(), // Create new rate limiters
-            client,
+            client: self.client.clone(),
         };
 
         // Initialize rate limiters
@@ -178,18 +173,11 @@ impl Clone for ExplorerApiManager {
 impl ExplorerApiManager {
     /// Create a new API manager with configuration
     pub fn new(config: ExplorerApiConfig) -> Self {
-        // HTTP client builder should never fail with these basic settings
-        let client = reqwest::Client::builder()
-            .timeout(config.request_timeout)
-            .user_agent("Vaughan-Wallet/1.0")
-            .build()
-            .unwrap_or_else(|_| reqwest::Client::new());
-
         let mut manager = Self {
             config,
             endpoints: HashMap::new(),
             rate_limiters: HashMap::new(),
-            client,
+            client: shared_client(),
         };
 
         manager.initialize_endpoints();
@@ -420,10 +408,14 @@ impl ExplorerApiManager {
     /// Make the actual HTTP request
     async fn make_api_request(&self, url: &str) -> Result<Vec<ApiTransaction>, String> {
         tracing::debug!("🌐 Making API request to: {}", url);
+        egress()
+            .check(EgressCategory::Explorer, url, "fetch transaction history")
+            .map_err(|e| e.to_string())?;
 
         let response = self
             .client
             .get(url)
+            .timeout(self.config.request_timeout)
             .send()
             .await
             .map_err(|e| format!("HTTP request failed: {e}"))?;
@@ -534,11 +526,13 @@ impl ExplorerApiManager {
         let url = format!("https://deep-index.moralis.io/api/v2.2/erc20/{token_address}/price?chain={chain}");
 
         tracing::debug!("🪙 Fetching price from Moralis: {}", url);
+        egress().check(EgressCategory::PriceData, &url, "fetch token price")?;
 
         let response = self
             .client
             .get(&url)
             .header("X-API-Key", api_key)
+            .timeout(self.config.request_timeout)
             .send()
             .await?
             .json::<TokenPrice>()
@@ -3,6 +3,10 @@
 //! This module provides unified access to blockchain data through
 //! various sources including RPC nodes and block explorer APIs.
 
+pub mod explorer;
 pub mod explorer_apis;
+pub mod probe;
 
+pub use explorer::{ContractSource, ExplorerApi, ExplorerClient};
 pub use explorer_apis::{load_config, save_config, ApiTransaction, ExplorerApiConfig, ExplorerApiManager};
+pub use probe::{BytecodeHints, ContractKind, ContractProber, ContractProfile};
@@ -0,0 +1,424 @@
+//! Contract classification for unknown addresses
+//!
+//! Recipient info, NFT detection and risk screening all need to know what an
+//! address is before the user interacts with it. [`ContractProber`] answers
+//! that with three signals, cheapest first:
+//!
+//! - **Bytecode**: no code means an externally owned account; EIP-1167 clones
+//!   and Safe proxies are recognised by their fixed bytecode
+//! - **Selector sampling**: `PUSH4` operands in the function dispatcher show
+//!   which functions a contract implements (ERC-20, Safe, DEX routers)
+//! - **ERC-165**: `supportsInterface` for ERC-721 and ERC-1155, trusted only
+//!   when the contract also answers the ERC-165 self-check
+//!
+//! Proxies are followed one level, through the EIP-1967 implementation slot
+//! or the clone target, so a proxied token is classified as a token.
+
+use crate::error::{NetworkError, Result};
+use crate::tokens::nft::{NftStandard, ERC1155_INTERFACE_ID, ERC721_INTERFACE_ID};
+use alloy::primitives::{b256, keccak256, Address, FixedBytes, Selector, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// ERC-165 interface ID of `supportsInterface` itself
+pub const ERC165_INTERFACE_ID: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+
+/// EIP-1967 implementation slot: `keccak256("eip1967.proxy.implementation") - 1`
+pub const EIP1967_IMPLEMENTATION_SLOT: B256 = b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// EIP-1167 minimal proxy code before and after the 20-byte target
+const EIP1167_PREFIX: &[u8] = &[0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+const EIP1167_SUFFIX: &[u8] = &[
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+
+/// `PUSH4` opcode
+const PUSH4: u8 = 0x63;
+
+const ERC20_SIGNATURES: &[&str] = &[
+    "totalSupply()",
+    "balanceOf(address)",
+    "transfer(address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "approve(address,uint256)",
+    "allowance(address,address)",
+];
+const SAFE_SIGNATURES: &[&str] = &[
+    "execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)",
+    "getThreshold()",
+    "getOwners()",
+];
+/// Any one of these marks a DEX router
+const ROUTER_SIGNATURES: &[&str] = &[
+    "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+    "swapExactETHForTokens(uint256,address[],address,uint256)",
+    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "execute(bytes,bytes[],uint256)",
+];
+/// `masterCopy()`, answered by Safe proxies in their fallback
+const SAFE_PROXY_SIGNATURE: &str = "masterCopy()";
+
+sol! {
+    interface IERC165Probe {
+        function supportsInterface(bytes4 interfaceId) external view returns (bool);
+    }
+}
+
+/// What an address was found to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractKind {
+    Erc20,
+    Erc721,
+    Erc1155,
+    /// Delegates calls to another contract
+    Proxy,
+    /// Safe multisig wallet
+    Safe,
+    /// DEX router or aggregator
+    Router,
+}
+
+impl fmt::Display for ContractKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Erc20 => "ERC-20 token",
+            Self::Erc721 => "ERC-721 collection",
+            Self::Erc1155 => "ERC-1155 collection",
+            Self::Proxy => "proxy",
+            Self::Safe => "Safe multisig",
+            Self::Router => "DEX router",
+        })
+    }
+}
+
+/// Classification of one address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractProfile {
+    pub address: Address,
+    /// Whether the address has code; `false` for externally owned accounts
+    pub has_code: bool,
+    pub kinds: BTreeSet<ContractKind>,
+    /// Implementation behind a proxy, when it could be resolved
+    pub implementation: Option<Address>,
+    /// Whether the contract passed the ERC-165 self-check
+    pub erc165: bool,
+}
+
+impl ContractProfile {
+    fn account(address: Address) -> Self {
+        Self {
+            address,
+            has_code: false,
+            kinds: BTreeSet::new(),
+            implementation: None,
+            erc165: false,
+        }
+    }
+
+    pub fn is_contract(&self) -> bool {
+        self.has_code
+    }
+
+    pub fn is(&self, kind: ContractKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// NFT standard, ERC-721 taking precedence
+    pub fn nft_standard(&self) -> Option<NftStandard> {
+        if self.is(ContractKind::Erc721) {
+            Some(NftStandard::Erc721)
+        } else if self.is(ContractKind::Erc1155) {
+            Some(NftStandard::Erc1155)
+        } else {
+            None
+        }
+    }
+
+    /// Short description, e.g. `ERC-20 token, proxy` or `account`
+    pub fn summary(&self) -> String {
+        if !self.has_code {
+            return "account".to_string();
+        }
+        if self.kinds.is_empty() {
+            return "unknown contract".to_string();
+        }
+        self.kinds
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Warnings worth showing when this address is the recipient of a
+    /// transfer of `token`
+    pub fn recipient_warnings(&self, token: Option<Address>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if token == Some(self.address) {
+            warnings.push("Recipient is the token contract itself; tokens sent there are usually lost".to_string());
+        } else if self.is(ContractKind::Erc20) || self.nft_standard().is_some() {
+            warnings.push(format!(
+                "Recipient is a {}, not a wallet; it may not be able to move what it receives",
+                self.summary()
+            ));
+        } else if self.is(ContractKind::Router) {
+            warnings.push("Recipient is a DEX router; direct transfers to routers are not recoverable".to_string());
+        }
+        warnings
+    }
+}
+
+/// Signals read from bytecode alone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BytecodeHints {
+    /// Selectors pushed by the dispatcher
+    pub selectors: HashSet<Selector>,
+    /// Target of an EIP-1167 clone
+    pub clone_target: Option<Address>,
+}
+
+impl BytecodeHints {
+    pub fn parse(code: &[u8]) -> Self {
+        Self {
+            selectors: sample_selectors(code),
+            clone_target: eip1167_target(code),
+        }
+    }
+
+    fn has_all(&self, signatures: &[&str]) -> bool {
+        signatures.iter().all(|s| self.selectors.contains(&selector(s)))
+    }
+
+    fn has_any(&self, signatures: &[&str]) -> bool {
+        signatures.iter().any(|s| self.selectors.contains(&selector(s)))
+    }
+
+    /// Kinds implied by the sampled selectors
+    pub fn kinds(&self) -> BTreeSet<ContractKind> {
+        let mut kinds = BTreeSet::new();
+        if self.has_all(ERC20_SIGNATURES) {
+            kinds.insert(ContractKind::Erc20);
+        }
+        if self.has_all(SAFE_SIGNATURES) {
+            kinds.insert(ContractKind::Safe);
+        }
+        if self.has_any(ROUTER_SIGNATURES) {
+            kinds.insert(ContractKind::Router);
+        }
+        if self.is_proxy() {
+            kinds.insert(ContractKind::Proxy);
+        }
+        kinds
+    }
+
+    fn is_proxy(&self) -> bool {
+        self.clone_target.is_some() || self.is_safe_proxy()
+    }
+
+    fn is_safe_proxy(&self) -> bool {
+        self.selectors.contains(&selector(SAFE_PROXY_SIGNATURE)) && !self.has_all(SAFE_SIGNATURES)
+    }
+}
+
+fn selector(signature: &str) -> Selector {
+    Selector::from_slice(&keccak256(signature.as_bytes())[..4])
+}
+
+/// Operands of every `PUSH4`, skipping the data of other pushes
+fn sample_selectors(code: &[u8]) -> HashSet<Selector> {
+    let mut selectors = HashSet::new();
+    let mut pc = 0;
+    while pc < code.len() {
+        let op = code[pc];
+        if op == PUSH4 {
+            if let Some(operand) = code.get(pc + 1..pc + 5) {
+                selectors.insert(Selector::from_slice(operand));
+            }
+        }
+        // PUSH1..PUSH32 carry 1..32 bytes of data
+        let data = if (0x60..=0x7f).contains(&op) {
+            usize::from(op - 0x5f)
+        } else {
+            0
+        };
+        pc += 1 + data;
+    }
+    selectors
+}
+
+fn eip1167_target(code: &[u8]) -> Option<Address> {
+    let target = code.strip_prefix(EIP1167_PREFIX)?.strip_suffix(EIP1167_SUFFIX)?;
+    (target.len() == 20).then(|| Address::from_slice(target))
+}
+
+/// Classifies addresses through a provider
+pub struct ContractProber<P> {
+    provider: P,
+}
+
+impl<P: Provider> ContractProber<P> {
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    /// Classify `address`
+    pub async fn probe(&self, address: Address) -> Result<ContractProfile> {
+        let code = self.code(address).await?;
+        if code.is_empty() {
+            return Ok(ContractProfile::account(address));
+        }
+
+        let hints = BytecodeHints::parse(&code);
+        let mut kinds = hints.kinds();
+        let mut implementation = hints.clone_target;
+        if implementation.is_none() {
+            implementation = self.eip1967_implementation(address).await?;
+            if hints.is_safe_proxy() {
+                // Safe proxies keep the singleton in slot 0
+                implementation = implementation.or(self.storage_address(address, U256::ZERO).await?);
+            }
+        }
+
+        if let Some(target) = implementation {
+            kinds.insert(ContractKind::Proxy);
+            let target_code = self.code(target).await?;
+            kinds.extend(BytecodeHints::parse(&target_code).kinds());
+        }
+
+        let erc165 = self.supports_interface(address, ERC165_INTERFACE_ID).await
+            && !self.supports_interface(address, [0xff; 4]).await;
+        if erc165 {
+            if self.supports_interface(address, ERC721_INTERFACE_ID).await {
+                kinds.insert(ContractKind::Erc721);
+            }
+            if self.supports_interface(address, ERC1155_INTERFACE_ID).await {
+                kinds.insert(ContractKind::Erc1155);
+            }
+        }
+
+        let profile = ContractProfile {
+            address,
+            has_code: true,
+            kinds,
+            implementation,
+            erc165,
+        };
+        tracing::debug!("Probed {}: {}", address, profile.summary());
+        Ok(profile)
+    }
+
+    /// Whether `contract` reports support for an ERC-165 interface
+    ///
+    /// Contracts without ERC-165 (or that revert) are treated as not supporting it.
+    pub async fn supports_interface(&self, contract: Address, interface_id: [u8; 4]) -> bool {
+        let call = IERC165Probe::supportsInterfaceCall {
+            interfaceId: FixedBytes(interface_id),
+        };
+        let request = TransactionRequest::default()
+            .to(contract)
+            .input(call.abi_encode().into());
+        match self.provider.call(request).await {
+            Ok(raw) => IERC165Probe::supportsInterfaceCall::abi_decode_returns(&raw).unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    async fn code(&self, address: Address) -> Result<alloy::primitives::Bytes> {
+        self.provider.get_code_at(address).await.map_err(|e| {
+            NetworkError::RpcError {
+                message: format!("Failed to fetch code at {address}: {e}"),
+            }
+            .into()
+        })
+    }
+
+    async fn eip1967_implementation(&self, address: Address) -> Result<Option<Address>> {
+        self.storage_address(address, U256::from_be_bytes(EIP1967_IMPLEMENTATION_SLOT.0))
+            .await
+    }
+
+    async fn storage_address(&self, address: Address, slot: U256) -> Result<Option<Address>> {
+        let value = self
+            .provider
+            .get_storage_at(address, slot)
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to read storage of {address}: {e}"),
+            })?;
+        let word = B256::from(value.to_be_bytes::<32>());
+        // An address slot holds 12 zero bytes followed by the address
+        let is_address = word[..12].iter().all(|b| *b == 0);
+        let target = Address::from_word(word);
+        Ok((is_address && target != Address::ZERO).then_some(target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dispatcher-like bytecode: `PUSH4 <selector> EQ` for each signature
+    fn dispatcher(signatures: &[&str]) -> Vec<u8> {
+        let mut code = vec![0x60, 0x80, 0x60, 0x40, 0x52];
+        for signature in signatures {
+            code.push(PUSH4);
+            code.extend_from_slice(selector(signature).as_slice());
+            code.push(0x14);
+        }
+        code
+    }
+
+    #[test]
+    fn test_selector_sampling_classifies_tokens_and_safes() {
+        let token = BytecodeHints::parse(&dispatcher(ERC20_SIGNATURES));
+        assert_eq!(token.kinds(), BTreeSet::from([ContractKind::Erc20]));
+
+        // A partial ERC-20 surface is not enough
+        assert!(BytecodeHints::parse(&dispatcher(&ERC20_SIGNATURES[..3]))
+            .kinds()
+            .is_empty());
+
+        let safe = BytecodeHints::parse(&dispatcher(SAFE_SIGNATURES));
+        assert_eq!(safe.kinds(), BTreeSet::from([ContractKind::Safe]));
+
+        let safe_proxy = BytecodeHints::parse(&dispatcher(&[SAFE_PROXY_SIGNATURE]));
+        assert_eq!(safe_proxy.kinds(), BTreeSet::from([ContractKind::Proxy]));
+
+        // Selector bytes inside PUSH32 data are not sampled
+        let mut hidden = vec![0x7f];
+        hidden.extend_from_slice(&[0u8; 28]);
+        hidden.extend_from_slice(selector("getThreshold()").as_slice());
+        assert!(BytecodeHints::parse(&hidden).selectors.is_empty());
+    }
+
+    #[test]
+    fn test_minimal_proxy_target() {
+        let target = Address::repeat_byte(0x42);
+        let mut code = EIP1167_PREFIX.to_vec();
+        code.extend_from_slice(target.as_slice());
+        code.extend_from_slice(EIP1167_SUFFIX);
+
+        let hints = BytecodeHints::parse(&code);
+        assert_eq!(hints.clone_target, Some(target));
+        assert!(hints.kinds().contains(&ContractKind::Proxy));
+        assert_eq!(BytecodeHints::parse(&code[..code.len() - 1]).clone_target, None);
+    }
+
+    #[test]
+    fn test_recipient_warnings() {
+        let token = Address::repeat_byte(0x11);
+        let mut profile = ContractProfile::account(token);
+        assert_eq!(profile.summary(), "account");
+        assert!(profile.recipient_warnings(None).is_empty());
+
+        profile.has_code = true;
+        profile.kinds.insert(ContractKind::Erc20);
+        assert!(profile.recipient_warnings(Some(token))[0].contains("token contract itself"));
+        assert!(profile.recipient_warnings(None)[0].contains("ERC-20 token"));
+    }
+}
@@ -10,6 +10,12 @@ use std::path::PathBuf;
 
 // Configuration submodules
 pub mod api_config;
+pub mod settings;
+
+pub use settings::{
+    init_settings_store, settings_store, FieldError, NetworkSettings, PricingSettings, SecurityPolicySettings,
+    SectionWatch, Settings, SettingsEvent, SettingsSection, SettingsStore, TelemetrySettings,
+};
 
 /// Main configuration manager
 #[derive(Debug)]
@@ -0,0 +1,873 @@
+//! Typed, validated wallet settings with live reload
+//!
+//! `settings.json` in the config directory holds one section per subsystem:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "network": { "default_network": "ethereum", "request_timeout_secs": 10, "degraded_cooldown_secs": 60 },
+//!   "pricing": { "enabled": true, "currency": "USD", "cache_size": 100, "cache_ttl_secs": 300 },
+//!   "security": { "auto_lock_minutes": 5, "lock_warning_secs": 30, "keychain_backend": "os" },
+//!   "telemetry": { "metrics_enabled": false, "detailed": false, "metrics_port": 9464 }
+//! }
+//! ```
+//!
+//! Missing fields take their defaults; unknown fields and out-of-range values
+//! are rejected with the dotted path of the offending field, e.g.
+//! `network.request_timeout_secs: must be between 1 and 300`.
+//!
+//! [`SettingsStore`] keeps the current settings and reloads them when the file
+//! changes. A reload that fails validation is rejected as a whole and the
+//! previous settings stay in effect. Subsystems subscribe to their own
+//! section with [`SettingsStore::subscribe_section`] and are only woken when
+//! that section changes; [`SettingsStore::events`] reports every reload.
+//!
+//! The process-wide store is [`settings_store`]; [`init_settings_store`]
+//! opens it at startup and starts watching the file.
+
+use crate::error::{ConfigurationError, Result, VaughanError};
+use crate::security::{KeychainBackend, OsConfirmationPolicy, SessionConfig, UnlockPolicy};
+use crate::tokens::fx::FiatCurrency;
+use alloy::primitives::U256;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Once, OnceLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{broadcast, watch};
+
+/// Settings file name inside the config directory
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Current settings schema version
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// How often [`init_settings_store`] checks the settings file for edits
+pub const SETTINGS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A setting that failed to parse or validate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Dotted path of the field, e.g. `pricing.cache_ttl_secs`
+    pub path: String,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+fn validation_error(errors: &[FieldError]) -> VaughanError {
+    let reason = errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ");
+    VaughanError::Configuration(ConfigurationError::ValidationFailed { reason })
+}
+
+fn parse_error(message: String) -> VaughanError {
+    VaughanError::Configuration(ConfigurationError::ParseError { message })
+}
+
+/// A settings section owned by one subsystem
+pub trait SettingsSection: Clone + PartialEq + Default + Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Key of the section in the settings file
+    const KEY: &'static str;
+
+    /// This section of `settings`
+    fn of(settings: &Settings) -> &Self;
+
+    /// Push an error for every invalid field; paths are relative to the section
+    fn validate(&self, errors: &mut Vec<FieldError>);
+}
+
+fn check_range<T: PartialOrd + fmt::Display>(errors: &mut Vec<FieldError>, field: &str, value: T, min: T, max: T) {
+    if value < min || value > max {
+        errors.push(FieldError::new(field, format!("must be between {min} and {max}")));
+    }
+}
+
+/// RPC connection settings, read by the network layer
+///
+/// `request_timeout_secs` and `degraded_cooldown_secs` feed
+/// [`crate::network::failover::FailoverProvider::with_settings`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkSettings {
+    /// Network selected at startup
+    pub default_network: String,
+    /// Per-request RPC timeout before failing over to the next endpoint
+    pub request_timeout_secs: u64,
+    /// How long a failing endpoint is skipped
+    pub degraded_cooldown_secs: u64,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            default_network: "ethereum".to_string(),
+            request_timeout_secs: 10,
+            degraded_cooldown_secs: 60,
+        }
+    }
+}
+
+impl NetworkSettings {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
+    pub fn degraded_cooldown(&self) -> Duration {
+        Duration::from_secs(self.degraded_cooldown_secs)
+    }
+}
+
+impl SettingsSection for NetworkSettings {
+    const KEY: &'static str = "network";
+
+    fn of(settings: &Settings) -> &Self {
+        &settings.network
+    }
+
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if self.default_network.trim().is_empty() {
+            errors.push(FieldError::new("default_network", "must not be empty"));
+        }
+        check_range(errors, "request_timeout_secs", self.request_timeout_secs, 1, 300);
+        check_range(errors, "degraded_cooldown_secs", self.degraded_cooldown_secs, 0, 3600);
+    }
+}
+
+/// Price feed settings, read by [`crate::controllers::PriceController::from_settings`],
+/// [`crate::tokens::oracle::MultiSourcePriceOracle::with_default_providers`] and
+/// [`crate::tokens::oracle::MultiSourcePriceOracle::follow_settings`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PricingSettings {
+    /// Whether fiat prices are fetched at all
+    pub enabled: bool,
+    /// ISO 4217 code values are displayed in, one of [`FiatCurrency::ALL`]
+    pub currency: String,
+    /// Number of token prices kept in memory
+    pub cache_size: usize,
+    /// How long a fetched price stays fresh
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for PricingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            currency: "USD".to_string(),
+            cache_size: 100,
+            cache_ttl_secs: 300,
+        }
+    }
+}
+
+impl PricingSettings {
+    pub fn cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.cache_ttl_secs)
+    }
+
+    /// Display currency, USD if `currency` is not supported
+    pub fn fiat_currency(&self) -> FiatCurrency {
+        self.currency.parse().unwrap_or_default()
+    }
+}
+
+impl SettingsSection for PricingSettings {
+    const KEY: &'static str = "pricing";
+
+    fn of(settings: &Settings) -> &Self {
+        &settings.pricing
+    }
+
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if self.currency.len() != 3 || !self.currency.chars().all(|c| c.is_ascii_uppercase()) {
+            errors.push(FieldError::new("currency", "must be a three-letter ISO 4217 code"));
+        } else if self.currency.parse::<FiatCurrency>().is_err() {
+            let supported: Vec<&str> = FiatCurrency::ALL.iter().map(FiatCurrency::code).collect();
+            errors.push(FieldError::new(
+                "currency",
+                format!("must be one of {}", supported.join(", ")),
+            ));
+        }
+        check_range(errors, "cache_size", self.cache_size, 1, 10_000);
+        check_range(errors, "cache_ttl_secs", self.cache_ttl_secs, 10, 86_400);
+    }
+}
+
+/// Security policy, read by the session manager
+///
+/// Converts into a [`SessionConfig`] for auto-lock and an
+/// [`OsConfirmationPolicy`] for OS authentication prompts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecurityPolicySettings {
+    /// Idle minutes before the wallet locks; 0 disables auto-lock
+    pub auto_lock_minutes: u32,
+    /// Idle minutes before signing locks while viewing stays allowed; 0 disables
+    pub signing_lock_minutes: u32,
+    /// Seconds before the lock to warn the user; 0 disables the warning
+    pub lock_warning_secs: u64,
+    /// Ask for the password before every transaction
+    pub require_password_for_transactions: bool,
+    /// Also require the OS authentication prompt for seed and key exports
+    pub os_confirmation: bool,
+    /// Transfers of at least this many wei also need the OS prompt; unset exempts transfers
+    pub os_confirmation_transfer_wei: Option<U256>,
+    /// Fall back to the password alone where the OS has no prompt
+    pub os_confirmation_fallback: bool,
+    /// Where keys are stored: `os`, `encrypted_file` or, in debug builds, `memory`
+    pub keychain_backend: KeychainBackend,
+    /// Vault file for the `encrypted_file` backend; defaults to `~/.vaughan/keychain.vault`
+    pub keychain_vault_path: Option<PathBuf>,
+    /// Failed unlocks allowed before each further failure adds a growing wait
+    pub unlock_backoff_after: u32,
+    /// Failed unlocks at which unlocking is locked out
+    pub unlock_lockout_after: u32,
+    /// Minutes unlocking stays locked out after each failure past the threshold
+    pub unlock_lockout_minutes: u32,
+}
+
+impl Default for SecurityPolicySettings {
+    fn default() -> Self {
+        Self {
+            auto_lock_minutes: 5,
+            signing_lock_minutes: 0,
+            lock_warning_secs: 30,
+            require_password_for_transactions: true,
+            os_confirmation: false,
+            os_confirmation_transfer_wei: None,
+            os_confirmation_fallback: true,
+            keychain_backend: KeychainBackend::default(),
+            keychain_vault_path: None,
+            unlock_backoff_after: 3,
+            unlock_lockout_after: 10,
+            unlock_lockout_minutes: 30,
+        }
+    }
+}
+
+impl SecurityPolicySettings {
+    /// Idle timeout, or `None` when auto-lock is disabled
+    pub fn auto_lock_timeout(&self) -> Option<Duration> {
+        (self.auto_lock_minutes > 0).then(|| Duration::from_secs(u64::from(self.auto_lock_minutes) * 60))
+    }
+
+    /// Idle time before signing locks, or `None` when disabled
+    pub fn signing_lock_timeout(&self) -> Option<Duration> {
+        (self.signing_lock_minutes > 0).then(|| Duration::from_secs(u64::from(self.signing_lock_minutes) * 60))
+    }
+}
+
+impl SettingsSection for SecurityPolicySettings {
+    const KEY: &'static str = "security";
+
+    fn of(settings: &Settings) -> &Self {
+        &settings.security
+    }
+
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        check_range(errors, "auto_lock_minutes", self.auto_lock_minutes, 0, 1440);
+        check_range(errors, "signing_lock_minutes", self.signing_lock_minutes, 0, 1440);
+        if let Some(timeout) = self.auto_lock_timeout() {
+            if self.lock_warning_secs >= timeout.as_secs() {
+                errors.push(FieldError::new(
+                    "lock_warning_secs",
+                    "must be shorter than the auto-lock timeout",
+                ));
+            }
+            if self.signing_lock_timeout().is_some_and(|signing| signing >= timeout) {
+                errors.push(FieldError::new(
+                    "signing_lock_minutes",
+                    "must be shorter than the auto-lock timeout",
+                ));
+            }
+        }
+        if self.os_confirmation_transfer_wei.is_some() && !self.os_confirmation {
+            errors.push(FieldError::new("os_confirmation_transfer_wei", "requires os_confirmation"));
+        }
+        if cfg!(not(debug_assertions)) && self.keychain_backend == KeychainBackend::Memory {
+            errors.push(FieldError::new("keychain_backend", "\"memory\" is only available in debug builds"));
+        }
+        if self.keychain_vault_path.is_some() && self.keychain_backend != KeychainBackend::EncryptedFile {
+            errors.push(FieldError::new("keychain_vault_path", "requires keychain_backend \"encrypted_file\""));
+        }
+        check_range(errors, "unlock_backoff_after", self.unlock_backoff_after, 1, 20);
+        check_range(errors, "unlock_lockout_after", self.unlock_lockout_after, 2, 100);
+        if self.unlock_lockout_after <= self.unlock_backoff_after {
+            errors.push(FieldError::new(
+                "unlock_lockout_after",
+                "must be greater than unlock_backoff_after",
+            ));
+        }
+        check_range(errors, "unlock_lockout_minutes", self.unlock_lockout_minutes, 1, 1440);
+    }
+}
+
+impl From<&SecurityPolicySettings> for UnlockPolicy {
+    fn from(policy: &SecurityPolicySettings) -> Self {
+        Self {
+            free_attempts: policy.unlock_backoff_after,
+            lockout_threshold: policy.unlock_lockout_after,
+            lockout_duration: Duration::from_secs(u64::from(policy.unlock_lockout_minutes) * 60),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&SecurityPolicySettings> for SessionConfig {
+    fn from(policy: &SecurityPolicySettings) -> Self {
+        Self {
+            auto_lock_timeout: policy.auto_lock_timeout(),
+            signing_lock_timeout: policy.signing_lock_timeout(),
+            warning_before: (policy.lock_warning_secs > 0).then(|| Duration::from_secs(policy.lock_warning_secs)),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&SecurityPolicySettings> for OsConfirmationPolicy {
+    fn from(policy: &SecurityPolicySettings) -> Self {
+        Self {
+            enabled: policy.os_confirmation,
+            transfer_threshold: policy.os_confirmation_transfer_wei,
+            allow_unavailable: policy.os_confirmation_fallback,
+        }
+    }
+}
+
+/// Local metrics, read by [`crate::telemetry::metrics`]
+///
+/// Off by default. Metric labels have addresses and amounts redacted unless
+/// `detailed` is explicitly enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetrySettings {
+    /// Collect metrics and serve them on `127.0.0.1:metrics_port`
+    pub metrics_enabled: bool,
+    /// Keep addresses and amounts in metric labels and logs
+    pub detailed: bool,
+    /// Port of the Prometheus-compatible `/metrics` endpoint
+    pub metrics_port: u16,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            metrics_enabled: false,
+            detailed: false,
+            metrics_port: crate::telemetry::metrics::DEFAULT_METRICS_PORT,
+        }
+    }
+}
+
+impl SettingsSection for TelemetrySettings {
+    const KEY: &'static str = "telemetry";
+
+    fn of(settings: &Settings) -> &Self {
+        &settings.telemetry
+    }
+
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        check_range(errors, "metrics_port", self.metrics_port, 1024, u16::MAX);
+        if self.detailed && !self.metrics_enabled {
+            errors.push(FieldError::new("detailed", "requires metrics_enabled"));
+        }
+    }
+}
+
+/// All wallet settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub version: u32,
+    pub network: NetworkSettings,
+    pub pricing: PricingSettings,
+    pub security: SecurityPolicySettings,
+    pub telemetry: TelemetrySettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            network: NetworkSettings::default(),
+            pricing: PricingSettings::default(),
+            security: SecurityPolicySettings::default(),
+            telemetry: TelemetrySettings::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Every invalid field, with paths from the settings root
+    pub fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if self.version != SETTINGS_VERSION {
+            errors.push(FieldError::new(
+                "version",
+                format!("unsupported version, expected {SETTINGS_VERSION}"),
+            ));
+        }
+        validate_section(&self.network, &mut errors);
+        validate_section(&self.pricing, &mut errors);
+        validate_section(&self.security, &mut errors);
+        validate_section(&self.telemetry, &mut errors);
+        errors
+    }
+
+    /// Parse and validate settings JSON, reporting every bad field by path
+    pub fn parse(json: &str) -> std::result::Result<Self, Vec<FieldError>> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| vec![FieldError::new("$", e.to_string())])?;
+        let serde_json::Value::Object(mut root) = value else {
+            return Err(vec![FieldError::new("$", "settings must be a JSON object")]);
+        };
+
+        let mut errors: Vec<FieldError> = root
+            .keys()
+            .filter(|key| {
+                !matches!(
+                    key.as_str(),
+                    "version" | "network" | "pricing" | "security" | "telemetry"
+                )
+            })
+            .map(|key| FieldError::new(key.clone(), "unknown setting"))
+            .collect();
+        let version = match root.remove("version") {
+            None => SETTINGS_VERSION,
+            Some(v) => serde_json::from_value(v).unwrap_or_else(|e| {
+                errors.push(FieldError::new("version", e.to_string()));
+                SETTINGS_VERSION
+            }),
+        };
+        let settings = Self {
+            version,
+            network: parse_section(root.remove(NetworkSettings::KEY), &mut errors),
+            pricing: parse_section(root.remove(PricingSettings::KEY), &mut errors),
+            security: parse_section(root.remove(SecurityPolicySettings::KEY), &mut errors),
+            telemetry: parse_section(root.remove(TelemetrySettings::KEY), &mut errors),
+        };
+
+        if errors.is_empty() {
+            errors = settings.validate();
+        }
+        if errors.is_empty() {
+            Ok(settings)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Load settings from `path`, or the defaults if the file does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Self::parse(&json).map_err(|errors| validation_error(&errors)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(parse_error(format!("Failed to read settings {}: {e}", path.display()))),
+        }
+    }
+
+    /// Load settings from the default location in the config directory
+    pub fn load_default() -> Result<Self> {
+        Self::load(&super::ConfigManager::get_config_dir().join(SETTINGS_FILE))
+    }
+
+    /// Validate and write settings to `path`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let errors = self.validate();
+        if !errors.is_empty() {
+            return Err(validation_error(&errors));
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| parse_error(format!("Failed to create config directory: {e}")))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| parse_error(format!("Failed to serialize settings: {e}")))?;
+        std::fs::write(path, json).map_err(|e| parse_error(format!("Failed to write settings {}: {e}", path.display())))
+    }
+}
+
+fn validate_section<T: SettingsSection>(section: &T, errors: &mut Vec<FieldError>) {
+    let mut section_errors = Vec::new();
+    section.validate(&mut section_errors);
+    errors.extend(
+        section_errors
+            .into_iter()
+            .map(|e| FieldError::new(format!("{}.{}", T::KEY, e.path), e.message)),
+    );
+}
+
+/// Deserialize one section; on failure, pin the error to the field that caused it
+fn parse_section<T: SettingsSection>(value: Option<serde_json::Value>, errors: &mut Vec<FieldError>) -> T {
+    let Some(value) = value else {
+        return T::default();
+    };
+    match serde_json::from_value::<T>(value.clone()) {
+        Ok(section) => section,
+        Err(e) => {
+            // serde_json does not report paths, so retry each field on its own
+            let field = value.as_object().and_then(|fields| {
+                fields.iter().find_map(|(name, field)| {
+                    let single = serde_json::Value::Object([(name.clone(), field.clone())].into_iter().collect());
+                    serde_json::from_value::<T>(single)
+                        .err()
+                        .map(|e| FieldError::new(format!("{}.{name}", T::KEY), e.to_string()))
+                })
+            });
+            errors.push(field.unwrap_or_else(|| FieldError::new(T::KEY, e.to_string())));
+            T::default()
+        }
+    }
+}
+
+/// Result of a settings reload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsEvent {
+    /// New settings are in effect; lists the keys of the sections that changed
+    Reloaded { changed: Vec<&'static str> },
+    /// The file was invalid and the previous settings were kept
+    Rejected { errors: Vec<FieldError> },
+}
+
+/// Receiver for one section, woken only when that section changes
+#[derive(Debug, Clone)]
+pub struct SectionWatch<T> {
+    rx: watch::Receiver<Arc<Settings>>,
+    current: T,
+}
+
+impl<T: SettingsSection> SectionWatch<T> {
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// The section as it is now, without waiting for or consuming a change
+    ///
+    /// For values read on every use, such as timeouts.
+    pub fn latest(&self) -> T {
+        T::of(&self.rx.borrow()).clone()
+    }
+
+    /// Wait for the section to change and return the new value
+    ///
+    /// Returns `None` once the store is dropped.
+    pub async fn changed(&mut self) -> Option<T> {
+        loop {
+            self.rx.changed().await.ok()?;
+            let next = T::of(&self.rx.borrow_and_update()).clone();
+            if next != self.current {
+                self.current = next.clone();
+                return Some(next);
+            }
+        }
+    }
+}
+
+/// Current settings, reloaded when the settings file changes
+pub struct SettingsStore {
+    path: PathBuf,
+    settings: watch::Sender<Arc<Settings>>,
+    events: broadcast::Sender<SettingsEvent>,
+}
+
+impl SettingsStore {
+    /// Load settings from `path`, falling back to the defaults if it does not exist
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let settings = Settings::load(&path)?;
+        Ok(Self::with_settings(path, settings))
+    }
+
+    fn with_settings(path: PathBuf, settings: Settings) -> Self {
+        let (settings, _) = watch::channel(Arc::new(settings));
+        let (events, _) = broadcast::channel(16);
+        Self { path, settings, events }
+    }
+
+    /// Settings store for `settings.json` in the default config directory
+    pub fn open_default() -> Result<Self> {
+        Self::open(super::ConfigManager::get_config_dir().join(SETTINGS_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn current(&self) -> Arc<Settings> {
+        self.settings.borrow().clone()
+    }
+
+    /// Current value of one section
+    pub fn section<T: SettingsSection>(&self) -> T {
+        T::of(&self.settings.borrow()).clone()
+    }
+
+    /// Watch one section for changes
+    pub fn subscribe_section<T: SettingsSection>(&self) -> SectionWatch<T> {
+        let rx = self.settings.subscribe();
+        let current = T::of(&rx.borrow()).clone();
+        SectionWatch { rx, current }
+    }
+
+    /// Every reload, accepted or rejected
+    pub fn events(&self) -> broadcast::Receiver<SettingsEvent> {
+        self.events.subscribe()
+    }
+
+    /// Validate, save and apply new settings
+    pub fn update(&self, settings: Settings) -> Result<()> {
+        settings.save(&self.path)?;
+        self.apply(settings);
+        Ok(())
+    }
+
+    /// Re-read the settings file and apply it if valid
+    pub fn reload(&self) -> Result<()> {
+        match Settings::load(&self.path) {
+            Ok(settings) => {
+                self.apply(settings);
+                Ok(())
+            }
+            Err(e) => {
+                let errors = match std::fs::read_to_string(&self.path).map(|json| Settings::parse(&json)) {
+                    Ok(Err(errors)) => errors,
+                    _ => vec![FieldError::new("$", e.to_string())],
+                };
+                tracing::warn!("⚠️ Rejected settings reload from {}: {}", self.path.display(), e);
+                let _ = self.events.send(SettingsEvent::Rejected { errors });
+                Err(e)
+            }
+        }
+    }
+
+    fn apply(&self, settings: Settings) {
+        let previous = self.current();
+        let changed: Vec<&'static str> = [
+            (NetworkSettings::KEY, previous.network != settings.network),
+            (PricingSettings::KEY, previous.pricing != settings.pricing),
+            (SecurityPolicySettings::KEY, previous.security != settings.security),
+            (TelemetrySettings::KEY, previous.telemetry != settings.telemetry),
+        ]
+        .into_iter()
+        .filter_map(|(key, differs)| differs.then_some(key))
+        .collect();
+
+        if *previous != settings {
+            self.settings.send_replace(Arc::new(settings));
+        }
+        if !changed.is_empty() {
+            tracing::info!("⚙️ Settings reloaded, changed sections: {}", changed.join(", "));
+        }
+        let _ = self.events.send(SettingsEvent::Reloaded { changed });
+    }
+
+    /// Poll the settings file every `interval` and reload it when it changes
+    pub fn watch_file(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let store = Arc::downgrade(self);
+        let path = self.path.clone();
+        tokio::spawn(async move {
+            let mut last_seen = file_stamp(&path);
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let Some(store) = store.upgrade() else {
+                    break;
+                };
+                let stamp = file_stamp(&path);
+                if stamp != last_seen {
+                    last_seen = stamp;
+                    let _ = store.reload();
+                }
+            }
+        })
+    }
+}
+
+static SETTINGS_STORE: OnceLock<Arc<SettingsStore>> = OnceLock::new();
+static SETTINGS_WATCHER: Once = Once::new();
+
+/// Get the global settings store
+///
+/// Opened from the default file on first use. An invalid file leaves the
+/// defaults in effect until it is fixed and reloaded.
+pub fn settings_store() -> &'static Arc<SettingsStore> {
+    SETTINGS_STORE.get_or_init(|| {
+        let path = super::ConfigManager::get_config_dir().join(SETTINGS_FILE);
+        let store = SettingsStore::open(&path).unwrap_or_else(|e| {
+            tracing::warn!("⚠️ Invalid settings in {}, using defaults: {}", path.display(), e);
+            SettingsStore::with_settings(path, Settings::default())
+        });
+        Arc::new(store)
+    })
+}
+
+/// Open the global settings store and reload it whenever the file changes
+///
+/// The file is polled on its own thread, so this can be called before any
+/// async runtime is running.
+pub fn init_settings_store() -> &'static Arc<SettingsStore> {
+    let store = settings_store();
+    SETTINGS_WATCHER.call_once(|| {
+        let watched = Arc::clone(store);
+        let spawned = std::thread::Builder::new()
+            .name("settings-watch".to_string())
+            .spawn(move || {
+                match tokio::runtime::Builder::new_current_thread().enable_time().build() {
+                    Ok(runtime) => {
+                        let _ = runtime.block_on(watched.watch_file(SETTINGS_POLL_INTERVAL));
+                    }
+                    Err(e) => tracing::warn!("⚠️ Settings live reload unavailable: {}", e),
+                }
+            });
+        if let Err(e) = spawned {
+            tracing::warn!("⚠️ Settings live reload unavailable: {}", e);
+        }
+    });
+    store
+}
+
+/// Modification time and size, enough to notice an edit between polls
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reports_field_paths() {
+        let errors = Settings::parse(
+            r#"{
+                "network": { "request_timeout_secs": 0 },
+                "pricing": { "cache_ttl_secs": "soon" },
+                "security": { "auto_lock_minutes": 5, "lock_warnings": 10 }
+            }"#,
+        )
+        .unwrap_err();
+        let paths: Vec<_> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["pricing.cache_ttl_secs", "security.lock_warnings"]);
+
+        let errors = Settings::parse(r#"{ "network": { "request_timeout_secs": 0 } }"#).unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "network.request_timeout_secs: must be between 1 and 300"
+        );
+
+        let settings = Settings::parse(r#"{ "pricing": { "currency": "EUR" } }"#).unwrap();
+        assert_eq!(settings.pricing.currency, "EUR");
+        assert_eq!(settings.pricing.fiat_currency(), FiatCurrency::Eur);
+        assert_eq!(settings.network, NetworkSettings::default());
+    }
+
+    #[test]
+    fn test_security_policy_to_session_config() {
+        let policy = SecurityPolicySettings {
+            auto_lock_minutes: 0,
+            signing_lock_minutes: 2,
+            lock_warning_secs: 0,
+            ..Default::default()
+        };
+        let config = SessionConfig::from(&policy);
+        assert_eq!(config.auto_lock_timeout, None);
+        assert_eq!(config.signing_lock_timeout, Some(Duration::from_secs(120)));
+        assert_eq!(config.warning_before, None);
+        assert_eq!(
+            SessionConfig::from(&SecurityPolicySettings::default()).auto_lock_timeout,
+            Some(Duration::from_secs(300))
+        );
+
+        let policy = SecurityPolicySettings {
+            os_confirmation: true,
+            os_confirmation_transfer_wei: Some(U256::from(10).pow(U256::from(18))),
+            os_confirmation_fallback: false,
+            ..Default::default()
+        };
+        let os = OsConfirmationPolicy::from(&policy);
+        assert!(os.enabled && !os.allow_unavailable);
+        assert_eq!(os.transfer_threshold, policy.os_confirmation_transfer_wei);
+        assert!(!OsConfirmationPolicy::from(&SecurityPolicySettings::default()).enabled);
+
+        let settings =
+            Settings::parse(r#"{ "security": { "unlock_lockout_after": 6, "unlock_lockout_minutes": 60 } }"#).unwrap();
+        let unlock = UnlockPolicy::from(&settings.security);
+        assert_eq!((unlock.free_attempts, unlock.lockout_threshold), (3, 6));
+        assert_eq!(unlock.lockout_duration, Duration::from_secs(3600));
+        let errors = Settings::parse(r#"{ "security": { "unlock_lockout_after": 3 } }"#).unwrap_err();
+        assert_eq!(errors[0].path, "security.unlock_lockout_after");
+    }
+
+    #[test]
+    fn test_keychain_backend_setting() {
+        let settings = Settings::parse(
+            r#"{ "security": { "keychain_backend": "encrypted_file", "keychain_vault_path": "/mnt/usb/keys.vault" } }"#,
+        )
+        .unwrap();
+        assert_eq!(settings.security.keychain_backend, KeychainBackend::EncryptedFile);
+
+        let errors = Settings::parse(r#"{ "security": { "keychain_vault_path": "/mnt/usb/keys.vault" } }"#)
+            .unwrap_err();
+        assert_eq!(errors[0].path, "security.keychain_vault_path");
+        assert!(Settings::parse(r#"{ "security": { "keychain_backend": "cloud" } }"#).is_err());
+    }
+
+    #[test]
+    fn test_telemetry_is_opt_in() {
+        let settings = Settings::default();
+        assert!(!settings.telemetry.metrics_enabled && !settings.telemetry.detailed);
+
+        let settings =
+            Settings::parse(r#"{ "telemetry": { "metrics_enabled": true, "metrics_port": 9100 } }"#).unwrap();
+        assert_eq!(settings.telemetry.metrics_port, 9100);
+
+        let errors = Settings::parse(r#"{ "telemetry": { "detailed": true, "metrics_port": 80 } }"#).unwrap_err();
+        let paths: Vec<_> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["telemetry.metrics_port", "telemetry.detailed"]);
+    }
+
+    #[tokio::test]
+    async fn test_reload_notifies_changed_section_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        let store = SettingsStore::open(&path).unwrap();
+        let mut pricing = store.subscribe_section::<PricingSettings>();
+        let mut network = store.subscribe_section::<NetworkSettings>();
+        let mut events = store.events();
+
+        let mut settings = Settings::default();
+        settings.pricing.cache_ttl_secs = 60;
+        store.update(settings).unwrap();
+        assert_eq!(pricing.changed().await.unwrap().cache_ttl_secs, 60);
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SettingsEvent::Reloaded {
+                changed: vec!["pricing"]
+            }
+        );
+        assert!(tokio::time::timeout(Duration::from_millis(20), network.changed())
+            .await
+            .is_err());
+
+        std::fs::write(&path, r#"{ "pricing": { "cache_size": 0 } }"#).unwrap();
+        assert!(store.reload().is_err());
+        assert!(
+            matches!(events.recv().await.unwrap(), SettingsEvent::Rejected { errors } if errors[0].path == "pricing.cache_size")
+        );
+        assert_eq!(store.section::<PricingSettings>().cache_ttl_secs, 60);
+    }
+}
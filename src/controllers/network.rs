@@ -20,14 +20,13 @@
 use super::{ControllerError, ControllerResult};
 use alloy::primitives::{Address, ChainId, U256};
 use alloy::providers::fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller};
-use alloy::providers::{Identity, Provider, ProviderBuilder, RootProvider};
+use alloy::providers::{Identity, Provider, RootProvider};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use url::Url;
 
 /// Type alias for HTTP provider (matches existing codebase pattern)
 ///
-/// This is the concrete type returned by `ProviderBuilder::new().connect_http(url)`.
+/// This is the concrete type returned by `crate::network::connect_rpc`.
 /// Using a type alias ensures consistency with the rest of the codebase.
 type HttpProvider = FillProvider<
     JoinFill<Identity, JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>>,
@@ -197,13 +196,9 @@ impl NetworkController<HttpProvider> {
     /// # }
     /// ```
     pub async fn new(rpc_url: String, chain_id: ChainId) -> ControllerResult<Self> {
-        // Parse and validate URL
-        let url = Url::parse(&rpc_url)
-            .map_err(|e| ControllerError::Network(format!("Invalid RPC URL: {}", e)))?;
-
-        // Create Alloy provider with HTTP transport (following existing codebase pattern)
-        // Note: connect_http() is the correct method in Alloy v1.5
-        let provider = ProviderBuilder::new().connect_http(url);
+        // Create Alloy provider with HTTP transport, if the egress settings allow it
+        let provider = crate::network::connect_rpc(&rpc_url, "network controller")
+            .map_err(|e| ControllerError::Network(e.to_string()))?;
 
         Ok(Self::from_provider(provider, chain_id, rpc_url))
     }
@@ -226,13 +221,9 @@ impl NetworkController<HttpProvider> {
         rpc_url: String,
         chain_id: ChainId,
     ) -> ControllerResult<()> {
-        // Parse and validate URL
-        let url = Url::parse(&rpc_url)
-            .map_err(|e| ControllerError::Network(format!("Invalid RPC URL: {}", e)))?;
-
-        // Create new provider with HTTP transport
-        // Note: connect_http() is the correct method in Alloy v1.5
-        let provider = ProviderBuilder::new().connect_http(url);
+        // Create Alloy provider with HTTP transport, if the egress settings allow it
+        let provider = crate::network::connect_rpc(&rpc_url, "network controller")
+            .map_err(|e| ControllerError::Network(e.to_string()))?;
 
         // Verify chain ID matches (MetaMask pattern)
         let actual_chain_id = provider
@@ -260,8 +251,16 @@ impl NetworkController<HttpProvider> {
 mod tests {
     use super::*;
 
+    /// Approve the public RPC so tests don't depend on first-run consent
+    fn approve_pulsechain_rpc() {
+        crate::network::egress::egress()
+            .set_host("rpc.pulsechain.com", true)
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_network_controller_creation() {
+        approve_pulsechain_rpc();
         // Use a public RPC endpoint for testing
         let result = NetworkController::new(
             "https://rpc.pulsechain.com".to_string(),
@@ -293,6 +292,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_balance() {
+        approve_pulsechain_rpc();
         // Use a public RPC endpoint
         let controller = NetworkController::new(
             "https://rpc.pulsechain.com".to_string(),
@@ -310,6 +310,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_check_network_health() {
+        approve_pulsechain_rpc();
         let controller = NetworkController::new(
             "https://rpc.pulsechain.com".to_string(),
             ChainId::from(369u64),
@@ -328,6 +329,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_chain_id() {
+        approve_pulsechain_rpc();
         let controller = NetworkController::new(
             "https://rpc.pulsechain.com".to_string(),
             ChainId::from(369u64),
@@ -18,6 +18,9 @@
 //! - ERC20 token price fetching
 
 use super::{ControllerError, ControllerResult};
+use crate::config::{PricingSettings, SectionWatch};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient};
 use alloy::primitives::Address;
 use lru::LruCache;
 use std::num::NonZeroUsize;
@@ -87,7 +90,9 @@ pub struct PriceController {
     /// Optional Moralis API key for premium features
     moralis_api_key: Option<String>,
     /// HTTP client for API requests
-    client: reqwest::Client,
+    client: HttpClient,
+    /// `pricing` settings, overriding the cache settings and able to disable fetching
+    settings: Option<SectionWatch<PricingSettings>>,
 }
 
 impl PriceController {
@@ -109,7 +114,8 @@ impl PriceController {
             cache: Arc::new(RwLock::new(cache)),
             cache_ttl: Duration::from_secs(300), // 5 minutes
             moralis_api_key,
-            client: reqwest::Client::new(),
+            client: shared_client(),
+            settings: None,
         }
     }
 
@@ -132,7 +138,30 @@ impl PriceController {
             cache: Arc::new(RwLock::new(cache)),
             cache_ttl,
             moralis_api_key,
-            client: reqwest::Client::new(),
+            client: shared_client(),
+            settings: None,
+        }
+    }
+
+    /// Create price controller from the `pricing` settings section, following changes
+    ///
+    /// A changed cache size or TTL applies to prices cached from then on;
+    /// while `enabled` is off, nothing is fetched.
+    pub fn from_settings(moralis_api_key: Option<String>, settings: SectionWatch<PricingSettings>) -> Self {
+        let current = settings.current();
+        Self {
+            settings: Some(settings.clone()),
+            ..Self::with_cache_settings(moralis_api_key, current.cache_size, current.cache_ttl())
+        }
+    }
+
+    /// Fail while price fetching is disabled in the settings
+    fn ensure_enabled(&self) -> ControllerResult<()> {
+        match &self.settings {
+            Some(settings) if !settings.latest().enabled => Err(ControllerError::Price(
+                "Price fetching is disabled in the settings".to_string(),
+            )),
+            _ => Ok(()),
         }
     }
 
@@ -175,6 +204,7 @@ impl PriceController {
         }
 
         // Fetch from API
+        self.ensure_enabled()?;
         let price = self.fetch_native_price_from_api(chain_id).await?;
 
         // Cache the result
@@ -213,6 +243,7 @@ impl PriceController {
         }
 
         // Fetch from API
+        self.ensure_enabled()?;
         let price = self.fetch_token_price_from_api(chain_id, token_address).await?;
 
         // Cache the result
@@ -259,12 +290,19 @@ impl PriceController {
 
     /// Cache a price
     async fn cache_price(&self, key: &CacheKey, price: &TokenPrice) {
+        let settings = self.settings.as_ref().map(SectionWatch::latest);
+        let ttl = settings.as_ref().map_or(self.cache_ttl, PricingSettings::cache_ttl);
         let entry = CacheEntry {
             price: price.clone(),
-            expires_at: Instant::now() + self.cache_ttl,
+            expires_at: Instant::now() + ttl,
         };
 
         let mut cache = self.cache.write().await;
+        if let Some(size) = settings.and_then(|settings| NonZeroUsize::new(settings.cache_size)) {
+            if cache.cap() != size {
+                cache.resize(size);
+            }
+        }
         cache.put(key.clone(), entry);
     }
 
@@ -279,6 +317,10 @@ impl PriceController {
             coin_id
         );
 
+        egress()
+            .check(EgressCategory::PriceData, &url, "fetch price")
+            .map_err(|e| ControllerError::Price(e.to_string()))?;
+
         let response = self
             .client
             .get(&url)
@@ -336,6 +378,10 @@ impl PriceController {
             token_address.to_string().to_lowercase()
         );
 
+        egress()
+            .check(EgressCategory::PriceData, &url, "fetch price")
+            .map_err(|e| ControllerError::Price(e.to_string()))?;
+
         let response = self
             .client
             .get(&url)
@@ -433,6 +479,38 @@ mod tests {
         assert_eq!(controller.cache_ttl, Duration::from_secs(60));
     }
 
+    #[tokio::test]
+    async fn test_follows_pricing_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = crate::config::SettingsStore::open(dir.path().join("settings.json")).unwrap();
+        let controller = PriceController::from_settings(None, store.subscribe_section());
+        assert_eq!(controller.cache_stats().await, (0, 100));
+
+        let mut settings = store.current().as_ref().clone();
+        settings.pricing.cache_size = 10;
+        settings.pricing.enabled = false;
+        store.update(settings).unwrap();
+
+        let price = TokenPrice {
+            address: Address::ZERO,
+            chain_id: 1,
+            price_usd: 2000.0,
+            price_change_24h: None,
+            fetched_at: Instant::now(),
+        };
+        let key = CacheKey {
+            chain_id: 1,
+            address: Address::ZERO,
+        };
+        controller.cache_price(&key, &price).await;
+        assert_eq!(controller.cache_stats().await, (1, 10));
+
+        // Cached prices are still served, nothing new is fetched
+        assert!(controller.fetch_native_token_price(1).await.is_ok());
+        let error = controller.fetch_native_token_price(137).await.unwrap_err();
+        assert!(error.to_string().contains("disabled"));
+    }
+
     #[tokio::test]
     async fn test_cache_stats() {
         let controller = PriceController::new(None);
@@ -0,0 +1,533 @@
+//! Bridge transfer tracking
+//!
+//! A bridge transfer is two transactions on two chains: the deposit the user
+//! signs on the source chain, and the mint or release the bridge performs on
+//! the destination chain later. [`BridgeTracker`] follows both and publishes a
+//! single [`BridgeTransfer`] status object:
+//!
+//! - the source transaction is watched with the same state machine as
+//!   [`crate::network::tx_watcher`]
+//! - arrival is detected on the destination chain either by an ERC-20
+//!   `Transfer` to the recipient or, for native assets, by the recipient's
+//!   balance growing by the expected amount
+//!
+//! Each transfer carries an ETA; transfers that run late get escalation
+//! guidance (wait, check the bridge explorer, contact support).
+
+use crate::network::tx_watcher::{self, TxStatus, TxWatchState, TxWatcherConfig};
+use crate::network::NetworkId;
+use alloy::primitives::{Address, TxHash, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log};
+use alloy::sol;
+use alloy::sol_types::SolEvent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
+use uuid::Uuid;
+
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+}
+
+/// How far past its ETA a transfer may run before it is considered stuck
+pub const STUCK_AFTER_ETA_FACTOR: u32 = 3;
+
+/// Where a bridge transfer currently is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum BridgeStatus {
+    /// Waiting for the source transaction to be mined
+    SourcePending,
+    /// Source transaction mined, waiting for confirmations
+    SourceConfirming { confirmations: u64 },
+    /// Source transaction final, waiting for funds on the destination chain
+    InFlight,
+    /// Funds arrived on the destination chain
+    Completed {
+        destination_tx: Option<TxHash>,
+        block_number: Option<u64>,
+        amount: U256,
+    },
+    /// The source transaction reverted, was replaced or dropped
+    SourceFailed { reason: String },
+}
+
+impl BridgeStatus {
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Completed { .. } | Self::SourceFailed { .. })
+    }
+}
+
+/// How arrival is recognised on the destination chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArrivalCheck {
+    /// An ERC-20 `Transfer` of `token` to the recipient
+    Erc20Transfer { token: Address },
+    /// The recipient's native balance growing from `baseline`
+    NativeBalance { baseline: U256 },
+}
+
+/// Funds seen on the destination chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrival {
+    pub tx_hash: Option<TxHash>,
+    pub block_number: Option<u64>,
+    pub amount: U256,
+}
+
+/// How urgent a late or failed transfer is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationLevel {
+    /// Past its ETA; usually just a slow relayer
+    Delayed,
+    /// Far past its ETA; needs the user's attention
+    Stuck,
+    /// The source transaction failed
+    Failed,
+}
+
+/// What to tell the user about a transfer that isn't going to plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeEscalation {
+    pub level: EscalationLevel,
+    pub message: String,
+}
+
+/// A tracked cross-chain transfer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeTransfer {
+    pub id: Uuid,
+    /// Bridge name shown to the user, e.g. "PulseChain Bridge"
+    pub bridge: String,
+    pub source_network: NetworkId,
+    pub source_tx: TxHash,
+    pub destination_network: NetworkId,
+    pub recipient: Address,
+    pub arrival_check: ArrivalCheck,
+    /// Smallest amount that counts as the transfer arriving (after bridge fees)
+    pub min_amount: U256,
+    /// First destination block searched for the arrival
+    pub destination_from_block: u64,
+    pub started_at: DateTime<Utc>,
+    /// Typical duration quoted by the bridge
+    pub expected_duration: Duration,
+    pub status: BridgeStatus,
+}
+
+impl BridgeTransfer {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        bridge: impl Into<String>,
+        source_network: NetworkId,
+        source_tx: TxHash,
+        destination_network: NetworkId,
+        recipient: Address,
+        arrival_check: ArrivalCheck,
+        min_amount: U256,
+        expected_duration: Duration,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            bridge: bridge.into(),
+            source_network,
+            source_tx,
+            destination_network,
+            recipient,
+            arrival_check,
+            min_amount,
+            destination_from_block: 0,
+            started_at: Utc::now(),
+            expected_duration,
+            status: BridgeStatus::SourcePending,
+        }
+    }
+
+    /// Only search destination blocks from `block` onwards
+    pub fn with_destination_from_block(mut self, block: u64) -> Self {
+        self.destination_from_block = block;
+        self
+    }
+
+    /// Expected completion time
+    pub fn eta(&self) -> DateTime<Utc> {
+        self.started_at + chrono::Duration::from_std(self.expected_duration).unwrap_or(chrono::Duration::zero())
+    }
+
+    /// Time left until the ETA; zero once it has passed
+    pub fn remaining(&self, now: DateTime<Utc>) -> Duration {
+        (self.eta() - now).to_std().unwrap_or(Duration::ZERO)
+    }
+
+    /// Apply a source-chain status from the transaction watcher
+    pub fn on_source_status(&mut self, status: &TxStatus, required_confirmations: u64) {
+        if self.status.is_final() {
+            return;
+        }
+        self.status = match status {
+            TxStatus::Failed { block_number } => BridgeStatus::SourceFailed {
+                reason: format!("source transaction reverted in block {block_number}"),
+            },
+            TxStatus::Replaced => BridgeStatus::SourceFailed {
+                reason: "source transaction was replaced by another with the same nonce".to_string(),
+            },
+            TxStatus::Dropped => BridgeStatus::SourceFailed {
+                reason: "source transaction was dropped from the mempool".to_string(),
+            },
+            status if status.is_final(required_confirmations) => BridgeStatus::InFlight,
+            TxStatus::Included { .. } => BridgeStatus::SourceConfirming { confirmations: 1 },
+            TxStatus::Confirmed { confirmations } => BridgeStatus::SourceConfirming {
+                confirmations: *confirmations,
+            },
+        };
+    }
+
+    /// Record funds seen on the destination chain
+    ///
+    /// Arrivals below `min_amount` are ignored. Bridges may relay before the
+    /// source is final, so an arrival completes the transfer from any
+    /// non-failed state.
+    pub fn on_arrival(&mut self, arrival: &Arrival) -> bool {
+        if self.status.is_final() || arrival.amount < self.min_amount {
+            return false;
+        }
+        self.status = BridgeStatus::Completed {
+            destination_tx: arrival.tx_hash,
+            block_number: arrival.block_number,
+            amount: arrival.amount,
+        };
+        true
+    }
+
+    /// Guidance for transfers that failed or are running late
+    pub fn escalation(&self, now: DateTime<Utc>) -> Option<BridgeEscalation> {
+        if let BridgeStatus::SourceFailed { reason } = &self.status {
+            return Some(BridgeEscalation {
+                level: EscalationLevel::Failed,
+                message: format!(
+                    "The bridge deposit did not go through ({reason}). Your funds never left {}; \
+                     only gas was spent. You can retry the transfer.",
+                    self.source_network.0
+                ),
+            });
+        }
+        if self.status.is_final() || now <= self.eta() {
+            return None;
+        }
+
+        let stuck_after = self.expected_duration * STUCK_AFTER_ETA_FACTOR;
+        let elapsed = (now - self.started_at).to_std().unwrap_or(Duration::ZERO);
+        if elapsed < stuck_after {
+            Some(BridgeEscalation {
+                level: EscalationLevel::Delayed,
+                message: format!(
+                    "{} is taking longer than usual. Relayers are sometimes slow; \
+                     check the bridge's explorer for {}.",
+                    self.bridge, self.source_tx
+                ),
+            })
+        } else {
+            Some(BridgeEscalation {
+                level: EscalationLevel::Stuck,
+                message: format!(
+                    "Funds have not arrived on network {} after {} minutes. Some bridges need a manual \
+                     claim on the destination chain; otherwise contact {} support with transaction {}.",
+                    self.destination_network.0,
+                    elapsed.as_secs() / 60,
+                    self.bridge,
+                    self.source_tx
+                ),
+            })
+        }
+    }
+}
+
+/// First ERC-20 transfer log worth at least `min_amount`
+pub fn find_transfer_arrival(logs: &[Log], min_amount: U256) -> Option<Arrival> {
+    logs.iter().find_map(|log| {
+        let transfer = Transfer::decode_log_data(log.data()).ok()?;
+        (transfer.value >= min_amount).then_some(Arrival {
+            tx_hash: log.transaction_hash,
+            block_number: log.block_number,
+            amount: transfer.value,
+        })
+    })
+}
+
+/// Tracker timing settings
+#[derive(Debug, Clone)]
+pub struct BridgeTrackerConfig {
+    pub poll_interval: Duration,
+    /// Source confirmations before a deposit is considered final
+    pub required_confirmations: u64,
+    /// Stop watching the destination after this many ETAs
+    pub give_up_factor: u32,
+}
+
+impl Default for BridgeTrackerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(15),
+            required_confirmations: 12,
+            give_up_factor: 10,
+        }
+    }
+}
+
+/// Follows bridge transfers on both chains and publishes status updates
+#[derive(Debug, Clone)]
+pub struct BridgeTracker {
+    transfers: Arc<RwLock<HashMap<Uuid, BridgeTransfer>>>,
+    sender: broadcast::Sender<BridgeTransfer>,
+    config: BridgeTrackerConfig,
+}
+
+impl BridgeTracker {
+    pub fn new(config: BridgeTrackerConfig) -> Self {
+        let (sender, _) = broadcast::channel(64);
+        Self {
+            transfers: Arc::new(RwLock::new(HashMap::new())),
+            sender,
+            config,
+        }
+    }
+
+    /// Updated transfer objects, sent whenever a status changes
+    pub fn subscribe(&self) -> broadcast::Receiver<BridgeTransfer> {
+        self.sender.subscribe()
+    }
+
+    pub async fn get(&self, id: Uuid) -> Option<BridgeTransfer> {
+        self.transfers.read().await.get(&id).cloned()
+    }
+
+    /// All tracked transfers, newest first
+    pub async fn transfers(&self) -> Vec<BridgeTransfer> {
+        let mut transfers: Vec<_> = self.transfers.read().await.values().cloned().collect();
+        transfers.sort_by_key(|t| std::cmp::Reverse(t.started_at));
+        transfers
+    }
+
+    async fn update(&self, transfer: &BridgeTransfer) {
+        self.transfers.write().await.insert(transfer.id, transfer.clone());
+        // No subscribers is fine
+        let _ = self.sender.send(transfer.clone());
+    }
+
+    /// Start tracking a transfer in the background
+    pub async fn track<S, D>(
+        &self,
+        mut transfer: BridgeTransfer,
+        source: S,
+        destination: D,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        S: Provider + Send + Sync + 'static,
+        D: Provider + Send + Sync + 'static,
+    {
+        self.update(&transfer).await;
+        let tracker = self.clone();
+
+        tokio::spawn(async move {
+            let watcher_config = TxWatcherConfig {
+                poll_interval: tracker.config.poll_interval,
+                required_confirmations: tracker.config.required_confirmations,
+                ..Default::default()
+            };
+            let mut source_state = TxWatchState::new(&watcher_config);
+            let give_up_after = transfer.expected_duration * tracker.config.give_up_factor;
+            let started = Instant::now();
+            let mut interval = tokio::time::interval(tracker.config.poll_interval);
+            tracing::info!(
+                "🌉 Tracking {} transfer {} from network {} to {}",
+                transfer.bridge,
+                transfer.source_tx,
+                transfer.source_network.0,
+                transfer.destination_network.0
+            );
+
+            while !transfer.status.is_final() && started.elapsed() < give_up_after {
+                interval.tick().await;
+                let before = transfer.status.clone();
+
+                if !source_state.is_finished() {
+                    match tx_watcher::poll(&source, transfer.source_tx, source_state.sender_nonce()).await {
+                        Ok(observation) => {
+                            for status in source_state.observe(&observation, Instant::now()) {
+                                transfer.on_source_status(&status, tracker.config.required_confirmations);
+                            }
+                        }
+                        Err(e) => tracing::warn!("⚠️ Bridge source poll for {} failed: {}", transfer.source_tx, e),
+                    }
+                }
+
+                if !transfer.status.is_final() && transfer.status != BridgeStatus::SourcePending {
+                    match poll_arrival(&destination, &transfer).await {
+                        Ok(Some(arrival)) => {
+                            transfer.on_arrival(&arrival);
+                        }
+                        Ok(None) => {}
+                        Err(e) => tracing::warn!("⚠️ Bridge destination poll for {} failed: {}", transfer.id, e),
+                    }
+                }
+
+                if transfer.status != before {
+                    tracing::info!("🌉 Bridge transfer {}: {:?}", transfer.id, transfer.status);
+                    tracker.update(&transfer).await;
+                }
+            }
+
+            if !transfer.status.is_final() {
+                tracing::warn!(
+                    "⚠️ Stopped watching bridge transfer {} before it completed",
+                    transfer.id
+                );
+            }
+        })
+    }
+}
+
+impl Default for BridgeTracker {
+    fn default() -> Self {
+        Self::new(BridgeTrackerConfig::default())
+    }
+}
+
+async fn poll_arrival<P: Provider>(
+    provider: &P,
+    transfer: &BridgeTransfer,
+) -> std::result::Result<Option<Arrival>, alloy::transports::TransportError> {
+    match &transfer.arrival_check {
+        ArrivalCheck::Erc20Transfer { token } => {
+            let filter = Filter::new()
+                .address(*token)
+                .event_signature(Transfer::SIGNATURE_HASH)
+                .topic2(transfer.recipient.into_word())
+                .from_block(transfer.destination_from_block);
+            let logs = provider.get_logs(&filter).await?;
+            Ok(find_transfer_arrival(&logs, transfer.min_amount))
+        }
+        ArrivalCheck::NativeBalance { baseline } => {
+            let balance = provider.get_balance(transfer.recipient).await?;
+            let received = balance.saturating_sub(*baseline);
+            Ok((received >= transfer.min_amount).then_some(Arrival {
+                tx_hash: None,
+                block_number: None,
+                amount: received,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{LogData, B256};
+
+    const TOKEN: Address = Address::repeat_byte(0x70);
+    const RECIPIENT: Address = Address::repeat_byte(0x42);
+
+    fn transfer() -> BridgeTransfer {
+        BridgeTransfer::new(
+            "PulseChain Bridge",
+            NetworkId(1),
+            TxHash::repeat_byte(1),
+            NetworkId(369),
+            RECIPIENT,
+            ArrivalCheck::Erc20Transfer { token: TOKEN },
+            U256::from(990),
+            Duration::from_secs(30 * 60),
+        )
+    }
+
+    fn transfer_log(value: u64, tx: u8) -> Log {
+        let event = Transfer {
+            from: Address::ZERO,
+            to: RECIPIENT,
+            value: U256::from(value),
+        };
+        Log {
+            inner: alloy::primitives::Log {
+                address: TOKEN,
+                data: LogData::new_unchecked(
+                    vec![Transfer::SIGNATURE_HASH, B256::ZERO, RECIPIENT.into_word()],
+                    event.encode_data().into(),
+                ),
+            },
+            transaction_hash: Some(TxHash::repeat_byte(tx)),
+            block_number: Some(500),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_source_progress_then_arrival() {
+        let mut transfer = transfer();
+        transfer.on_source_status(&TxStatus::Included { block_number: 10 }, 3);
+        assert_eq!(transfer.status, BridgeStatus::SourceConfirming { confirmations: 1 });
+        transfer.on_source_status(&TxStatus::Confirmed { confirmations: 3 }, 3);
+        assert_eq!(transfer.status, BridgeStatus::InFlight);
+
+        let arrival = find_transfer_arrival(&[transfer_log(500, 2), transfer_log(995, 3)], transfer.min_amount);
+        assert!(transfer.on_arrival(&arrival.unwrap()));
+        assert_eq!(
+            transfer.status,
+            BridgeStatus::Completed {
+                destination_tx: Some(TxHash::repeat_byte(3)),
+                block_number: Some(500),
+                amount: U256::from(995),
+            }
+        );
+        assert!(transfer.escalation(Utc::now() + chrono::Duration::days(1)).is_none());
+    }
+
+    #[test]
+    fn test_small_transfers_do_not_count_as_arrival() {
+        let mut transfer = transfer();
+        assert!(find_transfer_arrival(&[transfer_log(10, 2)], transfer.min_amount).is_none());
+        let dust = Arrival {
+            tx_hash: None,
+            block_number: None,
+            amount: U256::from(1),
+        };
+        assert!(!transfer.on_arrival(&dust));
+        assert_eq!(transfer.status, BridgeStatus::SourcePending);
+    }
+
+    #[test]
+    fn test_escalation_grows_with_delay() {
+        let transfer = transfer();
+        let start = transfer.started_at;
+        assert!(transfer.escalation(start + chrono::Duration::minutes(20)).is_none());
+        assert_eq!(
+            transfer.remaining(start + chrono::Duration::minutes(20)),
+            Duration::from_secs(600)
+        );
+
+        let delayed = transfer.escalation(start + chrono::Duration::minutes(45)).unwrap();
+        assert_eq!(delayed.level, EscalationLevel::Delayed);
+        let stuck = transfer.escalation(start + chrono::Duration::minutes(120)).unwrap();
+        assert_eq!(stuck.level, EscalationLevel::Stuck);
+        assert!(stuck.message.contains("PulseChain Bridge support"));
+    }
+
+    #[test]
+    fn test_failed_source_is_final() {
+        let mut transfer = transfer();
+        transfer.on_source_status(&TxStatus::Dropped, 12);
+        assert!(transfer.status.is_final());
+        assert_eq!(
+            transfer.escalation(transfer.started_at).map(|e| e.level),
+            Some(EscalationLevel::Failed)
+        );
+
+        // Later events don't revive it
+        transfer.on_source_status(&TxStatus::Included { block_number: 1 }, 12);
+        assert!(matches!(transfer.status, BridgeStatus::SourceFailed { .. }));
+    }
+}
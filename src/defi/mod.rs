@@ -0,0 +1,13 @@
+//! DeFi integrations
+//!
+//! Protocol integrations built on top of the wallet's network and token layers:
+//!
+//! - [`bridge`]: tracking of cross-chain bridge transfers on both chains
+//! - [`positions`]: read-only LP, staking and lending positions with their
+//!   underlying tokens
+//! - [`swap`]: token swaps quoted by DEX aggregators (0x, 1inch, ParaSwap) or
+//!   directly by Uniswap V2/V3 style routers
+
+pub mod bridge;
+pub mod positions;
+pub mod swap;
@@ -0,0 +1,370 @@
+//! Read-only DeFi position tracking
+//!
+//! Detects an account's positions among the token contracts it holds and
+//! resolves them into the underlying tokens:
+//!
+//! - Uniswap V2 style LP tokens (Uniswap, PulseX, PancakeSwap, ...): the
+//!   account's share of the pair reserves
+//! - Aave style aTokens: redeemable 1:1 for `UNDERLYING_ASSET_ADDRESS()`
+//! - Compound style cTokens: `balance * exchangeRateStored() / 1e18` of
+//!   `underlying()`
+//! - Staked balances in StakingRewards style contracts, configured per chain
+//!   with [`PositionTracker::with_staking_contract`]
+//!
+//! Positions are valued from the USD prices of their underlying tokens and can
+//! be merged into a portfolio snapshot with
+//! [`crate::wallet::portfolio::PortfolioSnapshot::add_positions`]. Nothing
+//! here sends transactions.
+
+use crate::error::{NetworkError, Result};
+use crate::tokens::oracle::{MultiSourcePriceOracle, PriceKey};
+use crate::wallet::transaction::erc20::format_amount;
+use alloy::primitives::{Address, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use std::collections::HashMap;
+
+sol! {
+    interface IPositionToken {
+        function balanceOf(address owner) external view returns (uint256);
+        function totalSupply() external view returns (uint256);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+    }
+
+    interface IV2Pair {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
+    }
+
+    interface IAToken {
+        function UNDERLYING_ASSET_ADDRESS() external view returns (address);
+    }
+
+    interface ICToken {
+        function underlying() external view returns (address);
+        function exchangeRateStored() external view returns (uint256);
+    }
+}
+
+/// What kind of position a contract holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PositionKind {
+    LiquidityPool,
+    Staked,
+    Lending,
+}
+
+/// A StakingRewards style contract whose `balanceOf` is the staked amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StakingContract {
+    pub chain_id: u64,
+    pub name: &'static str,
+    pub address: Address,
+    /// Token deposited into the contract
+    pub staked_token: Address,
+}
+
+/// An underlying token amount of a position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnderlyingAmount {
+    pub token: Address,
+    pub symbol: String,
+    pub decimals: u8,
+    pub amount: U256,
+}
+
+impl UnderlyingAmount {
+    pub fn amount_f64(&self) -> f64 {
+        format_amount(self.amount, self.decimals).parse().unwrap_or(0.0)
+    }
+}
+
+/// A position of one account in one contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub chain_id: u64,
+    pub account: Address,
+    pub kind: PositionKind,
+    /// Protocol or pair name, e.g. `PLP` or `Aave`
+    pub protocol: String,
+    /// LP token, aToken, cToken or staking contract
+    pub contract: Address,
+    /// Display name, e.g. `PLP WPLS/HEX`
+    pub symbol: String,
+    /// Position token balance (LP tokens, aTokens, cTokens or staked amount)
+    pub balance: U256,
+    pub decimals: u8,
+    pub underlying: Vec<UnderlyingAmount>,
+    /// USD value of the underlying tokens, `None` until every one is priced
+    pub usd_value: Option<f64>,
+}
+
+impl Position {
+    /// Position token balance in whole units
+    pub fn balance_f64(&self) -> f64 {
+        format_amount(self.balance, self.decimals).parse().unwrap_or(0.0)
+    }
+
+    /// Value the underlying tokens with USD `prices`; `None` if any is unpriced
+    pub fn value(&self, prices: &HashMap<PriceKey, f64>) -> Option<f64> {
+        self.underlying
+            .iter()
+            .map(|u| {
+                prices
+                    .get(&(self.chain_id, u.token))
+                    .map(|price| u.amount_f64() * price)
+            })
+            .sum()
+    }
+}
+
+/// The account's share of one pair reserve
+pub fn lp_share(balance: U256, total_supply: U256, reserve: U256) -> U256 {
+    if total_supply.is_zero() {
+        return U256::ZERO;
+    }
+    reserve.saturating_mul(balance) / total_supply
+}
+
+/// Underlying amount of a cToken balance; exchange rates are scaled by 1e18
+pub fn ctoken_underlying(balance: U256, exchange_rate: U256) -> U256 {
+    balance.saturating_mul(exchange_rate) / U256::from(10u64.pow(18))
+}
+
+async fn eth_call<P: Provider, C: SolCall>(provider: &P, contract: Address, call: C) -> Result<C::Return> {
+    let request = TransactionRequest::default()
+        .to(contract)
+        .input(call.abi_encode().into());
+    let raw = provider.call(request).await.map_err(|e| NetworkError::RpcError {
+        message: format!("Position call to {contract} failed: {e}"),
+    })?;
+    C::abi_decode_returns(&raw).map_err(|e| {
+        NetworkError::RpcError {
+            message: format!("Failed to decode position response from {contract}: {e}"),
+        }
+        .into()
+    })
+}
+
+/// Finds and resolves the positions of accounts on one chain
+pub struct PositionTracker<P> {
+    provider: P,
+    chain_id: u64,
+    staking: Vec<StakingContract>,
+}
+
+impl<P: Provider> PositionTracker<P> {
+    pub fn new(provider: P, chain_id: u64) -> Self {
+        Self {
+            provider,
+            chain_id,
+            staking: Vec::new(),
+        }
+    }
+
+    /// Also check `contract` for staked balances; ignored on other chains
+    pub fn with_staking_contract(mut self, contract: StakingContract) -> Self {
+        if contract.chain_id == self.chain_id {
+            self.staking.push(contract);
+        }
+        self
+    }
+
+    /// Positions of `account` among the `tokens` it holds and in the staking contracts
+    ///
+    /// Tokens that are not positions, and contracts that fail to answer, are
+    /// skipped.
+    pub async fn positions(&self, account: Address, tokens: &[Address]) -> Vec<Position> {
+        let mut positions = Vec::new();
+        for token in tokens {
+            match self.detect(account, *token).await {
+                Ok(Some(position)) => positions.push(position),
+                Ok(None) => {}
+                Err(e) => tracing::debug!("Skipping {} in position scan: {}", token, e),
+            }
+        }
+        for contract in &self.staking {
+            match self.staked(account, contract).await {
+                Ok(Some(position)) => positions.push(position),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to read {} stake: {}", contract.name, e),
+            }
+        }
+        positions
+    }
+
+    /// Classify `token` as an aToken, cToken or LP token held by `account`
+    pub async fn detect(&self, account: Address, token: Address) -> Result<Option<Position>> {
+        let balance = eth_call(&self.provider, token, IPositionToken::balanceOfCall { owner: account }).await?;
+        if balance.is_zero() {
+            return Ok(None);
+        }
+        let (symbol, decimals) = self.metadata(token).await;
+        let position = |kind, protocol: &str, symbol: String, underlying| Position {
+            chain_id: self.chain_id,
+            account,
+            kind,
+            protocol: protocol.to_string(),
+            contract: token,
+            symbol,
+            balance,
+            decimals,
+            underlying,
+            usd_value: None,
+        };
+
+        if let Ok(asset) = eth_call(&self.provider, token, IAToken::UNDERLYING_ASSET_ADDRESSCall {}).await {
+            let underlying = self.underlying(asset, balance).await;
+            return Ok(Some(position(PositionKind::Lending, "Aave", symbol, vec![underlying])));
+        }
+
+        if let Ok(asset) = eth_call(&self.provider, token, ICToken::underlyingCall {}).await {
+            let rate = eth_call(&self.provider, token, ICToken::exchangeRateStoredCall {}).await?;
+            let underlying = self.underlying(asset, ctoken_underlying(balance, rate)).await;
+            return Ok(Some(position(
+                PositionKind::Lending,
+                "Compound",
+                symbol,
+                vec![underlying],
+            )));
+        }
+
+        let Ok(token0) = eth_call(&self.provider, token, IV2Pair::token0Call {}).await else {
+            return Ok(None);
+        };
+        let token1 = eth_call(&self.provider, token, IV2Pair::token1Call {}).await?;
+        let reserves = eth_call(&self.provider, token, IV2Pair::getReservesCall {}).await?;
+        let total_supply = eth_call(&self.provider, token, IPositionToken::totalSupplyCall {}).await?;
+
+        let amount0 = lp_share(balance, total_supply, U256::from(reserves.reserve0));
+        let amount1 = lp_share(balance, total_supply, U256::from(reserves.reserve1));
+        let underlying = vec![
+            self.underlying(token0, amount0).await,
+            self.underlying(token1, amount1).await,
+        ];
+        let name = format!("{symbol} {}/{}", underlying[0].symbol, underlying[1].symbol);
+        Ok(Some(position(PositionKind::LiquidityPool, &symbol, name, underlying)))
+    }
+
+    async fn staked(&self, account: Address, contract: &StakingContract) -> Result<Option<Position>> {
+        let balance = eth_call(
+            &self.provider,
+            contract.address,
+            IPositionToken::balanceOfCall { owner: account },
+        )
+        .await?;
+        if balance.is_zero() {
+            return Ok(None);
+        }
+        let underlying = self.underlying(contract.staked_token, balance).await;
+        Ok(Some(Position {
+            chain_id: self.chain_id,
+            account,
+            kind: PositionKind::Staked,
+            protocol: contract.name.to_string(),
+            contract: contract.address,
+            symbol: format!("Staked {}", underlying.symbol),
+            balance,
+            decimals: underlying.decimals,
+            underlying: vec![underlying],
+            usd_value: None,
+        }))
+    }
+
+    async fn underlying(&self, token: Address, amount: U256) -> UnderlyingAmount {
+        let (symbol, decimals) = self.metadata(token).await;
+        UnderlyingAmount {
+            token,
+            symbol,
+            decimals,
+            amount,
+        }
+    }
+
+    /// Symbol and decimals, `?` and 18 when the token does not say
+    async fn metadata(&self, token: Address) -> (String, u8) {
+        let symbol = eth_call(&self.provider, token, IPositionToken::symbolCall {})
+            .await
+            .unwrap_or_else(|_| "?".to_string());
+        let decimals = eth_call(&self.provider, token, IPositionToken::decimalsCall {})
+            .await
+            .unwrap_or(18);
+        (symbol, decimals)
+    }
+}
+
+/// Set `usd_value` of each position from the oracle's prices of its underlying tokens
+pub async fn price_positions(positions: &mut [Position], oracle: &MultiSourcePriceOracle) -> Result<()> {
+    let keys: Vec<PriceKey> = positions
+        .iter()
+        .flat_map(|p| p.underlying.iter().map(move |u| (p.chain_id, u.token)))
+        .collect();
+    let prices: HashMap<PriceKey, f64> = oracle
+        .get_prices(keys)
+        .await?
+        .into_iter()
+        .map(|(key, price)| (key, price.price_usd))
+        .collect();
+    for position in positions.iter_mut() {
+        position.usd_value = position.value(&prices);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ether(amount: u64) -> U256 {
+        U256::from(amount) * U256::from(10u64.pow(18))
+    }
+
+    #[test]
+    fn test_share_math() {
+        // 10% of a pair holding 1,000 and 50 tokens
+        assert_eq!(lp_share(ether(10), ether(100), ether(1_000)), ether(100));
+        assert_eq!(lp_share(ether(10), ether(100), ether(50)), ether(5));
+        assert_eq!(lp_share(ether(10), U256::ZERO, ether(50)), U256::ZERO);
+
+        // 500 cTokens at 0.02 underlying each
+        let rate = U256::from(2) * U256::from(10u64.pow(16));
+        assert_eq!(ctoken_underlying(ether(500), rate), ether(10));
+    }
+
+    #[test]
+    fn test_position_value() {
+        let wpls = Address::repeat_byte(1);
+        let hex = Address::repeat_byte(2);
+        let underlying = |token, symbol: &str, amount| UnderlyingAmount {
+            token,
+            symbol: symbol.to_string(),
+            decimals: 18,
+            amount,
+        };
+        let position = Position {
+            chain_id: 369,
+            account: Address::repeat_byte(9),
+            kind: PositionKind::LiquidityPool,
+            protocol: "PLP".to_string(),
+            contract: Address::repeat_byte(3),
+            symbol: "PLP WPLS/HEX".to_string(),
+            balance: ether(1),
+            decimals: 18,
+            underlying: vec![
+                underlying(wpls, "WPLS", ether(1_000)),
+                underlying(hex, "HEX", ether(20)),
+            ],
+            usd_value: None,
+        };
+
+        let mut prices = HashMap::from([((369, wpls), 0.00005)]);
+        assert_eq!(position.value(&prices), None);
+        prices.insert((369, hex), 0.01);
+        let value = position.value(&prices).unwrap();
+        assert!((value - 0.25).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,542 @@
+//! DEX aggregator quoters
+//!
+//! HTTP clients for the 0x, 1inch and ParaSwap swap APIs. Quotes use each
+//! API's price endpoint; building a swap requests fresh calldata with the
+//! user's slippage, so the transaction always reflects current liquidity.
+
+use super::{
+    aggregator_request, parse_api_amount, QuoteParams, QuoteRoute, Slippage, SwapError, SwapQuote, SwapQuoter,
+    SwapSource, SwapTransaction,
+};
+use crate::error::{NetworkError, Result};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient, HttpRequest};
+use alloy::primitives::{Address, Bytes, U256};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Send a request and decode its JSON body
+async fn fetch_json<T: DeserializeOwned>(source: SwapSource, request: HttpRequest) -> Result<T> {
+    egress().check(
+        EgressCategory::Swap,
+        request.url(),
+        &format!("quote swap via {}", source.name()),
+    )?;
+
+    let response = request
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| NetworkError::RpcError {
+            message: format!("Failed to reach {}: {e}", source.name()),
+        })?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(NetworkError::RpcError {
+            message: format!("{} API error {status}: {body}", source.name()),
+        }
+        .into());
+    }
+
+    response.json().await.map_err(|e| {
+        NetworkError::RpcError {
+            message: format!("Failed to parse {} response: {e}", source.name()),
+        }
+        .into()
+    })
+}
+
+fn optional_gas(value: Option<&str>) -> Option<u64> {
+    value.and_then(|gas| gas.parse().ok())
+}
+
+fn quote_from(params: &QuoteParams, source: SwapSource, buy_amount: U256) -> SwapQuote {
+    SwapQuote {
+        source,
+        chain_id: params.chain_id,
+        sell_token: params.sell_token.clone(),
+        buy_token: params.buy_token.clone(),
+        sell_amount: params.sell_amount,
+        buy_amount,
+        gas_estimate: None,
+        allowance_target: None,
+        route: QuoteRoute::Aggregator { price_route: None },
+        taker: params.taker,
+        quoted_at: Utc::now(),
+    }
+}
+
+/// Calldata returned by an aggregator's swap endpoint
+#[derive(Debug, Deserialize)]
+struct AggregatorTx {
+    to: Address,
+    data: Bytes,
+    #[serde(default)]
+    value: Option<String>,
+    #[serde(default)]
+    gas: Option<serde_json::Value>,
+}
+
+impl AggregatorTx {
+    fn value(&self, source: SwapSource) -> Result<U256> {
+        match self.value.as_deref() {
+            None | Some("") => Ok(U256::ZERO),
+            Some(value) => parse_api_amount(source, value),
+        }
+    }
+
+    /// Gas limit, which APIs return as either a number or a decimal string
+    fn gas(&self) -> Option<u64> {
+        match &self.gas {
+            Some(serde_json::Value::Number(gas)) => gas.as_u64(),
+            Some(serde_json::Value::String(gas)) => gas.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// 0x
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ZeroExPrice {
+    #[serde(default)]
+    liquidity_available: bool,
+    #[serde(default)]
+    buy_amount: Option<String>,
+    #[serde(default)]
+    min_buy_amount: Option<String>,
+    #[serde(default)]
+    gas: Option<String>,
+    #[serde(default)]
+    issues: Option<ZeroExIssues>,
+    #[serde(default)]
+    transaction: Option<AggregatorTx>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZeroExIssues {
+    #[serde(default)]
+    allowance: Option<ZeroExAllowance>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZeroExAllowance {
+    spender: Address,
+}
+
+/// 0x Swap API v2 (AllowanceHolder flow)
+pub struct ZeroExQuoter {
+    client: HttpClient,
+    base_url: String,
+    api_key: String,
+}
+
+impl ZeroExQuoter {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: shared_client(),
+            base_url: "https://api.0x.org".to_string(),
+            api_key,
+        }
+    }
+
+    async fn request(&self, endpoint: &str, params: &QuoteParams, slippage: Option<Slippage>) -> Result<ZeroExPrice> {
+        let mut query = vec![
+            ("chainId", params.chain_id.to_string()),
+            ("sellToken", params.sell_token.api_address().to_string()),
+            ("buyToken", params.buy_token.api_address().to_string()),
+            ("sellAmount", params.sell_amount.to_string()),
+            ("taker", params.taker.to_string()),
+        ];
+        if let Some(slippage) = slippage {
+            query.push(("slippageBps", slippage.bps().to_string()));
+        }
+
+        let request = self
+            .client
+            .get(&format!("{}/swap/allowance-holder/{endpoint}", self.base_url))
+            .header("0x-api-key", &self.api_key)
+            .header("0x-version", "v2")
+            .query(&query);
+        fetch_json(SwapSource::ZeroEx, request).await
+    }
+
+    fn spender(price: &ZeroExPrice) -> Option<Address> {
+        price
+            .issues
+            .as_ref()
+            .and_then(|issues| issues.allowance.as_ref())
+            .map(|allowance| allowance.spender)
+    }
+}
+
+#[async_trait::async_trait]
+impl SwapQuoter for ZeroExQuoter {
+    fn source(&self) -> SwapSource {
+        SwapSource::ZeroEx
+    }
+
+    fn supports_chain(&self, chain_id: u64) -> bool {
+        matches!(chain_id, 1 | 10 | 56 | 137 | 8453 | 42161 | 43114 | 59144 | 81457)
+    }
+
+    async fn quote(&self, params: &QuoteParams) -> Result<Option<SwapQuote>> {
+        let price = self.request("price", params, None).await?;
+        let Some(buy_amount) = price.buy_amount.as_deref().filter(|_| price.liquidity_available) else {
+            return Ok(None);
+        };
+
+        let mut quote = quote_from(params, self.source(), parse_api_amount(self.source(), buy_amount)?);
+        quote.gas_estimate = optional_gas(price.gas.as_deref());
+        quote.allowance_target = Self::spender(&price);
+        Ok(Some(quote))
+    }
+
+    async fn build(&self, quote: &SwapQuote, slippage: Slippage) -> Result<SwapTransaction> {
+        let source = self.source();
+        let response = self.request("quote", &quote.params(), Some(slippage)).await?;
+        let tx = response
+            .transaction
+            .as_ref()
+            .ok_or_else(|| SwapError::InvalidResponse {
+                source_name: source.name().to_string(),
+                reason: "no transaction in quote".to_string(),
+            })?;
+
+        let min_buy_amount = match response.min_buy_amount.as_deref() {
+            Some(amount) => parse_api_amount(source, amount)?,
+            None => slippage.min_amount(quote.buy_amount),
+        };
+
+        Ok(SwapTransaction {
+            request: aggregator_request(quote.taker, tx.to, tx.data.clone(), tx.value(source)?, tx.gas()),
+            min_buy_amount,
+            approval_spender: Self::spender(&response),
+            quote: quote.clone(),
+        })
+    }
+}
+
+// ============================================================================
+// 1inch
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OneInchQuote {
+    dst_amount: String,
+    #[serde(default)]
+    gas: Option<u64>,
+    #[serde(default)]
+    tx: Option<AggregatorTx>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OneInchSpender {
+    address: Address,
+}
+
+/// 1inch Swap API v6
+pub struct OneInchQuoter {
+    client: HttpClient,
+    base_url: String,
+    api_key: String,
+}
+
+impl OneInchQuoter {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: shared_client(),
+            base_url: "https://api.1inch.dev/swap/v6.0".to_string(),
+            api_key,
+        }
+    }
+
+    fn get(&self, chain_id: u64, endpoint: &str) -> HttpRequest {
+        self.client
+            .get(&format!("{}/{chain_id}/{endpoint}", self.base_url))
+            .bearer_auth(&self.api_key)
+    }
+
+    async fn spender(&self, params: &QuoteParams) -> Result<Option<Address>> {
+        if params.sell_token.is_native {
+            return Ok(None);
+        }
+        let spender: OneInchSpender = fetch_json(self.source(), self.get(params.chain_id, "approve/spender")).await?;
+        Ok(Some(spender.address))
+    }
+}
+
+#[async_trait::async_trait]
+impl SwapQuoter for OneInchQuoter {
+    fn source(&self) -> SwapSource {
+        SwapSource::OneInch
+    }
+
+    fn supports_chain(&self, chain_id: u64) -> bool {
+        matches!(chain_id, 1 | 10 | 56 | 100 | 137 | 324 | 8453 | 42161 | 43114 | 59144)
+    }
+
+    async fn quote(&self, params: &QuoteParams) -> Result<Option<SwapQuote>> {
+        let request = self.get(params.chain_id, "quote").query(&[
+            ("src", params.sell_token.api_address().to_string()),
+            ("dst", params.buy_token.api_address().to_string()),
+            ("amount", params.sell_amount.to_string()),
+            ("includeGas", "true".to_string()),
+        ]);
+        let response: OneInchQuote = fetch_json(self.source(), request).await?;
+
+        let mut quote = quote_from(
+            params,
+            self.source(),
+            parse_api_amount(self.source(), &response.dst_amount)?,
+        );
+        quote.gas_estimate = response.gas;
+        quote.allowance_target = self.spender(params).await?;
+        Ok(Some(quote))
+    }
+
+    async fn build(&self, quote: &SwapQuote, slippage: Slippage) -> Result<SwapTransaction> {
+        let source = self.source();
+        let request = self.get(quote.chain_id, "swap").query(&[
+            ("src", quote.sell_token.api_address().to_string()),
+            ("dst", quote.buy_token.api_address().to_string()),
+            ("amount", quote.sell_amount.to_string()),
+            ("from", quote.taker.to_string()),
+            ("origin", quote.taker.to_string()),
+            ("slippage", slippage.percent().to_string()),
+            ("disableEstimate", "true".to_string()),
+        ]);
+        let response: OneInchQuote = fetch_json(source, request).await?;
+        let tx = response.tx.as_ref().ok_or_else(|| SwapError::InvalidResponse {
+            source_name: source.name().to_string(),
+            reason: "no transaction in swap response".to_string(),
+        })?;
+
+        let buy_amount = parse_api_amount(source, &response.dst_amount)?;
+        Ok(SwapTransaction {
+            request: aggregator_request(quote.taker, tx.to, tx.data.clone(), tx.value(source)?, tx.gas()),
+            min_buy_amount: slippage.min_amount(buy_amount),
+            approval_spender: quote.allowance_target,
+            quote: quote.clone(),
+        })
+    }
+}
+
+// ============================================================================
+// ParaSwap
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ParaSwapPrices {
+    price_route: serde_json::Value,
+}
+
+/// ParaSwap (Velora) market API
+pub struct ParaSwapQuoter {
+    client: HttpClient,
+    base_url: String,
+    partner: String,
+}
+
+impl ParaSwapQuoter {
+    pub fn new() -> Self {
+        Self {
+            client: shared_client(),
+            base_url: "https://api.paraswap.io".to_string(),
+            partner: crate::APP_NAME.to_lowercase(),
+        }
+    }
+
+    /// Output amount, gas and spender from a `priceRoute`
+    fn parse_route(route: &serde_json::Value) -> Result<(U256, Option<u64>, Option<Address>)> {
+        let source = SwapSource::ParaSwap;
+        let dest_amount =
+            route
+                .get("destAmount")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| SwapError::InvalidResponse {
+                    source_name: source.name().to_string(),
+                    reason: "missing destAmount".to_string(),
+                })?;
+        let gas = optional_gas(route.get("gasCost").and_then(|v| v.as_str()));
+        // v5 routes approve the TokenTransferProxy, v6 routes approve Augustus itself
+        let spender = ["tokenTransferProxy", "contractAddress"]
+            .iter()
+            .find_map(|key| route.get(*key).and_then(|v| v.as_str()))
+            .and_then(|address| address.parse().ok());
+        Ok((parse_api_amount(source, dest_amount)?, gas, spender))
+    }
+}
+
+impl Default for ParaSwapQuoter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SwapQuoter for ParaSwapQuoter {
+    fn source(&self) -> SwapSource {
+        SwapSource::ParaSwap
+    }
+
+    fn supports_chain(&self, chain_id: u64) -> bool {
+        matches!(chain_id, 1 | 10 | 56 | 100 | 137 | 1101 | 8453 | 42161 | 43114)
+    }
+
+    async fn quote(&self, params: &QuoteParams) -> Result<Option<SwapQuote>> {
+        let request = self.client.get(&format!("{}/prices", self.base_url)).query(&[
+            ("srcToken", params.sell_token.api_address().to_string()),
+            ("srcDecimals", params.sell_token.decimals.to_string()),
+            ("destToken", params.buy_token.api_address().to_string()),
+            ("destDecimals", params.buy_token.decimals.to_string()),
+            ("amount", params.sell_amount.to_string()),
+            ("side", "SELL".to_string()),
+            ("network", params.chain_id.to_string()),
+            ("userAddress", params.taker.to_string()),
+            ("partner", self.partner.clone()),
+        ]);
+        let prices: ParaSwapPrices = fetch_json(self.source(), request).await?;
+        let (buy_amount, gas, spender) = Self::parse_route(&prices.price_route)?;
+
+        let mut quote = quote_from(params, self.source(), buy_amount);
+        quote.gas_estimate = gas;
+        quote.allowance_target = spender.filter(|_| !params.sell_token.is_native);
+        quote.route = QuoteRoute::Aggregator {
+            price_route: Some(prices.price_route),
+        };
+        Ok(Some(quote))
+    }
+
+    async fn build(&self, quote: &SwapQuote, slippage: Slippage) -> Result<SwapTransaction> {
+        let source = self.source();
+        let QuoteRoute::Aggregator {
+            price_route: Some(price_route),
+        } = &quote.route
+        else {
+            return Err(SwapError::InvalidResponse {
+                source_name: source.name().to_string(),
+                reason: "quote has no price route".to_string(),
+            }
+            .into());
+        };
+
+        let body = serde_json::json!({
+            "srcToken": quote.sell_token.api_address().to_string(),
+            "srcDecimals": quote.sell_token.decimals,
+            "destToken": quote.buy_token.api_address().to_string(),
+            "destDecimals": quote.buy_token.decimals,
+            "srcAmount": quote.sell_amount.to_string(),
+            "slippage": slippage.bps(),
+            "priceRoute": price_route,
+            "userAddress": quote.taker.to_string(),
+            "partner": self.partner,
+        });
+        let request = self
+            .client
+            .post(&format!("{}/transactions/{}", self.base_url, quote.chain_id))
+            .query(&[("ignoreChecks", "true")])
+            .json(&body);
+        let tx: AggregatorTx = fetch_json(source, request).await?;
+
+        Ok(SwapTransaction {
+            request: aggregator_request(quote.taker, tx.to, tx.data.clone(), tx.value(source)?, tx.gas()),
+            min_buy_amount: slippage.min_amount(quote.buy_amount),
+            approval_spender: quote.allowance_target,
+            quote: quote.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zero_ex_quote() {
+        let json = r#"{
+            "liquidityAvailable": true,
+            "buyAmount": "2500000000",
+            "minBuyAmount": "2487500000",
+            "gas": "180000",
+            "issues": { "allowance": { "actual": "0", "spender": "0x0000000000001ff3684f28c67538d4d072c22734" } },
+            "transaction": {
+                "to": "0x0000000000001ff3684f28c67538d4d072c22734",
+                "data": "0xdeadbeef",
+                "gas": "200000",
+                "value": "0"
+            }
+        }"#;
+        let price: ZeroExPrice = serde_json::from_str(json).unwrap();
+        assert!(price.liquidity_available);
+        assert_eq!(price.buy_amount.as_deref(), Some("2500000000"));
+        assert!(ZeroExQuoter::spender(&price).is_some());
+
+        let tx = price.transaction.unwrap();
+        assert_eq!(tx.gas(), Some(200_000));
+        assert_eq!(tx.value(SwapSource::ZeroEx).unwrap(), U256::ZERO);
+        assert_eq!(tx.data, Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]));
+    }
+
+    #[test]
+    fn test_parse_one_inch_swap() {
+        let json = r#"{
+            "dstAmount": "998877",
+            "tx": {
+                "from": "0x1111111111111111111111111111111111111111",
+                "to": "0x111111125421ca6dc452d289314280a0f8842a65",
+                "data": "0x07ed2379",
+                "value": "1000000000000000000",
+                "gas": 0,
+                "gasPrice": "1000000000"
+            }
+        }"#;
+        let quote: OneInchQuote = serde_json::from_str(json).unwrap();
+        assert_eq!(quote.dst_amount, "998877");
+        let tx = quote.tx.unwrap();
+        assert_eq!(
+            tx.value(SwapSource::OneInch).unwrap(),
+            U256::from(1_000_000_000_000_000_000u64)
+        );
+        assert_eq!(tx.gas(), Some(0));
+    }
+
+    #[test]
+    fn test_parse_paraswap_route() {
+        let route = serde_json::json!({
+            "destAmount": "123456",
+            "gasCost": "150000",
+            "tokenTransferProxy": "0x216b4b4ba9f3e719726886d34a177484278bfcae",
+            "contractAddress": "0xdef171fe48cf0115b1d80b88dc8eab59176fee57"
+        });
+        let (amount, gas, spender) = ParaSwapQuoter::parse_route(&route).unwrap();
+        assert_eq!(amount, U256::from(123_456));
+        assert_eq!(gas, Some(150_000));
+        assert_eq!(
+            spender,
+            Some("0x216b4b4ba9f3e719726886d34a177484278bfcae".parse().unwrap())
+        );
+
+        assert!(ParaSwapQuoter::parse_route(&serde_json::json!({})).is_err());
+        assert!(ParaSwapQuoter::parse_route(&serde_json::json!({ "destAmount": "12x" })).is_err());
+    }
+
+    #[test]
+    fn test_supported_chains() {
+        assert!(ZeroExQuoter::new(String::new()).supports_chain(1));
+        assert!(!ZeroExQuoter::new(String::new()).supports_chain(369));
+        assert!(OneInchQuoter::new(String::new()).supports_chain(56));
+        assert!(!ParaSwapQuoter::new().supports_chain(943));
+    }
+}
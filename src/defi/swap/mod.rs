@@ -0,0 +1,723 @@
+//! Token Swaps
+//!
+//! Quotes a swap from every [`SwapQuoter`] that supports the current chain and
+//! builds the transaction for the best one. Quoters come in two flavours:
+//!
+//! - **Aggregators** ([`aggregators`]): 0x, 1inch and ParaSwap HTTP APIs. The
+//!   quote is indicative; [`SwapRouter::build_swap_tx`] fetches the final
+//!   calldata with the user's slippage.
+//! - **Routers** ([`uniswap`]): Uniswap V2 (and forks such as PulseX) and
+//!   Uniswap V3, quoted with `eth_call` and encoded locally.
+//!
+//! Before signing, [`SwapRouter::assess_risk`] checks a quote for high price
+//! impact, thin pools and sandwich-prone slippage, see [`risk`].
+//! [`crate::tokens::preflight::TokenPreflight::check_swap`] checks the tokens
+//! themselves for transfer taxes, pausing and blacklists.
+//!
+//! Executed swaps are kept in a [`SwapHistory`] next to the other wallet
+//! configuration files.
+
+pub mod aggregators;
+pub mod risk;
+pub mod uniswap;
+
+use crate::error::{ConfigurationError, Result, VaughanError};
+use crate::tokens::TokenInfo;
+use alloy::primitives::{address, Address, Bytes, TxHash, U256};
+use alloy::rpc::types::TransactionRequest;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+pub use aggregators::{OneInchQuoter, ParaSwapQuoter, ZeroExQuoter};
+pub use risk::{PoolReserves, SwapRiskConfig, SwapRiskReport, SwapWarning};
+pub use uniswap::{UniswapV2Quoter, UniswapV3Quoter};
+
+/// Address aggregator APIs use for the chain's native currency
+pub const NATIVE_TOKEN_SENTINEL: Address = address!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+
+/// How long a quote may be used to build a transaction
+pub const QUOTE_TTL: Duration = Duration::from_secs(60);
+
+/// Default slippage tolerance: 0.5%
+pub const DEFAULT_SLIPPAGE_BPS: u16 = 50;
+
+/// Highest slippage tolerance accepted: 50%
+pub const MAX_SLIPPAGE_BPS: u16 = 5_000;
+
+/// Errors preparing a swap
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SwapError {
+    #[error("Cannot swap a token for itself")]
+    SameToken,
+
+    #[error("Swap amount must be greater than zero")]
+    ZeroAmount,
+
+    #[error("Tokens are on different networks ({0} and {1})")]
+    ChainMismatch(u64, u64),
+
+    #[error("Slippage of {0} bps is outside the allowed range")]
+    InvalidSlippage(u16),
+
+    #[error("No swap route found from {sell} to {buy}")]
+    NoRoute { sell: String, buy: String },
+
+    #[error("Quote from {0} has expired, fetch a new one")]
+    QuoteExpired(String),
+
+    #[error("{source_name} returned an unusable response: {reason}")]
+    InvalidResponse { source_name: String, reason: String },
+}
+
+impl From<SwapError> for VaughanError {
+    fn from(error: SwapError) -> Self {
+        VaughanError::ValidationError(error.to_string())
+    }
+}
+
+/// Slippage tolerance in basis points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Slippage(u16);
+
+impl Slippage {
+    pub fn from_bps(bps: u16) -> std::result::Result<Self, SwapError> {
+        if bps == 0 || bps > MAX_SLIPPAGE_BPS {
+            return Err(SwapError::InvalidSlippage(bps));
+        }
+        Ok(Self(bps))
+    }
+
+    pub fn bps(&self) -> u16 {
+        self.0
+    }
+
+    /// Slippage as a percentage, as 1inch expects it
+    pub fn percent(&self) -> f64 {
+        f64::from(self.0) / 100.0
+    }
+
+    /// Smallest acceptable output for an expected `amount`
+    pub fn min_amount(&self, amount: U256) -> U256 {
+        amount * U256::from(10_000 - u32::from(self.0)) / U256::from(10_000)
+    }
+}
+
+impl Default for Slippage {
+    fn default() -> Self {
+        Self(DEFAULT_SLIPPAGE_BPS)
+    }
+}
+
+/// Where a quote came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapSource {
+    ZeroEx,
+    OneInch,
+    ParaSwap,
+    UniswapV2,
+    UniswapV3,
+}
+
+impl SwapSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ZeroEx => "0x",
+            Self::OneInch => "1inch",
+            Self::ParaSwap => "ParaSwap",
+            Self::UniswapV2 => "Uniswap V2",
+            Self::UniswapV3 => "Uniswap V3",
+        }
+    }
+}
+
+/// Token side of a swap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapToken {
+    pub address: Address,
+    pub symbol: String,
+    pub decimals: u8,
+    pub is_native: bool,
+}
+
+impl SwapToken {
+    /// Address to send to aggregator APIs
+    pub fn api_address(&self) -> Address {
+        if self.is_native {
+            NATIVE_TOKEN_SENTINEL
+        } else {
+            self.address
+        }
+    }
+}
+
+impl From<&TokenInfo> for SwapToken {
+    fn from(token: &TokenInfo) -> Self {
+        Self {
+            address: token.address,
+            symbol: token.symbol.clone(),
+            decimals: token.decimals,
+            is_native: token.is_native,
+        }
+    }
+}
+
+/// What a quoter is asked to price
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteParams {
+    pub chain_id: u64,
+    pub sell_token: SwapToken,
+    pub buy_token: SwapToken,
+    /// Amount of `sell_token` in its smallest unit
+    pub sell_amount: U256,
+    /// Account that will execute the swap
+    pub taker: Address,
+}
+
+/// Source-specific data needed to build the transaction later
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuoteRoute {
+    /// Aggregator quote; calldata is fetched when building. ParaSwap needs its
+    /// price route echoed back.
+    Aggregator { price_route: Option<serde_json::Value> },
+    /// Uniswap V2 router path, wrapped-native substituted for native legs
+    V2 { router: Address, path: Vec<Address> },
+    /// Uniswap V3 single pool hop
+    V3 { router: Address, fee: u32 },
+}
+
+/// A priced swap
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapQuote {
+    pub source: SwapSource,
+    pub chain_id: u64,
+    pub sell_token: SwapToken,
+    pub buy_token: SwapToken,
+    pub sell_amount: U256,
+    /// Expected output in `buy_token`'s smallest unit, before slippage
+    pub buy_amount: U256,
+    pub gas_estimate: Option<u64>,
+    /// Contract that must be approved to spend an ERC-20 sell token
+    pub allowance_target: Option<Address>,
+    pub route: QuoteRoute,
+    pub taker: Address,
+    pub quoted_at: DateTime<Utc>,
+}
+
+impl SwapQuote {
+    pub fn is_expired(&self) -> bool {
+        let age = Utc::now().signed_duration_since(self.quoted_at);
+        age.to_std().is_ok_and(|age| age > QUOTE_TTL)
+    }
+
+    fn params(&self) -> QuoteParams {
+        QuoteParams {
+            chain_id: self.chain_id,
+            sell_token: self.sell_token.clone(),
+            buy_token: self.buy_token.clone(),
+            sell_amount: self.sell_amount,
+            taker: self.taker,
+        }
+    }
+}
+
+/// A ready-to-sign swap
+#[derive(Debug, Clone)]
+pub struct SwapTransaction {
+    /// Transaction without nonce or fees
+    pub request: TransactionRequest,
+    /// Output guaranteed by the calldata
+    pub min_buy_amount: U256,
+    /// Spender that needs an allowance of at least `sell_amount` first
+    pub approval_spender: Option<Address>,
+    pub quote: SwapQuote,
+}
+
+/// A source of swap quotes
+#[async_trait::async_trait]
+pub trait SwapQuoter: Send + Sync {
+    fn source(&self) -> SwapSource;
+
+    fn supports_chain(&self, chain_id: u64) -> bool;
+
+    /// Price a swap; `Ok(None)` when the source has no route
+    async fn quote(&self, params: &QuoteParams) -> Result<Option<SwapQuote>>;
+
+    /// Build the transaction for a quote this quoter produced
+    async fn build(&self, quote: &SwapQuote, slippage: Slippage) -> Result<SwapTransaction>;
+
+    /// Reserves of each pool a quote this quoter produced goes through
+    ///
+    /// `Ok(None)` when the route's pools aren't readable, as for aggregators.
+    async fn pool_reserves(&self, _quote: &SwapQuote) -> Result<Option<Vec<PoolReserves>>> {
+        Ok(None)
+    }
+}
+
+/// Quotes swaps across all configured sources for one account and chain
+pub struct SwapRouter {
+    chain_id: u64,
+    taker: Address,
+    quoters: Vec<Box<dyn SwapQuoter>>,
+}
+
+impl SwapRouter {
+    pub fn new(chain_id: u64, taker: Address) -> Self {
+        Self {
+            chain_id,
+            taker,
+            quoters: Vec::new(),
+        }
+    }
+
+    /// Add a quote source; sources that don't support the chain are ignored
+    pub fn with_quoter(mut self, quoter: impl SwapQuoter + 'static) -> Self {
+        if quoter.supports_chain(self.chain_id) {
+            self.quoters.push(Box::new(quoter));
+        }
+        self
+    }
+
+    pub fn sources(&self) -> Vec<SwapSource> {
+        self.quoters.iter().map(|quoter| quoter.source()).collect()
+    }
+
+    /// All quotes for selling `amount` of `sell_token`, best output first
+    ///
+    /// Failing sources are logged and skipped.
+    pub async fn get_quotes(
+        &self,
+        sell_token: &TokenInfo,
+        buy_token: &TokenInfo,
+        amount: U256,
+    ) -> Result<Vec<SwapQuote>> {
+        let params = self.params(sell_token, buy_token, amount)?;
+
+        let results = futures_util::future::join_all(self.quoters.iter().map(|quoter| quoter.quote(&params))).await;
+        let mut quotes = Vec::new();
+        for (quoter, result) in self.quoters.iter().zip(results) {
+            match result {
+                Ok(Some(quote)) => quotes.push(quote),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("{} quote failed: {}", quoter.source().name(), e),
+            }
+        }
+
+        quotes.sort_by_key(|quote| std::cmp::Reverse(quote.buy_amount));
+        Ok(quotes)
+    }
+
+    /// Best quote for selling `amount` of `sell_token` for `buy_token`
+    pub async fn get_quote(&self, sell_token: &TokenInfo, buy_token: &TokenInfo, amount: U256) -> Result<SwapQuote> {
+        self.get_quotes(sell_token, buy_token, amount)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                SwapError::NoRoute {
+                    sell: sell_token.symbol.clone(),
+                    buy: buy_token.symbol.clone(),
+                }
+                .into()
+            })
+    }
+
+    /// Build the swap transaction for `quote` with the given slippage tolerance
+    pub async fn build_swap_tx(&self, quote: &SwapQuote, slippage: Slippage) -> Result<SwapTransaction> {
+        if quote.is_expired() {
+            return Err(SwapError::QuoteExpired(quote.source.name().to_string()).into());
+        }
+
+        self.quoter(quote.source)?.build(quote, slippage).await
+    }
+
+    /// Check `quote` for price impact, thin pools and sandwich risk at `slippage`
+    ///
+    /// The mid-price comes from the route's pool reserves when the quoter can
+    /// read them, otherwise from quoting a small fraction of the amount.
+    pub async fn assess_risk(
+        &self,
+        quote: &SwapQuote,
+        slippage: Slippage,
+        config: &SwapRiskConfig,
+    ) -> Result<SwapRiskReport> {
+        let quoter = self.quoter(quote.source)?;
+        let pools = quoter.pool_reserves(quote).await.unwrap_or_else(|e| {
+            tracing::debug!("Pool reserves unavailable from {}: {}", quote.source.name(), e);
+            None
+        });
+
+        let (mid_buy_amount, pool_share_bps) = match pools {
+            Some(pools) => {
+                let (mid, share) = risk::mid_price_through(quote.sell_amount, &pools);
+                (Some(mid), Some(share))
+            }
+            None => (probe_mid_price(quoter, quote).await, None),
+        };
+        Ok(risk::assess(quote, mid_buy_amount, pool_share_bps, slippage, config))
+    }
+
+    fn quoter(&self, source: SwapSource) -> Result<&dyn SwapQuoter> {
+        self.quoters
+            .iter()
+            .find(|quoter| quoter.source() == source)
+            .map(|quoter| &**quoter)
+            .ok_or_else(|| VaughanError::NotFound(format!("Swap source {}", source.name())))
+    }
+
+    fn params(&self, sell_token: &TokenInfo, buy_token: &TokenInfo, amount: U256) -> Result<QuoteParams> {
+        if sell_token.chain_id != buy_token.chain_id {
+            return Err(SwapError::ChainMismatch(sell_token.chain_id, buy_token.chain_id).into());
+        }
+        if sell_token.address == buy_token.address {
+            return Err(SwapError::SameToken.into());
+        }
+        if amount.is_zero() {
+            return Err(SwapError::ZeroAmount.into());
+        }
+
+        Ok(QuoteParams {
+            chain_id: self.chain_id,
+            sell_token: sell_token.into(),
+            buy_token: buy_token.into(),
+            sell_amount: amount,
+            taker: self.taker,
+        })
+    }
+}
+
+/// Output at mid-price, estimated by quoting a small fraction of the amount
+async fn probe_mid_price(quoter: &dyn SwapQuoter, quote: &SwapQuote) -> Option<U256> {
+    let probe_sell = quote.sell_amount / U256::from(risk::PROBE_DIVISOR);
+    if probe_sell.is_zero() {
+        return None;
+    }
+    let params = QuoteParams {
+        sell_amount: probe_sell,
+        ..quote.params()
+    };
+    match quoter.quote(&params).await {
+        Ok(Some(probe)) => risk::mid_price_from_probe(quote.sell_amount, probe_sell, probe.buy_amount),
+        Ok(None) => None,
+        Err(e) => {
+            tracing::debug!("Mid-price probe via {} failed: {}", quote.source.name(), e);
+            None
+        }
+    }
+}
+
+// ============================================================================
+// Swap history
+// ============================================================================
+
+/// Get the storage path for the swap history
+pub fn get_swap_history_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("vaughan");
+    path.push("swap_history.json");
+    path
+}
+
+/// A submitted swap
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapRecord {
+    pub tx_hash: TxHash,
+    pub chain_id: u64,
+    pub account: Address,
+    pub source: SwapSource,
+    pub sell_token: SwapToken,
+    pub buy_token: SwapToken,
+    pub sell_amount: U256,
+    pub expected_buy_amount: U256,
+    pub min_buy_amount: U256,
+    pub submitted_at: DateTime<Utc>,
+}
+
+impl SwapRecord {
+    pub fn new(swap: &SwapTransaction, tx_hash: TxHash) -> Self {
+        let quote = &swap.quote;
+        Self {
+            tx_hash,
+            chain_id: quote.chain_id,
+            account: quote.taker,
+            source: quote.source,
+            sell_token: quote.sell_token.clone(),
+            buy_token: quote.buy_token.clone(),
+            sell_amount: quote.sell_amount,
+            expected_buy_amount: quote.buy_amount,
+            min_buy_amount: swap.min_buy_amount,
+            submitted_at: Utc::now(),
+        }
+    }
+}
+
+/// Swaps submitted from this wallet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SwapHistory {
+    #[serde(default)]
+    swaps: Vec<SwapRecord>,
+}
+
+impl SwapHistory {
+    /// Load the history from the default location
+    pub fn load() -> Self {
+        Self::load_from(&get_swap_history_path())
+    }
+
+    /// Load the history from a file, falling back to an empty history
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid swap history {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the history to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_swap_history_path())
+    }
+
+    /// Save the history to a file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self).map_err(|e| ConfigurationError::ParseError {
+            message: format!("Failed to serialize swap history: {e}"),
+        })?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Record a broadcast swap
+    pub fn record(&mut self, swap: &SwapTransaction, tx_hash: TxHash) {
+        self.swaps.push(SwapRecord::new(swap, tx_hash));
+    }
+
+    /// Swaps made by `account`, most recent first
+    pub fn for_account(&self, account: Address) -> Vec<&SwapRecord> {
+        let mut swaps: Vec<_> = self.swaps.iter().filter(|swap| swap.account == account).collect();
+        swaps.sort_by_key(|swap| std::cmp::Reverse(swap.submitted_at));
+        swaps
+    }
+
+    pub fn get(&self, tx_hash: TxHash) -> Option<&SwapRecord> {
+        self.swaps.iter().find(|swap| swap.tx_hash == tx_hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.swaps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.swaps.is_empty()
+    }
+}
+
+/// Parse a decimal integer amount returned by an API
+pub(crate) fn parse_api_amount(source: SwapSource, value: &str) -> Result<U256> {
+    U256::from_str_radix(value, 10).map_err(|_| {
+        SwapError::InvalidResponse {
+            source_name: source.name().to_string(),
+            reason: format!("bad amount '{value}'"),
+        }
+        .into()
+    })
+}
+
+/// Build the transaction request for calldata returned by an aggregator
+pub(crate) fn aggregator_request(
+    taker: Address,
+    to: Address,
+    data: Bytes,
+    value: U256,
+    gas: Option<u64>,
+) -> TransactionRequest {
+    let mut request = TransactionRequest::default()
+        .from(taker)
+        .to(to)
+        .value(value)
+        .input(data.into());
+    if let Some(gas) = gas {
+        request = request.gas_limit(gas);
+    }
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const TAKER: Address = Address::repeat_byte(0x11);
+
+    fn token(byte: u8, symbol: &str) -> TokenInfo {
+        TokenInfo::new(
+            Address::repeat_byte(byte),
+            1,
+            symbol.to_string(),
+            symbol.to_string(),
+            18,
+        )
+    }
+
+    struct FixedQuoter {
+        source: SwapSource,
+        output: Option<u64>,
+        builds: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl SwapQuoter for FixedQuoter {
+        fn source(&self) -> SwapSource {
+            self.source
+        }
+
+        fn supports_chain(&self, chain_id: u64) -> bool {
+            chain_id == 1
+        }
+
+        async fn quote(&self, params: &QuoteParams) -> Result<Option<SwapQuote>> {
+            Ok(self.output.map(|output| SwapQuote {
+                source: self.source,
+                chain_id: params.chain_id,
+                sell_token: params.sell_token.clone(),
+                buy_token: params.buy_token.clone(),
+                sell_amount: params.sell_amount,
+                buy_amount: U256::from(output),
+                gas_estimate: None,
+                allowance_target: None,
+                route: QuoteRoute::Aggregator { price_route: None },
+                taker: params.taker,
+                quoted_at: Utc::now(),
+            }))
+        }
+
+        async fn build(&self, quote: &SwapQuote, slippage: Slippage) -> Result<SwapTransaction> {
+            self.builds.fetch_add(1, Ordering::SeqCst);
+            Ok(SwapTransaction {
+                request: TransactionRequest::default(),
+                min_buy_amount: slippage.min_amount(quote.buy_amount),
+                approval_spender: None,
+                quote: quote.clone(),
+            })
+        }
+    }
+
+    fn router(builds: &Arc<AtomicUsize>) -> SwapRouter {
+        let quoter = |source, output| FixedQuoter {
+            source,
+            output,
+            builds: Arc::clone(builds),
+        };
+        SwapRouter::new(1, TAKER)
+            .with_quoter(quoter(SwapSource::ZeroEx, Some(900)))
+            .with_quoter(quoter(SwapSource::OneInch, Some(1_000)))
+            .with_quoter(quoter(SwapSource::ParaSwap, None))
+    }
+
+    #[test]
+    fn test_slippage() {
+        let slippage = Slippage::from_bps(50).unwrap();
+        assert_eq!(slippage.min_amount(U256::from(10_000)), U256::from(9_950));
+        assert_eq!(slippage.percent(), 0.5);
+        assert_eq!(Slippage::default().bps(), DEFAULT_SLIPPAGE_BPS);
+        assert_eq!(Slippage::from_bps(0), Err(SwapError::InvalidSlippage(0)));
+        assert_eq!(Slippage::from_bps(5_001), Err(SwapError::InvalidSlippage(5_001)));
+    }
+
+    #[test]
+    fn test_native_sentinel() {
+        let native = TokenInfo::new(Address::ZERO, 1, "Ether".to_string(), "ETH".to_string(), 18);
+        assert_eq!(SwapToken::from(&native).api_address(), NATIVE_TOKEN_SENTINEL);
+        assert_eq!(SwapToken::from(&token(5, "DAI")).api_address(), Address::repeat_byte(5));
+    }
+
+    #[tokio::test]
+    async fn test_best_quote_and_build() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let router = router(&builds);
+
+        let quotes = router
+            .get_quotes(&token(1, "A"), &token(2, "B"), U256::from(5))
+            .await
+            .unwrap();
+        assert_eq!(quotes.len(), 2);
+
+        let best = router
+            .get_quote(&token(1, "A"), &token(2, "B"), U256::from(5))
+            .await
+            .unwrap();
+        assert_eq!(best.source, SwapSource::OneInch);
+
+        let swap = router
+            .build_swap_tx(&best, Slippage::from_bps(100).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(swap.min_buy_amount, U256::from(990));
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_quote_validation() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let router = router(&builds);
+
+        assert!(router
+            .get_quote(&token(1, "A"), &token(1, "A"), U256::from(5))
+            .await
+            .is_err());
+        assert!(router
+            .get_quote(&token(1, "A"), &token(2, "B"), U256::ZERO)
+            .await
+            .is_err());
+
+        let mut other_chain = token(2, "B");
+        other_chain.chain_id = 369;
+        assert!(router
+            .get_quote(&token(1, "A"), &other_chain, U256::from(5))
+            .await
+            .is_err());
+
+        let mut stale = router
+            .get_quote(&token(1, "A"), &token(2, "B"), U256::from(5))
+            .await
+            .unwrap();
+        stale.quoted_at -= chrono::Duration::minutes(5);
+        assert!(router.build_swap_tx(&stale, Slippage::default()).await.is_err());
+        assert_eq!(builds.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_swap_history_round_trip() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let router = router(&builds);
+        let quote = router
+            .get_quote(&token(1, "A"), &token(2, "B"), U256::from(5))
+            .await
+            .unwrap();
+        let swap = router.build_swap_tx(&quote, Slippage::default()).await.unwrap();
+
+        let mut history = SwapHistory::default();
+        history.record(&swap, TxHash::repeat_byte(9));
+        assert_eq!(history.for_account(TAKER).len(), 1);
+        assert!(history.for_account(Address::ZERO).is_empty());
+        assert_eq!(
+            history.get(TxHash::repeat_byte(9)).unwrap().expected_buy_amount,
+            U256::from(1_000)
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("swap_history.json");
+        history.save_to(&path).unwrap();
+        assert_eq!(SwapHistory::load_from(&path), history);
+    }
+}
@@ -0,0 +1,273 @@
+//! Swap risk checks
+//!
+//! Flags swaps the user should reconsider before signing:
+//!
+//! - **price impact**: how much worse the quote is than the pool mid-price,
+//!   taken from pool reserves where the quoter can read them and otherwise
+//!   from a quote for a tiny fraction of the amount
+//! - **low liquidity**: the trade is a large share of a pool's reserves
+//! - **sandwich risk**: the slippage tolerance leaves room for a bot to buy
+//!   ahead of the swap and sell right after it, pocketing the difference
+//!
+//! Warnings are advisory; the swap can still be built with any slippage.
+
+use super::{Slippage, SwapQuote, DEFAULT_SLIPPAGE_BPS};
+use alloy::primitives::U256;
+use std::fmt;
+
+/// Share of the amount quoted to approximate the mid-price, 1 in this many
+pub const PROBE_DIVISOR: u64 = 1_000;
+
+/// Reserves of one pool along a route, in swap direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolReserves {
+    /// Reserve of the token going into the pool
+    pub reserve_in: U256,
+    /// Reserve of the token coming out
+    pub reserve_out: U256,
+}
+
+/// Thresholds for swap warnings, in basis points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapRiskConfig {
+    /// Price impact above which the swap is flagged
+    pub high_impact_bps: u32,
+    /// Share of a pool's input reserve above which the pool counts as thin
+    pub low_liquidity_share_bps: u32,
+    /// Slippage above which a swap is an attractive sandwich target
+    pub sandwich_slippage_bps: u16,
+}
+
+impl Default for SwapRiskConfig {
+    fn default() -> Self {
+        Self {
+            high_impact_bps: 300,
+            low_liquidity_share_bps: 100,
+            sandwich_slippage_bps: 100,
+        }
+    }
+}
+
+/// Something worth telling the user before they sign a swap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapWarning {
+    HighPriceImpact {
+        impact_bps: u32,
+    },
+    /// The trade takes `pool_share_bps` of the thinnest pool's input reserve
+    LowLiquidity {
+        pool_share_bps: u32,
+    },
+    /// Bots can take up to the slippage tolerance; `suggested_bps` is safer
+    SandwichTarget {
+        slippage_bps: u16,
+        suggested_bps: u16,
+    },
+}
+
+fn percent(bps: impl Into<u64>) -> String {
+    let bps: u64 = bps.into();
+    format!("{:.2}%", bps as f64 / 100.0)
+}
+
+impl fmt::Display for SwapWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HighPriceImpact { impact_bps } => write!(
+                f,
+                "Price impact is {}: you receive that much less than the pool's current price",
+                percent(*impact_bps)
+            ),
+            Self::LowLiquidity { pool_share_bps } => write!(
+                f,
+                "Low liquidity: this swap is {} of a pool's reserves; consider a smaller amount",
+                percent(*pool_share_bps)
+            ),
+            Self::SandwichTarget {
+                slippage_bps,
+                suggested_bps,
+            } => write!(
+                f,
+                "Slippage of {} makes this swap an attractive sandwich target; lower it to {} \
+                 or submit through a private transaction RPC",
+                percent(*slippage_bps),
+                percent(*suggested_bps)
+            ),
+        }
+    }
+}
+
+/// Risk assessment of one quote at a given slippage
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SwapRiskReport {
+    /// Output lost to the trade moving the price, when the mid-price is known
+    pub price_impact_bps: Option<u32>,
+    /// Largest share of a pool's input reserve the trade takes
+    pub pool_share_bps: Option<u32>,
+    pub warnings: Vec<SwapWarning>,
+}
+
+impl SwapRiskReport {
+    pub fn is_clear(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+/// `part / whole` in basis points, saturating
+fn ratio_bps(part: U256, whole: U256) -> u32 {
+    if whole.is_zero() {
+        return u32::MAX;
+    }
+    (part.saturating_mul(U256::from(10_000)) / whole).saturating_to()
+}
+
+/// Output at mid-price and the largest pool share along `pools`
+///
+/// Each hop's input is the previous hop's mid-price output.
+pub fn mid_price_through(sell_amount: U256, pools: &[PoolReserves]) -> (U256, u32) {
+    pools.iter().fold((sell_amount, 0), |(amount, share), pool| {
+        let share = share.max(ratio_bps(amount, pool.reserve_in));
+        let out = if pool.reserve_in.is_zero() {
+            U256::ZERO
+        } else {
+            amount.saturating_mul(pool.reserve_out) / pool.reserve_in
+        };
+        (out, share)
+    })
+}
+
+/// Output at mid-price extrapolated from a quote for a small amount
+pub fn mid_price_from_probe(sell_amount: U256, probe_sell: U256, probe_buy: U256) -> Option<U256> {
+    (!probe_sell.is_zero()).then(|| sell_amount.saturating_mul(probe_buy) / probe_sell)
+}
+
+/// Assess `quote` given its output at mid-price and, if known, its pools
+pub fn assess(
+    quote: &SwapQuote,
+    mid_buy_amount: Option<U256>,
+    pool_share_bps: Option<u32>,
+    slippage: Slippage,
+    config: &SwapRiskConfig,
+) -> SwapRiskReport {
+    let price_impact_bps = mid_buy_amount
+        .filter(|mid| !mid.is_zero())
+        .map(|mid| ratio_bps(mid.saturating_sub(quote.buy_amount), mid));
+
+    let mut warnings = Vec::new();
+    if let Some(impact_bps) = price_impact_bps.filter(|impact| *impact > config.high_impact_bps) {
+        warnings.push(SwapWarning::HighPriceImpact { impact_bps });
+    }
+    let thin_pool = pool_share_bps.filter(|share| *share > config.low_liquidity_share_bps);
+    if let Some(pool_share_bps) = thin_pool {
+        warnings.push(SwapWarning::LowLiquidity { pool_share_bps });
+    }
+
+    // Moving a thin pool is cheap, so even moderate slack is worth attacking
+    let limit = match thin_pool {
+        Some(_) => config.sandwich_slippage_bps.min(DEFAULT_SLIPPAGE_BPS),
+        None => config.sandwich_slippage_bps,
+    };
+    if slippage.bps() > limit {
+        warnings.push(SwapWarning::SandwichTarget {
+            slippage_bps: slippage.bps(),
+            suggested_bps: limit,
+        });
+    }
+
+    SwapRiskReport {
+        price_impact_bps,
+        pool_share_bps,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::defi::swap::{QuoteRoute, SwapSource, SwapToken};
+    use alloy::primitives::Address;
+    use chrono::Utc;
+
+    fn quote(buy_amount: u64) -> SwapQuote {
+        let token = |byte| SwapToken {
+            address: Address::repeat_byte(byte),
+            symbol: String::new(),
+            decimals: 18,
+            is_native: false,
+        };
+        SwapQuote {
+            source: SwapSource::UniswapV2,
+            chain_id: 369,
+            sell_token: token(1),
+            buy_token: token(2),
+            sell_amount: U256::from(1_000),
+            buy_amount: U256::from(buy_amount),
+            gas_estimate: None,
+            allowance_target: None,
+            route: QuoteRoute::Aggregator { price_route: None },
+            taker: Address::ZERO,
+            quoted_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_mid_price_and_pool_share() {
+        // 1,000 into a 10,000 : 20,000 pool, then 2,000 into a 100,000 : 50,000 pool
+        let pools = [
+            PoolReserves {
+                reserve_in: U256::from(10_000),
+                reserve_out: U256::from(20_000),
+            },
+            PoolReserves {
+                reserve_in: U256::from(100_000),
+                reserve_out: U256::from(50_000),
+            },
+        ];
+        assert_eq!(mid_price_through(U256::from(1_000), &pools), (U256::from(1_000), 1_000));
+        assert_eq!(
+            mid_price_from_probe(U256::from(1_000), U256::from(1), U256::from(3)),
+            Some(U256::from(3_000))
+        );
+        assert_eq!(mid_price_from_probe(U256::from(1_000), U256::ZERO, U256::from(3)), None);
+    }
+
+    #[test]
+    fn test_assess_warnings() {
+        let config = SwapRiskConfig::default();
+        let clear = assess(
+            &quote(1_990),
+            Some(U256::from(2_000)),
+            Some(10),
+            Slippage::default(),
+            &config,
+        );
+        assert_eq!(clear.price_impact_bps, Some(50));
+        assert!(clear.is_clear());
+
+        // 10% impact in a thin pool with 1% slippage
+        let risky = assess(
+            &quote(1_800),
+            Some(U256::from(2_000)),
+            Some(900),
+            Slippage::from_bps(100).unwrap(),
+            &config,
+        );
+        assert_eq!(
+            risky.warnings,
+            vec![
+                SwapWarning::HighPriceImpact { impact_bps: 1_000 },
+                SwapWarning::LowLiquidity { pool_share_bps: 900 },
+                SwapWarning::SandwichTarget {
+                    slippage_bps: 100,
+                    suggested_bps: DEFAULT_SLIPPAGE_BPS
+                },
+            ]
+        );
+        assert!(risky.warnings[2].to_string().contains("private transaction RPC"));
+
+        // Unknown mid-price still checks slippage
+        let unknown = assess(&quote(1_800), None, None, Slippage::from_bps(300).unwrap(), &config);
+        assert_eq!(unknown.price_impact_bps, None);
+        assert_eq!(unknown.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,706 @@
+//! Direct router quoters
+//!
+//! Quotes swaps straight from Uniswap V2 style routers (Uniswap, PulseX,
+//! PancakeSwap, QuickSwap) and Uniswap V3 pools, for chains or tokens the
+//! aggregators don't cover. Native currency legs are routed through the
+//! chain's wrapped native token.
+//!
+//! V2 pairs expose their reserves, which [`super::risk`] uses as the mid-price.
+
+use super::{
+    PoolReserves, QuoteParams, QuoteRoute, Slippage, SwapError, SwapQuote, SwapQuoter, SwapSource, SwapToken,
+    SwapTransaction,
+};
+use crate::error::{NetworkError, Result};
+use crate::network::clock::clock;
+use alloy::primitives::{address, aliases::U24, Address, Bytes, U160, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use chrono::Utc;
+use std::time::Duration;
+
+/// How long a signed swap stays valid on-chain
+pub const DEFAULT_DEADLINE: Duration = Duration::from_secs(20 * 60);
+
+/// Uniswap V3 fee tiers tried when quoting, in hundredths of a bip
+pub const V3_FEE_TIERS: [u32; 4] = [100, 500, 3_000, 10_000];
+
+/// Recipient placeholder telling SwapRouter02 to keep output in the router
+const ROUTER_ADDRESS_THIS: Address = address!("0000000000000000000000000000000000000002");
+
+sol! {
+    interface IUniswapV2Router {
+        function factory() external pure returns (address);
+        function getAmountsOut(uint256 amountIn, address[] path) external view returns (uint256[] amounts);
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapExactETHForTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline) external payable returns (uint256[] amounts);
+        function swapExactTokensForETH(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+    }
+
+    interface IUniswapV2Factory {
+        function getPair(address tokenA, address tokenB) external view returns (address pair);
+    }
+
+    interface IUniswapV2Pair {
+        function token0() external view returns (address);
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
+    }
+
+    interface IQuoterV2 {
+        struct QuoteExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint256 amountIn;
+            uint24 fee;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        function quoteExactInputSingle(QuoteExactInputSingleParams params) external returns (uint256 amountOut, uint160 sqrtPriceX96After, uint32 initializedTicksCrossed, uint256 gasEstimate);
+    }
+
+    interface ISwapRouter02 {
+        struct ExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        function exactInputSingle(ExactInputSingleParams params) external payable returns (uint256 amountOut);
+        function unwrapWETH9(uint256 amountMinimum, address recipient) external payable;
+        function multicall(uint256 deadline, bytes[] data) external payable returns (bytes[] results);
+    }
+}
+
+/// A Uniswap V2 style router deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V2Deployment {
+    pub chain_id: u64,
+    pub name: &'static str,
+    pub router: Address,
+    pub wrapped_native: Address,
+}
+
+/// Well-known V2 routers
+pub const V2_DEPLOYMENTS: &[V2Deployment] = &[
+    V2Deployment {
+        chain_id: 1,
+        name: "Uniswap V2",
+        router: address!("7a250d5630B4cF539739dF2C5dAcb4c659F2488D"),
+        wrapped_native: address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+    },
+    V2Deployment {
+        chain_id: 56,
+        name: "PancakeSwap V2",
+        router: address!("10ED43C718714eb63d5aA57B78B54704E256024E"),
+        wrapped_native: address!("bb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c"),
+    },
+    V2Deployment {
+        chain_id: 137,
+        name: "QuickSwap",
+        router: address!("a5E0829CaCEd8fFDD4De3c43696c57F7D7A678ff"),
+        wrapped_native: address!("0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270"),
+    },
+    V2Deployment {
+        chain_id: 369,
+        name: "PulseX V2",
+        router: address!("165C3410fC91EF562C50559f7d2289fEbed552d9"),
+        wrapped_native: address!("A1077a294dDE1B09bB078844df40758a5D0f9a27"),
+    },
+];
+
+/// A Uniswap V3 deployment (QuoterV2 + SwapRouter02)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V3Deployment {
+    pub chain_id: u64,
+    pub quoter: Address,
+    pub router: Address,
+    pub wrapped_native: Address,
+}
+
+/// Well-known V3 deployments
+pub const V3_DEPLOYMENTS: &[V3Deployment] = &[
+    V3Deployment {
+        chain_id: 1,
+        quoter: address!("61fFE014bA17989E743c5F6cB21bF9697530B21e"),
+        router: address!("68b3465833fb72A70ecDF485E0e4C7bD8665Fc45"),
+        wrapped_native: address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+    },
+    V3Deployment {
+        chain_id: 10,
+        quoter: address!("61fFE014bA17989E743c5F6cB21bF9697530B21e"),
+        router: address!("68b3465833fb72A70ecDF485E0e4C7bD8665Fc45"),
+        wrapped_native: address!("4200000000000000000000000000000000000006"),
+    },
+    V3Deployment {
+        chain_id: 137,
+        quoter: address!("61fFE014bA17989E743c5F6cB21bF9697530B21e"),
+        router: address!("68b3465833fb72A70ecDF485E0e4C7bD8665Fc45"),
+        wrapped_native: address!("0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270"),
+    },
+    V3Deployment {
+        chain_id: 8453,
+        quoter: address!("3d4e44Eb1374240CE5F1B871ab261CD16335B76a"),
+        router: address!("2626664c2603336E57B271c5C0b26F421741e481"),
+        wrapped_native: address!("4200000000000000000000000000000000000006"),
+    },
+    V3Deployment {
+        chain_id: 42161,
+        quoter: address!("61fFE014bA17989E743c5F6cB21bF9697530B21e"),
+        router: address!("68b3465833fb72A70ecDF485E0e4C7bD8665Fc45"),
+        wrapped_native: address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+    },
+];
+
+/// Token address as the router sees it
+fn router_token(token: &SwapToken, wrapped_native: Address) -> Address {
+    if token.is_native {
+        wrapped_native
+    } else {
+        token.address
+    }
+}
+
+/// Unix timestamp `deadline` from now, corrected for a skewed local clock
+fn deadline_from_now(deadline: Duration) -> U256 {
+    U256::from(clock().deadline(deadline.as_secs()))
+}
+
+/// Candidate V2 paths: direct, then through the wrapped native token
+pub fn v2_paths(sell: Address, buy: Address, wrapped_native: Address) -> Vec<Vec<Address>> {
+    let mut paths = vec![vec![sell, buy]];
+    if sell != wrapped_native && buy != wrapped_native {
+        paths.push(vec![sell, wrapped_native, buy]);
+    }
+    paths
+}
+
+/// Router calldata and value for a V2 exact-input swap
+pub fn v2_swap_call(quote: &SwapQuote, path: Vec<Address>, min_out: U256, deadline: U256) -> (Bytes, U256) {
+    let to = quote.taker;
+    if quote.sell_token.is_native {
+        let call = IUniswapV2Router::swapExactETHForTokensCall {
+            amountOutMin: min_out,
+            path,
+            to,
+            deadline,
+        };
+        (call.abi_encode().into(), quote.sell_amount)
+    } else if quote.buy_token.is_native {
+        let call = IUniswapV2Router::swapExactTokensForETHCall {
+            amountIn: quote.sell_amount,
+            amountOutMin: min_out,
+            path,
+            to,
+            deadline,
+        };
+        (call.abi_encode().into(), U256::ZERO)
+    } else {
+        let call = IUniswapV2Router::swapExactTokensForTokensCall {
+            amountIn: quote.sell_amount,
+            amountOutMin: min_out,
+            path,
+            to,
+            deadline,
+        };
+        (call.abi_encode().into(), U256::ZERO)
+    }
+}
+
+/// SwapRouter02 `multicall` calldata and value for a single-pool V3 swap
+///
+/// Native output is swapped into the router and unwrapped to the taker.
+pub fn v3_swap_call(
+    quote: &SwapQuote,
+    wrapped_native: Address,
+    fee: u32,
+    min_out: U256,
+    deadline: U256,
+) -> (Bytes, U256) {
+    let swap = ISwapRouter02::exactInputSingleCall {
+        params: ISwapRouter02::ExactInputSingleParams {
+            tokenIn: router_token(&quote.sell_token, wrapped_native),
+            tokenOut: router_token(&quote.buy_token, wrapped_native),
+            fee: U24::from(fee),
+            recipient: if quote.buy_token.is_native {
+                ROUTER_ADDRESS_THIS
+            } else {
+                quote.taker
+            },
+            amountIn: quote.sell_amount,
+            amountOutMinimum: min_out,
+            sqrtPriceLimitX96: U160::ZERO,
+        },
+    };
+
+    let mut data: Vec<Bytes> = vec![swap.abi_encode().into()];
+    if quote.buy_token.is_native {
+        let unwrap = ISwapRouter02::unwrapWETH9Call {
+            amountMinimum: min_out,
+            recipient: quote.taker,
+        };
+        data.push(unwrap.abi_encode().into());
+    }
+
+    let value = if quote.sell_token.is_native {
+        quote.sell_amount
+    } else {
+        U256::ZERO
+    };
+    (
+        ISwapRouter02::multicallCall { deadline, data }.abi_encode().into(),
+        value,
+    )
+}
+
+fn router_quote(
+    params: &QuoteParams,
+    source: SwapSource,
+    buy_amount: U256,
+    route: QuoteRoute,
+    router: Address,
+) -> SwapQuote {
+    SwapQuote {
+        source,
+        chain_id: params.chain_id,
+        sell_token: params.sell_token.clone(),
+        buy_token: params.buy_token.clone(),
+        sell_amount: params.sell_amount,
+        buy_amount,
+        gas_estimate: None,
+        allowance_target: (!params.sell_token.is_native).then_some(router),
+        route,
+        taker: params.taker,
+        quoted_at: Utc::now(),
+    }
+}
+
+fn swap_request(quote: &SwapQuote, router: Address, data: Bytes, value: U256) -> TransactionRequest {
+    TransactionRequest::default()
+        .from(quote.taker)
+        .to(router)
+        .value(value)
+        .input(data.into())
+}
+
+fn wrong_route(source: SwapSource) -> crate::error::VaughanError {
+    SwapError::InvalidResponse {
+        source_name: source.name().to_string(),
+        reason: "quote was not produced by this router".to_string(),
+    }
+    .into()
+}
+
+async fn eth_call<P: Provider, C: SolCall>(provider: &P, contract: Address, call: C) -> Result<C::Return> {
+    let request = TransactionRequest::default()
+        .to(contract)
+        .input(call.abi_encode().into());
+    let raw = provider.call(request).await.map_err(|e| NetworkError::RpcError {
+        message: format!("Router call failed: {e}"),
+    })?;
+    C::abi_decode_returns(&raw).map_err(|e| {
+        NetworkError::RpcError {
+            message: format!("Failed to decode router response: {e}"),
+        }
+        .into()
+    })
+}
+
+// ============================================================================
+// Uniswap V2
+// ============================================================================
+
+/// Quotes from a Uniswap V2 style router via `getAmountsOut`
+pub struct UniswapV2Quoter<P> {
+    provider: P,
+    deployment: V2Deployment,
+    deadline: Duration,
+}
+
+impl<P: Provider> UniswapV2Quoter<P> {
+    pub fn new(provider: P, deployment: V2Deployment) -> Self {
+        Self {
+            provider,
+            deployment,
+            deadline: DEFAULT_DEADLINE,
+        }
+    }
+
+    /// Quoter for the well-known router on `chain_id`, if any
+    pub fn for_chain(provider: P, chain_id: u64) -> Option<Self> {
+        V2_DEPLOYMENTS
+            .iter()
+            .find(|deployment| deployment.chain_id == chain_id)
+            .map(|deployment| Self::new(provider, *deployment))
+    }
+
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: Provider> SwapQuoter for UniswapV2Quoter<P> {
+    fn source(&self) -> SwapSource {
+        SwapSource::UniswapV2
+    }
+
+    fn supports_chain(&self, chain_id: u64) -> bool {
+        chain_id == self.deployment.chain_id
+    }
+
+    async fn quote(&self, params: &QuoteParams) -> Result<Option<SwapQuote>> {
+        let wrapped = self.deployment.wrapped_native;
+        let sell = router_token(&params.sell_token, wrapped);
+        let buy = router_token(&params.buy_token, wrapped);
+        if sell == buy {
+            // Wrapping and unwrapping isn't a swap
+            return Ok(None);
+        }
+
+        let mut best: Option<(U256, Vec<Address>)> = None;
+        for path in v2_paths(sell, buy, wrapped) {
+            let call = IUniswapV2Router::getAmountsOutCall {
+                amountIn: params.sell_amount,
+                path: path.clone(),
+            };
+            // Missing pairs make the router revert; that path simply has no route
+            let amount = match eth_call(&self.provider, self.deployment.router, call).await {
+                Ok(amounts) => amounts.last().copied().unwrap_or_default(),
+                Err(e) => {
+                    tracing::debug!("{} has no route for {:?}: {}", self.deployment.name, path, e);
+                    continue;
+                }
+            };
+            if !amount.is_zero() && best.as_ref().is_none_or(|(current, _)| amount > *current) {
+                best = Some((amount, path));
+            }
+        }
+
+        Ok(best.map(|(buy_amount, path)| {
+            let route = QuoteRoute::V2 {
+                router: self.deployment.router,
+                path,
+            };
+            router_quote(params, self.source(), buy_amount, route, self.deployment.router)
+        }))
+    }
+
+    async fn build(&self, quote: &SwapQuote, slippage: Slippage) -> Result<SwapTransaction> {
+        let QuoteRoute::V2 { router, path } = &quote.route else {
+            return Err(wrong_route(self.source()));
+        };
+
+        let min_out = slippage.min_amount(quote.buy_amount);
+        let (data, value) = v2_swap_call(quote, path.clone(), min_out, deadline_from_now(self.deadline));
+        Ok(SwapTransaction {
+            request: swap_request(quote, *router, data, value),
+            min_buy_amount: min_out,
+            approval_spender: quote.allowance_target,
+            quote: quote.clone(),
+        })
+    }
+
+    async fn pool_reserves(&self, quote: &SwapQuote) -> Result<Option<Vec<PoolReserves>>> {
+        let QuoteRoute::V2 { router, path } = &quote.route else {
+            return Err(wrong_route(self.source()));
+        };
+
+        let factory = eth_call(&self.provider, *router, IUniswapV2Router::factoryCall {}).await?;
+        let mut pools = Vec::with_capacity(path.len().saturating_sub(1));
+        for hop in path.windows(2) {
+            let (token_in, token_out) = (hop[0], hop[1]);
+            let pair_call = IUniswapV2Factory::getPairCall {
+                tokenA: token_in,
+                tokenB: token_out,
+            };
+            let pair = eth_call(&self.provider, factory, pair_call).await?;
+            if pair.is_zero() {
+                return Ok(None);
+            }
+            let token0 = eth_call(&self.provider, pair, IUniswapV2Pair::token0Call {}).await?;
+            let reserves = eth_call(&self.provider, pair, IUniswapV2Pair::getReservesCall {}).await?;
+            let (reserve0, reserve1) = (U256::from(reserves.reserve0), U256::from(reserves.reserve1));
+            pools.push(if token0 == token_in {
+                PoolReserves {
+                    reserve_in: reserve0,
+                    reserve_out: reserve1,
+                }
+            } else {
+                PoolReserves {
+                    reserve_in: reserve1,
+                    reserve_out: reserve0,
+                }
+            });
+        }
+        Ok(Some(pools))
+    }
+}
+
+// ============================================================================
+// Uniswap V3
+// ============================================================================
+
+/// Quotes single-pool Uniswap V3 swaps across the standard fee tiers
+pub struct UniswapV3Quoter<P> {
+    provider: P,
+    deployment: V3Deployment,
+    deadline: Duration,
+}
+
+impl<P: Provider> UniswapV3Quoter<P> {
+    pub fn new(provider: P, deployment: V3Deployment) -> Self {
+        Self {
+            provider,
+            deployment,
+            deadline: DEFAULT_DEADLINE,
+        }
+    }
+
+    /// Quoter for the canonical deployment on `chain_id`, if any
+    pub fn for_chain(provider: P, chain_id: u64) -> Option<Self> {
+        V3_DEPLOYMENTS
+            .iter()
+            .find(|deployment| deployment.chain_id == chain_id)
+            .map(|deployment| Self::new(provider, *deployment))
+    }
+
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: Provider> SwapQuoter for UniswapV3Quoter<P> {
+    fn source(&self) -> SwapSource {
+        SwapSource::UniswapV3
+    }
+
+    fn supports_chain(&self, chain_id: u64) -> bool {
+        chain_id == self.deployment.chain_id
+    }
+
+    async fn quote(&self, params: &QuoteParams) -> Result<Option<SwapQuote>> {
+        let wrapped = self.deployment.wrapped_native;
+        let token_in = router_token(&params.sell_token, wrapped);
+        let token_out = router_token(&params.buy_token, wrapped);
+        if token_in == token_out {
+            return Ok(None);
+        }
+
+        let mut best: Option<(U256, u32, u64)> = None;
+        for fee in V3_FEE_TIERS {
+            let call = IQuoterV2::quoteExactInputSingleCall {
+                params: IQuoterV2::QuoteExactInputSingleParams {
+                    tokenIn: token_in,
+                    tokenOut: token_out,
+                    amountIn: params.sell_amount,
+                    fee: U24::from(fee),
+                    sqrtPriceLimitX96: U160::ZERO,
+                },
+            };
+            // The quoter reverts for fee tiers without a pool
+            let quoted = match eth_call(&self.provider, self.deployment.quoter, call).await {
+                Ok(quoted) => quoted,
+                Err(e) => {
+                    tracing::debug!("No Uniswap V3 pool at fee {}: {}", fee, e);
+                    continue;
+                }
+            };
+            if best.is_none_or(|(current, _, _)| quoted.amountOut > current) {
+                best = Some((quoted.amountOut, fee, quoted.gasEstimate.saturating_to()));
+            }
+        }
+
+        Ok(best
+            .filter(|(amount, _, _)| !amount.is_zero())
+            .map(|(buy_amount, fee, gas)| {
+                let route = QuoteRoute::V3 {
+                    router: self.deployment.router,
+                    fee,
+                };
+                let mut quote = router_quote(params, self.source(), buy_amount, route, self.deployment.router);
+                quote.gas_estimate = Some(gas);
+                quote
+            }))
+    }
+
+    async fn build(&self, quote: &SwapQuote, slippage: Slippage) -> Result<SwapTransaction> {
+        let QuoteRoute::V3 { router, fee } = &quote.route else {
+            return Err(wrong_route(self.source()));
+        };
+
+        let min_out = slippage.min_amount(quote.buy_amount);
+        let deadline = deadline_from_now(self.deadline);
+        let (data, value) = v3_swap_call(quote, self.deployment.wrapped_native, *fee, min_out, deadline);
+        Ok(SwapTransaction {
+            request: swap_request(quote, *router, data, value),
+            min_buy_amount: min_out,
+            approval_spender: quote.allowance_target,
+            quote: quote.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WPLS: Address = address!("A1077a294dDE1B09bB078844df40758a5D0f9a27");
+    const HEX: Address = Address::repeat_byte(0x2b);
+    const TAKER: Address = Address::repeat_byte(0x11);
+
+    fn swap_token(address: Address, is_native: bool) -> SwapToken {
+        SwapToken {
+            address,
+            symbol: String::new(),
+            decimals: 18,
+            is_native,
+        }
+    }
+
+    fn quote(sell: SwapToken, buy: SwapToken, route: QuoteRoute) -> SwapQuote {
+        SwapQuote {
+            source: SwapSource::UniswapV2,
+            chain_id: 369,
+            sell_token: sell,
+            buy_token: buy,
+            sell_amount: U256::from(1_000),
+            buy_amount: U256::from(2_000),
+            gas_estimate: None,
+            allowance_target: None,
+            route,
+            taker: TAKER,
+            quoted_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_v2_paths() {
+        assert_eq!(v2_paths(HEX, WPLS, WPLS), vec![vec![HEX, WPLS]]);
+        let token = Address::repeat_byte(3);
+        assert_eq!(
+            v2_paths(HEX, token, WPLS),
+            vec![vec![HEX, token], vec![HEX, WPLS, token]]
+        );
+    }
+
+    #[test]
+    fn test_v2_swap_calls() {
+        let native = swap_token(Address::ZERO, true);
+        let hex = swap_token(HEX, false);
+        let deadline = U256::from(1_700_000_000u64);
+        let min_out = U256::from(1_990);
+
+        let buy = quote(
+            native.clone(),
+            hex.clone(),
+            QuoteRoute::Aggregator { price_route: None },
+        );
+        let (data, value) = v2_swap_call(&buy, vec![WPLS, HEX], min_out, deadline);
+        assert_eq!(value, U256::from(1_000));
+        let decoded = IUniswapV2Router::swapExactETHForTokensCall::abi_decode(&data).unwrap();
+        assert_eq!((decoded.amountOutMin, decoded.to), (min_out, TAKER));
+
+        let sell = quote(hex.clone(), native, QuoteRoute::Aggregator { price_route: None });
+        let (data, value) = v2_swap_call(&sell, vec![HEX, WPLS], min_out, deadline);
+        assert_eq!(value, U256::ZERO);
+        assert!(IUniswapV2Router::swapExactTokensForETHCall::abi_decode(&data).is_ok());
+
+        let token_swap = quote(
+            hex,
+            swap_token(Address::repeat_byte(3), false),
+            QuoteRoute::Aggregator { price_route: None },
+        );
+        let (data, _) = v2_swap_call(&token_swap, vec![HEX, Address::repeat_byte(3)], min_out, deadline);
+        let decoded = IUniswapV2Router::swapExactTokensForTokensCall::abi_decode(&data).unwrap();
+        assert_eq!(decoded.amountIn, U256::from(1_000));
+        assert_eq!(decoded.deadline, deadline);
+    }
+
+    #[test]
+    fn test_v3_swap_call_unwraps_native_output() {
+        let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let usdc = swap_token(Address::repeat_byte(0xaa), false);
+        let native = swap_token(Address::ZERO, true);
+        let deadline = U256::from(1_700_000_000u64);
+        let route = QuoteRoute::V3 {
+            router: Address::ZERO,
+            fee: 500,
+        };
+
+        let (data, value) = v3_swap_call(
+            &quote(usdc.clone(), native.clone(), route.clone()),
+            weth,
+            500,
+            U256::from(1),
+            deadline,
+        );
+        assert_eq!(value, U256::ZERO);
+        let multicall = ISwapRouter02::multicallCall::abi_decode(&data).unwrap();
+        assert_eq!(multicall.deadline, deadline);
+        assert_eq!(multicall.data.len(), 2);
+        let swap = ISwapRouter02::exactInputSingleCall::abi_decode(&multicall.data[0]).unwrap();
+        assert_eq!(swap.params.tokenOut, weth);
+        assert_eq!(swap.params.recipient, ROUTER_ADDRESS_THIS);
+        let unwrap = ISwapRouter02::unwrapWETH9Call::abi_decode(&multicall.data[1]).unwrap();
+        assert_eq!(unwrap.recipient, TAKER);
+
+        let (data, value) = v3_swap_call(&quote(native, usdc, route), weth, 500, U256::from(1), deadline);
+        assert_eq!(value, U256::from(1_000));
+        let multicall = ISwapRouter02::multicallCall::abi_decode(&data).unwrap();
+        assert_eq!(multicall.data.len(), 1);
+        let swap = ISwapRouter02::exactInputSingleCall::abi_decode(&multicall.data[0]).unwrap();
+        assert_eq!((swap.params.tokenIn, swap.params.recipient), (weth, TAKER));
+    }
+
+    #[tokio::test]
+    async fn test_v2_pool_reserves_follow_swap_direction() {
+        use alloy::providers::ProviderBuilder;
+        use alloy::sol_types::SolValue;
+        use alloy::transports::mock::Asserter;
+
+        let pair = Address::repeat_byte(0x9a);
+        let asserter = Asserter::new();
+        asserter.push_success(&Bytes::from(Address::repeat_byte(0xfa).abi_encode())); // factory
+        asserter.push_success(&Bytes::from(pair.abi_encode()));
+        asserter.push_success(&Bytes::from(WPLS.abi_encode())); // token0 is the output
+        asserter.push_success(&Bytes::from(
+            (U256::from(5_000), U256::from(40_000), 0u32).abi_encode_params(),
+        ));
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+        let deployment = *V2_DEPLOYMENTS.iter().find(|d| d.chain_id == 369).unwrap();
+        let quoter = UniswapV2Quoter::new(provider, deployment);
+
+        let route = QuoteRoute::V2 {
+            router: deployment.router,
+            path: vec![HEX, WPLS],
+        };
+        let quote = quote(swap_token(HEX, false), swap_token(Address::ZERO, true), route);
+        let pools = quoter.pool_reserves(&quote).await.unwrap().unwrap();
+        assert_eq!(
+            pools,
+            vec![PoolReserves {
+                reserve_in: U256::from(40_000),
+                reserve_out: U256::from(5_000),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_known_deployments() {
+        assert!(V2_DEPLOYMENTS
+            .iter()
+            .any(|d| d.chain_id == 369 && d.wrapped_native == WPLS));
+        assert!(V3_DEPLOYMENTS.iter().all(|d| d.quoter != d.router));
+    }
+}
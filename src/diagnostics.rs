@@ -0,0 +1,458 @@
+//! Wallet self-diagnostics
+//!
+//! Backs `vaughan doctor` and the GUI diagnostics screen. Each check reports a
+//! [`CheckStatus`] and, when something is wrong, an actionable fix:
+//!
+//! - **config**: `settings.json` parses and validates
+//! - **keychain**: a probe key can be stored, read back and deleted on the
+//!   configured backend
+//! - **network**: every configured network has a reachable endpoint serving
+//!   the right chain (egress settings are respected)
+//! - **clock**: the local clock agrees with the latest block of the current
+//!   network
+//! - **hardware**: Ledger and Trezor transports find a device
+//! - **disk**: the data and cache directories have free space
+
+use crate::config::settings::SETTINGS_FILE;
+use crate::config::{ConfigManager, Settings};
+use crate::network::benchmark::{benchmark_network, BenchmarkConfig, BenchmarkReport};
+use crate::network::clock::{clock, SkewMeasurement};
+use crate::network::NetworkManager;
+use crate::security::file_vault::VAULT_PASSWORD_ENV;
+use crate::security::{create_keychain, KeyReference, KeychainBackend, SERVICE_NAME_PRIVATE_KEYS};
+use alloy::eips::BlockNumberOrTag;
+use alloy::network::Ethereum;
+use alloy::providers::{Provider, RootProvider};
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Key id of the keychain probe, deleted again by the check
+const KEYCHAIN_PROBE_ID: &str = "vaughan-doctor-probe";
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    /// Not run in this build or environment
+    Skipped,
+    /// Works, but degraded
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pass => "ok",
+            Self::Skipped => "skip",
+            Self::Warn => "warn",
+            Self::Fail => "FAIL",
+        })
+    }
+}
+
+/// Result of one check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckResult {
+    /// Check name, e.g. `network: Ethereum Mainnet`
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What the user can do about a warning or failure
+    pub fix: Option<String>,
+}
+
+impl CheckResult {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, detail)
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+/// Thresholds used by the checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiagnosticsConfig {
+    /// Time after which an RPC request or device probe counts as failed
+    pub timeout: Duration,
+    /// Clock difference to the latest block that is reported
+    pub max_clock_skew: Duration,
+    /// Free space below which a directory warns
+    pub min_free_bytes: u64,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            max_clock_skew: Duration::from_secs(120),
+            min_free_bytes: 100 * 1024 * 1024,
+        }
+    }
+}
+
+/// Results of a diagnostics run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiagnosticsReport {
+    pub checks: Vec<CheckResult>,
+    pub ran_at: DateTime<Utc>,
+}
+
+impl DiagnosticsReport {
+    /// Worst status of any check
+    pub fn status(&self) -> CheckStatus {
+        self.checks.iter().map(|c| c.status).max().unwrap_or(CheckStatus::Pass)
+    }
+
+    /// Whether no check failed
+    pub fn is_healthy(&self) -> bool {
+        self.status() < CheckStatus::Fail
+    }
+
+    /// Checks that warned or failed
+    pub fn problems(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|c| c.status >= CheckStatus::Warn)
+    }
+}
+
+impl fmt::Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "[{:>4}] {:<32} {}", check.status, check.name, check.detail)?;
+            if let Some(fix) = &check.fix {
+                writeln!(f, "       fix: {fix}")?;
+            }
+        }
+        let problems = self.problems().count();
+        if problems == 0 {
+            write!(f, "All checks passed")
+        } else {
+            write!(f, "{problems} check(s) need attention")
+        }
+    }
+}
+
+/// Run every check
+pub async fn run_diagnostics(config: &DiagnosticsConfig) -> DiagnosticsReport {
+    let mut checks = vec![check_config(), check_keychain()];
+
+    match NetworkManager::new().await {
+        Ok(manager) => {
+            let benchmark = BenchmarkConfig {
+                samples: 1,
+                timeout: config.timeout,
+                ..BenchmarkConfig::default()
+            };
+            let reports = join_all(
+                manager
+                    .get_all_networks()
+                    .values()
+                    .map(|network| benchmark_network(network, &benchmark)),
+            )
+            .await;
+            checks.extend(reports.iter().map(check_network));
+
+            let current = reports.iter().find(|r| r.network == manager.current_network());
+            checks.push(check_clock(current, config).await);
+        }
+        Err(e) => checks.push(
+            CheckResult::new(
+                "network",
+                CheckStatus::Fail,
+                format!("Network manager failed to start: {e}"),
+            )
+            .with_fix("Check the custom network definitions in the network settings"),
+        ),
+    }
+
+    checks.push(check_hardware(config).await);
+    checks.extend(
+        data_dirs()
+            .iter()
+            .map(|dir| check_disk_space(dir, config.min_free_bytes)),
+    );
+
+    DiagnosticsReport {
+        checks,
+        ran_at: Utc::now(),
+    }
+}
+
+/// Settings file parses and validates
+pub fn check_config() -> CheckResult {
+    let path = ConfigManager::get_config_dir().join(SETTINGS_FILE);
+    match Settings::load(&path) {
+        Ok(_) if path.exists() => CheckResult::pass("config", format!("{} is valid", path.display())),
+        Ok(_) => CheckResult::pass("config", "No settings file, using defaults"),
+        Err(e) => CheckResult::new("config", CheckStatus::Fail, e.to_string()).with_fix(format!(
+            "Correct the listed fields in {} or delete it to restore the defaults",
+            path.display()
+        )),
+    }
+}
+
+/// Probe key round-trips through the configured keychain backend
+pub fn check_keychain() -> CheckResult {
+    let backend = Settings::load_default()
+        .map(|s| s.security.keychain_backend)
+        .unwrap_or_default();
+    let fix = match backend {
+        KeychainBackend::Os => "Unlock the OS keychain; on Linux start a Secret Service such as gnome-keyring, \
+             or set security.keychain_backend to \"encrypted_file\""
+            .to_string(),
+        KeychainBackend::EncryptedFile => {
+            format!("Set {VAULT_PASSWORD_ENV} to the vault password and make sure the vault file is writable")
+        }
+        KeychainBackend::Memory => "Restart the wallet".to_string(),
+    };
+    let name = format!("keychain ({backend:?})");
+
+    let probe = KeyReference {
+        id: KEYCHAIN_PROBE_ID.to_string(),
+        service: SERVICE_NAME_PRIVATE_KEYS.to_string(),
+        account: KEYCHAIN_PROBE_ID.to_string(),
+    };
+    let secret = format!("probe-{}", Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    let round_trip = create_keychain(SERVICE_NAME_PRIVATE_KEYS).and_then(|keychain| {
+        keychain.store(&probe, SecretString::new(secret.clone()))?;
+        let read = keychain.retrieve(&probe);
+        keychain.delete(&probe)?;
+        Ok(read?.expose_secret() == &secret)
+    });
+    match round_trip {
+        Ok(true) => CheckResult::pass(name, "Store, read and delete succeeded"),
+        Ok(false) => CheckResult::new(name, CheckStatus::Fail, "Keychain returned a different secret").with_fix(fix),
+        Err(e) => CheckResult::new(name, CheckStatus::Fail, e.to_string()).with_fix(fix),
+    }
+}
+
+/// At least one endpoint of the network answers on the right chain
+pub fn check_network(report: &BenchmarkReport) -> CheckResult {
+    let name = format!("network: {}", report.network_name);
+    let usable = report.endpoints.iter().filter(|e| e.is_usable()).count();
+    let total = report.endpoints.len();
+    let first_error =
+        report
+            .endpoints
+            .iter()
+            .find(|e| !e.is_usable())
+            .map(|e| match (&e.last_error, e.chain_mismatch) {
+                (_, true) => format!("{} serves another chain", e.url),
+                (Some(error), false) => format!("{}: {error}", e.url),
+                (None, false) => format!("{} did not answer", e.url),
+            });
+
+    match (usable, first_error) {
+        (_, None) => CheckResult::pass(name, format!("{usable} of {total} endpoints reachable")),
+        (0, Some(error)) => CheckResult::new(name, CheckStatus::Fail, format!("No endpoint reachable; {error}"))
+            .with_fix("Check the internet connection, firewall and egress settings, or add a working RPC URL"),
+        (_, Some(error)) => CheckResult::new(
+            name,
+            CheckStatus::Warn,
+            format!("{usable} of {total} endpoints reachable; {error}"),
+        )
+        .with_fix(format!(
+            "Run `vaughan --benchmark-rpc {}` and remove or replace failing endpoints",
+            report.network.chain_id()
+        )),
+    }
+}
+
+/// Seconds the local clock is ahead of `block_time`; negative when behind
+pub fn clock_skew(block_time: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    (now - block_time).num_seconds()
+}
+
+/// Local clock agrees with the latest block of the current network
+///
+/// A block from the future means the local clock is behind. A block older
+/// than the tolerance is only a warning since the endpoint may be lagging.
+pub async fn check_clock(report: Option<&BenchmarkReport>, config: &DiagnosticsConfig) -> CheckResult {
+    let Some(endpoint) = report.and_then(BenchmarkReport::recommended) else {
+        return CheckResult::new(
+            "clock",
+            CheckStatus::Skipped,
+            "No reachable endpoint to compare against",
+        );
+    };
+    let block = match endpoint.url.parse() {
+        Ok(url) => {
+            let provider = RootProvider::<Ethereum>::new_http(url);
+            tokio::time::timeout(config.timeout, provider.get_block_by_number(BlockNumberOrTag::Latest)).await
+        }
+        Err(_) => return CheckResult::new("clock", CheckStatus::Skipped, "Invalid endpoint URL"),
+    };
+    let Some(block_time) = block
+        .ok()
+        .and_then(Result::ok)
+        .flatten()
+        .and_then(|block| DateTime::from_timestamp(block.header.timestamp as i64, 0))
+    else {
+        return CheckResult::new(
+            "clock",
+            CheckStatus::Skipped,
+            format!("{} returned no block", endpoint.url),
+        );
+    };
+
+    let now = Utc::now();
+    let skew = clock_skew(block_time, now);
+    if let (Some(report), Some(number)) = (report, endpoint.head_block) {
+        clock().record(SkewMeasurement::from_block(
+            report.network.0,
+            number,
+            block_time.timestamp() as u64,
+            now,
+        ));
+    }
+    let tolerance = config.max_clock_skew.as_secs() as i64;
+    let detail = format!("Local clock is {skew:+}s from the latest block");
+    let fix = "Enable automatic time synchronisation (NTP) in the system settings";
+    if skew < -tolerance {
+        CheckResult::new("clock", CheckStatus::Fail, detail).with_fix(fix)
+    } else if skew > tolerance {
+        CheckResult::new("clock", CheckStatus::Warn, detail)
+            .with_fix(format!("{fix}, or switch to a fresher endpoint than {}", endpoint.url))
+    } else {
+        CheckResult::pass("clock", detail)
+    }
+}
+
+/// Ledger and Trezor transports find a device
+#[cfg(feature = "hardware-wallets")]
+pub async fn check_hardware(config: &DiagnosticsConfig) -> CheckResult {
+    let mut manager = crate::security::HardwareManager::new();
+    match tokio::time::timeout(config.timeout * 2, manager.detect_wallets()).await {
+        Ok(Ok(devices)) if !devices.is_empty() => {
+            let names: Vec<_> = devices
+                .iter()
+                .map(|d| format!("{} {}", d.device_type, d.model))
+                .collect();
+            CheckResult::pass("hardware", format!("Found {}", names.join(", ")))
+        }
+        Ok(Ok(_)) => CheckResult::new("hardware", CheckStatus::Warn, "No Ledger or Trezor found").with_fix(
+            "Connect and unlock the device and open the Ethereum app; on Linux install the vendor udev rules",
+        ),
+        Ok(Err(e)) => CheckResult::new("hardware", CheckStatus::Fail, e.to_string())
+            .with_fix("Close other wallet apps using the device and reconnect it"),
+        Err(_) => CheckResult::new("hardware", CheckStatus::Warn, "Device detection timed out")
+            .with_fix("Reconnect the device and confirm any prompt on its screen"),
+    }
+}
+
+/// Ledger and Trezor transports find a device
+#[cfg(not(feature = "hardware-wallets"))]
+pub async fn check_hardware(_config: &DiagnosticsConfig) -> CheckResult {
+    CheckResult::new(
+        "hardware",
+        CheckStatus::Skipped,
+        "Built without the hardware-wallets feature",
+    )
+}
+
+/// Directories holding the keystore and caches
+fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![crate::security::keystore::storage::get_vaughan_dir()];
+    if let Some(config) = dirs::config_dir() {
+        dirs.push(config.join("vaughan"));
+    }
+    dirs
+}
+
+/// `path`, or its closest existing ancestor, has free space
+pub fn check_disk_space(path: &Path, min_free_bytes: u64) -> CheckResult {
+    let name = format!("disk: {}", path.display());
+    let existing = path.ancestors().find(|p| p.exists()).unwrap_or(path);
+    match free_space(existing) {
+        Some(free) if free >= min_free_bytes => CheckResult::pass(name, format!("{} MiB free", free >> 20)),
+        Some(free) => CheckResult::new(name, CheckStatus::Warn, format!("Only {} MiB free", free >> 20))
+            .with_fix("Free up disk space; price, gas and token caches are written here"),
+        None => CheckResult::new(name, CheckStatus::Skipped, "Free space unavailable on this platform"),
+    }
+}
+
+#[cfg(unix)]
+#[allow(unsafe_code)]
+fn free_space(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is a valid NUL-terminated string and `stat` points to
+    // writable memory for one statvfs, which is initialised when it returns 0.
+    let stat = unsafe {
+        if libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stat.assume_init()
+    };
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_status_and_display() {
+        let mut report = DiagnosticsReport {
+            checks: vec![
+                CheckResult::pass("config", "No settings file, using defaults"),
+                CheckResult::new(
+                    "hardware",
+                    CheckStatus::Skipped,
+                    "Built without the hardware-wallets feature",
+                ),
+            ],
+            ran_at: Utc::now(),
+        };
+        assert!(report.is_healthy());
+        assert!(report.to_string().ends_with("All checks passed"));
+
+        report
+            .checks
+            .push(CheckResult::new("clock", CheckStatus::Fail, "Local clock is -600s").with_fix("Enable NTP"));
+        assert_eq!(report.status(), CheckStatus::Fail);
+        assert!(!report.is_healthy());
+        let text = report.to_string();
+        assert!(text.contains("[FAIL] clock"));
+        assert!(text.contains("fix: Enable NTP"));
+        assert!(text.ends_with("1 check(s) need attention"));
+    }
+
+    #[test]
+    fn test_clock_skew_and_disk_space() {
+        let block = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert_eq!(clock_skew(block, block + chrono::Duration::seconds(12)), 12);
+        assert_eq!(clock_skew(block, block - chrono::Duration::seconds(300)), -300);
+
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("not").join("created");
+        assert_ne!(check_disk_space(&missing, 0).status, CheckStatus::Fail);
+        #[cfg(unix)]
+        assert_eq!(check_disk_space(&missing, u64::MAX).status, CheckStatus::Warn);
+    }
+}
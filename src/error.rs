@@ -113,6 +113,18 @@ impl From<String> for VaughanError {
     }
 }
 
+impl From<AccountError> for VaughanError {
+    fn from(error: AccountError) -> Self {
+        match error {
+            AccountError::AccountNotFound { address, .. } => WalletError::AccountNotFound { address }.into(),
+            AccountError::InvalidCredentials { .. } => SecurityError::InvalidPassword.into(),
+            AccountError::AccountLocked { .. } => WalletError::WalletLocked.into(),
+            AccountError::ValidationFailed { message, .. } => VaughanError::ValidationError(message),
+            other => WalletError::Generic(other.to_string()).into(),
+        }
+    }
+}
+
 impl From<std::num::ParseIntError> for VaughanError {
     fn from(error: std::num::ParseIntError) -> Self {
         VaughanError::ValidationError(format!("Parse error: {error}"))
@@ -167,10 +179,21 @@ pub enum WalletError {
     #[error("Wallet is locked")]
     WalletLocked,
 
+    /// No account has been selected for the operation
+    #[error("No account selected")]
+    NoAccountSelected,
+
     /// Account has insufficient balance for the operation
     #[error("Insufficient balance")]
     InsufficientBalance,
 
+    /// The user or the approval frontend did not approve signing
+    #[error("Signing not approved: {reason}")]
+    ApprovalRejected {
+        /// Why the request was rejected
+        reason: String
+    },
+
     /// General wallet operation error
     #[error("Wallet error: {message}")]
     WalletError {
@@ -238,6 +261,22 @@ pub enum NetworkError {
         /// Error message describing the network failure
         message: String
     },
+
+    /// Outbound connection refused by the user's egress consent settings
+    #[error("Connection to {host} not permitted: {reason}")]
+    EgressBlocked {
+        /// Host that was not contacted
+        host: String,
+        /// Why the connection was refused
+        reason: String
+    },
+
+    /// Another transaction with the same nonce was mined instead
+    #[error("Transaction {tx_hash} was replaced by another transaction with the same nonce")]
+    TransactionReplaced {
+        /// Hash of the transaction that will not be mined
+        tx_hash: String
+    },
 }
 
 /// Smart contract interaction errors
@@ -385,6 +424,50 @@ pub enum SecurityError {
     #[error("Invalid password")]
     InvalidPassword,
 
+    /// Password does not meet the password policy
+    #[error("Password does not meet the password policy: {}", reasons.join("; "))]
+    WeakPassword {
+        /// Requirements the password fails
+        reasons: Vec<String>,
+    },
+
+    /// Transaction touches a denylisted address the user has not accepted
+    #[error("Transaction flagged by address screening: {}", reasons.join("; "))]
+    UnacknowledgedRisk {
+        /// Findings awaiting acknowledgement
+        reasons: Vec<String>,
+    },
+
+    /// Transaction falls outside a transaction policy rule
+    #[error("Transaction blocked by policy: {}", reasons.join("; "))]
+    PolicyViolation {
+        /// Rules the transaction breaks
+        reasons: Vec<String>,
+    },
+
+    /// The transaction policy file is unreadable; signing is refused until it is fixed
+    #[error("Invalid transaction policy {path}: {reason}")]
+    InvalidTransactionPolicy {
+        /// Path of the policy file
+        path: String,
+        /// Why it could not be loaded
+        reason: String,
+    },
+
+    /// The OS authentication prompt for a high-risk operation did not approve it
+    #[error("OS authentication failed: {reason}")]
+    OsAuthenticationFailed {
+        /// Operation and why it was not approved
+        reason: String,
+    },
+
+    /// A seed account created with a BIP-39 passphrase was used without it
+    #[error("{account} was created with a BIP-39 passphrase; enter it to continue")]
+    Bip39PassphraseRequired {
+        /// Name of the account
+        account: String,
+    },
+
     /// Authentication token has expired
     #[error("Authentication token expired")]
     TokenExpired,
@@ -563,6 +646,9 @@ pub enum HardwareWalletError {
 
     #[error("Invalid transaction: {reason}")]
     InvalidTransaction { reason: String },
+
+    #[error("Incompatible device version: {reason}. {guidance}")]
+    IncompatibleVersion { reason: String, guidance: String },
 }
 
 /// Error context with recovery information
@@ -608,6 +694,13 @@ pub enum RecoveryAction {
 }
 
 impl VaughanError {
+    /// RPC failure, with what was being done when it happened
+    pub fn rpc(context: &str, e: impl std::fmt::Display) -> Self {
+        VaughanError::Network(NetworkError::RpcError {
+            message: format!("{context}: {e}"),
+        })
+    }
+
     /// Get comprehensive error context with recovery information
     pub fn context(&self) -> ErrorContext {
         let support_code = Uuid::new_v4().to_string();
@@ -644,6 +737,18 @@ impl VaughanError {
                 timestamp,
             },
 
+            VaughanError::Network(NetworkError::TransactionReplaced { .. }) => ErrorContext {
+                user_message: "Not sent: another transaction from this account already used its nonce.".to_string(),
+                recovery_steps: vec![
+                    "Check the account's recent activity for the transaction that was mined".to_string(),
+                    "Create the transaction again if it is still needed".to_string(),
+                ],
+                support_code,
+                severity: ErrorSeverity::Medium,
+                category: ErrorCategory::Network,
+                timestamp,
+            },
+
             VaughanError::Wallet(WalletError::InsufficientBalance) => ErrorContext {
                 user_message: "Insufficient balance for this transaction.".to_string(),
                 recovery_steps: vec![
@@ -917,6 +1022,8 @@ impl VaughanError {
                 | VaughanError::HardwareWallet(HardwareWalletError::DeviceNotConnected)
                 | VaughanError::HardwareWallet(HardwareWalletError::BlindSigningDisabled)
                 | VaughanError::Wallet(WalletError::InsufficientBalance)
+                | VaughanError::Wallet(WalletError::ApprovalRejected { .. })
+                | VaughanError::Wallet(WalletError::NoAccountSelected)
                 | VaughanError::Security(SecurityError::ConfirmationRequired)
         )
     }
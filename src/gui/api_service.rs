@@ -40,7 +40,7 @@ pub fn create_eth_price_command(api_manager: Option<ExplorerApiManager>, chain_i
 /// Token information fetching (simplified version for now)
 pub async fn fetch_token_info(token_address: String, network_id: NetworkId) -> Result<TokenInfo, String> {
     use alloy::primitives::{Address, U256};
-    use alloy::providers::{Provider, ProviderBuilder};
+    use alloy::providers::Provider;
     use alloy::rpc::types::TransactionRequest;
     use std::str::FromStr;
 
@@ -56,10 +56,7 @@ pub async fn fetch_token_info(token_address: String, network_id: NetworkId) -> R
     };
 
     // Create provider
-    let url = rpc_url
-        .parse()
-        .map_err(|e| format!("Invalid RPC URL: {}", e))?;
-    let provider = ProviderBuilder::new().connect_http(url);
+    let provider = crate::network::connect_rpc(rpc_url, "token info").map_err(|e| e.to_string())?;
 
     // Helper function to call contract method
     let call_contract = |selector: &[u8]| {
@@ -4,6 +4,7 @@
 
 use crate::gui::services::AccountDisplayServiceTrait;
 use crate::gui::wallet_messages::Message;
+use crate::gui::widgets::identity_badge;
 use crate::gui::working_wallet::AppState;
 use iced::widget::{Button, Column, Container, Row, Space, Text};
 use iced::{Alignment, Color, Element, Length};
@@ -87,6 +88,7 @@ impl AccountManager {
 
                 let account_row = Container::new(
                     Row::new()
+                        .push(identity_badge(account.address, 28.0))
                         .push(
                             Column::new()
                                 .push(Text::new(&account.name).size(14))
@@ -104,10 +106,17 @@ impl AccountManager {
                         )
                         .push(Space::with_width(Length::Fixed(10.0)))
                         .push(
-                            Text::new("0.0000 ETH") // TODO: Get actual balance
-                                .size(14)
-                                .style(Color::from_rgb(0.2, 0.8, 0.2))
-                                .width(Length::FillPortion(1)),
+                            Text::new(
+                                state
+                                    .wallet()
+                                    .account_balances
+                                    .get(&account.address)
+                                    .map(String::as_str)
+                                    .unwrap_or("…"),
+                            )
+                            .size(14)
+                            .style(Color::from_rgb(0.2, 0.8, 0.2))
+                            .width(Length::FillPortion(1)),
                         )
                         .push(Space::with_width(Length::Fixed(10.0)))
                         .push(
@@ -129,6 +138,14 @@ impl AccountManager {
                 column = column.push(account_row);
             }
 
+            if state.wallet().loading_accounts {
+                column = column.push(
+                    Text::new("⟲ Loading more accounts...")
+                        .size(12)
+                        .style(Color::from_rgb(0.6, 0.4, 1.0)),
+                );
+            }
+
             column.into()
         }
     }
@@ -2,6 +2,7 @@
 //!
 //! This module contains the balance display UI component.
 
+use crate::gui::safe_calculations::display_amount;
 use crate::gui::wallet_messages::Message;
 use crate::gui::working_wallet::AppState;
 use iced::widget::{Button, Column, Container, Row, Space, Text};
@@ -28,7 +29,7 @@ impl BalanceDisplay {
     fn main_balance(state: &AppState) -> Element<'_, Message> {
         let balance_text = format!(
             "{} {}",
-            state.network().balance,
+            display_amount(&state.network().balance),
             state
                 .network()
                 .available_networks
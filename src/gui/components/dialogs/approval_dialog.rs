@@ -0,0 +1,74 @@
+//! Signing Approval Dialog Component
+//!
+//! Shows what the wallet is about to sign and answers its approval request.
+
+use iced::{
+    widget::{Button, Column, Container, Row, Space, Text},
+    Color, Element, Length,
+};
+
+use super::confirmation_dialogs::ModalBackgroundStyle;
+use crate::gui::working_wallet::AppState;
+use crate::gui::{theme::styles, Message};
+
+/// Approval dialog for the oldest signing request, if one is waiting
+pub fn approval_dialog_view(state: &AppState) -> Option<Element<'_, Message>> {
+    let request = state.transaction().approval_requests.front()?;
+
+    let details = request
+        .details()
+        .into_iter()
+        .fold(Column::new().spacing(8), |column, line| {
+            column.push(Text::new(line).size(14).style(Color::from_rgb(0.8, 0.8, 0.8)))
+        });
+
+    let dialog = Container::new(
+        Column::new()
+            .push(
+                Text::new(format!("✍️ Approve {} signature", request.kind.label()))
+                    .size(20)
+                    .style(Color::WHITE),
+            )
+            .push(Space::with_height(Length::Fixed(20.0)))
+            .push(Container::new(details).padding(15).width(Length::Fill))
+            .push(Space::with_height(Length::Fixed(15.0)))
+            .push(
+                Text::new("Only approve if this is exactly what you meant to sign.")
+                    .size(12)
+                    .style(Color::from_rgb(1.0, 0.8, 0.4)),
+            )
+            .push(Space::with_height(Length::Fixed(30.0)))
+            .push(
+                Row::new()
+                    .spacing(15)
+                    .push(
+                        Button::new(Text::new("Reject"))
+                            .on_press(Message::AnswerApproval(request.id, false))
+                            .padding([10, 20])
+                            .style(styles::secondary_button()),
+                    )
+                    .push(
+                        Button::new(Text::new("Approve"))
+                            .on_press(Message::AnswerApproval(request.id, true))
+                            .padding([10, 20])
+                            .style(styles::primary_button()),
+                    ),
+            )
+            .align_items(iced::Alignment::Center)
+            .spacing(5),
+    )
+    .padding(30)
+    .style(styles::dark_flat_container())
+    .max_width(560);
+
+    Some(
+        Container::new(dialog)
+            .padding(50)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Custom(Box::new(ModalBackgroundStyle)))
+            .into(),
+    )
+}
@@ -8,6 +8,7 @@ use iced::{
     Element, Length,
 };
 
+use super::import_wallet_dialog::bip39_passphrase_input;
 use crate::gui::{theme::styles, working_wallet::AppState, Message};
 use crate::security::SeedStrength;
 
@@ -101,6 +102,8 @@ pub fn create_wallet_dialog_view(state: &AppState) -> Element<'_, Message> {
                 .spacing(5),
         )
         .push(Space::with_height(Length::Fixed(20.0)))
+        .push(bip39_passphrase_input(state))
+        .push(Space::with_height(Length::Fixed(20.0)))
         .push(
             // Master Password
             Column::new()
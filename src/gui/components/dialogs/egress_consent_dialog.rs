@@ -0,0 +1,91 @@
+//! First-Run Network Consent Dialog Component
+//!
+//! Lets the user choose which kinds of network services the wallet may contact.
+
+use iced::{
+    widget::{Button, Column, Container, Row, Space, Text},
+    Color, Element, Length,
+};
+
+use super::confirmation_dialogs::ModalBackgroundStyle;
+use crate::gui::working_wallet::AppState;
+use crate::gui::{theme::styles, Message};
+use crate::network::egress::{CategoryMode, EgressCategory};
+
+const MODES: [(CategoryMode, &str); 3] = [
+    (CategoryMode::Allow, "Allow"),
+    (CategoryMode::AskPerHost, "Ask"),
+    (CategoryMode::Disabled, "Off"),
+];
+
+/// Consent dialog shown until the first-run network choices are saved
+pub fn egress_consent_dialog_view(state: &AppState) -> Option<Element<'_, Message>> {
+    let choices = state.ui().egress_consent.as_ref()?;
+
+    let categories = EgressCategory::ALL
+        .into_iter()
+        .fold(Column::new().spacing(10), |column, category| {
+            let current = choices.get(&category).copied().unwrap_or(CategoryMode::Allow);
+            let buttons = MODES.into_iter().fold(Row::new().spacing(5), |row, (mode, label)| {
+                let style = if mode == current {
+                    styles::primary_button()
+                } else {
+                    styles::secondary_button()
+                };
+                row.push(
+                    Button::new(Text::new(label).size(12))
+                        .on_press(Message::EgressModeSelected(category, mode))
+                        .padding([5, 10])
+                        .style(style),
+                )
+            });
+            column.push(
+                Row::new()
+                    .spacing(15)
+                    .align_items(iced::Alignment::Center)
+                    .push(
+                        Text::new(category.description())
+                            .size(14)
+                            .style(Color::from_rgb(0.8, 0.8, 0.8))
+                            .width(Length::Fill),
+                    )
+                    .push(buttons),
+            )
+        });
+
+    let dialog = Container::new(
+        Column::new()
+            .push(Text::new("🌐 Network access").size(20).style(Color::WHITE))
+            .push(Space::with_height(Length::Fixed(20.0)))
+            .push(
+                Text::new("Choose which services Vaughan may contact. \"Ask\" only connects to hosts you approve.")
+                    .size(12)
+                    .style(Color::from_rgb(1.0, 0.8, 0.4)),
+            )
+            .push(Space::with_height(Length::Fixed(15.0)))
+            .push(Container::new(categories).padding(15).width(Length::Fill))
+            .push(Space::with_height(Length::Fixed(30.0)))
+            .push(
+                Button::new(Text::new("Continue"))
+                    .on_press(Message::ConfirmEgressConsent)
+                    .padding([10, 20])
+                    .style(styles::primary_button()),
+            )
+            .align_items(iced::Alignment::Center)
+            .spacing(5),
+    )
+    .padding(30)
+    .style(styles::dark_flat_container())
+    .max_width(720);
+
+    Some(
+        Container::new(dialog)
+            .padding(50)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Custom(Box::new(ModalBackgroundStyle)))
+            .into(),
+    )
+}
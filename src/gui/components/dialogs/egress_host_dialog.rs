@@ -0,0 +1,72 @@
+//! Network Host Approval Dialog Component
+//!
+//! Asks the user to allow or block a host that a restricted category wanted to contact.
+
+use iced::{
+    widget::{Button, Column, Container, Row, Space, Text},
+    Color, Element, Length,
+};
+
+use super::confirmation_dialogs::ModalBackgroundStyle;
+use crate::gui::working_wallet::AppState;
+use crate::gui::{theme::styles, Message};
+
+/// Approval dialog for the most recent host waiting for consent, if any
+pub fn egress_host_dialog_view(state: &AppState) -> Option<Element<'_, Message>> {
+    let event = state.ui().pending_egress_host.as_ref()?;
+
+    let details = Column::new()
+        .spacing(8)
+        .push(Text::new(&event.host).size(16).style(Color::WHITE))
+        .push(
+            Text::new(format!("{} ({})", event.category.description(), event.purpose))
+                .size(14)
+                .style(Color::from_rgb(0.8, 0.8, 0.8)),
+        );
+
+    let dialog = Container::new(
+        Column::new()
+            .push(Text::new("🌐 Allow connection?").size(20).style(Color::WHITE))
+            .push(Space::with_height(Length::Fixed(20.0)))
+            .push(Container::new(details).padding(15).width(Length::Fill))
+            .push(Space::with_height(Length::Fixed(15.0)))
+            .push(
+                Text::new("Your choice applies to every request to this host and can be undone.")
+                    .size(12)
+                    .style(Color::from_rgb(1.0, 0.8, 0.4)),
+            )
+            .push(Space::with_height(Length::Fixed(30.0)))
+            .push(
+                Row::new()
+                    .spacing(15)
+                    .push(
+                        Button::new(Text::new("Block"))
+                            .on_press(Message::EgressHostDecided(event.host.clone(), false))
+                            .padding([10, 20])
+                            .style(styles::secondary_button()),
+                    )
+                    .push(
+                        Button::new(Text::new("Allow"))
+                            .on_press(Message::EgressHostDecided(event.host.clone(), true))
+                            .padding([10, 20])
+                            .style(styles::primary_button()),
+                    ),
+            )
+            .align_items(iced::Alignment::Center)
+            .spacing(5),
+    )
+    .padding(30)
+    .style(styles::dark_flat_container())
+    .max_width(560);
+
+    Some(
+        Container::new(dialog)
+            .padding(50)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Custom(Box::new(ModalBackgroundStyle)))
+            .into(),
+    )
+}
@@ -0,0 +1,107 @@
+//! Network Activity Dialog Component
+//!
+//! Lists the hosts the wallet contacted and every recent connection check.
+
+use iced::{
+    widget::{Button, Column, Container, Row, Scrollable, Space, Text},
+    Color, Element, Length,
+};
+
+use super::confirmation_dialogs::ModalBackgroundStyle;
+use crate::gui::working_wallet::AppState;
+use crate::gui::{theme::styles, Message};
+use crate::network::egress::{egress, EgressVerdict};
+
+/// Egress log dialog, while it is open
+pub fn egress_log_dialog_view(state: &AppState) -> Option<Element<'_, Message>> {
+    if !state.ui().show_egress_log {
+        return None;
+    }
+
+    let contacted = egress()
+        .contacted_hosts()
+        .into_iter()
+        .fold(Column::new().spacing(6), |column, (category, hosts)| {
+            column.push(
+                Text::new(format!("{}: {}", category.description(), hosts.join(", ")))
+                    .size(13)
+                    .style(Color::from_rgb(0.8, 0.8, 0.8)),
+            )
+        });
+
+    let events = egress()
+        .log()
+        .into_iter()
+        .rev()
+        .fold(Column::new().spacing(4), |column, event| {
+            let (verdict, color) = match event.verdict {
+                EgressVerdict::Allowed => ("allowed", Color::from_rgb(0.4, 0.8, 0.4)),
+                EgressVerdict::ConsentRequired => ("waiting", Color::from_rgb(1.0, 0.8, 0.4)),
+                EgressVerdict::Denied => ("blocked", Color::from_rgb(0.9, 0.4, 0.4)),
+            };
+            column.push(
+                Row::new()
+                    .spacing(10)
+                    .push(
+                        Text::new(event.at.format("%H:%M:%S").to_string())
+                            .size(12)
+                            .style(Color::from_rgb(0.6, 0.6, 0.6)),
+                    )
+                    .push(Text::new(verdict).size(12).style(color).width(Length::Fixed(60.0)))
+                    .push(Text::new(event.host).size(12).style(Color::WHITE))
+                    .push(
+                        Text::new(event.purpose)
+                            .size(12)
+                            .style(Color::from_rgb(0.6, 0.6, 0.6)),
+                    ),
+            )
+        });
+
+    let dialog = Container::new(
+        Column::new()
+            .push(Text::new("🌐 Network activity").size(20).style(Color::WHITE))
+            .push(Space::with_height(Length::Fixed(20.0)))
+            .push(Text::new("Contacted hosts").size(14).style(Color::WHITE))
+            .push(Container::new(contacted).padding(10).width(Length::Fill))
+            .push(Space::with_height(Length::Fixed(10.0)))
+            .push(Text::new("Recent connections").size(14).style(Color::WHITE))
+            .push(
+                Container::new(Scrollable::new(events).height(Length::Fixed(300.0)))
+                    .padding(10)
+                    .width(Length::Fill),
+            )
+            .push(Space::with_height(Length::Fixed(20.0)))
+            .push(
+                Row::new()
+                    .spacing(15)
+                    .push(
+                        Button::new(Text::new("Clear"))
+                            .on_press(Message::ClearEgressLog)
+                            .padding([10, 20])
+                            .style(styles::secondary_button()),
+                    )
+                    .push(
+                        Button::new(Text::new("Close"))
+                            .on_press(Message::HideEgressLog)
+                            .padding([10, 20])
+                            .style(styles::primary_button()),
+                    ),
+            )
+            .align_items(iced::Alignment::Center)
+            .spacing(5),
+    )
+    .padding(30)
+    .style(styles::dark_flat_container())
+    .max_width(720);
+
+    Some(
+        Container::new(dialog)
+            .padding(50)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Custom(Box::new(ModalBackgroundStyle)))
+            .into(),
+    )
+}
@@ -35,7 +35,14 @@ pub fn export_wallet_dialog_view(state: &AppState) -> Element<'_, Message> {
         .push(if state.exporting_data {
             // Show loading state while exporting
             Column::new()
-                .push(Text::new("Exporting wallet data...").size(16))
+                .push(
+                    Text::new(if state.backup_check_active {
+                        "Checking seed phrase backup..."
+                    } else {
+                        "Exporting wallet data..."
+                    })
+                    .size(16),
+                )
                 .push(Space::with_height(Length::Fixed(10.0)))
                 .align_items(iced::Alignment::Center)
         } else if state.exported_seed_phrase.is_some() || state.exported_private_key.is_some() || state.export_error_message.is_some() {
@@ -269,6 +276,7 @@ pub fn export_wallet_dialog_view(state: &AppState) -> Element<'_, Message> {
                         )
                     }
                 })
+                .push(backup_check_section(state))
                 .spacing(5)
         })
         .spacing(5);
@@ -286,3 +294,88 @@ pub fn export_wallet_dialog_view(state: &AppState) -> Element<'_, Message> {
     .center_y()
     .into()
 }
+
+/// "Verify my backup": re-enter the seed phrase and learn only whether it matches
+fn backup_check_section(state: &AppState) -> Column<'_, Message> {
+    if state.selected_export_account_id.is_none() {
+        return Column::new();
+    }
+
+    let mut column = Column::new().push(Space::with_height(Length::Fixed(25.0)));
+
+    if !state.backup_check_active {
+        return column
+            .push(
+                Text::new("Check that your written seed phrase is correct without revealing it on screen.")
+                    .size(13)
+                    .style(text::secondary()),
+            )
+            .push(Space::with_height(Length::Fixed(8.0)))
+            .push(
+                Button::new(Text::new("Verify Seed Phrase Backup"))
+                    .on_press(Message::StartBackupCheck)
+                    .padding([10, 20])
+                    .style(styles::secondary_button())
+                    .width(Length::Fill),
+            );
+    }
+
+    column = column
+        .push(Text::new("Enter your seed phrase exactly as written on your backup:").size(14))
+        .push(Space::with_height(Length::Fixed(8.0)))
+        .push(
+            iced::widget::text_input("word1 word2 word3 ...", &state.backup_check_phrase)
+                .on_input(Message::BackupCheckPhraseChanged)
+                .on_submit(Message::SubmitBackupCheck)
+                .secure(true)
+                .size(14)
+                .padding(10)
+                .style(styles::primary_text_input()),
+        );
+
+    match &state.backup_check_result {
+        Some(Ok(true)) => {
+            column = column.push(
+                Text::new("✅ Your backup matches this account's seed phrase.")
+                    .size(14)
+                    .style(text::success()),
+            );
+        }
+        Some(Ok(false)) => {
+            column = column.push(
+                Text::new("❌ This phrase does not match. Check your backup word by word.")
+                    .size(14)
+                    .style(text::error()),
+            );
+        }
+        Some(Err(error)) => {
+            column = column.push(Text::new(error).size(14).style(text::error()));
+        }
+        None => {}
+    }
+
+    let check_button = Button::new(Text::new("Check Backup"))
+        .padding([10, 20])
+        .style(styles::primary_button())
+        .width(Length::FillPortion(1));
+
+    column
+        .push(Space::with_height(Length::Fixed(10.0)))
+        .push(
+            Row::new()
+                .push(
+                    Button::new(Text::new("Cancel"))
+                        .on_press(Message::CancelInlineExport)
+                        .padding([10, 20])
+                        .style(styles::secondary_button())
+                        .width(Length::FillPortion(1)),
+                )
+                .push(Space::with_width(Length::Fixed(10.0)))
+                .push(if state.backup_check_phrase.trim().is_empty() {
+                    check_button
+                } else {
+                    check_button.on_press(Message::SubmitBackupCheck)
+                }),
+        )
+        .spacing(5)
+}
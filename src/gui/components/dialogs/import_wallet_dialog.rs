@@ -9,6 +9,8 @@ use iced::{
 };
 
 use crate::gui::{theme::styles, wallet_types::ImportType, working_wallet::AppState, Message};
+use crate::security::seed::derivation::BIP39_PASSPHRASE_NOTICE;
+use crate::security::seed::SeedManager;
 
 /// Import wallet dialog view
 pub fn import_wallet_dialog_view(state: &AppState) -> Element<'_, Message> {
@@ -88,8 +90,11 @@ pub fn import_wallet_dialog_view(state: &AppState) -> Element<'_, Message> {
                             .padding(10)
                             .width(Length::Fill),
                     )
+                    .push(seed_word_hints(&state.wallet().seed_phrase))
                     .spacing(5),
             )
+            .push(Space::with_height(Length::Fixed(20.0)))
+            .push(bip39_passphrase_input(state))
         }
         ImportType::PrivateKey => {
             use secrecy::ExposeSecret;
@@ -210,3 +215,75 @@ pub fn import_wallet_dialog_view(state: &AppState) -> Element<'_, Message> {
     .center_y()
     .into()
 }
+
+/// Optional BIP-39 passphrase, labeled so users know it selects a different wallet
+pub fn bip39_passphrase_input(state: &AppState) -> Column<'_, Message> {
+    use secrecy::ExposeSecret;
+    Column::new()
+        .push(Text::new("BIP-39 Passphrase (optional)").size(14))
+        .push(Space::with_height(Length::Fixed(8.0)))
+        .push(
+            TextInput::new("Leave empty unless you set one", state.wallet().bip39_passphrase.expose_secret())
+                .on_input(Message::Bip39PassphraseChanged)
+                .secure(true)
+                .padding(10)
+                .width(Length::Fill),
+        )
+        .push(
+            Text::new(BIP39_PASSPHRASE_NOTICE)
+                .size(12)
+                .style(iced::Color::from_rgb(1.0, 0.75, 0.3)),
+        )
+        .spacing(5)
+}
+
+/// Inline checks of the words entered so far
+///
+/// Finished words that aren't in the BIP-39 wordlist are flagged with
+/// corrections, and the word being typed gets completions that fill it in.
+fn seed_word_hints(phrase: &str) -> Column<'_, Message> {
+    let error_color = iced::Color::from_rgb(1.0, 0.4, 0.4);
+    let mut hints = Column::new().spacing(4);
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    let typing = phrase.chars().last().is_some_and(|c| !c.is_whitespace());
+    let finished = if typing { &words[..words.len() - 1] } else { &words[..] };
+
+    for (position, word) in finished.iter().enumerate() {
+        if let Some(suggestion) = SeedManager::validate_word(word, position) {
+            let hint = if suggestion.suggested_words.is_empty() {
+                format!("Word {}: '{}' is not a BIP-39 word", position + 1, word)
+            } else {
+                format!(
+                    "Word {}: '{}' is not a BIP-39 word, did you mean {}?",
+                    position + 1,
+                    word,
+                    suggestion.suggested_words.join(", ")
+                )
+            };
+            hints = hints.push(Text::new(hint).size(12).style(error_color));
+        }
+    }
+
+    if let Some(prefix) = words.last().filter(|_| typing) {
+        let completions = SeedManager::suggest_words(prefix);
+        if completions.is_empty() {
+            hints = hints.push(
+                Text::new(format!("No BIP-39 word starts with '{prefix}'"))
+                    .size(12)
+                    .style(error_color),
+            );
+        } else if completions.len() > 1 || completions[0] != prefix.to_lowercase() {
+            let head = &phrase[..phrase.len() - prefix.len()];
+            let row = completions.into_iter().fold(Row::new().spacing(6), |row, word| {
+                row.push(
+                    Button::new(Text::new(word.clone()).size(12))
+                        .on_press(Message::SeedPhraseChanged(format!("{head}{word} ")))
+                        .padding([4, 8])
+                        .style(styles::secondary_button()),
+                )
+            });
+            hints = hints.push(row);
+        }
+    }
+    hints
+}
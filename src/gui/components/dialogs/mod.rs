@@ -4,10 +4,14 @@
 //! and views/dialogs.rs for better code organization.
 
 // Dialog component modules
+pub mod approval_dialog;
 pub mod cancel_transaction_dialog;
 pub mod confirmation_dialogs;
 pub mod create_wallet_dialog;
 pub mod custom_token_dialog;
+pub mod egress_consent_dialog;
+pub mod egress_host_dialog;
+pub mod egress_log_dialog;
 pub mod export_wallet_dialog;
 pub mod import_wallet_dialog;
 pub mod network_dialog;
@@ -16,6 +20,7 @@ pub mod transaction_confirmation;
 pub mod unified_password_dialog;
 
 // Re-export dialog functions for easy access
+pub use approval_dialog::approval_dialog_view;
 pub use cancel_transaction_dialog::cancel_transaction_dialog_view;
 pub use confirmation_dialogs::{
     clear_logs_confirmation_dialog_view, dapps_coming_soon_dialog_view, delete_account_dialog_view,
@@ -24,6 +29,9 @@ pub use confirmation_dialogs::{
 };
 pub use create_wallet_dialog::create_wallet_dialog_view;
 pub use custom_token_dialog::custom_token_screen_view;
+pub use egress_consent_dialog::egress_consent_dialog_view;
+pub use egress_host_dialog::egress_host_dialog_view;
+pub use egress_log_dialog::egress_log_dialog_view;
 pub use export_wallet_dialog::export_wallet_dialog_view;
 pub use import_wallet_dialog::import_wallet_dialog_view;
 pub use network_dialog::add_network_dialog_view;
@@ -1,15 +1,18 @@
 //! Transaction Confirmation Dialog
 //!
 //! Shows gas estimation and transaction details before final confirmation.
-//! Includes password input when session is locked.
+//! Includes password input when session is locked, and address screening
+//! findings that must be acknowledged before sending.
 
 use iced::{
     widget::{Button, Checkbox, Column, Container, Row, Space, Text, TextInput},
-    Color, Element, Length,
+    Alignment, Color, Element, Length,
 };
 
 use crate::gui::state::AppState;
+use crate::gui::widgets::identity_badge;
 use crate::gui::{theme::styles, Message};
+use crate::security::RiskLevel;
 
 /// Transaction confirmation dialog view
 pub fn transaction_confirmation_dialog_view(state: &AppState) -> Element<'_, Message> {
@@ -22,18 +25,43 @@ pub fn transaction_confirmation_dialog_view(state: &AppState) -> Element<'_, Mes
             .push(Text::new("Confirm Transaction").size(20).style(Color::WHITE))
             .push(Space::with_height(Length::Fixed(20.0)));
 
+        // Sender identity, so the user notices signing from the wrong account
+        let sender = state
+            .wallet()
+            .available_accounts
+            .iter()
+            .find(|account| state.wallet().current_account_id.as_ref() == Some(&account.id));
+        if let Some(sender) = sender {
+            column = column
+                .push(
+                    Row::new()
+                        .push(Text::new("From:").size(14).style(Color::from_rgb(0.7, 0.7, 0.7)))
+                        .push(Space::with_width(Length::Fixed(10.0)))
+                        .push(identity_badge(sender.address, 18.0))
+                        .push(Text::new(&sender.name).size(14).style(Color::WHITE))
+                        .spacing(5)
+                        .align_items(Alignment::Center),
+                )
+                .push(Space::with_height(Length::Fixed(10.0)));
+        }
+
         // Transaction details
+        let mut recipient = Row::new()
+            .push(Text::new("To:").size(14).style(Color::from_rgb(0.7, 0.7, 0.7)))
+            .push(Space::with_width(Length::Fixed(10.0)));
+        if let Ok(address) = state.transaction().send_to_address.trim().parse() {
+            recipient = recipient.push(identity_badge(address, 18.0));
+        }
         column = column
             .push(
-                Row::new()
-                    .push(Text::new("To:").size(14).style(Color::from_rgb(0.7, 0.7, 0.7)))
-                    .push(Space::with_width(Length::Fixed(10.0)))
+                recipient
                     .push(
                         Text::new(&state.transaction().send_to_address)
                             .size(14)
                             .style(Color::WHITE),
                     )
-                    .spacing(5),
+                    .spacing(5)
+                    .align_items(Alignment::Center),
             )
             .push(Space::with_height(Length::Fixed(10.0)))
             .push(
@@ -53,6 +81,84 @@ pub fn transaction_confirmation_dialog_view(state: &AppState) -> Element<'_, Mes
             )
             .push(Space::with_height(Length::Fixed(20.0)));
 
+        // Decoded contract call, so the user sees what the calldata does
+        if let Some(preview) = &estimation.call_preview {
+            column = column
+                .push(
+                    Row::new()
+                        .push(Text::new("Call:").size(14).style(Color::from_rgb(0.7, 0.7, 0.7)))
+                        .push(Space::with_width(Length::Fixed(10.0)))
+                        .push(Text::new(preview.as_str()).size(12).style(Color::WHITE))
+                        .spacing(5),
+                )
+                .push(Space::with_height(Length::Fixed(20.0)));
+        }
+
+        // What the recipient is, when it is not a plain wallet
+        for note in &estimation.recipient_notes {
+            column = column
+                .push(
+                    Text::new(format!("⚠️ {note}"))
+                        .size(12)
+                        .style(Color::from_rgb(1.0, 0.8, 0.2)),
+                )
+                .push(Space::with_height(Length::Fixed(10.0)));
+        }
+
+        // Address screening findings need an explicit acknowledgement
+        if !estimation.screening.is_clear() {
+            let critical = estimation.screening.risk_level() == RiskLevel::Critical;
+            let accent = if critical {
+                Color::from_rgb(1.0, 0.3, 0.3)
+            } else {
+                Color::from_rgb(1.0, 0.8, 0.2)
+            };
+            let mut findings = Column::new()
+                .push(
+                    Text::new(if critical {
+                        "🛑 Dangerous address"
+                    } else {
+                        "⚠️ Check this address"
+                    })
+                    .size(14)
+                    .style(accent),
+                )
+                .push(Space::with_height(Length::Fixed(8.0)));
+            for finding in &estimation.screening.findings {
+                findings = findings.push(Text::new(finding.to_string()).size(12).style(Color::WHITE));
+            }
+            column = column
+                .push(
+                    Container::new(
+                        findings.push(Space::with_height(Length::Fixed(10.0))).push(
+                            Checkbox::new(
+                                "I understand the risk and want to send anyway",
+                                state.transaction().risk_acknowledgement.is_some(),
+                            )
+                            .on_toggle(Message::AcknowledgeScreeningRisk)
+                            .size(16)
+                            .text_size(12),
+                        ),
+                    )
+                    .padding(15)
+                    .style(move |_theme: &iced::Theme| iced::widget::container::Appearance {
+                        background: Some(iced::Background::Color(Color { a: 0.1, ..accent })),
+                        border: iced::Border {
+                            color: accent,
+                            width: 1.0,
+                            radius: 4.0.into(),
+                        },
+                        ..Default::default()
+                    })
+                    .width(Length::Fill),
+                )
+                .push(Space::with_height(Length::Fixed(15.0)));
+        }
+        let screening_cleared = estimation
+            .screening
+            .ensure_cleared(state.transaction().risk_acknowledgement.as_ref())
+            .is_ok();
+
         // Password section (only if session is locked)
         if session_locked {
             column = column
@@ -215,7 +321,7 @@ pub fn transaction_confirmation_dialog_view(state: &AppState) -> Element<'_, Mes
                             })
                             .size(14),
                         )
-                        .on_press_maybe(if !state.transaction().sending_transaction {
+                        .on_press_maybe(if !state.transaction().sending_transaction && screening_cleared {
                             Some(Message::ConfirmTransaction)
                         } else {
                             None
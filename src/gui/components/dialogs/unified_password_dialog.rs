@@ -206,6 +206,12 @@ fn get_dialog_content(config: &PasswordDialogConfig) -> (String, String, String,
             "Master Password".into(),
             "Unlock".into(),
         ),
+        PasswordDialogConfig::KeychainVault => (
+            "Unlock Keychain Vault".into(),
+            "Enter the password of the encrypted keychain vault that holds your keys.".into(),
+            "Vault Password".into(),
+            "Unlock".into(),
+        ),
         PasswordDialogConfig::AccountUnlock { account_name, .. } => (
             "Unlock Account".into(),
             format!("Enter password for account '{}' to unlock it.", account_name),
@@ -261,7 +267,10 @@ fn get_dialog_content(config: &PasswordDialogConfig) -> (String, String, String,
         ),
         PasswordDialogConfig::DeleteAccount { account_name } => (
             "Delete Account".into(),
-            format!("Enter password to confirm DELETION of '{}'.", account_name),
+            format!(
+                "Enter password to confirm DELETION of '{}'. It also encrypts the recovery point taken first.",
+                account_name
+            ),
             "Password".into(),
             "Delete".into(),
         ),
@@ -307,7 +316,10 @@ fn get_dialog_content(config: &PasswordDialogConfig) -> (String, String, String,
 
 fn should_show_cancel(config: &PasswordDialogConfig) -> bool {
     // Force some flows to be non-cancellable if desired (e.g., initial setup modal that covers screen)
-    !matches!(config, PasswordDialogConfig::WalletSetup { .. })
+    !matches!(
+        config,
+        PasswordDialogConfig::WalletSetup { .. } | PasswordDialogConfig::KeychainVault
+    )
 }
 
 fn should_show_remember(config: &PasswordDialogConfig) -> bool {
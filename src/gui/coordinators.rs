@@ -30,7 +30,7 @@ impl NetworkCoordinator {
     /// Create a new network coordinator with default values
     pub fn new() -> Self {
         Self {
-            current_network: NetworkId(943), // Default to PulseChain Testnet v4
+            current_network: crate::network::startup::fallback_network(), // Replaced by the resolved startup network once the wallet loads
             available_networks: Vec::new(),
             current_balance: "0.0000".to_string(),
             is_loading: false,
@@ -16,6 +16,8 @@ impl WorkingWalletApp {
             Message::NetworkSelected(network_id) => self.handle_network_selected(network_id),
             Message::SmartPollTick => self.handle_smart_poll_tick(),
             Message::BalanceChanged(old_balance, new_balance) => self.handle_balance_changed(old_balance, new_balance),
+            Message::PolledBalanceChanged(change) => self.handle_polled_balance_changed(change),
+            Message::BalancePollingStarted(result) => self.handle_balance_polling_started(result),
             _ => Command::none(),
         }
     }
@@ -23,6 +25,12 @@ impl WorkingWalletApp {
     /// Handle network selection
     fn handle_network_selected(&mut self, network_id: NetworkId) -> Command<Message> {
         self.state.network_mut().current_network = network_id;
+
+        // Remember the selection so the next launch starts on this network
+        if let Err(e) = crate::network::startup::remember_last_used_network(network_id) {
+            tracing::warn!("Failed to persist last used network: {}", e);
+        }
+
         // Reset last balance when network changes
         // Reset last balance when network changes - handled by coordinator
 
@@ -72,7 +80,7 @@ impl WorkingWalletApp {
                 self.state.ui_mut().poll_interval = 60;
 
                 // Check for both balance updates and incoming transactions
-                let balance_cmd = self.dispatch_message(Message::InternalRefreshBalance);
+                let balance_cmd = self.poll_balance();
                 let tx_check_cmd = self.check_for_incoming_transactions_periodically();
 
                 Command::batch(vec![balance_cmd, tx_check_cmd])
@@ -81,7 +89,7 @@ impl WorkingWalletApp {
                 self.state.ui_mut().poll_interval = 10;
 
                 // Check for both balance updates and incoming transactions more frequently
-                let balance_cmd = self.dispatch_message(Message::InternalRefreshBalance);
+                let balance_cmd = self.poll_balance();
                 let tx_check_cmd = self.check_for_incoming_transactions_periodically();
 
                 Command::batch(vec![balance_cmd, tx_check_cmd])
@@ -91,6 +99,63 @@ impl WorkingWalletApp {
         }
     }
 
+    /// Refresh the balance on a poll tick unless the wallet's poller pushes changes
+    fn poll_balance(&mut self) -> Command<Message> {
+        if self.state.ui().balance_events_active {
+            return Command::none();
+        }
+        self.dispatch_message(Message::InternalRefreshBalance)
+    }
+
+    /// (Re)start the wallet's background balance poller over its current accounts
+    pub(crate) fn restart_balance_polling(&self) -> Command<Message> {
+        let Some(wallet) = self.wallet.clone() else {
+            return Command::none();
+        };
+        Command::perform(
+            async move {
+                wallet
+                    .write()
+                    .await
+                    .start_balance_polling()
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            Message::BalancePollingStarted,
+        )
+    }
+
+    /// Fall back to refreshing on poll ticks if the poller could not start
+    fn handle_balance_polling_started(&mut self, result: Result<(), String>) -> Command<Message> {
+        match result {
+            Ok(()) => {
+                tracing::info!("💰 Background balance polling active");
+                self.state.ui_mut().balance_events_active = true;
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ Background balance polling unavailable, refreshing on poll ticks: {}", e);
+                self.state.ui_mut().balance_events_active = false;
+            }
+        }
+        Command::none()
+    }
+
+    /// Refresh the displayed balance when the poller reports a change for it
+    fn handle_polled_balance_changed(&mut self, change: crate::network::poller::BalanceChanged) -> Command<Message> {
+        let current_account = self.state.wallet().current_account_id.as_ref().and_then(|id| {
+            self.state
+                .wallet()
+                .available_accounts
+                .iter()
+                .find(|account| &account.id == id)
+                .map(|account| account.address)
+        });
+        if change.network != self.state.network().current_network || current_account != Some(change.account) {
+            return Command::none();
+        }
+        self.dispatch_message(Message::InternalRefreshBalance)
+    }
+
     /// Handle balance change notifications
     fn handle_balance_changed(&mut self, old_balance: String, new_balance: String) -> Command<Message> {
         use crate::gui::utils::parse_balance;
@@ -163,6 +163,29 @@ impl WorkingWalletApp {
             return Command::none();
         }
 
+        if matches!(
+            security.password_dialog.config,
+            Some(crate::gui::state::auth_state::PasswordDialogConfig::KeychainVault)
+        ) {
+            return Command::perform(
+                async move {
+                    let attempt = password.clone();
+                    match tokio::task::spawn_blocking(move || crate::security::unlock_keychain_vault(&attempt)).await {
+                        Ok(Ok(())) => Ok(password),
+                        Ok(Err(e)) => {
+                            tracing::warn!("❌ Keychain vault unlock failed: {}", e);
+                            Err(PasswordError::DecryptionFailed)
+                        }
+                        Err(e) => {
+                            tracing::error!("❌ Keychain vault unlock task failed: {}", e);
+                            Err(PasswordError::DecryptionFailed)
+                        }
+                    }
+                },
+                Message::PasswordValidated,
+            );
+        }
+
         // Simple Alloy-based validation: try to create a wallet with the password
         // This follows DEVELOPMENT_RULES.md - use Alloy for everything
         tracing::info!("🔓 Using Alloy approach for password validation");
@@ -193,6 +216,18 @@ impl WorkingWalletApp {
         result: std::result::Result<SecretString, PasswordError>,
     ) -> Command<Message> {
         match result {
+            Ok(_)
+                if matches!(
+                    self.state.auth().password_dialog.config,
+                    Some(crate::gui::state::auth_state::PasswordDialogConfig::KeychainVault)
+                ) =>
+            {
+                // The vault password is not the session password; only continue startup
+                self.state.auth_mut().password_dialog.hide();
+                tracing::info!("🔓 Keychain vault unlocked");
+                self.add_log_entry(LogCategory::Wallet, "Keychain vault unlocked".to_string(), None);
+                Self::check_startup_accounts()
+            }
             Ok(password) => {
                 // Password accepted - unlock session immediately
                 let remember = self.state.auth().password_dialog.remember_session;
@@ -238,6 +273,10 @@ impl WorkingWalletApp {
                         tracing::info!("🔓 Export authenticated, proceeding");
                         self.dispatch_message(Message::PerformWalletExport(password))
                     }
+                    Some(crate::gui::state::auth_state::PasswordDialogConfig::DeleteAccount { .. }) => {
+                        tracing::info!("🔓 Account deletion authenticated, proceeding");
+                        self.dispatch_message(Message::PerformDeleteAccount(password))
+                    }
                     // Add other cases as needed
                     _ => {
                         tracing::info!("🔓 Authentication complete for {:?}", config);
@@ -245,10 +284,9 @@ impl WorkingWalletApp {
                     }
                 }
             }
-            Err(_error) => {
-                // For the simplified approach, just show a simple error
+            Err(error) => {
                 let security = self.state.auth_mut();
-                security.password_dialog.error = Some(PasswordError::IncorrectPassword { attempts_remaining: 3 });
+                security.password_dialog.error = Some(error);
 
                 tracing::warn!("Password validation failed - using simple validation");
                 self.add_log_entry(
@@ -274,6 +312,10 @@ impl WorkingWalletApp {
         // Clear cached password (Requirement 7.2)
         security.session.cached_password = None;
 
+        // Nothing is signed on behalf of a locked session
+        self.state.transaction_mut().approval_requests.clear();
+        self.approvals.reject_all("wallet locked");
+
         tracing::info!("Session locked - cached keys cleared");
         self.add_log_entry(
             LogCategory::Wallet,
@@ -677,12 +719,8 @@ impl WorkingWalletApp {
                     if let Some(account) = wallet_read.current_account().await {
                         if account.key_reference.service == "vaughan-wallet-encrypted-seeds" {
                             // Try to create HD wallet to validate password
-                            let keychain = Box::new(
-                                crate::security::keychain::OSKeychain::new(
-                                    "vaughan-wallet-encrypted-seeds".to_string(),
-                                )
-                                .map_err(|e| format!("Failed to access keychain: {e}"))?,
-                            );
+                            let keychain = crate::security::create_keychain("vaughan-wallet-encrypted-seeds")
+                                .map_err(|e| format!("Failed to access keychain: {e}"))?;
 
                             let default_path = "m/44'/60'/0'/0/0".to_string();
                             let derivation_path = account.derivation_path.as_ref().unwrap_or(&default_path);
@@ -738,15 +776,24 @@ impl WorkingWalletApp {
 
                 // Store the validated password temporarily for transaction processing
                 // This will be used by the transaction handler to derive the HD wallet
-                security.session.temporary_key = Some(validated_password);
+                security.session.temporary_key = Some(validated_password.clone());
+                let signing_session = security.signing_session.clone();
 
                 // Show success message
                 self.state.ui_mut().status_message = "Authentication successful".to_string();
                 self.state.ui_mut().status_message_color = crate::gui::StatusMessageColor::Success;
                 self.state.ui_mut().status_message_timer = Some(std::time::Instant::now());
 
-                // Now proceed with the transaction that was waiting for authentication
-                Command::perform(async {}, |_| Message::ConfirmTransaction)
+                // Unlock the wallet's signing session, then proceed with the
+                // transaction that was waiting for authentication
+                Command::perform(
+                    async move {
+                        if let Some(session) = signing_session {
+                            session.unlock(validated_password).await;
+                        }
+                    },
+                    |_| Message::ConfirmTransaction,
+                )
             }
             Err(error) => {
                 tracing::warn!("❌ Master password validation failed: {}", error);
@@ -7,15 +7,18 @@
 //! - Business logic delegated to TransactionController
 //! - Signing/sending still uses simple_transaction (Phase E2 will extract)
 
-use crate::gui::simple_transaction::{estimate_gas, send_transaction};
+use crate::gui::services::save_transaction_drafts;
+use crate::gui::simple_transaction::{call_preview, estimate_gas, recipient_notes, send_transaction};
 use crate::gui::working_wallet::WorkingWalletApp;
 use crate::gui::{LogCategory, Message, StatusMessageColor};
+use crate::wallet::ApprovalResponse;
+use crate::wallet::transaction::drafts::DraftBook;
 use iced::Command;
 use std::time::Instant;
 
 // Phase E1: Alloy type imports for controller bridge
+use crate::gui::input_validation;
 use alloy::primitives::{Address, U256};
-use std::str::FromStr;
 
 // ============================================================================
 // Phase E1: Helper Functions - UI String → Alloy Type Conversion
@@ -29,28 +32,19 @@ use std::str::FromStr;
 /// - With 0x prefix: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0"
 /// - Without prefix: "742d35Cc6634C0532925a3b844Bc9e7595f0bEb0"
 fn parse_address_from_ui(address_str: &str) -> Result<Address, String> {
-    Address::from_str(address_str.trim())
-        .map_err(|e| format!("Invalid address format: {}", e))
+    input_validation::recipient_address(address_str).map_err(|e| e.to_string())
 }
 
 /// Parse amount from UI string to wei (U256)
 ///
 /// Converts human-readable amounts (e.g., "1.5") to wei
-/// Handles decimals properly to prevent precision loss
+/// Uses exact decimal parsing to prevent precision loss
 fn parse_amount_from_ui(amount_str: &str, decimals: u8) -> Result<U256, String> {
-    let amount_f64: f64 = amount_str
-        .trim()
-        .parse()
-        .map_err(|e| format!("Invalid amount format: {}", e))?;
-    
-    if amount_f64 <= 0.0 {
+    let amount = input_validation::amount(amount_str, decimals).map_err(|e| e.to_string())?;
+    if amount.is_zero() {
         return Err("Amount must be greater than zero".to_string());
     }
-    
-    // Use safe conversion for different decimal places
-    let multiplier = 10f64.powi(decimals as i32);
-    let wei = (amount_f64 * multiplier) as u128;
-    Ok(U256::from(wei))
+    Ok(amount)
 }
 
 /// Parse gas limit from UI string
@@ -99,8 +93,29 @@ impl WorkingWalletApp {
             Message::ShowTransactionConfirmation => self.handle_show_transaction_confirmation(),
             Message::HideTransactionConfirmation => self.handle_hide_transaction_confirmation(),
             Message::ConfirmTransaction => self.handle_confirm_transaction(),
+            Message::AcknowledgeScreeningRisk(accepted) => self.handle_acknowledge_screening_risk(accepted),
+            Message::ApprovalRequested(request) => {
+                tracing::info!("✍️ Wallet asks for approval: {}", request);
+                self.state.transaction_mut().approval_requests.push_back(request);
+                Command::none()
+            }
+            Message::AnswerApproval(id, approved) => self.handle_answer_approval(id, approved),
             Message::SubmitTransaction => self.handle_submit_transaction(),
             Message::TransactionSubmitted(result) => self.handle_transaction_submitted(result),
+            Message::SendNotesChanged(notes) => {
+                self.state.transaction_mut().send_notes = notes;
+                Command::none()
+            }
+            Message::SaveDraft => self.handle_save_draft(),
+            Message::ToggleDrafts => {
+                let transaction = self.state.transaction_mut();
+                transaction.show_drafts = !transaction.show_drafts;
+                Command::none()
+            }
+            Message::ResumeDraft(id) => self.handle_resume_draft(id),
+            Message::DeleteDraft(id) => self.handle_delete_draft(id),
+            Message::DraftsLoaded(result) => self.handle_drafts_loaded(result),
+            Message::DraftsSaved(result) => self.handle_drafts_saved(result),
             // Legacy/Unused messages that might still be emitted by UI
             _ => Command::none(),
         }
@@ -257,6 +272,7 @@ impl WorkingWalletApp {
 
         self.state.transaction_mut().estimating_gas = true;
         self.state.transaction_mut().gas_estimation = None;
+        self.state.transaction_mut().risk_acknowledgement = None;
 
         let to_address = self.state.transaction().send_to_address.clone();
         let amount = self.state.transaction().send_amount.clone();
@@ -280,6 +296,13 @@ impl WorkingWalletApp {
 
         // Get RPC URL
         let rpc_url = self.state.network().get_current_rpc_url();
+        let network_state = self.state.network();
+        let fee_formatter = network_state
+            .available_networks
+            .iter()
+            .find(|n| n.id == network_state.current_network)
+            .map(crate::network::fees::FeeFormatter::for_network)
+            .unwrap_or_else(|| crate::network::fees::FeeFormatter::new(network_state.current_network.0, "ETH"));
 
         // Extract token contract address if this is an ERC-20 token
         let selected_token = &self.state.transaction().send_selected_token;
@@ -353,18 +376,28 @@ impl WorkingWalletApp {
 
         Command::perform(
             async move {
-                estimate_gas(&to_address, &amount, &from_address, &rpc_url, token_contract)
-                    .await
-                    .map(|gas| {
-                        let cost = format!("{:.6}", (gas as f64 * 20.0 * 1e9) / 1e18);
-                        crate::gui::GasEstimation {
-                            estimated_gas: gas,
-                            gas_price: "20.0".to_string(), // Default for now
-                            estimated_cost: cost.clone(),
-                            total_cost: cost,
-                            currency: "ETH".to_string(),
-                        }
-                    })
+                let screening = match to_address.trim().parse::<alloy::primitives::Address>() {
+                    Ok(recipient) => crate::security::Denylist::load_default().screen(recipient, token_contract),
+                    Err(_) => Default::default(),
+                };
+                let call_preview = call_preview(&to_address, &amount, &rpc_url, token_contract).await;
+                let recipient_notes = recipient_notes(&to_address, &rpc_url, token_contract).await;
+                let gas = estimate_gas(&to_address, &amount, &from_address, &rpc_url, token_contract).await?;
+                let fees = fee_formatter
+                    .with_oracle(&crate::tokens::oracle::MultiSourcePriceOracle::with_default_providers())
+                    .await;
+                // Default max fee for now
+                let max_fee = fees.max_cost(gas, U256::from(20_000_000_000u64));
+                Ok(crate::gui::GasEstimation {
+                    estimated_gas: gas,
+                    gas_price: "20.0".to_string(),
+                    estimated_cost: max_fee.native(),
+                    total_cost: max_fee.to_string(),
+                    currency: fees.symbol().to_string(),
+                    screening,
+                    call_preview,
+                    recipient_notes,
+                })
             },
             Message::GasEstimated,
         )
@@ -376,6 +409,9 @@ impl WorkingWalletApp {
         match result {
             Ok(gas_estimation) => {
                 tracing::info!("✅ Gas estimation successful: {} gas", gas_estimation.estimated_gas);
+                for finding in &gas_estimation.screening.findings {
+                    tracing::warn!("🛡️ Screening: {}", finding);
+                }
                 self.state.transaction_mut().gas_estimation = Some(gas_estimation);
                 self.state.transaction_mut().show_transaction_confirmation = true;
                 Command::none()
@@ -443,10 +479,35 @@ impl WorkingWalletApp {
     fn handle_hide_transaction_confirmation(&mut self) -> Command<Message> {
         self.state.transaction_mut().show_transaction_confirmation = false;
         self.state.transaction_mut().gas_estimation = None;
+        self.state.transaction_mut().risk_acknowledgement = None;
+        Command::none()
+    }
+
+    /// Handle the user accepting or withdrawing acceptance of screening findings
+    fn handle_acknowledge_screening_risk(&mut self, accepted: bool) -> Command<Message> {
+        let acknowledgement = match &self.state.transaction().gas_estimation {
+            Some(estimation) if accepted => Some(estimation.screening.acknowledge()),
+            _ => None,
+        };
+        self.state.transaction_mut().risk_acknowledgement = acknowledgement;
+        Command::none()
+    }
+
+    /// Answer the approval request `id` shown in the approval dialog
+    fn handle_answer_approval(&mut self, id: u64, approved: bool) -> Command<Message> {
+        self.state.transaction_mut().approval_requests.retain(|request| request.id != id);
+        let response = if approved {
+            ApprovalResponse::Approve
+        } else {
+            ApprovalResponse::Reject { reason: None }
+        };
+        if !self.approvals.respond(id, response) {
+            tracing::warn!("Approval request {} was no longer waiting for an answer", id);
+        }
         Command::none()
     }
 
-    /// Handle transaction confirmation - DIRECT SENDING (No 2-tier security)
+    /// Handle transaction confirmation, signing through the wallet's approval pipeline
     fn handle_confirm_transaction(&mut self) -> Command<Message> {
         if self.state.transaction_mut().sending_transaction {
             return Command::none();
@@ -465,39 +526,35 @@ impl WorkingWalletApp {
         }
         tracing::info!("✅ Controller validation passed - proceeding with transaction");
 
-        // Check if we need master password authentication for seed-based accounts
+        // Denylisted recipients or contracts need an explicit acknowledgement
+        if let Some(estimation) = &self.state.transaction().gas_estimation {
+            let acknowledgement = self.state.transaction().risk_acknowledgement.as_ref();
+            if let Err(e) = estimation.screening.ensure_cleared(acknowledgement) {
+                tracing::warn!("🛡️ Transaction held by address screening: {}", e);
+                self.state.ui_mut().status_message = e.to_string();
+                self.state.ui_mut().status_message_color = StatusMessageColor::Error;
+                self.state.ui_mut().status_message_timer = Some(Instant::now());
+                return Command::none();
+            }
+        }
+
+        // Signing needs the master password, asked again for every transaction
         if let Some(_wallet_arc) = &self.wallet {
-            // Check current account type - this needs to be synchronous
             let current_account_id = self.state.wallet().current_account_id.clone();
-            let needs_master_password = if let Some(account_id) = current_account_id {
-                if let Some(account) = self
+            let needs_master_password = match current_account_id {
+                Some(account_id) if self.state.auth().session.temporary_key.is_none() => self
                     .state
                     .wallet()
                     .available_accounts
                     .iter()
                     .find(|a| a.id == account_id)
-                {
-                    if account.key_reference.service == "vaughan-wallet-encrypted-seeds" {
-                        // This is a seed-based account - check if we have a temporary password
-                        let needs_password = self.state.auth().session.temporary_key.is_none();
-                        if needs_password {
-                            Some((account.id.clone(), account.name.clone()))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            } else {
-                None
+                    .map(|account| (account.id.clone(), account.name.clone())),
+                _ => None,
             };
 
             if let Some((_account_id, account_name)) = needs_master_password {
                 // Need to prompt for master password
-                tracing::info!("🔐 Seed-based account needs master password - showing dialog");
+                tracing::info!("🔐 Signing needs the master password - showing dialog");
 
                 // Clear any existing transaction state
                 self.state.transaction_mut().show_transaction_confirmation = false;
@@ -530,7 +587,6 @@ impl WorkingWalletApp {
 
         let to_address = self.state.transaction().send_to_address.clone();
         let amount = self.state.transaction().send_amount.clone();
-        let rpc_url = self.state.network().get_current_rpc_url();
         let chain_id = self.state.network().current_network.0;
 
         // Extract token contract address if this is an ERC-20 token
@@ -597,7 +653,22 @@ impl WorkingWalletApp {
             }
         };
 
-        // Get wallet and account to retrieve seed phrase
+        // The account the GUI shows, whether or not the wallet has selected it yet
+        let current_account_id = self.state.wallet().current_account_id.clone();
+        let from = self
+            .state
+            .wallet()
+            .available_accounts
+            .iter()
+            .find(|account| Some(&account.id) == current_account_id.as_ref())
+            .map(|account| account.address);
+        let Some(from) = from else {
+            self.state.transaction_mut().sending_transaction = false;
+            self.state.ui_mut().status_message = "No account selected".to_string();
+            self.state.ui_mut().status_message_color = StatusMessageColor::Error;
+            return Command::none();
+        };
+
         let wallet_arc = if let Some(w) = &self.wallet {
             w.clone()
         } else {
@@ -607,7 +678,7 @@ impl WorkingWalletApp {
             return Command::none();
         };
 
-        // Get the temporary key for seed-based accounts
+        // Master password from the SignTransaction dialog
         let temporary_key = self.state.auth().session.temporary_key.clone();
 
         // Extract token decimals lookup data before entering async block
@@ -616,56 +687,22 @@ impl WorkingWalletApp {
         // Extract gas estimation before async block
         let gas_estimation = self.state.transaction().gas_estimation.clone();
 
-        tracing::info!("🔐 Retrieving seed phrase for transaction signing");
-
         Command::perform(
             async move {
-                use secrecy::ExposeSecret;
-
-                // Get private key - check if seed-based account first to avoid unnecessary keychain access
-                let wallet_read = wallet_arc.read().await;
-                let account = wallet_read
-                    .current_account()
-                    .await
-                    .ok_or_else(|| "No account selected".to_string())?;
-
-                let private_key_hex = if account.key_reference.service == "vaughan-wallet-encrypted-seeds" {
-                    // This is a seed-based account - use the temporary password directly
-                    if let Some(master_password) = temporary_key {
-                        tracing::info!("🌱 Using stored master password for HD wallet derivation");
-
-                        // Use the validated master password to derive HD wallet
-                        let keychain = Box::new(
-                            crate::security::keychain::OSKeychain::new("vaughan-wallet-encrypted-seeds".to_string())
-                                .map_err(|e| format!("Failed to access keychain: {e}"))?,
-                        );
-
-                        let default_path = "m/44'/60'/0'/0/0".to_string();
-                        let derivation_path = account.derivation_path.as_ref().unwrap_or(&default_path);
-
-                        crate::gui::hd_wallet_service::HDWalletService::extract_private_key_from_encrypted_seed(
-                            keychain.as_ref(),
-                            &account.key_reference,
-                            derivation_path,
-                            &master_password,
-                        )
+                // Unlock signing through the wallet so the attempt is throttled and audited
+                if let Some(master_password) = temporary_key {
+                    let unlocked = wallet_arc
+                        .read()
                         .await
-                        .map_err(|e| format!("HD wallet private key extraction failed: {e}"))?
-                    } else {
-                        // No master password available (should not happen due to pre-check)
-                        return Err("Master password required for seed-based account".to_string());
-                    }
-                } else {
-                    // For private key accounts, use the existing wallet method
-                    match wallet_read.get_private_key_for_deployment().await {
-                        Ok(private_key) => private_key.expose_secret().to_string(),
-                        Err(e) => {
-                            return Err(format!("Failed to get private key for private key account: {e}"));
-                        }
+                        .unlock_signing(master_password)
+                        .await
+                        .map_err(|e| format!("Failed to unlock signing: {e}"))?;
+                    if !unlocked {
+                        return Err("Incorrect master password".to_string());
                     }
-                };
+                }
 
-                tracing::info!("🚀 Sending transaction using Alloy");
+                tracing::info!("🚀 Sending transaction through the wallet");
 
                 // Get token decimals if this is an ERC-20 transfer
                 let token_decimals = if let Some(contract_addr) = token_contract {
@@ -713,12 +750,12 @@ impl WorkingWalletApp {
                     gas_estimation.as_ref().map(|g| g.estimated_gas).unwrap_or(0)
                 );
 
-                // Send transaction using Alloy
+                // Signed by the wallet after the approval dialog, then broadcast
                 send_transaction(
+                    &wallet_arc,
+                    from,
                     &to_address,
                     &amount,
-                    &private_key_hex,
-                    &rpc_url,
                     chain_id,
                     gas_limit,      // Use estimated gas limit
                     Some(20.0),     // Default gas price
@@ -760,16 +797,22 @@ impl WorkingWalletApp {
 
                 self.add_transaction_to_history(tx_hash.clone());
 
-                // Clear form
+                // Clear form; a draft that was sent is done with
                 self.state.transaction_mut().send_to_address.clear();
                 self.state.transaction_mut().send_amount.clear();
+                self.state.transaction_mut().send_notes.clear();
                 self.state.transaction_mut().gas_estimation = None;
+                let sent_draft = self.state.transaction_mut().current_draft_id.take();
+                let persist = match sent_draft {
+                    Some(id) if self.state.transaction_mut().drafts.remove(&id).is_some() => self.persist_drafts(),
+                    _ => Command::none(),
+                };
 
                 self.state.ui_mut().status_message = format!("Transaction submitted: {tx_hash}");
                 self.state.ui_mut().status_message_color = StatusMessageColor::Success;
                 self.state.ui_mut().status_message_timer = Some(Instant::now());
 
-                self.update_account_balance()
+                Command::batch([self.update_account_balance(), persist])
             }
             Err(error_string) => {
                 tracing::error!("❌ Transaction failed: {}", error_string);
@@ -780,4 +823,112 @@ impl WorkingWalletApp {
             }
         }
     }
+
+    fn set_draft_status(&mut self, message: String, color: StatusMessageColor) {
+        self.state.ui_mut().status_message = message;
+        self.state.ui_mut().status_message_color = color;
+        self.state.ui_mut().status_message_timer = Some(Instant::now());
+    }
+
+    /// Write drafts to disk when the session holds the master password
+    ///
+    /// Without it drafts stay in memory until the wallet locks.
+    fn persist_drafts(&mut self) -> Command<Message> {
+        let password = self
+            .state
+            .auth()
+            .enhanced_session
+            .wallet_session
+            .cached_master_password
+            .clone();
+        match password {
+            Some(password) => Command::perform(
+                save_transaction_drafts(
+                    self.state.wallet().security_profile,
+                    self.state.transaction().drafts.clone(),
+                    password,
+                ),
+                Message::DraftsSaved,
+            ),
+            None => {
+                self.set_draft_status(
+                    "Draft kept for this session; unlock with \"remember session\" to keep drafts after restart"
+                        .to_string(),
+                    StatusMessageColor::Warning,
+                );
+                Command::none()
+            }
+        }
+    }
+
+    fn handle_save_draft(&mut self) -> Command<Message> {
+        let network = self.state.network().current_network;
+        let draft = self.state.transaction().draft_from_form(network);
+        let id = draft.id.clone();
+        match self.state.transaction_mut().drafts.save(draft) {
+            Ok(()) => {
+                self.state.transaction_mut().current_draft_id = Some(id);
+                self.set_draft_status("Draft saved".to_string(), StatusMessageColor::Success);
+                self.persist_drafts()
+            }
+            Err(e) => {
+                self.set_draft_status(e.to_string(), StatusMessageColor::Error);
+                Command::none()
+            }
+        }
+    }
+
+    fn handle_resume_draft(&mut self, id: String) -> Command<Message> {
+        let Some(draft) = self.state.transaction().drafts.get(&id).cloned() else {
+            return Command::none();
+        };
+        self.state.transaction_mut().resume_draft(&draft);
+        let network = self.state.network().current_network;
+        if draft.chain_id != network.0 {
+            self.set_draft_status(
+                format!(
+                    "This draft was written for chain {}; switch networks before sending",
+                    draft.chain_id
+                ),
+                StatusMessageColor::Warning,
+            );
+        }
+        Command::none()
+    }
+
+    fn handle_delete_draft(&mut self, id: String) -> Command<Message> {
+        if self.state.transaction_mut().drafts.remove(&id).is_none() {
+            return Command::none();
+        }
+        if self.state.transaction().current_draft_id.as_deref() == Some(id.as_str()) {
+            self.state.transaction_mut().current_draft_id = None;
+        }
+        self.persist_drafts()
+    }
+
+    fn handle_drafts_loaded(&mut self, result: Result<DraftBook, String>) -> Command<Message> {
+        match result {
+            Ok(drafts) => {
+                tracing::info!("📝 Loaded {} transaction drafts", drafts.len());
+                self.state.transaction_mut().drafts = drafts;
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ {}", e);
+                self.add_log_entry(
+                    LogCategory::Error,
+                    "Failed to load transaction drafts".to_string(),
+                    Some(e),
+                );
+            }
+        }
+        Command::none()
+    }
+
+    fn handle_drafts_saved(&mut self, result: Result<(), String>) -> Command<Message> {
+        if let Err(e) = result {
+            tracing::error!("❌ {}", e);
+            self.set_draft_status(e, StatusMessageColor::Error);
+        }
+        Command::none()
+    }
 }
@@ -5,6 +5,7 @@
 
 use crate::gui::working_wallet::WorkingWalletApp;
 use crate::gui::Message;
+use crate::network::egress::{egress, CategoryMode, EgressCategory};
 use iced::Command;
 use std::time::Instant;
 
@@ -41,6 +42,7 @@ impl WorkingWalletApp {
             // Status updates
             Message::ClearStatusMessage => self.handle_clear_status_message(),
             Message::UpdateLastActivity => self.handle_update_last_activity(),
+            Message::TogglePrivacyMode => self.handle_toggle_privacy_mode(),
             Message::SetStatusMessage(message, color) => self.handle_set_status_message(message, color),
             Message::StatusMessageTick => self.handle_status_message_tick(),
             Message::SpinnerTick => self.handle_spinner_tick(),
@@ -62,6 +64,15 @@ impl WorkingWalletApp {
             Message::ShowCancelConfirmation => self.handle_show_cancel_confirmation(),
             Message::HideCancelConfirmation => self.handle_hide_cancel_confirmation(),
 
+            // First-run network consent
+            Message::EgressModeSelected(category, mode) => self.handle_egress_mode_selected(category, mode),
+            Message::ConfirmEgressConsent => self.handle_confirm_egress_consent(),
+            Message::EgressConsentTick => self.handle_egress_consent_tick(),
+            Message::EgressHostDecided(host, allowed) => self.handle_egress_host_decided(host, allowed),
+            Message::ShowEgressLog => self.handle_show_egress_log(),
+            Message::HideEgressLog => self.handle_hide_egress_log(),
+            Message::ClearEgressLog => self.handle_clear_egress_log(),
+
             _ => Command::none(),
         }
     }
@@ -180,6 +191,16 @@ impl WorkingWalletApp {
         Command::none()
     }
 
+    fn handle_toggle_privacy_mode(&mut self) -> Command<Message> {
+        let hidden = crate::gui::privacy::toggle();
+        let message = if hidden {
+            "🙈 Privacy mode on - balances hidden"
+        } else {
+            "👁 Privacy mode off"
+        };
+        self.handle_set_status_message(message.to_string(), crate::gui::StatusMessageColor::Info)
+    }
+
     // Import/Export wallet dialog handlers
     fn handle_show_import_wallet(&mut self) -> Command<Message> {
         self.state.wallet_mut().show_import_wallet = true;
@@ -298,6 +319,7 @@ impl WorkingWalletApp {
         self.state.selected_export_account_id = None;
         self.state.exporting_data = false;
         self.state.export_loading = false;
+        self.reset_backup_check();
 
         // Clear export copy feedback and disable clipboard timer
         self.state.ui_mut().export_copy_feedback = None;
@@ -449,4 +471,81 @@ impl WorkingWalletApp {
         self.state.ui_mut().copy_feedback = None;
         Command::none()
     }
+
+    // First-run network consent handlers
+    fn handle_egress_mode_selected(&mut self, category: EgressCategory, mode: CategoryMode) -> Command<Message> {
+        if let Some(choices) = self.state.ui_mut().egress_consent.as_mut() {
+            choices.insert(category, mode);
+        }
+        Command::none()
+    }
+
+    fn handle_confirm_egress_consent(&mut self) -> Command<Message> {
+        let Some(choices) = self.state.ui_mut().egress_consent.take() else {
+            return Command::none();
+        };
+        if let Err(e) = egress().complete_first_run(choices.clone()) {
+            tracing::error!("Failed to save network consent: {}", e);
+            // Keep the screen open so the choices aren't lost
+            self.state.ui_mut().egress_consent = Some(choices);
+            return self.handle_set_status_message(
+                format!("Failed to save network settings: {e}"),
+                crate::gui::StatusMessageColor::Error,
+            );
+        }
+        tracing::info!("🌐 First-run network consent saved");
+        Command::none()
+    }
+
+    // Per-host network approval handlers
+    fn handle_egress_consent_tick(&mut self) -> Command<Message> {
+        let ui = self.state.ui_mut();
+        if ui.egress_consent.is_none() && ui.pending_egress_host.is_none() {
+            ui.pending_egress_host = egress().awaiting_consent().into_iter().next();
+        }
+        Command::none()
+    }
+
+    fn handle_egress_host_decided(&mut self, host: String, allowed: bool) -> Command<Message> {
+        self.state.ui_mut().pending_egress_host = None;
+        let Some(wallet) = self.wallet.clone() else {
+            // No wallet to record the change for undo; save it directly
+            if let Err(e) = egress().set_host(&host, allowed) {
+                return self.handle_set_status_message(
+                    format!("Failed to save network settings: {e}"),
+                    crate::gui::StatusMessageColor::Error,
+                );
+            }
+            return self.handle_egress_consent_tick();
+        };
+
+        Command::perform(
+            async move { wallet.write().await.set_egress_host(&host, allowed).map(|()| host) },
+            move |result| match result {
+                Ok(host) => {
+                    tracing::info!("🌐 {} {}", if allowed { "Allowed" } else { "Blocked" }, host);
+                    Message::EgressConsentTick
+                }
+                Err(e) => Message::SetStatusMessage(
+                    format!("Failed to save network settings: {e}"),
+                    crate::gui::StatusMessageColor::Error,
+                ),
+            },
+        )
+    }
+
+    fn handle_show_egress_log(&mut self) -> Command<Message> {
+        self.state.ui_mut().show_egress_log = true;
+        Command::none()
+    }
+
+    fn handle_hide_egress_log(&mut self) -> Command<Message> {
+        self.state.ui_mut().show_egress_log = false;
+        Command::none()
+    }
+
+    fn handle_clear_egress_log(&mut self) -> Command<Message> {
+        egress().clear_log();
+        Command::none()
+    }
 }
@@ -92,6 +92,7 @@ impl WorkingWalletApp {
                     account_name,
                     seed_phrase,
                     master_password_str,
+                    None,
                 )
                 .await
             },
@@ -3,6 +3,7 @@
 //! Provides secure hierarchical deterministic wallet functionality using Alloy.
 //! Follows industry standards for mnemonic-based account derivation.
 
+use crate::gui::input_validation;
 use crate::security::seed::SecureSeedStorage;
 use crate::security::{KeyReference, KeychainInterface};
 use alloy::network::EthereumWallet;
@@ -39,9 +40,7 @@ impl HDWalletService {
             .context("Failed to retrieve encrypted seed from keychain")?;
 
         // Step 2: Decrypt the seed data using existing SecureSeedStorage
-        let seed_storage = SecureSeedStorage::new(Box::new(crate::security::keychain::OSKeychain::new(
-            "vaughan-wallet-encrypted-seeds".to_string(),
-        )?));
+        let seed_storage = SecureSeedStorage::new(crate::security::create_keychain("vaughan-wallet-encrypted-seeds")?);
         let decrypted_mnemonic = seed_storage
             .retrieve_encrypted_seed_phrase(key_reference, master_password)
             .await
@@ -75,7 +74,8 @@ impl HDWalletService {
 
         // Parse derivation path (e.g., "m/44'/60'/0'/0/0")
         let derivation_path =
-            bip32::DerivationPath::from_str(derivation_path).context("Invalid BIP44 derivation path")?;
+            bip32::DerivationPath::from_str(&input_validation::derivation_path(derivation_path)?)
+                .context("Invalid BIP44 derivation path")?;
 
         // Derive along the path step by step (following existing pattern)
         for child in derivation_path.into_iter() {
@@ -122,9 +122,7 @@ impl HDWalletService {
             .context("Failed to retrieve encrypted seed from keychain")?;
 
         // Step 2: Decrypt the seed data using existing SecureSeedStorage
-        let seed_storage = SecureSeedStorage::new(Box::new(crate::security::keychain::OSKeychain::new(
-            "vaughan-wallet-encrypted-seeds".to_string(),
-        )?));
+        let seed_storage = SecureSeedStorage::new(crate::security::create_keychain("vaughan-wallet-encrypted-seeds")?);
         let decrypted_mnemonic = seed_storage
             .retrieve_encrypted_seed_phrase(key_reference, master_password)
             .await
@@ -158,7 +156,8 @@ impl HDWalletService {
 
         // Parse derivation path (e.g., "m/44'/60'/0'/0/0")
         let derivation_path =
-            bip32::DerivationPath::from_str(derivation_path).context("Invalid BIP44 derivation path")?;
+            bip32::DerivationPath::from_str(&input_validation::derivation_path(derivation_path)?)
+                .context("Invalid BIP44 derivation path")?;
 
         // Derive along the path step by step (following existing pattern)
         for child in derivation_path.into_iter() {
@@ -182,7 +181,8 @@ impl HDWalletService {
         let mut xprv = bip32::ExtendedPrivateKey::<bip32::secp256k1::SecretKey>::new(seed)
             .map_err(|e| anyhow::anyhow!("Failed to create master key: {e}"))?;
 
-        let derivation_path = bip32::DerivationPath::from_str(derivation_path).context("Invalid derivation path")?;
+        let derivation_path = bip32::DerivationPath::from_str(&input_validation::derivation_path(derivation_path)?)
+            .context("Invalid derivation path")?;
 
         // Derive along the path
         for child in derivation_path.into_iter() {
@@ -0,0 +1,344 @@
+//! Input validation for GUI text fields
+//!
+//! Every value typed by the user goes through one of these functions before it
+//! is handed to the wallet core. Each returns the parsed, normalized value
+//! (e.g. an [`Address`] instead of a string, a derivation path with canonical
+//! hardened markers) or a typed [`InputError`] the view can display.
+//!
+//! Amounts are parsed as exact decimals; floating point is never involved.
+
+use crate::error::VaughanError;
+use alloy::primitives::{Address, Bytes, U256};
+use thiserror::Error;
+use url::Url;
+
+/// Largest chain ID allowed by EIP-2294
+pub const MAX_CHAIN_ID: u64 = u64::MAX / 2 - 36;
+
+/// Maximum BIP-32 derivation depth
+const MAX_DERIVATION_DEPTH: usize = 255;
+
+/// Error for a rejected input field
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    #[error("Field cannot be empty")]
+    Empty,
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Address checksum does not match; check for typos")]
+    ChecksumMismatch,
+
+    #[error("Cannot use the zero address (0x0000...)")]
+    ZeroAddress,
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("Too many decimal places (maximum: {max})")]
+    TooManyDecimals { max: u8 },
+
+    #[error("Amount exceeds maximum value")]
+    AmountTooLarge,
+
+    #[error("Invalid hex data: {0}")]
+    InvalidHex(String),
+
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("Invalid chain ID: {0}")]
+    InvalidChainId(String),
+
+    #[error("Text contains control characters")]
+    ControlCharacters,
+
+    #[error("Text is too long (maximum: {max} characters)")]
+    TooLong { max: usize },
+}
+
+impl From<InputError> for VaughanError {
+    fn from(error: InputError) -> Self {
+        VaughanError::ValidationError(error.to_string())
+    }
+}
+
+fn non_empty(input: &str) -> Result<&str, InputError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        Err(InputError::Empty)
+    } else {
+        Ok(trimmed)
+    }
+}
+
+/// Parse an address, with or without `0x` prefix
+///
+/// All-lowercase and all-uppercase addresses are accepted as is; mixed-case
+/// addresses must carry a valid EIP-55 checksum.
+pub fn address(input: &str) -> Result<Address, InputError> {
+    let trimmed = non_empty(input)?;
+    let hex = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+
+    if hex.len() != 40 {
+        return Err(InputError::InvalidAddress(format!(
+            "expected 40 hex characters, got {}",
+            hex.len()
+        )));
+    }
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(InputError::InvalidAddress("contains non-hex characters".to_string()));
+    }
+
+    let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Address::parse_checksummed(format!("0x{hex}"), None).map_err(|_| InputError::ChecksumMismatch);
+    }
+
+    hex.parse::<Address>()
+        .map_err(|e| InputError::InvalidAddress(e.to_string()))
+}
+
+/// Parse a recipient address, rejecting the zero address
+pub fn recipient_address(input: &str) -> Result<Address, InputError> {
+    match address(input)? {
+        Address::ZERO => Err(InputError::ZeroAddress),
+        address => Ok(address),
+    }
+}
+
+/// Parse a decimal amount (e.g. `"1.5"`) into base units with `decimals` places
+///
+/// Zero is accepted; callers decide whether it is meaningful.
+pub fn amount(input: &str, decimals: u8) -> Result<U256, InputError> {
+    let trimmed = non_empty(input)?;
+
+    let (whole, fraction) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(InputError::InvalidAmount("no digits".to_string()));
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(InputError::InvalidAmount(format!(
+            "'{trimmed}' is not a positive decimal number"
+        )));
+    }
+
+    // Trailing zeros don't count against the decimal limit
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(InputError::TooManyDecimals { max: decimals });
+    }
+
+    let digits = format!("{whole}{fraction:0<width$}", width = decimals as usize);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    U256::from_str_radix(digits, 10).map_err(|_| InputError::AmountTooLarge)
+}
+
+/// Parse hex data such as calldata; `0x` prefix optional, empty input is empty data
+pub fn hex_data(input: &str) -> Result<Bytes, InputError> {
+    let trimmed = input.trim();
+    let hex = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+
+    if !hex.len().is_multiple_of(2) {
+        return Err(InputError::InvalidHex("odd number of hex digits".to_string()));
+    }
+    alloy::hex::decode(hex)
+        .map(Bytes::from)
+        .map_err(|e| InputError::InvalidHex(e.to_string()))
+}
+
+/// Parse a BIP-32 derivation path, normalizing hardened markers to `'`
+///
+/// `"m/44h/60H/0'/0/0"` becomes `"m/44'/60'/0'/0/0"`.
+pub fn derivation_path(input: &str) -> Result<String, InputError> {
+    let trimmed = non_empty(input)?;
+    let mut segments = trimmed.split('/');
+
+    match segments.next() {
+        Some("m") | Some("M") => {}
+        _ => return Err(InputError::InvalidDerivationPath("must start with 'm/'".to_string())),
+    }
+
+    let mut normalized = String::from("m");
+    let mut depth = 0;
+    for segment in segments {
+        let (index, hardened) = match segment.strip_suffix(['\'', 'h', 'H']) {
+            Some(index) => (index, true),
+            None => (segment, false),
+        };
+        if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+            return Err(InputError::InvalidDerivationPath(format!(
+                "invalid segment '{segment}'"
+            )));
+        }
+        let index: u32 = index
+            .parse()
+            .ok()
+            .filter(|i| *i < (1 << 31))
+            .ok_or_else(|| InputError::InvalidDerivationPath(format!("index '{segment}' out of range")))?;
+
+        depth += 1;
+        if depth > MAX_DERIVATION_DEPTH {
+            return Err(InputError::InvalidDerivationPath("path is too deep".to_string()));
+        }
+        normalized.push('/');
+        normalized.push_str(&index.to_string());
+        if hardened {
+            normalized.push('\'');
+        }
+    }
+
+    if depth == 0 {
+        return Err(InputError::InvalidDerivationPath("path has no segments".to_string()));
+    }
+    Ok(normalized)
+}
+
+/// Parse an RPC or explorer URL (http, https, ws, wss)
+pub fn url(input: &str) -> Result<Url, InputError> {
+    let trimmed = non_empty(input)?;
+    if trimmed.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(InputError::InvalidUrl("contains whitespace".to_string()));
+    }
+
+    crate::security::validation::validate_rpc_endpoint(trimmed).map_err(|e| match e {
+        VaughanError::Security(crate::error::SecurityError::KeystoreError { message }) => {
+            InputError::InvalidUrl(message)
+        }
+        other => InputError::InvalidUrl(other.to_string()),
+    })
+}
+
+/// Parse a chain ID, in decimal or `0x`-prefixed hex
+pub fn chain_id(input: &str) -> Result<u64, InputError> {
+    let trimmed = non_empty(input)?;
+
+    let parsed = match trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => trimmed.parse::<u64>(),
+    }
+    .map_err(|_| InputError::InvalidChainId(format!("'{trimmed}' is not a number")))?;
+
+    if parsed == 0 || parsed > MAX_CHAIN_ID {
+        return Err(InputError::InvalidChainId(format!(
+            "must be between 1 and {MAX_CHAIN_ID}"
+        )));
+    }
+    Ok(parsed)
+}
+
+/// Validate free text such as account or network names
+///
+/// Surrounding whitespace is trimmed; control characters are rejected.
+pub fn label(input: &str, max_len: usize) -> Result<String, InputError> {
+    let trimmed = non_empty(input)?;
+    if trimmed.chars().any(char::is_control) {
+        return Err(InputError::ControlCharacters);
+    }
+    if trimmed.chars().count() > max_len {
+        return Err(InputError::TooLong { max: max_len });
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address() {
+        let expected: Address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap();
+
+        assert_eq!(
+            address(" 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed ").unwrap(),
+            expected
+        );
+        assert_eq!(address("5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED").unwrap(), expected);
+        assert_eq!(address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap(), expected);
+
+        assert_eq!(
+            address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+            Err(InputError::ChecksumMismatch)
+        );
+        assert!(matches!(address("0x1234"), Err(InputError::InvalidAddress(_))));
+        assert_eq!(address("  "), Err(InputError::Empty));
+        assert_eq!(
+            recipient_address(&Address::ZERO.to_string()),
+            Err(InputError::ZeroAddress)
+        );
+    }
+
+    #[test]
+    fn test_amount_is_exact() {
+        assert_eq!(amount("1.5", 18).unwrap(), U256::from(1_500_000_000_000_000_000u128));
+        assert_eq!(amount(".5", 6).unwrap(), U256::from(500_000u64));
+        assert_eq!(amount("0.1", 18).unwrap(), U256::from(100_000_000_000_000_000u128));
+        assert_eq!(amount("2.500", 2).unwrap(), U256::from(250u64));
+        assert_eq!(amount("0", 18).unwrap(), U256::ZERO);
+
+        assert_eq!(amount("0.001", 2), Err(InputError::TooManyDecimals { max: 2 }));
+        assert!(matches!(amount("-1", 18), Err(InputError::InvalidAmount(_))));
+        assert!(matches!(amount("1e18", 18), Err(InputError::InvalidAmount(_))));
+        assert!(matches!(amount("1,000", 18), Err(InputError::InvalidAmount(_))));
+        assert!(matches!(amount(".", 18), Err(InputError::InvalidAmount(_))));
+        assert_eq!(amount(&"9".repeat(80), 18), Err(InputError::AmountTooLarge));
+    }
+
+    #[test]
+    fn test_hex_data() {
+        assert_eq!(
+            hex_data("0xa9059cbb").unwrap(),
+            Bytes::from(vec![0xa9, 0x05, 0x9c, 0xbb])
+        );
+        assert!(hex_data("").unwrap().is_empty());
+        assert!(hex_data("0xabc").is_err());
+        assert!(hex_data("0xzz").is_err());
+    }
+
+    #[test]
+    fn test_derivation_path() {
+        assert_eq!(derivation_path("m/44h/60H/0'/0/7").unwrap(), "m/44'/60'/0'/0/7");
+        assert!(derivation_path("44'/60'/0'/0/0").is_err());
+        assert!(derivation_path("m/").is_err());
+        assert!(derivation_path("m").is_err());
+        assert!(derivation_path("m/44'/x").is_err());
+        assert!(derivation_path("m/2147483648").is_err());
+    }
+
+    #[test]
+    fn test_url_and_chain_id() {
+        assert_eq!(
+            url(" https://rpc.pulsechain.com ").unwrap().host_str(),
+            Some("rpc.pulsechain.com")
+        );
+        assert!(url("ftp://example.com").is_err());
+        assert!(url("https://exa mple.com").is_err());
+
+        assert_eq!(chain_id("369").unwrap(), 369);
+        assert_eq!(chain_id("0x171").unwrap(), 369);
+        assert!(chain_id("0").is_err());
+        assert!(chain_id("abc").is_err());
+        assert!(chain_id(&u64::MAX.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_label() {
+        assert_eq!(label("  Savings ", 32).unwrap(), "Savings");
+        assert_eq!(label("a\u{0}b", 32), Err(InputError::ControlCharacters));
+        assert_eq!(label("abcdef", 3), Err(InputError::TooLong { max: 3 }));
+    }
+}
@@ -11,10 +11,12 @@ pub mod state;
 pub mod command_helpers;
 pub mod coordinators;
 pub mod launcher;
+pub mod privacy;
+pub mod replay;
 pub mod safe_calculations;
 
 use crate::network::{NetworkConfig, NetworkId};
-use crate::security::keychain::OSKeychain;
+use crate::security::create_keychain;
 use crate::security::keystore::SecureKeystoreImpl;
 
 /// Account information for selection and management
@@ -114,6 +116,7 @@ pub mod views;
 
 // Utilities and helpers
 pub mod constants;
+pub mod input_validation;
 pub mod spinner;
 
 pub mod theme;
@@ -198,6 +201,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://etherscan.io".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(369),
@@ -208,6 +212,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://scan.pulsechain.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(943),
@@ -218,6 +223,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://scan.v4.testnet.pulsechain.com".to_string(),
             is_testnet: true,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(56),
@@ -228,6 +234,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://bscscan.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(137),
@@ -238,13 +245,14 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://polygonscan.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
     ];
 
     // Load custom networks from keystore and deduplicate
-    match OSKeychain::new("vaughan-wallet".to_string()) {
+    match create_keychain("vaughan-wallet") {
         Ok(keychain) => {
-            match SecureKeystoreImpl::new(Box::new(keychain)).await {
+            match SecureKeystoreImpl::new(keychain).await {
                 Ok(keystore) => {
                     // Get custom networks from keystore
                     let custom_networks = keystore.get_custom_networks();
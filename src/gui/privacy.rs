@@ -0,0 +1,71 @@
+//! Privacy mode: hide balances and values on screen
+//!
+//! When enabled, every balance, amount and fiat value rendered through
+//! [`crate::gui::safe_calculations`] is replaced with [`MASK`], so screens
+//! that format values through that layer are covered without extra work.
+//!
+//! The flag is process-wide and lasts for the session: it survives screen
+//! changes and lock/unlock, and resets when the wallet restarts. Toggle it
+//! with Ctrl+Shift+H or the eye button next to the balance.
+
+use iced::keyboard::{Key, Modifiers};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Placeholder shown instead of a hidden value
+pub const MASK: &str = "•••••";
+
+static PRIVACY_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Whether values are currently hidden
+pub fn is_enabled() -> bool {
+    PRIVACY_MODE.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(enabled: bool) {
+    PRIVACY_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Flip privacy mode and return the new state
+pub fn toggle() -> bool {
+    !PRIVACY_MODE.fetch_xor(true, Ordering::Relaxed)
+}
+
+/// Whether a key press is the privacy hotkey (Ctrl+Shift+H, Cmd+Shift+H on macOS)
+pub fn is_toggle_hotkey(key: &Key, modifiers: Modifiers) -> bool {
+    modifiers.command() && modifiers.shift() && matches!(key, Key::Character(c) if c.eq_ignore_ascii_case("h"))
+}
+
+/// `value`, or [`MASK`] while privacy mode is on
+pub fn mask(value: impl Into<String>) -> String {
+    masked(is_enabled(), value.into())
+}
+
+fn masked(hidden: bool, value: String) -> String {
+    if hidden {
+        MASK.to_string()
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masking() {
+        assert_eq!(masked(true, "1.2345".to_string()), MASK);
+        assert_eq!(masked(false, "1.2345".to_string()), "1.2345");
+    }
+
+    #[test]
+    fn test_hotkey() {
+        let h = Key::Character("H".into());
+        assert!(is_toggle_hotkey(&h, Modifiers::COMMAND | Modifiers::SHIFT));
+        assert!(!is_toggle_hotkey(&h, Modifiers::COMMAND));
+        assert!(!is_toggle_hotkey(
+            &Key::Character("j".into()),
+            Modifiers::COMMAND | Modifiers::SHIFT
+        ));
+    }
+}
@@ -0,0 +1,335 @@
+//! Wallet event recording and replay
+//!
+//! Events that reach the GUI from outside (balance results, account and
+//! network switches, session locks) are recorded as [`WalletEvent`]s with
+//! their timestamps into a JSON-lines log. [`ReplayHarness`] feeds a recorded
+//! session back through [`WorkingWalletApp::update`] on a headless app and
+//! snapshots the resulting UI state after every event, so regression tests
+//! can check state transitions against real-world sequences.
+//!
+//! Payloads are redacted before they are written: every distinct address,
+//! hash and amount is replaced by a stable placeholder, so a replay sees the
+//! same equalities as the live session without the real values. Privacy mode
+//! off (`telemetry.detailed`) keeps the real values.
+//!
+//! Recording is enabled with `--record-events <path>`.
+
+use crate::gui::services::IntegratedAccountService;
+use crate::gui::wallet_messages::Message;
+use crate::gui::working_wallet::WorkingWalletApp;
+use crate::network::NetworkId;
+use crate::telemetry::account_events::privacy::get_privacy_mode;
+use crate::telemetry::metrics::{redact_with, SensitiveToken};
+use chrono::{DateTime, Utc};
+use iced::Application;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+/// An event from the wallet that changes GUI state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WalletEvent {
+    BalanceFetched { result: Result<String, String> },
+    TokenBalancesRefreshed { balances: Vec<(String, String)> },
+    AccountSelected { account_id: String },
+    NetworkSelected { chain_id: u64 },
+    SessionLocked,
+    SessionUnlocked,
+}
+
+impl WalletEvent {
+    /// The event carried by `message`, if it is one that is recorded
+    pub fn from_message(message: &Message) -> Option<Self> {
+        Some(match message {
+            Message::BalanceFetched(result) | Message::BalanceRefreshed(result) => {
+                Self::BalanceFetched { result: result.clone() }
+            }
+            Message::TokenBalancesRefreshed(balances) => Self::TokenBalancesRefreshed {
+                balances: balances.clone(),
+            },
+            Message::AccountSelected(account_id) => Self::AccountSelected {
+                account_id: account_id.clone(),
+            },
+            Message::NetworkSelected(network) => Self::NetworkSelected { chain_id: network.0 },
+            Message::SessionLocked => Self::SessionLocked,
+            Message::SessionUnlocked => Self::SessionUnlocked,
+            _ => return None,
+        })
+    }
+
+    pub fn to_message(&self) -> Message {
+        match self {
+            // The GUI routes refreshed balances, not the unused `BalanceFetched`
+            Self::BalanceFetched { result } => Message::BalanceRefreshed(result.clone()),
+            Self::TokenBalancesRefreshed { balances } => Message::TokenBalancesRefreshed(balances.clone()),
+            Self::AccountSelected { account_id } => Message::AccountSelected(account_id.clone()),
+            Self::NetworkSelected { chain_id } => Message::NetworkSelected(NetworkId(*chain_id)),
+            Self::SessionLocked => Message::SessionLocked,
+            Self::SessionUnlocked => Message::SessionUnlocked,
+        }
+    }
+
+    /// The event with every string passed through `redactor`
+    pub fn redacted(&self, redactor: &mut Redactor) -> Self {
+        match self {
+            Self::BalanceFetched { result } => Self::BalanceFetched {
+                result: match result {
+                    Ok(balance) => Ok(redactor.redact(balance)),
+                    Err(error) => Err(redactor.redact(error)),
+                },
+            },
+            Self::TokenBalancesRefreshed { balances } => Self::TokenBalancesRefreshed {
+                balances: balances
+                    .iter()
+                    .map(|(symbol, balance)| (redactor.redact(symbol), redactor.redact(balance)))
+                    .collect(),
+            },
+            Self::AccountSelected { account_id } => Self::AccountSelected {
+                account_id: redactor.redact(account_id),
+            },
+            Self::NetworkSelected { .. } | Self::SessionLocked | Self::SessionUnlocked => self.clone(),
+        }
+    }
+}
+
+/// Replaces sensitive values with placeholders that are stable within one log
+#[derive(Debug, Default)]
+pub struct Redactor {
+    /// Keep values as they are
+    detailed: bool,
+    aliases: HashMap<(SensitiveToken, String), String>,
+}
+
+impl Redactor {
+    pub fn new(detailed: bool) -> Self {
+        Self {
+            detailed,
+            aliases: HashMap::new(),
+        }
+    }
+
+    /// `text` with addresses, hashes and amounts replaced by their placeholders
+    pub fn redact(&mut self, text: &str) -> String {
+        if self.detailed {
+            return text.to_string();
+        }
+        redact_with(text, |kind, value| {
+            let next = self.aliases.keys().filter(|(k, _)| *k == kind).count() + 1;
+            self.aliases
+                .entry((kind, value.to_lowercase()))
+                .or_insert_with(|| match kind {
+                    SensitiveToken::Address => format!("0x{next:040x}"),
+                    SensitiveToken::Hash => format!("0x{next:064x}"),
+                    SensitiveToken::Amount => format!("{next}.0"),
+                })
+                .clone()
+        })
+    }
+}
+
+/// One recorded event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLogEntry {
+    /// Milliseconds since recording started
+    pub offset_ms: u64,
+    pub timestamp: DateTime<Utc>,
+    pub event: WalletEvent,
+}
+
+/// A recorded session, oldest event first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventLog {
+    pub entries: Vec<EventLogEntry>,
+}
+
+impl EventLog {
+    /// Read a JSON-lines log written by [`EventRecorder`]
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut entries = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(serde_json::from_str(&line).map_err(std::io::Error::other)?);
+        }
+        Ok(Self { entries })
+    }
+}
+
+/// Appends redacted events to a JSON-lines log
+#[derive(Debug)]
+pub struct EventRecorder {
+    started: Instant,
+    inner: Mutex<(File, Redactor)>,
+}
+
+impl EventRecorder {
+    /// Start a new log at `path`, replacing any previous one
+    pub fn create(path: &Path, detailed: bool) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+        Ok(Self {
+            started: Instant::now(),
+            inner: Mutex::new((file, Redactor::new(detailed))),
+        })
+    }
+
+    /// Record `message` if it carries a [`WalletEvent`]
+    pub fn record(&self, message: &Message) -> std::io::Result<()> {
+        let Some(event) = WalletEvent::from_message(message) else {
+            return Ok(());
+        };
+        let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (file, redactor) = &mut *inner;
+        let entry = EventLogEntry {
+            offset_ms: self.started.elapsed().as_millis() as u64,
+            timestamp: Utc::now(),
+            event: event.redacted(redactor),
+        };
+        let line = serde_json::to_string(&entry).map_err(std::io::Error::other)?;
+        writeln!(file, "{line}")
+    }
+}
+
+static EVENT_RECORDER: OnceLock<EventRecorder> = OnceLock::new();
+
+/// Record GUI events to `path` for the rest of the process
+pub fn init_event_recording(path: &Path) -> std::io::Result<()> {
+    let recorder = EventRecorder::create(path, !get_privacy_mode())?;
+    tracing::info!("⏺️ Recording wallet events to {}", path.display());
+    let _ = EVENT_RECORDER.set(recorder);
+    Ok(())
+}
+
+/// Record `message` in the global event log, if recording is enabled
+pub fn record(message: &Message) {
+    if let Some(recorder) = EVENT_RECORDER.get() {
+        if let Err(e) = recorder.record(message) {
+            tracing::warn!("Failed to record wallet event: {}", e);
+        }
+    }
+}
+
+/// GUI state compared between a recording and its replay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiSnapshot {
+    pub chain_id: u64,
+    pub account_id: Option<String>,
+    pub account_balance: String,
+    pub session_unlocked: bool,
+    /// `(symbol, balance)` in display order
+    pub token_balances: Vec<(String, String)>,
+}
+
+impl UiSnapshot {
+    pub fn of(app: &WorkingWalletApp) -> Self {
+        let state = &app.state;
+        Self {
+            chain_id: state.network().current_network.0,
+            account_id: state.wallet().current_account_id.clone(),
+            account_balance: state.account_balance.clone(),
+            session_unlocked: state.auth().session.is_unlocked,
+            token_balances: state
+                .token_balances
+                .iter()
+                .map(|token| (token.symbol.clone(), token.balance.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Drives a headless GUI from recorded events
+///
+/// Commands returned by the app are dropped: a replay only sees the recorded
+/// events, never the RPC calls or timers that produced them.
+pub struct ReplayHarness {
+    pub app: WorkingWalletApp,
+}
+
+impl ReplayHarness {
+    /// A GUI with default state and no wallet attached
+    pub fn new() -> Self {
+        let app = WorkingWalletApp {
+            state: Default::default(),
+            wallet: None,
+            api_manager: None,
+            account_service: Arc::new(IntegratedAccountService::new()),
+            approvals: Default::default(),
+            wallet_controller: Arc::new(crate::controllers::WalletController::new()),
+            price_controller: Arc::new(crate::controllers::PriceController::new(None)),
+            transaction_controller: None,
+            network_controller: None,
+        };
+        Self { app }
+    }
+
+    /// Apply one event and snapshot the state after it
+    pub fn apply(&mut self, event: &WalletEvent) -> UiSnapshot {
+        let _ = self.app.update(event.to_message());
+        UiSnapshot::of(&self.app)
+    }
+
+    /// Apply every event in order, returning the state after each
+    pub fn replay(&mut self, log: &EventLog) -> Vec<UiSnapshot> {
+        log.entries.iter().map(|entry| self.apply(&entry.event)).collect()
+    }
+}
+
+impl Default for ReplayHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction_keeps_equalities() {
+        let mut redactor = Redactor::new(false);
+        let address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+        let first = redactor.redact(&format!("{address} holds 1.5 ETH"));
+        assert_eq!(first, format!("0x{:040x} holds 1.0 ETH", 1));
+        assert_eq!(redactor.redact(&address.to_lowercase()), format!("0x{:040x}", 1));
+        assert_eq!(redactor.redact("2.25 ETH"), "2.0 ETH");
+        assert_eq!(redactor.redact("1.5"), "1.0");
+        assert_eq!(Redactor::new(true).redact(address), address);
+    }
+
+    #[test]
+    fn test_record_and_replay_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let recorder = EventRecorder::create(&path, false).unwrap();
+        for message in [
+            Message::SessionUnlocked,
+            Message::BalanceRefreshed(Ok("12.5000 ETH".to_string())),
+            Message::SmartPollTick,
+            Message::SessionLocked,
+        ] {
+            recorder.record(&message).unwrap();
+        }
+
+        let log = EventLog::load(&path).unwrap();
+        assert_eq!(log.entries.len(), 3);
+        assert_eq!(
+            log.entries[1].event,
+            WalletEvent::BalanceFetched {
+                result: Ok("1.0 ETH".to_string())
+            }
+        );
+
+        let snapshots = ReplayHarness::new().replay(&log);
+        assert!(snapshots[0].session_unlocked);
+        assert_eq!(snapshots[1].account_balance, "1.0 ETH");
+        assert!(!snapshots[2].session_unlocked);
+        assert_eq!(snapshots[2].account_balance, "1.0 ETH");
+    }
+}
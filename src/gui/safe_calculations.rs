@@ -2,9 +2,15 @@
 //!
 //! This module provides safety checks for all numeric calculations used in widget rendering
 //! to prevent crashes due to invalid floating-point values from password bypass scenarios.
+//! Values formatted here are hidden while privacy mode is on (see [`crate::gui::privacy`]).
+
+use crate::gui::privacy;
 
 /// Validates a balance string and ensures it's safe for UI rendering
 pub fn safe_balance(balance: &str) -> String {
+    if privacy::is_enabled() {
+        return privacy::MASK.to_string();
+    }
     if balance.is_empty() {
         return "0.0000".to_string();
     }
@@ -80,6 +86,16 @@ pub fn safe_usd_value(balance: f64, price_per_unit: f64) -> f64 {
     }
 }
 
+/// Format a USD value for display, e.g. `$12.34`
+pub fn safe_usd_display(balance: f64, price_per_unit: f64) -> String {
+    privacy::mask(format!("${:.2}", safe_usd_value(balance, price_per_unit)))
+}
+
+/// Display an already formatted amount (transaction values, balance labels)
+pub fn display_amount(amount: &str) -> String {
+    privacy::mask(amount)
+}
+
 /// Validates widget dimension (width/height) to ensure it's safe for rendering
 pub fn safe_dimension(value: f32) -> f32 {
     if value.is_finite() && value > 0.0 {
@@ -5,18 +5,19 @@
 
 use crate::gui::wallet_types::AccountType;
 use crate::security::{SecureAccount, SeedStrength};
+use crate::telemetry::audit::{self, AuditEvent};
+use uuid::Uuid;
 
 /// Check if any seed-based accounts exist in the keystore
 pub async fn check_for_seed_accounts() -> Result<bool, String> {
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
     use crate::security::keystore::SecureKeystoreImpl;
 
     tracing::info!("Checking for seed-based accounts");
 
     // Create keychain and keystore
-    let keychain = Box::new(
-        OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()).map_err(|e| format!("Failed to initialize keychain: {e}"))?,
-    );
+    let keychain = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+        .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
     let keystore = SecureKeystoreImpl::new(keychain)
         .await
         .map_err(|e| format!("Failed to initialize keystore: {e}"))?;
@@ -60,9 +61,14 @@ pub fn is_seed_based_account(account: &SecureAccount) -> bool {
     get_account_type(account) == AccountType::SeedBased
 }
 
-/// Create a new wallet from seed phrase
-pub async fn create_wallet_from_seed(name: String, seed: String, password: String) -> Result<String, String> {
-    use crate::security::keychain::OSKeychain;
+/// Create a new wallet from seed phrase and optional BIP-39 passphrase
+pub async fn create_wallet_from_seed(
+    name: String,
+    seed: String,
+    password: String,
+    passphrase: Option<String>,
+) -> Result<String, String> {
+    use crate::security::create_keychain;
     use crate::security::keystore::SecureKeystoreImpl;
     use crate::security::seed::SeedManager;
     use secrecy::SecretString;
@@ -70,15 +76,14 @@ pub async fn create_wallet_from_seed(name: String, seed: String, password: Strin
     tracing::info!("Creating wallet from seed: {}", name);
 
     // Create keychain and seed manager (using correct service name for encrypted seeds)
-    let keychain = Box::new(
-        OSKeychain::new(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string())
-            .map_err(|e| format!("Failed to initialize keychain: {e}"))?,
-    );
+    let keychain = create_keychain(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS)
+        .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
     let seed_manager = SeedManager::new(keychain);
 
     // Convert inputs to secure types
     let secure_seed = SecretString::new(seed);
     let secure_password = SecretString::new(password);
+    let secure_passphrase = passphrase.map(SecretString::new);
 
     // Validate the seed phrase first
     seed_manager
@@ -91,16 +96,14 @@ pub async fn create_wallet_from_seed(name: String, seed: String, password: Strin
             name,
             &secure_seed,
             &secure_password,
-            None, // No passphrase for now
+            secure_passphrase.as_ref(),
         )
         .await
         .map_err(|e| format!("Failed to create wallet: {e}"))?;
 
     // Also save the account to the keystore for persistence
-    let keychain2 = Box::new(
-        OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string())
-            .map_err(|e| format!("Failed to initialize keychain for keystore: {e}"))?,
-    );
+    let keychain2 = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+        .map_err(|e| format!("Failed to initialize keychain for keystore: {e}"))?;
     let mut keystore = SecureKeystoreImpl::new(keychain2)
         .await
         .map_err(|e| format!("Failed to initialize keystore: {e}"))?;
@@ -110,6 +113,12 @@ pub async fn create_wallet_from_seed(name: String, seed: String, password: Strin
         .import_account_with_key_reference(account.name.clone(), account.address, account.key_reference.clone())
         .await
         .map_err(|e| format!("Failed to save account to keystore: {e}"))?;
+    if !account.tags.is_empty() {
+        keystore
+            .update_account_tags(account.address, account.tags.clone())
+            .await
+            .map_err(|e| format!("Failed to save account tags: {e}"))?;
+    }
 
     tracing::info!(
         "Successfully created and saved wallet: {} with identity: {}",
@@ -120,9 +129,14 @@ pub async fn create_wallet_from_seed(name: String, seed: String, password: Strin
     Ok(account.id)
 }
 
-/// Import an existing wallet from seed phrase
-pub async fn import_wallet_from_seed(name: String, seed: String, password: String) -> Result<String, String> {
-    use crate::security::keychain::OSKeychain;
+/// Import an existing wallet from seed phrase and optional BIP-39 passphrase
+pub async fn import_wallet_from_seed(
+    name: String,
+    seed: String,
+    password: String,
+    passphrase: Option<String>,
+) -> Result<String, String> {
+    use crate::security::create_keychain;
     use crate::security::keystore::SecureKeystoreImpl;
     use crate::security::seed::SeedManager;
     use secrecy::SecretString;
@@ -139,15 +153,14 @@ pub async fn import_wallet_from_seed(name: String, seed: String, password: Strin
     }
 
     // Create keychain and seed manager (using correct service name for encrypted seeds)
-    let keychain = Box::new(
-        OSKeychain::new(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string())
-            .map_err(|e| format!("Failed to initialize keychain: {e}"))?,
-    );
+    let keychain = create_keychain(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS)
+        .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
     let seed_manager = SeedManager::new(keychain);
 
     // Convert inputs to secure types
     let secure_seed = SecretString::new(seed);
     let secure_password = SecretString::new(password);
+    let secure_passphrase = passphrase.map(SecretString::new);
 
     // Validate the seed phrase first
     seed_manager
@@ -160,16 +173,14 @@ pub async fn import_wallet_from_seed(name: String, seed: String, password: Strin
             name.clone(),
             &secure_seed,
             &secure_password,
-            None, // No passphrase for now
+            secure_passphrase.as_ref(),
         )
         .await
         .map_err(|e| format!("Failed to import wallet: {e}"))?;
 
     // Also save the account to the keystore for persistence
-    let keychain2 = Box::new(
-        OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string())
-            .map_err(|e| format!("Failed to initialize keychain for keystore: {e}"))?,
-    );
+    let keychain2 = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+        .map_err(|e| format!("Failed to initialize keychain for keystore: {e}"))?;
     let mut keystore = SecureKeystoreImpl::new(keychain2)
         .await
         .map_err(|e| format!("Failed to initialize keystore: {e}"))?;
@@ -179,6 +190,12 @@ pub async fn import_wallet_from_seed(name: String, seed: String, password: Strin
         .import_account_with_key_reference(account.name.clone(), account.address, account.key_reference.clone())
         .await
         .map_err(|e| format!("Failed to save account to keystore: {e}"))?;
+    if !account.tags.is_empty() {
+        keystore
+            .update_account_tags(account.address, account.tags.clone())
+            .await
+            .map_err(|e| format!("Failed to save account tags: {e}"))?;
+    }
 
     tracing::info!(
         "Successfully imported and saved wallet: {} with identity: {}",
@@ -191,7 +208,7 @@ pub async fn import_wallet_from_seed(name: String, seed: String, password: Strin
 
 /// Import wallet from private key
 pub async fn import_wallet_from_private_key(name: String, key: String, _password: String) -> Result<String, String> {
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
     use crate::security::keystore::SecureKeystoreImpl;
     use secrecy::SecretString;
 
@@ -227,9 +244,8 @@ pub async fn import_wallet_from_private_key(name: String, key: String, _password
     }
 
     // Create keychain and keystore
-    let keychain = Box::new(
-        OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()).map_err(|e| format!("Failed to initialize keychain: {e}"))?,
-    );
+    let keychain = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+        .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
     let mut keystore = SecureKeystoreImpl::new(keychain)
         .await
         .map_err(|e| format!("Failed to initialize keystore: {e}"))?;
@@ -266,9 +282,59 @@ pub async fn import_wallet_from_private_key(name: String, key: String, _password
     Ok(account.id)
 }
 
+/// Run a password-checked export behind the unlock throttle and export rate limit
+///
+/// Refuses while a back-off or lockout is pending, or once the hourly export
+/// allowance is used up. Any failed export counts as a failed attempt, since
+/// the legacy path can't tell a wrong password apart.
+async fn throttled_export(
+    operation: &str,
+    export: impl std::future::Future<Output = Result<String, String>>,
+) -> Result<String, String> {
+    let mut throttle = crate::security::UnlockThrottle::from_settings();
+    if let Some(wait) = throttle.wait() {
+        let seconds = wait.remaining().as_secs().max(1);
+        return Err(format!(
+            "Too many failed password attempts. Try again in {seconds} seconds."
+        ));
+    }
+    crate::security::ExportAuthenticator::shared()
+        .take_export_allowance(operation, None)
+        .map_err(|e| e.to_string())?;
+    let result = export.await;
+    let recorded = match &result {
+        Ok(_) => throttle.record_success(),
+        Err(_) => throttle.record_failure().map(drop),
+    };
+    if let Err(e) = recorded {
+        tracing::warn!("⚠️ Failed to update unlock attempt counter: {}", e);
+    }
+    result
+}
+
+/// Tag an account exported through the WalletManager, see [`cold_account::mark_key_exported`]
+///
+/// [`cold_account::mark_key_exported`]: crate::security::cold_account::mark_key_exported
+async fn mark_key_exported(address: alloy::primitives::Address) {
+    use crate::security::{create_keychain, keystore::SecureKeystoreImpl};
+
+    let keystore = match create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS) {
+        Ok(keychain) => SecureKeystoreImpl::new(keychain).await,
+        Err(e) => Err(e),
+    };
+    match keystore {
+        Ok(mut keystore) => crate::security::cold_account::mark_key_exported(&mut keystore, address).await,
+        Err(e) => tracing::warn!("Failed to record key export for {}: {}", address, e),
+    }
+}
+
 /// Unified export seed phrase - checks for new WalletManager format, falls back to legacy
 /// This is the new primary entry point for seed phrase export (Task 4.3)
 pub async fn export_seed_phrase_unified(account_id: String, password: String) -> Result<String, String> {
+    throttled_export("export_seed", export_seed_phrase_unchecked(account_id, password)).await
+}
+
+async fn export_seed_phrase_unchecked(account_id: String, password: String) -> Result<String, String> {
     use crate::wallet::WalletManager;
     use secrecy::SecretString;
 
@@ -293,6 +359,10 @@ pub async fn export_seed_phrase_unified(account_id: String, password: String) ->
                 match manager.export_seed_phrase() {
                     Ok(seed_phrase) => {
                         tracing::info!("✅ Successfully exported seed phrase using WalletManager");
+                        if let Ok(account) = manager.address() {
+                            audit::record(AuditEvent::ExportSeed { account }, Uuid::new_v4());
+                            mark_key_exported(account).await;
+                        }
                         return Ok(seed_phrase);
                     }
                     Err(e) => {
@@ -316,6 +386,10 @@ pub async fn export_seed_phrase_unified(account_id: String, password: String) ->
 /// Unified export private key - checks for new WalletManager format, falls back to legacy
 /// This is the new primary entry point for private key export (Task 4.3)
 pub async fn export_private_key_unified(account_id: String, password: String) -> Result<String, String> {
+    throttled_export("export_private_key", export_private_key_unchecked(account_id, password)).await
+}
+
+async fn export_private_key_unchecked(account_id: String, password: String) -> Result<String, String> {
     use crate::wallet::WalletManager;
     use secrecy::{ExposeSecret, SecretString};
 
@@ -340,6 +414,10 @@ pub async fn export_private_key_unified(account_id: String, password: String) ->
                 match manager.export_private_key() {
                     Ok(private_key) => {
                         tracing::info!("✅ Successfully exported private key using WalletManager");
+                        if let Ok(account) = manager.address() {
+                            audit::record(AuditEvent::ExportPrivateKey { account }, Uuid::new_v4());
+                            mark_key_exported(account).await;
+                        }
                         // Return with 0x prefix if not present
                         let key_string = private_key.expose_secret();
                         if key_string.starts_with("0x") {
@@ -368,7 +446,7 @@ pub async fn export_private_key_unified(account_id: String, password: String) ->
 
 /// Export seed phrase with password verification (legacy method)
 pub async fn export_seed_phrase_with_password(account_id: String, password: String) -> Result<String, String> {
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
 
     use crate::security::keystore::SecureKeystoreImpl;
     use crate::security::seed::SeedManager;
@@ -379,10 +457,9 @@ pub async fn export_seed_phrase_with_password(account_id: String, password: Stri
     // Add timeout to prevent hanging export operations
     let export_task = async move {
         // Create keychain and keystore to get the account
-        let keychain = Box::new(
-            OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()).map_err(|e| format!("Failed to initialize keychain: {e}"))?,
-        );
-        let keystore = SecureKeystoreImpl::new(keychain)
+        let keychain = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+            .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
+        let mut keystore = SecureKeystoreImpl::new(keychain)
             .await
             .map_err(|e| format!("Failed to initialize keystore: {e}"))?;
 
@@ -399,10 +476,8 @@ pub async fn export_seed_phrase_with_password(account_id: String, password: Stri
 
         // Create seed manager for export
         // IMPORTANT: Use "vaughan-wallet-encrypted-seeds" service - this is where seeds are stored
-        let keychain2 = Box::new(
-            OSKeychain::new(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string())
-                .map_err(|e| format!("Failed to initialize keychain for seed manager: {e}"))?,
-        );
+        let keychain2 = create_keychain(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS)
+            .map_err(|e| format!("Failed to initialize keychain for seed manager: {e}"))?;
         let seed_manager = SeedManager::new(keychain2);
 
         // Convert password to secure type
@@ -441,6 +516,13 @@ pub async fn export_seed_phrase_with_password(account_id: String, password: Stri
             })?;
 
         tracing::info!("Successfully exported seed phrase for account: {}", account.name);
+        audit::record(
+            AuditEvent::ExportSeed {
+                account: account.address,
+            },
+            Uuid::new_v4(),
+        );
+        crate::security::cold_account::mark_key_exported(&mut keystore, account.address).await;
 
         // Return the seed phrase from the export result
         let seed_phrase = export_result.data.expose_secret().clone();
@@ -455,12 +537,59 @@ pub async fn export_seed_phrase_with_password(account_id: String, password: Stri
     }
 }
 
+/// Check a re-entered seed phrase against the account's stored seed
+///
+/// Returns only whether the phrases match; the stored phrase never leaves the keystore.
+pub async fn verify_seed_backup(account_id: String, password: String, entered_phrase: String) -> Result<bool, String> {
+    use crate::security::create_keychain;
+    use crate::security::keystore::SecureKeystoreImpl;
+    use secrecy::SecretString;
+
+    let verify_task = async move {
+        let keychain = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+            .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
+        let keystore = SecureKeystoreImpl::new(keychain)
+            .await
+            .map_err(|e| format!("Failed to initialize keystore: {e}"))?;
+
+        let accounts = keystore
+            .list_accounts()
+            .await
+            .map_err(|e| format!("Failed to list accounts: {e}"))?;
+        let account = accounts
+            .iter()
+            .find(|a| a.id == account_id)
+            .ok_or_else(|| format!("Account not found: {account_id}"))?;
+
+        let password = SecretString::new(password);
+        let entered_phrase = SecretString::new(entered_phrase);
+        keystore
+            .verify_seed_backup(&account.address, &password, &entered_phrase)
+            .await
+            .map_err(|e| {
+                let error_str = e.to_string();
+                if error_str.contains("not seed-based") {
+                    "This account was not created from a seed phrase, so there is no backup phrase to check."
+                        .to_string()
+                } else if error_str.contains("password") || error_str.contains("authentication") {
+                    "Incorrect password. Please check your password and try again.".to_string()
+                } else {
+                    format!("Failed to check seed phrase backup: {e}")
+                }
+            })
+    };
+
+    match tokio::time::timeout(std::time::Duration::from_secs(30), verify_task).await {
+        Ok(result) => result,
+        Err(_) => Err("Backup check timed out. Please try again.".to_string()),
+    }
+}
+
 /// Export private key with password verification
 pub async fn export_private_key_with_password(account_id: String, password: String) -> Result<String, String> {
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
     use crate::security::keystore::SecureKeystoreImpl;
     use crate::security::seed::SeedManager;
-    use crate::security::KeychainInterface;
     // HDWallet functionality removed - using simplified key derivation
     use secrecy::{ExposeSecret, SecretString};
 
@@ -469,10 +598,9 @@ pub async fn export_private_key_with_password(account_id: String, password: Stri
     // Add timeout to prevent hanging export operations
     let export_task = async move {
         // Create keychain and keystore to get the account (following seed phrase export pattern)
-        let keychain = Box::new(
-            OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()).map_err(|e| format!("Failed to initialize keychain: {e}"))?,
-        );
-        let keystore = SecureKeystoreImpl::new(keychain)
+        let keychain = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+            .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
+        let mut keystore = SecureKeystoreImpl::new(keychain)
             .await
             .map_err(|e| format!("Failed to initialize keystore: {e}"))?;
 
@@ -489,10 +617,8 @@ pub async fn export_private_key_with_password(account_id: String, password: Stri
 
         // Create seed manager for export (following seed phrase export pattern)
         // IMPORTANT: Use "vaughan-wallet-encrypted-seeds" service - this is where seeds are stored
-        let keychain2 = Box::new(
-            OSKeychain::new(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string())
-                .map_err(|e| format!("Failed to initialize keychain for seed manager: {e}"))?,
-        );
+        let keychain2 = create_keychain(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS)
+            .map_err(|e| format!("Failed to initialize keychain for seed manager: {e}"))?;
         let seed_manager = SeedManager::new(keychain2);
 
         // Convert password to secure type
@@ -540,10 +666,8 @@ pub async fn export_private_key_with_password(account_id: String, password: Stri
                 );
 
                 // Try to retrieve private key directly from keychain
-                let keychain3 = Box::new(
-                    OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string())
-                        .map_err(|e| format!("Failed to initialize keychain for private key retrieval: {e}"))?,
-                );
+                let keychain3 = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+                    .map_err(|e| format!("Failed to initialize keychain for private key retrieval: {e}"))?;
                 let private_key_data = keychain3
                     .retrieve(&account.key_reference)
                     .map_err(|e| format!("Failed to retrieve private key from keychain: {e}"))?;
@@ -570,6 +694,13 @@ pub async fn export_private_key_with_password(account_id: String, password: Stri
         };
 
         tracing::info!("Successfully derived private key for account: {}", account.name);
+        audit::record(
+            AuditEvent::ExportPrivateKey {
+                account: account.address,
+            },
+            Uuid::new_v4(),
+        );
+        crate::security::cold_account::mark_key_exported(&mut keystore, account.address).await;
 
         Ok(private_key_hex)
     }; // End of export_task closure
@@ -584,15 +715,15 @@ pub async fn export_private_key_with_password(account_id: String, password: Stri
 /// Discover addresses from seed phrase
 pub async fn discover_addresses_from_seed(seed: String) -> Result<Vec<(String, String, bool)>, String> {
     // HDWallet functionality removed - using simplified key derivation
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
     use crate::security::keystore::SecureKeystoreImpl;
     use secrecy::SecretString;
 
     let _secure_seed = SecretString::new(seed);
 
     // Create keystore to check existing accounts
-    let keychain =
-        Box::new(OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()).map_err(|e| format!("Failed to create keychain: {e}"))?);
+    let keychain = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+        .map_err(|e| format!("Failed to create keychain: {e}"))?;
     let keystore = SecureKeystoreImpl::new(keychain)
         .await
         .map_err(|e| format!("Failed to create keystore: {e}"))?;
@@ -629,7 +760,7 @@ pub async fn import_multiple_addresses_from_seed(
     password: String,
     selected_paths: Vec<String>,
 ) -> Result<Vec<String>, String> {
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
     use crate::security::keystore::SecureKeystoreImpl;
     use crate::security::seed::SeedManager;
     use secrecy::SecretString;
@@ -641,10 +772,8 @@ pub async fn import_multiple_addresses_from_seed(
     let mut imported_addresses = Vec::new();
 
     // Create keychain and seed manager (using correct service name for encrypted seeds)
-    let keychain = Box::new(
-        OSKeychain::new(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string())
-            .map_err(|e| format!("Failed to initialize keychain: {e}"))?,
-    );
+    let keychain = create_keychain(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS)
+        .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
     let seed_manager = SeedManager::new(keychain);
 
     // Convert inputs to secure types
@@ -670,10 +799,8 @@ pub async fn import_multiple_addresses_from_seed(
         {
             Ok(account) => {
                 // Save to keystore for persistence
-                let keychain2 = Box::new(
-                    OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string())
-                        .map_err(|e| format!("Failed to initialize keychain for keystore: {e}"))?,
-                );
+                let keychain2 = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS)
+                    .map_err(|e| format!("Failed to initialize keychain for keystore: {e}"))?;
                 let mut keystore = SecureKeystoreImpl::new(keychain2)
                     .await
                     .map_err(|e| format!("Failed to initialize keystore: {e}"))?;
@@ -706,20 +833,19 @@ pub async fn import_multiple_addresses_from_seed(
 }
 
 /// Delete an account by ID
-pub async fn delete_account(id: String) -> Result<String, String> {
-    use crate::security::{create_keychain_interface, keystore::SecureKeystoreImpl};
-
+///
+/// The wallet takes a recovery point encrypted with `backup_password` first.
+pub async fn delete_account(
+    wallet: std::sync::Arc<tokio::sync::RwLock<crate::wallet::Vaughan>>,
+    id: String,
+    backup_password: secrecy::SecretString,
+) -> Result<String, String> {
     tracing::info!("🗑️ Deleting account: {}", id);
 
-    // Create keychain and keystore
-    let keychain = create_keychain_interface().map_err(|e| format!("Failed to create keychain interface: {e}"))?;
-
-    let mut keystore = SecureKeystoreImpl::new(keychain)
-        .await
-        .map_err(|e| format!("Failed to create keystore: {e}"))?;
+    let mut wallet = wallet.write().await;
 
     // Get all accounts to find the one to delete
-    let accounts = keystore
+    let accounts = wallet
         .list_accounts()
         .await
         .map_err(|e| format!("Failed to list accounts: {e}"))?;
@@ -733,9 +859,9 @@ pub async fn delete_account(id: String) -> Result<String, String> {
     let account_address = account_to_delete.address;
     let account_name = account_to_delete.name.clone();
 
-    // Delete the account from keystore
-    keystore
-        .remove_account(account_address)
+    // Delete the account through the wallet so a recovery point is taken
+    wallet
+        .remove_account(account_address, &backup_password)
         .await
         .map_err(|e| format!("Failed to delete account: {e}"))?;
 
@@ -745,7 +871,7 @@ pub async fn delete_account(id: String) -> Result<String, String> {
 
 /// Analyze seed phrase strength and security
 pub async fn analyze_seed_phrase(phrase: String) -> Result<crate::security::seed::SeedAnalysis, String> {
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
     use crate::security::seed::{SeedAnalysis, SeedImportConfig, SeedManager, SeedStrength};
 
     if phrase.trim().is_empty() {
@@ -753,10 +879,8 @@ pub async fn analyze_seed_phrase(phrase: String) -> Result<crate::security::seed
     }
 
     // Create keychain and seed manager for analysis
-    let keychain = Box::new(
-        OSKeychain::new("vaughan-wallet-temp".to_string())
-            .map_err(|e| format!("Failed to initialize keychain: {e}"))?,
-    );
+    let keychain = create_keychain("vaughan-wallet-temp")
+        .map_err(|e| format!("Failed to initialize keychain: {e}"))?;
     let seed_manager = SeedManager::new(keychain);
 
     // Use comprehensive validation for detailed feedback
@@ -812,16 +936,16 @@ pub async fn analyze_seed_phrase(phrase: String) -> Result<crate::security::seed
 
 /// Generate a new seed phrase with specified strength
 pub async fn generate_seed_phrase_with_strength(strength: SeedStrength) -> String {
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
     use crate::security::seed::SeedManager;
     use secrecy::ExposeSecret;
 
     tracing::info!("Generating new seed phrase with strength: {:?}", strength);
 
     // Create keychain and seed manager
-    match OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()) {
+    match create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS) {
         Ok(keychain) => {
-            let seed_manager = SeedManager::new(Box::new(keychain));
+            let seed_manager = SeedManager::new(keychain);
 
             match seed_manager.generate_seed_phrase(strength) {
                 Ok(seed_phrase) => {
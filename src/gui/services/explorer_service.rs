@@ -4,9 +4,10 @@
 //! to fetch transaction history and other data.
 
 use crate::gui::{Transaction, TransactionStatus};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::shared_client;
 use crate::network::NetworkId;
 use chrono;
-use reqwest;
 use serde_json;
 
 /// Comprehensive fetch from block explorer APIs
@@ -146,14 +147,13 @@ async fn fetch_from_pulsescan(address: &str, is_testnet: bool) -> Result<Vec<Tra
 
 /// Fetch from Blockscout v2 API format
 async fn fetch_from_blockscout_v2(url: &str, symbol: &str) -> Result<Vec<Transaction>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36")
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    egress()
+        .check(EgressCategory::Explorer, url, "fetch transaction history")
+        .map_err(|e| e.to_string())?;
 
-    let response = client
+    let response = shared_client()
         .get(url)
+        .timeout(std::time::Duration::from_secs(10))
         .send()
         .await
         .map_err(|e| format!("HTTP request failed: {e}"))?;
@@ -230,14 +230,13 @@ async fn fetch_from_blockscout_v2(url: &str, symbol: &str) -> Result<Vec<Transac
 async fn fetch_transactions_from_url(url: &str, symbol: &str) -> Result<Vec<Transaction>, String> {
     tracing::debug!("Fetching transactions from: {}", url);
 
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36")
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    egress()
+        .check(EgressCategory::Explorer, url, "fetch transaction history")
+        .map_err(|e| e.to_string())?;
 
-    let response = client
+    let response = shared_client()
         .get(url)
+        .timeout(std::time::Duration::from_secs(10))
         .send()
         .await
         .map_err(|e| format!("HTTP request failed: {e}"))?;
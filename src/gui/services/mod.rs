@@ -11,7 +11,7 @@
 //! # Services
 //! - `account_service`: Account creation, import, export operations
 //! - `network_service`: Network configuration and management
-//! - `token_service`: Custom token persistence
+//! - `token_service`: Custom token persistence and airdrop eligibility checks
 //! - `wallet_service`: Wallet initialization and account loading
 //! - `explorer_service`: Block explorer API integration
 //! - `auto_balance_service`: Automatic balance monitoring
@@ -45,7 +45,11 @@ pub use auto_balance_service::AutoBalanceMessage;
 pub use integrated_account_service::IntegratedAccountService;
 pub use network_service::*;
 pub use token_service::{load_custom_tokens, save_custom_tokens};
-pub use wallet_service::{initialize_wallet, load_available_accounts};
+pub use wallet_service::{
+    initialize_wallet, load_available_accounts, load_transaction_drafts, save_transaction_drafts,
+    stream_account_balances, stream_approval_requests, stream_available_accounts, stream_balance_changes,
+    ApprovalInbox,
+};
 
 // Re-exports from new services
 pub use asset_service::{AssetService, AssetServiceTrait};
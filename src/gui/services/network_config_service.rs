@@ -179,6 +179,7 @@ mod tests {
             block_explorer_url: String::new(),
             is_testnet: false,
             is_custom: true,
+            fallback_rpc_urls: Vec::new(),
         }
     }
 
@@ -159,7 +159,7 @@ pub async fn edit_existing_network(
     tracing::info!("Editing custom network: {} (Chain ID: {})", name, chain_id);
 
     // Parse the network ID
-    let parsed_chain_id = id.parse::<u64>().map_err(|e| format!("Invalid network ID: {e}"))?;
+    let parsed_chain_id = crate::gui::input_validation::chain_id(&id).map_err(|e| format!("Invalid network ID: {e}"))?;
 
     // Step 1: Validate form inputs
     let form_validation = validate_custom_network_config(
@@ -299,7 +299,7 @@ pub async fn delete_existing_network(id: String) -> Result<String, String> {
     tracing::info!("Deleting custom network: {}", id);
 
     // Parse the network ID
-    let chain_id = id.parse::<u64>().map_err(|e| format!("Invalid network ID: {e}"))?;
+    let chain_id = crate::gui::input_validation::chain_id(&id).map_err(|e| format!("Invalid network ID: {e}"))?;
 
     // Update keystore storage
     match create_keychain_interface() {
@@ -329,6 +329,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://etherscan.io".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(369),
@@ -339,6 +340,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://scan.pulsechain.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(943),
@@ -349,6 +351,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://scan.v4.testnet.pulsechain.com".to_string(),
             is_testnet: true,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(56),
@@ -359,6 +362,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://bscscan.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(137),
@@ -369,6 +373,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://polygonscan.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(42161),
@@ -379,6 +384,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://arbiscan.io".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
         NetworkConfig {
             id: NetworkId(10),
@@ -389,6 +395,7 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
             block_explorer_url: "https://optimistic.etherscan.io".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         },
     ];
 
@@ -409,15 +416,15 @@ pub async fn load_all_networks() -> Vec<NetworkConfig> {
 
 /// Save networks to persistent storage
 pub async fn save_networks_to_storage(networks: Vec<NetworkConfig>) -> Result<(), String> {
-    use crate::security::keychain::OSKeychain;
+    use crate::security::create_keychain;
     use crate::security::keystore::SecureKeystoreImpl;
 
     tracing::info!("Saving networks to storage...");
 
     // Create keychain and keystore
-    match OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()) {
+    match create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS) {
         Ok(keychain) => {
-            match SecureKeystoreImpl::new(Box::new(keychain)).await {
+            match SecureKeystoreImpl::new(keychain).await {
                 Ok(mut keystore) => {
                     // Clear existing custom networks and add all custom ones from the list
                     let custom_networks: Vec<NetworkConfig> = networks.into_iter().filter(|n| n.is_custom).collect();
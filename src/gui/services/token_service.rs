@@ -1,9 +1,12 @@
 //! Token Service - Custom token persistence
 //!
-//! Handles saving and loading custom tokens using the config system.
+//! Handles saving and loading custom tokens using the config system, and
+//! checks token balances at past blocks for airdrop eligibility.
 
 use crate::config::{ConfigManager, CustomToken, CustomTokensConfig};
 use crate::gui::wallet_types::TokenInfo;
+use crate::tokens::historical::HistoricalBalanceReader;
+use alloy::primitives::{Address, U256};
 use std::collections::HashMap;
 
 const TOKENS_CONFIG_FILE: &str = "custom_tokens.json";
@@ -77,3 +80,48 @@ pub async fn load_custom_tokens() -> Result<Vec<TokenInfo>, String> {
 
     Ok(gui_tokens)
 }
+
+/// Check whether `account` held at least `minimum` of `token` at `block`
+///
+/// `minimum` is in whole tokens and may be empty. `from_block` is used to
+/// rebuild the balance from transfer logs when the endpoint has no archive
+/// state. Returns the report to show the user.
+pub async fn check_airdrop_eligibility(
+    token: &TokenInfo,
+    account: &str,
+    block: &str,
+    minimum: &str,
+    from_block: Option<u64>,
+    rpc_url: &str,
+) -> Result<String, String> {
+    let token_address: Address = token.address.parse().map_err(|_| "Invalid token address".to_string())?;
+    let account: Address = account
+        .trim()
+        .parse()
+        .map_err(|_| "Invalid account address".to_string())?;
+    let block: u64 = block.trim().parse().map_err(|_| "Invalid block number".to_string())?;
+    let required = if minimum.trim().is_empty() {
+        U256::ZERO
+    } else {
+        crate::utils::parse_token_amount(minimum.trim(), token.decimals).map_err(|e| e.to_string())?
+    };
+    let provider = crate::network::connect_rpc(rpc_url, "token balance").map_err(|e| e.to_string())?;
+
+    let reader = HistoricalBalanceReader::new(provider);
+    let balance = reader
+        .balance_at(token_address, account, block, from_block)
+        .await
+        .map_err(|e| format!("Failed to read balance at block {block}: {e}"))?;
+    let check = balance.check(required);
+    let held = crate::utils::format_token_amount(balance.balance, token.decimals);
+    let verdict = if check.is_eligible() {
+        "eligible".to_string()
+    } else {
+        format!(
+            "not eligible, {} {} short",
+            crate::utils::format_token_amount(check.shortfall(), token.decimals),
+            token.symbol
+        )
+    };
+    Ok(format!("Held {held} {} at block {block}: {verdict}", token.symbol))
+}
@@ -14,6 +14,7 @@
 //! - Alloy Address validation: https://github.com/alloy-rs/alloy
 //! - MetaMask transaction validation patterns
 
+use crate::gui::input_validation::{self, InputError};
 use alloy::primitives::{Address, U256};
 use std::str::FromStr;
 
@@ -81,7 +82,7 @@ pub trait TransactionFormServiceTrait: Send + Sync {
     /// Validate a recipient address using Alloy primitives
     ///
     /// # Security
-    /// - Uses Alloy's Address::from_str for proper validation
+    /// - Uses the shared GUI input validator (format and EIP-55 checksum)
     /// - Checks for zero address (0x0000...0000)
     /// - Follows MetaMask address validation patterns
     fn validate_recipient(&self, address: &str) -> Result<Address, TransactionValidationError>;
@@ -157,73 +158,30 @@ impl TransactionFormService {
     /// # Returns
     /// U256 value in smallest unit (wei for ETH)
     fn parse_amount_to_wei(amount_str: &str, decimals: u8) -> Result<U256, TransactionValidationError> {
-        let trimmed = amount_str.trim();
+        // Exact decimal parsing, no floating point rounding
+        let amount = input_validation::amount(amount_str, decimals).map_err(|e| match e {
+            InputError::Empty => TransactionValidationError::InvalidAmount("Amount cannot be empty".to_string()),
+            InputError::AmountTooLarge => TransactionValidationError::AmountTooLarge,
+            other => TransactionValidationError::InvalidAmount(other.to_string()),
+        })?;
         
-        if trimmed.is_empty() {
-            return Err(TransactionValidationError::InvalidAmount(
-                "Amount cannot be empty".to_string()
-            ));
-        }
-        
-        // Parse as f64 first to handle decimal input
-        let amount_f64 = trimmed.parse::<f64>()
-            .map_err(|e| TransactionValidationError::InvalidAmount(
-                format!("Cannot parse amount: {}", e)
-            ))?;
-        
-        // Check for negative or zero
-        if amount_f64 <= 0.0 {
+        if amount.is_zero() {
             return Err(TransactionValidationError::AmountTooSmall);
         }
         
-        // Check for reasonable maximum (prevent overflow)
-        // Max safe f64 that won't overflow when multiplied by 10^18
-        if amount_f64 > 1e59 {
-            return Err(TransactionValidationError::AmountTooLarge);
-        }
-        
-        // Convert to wei by multiplying by 10^decimals
-        // For ETH: 1.5 ETH = 1.5 * 10^18 wei
-        let multiplier = 10_f64.powi(decimals as i32);
-        let amount_wei_f64 = amount_f64 * multiplier;
-        
-        // Convert to U256
-        // Use string conversion to avoid precision loss
-        let amount_wei_str = format!("{:.0}", amount_wei_f64);
-        U256::from_str(&amount_wei_str)
-            .map_err(|e| TransactionValidationError::InvalidAmount(
-                format!("Amount too large: {}", e)
-            ))
+        Ok(amount)
     }
 }
 
 impl TransactionFormServiceTrait for TransactionFormService {
     fn validate_recipient(&self, address: &str) -> Result<Address, TransactionValidationError> {
-        let trimmed = address.trim();
-        
-        if trimmed.is_empty() {
-            return Err(TransactionValidationError::InvalidRecipient(
-                "Address cannot be empty".to_string()
-            ));
-        }
-        
-        // Normalize to lowercase for parsing (Alloy accepts lowercase 0x prefix)
-        let normalized = trimmed.to_lowercase();
-        
-        // Use Alloy's Address::from_str for proper validation
-        // This follows Ethereum address standards (0x + 40 hex chars)
-        let parsed_address = Address::from_str(&normalized)
-            .map_err(|e| TransactionValidationError::InvalidRecipient(
-                format!("Invalid address format: {}", e)
-            ))?;
-        
-        // Check for zero address (0x0000000000000000000000000000000000000000)
-        // Sending to zero address burns tokens - prevent accidental burns
-        if parsed_address == Address::ZERO {
-            return Err(TransactionValidationError::RecipientIsZeroAddress);
-        }
-        
-        Ok(parsed_address)
+        // Checks format, EIP-55 checksum of mixed-case input and the zero address
+        // (sending to the zero address burns tokens)
+        input_validation::recipient_address(address).map_err(|e| match e {
+            InputError::Empty => TransactionValidationError::InvalidRecipient("Address cannot be empty".to_string()),
+            InputError::ZeroAddress => TransactionValidationError::RecipientIsZeroAddress,
+            other => TransactionValidationError::InvalidRecipient(other.to_string()),
+        })
     }
     
     fn validate_amount(
@@ -352,11 +310,20 @@ mod tests {
     #[test]
     fn test_validate_recipient_valid() {
         let s = service();
-        let addr = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+        let addr = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
         let result = s.validate_recipient(addr);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_validate_recipient_bad_checksum() {
+        let s = service();
+        // Mixed case with one character's case flipped
+        let addr = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+        let result = s.validate_recipient(addr);
+        assert!(matches!(result.unwrap_err(), TransactionValidationError::InvalidRecipient(_)));
+    }
+
     #[test]
     fn test_validate_recipient_lowercase() {
         let s = service();
@@ -3,20 +3,35 @@
 //! Contains functions for wallet initialization, account loading, and core wallet operations
 //! extracted from working_wallet.rs
 
+use crate::gui::utils::format_balance;
+use crate::network::poller::BalanceChanged;
 use crate::network::NetworkId;
-use crate::security::SecureAccount;
-use std::sync::Arc;
+use crate::security::{SecureAccount, SecurityProfile};
+use crate::wallet::account_manager::progressive::{
+    account_pages, balance_stream, AccountPage, BALANCE_CONCURRENCY, DEFAULT_PAGE_SIZE,
+};
+use crate::wallet::approval::{ApprovalId, ApprovalReceiver, ApprovalRequest, ApprovalResponse, PendingApproval};
+use crate::wallet::transaction::drafts::{DraftBook, DraftStore};
+use alloy::primitives::Address;
+use futures_util::{Stream, StreamExt};
+use secrecy::SecretString;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-/// Initialize a new wallet instance with default configuration
-pub async fn initialize_wallet() -> Result<Arc<tokio::sync::RwLock<crate::wallet::Vaughan>>, String> {
-    use crate::wallet::{Vaughan, WalletConfig};
-
-    let config = WalletConfig {
-        default_network: NetworkId(943), // PulseChain Testnet v4 - consistent with imported accounts
-        ..Default::default()
-    };
+/// Wallet configuration the GUI starts with
+///
+/// Startup network comes from the stored preferences (last used / configured
+/// default). Strict lock keeps its build default: signing stays locked until
+/// the password from the GUI's signing dialog unlocks it through the wallet.
+pub fn gui_wallet_config() -> crate::wallet::WalletConfig {
+    crate::wallet::WalletConfig::default()
+}
 
-    match Vaughan::new(config).await {
+/// Initialize a new wallet instance over `profile` with the GUI configuration
+pub async fn initialize_wallet(
+    profile: SecurityProfile,
+) -> Result<Arc<tokio::sync::RwLock<crate::wallet::Vaughan>>, String> {
+    match crate::wallet::Vaughan::for_profile(gui_wallet_config(), profile).await {
         Ok(wallet) => {
             tracing::info!("✅ Wallet initialized successfully");
             Ok(Arc::new(tokio::sync::RwLock::new(wallet)))
@@ -28,114 +43,205 @@ pub async fn initialize_wallet() -> Result<Arc<tokio::sync::RwLock<crate::wallet
     }
 }
 
-/// Load all available accounts from persistent storage
-pub async fn load_available_accounts() -> Result<Vec<SecureAccount>, String> {
-    use crate::security::{keychain::OSKeychain, KeyReference, KeychainInterface};
+/// Load all available accounts of `profile` from persistent storage
+///
+/// Reads metadata only, so the account list renders while the wallet is
+/// locked; keys are checked when an account is unlocked or used.
+pub async fn load_available_accounts(profile: SecurityProfile) -> Result<Vec<SecureAccount>, String> {
+    use crate::wallet::{AccountMetadataQueries, AccountMetadataStore};
 
     tracing::info!("Loading available accounts from persistent storage...");
 
-    // Load account metadata from persistent file using standardized path
-    let mut accounts_path = crate::security::keystore::storage::get_vaughan_dir();
-    accounts_path.push("accounts.json");
-
-    let mut all_accounts = Vec::new();
-
-    if let Ok(content) = std::fs::read_to_string(&accounts_path) {
-        if let Ok(stored_accounts) = serde_json::from_str::<Vec<serde_json::Value>>(&content) {
-            tracing::info!(
-                "Found {} stored accounts in {}",
-                stored_accounts.len(),
-                accounts_path.display()
-            );
-
-            for stored in stored_accounts {
-                // Extract account metadata
-                let id = stored["id"].as_str().unwrap_or("").to_string();
-                let name = stored["name"].as_str().unwrap_or("Unknown").to_string();
-                let address_str = stored["address"].as_str().unwrap_or("");
-                let service = stored["key_reference"]["service"].as_str().unwrap_or("");
-                let created_at_str = stored["created_at"].as_str().unwrap_or("");
-                let derivation_path = stored["derivation_path"]
-                    .as_str()
-                    .unwrap_or("m/44'/60'/0'/0/0")
-                    .to_string();
-
-                // Parse address
-                if let Ok(address) = address_str.parse::<alloy::primitives::Address>() {
-                    // Validate that the key exists in the appropriate keychain
-                    let key_exists = match service {
-                        crate::security::SERVICE_NAME_ENCRYPTED_SEEDS => {
-                            // Check encrypted seed keychain
-                            if let Ok(seed_keychain) = OSKeychain::new(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string()) {
-                                let key_ref = KeyReference {
-                                    id: stored["key_reference"]["id"].as_str().unwrap_or("").to_string(),
-                                    service: service.to_string(),
-                                    account: stored["key_reference"]["account"].as_str().unwrap_or("").to_string(),
-                                };
-                                seed_keychain.retrieve(&key_ref).is_ok()
-                            } else {
-                                false
-                            }
-                        }
-                        crate::security::SERVICE_NAME_PRIVATE_KEYS => {
-                            // Check private key keychain
-                            if let Ok(keychain) = OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()) {
-                                let key_ref = KeyReference {
-                                    id: stored["key_reference"]["id"].as_str().unwrap_or("").to_string(),
-                                    service: service.to_string(),
-                                    account: stored["key_reference"]["account"].as_str().unwrap_or("").to_string(),
-                                };
-                                keychain.retrieve(&key_ref).is_ok()
-                            } else {
-                                false
-                            }
-                        }
-                        _ => {
-                            tracing::warn!("Unknown service type: {}", service);
-                            false
-                        }
-                    };
-
-                    if key_exists {
-                        // Parse created_at timestamp
-                        let created_at = if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(created_at_str) {
-                            dt.with_timezone(&chrono::Utc)
-                        } else {
-                            chrono::Utc::now()
-                        };
-
-                        // Create SecureAccount
-                        let account = crate::security::SecureAccount {
-                            id,
-                            name: name.clone(),
-                            address,
-                            key_reference: KeyReference {
-                                id: stored["key_reference"]["id"].as_str().unwrap_or("").to_string(),
-                                service: service.to_string(),
-                                account: stored["key_reference"]["account"].as_str().unwrap_or("").to_string(),
-                            },
-                            created_at,
-                            is_hardware: false,
-                            derivation_path: Some(derivation_path),
-                            tags: Vec::new(),
-                            last_used: None,
-                            transaction_count: 0,
-                        };
-
-                        all_accounts.push(account);
-                        tracing::info!("✅ Loaded account: {} ({})", name, address);
-                    } else {
-                        tracing::warn!("⚠️ Account key not found in keychain, skipping: {} ({})", name, address);
-                    }
-                } else {
-                    tracing::warn!("⚠️ Invalid address format, skipping account: {}", name);
+    let accounts = AccountMetadataStore::new(profile)
+        .list_accounts()
+        .await
+        .map_err(|e| format!("Failed to load accounts: {e}"))?;
+
+    tracing::info!("Loaded {} accounts", accounts.len());
+    Ok(accounts)
+}
+
+/// Load the account list in pages with `visible` accounts first
+///
+/// Lets the list render before every account of a large wallet is in.
+pub fn stream_available_accounts(
+    profile: SecurityProfile,
+    visible: Vec<Address>,
+) -> impl Stream<Item = Result<AccountPage, String>> + Send {
+    async_stream::stream! {
+        match load_available_accounts(profile).await {
+            Ok(accounts) => {
+                let mut pages = account_pages(accounts, &visible, DEFAULT_PAGE_SIZE);
+                while let Some(page) = pages.next().await {
+                    yield Ok(page);
                 }
             }
+            Err(e) => yield Err(e),
         }
-    } else {
-        tracing::info!("No stored accounts found ({})", accounts_path.display());
+    }
+}
+
+/// Native balances of `addresses` for the account list, in the given order
+pub fn stream_account_balances(
+    wallet: Arc<tokio::sync::RwLock<crate::wallet::Vaughan>>,
+    addresses: Vec<Address>,
+    network_id: NetworkId,
+) -> impl Stream<Item = (Address, Result<String, String>)> + Send {
+    let fetch = move |address: Address| {
+        let wallet = wallet.clone();
+        async move {
+            let wallet_read = wallet.read().await;
+            let network_manager = wallet_read.network_manager();
+            let nm_read = network_manager.read().await;
+            nm_read.get_balance(address, None).await
+        }
+    };
+    balance_stream(addresses, BALANCE_CONCURRENCY, fetch).map(move |(address, balance)| {
+        let formatted = balance
+            .map(|wei| format_balance(wei, network_id))
+            .map_err(|e| e.to_string());
+        (address, formatted)
+    })
+}
+
+/// Decrypt the saved transaction drafts of `profile`
+pub async fn load_transaction_drafts(
+    profile: SecurityProfile,
+    master_password: SecretString,
+) -> Result<DraftBook, String> {
+    tokio::task::spawn_blocking(move || DraftStore::new(profile).load(&master_password))
+        .await
+        .map_err(|e| format!("Failed to load drafts: {e}"))?
+        .map_err(|e| format!("Failed to load drafts: {e}"))
+}
+
+/// Encrypt and save the transaction drafts of `profile`
+pub async fn save_transaction_drafts(
+    profile: SecurityProfile,
+    drafts: DraftBook,
+    master_password: SecretString,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || DraftStore::new(profile).save(&drafts, &master_password))
+        .await
+        .map_err(|e| format!("Failed to save drafts: {e}"))?
+        .map_err(|e| format!("Failed to save drafts: {e}"))
+}
+
+/// Signing requests waiting for the user's answer in the approval dialog
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalInbox(Arc<Mutex<HashMap<ApprovalId, PendingApproval>>>);
+
+impl ApprovalInbox {
+    fn insert(&self, pending: PendingApproval) {
+        let mut pending_approvals = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        pending_approvals.insert(pending.request().id, pending);
+    }
+
+    /// Answer request `id`, false when it was already answered or dropped
+    pub fn respond(&self, id: ApprovalId, response: ApprovalResponse) -> bool {
+        let pending = self.0.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+        pending.map(|pending| pending.respond(response)).is_some()
     }
 
-    tracing::info!("Loaded {} valid accounts", all_accounts.len());
-    Ok(all_accounts)
+    /// Reject everything still waiting, e.g. when the wallet locks
+    pub fn reject_all(&self, reason: &str) {
+        let pending: Vec<_> = self.0.lock().unwrap_or_else(|e| e.into_inner()).drain().collect();
+        for (_, pending) in pending {
+            pending.reject(Some(reason.to_string()));
+        }
+    }
+}
+
+/// Requests the wallet wants approved, each kept in `inbox` until answered
+///
+/// Ends once another frontend connects to the wallet's approval broker.
+pub fn stream_approval_requests(
+    mut receiver: ApprovalReceiver,
+    inbox: ApprovalInbox,
+) -> impl Stream<Item = ApprovalRequest> + Send {
+    async_stream::stream! {
+        while let Some(pending) = receiver.recv().await {
+            let request = pending.request().clone();
+            inbox.insert(pending);
+            yield request;
+        }
+    }
+}
+
+/// Balance changes found by the wallet's background poller
+///
+/// Changes missed while the GUI lagged behind are skipped; the next
+/// refresh reads the current balance anyway.
+pub fn stream_balance_changes(
+    mut receiver: tokio::sync::broadcast::Receiver<BalanceChanged>,
+) -> impl Stream<Item = BalanceChanged> + Send {
+    async_stream::stream! {
+        loop {
+            match receiver.recv().await {
+                Ok(change) => yield change,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!("⏩ Skipped {} balance changes", skipped);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::approval::{ApprovalBroker, ApprovalKind};
+
+    #[test]
+    fn test_gui_wallet_config_keeps_strict_lock() {
+        let config = gui_wallet_config();
+        assert_eq!(config.strict_lock, crate::wallet::WalletConfig::default().strict_lock);
+        assert_eq!(config.default_network, None);
+    }
+
+    #[tokio::test]
+    async fn test_approval_requests_wait_for_the_inbox_answer() {
+        let broker = ApprovalBroker::default();
+        let inbox = ApprovalInbox::default();
+        let mut requests = std::pin::pin!(stream_approval_requests(broker.connect(), inbox.clone()));
+
+        let signing = tokio::spawn({
+            let broker = broker.clone();
+            async move { broker.request(Address::ZERO, 1, ApprovalKind::message(b"gm")).await }
+        });
+        let request = requests.next().await.unwrap();
+        assert!(request.details().contains(&"Message: gm".to_string()));
+        assert!(inbox.respond(request.id, ApprovalResponse::Approve));
+        assert_eq!(signing.await.unwrap().unwrap(), request.id);
+        assert!(!inbox.respond(request.id, ApprovalResponse::Approve));
+
+        let signing = tokio::spawn({
+            let broker = broker.clone();
+            async move { broker.request(Address::ZERO, 1, ApprovalKind::message(b"gn")).await }
+        });
+        requests.next().await.unwrap();
+        inbox.reject_all("wallet locked");
+        assert!(signing.await.unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_balance_changes_survive_lag_and_end_with_the_poller() {
+        let (sender, receiver) = tokio::sync::broadcast::channel(1);
+        let change = |current: u64| BalanceChanged {
+            network: NetworkId(369),
+            account: Address::ZERO,
+            token: None,
+            previous: alloy::primitives::U256::ZERO,
+            current: alloy::primitives::U256::from(current),
+            block_number: current,
+        };
+        // The first change is overwritten before the GUI reads it
+        sender.send(change(1)).unwrap();
+        sender.send(change(2)).unwrap();
+        drop(sender);
+
+        let changes: Vec<BalanceChanged> = stream_balance_changes(receiver).collect().await;
+        assert_eq!(changes, vec![change(2)]);
+    }
 }
@@ -44,28 +44,35 @@
 //! a foundation for future updates as the Ethereum ecosystem evolves.
 //!
 //! Following CLAUDE.md guidelines: Under 10 lines for basic operations
-//! Uses Alloy directly without custom wrappers or complex session management
+//! Builds requests with Alloy directly; signing goes through the wallet's approval pipeline
 
+use crate::gui::input_validation;
+use crate::wallet::transaction::erc20::{self, TokenTransfer, TokenTransferError};
+use crate::wallet::provider::DappBackend;
+use crate::wallet::Vaughan;
 use alloy::primitives::{Address, U256};
-use alloy::providers::{Provider, ProviderBuilder};
+use alloy::providers::Provider;
 use alloy::rpc::types::TransactionRequest;
-use alloy::signers::local::PrivateKeySigner;
-use alloy::signers::Signer;
 use hex;
 use std::str::FromStr;
+use tokio::sync::RwLock;
 
-/// Send a transaction using Alloy
+/// Send a transfer from `from`, one of the wallet's accounts
 ///
 /// This function handles the entire flow:
-/// 1. Connects to provider
-/// 2. Creates signer from key
-/// 3. Builds transaction
-/// 4. Signs and broadcasts
+/// 1. Builds the transaction
+/// 2. Lets the wallet fill in nonce, fees and chain ID
+/// 3. Signs through [`Vaughan::sign_transaction_as`], which asks the
+///    approval dialog and applies the wallet's signing policies
+/// 4. Broadcasts it
+///
+/// [`Vaughan::sign_transaction_as`]: crate::wallet::Vaughan::sign_transaction_as
+#[allow(clippy::too_many_arguments)]
 pub async fn send_transaction(
+    wallet: &RwLock<Vaughan>,
+    from: Address,
     to_address: &str,
     amount_eth: &str,
-    private_key_hex: &str,
-    rpc_url: &str,
     chain_id: u64,
     gas_limit: Option<u64>,
     gas_price_gwei: Option<f64>,
@@ -75,27 +82,14 @@ pub async fn send_transaction(
     tracing::info!("🚀 Sending transaction: {} ETH to {}", amount_eth, to_address);
 
     // 1. Parse inputs
-    let to = Address::from_str(to_address).map_err(|e| format!("Invalid address: {e}"))?;
-    let amount = amount_eth.parse::<f64>().map_err(|_| "Invalid amount")?;
-
-    // INDUSTRY STANDARD: Safe U256 conversion (no u64 overflow)
-    // INSPIRED BY: MetaMask's BigNumber.js approach
-    let _value = if amount <= u64::MAX as f64 / 1e18 {
-        // Safe to use u64 for small amounts
-        U256::from((amount * 1e18) as u64)
-    } else {
-        // Use U256 math for large amounts to prevent overflow
-        crate::utils::parse_token_amount(&amount.to_string(), 18).map_err(|e| format!("Amount too large: {}", e))?
-    };
+    let to = input_validation::recipient_address(to_address).map_err(|e| e.to_string())?;
 
-    // 2. Setup Provider & Signer
-    let signer: PrivateKeySigner = private_key_hex.parse().map_err(|_| "Invalid private key")?;
-    let signer = signer.with_chain_id(Some(chain_id));
-    let wallet = alloy::network::EthereumWallet::from(signer);
-    let _from = wallet.default_signer().address(); // Get address from the wallet's signer
-
-    // 3. Build Transaction
-    let mut tx = TransactionRequest::default().from(wallet.default_signer().address()); // Get from address from wallet
+    // 2. Build Transaction, pinned to the chain the user sees
+    let mut tx = TransactionRequest {
+        from: Some(from),
+        chain_id: Some(chain_id),
+        ..Default::default()
+    };
 
     if let Some(contract_address) = token_contract {
         // ERC-20 token transfer
@@ -134,73 +128,23 @@ pub async fn send_transaction(
 
         tracing::info!("📊 Token contract {:#x} uses {} decimals", contract_address, decimals);
 
-        // Convert amount to raw token units using correct decimals
-        // Check for unreasonable amounts that could cause overflow
-        if amount < 0.0 || amount.is_nan() || amount.is_infinite() {
-            return Err("Invalid token amount".to_string());
-        }
-
-        // Cap at reasonable maximum to prevent overflow
-        let capped_amount = amount.min(1_000_000_000.0); // Max 1B tokens
-
-        // Convert to token's smallest unit using precise string-based conversion
-        let amount_str = capped_amount.to_string();
-        let token_amount = match crate::utils::parse_token_amount(&amount_str, decimals) {
-            Ok(amount) => amount,
-            Err(_) => {
-                return Err("Failed to parse token amount with proper precision".to_string());
-            }
-        };
-
-        // Sanity check the result
-        if token_amount.is_zero() {
-            return Err("Token amount too small (rounds to zero)".to_string());
-        }
-
-        tracing::info!(
-            "💰 Converting {} tokens (capped to {}) to raw amount: {}",
-            amount,
-            capped_amount,
-            token_amount
-        );
-
-        // Build calldata for ERC-20 transfer(address,uint256)
-        // Function selector: 0xa9059cbb
-        let mut calldata = Vec::with_capacity(68);
-        calldata.extend_from_slice(&[0xa9, 0x05, 0x9c, 0xbb]); // transfer selector
-
-        // ABI encode recipient address (32 bytes, left-padded)
-        let mut address_bytes = [0u8; 32];
-        address_bytes[12..].copy_from_slice(to.as_slice());
-        calldata.extend_from_slice(&address_bytes);
-
-        // ABI encode amount (32 bytes, big-endian)
-        let amount_bytes = token_amount.to_be_bytes_vec();
-        let mut amount_bytes_padded = [0u8; 32];
-        let start_pos = 32 - amount_bytes.len().min(32);
-        amount_bytes_padded[start_pos..].copy_from_slice(&amount_bytes);
-        calldata.extend_from_slice(&amount_bytes_padded);
+        // Convert the amount exactly as typed; out-of-range input is an error
+        let transfer = token_transfer(contract_address, decimals, to, amount_eth)?;
+        tracing::info!("💰 Converting {} tokens to raw amount: {}", amount_eth, transfer.amount);
 
         // ERC-20 token transaction
-        tx = tx.to(contract_address).value(U256::ZERO).input(calldata.into());
+        tx = tx.to(contract_address).value(U256::ZERO).input(transfer.calldata().into());
 
         tracing::info!(
             "✅ ERC-20 transaction built: contract={:#x}, amount={} tokens",
             contract_address,
-            token_amount
+            transfer.amount
         );
     } else {
         // Native currency transfer
         tracing::info!("💰 Sending native currency: {} tPLS to {}", amount_eth, to_address);
 
-        // Use the same safe conversion pattern as token amounts
-        let value = if amount <= u64::MAX as f64 / 1e18 {
-            // Safe to use u64 for small amounts (< 18.4 native)
-            U256::from((amount * 1e18) as u64)
-        } else {
-            // Use U256 math for large amounts to prevent overflow
-            crate::utils::parse_token_amount(&amount.to_string(), 18).map_err(|e| format!("Amount too large: {}", e))?
-        };
+        let value = native_value(amount_eth)?;
 
         tracing::info!("💰 Native amount conversion: {} → {} raw units", amount_eth, value);
 
@@ -219,22 +163,37 @@ pub async fn send_transaction(
         tx = tx.gas_price(price_wei);
     }
 
-    // 4. Send & Broadcast using Alloy wallet provider
-    let wallet_provider = ProviderBuilder::new()
-        .wallet(wallet)
-        .connect_http(rpc_url.parse().map_err(|_| "Invalid RPC URL")?);
-
-    let pending = wallet_provider
-        .send_transaction(tx)
+    // 3. Sign with the wallet & broadcast
+    let prepared = wallet
+        .prepare_transaction(tx)
         .await
-        .map_err(|e| format!("Broadcast failed: {e}"))?;
-
-    let tx_hash = pending.tx_hash();
+        .map_err(|e| format!("Failed to prepare transaction: {e}"))?;
+    let tx_hash = wallet
+        .send_transaction(prepared)
+        .await
+        .map_err(|e| format!("Transaction failed: {e}"))?;
     tracing::info!("✅ Transaction sent: 0x{:x}", tx_hash);
 
     Ok(format!("0x{tx_hash:x}"))
 }
 
+/// ERC-20 transfer of `amount` exactly as the user typed it
+///
+/// Amounts the token can't represent (too many decimal places, overflow)
+/// are rejected instead of being rounded or capped.
+fn token_transfer(contract: Address, decimals: u8, to: Address, amount: &str) -> Result<TokenTransfer, String> {
+    let raw = erc20::parse_amount(amount, decimals, "This token").map_err(|e| e.to_string())?;
+    TokenTransfer::from_raw(contract, to, raw).map_err(|e| e.to_string())
+}
+
+/// Native value in wei for `amount` exactly as typed; zero is allowed
+fn native_value(amount: &str) -> Result<U256, String> {
+    match erc20::parse_amount(amount, 18, "The native currency") {
+        Err(TokenTransferError::ZeroAmount) => Ok(U256::ZERO),
+        result => result.map_err(|e| e.to_string()),
+    }
+}
+
 /// Fetch token decimals from block explorer APIs
 async fn fetch_token_decimals(_contract_address: Address, _rpc_url: &str) -> Result<u8, String> {
     // For now, return 18 as fallback to prevent crashes
@@ -242,6 +201,40 @@ async fn fetch_token_decimals(_contract_address: Address, _rpc_url: &str) -> Res
     tracing::info!("⚠️  Using 18 decimals fallback (dynamic fetching not yet implemented)");
     Ok(18)
 }
+/// Decoded call shown in the confirmation dialog, `None` for native transfers
+pub async fn call_preview(
+    to_address: &str,
+    amount: &str,
+    rpc_url: &str,
+    token_contract: Option<Address>,
+) -> Option<String> {
+    let contract = token_contract?;
+    let to = input_validation::recipient_address(to_address).ok()?;
+    let decimals = fetch_token_decimals(contract, rpc_url).await.unwrap_or(18);
+    let transfer = token_transfer(contract, decimals, to, amount).ok()?;
+    let tx = TransactionRequest::default().to(contract).input(transfer.calldata().into());
+    crate::abi::describe_transaction(&tx)
+}
+
+/// Warnings about what the recipient is, e.g. a token contract rather than a wallet
+///
+/// Probing failures are logged and yield no warnings; they never block a send.
+pub async fn recipient_notes(to_address: &str, rpc_url: &str, token_contract: Option<Address>) -> Vec<String> {
+    let (Ok(to), Ok(provider)) = (
+        input_validation::recipient_address(to_address),
+        crate::network::connect_rpc(rpc_url, "recipient check"),
+    ) else {
+        return Vec::new();
+    };
+    match crate::blockchain::ContractProber::new(provider).probe(to).await {
+        Ok(profile) => profile.recipient_warnings(token_contract),
+        Err(e) => {
+            tracing::debug!("Could not probe recipient {}: {}", to, e);
+            Vec::new()
+        }
+    }
+}
+
 /// Industry Standard Gas Estimation - INSPIRED BY METAMASK
 ///
 /// IMPLEMENTATION INSPIRATION:
@@ -285,17 +278,11 @@ pub async fn estimate_gas(
     );
 
     // Parse addresses with validation
-    let to = Address::from_str(to_address).map_err(|e| format!("Invalid recipient address: {e}"))?;
-    let from = Address::from_str(from_address).map_err(|e| format!("Invalid sender address: {e}"))?;
-    let amount = amount_eth.parse::<f64>().map_err(|_| "Invalid amount format")?;
-
-    // Validate amount range
-    if amount < 0.0 || amount.is_nan() || amount.is_infinite() {
-        return Err("Amount must be a positive number".to_string());
-    }
+    let to = input_validation::recipient_address(to_address).map_err(|e| format!("Invalid recipient: {e}"))?;
+    let from = input_validation::address(from_address).map_err(|e| format!("Invalid sender address: {e}"))?;
 
     // Industry Standard: Setup provider with gas price
-    let provider = ProviderBuilder::new().connect_http(rpc_url.parse().map_err(|_| "Invalid RPC URL")?);
+    let provider = crate::network::connect_rpc(rpc_url, "gas estimation").map_err(|e| e.to_string())?;
 
     // Get current gas price (industry standard)
     let gas_price = match provider.get_gas_price().await {
@@ -329,39 +316,13 @@ pub async fn estimate_gas(
             }
         };
 
-        // INDUSTRY STANDARD: Convert amount to token units with precision
-        let amount_str = amount.to_string();
-        let token_amount = match crate::utils::parse_token_amount(&amount_str, token_decimals) {
-            Ok(amount) => amount,
-            Err(e) => {
-                return Err(format!("Failed to convert amount to token units: {}", e));
-            }
-        };
-
-        tracing::info!("💰 Amount conversion: {} → {} raw units", amount, token_amount);
-
-        // META MASK PATTERN: ABI encode ERC-20 transfer(address,uint256)
-        // REFERENCE: MetaMask's encodeFunctionData approach
-        // SOURCE: https://github.com/MetaMask/metamask-extension/blob/v10.29.0/app/scripts/controllers/token.js
-        let mut calldata = Vec::with_capacity(68);
+        // INDUSTRY STANDARD: Convert amount to token units exactly as typed
+        let transfer = token_transfer(contract_address, token_decimals, to, amount_eth)?;
 
-        // Standard function selector: transfer(address,uint256) = 0xa9059cbb
-        // UNIVERSAL: Same across all ERC-20 implementations
-        calldata.extend_from_slice(&[0xa9, 0x05, 0x9c, 0xbb]);
+        tracing::info!("💰 Amount conversion: {} → {} raw units", amount_eth, transfer.amount);
 
-        // ABI encode recipient address (32 bytes, left-padded)
-        // STANDARD: Ethereum ABI encoding specification
-        let mut address_bytes = [0u8; 32];
-        address_bytes[12..].copy_from_slice(to.as_slice());
-        calldata.extend_from_slice(&address_bytes);
-
-        // ABI encode amount (32 bytes, big-endian)
-        // STANDARD: Unsigned integer encoding
-        let amount_bytes = token_amount.to_be_bytes_vec();
-        let mut amount_bytes_padded = [0u8; 32];
-        let start_pos = 32 - amount_bytes.len().min(32);
-        amount_bytes_padded[start_pos..].copy_from_slice(&amount_bytes);
-        calldata.extend_from_slice(&amount_bytes_padded);
+        // ABI encode ERC-20 transfer(address,uint256)
+        let calldata = transfer.calldata().to_vec();
 
         tracing::info!("📋 Calldata: 0x{}", hex::encode(&calldata));
 
@@ -373,20 +334,11 @@ pub async fn estimate_gas(
         // INDUSTRY STANDARD: Native ETH Transfer Gas Estimation
         tracing::info!("💰 Estimating gas for native ETH transfer: {} → {}", from, to);
 
-        // INDUSTRY STANDARD: Safe ETH to wei conversion (no u64 overflow)
+        // INDUSTRY STANDARD: Exact ETH to wei conversion
         // INSPIRED BY: MetaMask/ethers.js parseEther() approach
-        let eth_wei = if amount <= u64::MAX as f64 / 1e18 {
-            // Safe to use u64 for small amounts (< 18.4 ETH)
-            U256::from((amount * 1e18) as u64)
-        } else {
-            // Use U256 math for large amounts to prevent overflow
-            crate::utils::parse_token_amount(&amount.to_string(), 18).unwrap_or_else(|_| {
-                tracing::warn!("⚠️  Large amount conversion fallback for {}", amount);
-                U256::from((amount * 1e18).trunc() as u128)
-            })
-        };
+        let eth_wei = native_value(amount_eth)?;
 
-        tracing::info!("💰 ETH amount conversion: {} → {} wei", amount, eth_wei);
+        tracing::info!("💰 ETH amount conversion: {} → {} wei", amount_eth, eth_wei);
 
         // INDUSTRY STANDARD: Native transaction structure
         (to, eth_wei, None)
@@ -441,7 +393,7 @@ pub async fn estimate_gas(
                 tracing::error!("   3. RPC provider unable to read balance");
                 tracing::error!("   From address: {:#x}", from);
                 tracing::error!("   To address: {:#x}", to);
-                tracing::error!("   Amount: {}", amount);
+                tracing::error!("   Amount: {}", amount_eth);
                 if let Some(contract) = token_contract {
                     tracing::error!("   Token contract: {:#x}", contract);
                 }
@@ -3,14 +3,13 @@
 //! Unified module for all security-related state including:
 //! - Password dialog state
 //! - Session management
-//! - Key cache handling
+//! - Signing session handle
 
 use secrecy::SecretString;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
 
-use crate::security::key_cache::KeyCache;
+use crate::security::SessionManager;
 
 /// Unified authentication state consolidating security and session management
 #[derive(Debug, Clone, Default)]
@@ -24,8 +23,8 @@ pub struct AuthState {
     /// Enhanced session management with wallet/account separation
     pub enhanced_session: EnhancedSessionState,
 
-    /// Key cache handle (actual cache stored separately for security)
-    pub key_cache_handle: Option<Arc<RwLock<KeyCache>>>,
+    /// Wallet signing session (master password and cached keys live there)
+    pub signing_session: Option<Arc<SessionManager>>,
 
     /// Password validator service (persists rate limiting state)
     pub password_validator: Option<crate::security::PasswordValidator>,
@@ -53,10 +52,10 @@ impl AuthState {
         // Ensure dialog is hidden and inputs zeroized
         self.password_dialog.hide();
 
-        // Clear sensitive data
-        if let Some(_key_cache) = &self.key_cache_handle {
-            // We can't easily clear the RwLock<KeyCache> here without async or blocking,
-            // but we've dropped the session keys.
+        // Zeroize the master password and signing keys held by the wallet session
+        if let (Some(session), Ok(runtime)) = (&self.signing_session, tokio::runtime::Handle::try_current()) {
+            let session = Arc::clone(session);
+            runtime.spawn(async move { session.lock().await });
         }
     }
 
@@ -132,6 +131,9 @@ pub enum PasswordDialogConfig {
     /// Wallet-level authentication
     WalletUnlock,
 
+    /// Password of the encrypted file keychain, asked for at startup
+    KeychainVault,
+
     /// Account-level authentication
     AccountUnlock { account_id: String, account_name: String },
 
@@ -632,6 +634,17 @@ impl std::fmt::Display for WalletPasswordError {
     }
 }
 
+impl From<crate::security::UnlockWait> for WalletPasswordError {
+    fn from(wait: crate::security::UnlockWait) -> Self {
+        // Round up so the user never retries a second too early
+        let retry_after_seconds = wait.remaining().as_secs() + u64::from(wait.remaining().subsec_nanos() > 0);
+        match wait {
+            crate::security::UnlockWait::Backoff(_) => WalletPasswordError::TooManyAttempts { retry_after_seconds },
+            crate::security::UnlockWait::Locked(_) => WalletPasswordError::AccountLocked { retry_after_seconds },
+        }
+    }
+}
+
 impl From<WalletPasswordError> for PasswordError {
     fn from(error: WalletPasswordError) -> Self {
         match error {
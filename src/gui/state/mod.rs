@@ -127,6 +127,10 @@ pub struct AppState {
     pub export_loading: bool,
     pub pending_export_type: ExportType,
     pub export_error_message: Option<String>,
+    /// Seed phrase backup check: entry shown, re-entered phrase and last outcome
+    pub backup_check_active: bool,
+    pub backup_check_phrase: String,
+    pub backup_check_result: Option<Result<bool, String>>,
 
     // Custom token fields
     pub custom_token_address_input: String,
@@ -181,6 +185,9 @@ impl Default for AppState {
             export_loading: false,
             pending_export_type: ExportType::None,
             export_error_message: None,
+            backup_check_active: false,
+            backup_check_phrase: String::new(),
+            backup_check_result: None,
 
             // Custom token fields
             custom_token_address_input: String::new(),
@@ -818,6 +825,11 @@ impl AppState {
         self.network.fetching_price
     }
 
+    /// Time since the price was last fetched successfully
+    pub fn eth_price_age(&self) -> Option<std::time::Duration> {
+        self.network.price_last_updated.map(|updated| updated.elapsed())
+    }
+
     // Token state accessor methods
     pub fn balance_selected_ticker(&self) -> &String {
         &self.balance_selected_ticker
@@ -44,7 +44,7 @@ pub struct NetworkState {
 impl Default for NetworkState {
     fn default() -> Self {
         Self {
-            current_network: NetworkId(943), // Default to PulseChain Testnet v4
+            current_network: crate::network::startup::fallback_network(), // Replaced by the resolved startup network once the wallet loads
             available_networks: Vec::new(),
             loading_networks: true,
             balance: "0.000000 tPLS".to_string(),
@@ -3,7 +3,10 @@
 use crate::gui::wallet_types::{GasEstimation, GasSpeed};
 use crate::gui::{HistoryTab, Transaction};
 use crate::network::NetworkId;
+use crate::wallet::transaction::drafts::{DraftBook, TransactionDraft};
+use crate::wallet::ApprovalRequest;
 use alloy::primitives::{Address, U256};
+use std::collections::VecDeque;
 use std::time::Instant;
 
 /// Transaction type for gas management (Legacy or EIP-1559)
@@ -81,10 +84,21 @@ pub struct TransactionState {
     pub estimating_gas: bool,
     pub gas_estimation: Option<GasEstimation>,
     pub show_transaction_confirmation: bool,
+    /// User accepted the screening findings of the current estimation
+    pub risk_acknowledgement: Option<crate::security::screening::RiskAcknowledgement>,
+    /// Signing requests from the wallet, the first one shown in the approval dialog
+    pub approval_requests: VecDeque<ApprovalRequest>,
 
     // Send from account selection
     pub send_from_account_id: Option<String>, // ID of the account to send from
 
+    // Drafts
+    pub send_notes: String,
+    pub drafts: DraftBook,
+    pub show_drafts: bool,
+    /// Draft the send form was resumed from, replaced when saved again
+    pub current_draft_id: Option<String>,
+
     // Pending transaction tracking for cancellation
     pub pending_transactions: Vec<PendingTransaction>,
     pub last_used_nonce: Option<u64>,
@@ -119,7 +133,13 @@ impl Default for TransactionState {
             estimating_gas: false,
             gas_estimation: None,
             show_transaction_confirmation: false,
+            risk_acknowledgement: None,
+            approval_requests: VecDeque::new(),
             send_from_account_id: None,
+            send_notes: String::new(),
+            drafts: DraftBook::new(),
+            show_drafts: false,
+            current_draft_id: None,
             pending_transactions: Vec::new(),
             last_used_nonce: None,
             cancellation_in_progress: false,
@@ -127,3 +147,38 @@ impl Default for TransactionState {
         }
     }
 }
+
+impl TransactionState {
+    /// Draft of the send form as currently filled in
+    pub fn draft_from_form(&self, network: NetworkId) -> TransactionDraft {
+        let mut draft = TransactionDraft::new(network.0);
+        if let Some(id) = &self.current_draft_id {
+            draft.id = id.clone();
+        }
+        draft.account_id = self.send_from_account_id.clone();
+        draft.recipient = self.send_to_address.clone();
+        draft.amount = self.send_amount.clone();
+        draft.token = self.send_selected_token.clone();
+        draft.custom_token_address = self.send_custom_token_address.clone();
+        draft.notes = self.send_notes.clone();
+        draft
+    }
+
+    /// Fill the send form from `draft`
+    pub fn resume_draft(&mut self, draft: &TransactionDraft) {
+        self.current_draft_id = Some(draft.id.clone());
+        if draft.account_id.is_some() {
+            self.send_from_account_id = draft.account_id.clone();
+        }
+        self.send_to_address = draft.recipient.clone();
+        self.send_amount = draft.amount.clone();
+        if !draft.token.is_empty() {
+            self.send_selected_token = draft.token.clone();
+        }
+        self.send_custom_token_address = draft.custom_token_address.clone();
+        self.send_show_custom_token_input = !draft.custom_token_address.is_empty();
+        self.send_notes = draft.notes.clone();
+        self.gas_estimation = None;
+        self.show_drafts = false;
+    }
+}
@@ -1,6 +1,8 @@
 //! UI state management including dialogs, spinners, and status messages
 
 use crate::gui::StatusMessageColor;
+use crate::network::egress::{CategoryMode, EgressCategory, EgressEvent};
+use std::collections::BTreeMap;
 use std::time::Instant;
 
 /// UI-related state including dialogs, spinners, feedback, and themes
@@ -18,6 +20,8 @@ pub struct UiState {
     // Polling configuration
     pub poll_interval: u64,
     pub polling_active: bool,
+    /// Balances come from the wallet's background poller rather than smart polling
+    pub balance_events_active: bool,
 
     // General dialogs
     pub show_settings_dialog: bool,
@@ -28,6 +32,11 @@ pub struct UiState {
     pub show_cancel_confirmation: bool,
     pub pending_cancel_tx: Option<crate::gui::state::transaction_state::PendingTransaction>,
     pub show_reset_wallet_confirmation: bool,
+    /// Network choices on the first-run consent screen, while it is open
+    pub egress_consent: Option<BTreeMap<EgressCategory, CategoryMode>>,
+    /// Host waiting for the user to allow or block it
+    pub pending_egress_host: Option<EgressEvent>,
+    pub show_egress_log: bool,
 
     // Spinners for loading states
     pub balance_spinner: bool,
@@ -61,6 +70,7 @@ impl Default for UiState {
             show_retry_options: false,
             poll_interval: 10,
             polling_active: false,
+            balance_events_active: false,
             show_settings_dialog: false,
             show_dapps_dialog: false,
             show_dapps_coming_soon: false,
@@ -69,6 +79,9 @@ impl Default for UiState {
             show_cancel_confirmation: false,
             show_reset_wallet_confirmation: false,
             pending_cancel_tx: None,
+            egress_consent: None,
+            pending_egress_host: None,
+            show_egress_log: false,
             balance_spinner: false,
             accounts_spinner: false,
             transaction_spinner: false,
@@ -2,9 +2,11 @@
 
 use crate::gui::wallet_types::ImportType;
 use crate::security::SecureAccount;
+use crate::security::SecurityProfile;
 use crate::security::SeedStrength;
+use alloy::primitives::Address;
 use secrecy::SecretString;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Wallet account management and creation/import state
 #[derive(Debug, Clone)]
@@ -14,7 +16,11 @@ pub struct WalletState {
     pub current_account_id: Option<String>,
     pub available_accounts: Vec<SecureAccount>,
     pub loading_accounts: bool,
+    /// Security profile the unlock password opened
+    pub security_profile: SecurityProfile,
     pub account_balance: String,
+    /// Balances shown in the account list, filled in as they load
+    pub account_balances: HashMap<Address, String>,
 
     // Account operations
     pub show_delete_account: bool,
@@ -38,6 +44,8 @@ pub struct WalletState {
     // Wallet import state
     pub show_import_wallet: bool,
     pub private_key: SecretString,
+    /// Optional BIP-39 passphrase for seed phrase creation and import
+    pub bip39_passphrase: SecretString,
     pub import_type: ImportType,
 
     // Export wallet state
@@ -83,7 +91,9 @@ impl Default for WalletState {
             current_account_id: None,
             available_accounts: Vec::new(),
             loading_accounts: true,
+            security_profile: SecurityProfile::Primary,
             account_balance: "0.000000 tPLS".to_string(),
+            account_balances: HashMap::new(),
             show_delete_account: false,
             deleting_account: false,
             address_just_copied: false,
@@ -98,6 +108,7 @@ impl Default for WalletState {
             confirm_password: String::new(),
             show_import_wallet: false,
             private_key: SecretString::new(String::new()),
+            bip39_passphrase: SecretString::new(String::new()),
             import_type: ImportType::PrivateKey,
             show_export_wallet: false,
             exported_seed_phrase: String::new(),
@@ -7,7 +7,7 @@ use crate::gui::state::transaction_state::{PendingTransaction, TransactionType};
 use crate::network::NetworkId;
 use crate::wallet::Vaughan;
 use alloy::primitives::{TxHash, U256};
-use alloy::providers::{Provider, ProviderBuilder};
+use alloy::providers::Provider;
 use alloy::rpc::types::TransactionRequest;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -79,11 +79,8 @@ impl TransactionCancellationService {
         wallet: &Arc<RwLock<Vaughan>>,
     ) -> Result<TxHash, CancellationError> {
         // 1. Connect to the provider
-        let provider = ProviderBuilder::new().connect_http(
-            self.provider_url
-                .parse()
-                .map_err(|e| CancellationError::NetworkError(format!("Invalid RPC URL: {e}")))?,
-        );
+        let provider = crate::network::connect_rpc(&self.provider_url, "transaction cancellation")
+            .map_err(|e| CancellationError::NetworkError(e.to_string()))?;
 
         // 2. Check if transaction is still pending
         if !self.is_cancellable_internal(&provider, &original_tx.tx_hash).await? {
@@ -136,14 +133,12 @@ impl TransactionCancellationService {
             gas_settings
         );
 
-        // 7. Sign the replacement transaction with the wallet
-        let signed_bytes = {
-            let wallet_guard = wallet.read().await;
-            wallet_guard.sign_transaction(&cancel_tx).await.map_err(|e| {
-                tracing::error!("❌ Failed to sign cancellation transaction: {}", e);
-                CancellationError::WalletError(format!("Failed to sign transaction: {e}"))
-            })?
-        };
+        // 7. Sign the replacement transaction with the wallet, which asks the
+        // approval dialog before signing
+        let signed_bytes = wallet.read().await.sign_transaction(&cancel_tx).await.map_err(|e| {
+            tracing::error!("❌ Failed to sign cancellation transaction: {}", e);
+            CancellationError::WalletError(format!("Failed to sign transaction: {e}"))
+        })?;
 
         // 8. Submit the signed transaction to the network
         let pending_tx = provider.send_raw_transaction(&signed_bytes).await.map_err(|e| {
@@ -165,11 +160,8 @@ impl TransactionCancellationService {
 
     /// Check if a transaction can still be cancelled
     pub async fn is_cancellable(&self, tx_hash: &str) -> Result<bool, CancellationError> {
-        let provider = ProviderBuilder::new().connect_http(
-            self.provider_url
-                .parse()
-                .map_err(|e| CancellationError::NetworkError(format!("Invalid RPC URL: {e}")))?,
-        );
+        let provider = crate::network::connect_rpc(&self.provider_url, "transaction cancellation")
+            .map_err(|e| CancellationError::NetworkError(e.to_string()))?;
 
         self.is_cancellable_internal(&provider, tx_hash).await
     }
@@ -180,11 +172,8 @@ impl TransactionCancellationService {
         original_tx: &PendingTransaction,
         fee_multiplier: f64,
     ) -> Result<GasSettings, CancellationError> {
-        let provider = ProviderBuilder::new().connect_http(
-            self.provider_url
-                .parse()
-                .map_err(|e| CancellationError::NetworkError(format!("Invalid RPC URL: {e}")))?,
-        );
+        let provider = crate::network::connect_rpc(&self.provider_url, "transaction cancellation")
+            .map_err(|e| CancellationError::NetworkError(e.to_string()))?;
 
         self.suggest_cancellation_gas_internal(&provider, original_tx, fee_multiplier)
             .await
@@ -363,10 +352,6 @@ pub async fn execute_cancellation_with_progress(
     send_progress(crate::gui::state::transaction_state::CancellationProgress::ValidatingTransaction);
     tokio::time::sleep(std::time::Duration::from_millis(500)).await; // Brief pause for UI feedback
 
-    // Connect to provider
-    let _provider = alloy::providers::ProviderBuilder::new()
-        .connect_http(provider_url.parse().map_err(|e| format!("Invalid RPC URL: {e}"))?);
-
     // Check if transaction is still cancellable
     if !service
         .is_cancellable(&tx_to_cancel.tx_hash)
@@ -9,6 +9,7 @@ use iced::{
 };
 
 use crate::gui::{
+    safe_calculations::display_amount,
     theme::{styles, text},
     wallet_types::HistoryTab,
     working_wallet::AppState,
@@ -195,7 +196,7 @@ impl AppState {
                                             )
                                             .push(Space::with_width(Length::Fixed(5.0)))
                                             .push(
-                                                Text::new(&tx.amount)
+                                                Text::new(display_amount(&tx.amount))
                                                     .size(14)
                                                     .style(iced::Color::from_rgb(0.9, 0.9, 0.1)),
                                             )
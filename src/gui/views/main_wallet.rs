@@ -13,6 +13,8 @@ use crate::gui::{
     Message, StatusMessageColor,
     services::AssetServiceTrait,
 };
+use crate::network::NetworkDisplay;
+use crate::tokens::degraded::{describe_age, STALE_PRICE_AGE};
 
 impl AppState {
     /// Main wallet interface view
@@ -136,28 +138,98 @@ impl AppState {
 
     /// Send form view component
     fn send_form_view(&self) -> Element<'_, Message> {
-        Container::new(
-            Column::new()
-                .push(self.account_balance_row())
-                .push(Space::with_height(Length::Fixed(safe_dimension(12.0))))
-                .push(self.address_input_row())
-                .push(Space::with_height(Length::Fixed(safe_dimension(30.0))))
-                .push(self.token_amount_row())
-                .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
-                .push(self.gas_settings_row())
-                .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
-                .push(self.tx_type_nonce_row())
-                .push(self.max_priority_fee_section())
-                .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
-                .push(self.gas_speed_buttons())
+        let mut form = Column::new()
+            .push(self.account_balance_row())
+            .push(Space::with_height(Length::Fixed(safe_dimension(12.0))))
+            .push(self.address_input_row())
+            .push(Space::with_height(Length::Fixed(safe_dimension(30.0))))
+            .push(self.token_amount_row())
+            .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
+            .push(self.notes_input_row())
+            .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
+            .push(self.gas_settings_row())
+            .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
+            .push(self.tx_type_nonce_row())
+            .push(self.max_priority_fee_section())
+            .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
+            .push(self.gas_speed_buttons())
+            .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
+            .push(self.send_button())
+            .spacing(5);
+        if self.transaction().show_drafts {
+            form = form
                 .push(Space::with_height(Length::Fixed(safe_dimension(10.0))))
-                .push(self.send_button())
-                .spacing(5),
-        )
-        .padding(safe_dimension(20.0))
-        .style(styles::dark_flat_container())
-        .width(Length::Fill)
-        .into()
+                .push(self.drafts_panel());
+        }
+
+        Container::new(form)
+            .padding(safe_dimension(20.0))
+            .style(styles::dark_flat_container())
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// Notes kept with a draft
+    fn notes_input_row(&self) -> Element<'_, Message> {
+        Row::new()
+            .push(
+                Text::new("Notes :")
+                    .size(13)
+                    .width(Length::Fixed(safe_dimension(85.0)))
+                    .vertical_alignment(iced::alignment::Vertical::Center),
+            )
+            .push(Space::with_width(Length::Fixed(safe_dimension(8.0))))
+            .push(
+                TextInput::new("Optional, saved with drafts", &self.transaction().send_notes)
+                    .on_input(Message::SendNotesChanged)
+                    .padding(10)
+                    .width(Length::Fill)
+                    .style(styles::black_grey_text_input()),
+            )
+            .align_items(iced::Alignment::Center)
+            .into()
+    }
+
+    /// Saved drafts with resume and delete buttons
+    fn drafts_panel(&self) -> Element<'_, Message> {
+        let drafts = self.transaction().drafts.drafts();
+        let mut list = Column::new().push(Text::new("Drafts").size(14)).spacing(6);
+        if drafts.is_empty() {
+            list = list.push(Text::new("No saved drafts").size(12));
+        }
+        for draft in drafts {
+            let mut label = Column::new().push(Text::new(draft.summary()).size(12));
+            if !draft.notes.is_empty() {
+                label = label.push(
+                    Text::new(draft.notes.as_str())
+                        .size(11)
+                        .style(iced::Color::from_rgb(0.6, 0.6, 0.6)),
+                );
+            }
+            list = list.push(
+                Row::new()
+                    .push(label.width(Length::Fill))
+                    .push(
+                        Button::new(Text::new("Resume").size(11))
+                            .on_press(Message::ResumeDraft(draft.id.clone()))
+                            .padding([4, 8])
+                            .style(styles::dark_slate_grey_button()),
+                    )
+                    .push(Space::with_width(Length::Fixed(safe_dimension(6.0))))
+                    .push(
+                        Button::new(Text::new("Delete").size(11))
+                            .on_press(Message::DeleteDraft(draft.id.clone()))
+                            .padding([4, 8])
+                            .style(styles::dark_slate_grey_button()),
+                    )
+                    .align_items(iced::Alignment::Center),
+            );
+        }
+        Container::new(list)
+            .padding(safe_dimension(10.0))
+            .style(styles::dark_flat_container())
+            .width(Length::Fill)
+            .into()
     }
 
     /// Account and balance row - unified layout
@@ -180,7 +252,7 @@ impl AppState {
             .iter()
             .find(|token| token.symbol == selected_symbol)
             .map(|token| safe_balance(&token.balance))
-            .unwrap_or_else(|| "0.0000".to_string());
+            .unwrap_or_else(|| safe_balance(""));
 
         Column::new()
             .push(Space::with_height(Length::Fixed(safe_dimension(5.0))))
@@ -229,6 +301,19 @@ impl AppState {
                         .padding([6, 10])
                         .style(styles::dark_flat_container())
                     )
+                    .push(Space::with_width(Length::Fixed(safe_dimension(4.0))))
+                    .push(
+                        Button::new(Text::new(if crate::gui::privacy::is_enabled() { "🙈" } else { "👁" }).size(16))
+                            .on_press(Message::TogglePrivacyMode)
+                            .padding([4, 6])
+                            .style(styles::transparent_button()),
+                    )
+                    .push(
+                        Button::new(Text::new("🌐").size(16))
+                            .on_press(Message::ShowEgressLog)
+                            .padding([4, 6])
+                            .style(styles::transparent_button()),
+                    )
                     .push(Space::with_width(Length::Fixed(safe_dimension(8.0))))
 
                     // Token selector and add button
@@ -428,11 +513,10 @@ impl AppState {
             .push(
                 Column::new()
                     .push(
-                        Text::new(if *self.send_tx_type() == "EIP-1559" {
-                            "Max Fee (Gwei)"
-                        } else {
-                            "Gas Price (Gwei)"
-                        })
+                        Text::new(
+                            NetworkDisplay::for_chain(self.current_network().chain_id())
+                                .fee_field_label(*self.send_tx_type() == "EIP-1559"),
+                        )
                         .size(12),
                     )
                     .push(Space::with_height(Length::Fixed(safe_dimension(5.0))))
@@ -562,31 +646,56 @@ impl AppState {
             .into()
     }
 
-    /// Send button
+    /// Send button with draft controls
     fn send_button(&self) -> Element<'_, Message> {
-        Button::new(
-            Text::new(if self.sending_transaction() {
-                "Sending..."
-            } else {
-                "Send"
-            })
-            .size(14),
-        )
-        .on_press_maybe(
-            if !self.send_to_address().is_empty()
-                && !self.send_amount().is_empty()
-                && self.send_from_account_id().is_some()
-                && !self.sending_transaction()
-            {
-                Some(Message::SubmitTransaction)
-            } else {
-                None
-            },
-        )
-        .padding([10, 16])
-        .style(styles::lighter_slate_grey_button())
-        .width(Length::Fill)
-        .into()
+        let drafts = self.transaction().drafts.len();
+        Row::new()
+            .push(
+                Button::new(
+                    Text::new(if self.sending_transaction() {
+                        "Sending..."
+                    } else {
+                        "Send"
+                    })
+                    .size(14),
+                )
+                .on_press_maybe(
+                    if !self.send_to_address().is_empty()
+                        && !self.send_amount().is_empty()
+                        && self.send_from_account_id().is_some()
+                        && !self.sending_transaction()
+                    {
+                        Some(Message::SubmitTransaction)
+                    } else {
+                        None
+                    },
+                )
+                .padding([10, 16])
+                .style(styles::lighter_slate_grey_button())
+                .width(Length::FillPortion(3)),
+            )
+            .push(Space::with_width(Length::Fixed(safe_dimension(8.0))))
+            .push(
+                Button::new(Text::new("Save Draft").size(12))
+                    .on_press_maybe(
+                        (!self.send_to_address().is_empty()
+                            || !self.send_amount().is_empty()
+                            || !self.transaction().send_notes.is_empty())
+                        .then_some(Message::SaveDraft),
+                    )
+                    .padding([10, 10])
+                    .style(styles::dark_slate_grey_button())
+                    .width(Length::FillPortion(1)),
+            )
+            .push(Space::with_width(Length::Fixed(safe_dimension(8.0))))
+            .push(
+                Button::new(Text::new(format!("Drafts ({drafts})")).size(12))
+                    .on_press(Message::ToggleDrafts)
+                    .padding([10, 10])
+                    .style(styles::dark_slate_grey_button())
+                    .width(Length::FillPortion(1)),
+            )
+            .into()
     }
 
     /// Action buttons view (refresh, receive, history, etc.)
@@ -708,25 +817,26 @@ impl AppState {
             .spacing(5);
 
         // ETH Price Section
-        let eth_price_text = if self.eth_price_loading() {
-            "Loading ETH price...".to_string()
-        } else {
-            format!("ETH: ${:.2}", self.current_eth_price().unwrap_or(0.0))
+        // Keep showing the last price when refreshes fail, with its age once it is stale
+        let eth_price_text = match (self.current_eth_price(), self.eth_price_age()) {
+            (None, _) if self.eth_price_loading() => "Loading ETH price...".to_string(),
+            (None, _) => "ETH: price unavailable".to_string(),
+            (Some(price), Some(age)) if age > STALE_PRICE_AGE => format!("ETH: ${price:.2} ({})", describe_age(age)),
+            (Some(price), _) => format!("ETH: ${price:.2}"),
         };
 
         // Balance section
-        let eth_balance = self
-            .token_balances()
-            .iter()
-            .find(|token| token.symbol == "ETH")
+        let eth_token = self.token_balances().iter().find(|token| token.symbol == "ETH");
+        let eth_balance = eth_token
             .map(|token| safe_balance(&token.balance))
-            .unwrap_or_else(|| "0.0000".to_string());
+            .unwrap_or_else(|| safe_balance(""));
 
-        let balance_usd_value = if let Some(price) = self.current_eth_price() {
-            let balance_f64 = eth_balance.parse::<f64>().unwrap_or(0.0);
-            format!("{:.2}", safe_usd_value(balance_f64, price))
-        } else {
-            "0.00".to_string()
+        let balance_usd_value = match self.current_eth_price() {
+            Some(price) => {
+                let balance_f64 = eth_token.and_then(|token| token.balance.parse::<f64>().ok()).unwrap_or(0.0);
+                safe_usd_display(balance_f64, price)
+            }
+            None => "—".to_string(),
         };
 
         let price_section = Container::new(
@@ -743,7 +853,7 @@ impl AppState {
                         .push(Text::new(format!("Balance: {eth_balance} ETH")).size(13))
                         .push(Space::with_width(Length::Fill))
                         .push(
-                            Text::new(format!("≈ ${balance_usd_value}"))
+                            Text::new(format!("≈ {balance_usd_value}"))
                                 .size(13)
                                 .style(iced::Color::from_rgb(0.8, 0.8, 0.8)),
                         ),
@@ -6,6 +6,7 @@ use super::services::auto_balance_service::AutoBalanceMessage;
 use super::wallet_types::{
     GasEstimation, GasSpeed, HistoryTab, ImportType, StatusMessageColor, TokenInfo, Transaction,
 };
+use crate::network::egress::{CategoryMode, EgressCategory};
 use crate::network::NetworkId;
 use crate::security::SecureAccount;
 use std::sync::Arc;
@@ -18,6 +19,10 @@ pub enum Message {
     // Smart polling messages
     SmartPollTick,
     BalanceChanged(String, String), // (old_balance, new_balance)
+    /// Balance change found by the wallet's background poller
+    PolledBalanceChanged(crate::network::poller::BalanceChanged),
+    /// Background balance polling (re)started over the wallet's accounts
+    BalancePollingStarted(Result<(), String>),
     UserActivity,
     // Internal refresh (doesn't show loading state)
     InternalRefreshBalance,
@@ -60,6 +65,14 @@ pub enum Message {
     SendPasteAddressFromClipboard, // Paste clipboard content to To Address field
     SendPasteAmountFromClipboard,  // Paste clipboard content to Amount field
     SendFromAccountSelected(String),
+    SendNotesChanged(String),
+    // Transaction drafts
+    SaveDraft,
+    ToggleDrafts,
+    ResumeDraft(String),
+    DeleteDraft(String),
+    DraftsLoaded(Result<crate::wallet::transaction::drafts::DraftBook, String>),
+    DraftsSaved(Result<(), String>),
     // Balance token selection
     BalanceTokenSelected(String),     // Token selected for balance display
     BalanceTickerSelected(String),    // Ticker selected for balance display
@@ -131,6 +144,22 @@ pub enum Message {
     ShowTransactionConfirmation,
     HideTransactionConfirmation,
     ConfirmTransaction,
+    AcknowledgeScreeningRisk(bool),
+    /// The wallet asks the user to approve a signature
+    ApprovalRequested(crate::wallet::ApprovalRequest),
+    /// The user approved (true) or rejected the request with this ID
+    AnswerApproval(u64, bool),
+    /// A category was set on the first-run network consent screen
+    EgressModeSelected(EgressCategory, CategoryMode),
+    /// Save the first-run network consent choices
+    ConfirmEgressConsent,
+    /// Look for hosts waiting for network approval
+    EgressConsentTick,
+    /// The user allowed (true) or blocked a host
+    EgressHostDecided(String, bool),
+    ShowEgressLog,
+    HideEgressLog,
+    ClearEgressLog,
     // Original transaction messages
     SubmitTransaction,
     TransactionSubmitted(Result<(String, Option<crate::gui::state::transaction_state::PendingTransaction>), String>),
@@ -177,7 +206,13 @@ pub enum Message {
     ExportPasswordChanged(String),
     SubmitInlineExport,
     CancelInlineExport,
+    // Seed phrase backup check (re-enter and compare, nothing displayed)
+    StartBackupCheck,
+    BackupCheckPhraseChanged(String),
+    SubmitBackupCheck,
+    BackupCheckCompleted(Result<bool, String>),
     SeedPhraseChanged(String),
+    Bip39PassphraseChanged(String),
     PrivateKeyChanged(String),
     // Export copy functionality with clipboard security
     CopyExportedData(String),             // Copy seed phrase or private key to clipboard
@@ -267,6 +302,10 @@ pub enum Message {
     CopyAddress(String),
     LoadAccounts,
     AccountsLoaded(Result<Vec<SecureAccount>, String>),
+    /// One page of a progressively loaded account list
+    AccountPageLoaded(Result<crate::wallet::account_manager::progressive::AccountPage, String>),
+    /// Native balance of an account in the account list
+    AccountListBalanceLoaded(alloy::primitives::Address, Result<String, String>),
     ResetCopyState,
     LoadNetworks,
     NetworksLoaded(Vec<crate::network::NetworkConfig>),
@@ -296,6 +335,8 @@ pub enum Message {
     // Export data handling
     ExportDataReceived(Result<String, String>),
     PerformWalletExport(secrecy::SecretString),
+    /// Delete the current account; the password encrypts its recovery point
+    PerformDeleteAccount(secrecy::SecretString),
 
     // Hardware wallet integration
     ScanHardwareWallets,
@@ -336,6 +377,9 @@ pub enum Message {
     // Activity tracking
     UpdateLastActivity,
 
+    // Privacy mode (hide balances and values)
+    TogglePrivacyMode,
+
     // Export account selection
     ToggleAccountDropdown,
     SelectExportAccount(String),
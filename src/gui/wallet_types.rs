@@ -141,6 +141,12 @@ pub struct GasEstimation {
     pub estimated_cost: String,
     pub total_cost: String,
     pub currency: String,
+    /// Denylist findings for the recipient and token contract
+    pub screening: crate::security::ScreeningReport,
+    /// Decoded contract call, e.g. `transfer(to=0x…, amount=1500)`
+    pub call_preview: Option<String>,
+    /// Advisory warnings about what kind of address the recipient is
+    pub recipient_notes: Vec<String>,
 }
 
 impl GasEstimation {
@@ -169,6 +175,9 @@ impl GasEstimation {
             estimated_cost: total_cost.clone(),
             total_cost,
             currency,
+            screening: Default::default(),
+            call_preview: None,
+            recipient_notes: Vec::new(),
         })
     }
 
@@ -3,17 +3,23 @@
 //! This module provides reusable, themed widgets that maintain consistency
 //! across the application while following Iced 0.12 best practices.
 
+use alloy::primitives::Address;
 use iced::{
     alignment::Horizontal,
-    widget::{Button, Checkbox, Column, Container, PickList, ProgressBar, Row, Scrollable, Space, Text, TextInput},
+    widget::{
+        image, Button, Checkbox, Column, Container, Image, PickList, ProgressBar, Row, Scrollable, Space, Text,
+        TextInput,
+    },
     Background, Border, Color, Element, Length,
 };
 
 use crate::gui::{
+    safe_calculations::display_amount,
     theme::{styles, text, VaughanColors},
     Message,
 };
 use crate::network::NetworkId;
+use crate::utils::identity::AccountIdentity;
 
 pub mod transaction;
 pub use transaction::*;
@@ -187,9 +193,9 @@ pub fn balance_display<'a>(symbol: &str, balance: &str, usd_value: &str) -> Elem
         Column::new()
             .push(Text::new("Balance").size(14).style(text::secondary()))
             .push(Space::with_height(Length::Fixed(10.0)))
-            .push(Text::new(format!("{balance} {symbol}")).size(24).style(text::primary()))
+            .push(Text::new(format!("{} {symbol}", display_amount(balance))).size(24).style(text::primary()))
             .push(Space::with_height(Length::Fixed(5.0)))
-            .push(Text::new(format!("≈ {usd_value}")).size(14).style(text::muted()))
+            .push(Text::new(format!("≈ {}", display_amount(usd_value))).size(14).style(text::muted()))
             .spacing(0)
             .align_items(iced::Alignment::Center)
             .into(),
@@ -359,6 +365,17 @@ pub fn success_container<'a>(message: &'a str) -> Element<'a, Message> {
         .into()
 }
 
+/// Identicon of `address`, `size` pixels square
+///
+/// Same identity as exported reports show, see [`AccountIdentity`].
+pub fn identity_badge<'a>(address: Address, size: f32) -> Element<'a, Message> {
+    let (side, pixels) = AccountIdentity::for_address(address).to_rgba(8);
+    Image::new(image::Handle::from_pixels(side, side, pixels))
+        .width(Length::Fixed(size))
+        .height(Length::Fixed(size))
+        .into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -411,4 +428,9 @@ mod tests {
         // Test theme switcher widget creation
         let _switcher = theme_switcher(true, Message::RefreshBalance);
     }
+
+    #[test]
+    fn test_identity_badge_creation() {
+        let _badge = identity_badge(Address::repeat_byte(1), 24.0);
+    }
 }
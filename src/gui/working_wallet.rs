@@ -4,9 +4,9 @@ use crate::blockchain::{load_config, ExplorerApiManager};
 use crate::error::VaughanError;
 use crate::gui::api_service::create_eth_price_command;
 use crate::gui::components::{
-    add_network_dialog_view, clear_logs_confirmation_dialog_view, create_wallet_dialog_view, custom_token_screen_view,
+    add_network_dialog_view, approval_dialog_view, clear_logs_confirmation_dialog_view, create_wallet_dialog_view, custom_token_screen_view,
     dapps_coming_soon_dialog_view, delete_account_dialog_view, delete_network_confirmation_dialog_view,
-    export_wallet_dialog_view, hardware_wallet_dialog_view, import_wallet_dialog_view, receive_dialog_view,
+    egress_consent_dialog_view, egress_host_dialog_view, egress_log_dialog_view, export_wallet_dialog_view, hardware_wallet_dialog_view, import_wallet_dialog_view, receive_dialog_view,
     reset_wallet_confirmation_dialog_view, transaction_confirmation_dialog_view,
     unified_password_dialog_view as password_dialog_view,
 };
@@ -18,7 +18,7 @@ use crate::gui::utils::{connect_hardware_wallet, detect_hardware_wallets, get_ha
 use crate::gui::wallet_types::HistoryTab;
 use crate::gui::*;
 use crate::network::{NetworkConfig, NetworkId};
-use crate::security::SecureAccount;
+use crate::security::{SecureAccount, SecurityProfile};
 use arboard::Clipboard;
 use chrono;
 use iced::{Application, Command, Element, Subscription, Theme};
@@ -53,6 +53,8 @@ pub struct WorkingWalletApp {
     pub wallet: Option<Arc<tokio::sync::RwLock<crate::wallet::Vaughan>>>,
     pub api_manager: Option<ExplorerApiManager>,
     pub account_service: Arc<IntegratedAccountService>,
+    /// Signing requests the approval dialog has not answered yet
+    pub approvals: ApprovalInbox,
     
     // Phase E: New controller fields (E4 complete)
     // Provider-independent controllers (always available)
@@ -112,7 +114,11 @@ impl Application for WorkingWalletApp {
         // Phase E: Initialize controllers (E4 - WorkingWalletApp structure)
         // Initialize controllers that don't need a provider
         let wallet_controller = Arc::new(WalletController::new());
-        let price_controller = Arc::new(PriceController::new(None)); // No Moralis API key yet
+        // No Moralis API key yet; cache and fetching follow the `pricing` settings
+        let price_controller = Arc::new(PriceController::from_settings(
+            None,
+            crate::config::settings_store().subscribe_section(),
+        ));
         tracing::info!("✅ Controllers initialized (wallet, price)");
         // Note: transaction_controller and network_controller will be initialized
         // after network setup when provider is available
@@ -122,6 +128,7 @@ impl Application for WorkingWalletApp {
             wallet: None,
             api_manager,
             account_service,
+            approvals: ApprovalInbox::default(),
             // Phase E: Controller fields (E4 complete)
             wallet_controller,
             price_controller,
@@ -149,24 +156,24 @@ impl Application for WorkingWalletApp {
             );
         }
 
-        // Simplified startup - skip wallet-level authentication, go straight to normal initialization
-        // Following DEVELOPMENT_RULES.md - simple Alloy approach, account-level passwords only
-        tracing::info!("🚀 Starting simplified wallet without wallet-level authentication");
-        let check_startup_cmd = Command::perform(
-            async {
-                // Just check for existing accounts without complex wallet authentication
-                match account_service::check_for_seed_accounts().await {
-                    Ok(has_accounts) => has_accounts,
-                    Err(e) => {
-                        tracing::warn!("Could not check for accounts: {}", e);
-                        false
-                    }
-                }
-            },
-            Message::SeedAccountsChecked,
-        );
+        // Ask which network services may be contacted before the first launch goes further
+        let egress = crate::network::egress::egress();
+        if egress.needs_first_run() {
+            wallet_app.state.ui_mut().egress_consent = Some(egress.policy().categories);
+        }
+
+        // Keys in an encrypted file vault can't be read until the user unlocks it
+        if crate::security::keychain_vault_locked() {
+            tracing::info!("🔐 Keychain vault is locked - asking for the vault password");
+            wallet_app
+                .state
+                .auth_mut()
+                .password_dialog
+                .show(crate::gui::state::auth_state::PasswordDialogConfig::KeychainVault);
+            return (wallet_app, Command::none());
+        }
 
-        (wallet_app, check_startup_cmd)
+        (wallet_app, Self::check_startup_accounts())
     }
 
     fn title(&self) -> String {
@@ -174,6 +181,8 @@ impl Application for WorkingWalletApp {
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
+        crate::gui::replay::record(&message);
+
         // Route messages to specialized handlers for better organization
         match message.clone() {
             // Transaction-related messages
@@ -182,15 +191,29 @@ impl Application for WorkingWalletApp {
             | Message::ShowTransactionConfirmation
             | Message::HideTransactionConfirmation
             | Message::ConfirmTransaction
+            | Message::AcknowledgeScreeningRisk(_)
+            | Message::ApprovalRequested(_)
+            | Message::AnswerApproval(_, _)
             | Message::SubmitTransaction
             | Message::TransactionSubmitted(_)
-            | Message::TransactionMonitoringTick => {
+            | Message::TransactionMonitoringTick
+            | Message::SendNotesChanged(_)
+            | Message::SaveDraft
+            | Message::ToggleDrafts
+            | Message::ResumeDraft(_)
+            | Message::DeleteDraft(_)
+            | Message::DraftsLoaded(_)
+            | Message::DraftsSaved(_) => {
                 // Use the new simplified handler directly
                 return self.handle_transaction_message(message);
             }
 
             // Network-related messages
-            Message::NetworkSelected(_) | Message::SmartPollTick | Message::BalanceChanged(_, _) => {
+            Message::NetworkSelected(_)
+            | Message::SmartPollTick
+            | Message::BalanceChanged(_, _)
+            | Message::PolledBalanceChanged(_)
+            | Message::BalancePollingStarted(_) => {
                 return self.handle_network_message(message);
             }
 
@@ -250,6 +273,7 @@ impl Application for WorkingWalletApp {
             | Message::ImportAccountNameChanged(_)
             | Message::ClearStatusMessage
             | Message::UpdateLastActivity
+            | Message::TogglePrivacyMode
             | Message::ToggleAccountDropdown
             | Message::SelectExportAccount(_)
             // Status message management
@@ -263,7 +287,15 @@ impl Application for WorkingWalletApp {
             | Message::ConfirmClearLogs
             | Message::CopyLogEntry(_)
             | Message::LogEntryCopied(_)
-            | Message::ResetCopyFeedback => {
+            | Message::ResetCopyFeedback
+            // First-run network consent
+            | Message::EgressModeSelected(_, _)
+            | Message::ConfirmEgressConsent
+            | Message::EgressConsentTick
+            | Message::EgressHostDecided(_, _)
+            | Message::ShowEgressLog
+            | Message::HideEgressLog
+            | Message::ClearEgressLog => {
                 return self.handle_ui_state_message(message);
             }
 
@@ -377,12 +409,30 @@ impl Application for WorkingWalletApp {
             Message::WalletInitialized(result) => {
                 match result {
                     Ok(wallet) => {
+                        let mut approval_requests = Command::none();
+                        let mut balance_changes = Command::none();
+                        if let Ok(guard) = wallet.try_read() {
+                            self.state.auth_mut().signing_session = Some(guard.session());
+                            // Every signature waits for the approval dialog
+                            let requests = stream_approval_requests(guard.approvals().connect(), self.approvals.clone());
+                            approval_requests = Command::run(requests, Message::ApprovalRequested);
+                            // Balances are pushed by the wallet's poller
+                            let changes = stream_balance_changes(guard.subscribe_balance_changes());
+                            balance_changes = Command::run(changes, Message::PolledBalanceChanged);
+                            // Sync UI network with the network the wallet actually started on
+                            let network_manager = guard.network_manager();
+                            let startup_network = network_manager.try_read().map(|manager| manager.current_network());
+                            if let Ok(startup_network) = startup_network {
+                                self.state.network_mut().current_network = startup_network;
+                                tracing::info!(
+                                    "🔗 UI network synced with wallet startup network (Chain ID: {})",
+                                    startup_network.0
+                                );
+                            }
+                        }
                         self.wallet = Some(wallet);
-                        // Sync UI network with wallet's default network (PulseChain Testnet v4)
-                        self.state.network_mut().current_network = NetworkId(943);
-                        tracing::info!("🔗 UI network synced with wallet: PulseChain Testnet v4 (Chain ID: 943)");
                         // Accounts and networks are already being loaded in parallel from Application::new()
-                        Command::none()
+                        Command::batch([approval_requests, balance_changes, self.restart_balance_polling()])
                     }
                     Err(e) => {
                         self.add_log_entry(
@@ -400,7 +450,10 @@ impl Application for WorkingWalletApp {
                 self.state.wallet_mut().loading_accounts = true;
                 self.state.wallet_mut().export_error_message = None;
                 self.state.ui_mut().show_retry_options = false;
-                Command::perform(load_available_accounts(), Message::AccountsLoaded)
+                Command::perform(
+                    load_available_accounts(self.state.wallet().security_profile),
+                    Message::AccountsLoaded,
+                )
             }
             Message::RetryExportOperation => {
                 self.state.wallet_mut().export_error_message = None;
@@ -421,7 +474,14 @@ impl Application for WorkingWalletApp {
                 // Also update session activity to prevent timeout
                 self.state.auth_mut().session.update_activity();
 
-                Command::none()
+                // Postpone the wallet's own auto-lock
+                match self.wallet.clone() {
+                    Some(wallet) => Command::perform(
+                        async move { wallet.read().await.record_activity().await },
+                        |_| Message::UpdateLastActivity,
+                    ),
+                    None => Command::none(),
+                }
             }
             Message::ShowTransactionHistory => {
                 // Use the same history view - don't create separate navigation layer
@@ -1382,6 +1442,72 @@ impl Application for WorkingWalletApp {
                 self.state.wallet_mut().export_error_message = None;
                 self.state.exporting_data = false;
                 self.state.export_loading = false;
+                self.reset_backup_check();
+                Command::none()
+            }
+            Message::StartBackupCheck => {
+                if !self.state.auth().enhanced_session.is_wallet_ready() {
+                    self.add_log_entry(
+                        LogCategory::Warning,
+                        "Wallet locked".to_string(),
+                        Some("Unlock the wallet before checking your seed phrase backup.".to_string()),
+                    );
+                    return Command::none();
+                }
+                self.reset_backup_check();
+                self.state.backup_check_active = true;
+                Command::none()
+            }
+            Message::BackupCheckPhraseChanged(phrase) => {
+                self.state.backup_check_phrase = phrase;
+                self.state.backup_check_result = None;
+                Command::none()
+            }
+            Message::SubmitBackupCheck => {
+                if self.state.backup_check_phrase.trim().is_empty() {
+                    return Command::none();
+                }
+                let Some(account_id) = self.state.selected_export_account_id.clone() else {
+                    return Command::none();
+                };
+                let Some(password) = self
+                    .state
+                    .auth()
+                    .enhanced_session
+                    .wallet_session
+                    .cached_master_password
+                    .as_ref()
+                    .map(|p| p.expose_secret().clone())
+                else {
+                    self.state.backup_check_result =
+                        Some(Err("Please lock and unlock your wallet, then try again.".to_string()));
+                    return Command::none();
+                };
+
+                // The entry is cleared as soon as it is handed off
+                let phrase = std::mem::take(&mut self.state.backup_check_phrase);
+                self.state.exporting_data = true;
+                Command::perform(
+                    crate::gui::services::account_service::verify_seed_backup(account_id, password, phrase),
+                    Message::BackupCheckCompleted,
+                )
+            }
+            Message::BackupCheckCompleted(result) => {
+                self.state.exporting_data = false;
+                match &result {
+                    Ok(true) => self.add_log_entry(
+                        LogCategory::Success,
+                        "Seed phrase backup verified".to_string(),
+                        Some("The phrase you entered matches this account's stored seed.".to_string()),
+                    ),
+                    Ok(false) => self.add_log_entry(
+                        LogCategory::Warning,
+                        "Seed phrase backup does not match".to_string(),
+                        Some("Check your written copy word by word before relying on it.".to_string()),
+                    ),
+                    Err(error) => tracing::error!("Seed phrase backup check failed: {}", error),
+                }
+                self.state.backup_check_result = Some(result);
                 Command::none()
             }
             Message::BackToExportOptions => {
@@ -1497,6 +1623,10 @@ impl Application for WorkingWalletApp {
                     Command::none()
                 }
             }
+            Message::Bip39PassphraseChanged(passphrase) => {
+                self.state.wallet_mut().bip39_passphrase = secrecy::SecretString::new(passphrase);
+                Command::none()
+            }
             Message::PrivateKeyChanged(mut key) => {
                 // Strip 0x prefix if present and normalize
                 if key.starts_with("0x") || key.starts_with("0X") {
@@ -1597,8 +1727,12 @@ impl Application for WorkingWalletApp {
                 let name = self.state.wallet().wallet_name.clone();
                 let phrase = self.state.wallet().seed_phrase.clone();
                 let password = self.state.wallet().master_password.clone();
+                let passphrase = self.bip39_passphrase_input();
 
-                Command::perform(create_wallet_from_seed(name, phrase, password), Message::WalletCreated)
+                Command::perform(
+                    create_wallet_from_seed(name, phrase, password, passphrase),
+                    Message::WalletCreated,
+                )
             }
             Message::ImportWalletFromSeed => {
                 if self.state.wallet().creating_wallet {
@@ -1623,8 +1757,12 @@ impl Application for WorkingWalletApp {
                 let name = self.state.wallet().wallet_name.clone();
                 let seed = self.state.wallet().seed_phrase.clone();
                 let password = self.state.wallet().master_password.clone();
+                let passphrase = self.bip39_passphrase_input();
 
-                Command::perform(import_wallet_from_seed(name, seed, password), Message::WalletCreated)
+                Command::perform(
+                    import_wallet_from_seed(name, seed, password, passphrase),
+                    Message::WalletCreated,
+                )
             }
             Message::ImportWalletFromPrivateKey => {
                 if self.state.wallet().creating_wallet {
@@ -1665,6 +1803,7 @@ impl Application for WorkingWalletApp {
                         self.state.auth_mut().password_dialog.hide();
                         // Clear all wallet creation fields for security
                         self.state.wallet_mut().seed_phrase.clear();
+                        self.state.wallet_mut().bip39_passphrase = secrecy::SecretString::new(String::new());
                         self.state.wallet_mut().master_password.clear();
                         self.state.wallet_mut().confirm_password.clear();
                         self.state.wallet_mut().wallet_name.clear();
@@ -1856,14 +1995,13 @@ impl Application for WorkingWalletApp {
                 }
 
                 // Validate Chain ID before spawning task
-                let chain_id_str = self.state.network_mut().network_chain_id.trim().to_string();
-                let parsed_chain_id = match chain_id_str.parse::<u64>() {
+                let parsed_chain_id = match input_validation::chain_id(&self.state.network().network_chain_id) {
                     Ok(id) => id,
-                    Err(_) => {
+                    Err(e) => {
                         self.add_log_entry(
                             LogCategory::Error,
                             "Invalid Chain ID".to_string(),
-                            Some("Please enter a valid numeric Chain ID (e.g., 1, 56, 8453)".to_string()),
+                            Some(format!("{e}. Please enter a valid Chain ID (e.g., 1, 56, 8453)")),
                         );
                         self.state.ui_mut().status_message =
                             "Invalid Chain ID — please enter a valid number".to_string();
@@ -1902,6 +2040,7 @@ impl Application for WorkingWalletApp {
                             block_explorer_url: self.state.network_mut().network_block_explorer.clone(),
                             is_testnet: false,
                             is_custom: true, // This is a custom network
+                            fallback_rpc_urls: Vec::new(),
                         };
 
                         // Add to available networks if not already present
@@ -2087,14 +2226,13 @@ impl Application for WorkingWalletApp {
                 }
 
                 // Validate Chain ID before spawning task
-                let chain_id_str = self.state.network_mut().network_chain_id.trim().to_string();
-                let parsed_chain_id = match chain_id_str.parse::<u64>() {
+                let parsed_chain_id = match input_validation::chain_id(&self.state.network().network_chain_id) {
                     Ok(id) => id,
-                    Err(_) => {
+                    Err(e) => {
                         self.add_log_entry(
                             LogCategory::Error,
                             "Invalid Chain ID".to_string(),
-                            Some("Please enter a valid numeric Chain ID (e.g., 1, 56, 8453)".to_string()),
+                            Some(format!("{e}. Please enter a valid Chain ID (e.g., 1, 56, 8453)")),
                         );
                         self.state.ui_mut().status_message =
                             "Invalid Chain ID — please enter a valid number".to_string();
@@ -2361,12 +2499,62 @@ impl Application for WorkingWalletApp {
                 self.state.wallet_mut().loading_accounts = true;
                 // Show spinner for account loading
                 self.state.ui_mut().accounts_spinner = true;
-                Command::perform(load_available_accounts(), Message::AccountsLoaded)
+                // Pages arrive with the selected account first so it renders straight away
+                let wallet = self.state.wallet();
+                let visible: Vec<_> = wallet
+                    .current_account_id
+                    .as_ref()
+                    .and_then(|id| wallet.available_accounts.iter().find(|a| &a.id == id))
+                    .map(|a| a.address)
+                    .into_iter()
+                    .collect();
+                let profile = wallet.security_profile;
+                Command::run(stream_available_accounts(profile, visible), Message::AccountPageLoaded)
+            }
+            Message::AccountPageLoaded(Err(error)) => self.dispatch_message(Message::AccountsLoaded(Err(error))),
+            Message::AccountPageLoaded(Ok(page)) => {
+                tracing::debug!(
+                    "📋 Loaded accounts {}-{} of {}",
+                    page.offset + 1,
+                    page.offset + page.accounts.len(),
+                    page.total
+                );
+                // Balances of this page load in the background while later pages arrive
+                let addresses: Vec<_> = page.accounts.iter().map(|a| a.address).collect();
+                let balances = match &self.wallet {
+                    Some(wallet) if !addresses.is_empty() => Command::run(
+                        stream_account_balances(wallet.clone(), addresses, self.state.network().current_network),
+                        |(address, balance)| Message::AccountListBalanceLoaded(address, balance),
+                    ),
+                    _ => Command::none(),
+                };
+                let is_last = page.is_last();
+                if page.is_first() {
+                    self.state.wallet_mut().available_accounts = page.accounts;
+                } else {
+                    self.state.wallet_mut().available_accounts.extend(page.accounts);
+                }
+                if !is_last {
+                    return balances;
+                }
+                // The last page finishes loading the same way a full list does
+                let accounts = self.state.wallet().available_accounts.clone();
+                Command::batch([balances, self.dispatch_message(Message::AccountsLoaded(Ok(accounts)))])
+            }
+            Message::AccountListBalanceLoaded(address, result) => {
+                match result {
+                    Ok(balance) => {
+                        self.state.wallet_mut().account_balances.insert(address, balance);
+                    }
+                    Err(error) => tracing::warn!("⚠️ Balance of {} unavailable: {}", address, error),
+                }
+                Command::none()
             }
             Message::AccountsLoaded(result) => {
                 self.state.wallet_mut().loading_accounts = false;
                 // Hide accounts spinner
                 self.state.ui_mut().accounts_spinner = false;
+                let polling;
                 match result {
                     Ok(accounts) => {
                         tracing::info!(
@@ -2376,6 +2564,8 @@ impl Application for WorkingWalletApp {
                         );
                         // Set the accounts first
                         self.state.wallet_mut().available_accounts = accounts;
+                        // Poll the balances of the new account list
+                        polling = self.restart_balance_polling();
 
                         // Activate auto balance polling when accounts are loaded successfully
                         if !self.state.wallet().available_accounts.is_empty() {
@@ -2404,7 +2594,10 @@ impl Application for WorkingWalletApp {
                             );
 
                             // Use AccountSelected to properly unlock the wallet
-                            return self.dispatch_message(Message::AccountSelected(first_account_id));
+                            return Command::batch([
+                                polling,
+                                self.dispatch_message(Message::AccountSelected(first_account_id)),
+                            ]);
                         }
                     }
                     Err(error) => {
@@ -2428,7 +2621,7 @@ impl Application for WorkingWalletApp {
                     }
                 }
 
-                Command::none()
+                polling
             }
             Message::AccountSelected(account_id) => {
                 tracing::info!("🔄 AccountSelected: Two-tier security check for account {}", account_id);
@@ -2506,9 +2699,14 @@ impl Application for WorkingWalletApp {
                 Command::none()
             }
             Message::ConfirmDeleteAccount => {
-                if let Some(account_id) = self.state.wallet().current_account_id.clone() {
-                    self.state.wallet_mut().deleting_account = true;
-                    Command::perform(delete_account(account_id), Message::AccountDeleted)
+                if self.state.wallet().current_account_id.is_some() {
+                    // The password encrypts the recovery point taken before deleting
+                    let account_name = self.state.wallet().current_account.clone();
+                    self.state
+                        .auth_mut()
+                        .password_dialog
+                        .show(crate::gui::state::auth_state::PasswordDialogConfig::DeleteAccount { account_name });
+                    Command::none()
                 } else {
                     self.add_log_entry(
                         LogCategory::Error,
@@ -2518,6 +2716,22 @@ impl Application for WorkingWalletApp {
                     Command::none()
                 }
             }
+            Message::PerformDeleteAccount(password) => {
+                match (self.wallet.clone(), self.state.wallet().current_account_id.clone()) {
+                    (Some(wallet), Some(account_id)) => {
+                        self.state.wallet_mut().deleting_account = true;
+                        Command::perform(delete_account(wallet, account_id, password), Message::AccountDeleted)
+                    }
+                    _ => {
+                        self.add_log_entry(
+                            LogCategory::Error,
+                            "Account deletion failed".to_string(),
+                            Some("Wallet not initialized or no account selected".to_string()),
+                        );
+                        Command::none()
+                    }
+                }
+            }
             Message::AccountDeleted(result) => {
                 self.state.wallet_mut().deleting_account = false;
                 self.state.wallet_mut().show_delete_account = false;
@@ -3084,6 +3298,26 @@ impl Application for WorkingWalletApp {
             return password_dialog_view(&self.state);
         }
 
+        // Show signing approval dialog (the wallet waits for its answer)
+        if let Some(dialog) = approval_dialog_view(&self.state) {
+            return dialog;
+        }
+
+        // Show first-run network consent
+        if let Some(dialog) = egress_consent_dialog_view(&self.state) {
+            return dialog;
+        }
+
+        // Ask about hosts waiting for network approval
+        if let Some(dialog) = egress_host_dialog_view(&self.state) {
+            return dialog;
+        }
+
+        // Show network activity
+        if let Some(dialog) = egress_log_dialog_view(&self.state) {
+            return dialog;
+        }
+
         // Show master password dialog (HD wallet authentication)
 
         // Show transaction confirmation dialog (high priority for transaction flow)
@@ -3186,6 +3420,11 @@ impl Application for WorkingWalletApp {
             subscriptions.push(iced::time::every(Duration::from_millis(100)).map(|_| Message::SpinnerTick));
         }
 
+        // Hosts waiting for network approval
+        if self.state.ui().pending_egress_host.is_none() {
+            subscriptions.push(iced::time::every(Duration::from_secs(2)).map(|_| Message::EgressConsentTick));
+        }
+
         // Price auto-refresh subscription
         if self.state.network().show_price_info {
             subscriptions.push(iced::time::every(Duration::from_secs(30)).map(|_| Message::PriceAutoRefreshTick));
@@ -3212,6 +3451,11 @@ impl Application for WorkingWalletApp {
             subscriptions.push(iced::time::every(Duration::from_secs(10)).map(|_| Message::SessionTimeoutCheck));
         }
 
+        // Privacy mode hotkey
+        subscriptions.push(iced::keyboard::on_key_press(|key, modifiers| {
+            crate::gui::privacy::is_toggle_hotkey(&key, modifiers).then_some(Message::TogglePrivacyMode)
+        }));
+
         // Keyboard event subscription for modal dialog handling
         if self.state.wallet().show_export_wallet {
             subscriptions.push(iced::keyboard::on_key_press(|key, _modifiers| {
@@ -3307,6 +3551,20 @@ impl WorkingWalletApp {
         }
     }
 
+    /// Close the seed phrase backup check and wipe any entered phrase
+    pub fn reset_backup_check(&mut self) {
+        use zeroize::Zeroize;
+        self.state.backup_check_phrase.zeroize();
+        self.state.backup_check_active = false;
+        self.state.backup_check_result = None;
+    }
+
+    /// The BIP-39 passphrase typed for seed creation or import, if any
+    fn bip39_passphrase_input(&self) -> Option<String> {
+        let passphrase = self.state.wallet().bip39_passphrase.expose_secret();
+        (!passphrase.is_empty()).then(|| passphrase.clone())
+    }
+
     /// Add a general log entry
     pub fn add_log_entry(&mut self, category: LogCategory, message: String, details: Option<String>) {
         let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
@@ -3373,6 +3631,26 @@ impl WorkingWalletApp {
         Command::none()
     }
 
+    /// First startup step: look for existing accounts
+    pub fn check_startup_accounts() -> Command<Message> {
+        // Simplified startup - skip wallet-level authentication, go straight to normal initialization
+        // Following DEVELOPMENT_RULES.md - simple Alloy approach, account-level passwords only
+        tracing::info!("🚀 Starting simplified wallet without wallet-level authentication");
+        Command::perform(
+            async {
+                // Just check for existing accounts without complex wallet authentication
+                match account_service::check_for_seed_accounts().await {
+                    Ok(has_accounts) => has_accounts,
+                    Err(e) => {
+                        tracing::warn!("Could not check for accounts: {}", e);
+                        false
+                    }
+                }
+            },
+            Message::SeedAccountsChecked,
+        )
+    }
+
     /// Start normal wallet initialization after authentication
     /// This loads wallet, accounts, networks, and tokens in parallel
     pub fn start_normal_initialization(&mut self) -> Command<Message> {
@@ -3382,8 +3660,11 @@ impl WorkingWalletApp {
         self.state.wallet_mut().loading_accounts = true;
         self.state.network_mut().loading_networks = true;
 
-        // Check if wallet is unlocked to determine account loading method
-        let load_accounts_cmd = if self.state.auth().enhanced_session.is_wallet_ready() {
+        // Check if wallet is unlocked to determine account loading method; the
+        // decoy profile only keeps account metadata
+        let profile = self.state.wallet().security_profile;
+        let wallet_ready = self.state.auth().enhanced_session.is_wallet_ready();
+        let load_accounts_cmd = if profile == SecurityProfile::Primary && wallet_ready {
             // Load accounts from wallet configuration
             tracing::info!("📁 Loading accounts from wallet configuration");
             {
@@ -3411,11 +3692,11 @@ impl WorkingWalletApp {
         } else {
             // Fallback to legacy account loading
             tracing::info!("📁 Loading accounts using legacy method");
-            Command::perform(load_available_accounts(), Message::AccountsLoaded)
+            Command::perform(load_available_accounts(profile), Message::AccountsLoaded)
         };
 
         // Create parallel loading commands
-        let init_wallet_cmd = Command::perform(initialize_wallet(), Message::WalletInitialized);
+        let init_wallet_cmd = Command::perform(initialize_wallet(profile), Message::WalletInitialized);
         let load_networks_cmd = Command::perform(load_all_networks(), Message::NetworksLoaded);
         let load_tokens_cmd = Command::perform(load_custom_tokens(), |result| {
             Message::CustomTokensLoaded(result.unwrap_or_default())
@@ -3723,15 +4004,15 @@ impl WorkingWalletApp {
                 // Load accounts from both keystores
                 tracing::info!("Refreshing accounts list from keystore");
 
-                use crate::security::keychain::OSKeychain;
+                use crate::security::create_keychain;
                 use crate::security::keystore::SecureKeystoreImpl;
 
                 // Load from both keychain services
                 let mut all_accounts = Vec::new();
 
                 // Load seed-based accounts
-                if let Ok(keychain) = OSKeychain::new(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string()) {
-                    if let Ok(keystore) = SecureKeystoreImpl::new(Box::new(keychain)).await {
+                if let Ok(keychain) = create_keychain(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS) {
+                    if let Ok(keystore) = SecureKeystoreImpl::new(keychain).await {
                         if let Ok(accounts) = keystore.list_accounts().await {
                             all_accounts.extend(accounts);
                             tracing::info!("Loaded {} seed-based accounts", all_accounts.len());
@@ -3740,8 +4021,8 @@ impl WorkingWalletApp {
                 }
 
                 // Load private-key accounts
-                if let Ok(keychain) = OSKeychain::new(crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string()) {
-                    if let Ok(keystore) = SecureKeystoreImpl::new(Box::new(keychain)).await {
+                if let Ok(keychain) = create_keychain(crate::security::SERVICE_NAME_PRIVATE_KEYS) {
+                    if let Ok(keystore) = SecureKeystoreImpl::new(keychain).await {
                         if let Ok(accounts) = keystore.list_accounts().await {
                             all_accounts.extend(accounts);
                             tracing::info!("Loaded {} total accounts", all_accounts.len());
@@ -4073,6 +4354,18 @@ impl WorkingWalletApp {
 
         tracing::info!("🔓 Wallet unlock attempt with password length: {}", password.len());
 
+        if let Some(wait) = crate::security::UnlockThrottle::from_settings().wait() {
+            tracing::warn!("⏳ Unlock refused for {:?} after failed attempts", wait.remaining());
+            let error = crate::gui::state::auth_state::WalletPasswordError::from(wait);
+            self.state.auth_mut().password_dialog.set_error(error.into());
+            return Command::none();
+        }
+
+        // The duress password opens the decoy accounts instead
+        if crate::security::DuressGuard::open_default().is_duress_password(&SecretString::new(password.clone())) {
+            return self.handle_decoy_unlock(password);
+        }
+
         // Get wallet directory
         let wallet_dir = crate::security::keystore::storage::get_vaughan_dir();
 
@@ -4103,6 +4396,7 @@ impl WorkingWalletApp {
                     };
 
                     tracing::info!("✅ Wallet unlocked successfully: {}", address);
+                    self.record_unlock_success(address);
 
                     // Create key reference for the account
                     let key_reference = crate::security::KeyReference {
@@ -4148,6 +4442,14 @@ impl WorkingWalletApp {
                         }
                     };
 
+                    let load_drafts = Command::perform(
+                        crate::gui::services::load_transaction_drafts(
+                            SecurityProfile::Primary,
+                            secrecy::SecretString::new(password.clone()),
+                        ),
+                        Message::DraftsLoaded,
+                    );
+
                     // Unlock the wallet session
                     let remember_session = self.state.auth().password_dialog.remember_session;
                     self.state.auth_mut().enhanced_session.wallet_session.unlock(
@@ -4166,14 +4468,12 @@ impl WorkingWalletApp {
                     self.state.ui_mut().status_message_color = crate::gui::wallet_types::StatusMessageColor::Success;
 
                     // Trigger startup authentication complete
-                    return self.dispatch_message(Message::StartupAuthenticationComplete);
+                    return Command::batch([load_drafts, self.dispatch_message(Message::StartupAuthenticationComplete)]);
                 }
                 Err(e) => {
                     tracing::error!("❌ Unlock failed: {}", e);
-                    self.state.auth_mut().password_dialog.set_error(
-                        crate::gui::state::auth_state::WalletPasswordError::IncorrectPassword { attempts_remaining: 3 }
-                            .into(),
-                    );
+                    let error = self.record_unlock_failure();
+                    self.state.auth_mut().password_dialog.set_error(error.into());
                     return Command::none();
                 }
             }
@@ -4326,10 +4626,8 @@ impl WorkingWalletApp {
             Ok(bytes) => bytes,
             Err(e) => {
                 tracing::error!("❌ Decryption failed (likely incorrect password): {}", e);
-                self.state.auth_mut().password_dialog.set_error(
-                    crate::gui::state::auth_state::WalletPasswordError::IncorrectPassword { attempts_remaining: 3 }
-                        .into(),
-                );
+                let error = self.record_unlock_failure();
+                self.state.auth_mut().password_dialog.set_error(error.into());
                 return Command::none();
             }
         };
@@ -4371,13 +4669,13 @@ impl WorkingWalletApp {
                 address_str,
                 decrypted_address
             );
-            self.state.auth_mut().password_dialog.set_error(
-                crate::gui::state::auth_state::WalletPasswordError::IncorrectPassword { attempts_remaining: 3 }.into(),
-            );
+            let error = self.record_unlock_failure();
+            self.state.auth_mut().password_dialog.set_error(error.into());
             return Command::none();
         }
 
         tracing::info!("✅ Legacy wallet password validated - address matches: {}", address_str);
+        self.record_unlock_success(decrypted_address);
 
         // Create key reference and account
         let key_reference = crate::security::KeyReference {
@@ -4420,6 +4718,13 @@ impl WorkingWalletApp {
             }
         };
 
+        let load_drafts = Command::perform(
+            crate::gui::services::load_transaction_drafts(
+                SecurityProfile::Primary,
+                secrecy::SecretString::new(password.clone()),
+            ),
+            Message::DraftsLoaded,
+        );
         let remember_session = self.state.auth().password_dialog.remember_session;
         self.state.auth_mut().enhanced_session.wallet_session.unlock(
             wallet_config,
@@ -4433,7 +4738,85 @@ impl WorkingWalletApp {
         self.state.ui_mut().status_message = "✅ Wallet unlocked successfully!".to_string();
         self.state.ui_mut().status_message_color = crate::gui::wallet_types::StatusMessageColor::Success;
 
-        self.dispatch_message(Message::StartupAuthenticationComplete)
+        Command::batch([load_drafts, self.dispatch_message(Message::StartupAuthenticationComplete)])
+    }
+
+    /// Unlock into the decoy profile, looking the same as a master password unlock
+    fn handle_decoy_unlock(&mut self, password: String) -> Command<Message> {
+        if let Err(e) = crate::security::UnlockThrottle::from_settings().record_success() {
+            tracing::warn!("⚠️ Failed to reset unlock attempt counter: {}", e);
+        }
+        self.state.wallet_mut().security_profile = SecurityProfile::Decoy;
+        self.state.wallet_mut().available_accounts.clear();
+        self.state.wallet_mut().current_account_id = None;
+
+        let wallet_config = match crate::security::WalletConfig::new(
+            "Vaughan Wallet".to_string(),
+            &secrecy::SecretString::new(password.clone()),
+        ) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::error!("❌ Failed to create wallet config: {}", e);
+                self.state.auth_mut().password_dialog.set_error(
+                    crate::gui::state::auth_state::WalletPasswordError::CreationFailed {
+                        reason: "Failed to create wallet configuration".to_string(),
+                    }
+                    .into(),
+                );
+                return Command::none();
+            }
+        };
+
+        let load_drafts = Command::perform(
+            crate::gui::services::load_transaction_drafts(
+                SecurityProfile::Decoy,
+                secrecy::SecretString::new(password.clone()),
+            ),
+            Message::DraftsLoaded,
+        );
+        let remember_session = self.state.auth().password_dialog.remember_session;
+        self.state.auth_mut().enhanced_session.wallet_session.unlock(
+            wallet_config,
+            secrecy::SecretString::new(password),
+            remember_session,
+        );
+
+        tracing::info!("✅ Wallet session unlocked successfully - ready for operations");
+
+        self.state.auth_mut().password_dialog.hide();
+        self.state.ui_mut().status_message = "✅ Wallet unlocked successfully!".to_string();
+        self.state.ui_mut().status_message_color = crate::gui::wallet_types::StatusMessageColor::Success;
+
+        Command::batch([load_drafts, self.dispatch_message(Message::StartupAuthenticationComplete)])
+    }
+
+    /// Count a wrong master password; the error says how long the next attempt has to wait
+    fn record_unlock_failure(&mut self) -> crate::gui::state::auth_state::WalletPasswordError {
+        let mut throttle = crate::security::UnlockThrottle::from_settings();
+        let wait = throttle.record_failure().unwrap_or_else(|e| {
+            tracing::warn!("⚠️ Failed to persist unlock attempt counter: {}", e);
+            throttle.wait()
+        });
+        match wait {
+            Some(wait) => wait.into(),
+            None => crate::gui::state::auth_state::WalletPasswordError::IncorrectPassword {
+                attempts_remaining: throttle.remaining_attempts(),
+            },
+        }
+    }
+
+    /// Reset the attempt counter and record the unlock in the audit log
+    ///
+    /// The master password opened the wallet, so the primary profile is used.
+    fn record_unlock_success(&mut self, account: alloy::primitives::Address) {
+        self.state.wallet_mut().security_profile = SecurityProfile::Primary;
+        if let Err(e) = crate::security::UnlockThrottle::from_settings().record_success() {
+            tracing::warn!("⚠️ Failed to reset unlock attempt counter: {}", e);
+        }
+        crate::telemetry::audit::record(
+            crate::telemetry::audit::AuditEvent::Unlock { account },
+            uuid::Uuid::new_v4(),
+        );
     }
 
     /// Load accounts from wallet configuration metadata
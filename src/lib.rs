@@ -7,15 +7,20 @@
 //!
 //! - **Multi-EVM Support**: Ethereum, PulseChain, BSC, Polygon, and custom networks
 //! - **Token Price Data**: Real-time token pricing from APIs
+//! - **Token Swaps**: Aggregator (0x, 1inch, ParaSwap) and Uniswap V2/V3 router quotes
 //! - **Hardware Wallet Support**: Ledger and Trezor integration
 
+pub mod abi;
 pub mod blockchain;
 pub mod config;
 pub mod controllers;
+pub mod defi;
+pub mod diagnostics;
 pub mod error;
 pub mod gui;
 pub mod network;
 pub mod performance;
+pub mod provenance;
 pub mod security;
 pub mod telemetry;
 pub mod tokens;
@@ -28,6 +33,7 @@ pub use wallet::Vaughan;
 
 // pub use gui::VaughanApp; // Temporarily disabled
 pub use error::{Result, VaughanError};
+pub use provenance::{build_info, BuildInfo};
 
 /// Application version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
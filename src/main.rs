@@ -1,14 +1,130 @@
-use tracing::info;
+use alloy::primitives::{Address, U256};
+use secrecy::SecretString;
+use std::sync::Arc;
+use tracing::{error, info};
+use vaughan::diagnostics::{run_diagnostics, DiagnosticsConfig};
 use vaughan::gui::launcher;
+use vaughan::network::benchmark::BenchmarkConfig;
+use vaughan::network::{NetworkId, NetworkManager};
+use vaughan::security::confirmation::{
+    AutoApprovePolicy, ConfirmationChannel, ConfirmationGate, PolicyConfirmation, TerminalConfirmation,
+    WebhookConfirmation,
+};
+use vaughan::tokens::historical::HistoricalBalanceReader;
+use vaughan::tokens::metadata::discover_token_metadata;
+use vaughan::wallet::provider::{HttpRpcServer, StdioRpcServer, DEFAULT_HTTP_RPC_PORT, WALLET_PASSWORD_ENV};
+use vaughan::wallet::transaction::csv_batch::{BatchControl, BatchRow, CsvBatch, CsvBatchRunner, CsvBatchStore};
+use vaughan::wallet::transaction::disperse::DisperseAsset;
+use vaughan::wallet::{Vaughan, WalletConfig};
+use zeroize::Zeroizing;
 
 fn main() -> iced::Result {
+    let args: Vec<String> = std::env::args().collect();
+
+    // Headless signing backend; stdout carries the protocol
+    if args.len() > 1 && args[1] == "--stdio-rpc" {
+        tracing_subscriber::fmt().with_writer(std::io::stderr).init();
+        vaughan::config::init_settings_store();
+        vaughan::telemetry::audit::init_audit_log();
+        init_metrics();
+        vaughan::network::egress::init_egress_control();
+        if let Err(e) = run_stdio_rpc(&args) {
+            error!("Wallet API server failed: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Initialize logging
     tracing_subscriber::fmt::init();
+    // Settings subsystems subscribe to, reloaded when settings.json is edited
+    vaughan::config::init_settings_store();
+    vaughan::telemetry::audit::init_audit_log();
+    init_metrics();
+    // Saved egress policy; every network client checks it
+    vaughan::network::egress::init_egress_control();
 
     info!("Starting Vaughan - Multi-EVM DeFi Wallet with Iced GUI");
+    info!("Build: {}", vaughan::build_info().version_line());
 
-    // Check command line arguments
-    let args: Vec<String> = std::env::args().collect();
+    // Print build provenance and exit
+    if args.len() > 1 && (args[1] == "--version" || args[1] == "--build-info") {
+        println!("{}", vaughan::build_info());
+        return Ok(());
+    }
+
+    // Compare the RPC endpoints of a network: --benchmark-rpc [chain-id]
+    if args.len() > 1 && args[1] == "--benchmark-rpc" {
+        let chain_id = args.get(2).and_then(|id| id.parse().ok());
+        if let Err(e) = run_rpc_benchmark(chain_id) {
+            error!("RPC benchmark failed: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Diagnose keychain, config, networks, clock, devices and disk space: doctor
+    if args.len() > 1 && args[1] == "doctor" {
+        match run_doctor() {
+            Ok(true) => return Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                error!("Diagnostics failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Airdrop eligibility: token-balance-at <token> <account> <block> [--min <raw>] [--from-block <n>] [--chain <id>]
+    if args.len() > 1 && args[1] == "token-balance-at" {
+        match run_token_balance_at(&args) {
+            Ok(true) => return Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                error!("Historical balance check failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Pay out a CSV file, resuming the batch left unfinished: csv-send [<file.csv>] [--from <address>] [--token <address>] [--report <path>]
+    if args.len() > 1 && args[1] == "csv-send" {
+        match run_csv_send(&args) {
+            Ok(true) => return Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                error!("CSV batch failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Set the password that unlocks decoy accounts: duress-password [--clear]
+    if args.len() > 1 && args[1] == "duress-password" {
+        if let Err(e) = run_duress_password(&args) {
+            error!("Duress password not changed: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Serve dApps on localhost: --http-rpc [port] [--read-only] [--confirm-webhook <url>] [--auto-approve <policy.json>]
+    if args.len() > 1 && args[1] == "--http-rpc" {
+        let port = args.get(2).and_then(|port| port.parse().ok()).unwrap_or(DEFAULT_HTTP_RPC_PORT);
+        let read_only = args.iter().any(|arg| arg == "--read-only");
+        if let Err(e) = run_http_rpc(&args, port, read_only) {
+            error!("Wallet HTTP server failed: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Record GUI events for deterministic replay: --record-events <path>
+    if let Some(path) = flag_value(&args, "--record-events") {
+        if let Err(e) = vaughan::gui::replay::init_event_recording(std::path::Path::new(path)) {
+            error!("Failed to start event recording: {}", e);
+        }
+    }
 
     // Check if user wants the simple wallet interface
     if args.len() > 1 && args[1] == "--simple" {
@@ -21,3 +137,375 @@ fn main() -> iced::Result {
 
     launcher::launch_working_gui()
 }
+
+/// Apply the `telemetry` settings: log redaction and the opt-in metrics endpoint
+fn init_metrics() {
+    match vaughan::config::Settings::load_default() {
+        Ok(settings) => {
+            vaughan::telemetry::metrics::init_metrics(&settings.telemetry);
+        }
+        Err(e) => {
+            error!("Invalid settings, metrics disabled: {}", e);
+            vaughan::telemetry::metrics::init_metrics(&Default::default());
+        }
+    }
+}
+
+/// Benchmark the endpoints of `chain_id`, or of the startup network, and print the ranking
+fn run_rpc_benchmark(chain_id: Option<u64>) -> Result<(), Box<dyn std::error::Error>> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let manager = NetworkManager::new().await?;
+        let network = chain_id.map(NetworkId).unwrap_or_else(|| manager.current_network());
+        let report = manager.benchmark_endpoints(network, &BenchmarkConfig::default()).await?;
+        println!("{report}");
+        Ok::<_, Box<dyn std::error::Error>>(())
+    })
+}
+
+/// Run every diagnostic check and print the report; `Ok(false)` when a check failed
+fn run_doctor() -> Result<bool, Box<dyn std::error::Error>> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let report = runtime.block_on(run_diagnostics(&DiagnosticsConfig::default()));
+    println!("{report}");
+    Ok(report.is_healthy())
+}
+
+/// Print a token balance at a past block; `Ok(false)` when it is below `--min`
+///
+/// Without an archive endpoint the balance is rebuilt from transfer logs
+/// when `--from-block` names a block at or before the token's deployment.
+fn run_token_balance_at(args: &[String]) -> Result<bool, Box<dyn std::error::Error>> {
+    let (Some(token), Some(account), Some(block)) = (args.get(2), args.get(3), args.get(4)) else {
+        return Err(
+            "usage: token-balance-at <token> <account> <block> [--min <raw>] [--from-block <n>] [--chain <id>]".into(),
+        );
+    };
+    let token: Address = token.parse()?;
+    let account: Address = account.parse()?;
+    let block: u64 = block.parse()?;
+    let required: U256 = flag_value(args, "--min").map(str::parse).transpose()?.unwrap_or(U256::ZERO);
+    let from_block = flag_value(args, "--from-block").map(str::parse).transpose()?;
+    let chain_id = flag_value(args, "--chain").map(str::parse).transpose()?;
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let manager = match chain_id {
+            Some(chain_id) => NetworkManager::with_startup_network(NetworkId(chain_id)).await?,
+            None => NetworkManager::new().await?,
+        };
+        let network = manager
+            .get_current_network_config()
+            .ok_or("Current network is not configured")?;
+        let reader = HistoricalBalanceReader::new(vaughan::network::connect_rpc(&network.rpc_url, "token balance")?);
+        let check = reader.balance_at(token, account, block, from_block).await?.check(required);
+        println!("{check}");
+        Ok::<_, Box<dyn std::error::Error>>(check.is_eligible())
+    })
+}
+
+/// Set the duress password, or remove it with `--clear`
+///
+/// Unlocking with the duress password opens the decoy accounts; the real
+/// ones are neither decrypted nor listed.
+fn run_duress_password(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    use secrecy::ExposeSecret;
+
+    let clear = args.iter().any(|arg| arg == "--clear");
+    let master_password = prompt_password("Master password: ")?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let mut wallet = Vaughan::new(WalletConfig::default()).await?;
+        if clear {
+            wallet.clear_duress_password(&master_password).await?;
+            println!("Duress password removed; the decoy accounts are kept");
+            return Ok(());
+        }
+        let duress_password = prompt_password("Duress password: ")?;
+        if prompt_password("Repeat duress password: ")?.expose_secret() != duress_password.expose_secret() {
+            return Err("the duress passwords do not match".into());
+        }
+        wallet.set_duress_password(&duress_password, &master_password).await?;
+        println!("Duress password set; unlock with it to create the decoy accounts");
+        Ok(())
+    })
+}
+
+/// Send the rows of a CSV file one transfer at a time; `Ok(false)` when rows are left
+///
+/// The batch is saved, encrypted under the master password, after every row.
+/// Ctrl-C pauses it before the next row, and running the command again
+/// without a file resumes the saved batch.
+fn run_csv_send(args: &[String]) -> Result<bool, Box<dyn std::error::Error>> {
+    let file = args.get(2).filter(|arg| !arg.starts_with("--"));
+    let from: Option<Address> = flag_value(args, "--from").map(str::parse).transpose()?;
+    let token: Option<Address> = flag_value(args, "--token").map(str::parse).transpose()?;
+    let report = flag_value(args, "--report");
+
+    let password = prompt_password("Master password: ")?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let mut wallet = Vaughan::new(WalletConfig::default()).await?;
+        if !wallet.unlock_signing(password.clone()).await? {
+            return Err("wrong master password".into());
+        }
+        let network = wallet
+            .get_current_network()
+            .await
+            .ok_or("Current network is not configured")?;
+        let provider = vaughan::network::connect_rpc(&network.rpc_url, "CSV send")?;
+
+        let store = CsvBatchStore::new(wallet.security_profile().await);
+        let mut batch = match (store.load(&password)?, file) {
+            (Some(_), Some(_)) => {
+                return Err("a CSV batch is unfinished; run `csv-send` without a file to resume it".into());
+            }
+            (Some(batch), None) => {
+                println!("Resuming CSV batch {} from {}", batch.id, batch.created_at);
+                batch
+            }
+            (None, Some(file)) => {
+                let asset = match token {
+                    Some(token) => DisperseAsset::Erc20 {
+                        token,
+                        decimals: discover_token_metadata(&provider, network.chain_id, token)
+                            .await?
+                            .decimals,
+                    },
+                    None => DisperseAsset::Native,
+                };
+                CsvBatch::parse(network.chain_id, asset, &std::fs::read_to_string(file)?)?
+            }
+            (None, None) => {
+                return Err(
+                    "usage: csv-send [<file.csv>] [--from <address>] [--token <address>] [--report <path>]".into(),
+                );
+            }
+        };
+
+        let from = match (from.or(batch.from), wallet.current_account().await) {
+            (Some(from), _) => from,
+            (None, Some(account)) => account.address,
+            (None, None) => return Err("no account selected; pass --from".into()),
+        };
+        wallet.switch_account(from).await?;
+
+        let summary = batch.summary();
+        for row in batch.rows() {
+            if let Some(error) = row.status.error() {
+                println!("line {}: {} ({})", row.line, row.status.label(), error);
+            }
+        }
+        println!(
+            "{} pending transfer(s) on {} from {from}; {} invalid, {} already sent",
+            summary.pending,
+            network.name,
+            summary.invalid,
+            summary.signing + summary.sent + summary.confirmed
+        );
+        store.save(&batch, &password)?;
+        if summary.pending > 0 && !confirm_on_terminal("Send them? [y/N] ")? {
+            println!("Batch saved; run `csv-send` again to resume it");
+            return Ok(false);
+        }
+
+        // Every transfer of the batch was approved above
+        wallet.approvals().delegate("csv-send command");
+        let signer = Arc::new(tokio::sync::RwLock::new(wallet));
+        let runner = CsvBatchRunner::new(provider, signer, network.chain_id);
+        let control = BatchControl::new();
+        let interrupt = tokio::spawn({
+            let control = control.clone();
+            async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    eprintln!("Pausing after the current transfer...");
+                    control.pause();
+                }
+            }
+        });
+
+        let mut saved = batch.clone();
+        saved.from = Some(from);
+        let mut save_row = |row: &BatchRow| {
+            saved.update_row(row);
+            if let Err(e) = store.save(&saved, &password) {
+                error!("Failed to save CSV batch: {}", e);
+            }
+        };
+        let run = runner.run(&mut batch, from, &control, &mut save_row).await;
+        interrupt.abort();
+        run?;
+        let summary = runner.refresh(&mut batch, &mut save_row).await?;
+
+        if let Some(path) = report {
+            batch.export_report(std::path::Path::new(path))?;
+        }
+        println!(
+            "{} confirmed, {} awaiting receipt, {} to re-send, {} failed, {} skipped, {} pending",
+            summary.confirmed, summary.sent, summary.signing, summary.failed, summary.skipped, summary.pending
+        );
+        if summary.is_done() {
+            store.clear()?;
+        } else {
+            store.save(&batch, &password)?;
+            println!("Batch saved; run `csv-send` again to resume it");
+        }
+        Ok::<_, Box<dyn std::error::Error>>(summary.is_done() && summary.failed == 0)
+    })
+}
+
+/// Ask a yes/no question on the terminal
+fn confirm_on_terminal(question: &str) -> std::io::Result<bool> {
+    use std::io::Write;
+    eprint!("{question}");
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Read the master password from the terminal without echoing it
+fn prompt_password(prompt: &str) -> std::io::Result<SecretString> {
+    use std::io::Write;
+    eprint!("{prompt}");
+    std::io::stderr().flush()?;
+    let mut line = Zeroizing::new(String::new());
+    {
+        #[cfg(unix)]
+        let _echo = EchoOff::stdin();
+        std::io::stdin().read_line(&mut line)?;
+    }
+    eprintln!();
+    Ok(SecretString::new(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+/// Turns terminal echo off on stdin until dropped
+#[cfg(unix)]
+struct EchoOff(Option<libc::termios>);
+
+#[cfg(unix)]
+#[allow(unsafe_code)]
+impl EchoOff {
+    fn stdin() -> Self {
+        // SAFETY: termios is plain data and fd 0 stays open for the whole process
+        unsafe {
+            let mut termios: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                // Not a terminal, e.g. piped input
+                return Self(None);
+            }
+            let saved = termios;
+            termios.c_lflag &= !libc::ECHO;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+            Self(Some(saved))
+        }
+    }
+}
+
+#[cfg(unix)]
+#[allow(unsafe_code)]
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        if let Some(saved) = self.0 {
+            // SAFETY: restores the settings read in `stdin`
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &saved);
+            }
+        }
+    }
+}
+
+/// Serve the wallet API over stdin/stdout until the host closes stdin
+///
+/// Signing is confirmed by the host unless a confirmation channel is given.
+fn run_stdio_rpc(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    // stdin carries the protocol, so the terminal cannot be asked
+    let confirmation = confirmation_from_args(args, None)?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let wallet = Vaughan::new(WalletConfig::default()).await?;
+        let mut server = StdioRpcServer::for_wallet(wallet);
+        if let Some(confirmation) = confirmation {
+            server = server.with_confirmation(confirmation);
+        }
+        server.serve_stdio().await?;
+        Ok::<_, Box<dyn std::error::Error>>(())
+    })
+}
+
+/// Serve the wallet to dApps on `127.0.0.1:port`, asking for consent on the terminal by default
+///
+/// Signing is unlocked with the master password from [`http_rpc_password`].
+fn run_http_rpc(args: &[String], port: u16, read_only: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let terminal: Arc<dyn ConfirmationChannel> = Arc::new(TerminalConfirmation::default());
+    let confirmation =
+        confirmation_from_args(args, Some(terminal.clone()))?.unwrap_or_else(|| ConfirmationGate::new(terminal));
+    let password = http_rpc_password(read_only)?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let wallet = Vaughan::new(WalletConfig::default()).await?;
+        let server = HttpRpcServer::with_confirmation(wallet, confirmation).read_only(read_only);
+        server.unlock_signing(password).await?;
+        server.serve_port(port).await?;
+        Ok::<_, Box<dyn std::error::Error>>(())
+    })
+}
+
+/// Master password the HTTP server unlocks signing with, `None` to keep it locked
+///
+/// Unattended runs pass it in `VAUGHAN_WALLET_PASSWORD`, which is removed
+/// from the environment as soon as it is read so child processes never
+/// inherit it. Otherwise it is asked on the terminal.
+fn http_rpc_password(read_only: bool) -> std::io::Result<Option<SecretString>> {
+    use secrecy::ExposeSecret;
+    use std::io::IsTerminal;
+
+    if let Ok(password) = std::env::var(WALLET_PASSWORD_ENV) {
+        std::env::remove_var(WALLET_PASSWORD_ENV);
+        return Ok(Some(SecretString::new(password)));
+    }
+    if read_only || !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+    let password = prompt_password("Master password (empty to keep signing locked): ")?;
+    Ok(Some(password).filter(|password| !password.expose_secret().is_empty()))
+}
+
+/// Value following `flag` on the command line
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+/// Confirmation channel chosen with `--confirm-webhook <url>` and `--auto-approve <policy.json>`
+///
+/// The webhook, or else `fallback`, is asked about whatever the auto-approve
+/// policy does not cover. The webhook's bearer token is read from
+/// `VAUGHAN_CONFIRM_WEBHOOK_TOKEN`.
+fn confirmation_from_args(
+    args: &[String],
+    fallback: Option<Arc<dyn ConfirmationChannel>>,
+) -> Result<Option<ConfirmationGate>, Box<dyn std::error::Error>> {
+    let webhook = flag_value(args, "--confirm-webhook").map(|url| {
+        let mut webhook = WebhookConfirmation::new(url);
+        if let Ok(token) = std::env::var("VAUGHAN_CONFIRM_WEBHOOK_TOKEN") {
+            webhook = webhook.with_bearer_token(token);
+        }
+        Arc::new(webhook) as Arc<dyn ConfirmationChannel>
+    });
+
+    let channel = match flag_value(args, "--auto-approve") {
+        Some(path) => {
+            let policy = AutoApprovePolicy::load(std::path::Path::new(path))?;
+            let mut channel = PolicyConfirmation::new(policy);
+            if let Some(fallback) = webhook.or(fallback) {
+                channel = channel.with_fallback(fallback);
+            }
+            Some(Arc::new(channel) as Arc<dyn ConfirmationChannel>)
+        }
+        None => webhook,
+    };
+    Ok(channel.map(ConfirmationGate::new))
+}
@@ -0,0 +1,328 @@
+//! RPC endpoint benchmarking
+//!
+//! Measures every configured endpoint of a network side by side so users can
+//! pick the best one as primary:
+//!
+//! - **latency**: median and 95th percentile of repeated `eth_blockNumber` calls
+//! - **error rate**: failed or timed-out samples
+//! - **freshness**: how many blocks the endpoint's head trails the freshest
+//!   endpoint measured at the same time
+//!
+//! Endpoints reporting the wrong chain ID or failing every sample rank last.
+//! Among the rest, stale endpoints rank below fresh ones, then fewer errors
+//! and lower median latency win.
+
+use super::egress::{egress, EgressCategory};
+use super::{NetworkConfig, NetworkId};
+use alloy::providers::{Provider, RootProvider};
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// How endpoints are measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchmarkConfig {
+    /// `eth_blockNumber` calls per endpoint
+    pub samples: u32,
+    /// Time after which a sample counts as failed
+    pub timeout: Duration,
+    /// Blocks behind the freshest endpoint after which an endpoint is stale
+    pub stale_after_blocks: u64,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            samples: 5,
+            timeout: Duration::from_secs(5),
+            stale_after_blocks: 3,
+        }
+    }
+}
+
+/// Measurements of one endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointBenchmark {
+    pub url: String,
+    /// Whether the endpoint reported a chain ID other than the network's
+    pub chain_mismatch: bool,
+    pub samples: u32,
+    pub failures: u32,
+    pub latency_median: Option<Duration>,
+    pub latency_p95: Option<Duration>,
+    /// Highest block the endpoint reported
+    pub head_block: Option<u64>,
+    /// Blocks behind the freshest endpoint, set when ranking
+    pub head_lag: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+impl EndpointBenchmark {
+    fn failed(url: &str, samples: u32, error: String) -> Self {
+        Self {
+            url: url.to_string(),
+            chain_mismatch: false,
+            samples,
+            failures: samples,
+            latency_median: None,
+            latency_p95: None,
+            head_block: None,
+            head_lag: None,
+            last_error: Some(error),
+        }
+    }
+
+    /// Share of failed samples, 0.0 to 1.0
+    pub fn error_rate(&self) -> f64 {
+        if self.samples == 0 {
+            return 1.0;
+        }
+        f64::from(self.failures) / f64::from(self.samples)
+    }
+
+    /// Whether the endpoint serves the right chain and answered at least once
+    pub fn is_usable(&self) -> bool {
+        !self.chain_mismatch && self.failures < self.samples
+    }
+
+    fn is_stale(&self, stale_after_blocks: u64) -> bool {
+        self.head_lag.is_some_and(|lag| lag > stale_after_blocks)
+    }
+}
+
+/// Percentile of sorted latencies, nearest-rank
+fn percentile(sorted: &[Duration], percent: usize) -> Option<Duration> {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+/// Measure one endpoint through `provider`
+pub async fn measure_endpoint<P: Provider>(
+    provider: &P,
+    url: &str,
+    expected_chain_id: u64,
+    config: &BenchmarkConfig,
+) -> EndpointBenchmark {
+    let chain_mismatch = matches!(
+        tokio::time::timeout(config.timeout, provider.get_chain_id()).await,
+        Ok(Ok(chain_id)) if chain_id != expected_chain_id
+    );
+
+    let mut latencies = Vec::with_capacity(config.samples as usize);
+    let mut head_block = None;
+    let mut last_error = None;
+    for _ in 0..config.samples {
+        let started = Instant::now();
+        match tokio::time::timeout(config.timeout, provider.get_block_number()).await {
+            Ok(Ok(block)) => {
+                latencies.push(started.elapsed());
+                head_block = head_block.max(Some(block));
+            }
+            Ok(Err(e)) => last_error = Some(e.to_string()),
+            Err(_) => last_error = Some(format!("timed out after {:?}", config.timeout)),
+        }
+    }
+    latencies.sort();
+
+    EndpointBenchmark {
+        url: url.to_string(),
+        chain_mismatch,
+        samples: config.samples,
+        failures: config.samples - latencies.len() as u32,
+        latency_median: percentile(&latencies, 50),
+        latency_p95: percentile(&latencies, 95),
+        head_block,
+        head_lag: None,
+        last_error,
+    }
+}
+
+/// Set each endpoint's head lag and sort them best first
+pub fn rank(mut endpoints: Vec<EndpointBenchmark>, stale_after_blocks: u64) -> Vec<EndpointBenchmark> {
+    let freshest = endpoints
+        .iter()
+        .filter(|e| e.is_usable())
+        .filter_map(|e| e.head_block)
+        .max();
+    for endpoint in &mut endpoints {
+        endpoint.head_lag = match (freshest, endpoint.head_block) {
+            (Some(freshest), Some(head)) if endpoint.is_usable() => Some(freshest.saturating_sub(head)),
+            _ => None,
+        };
+    }
+    endpoints.sort_by_key(|e| {
+        (
+            !e.is_usable(),
+            e.is_stale(stale_after_blocks),
+            e.failures,
+            e.latency_median.unwrap_or(Duration::MAX),
+        )
+    });
+    endpoints
+}
+
+/// Ranked comparison of a network's endpoints
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkReport {
+    pub network: NetworkId,
+    pub network_name: String,
+    /// Best endpoint first
+    pub endpoints: Vec<EndpointBenchmark>,
+    pub measured_at: DateTime<Utc>,
+}
+
+impl BenchmarkReport {
+    /// Endpoint worth setting as primary, if any is usable
+    pub fn recommended(&self) -> Option<&EndpointBenchmark> {
+        self.endpoints.first().filter(|e| e.is_usable())
+    }
+}
+
+fn format_latency(latency: Option<Duration>) -> String {
+    latency.map_or_else(|| "-".to_string(), |l| format!("{} ms", l.as_millis()))
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "RPC endpoints for {} (chain {})",
+            self.network_name,
+            self.network.chain_id()
+        )?;
+        writeln!(
+            f,
+            "{:<4} {:<48} {:>9} {:>9} {:>7} {:>6}",
+            "#", "Endpoint", "Median", "p95", "Errors", "Lag"
+        )?;
+        for (position, endpoint) in self.endpoints.iter().enumerate() {
+            let lag = match (endpoint.chain_mismatch, endpoint.head_lag) {
+                (true, _) => "wrong chain".to_string(),
+                (false, Some(lag)) => lag.to_string(),
+                (false, None) => "-".to_string(),
+            };
+            writeln!(
+                f,
+                "{:<4} {:<48} {:>9} {:>9} {:>6.0}% {:>6}",
+                position + 1,
+                endpoint.url,
+                format_latency(endpoint.latency_median),
+                format_latency(endpoint.latency_p95),
+                endpoint.error_rate() * 100.0,
+                lag
+            )?;
+        }
+        match self.recommended() {
+            Some(best) => write!(f, "Recommended primary: {}", best.url),
+            None => write!(f, "No endpoint answered"),
+        }
+    }
+}
+
+/// Benchmark every RPC URL of `network` concurrently
+///
+/// Endpoints blocked by the egress settings are reported as failed rather
+/// than contacted.
+pub async fn benchmark_network(network: &NetworkConfig, config: &BenchmarkConfig) -> BenchmarkReport {
+    let purpose = format!("benchmark {} RPC", network.name);
+    let measurements = network.rpc_urls().into_iter().map(|url| {
+        let purpose = purpose.clone();
+        async move {
+            if let Err(e) = egress().check(EgressCategory::Rpc, url, &purpose) {
+                return EndpointBenchmark::failed(url, config.samples, e.to_string());
+            }
+            match url.parse::<url::Url>() {
+                Ok(parsed) => {
+                    let provider = RootProvider::new_http(parsed);
+                    measure_endpoint(&provider, url, network.chain_id, config).await
+                }
+                Err(e) => EndpointBenchmark::failed(url, config.samples, format!("Invalid URL: {e}")),
+            }
+        }
+    });
+    let endpoints = join_all(measurements).await;
+
+    BenchmarkReport {
+        network: network.id,
+        network_name: network.name.clone(),
+        endpoints: rank(endpoints, config.stale_after_blocks),
+        measured_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+
+    fn measured(url: &str, failures: u32, median_ms: u64, head: u64) -> EndpointBenchmark {
+        EndpointBenchmark {
+            url: url.to_string(),
+            chain_mismatch: false,
+            samples: 5,
+            failures,
+            latency_median: Some(Duration::from_millis(median_ms)),
+            latency_p95: Some(Duration::from_millis(median_ms * 2)),
+            head_block: Some(head),
+            head_lag: None,
+            last_error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_measure_endpoint() {
+        let asserter = Asserter::new();
+        asserter.push_success(&"0x171"); // chain 369
+        asserter.push_success(&"0x10");
+        asserter.push_failure_msg("rate limited");
+        asserter.push_success(&"0x12");
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+        let config = BenchmarkConfig {
+            samples: 3,
+            ..Default::default()
+        };
+
+        let result = measure_endpoint(&provider, "https://rpc.example", 369, &config).await;
+        assert!(!result.chain_mismatch);
+        assert_eq!((result.samples, result.failures), (3, 1));
+        assert_eq!(result.head_block, Some(0x12));
+        assert!(result.latency_median.is_some());
+        assert!(result.last_error.unwrap().contains("rate limited"));
+    }
+
+    #[test]
+    fn test_rank_prefers_fresh_reliable_fast_endpoints() {
+        let wrong_chain = EndpointBenchmark {
+            chain_mismatch: true,
+            ..measured("wrong", 0, 10, 1_000)
+        };
+        let endpoints = vec![
+            measured("slow", 0, 300, 1_000),
+            measured("stale", 0, 20, 990),
+            EndpointBenchmark::failed("down", 5, "connection refused".into()),
+            wrong_chain,
+            measured("flaky", 2, 30, 1_000),
+            measured("fast", 0, 50, 999),
+        ];
+
+        let ranked = rank(endpoints, 3);
+        let order: Vec<&str> = ranked.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(order, vec!["fast", "slow", "flaky", "stale", "wrong", "down"]);
+        assert_eq!(ranked[0].head_lag, Some(1));
+        assert_eq!(ranked[3].head_lag, Some(10));
+        assert_eq!(ranked[4].head_lag, None);
+
+        let report = BenchmarkReport {
+            network: NetworkId(369),
+            network_name: "PulseChain".into(),
+            endpoints: ranked,
+            measured_at: Utc::now(),
+        };
+        assert_eq!(report.recommended().unwrap().url, "fast");
+        assert!(report.to_string().contains("Recommended primary: fast"));
+    }
+}
@@ -0,0 +1,235 @@
+//! Idempotent broadcast
+//!
+//! `eth_sendRawTransaction` can fail for a transaction that is actually fine:
+//! a failover retry or a resend after a timeout reaches a node that already
+//! has it ("already known"), or it was mined in the meantime ("nonce too
+//! low"). "Nonce too low" can also mean another transaction with the same
+//! nonce won.
+//!
+//! Instead of surfacing those RPC errors, [`resolve_rejection`] asks the
+//! chain what happened to the transaction and returns a
+//! [`BroadcastOutcome`] the caller can reconcile its nonce and watcher state
+//! with.
+
+use crate::error::{Result, VaughanError};
+use alloy::consensus::transaction::SignerRecoverable;
+use alloy::consensus::{Transaction as _, TxEnvelope};
+use alloy::eips::Decodable2718;
+use alloy::primitives::{Address, TxHash};
+use alloy::providers::Provider;
+
+/// Broadcast errors that may hide a transaction the chain already has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastRejection {
+    /// The node already has this exact transaction
+    AlreadyKnown,
+    /// The sender's nonce has moved past the transaction's
+    NonceTooLow,
+}
+
+impl BroadcastRejection {
+    /// Recognise the rejection in an RPC error message
+    ///
+    /// Covers the wording of geth, Erigon, Nethermind and Besu.
+    pub fn classify(error: &str) -> Option<Self> {
+        let error = error.to_ascii_lowercase();
+        if ["already known", "known transaction", "alreadyknown", "already imported"]
+            .iter()
+            .any(|pattern| error.contains(pattern))
+        {
+            Some(Self::AlreadyKnown)
+        } else if ["nonce too low", "oldnonce", "nonce is too low"]
+            .iter()
+            .any(|pattern| error.contains(pattern))
+        {
+            Some(Self::NonceTooLow)
+        } else {
+            None
+        }
+    }
+}
+
+/// Hash, sender and nonce of a signed transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawTransactionInfo {
+    pub hash: TxHash,
+    pub sender: Address,
+    pub nonce: u64,
+}
+
+impl RawTransactionInfo {
+    /// Decode an EIP-2718 encoded signed transaction
+    pub fn decode(raw_tx: &[u8]) -> Result<Self> {
+        let envelope = TxEnvelope::decode_2718(&mut &raw_tx[..])
+            .map_err(|e| VaughanError::ValidationError(format!("Invalid signed transaction: {e}")))?;
+        let sender = envelope
+            .recover_signer()
+            .map_err(|e| VaughanError::ValidationError(format!("Invalid transaction signature: {e}")))?;
+        Ok(Self {
+            hash: *envelope.tx_hash(),
+            sender,
+            nonce: envelope.nonce(),
+        })
+    }
+}
+
+/// What happened to a broadcast transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastOutcome {
+    /// Accepted by the node now
+    Sent,
+    /// Already in the node's pool from an earlier attempt
+    AlreadyPending,
+    /// Already mined from an earlier attempt
+    AlreadyMined { block_number: u64, success: bool },
+    /// Another transaction used the nonce; this one will never be mined
+    Replaced { account_nonce: u64 },
+}
+
+impl BroadcastOutcome {
+    /// Whether the transaction is, or will be, on chain
+    pub fn is_ours(&self) -> bool {
+        !matches!(self, Self::Replaced { .. })
+    }
+}
+
+/// Decide the outcome from what the chain reports
+///
+/// `receipt` is the block number and status of the transaction's receipt,
+/// `known` whether the node returns the transaction by hash, and
+/// `account_nonce` the sender's mined transaction count. `None` means the
+/// chain does not explain the rejection, e.g. a lagging node.
+pub fn reconcile_rejection(
+    rejection: BroadcastRejection,
+    tx: &RawTransactionInfo,
+    receipt: Option<(u64, bool)>,
+    known: bool,
+    account_nonce: u64,
+) -> Option<BroadcastOutcome> {
+    if let Some((block_number, success)) = receipt {
+        return Some(BroadcastOutcome::AlreadyMined { block_number, success });
+    }
+    if known {
+        return Some(BroadcastOutcome::AlreadyPending);
+    }
+    if account_nonce > tx.nonce {
+        return Some(BroadcastOutcome::Replaced { account_nonce });
+    }
+    match rejection {
+        // The node vouched for the exact transaction; it is still propagating
+        BroadcastRejection::AlreadyKnown => Some(BroadcastOutcome::AlreadyPending),
+        BroadcastRejection::NonceTooLow => None,
+    }
+}
+
+/// Query the chain to explain a rejected broadcast of `tx`
+pub async fn resolve_rejection<P: Provider>(
+    provider: &P,
+    tx: &RawTransactionInfo,
+    rejection: BroadcastRejection,
+) -> std::result::Result<Option<BroadcastOutcome>, alloy::transports::TransportError> {
+    let receipt = provider
+        .get_transaction_receipt(tx.hash)
+        .await?
+        .and_then(|receipt| Some((receipt.block_number?, receipt.status())));
+    let known = receipt.is_none() && provider.get_transaction_by_hash(tx.hash).await?.is_some();
+    let account_nonce = provider.get_transaction_count(tx.sender).latest().await?;
+
+    let outcome = reconcile_rejection(rejection, tx, receipt, known, account_nonce);
+    tracing::info!(
+        "🔁 Broadcast of {} rejected as {:?}, resolved to {:?}",
+        tx.hash,
+        rejection,
+        outcome
+    );
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TX: RawTransactionInfo = RawTransactionInfo {
+        hash: TxHash::repeat_byte(0xaa),
+        sender: Address::repeat_byte(0x11),
+        nonce: 7,
+    };
+
+    #[test]
+    fn test_classify_rejections() {
+        use BroadcastRejection::*;
+        assert_eq!(
+            BroadcastRejection::classify("server returned an error response: error code -32000: already known"),
+            Some(AlreadyKnown)
+        );
+        assert_eq!(
+            BroadcastRejection::classify("Known transaction: 0xabc"),
+            Some(AlreadyKnown)
+        );
+        assert_eq!(
+            BroadcastRejection::classify("nonce too low: next nonce 8, tx nonce 7"),
+            Some(NonceTooLow)
+        );
+        assert_eq!(BroadcastRejection::classify("OldNonce"), Some(NonceTooLow));
+        assert_eq!(
+            BroadcastRejection::classify("insufficient funds for gas * price + value"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_decode_recovers_sender() {
+        use alloy::consensus::{Signed, TxEip1559};
+        use alloy::eips::Encodable2718;
+        use alloy::network::TxSignerSync;
+        use alloy::signers::local::PrivateKeySigner;
+
+        let signer = PrivateKeySigner::random();
+        let mut tx = TxEip1559 {
+            chain_id: 1,
+            nonce: 7,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: Address::repeat_byte(0x22).into(),
+            ..Default::default()
+        };
+        let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+        let envelope = TxEnvelope::from(Signed::new_unhashed(tx, signature));
+
+        let info = RawTransactionInfo::decode(&envelope.encoded_2718()).unwrap();
+        assert_eq!(info.sender, signer.address());
+        assert_eq!(info.nonce, 7);
+        assert_eq!(info.hash, *envelope.tx_hash());
+
+        assert!(RawTransactionInfo::decode(&[0x02, 0xc0]).is_err());
+    }
+
+    #[test]
+    fn test_reconcile_rejection() {
+        use BroadcastRejection::*;
+        assert_eq!(
+            reconcile_rejection(NonceTooLow, &TX, Some((100, true)), false, 8),
+            Some(BroadcastOutcome::AlreadyMined {
+                block_number: 100,
+                success: true
+            })
+        );
+        assert_eq!(
+            reconcile_rejection(NonceTooLow, &TX, None, true, 7),
+            Some(BroadcastOutcome::AlreadyPending)
+        );
+        assert_eq!(
+            reconcile_rejection(NonceTooLow, &TX, None, false, 8),
+            Some(BroadcastOutcome::Replaced { account_nonce: 8 })
+        );
+        assert!(!BroadcastOutcome::Replaced { account_nonce: 8 }.is_ours());
+
+        // Lagging node: nothing explains "nonce too low", keep the error
+        assert_eq!(reconcile_rejection(NonceTooLow, &TX, None, false, 7), None);
+        assert_eq!(
+            reconcile_rejection(AlreadyKnown, &TX, None, false, 7),
+            Some(BroadcastOutcome::AlreadyPending)
+        );
+    }
+}
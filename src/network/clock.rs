@@ -0,0 +1,310 @@
+//! Clock skew detection
+//!
+//! Swap deadlines, ERC-2771 forward request deadlines, permits and sign-in
+//! messages are all stamped from the local clock. A clock that is minutes off
+//! makes them expire early or outlive what the user agreed to. The
+//! [`ClockMonitor`] keeps the latest measurement of the local clock against a
+//! reference:
+//!
+//! - **block**: the timestamp of the latest block on the current network;
+//!   blocks lag real time by up to one block interval, so small skews are
+//!   noise
+//! - **NTP**: an SNTP query to a time server, checked against the
+//!   [`TimeSync`](crate::network::egress::EgressCategory::TimeSync) egress category
+//!
+//! Once the skew exceeds the policy threshold, deadlines either keep the local
+//! clock and log a warning, or are computed from the corrected clock.
+
+use crate::error::{NetworkError, Result};
+use crate::network::egress::{egress, EgressCategory};
+use chrono::{DateTime, Utc};
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+/// Default SNTP server
+pub const DEFAULT_NTP_SERVER: &str = "pool.ntp.org";
+
+/// Skew below which the clock counts as correct
+pub const DEFAULT_SKEW_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// Seconds between the NTP epoch (1900) and the Unix epoch
+const NTP_UNIX_OFFSET: f64 = 2_208_988_800.0;
+
+/// Reference the local clock was compared against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeSource {
+    Block { chain_id: u64, number: u64 },
+    Ntp { server: String },
+}
+
+/// Local clock minus the reference clock, in seconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkewMeasurement {
+    /// Positive when the local clock is ahead
+    pub skew_secs: i64,
+    pub source: TimeSource,
+    pub measured_at: DateTime<Utc>,
+}
+
+impl SkewMeasurement {
+    /// Compare the local clock at `now` against a block timestamp
+    pub fn from_block(chain_id: u64, number: u64, timestamp: u64, now: DateTime<Utc>) -> Self {
+        Self {
+            skew_secs: now.timestamp() - timestamp as i64,
+            source: TimeSource::Block { chain_id, number },
+            measured_at: now,
+        }
+    }
+
+    pub fn exceeds(&self, threshold: Duration) -> bool {
+        self.skew_secs.unsigned_abs() > threshold.as_secs()
+    }
+
+    /// Warning for the user, phrased for the direction of the skew
+    pub fn describe(&self) -> String {
+        let source = match &self.source {
+            TimeSource::Block { chain_id, number } => format!("block {number} on chain {chain_id}"),
+            TimeSource::Ntp { server } => server.clone(),
+        };
+        let direction = if self.skew_secs >= 0 { "ahead of" } else { "behind" };
+        format!(
+            "Local clock is {}s {direction} {source}; deadlines and signed messages may be rejected",
+            self.skew_secs.unsigned_abs()
+        )
+    }
+}
+
+/// What to do with deadlines once the skew exceeds the threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SkewAction {
+    /// Keep the local clock and log a warning
+    #[default]
+    Warn,
+    /// Compute deadlines from the local clock minus the measured skew
+    Correct,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockPolicy {
+    pub threshold: Duration,
+    pub action: SkewAction,
+}
+
+impl Default for ClockPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_SKEW_THRESHOLD,
+            action: SkewAction::Warn,
+        }
+    }
+}
+
+/// Latest clock measurement and the policy applied to deadlines
+#[derive(Debug, Default)]
+pub struct ClockMonitor {
+    policy: RwLock<ClockPolicy>,
+    last: RwLock<Option<SkewMeasurement>>,
+}
+
+impl ClockMonitor {
+    pub fn new(policy: ClockPolicy) -> Self {
+        Self {
+            policy: RwLock::new(policy),
+            last: RwLock::new(None),
+        }
+    }
+
+    pub fn policy(&self) -> ClockPolicy {
+        *self.policy.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_policy(&self, policy: ClockPolicy) {
+        *self.policy.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    pub fn last_measurement(&self) -> Option<SkewMeasurement> {
+        self.last.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Store a measurement, logging it when it exceeds the threshold
+    pub fn record(&self, measurement: SkewMeasurement) {
+        if measurement.exceeds(self.policy().threshold) {
+            tracing::warn!("🕒 {}", measurement.describe());
+        } else {
+            tracing::debug!(
+                "🕒 Clock skew {}s against {:?}",
+                measurement.skew_secs,
+                measurement.source
+            );
+        }
+        *self.last.write().unwrap_or_else(|e| e.into_inner()) = Some(measurement);
+    }
+
+    /// Warning to show while the last measurement exceeds the threshold
+    pub fn warning(&self) -> Option<String> {
+        self.last_measurement()
+            .filter(|m| m.exceeds(self.policy().threshold))
+            .map(|m| m.describe())
+    }
+
+    /// Current time on the clock deadlines are computed from
+    pub fn now_at(&self, local: DateTime<Utc>) -> DateTime<Utc> {
+        let policy = self.policy();
+        match self.last_measurement() {
+            Some(m) if policy.action == SkewAction::Correct && m.exceeds(policy.threshold) => {
+                local - chrono::Duration::seconds(m.skew_secs)
+            }
+            _ => local,
+        }
+    }
+
+    /// Unix timestamp `secs` from now, on the corrected clock when the policy says so
+    pub fn deadline(&self, secs: u64) -> u64 {
+        if let Some(warning) = self.warning() {
+            match self.policy().action {
+                SkewAction::Warn => tracing::warn!("⚠️ Deadline from an unsynchronised clock: {}", warning),
+                SkewAction::Correct => tracing::info!("🕒 Correcting deadline for clock skew: {}", warning),
+            }
+        }
+        (self.now_at(Utc::now()).timestamp().max(0) as u64).saturating_add(secs)
+    }
+}
+
+/// Process-wide clock monitor
+pub fn clock() -> &'static ClockMonitor {
+    static CLOCK: OnceLock<ClockMonitor> = OnceLock::new();
+    CLOCK.get_or_init(ClockMonitor::default)
+}
+
+fn ntp_timestamp(bytes: &[u8]) -> f64 {
+    let secs = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as f64 / 4_294_967_296.0;
+    secs + fraction - NTP_UNIX_OFFSET
+}
+
+/// Local clock minus server clock from an SNTP response
+///
+/// `sent` and `received` are local Unix times of the request and response.
+/// `None` for a response that isn't a valid server reply.
+pub fn ntp_skew(sent: f64, response: &[u8], received: f64) -> Option<f64> {
+    // Mode 4 (server) or 5 (broadcast), and a synchronised stratum
+    if response.len() < 48 || !matches!(response[0] & 0x07, 4 | 5) || response[1] == 0 {
+        return None;
+    }
+    let server_received = ntp_timestamp(&response[32..40]);
+    let server_sent = ntp_timestamp(&response[40..48]);
+    let offset = ((server_received - sent) + (server_sent - received)) / 2.0;
+    Some(-offset)
+}
+
+fn unix_now() -> f64 {
+    Utc::now().timestamp_micros() as f64 / 1_000_000.0
+}
+
+fn ntp_error(server: &str, e: impl std::fmt::Display) -> crate::error::VaughanError {
+    NetworkError::RpcError {
+        message: format!("NTP query to {server} failed: {e}"),
+    }
+    .into()
+}
+
+/// Measure the local clock against an SNTP server
+pub async fn measure_ntp(server: &str, timeout: Duration) -> Result<SkewMeasurement> {
+    egress().check(
+        EgressCategory::TimeSync,
+        &format!("ntp://{server}"),
+        "check the local clock",
+    )?;
+
+    let query = async {
+        let socket = UdpSocket::bind("0.0.0.0:0").await?;
+        socket.connect((server, 123)).await?;
+        // LI 0, version 3, mode 3 (client)
+        let mut request = [0u8; 48];
+        request[0] = 0x1b;
+        let sent = unix_now();
+        socket.send(&request).await?;
+        let mut response = [0u8; 48];
+        let len = socket.recv(&mut response).await?;
+        Ok::<_, std::io::Error>((sent, response, len, unix_now()))
+    };
+    let (sent, response, len, received) = tokio::time::timeout(timeout, query)
+        .await
+        .map_err(|_| ntp_error(server, "timed out"))?
+        .map_err(|e| ntp_error(server, e))?;
+    let skew = ntp_skew(sent, &response[..len], received).ok_or_else(|| ntp_error(server, "invalid response"))?;
+
+    Ok(SkewMeasurement {
+        skew_secs: skew.round() as i64,
+        source: TimeSource::Ntp {
+            server: server.to_string(),
+        },
+        measured_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ntp_bytes(unix: f64) -> [u8; 8] {
+        let ntp = unix + NTP_UNIX_OFFSET;
+        let secs = ntp.trunc() as u32;
+        let fraction = (ntp.fract() * 4_294_967_296.0) as u32;
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&secs.to_be_bytes());
+        bytes[4..].copy_from_slice(&fraction.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_ntp_skew() {
+        // Local clock 90s ahead, 0.2s round trip
+        let server_time = 1_700_000_000.0;
+        let mut response = [0u8; 48];
+        response[0] = 0x24; // version 4, server mode
+        response[1] = 2;
+        response[32..40].copy_from_slice(&ntp_bytes(server_time + 0.1));
+        response[40..48].copy_from_slice(&ntp_bytes(server_time + 0.1));
+        let skew = ntp_skew(server_time + 90.0, &response, server_time + 90.2).unwrap();
+        assert!((skew - 90.0).abs() < 0.01);
+
+        response[1] = 0; // kiss-of-death
+        assert!(ntp_skew(server_time, &response, server_time).is_none());
+    }
+
+    #[test]
+    fn test_deadlines_follow_policy() {
+        let monitor = ClockMonitor::default();
+        let local = Utc::now();
+        assert_eq!(monitor.now_at(local), local);
+
+        // Local clock 10 minutes behind the chain
+        monitor.record(SkewMeasurement::from_block(
+            1,
+            100,
+            (local.timestamp() + 600) as u64,
+            local,
+        ));
+        assert!(monitor.warning().unwrap().contains("600s behind"));
+        assert_eq!(monitor.now_at(local), local);
+
+        monitor.set_policy(ClockPolicy {
+            action: SkewAction::Correct,
+            ..ClockPolicy::default()
+        });
+        assert_eq!(monitor.now_at(local).timestamp(), local.timestamp() + 600);
+        assert!(monitor.deadline(60) >= (local.timestamp() + 660) as u64);
+
+        // Small skews from block lag are ignored
+        monitor.record(SkewMeasurement::from_block(
+            1,
+            101,
+            (local.timestamp() - 12) as u64,
+            local,
+        ));
+        assert!(monitor.warning().is_none());
+        assert_eq!(monitor.now_at(local), local);
+    }
+}
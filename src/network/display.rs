@@ -0,0 +1,149 @@
+//! Chain-specific display conventions
+//!
+//! Wallet text tends to assume Ethereum: gas prices in Gwei with two
+//! decimals and roughly 12 seconds per block. Other chains differ.
+//! PulseChain users quote gas prices in beats (10⁹ wei, the same size as a
+//! Gwei) and those prices run into the millions, so decimals are noise; BSC
+//! and Polygon produce blocks every few seconds, so Ethereum-based
+//! confirmation estimates are far too pessimistic there.
+//!
+//! [`NetworkDisplay`] collects these conventions per chain. Unknown chains,
+//! including custom networks, get the Ethereum defaults.
+
+use super::NetworkConfig;
+use alloy::primitives::utils::{format_units, parse_units, ParseUnits};
+use alloy::primitives::U256;
+use std::time::Duration;
+
+/// How a chain's fees and timings are presented
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkDisplay {
+    /// Name of the gas price unit, e.g. `Gwei` or `beats`
+    pub fee_unit: &'static str,
+    /// Wei per fee unit, as a power of ten
+    pub fee_unit_decimals: u8,
+    /// Decimal places shown for gas prices
+    pub fee_precision: usize,
+    /// Decimal places shown for native token amounts
+    pub amount_precision: usize,
+    /// Average time between blocks
+    pub block_time: Duration,
+}
+
+impl Default for NetworkDisplay {
+    fn default() -> Self {
+        Self {
+            fee_unit: "Gwei",
+            fee_unit_decimals: 9,
+            fee_precision: 2,
+            amount_precision: 6,
+            block_time: Duration::from_secs(12),
+        }
+    }
+}
+
+impl NetworkDisplay {
+    /// Conventions of `chain_id`; Ethereum's for unknown chains
+    pub fn for_chain(chain_id: u64) -> Self {
+        match chain_id {
+            // PulseChain and its testnet
+            369 | 943 => Self {
+                fee_unit: "beats",
+                fee_precision: 0,
+                amount_precision: 4,
+                block_time: Duration::from_secs(10),
+                ..Self::default()
+            },
+            56 => Self {
+                block_time: Duration::from_secs(3),
+                ..Self::default()
+            },
+            137 => Self {
+                block_time: Duration::from_secs(2),
+                ..Self::default()
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Gas price of `wei` in the fee unit, e.g. `1250000 beats` or `12.5 Gwei`
+    pub fn format_gas_price(&self, wei: U256) -> String {
+        let units = format_units(wei, self.fee_unit_decimals).unwrap_or_else(|_| "0".to_string());
+        let value: f64 = units.parse().unwrap_or(0.0);
+        let formatted = format!("{value:.prec$}", prec = self.fee_precision);
+        let trimmed = if formatted.contains('.') {
+            formatted.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            &formatted
+        };
+        format!("{trimmed} {}", self.fee_unit)
+    }
+
+    /// Wei for a gas price entered in the fee unit
+    pub fn parse_gas_price(&self, input: &str) -> Option<U256> {
+        match parse_units(input.trim(), self.fee_unit_decimals).ok()? {
+            ParseUnits::U256(wei) => Some(wei),
+            ParseUnits::I256(_) => None,
+        }
+    }
+
+    /// Label of the gas price input, e.g. `Max Fee (beats)`
+    pub fn fee_field_label(&self, eip1559: bool) -> String {
+        let field = if eip1559 { "Max Fee" } else { "Gas Price" };
+        format!("{field} ({})", self.fee_unit)
+    }
+
+    /// Expected time for `blocks` blocks to be produced
+    pub fn confirmation_time(&self, blocks: u64) -> Duration {
+        self.block_time.saturating_mul(blocks.min(u32::MAX as u64) as u32)
+    }
+
+    /// Rough human estimate, e.g. `~30 s` or `~2 min`
+    pub fn describe_confirmation(&self, blocks: u64) -> String {
+        let secs = self.confirmation_time(blocks).as_secs();
+        if secs < 90 {
+            format!("~{secs} s")
+        } else {
+            format!("~{} min", (secs + 30) / 60)
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Display conventions of this network's chain
+    pub fn display(&self) -> NetworkDisplay {
+        NetworkDisplay::for_chain(self.chain_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_pulsechain_uses_beats_without_decimals() {
+        let pulse = NetworkDisplay::for_chain(369);
+        assert_eq!(
+            pulse.format_gas_price(U256::from(1_250_000 * GWEI + 1)),
+            "1250000 beats"
+        );
+        assert_eq!(pulse.fee_field_label(true), "Max Fee (beats)");
+        assert_eq!(pulse.parse_gas_price("2000000"), Some(U256::from(2_000_000 * GWEI)));
+
+        let eth = NetworkDisplay::for_chain(1);
+        assert_eq!(eth.format_gas_price(U256::from(12_500_000_000u64)), "12.5 Gwei");
+        assert_eq!(eth.format_gas_price(U256::from(20 * GWEI)), "20 Gwei");
+        assert_eq!(eth.fee_field_label(false), "Gas Price (Gwei)");
+    }
+
+    #[test]
+    fn test_confirmation_estimates_follow_block_time() {
+        assert_eq!(NetworkDisplay::for_chain(369).describe_confirmation(3), "~30 s");
+        assert_eq!(NetworkDisplay::for_chain(56).describe_confirmation(3), "~9 s");
+        assert_eq!(NetworkDisplay::for_chain(1).describe_confirmation(10), "~2 min");
+        // Custom chains fall back to Ethereum conventions
+        assert_eq!(NetworkDisplay::for_chain(9001), NetworkDisplay::default());
+    }
+}
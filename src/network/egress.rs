@@ -0,0 +1,597 @@
+//! Outbound connection consent
+//!
+//! Every outbound connection the wallet makes (RPC nodes, price APIs, token
+//! lists, block explorers, NFT metadata, swap aggregators, multisig services,
+//! approval webhooks) is checked against an [`EgressPolicy`] before it is opened:
+//!
+//! - whole categories can be allowed, restricted to approved hosts, or disabled
+//! - individual hosts can be approved or blocked
+//! - every check is recorded in an in-memory [`EgressEvent`] log so users can
+//!   see which hosts were contacted and why
+//!
+//! Until the first-run consent screen has been completed every host needs
+//! approval, except the RPC endpoints of the network in use (see
+//! [`EgressControl::set_active_rpc`]), and [`EgressControl::needs_first_run`]
+//! tells the UI to ask. The policy is stored in `<config_dir>/vaughan/egress_policy.json`.
+
+use crate::error::{ConfigurationError, NetworkError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock, RwLock};
+
+/// Number of egress events kept in memory
+pub const EGRESS_LOG_CAPACITY: usize = 500;
+
+/// Get the storage path for the egress policy
+pub fn get_egress_policy_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("vaughan");
+    path.push("egress_policy.json");
+    path
+}
+
+/// Why the wallet wants to connect somewhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EgressCategory {
+    /// JSON-RPC nodes; required for balances and sending
+    Rpc,
+    /// Token price APIs (CoinGecko, DexScreener, ...)
+    PriceData,
+    /// Remote token lists
+    TokenLists,
+    /// Block explorer APIs (history, contract lookups)
+    Explorer,
+    /// NFT metadata and IPFS gateways
+    NftMetadata,
+    /// Swap aggregator APIs
+    Swap,
+    /// Safe Transaction Service for multisig proposals
+    Multisig,
+    /// Webhooks asked to approve headless signing requests
+    Approval,
+    /// NTP servers queried to check the local clock
+    TimeSync,
+}
+
+impl EgressCategory {
+    pub const ALL: [EgressCategory; 9] = [
+        Self::Rpc,
+        Self::PriceData,
+        Self::TokenLists,
+        Self::Explorer,
+        Self::NftMetadata,
+        Self::Swap,
+        Self::Multisig,
+        Self::Approval,
+        Self::TimeSync,
+    ];
+
+    /// Short description for the consent screen
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Rpc => "Blockchain nodes used to read balances and send transactions",
+            Self::PriceData => "Price services used to show fiat values",
+            Self::TokenLists => "Public token lists used to recognise tokens",
+            Self::Explorer => "Block explorers used for transaction history",
+            Self::NftMetadata => "NFT metadata servers and IPFS gateways",
+            Self::Swap => "Swap aggregators used to quote trades",
+            Self::Multisig => "Safe Transaction Service used to share multisig signatures",
+            Self::Approval => "Approval webhooks asked to confirm signing without the GUI",
+            Self::TimeSync => "Time servers used to check the local clock",
+        }
+    }
+}
+
+/// How a category treats hosts without an explicit rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CategoryMode {
+    /// Any host may be contacted
+    Allow,
+    /// Only hosts the user approved may be contacted
+    AskPerHost,
+    /// No connections at all
+    Disabled,
+}
+
+/// A user decision about one host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostRule {
+    pub allowed: bool,
+    pub decided_at: DateTime<Utc>,
+}
+
+/// Result of checking a connection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EgressVerdict {
+    Allowed,
+    /// The category is restricted and this host hasn't been approved yet
+    ConsentRequired,
+    /// Blocked by the category mode or a host rule
+    Denied,
+}
+
+/// The user's egress settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EgressPolicy {
+    /// Whether the first-run consent screen was completed
+    #[serde(default)]
+    pub first_run_completed: bool,
+    #[serde(default)]
+    pub categories: BTreeMap<EgressCategory, CategoryMode>,
+    /// Per-host decisions, keyed by lowercase host name
+    #[serde(default)]
+    pub hosts: BTreeMap<String, HostRule>,
+}
+
+impl Default for EgressPolicy {
+    fn default() -> Self {
+        Self {
+            first_run_completed: false,
+            categories: EgressCategory::ALL.iter().map(|c| (*c, CategoryMode::Allow)).collect(),
+            hosts: BTreeMap::new(),
+        }
+    }
+}
+
+impl EgressPolicy {
+    /// Preset for privacy-minded users: RPC allowed, everything else per host
+    pub fn privacy_first() -> Self {
+        let mut policy = Self::default();
+        for category in EgressCategory::ALL {
+            if category != EgressCategory::Rpc {
+                policy.categories.insert(category, CategoryMode::AskPerHost);
+            }
+        }
+        policy
+    }
+
+    /// Load the policy from the default location
+    pub fn load() -> Self {
+        Self::load_from(&get_egress_policy_path())
+    }
+
+    /// Load the policy from a file, falling back to the default policy
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid egress policy {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the policy to a file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self).map_err(|e| ConfigurationError::ParseError {
+            message: format!("Failed to serialize egress policy: {e}"),
+        })?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn mode(&self, category: EgressCategory) -> CategoryMode {
+        self.categories.get(&category).copied().unwrap_or(CategoryMode::Allow)
+    }
+
+    /// Decide whether `host` may be contacted for `category`
+    ///
+    /// Before the first-run consent every category is treated as
+    /// [`CategoryMode::AskPerHost`].
+    pub fn evaluate(&self, category: EgressCategory, host: &str) -> EgressVerdict {
+        let mode = match self.first_run_completed {
+            true => self.mode(category),
+            false => CategoryMode::AskPerHost,
+        };
+        if mode == CategoryMode::Disabled {
+            return EgressVerdict::Denied;
+        }
+        match self.hosts.get(&host.to_ascii_lowercase()) {
+            Some(rule) if rule.allowed => EgressVerdict::Allowed,
+            Some(_) => EgressVerdict::Denied,
+            None if mode == CategoryMode::Allow => EgressVerdict::Allowed,
+            None => EgressVerdict::ConsentRequired,
+        }
+    }
+}
+
+/// One checked connection attempt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EgressEvent {
+    pub at: DateTime<Utc>,
+    pub host: String,
+    pub category: EgressCategory,
+    /// Why the connection was needed, e.g. "fetch token prices"
+    pub purpose: String,
+    pub verdict: EgressVerdict,
+}
+
+/// Host part of a URL, lowercased; `None` for URLs without a host
+pub fn host_of(url: &str) -> Option<String> {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.to_ascii_lowercase()))
+}
+
+/// Policy plus egress log, shared by everything that opens connections
+#[derive(Debug)]
+pub struct EgressControl {
+    policy: RwLock<EgressPolicy>,
+    policy_path: Option<PathBuf>,
+    /// Hosts of the RPC endpoints currently in use, allowed before first-run consent
+    active_rpc_hosts: RwLock<Vec<String>>,
+    log: Mutex<VecDeque<EgressEvent>>,
+}
+
+impl EgressControl {
+    /// In-memory control that is never persisted
+    pub fn new(policy: EgressPolicy) -> Self {
+        Self {
+            policy: RwLock::new(policy),
+            policy_path: None,
+            active_rpc_hosts: RwLock::new(Vec::new()),
+            log: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Control backed by a policy file; changes are saved immediately
+    pub fn load_from(path: PathBuf) -> Self {
+        Self {
+            policy: RwLock::new(EgressPolicy::load_from(&path)),
+            policy_path: Some(path),
+            active_rpc_hosts: RwLock::new(Vec::new()),
+            log: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn policy(&self) -> EgressPolicy {
+        self.policy.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn needs_first_run(&self) -> bool {
+        !self
+            .policy
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .first_run_completed
+    }
+
+    /// Set the RPC endpoints of the network the user is working on
+    ///
+    /// Until the first-run consent is completed these are the only hosts that
+    /// can be contacted without approval.
+    pub fn set_active_rpc<S: AsRef<str>>(&self, urls: &[S]) {
+        let hosts = urls.iter().filter_map(|url| host_of(url.as_ref())).collect();
+        *self.active_rpc_hosts.write().unwrap_or_else(|e| e.into_inner()) = hosts;
+    }
+
+    /// Check a connection to `url`, record it, and return the verdict
+    pub fn evaluate(&self, category: EgressCategory, url: &str, purpose: &str) -> EgressVerdict {
+        let host = host_of(url).unwrap_or_else(|| url.to_string());
+        let verdict = match self
+            .policy
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .evaluate(category, &host)
+        {
+            EgressVerdict::ConsentRequired if category == EgressCategory::Rpc && self.is_active_rpc(&host) => {
+                EgressVerdict::Allowed
+            }
+            verdict => verdict,
+        };
+
+        if verdict != EgressVerdict::Allowed {
+            tracing::info!(host = %host, ?category, ?verdict, "Outbound connection not permitted: {}", purpose);
+        }
+
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        if log.len() == EGRESS_LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(EgressEvent {
+            at: Utc::now(),
+            host,
+            category,
+            purpose: purpose.to_string(),
+            verdict: verdict.clone(),
+        });
+        verdict
+    }
+
+    /// Like [`evaluate`](Self::evaluate), but an error unless allowed
+    pub fn check(&self, category: EgressCategory, url: &str, purpose: &str) -> Result<()> {
+        match self.evaluate(category, url, purpose) {
+            EgressVerdict::Allowed => Ok(()),
+            verdict => Err(NetworkError::EgressBlocked {
+                host: host_of(url).unwrap_or_else(|| url.to_string()),
+                reason: match verdict {
+                    EgressVerdict::ConsentRequired => "waiting for your approval".to_string(),
+                    _ => format!("{category:?} connections are blocked in privacy settings"),
+                },
+            }
+            .into()),
+        }
+    }
+
+    fn is_active_rpc(&self, host: &str) -> bool {
+        self.active_rpc_hosts
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|active| active == host)
+    }
+
+    /// Logged events, most recent last
+    pub fn log(&self) -> Vec<EgressEvent> {
+        self.log
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Distinct hosts contacted (allowed) per category
+    pub fn contacted_hosts(&self) -> BTreeMap<EgressCategory, Vec<String>> {
+        let mut hosts: BTreeMap<EgressCategory, Vec<String>> = BTreeMap::new();
+        for event in self.log.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+            if event.verdict != EgressVerdict::Allowed {
+                continue;
+            }
+            let seen = hosts.entry(event.category).or_default();
+            if !seen.contains(&event.host) {
+                seen.push(event.host.clone());
+            }
+        }
+        hosts
+    }
+
+    /// Hosts that were refused only because nobody approved them yet
+    ///
+    /// One event per undecided host, most recent request first.
+    pub fn awaiting_consent(&self) -> Vec<EgressEvent> {
+        let policy = self.policy();
+        let mut waiting: Vec<EgressEvent> = Vec::new();
+        for event in self.log.lock().unwrap_or_else(|e| e.into_inner()).iter().rev() {
+            if event.verdict == EgressVerdict::ConsentRequired
+                && !policy.hosts.contains_key(&event.host)
+                && !waiting.iter().any(|waiting| waiting.host == event.host)
+            {
+                waiting.push(event.clone());
+            }
+        }
+        waiting
+    }
+
+    pub fn clear_log(&self) {
+        self.log.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Record the choices made on the first-run consent screen
+    pub fn complete_first_run(&self, categories: BTreeMap<EgressCategory, CategoryMode>) -> Result<()> {
+        self.update(|policy| {
+            policy.categories.extend(categories);
+            policy.first_run_completed = true;
+        })
+    }
+
+    pub fn set_category_mode(&self, category: EgressCategory, mode: CategoryMode) -> Result<()> {
+        self.update(|policy| {
+            policy.categories.insert(category, mode);
+        })
+    }
+
+    /// Replace the whole policy, e.g. to revert an earlier change
+    pub fn set_policy(&self, policy: EgressPolicy) -> Result<()> {
+        self.update(|current| *current = policy)
+    }
+
+    /// Approve or block a host for all categories
+    pub fn set_host(&self, host: &str, allowed: bool) -> Result<()> {
+        let host = host_of(host).unwrap_or_else(|| host.to_ascii_lowercase());
+        self.update(|policy| {
+            policy.hosts.insert(
+                host,
+                HostRule {
+                    allowed,
+                    decided_at: Utc::now(),
+                },
+            );
+        })
+    }
+
+    /// Forget a host decision so it follows its category again
+    pub fn forget_host(&self, host: &str) -> Result<()> {
+        let host = host_of(host).unwrap_or_else(|| host.to_ascii_lowercase());
+        self.update(|policy| {
+            policy.hosts.remove(&host);
+        })
+    }
+
+    fn update(&self, change: impl FnOnce(&mut EgressPolicy)) -> Result<()> {
+        let policy = {
+            let mut policy = self.policy.write().unwrap_or_else(|e| e.into_inner());
+            change(&mut policy);
+            policy.clone()
+        };
+        match &self.policy_path {
+            Some(path) => policy.save_to(path),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Global egress control
+static EGRESS_CONTROL: OnceLock<EgressControl> = OnceLock::new();
+
+/// Initialize the global egress control from the default policy file
+pub fn init_egress_control() -> &'static EgressControl {
+    EGRESS_CONTROL.get_or_init(|| EgressControl::load_from(get_egress_policy_path()))
+}
+
+/// Get the global egress control
+///
+/// Without [`init_egress_control`] an in-memory default policy is used.
+pub fn egress() -> &'static EgressControl {
+    EGRESS_CONTROL.get_or_init(|| EgressControl::new(EgressPolicy::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consented(policy: EgressPolicy) -> EgressControl {
+        EgressControl::new(EgressPolicy {
+            first_run_completed: true,
+            ..policy
+        })
+    }
+
+    #[test]
+    fn test_only_active_rpc_is_allowed_before_first_run() {
+        let control = EgressControl::new(EgressPolicy::default());
+        assert!(control.needs_first_run());
+        for category in EgressCategory::ALL {
+            assert_eq!(
+                control.evaluate(category, "https://api.example.com/x", "test"),
+                EgressVerdict::ConsentRequired
+            );
+        }
+
+        control.set_active_rpc(&["https://rpc.pulsechain.com", "https://rpc-pulsechain.g4mm4.io"]);
+        assert!(control
+            .check(EgressCategory::Rpc, "https://rpc.pulsechain.com", "balance")
+            .is_ok());
+        assert_eq!(
+            control.evaluate(EgressCategory::PriceData, "https://rpc.pulsechain.com", "prices"),
+            EgressVerdict::ConsentRequired
+        );
+        assert_eq!(
+            control.evaluate(EgressCategory::Rpc, "https://mainnet.infura.io", "balance"),
+            EgressVerdict::ConsentRequired
+        );
+
+        control.set_host("rpc.pulsechain.com", false).unwrap();
+        assert_eq!(
+            control.evaluate(EgressCategory::Rpc, "https://rpc.pulsechain.com", "balance"),
+            EgressVerdict::Denied
+        );
+        control.set_host("api.example.com", true).unwrap();
+        assert!(control
+            .check(EgressCategory::PriceData, "https://api.example.com/x", "prices")
+            .is_ok());
+
+        control.complete_first_run(BTreeMap::new()).unwrap();
+        assert!(control
+            .check(EgressCategory::Rpc, "https://mainnet.infura.io", "balance")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_category_modes_and_host_rules() {
+        let control = consented(EgressPolicy::privacy_first());
+        let prices = "https://api.coingecko.com/api/v3/simple/price";
+
+        assert_eq!(
+            control.evaluate(EgressCategory::Rpc, "https://rpc.pulsechain.com", "balance"),
+            EgressVerdict::Allowed
+        );
+        assert_eq!(
+            control.evaluate(EgressCategory::PriceData, prices, "prices"),
+            EgressVerdict::ConsentRequired
+        );
+
+        control.set_host("https://API.coingecko.com", true).unwrap();
+        assert!(control.check(EgressCategory::PriceData, prices, "prices").is_ok());
+
+        control.set_host("rpc.pulsechain.com", false).unwrap();
+        assert_eq!(
+            control.evaluate(EgressCategory::Rpc, "https://rpc.pulsechain.com", "balance"),
+            EgressVerdict::Denied
+        );
+
+        control.forget_host("rpc.pulsechain.com").unwrap();
+        control
+            .set_category_mode(EgressCategory::Rpc, CategoryMode::Disabled)
+            .unwrap();
+        let error = control.check(EgressCategory::Rpc, "https://rpc.pulsechain.com", "balance");
+        assert!(error.unwrap_err().to_string().contains("rpc.pulsechain.com"));
+    }
+
+    #[test]
+    fn test_egress_log() {
+        let control = consented(EgressPolicy::default());
+        control.evaluate(
+            EgressCategory::TokenLists,
+            "https://tokens.uniswap.org",
+            "load token list",
+        );
+        control.evaluate(
+            EgressCategory::TokenLists,
+            "https://tokens.uniswap.org/",
+            "load token list",
+        );
+        control
+            .set_category_mode(EgressCategory::Swap, CategoryMode::Disabled)
+            .unwrap();
+        control.evaluate(EgressCategory::Swap, "https://api.0x.org/swap", "quote");
+
+        let log = control.log();
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[0].purpose, "load token list");
+        assert_eq!(log[2].verdict, EgressVerdict::Denied);
+
+        let contacted = control.contacted_hosts();
+        assert_eq!(contacted[&EgressCategory::TokenLists], vec!["tokens.uniswap.org"]);
+        assert!(!contacted.contains_key(&EgressCategory::Swap));
+
+        for _ in 0..EGRESS_LOG_CAPACITY {
+            control.evaluate(EgressCategory::Rpc, "http://localhost:8545", "poll");
+        }
+        assert_eq!(control.log().len(), EGRESS_LOG_CAPACITY);
+    }
+
+    #[test]
+    fn test_hosts_awaiting_consent() {
+        let control = consented(EgressPolicy::privacy_first());
+        control.evaluate(EgressCategory::PriceData, "https://api.coingecko.com/a", "prices");
+        control.evaluate(EgressCategory::TokenLists, "https://tokens.uniswap.org", "token list");
+        control.evaluate(EgressCategory::PriceData, "https://api.coingecko.com/b", "prices");
+        control.evaluate(EgressCategory::Rpc, "https://rpc.pulsechain.com", "balance");
+
+        let hosts: Vec<_> = control.awaiting_consent().into_iter().map(|e| e.host).collect();
+        assert_eq!(hosts, vec!["api.coingecko.com", "tokens.uniswap.org"]);
+
+        control.set_host("api.coingecko.com", false).unwrap();
+        let hosts: Vec<_> = control.awaiting_consent().into_iter().map(|e| e.host).collect();
+        assert_eq!(hosts, vec!["tokens.uniswap.org"]);
+    }
+
+    #[test]
+    fn test_first_run_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("egress_policy.json");
+
+        let control = EgressControl::load_from(path.clone());
+        control
+            .complete_first_run(BTreeMap::from([(EgressCategory::PriceData, CategoryMode::Disabled)]))
+            .unwrap();
+
+        let reloaded = EgressControl::load_from(path);
+        assert!(!reloaded.needs_first_run());
+        assert_eq!(
+            reloaded.policy().mode(EgressCategory::PriceData),
+            CategoryMode::Disabled
+        );
+        assert_eq!(reloaded.policy().mode(EgressCategory::Rpc), CategoryMode::Allow);
+    }
+}
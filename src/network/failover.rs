@@ -0,0 +1,328 @@
+//! RPC failover
+//!
+//! Wraps the prioritized RPC endpoints of a network. Requests go to the
+//! preferred endpoint and, on an error or timeout, are retried on the next
+//! one. Every outcome feeds the endpoint's [`EndpointScore`]; endpoints that
+//! keep failing are marked degraded and skipped until their cooldown expires,
+//! so traffic rotates away from them automatically.
+
+use super::health::{self, EndpointScore};
+use super::{AlloyCoreProvider, NetworkId};
+use crate::config::{NetworkSettings, SectionWatch};
+use crate::telemetry::metrics;
+use alloy::providers::ProviderBuilder;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Default per-endpoint request timeout
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time a degraded endpoint is skipped
+pub const DEFAULT_DEGRADED_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Every endpoint of a network failed
+#[derive(Error, Debug, Clone)]
+#[error("all {} RPC endpoints failed: {}", .attempts.len(), format_attempts(.attempts))]
+pub struct FailoverError {
+    /// Endpoint URL and error, in the order tried
+    pub attempts: Vec<(String, String)>,
+}
+
+fn format_attempts(attempts: &[(String, String)]) -> String {
+    attempts
+        .iter()
+        .map(|(url, error)| format!("{url}: {error}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    provider: AlloyCoreProvider,
+}
+
+/// Provider over a network's prioritized RPC endpoints with automatic failover
+#[derive(Debug)]
+pub struct FailoverProvider {
+    network: NetworkId,
+    endpoints: Vec<Endpoint>,
+    scores: Mutex<Vec<EndpointScore>>,
+    /// Endpoint that served the last successful request
+    active: AtomicUsize,
+    request_timeout: Duration,
+    degraded_cooldown: Duration,
+    /// Timeouts from the `network` settings, read on every request
+    settings: Option<SectionWatch<NetworkSettings>>,
+}
+
+impl FailoverProvider {
+    /// Create a failover provider; URLs that don't parse are skipped
+    ///
+    /// Returns `None` if no URL is usable.
+    pub fn new(network: NetworkId, urls: &[&str]) -> Option<Self> {
+        let endpoints: Vec<Endpoint> = urls
+            .iter()
+            .filter_map(|url| match url.parse::<reqwest::Url>() {
+                Ok(parsed) => Some(Endpoint {
+                    url: url.to_string(),
+                    provider: ProviderBuilder::new().connect_http(parsed),
+                }),
+                Err(e) => {
+                    tracing::warn!("❌ Skipping invalid RPC URL {} for network {}: {}", url, network.0, e);
+                    None
+                }
+            })
+            .collect();
+
+        if endpoints.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            network,
+            scores: Mutex::new(vec![EndpointScore::default(); endpoints.len()]),
+            endpoints,
+            active: AtomicUsize::new(0),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            degraded_cooldown: DEFAULT_DEGRADED_COOLDOWN,
+            settings: None,
+        })
+    }
+
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn with_degraded_cooldown(mut self, cooldown: Duration) -> Self {
+        self.degraded_cooldown = cooldown;
+        self
+    }
+
+    /// Take the timeouts from the `network` settings section, following changes
+    ///
+    /// Overrides [`Self::with_request_timeout`] and [`Self::with_degraded_cooldown`].
+    pub fn with_settings(mut self, settings: SectionWatch<NetworkSettings>) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    fn request_timeout(&self) -> Duration {
+        self.settings
+            .as_ref()
+            .map_or(self.request_timeout, |settings| settings.latest().request_timeout())
+    }
+
+    fn degraded_cooldown(&self) -> Duration {
+        self.settings
+            .as_ref()
+            .map_or(self.degraded_cooldown, |settings| settings.latest().degraded_cooldown())
+    }
+
+    /// Provider of the highest-priority usable endpoint
+    pub fn primary(&self) -> &AlloyCoreProvider {
+        &self.endpoints[0].provider
+    }
+
+    /// Provider of the endpoint that served the last successful request
+    pub fn active(&self) -> &AlloyCoreProvider {
+        &self.endpoints[self.active.load(Ordering::Relaxed)].provider
+    }
+
+    /// URL of the endpoint that served the last successful request
+    pub fn active_url(&self) -> &str {
+        &self.endpoints[self.active.load(Ordering::Relaxed)].url
+    }
+
+    /// Endpoint URLs with their current health scores, in priority order
+    pub fn scores(&self) -> Vec<(String, EndpointScore)> {
+        let scores = self.lock_scores();
+        self.endpoints
+            .iter()
+            .zip(scores.iter())
+            .map(|(endpoint, score)| (endpoint.url.clone(), score.clone()))
+            .collect()
+    }
+
+    /// Order in which endpoints are tried
+    ///
+    /// Healthy endpoints in priority order first, then degraded endpoints by
+    /// score as a last resort.
+    fn attempt_order(&self) -> Vec<usize> {
+        let cooldown = self.degraded_cooldown();
+        let scores = self.lock_scores();
+        let (mut healthy, mut degraded): (Vec<usize>, Vec<usize>) =
+            (0..self.endpoints.len()).partition(|&i| !scores[i].is_degraded(cooldown));
+        degraded.sort_by(|&a, &b| scores[b].score.total_cmp(&scores[a].score));
+        healthy.append(&mut degraded);
+        healthy
+    }
+
+    /// Run a request, failing over to the next endpoint on error or timeout
+    pub async fn call<T, E, F, Fut>(&self, request: F) -> std::result::Result<T, FailoverError>
+    where
+        E: Display,
+        F: Fn(AlloyCoreProvider) -> Fut,
+        Fut: Future<Output = std::result::Result<T, E>>,
+    {
+        let mut attempts = Vec::new();
+        let request_started = Instant::now();
+        let request_timeout = self.request_timeout();
+
+        for index in self.attempt_order() {
+            let endpoint = &self.endpoints[index];
+            let started = Instant::now();
+
+            let error = match tokio::time::timeout(request_timeout, request(endpoint.provider.clone())).await {
+                Ok(Ok(value)) => {
+                    self.lock_scores()[index].record_success(started.elapsed().as_millis() as u64);
+                    let previous = self.active.swap(index, Ordering::Relaxed);
+                    if previous != index {
+                        tracing::warn!(
+                            "🔀 Network {} RPC rotated from {} to {}",
+                            self.network.0,
+                            self.endpoints[previous].url,
+                            endpoint.url
+                        );
+                    }
+                    metrics::record_rpc(self.network.0, request_started.elapsed(), true);
+                    return Ok(value);
+                }
+                Ok(Err(e)) => e.to_string(),
+                Err(_) => format!("timed out after {request_timeout:?}"),
+            };
+
+            tracing::warn!("⚠️ RPC request to {} failed: {}", endpoint.url, error);
+            self.lock_scores()[index].record_failure(error.clone());
+            attempts.push((endpoint.url.clone(), error));
+        }
+
+        metrics::record_rpc(self.network.0, request_started.elapsed(), false);
+        Err(FailoverError { attempts })
+    }
+
+    /// Probe degraded endpoints so recovered ones are used again
+    pub async fn probe_degraded(&self) {
+        let degraded: Vec<usize> = {
+            let cooldown = self.degraded_cooldown();
+            let scores = self.lock_scores();
+            (0..self.endpoints.len())
+                .filter(|&i| scores[i].is_degraded(cooldown))
+                .collect()
+        };
+
+        for index in degraded {
+            let url = &self.endpoints[index].url;
+            match health::check_endpoint_health(url).await {
+                Ok(health) => self.lock_scores()[index].record_probe(&health),
+                Err(e) => self.lock_scores()[index].record_failure(e.to_string()),
+            }
+        }
+    }
+
+    fn lock_scores(&self) -> MutexGuard<'_, Vec<EndpointScore>> {
+        self.scores.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    const URLS: [&str; 3] = ["http://127.0.0.1:1", "http://127.0.0.1:2", "http://127.0.0.1:3"];
+
+    fn provider() -> FailoverProvider {
+        FailoverProvider::new(NetworkId(369), &URLS).unwrap()
+    }
+
+    #[test]
+    fn test_invalid_urls_are_skipped() {
+        let failover = FailoverProvider::new(NetworkId(1), &["not a url", URLS[1]]).unwrap();
+        assert_eq!(failover.scores().len(), 1);
+        assert!(FailoverProvider::new(NetworkId(1), &["not a url"]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_and_rotates_away_from_degraded_endpoint() {
+        let failover = provider();
+        let calls = AtomicU32::new(0);
+
+        // The first two requests hit a failing primary before succeeding on the next endpoint
+        for _ in 0..2 {
+            calls.store(0, Ordering::SeqCst);
+            let value = failover
+                .call(|_| async {
+                    match calls.fetch_add(1, Ordering::SeqCst) {
+                        0 => Err("connection refused"),
+                        _ => Ok(42),
+                    }
+                })
+                .await
+                .unwrap();
+            assert_eq!(value, 42);
+        }
+        assert_eq!(failover.active_url(), URLS[1]);
+
+        // The primary is now degraded and skipped entirely
+        calls.store(0, Ordering::SeqCst);
+        failover
+            .call(|_| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, String>(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(failover.attempt_order(), vec![1, 2, 0]);
+
+        let scores = failover.scores();
+        assert_eq!(scores[0].1.consecutive_failures, 2);
+        assert_eq!(scores[1].1.consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_error_lists_every_attempt() {
+        let failover = provider().with_request_timeout(Duration::from_millis(20));
+
+        let error = failover
+            .call(|_| async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                Ok::<(), String>(())
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.attempts.len(), 3);
+        assert!(error.to_string().contains("timed out"));
+    }
+
+    #[test]
+    fn test_degraded_endpoints_are_retried_after_cooldown() {
+        let failover = provider().with_degraded_cooldown(Duration::ZERO);
+        failover.lock_scores()[0].record_failure("down");
+        failover.lock_scores()[0].record_failure("down");
+        assert_eq!(failover.attempt_order(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_settings_changes_apply_to_a_running_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = crate::config::SettingsStore::open(dir.path().join("settings.json")).unwrap();
+        let failover = provider().with_settings(store.subscribe_section());
+        failover.lock_scores()[0].record_failure("down");
+        failover.lock_scores()[0].record_failure("down");
+        assert_eq!(failover.attempt_order(), vec![1, 2, 0]);
+
+        let mut settings = store.current().as_ref().clone();
+        settings.network.degraded_cooldown_secs = 0;
+        settings.network.request_timeout_secs = 3;
+        store.update(settings).unwrap();
+        assert_eq!(failover.attempt_order(), vec![0, 1, 2]);
+        assert_eq!(failover.request_timeout(), Duration::from_secs(3));
+    }
+}
@@ -0,0 +1,186 @@
+//! Network-aware fee display
+//!
+//! Gas is paid in the network's native token, which is ETH only on Ethereum
+//! and its rollups: BSC charges BNB, Polygon MATIC and PulseChain PLS, each
+//! with its own price. [`FeeFormatter`] converts wei amounts into the right
+//! symbol and, when a price is known, into USD, and computes the worst-case
+//! cost `gas_limit × max_fee_per_gas` the confirm screen shows.
+
+use super::NetworkConfig;
+use crate::tokens::oracle::MultiSourcePriceOracle;
+use alloy::primitives::utils::format_units;
+use alloy::primitives::{Address, U256};
+use std::fmt;
+
+/// Decimals of every EVM native token this wallet supports
+pub const NATIVE_DECIMALS: u8 = 18;
+
+/// Decimal places shown for native amounts unless the network prefers fewer
+const DISPLAY_DECIMALS: usize = 6;
+
+/// Upper bound on what a transaction can cost: `gas_limit × max_fee_per_gas`
+pub fn max_cost_wei(gas_limit: u64, max_fee_per_gas: U256) -> U256 {
+    U256::from(gas_limit).saturating_mul(max_fee_per_gas)
+}
+
+/// A fee in the native token, with its USD value when priced
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeAmount {
+    pub wei: U256,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Decimal places shown
+    pub precision: usize,
+    pub usd: Option<f64>,
+}
+
+impl FeeAmount {
+    /// Native amount with up to `precision` decimals, e.g. `0.00042 PLS`
+    pub fn native(&self) -> String {
+        let precision = self.precision.max(1);
+        let formatted = format_units(self.wei, self.decimals).unwrap_or_else(|_| "0".to_string());
+        let (whole, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+        let fraction = fraction[..fraction.len().min(precision)].trim_end_matches('0');
+
+        if fraction.is_empty() {
+            if whole == "0" && !self.wei.is_zero() {
+                return format!("<0.{}1 {}", "0".repeat(precision - 1), self.symbol);
+            }
+            format!("{whole} {}", self.symbol)
+        } else {
+            format!("{whole}.{fraction} {}", self.symbol)
+        }
+    }
+
+    /// USD value, e.g. `$1.23`, or `None` when unpriced
+    pub fn usd_display(&self) -> Option<String> {
+        self.usd.map(|usd| {
+            if usd > 0.0 && usd < 0.01 {
+                "<$0.01".to_string()
+            } else {
+                format!("${usd:.2}")
+            }
+        })
+    }
+}
+
+impl fmt::Display for FeeAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.usd_display() {
+            Some(usd) => write!(f, "{} (≈ {usd})", self.native()),
+            None => f.write_str(&self.native()),
+        }
+    }
+}
+
+/// Formats gas costs in a network's native token
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeFormatter {
+    chain_id: u64,
+    symbol: String,
+    decimals: u8,
+    precision: usize,
+    native_usd: Option<f64>,
+}
+
+impl FeeFormatter {
+    pub fn new(chain_id: u64, symbol: impl Into<String>) -> Self {
+        Self {
+            chain_id,
+            symbol: symbol.into(),
+            decimals: NATIVE_DECIMALS,
+            precision: DISPLAY_DECIMALS,
+            native_usd: None,
+        }
+    }
+
+    /// Formatter using the network's symbol and preferred precision
+    pub fn for_network(config: &NetworkConfig) -> Self {
+        Self {
+            precision: config.display().amount_precision,
+            ..Self::new(config.chain_id, config.symbol.clone())
+        }
+    }
+
+    /// Use a known USD price of the native token
+    pub fn with_native_usd(mut self, usd: f64) -> Self {
+        self.native_usd = (usd.is_finite() && usd > 0.0).then_some(usd);
+        self
+    }
+
+    /// Look up the native token price; fees stay unpriced if it fails
+    pub async fn with_oracle(self, oracle: &MultiSourcePriceOracle) -> Self {
+        match oracle.get_price(self.chain_id, Address::ZERO).await {
+            Ok(Some(price)) => self.with_native_usd(price.price_usd),
+            Ok(None) => self,
+            Err(e) => {
+                tracing::debug!("No native price for chain {}: {}", self.chain_id, e);
+                self
+            }
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn native_usd(&self) -> Option<f64> {
+        self.native_usd
+    }
+
+    /// Express `wei` of the native token
+    pub fn amount(&self, wei: U256) -> FeeAmount {
+        let usd = self.native_usd.and_then(|price| {
+            let native: f64 = format_units(wei, self.decimals).ok()?.parse().ok()?;
+            Some(native * price)
+        });
+        FeeAmount {
+            wei,
+            symbol: self.symbol.clone(),
+            decimals: self.decimals,
+            precision: self.precision,
+            usd,
+        }
+    }
+
+    /// Cost of `gas` units at `gas_price` wei
+    pub fn cost(&self, gas: u64, gas_price: U256) -> FeeAmount {
+        self.amount(max_cost_wei(gas, gas_price))
+    }
+
+    /// Worst-case cost shown on the confirm screen
+    pub fn max_cost(&self, gas_limit: u64, max_fee_per_gas: U256) -> FeeAmount {
+        self.amount(max_cost_wei(gas_limit, max_fee_per_gas))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_max_cost_in_native_symbol() {
+        let pulse = FeeFormatter::new(369, "PLS");
+        // 21,000 gas at 2,000,000 gwei is 42 PLS
+        let fee = pulse.max_cost(21_000, U256::from(2_000_000 * GWEI));
+        assert_eq!(fee.wei, U256::from(42u64) * U256::from(10u64).pow(U256::from(18)));
+        assert_eq!(fee.to_string(), "42 PLS");
+
+        let bsc = FeeFormatter::new(56, "BNB").with_native_usd(600.0);
+        let fee = bsc.cost(21_000, U256::from(3 * GWEI));
+        assert_eq!(fee.native(), "0.000063 BNB");
+        assert_eq!(fee.usd_display().as_deref(), Some("$0.04"));
+        assert_eq!(fee.to_string(), "0.000063 BNB (≈ $0.04)");
+    }
+
+    #[test]
+    fn test_tiny_and_overflowing_amounts() {
+        let eth = FeeFormatter::new(1, "ETH").with_native_usd(f64::NAN);
+        assert!(eth.native_usd().is_none());
+        assert_eq!(eth.amount(U256::from(1u64)).native(), "<0.000001 ETH");
+        assert_eq!(eth.amount(U256::ZERO).native(), "0 ETH");
+        assert_eq!(max_cost_wei(u64::MAX, U256::MAX), U256::MAX);
+    }
+}
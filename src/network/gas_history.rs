@@ -0,0 +1,383 @@
+//! Gas price history
+//!
+//! Periodically sampled base fee and gas price per network, kept in a bounded
+//! local time series. Backs the "gas over the last 24h" chart and the
+//! "cheapest hour of day" statistics that help users time non-urgent
+//! transactions.
+//!
+//! History is stored in `<config_dir>/vaughan/gas_history.json`.
+
+use super::NetworkId;
+use crate::error::{ConfigurationError, NetworkError, Result};
+use alloy::eips::BlockNumberOrTag;
+use alloy::providers::Provider;
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Samples older than this are discarded
+pub const DEFAULT_RETENTION_DAYS: i64 = 14;
+
+/// Upper bound on stored samples per network
+pub const DEFAULT_MAX_SAMPLES: usize = 20_000;
+
+/// Get the storage path for gas history
+pub fn get_gas_history_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("vaughan");
+    path.push("gas_history.json");
+    path
+}
+
+/// A single gas price observation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSample {
+    pub timestamp: DateTime<Utc>,
+    /// Base fee of the latest block, on EIP-1559 networks
+    pub base_fee_per_gas: Option<u64>,
+    /// `eth_gasPrice` at sampling time
+    pub gas_price: u64,
+}
+
+/// Aggregated gas prices for one chart bucket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasChartPoint {
+    pub start: DateTime<Utc>,
+    pub samples: usize,
+    pub min_gas_price: u64,
+    pub avg_gas_price: u64,
+    pub max_gas_price: u64,
+    pub avg_base_fee: Option<u64>,
+}
+
+/// Average gas price during an hour of the day (UTC)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HourlyGasStat {
+    pub hour: u32,
+    pub samples: usize,
+    pub avg_gas_price: u64,
+}
+
+/// Bounded gas price time series, keyed by chain ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasHistory {
+    #[serde(default)]
+    networks: HashMap<u64, VecDeque<GasSample>>,
+    #[serde(skip, default = "default_retention")]
+    retention: Duration,
+    #[serde(skip, default = "default_max_samples")]
+    max_samples: usize,
+}
+
+fn default_retention() -> Duration {
+    Duration::days(DEFAULT_RETENTION_DAYS)
+}
+
+fn default_max_samples() -> usize {
+    DEFAULT_MAX_SAMPLES
+}
+
+impl Default for GasHistory {
+    fn default() -> Self {
+        Self {
+            networks: HashMap::new(),
+            retention: default_retention(),
+            max_samples: default_max_samples(),
+        }
+    }
+}
+
+impl GasHistory {
+    /// Create an empty history with custom retention limits
+    pub fn with_retention(retention: Duration, max_samples: usize) -> Self {
+        Self {
+            retention,
+            max_samples: max_samples.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Load history from the default location
+    pub fn load() -> Self {
+        Self::load_from(&get_gas_history_path())
+    }
+
+    /// Load history from a file, falling back to an empty history
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid gas history file {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save history to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_gas_history_path())
+    }
+
+    /// Save history to a file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string(self).map_err(|e| ConfigurationError::ParseError {
+            message: format!("Failed to serialize gas history: {e}"),
+        })?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Record a sample, dropping samples past retention
+    pub fn record(&mut self, network: NetworkId, sample: GasSample) {
+        let samples = self.networks.entry(network.chain_id()).or_default();
+
+        // Keep the series ordered; out-of-order samples are inserted in place
+        let position = samples.partition_point(|s| s.timestamp <= sample.timestamp);
+        samples.insert(position, sample);
+
+        let newest = samples.back().map_or(sample.timestamp, |s| s.timestamp);
+        let cutoff = newest - self.retention;
+        while samples.front().is_some_and(|s| s.timestamp < cutoff) || samples.len() > self.max_samples {
+            samples.pop_front();
+        }
+    }
+
+    /// Samples for a network within `[from, to]`, oldest first
+    pub fn samples(&self, network: NetworkId, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<GasSample> {
+        self.networks
+            .get(&network.chain_id())
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter(|s| s.timestamp >= from && s.timestamp <= to)
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Most recent sample for a network
+    pub fn latest(&self, network: NetworkId) -> Option<GasSample> {
+        self.networks.get(&network.chain_id())?.back().copied()
+    }
+
+    /// Chart data for the `window` ending at `now`, split into `buckets` equal buckets
+    ///
+    /// Buckets without samples are omitted.
+    pub fn chart(&self, network: NetworkId, now: DateTime<Utc>, window: Duration, buckets: u32) -> Vec<GasChartPoint> {
+        let buckets = buckets.max(1);
+        let start = now - window;
+        let bucket_len = window / buckets as i32;
+        let bucket_ms = bucket_len.num_milliseconds().max(1);
+
+        let mut grouped: Vec<Vec<GasSample>> = vec![Vec::new(); buckets as usize];
+        for sample in self.samples(network, start, now) {
+            let index = ((sample.timestamp - start).num_milliseconds() / bucket_ms).clamp(0, buckets as i64 - 1);
+            grouped[index as usize].push(sample);
+        }
+
+        grouped
+            .into_iter()
+            .enumerate()
+            .filter(|(_, samples)| !samples.is_empty())
+            .map(|(i, samples)| {
+                let prices: Vec<u64> = samples.iter().map(|s| s.gas_price).collect();
+                let base_fees: Vec<u64> = samples.iter().filter_map(|s| s.base_fee_per_gas).collect();
+                GasChartPoint {
+                    start: start + bucket_len * i as i32,
+                    samples: samples.len(),
+                    min_gas_price: prices.iter().copied().min().unwrap_or(0),
+                    avg_gas_price: average(&prices),
+                    max_gas_price: prices.iter().copied().max().unwrap_or(0),
+                    avg_base_fee: (!base_fees.is_empty()).then(|| average(&base_fees)),
+                }
+            })
+            .collect()
+    }
+
+    /// Hourly chart data for the 24 hours ending at `now`
+    pub fn last_24h(&self, network: NetworkId, now: DateTime<Utc>) -> Vec<GasChartPoint> {
+        self.chart(network, now, Duration::hours(24), 24)
+    }
+
+    /// Average gas price per hour of day (UTC) over all retained samples
+    pub fn hourly_stats(&self, network: NetworkId) -> Vec<HourlyGasStat> {
+        let mut by_hour: [Vec<u64>; 24] = Default::default();
+        if let Some(samples) = self.networks.get(&network.chain_id()) {
+            for sample in samples {
+                by_hour[sample.timestamp.hour() as usize].push(sample.gas_price);
+            }
+        }
+
+        by_hour
+            .iter()
+            .enumerate()
+            .filter(|(_, prices)| !prices.is_empty())
+            .map(|(hour, prices)| HourlyGasStat {
+                hour: hour as u32,
+                samples: prices.len(),
+                avg_gas_price: average(prices),
+            })
+            .collect()
+    }
+
+    /// Hour of day (UTC) with the lowest average gas price
+    ///
+    /// Hours with fewer than `min_samples` samples are ignored so a single
+    /// lucky reading doesn't win.
+    pub fn cheapest_hour(&self, network: NetworkId, min_samples: usize) -> Option<HourlyGasStat> {
+        self.hourly_stats(network)
+            .into_iter()
+            .filter(|stat| stat.samples >= min_samples)
+            .min_by_key(|stat| (stat.avg_gas_price, stat.hour))
+    }
+}
+
+fn average(values: &[u64]) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    (values.iter().map(|v| *v as u128).sum::<u128>() / values.len() as u128) as u64
+}
+
+/// Take a gas sample from a provider
+pub async fn sample_gas<P: Provider>(provider: &P) -> Result<GasSample> {
+    let gas_price = provider.get_gas_price().await.map_err(|e| NetworkError::RpcError {
+        message: format!("Failed to fetch gas price: {e}"),
+    })?;
+    let base_fee_per_gas = provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|block| block.header.base_fee_per_gas);
+
+    Ok(GasSample {
+        timestamp: Utc::now(),
+        base_fee_per_gas,
+        gas_price: u64::try_from(gas_price).unwrap_or(u64::MAX),
+    })
+}
+
+/// Sample gas prices for a network every `interval` until the task is aborted
+///
+/// History is saved to the default location after each sample.
+pub fn spawn_gas_recorder<P>(
+    history: Arc<Mutex<GasHistory>>,
+    provider: P,
+    network: NetworkId,
+    interval: std::time::Duration,
+) -> tokio::task::JoinHandle<()>
+where
+    P: Provider + Send + Sync + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match sample_gas(&provider).await {
+                Ok(sample) => {
+                    let mut history = history.lock().await;
+                    history.record(network, sample);
+                    if let Err(e) = history.save() {
+                        tracing::warn!("⚠️ Failed to save gas history: {}", e);
+                    }
+                }
+                Err(e) => tracing::debug!("Gas sample for network {} failed: {}", network.0, e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const NETWORK: NetworkId = NetworkId(369);
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, hour, minute, 0).unwrap()
+    }
+
+    fn sample(timestamp: DateTime<Utc>, gas_price: u64) -> GasSample {
+        GasSample {
+            timestamp,
+            base_fee_per_gas: Some(gas_price - 1),
+            gas_price,
+        }
+    }
+
+    #[test]
+    fn test_retention_is_bounded() {
+        let mut history = GasHistory::with_retention(Duration::hours(2), 3);
+        history.record(NETWORK, sample(at(0, 0), 10));
+        history.record(NETWORK, sample(at(3, 0), 20));
+        assert_eq!(history.samples(NETWORK, at(0, 0), at(23, 0)).len(), 1);
+
+        for minute in 1..5 {
+            history.record(NETWORK, sample(at(3, minute), 20));
+        }
+        assert_eq!(history.samples(NETWORK, at(0, 0), at(23, 0)).len(), 3);
+        assert_eq!(history.latest(NETWORK).unwrap().timestamp, at(3, 4));
+    }
+
+    #[test]
+    fn test_chart_buckets() {
+        let mut history = GasHistory::default();
+        history.record(NETWORK, sample(at(10, 5), 10));
+        history.record(NETWORK, sample(at(10, 35), 30));
+        history.record(NETWORK, sample(at(12, 0), 50));
+        history.record(NetworkId(1), sample(at(10, 0), 999));
+
+        let chart = history.chart(NETWORK, at(13, 0), Duration::hours(3), 3);
+        assert_eq!(chart.len(), 2);
+        assert_eq!(chart[0].start, at(10, 0));
+        assert_eq!(chart[0].samples, 2);
+        assert_eq!(chart[0].min_gas_price, 10);
+        assert_eq!(chart[0].avg_gas_price, 20);
+        assert_eq!(chart[0].max_gas_price, 30);
+        assert_eq!(chart[0].avg_base_fee, Some(19));
+        assert_eq!(chart[1].start, at(12, 0));
+
+        assert_eq!(history.last_24h(NETWORK, at(13, 0)).len(), 2);
+    }
+
+    #[test]
+    fn test_cheapest_hour() {
+        let mut history = GasHistory::default();
+        for day in 0..3 {
+            let offset = Duration::days(day);
+            history.record(NETWORK, sample(at(3, 0) - offset, 5));
+            history.record(NETWORK, sample(at(15, 0) - offset, 40));
+        }
+        history.record(NETWORK, sample(at(8, 0), 1));
+
+        assert_eq!(history.hourly_stats(NETWORK).len(), 3);
+        let cheapest = history.cheapest_hour(NETWORK, 2).unwrap();
+        assert_eq!(cheapest.hour, 3);
+        assert_eq!(cheapest.avg_gas_price, 5);
+        assert_eq!(history.cheapest_hour(NETWORK, 1).unwrap().hour, 8);
+        assert!(history.cheapest_hour(NetworkId(56), 1).is_none());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gas_history.json");
+
+        let mut history = GasHistory::default();
+        history.record(NETWORK, sample(at(1, 0), 7));
+        history.save_to(&path).unwrap();
+
+        let loaded = GasHistory::load_from(&path);
+        assert_eq!(loaded.latest(NETWORK), history.latest(NETWORK));
+    }
+}
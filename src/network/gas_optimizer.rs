@@ -19,7 +19,7 @@ use tokio::sync::RwLock;
 use tracing::{info, warn};
 
 use crate::error::{NetworkError, Result};
-use crate::network::{NetworkId, NetworkManager};
+use crate::network::{NetworkDisplay, NetworkId, NetworkManager};
 
 /// Advanced gas optimization strategies
 #[derive(Debug, Clone)]
@@ -114,18 +114,15 @@ impl GasOptimizer {
             .await?;
 
         // Estimate transaction times
-        let time_estimate = self.estimate_transaction_times(&congestion, gas_price).await;
+        let net_display = NetworkDisplay::for_chain(network_id.chain_id());
+        let time_estimate = self.estimate_transaction_times(&congestion, &net_display);
 
         // Calculate total estimated cost
         let estimated_cost = gas_limit * gas_price;
 
         info!("✅ Optimized gas estimation completed:");
         info!("   Gas Limit: {} units", gas_limit);
-        info!(
-            "   Gas Price: {} wei ({:.2} Gwei)",
-            gas_price,
-            gas_price.to::<u128>() as f64 / 1e9
-        );
+        info!("   Gas Price: {} wei ({})", gas_price, net_display.format_gas_price(gas_price));
         info!("   Estimated Cost: {} wei", estimated_cost);
         info!("   Network Congestion: {:?}", congestion);
 
@@ -304,28 +301,20 @@ impl GasOptimizer {
     }
 
     /// Estimate transaction confirmation times
-    async fn estimate_transaction_times(&self, congestion: &NetworkCongestion, _gas_price: U256) -> TimeEstimate {
-        match congestion {
-            NetworkCongestion::Low => TimeEstimate {
-                fast: 15,     // 15 seconds
-                standard: 30, // 30 seconds
-                safe: 60,     // 1 minute
-            },
-            NetworkCongestion::Medium => TimeEstimate {
-                fast: 30,     // 30 seconds
-                standard: 60, // 1 minute
-                safe: 120,    // 2 minutes
-            },
-            NetworkCongestion::High => TimeEstimate {
-                fast: 60,      // 1 minute
-                standard: 180, // 3 minutes
-                safe: 300,     // 5 minutes
-            },
-            NetworkCongestion::Critical => TimeEstimate {
-                fast: 180,     // 3 minutes
-                standard: 600, // 10 minutes
-                safe: 900,     // 15 minutes
-            },
+    ///
+    /// Congestion sets how many blocks each speed waits; the network's block
+    /// time turns that into seconds.
+    fn estimate_transaction_times(&self, congestion: &NetworkCongestion, display: &NetworkDisplay) -> TimeEstimate {
+        let (fast, standard, safe) = match congestion {
+            NetworkCongestion::Low => (1, 2, 5),
+            NetworkCongestion::Medium => (2, 5, 10),
+            NetworkCongestion::High => (5, 15, 25),
+            NetworkCongestion::Critical => (15, 50, 75),
+        };
+        TimeEstimate {
+            fast: display.confirmation_time(fast).as_secs(),
+            standard: display.confirmation_time(standard).as_secs(),
+            safe: display.confirmation_time(safe).as_secs(),
         }
     }
 
@@ -14,17 +14,12 @@ pub struct EndpointHealth {
 
 /// Check endpoint health
 pub async fn check_endpoint_health(url: &str) -> Result<EndpointHealth> {
-    use alloy::providers::{Provider, ProviderBuilder};
+    use alloy::providers::Provider;
     use std::time::Instant;
 
     let start_time = Instant::now();
 
-    let parsed_url = url
-        .parse()
-        .map_err(|e| crate::error::VaughanError::Network(crate::error::NetworkError::RpcError {
-            message: format!("Invalid URL: {}", e),
-        }))?;
-    let provider = ProviderBuilder::new().connect_http(parsed_url);
+    let provider = crate::network::connect_rpc(url, "endpoint health check")?;
 
     // Test basic connectivity
     match provider.get_block_number().await {
@@ -57,3 +52,103 @@ pub async fn check_endpoint_health(url: &str) -> Result<EndpointHealth> {
         }),
     }
 }
+
+/// Consecutive failures after which an endpoint is considered degraded
+const DEGRADED_AFTER_FAILURES: u32 = 2;
+
+/// Success score below which an endpoint is considered degraded
+const DEGRADED_SCORE: f64 = 0.5;
+
+/// Weight of the newest observation in the rolling averages
+const SMOOTHING: f64 = 0.3;
+
+/// Rolling health score of an RPC endpoint, fed by live requests and probes
+#[derive(Debug, Clone)]
+pub struct EndpointScore {
+    /// Exponentially weighted success rate, 0.0 to 1.0
+    pub score: f64,
+    /// Exponentially weighted latency of successful requests
+    pub latency_ms: Option<u64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    degraded_since: Option<std::time::Instant>,
+}
+
+impl Default for EndpointScore {
+    fn default() -> Self {
+        Self {
+            score: 1.0,
+            latency_ms: None,
+            consecutive_failures: 0,
+            last_error: None,
+            degraded_since: None,
+        }
+    }
+}
+
+impl EndpointScore {
+    pub fn record_success(&mut self, latency_ms: u64) {
+        self.score = self.score * (1.0 - SMOOTHING) + SMOOTHING;
+        self.latency_ms = Some(match self.latency_ms {
+            Some(avg) => (avg as f64 * (1.0 - SMOOTHING) + latency_ms as f64 * SMOOTHING) as u64,
+            None => latency_ms,
+        });
+        self.consecutive_failures = 0;
+        if self.score >= DEGRADED_SCORE {
+            self.degraded_since = None;
+        }
+    }
+
+    pub fn record_failure(&mut self, error: impl Into<String>) {
+        self.score *= 1.0 - SMOOTHING;
+        self.consecutive_failures += 1;
+        self.last_error = Some(error.into());
+        if self.consecutive_failures >= DEGRADED_AFTER_FAILURES || self.score < DEGRADED_SCORE {
+            // Restart the cooldown on every failure while degraded
+            self.degraded_since = Some(std::time::Instant::now());
+        }
+    }
+
+    /// Feed the result of a [`check_endpoint_health`] probe
+    pub fn record_probe(&mut self, health: &EndpointHealth) {
+        if health.is_responsive && !health.is_syncing {
+            self.record_success(health.latency_ms);
+        } else {
+            self.record_failure("health check failed");
+        }
+    }
+
+    /// Whether the endpoint failed recently and should be avoided
+    ///
+    /// After `cooldown` a degraded endpoint is tried again; the next failure
+    /// marks it degraded for another cooldown.
+    pub fn is_degraded(&self, cooldown: std::time::Duration) -> bool {
+        self.degraded_since.is_some_and(|since| since.elapsed() < cooldown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_endpoint_score_degrades_and_recovers() {
+        let mut score = EndpointScore::default();
+        score.record_success(100);
+        assert!(!score.is_degraded(Duration::from_secs(60)));
+
+        score.record_failure("timeout");
+        assert!(!score.is_degraded(Duration::from_secs(60)));
+        score.record_failure("timeout");
+        assert!(score.is_degraded(Duration::from_secs(60)));
+        assert!(!score.is_degraded(Duration::ZERO));
+        assert_eq!(score.last_error.as_deref(), Some("timeout"));
+
+        for _ in 0..3 {
+            score.record_success(50);
+        }
+        assert!(!score.is_degraded(Duration::from_secs(60)));
+        assert!(score.latency_ms.unwrap() < 100);
+    }
+}
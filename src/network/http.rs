@@ -0,0 +1,248 @@
+//! Shared outbound HTTP client
+//!
+//! Price feeds, token lists, explorers and endpoint validation all talk to
+//! third-party HTTP APIs. Giving each of them its own `reqwest::Client` meant
+//! separate connection pools, no common timeout and no bound on how many
+//! requests could be in flight at once, which is how free-tier APIs end up
+//! rate limiting the wallet.
+//!
+//! [`HttpClient`] wraps one pooled client shared by all of them. Every request
+//! sent through it waits for a slot on its host and a slot in the global cap,
+//! and carries the default timeouts and a `Vaughan-Wallet/<version>` user
+//! agent. Clones share the pool and the limits.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// User agent sent with every request
+pub const USER_AGENT: &str = concat!("Vaughan-Wallet/", env!("CARGO_PKG_VERSION"));
+
+static SHARED: LazyLock<HttpClient> = LazyLock::new(|| HttpClient::new(HttpLimits::default()));
+
+/// Client shared by the whole process
+pub fn shared_client() -> HttpClient {
+    SHARED.clone()
+}
+
+/// Concurrency and timeout limits of an [`HttpClient`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpLimits {
+    /// Requests in flight across all hosts
+    pub max_concurrent: usize,
+    /// Requests in flight to any single host
+    pub max_per_host: usize,
+    /// Whole-request timeout unless a request sets its own
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+}
+
+impl Default for HttpLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 16,
+            max_per_host: 4,
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    client: reqwest::Client,
+    limits: HttpLimits,
+    global: Arc<Semaphore>,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+/// Pooled HTTP client with per-host and global concurrency limits
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    inner: Arc<Inner>,
+}
+
+/// Slots held while a request is in flight
+#[derive(Debug)]
+pub struct HttpPermit {
+    _host: Option<OwnedSemaphorePermit>,
+    _global: Option<OwnedSemaphorePermit>,
+}
+
+impl HttpClient {
+    pub fn new(limits: HttpLimits) -> Self {
+        let limits = HttpLimits {
+            max_concurrent: limits.max_concurrent.max(1),
+            max_per_host: limits.max_per_host.max(1),
+            ..limits
+        };
+        // HTTP client builder should never fail with these basic settings
+        let client = reqwest::Client::builder()
+            .timeout(limits.timeout)
+            .connect_timeout(limits.connect_timeout)
+            .pool_max_idle_per_host(limits.max_per_host)
+            .user_agent(USER_AGENT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            inner: Arc::new(Inner {
+                client,
+                limits,
+                global: Arc::new(Semaphore::new(limits.max_concurrent)),
+                hosts: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    pub fn limits(&self) -> HttpLimits {
+        self.inner.limits
+    }
+
+    /// Underlying client, for APIs that need a `reqwest::Client`
+    ///
+    /// Requests made directly on it share the pool and defaults but bypass
+    /// the concurrency limits.
+    pub fn reqwest(&self) -> &reqwest::Client {
+        &self.inner.client
+    }
+
+    pub fn get(&self, url: &str) -> HttpRequest {
+        self.request(url, self.inner.client.get(url))
+    }
+
+    pub fn post(&self, url: &str) -> HttpRequest {
+        self.request(url, self.inner.client.post(url))
+    }
+
+    fn request(&self, url: &str, builder: reqwest::RequestBuilder) -> HttpRequest {
+        HttpRequest {
+            client: self.clone(),
+            url: url.to_string(),
+            builder,
+        }
+    }
+
+    /// Wait for a request slot on `host`
+    ///
+    /// The host slot is taken first so requests queued behind a busy host do
+    /// not hold global slots other hosts could use.
+    pub async fn acquire(&self, host: &str) -> HttpPermit {
+        let host_semaphore = {
+            let mut hosts = self.inner.hosts.lock().unwrap_or_else(PoisonError::into_inner);
+            hosts
+                .entry(host.to_ascii_lowercase())
+                .or_insert_with(|| Arc::new(Semaphore::new(self.inner.limits.max_per_host)))
+                .clone()
+        };
+        // Semaphores are never closed, so acquiring cannot fail
+        let host = host_semaphore.acquire_owned().await.ok();
+        let global = self.inner.global.clone().acquire_owned().await.ok();
+        HttpPermit {
+            _host: host,
+            _global: global,
+        }
+    }
+
+    /// Requests currently holding a global slot
+    pub fn in_flight(&self) -> usize {
+        self.inner.limits.max_concurrent - self.inner.global.available_permits()
+    }
+}
+
+/// Request being built on an [`HttpClient`]
+#[derive(Debug)]
+pub struct HttpRequest {
+    client: HttpClient,
+    url: String,
+    builder: reqwest::RequestBuilder,
+}
+
+impl HttpRequest {
+    /// URL the request was created with, without any added query
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.builder = self.builder.header(name, value);
+        self
+    }
+
+    pub fn bearer_auth(mut self, token: &str) -> Self {
+        self.builder = self.builder.bearer_auth(token);
+        self
+    }
+
+    pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+        self.builder = self.builder.query(query);
+        self
+    }
+
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        self.builder = self.builder.json(body);
+        self
+    }
+
+    /// Override the client's default timeout for this request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.builder = self.builder.timeout(timeout);
+        self
+    }
+
+    /// Send once a slot is free; the slot is released when the response
+    /// headers arrive
+    pub async fn send(self) -> reqwest::Result<reqwest::Response> {
+        let request = self.builder.build()?;
+        let host = request.url().host_str().unwrap_or_default().to_string();
+        let _permit = self.client.acquire(&host).await;
+        self.client.inner.client.execute(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_per_host_and_global_limits() {
+        let client = HttpClient::new(HttpLimits {
+            max_concurrent: 3,
+            max_per_host: 2,
+            ..HttpLimits::default()
+        });
+        let wait = Duration::from_millis(50);
+
+        let a1 = client.acquire("api.example.com").await;
+        let _a2 = client.acquire("API.example.com").await;
+        assert!(tokio::time::timeout(wait, client.acquire("api.example.com"))
+            .await
+            .is_err());
+
+        let _b = client.acquire("other.example.com").await;
+        assert_eq!(client.in_flight(), 3);
+        // Global cap reached even though this host is idle
+        assert!(tokio::time::timeout(wait, client.acquire("third.example.com"))
+            .await
+            .is_err());
+
+        drop(a1);
+        assert!(tokio::time::timeout(wait, client.acquire("api.example.com"))
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn test_shared_client_defaults() {
+        assert!(USER_AGENT.starts_with("Vaughan-Wallet/"));
+        assert_eq!(shared_client().limits(), HttpLimits::default());
+
+        let zero = HttpClient::new(HttpLimits {
+            max_concurrent: 0,
+            max_per_host: 0,
+            ..HttpLimits::default()
+        });
+        assert_eq!(zero.limits().max_concurrent, 1);
+    }
+}
@@ -3,17 +3,21 @@
 //! This module handles connections to multiple EVM networks including Ethereum,
 //! PulseChain, BSC, Polygon, and custom networks.
 
-use alloy::primitives::{Address, TxHash, U256};
+use alloy::eips::BlockNumberOrTag;
+use alloy::primitives::{Address, TxHash, B256, U256};
 use alloy::providers::fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller};
 use alloy::providers::Identity;
-use alloy::providers::{Provider, ProviderBuilder, RootProvider};
-use alloy::rpc::types::TransactionRequest;
+use alloy::providers::{Provider, RootProvider};
+use alloy::rpc::types::state::StateOverride;
+use alloy::rpc::types::{Block, TransactionReceipt, TransactionRequest};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use crate::error::{NetworkError, Result};
+use crate::performance::rpc_cache::RpcCache;
+use crate::tokens::TokenInfo;
 
 // Type alias for the actual provider type returned by Alloy v1.1
 // Made public for use in controllers (Phase E)
@@ -22,13 +26,42 @@ pub type AlloyCoreProvider = FillProvider<
     RootProvider,
 >;
 
+/// Connect an HTTP provider to an RPC endpoint the egress settings allow
+///
+/// Code that talks to a node outside the [`NetworkManager`] should build its
+/// provider here so the user's RPC consent applies to it too.
+pub fn connect_rpc(url: &str, purpose: &str) -> Result<AlloyCoreProvider> {
+    let parsed = url.parse::<url::Url>().map_err(|e| NetworkError::NetworkError {
+        message: format!("Invalid RPC URL {url}: {e}"),
+    })?;
+    egress::egress().check(egress::EgressCategory::Rpc, url, purpose)?;
+    Ok(alloy::providers::ProviderBuilder::new().connect_http(parsed))
+}
+
+pub mod benchmark;
+pub mod broadcast;
+pub mod clock;
 pub mod config;
+pub mod display;
+pub mod egress;
+pub mod failover;
+pub mod fees;
+pub mod gas_history;
 pub mod gas_optimizer;
 pub mod health;
+pub mod http;
+pub mod nonce_manager;
+pub mod poller;
 pub mod professional;
+pub mod profiles;
+pub mod routing;
+pub mod snapshot;
+pub mod startup;
+pub mod tx_watcher;
 pub mod validation;
 
 pub use config::*;
+pub use display::NetworkDisplay;
 pub use gas_optimizer::*;
 pub use health::*;
 pub use validation::*;
@@ -59,6 +92,9 @@ pub struct NetworkConfig {
     pub block_explorer_url: String,
     pub is_testnet: bool,
     pub is_custom: bool,
+    /// Additional RPC URLs, in priority order, used when `rpc_url` fails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rpc_urls: Vec<String>,
 }
 
 impl NetworkConfig {
@@ -83,6 +119,10 @@ impl NetworkConfig {
             block_explorer_url: "https://etherscan.io".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: vec![
+                "https://eth.drpc.org".to_string(),
+                "https://eth.llamarpc.com".to_string(),
+            ],
         }
     }
 
@@ -97,6 +137,10 @@ impl NetworkConfig {
             block_explorer_url: "https://scan.pulsechain.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: vec![
+                "https://pulsechain-rpc.publicnode.com".to_string(),
+                "https://rpc-pulsechain.g4mm4.io".to_string(),
+            ],
         }
     }
 
@@ -111,6 +155,7 @@ impl NetworkConfig {
             block_explorer_url: "https://scan.v4.testnet.pulsechain.com".to_string(),
             is_testnet: true,
             is_custom: false,
+            fallback_rpc_urls: Vec::new(),
         }
     }
 
@@ -125,6 +170,10 @@ impl NetworkConfig {
             block_explorer_url: "https://bscscan.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: vec![
+                "https://bsc-dataseed2.binance.org".to_string(),
+                "https://bsc-rpc.publicnode.com".to_string(),
+            ],
         }
     }
 
@@ -139,10 +188,25 @@ impl NetworkConfig {
             block_explorer_url: "https://polygonscan.com".to_string(),
             is_testnet: false,
             is_custom: false,
+            fallback_rpc_urls: vec!["https://polygon-bor-rpc.publicnode.com".to_string()],
         }
     }
 }
 
+impl NetworkConfig {
+    /// All RPC URLs in priority order, primary first, without duplicates
+    pub fn rpc_urls(&self) -> Vec<&str> {
+        let mut urls: Vec<&str> = Vec::with_capacity(1 + self.fallback_rpc_urls.len());
+        for url in std::iter::once(&self.rpc_url).chain(&self.fallback_rpc_urls) {
+            let url = url.trim();
+            if !url.is_empty() && !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        urls
+    }
+}
+
 impl std::fmt::Display for NetworkConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} ({})", self.name, self.symbol)
@@ -155,11 +219,48 @@ pub struct NetworkManager {
     networks: HashMap<NetworkId, NetworkConfig>,
     current_network: NetworkId,
     providers: Arc<RwLock<HashMap<NetworkId, AlloyCoreProvider>>>,
+    failover: Arc<RwLock<HashMap<NetworkId, Arc<failover::FailoverProvider>>>>,
+    nonce_manager: Arc<nonce_manager::NonceManager>,
+    tx_watcher: tx_watcher::TxWatcher,
+    /// Consulted before the RPC endpoints; `None` disables caching
+    rpc_cache: Option<Arc<RpcCache>>,
 }
 
 impl NetworkManager {
     /// Create a new network manager with default networks
+    ///
+    /// The initial network is resolved from the stored startup preferences
+    /// (see [`startup`]).
     pub async fn new() -> Result<Self> {
+        let mut manager = Self::with_default_networks();
+        manager.current_network = startup::resolve_startup_network(|id| manager.networks.contains_key(&id));
+        manager.mark_active_rpc();
+
+        // Initialize providers for all networks
+        manager.initialize_providers().await?;
+
+        Ok(manager)
+    }
+
+    /// Create a new network manager starting on a specific network
+    ///
+    /// Falls back to the stored startup preferences if `network_id` is not a known network.
+    pub async fn with_startup_network(network_id: NetworkId) -> Result<Self> {
+        let mut manager = Self::with_default_networks();
+        manager.current_network = if manager.networks.contains_key(&network_id) {
+            network_id
+        } else {
+            tracing::warn!("Unknown startup network {}, using stored preference", network_id.0);
+            startup::resolve_startup_network(|id| manager.networks.contains_key(&id))
+        };
+        manager.mark_active_rpc();
+
+        manager.initialize_providers().await?;
+
+        Ok(manager)
+    }
+
+    fn with_default_networks() -> Self {
         let mut networks = HashMap::new();
 
         // Add default networks
@@ -175,44 +276,111 @@ impl NetworkManager {
         networks.insert(bsc.id, bsc);
         networks.insert(polygon.id, polygon);
 
-        let mut manager = Self {
+        Self {
             networks,
-            current_network: NetworkId(943), // Default to PulseChain Testnet v4 for testing
+            current_network: startup::fallback_network(),
             providers: Arc::new(RwLock::new(HashMap::new())),
-        };
+            failover: Arc::new(RwLock::new(HashMap::new())),
+            nonce_manager: Arc::new(nonce_manager::NonceManager::new()),
+            tx_watcher: tx_watcher::TxWatcher::default(),
+            rpc_cache: Some(Arc::new(RpcCache::default())),
+        }
+    }
 
-        // Initialize providers for all networks
-        manager.initialize_providers().await?;
+    /// Tell the egress control which RPC endpoints the user is working with
+    ///
+    /// Before first-run consent these are the only hosts the wallet contacts.
+    fn mark_active_rpc(&self) {
+        if let Some(config) = self.networks.get(&self.current_network) {
+            egress::egress().set_active_rpc(&config.rpc_urls());
+        }
+    }
 
-        Ok(manager)
+    /// Make the current network usable after it changed
+    ///
+    /// Its providers may be missing if they were blocked while another
+    /// network was active, so they are installed now.
+    async fn activate_current_network(&self) {
+        self.mark_active_rpc();
+        let Some(config) = self.networks.get(&self.current_network) else {
+            return;
+        };
+        if self.failover.read().await.contains_key(&config.id) {
+            return;
+        }
+        if let Err(e) = self.install_providers(config).await {
+            tracing::warn!("❌ Failed to initialize provider for {}: {}", config.name, e);
+        }
     }
 
     /// Initialize providers for all configured networks
     async fn initialize_providers(&mut self) -> Result<()> {
-        let mut providers = self.providers.write().await;
-
-        for (network_id, config) in &self.networks {
-            match config.rpc_url.parse::<reqwest::Url>() {
-                Ok(url) => {
-                    // Create provider with HTTP URL
-                    let provider = ProviderBuilder::new().connect_http(url);
-                    providers.insert(*network_id, provider);
-                    tracing::info!(
-                        "✅ Initialized provider for {} ({}) with 30s timeout",
-                        config.name,
-                        config.rpc_url
-                    );
-                }
-                Err(e) => {
-                    tracing::warn!("❌ Failed to initialize provider for {}: {}", config.name, e);
-                }
+        for config in self.networks.values() {
+            match self.install_providers(config).await {
+                Ok(()) => tracing::info!(
+                    "✅ Initialized provider for {} ({} endpoint(s))",
+                    config.name,
+                    config.rpc_urls().len()
+                ),
+                Err(e) => tracing::warn!("❌ Failed to initialize provider for {}: {}", config.name, e),
             }
         }
 
-        tracing::info!("🌐 Initialized {} network providers", providers.len());
+        tracing::info!("🌐 Initialized {} network providers", self.providers.read().await.len());
+        Ok(())
+    }
+
+    /// Build the failover provider for a network from its prioritized RPC URLs
+    ///
+    /// Endpoints the user hasn't permitted in the egress settings are skipped.
+    async fn install_providers(&self, config: &NetworkConfig) -> Result<()> {
+        let purpose = format!("{} RPC", config.name);
+        let mut blocked = None;
+        let urls: Vec<&str> = config
+            .rpc_urls()
+            .into_iter()
+            .filter(
+                |url| match egress::egress().check(egress::EgressCategory::Rpc, url, &purpose) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        blocked = Some(e);
+                        false
+                    }
+                },
+            )
+            .collect();
+        if let (true, Some(e)) = (urls.is_empty(), blocked) {
+            return Err(e);
+        }
+
+        let failover = failover::FailoverProvider::new(config.id, &urls)
+            .ok_or(NetworkError::InvalidConfiguration)?
+            .with_settings(crate::config::settings_store().subscribe_section());
+        self.invalidate_cache(config.id);
+
+        self.providers
+            .write()
+            .await
+            .insert(config.id, failover.primary().clone());
+        self.failover.write().await.insert(config.id, Arc::new(failover));
         Ok(())
     }
 
+    /// Failover provider of the current network
+    async fn current_failover(&self) -> Result<Arc<failover::FailoverProvider>> {
+        self.failover
+            .read()
+            .await
+            .get(&self.current_network)
+            .cloned()
+            .ok_or_else(|| {
+                NetworkError::UnsupportedNetwork {
+                    network_id: self.current_network.chain_id(),
+                }
+                .into()
+            })
+    }
+
     /// Switch to a different network
     pub async fn switch_network(&mut self, network_id: NetworkId) -> Result<()> {
         if !self.networks.contains_key(&network_id) {
@@ -223,6 +391,7 @@ impl NetworkManager {
         }
 
         self.current_network = network_id;
+        self.activate_current_network().await;
         Ok(())
     }
 
@@ -253,18 +422,9 @@ impl NetworkManager {
             );
         }
 
-        // Create provider for the new network
-        let provider = match config.rpc_url.parse::<reqwest::Url>() {
-            Ok(url) => ProviderBuilder::new().connect_http(url),
-            Err(_) => return Err(NetworkError::InvalidConfiguration.into()),
-        };
-
-        // Add to networks and providers
-        let network_id = config.id;
-        self.networks.insert(network_id, config);
-
-        let mut providers = self.providers.write().await;
-        providers.insert(network_id, provider);
+        // Create providers for the new network, then add it
+        self.install_providers(&config).await?;
+        self.networks.insert(config.id, config);
 
         Ok(())
     }
@@ -283,32 +443,31 @@ impl NetworkManager {
             return Err(NetworkError::InvalidConfiguration.into());
         }
 
-        // Build provider for the new config
-        let provider = match new_config.rpc_url.parse::<reqwest::Url>() {
-            Ok(url) => ProviderBuilder::new().connect_http(url),
-            Err(_) => return Err(NetworkError::InvalidConfiguration.into()),
-        };
+        self.restore_custom_network(old_id, new_config).await
+    }
+
+    /// Install a previously validated network in place of `old_id`
+    ///
+    /// Used to revert configuration changes; the endpoint is not validated
+    /// again, so reverting works while the RPC is unreachable.
+    pub async fn restore_custom_network(&mut self, old_id: NetworkId, new_config: NetworkConfig) -> Result<()> {
+        // Build providers for the new config
+        self.install_providers(&new_config).await?;
 
         // Remove old entry if id changed
         if old_id != new_config.id {
             self.networks.remove(&old_id);
-            let mut providers = self.providers.write().await;
-            providers.remove(&old_id);
+            self.providers.write().await.remove(&old_id);
+            self.failover.write().await.remove(&old_id);
+            self.invalidate_cache(old_id);
 
             // If the current network was the old id, point it to the new id
             if self.current_network == old_id {
                 self.current_network = new_config.id;
             }
-
-            // Insert new
-            self.networks.insert(new_config.id, new_config.clone());
-            providers.insert(new_config.id, provider);
-        } else {
-            // Same id: update config and refresh provider
-            self.networks.insert(new_config.id, new_config.clone());
-            let mut providers = self.providers.write().await;
-            providers.insert(new_config.id, provider);
         }
+        self.networks.insert(new_config.id, new_config);
+        self.mark_active_rpc();
 
         Ok(())
     }
@@ -317,8 +476,9 @@ impl NetworkManager {
     pub async fn remove_custom_network(&mut self, network_id: NetworkId) -> Result<()> {
         // Remove from networks and providers
         let existed = self.networks.remove(&network_id).is_some();
-        let mut providers = self.providers.write().await;
-        providers.remove(&network_id);
+        self.providers.write().await.remove(&network_id);
+        self.failover.write().await.remove(&network_id);
+        self.invalidate_cache(network_id);
 
         if !existed {
             return Err(NetworkError::UnsupportedNetwork {
@@ -336,7 +496,92 @@ impl NetworkManager {
                 // but if it does, default back to Ethereum mainnet id if present
                 self.current_network = NetworkId(1);
             }
+            self.activate_current_network().await;
+        }
+        Ok(())
+    }
+
+    /// Custom networks as a shareable profile
+    pub fn export_profile(&self) -> profiles::NetworkProfile {
+        profiles::NetworkProfile::new(self.networks.values().filter(|n| n.is_custom).cloned())
+    }
+
+    /// Custom networks as profile JSON, without explorer keys
+    pub fn export_networks(&self) -> Result<String> {
+        self.export_profile().to_json()
+    }
+
+    /// Import networks from profile JSON
+    ///
+    /// Explorer keys in the profile are not applied here; see
+    /// [`profiles::NetworkProfile::apply_explorer_keys`].
+    pub async fn import_networks(
+        &mut self,
+        json: &str,
+        resolution: profiles::ConflictResolution,
+    ) -> Result<profiles::NetworkImportReport> {
+        let profile = profiles::NetworkProfile::from_json(json)?;
+        self.import_profile(&profile, resolution).await
+    }
+
+    /// Install the networks of a parsed profile
+    ///
+    /// Endpoints are not probed, so a profile can be imported offline; each
+    /// network's providers are installed as for any other network.
+    pub async fn import_profile(
+        &mut self,
+        profile: &profiles::NetworkProfile,
+        resolution: profiles::ConflictResolution,
+    ) -> Result<profiles::NetworkImportReport> {
+        let (install, report) = profiles::plan_import(&self.networks, profile, resolution)?;
+        for network in install {
+            self.restore_custom_network(network.id, network).await?;
+        }
+        tracing::info!(
+            "📥 Imported network profile: {} added, {} replaced, {} skipped",
+            report.added.len(),
+            report.replaced.len(),
+            report.skipped.len()
+        );
+        Ok(report)
+    }
+
+    /// Measure and rank every RPC endpoint configured for `network_id`
+    pub async fn benchmark_endpoints(
+        &self,
+        network_id: NetworkId,
+        config: &benchmark::BenchmarkConfig,
+    ) -> Result<benchmark::BenchmarkReport> {
+        let network = self
+            .networks
+            .get(&network_id)
+            .ok_or(NetworkError::UnsupportedNetwork {
+                network_id: network_id.chain_id(),
+            })?;
+        Ok(benchmark::benchmark_network(network, config).await)
+    }
+
+    /// Make `url`, one of the network's configured endpoints, its primary RPC
+    ///
+    /// The previous primary becomes the first fallback.
+    pub async fn set_primary_rpc(&mut self, network_id: NetworkId, url: &str) -> Result<()> {
+        let mut config = self
+            .networks
+            .get(&network_id)
+            .cloned()
+            .ok_or(NetworkError::UnsupportedNetwork {
+                network_id: network_id.chain_id(),
+            })?;
+        let urls: Vec<String> = config.rpc_urls().into_iter().map(str::to_string).collect();
+        if !urls.iter().any(|u| u == url) {
+            return Err(NetworkError::InvalidConfiguration.into());
         }
+
+        config.rpc_url = url.to_string();
+        config.fallback_rpc_urls = urls.into_iter().filter(|u| u != url).collect();
+        self.install_providers(&config).await?;
+        tracing::info!("🌐 Primary RPC for {} set to {}", config.name, url);
+        self.networks.insert(network_id, config);
         Ok(())
     }
 
@@ -354,12 +599,10 @@ impl NetworkManager {
 
     /// Get current gas price
     pub async fn get_gas_price(&self) -> Result<U256> {
-        let providers = self.providers.read().await;
-        let provider = providers
-            .get(&self.current_network)
-            .ok_or(NetworkError::UnsupportedNetwork {
-                network_id: self.current_network.chain_id(),
-            })?;
+        if let Some(price) = self.rpc_cache.as_ref().and_then(|c| c.gas_price(self.current_network)) {
+            return Ok(price);
+        }
+        let provider = self.current_failover().await?;
 
         let network_name = self
             .networks
@@ -378,7 +621,7 @@ impl NetworkManager {
         let max_attempts = 3;
 
         while attempts < max_attempts {
-            match provider.get_gas_price().await {
+            match provider.call(|p| async move { p.get_gas_price().await }).await {
                 Ok(price) => {
                     let price_u256 = U256::from(price);
                     tracing::info!(
@@ -386,6 +629,9 @@ impl NetworkManager {
                         price_u256,
                         price as f64 / 1e9
                     );
+                    if let Some(cache) = &self.rpc_cache {
+                        cache.insert_gas_price(self.current_network, price_u256);
+                    }
                     return Ok(price_u256);
                 }
                 Err(e) => {
@@ -424,12 +670,7 @@ impl NetworkManager {
 
     /// Estimate gas for a transaction
     pub async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<U256> {
-        let providers = self.providers.read().await;
-        let provider = providers
-            .get(&self.current_network)
-            .ok_or(NetworkError::UnsupportedNetwork {
-                network_id: self.current_network.chain_id(),
-            })?;
+        let provider = self.current_failover().await?;
 
         tracing::info!(
             "📊 Estimating gas for transaction on Chain ID {}",
@@ -437,7 +678,13 @@ impl NetworkManager {
         );
 
         // Estimate gas using the provider
-        let gas_estimate = match provider.estimate_gas(tx.clone()).await {
+        let gas_estimate = match provider
+            .call(|p| {
+                let tx = tx.clone();
+                async move { p.estimate_gas(tx).await }
+            })
+            .await
+        {
             Ok(estimate) => {
                 let estimate_u256 = U256::from(estimate);
                 tracing::info!("✅ Gas estimated: {} units", estimate_u256);
@@ -452,14 +699,51 @@ impl NetworkManager {
         Ok(gas_estimate)
     }
 
+    /// Estimate gas for `tx` against state changed by `overrides`
+    ///
+    /// Unlike [`Self::estimate_gas`] there is no fallback: a failure here
+    /// usually means the endpoint doesn't support state overrides.
+    pub async fn estimate_gas_with_overrides(&self, tx: &TransactionRequest, overrides: &StateOverride) -> Result<u64> {
+        let provider = self.current_failover().await?;
+
+        let estimate = provider
+            .call(|p| {
+                let tx = tx.clone();
+                let overrides = overrides.clone();
+                async move { p.estimate_gas(tx).overrides(overrides).await }
+            })
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to estimate gas with state overrides: {e}"),
+            })?;
+
+        tracing::info!("✅ Gas estimated with state overrides: {} units", estimate);
+        Ok(estimate)
+    }
+
     /// Send a raw signed transaction
+    ///
+    /// Idempotent: resending a transaction the network already has, pending or
+    /// mined, returns its hash. Fails with [`NetworkError::TransactionReplaced`]
+    /// when another transaction used its nonce. See [`Self::broadcast_raw_transaction`].
     pub async fn send_raw_transaction(&self, raw_tx: &[u8]) -> Result<TxHash> {
-        let providers = self.providers.read().await;
-        let provider = providers
-            .get(&self.current_network)
-            .ok_or(NetworkError::UnsupportedNetwork {
-                network_id: self.current_network.chain_id(),
-            })?;
+        let (tx_hash, outcome) = self.broadcast_raw_transaction(raw_tx).await?;
+        match outcome {
+            broadcast::BroadcastOutcome::Replaced { .. } => Err(NetworkError::TransactionReplaced {
+                tx_hash: tx_hash.to_string(),
+            }
+            .into()),
+            _ => Ok(tx_hash),
+        }
+    }
+
+    /// Broadcast a raw signed transaction and report what happened to it
+    ///
+    /// "Already known" and "nonce too low" rejections are resolved against the
+    /// chain (see [`broadcast`]); nonce tracking and the transaction watcher
+    /// are reconciled with the result either way.
+    pub async fn broadcast_raw_transaction(&self, raw_tx: &[u8]) -> Result<(TxHash, broadcast::BroadcastOutcome)> {
+        let provider = self.current_failover().await?;
 
         tracing::info!(
             "🚀 Broadcasting raw transaction to network: Chain ID {}",
@@ -479,52 +763,155 @@ impl NetworkManager {
             }
             .into());
         }
+        let tx = broadcast::RawTransactionInfo::decode(raw_tx)?;
 
         // Send the raw transaction to the network using eth_sendRawTransaction
-        let pending_tx = provider.send_raw_transaction(raw_tx).await.map_err(|e| {
-            tracing::error!("❌ Raw transaction broadcast failed: {}", e);
-            NetworkError::RpcError {
-                message: format!("Failed to broadcast raw transaction: {e}"),
+        let sent = provider
+            .call(|p| async move { p.send_raw_transaction(raw_tx).await.map(|pending| *pending.tx_hash()) })
+            .await;
+        let outcome = match sent {
+            Ok(tx_hash) => {
+                tracing::info!("✅ Raw transaction broadcast successful: {}", tx_hash);
+                broadcast::BroadcastOutcome::Sent
+            }
+            Err(e) => {
+                let resolved = match broadcast::BroadcastRejection::classify(&e.to_string()) {
+                    Some(rejection) => broadcast::resolve_rejection(provider.active(), &tx, rejection)
+                        .await
+                        .unwrap_or_else(|resolve_error| {
+                            tracing::warn!("⚠️ Could not resolve rejected broadcast: {}", resolve_error);
+                            None
+                        }),
+                    None => None,
+                };
+                resolved.ok_or_else(|| {
+                    tracing::error!("❌ Raw transaction broadcast failed: {}", e);
+                    NetworkError::RpcError {
+                        message: format!("Failed to broadcast raw transaction: {e}"),
+                    }
+                })?
             }
-        })?;
+        };
+
+        if let Some(cache) = &self.rpc_cache {
+            cache.invalidate_balances(self.current_network);
+        }
 
-        let tx_hash = *pending_tx.tx_hash();
-        tracing::info!("✅ Raw transaction broadcast successful: {}", tx_hash);
+        match outcome {
+            broadcast::BroadcastOutcome::Replaced { account_nonce } => {
+                tracing::warn!(
+                    "⚠️ Transaction {} was replaced; nonce {} already used",
+                    tx.hash,
+                    tx.nonce
+                );
+                self.nonce_manager
+                    .reconcile(tx.sender, self.current_network, account_nonce);
+                self.tx_watcher.publish(tx_watcher::TxEvent {
+                    tx_hash: tx.hash,
+                    network: self.current_network,
+                    status: tx_watcher::TxStatus::Replaced,
+                });
+            }
+            _ => {
+                self.nonce_manager
+                    .mark_sent(tx.sender, self.current_network, tx.nonce, tx.hash);
+                self.tx_watcher
+                    .watch(provider.active().clone(), self.current_network, tx.hash);
+            }
+        }
 
-        Ok(tx_hash)
+        Ok((tx.hash, outcome))
     }
 
     /// Get transaction count (nonce) for an address
     pub async fn get_transaction_count(&self, address: Address) -> Result<u64> {
-        let providers = self.providers.read().await;
-        let provider = providers
-            .get(&self.current_network)
-            .ok_or(NetworkError::UnsupportedNetwork {
-                network_id: self.current_network.chain_id(),
-            })?;
+        let provider = self.current_failover().await?;
 
         tracing::debug!("🔢 Getting transaction count for address: {}", address);
 
-        let nonce = provider.get_transaction_count(address).await.map_err(|e| {
-            tracing::error!("❌ Failed to get transaction count: {}", e);
-            NetworkError::RpcError {
-                message: format!("Failed to get transaction count: {e}"),
-            }
-        })?;
+        let nonce = provider
+            .call(|p| async move { p.get_transaction_count(address).await })
+            .await
+            .map_err(|e| {
+                tracing::error!("❌ Failed to get transaction count: {}", e);
+                NetworkError::RpcError {
+                    message: format!("Failed to get transaction count: {e}"),
+                }
+            })?;
 
         tracing::debug!("✅ Transaction count for {}: {}", address, nonce);
 
         Ok(nonce)
     }
 
+    /// Reserve the nonce for the next transaction from `address` on the current network
+    ///
+    /// Uses the pending transaction count as a floor and the local
+    /// [`nonce_manager::NonceManager`] to avoid reusing nonces of transactions
+    /// that are still in flight.
+    pub async fn reserve_nonce(&self, address: Address) -> Result<u64> {
+        let chain_nonce = self.get_pending_transaction_count(address).await?;
+        Ok(self.nonce_manager.reserve(address, self.current_network, chain_nonce))
+    }
+
+    /// Give back a nonce from [`Self::reserve_nonce`] whose transaction was never broadcast
+    pub fn release_nonce(&self, address: Address, nonce: u64) {
+        self.nonce_manager.release(address, self.current_network, nonce);
+    }
+
+    /// Reconcile tracked nonces for `address` with the chain (call after a confirmation)
+    pub async fn reconcile_nonce(&self, address: Address) -> Result<()> {
+        let chain_nonce = self.get_transaction_count(address).await?;
+        self.nonce_manager.reconcile(address, self.current_network, chain_nonce);
+        Ok(())
+    }
+
+    /// Subscribe to receipt and confirmation events of broadcast transactions
+    pub fn subscribe_tx_events(&self) -> tokio::sync::broadcast::Receiver<tx_watcher::TxEvent> {
+        self.tx_watcher.subscribe()
+    }
+
+    /// Transaction watcher, e.g. to watch transactions broadcast elsewhere
+    pub fn tx_watcher(&self) -> &tx_watcher::TxWatcher {
+        &self.tx_watcher
+    }
+
+    /// Shared nonce manager tracking locally issued nonces
+    pub fn nonce_manager(&self) -> Arc<nonce_manager::NonceManager> {
+        Arc::clone(&self.nonce_manager)
+    }
+
+    async fn get_pending_transaction_count(&self, address: Address) -> Result<u64> {
+        let provider = self.current_failover().await?;
+
+        provider
+            .call(|p| async move { p.get_transaction_count(address).pending().await })
+            .await
+            .map_err(|e| {
+                NetworkError::RpcError {
+                    message: format!("Failed to get pending transaction count: {e}"),
+                }
+                .into()
+            })
+    }
+
     /// Get balance for an address
+    ///
+    /// Balances fetched within the RPC cache's balance TTL are reused.
     pub async fn get_balance(&self, address: Address, token: Option<Address>) -> Result<U256> {
-        let providers = self.providers.read().await;
-        let provider = providers
-            .get(&self.current_network)
-            .ok_or(NetworkError::UnsupportedNetwork {
-                network_id: self.current_network.chain_id(),
-            })?;
+        let network = self.current_network;
+        if let Some(balance) = self.rpc_cache.as_ref().and_then(|c| c.balance(network, address, token)) {
+            return Ok(balance);
+        }
+        let balance = self.fetch_balance(address, token).await?;
+        if let Some(cache) = &self.rpc_cache {
+            cache.insert_balance(network, address, token, balance);
+        }
+        Ok(balance)
+    }
+
+    async fn fetch_balance(&self, address: Address, token: Option<Address>) -> Result<U256> {
+        let provider = self.current_failover().await?;
 
         // Get network name for logging
         let network_name = self
@@ -548,7 +935,7 @@ impl NetworkManager {
                 let max_attempts = 3;
 
                 let balance = loop {
-                    match provider.get_balance(address).await {
+                    match provider.call(|p| async move { p.get_balance(address).await }).await {
                         Ok(balance) => break balance,
                         Err(e) => {
                             attempts += 1;
@@ -607,12 +994,18 @@ impl NetworkManager {
 
                 let call_request = TransactionRequest::default().to(token_address).input(call_data.into());
 
-                let result = provider.call(call_request.clone()).await.map_err(|e| {
-                    tracing::error!("❌ ERC20 balance call failed: {}", e);
-                    NetworkError::RpcError {
-                        message: format!("Failed to call ERC20 balanceOf: {e}"),
-                    }
-                })?;
+                let result = provider
+                    .call(|p| {
+                        let call_request = call_request.clone();
+                        async move { p.call(call_request).await }
+                    })
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("❌ ERC20 balance call failed: {}", e);
+                        NetworkError::RpcError {
+                            message: format!("Failed to call ERC20 balanceOf: {e}"),
+                        }
+                    })?;
 
                 // Parse the result as U256
                 if result.len() >= 32 {
@@ -627,6 +1020,174 @@ impl NetworkManager {
         }
     }
 
+    /// Chain ID reported by the current network's RPC
+    pub async fn get_chain_id(&self) -> Result<u64> {
+        let network = self.current_network;
+        if let Some(chain_id) = self.rpc_cache.as_ref().and_then(|c| c.chain_id(network)) {
+            return Ok(chain_id);
+        }
+        let provider = self.current_failover().await?;
+        let chain_id = provider
+            .call(|p| async move { p.get_chain_id().await })
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to get chain ID: {e}"),
+            })?;
+        if let Some(cache) = &self.rpc_cache {
+            cache.insert_chain_id(network, chain_id);
+        }
+        Ok(chain_id)
+    }
+
+    /// Block by hash on the current network
+    pub async fn get_block_by_hash(&self, hash: B256) -> Result<Option<Block>> {
+        let network = self.current_network;
+        if let Some(block) = self.rpc_cache.as_ref().and_then(|c| c.block(network, hash)) {
+            return Ok(Some(block));
+        }
+        let provider = self.current_failover().await?;
+        let block = provider
+            .call(|p| async move { p.get_block_by_hash(hash).await })
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to get block {hash}: {e}"),
+            })?;
+        if let (Some(cache), Some(block)) = (&self.rpc_cache, &block) {
+            cache.insert_block(network, hash, block.clone());
+        }
+        Ok(block)
+    }
+
+    /// Transaction receipt on the current network
+    ///
+    /// Receipts are cached once their block is finalized.
+    pub async fn get_transaction_receipt(&self, tx_hash: TxHash) -> Result<Option<TransactionReceipt>> {
+        let network = self.current_network;
+        if let Some(receipt) = self.rpc_cache.as_ref().and_then(|c| c.receipt(network, tx_hash)) {
+            return Ok(Some(receipt));
+        }
+        let provider = self.current_failover().await?;
+        let receipt = provider
+            .call(|p| async move { p.get_transaction_receipt(tx_hash).await })
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to get receipt for {tx_hash}: {e}"),
+            })?;
+
+        if let (Some(cache), Some(receipt)) = (&self.rpc_cache, &receipt) {
+            let finalized = match cache.finalized_head(network) {
+                Some(number) => Some(number),
+                // Chains without a finalized tag error here; their receipts are not cached
+                None => provider
+                    .call(|p| async move { p.get_block_by_number(BlockNumberOrTag::Finalized).await })
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|block| {
+                        cache.insert_finalized_head(network, block.header.number);
+                        block.header.number
+                    }),
+            };
+            if let Some(finalized) = finalized {
+                cache.insert_receipt(network, receipt.clone(), finalized);
+            }
+        }
+        Ok(receipt)
+    }
+
+    /// Compare the local clock with the latest block and record the skew
+    ///
+    /// Deadlines computed through [`clock::clock`] pick up the measurement.
+    pub async fn measure_clock_skew(&self) -> Result<clock::SkewMeasurement> {
+        let provider = self.current_failover().await?;
+        let block = provider
+            .call(|p| async move { p.get_block_by_number(BlockNumberOrTag::Latest).await })
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to get latest block: {e}"),
+            })?
+            .ok_or_else(|| NetworkError::RpcError {
+                message: "Latest block not available".to_string(),
+            })?;
+        let measurement = clock::SkewMeasurement::from_block(
+            self.current_network.0,
+            block.header.number,
+            block.header.timestamp,
+            chrono::Utc::now(),
+        );
+        clock::clock().record(measurement.clone());
+        Ok(measurement)
+    }
+
+    /// Name, symbol and decimals of a token on the current network
+    pub async fn get_token_metadata(&self, token: Address) -> Result<TokenInfo> {
+        let network = self.current_network;
+        if let Some(info) = self.rpc_cache.as_ref().and_then(|c| c.token_metadata(network, token)) {
+            return Ok(info);
+        }
+        let provider = self.current_failover().await?;
+        let info = provider
+            .call(|p| async move {
+                crate::tokens::metadata::discover_token_metadata(&p, network.chain_id(), token).await
+            })
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to read token metadata for {token}: {e}"),
+            })?;
+        if let Some(cache) = &self.rpc_cache {
+            cache.insert_token_metadata(network, info.clone());
+        }
+        Ok(info)
+    }
+
+    /// RPC response cache, if enabled
+    pub fn rpc_cache(&self) -> Option<Arc<RpcCache>> {
+        self.rpc_cache.clone()
+    }
+
+    /// Replace the RPC response cache; `None` sends every request to the RPC
+    ///
+    /// A cache can be shared between managers; entries are keyed by network.
+    pub fn set_rpc_cache(&mut self, cache: Option<Arc<RpcCache>>) {
+        self.rpc_cache = cache;
+    }
+
+    fn invalidate_cache(&self, network: NetworkId) {
+        if let Some(cache) = &self.rpc_cache {
+            cache.invalidate_network(network);
+        }
+    }
+
+    /// Read balances and nonces as of a single block on the current network
+    ///
+    /// The whole snapshot is served by one endpoint; on failure the next
+    /// endpoint retries it from scratch.
+    pub async fn read_snapshot(
+        &self,
+        accounts: Vec<Address>,
+        mode: snapshot::SnapshotMode,
+    ) -> Result<snapshot::ChainSnapshot> {
+        let request = snapshot::SnapshotRequest::new(self.current_network.chain_id(), accounts).with_mode(mode);
+        self.read_snapshot_with(request).await
+    }
+
+    /// Read a snapshot for a custom request (e.g. including token balances)
+    pub async fn read_snapshot_with(&self, request: snapshot::SnapshotRequest) -> Result<snapshot::ChainSnapshot> {
+        let provider = self.current_failover().await?;
+        provider
+            .call(|p| {
+                let request = request.clone();
+                async move { snapshot::read_snapshot(&p, &request).await }
+            })
+            .await
+            .map_err(|e| {
+                NetworkError::RpcError {
+                    message: format!("Failed to read chain snapshot: {e}"),
+                }
+                .into()
+            })
+    }
+
     /// Get current network configuration
     pub fn get_current_network_config(&self) -> Option<&NetworkConfig> {
         self.networks.get(&self.current_network)
@@ -0,0 +1,269 @@
+//! Local nonce management
+//!
+//! Tracks nonces issued by this wallet per (account, network) so back-to-back
+//! sends don't both read the same `eth_getTransactionCount` and collide.
+//!
+//! The chain's pending transaction count is used as a floor: when it moves past
+//! locally tracked nonces (confirmations, or transactions sent from another
+//! wallet) the local state is reconciled. A manual override can be set for the
+//! next send, and gaps between the confirmed count and locally issued nonces
+//! are reported so stuck transactions can be flagged in the UI.
+
+use super::NetworkId;
+use alloy::primitives::{Address, TxHash};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+
+/// Nonces are tracked per account and network
+pub type NonceKey = (Address, NetworkId);
+
+/// A nonce handed out by the manager that has not been confirmed yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingNonce {
+    pub nonce: u64,
+    /// Hash of the broadcast transaction, once known
+    pub tx_hash: Option<TxHash>,
+    pub issued_at: DateTime<Utc>,
+}
+
+/// Missing nonces below locally issued ones
+///
+/// Transactions above a gap cannot be mined until the missing nonces are used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceGap {
+    pub account: Address,
+    pub network: NetworkId,
+    pub missing: Vec<u64>,
+    /// Lowest pending nonce blocked by the gap
+    pub blocked_from: u64,
+}
+
+#[derive(Debug, Default)]
+struct AccountNonceState {
+    /// Last known on-chain transaction count
+    chain_nonce: u64,
+    /// Next nonce to hand out
+    next: u64,
+    pending: BTreeMap<u64, PendingNonce>,
+    override_nonce: Option<u64>,
+}
+
+impl AccountNonceState {
+    fn reconcile(&mut self, chain_nonce: u64) {
+        if chain_nonce < self.chain_nonce {
+            // Reorg or a lagging RPC node; never move the floor backwards
+            return;
+        }
+        self.chain_nonce = chain_nonce;
+        self.pending.retain(|nonce, _| *nonce >= chain_nonce);
+        self.next = self.next.max(chain_nonce);
+    }
+}
+
+/// Tracks locally issued nonces per (account, network)
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    state: Mutex<HashMap<NonceKey, AccountNonceState>>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve the nonce for the next transaction
+    ///
+    /// `chain_nonce` is the account's pending transaction count as reported by
+    /// the network. A manual override, if set, is used once and then cleared.
+    pub fn reserve(&self, account: Address, network: NetworkId, chain_nonce: u64) -> u64 {
+        let mut state = self.lock();
+        let entry = state.entry((account, network)).or_default();
+        entry.reconcile(chain_nonce);
+
+        let nonce = match entry.override_nonce.take() {
+            Some(nonce) => {
+                tracing::info!("🔢 Using manual nonce override {} for {}", nonce, account);
+                nonce
+            }
+            None => entry.next,
+        };
+
+        entry.pending.insert(
+            nonce,
+            PendingNonce {
+                nonce,
+                tx_hash: None,
+                issued_at: Utc::now(),
+            },
+        );
+        entry.next = entry.next.max(nonce + 1);
+
+        tracing::debug!("🔢 Reserved nonce {} for {} on network {}", nonce, account, network.0);
+        nonce
+    }
+
+    /// Attach the broadcast transaction hash to a reserved nonce
+    pub fn mark_sent(&self, account: Address, network: NetworkId, nonce: u64, tx_hash: TxHash) {
+        let mut state = self.lock();
+        if let Some(pending) = state
+            .get_mut(&(account, network))
+            .and_then(|entry| entry.pending.get_mut(&nonce))
+        {
+            pending.tx_hash = Some(tx_hash);
+        }
+    }
+
+    /// Give back a reserved nonce whose transaction was never broadcast
+    ///
+    /// If it was the most recently issued nonce the counter is rolled back so
+    /// it is reused; otherwise it is left as a gap for [`Self::detect_gap`].
+    pub fn release(&self, account: Address, network: NetworkId, nonce: u64) {
+        let mut state = self.lock();
+        let Some(entry) = state.get_mut(&(account, network)) else {
+            return;
+        };
+        if entry.pending.remove(&nonce).is_some() && entry.next == nonce + 1 {
+            entry.next = entry
+                .pending
+                .keys()
+                .next_back()
+                .map(|n| n + 1)
+                .unwrap_or(entry.chain_nonce)
+                .max(entry.chain_nonce);
+        }
+    }
+
+    /// Reconcile with the chain after a confirmation
+    ///
+    /// Drops every pending nonce below `chain_nonce` (they are mined or
+    /// replaced) and advances the local counter if another wallet sent
+    /// transactions from the same account.
+    pub fn reconcile(&self, account: Address, network: NetworkId, chain_nonce: u64) {
+        let mut state = self.lock();
+        state.entry((account, network)).or_default().reconcile(chain_nonce);
+    }
+
+    /// Use `nonce` for the next reservation instead of the tracked value
+    pub fn set_override(&self, account: Address, network: NetworkId, nonce: Option<u64>) {
+        let mut state = self.lock();
+        state.entry((account, network)).or_default().override_nonce = nonce;
+    }
+
+    /// Nonce that the next reservation will return without a chain update
+    pub fn peek_next(&self, account: Address, network: NetworkId) -> Option<u64> {
+        let state = self.lock();
+        state
+            .get(&(account, network))
+            .map(|entry| entry.override_nonce.unwrap_or(entry.next))
+    }
+
+    /// Unconfirmed nonces issued for an account, lowest first
+    pub fn pending(&self, account: Address, network: NetworkId) -> Vec<PendingNonce> {
+        let state = self.lock();
+        state
+            .get(&(account, network))
+            .map(|entry| entry.pending.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Detect nonces missing between the chain count and pending transactions
+    pub fn detect_gap(&self, account: Address, network: NetworkId) -> Option<NonceGap> {
+        let state = self.lock();
+        let entry = state.get(&(account, network))?;
+        let highest = *entry.pending.keys().next_back()?;
+
+        let missing: Vec<u64> = (entry.chain_nonce..highest)
+            .filter(|nonce| !entry.pending.contains_key(nonce))
+            .collect();
+        let first_missing = *missing.first()?;
+        let blocked_from = *entry.pending.range(first_missing..).next()?.0;
+
+        tracing::warn!(
+            "⚠️ Nonce gap for {} on network {}: missing {:?}",
+            account,
+            network.0,
+            missing
+        );
+        Some(NonceGap {
+            account,
+            network,
+            missing,
+            blocked_from,
+        })
+    }
+
+    /// Forget all state for an account (e.g. after removing it)
+    pub fn clear(&self, account: Address, network: NetworkId) {
+        self.lock().remove(&(account, network));
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<NonceKey, AccountNonceState>> {
+        // Nonce bookkeeping stays usable even if a holder panicked
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: Address = Address::repeat_byte(0x11);
+    const NETWORK: NetworkId = NetworkId(369);
+
+    #[test]
+    fn test_back_to_back_reservations_do_not_collide() {
+        let manager = NonceManager::new();
+
+        // Chain still reports 5 for both sends
+        assert_eq!(manager.reserve(ACCOUNT, NETWORK, 5), 5);
+        assert_eq!(manager.reserve(ACCOUNT, NETWORK, 5), 6);
+        assert_eq!(manager.reserve(ACCOUNT, NetworkId(1), 0), 0);
+        assert_eq!(manager.pending(ACCOUNT, NETWORK).len(), 2);
+    }
+
+    #[test]
+    fn test_reconcile_on_confirmation_and_external_sends() {
+        let manager = NonceManager::new();
+        manager.reserve(ACCOUNT, NETWORK, 5);
+        manager.reserve(ACCOUNT, NETWORK, 5);
+
+        manager.reconcile(ACCOUNT, NETWORK, 6);
+        assert_eq!(manager.pending(ACCOUNT, NETWORK).len(), 1);
+
+        // Another wallet used nonces 7..=9
+        assert_eq!(manager.reserve(ACCOUNT, NETWORK, 10), 10);
+
+        // A lagging node must not move the floor back
+        manager.reconcile(ACCOUNT, NETWORK, 3);
+        assert_eq!(manager.peek_next(ACCOUNT, NETWORK), Some(11));
+    }
+
+    #[test]
+    fn test_manual_override_used_once() {
+        let manager = NonceManager::new();
+        manager.set_override(ACCOUNT, NETWORK, Some(42));
+
+        assert_eq!(manager.reserve(ACCOUNT, NETWORK, 5), 42);
+        assert_eq!(manager.reserve(ACCOUNT, NETWORK, 5), 43);
+    }
+
+    #[test]
+    fn test_release_and_gap_detection() {
+        let manager = NonceManager::new();
+        let first = manager.reserve(ACCOUNT, NETWORK, 0);
+        let second = manager.reserve(ACCOUNT, NETWORK, 0);
+        let third = manager.reserve(ACCOUNT, NETWORK, 0);
+
+        // Releasing the latest nonce rolls the counter back
+        manager.release(ACCOUNT, NETWORK, third);
+        assert_eq!(manager.peek_next(ACCOUNT, NETWORK), Some(2));
+        assert!(manager.detect_gap(ACCOUNT, NETWORK).is_none());
+
+        // Releasing a nonce below another pending one leaves a gap
+        manager.release(ACCOUNT, NETWORK, first);
+        let gap = manager.detect_gap(ACCOUNT, NETWORK).unwrap();
+        assert_eq!(gap.missing, vec![first]);
+        assert_eq!(gap.blocked_from, second);
+    }
+}
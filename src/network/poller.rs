@@ -0,0 +1,267 @@
+//! Background balance poller
+//!
+//! Instead of every view refreshing balances on its own timer, the poller
+//! reads the native and watched token balances of the active accounts from
+//! one chain snapshot per interval, compares them with the previous reading
+//! and publishes a [`BalanceChanged`] event for each balance that moved.
+//! Subscribers (the GUI, sound notifications) only wake up on real changes.
+//!
+//! The first reading of a balance is the baseline and produces no event.
+//! Switching networks starts a new baseline for that network.
+
+use super::snapshot::{ChainSnapshot, SnapshotRequest};
+use super::{NetworkId, NetworkManager};
+use alloy::primitives::{Address, U256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+
+/// Default time between polls
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A balance differs from the previous poll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChanged {
+    pub network: NetworkId,
+    pub account: Address,
+    /// ERC-20 token, or `None` for the native token
+    pub token: Option<Address>,
+    pub previous: U256,
+    pub current: U256,
+    /// Block the new balance was read at
+    pub block_number: u64,
+}
+
+impl BalanceChanged {
+    /// Whether the balance went up
+    pub fn is_incoming(&self) -> bool {
+        self.current > self.previous
+    }
+
+    /// Absolute size of the change
+    pub fn amount(&self) -> U256 {
+        self.current.abs_diff(self.previous)
+    }
+}
+
+type BalanceKey = (NetworkId, Address, Option<Address>);
+
+/// Last known balances, turning snapshots into change events
+#[derive(Debug, Clone, Default)]
+pub struct BalanceTracker {
+    balances: HashMap<BalanceKey, U256>,
+}
+
+impl BalanceTracker {
+    /// Record the balances in `snapshot`, returning those that changed
+    pub fn update(&mut self, network: NetworkId, snapshot: &ChainSnapshot) -> Vec<BalanceChanged> {
+        let native = snapshot
+            .native_balances
+            .iter()
+            .map(|(account, balance)| (*account, None, *balance));
+        let tokens = snapshot
+            .token_balances
+            .iter()
+            .map(|((owner, token), balance)| (*owner, Some(*token), *balance));
+
+        let mut changes: Vec<BalanceChanged> = native
+            .chain(tokens)
+            .filter_map(|(account, token, current)| {
+                let previous = self.balances.insert((network, account, token), current)?;
+                (previous != current).then_some(BalanceChanged {
+                    network,
+                    account,
+                    token,
+                    previous,
+                    current,
+                    block_number: snapshot.block_number,
+                })
+            })
+            .collect();
+        // Snapshot maps are unordered; keep events stable for subscribers
+        changes.sort_by_key(|c| (c.account, c.token));
+        changes
+    }
+
+    /// Forget balances of accounts no longer polled
+    pub fn retain_accounts(&mut self, accounts: &[Address]) {
+        self.balances.retain(|(_, account, _), _| accounts.contains(account));
+    }
+}
+
+#[derive(Debug, Default)]
+struct Targets {
+    accounts: Vec<Address>,
+    tokens: HashMap<NetworkId, Vec<Address>>,
+}
+
+/// Polls balances of the active accounts and publishes [`BalanceChanged`] events
+#[derive(Debug, Clone)]
+pub struct BalancePoller {
+    sender: broadcast::Sender<BalanceChanged>,
+    targets: Arc<Mutex<Targets>>,
+    interval: Duration,
+}
+
+impl Default for BalancePoller {
+    fn default() -> Self {
+        Self::new(DEFAULT_POLL_INTERVAL)
+    }
+}
+
+impl BalancePoller {
+    pub fn new(interval: Duration) -> Self {
+        let (sender, _) = broadcast::channel(256);
+        Self {
+            sender,
+            targets: Arc::new(Mutex::new(Targets::default())),
+            interval,
+        }
+    }
+
+    /// Subscribe to balance changes on every network
+    pub fn subscribe(&self) -> broadcast::Receiver<BalanceChanged> {
+        self.sender.subscribe()
+    }
+
+    /// Accounts whose balances are polled
+    pub fn set_accounts(&self, accounts: Vec<Address>) {
+        self.lock_targets().accounts = accounts;
+    }
+
+    /// Also poll the active accounts' balance of `token` on `network`
+    pub fn watch_token(&self, network: NetworkId, token: Address) {
+        let mut targets = self.lock_targets();
+        let tokens = targets.tokens.entry(network).or_default();
+        if !tokens.contains(&token) {
+            tokens.push(token);
+        }
+    }
+
+    pub fn unwatch_token(&self, network: NetworkId, token: Address) {
+        if let Some(tokens) = self.lock_targets().tokens.get_mut(&network) {
+            tokens.retain(|t| *t != token);
+        }
+    }
+
+    /// Snapshot request for the current targets on `network`, if any account is active
+    fn request(&self, network: NetworkId) -> Option<SnapshotRequest> {
+        let targets = self.lock_targets();
+        if targets.accounts.is_empty() {
+            return None;
+        }
+        let mut request = SnapshotRequest::new(network.chain_id(), targets.accounts.clone());
+        for token in targets.tokens.get(&network).into_iter().flatten() {
+            for account in &targets.accounts {
+                request = request.with_token_balance(*account, *token);
+            }
+        }
+        Some(request)
+    }
+
+    /// Poll the current network of `manager` in the background until aborted
+    pub fn start(&self, manager: Arc<RwLock<NetworkManager>>) -> tokio::task::JoinHandle<()> {
+        let poller = self.clone();
+
+        tokio::spawn(async move {
+            let mut tracker = BalanceTracker::default();
+            let mut interval = tokio::time::interval(poller.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            tracing::debug!("💰 Balance poller started ({:?} interval)", poller.interval);
+
+            loop {
+                interval.tick().await;
+
+                let manager = manager.read().await;
+                let network = manager.current_network();
+                let Some(request) = poller.request(network) else {
+                    continue;
+                };
+                let snapshot = match manager.read_snapshot_with(request).await {
+                    Ok(snapshot) => snapshot,
+                    Err(e) => {
+                        tracing::warn!("⚠️ Balance poll on network {} failed: {}", network.0, e);
+                        continue;
+                    }
+                };
+                drop(manager);
+
+                tracker.retain_accounts(&poller.lock_targets().accounts);
+                for change in tracker.update(network, &snapshot) {
+                    tracing::info!(
+                        "💰 Balance of {} changed on network {}: {} → {}",
+                        change.account,
+                        network.0,
+                        change.previous,
+                        change.current
+                    );
+                    // No subscribers is fine
+                    let _ = poller.sender.send(change);
+                }
+            }
+        })
+    }
+
+    fn lock_targets(&self) -> MutexGuard<'_, Targets> {
+        self.targets.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::snapshot::SnapshotMode;
+    use alloy::primitives::B256;
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const TOKEN: Address = Address::repeat_byte(0x70);
+    const PULSE: NetworkId = NetworkId(369);
+
+    fn snapshot(block_number: u64, native: u64, token: u64) -> ChainSnapshot {
+        ChainSnapshot {
+            block_number,
+            block_hash: B256::ZERO,
+            timestamp: 0,
+            base_fee_per_gas: None,
+            mode: SnapshotMode::Multicall,
+            native_balances: HashMap::from([(ALICE, U256::from(native))]),
+            nonces: HashMap::new(),
+            token_balances: HashMap::from([((ALICE, TOKEN), U256::from(token))]),
+        }
+    }
+
+    #[test]
+    fn test_only_changed_balances_produce_events() {
+        let mut tracker = BalanceTracker::default();
+        // Baseline
+        assert!(tracker.update(PULSE, &snapshot(1, 100, 5)).is_empty());
+        assert!(tracker.update(PULSE, &snapshot(2, 100, 5)).is_empty());
+
+        let changes = tracker.update(PULSE, &snapshot(3, 70, 8));
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].token, None);
+        assert!(!changes[0].is_incoming());
+        assert_eq!(changes[0].amount(), U256::from(30u64));
+        assert_eq!(changes[1].token, Some(TOKEN));
+        assert!(changes[1].is_incoming());
+        assert_eq!(changes[1].block_number, 3);
+
+        // Another network has its own baseline
+        assert!(tracker.update(NetworkId(1), &snapshot(3, 1, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_request_covers_watched_tokens() {
+        let poller = BalancePoller::default();
+        assert!(poller.request(PULSE).is_none());
+
+        poller.set_accounts(vec![ALICE]);
+        poller.watch_token(PULSE, TOKEN);
+        poller.watch_token(PULSE, TOKEN);
+        let request = poller.request(PULSE).unwrap();
+        assert_eq!(request.accounts, vec![ALICE]);
+        assert_eq!(request.token_balances, vec![(ALICE, TOKEN)]);
+        assert!(poller.request(NetworkId(1)).unwrap().token_balances.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 #![cfg_attr(not(feature = "professional"), allow(dead_code))]
 
 use crate::error::{NetworkError, Result};
+use crate::network::routing::{self, EndpointObservation, RequestClass};
 use crate::network::{NetworkConfig, NetworkId};
 use reqwest::{Client, ClientBuilder};
 use serde::Deserialize;
@@ -147,6 +148,8 @@ struct HealthCheckState {
     consecutive_failures: u32,
     consecutive_successes: u32,
     current_status: EndpointHealth,
+    /// Latency of the last successful check
+    last_latency: Option<Duration>,
 }
 
 /// Rate limiting for API calls
@@ -282,6 +285,7 @@ impl ProfessionalNetworkManager {
                     block_explorer_url: "https://etherscan.io".to_string(),
                     is_testnet: false,
                     is_custom: false,
+                    fallback_rpc_urls: Vec::new(),
                 },
                 endpoints: vec![
                     RpcEndpoint {
@@ -339,6 +343,7 @@ impl ProfessionalNetworkManager {
                     block_explorer_url: "https://polygonscan.com".to_string(),
                     is_testnet: false,
                     is_custom: false,
+                    fallback_rpc_urls: Vec::new(),
                 },
                 endpoints: vec![
                     RpcEndpoint {
@@ -386,6 +391,7 @@ impl ProfessionalNetworkManager {
                     block_explorer_url: "https://bscscan.com".to_string(),
                     is_testnet: false,
                     is_custom: false,
+                    fallback_rpc_urls: Vec::new(),
                 },
                 endpoints: vec![
                     RpcEndpoint {
@@ -508,13 +514,69 @@ impl ProfessionalNetworkManager {
         Ok(healthy_endpoints[0].url.clone())
     }
 
+    /// Get the endpoint best suited to a class of request
+    ///
+    /// Latency-sensitive requests go to the fastest healthy endpoint and bulk
+    /// requests to the endpoint with the most headroom, based on the health
+    /// monitor's observations (see [`routing::select_endpoint`]). Standard
+    /// requests use the cluster's load balancing strategy.
+    pub async fn get_endpoint_for(&self, network_id: NetworkId, class: RequestClass) -> Result<String> {
+        if class == RequestClass::Standard {
+            return self.get_best_endpoint(network_id).await;
+        }
+
+        if !self.rate_limiter.check_rate_limit("global").await {
+            return Err(NetworkError::NetworkError {
+                message: "Rate limit exceeded".to_string(),
+            }
+            .into());
+        }
+
+        let networks = self.networks.read().await;
+        let cluster = networks.get(&network_id).ok_or(NetworkError::UnsupportedNetwork {
+            network_id: network_id.0,
+        })?;
+
+        let observations = self.health_monitor.observations().await;
+        match routing::select_endpoint(class, &cluster.endpoints, &observations) {
+            Some(index) => {
+                debug!("🧭 Routing {:?} request to {}", class, cluster.endpoints[index].url);
+                Ok(cluster.endpoints[index].url.clone())
+            }
+            None => {
+                warn!(
+                    "⚠️ No healthy endpoints available for network {}, using fallback",
+                    network_id.0
+                );
+                Ok(cluster.primary_config.rpc_url.clone())
+            }
+        }
+    }
+
     /// Make a request with automatic failover and retries
+    ///
+    /// The endpoint is chosen according to the method's [`RequestClass`].
     pub async fn request_with_failover<T>(
         &self,
         network_id: NetworkId,
         method: &str,
         params: serde_json::Value,
     ) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.request_with_class(network_id, RequestClass::for_method(method), method, params)
+            .await
+    }
+
+    /// Make a request routed as `class`, with automatic failover and retries
+    pub async fn request_with_class<T>(
+        &self,
+        network_id: NetworkId,
+        class: RequestClass,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
     {
@@ -524,7 +586,10 @@ impl ProfessionalNetworkManager {
         while attempts < max_attempts {
             attempts += 1;
 
-            match self.make_request_internal(network_id, method, params.clone()).await {
+            match self
+                .make_request_internal(network_id, class, method, params.clone())
+                .await
+            {
                 Ok(response) => {
                     // Update statistics
                     self.update_success_stats(network_id).await;
@@ -559,6 +624,7 @@ impl ProfessionalNetworkManager {
     async fn make_request_internal<T>(
         &self,
         network_id: NetworkId,
+        class: RequestClass,
         method: &str,
         params: serde_json::Value,
     ) -> Result<T>
@@ -583,8 +649,8 @@ impl ProfessionalNetworkManager {
             });
         }
 
-        // Get best endpoint
-        let endpoint_url = self.get_best_endpoint(network_id).await?;
+        // Get best endpoint for this kind of request
+        let endpoint_url = self.get_endpoint_for(network_id, class).await?;
 
         // Check circuit breaker
         if !self.circuit_breaker.allow_request(&endpoint_url).await {
@@ -812,6 +878,7 @@ impl NetworkHealthMonitor {
                 consecutive_failures: 0,
                 consecutive_successes: 0,
                 current_status: EndpointHealth::Unknown,
+                last_latency: None,
             });
 
         state.last_check = SystemTime::now();
@@ -820,6 +887,7 @@ impl NetworkHealthMonitor {
             Ok(_) => {
                 state.consecutive_successes += 1;
                 state.consecutive_failures = 0;
+                state.last_latency = Some(latency);
 
                 if state.consecutive_successes >= config.healthy_threshold {
                     state.current_status = EndpointHealth::Healthy;
@@ -842,6 +910,24 @@ impl NetworkHealthMonitor {
         }
     }
 
+    /// Latest status and latency observed for every monitored endpoint
+    pub async fn observations(&self) -> HashMap<String, EndpointObservation> {
+        self.health_checks
+            .read()
+            .await
+            .iter()
+            .map(|(url, state)| {
+                (
+                    url.clone(),
+                    EndpointObservation {
+                        status: state.current_status.clone(),
+                        latency: state.last_latency,
+                    },
+                )
+            })
+            .collect()
+    }
+
     async fn check_eth_block_number(&self, endpoint_url: &str, client: &Client, timeout: Duration) -> Result<u64> {
         let request = serde_json::json!({
             "jsonrpc": "2.0",
@@ -0,0 +1,242 @@
+//! Shareable JSON profiles of custom networks
+//!
+//! Teams distribute a standard set of networks (private RPCs, fallback
+//! endpoints, explorer keys) as one JSON file. A [`NetworkProfile`] carries
+//! the custom networks of a [`NetworkManager`](super::NetworkManager) and,
+//! only when explicitly added, explorer API keys.
+//!
+//! Networks are keyed by chain ID, so importing a network whose chain ID is
+//! already configured is a conflict, settled by [`ConflictResolution`].
+
+use super::{NetworkConfig, NetworkId};
+use crate::blockchain::ExplorerApiConfig;
+use crate::error::{Result, VaughanError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Format version written to exported profiles
+pub const NETWORK_PROFILE_VERSION: u32 = 1;
+
+/// Exported set of custom networks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub networks: Vec<NetworkConfig>,
+    /// Explorer API keys by service name, e.g. `etherscan`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub explorer_api_keys: BTreeMap<String, String>,
+}
+
+impl NetworkProfile {
+    /// Profile of `networks`, ordered by chain ID
+    pub fn new(networks: impl IntoIterator<Item = NetworkConfig>) -> Self {
+        let mut networks: Vec<NetworkConfig> = networks.into_iter().collect();
+        networks.sort_by_key(|n| n.chain_id);
+        Self {
+            version: NETWORK_PROFILE_VERSION,
+            exported_at: Utc::now(),
+            networks,
+            explorer_api_keys: BTreeMap::new(),
+        }
+    }
+
+    /// Include the explorer API keys of `config`
+    ///
+    /// Keys are secrets; only add them to profiles shared within a team.
+    pub fn with_explorer_keys(mut self, config: &ExplorerApiConfig) -> Self {
+        self.explorer_api_keys = config.api_keys.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        self
+    }
+
+    /// Copy the profile's explorer API keys into `config`; returns how many were set
+    pub fn apply_explorer_keys(&self, config: &mut ExplorerApiConfig) -> usize {
+        for (service, key) in &self.explorer_api_keys {
+            config.api_keys.insert(service.to_lowercase(), key.clone());
+        }
+        self.explorer_api_keys.len()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| VaughanError::ValidationError(format!("Failed to serialize network profile: {e}")))
+    }
+
+    /// Parse and validate a profile
+    pub fn from_json(json: &str) -> Result<Self> {
+        let profile: Self = serde_json::from_str(json)
+            .map_err(|e| VaughanError::ValidationError(format!("Invalid network profile: {e}")))?;
+        if profile.version > NETWORK_PROFILE_VERSION {
+            return Err(VaughanError::ValidationError(format!(
+                "Network profile version {} is newer than supported version {NETWORK_PROFILE_VERSION}",
+                profile.version
+            )));
+        }
+        for network in &profile.networks {
+            validate_network(network)?;
+        }
+        Ok(profile)
+    }
+}
+
+/// What to do when an imported network's chain ID is already configured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictResolution {
+    /// Keep the existing network
+    #[default]
+    Skip,
+    /// Replace the existing network with the imported one
+    Replace,
+    /// Import nothing if any network conflicts
+    Abort,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkImportReport {
+    pub added: Vec<NetworkId>,
+    pub replaced: Vec<NetworkId>,
+    /// Conflicting networks left as they were
+    pub skipped: Vec<NetworkId>,
+    /// Networks identical to what is already configured
+    pub unchanged: Vec<NetworkId>,
+}
+
+impl NetworkImportReport {
+    pub fn changed(&self) -> usize {
+        self.added.len() + self.replaced.len()
+    }
+}
+
+/// Decide what importing `profile` into `existing` does
+///
+/// Returns the networks to install, marked custom, and the report.
+pub fn plan_import(
+    existing: &HashMap<NetworkId, NetworkConfig>,
+    profile: &NetworkProfile,
+    resolution: ConflictResolution,
+) -> Result<(Vec<NetworkConfig>, NetworkImportReport)> {
+    let mut install = Vec::new();
+    let mut report = NetworkImportReport::default();
+
+    for network in &profile.networks {
+        let incoming = NetworkConfig {
+            is_custom: true,
+            ..network.clone()
+        };
+        match existing.get(&incoming.id) {
+            None => {
+                report.added.push(incoming.id);
+                install.push(incoming);
+            }
+            Some(current) if *current == incoming => report.unchanged.push(incoming.id),
+            Some(current) => match resolution {
+                ConflictResolution::Skip => report.skipped.push(incoming.id),
+                ConflictResolution::Replace => {
+                    report.replaced.push(incoming.id);
+                    install.push(incoming);
+                }
+                ConflictResolution::Abort => {
+                    return Err(VaughanError::ValidationError(format!(
+                        "Chain ID {} is already configured as {}",
+                        incoming.chain_id, current.name
+                    )));
+                }
+            },
+        }
+    }
+    Ok((install, report))
+}
+
+fn validate_network(network: &NetworkConfig) -> Result<()> {
+    let invalid = |reason: String| VaughanError::ValidationError(format!("Network {:?}: {reason}", network.name));
+
+    if network.name.trim().is_empty() {
+        return Err(invalid("name is empty".to_string()));
+    }
+    if network.chain_id == 0 || network.id != NetworkId(network.chain_id) {
+        return Err(invalid(format!(
+            "ID {} does not match chain ID {}",
+            network.id.0, network.chain_id
+        )));
+    }
+    for rpc in network.rpc_urls() {
+        let scheme_ok = url::Url::parse(rpc)
+            .map(|url| matches!(url.scheme(), "http" | "https" | "ws" | "wss"))
+            .unwrap_or(false);
+        if !scheme_ok {
+            return Err(invalid(format!("invalid RPC URL {rpc:?}")));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(chain_id: u64, rpc_url: &str) -> NetworkConfig {
+        NetworkConfig {
+            id: NetworkId(chain_id),
+            name: format!("Team chain {chain_id}"),
+            rpc_url: rpc_url.to_string(),
+            chain_id,
+            symbol: "TEAM".to_string(),
+            block_explorer_url: "https://explorer.example.com".to_string(),
+            is_testnet: false,
+            is_custom: true,
+            fallback_rpc_urls: vec!["https://backup.example.com".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_profile_round_trip_and_validation() {
+        let mut keys = ExplorerApiConfig::default();
+        keys.api_keys.insert("etherscan".to_string(), "team-key".to_string());
+        let profile = NetworkProfile::new(vec![network(9001, "https://rpc.example.com")]).with_explorer_keys(&keys);
+
+        let parsed = NetworkProfile::from_json(&profile.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, profile);
+        assert_eq!(parsed.networks[0].fallback_rpc_urls.len(), 1);
+
+        let mut target = ExplorerApiConfig::default();
+        assert_eq!(parsed.apply_explorer_keys(&mut target), 1);
+        assert_eq!(target.api_keys.get("etherscan").map(String::as_str), Some("team-key"));
+
+        let bad = NetworkProfile::new(vec![network(9002, "file:///etc/passwd")]);
+        assert!(NetworkProfile::from_json(&bad.to_json().unwrap()).is_err());
+        let mut mismatched = network(9003, "https://rpc.example.com");
+        mismatched.id = NetworkId(1);
+        assert!(NetworkProfile::from_json(&NetworkProfile::new(vec![mismatched]).to_json().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_conflict_resolution() {
+        let existing: HashMap<NetworkId, NetworkConfig> = [
+            network(9001, "https://old.example.com"),
+            network(9002, "https://rpc.example.com"),
+        ]
+        .into_iter()
+        .map(|n| (n.id, n))
+        .collect();
+        let profile = NetworkProfile::new(vec![
+            network(9001, "https://new.example.com"),
+            network(9002, "https://rpc.example.com"),
+            network(9003, "https://rpc.example.com"),
+        ]);
+
+        let (install, report) = plan_import(&existing, &profile, ConflictResolution::Skip).unwrap();
+        assert_eq!(install.len(), 1);
+        assert_eq!(report.added, vec![NetworkId(9003)]);
+        assert_eq!(report.skipped, vec![NetworkId(9001)]);
+        assert_eq!(report.unchanged, vec![NetworkId(9002)]);
+
+        let (install, report) = plan_import(&existing, &profile, ConflictResolution::Replace).unwrap();
+        assert_eq!(install.len(), 2);
+        assert_eq!(report.replaced, vec![NetworkId(9001)]);
+        assert_eq!(report.changed(), 2);
+
+        assert!(plan_import(&existing, &profile, ConflictResolution::Abort).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! Request-class aware endpoint routing
+//!
+//! Not every RPC call has the same needs. Gas price and nonce lookups right
+//! before a send should hit the fastest healthy endpoint, while history
+//! backfills and log scans are better sent to endpoints that tolerate heavy
+//! traffic, keeping them away from the endpoint serving interactive requests.
+//!
+//! Routing decisions are made from the continuous health monitor's
+//! observations (status and last measured latency) of each endpoint.
+
+use super::professional::{EndpointHealth, RpcEndpoint};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Class of an RPC request, used to pick an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestClass {
+    /// Needed right away, e.g. gas price or nonce before a send
+    LatencySensitive,
+    /// Regular reads
+    Standard,
+    /// Large or numerous requests, e.g. history backfill and log scans
+    Bulk,
+}
+
+impl RequestClass {
+    /// Classify a JSON-RPC method
+    pub fn for_method(method: &str) -> Self {
+        match method {
+            "eth_gasPrice"
+            | "eth_maxPriorityFeePerGas"
+            | "eth_feeHistory"
+            | "eth_getTransactionCount"
+            | "eth_estimateGas"
+            | "eth_sendRawTransaction" => RequestClass::LatencySensitive,
+            "eth_getLogs" | "eth_getBlockReceipts" | "eth_getFilterLogs" => RequestClass::Bulk,
+            m if m.starts_with("trace_") || m.starts_with("debug_") => RequestClass::Bulk,
+            _ => RequestClass::Standard,
+        }
+    }
+}
+
+/// What the health monitor last observed for an endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointObservation {
+    pub status: EndpointHealth,
+    /// Latency of the last successful health check
+    pub latency: Option<Duration>,
+}
+
+fn status_of(endpoint: &RpcEndpoint, observations: &HashMap<String, EndpointObservation>) -> EndpointHealth {
+    observations
+        .get(&endpoint.url)
+        .map(|o| o.status.clone())
+        .unwrap_or_else(|| endpoint.health_status.clone())
+}
+
+fn latency_of(endpoint: &RpcEndpoint, observations: &HashMap<String, EndpointObservation>) -> Option<Duration> {
+    observations.get(&endpoint.url).and_then(|o| o.latency).or_else(|| {
+        (endpoint.performance_metrics.total_requests > 0).then_some(endpoint.performance_metrics.average_latency)
+    })
+}
+
+/// Pick an endpoint index for a request class
+///
+/// Only healthy endpoints (or ones not checked yet) are considered; degraded
+/// endpoints are used when nothing better is available. Returns `None` when
+/// every endpoint is unhealthy.
+pub fn select_endpoint(
+    class: RequestClass,
+    endpoints: &[RpcEndpoint],
+    observations: &HashMap<String, EndpointObservation>,
+) -> Option<usize> {
+    let candidates = |accept: &dyn Fn(&EndpointHealth) -> bool| -> Vec<usize> {
+        endpoints
+            .iter()
+            .enumerate()
+            .filter(|(_, ep)| accept(&status_of(ep, observations)))
+            .map(|(i, _)| i)
+            .collect()
+    };
+
+    let mut usable = candidates(&|s| matches!(s, EndpointHealth::Healthy | EndpointHealth::Unknown));
+    if usable.is_empty() {
+        usable = candidates(&|s| *s == EndpointHealth::Degraded);
+    }
+    if usable.is_empty() {
+        return None;
+    }
+
+    // Unmeasured endpoints sort after measured ones
+    let latency = |i: usize| latency_of(&endpoints[i], observations).unwrap_or(Duration::MAX);
+    let fastest = *usable.iter().min_by_key(|&&i| (latency(i), endpoints[i].priority))?;
+
+    match class {
+        RequestClass::LatencySensitive => Some(fastest),
+        RequestClass::Standard => usable
+            .iter()
+            .copied()
+            .min_by_key(|&i| (endpoints[i].priority, latency(i))),
+        RequestClass::Bulk => {
+            // Keep bulk traffic off the fastest endpoint when there is a choice,
+            // preferring endpoints that allow the most concurrent requests
+            let others: Vec<usize> = usable.iter().copied().filter(|&i| i != fastest).collect();
+            let pool = if others.is_empty() { &usable } else { &others };
+            pool.iter().copied().max_by_key(|&i| {
+                let ep = &endpoints[i];
+                (
+                    ep.max_concurrent_requests,
+                    std::cmp::Reverse(ep.performance_metrics.consecutive_failures),
+                    std::cmp::Reverse(ep.priority),
+                )
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::professional::PerformanceMetrics;
+
+    fn endpoint(url: &str, priority: u32, max_concurrent_requests: u32) -> RpcEndpoint {
+        RpcEndpoint {
+            url: url.to_string(),
+            priority,
+            weight: 1.0,
+            max_concurrent_requests,
+            timeout: Duration::from_secs(10),
+            health_status: EndpointHealth::Unknown,
+            performance_metrics: PerformanceMetrics::default(),
+            auth_config: None,
+        }
+    }
+
+    fn observed(status: EndpointHealth, latency_ms: u64) -> EndpointObservation {
+        EndpointObservation {
+            status,
+            latency: Some(Duration::from_millis(latency_ms)),
+        }
+    }
+
+    fn cluster() -> (Vec<RpcEndpoint>, HashMap<String, EndpointObservation>) {
+        let endpoints = vec![
+            endpoint("https://primary", 1, 50),
+            endpoint("https://fast", 2, 20),
+            endpoint("https://archive", 3, 200),
+        ];
+        let observations = HashMap::from([
+            ("https://primary".to_string(), observed(EndpointHealth::Healthy, 120)),
+            ("https://fast".to_string(), observed(EndpointHealth::Healthy, 40)),
+            ("https://archive".to_string(), observed(EndpointHealth::Healthy, 300)),
+        ]);
+        (endpoints, observations)
+    }
+
+    #[test]
+    fn test_method_classification() {
+        assert_eq!(RequestClass::for_method("eth_gasPrice"), RequestClass::LatencySensitive);
+        assert_eq!(
+            RequestClass::for_method("eth_getTransactionCount"),
+            RequestClass::LatencySensitive
+        );
+        assert_eq!(RequestClass::for_method("eth_getLogs"), RequestClass::Bulk);
+        assert_eq!(RequestClass::for_method("trace_block"), RequestClass::Bulk);
+        assert_eq!(RequestClass::for_method("eth_getBalance"), RequestClass::Standard);
+    }
+
+    #[test]
+    fn test_routing_per_class() {
+        let (endpoints, observations) = cluster();
+
+        assert_eq!(
+            select_endpoint(RequestClass::LatencySensitive, &endpoints, &observations),
+            Some(1)
+        );
+        assert_eq!(
+            select_endpoint(RequestClass::Standard, &endpoints, &observations),
+            Some(0)
+        );
+        assert_eq!(select_endpoint(RequestClass::Bulk, &endpoints, &observations), Some(2));
+    }
+
+    #[test]
+    fn test_unhealthy_endpoints_are_skipped() {
+        let (endpoints, mut observations) = cluster();
+        observations.insert("https://fast".to_string(), observed(EndpointHealth::Unhealthy, 10));
+        observations.insert("https://archive".to_string(), observed(EndpointHealth::Degraded, 300));
+
+        assert_eq!(
+            select_endpoint(RequestClass::LatencySensitive, &endpoints, &observations),
+            Some(0)
+        );
+        // Only one healthy endpoint left, so bulk shares it
+        assert_eq!(select_endpoint(RequestClass::Bulk, &endpoints, &observations), Some(0));
+
+        observations.insert("https://primary".to_string(), observed(EndpointHealth::Unhealthy, 10));
+        assert_eq!(
+            select_endpoint(RequestClass::Standard, &endpoints, &observations),
+            Some(2)
+        );
+
+        observations.insert("https://archive".to_string(), observed(EndpointHealth::Unhealthy, 10));
+        assert_eq!(select_endpoint(RequestClass::Standard, &endpoints, &observations), None);
+    }
+}
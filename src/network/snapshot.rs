@@ -0,0 +1,331 @@
+//! Snapshot-consistent chain reads for UI refresh
+//!
+//! A dashboard refresh reads balances, nonces and fees with several RPC
+//! calls. When a block lands between them the values come from different
+//! chain states and the UI flickers. A snapshot fixes the head block first
+//! and reads everything at that block:
+//!
+//! - [`SnapshotMode::PinnedBlock`] issues one call per value, each pinned to
+//!   the block number
+//! - [`SnapshotMode::Multicall`] folds all balances into a single Multicall3
+//!   `aggregate3` call at the pinned block; nonces are still read per account,
+//!   since contracts cannot see them
+//!
+//! Multicall falls back to pinned reads on chains without Multicall3 or when
+//! the aggregate call fails. The block number is part of the result so the
+//! UI can show which state it displays.
+
+use alloy::eips::{BlockId, BlockNumberOrTag};
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use futures_util::future::try_join_all;
+use std::collections::HashMap;
+
+use crate::error::{NetworkError, Result};
+use crate::performance::multicall::{get_multicall3_address, is_multicall3_supported, IMulticall3};
+
+sol! {
+    interface IErc20Balance {
+        function balanceOf(address owner) external view returns (uint256);
+    }
+}
+
+/// How a snapshot reads its values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotMode {
+    /// One RPC call per value, each pinned to the snapshot block
+    PinnedBlock,
+    /// Balances in a single Multicall3 call at the snapshot block
+    Multicall,
+}
+
+/// Values to read in one snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRequest {
+    pub chain_id: u64,
+    pub accounts: Vec<Address>,
+    /// `(owner, token)` pairs
+    pub token_balances: Vec<(Address, Address)>,
+    pub mode: SnapshotMode,
+}
+
+impl SnapshotRequest {
+    /// Native balances and nonces of `accounts`, read through Multicall3
+    pub fn new(chain_id: u64, accounts: Vec<Address>) -> Self {
+        Self {
+            chain_id,
+            accounts,
+            token_balances: Vec::new(),
+            mode: SnapshotMode::Multicall,
+        }
+    }
+
+    /// Also read `owner`'s balance of ERC-20 `token`
+    pub fn with_token_balance(mut self, owner: Address, token: Address) -> Self {
+        self.token_balances.push((owner, token));
+        self
+    }
+
+    pub fn with_mode(mut self, mode: SnapshotMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+/// Chain state as of a single block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSnapshot {
+    pub block_number: u64,
+    pub block_hash: B256,
+    pub timestamp: u64,
+    pub base_fee_per_gas: Option<u64>,
+    /// Mode actually used, after any fallback
+    pub mode: SnapshotMode,
+    pub native_balances: HashMap<Address, U256>,
+    pub nonces: HashMap<Address, u64>,
+    /// Keyed by `(owner, token)`; tokens whose call reverted are missing
+    pub token_balances: HashMap<(Address, Address), U256>,
+}
+
+impl ChainSnapshot {
+    pub fn native_balance(&self, account: Address) -> Option<U256> {
+        self.native_balances.get(&account).copied()
+    }
+
+    pub fn nonce(&self, account: Address) -> Option<u64> {
+        self.nonces.get(&account).copied()
+    }
+
+    pub fn token_balance(&self, owner: Address, token: Address) -> Option<U256> {
+        self.token_balances.get(&(owner, token)).copied()
+    }
+}
+
+fn rpc_error(what: &str, e: impl std::fmt::Display) -> NetworkError {
+    NetworkError::RpcError {
+        message: format!("Failed to read {what} for snapshot: {e}"),
+    }
+}
+
+/// Read a snapshot of `request` at the current head block
+pub async fn read_snapshot<P: Provider>(provider: &P, request: &SnapshotRequest) -> Result<ChainSnapshot> {
+    let block = provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await
+        .map_err(|e| rpc_error("head block", e))?
+        .ok_or_else(|| rpc_error("head block", "node returned no block"))?;
+    let header = &block.header;
+    let at = BlockId::number(header.number);
+
+    let nonces = try_join_all(request.accounts.iter().map(|&account| async move {
+        provider
+            .get_transaction_count(account)
+            .block_id(at)
+            .await
+            .map(|nonce| (account, nonce))
+            .map_err(|e| rpc_error("nonce", e))
+    }))
+    .await?;
+
+    let multicall = match request.mode {
+        SnapshotMode::Multicall if is_multicall3_supported(request.chain_id) => {
+            match read_balances_multicall(provider, request, at).await {
+                Ok(balances) => Some(balances),
+                Err(e) => {
+                    tracing::warn!("⚠️ Multicall3 snapshot failed, falling back to pinned reads: {}", e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let mode = if multicall.is_some() {
+        SnapshotMode::Multicall
+    } else {
+        SnapshotMode::PinnedBlock
+    };
+    let (native_balances, token_balances) = match multicall {
+        Some(balances) => balances,
+        None => read_balances_pinned(provider, request, at).await?,
+    };
+
+    tracing::debug!(
+        block = header.number,
+        ?mode,
+        accounts = request.accounts.len(),
+        tokens = token_balances.len(),
+        "📸 Chain snapshot read"
+    );
+    Ok(ChainSnapshot {
+        block_number: header.number,
+        block_hash: header.hash,
+        timestamp: header.timestamp,
+        base_fee_per_gas: header.base_fee_per_gas,
+        mode,
+        native_balances,
+        nonces: nonces.into_iter().collect(),
+        token_balances,
+    })
+}
+
+type Balances = (HashMap<Address, U256>, HashMap<(Address, Address), U256>);
+
+async fn read_balances_pinned<P: Provider>(provider: &P, request: &SnapshotRequest, at: BlockId) -> Result<Balances> {
+    let native = try_join_all(request.accounts.iter().map(|&account| async move {
+        provider
+            .get_balance(account)
+            .block_id(at)
+            .await
+            .map(|balance| (account, balance))
+            .map_err(|e| rpc_error("balance", e))
+    }))
+    .await?;
+
+    let tokens = try_join_all(request.token_balances.iter().map(|&(owner, token)| async move {
+        let call = TransactionRequest::default()
+            .to(token)
+            .input(Bytes::from(IErc20Balance::balanceOfCall { owner }.abi_encode()).into());
+        let result = provider.call(call).block(at).await;
+        Ok::<_, NetworkError>(match result {
+            Ok(data) => IErc20Balance::balanceOfCall::abi_decode_returns(&data)
+                .ok()
+                .map(|balance| ((owner, token), balance)),
+            Err(e) => {
+                tracing::warn!("⚠️ Token {} balance read failed in snapshot: {}", token, e);
+                None
+            }
+        })
+    }))
+    .await?;
+
+    Ok((native.into_iter().collect(), tokens.into_iter().flatten().collect()))
+}
+
+async fn read_balances_multicall<P: Provider>(
+    provider: &P,
+    request: &SnapshotRequest,
+    at: BlockId,
+) -> Result<Balances> {
+    let multicall = get_multicall3_address(request.chain_id);
+    let native_calls = request.accounts.iter().map(|&addr| IMulticall3::Call3 {
+        target: multicall,
+        allowFailure: false,
+        callData: IMulticall3::getEthBalanceCall { addr }.abi_encode().into(),
+    });
+    let token_calls = request.token_balances.iter().map(|&(owner, token)| IMulticall3::Call3 {
+        target: token,
+        allowFailure: true,
+        callData: IErc20Balance::balanceOfCall { owner }.abi_encode().into(),
+    });
+    let calls: Vec<_> = native_calls.chain(token_calls).collect();
+
+    let call = TransactionRequest::default()
+        .to(multicall)
+        .input(Bytes::from(IMulticall3::aggregate3Call { calls }.abi_encode()).into());
+    let data = provider
+        .call(call)
+        .block(at)
+        .await
+        .map_err(|e| rpc_error("Multicall3 aggregate", e))?;
+    let results =
+        IMulticall3::aggregate3Call::abi_decode_returns(&data).map_err(|e| rpc_error("Multicall3 aggregate", e))?;
+    if results.len() != request.accounts.len() + request.token_balances.len() {
+        return Err(rpc_error("Multicall3 aggregate", "unexpected number of results").into());
+    }
+
+    let (native_results, token_results) = results.split_at(request.accounts.len());
+    let native = request
+        .accounts
+        .iter()
+        .zip(native_results)
+        .map(|(&account, result)| {
+            IMulticall3::getEthBalanceCall::abi_decode_returns(&result.returnData)
+                .map(|balance| (account, balance))
+                .map_err(|e| rpc_error("balance", e).into())
+        })
+        .collect::<Result<_>>()?;
+    let tokens = request
+        .token_balances
+        .iter()
+        .zip(token_results)
+        .filter(|(_, result)| result.success)
+        .filter_map(|(&key, result)| {
+            IErc20Balance::balanceOfCall::abi_decode_returns(&result.returnData)
+                .ok()
+                .map(|balance| (key, balance))
+        })
+        .collect();
+    Ok((native, tokens))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::ProviderBuilder;
+    use alloy::sol_types::SolValue;
+    use alloy::transports::mock::Asserter;
+
+    fn head_block(number: u64) -> serde_json::Value {
+        let mut block = serde_json::to_value(alloy::rpc::types::Block::<TransactionRequest>::default()).unwrap();
+        block["number"] = format!("{number:#x}").into();
+        block["timestamp"] = "0x6553f100".into();
+        block["baseFeePerGas"] = "0x3b9aca00".into();
+        block
+    }
+
+    #[tokio::test]
+    async fn test_multicall_snapshot_reads_one_block() {
+        let account = Address::repeat_byte(0x11);
+        let token = Address::repeat_byte(0x22);
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+
+        asserter.push_success(&head_block(100));
+        asserter.push_success(&"0x7");
+        let results = vec![
+            IMulticall3::Result {
+                success: true,
+                returnData: U256::from(5u64).abi_encode().into(),
+            },
+            IMulticall3::Result {
+                success: true,
+                returnData: U256::from(9u64).abi_encode().into(),
+            },
+        ];
+        asserter.push_success(&Bytes::from(results.abi_encode()));
+
+        let request = SnapshotRequest::new(1, vec![account]).with_token_balance(account, token);
+        let snapshot = read_snapshot(&provider, &request).await.unwrap();
+        assert_eq!(snapshot.block_number, 100);
+        assert_eq!(snapshot.mode, SnapshotMode::Multicall);
+        assert_eq!(snapshot.base_fee_per_gas, Some(1_000_000_000));
+        assert_eq!(snapshot.nonce(account), Some(7));
+        assert_eq!(snapshot.native_balance(account), Some(U256::from(5u64)));
+        assert_eq!(snapshot.token_balance(account, token), Some(U256::from(9u64)));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_chain_uses_pinned_reads() {
+        let account = Address::repeat_byte(0x11);
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+
+        asserter.push_success(&head_block(42));
+        asserter.push_success(&"0x1");
+        asserter.push_success(&"0x64");
+
+        let request = SnapshotRequest::new(999_999, vec![account]);
+        let snapshot = read_snapshot(&provider, &request).await.unwrap();
+        assert_eq!(snapshot.block_number, 42);
+        assert_eq!(snapshot.mode, SnapshotMode::PinnedBlock);
+        assert_eq!(snapshot.nonce(account), Some(1));
+        assert_eq!(snapshot.native_balance(account), Some(U256::from(100u64)));
+    }
+}
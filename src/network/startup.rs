@@ -0,0 +1,221 @@
+//! Startup network selection
+//!
+//! Decides which network the wallet connects to at launch. The choice is
+//! resolved in this order:
+//!
+//! 1. The network last used by the active profile (if remembering is enabled)
+//! 2. The `default_network` from `startup_network.json`
+//! 3. A build-dependent fallback: PulseChain Testnet v4 for debug builds,
+//!    Ethereum Mainnet for release builds
+//!
+//! Preferences are stored in `<config_dir>/vaughan/startup_network.json`.
+
+use super::NetworkId;
+use crate::error::{ConfigurationError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Profile used when `VAUGHAN_PROFILE` is not set
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Network used when nothing else is configured
+///
+/// Debug builds keep the historical testnet default so development never
+/// touches mainnet funds by accident; release builds start on Ethereum Mainnet.
+pub fn fallback_network() -> NetworkId {
+    if cfg!(debug_assertions) {
+        crate::networks::PULSECHAIN_TESTNET
+    } else {
+        crate::networks::ETHEREUM_MAINNET
+    }
+}
+
+/// Name of the active wallet profile (`VAUGHAN_PROFILE`, or `"default"`)
+pub fn current_profile() -> String {
+    std::env::var("VAUGHAN_PROFILE")
+        .ok()
+        .filter(|profile| !profile.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Get the storage path for startup network preferences
+pub fn get_startup_network_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("vaughan");
+    path.push("startup_network.json");
+    path
+}
+
+/// Persisted startup network preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StartupNetworkPreferences {
+    /// Explicitly configured startup network (chain ID)
+    #[serde(default)]
+    pub default_network: Option<u64>,
+    /// Whether the last used network should take precedence at startup
+    #[serde(default = "default_remember_last_used")]
+    pub remember_last_used: bool,
+    /// Last used network (chain ID) per profile
+    #[serde(default)]
+    pub last_used: HashMap<String, u64>,
+}
+
+fn default_remember_last_used() -> bool {
+    true
+}
+
+impl Default for StartupNetworkPreferences {
+    fn default() -> Self {
+        Self {
+            default_network: None,
+            remember_last_used: true,
+            last_used: HashMap::new(),
+        }
+    }
+}
+
+impl StartupNetworkPreferences {
+    /// Load preferences from the default location, falling back to defaults
+    pub fn load() -> Self {
+        Self::load_from(&get_startup_network_path())
+    }
+
+    /// Load preferences from a specific file, falling back to defaults
+    ///
+    /// A missing or corrupt file is not an error: the wallet must always be able
+    /// to start, so problems are logged and defaults are used instead.
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid startup network file {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save preferences to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_startup_network_path())
+    }
+
+    /// Save preferences to a specific file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self).map_err(|e| ConfigurationError::ParseError {
+            message: format!("Failed to serialize startup network preferences: {e}"),
+        })?;
+        std::fs::write(path, contents)?;
+
+        tracing::debug!("Saved startup network preferences to {}", path.display());
+        Ok(())
+    }
+
+    /// Resolve the network to start on for `profile`
+    ///
+    /// `is_available` is used to skip networks that no longer exist (for
+    /// example a removed custom network).
+    pub fn resolve(&self, profile: &str, is_available: impl Fn(NetworkId) -> bool) -> NetworkId {
+        if self.remember_last_used {
+            if let Some(network) = self.last_used.get(profile).map(|id| NetworkId(*id)) {
+                if is_available(network) {
+                    return network;
+                }
+                tracing::warn!("Last used network {} is no longer available", network.0);
+            }
+        }
+
+        if let Some(network) = self.default_network.map(NetworkId) {
+            if is_available(network) {
+                return network;
+            }
+            tracing::warn!("Configured startup network {} is not available", network.0);
+        }
+
+        fallback_network()
+    }
+
+    /// Remember `network` as the last used network for `profile`
+    pub fn record_last_used(&mut self, profile: &str, network: NetworkId) {
+        self.last_used.insert(profile.to_string(), network.0);
+    }
+
+    /// Set or clear the explicitly configured startup network
+    pub fn set_default_network(&mut self, network: Option<NetworkId>) {
+        self.default_network = network.map(|n| n.0);
+    }
+}
+
+/// Resolve the startup network for the active profile from stored preferences
+pub fn resolve_startup_network(is_available: impl Fn(NetworkId) -> bool) -> NetworkId {
+    StartupNetworkPreferences::load().resolve(&current_profile(), is_available)
+}
+
+/// Persist `network` as the last used network for the active profile
+pub fn remember_last_used_network(network: NetworkId) -> Result<()> {
+    let mut preferences = StartupNetworkPreferences::load();
+    if !preferences.remember_last_used {
+        return Ok(());
+    }
+    preferences.record_last_used(&current_profile(), network);
+    preferences.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_last_used() {
+        let mut prefs = StartupNetworkPreferences::default();
+        prefs.set_default_network(Some(NetworkId(1)));
+        prefs.record_last_used("alice", NetworkId(369));
+
+        assert_eq!(prefs.resolve("alice", |_| true), NetworkId(369));
+        assert_eq!(prefs.resolve("bob", |_| true), NetworkId(1));
+    }
+
+    #[test]
+    fn test_resolve_skips_unavailable_and_disabled() {
+        let mut prefs = StartupNetworkPreferences::default();
+        prefs.set_default_network(Some(NetworkId(56)));
+        prefs.record_last_used(DEFAULT_PROFILE, NetworkId(12345));
+
+        assert_eq!(prefs.resolve(DEFAULT_PROFILE, |n| n != NetworkId(12345)), NetworkId(56));
+
+        prefs.record_last_used(DEFAULT_PROFILE, NetworkId(137));
+        prefs.remember_last_used = false;
+        assert_eq!(prefs.resolve(DEFAULT_PROFILE, |_| true), NetworkId(56));
+
+        prefs.set_default_network(None);
+        assert_eq!(prefs.resolve(DEFAULT_PROFILE, |_| true), fallback_network());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("startup_network.json");
+
+        let mut prefs = StartupNetworkPreferences::default();
+        prefs.record_last_used("work", NetworkId(56));
+        prefs.save_to(&path).unwrap();
+
+        assert_eq!(StartupNetworkPreferences::load_from(&path), prefs);
+    }
+
+    #[test]
+    fn test_load_corrupt_file_uses_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("startup_network.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        assert_eq!(
+            StartupNetworkPreferences::load_from(&path),
+            StartupNetworkPreferences::default()
+        );
+    }
+}
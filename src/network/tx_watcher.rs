@@ -0,0 +1,398 @@
+//! Transaction receipt watcher
+//!
+//! After a transaction is broadcast, the watcher polls the network for its
+//! receipt and publishes confirmation events on a tokio broadcast channel so
+//! the GUI and telemetry can follow it without polling themselves:
+//!
+//! - `Included` / `Failed` when the first receipt shows up
+//! - `Confirmed` as the confirmation count grows, up to the configured depth
+//! - `Replaced` when the transaction vanished but its nonce was used
+//! - `Dropped` when the transaction vanished and its nonce is still free
+//!
+//! Providers are HTTP-only, so receipts are polled rather than subscribed to.
+
+use super::NetworkId;
+use alloy::consensus::Transaction as _;
+use alloy::network::TransactionResponse;
+use alloy::primitives::{Address, TxHash};
+use alloy::providers::Provider;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// Status change of a watched transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    /// Mined successfully in `block_number`
+    Included { block_number: u64 },
+    /// Mined but reverted
+    Failed { block_number: u64 },
+    /// Reached `confirmations` blocks deep (the including block counts as 1)
+    Confirmed { confirmations: u64 },
+    /// Another transaction with the same nonce was mined
+    Replaced,
+    /// Disappeared from the mempool without its nonce being used
+    Dropped,
+}
+
+impl TxStatus {
+    /// Whether no further events follow this one
+    pub fn is_final(&self, required_confirmations: u64) -> bool {
+        match self {
+            TxStatus::Failed { .. } | TxStatus::Replaced | TxStatus::Dropped => true,
+            TxStatus::Confirmed { confirmations } => *confirmations >= required_confirmations,
+            TxStatus::Included { .. } => required_confirmations <= 1,
+        }
+    }
+}
+
+/// Event published for a watched transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxEvent {
+    pub tx_hash: TxHash,
+    pub network: NetworkId,
+    pub status: TxStatus,
+}
+
+/// Watcher timing and depth settings
+#[derive(Debug, Clone)]
+pub struct TxWatcherConfig {
+    pub poll_interval: Duration,
+    /// Confirmations after which a transaction is considered final
+    pub required_confirmations: u64,
+    /// How long a transaction may be missing from the node before it is reported dropped
+    pub drop_timeout: Duration,
+}
+
+impl Default for TxWatcherConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(4),
+            required_confirmations: 12,
+            drop_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// One poll's view of a transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxObservation {
+    /// Block number and success flag of the receipt, if mined
+    pub receipt: Option<(u64, bool)>,
+    /// Sender and nonce, if the node knows the transaction
+    pub transaction: Option<(Address, u64)>,
+    pub head_block: u64,
+    /// Mined transaction count of the sender, if known
+    pub account_nonce: Option<u64>,
+}
+
+/// Turns successive observations into status events
+#[derive(Debug, Clone)]
+pub struct TxWatchState {
+    required_confirmations: u64,
+    drop_timeout: Duration,
+    sender_nonce: Option<(Address, u64)>,
+    included_in: Option<u64>,
+    confirmations: u64,
+    missing_since: Option<Instant>,
+    finished: bool,
+}
+
+impl TxWatchState {
+    pub fn new(config: &TxWatcherConfig) -> Self {
+        Self {
+            required_confirmations: config.required_confirmations.max(1),
+            drop_timeout: config.drop_timeout,
+            sender_nonce: None,
+            included_in: None,
+            confirmations: 0,
+            missing_since: None,
+            finished: false,
+        }
+    }
+
+    /// Sender and nonce learned from the node, if any
+    pub fn sender_nonce(&self) -> Option<(Address, u64)> {
+        self.sender_nonce
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Apply an observation made at `now`, returning the resulting events
+    pub fn observe(&mut self, observation: &TxObservation, now: Instant) -> Vec<TxStatus> {
+        if self.finished {
+            return Vec::new();
+        }
+        if let Some(sender_nonce) = observation.transaction {
+            self.sender_nonce = Some(sender_nonce);
+        }
+
+        let mut events = Vec::new();
+        match observation.receipt {
+            Some((block_number, success)) => {
+                self.missing_since = None;
+                if self.included_in != Some(block_number) {
+                    // First inclusion, or re-included in another block after a reorg
+                    self.included_in = Some(block_number);
+                    self.confirmations = 0;
+                    if !success {
+                        events.push(TxStatus::Failed { block_number });
+                        self.finished = true;
+                        return events;
+                    }
+                    events.push(TxStatus::Included { block_number });
+                }
+
+                let confirmations =
+                    (observation.head_block.saturating_sub(block_number) + 1).min(self.required_confirmations);
+                if confirmations > self.confirmations {
+                    self.confirmations = confirmations;
+                    if confirmations > 1 {
+                        events.push(TxStatus::Confirmed { confirmations });
+                    }
+                }
+            }
+            None => {
+                // Receipt vanished after a reorg; wait for re-inclusion
+                self.included_in = None;
+                self.confirmations = 0;
+
+                let nonce_used = match (self.sender_nonce, observation.account_nonce) {
+                    (Some((_, nonce)), Some(account_nonce)) => account_nonce > nonce,
+                    _ => false,
+                };
+
+                if observation.transaction.is_none() && nonce_used {
+                    events.push(TxStatus::Replaced);
+                } else if observation.transaction.is_none() {
+                    let since = *self.missing_since.get_or_insert(now);
+                    if now.duration_since(since) >= self.drop_timeout {
+                        events.push(TxStatus::Dropped);
+                    }
+                } else {
+                    self.missing_since = None;
+                }
+            }
+        }
+
+        if events.iter().any(|e| e.is_final(self.required_confirmations)) {
+            self.finished = true;
+        }
+        events
+    }
+}
+
+/// Watches broadcast transactions and publishes [`TxEvent`]s
+#[derive(Debug, Clone)]
+pub struct TxWatcher {
+    sender: broadcast::Sender<TxEvent>,
+    config: TxWatcherConfig,
+}
+
+impl TxWatcher {
+    pub fn new(config: TxWatcherConfig) -> Self {
+        let (sender, _) = broadcast::channel(256);
+        Self { sender, config }
+    }
+
+    /// Subscribe to events for all watched transactions
+    pub fn subscribe(&self) -> broadcast::Receiver<TxEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn config(&self) -> &TxWatcherConfig {
+        &self.config
+    }
+
+    /// Publish an event learned outside the watch loop, e.g. while resolving a broadcast
+    pub fn publish(&self, event: TxEvent) {
+        tracing::info!("📬 Transaction {} on network {}: {:?}", event.tx_hash, event.network.0, event.status);
+        // No subscribers is fine
+        let _ = self.sender.send(event);
+    }
+
+    /// Start watching a transaction in the background
+    pub fn watch<P>(&self, provider: P, network: NetworkId, tx_hash: TxHash) -> tokio::task::JoinHandle<()>
+    where
+        P: Provider + Send + Sync + 'static,
+    {
+        let sender = self.sender.clone();
+        let config = self.config.clone();
+
+        tokio::spawn(async move {
+            let mut state = TxWatchState::new(&config);
+            let mut interval = tokio::time::interval(config.poll_interval);
+            tracing::debug!("👀 Watching transaction {} on network {}", tx_hash, network.0);
+
+            while !state.is_finished() {
+                interval.tick().await;
+
+                let observation = match poll(&provider, tx_hash, state.sender_nonce()).await {
+                    Ok(observation) => observation,
+                    Err(e) => {
+                        tracing::warn!("⚠️ Receipt poll for {} failed: {}", tx_hash, e);
+                        continue;
+                    }
+                };
+
+                for status in state.observe(&observation, Instant::now()) {
+                    tracing::info!("📬 Transaction {} on network {}: {:?}", tx_hash, network.0, status);
+                    // No subscribers is fine
+                    let _ = sender.send(TxEvent {
+                        tx_hash,
+                        network,
+                        status,
+                    });
+                }
+            }
+        })
+    }
+}
+
+impl Default for TxWatcher {
+    fn default() -> Self {
+        Self::new(TxWatcherConfig::default())
+    }
+}
+
+/// Observe a transaction once: receipt, head block and, while unmined, sender nonce
+pub(crate) async fn poll<P: Provider>(
+    provider: &P,
+    tx_hash: TxHash,
+    known: Option<(Address, u64)>,
+) -> std::result::Result<TxObservation, alloy::transports::TransportError> {
+    let receipt = provider.get_transaction_receipt(tx_hash).await?;
+    let head_block = provider.get_block_number().await?;
+
+    let mut observation = TxObservation {
+        receipt: receipt.and_then(|r| Some((r.block_number?, r.status()))),
+        head_block,
+        ..Default::default()
+    };
+
+    if observation.receipt.is_none() {
+        observation.transaction = provider
+            .get_transaction_by_hash(tx_hash)
+            .await?
+            .map(|tx| (tx.from(), tx.nonce()));
+
+        if let Some((from, _)) = observation.transaction.or(known) {
+            observation.account_nonce = Some(provider.get_transaction_count(from).latest().await?);
+        }
+    }
+
+    Ok(observation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: Address = Address::repeat_byte(0x11);
+
+    fn config(required_confirmations: u64) -> TxWatcherConfig {
+        TxWatcherConfig {
+            poll_interval: Duration::from_millis(10),
+            required_confirmations,
+            drop_timeout: Duration::from_secs(60),
+        }
+    }
+
+    fn pending() -> TxObservation {
+        TxObservation {
+            transaction: Some((SENDER, 5)),
+            head_block: 100,
+            account_nonce: Some(5),
+            ..Default::default()
+        }
+    }
+
+    fn mined(block: u64, head_block: u64, success: bool) -> TxObservation {
+        TxObservation {
+            receipt: Some((block, success)),
+            head_block,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_inclusion_and_confirmations() {
+        let mut state = TxWatchState::new(&config(3));
+        let now = Instant::now();
+
+        assert!(state.observe(&pending(), now).is_empty());
+        assert_eq!(
+            state.observe(&mined(101, 101, true), now),
+            vec![TxStatus::Included { block_number: 101 }]
+        );
+        assert!(state.observe(&mined(101, 101, true), now).is_empty());
+        assert_eq!(
+            state.observe(&mined(101, 102, true), now),
+            vec![TxStatus::Confirmed { confirmations: 2 }]
+        );
+        assert_eq!(
+            state.observe(&mined(101, 110, true), now),
+            vec![TxStatus::Confirmed { confirmations: 3 }]
+        );
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn test_reverted_transaction_fails() {
+        let mut state = TxWatchState::new(&config(12));
+        assert_eq!(
+            state.observe(&mined(50, 55, false), Instant::now()),
+            vec![TxStatus::Failed { block_number: 50 }]
+        );
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn test_replaced_when_nonce_used_by_another_transaction() {
+        let mut state = TxWatchState::new(&config(12));
+        let now = Instant::now();
+        state.observe(&pending(), now);
+
+        let replaced = TxObservation {
+            head_block: 101,
+            account_nonce: Some(6),
+            ..Default::default()
+        };
+        assert_eq!(state.observe(&replaced, now), vec![TxStatus::Replaced]);
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn test_dropped_after_timeout() {
+        let mut state = TxWatchState::new(&config(12));
+        let now = Instant::now();
+        state.observe(&pending(), now);
+
+        let missing = TxObservation {
+            head_block: 101,
+            account_nonce: Some(5),
+            ..Default::default()
+        };
+        assert!(state.observe(&missing, now).is_empty());
+        assert!(state.observe(&missing, now + Duration::from_secs(30)).is_empty());
+        assert_eq!(
+            state.observe(&missing, now + Duration::from_secs(61)),
+            vec![TxStatus::Dropped]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let watcher = TxWatcher::new(config(1));
+        let mut receiver = watcher.subscribe();
+
+        let event = TxEvent {
+            tx_hash: TxHash::repeat_byte(1),
+            network: NetworkId(369),
+            status: TxStatus::Included { block_number: 1 },
+        };
+        watcher.sender.send(event.clone()).unwrap();
+        assert_eq!(receiver.recv().await.unwrap(), event);
+    }
+}
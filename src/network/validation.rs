@@ -30,7 +30,6 @@ pub enum NetworkValidationIssue {
 
 /// Validate a network endpoint
 pub async fn validate_network_endpoint(url: &str, expected_chain_id: u64) -> Result<NetworkValidation> {
-    use alloy::providers::ProviderBuilder;
     use std::time::Instant;
 
     let mut issues = Vec::new();
@@ -54,7 +53,7 @@ pub async fn validate_network_endpoint(url: &str, expected_chain_id: u64) -> Res
     }
 
     // Try to create provider and test connection
-    let parsed_url = match url.parse() {
+    let parsed_url: url::Url = match url.parse() {
         Ok(url) => url,
         Err(_) => {
             issues.push(NetworkValidationIssue::InvalidUrl);
@@ -68,7 +67,11 @@ pub async fn validate_network_endpoint(url: &str, expected_chain_id: u64) -> Res
         }
     };
 
-    let provider = ProviderBuilder::new().connect_http(parsed_url);
+    // alloy's transport brings its own reqwest; hold a shared slot so validation
+    // still counts against the HTTP concurrency limits
+    let host = parsed_url.host_str().unwrap_or_default().to_string();
+    let _permit = crate::network::http::shared_client().acquire(&host).await;
+    let provider = crate::network::connect_rpc(url, "network validation")?;
 
     // Test provider connectivity with timeout
     let connectivity_result = tokio::time::timeout(
@@ -258,5 +261,6 @@ pub fn create_network_config(
         },
         is_testnet,
         is_custom: true, // All networks created via this function are custom
+        fallback_rpc_urls: Vec::new(),
     }
 }
@@ -4,6 +4,7 @@
 //! - Batch processing for RPC calls using Alloy
 //! - LRU caching for frequently accessed data
 //! - Multicall3 contract integration for efficient batching
+//! - Per-network caching of RPC responses
 //!
 //! # Requirements Addressed
 //!
@@ -20,8 +21,10 @@
 pub mod batch;
 pub mod cache;
 pub mod multicall;
+pub mod rpc_cache;
 
 pub use batch::*;
 pub use cache::*;
 pub use multicall::*;
+pub use rpc_cache::{RpcCache, RpcCacheConfig};
 
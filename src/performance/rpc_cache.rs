@@ -0,0 +1,350 @@
+//! RPC response cache
+//!
+//! GUI refresh loops ask for the same chain data every few seconds. Results
+//! that can never change are cached until evicted: the chain ID, blocks by
+//! hash, receipts of finalized transactions and token metadata. Balances and
+//! gas prices change with every block, so they are only reused for a short
+//! TTL to collapse bursts of identical requests.
+//!
+//! Every entry is keyed by [`NetworkId`], so switching networks never serves
+//! another chain's data. [`NetworkManager`](crate::network::NetworkManager)
+//! consults the cache before its failover provider; see
+//! `NetworkManager::set_rpc_cache`.
+
+use alloy::primitives::{Address, B256, U256};
+use alloy::rpc::types::{Block, TransactionReceipt};
+use lru::LruCache;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::cache::CacheMetrics;
+use crate::network::NetworkId;
+use crate::tokens::TokenInfo;
+
+/// Default TTL of balance entries
+pub const DEFAULT_BALANCE_TTL: Duration = Duration::from_secs(5);
+
+/// Default TTL of gas price entries
+pub const DEFAULT_GAS_TTL: Duration = Duration::from_secs(10);
+
+/// Default entries per cached result type
+pub const DEFAULT_RPC_CACHE_CAPACITY: usize = 1024;
+
+/// Cache limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcCacheConfig {
+    /// Maximum entries per result type
+    pub capacity: usize,
+    pub balance_ttl: Duration,
+    pub gas_ttl: Duration,
+}
+
+impl Default for RpcCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_RPC_CACHE_CAPACITY,
+            balance_ttl: DEFAULT_BALANCE_TTL,
+            gas_ttl: DEFAULT_GAS_TTL,
+        }
+    }
+}
+
+/// Balance of `account` in `token`, or the native token when `None`
+type BalanceKey = (NetworkId, Address, Option<Address>);
+
+#[derive(Debug, Clone)]
+struct Expiring<T> {
+    value: T,
+    stored_at: Instant,
+}
+
+impl<T: Clone> Expiring<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            stored_at: Instant::now(),
+        }
+    }
+
+    fn fresh(&self, ttl: Duration) -> Option<T> {
+        (self.stored_at.elapsed() < ttl).then(|| self.value.clone())
+    }
+}
+
+#[derive(Debug)]
+struct Entries {
+    chain_ids: HashMap<NetworkId, u64>,
+    blocks: LruCache<(NetworkId, B256), Block>,
+    receipts: LruCache<(NetworkId, B256), TransactionReceipt>,
+    token_metadata: LruCache<(NetworkId, Address), TokenInfo>,
+    balances: LruCache<BalanceKey, Expiring<U256>>,
+    gas_prices: HashMap<NetworkId, Expiring<U256>>,
+    finalized_heads: HashMap<NetworkId, Expiring<u64>>,
+    metrics: CacheMetrics,
+}
+
+impl Entries {
+    fn new(capacity: NonZeroUsize, metrics: CacheMetrics) -> Self {
+        Self {
+            chain_ids: HashMap::new(),
+            blocks: LruCache::new(capacity),
+            receipts: LruCache::new(capacity),
+            token_metadata: LruCache::new(capacity),
+            balances: LruCache::new(capacity),
+            gas_prices: HashMap::new(),
+            finalized_heads: HashMap::new(),
+            metrics,
+        }
+    }
+}
+
+/// Per-network cache of RPC results
+#[derive(Debug)]
+pub struct RpcCache {
+    config: RpcCacheConfig,
+    entries: Mutex<Entries>,
+}
+
+impl Default for RpcCache {
+    fn default() -> Self {
+        Self::new(RpcCacheConfig::default())
+    }
+}
+
+impl RpcCache {
+    pub fn new(config: RpcCacheConfig) -> Self {
+        Self {
+            entries: Mutex::new(Entries::new(capacity(&config), CacheMetrics::default())),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> RpcCacheConfig {
+        self.config
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        self.lock().metrics
+    }
+
+    pub fn chain_id(&self, network: NetworkId) -> Option<u64> {
+        let mut entries = self.lock();
+        let value = entries.chain_ids.get(&network).copied();
+        record(&mut entries.metrics, value)
+    }
+
+    pub fn insert_chain_id(&self, network: NetworkId, chain_id: u64) {
+        self.lock().chain_ids.insert(network, chain_id);
+    }
+
+    pub fn block(&self, network: NetworkId, hash: B256) -> Option<Block> {
+        let mut entries = self.lock();
+        let value = entries.blocks.get(&(network, hash)).cloned();
+        record(&mut entries.metrics, value)
+    }
+
+    pub fn insert_block(&self, network: NetworkId, hash: B256, block: Block) {
+        let entries = &mut *self.lock();
+        put(&mut entries.blocks, &mut entries.metrics, (network, hash), block);
+    }
+
+    pub fn receipt(&self, network: NetworkId, tx_hash: B256) -> Option<TransactionReceipt> {
+        let mut entries = self.lock();
+        let value = entries.receipts.get(&(network, tx_hash)).cloned();
+        record(&mut entries.metrics, value)
+    }
+
+    /// Cache a receipt if its block is at or below the finalized head
+    ///
+    /// Receipts of unfinalized blocks can disappear in a reorg and are not
+    /// stored. Returns whether the receipt was cached.
+    pub fn insert_receipt(&self, network: NetworkId, receipt: TransactionReceipt, finalized_head: u64) -> bool {
+        match receipt.block_number {
+            Some(number) if number <= finalized_head => {
+                let entries = &mut *self.lock();
+                let key = (network, receipt.transaction_hash);
+                put(&mut entries.receipts, &mut entries.metrics, key, receipt);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Recently fetched finalized block number, reused for the gas TTL
+    pub fn finalized_head(&self, network: NetworkId) -> Option<u64> {
+        let ttl = self.config.gas_ttl;
+        self.lock().finalized_heads.get(&network).and_then(|e| e.fresh(ttl))
+    }
+
+    pub fn insert_finalized_head(&self, network: NetworkId, number: u64) {
+        self.lock().finalized_heads.insert(network, Expiring::new(number));
+    }
+
+    pub fn token_metadata(&self, network: NetworkId, token: Address) -> Option<TokenInfo> {
+        let mut entries = self.lock();
+        let value = entries.token_metadata.get(&(network, token)).cloned();
+        record(&mut entries.metrics, value)
+    }
+
+    pub fn insert_token_metadata(&self, network: NetworkId, info: TokenInfo) {
+        let entries = &mut *self.lock();
+        put(
+            &mut entries.token_metadata,
+            &mut entries.metrics,
+            (network, info.address),
+            info,
+        );
+    }
+
+    pub fn balance(&self, network: NetworkId, account: Address, token: Option<Address>) -> Option<U256> {
+        let ttl = self.config.balance_ttl;
+        let mut entries = self.lock();
+        let value = entries
+            .balances
+            .get(&(network, account, token))
+            .and_then(|e| e.fresh(ttl));
+        record(&mut entries.metrics, value)
+    }
+
+    pub fn insert_balance(&self, network: NetworkId, account: Address, token: Option<Address>, balance: U256) {
+        let entries = &mut *self.lock();
+        let key = (network, account, token);
+        put(&mut entries.balances, &mut entries.metrics, key, Expiring::new(balance));
+    }
+
+    pub fn gas_price(&self, network: NetworkId) -> Option<U256> {
+        let ttl = self.config.gas_ttl;
+        let mut entries = self.lock();
+        let value = entries.gas_prices.get(&network).and_then(|e| e.fresh(ttl));
+        record(&mut entries.metrics, value)
+    }
+
+    pub fn insert_gas_price(&self, network: NetworkId, price: U256) {
+        self.lock().gas_prices.insert(network, Expiring::new(price));
+    }
+
+    /// Drop the balances of a network, e.g. after broadcasting a transaction
+    pub fn invalidate_balances(&self, network: NetworkId) {
+        let mut entries = self.lock();
+        retain_lru(&mut entries.balances, |(id, _, _)| *id != network);
+    }
+
+    /// Drop everything cached for a network, e.g. after its RPC endpoints change
+    pub fn invalidate_network(&self, network: NetworkId) {
+        let mut entries = self.lock();
+        entries.chain_ids.remove(&network);
+        entries.gas_prices.remove(&network);
+        entries.finalized_heads.remove(&network);
+        retain_lru(&mut entries.blocks, |(id, _)| *id != network);
+        retain_lru(&mut entries.receipts, |(id, _)| *id != network);
+        retain_lru(&mut entries.token_metadata, |(id, _)| *id != network);
+        retain_lru(&mut entries.balances, |(id, _, _)| *id != network);
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        let metrics = entries.metrics;
+        *entries = Entries::new(capacity(&self.config), metrics);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn capacity(config: &RpcCacheConfig) -> NonZeroUsize {
+    NonZeroUsize::new(config.capacity).unwrap_or(NonZeroUsize::MIN)
+}
+
+fn record<T>(metrics: &mut CacheMetrics, value: Option<T>) -> Option<T> {
+    if value.is_some() {
+        metrics.hits += 1;
+    } else {
+        metrics.misses += 1;
+    }
+    value
+}
+
+/// Insert into an LRU, counting the entry it displaces when full
+fn put<K: Hash + Eq, V>(cache: &mut LruCache<K, V>, metrics: &mut CacheMetrics, key: K, value: V) {
+    if cache.len() == cache.cap().get() && !cache.contains(&key) {
+        metrics.evictions += 1;
+    }
+    cache.put(key, value);
+}
+
+fn retain_lru<K: Hash + Eq + Clone, V>(cache: &mut LruCache<K, V>, keep: impl Fn(&K) -> bool) {
+    let stale: Vec<K> = cache.iter().map(|(k, _)| k).filter(|k| !keep(k)).cloned().collect();
+    for key in stale {
+        cache.pop(&key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PULSE: NetworkId = NetworkId(369);
+    const ETH: NetworkId = NetworkId(1);
+
+    #[test]
+    fn test_entries_are_keyed_by_network_and_expire() {
+        let cache = RpcCache::new(RpcCacheConfig {
+            balance_ttl: Duration::ZERO,
+            ..RpcCacheConfig::default()
+        });
+        let account = Address::repeat_byte(0x11);
+
+        cache.insert_chain_id(PULSE, 369);
+        cache.insert_gas_price(PULSE, U256::from(7u64));
+        assert_eq!(cache.chain_id(PULSE), Some(369));
+        assert_eq!(cache.chain_id(ETH), None);
+        assert_eq!(cache.gas_price(PULSE), Some(U256::from(7u64)));
+        assert_eq!(cache.gas_price(ETH), None);
+
+        // Zero TTL: balances are never reused
+        cache.insert_balance(PULSE, account, None, U256::from(1u64));
+        assert_eq!(cache.balance(PULSE, account, None), None);
+
+        let metrics = cache.metrics();
+        assert_eq!((metrics.hits, metrics.misses), (2, 3));
+
+        cache.invalidate_network(PULSE);
+        assert_eq!(cache.chain_id(PULSE), None);
+    }
+
+    #[test]
+    fn test_only_finalized_receipts_are_cached() {
+        let cache = RpcCache::default();
+        let mut receipt: TransactionReceipt = serde_json::from_value(serde_json::json!({
+            "transactionHash": B256::repeat_byte(0xaa),
+            "transactionIndex": "0x0",
+            "blockHash": B256::repeat_byte(0xbb),
+            "blockNumber": "0x64",
+            "from": Address::repeat_byte(0x01),
+            "to": Address::repeat_byte(0x02),
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "cumulativeGasUsed": "0x5208",
+            "contractAddress": null,
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "type": "0x2",
+            "status": "0x1"
+        }))
+        .unwrap();
+
+        assert!(!cache.insert_receipt(PULSE, receipt.clone(), 99));
+        assert!(cache.receipt(PULSE, receipt.transaction_hash).is_none());
+
+        assert!(cache.insert_receipt(PULSE, receipt.clone(), 100));
+        assert!(cache.receipt(PULSE, receipt.transaction_hash).is_some());
+        assert!(cache.receipt(ETH, receipt.transaction_hash).is_none());
+
+        receipt.block_number = None;
+        assert!(!cache.insert_receipt(ETH, receipt, u64::MAX));
+    }
+}
@@ -0,0 +1,135 @@
+//! Build provenance
+//!
+//! Metadata embedded at compile time by `build.rs` so users can verify exactly
+//! which build they are running: git commit, build timestamp, enabled cargo
+//! features, target triple and compiler. Shown on the about screen and printed
+//! by `vaughan --version`.
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// Metadata describing the running binary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// Crate version (`CARGO_PKG_VERSION`)
+    pub version: &'static str,
+    /// Full git commit hash, or `"unknown"` when built outside a checkout
+    pub git_commit: &'static str,
+    /// Whether the working tree had uncommitted changes
+    pub git_dirty: bool,
+    /// Build time in seconds since the Unix epoch (`SOURCE_DATE_EPOCH` if set)
+    pub build_timestamp: i64,
+    /// Enabled cargo features, sorted
+    pub features: Vec<&'static str>,
+    /// Target triple, e.g. `x86_64-unknown-linux-gnu`
+    pub target: &'static str,
+    /// Cargo profile (`debug` or `release`)
+    pub profile: &'static str,
+    /// `rustc --version` of the compiler used
+    pub rustc: &'static str,
+}
+
+impl BuildInfo {
+    /// Abbreviated commit hash for display
+    pub fn short_commit(&self) -> &'static str {
+        self.git_commit.get(..10).unwrap_or(self.git_commit)
+    }
+
+    /// Build time as a UTC timestamp
+    pub fn build_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.build_timestamp, 0)
+    }
+
+    /// Whether a cargo feature was enabled at build time
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// One-line version string, e.g. `0.1.0 (a1b2c3d4e5 2026-01-01, release)`
+    pub fn version_line(&self) -> String {
+        let date = self
+            .build_time()
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "unknown date".to_string());
+        let dirty = if self.git_dirty { "-dirty" } else { "" };
+        format!(
+            "{} ({}{} {}, {})",
+            self.version,
+            self.short_commit(),
+            dirty,
+            date,
+            self.profile
+        )
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let build_time = self
+            .build_time()
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        writeln!(f, "{} {}", crate::APP_NAME, self.version)?;
+        writeln!(
+            f,
+            "commit:   {}{}",
+            self.git_commit,
+            if self.git_dirty { " (dirty)" } else { "" }
+        )?;
+        writeln!(f, "built:    {build_time}")?;
+        writeln!(f, "target:   {} ({})", self.target, self.profile)?;
+        writeln!(f, "features: {}", self.features.join(", "))?;
+        write!(f, "rustc:    {}", self.rustc)
+    }
+}
+
+/// Build metadata of the running binary
+pub fn build_info() -> BuildInfo {
+    let features = env!("VAUGHAN_BUILD_FEATURES");
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        git_commit: env!("VAUGHAN_BUILD_GIT_COMMIT"),
+        git_dirty: env!("VAUGHAN_BUILD_GIT_DIRTY") == "true",
+        build_timestamp: env!("VAUGHAN_BUILD_TIMESTAMP").parse().unwrap_or(0),
+        features: features.split(',').filter(|f| !f.is_empty()).collect(),
+        target: env!("VAUGHAN_BUILD_TARGET"),
+        profile: env!("VAUGHAN_BUILD_PROFILE"),
+        rustc: env!("VAUGHAN_BUILD_RUSTC"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_is_embedded() {
+        let info = build_info();
+
+        assert_eq!(info.version, crate::VERSION);
+        assert!(!info.git_commit.is_empty());
+        assert!(!info.target.is_empty());
+        assert!(info.build_time().is_some());
+        assert!(info.features.windows(2).all(|w| w[0] <= w[1]));
+        assert!(info.to_string().contains(info.git_commit));
+    }
+
+    #[test]
+    fn test_version_line() {
+        let info = BuildInfo {
+            version: "1.2.3",
+            git_commit: "0123456789abcdef",
+            git_dirty: true,
+            build_timestamp: 0,
+            features: vec!["qr"],
+            target: "x86_64-unknown-linux-gnu",
+            profile: "release",
+            rustc: "rustc 1.80.0",
+        };
+
+        assert_eq!(info.version_line(), "1.2.3 (0123456789-dirty 1970-01-01, release)");
+        assert!(info.has_feature("qr"));
+        assert!(!info.has_feature("audio"));
+    }
+}
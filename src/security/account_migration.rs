@@ -7,6 +7,7 @@ use crate::error::Result;
 use crate::security::keychain::OSKeychain;
 use crate::security::keystore::SecureKeystoreImpl;
 use crate::security::seed::SeedManager;
+use crate::wallet::backup::{DestructiveOperation, RecoveryRegistry};
 use secrecy::SecretString;
 
 /// Migrate an old account to use password encryption
@@ -72,6 +73,17 @@ pub async fn migrate_account_to_password_protected(
         ));
     }
 
+    // Recovery point in case the account is lost between removal and re-import
+    RecoveryRegistry::open_default()
+        .snapshot(
+            &keystore,
+            &secure_password,
+            DestructiveOperation::MigrateStorage {
+                description: format!("password-protect account {account_address}"),
+            },
+        )
+        .await?;
+
     // Remove old account
     tracing::info!("Removing old account...");
     keystore.remove_account(account_address).await?;
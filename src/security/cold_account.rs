@@ -0,0 +1,321 @@
+//! Cold accounts
+//!
+//! An account tagged [`COLD_TAG`] only holds funds: the wallet refuses to
+//! sign any transaction, message or typed data with it until the user
+//! completes an unlock ritual, and each unlock covers a single signature,
+//! since a signed permit moves funds as well as a transfer. The ritual is three
+//! deliberate steps spread over time:
+//!
+//! 1. request the unlock, which returns a confirmation phrase naming the account
+//! 2. type the phrase back, which starts the waiting period
+//! 3. confirm again once [`ColdAccountPolicy::unlock_delay`] has passed and
+//!    before [`ColdAccountPolicy::unlock_window`] closes
+//!
+//! A wrong phrase or a missed window starts over. Exporting an account's key
+//! adds [`KEY_EXPORTED_TAG`], and cold accounts carrying it are reported as
+//! possibly compromised.
+
+use crate::error::{Result, SecurityError, VaughanError};
+use crate::security::{SecureAccount, SecureKeystore};
+use alloy::primitives::Address;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Account tag marking a cold account
+pub const COLD_TAG: &str = "cold";
+
+/// Account tag recording that the private key or seed has left the wallet
+pub const KEY_EXPORTED_TAG: &str = "key-exported";
+
+pub fn is_cold(account: &SecureAccount) -> bool {
+    account.tags.iter().any(|t| t == COLD_TAG)
+}
+
+pub fn key_exported(account: &SecureAccount) -> bool {
+    account.tags.iter().any(|t| t == KEY_EXPORTED_TAG)
+}
+
+/// Tag the account at `address` after its key has left the wallet
+///
+/// Every export path calls this, full backups included. Failures are only logged, since the key is
+/// already out.
+pub async fn mark_key_exported(keystore: &mut SecureKeystore, address: Address) {
+    let Ok(account) = keystore.get_account(address).await else {
+        return;
+    };
+    if key_exported(&account) {
+        return;
+    }
+    let mut tags = account.tags;
+    tags.push(KEY_EXPORTED_TAG.to_string());
+    if let Err(e) = keystore.update_account_tags(address, tags).await {
+        tracing::warn!("Failed to record key export for {}: {}", address, e);
+    }
+}
+
+/// Warnings to show alongside a cold account
+pub fn cold_warnings(account: &SecureAccount) -> Vec<String> {
+    if is_cold(account) && key_exported(account) {
+        vec![format!(
+            "The key of cold account {} has been exported; it may exist outside this wallet",
+            account.name
+        )]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Timing of the unlock ritual
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColdAccountPolicy {
+    /// Wait between typing the phrase and the final confirmation
+    pub unlock_delay: Duration,
+    /// Time after the delay in which the final confirmation is accepted
+    pub unlock_window: Duration,
+}
+
+impl Default for ColdAccountPolicy {
+    fn default() -> Self {
+        Self {
+            unlock_delay: Duration::minutes(5),
+            unlock_window: Duration::minutes(10),
+        }
+    }
+}
+
+/// Progress of one account's unlock ritual
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColdUnlockStage {
+    Requested {
+        phrase: String,
+    },
+    PhraseConfirmed {
+        at: DateTime<Utc>,
+    },
+    /// One outgoing transaction may be signed until `until`
+    Unlocked {
+        until: DateTime<Utc>,
+    },
+}
+
+fn refused(reason: String) -> VaughanError {
+    VaughanError::Security(SecurityError::PolicyViolation { reasons: vec![reason] })
+}
+
+/// Enforces the cold account policy before signing
+#[derive(Debug, Default)]
+pub struct ColdAccountGuard {
+    policy: ColdAccountPolicy,
+    stages: Mutex<HashMap<Address, ColdUnlockStage>>,
+}
+
+impl ColdAccountGuard {
+    pub fn new(policy: ColdAccountPolicy) -> Self {
+        Self {
+            policy,
+            stages: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn policy(&self) -> &ColdAccountPolicy {
+        &self.policy
+    }
+
+    fn stages(&self) -> std::sync::MutexGuard<'_, HashMap<Address, ColdUnlockStage>> {
+        self.stages.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn stage(&self, address: Address) -> Option<ColdUnlockStage> {
+        self.stages().get(&address).cloned()
+    }
+
+    /// Step 1: start the ritual, returning the phrase the user must type
+    pub fn request_unlock(&self, account: &SecureAccount) -> Result<String> {
+        if !is_cold(account) {
+            return Err(refused(format!("{} is not a cold account", account.name)));
+        }
+        let address = account.address.to_string();
+        let phrase = format!("unlock cold account {}", &address[address.len() - 6..]);
+        tracing::warn!("🧊 Unlock requested for cold account {}", account.address);
+        self.stages()
+            .insert(account.address, ColdUnlockStage::Requested { phrase: phrase.clone() });
+        Ok(phrase)
+    }
+
+    /// Step 2: check the typed phrase, returning when the final confirmation opens
+    pub fn confirm_phrase(&self, address: Address, typed: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+        let mut stages = self.stages();
+        match stages.get(&address) {
+            Some(ColdUnlockStage::Requested { phrase }) if typed.trim().eq_ignore_ascii_case(phrase) => {
+                stages.insert(address, ColdUnlockStage::PhraseConfirmed { at: now });
+                Ok(now + self.policy.unlock_delay)
+            }
+            Some(ColdUnlockStage::Requested { .. }) => {
+                stages.remove(&address);
+                Err(refused(
+                    "Confirmation phrase does not match; request the unlock again".to_string(),
+                ))
+            }
+            _ => Err(refused("Request the cold account unlock first".to_string())),
+        }
+    }
+
+    /// Step 3: finish the ritual once the waiting period has passed
+    pub fn complete_unlock(&self, address: Address, now: DateTime<Utc>) -> Result<()> {
+        let mut stages = self.stages();
+        let Some(ColdUnlockStage::PhraseConfirmed { at }) = stages.get(&address).cloned() else {
+            return Err(refused("Confirm the phrase before completing the unlock".to_string()));
+        };
+        let opens = at + self.policy.unlock_delay;
+        if now < opens {
+            return Err(refused(format!(
+                "Cold account unlock can be completed in {} seconds",
+                (opens - now).num_seconds()
+            )));
+        }
+        let closes = opens + self.policy.unlock_window;
+        if now > closes {
+            stages.remove(&address);
+            return Err(refused("Cold account unlock window expired; start again".to_string()));
+        }
+        tracing::warn!("🧊 Cold account {} unlocked for one transaction", address);
+        stages.insert(address, ColdUnlockStage::Unlocked { until: closes });
+        Ok(())
+    }
+
+    /// Abandon an unlock in progress or unused
+    pub fn cancel(&self, address: Address) {
+        self.stages().remove(&address);
+    }
+
+    /// Fail unless `account` may sign at `now`
+    ///
+    /// Consumes a completed unlock, so the next signature needs a new ritual.
+    pub fn authorize(&self, account: &SecureAccount, now: DateTime<Utc>) -> Result<()> {
+        if !is_cold(account) {
+            return Ok(());
+        }
+        for warning in cold_warnings(account) {
+            tracing::warn!("🧊 {}", warning);
+        }
+        let mut stages = self.stages();
+        match stages.get(&account.address) {
+            Some(ColdUnlockStage::Unlocked { until }) if now <= *until => {
+                stages.remove(&account.address);
+                Ok(())
+            }
+            _ => Err(refused(format!(
+                "{} is a cold account; complete the unlock ritual to sign from it",
+                account.name
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{KeyReference, SecureAccount};
+
+    fn account(tags: &[&str]) -> SecureAccount {
+        SecureAccount {
+            id: "cold".to_string(),
+            name: "Vault".to_string(),
+            address: Address::repeat_byte(0xc0),
+            key_reference: KeyReference {
+                id: "cold".to_string(),
+                service: "vaughan-wallet".to_string(),
+                account: "cold".to_string(),
+            },
+            created_at: Utc::now(),
+            is_hardware: false,
+            derivation_path: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            last_used: None,
+            transaction_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_unlock_ritual_covers_one_transaction() {
+        let guard = ColdAccountGuard::default();
+        let cold = account(&[COLD_TAG]);
+        let now = Utc::now();
+        assert!(guard.authorize(&account(&[]), now).is_ok());
+        assert!(guard.authorize(&cold, now).is_err());
+
+        // A wrong phrase starts over
+        guard.request_unlock(&cold).unwrap();
+        assert!(guard.confirm_phrase(cold.address, "unlock", now).is_err());
+        assert!(guard.stage(cold.address).is_none());
+
+        let phrase = guard.request_unlock(&cold).unwrap();
+        let opens = guard.confirm_phrase(cold.address, &phrase.to_uppercase(), now).unwrap();
+        assert!(guard.complete_unlock(cold.address, now).is_err());
+        assert!(guard.authorize(&cold, now).is_err());
+
+        let phrase = guard.request_unlock(&cold).unwrap();
+        guard.confirm_phrase(cold.address, &phrase, now).unwrap();
+        guard.complete_unlock(cold.address, opens).unwrap();
+        assert!(guard.authorize(&cold, opens).is_ok());
+        assert!(guard.authorize(&cold, opens).is_err());
+    }
+
+    #[test]
+    fn test_expired_window_and_export_warning() {
+        let guard = ColdAccountGuard::default();
+        let cold = account(&[COLD_TAG, KEY_EXPORTED_TAG]);
+        let now = Utc::now();
+        let phrase = guard.request_unlock(&cold).unwrap();
+        guard.confirm_phrase(cold.address, &phrase, now).unwrap();
+        assert!(guard.complete_unlock(cold.address, now + Duration::hours(1)).is_err());
+        assert!(guard.stage(cold.address).is_none());
+
+        assert_eq!(cold_warnings(&cold).len(), 1);
+        assert!(cold_warnings(&account(&[KEY_EXPORTED_TAG])).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wallet_refuses_off_chain_signatures_from_cold_accounts() {
+        use crate::wallet::{Vaughan, WalletConfig};
+        use alloy::signers::local::PrivateKeySigner;
+        use secrecy::SecretString;
+
+        let config = WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: false,
+            ..Default::default()
+        };
+        let wallet = Vaughan::with_keychain(config, Box::new(crate::security::TestKeychain::new()))
+            .await
+            .unwrap();
+        wallet.approvals().delegate("test host");
+        let signer = PrivateKeySigner::random();
+        let key = SecretString::new(alloy::hex::encode(signer.to_bytes()));
+        wallet
+            .keystore()
+            .write()
+            .await
+            .import_account(key, "Vault".to_string())
+            .await
+            .unwrap();
+        let typed_data: alloy::dyn_abi::TypedData = serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [{ "name": "name", "type": "string" }],
+                "Mail": [{ "name": "contents", "type": "string" }]
+            },
+            "primaryType": "Mail",
+            "domain": { "name": "Vaughan" },
+            "message": { "contents": "gm" }
+        }))
+        .unwrap();
+        wallet.sign_typed_data(signer.address(), &typed_data).await.unwrap();
+
+        wallet.set_cold_account(signer.address(), true).await.unwrap();
+        let refused = wallet.sign_typed_data(signer.address(), &typed_data).await.unwrap_err();
+        assert!(refused.to_string().contains("cold account"));
+        assert!(wallet.sign_message(signer.address(), b"gm").await.is_err());
+    }
+}
@@ -0,0 +1,595 @@
+//! Headless signing confirmation
+//!
+//! Signing requests that arrive without the GUI (the localhost JSON-RPC
+//! server, the stdio wallet API, scripts) are confirmed through a pluggable
+//! [`ConfirmationChannel`]:
+//!
+//! - [`TerminalConfirmation`] - a `y/N` prompt on the controlling terminal
+//! - [`WebhookConfirmation`] - an HTTP endpoint, e.g. a chat bot or approval
+//!   service, answers `{"approved": bool}`
+//! - [`PolicyConfirmation`] - approves only what an [`AutoApprovePolicy`]
+//!   explicitly allows and hands everything else to another channel
+//!
+//! Channels are wrapped in a [`ConfirmationGate`], which applies a timeout
+//! (no answer is a rejection) and records every decision in an audit log so
+//! consent given outside the GUI can be reviewed later.
+
+use crate::error::{ConfigurationError, NetworkError, Result};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient};
+use crate::wallet::provider::history::message_preview;
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::TransactionRequest;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// How long a channel has to answer
+pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+/// Number of decisions kept in the audit log
+const MAX_AUDIT_RECORDS: usize = 256;
+
+/// Something the user is asked to allow
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmationRequest {
+    Connect {
+        origin: String,
+    },
+    SendTransaction {
+        origin: String,
+        tx: TransactionRequest,
+    },
+    /// Sign without broadcasting; the caller submits the transaction
+    SignTransaction {
+        origin: String,
+        tx: TransactionRequest,
+    },
+    PersonalSign {
+        origin: String,
+        account: Address,
+        message: Vec<u8>,
+    },
+}
+
+impl ConfirmationRequest {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Connect { .. } => "connect",
+            Self::SendTransaction { .. } => "send_transaction",
+            Self::SignTransaction { .. } => "sign_transaction",
+            Self::PersonalSign { .. } => "personal_sign",
+        }
+    }
+
+    pub fn origin(&self) -> &str {
+        match self {
+            Self::Connect { origin }
+            | Self::SendTransaction { origin, .. }
+            | Self::SignTransaction { origin, .. }
+            | Self::PersonalSign { origin, .. } => origin,
+        }
+    }
+}
+
+impl fmt::Display for ConfirmationRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect { origin } => write!(f, "{origin} wants to see your accounts"),
+            Self::SendTransaction { origin, tx } | Self::SignTransaction { origin, tx } => {
+                let to = tx.to.as_ref().and_then(|kind| kind.to().copied());
+                let action = match self {
+                    Self::SendTransaction { .. } => "send",
+                    _ => "sign",
+                };
+                write!(f, "{origin} wants to {action} a transaction")?;
+                if let Some(from) = tx.from {
+                    write!(f, " from {from}")?;
+                }
+                write!(
+                    f,
+                    " to {} with value {} wei",
+                    to.map_or_else(|| "a new contract".to_string(), |to| to.to_string()),
+                    tx.value.unwrap_or_default()
+                )?;
+                if let Some(call) = crate::abi::describe_transaction(tx) {
+                    write!(f, ", calling {call}")?;
+                }
+                if let Some(nonce) = tx.nonce {
+                    write!(f, ", nonce {nonce}")?;
+                }
+                if let Some(gas) = tx.gas {
+                    write!(f, ", gas limit {gas}")?;
+                }
+                match (tx.max_fee_per_gas, tx.gas_price) {
+                    (Some(max_fee), _) => write!(f, ", max fee {max_fee} wei/gas"),
+                    (None, Some(gas_price)) => write!(f, ", gas price {gas_price} wei"),
+                    (None, None) => Ok(()),
+                }
+            }
+            Self::PersonalSign {
+                origin,
+                account,
+                message,
+            } => write!(f, "{origin} wants {account} to sign: {}", message_preview(message)),
+        }
+    }
+}
+
+/// Asks the user whether to allow a request
+#[async_trait::async_trait]
+pub trait ConfirmationChannel: Send + Sync {
+    /// Name recorded in the audit log
+    fn name(&self) -> &'static str;
+
+    async fn confirm(&self, request: &ConfirmationRequest) -> bool;
+}
+
+/// Lines typed on an input, read by one long-lived thread
+type InputLines = tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<String>>;
+
+fn read_lines(reader: impl BufRead + Send + 'static) -> InputLines {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    let spawned = std::thread::Builder::new()
+        .name("confirmation-input".to_string())
+        .spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::error!("Failed to start the confirmation input reader: {}", e);
+    }
+    tokio::sync::Mutex::new(receiver)
+}
+
+/// The wallet's stdin, read from the first prompt on
+fn stdin_lines() -> &'static InputLines {
+    static STDIN_LINES: OnceLock<InputLines> = OnceLock::new();
+    STDIN_LINES.get_or_init(|| read_lines(std::io::BufReader::new(std::io::stdin())))
+}
+
+/// Prompts on the terminal the wallet was started from
+///
+/// Prompts are shown one at a time; requests arriving meanwhile wait. A
+/// prompt that is cancelled, e.g. by the gate's timeout, leaves no read
+/// behind: lines typed while no prompt is shown are dropped before the
+/// next one, so a late answer never answers a different request.
+#[derive(Debug, Default)]
+pub struct TerminalConfirmation {
+    /// Answers come from stdin when unset
+    input: Option<InputLines>,
+}
+
+impl TerminalConfirmation {
+    /// Read answers from `reader` instead of stdin
+    pub fn from_reader(reader: impl BufRead + Send + 'static) -> Self {
+        Self {
+            input: Some(read_lines(reader)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ConfirmationChannel for TerminalConfirmation {
+    fn name(&self) -> &'static str {
+        "terminal"
+    }
+
+    async fn confirm(&self, request: &ConfirmationRequest) -> bool {
+        let mut lines = self.input.as_ref().unwrap_or_else(|| stdin_lines()).lock().await;
+        while lines.try_recv().is_ok() {}
+        {
+            use std::io::Write;
+            eprint!("{request}. Allow? [y/N] ");
+            let _ = std::io::stderr().flush();
+        }
+        lines
+            .recv()
+            .await
+            .is_some_and(|answer| answer.trim().eq_ignore_ascii_case("y"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WebhookAnswer {
+    #[serde(default)]
+    approved: bool,
+}
+
+/// Posts each request to an approval webhook and waits for its answer
+///
+/// The body carries the request kind, origin, a readable summary and the
+/// raw request; the endpoint answers `{"approved": true}` to allow it. Any
+/// other answer, an error status or a timeout rejects.
+#[derive(Debug, Clone)]
+pub struct WebhookConfirmation {
+    url: String,
+    bearer_token: Option<String>,
+    timeout: Duration,
+    client: HttpClient,
+}
+
+impl WebhookConfirmation {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            bearer_token: None,
+            timeout: DEFAULT_CONFIRMATION_TIMEOUT,
+            client: shared_client(),
+        }
+    }
+
+    /// Send `Authorization: Bearer <token>` so the endpoint can trust the caller
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// How long the endpoint may take to answer
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn body(request: &ConfirmationRequest) -> serde_json::Value {
+        let details = match request {
+            ConfirmationRequest::Connect { .. } => json!({}),
+            ConfirmationRequest::SendTransaction { tx, .. } | ConfirmationRequest::SignTransaction { tx, .. } => {
+                json!({ "transaction": tx })
+            }
+            ConfirmationRequest::PersonalSign { account, message, .. } => json!({
+                "account": account,
+                "message": alloy::primitives::Bytes::from(message.clone()),
+            }),
+        };
+        json!({
+            "kind": request.kind(),
+            "origin": request.origin(),
+            "summary": request.to_string(),
+            "details": details,
+        })
+    }
+
+    async fn ask(&self, request: &ConfirmationRequest) -> Result<bool> {
+        egress().check(EgressCategory::Approval, &self.url, "confirm headless signing")?;
+        let mut http = self
+            .client
+            .post(&self.url)
+            .json(&Self::body(request))
+            .timeout(self.timeout);
+        if let Some(token) = &self.bearer_token {
+            http = http.header("Authorization", &format!("Bearer {token}"));
+        }
+        let response = http
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Approval webhook failed: {e}"),
+            })?;
+        let answer: WebhookAnswer = response.json().await.map_err(|e| NetworkError::RpcError {
+            message: format!("Failed to parse approval webhook answer: {e}"),
+        })?;
+        Ok(answer.approved)
+    }
+}
+
+#[async_trait::async_trait]
+impl ConfirmationChannel for WebhookConfirmation {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn confirm(&self, request: &ConfirmationRequest) -> bool {
+        match self.ask(request).await {
+            Ok(approved) => approved,
+            Err(e) => {
+                tracing::warn!("Approval webhook failed, rejecting: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// What may be approved without asking
+///
+/// Deliberately narrow: only listed origins, and only plain value transfers
+/// to listed recipients up to `max_value`. Message signing is never
+/// approved automatically.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoApprovePolicy {
+    /// Callers whose requests may be approved
+    #[serde(default)]
+    pub origins: Vec<String>,
+    /// Recipients transactions may be sent to
+    #[serde(default)]
+    pub recipients: Vec<Address>,
+    /// Highest value per transaction, in wei
+    #[serde(default)]
+    pub max_value: U256,
+    /// Whether contract calls to listed recipients qualify
+    #[serde(default)]
+    pub allow_calldata: bool,
+}
+
+impl AutoApprovePolicy {
+    /// Load a policy from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| {
+            ConfigurationError::ParseError {
+                message: format!("Invalid auto-approve policy {}: {e}", path.display()),
+            }
+            .into()
+        })
+    }
+
+    /// Whether the policy covers `request`
+    pub fn allows(&self, request: &ConfirmationRequest) -> bool {
+        if !self.origins.iter().any(|origin| origin == request.origin()) {
+            return false;
+        }
+        match request {
+            ConfirmationRequest::Connect { .. } => true,
+            ConfirmationRequest::SendTransaction { tx, .. } | ConfirmationRequest::SignTransaction { tx, .. } => {
+                let to = tx.to.as_ref().and_then(|kind| kind.to());
+                let has_calldata = tx.input.input().is_some_and(|data| !data.is_empty());
+                to.is_some_and(|to| self.recipients.contains(to))
+                    && tx.value.unwrap_or_default() <= self.max_value
+                    && (self.allow_calldata || !has_calldata)
+            }
+            ConfirmationRequest::PersonalSign { .. } => false,
+        }
+    }
+}
+
+/// Approves what the policy allows and asks `fallback`, if any, about the rest
+pub struct PolicyConfirmation {
+    policy: AutoApprovePolicy,
+    fallback: Option<Arc<dyn ConfirmationChannel>>,
+}
+
+impl PolicyConfirmation {
+    /// Reject everything the policy does not cover
+    pub fn new(policy: AutoApprovePolicy) -> Self {
+        Self { policy, fallback: None }
+    }
+
+    pub fn with_fallback(mut self, fallback: Arc<dyn ConfirmationChannel>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl ConfirmationChannel for PolicyConfirmation {
+    fn name(&self) -> &'static str {
+        "policy"
+    }
+
+    async fn confirm(&self, request: &ConfirmationRequest) -> bool {
+        if self.policy.allows(request) {
+            return true;
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.confirm(request).await,
+            None => false,
+        }
+    }
+}
+
+/// One decision in the confirmation audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfirmationRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Channel that decided
+    pub channel: String,
+    pub kind: String,
+    pub origin: String,
+    pub summary: String,
+    pub approved: bool,
+    /// Whether the channel failed to answer in time
+    pub timed_out: bool,
+}
+
+/// Confirms requests through a channel, with a timeout and an audit log
+#[derive(Clone)]
+pub struct ConfirmationGate {
+    channel: Arc<dyn ConfirmationChannel>,
+    timeout: Duration,
+    log: Arc<Mutex<VecDeque<ConfirmationRecord>>>,
+}
+
+impl ConfirmationGate {
+    pub fn new(channel: Arc<dyn ConfirmationChannel>) -> Self {
+        Self {
+            channel,
+            timeout: DEFAULT_CONFIRMATION_TIMEOUT,
+            log: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Ask the channel; no answer within the timeout is a rejection
+    pub async fn confirm(&self, request: &ConfirmationRequest) -> bool {
+        tracing::info!("🙋 Confirmation requested via {}: {}", self.channel.name(), request);
+        let answer = tokio::time::timeout(self.timeout, self.channel.confirm(request)).await;
+        let timed_out = answer.is_err();
+        let approved = answer.unwrap_or(false);
+        self.record(request, approved, timed_out);
+        approved
+    }
+
+    /// Decisions made so far, oldest first
+    pub fn audit_log(&self) -> Vec<ConfirmationRecord> {
+        self.log
+            .lock()
+            .map(|log| log.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn record(&self, request: &ConfirmationRequest, approved: bool, timed_out: bool) {
+        tracing::warn!(
+            target: "vaughan::audit",
+            channel = self.channel.name(),
+            kind = request.kind(),
+            origin = request.origin(),
+            approved,
+            timed_out,
+            "Signing confirmation: {}",
+            request
+        );
+
+        if let Ok(mut log) = self.log.lock() {
+            if log.len() >= MAX_AUDIT_RECORDS {
+                log.pop_front();
+            }
+            log.push_back(ConfirmationRecord {
+                timestamp: Utc::now(),
+                channel: self.channel.name().to_string(),
+                kind: request.kind().to_string(),
+                origin: request.origin().to_string(),
+                summary: request.to_string(),
+                approved,
+                timed_out,
+            });
+        }
+    }
+}
+
+impl fmt::Debug for ConfirmationGate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfirmationGate")
+            .field("channel", &self.channel.name())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Bytes;
+
+    const SHOP: Address = Address::repeat_byte(0x5a);
+
+    /// Never answers
+    struct Silent;
+
+    #[async_trait::async_trait]
+    impl ConfirmationChannel for Silent {
+        fn name(&self) -> &'static str {
+            "silent"
+        }
+
+        async fn confirm(&self, _request: &ConfirmationRequest) -> bool {
+            std::future::pending().await
+        }
+    }
+
+    fn send(to: Address, value: u64, data: &[u8]) -> ConfirmationRequest {
+        ConfirmationRequest::SendTransaction {
+            origin: "payroll-script".to_string(),
+            tx: TransactionRequest::default()
+                .to(to)
+                .value(U256::from(value))
+                .input(Bytes::copy_from_slice(data).into()),
+        }
+    }
+
+    #[test]
+    fn test_auto_approve_policy_is_strict() {
+        let policy: AutoApprovePolicy = serde_json::from_value(json!({
+            "origins": ["payroll-script"],
+            "recipients": [SHOP],
+            "max_value": "0x3e8",
+        }))
+        .unwrap();
+
+        assert!(policy.allows(&send(SHOP, 1_000, &[])));
+        assert!(!policy.allows(&send(SHOP, 1_001, &[])));
+        assert!(!policy.allows(&send(Address::repeat_byte(1), 1, &[])));
+        assert!(!policy.allows(&send(SHOP, 1, &[0xa9, 0x05, 0x9c, 0xbb])));
+        assert!(!policy.allows(&ConfirmationRequest::PersonalSign {
+            origin: "payroll-script".to_string(),
+            account: SHOP,
+            message: b"hi".to_vec(),
+        }));
+        assert!(!policy.allows(&ConfirmationRequest::Connect {
+            origin: "https://dapp.example".to_string()
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_gate_times_out_and_audits() {
+        let policy = AutoApprovePolicy {
+            origins: vec!["payroll-script".to_string()],
+            recipients: vec![SHOP],
+            max_value: U256::from(1_000),
+            allow_calldata: false,
+        };
+        let channel = PolicyConfirmation::new(policy).with_fallback(Arc::new(Silent));
+        let gate = ConfirmationGate::new(Arc::new(channel)).with_timeout(Duration::from_millis(20));
+
+        assert!(gate.confirm(&send(SHOP, 10, &[])).await);
+        assert!(!gate.confirm(&send(SHOP, 10_000, &[])).await);
+
+        let log = gate.audit_log();
+        assert_eq!(log.len(), 2);
+        assert!(log[0].approved && !log[0].timed_out);
+        assert!(!log[1].approved && log[1].timed_out);
+        assert_eq!(
+            (log[1].channel.as_str(), log[1].kind.as_str()),
+            ("policy", "send_transaction")
+        );
+
+        let body = WebhookConfirmation::body(&send(SHOP, 10, &[]));
+        assert_eq!(body["kind"], "send_transaction");
+        assert_eq!(body["origin"], "payroll-script");
+    }
+
+    #[tokio::test]
+    async fn test_late_terminal_answer_does_not_answer_the_next_prompt() {
+        use std::io::Write;
+        let (reader, mut writer) = std::io::pipe().unwrap();
+        let gate = ConfirmationGate::new(Arc::new(TerminalConfirmation::from_reader(std::io::BufReader::new(
+            reader,
+        ))))
+        .with_timeout(Duration::from_millis(20));
+
+        assert!(!gate.confirm(&send(SHOP, 10, &[])).await);
+        // Typed after the first prompt gave up
+        writeln!(writer, "y").unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let second = tokio::spawn({
+            let gate = gate.clone().with_timeout(Duration::from_secs(5));
+            async move { gate.confirm(&send(SHOP, 20, &[])).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        writeln!(writer, "n").unwrap();
+        assert!(!second.await.unwrap());
+
+        let third = tokio::spawn({
+            let gate = gate.clone().with_timeout(Duration::from_secs(5));
+            async move { gate.confirm(&send(SHOP, 30, &[])).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        writeln!(writer, "y").unwrap();
+        assert!(third.await.unwrap());
+    }
+}
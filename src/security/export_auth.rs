@@ -3,23 +3,32 @@
 //! This module provides secure authentication for sensitive export operations.
 //! It implements:
 //! - Time-limited authentication tokens (2 minutes)
-//! - Rate limiting (5 attempts per minute)
+//! - Rate limiting (5 attempts per minute, 3 exports per hour by default)
+//! - Mandatory cooldown after failed password attempts, doubling with each
+//!   consecutive failure and persisted so a restart doesn't clear it
+//! - Export attempts recorded in the persistent audit log
 //! - Secure password verification hooks
 //!
 //! # Requirements
 //! - **Requirement 2.2**: Secure authentication for critical operations
 
+use alloy::primitives::Address;
 use chrono::{DateTime, Duration, Utc};
 // secrecy imports removed as unused
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
 use uuid::Uuid;
 
 use crate::error::{Result, SecurityError, VaughanError};
+use crate::telemetry::audit::{self, AuditEvent, AuditLog};
 
 /// Time-to-live for an authentication token
 const TOKEN_TTL_SECONDS: i64 = 120; // 2 minutes
 
+/// Failed export password state, next to the persisted rate limits
+const EXPORT_FAILURES_FILE: &str = "export_failures.json";
+
 /// A time-limited authentication token
 ///
 /// This token serves as proof of recent authentication efficiently,
@@ -54,45 +63,173 @@ impl AuthToken {
 use crate::security::{RateLimiter, RateLimitConfig};
 use std::collections::HashMap;
 
+/// Rate limiting policy for export operations
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExportRateLimitPolicy {
+    /// Maximum successful export operations per hour
+    pub max_exports_per_hour: u32,
+    /// Maximum authentication attempts per minute (successful or not)
+    pub max_auth_attempts_per_minute: u32,
+    /// Cooldown after the first failed password attempt
+    pub failure_cooldown_seconds: u64,
+    /// Upper bound for the escalating cooldown
+    pub max_cooldown_seconds: u64,
+}
+
+impl Default for ExportRateLimitPolicy {
+    fn default() -> Self {
+        Self {
+            max_exports_per_hour: 3,
+            max_auth_attempts_per_minute: 5,
+            failure_cooldown_seconds: 30,
+            max_cooldown_seconds: 3600,
+        }
+    }
+}
+
+impl ExportRateLimitPolicy {
+    /// Cooldown enforced after `consecutive_failures` failed attempts
+    ///
+    /// The delay doubles with each consecutive failure, capped at
+    /// `max_cooldown_seconds`.
+    pub fn cooldown_for(&self, consecutive_failures: u32) -> u64 {
+        if consecutive_failures == 0 {
+            return 0;
+        }
+        let multiplier = 1u64.checked_shl(consecutive_failures - 1).unwrap_or(u64::MAX);
+        self.failure_cooldown_seconds
+            .saturating_mul(multiplier)
+            .min(self.max_cooldown_seconds)
+    }
+}
+
+/// Kind of export audit event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportAuditKind {
+    AuthSucceeded,
+    AuthFailed,
+    CooldownEnforced,
+    RateLimited,
+    ExportSucceeded,
+    ExportFailed,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct FailureState {
+    consecutive_failures: u32,
+    locked_until: Option<DateTime<Utc>>,
+}
+
 /// Authenticator for sensitive operations
 #[derive(Debug, Clone)]
 pub struct ExportAuthenticator {
     rate_limiter: Arc<RateLimiter>,
+    policy: ExportRateLimitPolicy,
+    failures: Arc<Mutex<FailureState>>,
+    /// Where `failures` is persisted, `None` to keep it in memory
+    failures_path: Option<PathBuf>,
+    /// Log to record to instead of the global one
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 impl ExportAuthenticator {
     /// Create a new authenticator with standard limits
     pub fn new() -> Self {
+        Self::with_policy(ExportRateLimitPolicy::default())
+    }
+
+    /// Create an authenticator with a custom rate limiting policy
+    pub fn with_policy(policy: ExportRateLimitPolicy) -> Self {
+        let mut authenticator = Self::from_limiter(RateLimiter::new(Self::limit_configs(&policy)), policy);
+        authenticator.failures_path = RateLimiter::state_path(EXPORT_FAILURES_FILE);
+        authenticator.reload_failures();
+        authenticator
+    }
+
+    /// Create an authenticator that keeps its allowance and cooldown in memory
+    ///
+    /// Nothing is shared with other processes or persisted, so tests can
+    /// export freely without touching the user's limits.
+    pub fn in_memory(policy: ExportRateLimitPolicy) -> Self {
+        Self::from_limiter(RateLimiter::in_memory(Self::limit_configs(&policy)), policy)
+    }
+
+    fn from_limiter(rate_limiter: RateLimiter, policy: ExportRateLimitPolicy) -> Self {
+        Self {
+            rate_limiter: Arc::new(rate_limiter),
+            policy,
+            failures: Arc::new(Mutex::new(FailureState::default())),
+            failures_path: None,
+            audit_log: None,
+        }
+    }
+
+    fn limit_configs(policy: &ExportRateLimitPolicy) -> HashMap<String, RateLimitConfig> {
         let mut configs = HashMap::new();
-        
-        // Task 10.3: Authentication limit (e.g., 5 attempts per minute)
-        // Capacity 5, refill 5 per 60s (~0.083/s)
+
+        // Task 10.3: Authentication limit (default 5 attempts per minute)
         configs.insert("auth_attempt".to_string(), RateLimitConfig {
-            capacity: 5,
-            refill_rate_per_second: 5.0 / 60.0,
+            capacity: policy.max_auth_attempts_per_minute,
+            refill_rate_per_second: policy.max_auth_attempts_per_minute as f64 / 60.0,
         });
 
-        // Task 10.2: Export limit (e.g., 3 exports per hour)
-        // Capacity 3, refill 3 per 3600s (~0.00083/s)
+        // Task 10.2: Export limit (default 3 exports per hour)
         configs.insert("export_op".to_string(), RateLimitConfig {
-            capacity: 3,
-            refill_rate_per_second: 3.0 / 3600.0,
+            capacity: policy.max_exports_per_hour,
+            refill_rate_per_second: policy.max_exports_per_hour as f64 / 3600.0,
         });
 
-        Self {
-            rate_limiter: Arc::new(RateLimiter::new(configs)),
-        }
+        configs
+    }
+
+    /// Authenticator shared by every export path of the process
+    ///
+    /// The GUI and the account manager both draw on its export allowance,
+    /// which is persisted and reloaded before each export like the cooldown
+    /// after failed passwords, so neither another entry point nor a restart
+    /// refills or clears them.
+    pub fn shared() -> &'static ExportAuthenticator {
+        static SHARED: OnceLock<ExportAuthenticator> = OnceLock::new();
+        SHARED.get_or_init(Self::new)
+    }
+
+    /// Record export attempts to `log` instead of the global audit log
+    pub fn with_audit_log(mut self, log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(log);
+        self
+    }
+
+    /// Persist the failure state at `path` instead of the default location
+    #[cfg(test)]
+    fn with_failures_path(mut self, path: PathBuf) -> Self {
+        self.failures_path = Some(path);
+        self.reload_failures();
+        self
+    }
+
+    /// The active rate limiting policy
+    pub fn policy(&self) -> &ExportRateLimitPolicy {
+        &self.policy
     }
 
     /// Authenticate the user and issue a token
     pub async fn authenticate(&self, password_valid: bool) -> Result<AuthToken> {
+        self.check_cooldown("authenticate", None)?;
+
         // Check rate limit for authentication attempts
-        self.rate_limiter.check("auth_attempt")?;
+        if let Err(e) = self.rate_limiter.check("auth_attempt") {
+            self.record_audit(ExportAuditKind::RateLimited, "authenticate", None, e.to_string());
+            return Err(e);
+        }
 
         if !password_valid {
+            self.register_failed_attempt("authenticate", None);
             return Err(VaughanError::Security(SecurityError::InvalidPassword));
         }
 
+        self.reset_failures();
+        self.record_audit(ExportAuditKind::AuthSucceeded, "authenticate", None, String::new());
         Ok(AuthToken::new())
     }
 
@@ -108,6 +245,163 @@ impl ExportAuthenticator {
     pub fn check_export_limit(&self) -> Result<()> {
         self.rate_limiter.check("export_op")
     }
+
+    /// Authorize an export: validates the token, enforces any active cooldown
+    /// and consumes one export from the hourly allowance
+    pub fn authorize_export(&self, operation: &str, address: Address, token: &AuthToken) -> Result<()> {
+        self.validate_token(token)?;
+        self.authorize_export_attempt(operation, address)
+    }
+
+    /// Enforce any active cooldown and consume one export from the hourly allowance
+    ///
+    /// For callers that checked the password and operation themselves.
+    pub fn authorize_export_attempt(&self, operation: &str, address: Address) -> Result<()> {
+        self.check_cooldown(operation, Some(address))?;
+        self.take_export_allowance(operation, Some(address))
+    }
+
+    /// Consume one export from the hourly allowance, recording a refusal
+    pub fn take_export_allowance(&self, operation: &str, address: Option<Address>) -> Result<()> {
+        if let Err(e) = self.check_export_limit() {
+            self.record_audit(ExportAuditKind::RateLimited, operation, address, e.to_string());
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Remaining cooldown after failed password attempts, if any
+    pub fn cooldown_remaining(&self) -> Option<std::time::Duration> {
+        self.reload_failures();
+        let failures = self.failures.lock().ok()?;
+        let locked_until = failures.locked_until?;
+        (locked_until - Utc::now()).to_std().ok().filter(|d| !d.is_zero())
+    }
+
+    /// Record a failed password attempt and start the escalating cooldown
+    pub fn register_failed_attempt(&self, operation: &str, address: Option<Address>) {
+        self.reload_failures();
+        let cooldown = match self.failures.lock() {
+            Ok(mut failures) => {
+                failures.consecutive_failures = failures.consecutive_failures.saturating_add(1);
+                let cooldown = self.policy.cooldown_for(failures.consecutive_failures);
+                failures.locked_until = Some(Utc::now() + Duration::seconds(cooldown as i64));
+                cooldown
+            }
+            Err(_) => self.policy.max_cooldown_seconds,
+        };
+        self.save_failures();
+
+        self.record_audit(
+            ExportAuditKind::AuthFailed,
+            operation,
+            address,
+            format!("cooldown {}s", cooldown),
+        );
+    }
+
+    /// Record the outcome of an export in the audit log
+    pub fn record_export_result(&self, operation: &str, address: Address, result: std::result::Result<(), &VaughanError>) {
+        match result {
+            Ok(()) => {
+                self.reset_failures();
+                self.record_audit(ExportAuditKind::ExportSucceeded, operation, Some(address), String::new());
+            }
+            // A wrong password also shows as the secret failing to decrypt
+            Err(VaughanError::Security(SecurityError::InvalidPassword | SecurityError::DecryptionError { .. })) => {
+                self.register_failed_attempt(operation, Some(address));
+            }
+            Err(e) => {
+                self.record_audit(ExportAuditKind::ExportFailed, operation, Some(address), e.to_string());
+            }
+        }
+    }
+
+    fn check_cooldown(&self, operation: &str, address: Option<Address>) -> Result<()> {
+        if let Some(remaining) = self.cooldown_remaining() {
+            let wait_time_seconds = remaining.as_secs().max(1);
+            self.record_audit(
+                ExportAuditKind::CooldownEnforced,
+                operation,
+                address,
+                format!("{}s remaining", wait_time_seconds),
+            );
+            return Err(VaughanError::Security(SecurityError::RateLimitExceeded {
+                operation: operation.to_string(),
+                wait_time_seconds,
+            }));
+        }
+        Ok(())
+    }
+
+    fn reset_failures(&self) {
+        if let Ok(mut failures) = self.failures.lock() {
+            *failures = FailureState::default();
+        }
+        self.save_failures();
+    }
+
+    /// Pick up failures another process or an earlier run persisted
+    ///
+    /// An unreadable file leaves the in-memory state as it is.
+    fn reload_failures(&self) {
+        let Some(path) = &self.failures_path else {
+            return;
+        };
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+            Err(e) => {
+                tracing::warn!("Failed to read export failure state: {}", e);
+                return;
+            }
+        };
+        match serde_json::from_str::<FailureState>(&json) {
+            Ok(loaded) => {
+                if let Ok(mut failures) = self.failures.lock() {
+                    *failures = loaded;
+                }
+            }
+            Err(e) => tracing::warn!("Failed to parse export failure state: {}", e),
+        }
+    }
+
+    fn save_failures(&self) {
+        let Some(path) = &self.failures_path else {
+            return;
+        };
+        let Ok(failures) = self.failures.lock().map(|failures| *failures) else {
+            return;
+        };
+        let result = serde_json::to_string(&failures)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                std::fs::write(path, json).map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            tracing::warn!("Failed to persist export failure state: {}", e);
+        }
+    }
+
+    fn record_audit(&self, kind: ExportAuditKind, operation: &str, address: Option<Address>, detail: String) {
+        let event = AuditEvent::ExportAttempt {
+            outcome: kind,
+            operation: operation.to_string(),
+            account: address,
+            detail,
+        };
+        match &self.audit_log {
+            Some(log) => {
+                if let Err(e) = log.append(event, Uuid::new_v4()) {
+                    tracing::error!("Failed to write audit log entry: {}", e);
+                }
+            }
+            None => audit::record(event, Uuid::new_v4()),
+        }
+    }
 }
 
 impl Default for ExportAuthenticator {
@@ -120,6 +414,21 @@ impl Default for ExportAuthenticator {
 mod tests {
     use super::*;
 
+    fn temp_audit_log(dir: &tempfile::TempDir) -> Arc<AuditLog> {
+        Arc::new(AuditLog::open(dir.path().join("audit.log"), &[7u8; 32]).unwrap())
+    }
+
+    fn recorded_outcomes(log: &AuditLog) -> Vec<ExportAuditKind> {
+        log.entries()
+            .unwrap()
+            .into_iter()
+            .filter_map(|entry| match entry.event {
+                AuditEvent::ExportAttempt { outcome, .. } => Some(outcome),
+                _ => None,
+            })
+            .collect()
+    }
+
     #[tokio::test]
     async fn test_auth_success() {
         let auth = ExportAuthenticator::new();
@@ -152,6 +461,76 @@ mod tests {
         assert!(matches!(result, Err(VaughanError::Security(SecurityError::RateLimitExceeded { .. }))));
     }
 
+    #[tokio::test]
+    async fn test_failed_attempt_enforces_cooldown() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = temp_audit_log(&dir);
+        let auth = ExportAuthenticator::new().with_audit_log(log.clone());
+
+        let _ = auth.authenticate(false).await;
+        assert!(auth.cooldown_remaining().is_some());
+
+        // Even a correct password is rejected until the cooldown expires
+        let result = auth.authenticate(true).await;
+        assert!(matches!(
+            result,
+            Err(VaughanError::Security(SecurityError::RateLimitExceeded { wait_time_seconds, .. })) if wait_time_seconds <= 30
+        ));
+
+        assert_eq!(
+            recorded_outcomes(&log),
+            vec![ExportAuditKind::AuthFailed, ExportAuditKind::CooldownEnforced]
+        );
+        assert!(log.verify().unwrap().is_intact());
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(EXPORT_FAILURES_FILE);
+        let log = temp_audit_log(&dir);
+        let auth = ExportAuthenticator::new()
+            .with_audit_log(log.clone())
+            .with_failures_path(path.clone());
+        let _ = auth.authenticate(false).await;
+
+        let restarted = ExportAuthenticator::new().with_audit_log(log).with_failures_path(path);
+        assert!(restarted.cooldown_remaining().is_some());
+        assert!(matches!(
+            restarted.authenticate(true).await,
+            Err(VaughanError::Security(SecurityError::RateLimitExceeded { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_cooldown_escalates() {
+        let policy = ExportRateLimitPolicy::default();
+        assert_eq!(policy.cooldown_for(0), 0);
+        assert_eq!(policy.cooldown_for(1), 30);
+        assert_eq!(policy.cooldown_for(3), 120);
+        assert_eq!(policy.cooldown_for(20), policy.max_cooldown_seconds);
+        assert_eq!(policy.cooldown_for(200), policy.max_cooldown_seconds);
+    }
+
+    #[tokio::test]
+    async fn test_export_limit_per_hour() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = temp_audit_log(&dir);
+        let auth = ExportAuthenticator::with_policy(ExportRateLimitPolicy {
+            max_exports_per_hour: 1,
+            ..Default::default()
+        })
+        .with_audit_log(log.clone());
+        let token = auth.authenticate(true).await.unwrap();
+
+        assert!(auth.authorize_export("export_seed", Address::ZERO, &token).is_ok());
+        assert!(matches!(
+            auth.authorize_export("export_seed", Address::ZERO, &token),
+            Err(VaughanError::Security(SecurityError::RateLimitExceeded { .. }))
+        ));
+        assert_eq!(recorded_outcomes(&log).last(), Some(&ExportAuditKind::RateLimited));
+    }
+
     #[tokio::test]
     async fn test_token_expiration() {
         // Create a token that expires very soon (manually for test, normally constant)
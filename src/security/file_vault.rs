@@ -0,0 +1,347 @@
+//! Encrypted file keychain
+//!
+//! Keychain backend for Linux systems without a Secret Service and for
+//! portable installs. Every secret lives in one file,
+//! `~/.vaughan/keychain.vault` by default, encrypted with AES-256-GCM under a
+//! key derived from the vault password with Argon2id.
+//!
+//! The key is derived once when the vault is opened and kept, zeroized on
+//! drop, for the life of the process. The Argon2 parameters are stored in the
+//! vault file, so a vault always reopens with the cost it was created with.
+//! Each write re-encrypts the whole vault with a fresh nonce and replaces the
+//! file atomically.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use zeroize::Zeroizing;
+
+use super::seed::encryption::{derive_key_argon2id, generate_nonce, generate_salt};
+use super::{Argon2Params, KeyReference, KeychainInterface};
+use crate::error::{Result, SecurityError};
+
+/// Vault file name inside the `.vaughan` directory
+pub const VAULT_FILE: &str = "keychain.vault";
+/// Environment variable holding the vault password for headless use
+pub const VAULT_PASSWORD_ENV: &str = "VAUGHAN_VAULT_PASSWORD";
+
+const VAULT_VERSION: u32 = 1;
+
+/// Largest Argon2 memory cost accepted from a vault file, in KiB (4 GiB)
+const MAX_ARGON2_MEMORY: u32 = 1 << 22;
+/// Largest Argon2 iteration count and parallelism accepted from a vault file
+const MAX_ARGON2_TIME: u32 = 64;
+const MAX_ARGON2_PARALLELISM: u32 = 64;
+
+/// Secrets by `service/key id`, each wiped on drop
+type Entries = BTreeMap<String, Zeroizing<String>>;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    argon2: Argon2Params,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn vault_error(message: impl Into<String>) -> crate::error::VaughanError {
+    SecurityError::KeystoreError {
+        message: message.into(),
+    }
+    .into()
+}
+
+/// Decrypted-key handle on a vault file, shared by every service using it
+struct Vault {
+    path: PathBuf,
+    argon2: Argon2Params,
+    salt: [u8; 32],
+    cipher: Aes256Gcm,
+    /// Serialises read-modify-write cycles
+    write_lock: Mutex<()>,
+}
+
+impl Vault {
+    fn read_file(path: &Path) -> Result<Option<VaultFile>> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| vault_error(format!("Corrupt keychain vault: {e}"))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(vault_error(format!("Failed to read keychain vault: {e}"))),
+        }
+    }
+
+    /// `argon2` only applies to a new vault; an existing one keeps its own
+    fn open(path: PathBuf, password: &SecretString, argon2: Argon2Params) -> Result<Self> {
+        let existing = Self::read_file(&path)?;
+        let argon2 = existing.as_ref().map_or(argon2, |file| file.argon2.clone());
+        // The file is not authenticated until decrypted, so bound the work it can ask for
+        if argon2.memory_cost > MAX_ARGON2_MEMORY
+            || argon2.time_cost > MAX_ARGON2_TIME
+            || argon2.parallelism > MAX_ARGON2_PARALLELISM
+        {
+            return Err(vault_error(format!(
+                "Keychain vault Argon2 parameters m={} t={} p={} are out of range",
+                argon2.memory_cost, argon2.time_cost, argon2.parallelism
+            )));
+        }
+        let salt = match &existing {
+            Some(file) => {
+                if file.version != VAULT_VERSION {
+                    return Err(vault_error(format!(
+                        "Unsupported keychain vault version {}",
+                        file.version
+                    )));
+                }
+                hex::decode(&file.salt)
+                    .ok()
+                    .and_then(|salt| <[u8; 32]>::try_from(salt).ok())
+                    .ok_or_else(|| vault_error("Corrupt keychain vault salt"))?
+            }
+            None => generate_salt()?,
+        };
+
+        let key = Zeroizing::new(derive_key_argon2id(
+            password,
+            &salt,
+            argon2.memory_cost,
+            argon2.time_cost,
+            argon2.parallelism,
+        )?);
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref()).map_err(|_| vault_error("Invalid vault key length"))?;
+        let vault = Self {
+            path,
+            argon2,
+            salt,
+            cipher,
+            write_lock: Mutex::new(()),
+        };
+
+        match existing {
+            // Decrypting proves the password before anything is stored
+            Some(file) => drop(vault.decrypt(&file)?),
+            None => vault.write(&Entries::new())?,
+        }
+        Ok(vault)
+    }
+
+    fn decrypt(&self, file: &VaultFile) -> Result<Entries> {
+        let nonce: [u8; 12] = hex::decode(&file.nonce)
+            .ok()
+            .and_then(|nonce| nonce.try_into().ok())
+            .ok_or_else(|| vault_error("Corrupt keychain vault nonce"))?;
+        let ciphertext = hex::decode(&file.ciphertext).map_err(|_| vault_error("Corrupt keychain vault"))?;
+        let plaintext = Zeroizing::new(
+            self.cipher
+                .decrypt(&Nonce::from(nonce), ciphertext.as_ref())
+                .map_err(|_| vault_error("Wrong keychain vault password or tampered vault"))?,
+        );
+        serde_json::from_slice(&plaintext).map_err(|e| vault_error(format!("Corrupt keychain vault contents: {e}")))
+    }
+
+    fn read(&self) -> Result<Entries> {
+        match Self::read_file(&self.path)? {
+            Some(file) => self.decrypt(&file),
+            None => Ok(Entries::new()),
+        }
+    }
+
+    fn write(&self, entries: &Entries) -> Result<()> {
+        let plaintext = Zeroizing::new(serde_json::to_vec(entries).map_err(|e| vault_error(e.to_string()))?);
+        let nonce = generate_nonce()?;
+        let ciphertext = self
+            .cipher
+            .encrypt(&Nonce::from(nonce), plaintext.as_ref())
+            .map_err(|e| vault_error(format!("Failed to encrypt keychain vault: {e}")))?;
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            argon2: self.argon2.clone(),
+            salt: hex::encode(self.salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        };
+        let json = serde_json::to_string(&file).map_err(|e| vault_error(e.to_string()))?;
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| vault_error(format!("Failed to create vault directory: {e}")))?;
+        }
+        let tmp = self.path.with_extension("vault.tmp");
+        let tmp_str = tmp
+            .to_str()
+            .ok_or_else(|| vault_error("Vault path is not valid UTF-8"))?;
+        super::keystore::storage::write_secure_file(tmp_str, &json)?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| vault_error(format!("Failed to replace keychain vault: {e}")))
+    }
+
+    fn update<R>(&self, f: impl FnOnce(&mut Entries) -> R) -> Result<R> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries = self.read()?;
+        let result = f(&mut entries);
+        self.write(&entries)?;
+        Ok(result)
+    }
+}
+
+/// Keychain storing one service's secrets in an encrypted vault file
+#[derive(Clone)]
+pub struct FileVaultKeychain {
+    vault: Arc<Vault>,
+    service: String,
+}
+
+impl fmt::Debug for FileVaultKeychain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileVaultKeychain")
+            .field("path", &self.vault.path)
+            .field("service", &self.service)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileVaultKeychain {
+    /// Open the vault at `path`, creating it if missing
+    ///
+    /// Fails when the password does not decrypt an existing vault.
+    pub fn open(path: impl Into<PathBuf>, service: &str, password: &SecretString) -> Result<Self> {
+        Self::open_with_argon2_params(path, service, password, Argon2Params::default())
+    }
+
+    /// Like [`Self::open`], creating a missing vault with the given key derivation cost
+    pub fn open_with_argon2_params(
+        path: impl Into<PathBuf>,
+        service: &str,
+        password: &SecretString,
+        argon2: Argon2Params,
+    ) -> Result<Self> {
+        Ok(Self {
+            vault: Arc::new(Vault::open(path.into(), password, argon2)?),
+            service: service.to_string(),
+        })
+    }
+
+    /// The same vault seen by another keychain service
+    pub fn for_service(&self, service: &str) -> Self {
+        Self {
+            vault: Arc::clone(&self.vault),
+            service: service.to_string(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.vault.path
+    }
+
+    fn entry(&self, key_ref: &KeyReference) -> String {
+        format!("{}/{}", self.service, key_ref.id)
+    }
+}
+
+impl KeychainInterface for FileVaultKeychain {
+    fn store(&self, key_ref: &KeyReference, key: SecretString) -> Result<()> {
+        let entry = self.entry(key_ref);
+        self.vault.update(|entries| {
+            entries.insert(entry, Zeroizing::new(key.expose_secret().to_string()));
+        })
+    }
+
+    fn retrieve(&self, key_ref: &KeyReference) -> Result<SecretString> {
+        self.vault
+            .read()?
+            .get(&self.entry(key_ref))
+            .map(|secret| SecretString::new(secret.to_string()))
+            .ok_or_else(|| vault_error(format!("Key {} not found in keychain vault", key_ref.id)))
+    }
+
+    fn delete(&self, key_ref: &KeyReference) -> Result<()> {
+        let entry = self.entry(key_ref);
+        self.vault.update(|entries| {
+            entries.remove(&entry);
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn KeychainInterface> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap key derivation so the tests don't spend seconds in Argon2
+    fn open(path: &Path, password: &str) -> Result<FileVaultKeychain> {
+        let argon2 = Argon2Params {
+            memory_cost: 8,
+            time_cost: 1,
+            parallelism: 1,
+            output_length: 32,
+        };
+        FileVaultKeychain::open_with_argon2_params(path, "keys", &SecretString::new(password.to_string()), argon2)
+    }
+
+    fn key_ref(id: &str) -> KeyReference {
+        KeyReference {
+            id: id.to_string(),
+            service: "vaughan-wallet".to_string(),
+            account: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_vault_round_trip_and_services() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(VAULT_FILE);
+        let keys = open(&path, "correct horse").unwrap();
+        let seeds = keys.for_service("seeds");
+        keys.store(&key_ref("a"), SecretString::new("0xkey".to_string()))
+            .unwrap();
+        seeds
+            .store(&key_ref("a"), SecretString::new("seed words".to_string()))
+            .unwrap();
+
+        // Reopening with the default parameters uses the ones in the file;
+        // services do not see each other's keys
+        let reopened = FileVaultKeychain::open(&path, "keys", &SecretString::new("correct horse".to_string())).unwrap();
+        assert_eq!(reopened.retrieve(&key_ref("a")).unwrap().expose_secret(), "0xkey");
+        assert_eq!(seeds.retrieve(&key_ref("a")).unwrap().expose_secret(), "seed words");
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("0xkey"));
+
+        reopened.delete(&key_ref("a")).unwrap();
+        assert!(keys.retrieve(&key_ref("a")).is_err());
+        assert!(seeds.retrieve(&key_ref("a")).is_ok());
+    }
+
+    #[test]
+    fn test_wrong_password_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(VAULT_FILE);
+        open(&path, "right").unwrap();
+
+        let err = open(&path, "wrong").unwrap_err();
+        assert!(err.to_string().contains("Wrong keychain vault password"));
+    }
+
+    #[test]
+    fn test_rejects_hostile_parameters() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(VAULT_FILE);
+        open(&path, "right").unwrap();
+
+        let mut file: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        file["argon2"]["memory_cost"] = u32::MAX.into();
+        std::fs::write(&path, file.to_string()).unwrap();
+        let err = open(&path, "right").unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+
+        file["argon2"].as_object_mut().unwrap().remove("memory_cost");
+        std::fs::write(&path, file.to_string()).unwrap();
+        assert!(open(&path, "right").is_err());
+    }
+}
@@ -418,6 +418,24 @@ impl LedgerWallet {
         Ok(())
     }
 
+    /// Derive the address at an arbitrary BIP-32 path
+    ///
+    /// Unlike [`HardwareWalletTrait::get_addresses`], which only walks Ledger
+    /// Live paths, this accepts any Ethereum path (BIP-44, legacy MEW, custom).
+    pub async fn get_address_at(&self, derivation_path: &str) -> Result<Address> {
+        self.check_connection().await?;
+        self.validate_derivation_path(derivation_path)?;
+
+        let signer = self.signer.as_ref().ok_or(HardwareWalletError::DeviceNotFound)?;
+        signer
+            .get_address_with_path(&LedgerHDPath::Other(derivation_path.to_string()))
+            .await
+            .map_err(|e| {
+                tracing::error!("❌ Failed to derive address for {}: {}", derivation_path, e);
+                HardwareWalletError::CommunicationError.into()
+            })
+    }
+
     /// Check if device is still responsive
     async fn check_connection(&self) -> Result<()> {
         if !self.connected {
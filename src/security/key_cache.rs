@@ -1,10 +1,12 @@
 //! Key Cache Module
 //!
 //! Provides secure caching of derived private keys in memory with automatic zeroization.
-//! Keys are stored in SecureMemory which uses memory locking when available.
+//! Keys are stored in a [`SecretBuffer`], locked in RAM when the OS allows it,
+//! and handed out only as further locked copies.
+//! A key is evicted when its TTL runs out or, if a use limit is set, after it
+//! has been handed out that many times.
 
-use crate::error::Result;
-use crate::security::memory::SecureMemory;
+use crate::security::memory::SecretBuffer;
 use alloy::primitives::Address;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
@@ -18,6 +20,9 @@ pub struct KeyCache {
     /// Cache timeout duration
     cache_timeout: Duration,
 
+    /// How many times a key may be read before it is evicted (None = unlimited)
+    max_uses: Option<u32>,
+
     /// Whether memory locking is available
     memory_lock_available: bool,
 }
@@ -25,14 +30,17 @@ pub struct KeyCache {
 /// A cached key with metadata
 #[derive(Debug)]
 struct CachedKey {
-    /// The actual key data in secure memory
-    key: SecureMemory,
+    /// The actual key data in locked memory
+    key: SecretBuffer,
 
     /// When this key was cached
     cached_at: Instant,
 
     /// Last time this key was accessed
     last_access: Instant,
+
+    /// Number of times this key was read
+    uses: u32,
 }
 
 impl KeyCache {
@@ -45,11 +53,11 @@ impl KeyCache {
             tracing::warn!("⚠️ Memory locking unavailable - using shorter cache timeout for security");
         }
 
-        // Use shorter timeout (5 min) if memory locking fails
+        // Cap the timeout at 5 min if memory locking fails
         let actual_timeout = if memory_lock_available {
             timeout
         } else {
-            Duration::from_secs(5 * 60) // 5 minutes
+            timeout.min(Duration::from_secs(5 * 60))
         };
 
         tracing::info!(
@@ -61,15 +69,31 @@ impl KeyCache {
         Self {
             cached_keys: HashMap::new(),
             cache_timeout: actual_timeout,
+            max_uses: None,
             memory_lock_available,
         }
     }
 
+    /// Evict each key after it has been read `max_uses` times
+    pub fn with_max_uses(mut self, max_uses: u32) -> Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Change the cache timeout, keeping the cap when memory locking is unavailable
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.cache_timeout = if self.memory_lock_available {
+            timeout
+        } else {
+            timeout.min(Duration::from_secs(5 * 60))
+        };
+    }
+
     /// Test if memory locking is available
     fn test_memory_locking() -> bool {
-        // Try to create a small SecureMemory to test if mlock works
-        match SecureMemory::new(32) {
-            Ok(_) => true,
+        // Try to lock a small buffer to test if mlock works
+        match SecretBuffer::from_slice(&[0; 32]) {
+            Ok(buffer) => buffer.is_locked(),
             Err(e) => {
                 tracing::debug!("Memory locking test failed: {}", e);
                 false
@@ -78,15 +102,12 @@ impl KeyCache {
     }
 
     /// Insert a key into the cache
-    pub fn insert(&mut self, address: Address, key_bytes: Vec<u8>) -> Result<()> {
-        // Create secure memory for the key
-        let mut secure_key = SecureMemory::new(key_bytes.len())?;
-        secure_key.as_mut_slice().copy_from_slice(&key_bytes);
-
+    pub fn insert(&mut self, address: Address, key: SecretBuffer) {
         let cached_key = CachedKey {
-            key: secure_key,
+            key,
             cached_at: Instant::now(),
             last_access: Instant::now(),
+            uses: 0,
         };
 
         // Remove old key if it exists (will be zeroized on drop)
@@ -97,12 +118,12 @@ impl KeyCache {
         self.cached_keys.insert(address, cached_key);
 
         tracing::debug!("🔑 Cached key for address: {}", address);
-
-        Ok(())
     }
 
-    /// Get a key from the cache (returns a copy for safety)
-    pub fn get(&mut self, address: &Address) -> Option<Vec<u8>> {
+    /// Get a key from the cache (returns a locked copy for safety)
+    ///
+    /// Counts as a use even if the copy cannot be allocated.
+    pub fn get(&mut self, address: &Address) -> Option<SecretBuffer> {
         if let Some(cached_key) = self.cached_keys.get_mut(address) {
             // Check if key has expired
             if cached_key.cached_at.elapsed() >= self.cache_timeout {
@@ -113,11 +134,17 @@ impl KeyCache {
 
             // Update last access time
             cached_key.last_access = Instant::now();
+            cached_key.uses += 1;
 
             tracing::debug!("🔑 Retrieved cached key for address: {}", address);
 
             // Return a copy of the key bytes
-            Some(cached_key.key.as_mut_slice().to_vec())
+            let key = cached_key.key.try_clone();
+            if self.max_uses.is_some_and(|max| cached_key.uses >= max) {
+                tracing::debug!("🔑 Key use limit reached for address: {}", address);
+                self.cached_keys.remove(address); // Will be zeroized on drop
+            }
+            key.map_err(|e| tracing::warn!("Failed to copy cached key: {}", e)).ok()
         } else {
             None
         }
@@ -181,6 +208,11 @@ impl KeyCache {
     pub fn timeout(&self) -> Duration {
         self.cache_timeout
     }
+
+    /// Get the per-key use limit
+    pub fn max_uses(&self) -> Option<u32> {
+        self.max_uses
+    }
 }
 
 impl Drop for KeyCache {
@@ -200,15 +232,15 @@ mod tests {
         let mut cache = KeyCache::new(Duration::from_secs(60));
 
         let address = Address::from_slice(&[0u8; 20]);
-        let key_bytes = vec![1, 2, 3, 4, 5];
+        let key_bytes = [1, 2, 3, 4, 5];
 
         // Insert key
-        cache.insert(address, key_bytes.clone()).unwrap();
+        cache.insert(address, SecretBuffer::from_slice(&key_bytes).unwrap());
         assert_eq!(cache.len(), 1);
 
         // Retrieve key
         let retrieved = cache.get(&address).unwrap();
-        assert_eq!(retrieved, &key_bytes[..]);
+        assert_eq!(retrieved.expose_secret(), &key_bytes[..]);
 
         // Remove key
         assert!(cache.remove(&address));
@@ -220,10 +252,10 @@ mod tests {
         let mut cache = KeyCache::new(Duration::from_millis(100));
 
         let address = Address::from_slice(&[1u8; 20]);
-        let key_bytes = vec![1, 2, 3, 4, 5];
+        let key_bytes = SecretBuffer::from_slice(&[1, 2, 3, 4, 5]).unwrap();
 
         // Insert key
-        cache.insert(address, key_bytes).unwrap();
+        cache.insert(address, key_bytes);
 
         // Key should be available immediately
         assert!(cache.get(&address).is_some());
@@ -235,6 +267,18 @@ mod tests {
         assert!(cache.get(&address).is_none());
     }
 
+    #[test]
+    fn test_key_cache_max_uses() {
+        let mut cache = KeyCache::new(Duration::from_secs(60)).with_max_uses(2);
+        let address = Address::from_slice(&[2u8; 20]);
+        cache.insert(address, SecretBuffer::from_slice(&[7; 32]).unwrap());
+
+        assert!(cache.get(&address).is_some());
+        assert!(cache.get(&address).is_some());
+        assert!(cache.get(&address).is_none());
+        assert!(cache.is_empty());
+    }
+
     #[test]
     fn test_key_cache_clear() {
         let mut cache = KeyCache::new(Duration::from_secs(60));
@@ -242,8 +286,8 @@ mod tests {
         // Insert multiple keys
         for i in 0..5 {
             let address = Address::from_slice(&[i as u8; 20]);
-            let key_bytes = vec![i; 32];
-            cache.insert(address, key_bytes).unwrap();
+            let key_bytes = SecretBuffer::from_slice(&[i; 32]).unwrap();
+            cache.insert(address, key_bytes);
         }
 
         assert_eq!(cache.len(), 5);
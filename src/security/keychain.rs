@@ -2,41 +2,37 @@
 //!
 //! This module provides platform-specific keychain integration for secure
 //! storage of private keys using the operating system's native keychain.
+//! [`create_keychain`] picks the backend configured in the settings instead,
+//! which may be the encrypted file vault or process memory.
 
 // Allow unsafe code - all unsafe blocks are documented with SAFETY comments
 // See Phase 4 Task 4.3 completion for full unsafe code audit
 #![allow(unsafe_code)]
 
 use secrecy::{ExposeSecret, SecretString};
-#[cfg(test)]
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-#[cfg(test)]
-use std::sync::LazyLock;
-#[cfg(test)]
-use std::sync::Mutex;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
 
-use super::{KeyReference, KeychainInterface};
+use super::file_vault::{FileVaultKeychain, VAULT_FILE, VAULT_PASSWORD_ENV};
+use super::{KeyReference, KeychainInterface, SERVICE_NAME_PRIVATE_KEYS};
+use crate::config::SecurityPolicySettings;
 use crate::error::{Result, SecurityError};
 
 /// Platform-specific keychain implementation
+///
+/// Under `cfg(test)` keys go to an in-memory store namespaced by this
+/// process and the service name, see [`super::test_keychain`].
 #[derive(Debug)]
 pub struct OSKeychain {
     service_name: String,
-    #[cfg(test)]
-    mock_storage: &'static Mutex<HashMap<String, String>>,
 }
 
-#[cfg(test)]
-static MOCK_STORAGE: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
-
 impl OSKeychain {
     /// Create a new OS keychain interface
     pub fn new(service_name: String) -> Result<Self> {
-        Ok(Self {
-            service_name,
-            #[cfg(test)]
-            mock_storage: &MOCK_STORAGE,
-        })
+        Ok(Self { service_name })
     }
 }
 
@@ -49,10 +45,7 @@ impl KeychainInterface for OSKeychain {
 
         #[cfg(test)]
         {
-            let mut storage = self.mock_storage.lock().map_err(|_| SecurityError::KeystoreError {
-                message: "Failed to acquire mock storage lock".to_string(),
-            })?;
-            storage.insert(key_ref.id.clone(), key.expose_secret().to_string());
+            super::test_keychain::store_key(&super::test_keychain::process_service(&self.service_name), key_ref, &key);
             Ok(())
         }
     }
@@ -65,18 +58,7 @@ impl KeychainInterface for OSKeychain {
 
         #[cfg(test)]
         {
-            let storage = self.mock_storage.lock().map_err(|_| SecurityError::KeystoreError {
-                message: "Failed to acquire mock storage lock".to_string(),
-            })?;
-            storage
-                .get(&key_ref.id)
-                .map(|s| SecretString::new(s.clone()))
-                .ok_or_else(|| {
-                    SecurityError::KeystoreError {
-                        message: "Key not found".to_string(),
-                    }
-                    .into()
-                })
+            super::test_keychain::retrieve_key(&super::test_keychain::process_service(&self.service_name), key_ref)
         }
     }
 
@@ -88,10 +70,7 @@ impl KeychainInterface for OSKeychain {
 
         #[cfg(test)]
         {
-            let mut storage = self.mock_storage.lock().map_err(|_| SecurityError::KeystoreError {
-                message: "Failed to acquire mock storage lock".to_string(),
-            })?;
-            storage.remove(&key_ref.id);
+            super::test_keychain::delete_key(&super::test_keychain::process_service(&self.service_name), key_ref);
             Ok(())
         }
     }
@@ -99,8 +78,6 @@ impl KeychainInterface for OSKeychain {
     fn clone_box(&self) -> Box<dyn KeychainInterface> {
         Box::new(OSKeychain {
             service_name: self.service_name.clone(),
-            #[cfg(test)]
-            mock_storage: self.mock_storage,
         })
     }
 }
@@ -799,11 +776,144 @@ impl KeychainInterface for MockKeychain {
     }
 }
 
+/// Where keychain services keep their secrets
+///
+/// Chosen with `security.keychain_backend` in `settings.json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeychainBackend {
+    /// The operating system keychain
+    #[default]
+    Os,
+    /// An Argon2id-protected vault file, see [`super::file_vault`]
+    EncryptedFile,
+    /// Process memory only; keys are lost on exit. Rejected in release builds
+    Memory,
+}
+
+/// Opened vault and memory keychains, shared by every service in the process
+static BACKENDS: LazyLock<Mutex<OpenBackends>> = LazyLock::new(Default::default);
+
+#[derive(Default)]
+struct OpenBackends {
+    vault: Option<FileVaultKeychain>,
+    memory: HashMap<String, MemoryKeychain>,
+}
+
+/// Keys of one service kept in process memory, shared by its clones
+#[derive(Debug, Clone, Default)]
+struct MemoryKeychain {
+    keys: Arc<Mutex<HashMap<String, SecretString>>>,
+}
+
+impl KeychainInterface for MemoryKeychain {
+    fn store(&self, key_ref: &KeyReference, key: SecretString) -> Result<()> {
+        self.keys
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key_ref.id.clone(), key);
+        Ok(())
+    }
+
+    fn retrieve(&self, key_ref: &KeyReference) -> Result<SecretString> {
+        self.keys
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key_ref.id)
+            .cloned()
+            .ok_or_else(|| {
+                SecurityError::KeystoreError {
+                    message: "Key not found".to_string(),
+                }
+                .into()
+            })
+    }
+
+    fn delete(&self, key_ref: &KeyReference) -> Result<()> {
+        self.keys
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&key_ref.id);
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn KeychainInterface> {
+        Box::new(self.clone())
+    }
+}
+
+/// Security settings, or the defaults when `settings.json` doesn't load
+///
+/// A malformed settings file must not take every keychain down with it.
+fn security_settings() -> SecurityPolicySettings {
+    match crate::config::Settings::load_default() {
+        Ok(settings) => settings.security,
+        Err(e) => {
+            tracing::warn!("⚠️ Invalid settings, using the default keychain backend: {}", e);
+            SecurityPolicySettings::default()
+        }
+    }
+}
+
+fn vault_path(security: &SecurityPolicySettings) -> PathBuf {
+    match &security.keychain_vault_path {
+        Some(path) => path.clone(),
+        None => super::keystore::storage::get_vaughan_dir().join(VAULT_FILE),
+    }
+}
+
+/// Whether the configured vault still needs a password from the user
+///
+/// False once the vault is open, when [`VAULT_PASSWORD_ENV`] is set, and for
+/// the other backends.
+pub fn keychain_vault_locked() -> bool {
+    security_settings().keychain_backend == KeychainBackend::EncryptedFile
+        && BACKENDS.lock().unwrap_or_else(PoisonError::into_inner).vault.is_none()
+        && std::env::var_os(VAULT_PASSWORD_ENV).is_none()
+}
+
+/// Open the configured vault with a password entered by the user
+///
+/// Fails, leaving the vault locked, when the password does not decrypt it.
+pub fn unlock_keychain_vault(password: &SecretString) -> Result<()> {
+    let vault = FileVaultKeychain::open(vault_path(&security_settings()), SERVICE_NAME_PRIVATE_KEYS, password)?;
+    BACKENDS.lock().unwrap_or_else(PoisonError::into_inner).vault = Some(vault);
+    Ok(())
+}
+
+/// Keychain for `service` on the backend selected in the settings
+///
+/// The encrypted file vault is unlocked on first use, either by
+/// [`unlock_keychain_vault`] or with the password from
+/// [`VAULT_PASSWORD_ENV`], and stays open for the life of the process.
+pub fn create_keychain(service: &str) -> Result<Box<dyn KeychainInterface>> {
+    let security = security_settings();
+    match security.keychain_backend {
+        KeychainBackend::Os => Ok(Box::new(OSKeychain::new(service.to_string())?)),
+        KeychainBackend::EncryptedFile => {
+            let mut backends = BACKENDS.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(vault) = &backends.vault {
+                return Ok(Box::new(vault.for_service(service)));
+            }
+            let password = std::env::var(VAULT_PASSWORD_ENV).map_err(|_| SecurityError::KeystoreError {
+                message: format!("Keychain vault is locked; enter its password or set {VAULT_PASSWORD_ENV}"),
+            })?;
+            let vault = FileVaultKeychain::open(vault_path(&security), service, &SecretString::new(password))?;
+            backends.vault = Some(vault.clone());
+            Ok(Box::new(vault))
+        }
+        KeychainBackend::Memory => {
+            let mut backends = BACKENDS.lock().unwrap_or_else(PoisonError::into_inner);
+            let keychain = backends.memory.entry(service.to_string()).or_default();
+            Ok(Box::new(keychain.clone()))
+        }
+    }
+}
+
 /// Create platform-specific keychain interface
 pub fn create_keychain_interface() -> Result<Box<dyn KeychainInterface>> {
     // Use the encrypted seeds service since that's where new accounts are created
-    let service_name = "vaughan-wallet-encrypted-seeds".to_string();
-    Ok(Box::new(OSKeychain::new(service_name)?))
+    create_keychain("vaughan-wallet-encrypted-seeds")
 }
 
 #[cfg(test)]
@@ -10,11 +10,13 @@
 pub mod encryption;
 pub mod storage;
 
-use crate::error::{Result, SecurityError};
+use crate::error::{NetworkError, Result, SecurityError};
 use crate::network::{NetworkConfig, NetworkId};
-use crate::security::{EncryptionType, KeyReference, KeychainInterface, SecureAccount, SecureExport};
+use crate::security::profile::{is_seed_service, SecurityProfile};
+use crate::security::{EncryptionType, KeyReference, KeychainInterface, SecretBuffer, SecureAccount, SecureExport};
+use crate::wallet::account_manager::progressive::{account_pages, AccountPage};
 use alloy::{
-    network::TxSigner,
+    network::TxSignerSync,
     primitives::{Address, TxKind},
     rpc::types::TransactionRequest,
     signers::local::PrivateKeySigner,
@@ -27,6 +29,20 @@ use uuid::Uuid;
 // Re-export storage types for convenience
 pub use storage::{StoredAccountMeta, StoredNetworkMeta};
 
+/// Keychain entries replaced by [`SecureKeystoreImpl::reencrypt_seeds`]
+///
+/// Holds the seeds still encrypted under the old password, for rollback.
+#[derive(Debug)]
+pub struct SeedPasswordChange {
+    previous: Vec<(KeyReference, SecretString)>,
+}
+
+impl SeedPasswordChange {
+    pub fn seed_count(&self) -> usize {
+        self.previous.len()
+    }
+}
+
 /// Secure keystore implementation
 #[derive(Debug)]
 pub struct SecureKeystoreImpl {
@@ -36,17 +52,27 @@ pub struct SecureKeystoreImpl {
     is_locked: bool,
     #[allow(dead_code)] // Stored for future keychain operations
     service_name: String,
+    /// Namespace of the accounts and keychain entries this keystore manages
+    profile: SecurityProfile,
 }
 
 impl SecureKeystoreImpl {
     /// Create a new secure keystore
     pub async fn new(keychain: Box<dyn KeychainInterface>) -> Result<Self> {
+        Self::with_profile(keychain, SecurityProfile::Primary).await
+    }
+
+    /// Create a keystore over one security profile's accounts
+    ///
+    /// Accounts and keys of other profiles are never loaded.
+    pub async fn with_profile(keychain: Box<dyn KeychainInterface>, profile: SecurityProfile) -> Result<Self> {
         let mut keystore = Self {
             accounts: HashMap::new(),
             custom_networks: HashMap::new(),
             keychain,
             is_locked: false,
-            service_name: crate::security::SERVICE_NAME_PRIVATE_KEYS.to_string(),
+            service_name: profile.private_key_service().to_string(),
+            profile,
         };
 
         // Load existing accounts and networks from persistent storage
@@ -74,7 +100,7 @@ impl SecureKeystoreImpl {
         let account_id = Uuid::new_v4().to_string();
         let key_ref = KeyReference {
             id: account_id.clone(),
-            service: "vaughan-wallet".to_string(),
+            service: self.profile.private_key_service().to_string(),
             account: format!("{address}"),
         };
 
@@ -128,6 +154,8 @@ impl SecureKeystoreImpl {
             .into());
         }
 
+        self.ensure_owned(&key_reference)?;
+
         // Create secure account using the provided key reference
         let account = SecureAccount {
             id: key_reference.id.clone(),
@@ -150,6 +178,33 @@ impl SecureKeystoreImpl {
         Ok(account)
     }
 
+    /// Re-add an account from a backup, restoring its keychain entry when one was saved
+    pub async fn restore_account(&mut self, account: SecureAccount, secret: Option<SecretString>) -> Result<()> {
+        if self.is_locked {
+            return Err(SecurityError::KeystoreError {
+                message: "Keystore is locked".to_string(),
+            }
+            .into());
+        }
+
+        if self.accounts.contains_key(&account.address) {
+            return Err(SecurityError::KeystoreError {
+                message: "Account already exists".to_string(),
+            }
+            .into());
+        }
+
+        self.ensure_owned(&account.key_reference)?;
+
+        if let Some(secret) = secret {
+            self.keychain.store(&account.key_reference, secret)?;
+        }
+        self.accounts.insert(account.address, account);
+        self.save_accounts().await?;
+
+        Ok(())
+    }
+
     /// Import an account from private key
     pub async fn import_account(&mut self, private_key: SecretString, name: String) -> Result<SecureAccount> {
         if self.is_locked {
@@ -200,7 +255,7 @@ impl SecureKeystoreImpl {
         let account_id = Uuid::new_v4().to_string();
         let key_ref = KeyReference {
             id: account_id.clone(),
-            service: "vaughan-wallet".to_string(),
+            service: self.profile.private_key_service().to_string(),
             account: format!("{address}"),
         };
 
@@ -264,15 +319,31 @@ impl SecureKeystoreImpl {
 
     /// Sign a transaction and return the signed transaction bytes
     ///
-    /// For seed-based accounts, requires a password to decrypt the seed.
-    /// The key cache can be used to avoid repeated password prompts.
+    /// For seed-based accounts, requires a password to decrypt the seed. The
+    /// wallet signs through [`crate::security::SessionManager::with_signer`],
+    /// which caches the derived key instead of asking for the password again.
     pub async fn sign_transaction(
         &self,
         tx: &TransactionRequest,
         address: &Address,
         password: Option<&SecretString>,
-        key_cache: Option<&mut crate::security::KeyCache>,
     ) -> Result<Vec<u8>> {
+        let key = self.derive_signing_key(address, password, None).await?;
+        let signer = signer_from_key(key.expose_secret(), address)?;
+        encode_signed_transaction(tx, &signer)
+    }
+
+    /// Private key bytes for `address`
+    ///
+    /// Seed-based accounts derive the key from the seed, decrypted with
+    /// `password`, and from `passphrase` when they were created with a BIP-39
+    /// passphrase; private-key accounts read it from the keychain.
+    pub async fn derive_signing_key(
+        &self,
+        address: &Address,
+        password: Option<&SecretString>,
+        passphrase: Option<&SecretString>,
+    ) -> Result<SecretBuffer> {
         if self.is_locked {
             return Err(SecurityError::KeystoreError {
                 message: "Keystore is locked".to_string(),
@@ -280,13 +351,6 @@ impl SecureKeystoreImpl {
             .into());
         }
 
-        tracing::info!("🔍 Attempting to sign transaction for address: {}", address);
-        tracing::info!(
-            "📋 Available accounts in keystore: {:?}",
-            self.accounts.keys().collect::<Vec<_>>()
-        );
-        tracing::info!("🔓 Keystore locked status: {}", self.is_locked);
-
         let account = self.accounts.get(address).ok_or_else(|| {
             let available_addresses: Vec<String> = self.accounts.keys().map(|addr| addr.to_string()).collect();
             let error_msg = if available_addresses.is_empty() {
@@ -303,71 +367,25 @@ impl SecureKeystoreImpl {
         })?;
 
         // Check if this is a seed-based account or private-key account
-        let is_seed_based = account.key_reference.service == crate::security::SERVICE_NAME_ENCRYPTED_SEEDS;
-
-        let key_bytes = if is_seed_based {
-            tracing::info!("🌱 Seed-based account detected");
-
-            // Check key cache first
-            if let Some(cache) = key_cache {
-                if let Some(cached_key) = cache.get(address) {
-                    tracing::info!("🔑 Using cached key for address: {}", address);
-                    cached_key
-                } else {
-                    // Not in cache - need to derive from seed
-                    tracing::info!("🔓 Key not in cache, deriving from seed");
-
-                    // Require password for seed-based accounts
-                    let password = password.ok_or_else(|| SecurityError::KeystoreError {
-                        message: "Password required for seed-based account".to_string(),
-                    })?;
+        let is_seed_based = is_seed_service(&account.key_reference.service);
 
-                    // Decrypt seed with password
-                    let seed_storage = crate::security::SecureSeedStorage::new(self.keychain.clone_box());
-                    let seed_phrase =
-                        crate::security::decrypt_seed_with_password(&seed_storage, &account.key_reference, password)
-                            .await?;
-
-                    // Derive private key from seed
-                    let derivation_path = account.derivation_path.as_deref();
-                    let mut secure_key = crate::security::derive_key_from_seed(
-                        self.keychain.clone_box(),
-                        &seed_phrase,
-                        derivation_path,
-                    )?;
+        if is_seed_based {
+            tracing::info!("🌱 Seed-based account detected, deriving key from seed");
 
-                    // Get key bytes
-                    let key_bytes = secure_key.as_mut_slice().to_vec();
+            // Require password for seed-based accounts
+            let password = password.ok_or_else(|| SecurityError::KeystoreError {
+                message: "Password required for seed-based account".to_string(),
+            })?;
 
-                    // Cache the key for future use
-                    cache.insert(*address, key_bytes.clone())?;
-                    tracing::info!("🔑 Derived and cached key for address: {}", address);
+            // Decrypt seed with password
+            let seed_storage = crate::security::SecureSeedStorage::new(self.keychain.clone_box());
+            let seed_phrase =
+                crate::security::decrypt_seed_with_password(&seed_storage, &account.key_reference, password).await?;
 
-                    key_bytes
-                }
-            } else {
-                // No cache provided - derive without caching
-                tracing::info!("🔓 No cache provided, deriving key without caching");
-
-                // Require password
-                let password = password.ok_or_else(|| SecurityError::KeystoreError {
-                    message: "Password required for seed-based account".to_string(),
-                })?;
-
-                // Decrypt seed with password
-                let seed_storage = crate::security::SecureSeedStorage::new(self.keychain.clone_box());
-                let seed_phrase =
-                    crate::security::decrypt_seed_with_password(&seed_storage, &account.key_reference, password)
-                        .await?;
-
-                // Derive private key from seed
-                let derivation_path = account.derivation_path.as_deref();
-                let mut secure_key =
-                    crate::security::derive_key_from_seed(self.keychain.clone_box(), &seed_phrase, derivation_path)?;
-
-                // Get key bytes (will be zeroized when secure_key drops)
-                secure_key.as_mut_slice().to_vec()
-            }
+            // Derive private key from seed
+            let passphrase = crate::security::seed::derivation::account_passphrase(account, passphrase)?;
+            let derivation_path = account.derivation_path.as_deref();
+            crate::security::derive_key_from_seed(self.keychain.clone_box(), &seed_phrase, passphrase, derivation_path)
         } else {
             // For private-key accounts, retrieve directly from keychain
             tracing::info!("🔑 Private-key account detected, retrieving from keychain");
@@ -378,139 +396,8 @@ impl SecureKeystoreImpl {
                 .strip_prefix("0x")
                 .unwrap_or(private_key_str);
 
-            hex::decode(clean_key).map_err(|_| SecurityError::InvalidPrivateKey)?
-        };
-
-        let signing_key = SigningKey::from_bytes(
-            key_bytes
-                .as_slice()
-                .into(),
-        )
-        .map_err(|_| SecurityError::InvalidPrivateKey)?;
-
-        // Build Alloy signer from the private key and use its built-in transaction signing
-        let signer = PrivateKeySigner::from(signing_key);
-
-        // Sanity-check: the signer's derived address must match the requested account address
-        let signer_addr = signer.address();
-        if &signer_addr != address {
-            tracing::error!(
-                "❌ Signer address ({}) does not match requested address ({})",
-                signer_addr,
-                address
-            );
-            return Err(SecurityError::InvalidAddress(format!(
-                "Signer address {signer_addr} does not match requested address {address}"
-            ))
-            .into());
+            SecretBuffer::from_vec(hex::decode(clean_key).map_err(|_| SecurityError::InvalidPrivateKey)?)
         }
-
-        tracing::info!(
-            "🧾 Signing tx via Alloy signer: to={:?}, value={:?}, nonce={:?}, chain_id={:?}",
-            tx.to,
-            tx.value,
-            tx.nonce,
-            tx.chain_id
-        );
-
-        // Convert the generic request into a concrete transaction for signing
-        use alloy::consensus::{TxEip1559, TxLegacy};
-        use alloy::primitives::Bytes;
-
-        let chain_id = tx.chain_id.unwrap_or(1u64);
-        let nonce = tx.nonce.unwrap_or(0u64);
-        let gas_limit = tx.gas.unwrap_or(21_000u64);
-        let value = tx.value.unwrap_or_default();
-        let input_data: Bytes = tx.input.input.clone().unwrap_or_default();
-
-        let to_kind: TxKind = match &tx.to {
-            Some(TxKind::Call(addr)) => TxKind::Call(*addr),
-            _ => TxKind::Create,
-        };
-
-        // Sign and encode transaction based on type using Alloy's consensus types
-        use alloy::consensus::TxEnvelope;
-        use alloy::rlp::Encodable;
-
-        let raw_bytes: Vec<u8> =
-            if let (Some(max_fee), Some(max_prio)) = (tx.max_fee_per_gas, tx.max_priority_fee_per_gas) {
-                // EIP-1559 transaction
-                let mut eip1559_tx = TxEip1559 {
-                    chain_id,
-                    nonce,
-                    max_priority_fee_per_gas: max_prio,
-                    max_fee_per_gas: max_fee,
-                    gas_limit,
-                    to: to_kind,
-                    value,
-                    input: input_data,
-                    access_list: Default::default(),
-                };
-
-                // Use TxSigner to sign and get the signature
-                let signature =
-                    signer
-                        .sign_transaction(&mut eip1559_tx)
-                        .await
-                        .map_err(|e| SecurityError::KeystoreError {
-                            message: format!("Failed to sign EIP-1559 tx: {e}"),
-                        })?;
-
-                // Build the signed transaction
-                use alloy::consensus::Signed;
-                let signed_tx = Signed::new_unchecked(eip1559_tx, signature, Default::default());
-
-                // Build the signed envelope
-                let envelope = TxEnvelope::from(signed_tx);
-
-                // Encode to bytes
-                let mut buf = Vec::new();
-                envelope.encode(&mut buf);
-                buf
-            } else {
-                // Legacy transaction
-                let gas_price = tx.gas_price.unwrap_or(20_000_000_000u128);
-                let mut legacy_tx = TxLegacy {
-                    chain_id: Some(chain_id),
-                    nonce,
-                    gas_price,
-                    gas_limit,
-                    to: to_kind,
-                    value,
-                    input: input_data,
-                };
-
-                // Use TxSigner to sign and get the signature
-                let signature =
-                    signer
-                        .sign_transaction(&mut legacy_tx)
-                        .await
-                        .map_err(|e| SecurityError::KeystoreError {
-                            message: format!("Failed to sign legacy tx: {e}"),
-                        })?;
-
-                // Build the signed transaction
-                use alloy::consensus::Signed;
-                let signed_tx = Signed::new_unchecked(legacy_tx, signature, Default::default());
-
-                // Build the signed envelope
-                let envelope = TxEnvelope::from(signed_tx);
-
-                // Encode to bytes
-                let mut buf = Vec::new();
-                envelope.encode(&mut buf);
-                buf
-            };
-
-        // Basic diagnostics
-        tracing::info!("✅ Transaction signed with Alloy signer for address: {}", address);
-        tracing::info!("📦 Encoded transaction length: {} bytes", raw_bytes.len());
-        tracing::info!(
-            "📄 Encoded transaction (hex, prefix 0x, truncated): 0x{}...",
-            hex::encode(&raw_bytes).chars().take(96).collect::<String>()
-        );
-
-        Ok(raw_bytes)
     }
 
     /// Get an account
@@ -528,6 +415,25 @@ impl SecureKeystoreImpl {
         Ok(self.accounts.values().cloned().collect())
     }
 
+    /// Accounts in pages with `visible` ones first, for progressive loading
+    pub fn stream_accounts(
+        &self,
+        visible: &[Address],
+        page_size: usize,
+    ) -> impl futures_util::Stream<Item = AccountPage> + Send + 'static {
+        account_pages(self.accounts.values().cloned().collect(), visible, page_size)
+    }
+
+    /// Replace an account's tags and persist them
+    pub async fn update_account_tags(&mut self, address: Address, tags: Vec<String>) -> Result<()> {
+        let account = self
+            .accounts
+            .get_mut(&address)
+            .ok_or(SecurityError::InvalidAddress(address.to_string()))?;
+        account.tags = tags;
+        self.save_accounts().await
+    }
+
     /// Remove an account
     pub async fn remove_account(&mut self, address: Address) -> Result<()> {
         let account = self
@@ -535,8 +441,14 @@ impl SecureKeystoreImpl {
             .remove(&address)
             .ok_or(SecurityError::InvalidAddress(address.to_string()))?;
 
-        // Remove from keychain
-        self.keychain.delete(&account.key_reference)?;
+        // Remove from keychain, unless other HD accounts still derive from the same seed
+        let shared = self
+            .accounts
+            .values()
+            .any(|other| other.key_reference.id == account.key_reference.id);
+        if !shared {
+            self.keychain.delete(&account.key_reference)?;
+        }
 
         // Save accounts to persistent storage to persist the deletion
         self.save_accounts().await?;
@@ -563,6 +475,23 @@ impl SecureKeystoreImpl {
         Ok(())
     }
 
+    /// Security profile whose accounts this keystore holds
+    pub fn profile(&self) -> SecurityProfile {
+        self.profile
+    }
+
+    /// Refuse key references that belong to another security profile
+    fn ensure_owned(&self, key_reference: &KeyReference) -> Result<()> {
+        if self.profile.owns_service(&key_reference.service) {
+            Ok(())
+        } else {
+            Err(SecurityError::KeystoreError {
+                message: "Key reference belongs to another profile".to_string(),
+            }
+            .into())
+        }
+    }
+
     /// Check if keystore is locked
     pub fn is_locked(&self) -> bool {
         self.is_locked
@@ -673,12 +602,24 @@ impl SecureKeystoreImpl {
         &self,
         address: &Address,
         password: Option<&SecretString>,
+    ) -> Result<SecretString> {
+        self.get_decrypted_private_key_with_passphrase(address, password, None).await
+    }
+
+    /// Retrieve decrypted private key of an account created with a BIP-39 passphrase
+    ///
+    /// A passphrase that derives a different address is rejected.
+    pub async fn get_decrypted_private_key_with_passphrase(
+        &self,
+        address: &Address,
+        password: Option<&SecretString>,
+        passphrase: Option<&SecretString>,
     ) -> Result<SecretString> {
         let account = self.accounts.get(address).ok_or_else(|| {
              SecurityError::InvalidAddress(address.to_string())
         })?;
 
-        let is_seed_based = account.key_reference.service == "vaughan-wallet-encrypted-seeds";
+        let is_seed_based = is_seed_service(&account.key_reference.service);
 
         if is_seed_based {
              let password = password.ok_or_else(|| SecurityError::KeystoreError {
@@ -688,15 +629,21 @@ impl SecureKeystoreImpl {
              let seed_storage = crate::security::SecureSeedStorage::new(self.keychain.clone_box());
              let seed_phrase = crate::security::decrypt_seed_with_password(&seed_storage, &account.key_reference, password).await?;
 
+             let passphrase = crate::security::seed::derivation::account_passphrase(account, passphrase)?;
              let derivation_path = account.derivation_path.as_deref();
-             let mut secure_key = crate::security::derive_key_from_seed(
+             let secure_key = crate::security::derive_key_from_seed(
                  self.keychain.clone_box(), 
                  &seed_phrase, 
+                 passphrase,
                  derivation_path
              )?;
+             if passphrase.is_some() {
+                 // A wrong passphrase derives another wallet rather than failing
+                 signer_from_key(secure_key.expose_secret(), address)?;
+             }
              
              // Convert key bytes to hex string
-             let key_hex = hex::encode(secure_key.as_mut_slice());
+             let key_hex = hex::encode(secure_key.expose_secret());
              Ok(SecretString::new(key_hex))
         } else {
              // Direct private key
@@ -715,7 +662,7 @@ impl SecureKeystoreImpl {
              SecurityError::InvalidAddress(address.to_string())
         })?;
 
-        if account.key_reference.service != "vaughan-wallet-encrypted-seeds" {
+        if !is_seed_service(&account.key_reference.service) {
             return Err(SecurityError::KeystoreError {
                 message: "Account is not seed-based".to_string(),
             }.into());
@@ -725,14 +672,117 @@ impl SecureKeystoreImpl {
         crate::security::decrypt_seed_with_password(&seed_storage, &account.key_reference, password).await
     }
 
+    /// Check a re-entered seed phrase against the account's stored seed
+    ///
+    /// Nothing is exported: only whether the phrases match is returned.
+    pub async fn verify_seed_backup(
+        &self,
+        address: &Address,
+        password: &SecretString,
+        entered_phrase: &SecretString,
+    ) -> Result<bool> {
+        let account = self
+            .accounts
+            .get(address)
+            .ok_or_else(|| SecurityError::InvalidAddress(address.to_string()))?;
+
+        if !is_seed_service(&account.key_reference.service) {
+            return Err(SecurityError::KeystoreError {
+                message: "Account is not seed-based".to_string(),
+            }
+            .into());
+        }
+
+        let seed_storage = crate::security::SecureSeedStorage::new(self.keychain.clone_box());
+        seed_storage
+            .verify_backup(&account.key_reference, password, entered_phrase)
+            .await
+    }
+
+    /// Whether `password` decrypts the wallet's seeds, `None` without seed accounts
+    pub async fn verify_seed_password(&self, password: &SecretString) -> Result<Option<bool>> {
+        let Some(account) = self
+            .accounts
+            .values()
+            .find(|account| is_seed_service(&account.key_reference.service))
+        else {
+            return Ok(None);
+        };
+        let seed_storage = crate::security::SecureSeedStorage::new(self.keychain.clone_box());
+        match seed_storage
+            .retrieve_encrypted_seed_phrase(&account.key_reference, password)
+            .await
+        {
+            Ok(_) => Ok(Some(true)),
+            Err(crate::error::VaughanError::Security(SecurityError::DecryptionError { .. })) => Ok(Some(false)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Re-encrypt every stored seed under a new master password
+    ///
+    /// All seeds are decrypted and re-encrypted in memory before any keychain
+    /// entry is written, so a wrong `old_password` changes nothing. If a write
+    /// fails, the entries already replaced are put back. The returned
+    /// [`SeedPasswordChange`] keeps the previous entries so the caller can
+    /// still undo the change when a later step fails.
+    pub async fn reencrypt_seeds(
+        &self,
+        old_password: &SecretString,
+        new_password: &SecretString,
+    ) -> Result<SeedPasswordChange> {
+        let seed_storage = crate::security::SecureSeedStorage::new(self.keychain.clone_box());
+
+        // Accounts derived from one seed share its keychain entry
+        let mut seen = std::collections::HashSet::new();
+        let mut pending = Vec::new();
+        for account in self.accounts.values() {
+            let key_ref = &account.key_reference;
+            if !is_seed_service(&key_ref.service) || !seen.insert(key_ref.id.clone()) {
+                continue;
+            }
+            let previous = self.keychain.retrieve(key_ref)?;
+            let phrase = seed_storage.retrieve_encrypted_seed_phrase(key_ref, old_password).await?;
+            let sealed = crate::security::SecureSeedStorage::encrypt_for_keychain(&phrase, new_password)?;
+            pending.push((key_ref.clone(), previous, sealed));
+        }
+
+        let mut change = SeedPasswordChange { previous: Vec::new() };
+        for (key_ref, previous, sealed) in pending {
+            if let Err(e) = self.keychain.store(&key_ref, sealed) {
+                tracing::error!("Failed to store re-encrypted seed {}: {}", key_ref.id, e);
+                if let Err(rollback) = self.revert_seed_password_change(change) {
+                    tracing::error!("Rolling back seed re-encryption failed: {}", rollback);
+                }
+                return Err(e);
+            }
+            change.previous.push((key_ref, previous));
+        }
+
+        tracing::info!("Re-encrypted {} seeds under the new master password", change.seed_count());
+        Ok(change)
+    }
+
+    /// Put back the keychain entries replaced by [`Self::reencrypt_seeds`]
+    pub fn revert_seed_password_change(&self, change: SeedPasswordChange) -> Result<()> {
+        let mut first_error = None;
+        for (key_ref, previous) in change.previous.into_iter().rev() {
+            if let Err(e) = self.keychain.store(&key_ref, previous) {
+                tracing::error!("Failed to restore seed {}: {}", key_ref.id, e);
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
     /// Reload accounts from persistent storage
     async fn reload_accounts(&mut self) -> Result<()> {
-        storage::load_accounts(&mut self.accounts, self.keychain.as_ref())
+        storage::load_accounts(&mut self.accounts, self.keychain.as_ref(), self.profile.accounts_file())
     }
 
     /// Save accounts to persistent storage
     async fn save_accounts(&self) -> Result<()> {
-        storage::save_accounts(&self.accounts)
+        storage::save_accounts(&self.accounts, self.profile.accounts_file())
     }
 
     /// Reload networks from persistent storage
@@ -746,6 +796,177 @@ impl SecureKeystoreImpl {
     }
 }
 
+#[async_trait::async_trait]
+impl crate::security::SigningKeySource for tokio::sync::RwLock<SecureKeystoreImpl> {
+    async fn signing_key(
+        &self,
+        address: &Address,
+        password: Option<&SecretString>,
+        passphrase: Option<&SecretString>,
+    ) -> Result<SecretBuffer> {
+        self.read().await.derive_signing_key(address, password, passphrase).await
+    }
+}
+
+/// Build a signer from private key bytes, checking it controls `address`
+pub fn signer_from_key(key_bytes: &[u8], address: &Address) -> Result<PrivateKeySigner> {
+    let signing_key = SigningKey::from_bytes(key_bytes.into()).map_err(|_| SecurityError::InvalidPrivateKey)?;
+    let signer = PrivateKeySigner::from(signing_key);
+
+    // Sanity-check: the signer's derived address must match the requested account address
+    let signer_addr = signer.address();
+    if &signer_addr != address {
+        tracing::error!(
+            "❌ Signer address ({}) does not match requested address ({})",
+            signer_addr,
+            address
+        );
+        return Err(SecurityError::InvalidAddress(format!(
+            "Signer address {signer_addr} does not match requested address {address}"
+        ))
+        .into());
+    }
+    Ok(signer)
+}
+
+/// Pin `tx` to `expected_chain_id` for EIP-155 replay protection
+///
+/// An unset chain ID is filled in; a different one is rejected, since the
+/// signed transaction would be valid on another chain.
+pub fn resolve_chain_id(tx: &TransactionRequest, expected_chain_id: u64) -> Result<TransactionRequest> {
+    match tx.chain_id {
+        Some(actual) if actual != expected_chain_id => Err(NetworkError::ChainIdMismatch {
+            expected: expected_chain_id,
+            actual,
+        }
+        .into()),
+        _ => Ok(TransactionRequest {
+            chain_id: Some(expected_chain_id),
+            ..tx.clone()
+        }),
+    }
+}
+
+/// Sign `tx` with `signer` and return the RLP-encoded signed transaction
+///
+/// `tx.chain_id` must be set; see [`resolve_chain_id`].
+pub fn encode_signed_transaction(tx: &TransactionRequest, signer: &PrivateKeySigner) -> Result<Vec<u8>> {
+    tracing::info!(
+        "🧾 Signing tx via Alloy signer: to={:?}, value={:?}, nonce={:?}, chain_id={:?}",
+        tx.to,
+        tx.value,
+        tx.nonce,
+        tx.chain_id
+    );
+
+    // Convert the generic request into a concrete transaction for signing
+    use alloy::consensus::{TxEip1559, TxLegacy};
+    use alloy::primitives::Bytes;
+
+    let chain_id = tx.chain_id.ok_or_else(|| SecurityError::KeystoreError {
+        message: "Transaction has no chain ID; refusing to sign without replay protection".to_string(),
+    })?;
+    let nonce = tx.nonce.unwrap_or(0u64);
+    let gas_limit = tx.gas.unwrap_or(21_000u64);
+    let value = tx.value.unwrap_or_default();
+    // dApps send calldata as `data`, newer clients as `input`
+    let input_data: Bytes = tx
+        .input
+        .unique_input()
+        .map_err(|_| SecurityError::KeystoreError {
+            message: "Transaction sets both input and data with different values; refusing to sign".to_string(),
+        })?
+        .cloned()
+        .unwrap_or_default();
+
+    let to_kind: TxKind = match &tx.to {
+        Some(TxKind::Call(addr)) => TxKind::Call(*addr),
+        _ => TxKind::Create,
+    };
+
+    // Sign and encode transaction based on type using Alloy's consensus types
+    use alloy::consensus::TxEnvelope;
+    use alloy::rlp::Encodable;
+
+    let raw_bytes: Vec<u8> =
+        if let (Some(max_fee), Some(max_prio)) = (tx.max_fee_per_gas, tx.max_priority_fee_per_gas) {
+            // EIP-1559 transaction
+            let mut eip1559_tx = TxEip1559 {
+                chain_id,
+                nonce,
+                max_priority_fee_per_gas: max_prio,
+                max_fee_per_gas: max_fee,
+                gas_limit,
+                to: to_kind,
+                value,
+                input: input_data,
+                access_list: Default::default(),
+            };
+
+            // Use TxSignerSync to sign and get the signature
+            let signature =
+                signer
+                    .sign_transaction_sync(&mut eip1559_tx)
+                    .map_err(|e| SecurityError::KeystoreError {
+                        message: format!("Failed to sign EIP-1559 tx: {e}"),
+                    })?;
+
+            // Build the signed transaction
+            use alloy::consensus::Signed;
+            let signed_tx = Signed::new_unchecked(eip1559_tx, signature, Default::default());
+
+            // Build the signed envelope
+            let envelope = TxEnvelope::from(signed_tx);
+
+            // Encode to bytes
+            let mut buf = Vec::new();
+            envelope.encode(&mut buf);
+            buf
+        } else {
+            // Legacy transaction
+            let gas_price = tx.gas_price.unwrap_or(20_000_000_000u128);
+            let mut legacy_tx = TxLegacy {
+                chain_id: Some(chain_id),
+                nonce,
+                gas_price,
+                gas_limit,
+                to: to_kind,
+                value,
+                input: input_data,
+            };
+
+            // Use TxSignerSync to sign and get the signature
+            let signature =
+                signer
+                    .sign_transaction_sync(&mut legacy_tx)
+                    .map_err(|e| SecurityError::KeystoreError {
+                        message: format!("Failed to sign legacy tx: {e}"),
+                    })?;
+
+            // Build the signed transaction
+            use alloy::consensus::Signed;
+            let signed_tx = Signed::new_unchecked(legacy_tx, signature, Default::default());
+
+            // Build the signed envelope
+            let envelope = TxEnvelope::from(signed_tx);
+
+            // Encode to bytes
+            let mut buf = Vec::new();
+            envelope.encode(&mut buf);
+            buf
+        };
+
+    // Basic diagnostics
+    tracing::info!("✅ Transaction signed with Alloy signer for address: {}", signer.address());
+    tracing::info!("📦 Encoded transaction length: {} bytes", raw_bytes.len());
+    tracing::info!(
+        "📄 Encoded transaction (hex, prefix 0x, truncated): 0x{}...",
+        hex::encode(&raw_bytes).chars().take(96).collect::<String>()
+    );
+
+    Ok(raw_bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -801,4 +1022,141 @@ mod tests {
         assert!(!keystore.is_locked());
         Ok(())
     }
+
+    /// Shared in-memory keychain whose `store` can be made to fail for one entry
+    #[derive(Debug, Clone, Default)]
+    struct FailingKeychain {
+        entries: std::sync::Arc<std::sync::Mutex<HashMap<String, String>>>,
+        fail_store_for: std::sync::Arc<std::sync::Mutex<Option<String>>>,
+    }
+
+    impl KeychainInterface for FailingKeychain {
+        fn store(&self, key_ref: &KeyReference, key: SecretString) -> Result<()> {
+            if self.fail_store_for.lock().unwrap().as_deref() == Some(key_ref.id.as_str()) {
+                return Err(SecurityError::KeychainError {
+                    message: "store failed".to_string(),
+                }
+                .into());
+            }
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(key_ref.id.clone(), key.expose_secret().clone());
+            Ok(())
+        }
+
+        fn retrieve(&self, key_ref: &KeyReference) -> Result<SecretString> {
+            self.entries
+                .lock()
+                .unwrap()
+                .get(&key_ref.id)
+                .map(|s| SecretString::new(s.clone()))
+                .ok_or_else(|| SecurityError::KeychainError { message: "missing".to_string() }.into())
+        }
+
+        fn delete(&self, key_ref: &KeyReference) -> Result<()> {
+            self.entries.lock().unwrap().remove(&key_ref.id);
+            Ok(())
+        }
+
+        fn clone_box(&self) -> Box<dyn KeychainInterface> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reencrypt_seeds_rolls_back_on_partial_failure() -> Result<()> {
+        let keychain = FailingKeychain::default();
+        let mut keystore = SecureKeystoreImpl::new(Box::new(keychain.clone())).await?;
+        let old = SecretString::new("old master password".to_string());
+        let new = SecretString::new("new master password".to_string());
+        let phrase = SecretString::new(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+        );
+        let sealed = crate::security::SecureSeedStorage::encrypt_for_keychain(&phrase, &old)?;
+
+        for (id, byte) in [("seed-a", 0xa1), ("seed-b", 0xb2)] {
+            let account = SecureAccount {
+                id: id.to_string(),
+                name: id.to_string(),
+                address: Address::repeat_byte(byte),
+                key_reference: KeyReference {
+                    id: id.to_string(),
+                    service: crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string(),
+                    account: id.to_string(),
+                },
+                created_at: chrono::Utc::now(),
+                is_hardware: false,
+                derivation_path: None,
+                tags: Vec::new(),
+                last_used: None,
+                transaction_count: 0,
+            };
+            keystore.restore_account(account, Some(sealed.clone())).await?;
+        }
+        let stored = |id: &str| keychain.entries.lock().unwrap().get(id).cloned();
+        let before = (stored("seed-a"), stored("seed-b"));
+
+        // A wrong old password fails before anything is written
+        let wrong = SecretString::new("wrong".to_string());
+        assert!(keystore.reencrypt_seeds(&wrong, &new).await.is_err());
+        assert_eq!((stored("seed-a"), stored("seed-b")), before);
+
+        // A failed write leaves every seed under the old password
+        *keychain.fail_store_for.lock().unwrap() = Some("seed-b".to_string());
+        assert!(keystore.reencrypt_seeds(&old, &new).await.is_err());
+        assert_eq!((stored("seed-a"), stored("seed-b")), before);
+
+        *keychain.fail_store_for.lock().unwrap() = None;
+        let change = keystore.reencrypt_seeds(&old, &new).await?;
+        assert_eq!(change.seed_count(), 2);
+        assert!(keystore.verify_seed_backup(&Address::repeat_byte(0xa1), &new, &phrase).await?);
+        assert!(keystore.verify_seed_backup(&Address::repeat_byte(0xb2), &old, &phrase).await.is_err());
+
+        keystore.revert_seed_password_change(change)?;
+        assert_eq!((stored("seed-a"), stored("seed-b")), before);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_decoy_profile_keeps_its_own_namespace() -> Result<()> {
+        let mut decoy = SecureKeystoreImpl::with_profile(Box::new(MockKeychain::new()), SecurityProfile::Decoy).await?;
+        assert_eq!(decoy.profile(), SecurityProfile::Decoy);
+
+        let account = decoy.create_account("Decoy".to_string()).await?;
+        assert_eq!(account.key_reference.service, SecurityProfile::Decoy.private_key_service());
+
+        // Key references of the real wallet are refused
+        let real_seed = KeyReference {
+            id: "real-seed".to_string(),
+            service: crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string(),
+            account: "real".to_string(),
+        };
+        assert!(decoy
+            .import_account_with_key_reference("Real".to_string(), Address::repeat_byte(0x11), real_seed)
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_signing_requires_matching_chain_id() {
+        let signer = PrivateKeySigner::random();
+        let tx = TransactionRequest::default().nonce(0).gas_limit(21_000).gas_price(1);
+
+        // No silent mainnet default
+        assert!(encode_signed_transaction(&tx, &signer).is_err());
+
+        let pinned = resolve_chain_id(&tx, 369).unwrap();
+        assert_eq!(pinned.chain_id, Some(369));
+        assert!(encode_signed_transaction(&pinned, &signer).is_ok());
+
+        assert!(matches!(
+            resolve_chain_id(&pinned, 1),
+            Err(crate::error::VaughanError::Network(NetworkError::ChainIdMismatch {
+                expected: 1,
+                actual: 369
+            }))
+        ));
+    }
 }
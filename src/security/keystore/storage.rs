@@ -5,7 +5,8 @@
 
 use crate::error::{Result, SecurityError};
 use crate::network::{NetworkConfig, NetworkId};
-use crate::security::{KeyReference, KeychainInterface, SecureAccount};
+use crate::security::profile::DECOY_SERVICE_NAME_PRIVATE_KEYS;
+use crate::security::{KeyReference, SecureAccount, SERVICE_NAME_PRIVATE_KEYS};
 use chrono;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -40,9 +41,29 @@ pub struct StoredNetworkMeta {
     pub block_explorer: String,
     pub is_testnet: bool,
     pub is_custom: bool,
+    /// Additional RPC URLs tried in order when `rpc_url` fails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rpc_urls: Vec<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl From<StoredAccountMeta> for SecureAccount {
+    fn from(stored: StoredAccountMeta) -> Self {
+        SecureAccount {
+            id: stored.id,
+            name: stored.name,
+            address: stored.address,
+            key_reference: stored.key_reference,
+            created_at: stored.created_at,
+            is_hardware: stored.is_hardware,
+            derivation_path: stored.derivation_path,
+            tags: stored.tags,
+            last_used: stored.last_used,
+            transaction_count: stored.transaction_count,
+        }
+    }
+}
+
 /// Get the path to the .vaughan directory
 pub fn get_vaughan_dir() -> PathBuf {
     let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -109,12 +130,15 @@ pub fn write_secure_file(path: &str, content: &str) -> Result<()> {
 }
 
 /// Load accounts from persistent storage
+///
+/// `file_name` is the profile's accounts file in the `.vaughan` directory.
 pub fn load_accounts(
     accounts: &mut HashMap<alloy::primitives::Address, SecureAccount>,
     keychain: &dyn crate::security::KeychainInterface,
+    file_name: &str,
 ) -> Result<()> {
     let mut accounts_path = get_vaughan_dir();
-    accounts_path.push("accounts.json");
+    accounts_path.push(file_name);
 
     let accounts_file = accounts_path.to_string_lossy().to_string();
 
@@ -129,10 +153,9 @@ pub fn load_accounts(
                     stored.key_reference.id
                 );
                 let key_exists = match stored.key_reference.service.as_str() {
-                    crate::security::SERVICE_NAME_ENCRYPTED_SEEDS => {
+                    service if crate::security::profile::is_seed_service(service) => {
                         // For seed-based accounts, check if the encrypted seed exists
-                        let seed_keychain =
-                            crate::security::keychain::OSKeychain::new(crate::security::SERVICE_NAME_ENCRYPTED_SEEDS.to_string());
+                        let seed_keychain = crate::security::create_keychain(service);
                         match seed_keychain {
                             Ok(kc) => {
                                 let result = kc.retrieve(&stored.key_reference).is_ok();
@@ -145,7 +168,7 @@ pub fn load_accounts(
                             }
                         }
                     }
-                    crate::security::SERVICE_NAME_PRIVATE_KEYS => {
+                    SERVICE_NAME_PRIVATE_KEYS | DECOY_SERVICE_NAME_PRIVATE_KEYS => {
                         // For private key accounts, check if the key exists
                         let result = keychain.retrieve(&stored.key_reference).is_ok();
                         tracing::info!(
@@ -168,24 +191,12 @@ pub fn load_accounts(
                 if key_exists {
                     let account_name = stored.name.clone();
                     let account_address = stored.address;
-                    let account = SecureAccount {
-                        id: stored.id,
-                        name: stored.name,
-                        address: stored.address,
-                        key_reference: stored.key_reference,
-                        created_at: stored.created_at,
-                        is_hardware: stored.is_hardware,
-                        derivation_path: stored.derivation_path,
-                        tags: stored.tags,
-                        last_used: stored.last_used,
-                        transaction_count: stored.transaction_count,
-                    };
-                    accounts.insert(stored.address, account);
+                    accounts.insert(stored.address, SecureAccount::from(stored));
                     tracing::info!(
                         "✅ Keystore loaded account: {} ({}) - address key: {:#x}",
                         account_name,
                         account_address,
-                        stored.address
+                        account_address
                     );
                 } else {
                     tracing::warn!(
@@ -206,10 +217,26 @@ pub fn load_accounts(
     Ok(())
 }
 
-/// Save accounts to persistent storage
-pub fn save_accounts(accounts: &HashMap<alloy::primitives::Address, SecureAccount>) -> Result<()> {
+/// Read account metadata from the accounts file at `path` without the keychain
+///
+/// Unlike [`load_accounts`], nothing is looked up in the keychain, so this is
+/// safe while the wallet is locked and includes accounts whose key is missing.
+pub fn read_account_metadata(path: &std::path::Path) -> Result<Vec<SecureAccount>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let stored: Vec<StoredAccountMeta> = serde_json::from_str(&content).map_err(|e| SecurityError::KeystoreError {
+        message: format!("Failed to parse accounts file: {e}"),
+    })?;
+    Ok(stored.into_iter().map(SecureAccount::from).collect())
+}
+
+/// Save accounts to persistent storage, in the profile's accounts file `file_name`
+pub fn save_accounts(accounts: &HashMap<alloy::primitives::Address, SecureAccount>, file_name: &str) -> Result<()> {
     let mut vaughan_path = ensure_vaughan_dir()?;
-    vaughan_path.push("accounts.json");
+    vaughan_path.push(file_name);
 
     let accounts_file = vaughan_path.to_string_lossy().to_string();
     let stored_accounts: Vec<StoredAccountMeta> = accounts
@@ -256,6 +283,7 @@ pub fn load_networks(networks: &mut HashMap<NetworkId, NetworkConfig>) -> Result
                     block_explorer_url: stored.block_explorer,
                     is_testnet: stored.is_testnet,
                     is_custom: true, // All stored networks are custom
+                    fallback_rpc_urls: stored.fallback_rpc_urls,
                 };
                 networks.insert(stored.id, network);
             }
@@ -282,6 +310,7 @@ pub fn save_networks(networks: &HashMap<NetworkId, NetworkConfig>) -> Result<()>
             block_explorer: network.block_explorer_url.clone(),
             is_testnet: network.is_testnet,
             is_custom: true,
+            fallback_rpc_urls: network.fallback_rpc_urls.clone(),
             created_at: chrono::Utc::now(),
         })
         .collect();
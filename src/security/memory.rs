@@ -9,7 +9,8 @@
 #![allow(unsafe_code)]
 
 use crate::error::{Result, SecurityError};
-use std::ptr;
+use std::fmt;
+use zeroize::Zeroize;
 
 /// Platform-specific memory protection implementation
 pub struct MemoryProtection;
@@ -24,13 +25,12 @@ impl MemoryProtection {
 
         if result != 0 {
             let error = std::io::Error::last_os_error();
+            // Not fatal for callers: mlock is often limited by RLIMIT_MEMLOCK
             tracing::warn!("Failed to lock memory: {}", error);
-            // Don't fail hard on mlock failure - it's not always available
-            // but log the warning so users can investigate
-        } else {
-            tracing::debug!("Successfully locked {} bytes of memory", len);
+            return Err(lock_error(error));
         }
 
+        tracing::debug!("Successfully locked {} bytes of memory", len);
         Ok(())
     }
 
@@ -64,12 +64,12 @@ impl MemoryProtection {
 
         if result == 0 {
             let error = std::io::Error::last_os_error();
+            // Not fatal for callers: the working set quota may be exhausted
             tracing::warn!("Failed to lock memory: {}", error);
-            // Don't fail hard - memory locking might not be available
-        } else {
-            tracing::debug!("Successfully locked {} bytes of memory", len);
+            return Err(lock_error(error));
         }
 
+        tracing::debug!("Successfully locked {} bytes of memory", len);
         Ok(())
     }
 
@@ -92,11 +92,11 @@ impl MemoryProtection {
         Ok(())
     }
 
-    /// No-op for unsupported platforms
+    /// Memory locking is not available on other platforms
     #[cfg(not(any(unix, windows)))]
     pub fn lock_memory(_addr: *mut u8, _len: usize) -> Result<()> {
         tracing::warn!("Memory locking not supported on this platform");
-        Ok(())
+        Err(lock_error(std::io::ErrorKind::Unsupported.into()))
     }
 
     /// No-op for unsupported platforms
@@ -106,6 +106,41 @@ impl MemoryProtection {
         Ok(())
     }
 
+    /// Size of a memory page, the unit [`Self::lock_memory`] works in
+    pub fn page_size() -> usize {
+        static PAGE_SIZE: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+        *PAGE_SIZE.get_or_init(|| {
+            Self::query_page_size()
+                .filter(|size| size.is_power_of_two())
+                .unwrap_or(4096)
+        })
+    }
+
+    #[cfg(unix)]
+    fn query_page_size() -> Option<usize> {
+        // SAFETY: sysconf only reads a system constant.
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        usize::try_from(size).ok()
+    }
+
+    #[cfg(windows)]
+    fn query_page_size() -> Option<usize> {
+        use winapi::um::sysinfoapi::{GetSystemInfo, SYSTEM_INFO};
+
+        // SAFETY: GetSystemInfo fills the zero-initialised SYSTEM_INFO it is given.
+        let info = unsafe {
+            let mut info: SYSTEM_INFO = std::mem::zeroed();
+            GetSystemInfo(&mut info);
+            info
+        };
+        usize::try_from(info.dwPageSize).ok()
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn query_page_size() -> Option<usize> {
+        None
+    }
+
     /// Disable core dumps for the current process
     #[cfg(unix)]
     pub fn disable_core_dumps() -> Result<()> {
@@ -125,14 +160,36 @@ impl MemoryProtection {
             .into());
         }
 
+        // A zero rlimit can be raised again by a debugger or core_pattern pipe;
+        // a non-dumpable process also refuses ptrace from same-user processes
+        #[cfg(target_os = "linux")]
+        {
+            // SAFETY: PR_SET_DUMPABLE takes a single integer argument and has no memory effects.
+            let result = unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0) };
+            if result != 0 {
+                tracing::warn!("Failed to mark process non-dumpable: {}", std::io::Error::last_os_error());
+            }
+        }
+
         tracing::info!("Core dumps disabled for security");
         Ok(())
     }
 
-    /// Windows doesn't have core dumps in the same way
+    /// Suppress Windows Error Reporting crash dialogs and the dumps they collect
     #[cfg(windows)]
     pub fn disable_core_dumps() -> Result<()> {
-        tracing::info!("Core dump protection not needed on Windows");
+        use winapi::um::errhandlingapi::SetErrorMode;
+
+        /// `SEM_FAILCRITICALERRORS | SEM_NOGPFAULTERRORBOX`
+        const SEM_NO_CRASH_REPORTS: u32 = 0x0001 | 0x0002;
+
+        // SAFETY: SetErrorMode only changes process-wide error handling flags.
+        unsafe {
+            let previous = SetErrorMode(SEM_NO_CRASH_REPORTS);
+            SetErrorMode(previous | SEM_NO_CRASH_REPORTS);
+        }
+
+        tracing::info!("Crash dumps disabled for security");
         Ok(())
     }
 
@@ -143,21 +200,32 @@ impl MemoryProtection {
     }
 }
 
+fn lock_error(error: std::io::Error) -> crate::error::VaughanError {
+    SecurityError::KeystoreError {
+        message: format!("Failed to lock memory: {error}"),
+    }
+    .into()
+}
+
 /// Secure memory allocation that locks memory and zeros on drop
+///
+/// Every allocation gets whole pages of its own. Locks are per page, so a
+/// buffer sharing a page with another would lose its lock when the other
+/// one is dropped and unlocks the page.
 #[derive(Debug)]
 pub struct SecureMemory {
     ptr: *mut u8,
     len: usize,
+    /// Bytes allocated and locked, `len` rounded up to whole pages
+    capacity: usize,
     locked: bool,
 }
 
 impl SecureMemory {
     /// Allocate secure memory
     pub fn new(size: usize) -> Result<Self> {
-        // Allocate aligned memory
-        let layout = std::alloc::Layout::from_size_align(size, 8).map_err(|_| SecurityError::KeystoreError {
-            message: "Invalid memory layout".to_string(),
-        })?;
+        // Page-aligned whole pages; zero-sized allocations are undefined behaviour
+        let layout = Self::layout(size)?;
 
         // SAFETY: alloc_zeroed is safe when called with valid layout.
         // Layout was validated above and we check for null pointer below.
@@ -171,9 +239,26 @@ impl SecureMemory {
         }
 
         // Attempt to lock the memory
-        let locked = MemoryProtection::lock_memory(ptr, size).is_ok();
+        let locked = MemoryProtection::lock_memory(ptr, layout.size()).is_ok();
+
+        Ok(SecureMemory {
+            ptr,
+            len: size,
+            capacity: layout.size(),
+            locked,
+        })
+    }
 
-        Ok(SecureMemory { ptr, len: size, locked })
+    fn layout(size: usize) -> Result<std::alloc::Layout> {
+        let page = MemoryProtection::page_size();
+        let invalid = || -> crate::error::VaughanError {
+            SecurityError::KeystoreError {
+                message: "Invalid memory layout".to_string(),
+            }
+            .into()
+        };
+        let capacity = size.max(1).checked_next_multiple_of(page).ok_or_else(invalid)?;
+        std::alloc::Layout::from_size_align(capacity, page).map_err(|_| invalid())
     }
 
     /// Get a mutable pointer to the memory
@@ -186,6 +271,13 @@ impl SecureMemory {
         self.ptr
     }
 
+    /// Get a slice view of the memory
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: self.ptr was allocated with at least self.len bytes and is
+        // valid for reads; the slice borrows self so it cannot outlive it
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
     /// Get a mutable slice view of the memory
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
         // SAFETY: Creating slice from raw parts is safe here because:
@@ -206,12 +298,13 @@ impl SecureMemory {
 
     /// Zero the memory contents
     pub fn zero(&mut self) {
-        // SAFETY: write_bytes is safe here because:
-        // - self.ptr was allocated with self.len bytes and is valid for writes
-        // - We're writing zeros to the entire allocated region
-        unsafe {
-            ptr::write_bytes(self.ptr, 0, self.len);
-        }
+        // Volatile writes, so zeroing right before dealloc is not optimised away
+        self.as_mut_slice().zeroize();
+    }
+
+    /// Bytes reserved for the buffer, whole pages
+    pub fn capacity(&self) -> usize {
+        self.capacity
     }
 
     /// Check if memory is locked
@@ -225,17 +318,17 @@ impl Drop for SecureMemory {
         // Zero the memory before deallocation
         self.zero();
 
-        // Unlock memory if it was locked
+        // Unlock memory if it was locked; no other buffer shares these pages
         if self.locked {
-            let _ = MemoryProtection::unlock_memory(self.ptr, self.len);
+            let _ = MemoryProtection::unlock_memory(self.ptr, self.capacity);
         }
 
         // Deallocate
         // SAFETY: dealloc is safe here because:
-        // - self.ptr was allocated with the exact same layout (size=self.len, align=8)
+        // - self.ptr was allocated with the exact same layout (size=self.capacity, align=page size)
         // - We're calling dealloc exactly once per allocation
         unsafe {
-            let layout = std::alloc::Layout::from_size_align_unchecked(self.len, 8);
+            let layout = std::alloc::Layout::from_size_align_unchecked(self.capacity, MemoryProtection::page_size());
             std::alloc::dealloc(self.ptr, layout);
         }
     }
@@ -250,6 +343,68 @@ impl Drop for SecureMemory {
 // - Drop implementation properly cleans up regardless of thread
 unsafe impl Send for SecureMemory {}
 
+// SAFETY: shared references only read the pointer, length and lock flag;
+// writing through the buffer requires `&mut self`, as with `Vec<u8>`
+unsafe impl Sync for SecureMemory {}
+
+/// Key material in locked memory, zeroized on drop
+///
+/// Used instead of `Vec<u8>` wherever private keys or seeds are held so
+/// every copy lives in pages that are kept out of swap where the OS allows
+/// it. Copies are explicit through [`Self::try_clone`].
+pub struct SecretBuffer(SecureMemory);
+
+impl SecretBuffer {
+    /// Copy `bytes` into a new locked buffer
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let mut memory = SecureMemory::new(bytes.len())?;
+        memory.as_mut_slice().copy_from_slice(bytes);
+        Ok(Self(memory))
+    }
+
+    /// Move `bytes` into a new locked buffer, zeroizing the vector
+    pub fn from_vec(mut bytes: Vec<u8>) -> Result<Self> {
+        let buffer = Self::from_slice(&bytes);
+        bytes.zeroize();
+        buffer
+    }
+
+    /// Copy into a new locked buffer
+    pub fn try_clone(&self) -> Result<Self> {
+        Self::from_slice(self.expose_secret())
+    }
+
+    pub fn expose_secret(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    pub fn expose_secret_mut(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether the pages are locked in RAM
+    pub fn is_locked(&self) -> bool {
+        self.0.is_locked()
+    }
+}
+
+impl fmt::Debug for SecretBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretBuffer")
+            .field("len", &self.len())
+            .field("locked", &self.is_locked())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Initialize memory protection for the application
 pub fn init_memory_protection() -> Result<()> {
     // Disable core dumps to prevent sensitive data from being written to disk
@@ -300,6 +455,30 @@ mod tests {
         let _ = MemoryProtection::unlock_memory(ptr, 4096);
     }
 
+    #[test]
+    fn test_buffers_never_share_a_page() {
+        let page = MemoryProtection::page_size();
+        let buffers: Vec<_> = (0..8).map(|_| SecretBuffer::from_slice(&[1u8; 32]).unwrap()).collect();
+        for buffer in &buffers {
+            assert_eq!(buffer.0.as_ptr() as usize % page, 0);
+            assert_eq!(buffer.0.capacity(), page);
+        }
+        assert_eq!(SecureMemory::new(page + 1).unwrap().capacity(), 2 * page);
+    }
+
+    #[test]
+    fn test_secret_buffer() {
+        let mut bytes = vec![7u8; 32];
+        let buffer = SecretBuffer::from_slice(&bytes).unwrap();
+        bytes.zeroize();
+        assert_eq!(buffer.expose_secret(), &[7u8; 32]);
+        assert!(!format!("{buffer:?}").contains('7'));
+
+        let copy = buffer.try_clone().unwrap();
+        assert_eq!(copy.expose_secret(), buffer.expose_secret());
+        assert!(SecretBuffer::from_vec(Vec::new()).unwrap().is_empty());
+    }
+
     #[test]
     fn test_disable_core_dumps() {
         // Should not panic
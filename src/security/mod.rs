@@ -20,17 +20,28 @@ pub const SERVICE_NAME_ENCRYPTED_SEEDS: &str = "vaughan-wallet-encrypted-seeds";
 pub mod hardware;
 pub mod hardware_feedback;
 pub mod export_auth;
+pub mod cold_account;
+pub mod confirmation;
+pub mod file_vault;
 // pub mod hardware_manager; // Removed redundant module
 
 pub mod key_cache;
 pub mod keychain;
 pub mod keystore;
 pub mod memory;
+pub mod os_auth;
+pub mod password_policy;
 pub mod password_validator;
+pub mod profile;
 pub mod seed;
 pub mod session;
 pub mod rate_limiter;
+pub mod screening;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_keychain;
 pub mod transaction_signing;
+pub mod tx_policy;
+pub mod unlock_throttle;
 pub mod validation;
 pub mod wallet_config;
 pub mod wallet_password_validator;
@@ -38,18 +49,32 @@ pub mod wallet_storage;
 
 pub use hardware::*;
 pub use hardware_feedback::*;
+pub use cold_account::{ColdAccountGuard, ColdAccountPolicy, ColdUnlockStage};
+pub use confirmation::{
+    AutoApprovePolicy, ConfirmationChannel, ConfirmationGate, ConfirmationRecord, ConfirmationRequest, PolicyConfirmation,
+    TerminalConfirmation, WebhookConfirmation,
+};
 pub use export_auth::*;
+pub use file_vault::FileVaultKeychain;
 pub use key_cache::*;
 pub use keychain::*;
 #[allow(ambiguous_glob_reexports)] // encryption module exists in both keystore and seed
 pub use keystore::*;
 pub use memory::*;
+pub use os_auth::{HighRiskOperation, OsAuthOutcome, OsAuthenticator, OsConfirmation, OsConfirmationPolicy};
+pub use password_policy::{PasswordPolicy, PasswordReport, PasswordStrength};
 pub use password_validator::*;
+pub use profile::{DuressGuard, SecurityProfile};
 #[allow(ambiguous_glob_reexports)] // encryption module exists in both keystore and seed
 pub use seed::*;
 pub use session::*;
 pub use rate_limiter::*;
+pub use screening::{Denylist, RiskLevel, ScreeningReport};
+#[cfg(any(test, feature = "test-utils"))]
+pub use test_keychain::TestKeychain;
 pub use transaction_signing::*;
+pub use tx_policy::{OperatingHoursRule, PolicyTimeZone, TimeWindow, TransactionPolicy, ValueThreshold};
+pub use unlock_throttle::{UnlockPolicy, UnlockThrottle, UnlockWait};
 pub use validation::*;
 pub use wallet_config::*;
 pub use wallet_password_validator::*;
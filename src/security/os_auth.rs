@@ -0,0 +1,367 @@
+//! OS-level confirmation of high-risk operations
+//!
+//! On top of the wallet password, the highest-risk operations can require the
+//! operating system's own authentication prompt, which on most machines is
+//! backed by the Keychain, TPM or a fingerprint reader. A compromised wallet
+//! password alone is then not enough to export a seed or drain an account.
+//!
+//! - macOS: an authorization prompt through `osascript`
+//! - Linux: a polkit prompt through `pkcheck`
+//! - other platforms: unavailable, handled per [`OsConfirmationPolicy::allow_unavailable`]
+
+use alloy::primitives::{Address, U256};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use crate::config::{SectionWatch, SecurityPolicySettings};
+use crate::error::{Result, SecurityError};
+
+/// Operation that may need OS confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HighRiskOperation {
+    SeedExport {
+        address: Address,
+    },
+    PrivateKeyExport {
+        address: Address,
+    },
+    /// Transfer of `value` wei
+    Transfer {
+        value: U256,
+    },
+    /// Message or typed-data signature by `address`
+    Signature {
+        address: Address,
+    },
+}
+
+impl HighRiskOperation {
+    /// Text shown in the OS prompt
+    pub fn reason(&self) -> String {
+        match self {
+            Self::SeedExport { address } => format!("Vaughan wants to export the seed phrase of {address}"),
+            Self::PrivateKeyExport { address } => format!("Vaughan wants to export the private key of {address}"),
+            Self::Transfer { value } => format!("Vaughan wants to sign a transfer of {value} wei"),
+            Self::Signature { address } => format!("Vaughan wants to sign a message with {address}"),
+        }
+    }
+}
+
+/// Result of an OS authentication prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OsAuthOutcome {
+    Approved,
+    Denied,
+    /// The platform has no usable prompt
+    Unavailable,
+}
+
+/// Shows the OS authentication prompt
+///
+/// Implementations block until the user answers.
+pub trait OsAuthenticator: Send + Sync + fmt::Debug {
+    fn authenticate(&self, reason: &str) -> OsAuthOutcome;
+}
+
+/// The current platform's authentication prompt
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PlatformAuthenticator;
+
+impl OsAuthenticator for PlatformAuthenticator {
+    #[cfg(target_os = "macos")]
+    fn authenticate(&self, reason: &str) -> OsAuthOutcome {
+        use std::process::Command;
+
+        let prompt = reason.replace('\\', "\\\\").replace('"', "\\\"");
+        let script = format!("do shell script \"true\" with prompt \"{prompt}\" with administrator privileges");
+        match Command::new("osascript").args(["-e", &script]).output() {
+            Ok(output) if output.status.success() => OsAuthOutcome::Approved,
+            Ok(_) => OsAuthOutcome::Denied,
+            Err(e) => {
+                tracing::warn!("⚠️ osascript not available: {}", e);
+                OsAuthOutcome::Unavailable
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn authenticate(&self, reason: &str) -> OsAuthOutcome {
+        use std::process::Command;
+
+        tracing::info!("🔐 Requesting OS authentication: {}", reason);
+        let output = Command::new("pkcheck")
+            .args([
+                "--action-id",
+                "org.freedesktop.policykit.exec",
+                "--process",
+                &std::process::id().to_string(),
+                "--allow-user-interaction",
+            ])
+            .output();
+        match output {
+            Ok(output) if output.status.success() => OsAuthOutcome::Approved,
+            // pkcheck exits with 1 when authorization is refused, other codes on errors
+            Ok(output) if output.status.code() == Some(1) => OsAuthOutcome::Denied,
+            Ok(output) => {
+                tracing::warn!("⚠️ pkcheck failed: {}", String::from_utf8_lossy(&output.stderr));
+                OsAuthOutcome::Unavailable
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ pkcheck not available: {}", e);
+                OsAuthOutcome::Unavailable
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    fn authenticate(&self, _reason: &str) -> OsAuthOutcome {
+        OsAuthOutcome::Unavailable
+    }
+}
+
+/// Which operations need OS confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsConfirmationPolicy {
+    pub enabled: bool,
+    /// Transfers of at least this many wei need confirmation; `None` exempts transfers
+    ///
+    /// Message and typed-data signatures need confirmation whenever transfers
+    /// may, since a signed permit can move any amount.
+    pub transfer_threshold: Option<U256>,
+    /// Proceed on platforms without a prompt instead of refusing
+    pub allow_unavailable: bool,
+}
+
+impl Default for OsConfirmationPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            transfer_threshold: None,
+            allow_unavailable: true,
+        }
+    }
+}
+
+impl OsConfirmationPolicy {
+    pub fn requires(&self, operation: &HighRiskOperation) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        match operation {
+            HighRiskOperation::SeedExport { .. } | HighRiskOperation::PrivateKeyExport { .. } => true,
+            HighRiskOperation::Transfer { value } => self.transfer_threshold.is_some_and(|t| *value >= t),
+            HighRiskOperation::Signature { .. } => self.transfer_threshold.is_some(),
+        }
+    }
+}
+
+/// Applies an [`OsConfirmationPolicy`] with an authenticator
+///
+/// Clones share the policy, so a change made through one applies to all.
+#[derive(Debug, Clone)]
+pub struct OsConfirmation {
+    policy: Arc<RwLock<OsConfirmationPolicy>>,
+    authenticator: Arc<dyn OsAuthenticator>,
+}
+
+impl Default for OsConfirmation {
+    fn default() -> Self {
+        Self::new(OsConfirmationPolicy::default())
+    }
+}
+
+impl OsConfirmation {
+    /// Confirmation through the platform prompt
+    pub fn new(policy: OsConfirmationPolicy) -> Self {
+        Self::with_authenticator(policy, Arc::new(PlatformAuthenticator))
+    }
+
+    pub fn with_authenticator(policy: OsConfirmationPolicy, authenticator: Arc<dyn OsAuthenticator>) -> Self {
+        Self {
+            policy: Arc::new(RwLock::new(policy)),
+            authenticator,
+        }
+    }
+
+    pub fn policy(&self) -> OsConfirmationPolicy {
+        self.policy.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set_policy(&self, policy: OsConfirmationPolicy) {
+        *self.policy.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    /// Apply the `security` section now and whenever it changes
+    pub fn follow_settings(&self, mut settings: SectionWatch<SecurityPolicySettings>) {
+        self.set_policy(OsConfirmationPolicy::from(settings.current()));
+        let policy = Arc::downgrade(&self.policy);
+        tokio::spawn(async move {
+            while let Some(security) = settings.changed().await {
+                let Some(policy) = policy.upgrade() else {
+                    break;
+                };
+                tracing::info!("🔐 OS confirmation policy updated from settings");
+                *policy.write().unwrap_or_else(|e| e.into_inner()) = OsConfirmationPolicy::from(&security);
+            }
+        });
+    }
+
+    /// Prompt for `operation` if the policy requires it
+    pub async fn confirm(&self, operation: &HighRiskOperation) -> Result<()> {
+        let policy = self.policy();
+        if !policy.requires(operation) {
+            return Ok(());
+        }
+
+        let reason = operation.reason();
+        let authenticator = Arc::clone(&self.authenticator);
+        let prompt_reason = reason.clone();
+        let outcome = tokio::task::spawn_blocking(move || authenticator.authenticate(&prompt_reason))
+            .await
+            .unwrap_or(OsAuthOutcome::Unavailable);
+
+        match outcome {
+            OsAuthOutcome::Approved => Ok(()),
+            OsAuthOutcome::Unavailable if policy.allow_unavailable => {
+                tracing::warn!("⚠️ OS authentication unavailable, continuing with password only");
+                Ok(())
+            }
+            OsAuthOutcome::Unavailable => Err(SecurityError::OsAuthenticationFailed {
+                reason: format!("{reason}: no OS authentication prompt available"),
+            }
+            .into()),
+            OsAuthOutcome::Denied => Err(SecurityError::OsAuthenticationFailed {
+                reason: format!("{reason}: denied"),
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug)]
+    struct MockAuthenticator {
+        outcome: OsAuthOutcome,
+        prompts: AtomicUsize,
+    }
+
+    fn mock(outcome: OsAuthOutcome) -> Arc<MockAuthenticator> {
+        Arc::new(MockAuthenticator {
+            outcome,
+            prompts: AtomicUsize::new(0),
+        })
+    }
+
+    impl OsAuthenticator for MockAuthenticator {
+        fn authenticate(&self, _reason: &str) -> OsAuthOutcome {
+            self.prompts.fetch_add(1, Ordering::SeqCst);
+            self.outcome
+        }
+    }
+
+    fn policy() -> OsConfirmationPolicy {
+        OsConfirmationPolicy {
+            enabled: true,
+            transfer_threshold: Some(U256::from(1_000)),
+            allow_unavailable: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_high_risk_operations_prompt() {
+        let authenticator = mock(OsAuthOutcome::Approved);
+        let confirmation = OsConfirmation::with_authenticator(policy(), authenticator.clone());
+
+        confirmation
+            .confirm(&HighRiskOperation::Transfer { value: U256::from(999) })
+            .await
+            .unwrap();
+        assert_eq!(authenticator.prompts.load(Ordering::SeqCst), 0);
+
+        confirmation
+            .confirm(&HighRiskOperation::Transfer {
+                value: U256::from(1_000),
+            })
+            .await
+            .unwrap();
+        confirmation
+            .confirm(&HighRiskOperation::SeedExport { address: Address::ZERO })
+            .await
+            .unwrap();
+        confirmation
+            .confirm(&HighRiskOperation::Signature { address: Address::ZERO })
+            .await
+            .unwrap();
+        assert_eq!(authenticator.prompts.load(Ordering::SeqCst), 3);
+
+        let disabled = OsConfirmation::with_authenticator(OsConfirmationPolicy::default(), authenticator.clone());
+        disabled
+            .confirm(&HighRiskOperation::PrivateKeyExport { address: Address::ZERO })
+            .await
+            .unwrap();
+        assert_eq!(authenticator.prompts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_denied_and_unavailable_prompts() {
+        let export = HighRiskOperation::SeedExport { address: Address::ZERO };
+
+        let denied = OsConfirmation::with_authenticator(policy(), mock(OsAuthOutcome::Denied));
+        assert!(denied
+            .confirm(&export)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("denied"));
+
+        let strict = OsConfirmation::with_authenticator(policy(), mock(OsAuthOutcome::Unavailable));
+        assert!(strict.confirm(&export).await.is_err());
+
+        let lenient = OsConfirmation::with_authenticator(
+            OsConfirmationPolicy {
+                allow_unavailable: true,
+                ..policy()
+            },
+            mock(OsAuthOutcome::Unavailable),
+        );
+        lenient.confirm(&export).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wallet_policy_follows_security_settings() {
+        use crate::wallet::{Vaughan, WalletConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = crate::config::SettingsStore::open(dir.path().join("settings.json")).unwrap();
+        let mut settings = store.current().as_ref().clone();
+        settings.security.os_confirmation = true;
+        store.update(settings.clone()).unwrap();
+
+        let config = WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            ..Default::default()
+        };
+        let wallet = Vaughan::with_keychain(config, Box::new(crate::security::TestKeychain::new()))
+            .await
+            .unwrap();
+        wallet.follow_security_settings(store.subscribe_section());
+        assert!(wallet.os_confirmation_policy().enabled);
+
+        assert_eq!(wallet.os_confirmation_policy().transfer_threshold, None);
+
+        settings.security.os_confirmation_transfer_wei = Some(U256::from(5));
+        store.update(settings).unwrap();
+        for _ in 0..100 {
+            if wallet.os_confirmation_policy().transfer_threshold.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(wallet.os_confirmation_policy().transfer_threshold, Some(U256::from(5)));
+    }
+}
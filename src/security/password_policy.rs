@@ -0,0 +1,392 @@
+//! Master password strength policy
+//!
+//! Passwords are scored by estimating how many guesses an attacker would
+//! need, in the spirit of zxcvbn: the password is split into the cheapest
+//! patterns that explain it (common passwords and words, repeated
+//! characters, alphabet or keyboard sequences) and only the remaining
+//! characters are charged at full brute-force cost. The estimate is then
+//! bucketed into a [`PasswordStrength`].
+//!
+//! [`PasswordPolicy`] combines that score with minimum requirements
+//! (length, character classes) and a blacklist of common passwords.
+
+use crate::error::{Result, SecurityError};
+use secrecy::{ExposeSecret, SecretString};
+use std::fmt;
+
+/// Most common passwords from public breach corpora, plus wallet-themed ones
+#[rustfmt::skip]
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "123456789", "12345678", "password", "qwerty", "12345", "1234567", "111111", "123123", "1234567890",
+    "000000", "abc123", "password1", "1234", "iloveyou", "qwerty123", "dragon", "monkey", "letmein", "sunshine",
+    "princess", "football", "baseball", "welcome", "shadow", "superman", "michael", "master", "trustno1", "696969",
+    "jennifer", "hunter", "hunter2", "freedom", "whatever", "qazwsx", "starwars", "654321", "666666", "121212",
+    "batman", "passw0rd", "login", "admin", "administrator", "charlie", "donald", "zaq1zaq1", "mustang", "access",
+    "flower", "lovely", "ashley", "bailey", "soccer", "hockey", "killer", "jordan", "harley", "ranger",
+    "thomas", "tigger", "robert", "daniel", "hannah", "maggie", "jessica", "pepper", "ginger", "summer",
+    "computer", "internet", "cheese", "secret", "changeme", "default", "qwertyuiop", "asdfghjkl", "zxcvbnm",
+    "1q2w3e4r", "1qaz2wsx", "q1w2e3r4", "aa123456", "987654321", "7777777", "555555", "solo", "matrix", "test",
+    "testing", "guest", "root", "love", "money", "blockchain", "bitcoin", "ethereum", "crypto", "wallet",
+    "metamask", "satoshi", "nakamoto", "tothemoon", "hodl", "lambo", "pulsechain", "vaughan", "mnemonic", "seed",
+];
+
+/// Everyday words that often anchor human-chosen passwords
+#[rustfmt::skip]
+const COMMON_WORDS: &[&str] = &[
+    "love", "baby", "angel", "god", "jesus", "life", "happy", "family", "friend", "summer", "winter", "spring",
+    "autumn", "monday", "sunday", "january", "december", "dog", "cat", "horse", "tiger", "eagle", "dragon", "star",
+    "moon", "sun", "blue", "red", "green", "black", "white", "gold", "silver", "money", "coin", "token", "vault",
+    "safe", "key", "lock", "pass", "word", "user", "name", "home", "house", "world", "hello", "welcome", "qwerty",
+];
+
+/// Keyboard rows used to detect walks like `qwert` or `!@#$`
+const KEYBOARD_ROWS: &[&str] = &[
+    "`1234567890-=",
+    "qwertyuiop[]\\",
+    "asdfghjkl;'",
+    "zxcvbnm,./",
+    "~!@#$%^&*()_+",
+    "QWERTYUIOP{}|",
+    "ASDFGHJKL:\"",
+    "ZXCVBNM<>?",
+];
+
+/// Estimated password strength, weakest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PasswordStrength {
+    VeryWeak,
+    Weak,
+    Fair,
+    Strong,
+    VeryStrong,
+}
+
+impl PasswordStrength {
+    /// Bucket an entropy estimate in bits
+    pub fn from_entropy(bits: f64) -> Self {
+        match bits {
+            b if b < 28.0 => Self::VeryWeak,
+            b if b < 36.0 => Self::Weak,
+            b if b < 50.0 => Self::Fair,
+            b if b < 64.0 => Self::Strong,
+            _ => Self::VeryStrong,
+        }
+    }
+
+    /// Score from 0 (very weak) to 4 (very strong)
+    pub fn score(self) -> u8 {
+        self as u8
+    }
+}
+
+impl fmt::Display for PasswordStrength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::VeryWeak => "very weak",
+            Self::Weak => "weak",
+            Self::Fair => "fair",
+            Self::Strong => "strong",
+            Self::VeryStrong => "very strong",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Outcome of checking a password against a [`PasswordPolicy`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordReport {
+    pub strength: PasswordStrength,
+    /// Estimated entropy after pattern penalties
+    pub entropy_bits: f64,
+    /// Policy requirements the password fails; empty when acceptable
+    pub violations: Vec<String>,
+    /// Hints for making the password stronger
+    pub suggestions: Vec<String>,
+}
+
+impl PasswordReport {
+    pub fn is_acceptable(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Minimum requirements for a master password
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    /// Of lowercase, uppercase, digits and symbols
+    pub min_character_classes: usize,
+    pub min_strength: PasswordStrength,
+    pub reject_common: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 12,
+            min_character_classes: 3,
+            min_strength: PasswordStrength::Fair,
+            reject_common: true,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Score a password and list the requirements it fails
+    pub fn evaluate(&self, password: &SecretString) -> PasswordReport {
+        let password = password.expose_secret();
+        let estimate = estimate_entropy(password);
+        let strength = PasswordStrength::from_entropy(estimate.bits);
+        let mut violations = Vec::new();
+        let mut suggestions = Vec::new();
+
+        let length = password.chars().count();
+        if length < self.min_length {
+            violations.push(format!("Must be at least {} characters long", self.min_length));
+        }
+
+        let classes = character_classes(password);
+        if classes < self.min_character_classes {
+            violations.push(format!(
+                "Must mix at least {} of: lowercase, uppercase, digits, symbols",
+                self.min_character_classes
+            ));
+        }
+
+        if self.reject_common && is_common_password(password) {
+            violations.push("Is a commonly used password".to_string());
+        }
+
+        if strength < self.min_strength {
+            violations.push(format!("Is {strength}; must be at least {}", self.min_strength));
+        }
+
+        if estimate.dictionary {
+            suggestions.push("Avoid common words and passwords, even with letters swapped for digits".to_string());
+        }
+        if estimate.sequences {
+            suggestions.push("Avoid sequences like 'abc', '123' or keyboard rows".to_string());
+        }
+        if estimate.repeats {
+            suggestions.push("Avoid repeated characters".to_string());
+        }
+        if strength < PasswordStrength::Strong {
+            suggestions.push("Add a few more unrelated words or characters".to_string());
+        }
+
+        PasswordReport {
+            strength,
+            entropy_bits: estimate.bits,
+            violations,
+            suggestions,
+        }
+    }
+
+    /// Fail with [`SecurityError::WeakPassword`] unless the password meets the policy
+    pub fn check(&self, password: &SecretString) -> Result<PasswordReport> {
+        let report = self.evaluate(password);
+        if report.is_acceptable() {
+            Ok(report)
+        } else {
+            Err(SecurityError::WeakPassword {
+                reasons: report.violations,
+            }
+            .into())
+        }
+    }
+}
+
+/// Whether the password, ignoring case, common digit-for-letter swaps and
+/// trailing digits or symbols, is blacklisted
+pub fn is_common_password(password: &str) -> bool {
+    let lowered = password.to_lowercase();
+    let base = lowered.trim_end_matches(|c: char| !c.is_alphabetic());
+    [lowered.as_str(), base]
+        .iter()
+        .filter(|candidate| !candidate.is_empty())
+        .any(|candidate| COMMON_PASSWORDS.contains(candidate) || COMMON_PASSWORDS.contains(&unleet(candidate).as_str()))
+}
+
+fn character_classes(password: &str) -> usize {
+    [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .iter()
+    .filter(|&&present| present)
+    .count()
+}
+
+fn unleet(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c => c,
+        })
+        .collect()
+}
+
+/// Size of the alphabet an attacker must search for this password
+fn charset_size(password: &str) -> f64 {
+    let mut size = 0.0;
+    if password.chars().any(|c| c.is_ascii_lowercase()) {
+        size += 26.0;
+    }
+    if password.chars().any(|c| c.is_ascii_uppercase()) {
+        size += 26.0;
+    }
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        size += 10.0;
+    }
+    if password.chars().any(|c| c.is_ascii_punctuation() || c == ' ') {
+        size += 33.0;
+    }
+    if !password.is_ascii() {
+        size += 100.0;
+    }
+    f64::max(size, 10.0)
+}
+
+struct Estimate {
+    bits: f64,
+    dictionary: bool,
+    sequences: bool,
+    repeats: bool,
+}
+
+/// Estimate entropy by charging each recognized pattern its guess cost and
+/// every other character the brute-force cost
+fn estimate_entropy(password: &str) -> Estimate {
+    let chars: Vec<char> = password.chars().collect();
+    let lowered: Vec<char> = unleet(&password.to_lowercase()).chars().collect();
+    let char_bits = charset_size(password).log2();
+    let mut estimate = Estimate {
+        bits: 0.0,
+        dictionary: false,
+        sequences: false,
+        repeats: false,
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some(len) = dictionary_match(&lowered[i..]) {
+            // A word from a few hundred; capitals and substitutions add a bit each
+            let word = &chars[i..i + len];
+            let capitalized = word.iter().any(|c| c.is_uppercase());
+            let substituted = word.iter().any(|c| !c.is_alphabetic());
+            estimate.bits += ((COMMON_PASSWORDS.len() + COMMON_WORDS.len()) as f64).log2()
+                + f64::from(u8::from(capitalized) + u8::from(substituted));
+            estimate.dictionary = true;
+            i += len;
+        } else if let Some(len) = repeat_run(&chars[i..]) {
+            estimate.bits += char_bits + (len as f64).log2();
+            estimate.repeats = true;
+            i += len;
+        } else if let Some(len) = sequence_run(&chars[i..]) {
+            // Start character plus direction and length
+            estimate.bits += char_bits.min(6.0) + 1.0 + (len as f64).log2();
+            estimate.sequences = true;
+            i += len;
+        } else {
+            estimate.bits += char_bits;
+            i += 1;
+        }
+    }
+
+    estimate
+}
+
+/// Longest blacklisted password or common word (4+ letters) starting the slice
+fn dictionary_match(lowered: &[char]) -> Option<usize> {
+    COMMON_PASSWORDS
+        .iter()
+        .chain(COMMON_WORDS)
+        .filter(|word| word.len() >= 4 && word.chars().all(char::is_alphabetic))
+        .map(|word| word.chars().collect::<Vec<_>>())
+        .filter(|word| lowered.starts_with(word))
+        .map(|word| word.len())
+        .max()
+}
+
+/// Length of a run of one repeated character, if at least 3
+fn repeat_run(chars: &[char]) -> Option<usize> {
+    let first = *chars.first()?;
+    let len = chars.iter().take_while(|&&c| c == first).count();
+    (len >= 3).then_some(len)
+}
+
+/// Length of an alphabetic, numeric or keyboard sequence, if at least 3
+fn sequence_run(chars: &[char]) -> Option<usize> {
+    let len = 1 + chars
+        .windows(2)
+        .take_while(|pair| is_sequence_step(pair[0], pair[1]))
+        .count();
+    (len >= 3).then_some(len)
+}
+
+/// Whether `b` follows `a` in the alphabet, digits or a keyboard row (either direction)
+fn is_sequence_step(a: char, b: char) -> bool {
+    let (la, lb) = (a.to_ascii_lowercase(), b.to_ascii_lowercase());
+    let adjacent = la.is_ascii_alphanumeric()
+        && lb.is_ascii_alphanumeric()
+        && (la.is_ascii_digit() == lb.is_ascii_digit())
+        && (la as i32 - lb as i32).abs() == 1;
+    adjacent
+        || KEYBOARD_ROWS
+            .iter()
+            .any(|row| row.contains(&format!("{a}{b}")) || row.contains(&format!("{b}{a}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(password: &str) -> PasswordReport {
+        PasswordPolicy::default().evaluate(&SecretString::new(password.to_string()))
+    }
+
+    #[test]
+    fn test_common_and_patterned_passwords_are_rejected() {
+        assert!(is_common_password("P@ssw0rd"));
+        assert!(is_common_password("password123!"));
+
+        for password in [
+            "password",
+            "P@ssw0rd1234",
+            "aaaaaaaaaaaaaaaa",
+            "abcdefgh12345678",
+            "qwertyuiop123!",
+        ] {
+            let report = evaluate(password);
+            assert!(!report.is_acceptable(), "{password} should be rejected");
+        }
+        assert_eq!(evaluate("aaaaaaaaaaaa").strength, PasswordStrength::VeryWeak);
+    }
+
+    #[test]
+    fn test_strength_grows_with_unpredictability() {
+        let weak = evaluate("Summer2024!!");
+        let strong = evaluate("t7#Kq9!vRm2$wX");
+        assert!(strong.entropy_bits > weak.entropy_bits);
+        assert!(strong.strength >= PasswordStrength::Strong);
+        assert!(strong.is_acceptable());
+
+        let short = evaluate("t7#Kq9!");
+        assert!(short.violations.iter().any(|v| v.contains("12 characters")));
+    }
+
+    #[test]
+    fn test_check_reports_violations() {
+        let err = PasswordPolicy::default()
+            .check(&SecretString::new("letmein".to_string()))
+            .unwrap_err();
+        assert!(err.to_string().contains("commonly used"));
+    }
+}
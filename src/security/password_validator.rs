@@ -301,7 +301,7 @@ mod tests {
 
     #[test]
     fn test_initial_state() {
-        let keychain = crate::security::keychain::OSKeychain::new("test".to_string()).unwrap();
+        let keychain = crate::security::TestKeychain::new();
         let seed_storage = SecureSeedStorage::new(Box::new(keychain));
         let validator = PasswordValidator::new(seed_storage);
 
@@ -312,7 +312,7 @@ mod tests {
 
     #[test]
     fn test_failure_increment() {
-        let keychain = crate::security::keychain::OSKeychain::new("test".to_string()).unwrap();
+        let keychain = crate::security::TestKeychain::new();
         let seed_storage = SecureSeedStorage::new(Box::new(keychain));
         let validator = PasswordValidator::new(seed_storage);
 
@@ -327,7 +327,7 @@ mod tests {
 
     #[test]
     fn test_lockout_after_max_failures() {
-        let keychain = crate::security::keychain::OSKeychain::new("test".to_string()).unwrap();
+        let keychain = crate::security::TestKeychain::new();
         let seed_storage = SecureSeedStorage::new(Box::new(keychain));
         let validator = PasswordValidator::new(seed_storage);
 
@@ -353,7 +353,7 @@ mod tests {
 
     #[test]
     fn test_clear_failures_on_success() {
-        let keychain = crate::security::keychain::OSKeychain::new("test".to_string()).unwrap();
+        let keychain = crate::security::TestKeychain::new();
         let seed_storage = SecureSeedStorage::new(Box::new(keychain));
         let validator = PasswordValidator::new(seed_storage);
 
@@ -373,7 +373,7 @@ mod tests {
 
     #[test]
     fn test_attempt_recording() {
-        let keychain = crate::security::keychain::OSKeychain::new("test".to_string()).unwrap();
+        let keychain = crate::security::TestKeychain::new();
         let seed_storage = SecureSeedStorage::new(Box::new(keychain));
         let validator = PasswordValidator::new(seed_storage);
 
@@ -395,7 +395,7 @@ mod tests {
 
     #[test]
     fn test_rate_limit_check() {
-        let keychain = crate::security::keychain::OSKeychain::new("test".to_string()).unwrap();
+        let keychain = crate::security::TestKeychain::new();
         let seed_storage = SecureSeedStorage::new(Box::new(keychain));
         let validator = PasswordValidator::new(seed_storage);
 
@@ -419,7 +419,7 @@ mod tests {
 
     #[test]
     fn test_clear_all() {
-        let keychain = crate::security::keychain::OSKeychain::new("test".to_string()).unwrap();
+        let keychain = crate::security::TestKeychain::new();
         let seed_storage = SecureSeedStorage::new(Box::new(keychain));
         let validator = PasswordValidator::new(seed_storage);
 
@@ -442,7 +442,7 @@ mod tests {
 
     #[test]
     fn test_multiple_accounts() {
-        let keychain = crate::security::keychain::OSKeychain::new("test".to_string()).unwrap();
+        let keychain = crate::security::TestKeychain::new();
         let seed_storage = SecureSeedStorage::new(Box::new(keychain));
         let validator = PasswordValidator::new(seed_storage);
 
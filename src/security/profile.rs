@@ -0,0 +1,268 @@
+//! Security profiles and the duress password
+//!
+//! The wallet keeps two independent keystore namespaces. The primary
+//! profile holds the real accounts; the secondary (decoy) profile has its
+//! own accounts file and keychain service names, so nothing in it refers
+//! to the real accounts.
+//!
+//! An optional duress password selects the decoy profile at unlock. When
+//! someone is forced to unlock the wallet, entering the duress password
+//! opens the decoy accounts while the real ones are never decrypted or
+//! listed. Storage names are deliberately neutral so the decoy namespace
+//! does not announce itself.
+
+use crate::error::{Result, SecurityError};
+use crate::security::keystore::storage::{ensure_vaughan_dir, get_vaughan_dir, write_secure_file};
+use crate::security::seed::zeroization::constant_time_eq;
+use crate::security::{KeychainInterface, SERVICE_NAME_ENCRYPTED_SEEDS, SERVICE_NAME_PRIVATE_KEYS};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Private key service of the decoy profile
+pub const DECOY_SERVICE_NAME_PRIVATE_KEYS: &str = "vaughan-wallet-2";
+/// Encrypted seed service of the decoy profile
+pub const DECOY_SERVICE_NAME_ENCRYPTED_SEEDS: &str = "vaughan-wallet-encrypted-seeds-2";
+/// File holding the duress password verifier
+pub const AUTH_PARAMS_FILE: &str = "auth-params.json";
+
+/// Argon2id parameters for the duress verifier (64 MiB, 3 iterations, 4 lanes)
+const VERIFIER_MEMORY: u32 = 65536;
+const VERIFIER_ITERATIONS: u32 = 3;
+const VERIFIER_PARALLELISM: u32 = 4;
+
+/// Which keystore namespace is open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityProfile {
+    /// The real wallet
+    #[default]
+    Primary,
+    /// Decoy accounts opened by the duress password
+    Decoy,
+}
+
+impl SecurityProfile {
+    /// Keychain service for private-key accounts
+    pub fn private_key_service(self) -> &'static str {
+        match self {
+            Self::Primary => SERVICE_NAME_PRIVATE_KEYS,
+            Self::Decoy => DECOY_SERVICE_NAME_PRIVATE_KEYS,
+        }
+    }
+
+    /// Keychain service for encrypted seed phrases
+    pub fn seed_service(self) -> &'static str {
+        match self {
+            Self::Primary => SERVICE_NAME_ENCRYPTED_SEEDS,
+            Self::Decoy => DECOY_SERVICE_NAME_ENCRYPTED_SEEDS,
+        }
+    }
+
+    /// Account metadata file in the `.vaughan` directory
+    pub fn accounts_file(self) -> &'static str {
+        match self {
+            Self::Primary => "accounts.json",
+            Self::Decoy => "accounts-2.json",
+        }
+    }
+
+    /// Whether a keychain entry under `service` may be used by this profile
+    ///
+    /// Services of the other profile are refused so keys never cross namespaces.
+    pub fn owns_service(self, service: &str) -> bool {
+        let other = match self {
+            Self::Primary => Self::Decoy,
+            Self::Decoy => Self::Primary,
+        };
+        service != other.private_key_service() && service != other.seed_service()
+    }
+
+    /// Keychain the wallet opens this profile with, on the configured backend
+    ///
+    /// Like [`crate::security::create_keychain_interface`] for the primary
+    /// profile, it is bound to the profile's seed service.
+    pub fn keychain(self) -> Result<Box<dyn KeychainInterface>> {
+        crate::security::create_keychain(self.seed_service())
+    }
+}
+
+/// Whether `service` holds encrypted seed phrases, in either profile
+pub fn is_seed_service(service: &str) -> bool {
+    service == SERVICE_NAME_ENCRYPTED_SEEDS || service == DECOY_SERVICE_NAME_ENCRYPTED_SEEDS
+}
+
+/// Stored verifier for the duress password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DuressVerifier {
+    /// Hex-encoded Argon2id salt
+    salt: String,
+    /// Hex-encoded Argon2id output
+    hash: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Duress password configuration
+///
+/// Only an Argon2id verifier is stored, never the password itself.
+#[derive(Debug)]
+pub struct DuressGuard {
+    path: PathBuf,
+    verifier: Option<DuressVerifier>,
+}
+
+impl DuressGuard {
+    /// Open the configuration in the `.vaughan` directory
+    pub fn open_default() -> Self {
+        Self::open(get_vaughan_dir().join(AUTH_PARAMS_FILE))
+    }
+
+    /// Open the configuration at `path`; a missing or unreadable file means no duress password
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let verifier = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(verifier) => Some(verifier),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid auth parameters {}: {}", path.display(), e);
+                    None
+                }
+            });
+        Self { path, verifier }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.verifier.is_some()
+    }
+
+    /// Set or replace the duress password
+    ///
+    /// `master_password` is only used to refuse a duress password equal to it.
+    pub fn set(&mut self, duress_password: &SecretString, master_password: &SecretString) -> Result<()> {
+        use secrecy::ExposeSecret;
+        if duress_password.expose_secret().is_empty() {
+            return Err(SecurityError::KeystoreError {
+                message: "Duress password cannot be empty".to_string(),
+            }
+            .into());
+        }
+        if constant_time_eq(
+            duress_password.expose_secret().as_bytes(),
+            master_password.expose_secret().as_bytes(),
+        ) {
+            return Err(SecurityError::KeystoreError {
+                message: "Duress password must differ from the master password".to_string(),
+            }
+            .into());
+        }
+
+        let salt: [u8; 16] = rand::random();
+        let hash = Self::derive(duress_password, &salt)?;
+        let verifier = DuressVerifier {
+            salt: hex::encode(salt),
+            hash: hex::encode(hash),
+            created_at: chrono::Utc::now(),
+        };
+
+        if self.path.starts_with(get_vaughan_dir()) {
+            ensure_vaughan_dir()?;
+        }
+        let contents = serde_json::to_string_pretty(&verifier).map_err(|e| SecurityError::SerializationError {
+            message: format!("Failed to serialize auth parameters: {e}"),
+        })?;
+        write_secure_file(&self.path.to_string_lossy(), &contents)?;
+        self.verifier = Some(verifier);
+
+        tracing::info!("Auth parameters updated");
+        Ok(())
+    }
+
+    /// Remove the duress password; the decoy accounts are kept
+    pub fn clear(&mut self) -> Result<()> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path).map_err(|e| SecurityError::KeystoreError {
+                message: format!("Failed to remove auth parameters: {e}"),
+            })?;
+        }
+        self.verifier = None;
+        Ok(())
+    }
+
+    /// Whether `password` is the duress password
+    pub fn is_duress_password(&self, password: &SecretString) -> bool {
+        let Some(verifier) = &self.verifier else {
+            return false;
+        };
+        let (Ok(salt), Ok(expected)) = (hex::decode(&verifier.salt), hex::decode(&verifier.hash)) else {
+            return false;
+        };
+        match Self::derive(password, &salt) {
+            Ok(hash) => constant_time_eq(&hash, &expected),
+            Err(_) => false,
+        }
+    }
+
+    /// Profile an unlock with `password` should open
+    pub fn resolve(&self, password: &SecretString) -> SecurityProfile {
+        if self.is_duress_password(password) {
+            SecurityProfile::Decoy
+        } else {
+            SecurityProfile::Primary
+        }
+    }
+
+    fn derive(password: &SecretString, salt: &[u8]) -> Result<[u8; 32]> {
+        crate::security::seed::encryption::derive_key_argon2id(
+            password,
+            salt,
+            VERIFIER_MEMORY,
+            VERIFIER_ITERATIONS,
+            VERIFIER_PARALLELISM,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duress_password_selects_decoy_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUTH_PARAMS_FILE);
+        let master = SecretString::new("real master password".to_string());
+        let duress = SecretString::new("decoy password".to_string());
+
+        let mut guard = DuressGuard::open(&path);
+        assert!(!guard.is_enabled());
+        assert_eq!(guard.resolve(&duress), SecurityProfile::Primary);
+
+        assert!(guard.set(&master, &master).is_err());
+        guard.set(&duress, &master).unwrap();
+
+        // The verifier survives a restart and never stores the password
+        let guard = DuressGuard::open(&path);
+        assert!(guard.is_enabled());
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("decoy password"));
+        assert_eq!(guard.resolve(&duress), SecurityProfile::Decoy);
+        assert_eq!(guard.resolve(&master), SecurityProfile::Primary);
+
+        let mut guard = guard;
+        guard.clear().unwrap();
+        assert_eq!(DuressGuard::open(&path).resolve(&duress), SecurityProfile::Primary);
+    }
+
+    #[test]
+    fn test_profiles_use_separate_namespaces() {
+        let (primary, decoy) = (SecurityProfile::Primary, SecurityProfile::Decoy);
+        assert_ne!(primary.private_key_service(), decoy.private_key_service());
+        assert_ne!(primary.seed_service(), decoy.seed_service());
+        assert_ne!(primary.accounts_file(), decoy.accounts_file());
+        assert!(is_seed_service(decoy.seed_service()));
+        assert!(!is_seed_service(decoy.private_key_service()));
+        assert!(!decoy.owns_service(primary.seed_service()));
+        assert!(!primary.owns_service(decoy.private_key_service()));
+        assert!(decoy.owns_service(decoy.seed_service()));
+    }
+}
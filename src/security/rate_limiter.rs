@@ -97,19 +97,37 @@ impl RateLimiter {
         limiter
     }
 
+    /// Create a RateLimiter that keeps its buckets in memory only
+    ///
+    /// Its limits are neither shared with other processes nor kept across
+    /// restarts, so it suits tests and other throwaway limiters.
+    pub fn in_memory(configs: HashMap<String, RateLimitConfig>) -> Self {
+        Self {
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+            configs: Arc::new(configs),
+            persistence_path: None,
+        }
+    }
+
     /// Get default path for rate limit persistence
     fn get_default_path() -> Option<PathBuf> {
+        Self::state_path("rate_limits.json")
+    }
+
+    /// Path of `file_name` in the directory rate limit state is persisted to
+    pub(crate) fn state_path(file_name: &str) -> Option<PathBuf> {
         #[cfg(test)]
         {
+            let _ = file_name;
             return None;
         }
 
         #[cfg(not(test))]
         {
             if let Ok(home_dir) = std::env::var("HOME") {
-                 Some(PathBuf::from(home_dir).join(".config").join("vaughan").join("rate_limits.json"))
+                 Some(PathBuf::from(home_dir).join(".config").join("vaughan").join(file_name))
             } else if let Ok(appdata_dir) = std::env::var("APPDATA") {
-                 Some(PathBuf::from(appdata_dir).join("Vaughan").join("rate_limits.json"))
+                 Some(PathBuf::from(appdata_dir).join("Vaughan").join(file_name))
             } else {
                  None
             }
@@ -124,6 +142,11 @@ impl RateLimiter {
              VaughanError::Configuration(crate::error::ConfigurationError::MissingKey { key: operation.to_string() })
         })?;
         
+        // Another process may have drawn on the persisted buckets since
+        if let Err(e) = self.load() {
+            tracing::warn!("Failed to reload rate limiter state: {}", e);
+        }
+
         // We need to initialize the bucket if it doesn't exist
         let mut buckets = self.buckets.lock().map_err(|_| {
             VaughanError::Security(SecurityError::RateLimitExceeded { 
@@ -0,0 +1,383 @@
+//! Recipient and contract screening before send
+//!
+//! Every address a transaction touches (the recipient and, for token
+//! transfers, the token contract) is checked against a denylist of known
+//! drainers, phishing contracts and scam addresses. The denylist starts from
+//! a small bundled list and is extended by update files, stored as
+//! `~/.vaughan/address-denylist.json`.
+//!
+//! A flagged send is not refused outright: the [`ScreeningReport`] travels
+//! with the confirmation data so the GUI can show the warning, and the send
+//! only proceeds once the user explicitly acknowledges that exact report.
+
+use crate::error::{Result, SecurityError, VaughanError};
+use crate::security::keystore::storage::{get_vaughan_dir, write_secure_file};
+use alloy::primitives::{address, Address};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Update file in the `.vaughan` directory
+pub const DENYLIST_UPDATE_FILE: &str = "address-denylist.json";
+
+/// Why an address is on the denylist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreatCategory {
+    /// Approval or permit drainer
+    Drainer,
+    /// Phishing contract or impersonation address
+    Phishing,
+    /// Other reported scam
+    Scam,
+    /// Burn address; funds sent here are unrecoverable
+    Burn,
+}
+
+impl ThreatCategory {
+    pub fn risk_level(self) -> RiskLevel {
+        match self {
+            Self::Drainer | Self::Phishing => RiskLevel::Critical,
+            Self::Scam | Self::Burn => RiskLevel::Warning,
+        }
+    }
+}
+
+impl fmt::Display for ThreatCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Drainer => "known drainer",
+            Self::Phishing => "phishing",
+            Self::Scam => "reported scam",
+            Self::Burn => "burn address",
+        })
+    }
+}
+
+/// How risky a transaction is, lowest first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RiskLevel {
+    #[default]
+    None,
+    Warning,
+    Critical,
+}
+
+/// One denylisted address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DenylistEntry {
+    pub address: Address,
+    pub category: ThreatCategory,
+    /// Short description shown to the user
+    pub label: String,
+    /// Where the report came from
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Contents of a denylist update file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DenylistUpdate {
+    /// Increases with every published list; older updates are ignored
+    pub revision: u64,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    pub entries: Vec<DenylistEntry>,
+}
+
+/// Addresses shipped with the wallet
+const BUNDLED: [(Address, ThreatCategory, &str); 2] = [
+    (
+        address!("0x0000000000000000000000000000000000000000"),
+        ThreatCategory::Burn,
+        "Zero address",
+    ),
+    (
+        address!("0x000000000000000000000000000000000000dEaD"),
+        ThreatCategory::Burn,
+        "Common burn address",
+    ),
+];
+
+/// Known malicious and burn addresses
+#[derive(Debug, Clone)]
+pub struct Denylist {
+    entries: HashMap<Address, DenylistEntry>,
+    /// Revision of the last applied update, 0 for the bundled list only
+    revision: u64,
+}
+
+impl Default for Denylist {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+impl Denylist {
+    /// The bundled list without any updates
+    pub fn bundled() -> Self {
+        let entries = BUNDLED
+            .iter()
+            .map(|&(address, category, label)| {
+                let entry = DenylistEntry {
+                    address,
+                    category,
+                    label: label.to_string(),
+                    source: Some("bundled".to_string()),
+                };
+                (address, entry)
+            })
+            .collect();
+        Self { entries, revision: 0 }
+    }
+
+    /// The bundled list plus the update file in the `.vaughan` directory
+    pub fn load_default() -> Self {
+        Self::load_from(&Self::update_path())
+    }
+
+    /// The bundled list plus the update file at `path`, if it exists and is valid
+    pub fn load_from(path: &Path) -> Self {
+        let mut list = Self::bundled();
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            match serde_json::from_str(&contents) {
+                Ok(update) => {
+                    list.apply(update);
+                }
+                Err(e) => tracing::warn!("Ignoring invalid denylist update {}: {}", path.display(), e),
+            }
+        }
+        list
+    }
+
+    /// Path of the update file
+    pub fn update_path() -> PathBuf {
+        get_vaughan_dir().join(DENYLIST_UPDATE_FILE)
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn lookup(&self, address: Address) -> Option<&DenylistEntry> {
+        self.entries.get(&address)
+    }
+
+    /// Merge an update; returns the number of entries added or changed
+    ///
+    /// Updates older than the one already applied are ignored.
+    pub fn apply(&mut self, update: DenylistUpdate) -> usize {
+        if update.revision < self.revision {
+            tracing::warn!(
+                "Ignoring denylist revision {} older than applied revision {}",
+                update.revision,
+                self.revision
+            );
+            return 0;
+        }
+        self.revision = update.revision;
+        let mut changed = 0;
+        for entry in update.entries {
+            if self.entries.get(&entry.address) != Some(&entry) {
+                self.entries.insert(entry.address, entry);
+                changed += 1;
+            }
+        }
+        tracing::info!("🛡️ Denylist revision {} applied ({} entries changed)", self.revision, changed);
+        changed
+    }
+
+    /// Apply an update received as JSON and keep it at `path` for later runs
+    pub fn install_update(&mut self, json: &str, path: &Path) -> Result<usize> {
+        let update: DenylistUpdate = serde_json::from_str(json).map_err(|e| SecurityError::DeserializationError {
+            message: format!("Invalid denylist update: {e}"),
+        })?;
+        if update.revision < self.revision {
+            return Err(VaughanError::ValidationError(format!(
+                "Denylist revision {} is older than the installed revision {}",
+                update.revision, self.revision
+            )));
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_secure_file(&path.to_string_lossy(), json)?;
+        Ok(self.apply(update))
+    }
+
+    /// Screen the addresses a transaction touches
+    pub fn screen(&self, recipient: Address, contract: Option<Address>) -> ScreeningReport {
+        let findings = [(recipient, AddressRole::Recipient)]
+            .into_iter()
+            .chain(contract.map(|c| (c, AddressRole::Contract)))
+            .filter_map(|(address, role)| {
+                self.lookup(address).map(|entry| ScreeningFinding {
+                    address,
+                    role,
+                    category: entry.category,
+                    label: entry.label.clone(),
+                })
+            })
+            .collect();
+        ScreeningReport { findings }
+    }
+}
+
+/// Which part of the transaction an address is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressRole {
+    Recipient,
+    /// Token or other contract the transaction calls
+    Contract,
+}
+
+/// A denylisted address found in a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreeningFinding {
+    pub address: Address,
+    pub role: AddressRole,
+    pub category: ThreatCategory,
+    pub label: String,
+}
+
+impl fmt::Display for ScreeningFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let role = match self.role {
+            AddressRole::Recipient => "Recipient",
+            AddressRole::Contract => "Contract",
+        };
+        write!(f, "{role} {} is a {}: {}", self.address, self.category, self.label)
+    }
+}
+
+/// Screening result attached to a transaction awaiting confirmation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScreeningReport {
+    pub findings: Vec<ScreeningFinding>,
+}
+
+impl ScreeningReport {
+    pub fn is_clear(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub fn risk_level(&self) -> RiskLevel {
+        self.findings
+            .iter()
+            .map(|f| f.category.risk_level())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Record the user's explicit acceptance of this report's findings
+    pub fn acknowledge(&self) -> RiskAcknowledgement {
+        tracing::warn!("⚠️ User acknowledged screening findings: {:?}", self.flagged());
+        RiskAcknowledgement {
+            flagged: self.flagged(),
+        }
+    }
+
+    /// Allow the send if nothing was flagged or these findings were acknowledged
+    ///
+    /// An acknowledgement only clears the report it was made for.
+    pub fn ensure_cleared(&self, acknowledgement: Option<&RiskAcknowledgement>) -> Result<()> {
+        if self.is_clear() || acknowledgement.is_some_and(|ack| ack.flagged == self.flagged()) {
+            return Ok(());
+        }
+        Err(SecurityError::UnacknowledgedRisk {
+            reasons: self.findings.iter().map(ToString::to_string).collect(),
+        }
+        .into())
+    }
+
+    fn flagged(&self) -> Vec<Address> {
+        let mut flagged: Vec<Address> = self.findings.iter().map(|f| f.address).collect();
+        flagged.sort();
+        flagged
+    }
+}
+
+/// Proof that the user accepted a specific screening report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskAcknowledgement {
+    flagged: Vec<Address>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(revision: u64, address: Address, category: ThreatCategory) -> DenylistUpdate {
+        DenylistUpdate {
+            revision,
+            updated_at: None,
+            entries: vec![DenylistEntry {
+                address,
+                category,
+                label: "Test entry".to_string(),
+                source: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_screening_flags_recipient_and_contract() {
+        let drainer = Address::repeat_byte(0xd1);
+        let token = Address::repeat_byte(0x70);
+        let mut list = Denylist::bundled();
+        assert_eq!(list.apply(update(3, drainer, ThreatCategory::Drainer)), 1);
+
+        assert!(list.screen(Address::repeat_byte(0x01), Some(token)).is_clear());
+        assert_eq!(list.screen(Address::ZERO, None).risk_level(), RiskLevel::Warning);
+
+        let report = list.screen(token, Some(drainer));
+        assert_eq!(report.risk_level(), RiskLevel::Critical);
+        assert_eq!(report.findings[0].role, AddressRole::Contract);
+
+        // Older revisions never replace newer data
+        assert_eq!(list.apply(update(2, token, ThreatCategory::Scam)), 0);
+        assert!(list.lookup(token).is_none());
+    }
+
+    #[test]
+    fn test_override_requires_matching_acknowledgement() {
+        let drainer = Address::repeat_byte(0xd1);
+        let mut list = Denylist::bundled();
+        list.apply(update(1, drainer, ThreatCategory::Phishing));
+
+        let report = list.screen(drainer, None);
+        assert!(report.ensure_cleared(None).is_err());
+
+        // Acknowledging a different report does not clear this one
+        let other = list.screen(Address::ZERO, None).acknowledge();
+        assert!(report.ensure_cleared(Some(&other)).is_err());
+        assert!(report.ensure_cleared(Some(&report.acknowledge())).is_ok());
+        assert!(ScreeningReport::default().ensure_cleared(None).is_ok());
+    }
+
+    #[test]
+    fn test_installed_update_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DENYLIST_UPDATE_FILE);
+        let drainer = Address::repeat_byte(0xd1);
+        let json = serde_json::to_string(&update(5, drainer, ThreatCategory::Drainer)).unwrap();
+
+        let mut list = Denylist::bundled();
+        assert_eq!(list.install_update(&json, &path).unwrap(), 1);
+
+        let reloaded = Denylist::load_from(&path);
+        assert_eq!(reloaded.revision(), 5);
+        assert_eq!(reloaded.lookup(drainer).map(|e| e.category), Some(ThreatCategory::Drainer));
+        assert!(reloaded.lookup(Address::ZERO).is_some());
+    }
+}
@@ -8,14 +8,23 @@
 //! - HD key derivation following standard paths
 //! - Multi-account derivation
 //! - Alloy-compatible wallet generation
+//! - Named path presets (MetaMask, Ledger Live, Legacy MEW, Trezor) and a
+//!   strict path validator with address preview
+//! - Optional BIP-39 passphrases, recorded on accounts with
+//!   [`BIP39_PASSPHRASE_TAG`] but never stored
 
 use crate::error::{Result, SecurityError};
+use crate::security::SecureAccount;
+use crate::wallet::hardware::{DerivationPathError, DerivationStandard};
 use alloy::{primitives::Address, signers::local::PrivateKeySigner};
 use bip32::{secp256k1::SecretKey, ExtendedPrivateKey};
 use bip39::Mnemonic;
 use k256::ecdsa::SigningKey;
 use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 use super::types::{
     DerivationPathConfig, DerivedAccount, MultiAccountDerivation, SecureSeed, SeedAnalysis, SeedStrength,
@@ -37,13 +46,51 @@ pub fn phrase_to_seed(phrase: &SecretString, passphrase: Option<&SecretString>)
     // Generate seed with optional passphrase
     let passphrase_str = passphrase.map(|p| p.expose_secret().as_str()).unwrap_or("");
 
-    let seed = mnemonic.to_seed(passphrase_str);
+    let mut seed_bytes = mnemonic.to_seed(passphrase_str);
 
-    // Convert to our secure seed format
-    let mut seed_bytes = [0u8; 64];
-    seed_bytes.copy_from_slice(&seed);
+    // Move into locked memory; from_bytes zeroizes the stack copy
+    SecureSeed::from_bytes(&mut seed_bytes)
+}
+
+// ============================================================================
+// BIP-39 Passphrase
+// ============================================================================
+
+/// Account tag marking a seed account derived with a BIP-39 passphrase
+///
+/// The passphrase itself is never stored, so signing asks for it again.
+pub const BIP39_PASSPHRASE_TAG: &str = "bip39-passphrase";
+
+/// Label for BIP-39 passphrase inputs
+pub const BIP39_PASSPHRASE_NOTICE: &str = "Optional BIP-39 passphrase (\"25th word\"). Every passphrase opens a \
+     different wallet from the same seed phrase, and a lost passphrase cannot be recovered.";
 
-    Ok(SecureSeed::from_bytes(seed_bytes))
+/// `passphrase` unless it is absent or empty, which BIP-39 treats alike
+pub fn effective_passphrase(passphrase: Option<&SecretString>) -> Option<&SecretString> {
+    passphrase.filter(|p| !p.expose_secret().is_empty())
+}
+
+pub fn uses_bip39_passphrase(account: &SecureAccount) -> bool {
+    account.tags.iter().any(|t| t == BIP39_PASSPHRASE_TAG)
+}
+
+/// Passphrase to derive `account`'s key with
+///
+/// Accounts without [`BIP39_PASSPHRASE_TAG`] ignore `passphrase`; tagged
+/// accounts fail without one.
+pub fn account_passphrase<'a>(
+    account: &SecureAccount,
+    passphrase: Option<&'a SecretString>,
+) -> Result<Option<&'a SecretString>> {
+    if !uses_bip39_passphrase(account) {
+        return Ok(None);
+    }
+    effective_passphrase(passphrase).map(Some).ok_or_else(|| {
+        SecurityError::Bip39PassphraseRequired {
+            account: account.name.clone(),
+        }
+        .into()
+    })
 }
 
 // ============================================================================
@@ -273,3 +320,247 @@ pub fn get_standard_derivation_paths() -> Vec<DerivationPathConfig> {
         DerivationPathConfig::legacy(),                // m/44'/60'/0'
     ]
 }
+
+// ============================================================================
+// Derivation Path Presets
+// ============================================================================
+
+/// Wallets whose account layout a seed account can follow
+///
+/// Importing a seed from one of these wallets with its preset yields the
+/// same addresses in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DerivationPreset {
+    /// `m/44'/60'/0'/0/{index}`
+    MetaMask,
+    /// `m/44'/60'/{index}'/0/0`
+    LedgerLive,
+    /// `m/44'/60'/0'/{index}`, also older Ledger Chrome app accounts
+    LegacyMew,
+    /// `m/44'/60'/0'/0/{index}`, as Trezor Suite derives Ethereum accounts
+    Trezor,
+}
+
+impl DerivationPreset {
+    pub const ALL: [DerivationPreset; 4] = [Self::MetaMask, Self::LedgerLive, Self::LegacyMew, Self::Trezor];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::MetaMask => "MetaMask",
+            Self::LedgerLive => "Ledger Live",
+            Self::LegacyMew => "Legacy (MEW)",
+            Self::Trezor => "Trezor",
+        }
+    }
+
+    pub fn standard(self) -> DerivationStandard {
+        match self {
+            Self::MetaMask | Self::Trezor => DerivationStandard::Bip44,
+            Self::LedgerLive => DerivationStandard::LedgerLive,
+            Self::LegacyMew => DerivationStandard::Legacy,
+        }
+    }
+
+    /// Path of the account at `index`
+    pub fn path(self, index: u32) -> String {
+        self.standard().path_for_index(index)
+    }
+}
+
+impl From<DerivationPreset> for DerivationStandard {
+    fn from(preset: DerivationPreset) -> Self {
+        preset.standard()
+    }
+}
+
+impl fmt::Display for DerivationPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// ============================================================================
+// Path Validation
+// ============================================================================
+
+/// Deepest path accepted
+pub const MAX_PATH_DEPTH: usize = 10;
+
+/// Indices at or above this are hardened
+const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// One level of a derivation path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathComponent {
+    pub index: u32,
+    pub hardened: bool,
+}
+
+/// An Ethereum derivation path that passed strict validation
+///
+/// Stricter than [`crate::wallet::hardware::validate_derivation_path`]:
+/// components must be canonical decimals below 2^31 with at most one
+/// hardened marker, and the account level (third) must be hardened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedPath {
+    components: Vec<PathComponent>,
+}
+
+impl ValidatedPath {
+    pub fn parse(path: &str) -> std::result::Result<Self, DerivationPathError> {
+        let rest = path.strip_prefix("m/").ok_or(DerivationPathError::InvalidPrefix)?;
+        let parts: Vec<&str> = rest.split('/').collect();
+        if parts.len() < 2 {
+            return Err(DerivationPathError::TooShort);
+        }
+        if parts.len() > MAX_PATH_DEPTH {
+            return Err(DerivationPathError::TooLong);
+        }
+
+        let mut components = Vec::with_capacity(parts.len());
+        for part in &parts {
+            let (digits, hardened) = match part.strip_suffix('\'') {
+                Some(digits) => (digits, true),
+                None => (*part, false),
+            };
+            if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+                return Err(DerivationPathError::InvalidCharacter(c));
+            }
+            if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) {
+                return Err(DerivationPathError::InvalidComponent(part.to_string()));
+            }
+            let index = digits
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < HARDENED_OFFSET)
+                .ok_or_else(|| DerivationPathError::IndexOutOfRange(part.to_string()))?;
+            components.push(PathComponent { index, hardened });
+        }
+
+        let hardened = |index| PathComponent { index, hardened: true };
+        if components[0] != hardened(44) {
+            return Err(DerivationPathError::MissingPurpose);
+        }
+        if components[1] != hardened(60) {
+            return Err(DerivationPathError::MissingCoinType);
+        }
+        if components.get(2).is_some_and(|account| !account.hardened) {
+            return Err(DerivationPathError::NotHardened(parts[2].to_string()));
+        }
+        Ok(Self { components })
+    }
+
+    pub fn components(&self) -> &[PathComponent] {
+        &self.components
+    }
+}
+
+impl FromStr for ValidatedPath {
+    type Err = DerivationPathError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for ValidatedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+        for component in &self.components {
+            write!(f, "/{}", component.index)?;
+            if component.hardened {
+                f.write_str("'")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Address `phrase` yields at `path`, to show before the account is created
+pub fn preview_address(phrase: &SecretString, passphrase: Option<&SecretString>, path: &str) -> Result<Address> {
+    let path = ValidatedPath::parse(path).map_err(|e| SecurityError::KeyDerivationError {
+        message: format!("Invalid derivation path '{path}': {e}"),
+    })?;
+    Ok(derive_wallet_from_seed(phrase, passphrase, Some(&path.to_string()))?.address())
+}
+
+/// The first `count` addresses of `preset`, with their paths
+pub fn preview_preset(
+    phrase: &SecretString,
+    passphrase: Option<&SecretString>,
+    preset: DerivationPreset,
+    count: u32,
+) -> Result<Vec<(String, Address)>> {
+    (0..count)
+        .map(|index| {
+            let path = preset.path(index);
+            preview_address(phrase, passphrase, &path).map(|address| (path, address))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    #[test]
+    fn test_strict_path_validation() {
+        for preset in DerivationPreset::ALL {
+            assert!(ValidatedPath::parse(&preset.path(7)).is_ok(), "{preset}");
+        }
+        let path = ValidatedPath::parse("m/44'/60'/0'/0/5").unwrap();
+        assert_eq!(path.to_string(), "m/44'/60'/0'/0/5");
+        assert_eq!(
+            path.components()[4],
+            PathComponent {
+                index: 5,
+                hardened: false
+            }
+        );
+
+        let rejected = [
+            ("m/44'/60'/0'/0/0''", DerivationPathError::InvalidCharacter('\'')),
+            ("m/44'/60'//0", DerivationPathError::InvalidComponent(String::new())),
+            (
+                "m/44'/60'/0'/00",
+                DerivationPathError::InvalidComponent("00".to_string()),
+            ),
+            (
+                "m/44'/60'/0'/2147483648",
+                DerivationPathError::IndexOutOfRange("2147483648".to_string()),
+            ),
+            ("m/44'/60'/0/0/0", DerivationPathError::NotHardened("0".to_string())),
+            ("m/44'/60'/0'/0/0 ", DerivationPathError::InvalidCharacter(' ')),
+            ("m/44'/61'/0'", DerivationPathError::MissingCoinType),
+        ];
+        for (path, error) in rejected {
+            assert_eq!(ValidatedPath::parse(path), Err(error), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_preview_matches_known_addresses() {
+        let phrase = SecretString::new("test test test test test test test test test test test junk".into());
+        let addresses = preview_preset(&phrase, None, DerivationPreset::MetaMask, 2).unwrap();
+        assert_eq!(
+            addresses,
+            [
+                (
+                    "m/44'/60'/0'/0/0".to_string(),
+                    address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+                ),
+                (
+                    "m/44'/60'/0'/0/1".to_string(),
+                    address!("70997970C51812dc3A010C7d01b50e0d17dc79C8")
+                ),
+            ]
+        );
+        // Ledger Live's first account shares MetaMask's path
+        assert_eq!(
+            preview_address(&phrase, None, &DerivationPreset::LedgerLive.path(0)).unwrap(),
+            addresses[0].1
+        );
+        assert!(preview_address(&phrase, None, "m/44'/60'/0'/0/x").is_err());
+    }
+}
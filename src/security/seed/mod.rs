@@ -36,6 +36,7 @@ use aes_gcm::{aead::Aead, Aes256Gcm, Key, KeyInit, Nonce};
 use bip32::{secp256k1::SecretKey, ExtendedPrivateKey};
 use bip39::Mnemonic;
 use secrecy::{ExposeSecret, SecretString};
+use zeroize::Zeroizing;
 
 // ============================================================================
 // SecureSeedStorage - Secure storage with keychain integration
@@ -56,6 +57,14 @@ impl SecureSeedStorage {
         }
     }
 
+    /// Create seed storage for one security profile's seed service
+    pub fn for_profile(keychain: Box<dyn KeychainInterface>, profile: crate::security::SecurityProfile) -> Self {
+        Self {
+            keychain,
+            service_name: profile.seed_service().to_string(),
+        }
+    }
+
     /// Store encrypted seed phrase in keychain
     pub async fn store_encrypted_seed_phrase(
         &self,
@@ -63,6 +72,32 @@ impl SecureSeedStorage {
         seed_phrase: &SecretString,
         master_password: &SecretString,
     ) -> Result<KeyReference> {
+        let serialized_data = Self::encrypt_for_keychain(seed_phrase, master_password)?;
+
+        // Create key reference
+        let key_ref = KeyReference {
+            id: uuid::Uuid::new_v4().to_string(),
+            service: self.service_name.clone(),
+            account: format!("encrypted-seed-v2-{wallet_id}"),
+        };
+
+        // Store in keychain
+        self.keychain
+            .store(&key_ref, serialized_data)
+            .map_err(|e| SecurityError::KeychainError {
+                message: format!("Failed to store encrypted seed phrase: {e}"),
+            })?;
+
+        tracing::info!("Encrypted seed phrase stored securely for wallet: {}", wallet_id);
+
+        Ok(key_ref)
+    }
+
+    /// Encrypt a seed phrase into the serialized form kept in the keychain
+    ///
+    /// The result can be stored under any seed key reference and later read
+    /// back with [`Self::retrieve_encrypted_seed_phrase`].
+    pub fn encrypt_for_keychain(seed_phrase: &SecretString, master_password: &SecretString) -> Result<SecretString> {
         // Validate seed phrase before storing
         let _mnemonic = Mnemonic::parse(seed_phrase.expose_secret()).map_err(|e| SecurityError::InvalidSeedPhrase {
             reason: format!("Invalid BIP39 mnemonic: {e}"),
@@ -85,24 +120,7 @@ impl SecureSeedStorage {
             serde_json::to_string(&encrypted_data).map_err(|e| SecurityError::SerializationError {
                 message: format!("Failed to serialize encrypted data: {e}"),
             })?;
-
-        // Create key reference
-        let key_ref = KeyReference {
-            id: uuid::Uuid::new_v4().to_string(),
-            service: self.service_name.clone(),
-            account: format!("encrypted-seed-v2-{wallet_id}"),
-        };
-
-        // Store in keychain
-        self.keychain
-            .store(&key_ref, SecretString::new(serialized_data))
-            .map_err(|e| SecurityError::KeychainError {
-                message: format!("Failed to store encrypted seed phrase: {e}"),
-            })?;
-
-        tracing::info!("Encrypted seed phrase stored securely for wallet: {}", wallet_id);
-
-        Ok(key_ref)
+        Ok(SecretString::new(serialized_data))
     }
 
     /// Retrieve and decrypt seed phrase from keychain
@@ -148,6 +166,28 @@ impl SecureSeedStorage {
         Ok(seed_phrase)
     }
 
+    /// Check a re-entered seed phrase against the stored encrypted seed
+    ///
+    /// Lets a user confirm their paper backup without exporting anything.
+    /// Both phrases are normalized (case and whitespace), held only in
+    /// zeroizing buffers and compared in constant time. Only the outcome is
+    /// logged; neither phrase is ever returned or displayed.
+    pub async fn verify_backup(
+        &self,
+        key_ref: &KeyReference,
+        master_password: &SecretString,
+        entered_phrase: &SecretString,
+    ) -> Result<bool> {
+        let stored = self.retrieve_encrypted_seed_phrase(key_ref, master_password).await?;
+        let stored = Zeroizing::new(validation::preprocess_seed_phrase(stored.expose_secret()));
+        let entered = Zeroizing::new(validation::preprocess_seed_phrase(entered_phrase.expose_secret()));
+
+        let matches = zeroization::constant_time_eq(stored.as_bytes(), entered.as_bytes());
+        tracing::info!("Seed backup check for key {}: {}", key_ref.id, if matches { "match" } else { "mismatch" });
+
+        Ok(matches)
+    }
+
     /// Securely delete encrypted seed phrase from keychain
     pub async fn delete_encrypted_seed_phrase(&self, key_ref: &KeyReference) -> Result<()> {
         self.keychain
@@ -338,14 +378,22 @@ pub struct SeedManager {
     keychain: Box<dyn KeychainInterface>,
     #[allow(dead_code)] // Reserved for future keychain operations service identifier
     service_name: String,
+    /// Security profile whose seed service holds the encrypted seeds
+    profile: crate::security::SecurityProfile,
 }
 
 impl SeedManager {
     /// Create a new seed manager
     pub fn new(keychain: Box<dyn KeychainInterface>) -> Self {
+        Self::for_profile(keychain, crate::security::SecurityProfile::Primary)
+    }
+
+    /// Create a seed manager storing seeds in one security profile's seed service
+    pub fn for_profile(keychain: Box<dyn KeychainInterface>, profile: crate::security::SecurityProfile) -> Self {
         Self {
             keychain,
             service_name: "vaughan-wallet-seeds".to_string(),
+            profile,
         }
     }
 
@@ -412,6 +460,16 @@ impl SeedManager {
         validation::validate_seed_phrase_comprehensive(phrase, config)
     }
 
+    /// BIP-39 completions for a partly typed word, for import autocomplete
+    pub fn suggest_words(prefix: &str) -> Vec<String> {
+        validation::suggest_words(prefix)
+    }
+
+    /// Check one word of a phrase while it is entered, see [`validation::validate_word`]
+    pub fn validate_word(word: &str, position: usize) -> Option<WordSuggestion> {
+        validation::validate_word(word, position)
+    }
+
     /// Import seed phrase and validate it (legacy method)
     pub fn import_seed_phrase(&self, phrase: SecretString) -> Result<SecretString> {
         self.validate_seed_phrase(&phrase)?;
@@ -510,7 +568,7 @@ impl SeedManager {
         master_password: &SecretString,
     ) -> Result<KeyReference> {
         self.validate_seed_phrase(phrase)?;
-        let secure_storage = SecureSeedStorage::new(self.keychain.clone_box());
+        let secure_storage = SecureSeedStorage::for_profile(self.keychain.clone_box(), self.profile);
         secure_storage
             .store_encrypted_seed_phrase(wallet_id, phrase, master_password)
             .await
@@ -580,11 +638,12 @@ impl SeedManager {
         self.validate_seed_phrase(phrase)?;
 
         // Derive wallet from seed phrase
+        let passphrase = derivation::effective_passphrase(passphrase);
         let wallet = self.derive_wallet_from_seed(phrase, passphrase, None)?;
         let address = wallet.address();
 
-        // Store the seed phrase with encryption
-        let secure_storage = SecureSeedStorage::new(self.keychain.clone_box());
+        // Store the seed phrase with encryption; the passphrase is only recorded as a tag
+        let secure_storage = SecureSeedStorage::for_profile(self.keychain.clone_box(), self.profile);
         let key_ref = secure_storage
             .store_encrypted_seed_phrase(&wallet_name, phrase, master_password)
             .await?;
@@ -597,7 +656,9 @@ impl SeedManager {
             created_at: chrono::Utc::now(),
             is_hardware: false,
             derivation_path: Some("m/44'/60'/0'/0/0".to_string()),
-            tags: Vec::new(),
+            tags: passphrase
+                .map(|_| vec![derivation::BIP39_PASSPHRASE_TAG.to_string()])
+                .unwrap_or_default(),
             last_used: None,
             transaction_count: 0,
         };
@@ -623,9 +684,7 @@ impl SeedManager {
         let wallet = self.derive_wallet_from_seed(phrase, passphrase, Some(path))?;
         let address = wallet.address();
 
-        let secure_storage = SecureSeedStorage::new(Box::new(crate::security::keychain::OSKeychain::new(
-            "vaughan-wallet".to_string(),
-        )?));
+        let secure_storage = SecureSeedStorage::new(crate::security::create_keychain("vaughan-wallet")?);
 
         let key_ref = secure_storage
             .store_encrypted_seed_phrase(&wallet_name, phrase, master_password)
@@ -660,12 +719,25 @@ impl SeedManager {
         key_ref: &KeyReference,
         master_password: &SecretString,
     ) -> Result<SecretString> {
-        let secure_storage = SecureSeedStorage::new(self.keychain.clone_box());
+        let secure_storage = SecureSeedStorage::for_profile(self.keychain.clone_box(), self.profile);
         secure_storage
             .retrieve_encrypted_seed_phrase(key_ref, master_password)
             .await
     }
 
+    /// Check a re-entered seed phrase against the stored one without exporting it
+    pub async fn verify_backup(
+        &self,
+        key_ref: &KeyReference,
+        master_password: &SecretString,
+        entered_phrase: &SecretString,
+    ) -> Result<bool> {
+        let secure_storage = SecureSeedStorage::for_profile(self.keychain.clone_box(), self.profile);
+        secure_storage
+            .verify_backup(key_ref, master_password, entered_phrase)
+            .await
+    }
+
     /// Export seed phrase with comprehensive security verification
     pub async fn export_seed_phrase(
         &self,
@@ -674,7 +746,7 @@ impl SeedManager {
         options: ExportOptions,
         export_password: Option<&SecretString>,
     ) -> Result<ExportResult> {
-        let secure_storage = SecureSeedStorage::new(self.keychain.clone_box());
+        let secure_storage = SecureSeedStorage::for_profile(self.keychain.clone_box(), self.profile);
         secure_storage
             .export_seed_phrase(key_ref, master_password, options, export_password)
             .await
@@ -736,6 +808,48 @@ mod tests {
         assert!(seed_manager.validate_seed_phrase(&invalid_phrase).is_err());
     }
 
+    #[tokio::test]
+    async fn test_verify_backup_matches_without_export() {
+        let storage = SecureSeedStorage::new(Box::new(MockKeychain::new()));
+        let password = SecretString::new("correct horse battery".to_string());
+        let phrase = SecretString::new(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+        );
+        let key_ref = storage
+            .store_encrypted_seed_phrase("backup-check", &phrase, &password)
+            .await
+            .unwrap();
+
+        // Case and spacing differences from the paper copy are tolerated
+        let retyped = SecretString::new(
+            "  Abandon abandon abandon abandon abandon abandon\nabandon abandon abandon abandon abandon ABOUT ".to_string(),
+        );
+        assert!(storage.verify_backup(&key_ref, &password, &retyped).await.unwrap());
+
+        let wrong = SecretString::new(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon".to_string(),
+        );
+        assert!(!storage.verify_backup(&key_ref, &password, &wrong).await.unwrap());
+
+        let bad_password = SecretString::new("wrong".to_string());
+        assert!(storage.verify_backup(&key_ref, &bad_password, &phrase).await.is_err());
+    }
+
+    #[test]
+    fn test_per_word_suggestions() {
+        assert_eq!(SeedManager::suggest_words("abst"), vec!["abstract"]);
+        assert_eq!(SeedManager::suggest_words(" ZOO"), vec!["zoo"]);
+        assert!(SeedManager::suggest_words("").is_empty());
+        assert!(SeedManager::suggest_words("xyz").is_empty());
+        assert!(SeedManager::suggest_words("a").len() <= validation::MAX_WORD_COMPLETIONS);
+
+        assert!(SeedManager::validate_word("Abandon", 0).is_none());
+        let typo = SeedManager::validate_word("abandn", 4).unwrap();
+        assert_eq!(typo.position, 4);
+        assert!(typo.suggested_words.contains(&"abandon".to_string()));
+        assert!(SeedManager::validate_word("qqqqqqqq", 0).unwrap().suggested_words.is_empty());
+    }
+
     #[test]
     fn test_seed_strength_detection() {
         let phrase_12 = SecretString::new(
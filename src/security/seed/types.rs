@@ -4,9 +4,11 @@
 //! used throughout the seed management system.
 
 use crate::error::{Result, SecurityError};
-use secrecy::{ExposeSecret, Secret, SecretString};
+use crate::security::memory::SecretBuffer;
+use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use zeroize::Zeroize;
 
 use alloy::primitives::Address;
 
@@ -14,18 +16,24 @@ use alloy::primitives::Address;
 // SecureSeed - Wrapper with automatic zeroization
 // ============================================================================
 
-/// Secure wrapper for seed phrases with automatic zeroization
-#[derive(Clone)]
-pub struct SecureSeed(Secret<[u8; 64]>);
+/// Secure wrapper for seed phrases, held in locked memory and zeroized on drop
+pub struct SecureSeed(SecretBuffer);
 
 impl SecureSeed {
-    /// Create from raw seed bytes
-    pub fn from_bytes(seed_bytes: [u8; 64]) -> Self {
-        Self(Secret::new(seed_bytes))
+    /// Create from raw seed bytes, zeroizing the caller's copy
+    pub fn from_bytes(seed_bytes: &mut [u8; 64]) -> Result<Self> {
+        let buffer = SecretBuffer::from_slice(seed_bytes.as_slice());
+        seed_bytes.zeroize();
+        Ok(Self(buffer?))
     }
 
-    /// Expose the seed bytes (use with extreme caution)
-    pub fn expose_seed(&self) -> &[u8; 64] {
+    /// Copy into a new locked buffer
+    pub fn try_clone(&self) -> Result<Self> {
+        Ok(Self(self.0.try_clone()?))
+    }
+
+    /// Expose the 64 seed bytes (use with extreme caution)
+    pub fn expose_seed(&self) -> &[u8] {
         self.0.expose_secret()
     }
 }
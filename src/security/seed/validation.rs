@@ -173,6 +173,49 @@ pub fn find_word_suggestions(word: &str, wordlist: &HashSet<&'static str>, max_s
     suggestions
 }
 
+/// Completions offered for a partly typed word
+pub const MAX_WORD_COMPLETIONS: usize = 6;
+
+/// BIP-39 words starting with `prefix`, in wordlist order
+///
+/// Every word is unique within its first four letters, so a four-letter
+/// prefix has at most one completion.
+pub fn suggest_words(prefix: &str) -> Vec<String> {
+    let prefix = prefix.trim().to_lowercase();
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    bip39::Language::English
+        .word_list()
+        .iter()
+        .filter(|word| word.starts_with(&prefix))
+        .take(MAX_WORD_COMPLETIONS)
+        .map(|word| word.to_string())
+        .collect()
+}
+
+/// Check one word of a phrase as it is entered
+///
+/// `None` when `word` is in the BIP-39 wordlist. Otherwise the closest
+/// corrections for the word at `position` (zero-based), which may be empty.
+pub fn validate_word(word: &str, position: usize) -> Option<WordSuggestion> {
+    let normalized = word.trim().to_lowercase();
+    let wordlist = get_bip39_wordlist_set();
+    if wordlist.contains(normalized.as_str()) {
+        return None;
+    }
+    let suggested_words = find_word_suggestions(&normalized, &wordlist, 3);
+    let confidence = suggested_words
+        .first()
+        .map_or(0.0, |best| calculate_suggestion_confidence(&normalized, best));
+    Some(WordSuggestion {
+        position,
+        original_word: word.to_string(),
+        suggested_words,
+        confidence,
+    })
+}
+
 /// Calculate Levenshtein distance between two strings
 #[allow(clippy::needless_range_loop)]
 pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
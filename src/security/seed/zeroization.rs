@@ -38,6 +38,21 @@ pub fn zero_string(string: &mut String) {
     string.zeroize();
 }
 
+/// Compare two byte strings in time that depends only on their lengths
+///
+/// Every byte is visited even after a mismatch, so the comparison does not
+/// reveal how long a matching prefix was.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let len = a.len().max(b.len());
+    let mut diff = u8::from(a.len() != b.len());
+    for i in 0..len {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        diff |= x ^ y;
+    }
+    std::hint::black_box(diff) == 0
+}
+
 // ============================================================================
 // Secure Memory Guard
 // ============================================================================
@@ -101,6 +116,15 @@ mod tests {
         assert!(verify_zeroed(&array));
     }
 
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abandon about", b"abandon about"));
+        assert!(!constant_time_eq(b"abandon about", b"abandon abouT"));
+        assert!(!constant_time_eq(b"abandon", b"abandon about"));
+        assert!(!constant_time_eq(&[0u8; 256], &[0u8; 0]));
+        assert!(constant_time_eq(b"", b""));
+    }
+
     #[test]
     fn test_secure_guard() {
         let mut guarded = SecureGuard::new(vec![1u8, 2, 3, 4]);
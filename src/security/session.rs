@@ -12,6 +12,32 @@
 //!
 //! - **Requirement 2.5**: Auto-lock with configurable timeout periods
 //!
+//! # Events
+//!
+//! Subscribers receive a [`SessionEvent::LockWarning`] once per idle period,
+//! `warning_before` ahead of the lock, and [`SessionEvent::Locked`] after the
+//! lock callback has run. Recording activity re-arms the warning.
+//!
+//! # Lock levels
+//!
+//! A session is at one of three [`LockLevel`]s. `Unlocked` signs freely;
+//! `SigningLock` drops the password and keys but keeps balances and history
+//! viewable, so signing needs the password again; `FullLock` keeps nothing
+//! sensitive in memory. With `signing_lock_timeout` set, the auto-lock
+//! monitor steps an idle session down to `SigningLock` first and to
+//! `FullLock` after `auto_lock_timeout`.
+//!
+//! # Signing
+//!
+//! The session is also where signing keys live. [`SessionManager::unlock`]
+//! takes the master password once; [`SessionManager::with_signer`] derives the
+//! key for an address through the attached [`SigningKeySource`], caches it for
+//! `key_ttl` (and at most `key_max_uses` signatures), and hands a signer to a
+//! closure. Accounts created with a BIP-39 passphrase also need it, handed
+//! over once with [`SessionManager::set_bip39_passphrase`]. The password,
+//! passphrases and every cached key are zeroized on lock, on auto-lock and
+//! on expiry, so callers never hold a password or key cache.
+//!
 //! # Usage
 //!
 //! ```rust,ignore
@@ -24,13 +50,43 @@
 //! }).await;
 //! ```
 
+use crate::error::{Result, SecurityError, WalletError};
+use crate::security::key_cache::KeyCache;
+use crate::security::keystore::signer_from_key;
+use crate::security::memory::SecretBuffer;
+use alloy::primitives::Address;
+use alloy::signers::local::PrivateKeySigner;
 use chrono::{DateTime, Utc};
+use secrecy::SecretString;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tokio::time::Instant;
 use uuid::Uuid;
 
+/// How much of the wallet is locked, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum LockLevel {
+    #[default]
+    Unlocked,
+    /// Viewing allowed; signing requires the password
+    SigningLock,
+    /// Nothing sensitive in memory
+    FullLock,
+}
+
+impl LockLevel {
+    /// Whether balances and history may be shown
+    pub fn can_view(self) -> bool {
+        self != Self::FullLock
+    }
+
+    pub fn can_sign(self) -> bool {
+        self == Self::Unlocked
+    }
+}
+
 /// Session state for tracking activity
 #[derive(Debug, Clone)]
 pub struct SessionState {
@@ -40,8 +96,18 @@ pub struct SessionState {
     pub session_start: DateTime<Utc>,
     /// Unique session ID for correlation
     pub session_id: String,
-    /// Whether the session is active
+    /// Whether the session is active, i.e. not fully locked
     pub is_active: bool,
+    pub lock_level: LockLevel,
+    /// Whether the lock warning went out for the current idle period
+    pub warning_sent: bool,
+}
+
+impl SessionState {
+    fn set_level(&mut self, level: LockLevel) {
+        self.lock_level = level;
+        self.is_active = level.can_view();
+    }
 }
 
 impl Default for SessionState {
@@ -51,24 +117,84 @@ impl Default for SessionState {
             session_start: Utc::now(),
             session_id: Uuid::new_v4().to_string(),
             is_active: true,
+            lock_level: LockLevel::Unlocked,
+            warning_sent: false,
         }
     }
 }
 
+/// Idle events published by the auto-lock monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// The session locks in `remaining` unless activity is recorded
+    LockWarning { remaining: Duration },
+    /// The session stepped down to [`LockLevel::SigningLock`] after idling
+    SigningLocked,
+    /// The session timed out and the lock callback has run
+    Locked,
+}
+
+/// Default lifetime of a cached signing key
+pub const DEFAULT_KEY_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Where the session gets private keys from
+#[async_trait::async_trait]
+pub trait SigningKeySource: Send + Sync + std::fmt::Debug {
+    /// Private key bytes for `address`
+    ///
+    /// `password` is the session's master password and `passphrase` the
+    /// BIP-39 passphrase entered for `address` this session, if any.
+    async fn signing_key(
+        &self,
+        address: &Address,
+        password: Option<&SecretString>,
+        passphrase: Option<&SecretString>,
+    ) -> Result<SecretBuffer>;
+}
+
+/// Master password, BIP-39 passphrases and keys derived with them
+#[derive(Debug)]
+struct SigningState {
+    password: Option<SecretString>,
+    passphrases: HashMap<Address, SecretString>,
+    keys: KeyCache,
+}
+
+impl SigningState {
+    fn clear(&mut self) {
+        // SecretString zeroizes on drop; KeyCache zeroizes its SecretBuffers
+        self.password = None;
+        self.passphrases.clear();
+        self.keys.clear();
+    }
+}
+
 /// Session configuration
 #[derive(Debug, Clone)]
 pub struct SessionConfig {
     /// Auto-lock timeout duration (None = disabled)
     pub auto_lock_timeout: Option<Duration>,
+    /// Idle time before signing locks while viewing stays allowed (None = disabled)
+    pub signing_lock_timeout: Option<Duration>,
     /// Interval for checking auto-lock condition
     pub check_interval: Duration,
+    /// How long before the lock to publish a warning (None = no warning)
+    pub warning_before: Option<Duration>,
+    /// How long a derived signing key stays cached
+    pub key_ttl: Duration,
+    /// Signatures per cached key before it is derived again (None = unlimited)
+    pub key_max_uses: Option<u32>,
 }
 
 impl Default for SessionConfig {
     fn default() -> Self {
         Self {
             auto_lock_timeout: Some(Duration::from_secs(300)), // 5 minutes default
+            signing_lock_timeout: None,
             check_interval: Duration::from_secs(10),           // Check every 10 seconds
+            warning_before: Some(Duration::from_secs(30)),
+            key_ttl: DEFAULT_KEY_TTL,
+            key_max_uses: None,
         }
     }
 }
@@ -79,6 +205,8 @@ impl SessionConfig {
         Self {
             auto_lock_timeout: Some(timeout),
             check_interval: Duration::from_secs(10),
+            warning_before: Some(Duration::from_secs(30)),
+            ..Self::default()
         }
     }
 
@@ -87,6 +215,8 @@ impl SessionConfig {
         Self {
             auto_lock_timeout: None,
             check_interval: Duration::from_secs(60),
+            warning_before: None,
+            ..Self::default()
         }
     }
 }
@@ -106,9 +236,14 @@ pub struct SessionManager {
     /// Session state
     state: Arc<RwLock<SessionState>>,
     /// Configuration
-    config: SessionConfig,
+    config: RwLock<SessionConfig>,
     /// Whether the auto-lock monitor is running
     monitor_running: Arc<RwLock<bool>>,
+    /// Lock warning and lock notifications
+    events: broadcast::Sender<SessionEvent>,
+    /// Master password and cached signing keys
+    signing: Arc<RwLock<SigningState>>,
+    key_source: Option<Arc<dyn SigningKeySource>>,
 }
 
 impl SessionManager {
@@ -121,16 +256,34 @@ impl SessionManager {
             "🔐 Creating new session manager"
         );
 
+        let mut keys = KeyCache::new(config.key_ttl);
+        if let Some(max_uses) = config.key_max_uses {
+            keys = keys.with_max_uses(max_uses);
+        }
+
         Self {
             state: Arc::new(RwLock::new(SessionState {
                 session_id,
                 ..Default::default()
             })),
-            config,
+            config: RwLock::new(config),
             monitor_running: Arc::new(RwLock::new(false)),
+            events: broadcast::channel(16).0,
+            signing: Arc::new(RwLock::new(SigningState {
+                password: None,
+                passphrases: HashMap::new(),
+                keys,
+            })),
+            key_source: None,
         }
     }
 
+    /// Derive signing keys through `source`
+    pub fn with_key_source(mut self, source: Arc<dyn SigningKeySource>) -> Self {
+        self.key_source = Some(source);
+        self
+    }
+
     /// Create a new SessionManager with a specific timeout duration
     pub fn with_timeout(timeout: Duration) -> Self {
         Self::new(SessionConfig::with_timeout(timeout))
@@ -141,6 +294,15 @@ impl SessionManager {
         Self::new(SessionConfig::no_auto_lock())
     }
 
+    /// Replace the timeouts and signing key lifetime
+    ///
+    /// A running monitor keeps the timeouts it started with; stop it and
+    /// start a new one to enforce these. `key_max_uses` is left as it was.
+    pub async fn reconfigure(&self, config: SessionConfig) {
+        self.signing.write().await.keys.set_timeout(config.key_ttl);
+        *self.config.write().await = config;
+    }
+
     /// Record user activity - resets the inactivity timer
     ///
     /// Call this whenever the user performs an action that should
@@ -148,12 +310,18 @@ impl SessionManager {
     pub async fn record_activity(&self) {
         let mut state = self.state.write().await;
         state.last_activity = Instant::now();
+        state.warning_sent = false;
         tracing::trace!(
             session_id = %state.session_id,
             "📝 Activity recorded"
         );
     }
 
+    /// Subscribe to lock warnings and auto-lock notifications
+    pub fn subscribe(&self) -> broadcast::Receiver<SessionEvent> {
+        self.events.subscribe()
+    }
+
     /// Get the current session state
     pub async fn get_state(&self) -> SessionState {
         self.state.read().await.clone()
@@ -166,7 +334,8 @@ impl SessionManager {
 
     /// Check if the session has timed out
     pub async fn is_timed_out(&self) -> bool {
-        if let Some(timeout) = self.config.auto_lock_timeout {
+        let timeout = self.config.read().await.auto_lock_timeout;
+        if let Some(timeout) = timeout {
             let state = self.state.read().await;
             if !state.is_active {
                 return false; // Already inactive
@@ -179,7 +348,8 @@ impl SessionManager {
 
     /// Get time remaining until auto-lock (None if no timeout configured)
     pub async fn time_until_lock(&self) -> Option<Duration> {
-        if let Some(timeout) = self.config.auto_lock_timeout {
+        let timeout = self.config.read().await.auto_lock_timeout;
+        if let Some(timeout) = timeout {
             let state = self.state.read().await;
             let elapsed = state.last_activity.elapsed();
             if elapsed >= timeout {
@@ -192,10 +362,15 @@ impl SessionManager {
         }
     }
 
+    /// Current lock level
+    pub async fn lock_level(&self) -> LockLevel {
+        self.state.read().await.lock_level
+    }
+
     /// Deactivate the session (called when wallet is locked)
     pub async fn deactivate(&self) {
         let mut state = self.state.write().await;
-        state.is_active = false;
+        state.set_level(LockLevel::FullLock);
         tracing::info!(
             session_id = %state.session_id,
             "🔒 Session deactivated"
@@ -205,14 +380,104 @@ impl SessionManager {
     /// Reactivate the session (called when wallet is unlocked)
     pub async fn reactivate(&self) {
         let mut state = self.state.write().await;
-        state.is_active = true;
+        state.set_level(LockLevel::Unlocked);
         state.last_activity = Instant::now();
+        state.warning_sent = false;
         tracing::info!(
             session_id = %state.session_id,
             "🔓 Session reactivated"
         );
     }
 
+    /// Unlock the session with the master password
+    ///
+    /// The password is not checked here; callers verify it first, as
+    /// [`crate::wallet::Vaughan::unlock_signing`] does. It is kept, zeroized
+    /// on drop, until the session locks.
+    pub async fn unlock(&self, password: SecretString) {
+        self.signing.write().await.password = Some(password);
+        self.reactivate().await;
+    }
+
+    /// Lock the session, zeroizing the master password and all cached keys
+    pub async fn lock(&self) {
+        self.signing.write().await.clear();
+        self.deactivate().await;
+    }
+
+    /// Lock signing only, zeroizing the master password and all cached keys
+    ///
+    /// Balances and history stay viewable; [`Self::unlock`] restores signing.
+    /// Does nothing to a fully locked session.
+    pub async fn lock_signing(&self) {
+        self.signing.write().await.clear();
+        let mut state = self.state.write().await;
+        if state.lock_level == LockLevel::Unlocked {
+            state.set_level(LockLevel::SigningLock);
+            tracing::info!(session_id = %state.session_id, "🔏 Signing locked");
+        }
+    }
+
+    /// Whether the master password is held for signing
+    pub async fn has_master_password(&self) -> bool {
+        self.signing.read().await.password.is_some()
+    }
+
+    /// Keep the BIP-39 passphrase of `address` until the session locks
+    ///
+    /// A cached key for `address` is dropped so the next signature derives
+    /// with the new passphrase.
+    pub async fn set_bip39_passphrase(&self, address: Address, passphrase: SecretString) {
+        let mut signing = self.signing.write().await;
+        signing.keys.remove(&address);
+        signing.passphrases.insert(address, passphrase);
+    }
+
+    /// Whether a BIP-39 passphrase is held for `address`
+    pub async fn has_bip39_passphrase(&self, address: Address) -> bool {
+        self.signing.read().await.passphrases.contains_key(&address)
+    }
+
+    /// Number of signing keys currently cached
+    pub async fn cached_key_count(&self) -> usize {
+        self.signing.read().await.keys.len()
+    }
+
+    /// Run `f` with the signer for `address`
+    ///
+    /// Uses the cached key if it is still valid, otherwise derives it from the
+    /// key source with the master password and caches it. Signing counts as
+    /// activity. Fails while signing is locked.
+    pub async fn with_signer<R>(&self, address: Address, f: impl FnOnce(&PrivateKeySigner) -> R) -> Result<R> {
+        if !self.state.read().await.lock_level.can_sign() {
+            return Err(WalletError::WalletLocked.into());
+        }
+
+        let signer = {
+            let mut signing = self.signing.write().await;
+            let key = match signing.keys.get(&address) {
+                Some(key) => key,
+                None => {
+                    let source = self.key_source.as_ref().ok_or_else(|| SecurityError::KeystoreError {
+                        message: "No signing key source attached to the session".to_string(),
+                    })?;
+                    let key = source
+                        .signing_key(&address, signing.password.as_ref(), signing.passphrases.get(&address))
+                        .await?;
+                    signing.keys.insert(address, key.try_clone()?);
+                    // Count this signature against the use limit
+                    signing.keys.get(&address);
+                    tracing::debug!("🔑 Derived signing key for {} in session", address);
+                    key
+                }
+            };
+            signer_from_key(key.expose_secret(), &address)?
+        };
+
+        self.record_activity().await;
+        Ok(f(&signer))
+    }
+
     /// Start the auto-lock monitor background task
     ///
     /// This spawns a background task that periodically checks if the
@@ -243,8 +508,13 @@ impl SessionManager {
 
         let state = Arc::clone(&self.state);
         let monitor_running = Arc::clone(&self.monitor_running);
-        let check_interval = self.config.check_interval;
-        let timeout = self.config.auto_lock_timeout;
+        let config = self.config.read().await.clone();
+        let check_interval = config.check_interval;
+        let timeout = config.auto_lock_timeout;
+        let signing_timeout = config.signing_lock_timeout;
+        let warning_before = config.warning_before;
+        let events = self.events.clone();
+        let signing = Arc::clone(&self.signing);
 
         let session_id = self.state.read().await.session_id.clone();
         tracing::info!(
@@ -264,6 +534,23 @@ impl SessionManager {
                     break;
                 }
 
+                signing.write().await.keys.remove_expired();
+
+                // Step down to a signing lock first
+                if let Some(signing_timeout) = signing_timeout {
+                    let mut state_write = state.write().await;
+                    if state_write.lock_level == LockLevel::Unlocked
+                        && state_write.last_activity.elapsed() >= signing_timeout
+                    {
+                        state_write.set_level(LockLevel::SigningLock);
+                        drop(state_write);
+                        signing.write().await.clear();
+
+                        tracing::info!(session_id = %session_id, "🔏 Idle signing lock reached");
+                        let _ = events.send(SessionEvent::SigningLocked);
+                    }
+                }
+
                 // Check timeout if configured
                 if let Some(timeout_duration) = timeout {
                     let state_guard = state.read().await;
@@ -271,14 +558,23 @@ impl SessionManager {
                     // Only check if session is active
                     if state_guard.is_active {
                         let elapsed = state_guard.last_activity.elapsed();
-                        if elapsed >= timeout_duration {
+                        let remaining = timeout_duration.saturating_sub(elapsed);
+                        let warn = warning_before.is_some_and(|lead| remaining <= lead);
+                        if elapsed < timeout_duration && warn && !state_guard.warning_sent {
+                            drop(state_guard);
+                            state.write().await.warning_sent = true;
+
+                            tracing::info!(
+                                session_id = %session_id,
+                                remaining_secs = remaining.as_secs(),
+                                "⚠️ Auto-lock warning"
+                            );
+                            let _ = events.send(SessionEvent::LockWarning { remaining });
+                        } else if elapsed >= timeout_duration {
                             drop(state_guard);
 
                             // Mark session as inactive before calling callback
-                            {
-                                let mut state_write = state.write().await;
-                                state_write.is_active = false;
-                            }
+                            state.write().await.set_level(LockLevel::FullLock);
 
                             tracing::info!(
                                 session_id = %session_id,
@@ -286,8 +582,11 @@ impl SessionManager {
                                 "⏰ Auto-lock timeout reached, triggering lock"
                             );
 
+                            signing.write().await.clear();
+
                             // Call the timeout callback
                             on_timeout().await;
+                            let _ = events.send(SessionEvent::Locked);
                         }
                     }
                 }
@@ -418,6 +717,8 @@ mod tests {
         let session = SessionManager::new(SessionConfig {
             auto_lock_timeout: Some(Duration::from_millis(50)),
             check_interval: Duration::from_millis(20),
+            warning_before: None,
+            ..SessionConfig::default()
         });
 
         let lock_triggered = Arc::new(AtomicBool::new(false));
@@ -439,11 +740,35 @@ mod tests {
         assert!(!session.get_state().await.is_active);
     }
 
+    #[tokio::test]
+    async fn test_reconfigure_applies_to_next_monitor() {
+        let session = SessionManager::no_auto_lock();
+        assert!(!session.is_timed_out().await);
+
+        session
+            .reconfigure(SessionConfig {
+                auto_lock_timeout: Some(Duration::from_millis(50)),
+                check_interval: Duration::from_millis(20),
+                warning_before: None,
+                key_ttl: Duration::from_secs(1),
+                ..SessionConfig::default()
+            })
+            .await;
+        assert_eq!(session.signing.read().await.keys.timeout(), Duration::from_secs(1));
+
+        let _handle = session.start_auto_lock_monitor(|| async {}).await;
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        assert!(!session.get_state().await.is_active);
+    }
+
     #[tokio::test]
     async fn test_stop_monitor() {
         let session = SessionManager::new(SessionConfig {
             auto_lock_timeout: Some(Duration::from_secs(1)),
             check_interval: Duration::from_millis(50),
+            warning_before: None,
+            ..SessionConfig::default()
         });
 
         let _handle = session
@@ -459,6 +784,107 @@ mod tests {
 
         assert!(!session.is_monitor_running().await);
     }
+
+    #[tokio::test]
+    async fn test_lock_warning_precedes_lock() {
+        let session = SessionManager::new(SessionConfig {
+            auto_lock_timeout: Some(Duration::from_millis(100)),
+            check_interval: Duration::from_millis(10),
+            warning_before: Some(Duration::from_millis(60)),
+            ..SessionConfig::default()
+        });
+        let mut events = session.subscribe();
+        let _handle = session.start_auto_lock_monitor(|| async {}).await;
+
+        let wait = Duration::from_secs(1);
+        let warning = tokio::time::timeout(wait, events.recv()).await.unwrap().unwrap();
+        assert!(matches!(warning, SessionEvent::LockWarning { remaining } if remaining <= Duration::from_millis(60)));
+        assert!(session.get_state().await.warning_sent);
+        let locked = tokio::time::timeout(wait, events.recv()).await.unwrap().unwrap();
+        assert_eq!(locked, SessionEvent::Locked);
+    }
+
+    #[tokio::test]
+    async fn test_idle_session_steps_down_through_lock_levels() {
+        let session = SessionManager::new(SessionConfig {
+            auto_lock_timeout: Some(Duration::from_millis(150)),
+            signing_lock_timeout: Some(Duration::from_millis(40)),
+            check_interval: Duration::from_millis(10),
+            warning_before: None,
+            ..SessionConfig::default()
+        });
+        session.unlock(SecretString::new("master".to_string())).await;
+        let mut events = session.subscribe();
+        let _handle = session.start_auto_lock_monitor(|| async {}).await;
+
+        let wait = Duration::from_secs(1);
+        let event = tokio::time::timeout(wait, events.recv()).await.unwrap().unwrap();
+        assert_eq!(event, SessionEvent::SigningLocked);
+        let level = session.lock_level().await;
+        assert!(level.can_view() && !level.can_sign());
+        assert!(!session.has_master_password().await);
+        assert!(session.with_signer(Address::ZERO, |_| ()).await.is_err());
+
+        let event = tokio::time::timeout(wait, events.recv()).await.unwrap().unwrap();
+        assert_eq!(event, SessionEvent::Locked);
+        assert_eq!(session.lock_level().await, LockLevel::FullLock);
+
+        // A full lock is not loosened by a signing lock; the password unlocks everything
+        session.lock_signing().await;
+        assert_eq!(session.lock_level().await, LockLevel::FullLock);
+        session.unlock(SecretString::new("master".to_string())).await;
+        assert_eq!(session.lock_level().await, LockLevel::Unlocked);
+    }
+
+    /// Key source that requires a password and counts derivations
+    #[derive(Debug, Default)]
+    struct CountingKeySource {
+        derivations: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl SigningKeySource for CountingKeySource {
+        async fn signing_key(
+            &self,
+            _address: &Address,
+            password: Option<&SecretString>,
+            _passphrase: Option<&SecretString>,
+        ) -> Result<SecretBuffer> {
+            password.ok_or_else(|| SecurityError::KeystoreError {
+                message: "Password required".to_string(),
+            })?;
+            self.derivations.fetch_add(1, Ordering::SeqCst);
+            SecretBuffer::from_slice(&[0x42; 32])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_signer_caches_key_until_use_limit_and_lock() {
+        let source = Arc::new(CountingKeySource::default());
+        let session = SessionManager::new(SessionConfig {
+            key_max_uses: Some(2),
+            ..SessionConfig::no_auto_lock()
+        })
+        .with_key_source(source.clone());
+        let address = PrivateKeySigner::from_slice(&[0x42; 32]).unwrap().address();
+
+        // No master password yet
+        assert!(session.with_signer(address, |s| s.address()).await.is_err());
+
+        session.unlock(SecretString::new("master".to_string())).await;
+        for _ in 0..3 {
+            assert_eq!(session.with_signer(address, |s| s.address()).await.unwrap(), address);
+        }
+        assert_eq!(source.derivations.load(Ordering::SeqCst), 2);
+
+        // A key for another address is rejected
+        assert!(session.with_signer(Address::repeat_byte(1), |_| ()).await.is_err());
+
+        session.lock().await;
+        assert!(!session.has_master_password().await);
+        assert_eq!(session.cached_key_count().await, 0);
+        assert!(session.with_signer(address, |_| ()).await.is_err());
+    }
 }
 
 /// Property-based tests for auto-lock timeout
@@ -594,6 +1020,8 @@ mod property_tests {
                 let session = SessionManager::new(SessionConfig {
                     auto_lock_timeout: Some(Duration::from_millis(timeout_ms)),
                     check_interval: Duration::from_millis(10),
+                    warning_before: None,
+                    ..SessionConfig::default()
                 });
 
                 let triggered = Arc::new(AtomicBool::new(false));
@@ -632,6 +1060,8 @@ mod property_tests {
                 let session = SessionManager::new(SessionConfig {
                     auto_lock_timeout: Some(Duration::from_millis(timeout_ms)),
                     check_interval: Duration::from_millis(10),
+                    warning_before: None,
+                    ..SessionConfig::default()
                 });
 
                 let triggered = Arc::new(AtomicBool::new(false));
@@ -674,6 +1104,8 @@ mod property_tests {
                 let session = Arc::new(SessionManager::new(SessionConfig {
                     auto_lock_timeout: Some(Duration::from_millis(timeout_ms)),
                     check_interval: Duration::from_millis(10),
+                    warning_before: None,
+                    ..SessionConfig::default()
                 }));
 
                 let triggered = Arc::new(AtomicBool::new(false));
@@ -719,6 +1151,8 @@ mod property_tests {
                 let session = SessionManager::new(SessionConfig {
                     auto_lock_timeout: Some(Duration::from_millis(timeout_ms)),
                     check_interval: Duration::from_millis(10),
+                    warning_before: None,
+                    ..SessionConfig::default()
                 });
 
                 let was_active = session.get_state().await.is_active;
@@ -0,0 +1,192 @@
+//! Isolated keychain backend for tests
+//!
+//! Unit tests used to share one in-memory store keyed only by key id, and
+//! integration tests (which link the library without `cfg(test)`) went to the
+//! real OS keychain, so parallel test runs overwrote each other's keys.
+//!
+//! [`TestKeychain`] keeps keys in memory under a service name that is unique
+//! to the test process and the instance, e.g.
+//! `vaughan-test-4242-7.vaughan-wallet`. Clones share the namespace and the
+//! keys are removed when the last handle is dropped.
+//!
+//! Only built for unit tests and with the `test-utils` feature, which the
+//! integration tests enable through the crate's dev-dependency on itself.
+
+use secrecy::{ExposeSecret, SecretString};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard, PoisonError};
+
+use super::{KeyReference, KeychainInterface, SERVICE_NAME_PRIVATE_KEYS};
+use crate::error::{Result, SecurityError};
+
+/// Keys by namespaced service name, then key id
+type Store = HashMap<String, HashMap<String, String>>;
+
+static STORE: LazyLock<Mutex<Store>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+static NEXT_INSTANCE: AtomicU64 = AtomicU64::new(0);
+
+/// Service prefix unique to this test process
+pub fn process_prefix() -> String {
+    format!("vaughan-test-{}", std::process::id())
+}
+
+/// Namespaced name for `service` shared by everything in this process
+///
+/// Used by `OSKeychain` under `cfg(test)`, where separate instances of the
+/// same service must see each other's keys like the real keychain does.
+#[cfg(test)]
+pub(crate) fn process_service(service: &str) -> String {
+    format!("{}.{service}", process_prefix())
+}
+
+fn lock() -> MutexGuard<'static, Store> {
+    // A panicking test must not take every other test's keychain down with it
+    STORE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub(crate) fn store_key(service: &str, key_ref: &KeyReference, key: &SecretString) {
+    lock()
+        .entry(service.to_string())
+        .or_default()
+        .insert(key_ref.id.clone(), key.expose_secret().to_string());
+}
+
+pub(crate) fn retrieve_key(service: &str, key_ref: &KeyReference) -> Result<SecretString> {
+    lock()
+        .get(service)
+        .and_then(|keys| keys.get(&key_ref.id))
+        .map(|key| SecretString::new(key.clone()))
+        .ok_or_else(|| {
+            SecurityError::KeystoreError {
+                message: "Key not found".to_string(),
+            }
+            .into()
+        })
+}
+
+pub(crate) fn delete_key(service: &str, key_ref: &KeyReference) {
+    if let Some(keys) = lock().get_mut(service) {
+        keys.remove(&key_ref.id);
+    }
+}
+
+/// Removes a namespace's keys once the last handle is gone
+#[derive(Debug)]
+struct Namespace {
+    service: String,
+}
+
+impl Drop for Namespace {
+    fn drop(&mut self) {
+        lock().remove(&self.service);
+    }
+}
+
+/// In-memory keychain with a per-process, per-instance namespace
+#[derive(Debug, Clone)]
+pub struct TestKeychain {
+    namespace: Arc<Namespace>,
+}
+
+impl Default for TestKeychain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestKeychain {
+    /// Isolated keychain for the private key service
+    pub fn new() -> Self {
+        Self::with_service(SERVICE_NAME_PRIVATE_KEYS)
+    }
+
+    /// Isolated keychain for `service`
+    pub fn with_service(service: &str) -> Self {
+        let instance = NEXT_INSTANCE.fetch_add(1, Ordering::Relaxed);
+        Self {
+            namespace: Arc::new(Namespace {
+                service: format!("{}-{instance}.{service}", process_prefix()),
+            }),
+        }
+    }
+
+    /// Namespaced service name the keys are stored under
+    pub fn service_name(&self) -> &str {
+        &self.namespace.service
+    }
+
+    /// Number of keys currently stored
+    pub fn len(&self) -> usize {
+        lock().get(self.service_name()).map_or(0, HashMap::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl KeychainInterface for TestKeychain {
+    fn store(&self, key_ref: &KeyReference, key: SecretString) -> Result<()> {
+        store_key(self.service_name(), key_ref, &key);
+        Ok(())
+    }
+
+    fn retrieve(&self, key_ref: &KeyReference) -> Result<SecretString> {
+        retrieve_key(self.service_name(), key_ref)
+    }
+
+    fn delete(&self, key_ref: &KeyReference) -> Result<()> {
+        delete_key(self.service_name(), key_ref);
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn KeychainInterface> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_ref(id: &str) -> KeyReference {
+        KeyReference {
+            id: id.to_string(),
+            service: SERVICE_NAME_PRIVATE_KEYS.to_string(),
+            account: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_instances_are_isolated() {
+        let a = TestKeychain::new();
+        let b = TestKeychain::new();
+        assert_ne!(a.service_name(), b.service_name());
+        assert!(a.service_name().starts_with(&process_prefix()));
+
+        a.store(&key_ref("shared-id"), SecretString::new("a".into())).unwrap();
+        b.store(&key_ref("shared-id"), SecretString::new("b".into())).unwrap();
+        assert_eq!(a.retrieve(&key_ref("shared-id")).unwrap().expose_secret(), "a");
+        assert_eq!(b.retrieve(&key_ref("shared-id")).unwrap().expose_secret(), "b");
+
+        // Clones see the same keys
+        let clone = a.clone_box();
+        clone.delete(&key_ref("shared-id")).unwrap();
+        assert!(a.retrieve(&key_ref("shared-id")).is_err());
+    }
+
+    #[test]
+    fn test_keys_removed_on_drop() {
+        let keychain = TestKeychain::with_service("drop-test");
+        let service = keychain.service_name().to_string();
+        keychain.store(&key_ref("k"), SecretString::new("secret".into())).unwrap();
+
+        let clone = keychain.clone();
+        drop(keychain);
+        assert_eq!(clone.len(), 1);
+
+        drop(clone);
+        assert!(retrieve_key(&service, &key_ref("k")).is_err());
+    }
+}
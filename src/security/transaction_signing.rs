@@ -3,9 +3,10 @@
 //! Helper functions for decrypting seeds and deriving keys for transaction signing.
 
 use crate::error::Result;
-use crate::security::{KeyReference, SecureMemory, SecureSeedStorage};
+use crate::security::{KeyReference, SecretBuffer, SecureSeedStorage};
 use alloy::signers::local::PrivateKeySigner;
 use secrecy::SecretString;
+use zeroize::Zeroize;
 
 /// Decrypt a seed phrase with a password
 ///
@@ -18,30 +19,31 @@ pub async fn decrypt_seed_with_password(
     seed_storage.retrieve_encrypted_seed_phrase(key_ref, password).await
 }
 
-/// Derive a private key from a seed phrase
+/// Derive a private key from a seed phrase and optional BIP-39 passphrase
 ///
-/// Returns the private key bytes in SecureMemory (auto-zeroized on drop)
+/// Returns the private key bytes in a [`SecretBuffer`] (locked, zeroized on drop)
 pub fn derive_key_from_seed(
     keychain: Box<dyn crate::security::KeychainInterface>,
     seed_phrase: &SecretString,
+    passphrase: Option<&SecretString>,
     derivation_path: Option<&str>,
-) -> Result<SecureMemory> {
+) -> Result<SecretBuffer> {
     use crate::security::seed::SeedManager;
 
     // Create seed manager
     let seed_manager = SeedManager::new(keychain);
 
     // Derive wallet from seed
-    let wallet = seed_manager.derive_wallet_from_seed(seed_phrase, None, derivation_path)?;
+    let wallet = seed_manager.derive_wallet_from_seed(seed_phrase, passphrase, derivation_path)?;
 
     // Extract private key bytes
-    let private_key_bytes = wallet.to_bytes();
+    let mut private_key_bytes = wallet.to_bytes();
 
-    // Store in secure memory
-    let mut secure_key = SecureMemory::new(32)?; // Private keys are always 32 bytes
-    secure_key.as_mut_slice().copy_from_slice(private_key_bytes.as_slice());
+    // Store in secure memory and wipe the stack copy
+    let secure_key = SecretBuffer::from_slice(private_key_bytes.as_slice());
+    private_key_bytes.0.zeroize();
 
-    Ok(secure_key)
+    secure_key
 }
 
 /// Derive a wallet (PrivateKeySigner) from a seed phrase
@@ -70,13 +72,13 @@ mod tests {
         );
 
         // Create keychain
-        let keychain = Box::new(crate::security::keychain::OSKeychain::new("test".to_string()).unwrap());
+        let keychain = Box::new(crate::security::TestKeychain::new());
 
         // Derive key
-        let mut secure_key = derive_key_from_seed(keychain, &seed_phrase, None).unwrap();
+        let secure_key = derive_key_from_seed(keychain, &seed_phrase, None, None).unwrap();
 
         // Should have 32 bytes
-        assert_eq!(secure_key.as_mut_slice().len(), 32);
+        assert_eq!(secure_key.len(), 32);
     }
 
     #[test]
@@ -87,7 +89,7 @@ mod tests {
         );
 
         // Create keychain
-        let keychain = Box::new(crate::security::keychain::OSKeychain::new("test".to_string()).unwrap());
+        let keychain = Box::new(crate::security::TestKeychain::new());
 
         // Derive wallet
         let wallet = derive_wallet_from_seed(keychain, &seed_phrase, None).unwrap();
@@ -103,8 +105,8 @@ mod tests {
         );
 
         // Create keychains
-        let keychain1 = Box::new(crate::security::keychain::OSKeychain::new("test".to_string()).unwrap());
-        let keychain2 = Box::new(crate::security::keychain::OSKeychain::new("test".to_string()).unwrap());
+        let keychain1 = Box::new(crate::security::TestKeychain::new());
+        let keychain2 = Box::new(crate::security::TestKeychain::new());
 
         // Derive with standard Ethereum path
         let wallet1 = derive_wallet_from_seed(keychain1, &seed_phrase, Some("m/44'/60'/0'/0/0")).unwrap();
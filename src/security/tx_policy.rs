@@ -0,0 +1,442 @@
+//! Transaction policy rules
+//!
+//! Institutional users restrict when sizeable transactions may be signed,
+//! e.g. "only above $10,000 between 9:00 and 18:00 local time on weekdays".
+//! A [`TransactionPolicy`] is a list of such rules, stored as
+//! `~/.vaughan/transaction-policy.json`; an empty policy allows everything.
+//!
+//! Windows are evaluated in a configurable time zone: the system's local
+//! zone (following its daylight saving rules), UTC or a fixed offset.
+//! Windows may cross midnight. A rule can allow itself to be overridden when
+//! the transaction is confirmed on a hardware wallet, so an out-of-hours
+//! payment needs a physical approval rather than being impossible.
+//!
+//! ERC-20 `transfer`, `transferFrom` and `approve` calls are decoded into
+//! [`PolicyContext::token_amount`]. Token amounts cannot be compared with a
+//! wei limit, so [`ValueThreshold::NativeWei`] rules treat every token call
+//! as above their limit, the same way unpriced transactions count as above a
+//! [`ValueThreshold::Usd`] limit.
+//!
+//! A policy file that exists but cannot be parsed is an error rather than an
+//! empty policy: the wallet refuses to sign until the file is fixed or a new
+//! policy is set.
+
+use crate::error::{Result, SecurityError, VaughanError};
+use crate::security::keystore::storage::{get_vaughan_dir, write_secure_file};
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Policy file in the `.vaughan` directory
+pub const TRANSACTION_POLICY_FILE: &str = "transaction-policy.json";
+
+sol! {
+    interface IERC20Policy {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+}
+
+/// Time zone a window is evaluated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyTimeZone {
+    /// The system time zone, including daylight saving changes
+    #[default]
+    Local,
+    Utc,
+    /// Fixed offset east of UTC in minutes, e.g. `60` for CET
+    Offset(i32),
+}
+
+impl PolicyTimeZone {
+    fn local_time(self, at: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Local => at.with_timezone(&Local).naive_local(),
+            Self::Utc => at.naive_utc(),
+            Self::Offset(minutes) => match FixedOffset::east_opt(minutes * 60) {
+                Some(offset) => at.with_timezone(&offset).naive_local(),
+                None => at.naive_utc(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for PolicyTimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => f.write_str("local time"),
+            Self::Utc => f.write_str("UTC"),
+            Self::Offset(minutes) => {
+                let sign = if *minutes < 0 { '-' } else { '+' };
+                write!(f, "UTC{sign}{:02}:{:02}", minutes.abs() / 60, minutes.abs() % 60)
+            }
+        }
+    }
+}
+
+/// Daily time window on selected weekdays
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Start of the window, inclusive
+    pub start: NaiveTime,
+    /// End of the window, exclusive; earlier than `start` for overnight windows
+    pub end: NaiveTime,
+    /// Days the window opens on; empty means every day
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    #[serde(default)]
+    pub timezone: PolicyTimeZone,
+}
+
+impl TimeWindow {
+    /// Whether `at` falls inside the window
+    ///
+    /// An overnight window belongs to the day it opens on, so a Friday
+    /// 22:00–02:00 window includes early Saturday.
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let local = self.timezone.local_time(at);
+        let (day, time) = (local.weekday(), local.time());
+        if self.start <= self.end {
+            self.opens_on(day) && time >= self.start && time < self.end
+        } else {
+            (time >= self.start && self.opens_on(day)) || (time < self.end && self.opens_on(day.pred()))
+        }
+    }
+
+    fn opens_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}–{}", self.start.format("%H:%M"), self.end.format("%H:%M"))?;
+        if !self.days.is_empty() {
+            let days: Vec<String> = self.days.iter().map(ToString::to_string).collect();
+            write!(f, " {}", days.join(","))?;
+        }
+        write!(f, " {}", self.timezone)
+    }
+}
+
+/// Which transactions a rule applies to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueThreshold {
+    Any,
+    /// Native value above this many wei; token transfers and approvals always count as above
+    NativeWei(U256),
+    /// USD value above this amount; unpriced transactions count as above
+    Usd(f64),
+}
+
+impl ValueThreshold {
+    fn applies(&self, context: &PolicyContext) -> bool {
+        match self {
+            Self::Any => true,
+            Self::NativeWei(limit) => context.token_amount.is_some() || context.value_wei > *limit,
+            Self::Usd(limit) => context.value_usd.is_none_or(|usd| usd > *limit),
+        }
+    }
+}
+
+/// Transactions above a threshold may only be signed inside a window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperatingHoursRule {
+    pub name: String,
+    pub threshold: ValueThreshold,
+    pub window: TimeWindow,
+    /// Allow signing outside the window when confirmed on a hardware wallet
+    #[serde(default)]
+    pub hardware_override: bool,
+}
+
+/// ERC-20 amount a transaction transfers or approves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenAmount {
+    pub token: Address,
+    /// Amount in the token's smallest unit
+    pub amount: U256,
+}
+
+/// What the policy engine knows about a transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PolicyContext {
+    pub value_wei: U256,
+    pub value_usd: Option<f64>,
+    pub token_amount: Option<TokenAmount>,
+}
+
+impl PolicyContext {
+    /// Native value and decoded token amount of `tx`, unpriced
+    pub fn from_transaction(tx: &TransactionRequest) -> Self {
+        Self {
+            value_wei: tx.value.unwrap_or_default(),
+            value_usd: None,
+            token_amount: token_amount(tx),
+        }
+    }
+
+    pub fn with_value_usd(mut self, usd: f64) -> Self {
+        self.value_usd = Some(usd);
+        self
+    }
+}
+
+/// Amount of an ERC-20 `transfer`, `transferFrom` or `approve` call
+fn token_amount(tx: &TransactionRequest) -> Option<TokenAmount> {
+    let token = tx.to?.to().copied()?;
+    let input = tx.input.input()?;
+    let amount = match input.get(..4)?.try_into().ok()? {
+        IERC20Policy::transferCall::SELECTOR => IERC20Policy::transferCall::abi_decode(input).ok()?.amount,
+        IERC20Policy::transferFromCall::SELECTOR => IERC20Policy::transferFromCall::abi_decode(input).ok()?.amount,
+        IERC20Policy::approveCall::SELECTOR => IERC20Policy::approveCall::abi_decode(input).ok()?.amount,
+        _ => return None,
+    };
+    Some(TokenAmount { token, amount })
+}
+
+/// A rule the transaction breaks
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyViolation {
+    pub rule: String,
+    pub window: TimeWindow,
+    pub hardware_override: bool,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: only allowed {}", self.rule, self.window)?;
+        if self.hardware_override {
+            f.write_str(" (confirm on a hardware wallet to override)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Rules checked before signing
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransactionPolicy {
+    #[serde(default)]
+    pub operating_hours: Vec<OperatingHoursRule>,
+}
+
+impl TransactionPolicy {
+    /// Policy from the default file; empty when missing
+    pub fn load_default() -> Result<Self> {
+        Self::load_from(&Self::default_path())
+    }
+
+    /// Policy from `path`; empty when missing, an error when it cannot be read or parsed
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let invalid = |reason: String| {
+            tracing::error!("❌ Invalid transaction policy {}, signing is refused: {}", path.display(), reason);
+            VaughanError::Security(SecurityError::InvalidTransactionPolicy {
+                path: path.display().to_string(),
+                reason,
+            })
+        };
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| invalid(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(invalid(e.to_string())),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| SecurityError::SerializationError {
+            message: format!("Failed to serialize transaction policy: {e}"),
+        })?;
+        write_secure_file(&path.to_string_lossy(), &json)
+    }
+
+    pub fn default_path() -> PathBuf {
+        get_vaughan_dir().join(TRANSACTION_POLICY_FILE)
+    }
+
+    /// Rules `context` breaks at `now`
+    pub fn evaluate(&self, context: &PolicyContext, now: DateTime<Utc>) -> Vec<PolicyViolation> {
+        self.operating_hours
+            .iter()
+            .filter(|rule| rule.threshold.applies(context) && !rule.window.contains(now))
+            .map(|rule| PolicyViolation {
+                rule: rule.name.clone(),
+                window: rule.window.clone(),
+                hardware_override: rule.hardware_override,
+            })
+            .collect()
+    }
+
+    /// Fail unless the transaction is allowed at `now`
+    ///
+    /// With `hardware_confirmed`, violations of rules that allow a hardware
+    /// override are accepted.
+    pub fn check(&self, context: &PolicyContext, now: DateTime<Utc>, hardware_confirmed: bool) -> Result<()> {
+        let violations = self.evaluate(context, now);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        if hardware_confirmed && violations.iter().all(|v| v.hardware_override) {
+            tracing::warn!(
+                "⏰ Transaction outside policy hours allowed by hardware confirmation: {:?}",
+                violations.iter().map(|v| &v.rule).collect::<Vec<_>>()
+            );
+            return Ok(());
+        }
+        Err(VaughanError::Security(SecurityError::PolicyViolation {
+            reasons: violations.iter().map(ToString::to_string).collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn hm(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn utc(day: u32, h: u32, m: u32) -> DateTime<Utc> {
+        // January 2024: the 1st is a Monday
+        Utc.with_ymd_and_hms(2024, 1, day, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_windows_with_offsets_and_midnight() {
+        let office = TimeWindow {
+            start: hm(9, 0),
+            end: hm(18, 0),
+            days: vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+            timezone: PolicyTimeZone::Offset(120),
+        };
+        // 07:30 UTC is 09:30 at UTC+2
+        assert!(office.contains(utc(1, 7, 30)));
+        assert!(!office.contains(utc(1, 16, 0)));
+        // Saturday
+        assert!(!office.contains(utc(6, 10, 0)));
+
+        let night = TimeWindow {
+            start: hm(22, 0),
+            end: hm(2, 0),
+            days: vec![Weekday::Fri],
+            timezone: PolicyTimeZone::Utc,
+        };
+        assert!(night.contains(utc(5, 23, 0)));
+        assert!(night.contains(utc(6, 1, 0)));
+        assert!(!night.contains(utc(6, 23, 0)));
+        assert_eq!(night.to_string(), "22:00–02:00 Fri UTC");
+    }
+
+    #[test]
+    fn test_threshold_and_hardware_override() {
+        let policy = TransactionPolicy {
+            operating_hours: vec![OperatingHoursRule {
+                name: "Treasury hours".to_string(),
+                threshold: ValueThreshold::Usd(10_000.0),
+                window: TimeWindow {
+                    start: hm(9, 0),
+                    end: hm(18, 0),
+                    days: Vec::new(),
+                    timezone: PolicyTimeZone::Utc,
+                },
+                hardware_override: true,
+            }],
+        };
+        let evening = utc(2, 20, 0);
+        let small = PolicyContext::default().with_value_usd(500.0);
+        let large = PolicyContext::default().with_value_usd(50_000.0);
+
+        assert!(policy.check(&small, evening, false).is_ok());
+        assert!(policy.check(&large, utc(2, 10, 0), false).is_ok());
+        assert!(matches!(
+            policy.check(&large, evening, false),
+            Err(VaughanError::Security(SecurityError::PolicyViolation { .. }))
+        ));
+        assert!(policy.check(&large, evening, true).is_ok());
+        // Unpriced transactions are treated as above the threshold
+        assert_eq!(policy.evaluate(&PolicyContext::default(), evening).len(), 1);
+
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(serde_json::from_str::<TransactionPolicy>(&json).unwrap(), policy);
+    }
+
+    #[test]
+    fn test_invalid_policy_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TRANSACTION_POLICY_FILE);
+        assert_eq!(TransactionPolicy::load_from(&path).unwrap(), TransactionPolicy::default());
+
+        std::fs::write(&path, r#"{ "operating_hours": [ { "name": "Office" } ] }"#).unwrap();
+        assert!(matches!(
+            TransactionPolicy::load_from(&path),
+            Err(VaughanError::Security(SecurityError::InvalidTransactionPolicy { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_token_calls_count_above_native_limits() {
+        use alloy::primitives::Bytes;
+
+        let policy = TransactionPolicy {
+            operating_hours: vec![OperatingHoursRule {
+                name: "Office hours".to_string(),
+                threshold: ValueThreshold::NativeWei(U256::from(1_000)),
+                window: TimeWindow {
+                    start: hm(9, 0),
+                    end: hm(18, 0),
+                    days: Vec::new(),
+                    timezone: PolicyTimeZone::Utc,
+                },
+                hardware_override: false,
+            }],
+        };
+        let evening = utc(2, 20, 0);
+        let token = Address::repeat_byte(0x70);
+        let call = |input: Vec<u8>| {
+            TransactionRequest::default()
+                .to(token)
+                .input(Bytes::from(input).into())
+        };
+
+        let transfer = call(
+            IERC20Policy::transferCall {
+                to: Address::repeat_byte(1),
+                amount: U256::from(5),
+            }
+            .abi_encode(),
+        );
+        let context = PolicyContext::from_transaction(&transfer);
+        assert_eq!(
+            context.token_amount,
+            Some(TokenAmount {
+                token,
+                amount: U256::from(5)
+            })
+        );
+        assert!(policy.check(&context, evening, false).is_err());
+
+        let approve = call(
+            IERC20Policy::approveCall {
+                spender: Address::repeat_byte(2),
+                amount: U256::MAX,
+            }
+            .abi_encode(),
+        );
+        assert!(policy.check(&PolicyContext::from_transaction(&approve), evening, false).is_err());
+
+        // Small native transfers and other calls are below the limit
+        let native = TransactionRequest::default().to(token).value(U256::from(10));
+        assert!(policy.check(&PolicyContext::from_transaction(&native), evening, false).is_ok());
+        let other = call(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(PolicyContext::from_transaction(&other).token_amount, None);
+    }
+}
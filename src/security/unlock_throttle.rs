@@ -0,0 +1,282 @@
+//! Unlock Attempt Throttling
+//!
+//! Failed master password attempts are counted in `unlock-attempts.json`, so
+//! restarting the app doesn't reset the back-off. The first few failures are
+//! free; after that each failure doubles the wait before the next attempt, and
+//! at the lockout threshold unlocking is refused for the lockout period.
+//!
+//! Recovery is by waiting only: nothing is wiped, however many attempts fail.
+//! Waits are measured from the wall-clock time of the last failure, so setting
+//! the clock back makes them longer, not shorter. An unreadable counter starts
+//! over, with an audit log entry, rather than locking the user out for good.
+
+use crate::error::{Result, WalletError};
+use crate::security::keystore::storage;
+use crate::telemetry::audit::{self, AuditEvent};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Attempt counter file in the `.vaughan` directory
+pub const UNLOCK_ATTEMPTS_FILE: &str = "unlock-attempts.json";
+
+/// When failed unlocks start to cost time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnlockPolicy {
+    /// Failures allowed before any wait
+    pub free_attempts: u32,
+    /// Wait after the first failure past the free ones, doubled per failure
+    pub base_delay: Duration,
+    /// Longest back-off wait below the lockout threshold
+    pub max_delay: Duration,
+    /// Failures at which unlocking is locked out
+    pub lockout_threshold: u32,
+    /// Wait after every failure from the threshold on
+    pub lockout_duration: Duration,
+}
+
+impl Default for UnlockPolicy {
+    fn default() -> Self {
+        Self {
+            free_attempts: 3,
+            base_delay: Duration::from_secs(5),
+            max_delay: Duration::from_secs(5 * 60),
+            lockout_threshold: 10,
+            lockout_duration: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+impl UnlockPolicy {
+    /// Wait imposed after `failures` consecutive failures
+    pub fn delay_after(&self, failures: u32) -> Duration {
+        if failures >= self.lockout_threshold {
+            self.lockout_duration
+        } else if failures <= self.free_attempts {
+            Duration::ZERO
+        } else {
+            let doublings = (failures - self.free_attempts - 1).min(16);
+            self.base_delay.saturating_mul(1 << doublings).min(self.max_delay)
+        }
+    }
+}
+
+/// Persisted failure counter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnlockAttempts {
+    /// Consecutive failures since the last successful unlock
+    pub failures: u32,
+    pub last_failure: Option<DateTime<Utc>>,
+}
+
+/// Why the next unlock attempt has to wait
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnlockWait {
+    /// Back-off after repeated failures
+    Backoff(Duration),
+    /// Lockout threshold reached
+    Locked(Duration),
+}
+
+impl UnlockWait {
+    pub fn remaining(&self) -> Duration {
+        match self {
+            Self::Backoff(remaining) | Self::Locked(remaining) => *remaining,
+        }
+    }
+}
+
+/// Failed unlock counter with back-off and lockout
+#[derive(Debug, Clone)]
+pub struct UnlockThrottle {
+    path: PathBuf,
+    policy: UnlockPolicy,
+    attempts: UnlockAttempts,
+}
+
+impl UnlockThrottle {
+    /// Counter in the `.vaughan` directory with the default policy
+    pub fn new() -> Self {
+        Self::at(storage::get_vaughan_dir().join(UNLOCK_ATTEMPTS_FILE))
+    }
+
+    /// Counter in the `.vaughan` directory with the policy from the settings
+    pub fn from_settings() -> Self {
+        let policy = match crate::config::SettingsStore::open_default() {
+            Ok(store) => UnlockPolicy::from(&store.section::<crate::config::SecurityPolicySettings>()),
+            Err(e) => {
+                tracing::warn!("⚠️ Invalid settings, using the default unlock policy: {}", e);
+                UnlockPolicy::default()
+            }
+        };
+        Self::new().with_policy(policy)
+    }
+
+    /// Counter at an explicit path
+    ///
+    /// A missing file means no failures. A corrupt one is reset and the reset
+    /// recorded in the audit log.
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let attempts = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                tracing::warn!("⚠️ Unreadable unlock attempt counter, resetting it: {}", e);
+                audit::record(AuditEvent::UnlockCounterReset { detail: e.to_string() }, Uuid::new_v4());
+                UnlockAttempts::default()
+            }),
+            Err(_) => UnlockAttempts::default(),
+        };
+        Self {
+            path,
+            policy: UnlockPolicy::default(),
+            attempts,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: UnlockPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Read the counter file again, picking up attempts counted elsewhere
+    pub fn reload(&self) -> Self {
+        Self::at(&self.path).with_policy(self.policy)
+    }
+
+    pub fn attempts(&self) -> &UnlockAttempts {
+        &self.attempts
+    }
+
+    /// Failures left before the lockout
+    pub fn remaining_attempts(&self) -> u32 {
+        self.policy.lockout_threshold.saturating_sub(self.attempts.failures)
+    }
+
+    /// Wait before an attempt at `now` is allowed
+    pub fn wait_at(&self, now: DateTime<Utc>) -> Option<UnlockWait> {
+        let last_failure = self.attempts.last_failure?;
+        let delay = self.policy.delay_after(self.attempts.failures);
+        let until = last_failure + chrono::Duration::from_std(delay).ok()?;
+        let remaining = (until - now).to_std().ok().filter(|r| !r.is_zero())?;
+        Some(if self.attempts.failures >= self.policy.lockout_threshold {
+            UnlockWait::Locked(remaining)
+        } else {
+            UnlockWait::Backoff(remaining)
+        })
+    }
+
+    pub fn wait(&self) -> Option<UnlockWait> {
+        self.wait_at(Utc::now())
+    }
+
+    /// Count a failure at `now`, returning the wait it imposes
+    pub fn record_failure_at(&mut self, now: DateTime<Utc>) -> Result<Option<UnlockWait>> {
+        self.attempts.failures = self.attempts.failures.saturating_add(1);
+        self.attempts.last_failure = Some(now);
+        if self.attempts.failures >= self.policy.lockout_threshold {
+            tracing::warn!("🚫 Unlock locked out after {} failed attempts", self.attempts.failures);
+        }
+        self.save()?;
+        Ok(self.wait_at(now))
+    }
+
+    pub fn record_failure(&mut self) -> Result<Option<UnlockWait>> {
+        self.record_failure_at(Utc::now())
+    }
+
+    /// Reset the counter after a successful unlock
+    pub fn record_success(&mut self) -> Result<()> {
+        if self.attempts == UnlockAttempts::default() {
+            return Ok(());
+        }
+        self.attempts = UnlockAttempts::default();
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string(&self.attempts).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| WalletError::Generic(format!("Failed to create unlock counter directory: {e}")))?;
+        }
+        storage::write_secure_file(&self.path.to_string_lossy(), &json)
+    }
+}
+
+impl Default for UnlockThrottle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_schedule() {
+        let policy = UnlockPolicy::default();
+        assert_eq!(policy.delay_after(3), Duration::ZERO);
+        assert_eq!(policy.delay_after(4), Duration::from_secs(5));
+        assert_eq!(policy.delay_after(5), Duration::from_secs(10));
+        assert_eq!(policy.delay_after(9), Duration::from_secs(160));
+        let capped = UnlockPolicy {
+            max_delay: Duration::from_secs(60),
+            ..policy
+        };
+        assert_eq!(capped.delay_after(9), Duration::from_secs(60));
+        assert_eq!(policy.delay_after(10), Duration::from_secs(30 * 60));
+        assert_eq!(policy.delay_after(u32::MAX), Duration::from_secs(30 * 60));
+    }
+
+    #[test]
+    fn test_counter_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(UNLOCK_ATTEMPTS_FILE);
+        let policy = UnlockPolicy {
+            lockout_threshold: 5,
+            ..UnlockPolicy::default()
+        };
+        let now = Utc::now();
+
+        let mut throttle = UnlockThrottle::at(&path).with_policy(policy);
+        for _ in 0..3 {
+            assert_eq!(throttle.record_failure_at(now).unwrap(), None);
+        }
+        assert_eq!(
+            throttle.record_failure_at(now).unwrap(),
+            Some(UnlockWait::Backoff(Duration::from_secs(5)))
+        );
+
+        // A restart reads the counter back
+        let mut throttle = UnlockThrottle::at(&path).with_policy(policy);
+        assert_eq!(throttle.attempts().failures, 4);
+        assert_eq!(throttle.remaining_attempts(), 1);
+        let wait = throttle.record_failure_at(now).unwrap().unwrap();
+        assert_eq!(wait, UnlockWait::Locked(Duration::from_secs(30 * 60)));
+
+        // Waiting out the lockout allows another attempt; a clock set back doesn't
+        assert!(throttle.wait_at(now + chrono::Duration::minutes(31)).is_none());
+        assert!(throttle.wait_at(now - chrono::Duration::hours(1)).unwrap().remaining() > wait.remaining());
+
+        throttle.record_success().unwrap();
+        assert_eq!(UnlockThrottle::at(&path).attempts(), &UnlockAttempts::default());
+    }
+
+    #[test]
+    fn test_corrupt_counter_starts_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(UNLOCK_ATTEMPTS_FILE);
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let mut throttle = UnlockThrottle::at(&path);
+        assert_eq!(throttle.attempts(), &UnlockAttempts::default());
+        assert!(throttle.wait().is_none());
+
+        // The next failure overwrites the corrupt file
+        throttle.record_failure().unwrap();
+        assert_eq!(throttle.reload().attempts().failures, 1);
+    }
+}
@@ -337,11 +337,11 @@ impl WalletPasswordValidator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::security::keychain::OSKeychain;
+    use crate::security::TestKeychain;
 
     #[tokio::test]
     async fn test_wallet_password_validator() {
-        let _keychain = Box::new(OSKeychain::new("test-wallet-validator".to_string()).unwrap());
+        let _keychain = Box::new(TestKeychain::with_service("test-wallet-validator"));
         let _storage = WalletConfigStorage::new().unwrap();
         let validator = WalletPasswordValidator::new().unwrap();
 
@@ -373,7 +373,7 @@ mod tests {
 
     #[test]
     fn test_password_strength_validation() {
-        let _keychain = Box::new(OSKeychain::new("test-strength".to_string()).unwrap());
+        let _keychain = Box::new(TestKeychain::with_service("test-strength"));
         let _storage = WalletConfigStorage::new().unwrap();
         let validator = WalletPasswordValidator::new().expect("Failed to create validator");
 
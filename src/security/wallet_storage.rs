@@ -3,12 +3,18 @@
 //! This module provides persistent storage for wallet configuration
 //! using the OS keychain, separate from individual account storage.
 
-use crate::error::{Result, SecurityError};
+use crate::error::{Result, SecurityError, VaughanError};
 use crate::security::{KeyReference, KeychainInterface, WalletConfig};
+use crate::wallet::backup::{canonical, DestructiveOperation, RecoveryRegistry};
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// Schema name embedded in wallet configuration exports
+const EXPORT_SCHEMA: &str = "vaughan.wallet-config-export";
+/// Current layout of wallet configuration exports
+const EXPORT_SCHEMA_VERSION: u32 = 1;
+
 /// Wallet configuration storage manager
 #[derive(Debug)]
 pub struct WalletConfigStorage {
@@ -194,6 +200,43 @@ impl WalletConfigStorage {
         Ok(())
     }
 
+    /// Re-encrypt the wallet configuration under a new master password
+    ///
+    /// Returns the previous configuration, which [`Self::save_wallet_config`]
+    /// with the old password puts back, or `None` when no wallet exists.
+    pub async fn change_master_password(
+        &self,
+        old_password: &SecretString,
+        new_password: &SecretString,
+    ) -> Result<Option<WalletConfig>> {
+        let Some(previous) = self.load_wallet_config(old_password).await? else {
+            return Ok(None);
+        };
+
+        let mut wallet_config = previous.clone();
+        let accounts = wallet_config.decrypt_account_metadata(old_password)?;
+        let settings = wallet_config.decrypt_wallet_settings(old_password)?;
+        wallet_config.update_account_metadata(&accounts, new_password)?;
+        wallet_config.update_wallet_settings(&settings, new_password)?;
+        wallet_config.update_encryption_info(new_password)?;
+
+        self.save_wallet_config(&wallet_config, new_password).await?;
+        tracing::info!("✅ Wallet configuration re-encrypted under the new master password");
+        Ok(Some(previous))
+    }
+
+    /// Whether `password` is the master password, `None` when no wallet exists
+    pub async fn verify_master_password(&self, password: &SecretString) -> Result<Option<bool>> {
+        if !self.wallet_exists()? {
+            return Ok(None);
+        }
+        match self.load_wallet_config(password).await {
+            Ok(wallet_config) => Ok(wallet_config.map(|_| true)),
+            Err(VaughanError::Security(SecurityError::DecryptionError { .. })) => Ok(Some(false)),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Check if a wallet configuration exists
     pub fn wallet_exists(&self) -> Result<bool> {
         Ok(self.config_path.exists() && self.config_path.is_file())
@@ -274,6 +317,19 @@ impl WalletConfigStorage {
     ) -> Result<WalletConfig> {
         tracing::info!("🔄 Migrating from legacy storage to wallet-based configuration");
 
+        // Recovery point of the accounts in the legacy storage
+        let keystore =
+            crate::security::keystore::SecureKeystoreImpl::new(crate::security::create_keychain_interface()?).await?;
+        RecoveryRegistry::open_default()
+            .snapshot(
+                &keystore,
+                master_password,
+                DestructiveOperation::MigrateStorage {
+                    description: format!("legacy storage to wallet '{wallet_name}'"),
+                },
+            )
+            .await?;
+
         // Create new wallet config
         let wallet_config = self.create_wallet_config(wallet_name, master_password).await?;
 
@@ -303,9 +359,7 @@ impl WalletConfigStorage {
         wallet_config.update_wallet_settings(&settings, export_password)?;
 
         // Create export data with different encryption
-        let export_data = serde_json::to_string(&wallet_config).map_err(|e| SecurityError::SerializationError {
-            message: format!("Failed to serialize wallet config for export: {e}"),
-        })?;
+        let export_data = canonical::to_canonical_string(&wallet_config)?;
 
         // Re-encrypt with export password
         let encrypted_export = WalletConfig::encrypt_data(
@@ -314,9 +368,7 @@ impl WalletConfigStorage {
             &crate::security::Argon2Params::default(),
         )?;
 
-        let export_json = serde_json::to_string(&encrypted_export).map_err(|e| SecurityError::SerializationError {
-            message: format!("Failed to serialize export data: {e}"),
-        })?;
+        let export_json = canonical::to_versioned_document(EXPORT_SCHEMA, EXPORT_SCHEMA_VERSION, &encrypted_export)?;
 
         tracing::info!("✅ Wallet configuration exported successfully");
         Ok(export_json)
@@ -332,10 +384,8 @@ impl WalletConfigStorage {
         tracing::info!("📥 Importing wallet configuration from backup");
 
         // Deserialize encrypted import data
-        let encrypted_data: crate::security::EncryptedData =
-            serde_json::from_str(import_data).map_err(|e| SecurityError::SerializationError {
-                message: format!("Failed to deserialize import data: {e}"),
-            })?;
+        let (_, encrypted_data): (u32, crate::security::EncryptedData) =
+            canonical::from_versioned_document(EXPORT_SCHEMA, EXPORT_SCHEMA_VERSION, import_data)?;
 
         // Decrypt with import password
         let decrypted_data = WalletConfig::decrypt_data(
@@ -460,11 +510,11 @@ impl WalletConfigStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::security::keychain::OSKeychain;
+    use crate::security::TestKeychain;
 
     #[tokio::test]
     async fn test_wallet_config_storage() {
-        let _keychain = Box::new(OSKeychain::new("test-wallet-config".to_string()).unwrap());
+        let _keychain = Box::new(TestKeychain::with_service("test-wallet-config"));
         let mut storage = WalletConfigStorage::new().unwrap();
         // Use unique path to avoid race conditions
         let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
@@ -502,7 +552,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_wallet_export_import() {
-        let _keychain = Box::new(OSKeychain::new("test-wallet-export".to_string()).unwrap());
+        let _keychain = Box::new(TestKeychain::with_service("test-wallet-export"));
         let mut storage = WalletConfigStorage::new().unwrap();
         // Use unique path to avoid race conditions
         let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
@@ -0,0 +1,702 @@
+//! Audit Log for Sensitive Operations
+//!
+//! Append-only local record of wallet unlocks, seed and key exports and the
+//! authentication steps leading to them, signed transactions, messages,
+//! typed data and auditor statements, added networks, removed accounts and
+//! reset unlock counters. Each entry carries a timestamp and the correlation
+//! ID of the operation that produced it, and is chained to the previous entry
+//! with HMAC-SHA256, so editing, reordering or deleting an entry breaks
+//! [`AuditLog::verify`].
+//!
+//! Entries are JSON lines in `~/.vaughan/audit.log`. The HMAC key is
+//! generated while the log is still empty and kept in the keychain, not next
+//! to the log. A last line cut short by a crash mid-write is dropped when the
+//! log is opened and recorded as a [`AuditEvent::TornWrite`] entry. Each
+//! append locks the file and continues after its last entry, so several
+//! handles and processes can share one log.
+//!
+//! Events are only written once [`init_audit_log`] has opened the global
+//! log; until then [`record`] just traces them.
+
+use crate::error::{Result, SecurityError};
+use crate::security::keystore::storage::{get_vaughan_dir, write_secure_file};
+use crate::security::{ExportAuditKind, KeyReference, KeychainInterface};
+use alloy::primitives::{Address, B256, U256};
+use alloy::rpc::types::TransactionRequest;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+/// Audit log file name inside the `.vaughan` directory
+pub const AUDIT_LOG_FILE: &str = "audit.log";
+/// Keychain service holding the HMAC key
+pub const AUDIT_KEYCHAIN_SERVICE: &str = "vaughan-audit";
+const AUDIT_KEY_ID: &str = "audit-log-hmac-key";
+
+/// Previous MAC of the first entry
+const GENESIS_MAC: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A sensitive operation worth keeping a record of
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditEvent {
+    Unlock {
+        account: Address,
+    },
+    ExportSeed {
+        account: Address,
+    },
+    ExportPrivateKey {
+        account: Address,
+    },
+    /// Step of an authenticated export: password check, cooldown, rate limit
+    /// or outcome
+    ExportAttempt {
+        outcome: ExportAuditKind,
+        /// Export operation name (e.g. `export_seed`)
+        operation: String,
+        account: Option<Address>,
+        detail: String,
+    },
+    SignTransaction {
+        account: Address,
+        chain_id: u64,
+        to: Option<Address>,
+        value: U256,
+        /// Decoded call preview, e.g. `approve(spender=0x…, amount=unlimited)`
+        summary: String,
+    },
+    SignMessage {
+        account: Address,
+        chain_id: u64,
+        /// EIP-191 hash of the message
+        hash: B256,
+    },
+    SignTypedData {
+        account: Address,
+        chain_id: u64,
+        primary_type: String,
+        verifying_contract: Option<Address>,
+        /// EIP-712 signing hash
+        hash: B256,
+    },
+    /// Attestation of an account's statement in an auditor bundle
+    SignAuditorStatement {
+        account: Address,
+        generated_at: DateTime<Utc>,
+    },
+    AddNetwork {
+        chain_id: u64,
+        name: String,
+    },
+    RemoveAccount {
+        account: Address,
+    },
+    /// Unreadable failed-unlock counter started over
+    UnlockCounterReset {
+        detail: String,
+    },
+    /// Incomplete last line, left by an interrupted write, removed on open
+    TornWrite {
+        partial: String,
+    },
+}
+
+impl AuditEvent {
+    /// Signed transaction event with its calldata decoded where possible
+    pub fn sign_transaction(account: Address, chain_id: u64, tx: &TransactionRequest) -> Self {
+        let input = tx.input.input().filter(|input| !input.is_empty());
+        let summary = match input {
+            None => "native transfer".to_string(),
+            Some(input) => crate::abi::decode_transaction(tx)
+                .map(|call| call.preview())
+                .unwrap_or_else(|| format!("undecoded call 0x{}", alloy::hex::encode(&input[..input.len().min(4)]))),
+        };
+        Self::SignTransaction {
+            account,
+            chain_id,
+            to: tx.to.and_then(|to| to.to().copied()),
+            value: tx.value.unwrap_or_default(),
+            summary,
+        }
+    }
+}
+
+impl fmt::Display for AuditEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unlock { account } => write!(f, "unlock {account}"),
+            Self::ExportSeed { account } => write!(f, "export seed of {account}"),
+            Self::ExportPrivateKey { account } => write!(f, "export private key of {account}"),
+            Self::ExportAttempt {
+                outcome,
+                operation,
+                account,
+                detail,
+            } => {
+                write!(f, "{operation} {outcome:?}")?;
+                if let Some(account) = account {
+                    write!(f, " for {account}")?;
+                }
+                if !detail.is_empty() {
+                    write!(f, " ({detail})")?;
+                }
+                Ok(())
+            }
+            Self::SignTransaction {
+                account,
+                chain_id,
+                summary,
+                ..
+            } => write!(f, "sign {summary} from {account} on chain {chain_id}"),
+            Self::SignMessage {
+                account,
+                chain_id,
+                hash,
+            } => write!(f, "sign message {hash} with {account} on chain {chain_id}"),
+            Self::SignTypedData {
+                account,
+                chain_id,
+                primary_type,
+                ..
+            } => write!(f, "sign typed data {primary_type} with {account} on chain {chain_id}"),
+            Self::SignAuditorStatement { account, generated_at } => {
+                write!(f, "sign auditor statement of {account} at {generated_at}")
+            }
+            Self::AddNetwork { chain_id, name } => write!(f, "add network {name} ({chain_id})"),
+            Self::RemoveAccount { account } => write!(f, "remove account {account}"),
+            Self::UnlockCounterReset { detail } => write!(f, "reset unreadable unlock counter ({detail})"),
+            Self::TornWrite { partial } => write!(f, "drop incomplete entry of {} bytes", partial.len()),
+        }
+    }
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 0
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub event: AuditEvent,
+    /// MAC of the previous entry, hex
+    pub prev_mac: String,
+    /// HMAC-SHA256 over `prev_mac` and the fields above, hex
+    pub mac: String,
+}
+
+/// Fields covered by an entry's MAC, in a fixed order
+#[derive(Serialize)]
+struct MacInput<'a> {
+    seq: u64,
+    timestamp: &'a DateTime<Utc>,
+    correlation_id: &'a Uuid,
+    event: &'a AuditEvent,
+    prev_mac: &'a str,
+}
+
+/// Result of checking the chain of an audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditVerification {
+    /// Entries checked before the first problem, or all of them
+    pub valid_entries: u64,
+    /// Sequence number where the chain breaks
+    pub broken_at: Option<u64>,
+    pub reason: Option<String>,
+}
+
+impl AuditVerification {
+    pub fn is_intact(&self) -> bool {
+        self.broken_at.is_none()
+    }
+}
+
+/// Exported copy of the log with its verification result
+#[derive(Debug, Serialize)]
+struct AuditExport<'a> {
+    exported_at: DateTime<Utc>,
+    verification: &'a AuditVerification,
+    entries: &'a [AuditEntry],
+}
+
+/// Position of the next entry
+struct ChainHead {
+    seq: u64,
+    mac: String,
+}
+
+impl ChainHead {
+    /// Head following `last`, or the start of an empty log
+    fn after(last: Option<&AuditEntry>) -> Self {
+        match last {
+            Some(last) => Self {
+                seq: last.seq + 1,
+                mac: last.mac.clone(),
+            },
+            None => Self {
+                seq: 0,
+                mac: GENESIS_MAC.to_string(),
+            },
+        }
+    }
+}
+
+/// Append-only, HMAC-chained audit log file
+pub struct AuditLog {
+    path: PathBuf,
+    key: Zeroizing<Vec<u8>>,
+    head: Mutex<ChainHead>,
+}
+
+impl fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AuditLog {
+    /// Open the log at `path` with `key`, continuing after its last entry
+    ///
+    /// An unreadable last line is taken for a write cut short: it is removed
+    /// and recorded as [`AuditEvent::TornWrite`] so the chain continues.
+    pub fn open(path: impl Into<PathBuf>, key: &[u8]) -> Result<Self> {
+        let path = path.into();
+        let (entries, torn) = read_log(&path)?;
+        let log = Self {
+            path,
+            key: Zeroizing::new(key.to_vec()),
+            head: Mutex::new(ChainHead::after(entries.last())),
+        };
+        if torn.is_some() {
+            let mut head = log.head.lock().unwrap_or_else(|e| e.into_inner());
+            let mut file = log.lock_file()?;
+            log.catch_up(&mut file, &mut head)?;
+        }
+        Ok(log)
+    }
+
+    /// Open `~/.vaughan/audit.log` with the key from the keychain
+    ///
+    /// The key is created only while the log has no entries.
+    pub fn open_default() -> Result<Self> {
+        let keychain = crate::security::create_keychain(AUDIT_KEYCHAIN_SERVICE)?;
+        let path = get_vaughan_dir().join(AUDIT_LOG_FILE);
+        let key = load_or_create_key(keychain.as_ref(), &path)?;
+        Self::open(path, &key)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an event
+    ///
+    /// Other handles, in this process or another, may have appended since the
+    /// last call, so the chain continues after whatever entry is last in the
+    /// file while it is locked.
+    pub fn append(&self, event: AuditEvent, correlation_id: Uuid) -> Result<AuditEntry> {
+        let mut head = self.head.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = self.lock_file()?;
+        self.catch_up(&mut file, &mut head)?;
+        self.write_entry(&mut file, &mut head, event, correlation_id)
+    }
+
+    /// Open the log for appending, holding an exclusive lock until the file is dropped
+    fn lock_file(&self) -> Result<std::fs::File> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = open_append(&self.path)?;
+        file.lock()?;
+        Ok(file)
+    }
+
+    /// Move `head` after the last entry in the file, dropping a torn last line
+    fn catch_up(&self, file: &mut std::fs::File, head: &mut ChainHead) -> Result<()> {
+        let (entries, torn) = read_log(&self.path)?;
+        *head = ChainHead::after(entries.last());
+        if let Some(offset) = torn {
+            let contents = std::fs::read(&self.path)?;
+            let partial = String::from_utf8_lossy(&contents[offset..]).trim_end().to_string();
+            file.set_len(offset as u64)?;
+            tracing::warn!("Audit log ended in an incomplete entry, probably an interrupted write; dropping it");
+            self.write_entry(file, head, AuditEvent::TornWrite { partial }, Uuid::new_v4())?;
+        }
+        Ok(())
+    }
+
+    fn write_entry(
+        &self,
+        file: &mut std::fs::File,
+        head: &mut ChainHead,
+        event: AuditEvent,
+        correlation_id: Uuid,
+    ) -> Result<AuditEntry> {
+        let timestamp = Utc::now();
+        let mac = self.mac(head.seq, &timestamp, &correlation_id, &event, &head.mac)?;
+        let entry = AuditEntry {
+            seq: head.seq,
+            timestamp,
+            correlation_id,
+            event,
+            prev_mac: head.mac.clone(),
+            mac,
+        };
+
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        file.sync_data()?;
+
+        head.seq += 1;
+        head.mac = entry.mac.clone();
+        Ok(entry)
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> Result<Vec<AuditEntry>> {
+        Ok(read_log(&self.path)?.0)
+    }
+
+    /// Check sequence numbers, links and MACs of every entry
+    ///
+    /// Removing entries from the end of the log leaves a valid chain; compare
+    /// `valid_entries` against an earlier export to detect truncation.
+    pub fn verify(&self) -> Result<AuditVerification> {
+        Ok(self.verify_entries(&self.entries()?))
+    }
+
+    /// Write the entries and their verification result to `path` as JSON
+    pub fn export(&self, path: &Path) -> Result<AuditVerification> {
+        let entries = self.entries()?;
+        let verification = self.verify_entries(&entries);
+        let export = AuditExport {
+            exported_at: Utc::now(),
+            verification: &verification,
+            entries: &entries,
+        };
+        let path = path.to_str().ok_or_else(|| SecurityError::KeystoreError {
+            message: "Audit export path is not valid UTF-8".to_string(),
+        })?;
+        write_secure_file(path, &serde_json::to_string_pretty(&export)?)?;
+        Ok(verification)
+    }
+
+    fn verify_entries(&self, entries: &[AuditEntry]) -> AuditVerification {
+        let mut prev_mac = GENESIS_MAC;
+        for (seq, entry) in (0u64..).zip(entries) {
+            let problem = if entry.seq != seq {
+                Some(format!("expected entry {seq}, found {}", entry.seq))
+            } else if entry.prev_mac != prev_mac {
+                Some("link to the previous entry does not match".to_string())
+            } else {
+                match self.mac(
+                    entry.seq,
+                    &entry.timestamp,
+                    &entry.correlation_id,
+                    &entry.event,
+                    prev_mac,
+                ) {
+                    Ok(mac) if bool::from(mac.as_bytes().ct_eq(entry.mac.as_bytes())) => None,
+                    Ok(_) => Some("entry MAC does not match its contents".to_string()),
+                    Err(e) => Some(e.to_string()),
+                }
+            };
+            if let Some(reason) = problem {
+                return AuditVerification {
+                    valid_entries: seq,
+                    broken_at: Some(seq),
+                    reason: Some(reason),
+                };
+            }
+            prev_mac = &entry.mac;
+        }
+        AuditVerification {
+            valid_entries: entries.len() as u64,
+            broken_at: None,
+            reason: None,
+        }
+    }
+
+    fn mac(
+        &self,
+        seq: u64,
+        timestamp: &DateTime<Utc>,
+        correlation_id: &Uuid,
+        event: &AuditEvent,
+        prev_mac: &str,
+    ) -> Result<String> {
+        let input = serde_json::to_vec(&MacInput {
+            seq,
+            timestamp,
+            correlation_id,
+            event,
+            prev_mac,
+        })?;
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.key).map_err(|_| SecurityError::KeystoreError {
+            message: "Invalid audit log key".to_string(),
+        })?;
+        mac.update(&input);
+        Ok(alloy::hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+/// Entries of the log, and the byte offset of an unreadable last line
+///
+/// Unreadable lines before the last one are an error.
+fn read_log(path: &Path) -> Result<(Vec<AuditEntry>, Option<usize>)> {
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), None)),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    for line in contents.split_inclusive(|b| *b == b'\n') {
+        let start = offset;
+        offset += line.len();
+        if line.trim_ascii().is_empty() {
+            continue;
+        }
+        match serde_json::from_slice(line) {
+            Ok(entry) => entries.push(entry),
+            Err(_) if contents[offset..].trim_ascii().is_empty() => return Ok((entries, Some(start))),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok((entries, None))
+}
+
+fn open_append(path: &Path) -> Result<std::fs::File> {
+    let mut options = std::fs::OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    Ok(options.open(path)?)
+}
+
+/// HMAC key of the log at `log_path`, generated only while the log has no entries
+///
+/// Existing entries only verify with the key that wrote them, so a key the
+/// keychain cannot return or that is corrupt is an error, not a reason to
+/// start over with a new one.
+fn load_or_create_key(keychain: &dyn KeychainInterface, log_path: &Path) -> Result<Zeroizing<Vec<u8>>> {
+    let key_ref = KeyReference {
+        id: AUDIT_KEY_ID.to_string(),
+        service: AUDIT_KEYCHAIN_SERVICE.to_string(),
+        account: AUDIT_KEY_ID.to_string(),
+    };
+    match keychain.retrieve(&key_ref) {
+        Ok(stored) => {
+            return match alloy::hex::decode(stored.expose_secret()) {
+                Ok(key) if !key.is_empty() => Ok(Zeroizing::new(key)),
+                _ => Err(SecurityError::KeystoreError {
+                    message: "Audit log key in the keychain is corrupt".to_string(),
+                }
+                .into()),
+            };
+        }
+        Err(e) if !read_log(log_path)?.0.is_empty() => {
+            return Err(SecurityError::KeystoreError {
+                message: format!("Audit log has entries but its key is unavailable: {e}"),
+            }
+            .into());
+        }
+        Err(_) => {}
+    }
+
+    let mut key = Zeroizing::new(vec![0u8; 32]);
+    getrandom::getrandom(&mut key).map_err(|e| SecurityError::KeyDerivationError {
+        message: format!("Failed to generate audit log key: {e}"),
+    })?;
+    keychain.store(&key_ref, SecretString::new(alloy::hex::encode(key.as_slice())))?;
+    Ok(key)
+}
+
+/// Global audit log
+static AUDIT_LOG: OnceLock<Option<AuditLog>> = OnceLock::new();
+
+/// Open the global audit log at its default location
+///
+/// Failure is logged and leaves auditing disabled for the process.
+pub fn init_audit_log() -> Option<&'static AuditLog> {
+    AUDIT_LOG
+        .get_or_init(|| match AuditLog::open_default() {
+            Ok(log) => Some(log),
+            Err(e) => {
+                tracing::error!(
+                    "Audit log unavailable, sensitive operations will not be recorded: {}",
+                    e
+                );
+                None
+            }
+        })
+        .as_ref()
+}
+
+/// The global audit log, if [`init_audit_log`] opened it
+pub fn audit_log() -> Option<&'static AuditLog> {
+    AUDIT_LOG.get().and_then(Option::as_ref)
+}
+
+/// Record `event` in the global audit log
+///
+/// Write failures are logged rather than failing the operation that already
+/// happened.
+pub fn record(event: AuditEvent, correlation_id: Uuid) {
+    tracing::info!(correlation_id = %correlation_id, "🧾 Audit: {}", event);
+    if let Some(log) = audit_log() {
+        if let Err(e) = log.append(event, correlation_id) {
+            tracing::error!(correlation_id = %correlation_id, "Failed to write audit log entry: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = &[7u8; 32];
+
+    fn unlock(byte: u8) -> AuditEvent {
+        AuditEvent::Unlock {
+            account: Address::repeat_byte(byte),
+        }
+    }
+
+    #[test]
+    fn test_chain_survives_reopen_and_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUDIT_LOG_FILE);
+
+        let log = AuditLog::open(&path, KEY).unwrap();
+        let first = log.append(unlock(1), Uuid::new_v4()).unwrap();
+        assert_eq!(first.prev_mac, GENESIS_MAC);
+
+        let reopened = AuditLog::open(&path, KEY).unwrap();
+        let second = reopened
+            .append(
+                AuditEvent::ExportSeed {
+                    account: Address::repeat_byte(1),
+                },
+                Uuid::new_v4(),
+            )
+            .unwrap();
+        assert_eq!((second.seq, second.prev_mac.as_str()), (1, first.mac.as_str()));
+        assert!(reopened.verify().unwrap().is_intact());
+
+        // A different key cannot vouch for the chain
+        let forged = AuditLog::open(&path, &[8u8; 32]).unwrap().verify().unwrap();
+        assert_eq!(forged.broken_at, Some(0));
+
+        // Editing the first entry breaks it
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let tampered = contents.replacen(
+            &Address::repeat_byte(1).to_string(),
+            &Address::repeat_byte(2).to_string(),
+            1,
+        );
+        std::fs::write(&path, tampered).unwrap();
+        let result = reopened.verify().unwrap();
+        assert_eq!((result.valid_entries, result.broken_at), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_torn_last_line_is_dropped_and_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUDIT_LOG_FILE);
+        let log = AuditLog::open(&path, KEY).unwrap();
+        log.append(unlock(1), Uuid::new_v4()).unwrap();
+        let mut file = open_append(&path).unwrap();
+        write!(file, "{{\"seq\":1,\"timestamp\":\"20").unwrap();
+
+        let reopened = AuditLog::open(&path, KEY).unwrap();
+        let entries = reopened.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(&entries[1].event, AuditEvent::TornWrite { partial } if partial.starts_with("{\"seq\":1")));
+        reopened.append(unlock(2), Uuid::new_v4()).unwrap();
+        assert!(reopened.verify().unwrap().is_intact());
+
+        // Damage before the last line is not a torn write
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, format!("garbage\n{contents}")).unwrap();
+        assert!(AuditLog::open(&path, KEY).is_err());
+    }
+
+    #[test]
+    fn test_handles_sharing_a_file_keep_one_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUDIT_LOG_FILE);
+        let first = AuditLog::open(&path, KEY).unwrap();
+        let second = AuditLog::open(&path, KEY).unwrap();
+
+        for byte in 0..3 {
+            first.append(unlock(byte), Uuid::new_v4()).unwrap();
+            second.append(unlock(byte), Uuid::new_v4()).unwrap();
+        }
+
+        let entries = first.entries().unwrap();
+        assert_eq!(entries.iter().map(|e| e.seq).collect::<Vec<_>>(), (0..6).collect::<Vec<_>>());
+        assert!(second.verify().unwrap().is_intact());
+    }
+
+    #[test]
+    fn test_key_is_only_created_for_an_empty_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUDIT_LOG_FILE);
+        let keychain = crate::security::TestKeychain::new();
+        let key = load_or_create_key(&keychain, &path).unwrap();
+        assert_eq!(load_or_create_key(&keychain, &path).unwrap(), key);
+        AuditLog::open(&path, &key)
+            .unwrap()
+            .append(unlock(1), Uuid::new_v4())
+            .unwrap();
+
+        // A log with entries never gets a new key
+        assert!(load_or_create_key(&crate::security::TestKeychain::new(), &path).is_err());
+        let key_ref = KeyReference {
+            id: AUDIT_KEY_ID.to_string(),
+            service: AUDIT_KEYCHAIN_SERVICE.to_string(),
+            account: AUDIT_KEY_ID.to_string(),
+        };
+        keychain
+            .store(&key_ref, SecretString::new("not hex".to_string()))
+            .unwrap();
+        assert!(load_or_create_key(&keychain, &path).is_err());
+    }
+
+    #[test]
+    fn test_sign_event_summary_and_export() {
+        let tx = TransactionRequest::default()
+            .to(Address::repeat_byte(9))
+            .input(crate::wallet::transaction::erc20::transfer_calldata(Address::repeat_byte(3), U256::from(5)).into());
+        let event = AuditEvent::sign_transaction(Address::repeat_byte(1), 369, &tx);
+        assert!(matches!(
+            &event,
+            AuditEvent::SignTransaction { summary, to, .. }
+                if summary.starts_with("transfer(") && *to == Some(Address::repeat_byte(9))
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        let log = AuditLog::open(dir.path().join(AUDIT_LOG_FILE), KEY).unwrap();
+        log.append(event, Uuid::new_v4()).unwrap();
+
+        let export_path = dir.path().join("audit-export.json");
+        assert!(log.export(&export_path).unwrap().is_intact());
+        let export: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(export_path).unwrap()).unwrap();
+        assert_eq!(export["entries"][0]["event"]["kind"], "sign_transaction");
+        assert_eq!(export["verification"]["valid_entries"], 1);
+    }
+}
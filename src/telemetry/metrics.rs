@@ -0,0 +1,423 @@
+//! Opt-in local metrics
+//!
+//! Counters and histograms for RPC latency, signing duration and error rates,
+//! served in the Prometheus text format on `http://127.0.0.1:<port>/metrics`.
+//! Nothing is collected unless `telemetry.metrics_enabled` is set, see
+//! [`crate::config::TelemetrySettings`], and nothing leaves the machine.
+//!
+//! Every label value passes through [`redact`], which masks addresses, hashes
+//! and amounts, unless the user explicitly enables `telemetry.detailed`.
+
+use super::account_events::privacy::set_privacy_mode;
+use crate::config::TelemetrySettings;
+use crate::error::VaughanError;
+use alloy::primitives::Address;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Default port of the `/metrics` endpoint
+pub const DEFAULT_METRICS_PORT: u16 = 9464;
+
+/// Histogram bucket bounds in seconds
+pub const DURATION_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+const RPC_REQUESTS: &str = "vaughan_rpc_requests_total";
+const RPC_DURATION: &str = "vaughan_rpc_request_duration_seconds";
+const SIGNING_DURATION: &str = "vaughan_signing_duration_seconds";
+const ERRORS: &str = "vaughan_errors_total";
+
+/// Name, type and help text of every metric
+const METRICS: &[(&str, &str, &str)] = &[
+    (RPC_REQUESTS, "counter", "RPC requests by chain and outcome"),
+    (RPC_DURATION, "histogram", "RPC request latency including failover"),
+    (SIGNING_DURATION, "histogram", "Time to sign a transaction"),
+    (ERRORS, "counter", "Wallet errors by category"),
+];
+
+/// Largest request head the endpoint reads
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+type Labels = Vec<(&'static str, String)>;
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Cumulative count per bucket in [`DURATION_BUCKETS`]
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; DURATION_BUCKETS.len()];
+        }
+        for (bucket, bound) in self.buckets.iter_mut().zip(DURATION_BUCKETS) {
+            if seconds <= *bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+/// Kind of sensitive value found by [`redact_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SensitiveToken {
+    Address,
+    Hash,
+    Amount,
+}
+
+/// Mask addresses, hashes and amounts in `text`
+///
+/// `0x` followed by 40 or more hex digits becomes `[address]` or `[hash]`;
+/// decimal numbers and integers of ten or more digits, e.g. wei values,
+/// become `[amount]`. Shorter integers such as chain ids are kept.
+pub fn redact(text: &str) -> String {
+    redact_with(text, |kind, _| {
+        match kind {
+            SensitiveToken::Address => "[address]",
+            SensitiveToken::Hash => "[hash]",
+            SensitiveToken::Amount => "[amount]",
+        }
+        .to_string()
+    })
+}
+
+/// Replace every value [`redact`] would mask with `replace(kind, value)`
+pub fn redact_with(text: &str, mut replace: impl FnMut(SensitiveToken, &str) -> String) -> String {
+    let bytes = text.as_bytes();
+    let mut redacted = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &text[i..];
+        let boundary = i == 0 || !bytes[i - 1].is_ascii_alphanumeric();
+
+        if boundary && (rest.starts_with("0x") || rest.starts_with("0X")) {
+            let hex = rest[2..].bytes().take_while(u8::is_ascii_hexdigit).count();
+            if hex >= 40 {
+                let kind = if hex >= 64 {
+                    SensitiveToken::Hash
+                } else {
+                    SensitiveToken::Address
+                };
+                redacted.push_str(&replace(kind, &rest[..2 + hex]));
+                i += 2 + hex;
+                continue;
+            }
+        }
+        if boundary && bytes[i].is_ascii_digit() {
+            let len = rest.bytes().take_while(|b| b.is_ascii_digit() || *b == b'.').count();
+            let number = rest[..len].trim_end_matches('.');
+            if number.contains('.') || number.len() >= 10 {
+                redacted.push_str(&replace(SensitiveToken::Amount, number));
+            } else {
+                redacted.push_str(number);
+            }
+            i += number.len();
+            continue;
+        }
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        redacted.push(c);
+        i += c.len_utf8();
+    }
+    redacted
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn format_labels(labels: &[(&str, String)], extra: Option<(&str, &str)>) -> String {
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .chain(extra)
+        .map(|(name, value)| format!("{name}=\"{}\"", escape_label(value)))
+        .collect();
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+/// In-memory metric store
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    /// Keep label values unredacted
+    detailed: bool,
+    counters: Mutex<BTreeMap<(&'static str, Labels), u64>>,
+    histograms: Mutex<BTreeMap<(&'static str, Labels), Histogram>>,
+}
+
+impl MetricsRegistry {
+    pub fn new(detailed: bool) -> Self {
+        Self {
+            detailed,
+            ..Self::default()
+        }
+    }
+
+    fn labels(&self, labels: &[(&'static str, &str)]) -> Labels {
+        labels
+            .iter()
+            .map(|(name, value)| {
+                let value = if self.detailed {
+                    value.to_string()
+                } else {
+                    redact(value)
+                };
+                (*name, value)
+            })
+            .collect()
+    }
+
+    pub fn increment(&self, name: &'static str, labels: &[(&'static str, &str)]) {
+        *lock(&self.counters).entry((name, self.labels(labels))).or_default() += 1;
+    }
+
+    pub fn observe(&self, name: &'static str, labels: &[(&'static str, &str)], duration: Duration) {
+        lock(&self.histograms)
+            .entry((name, self.labels(labels)))
+            .or_default()
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Count one RPC request to `chain_id` and its latency
+    pub fn record_rpc(&self, chain_id: u64, duration: Duration, success: bool) {
+        let chain_id = chain_id.to_string();
+        let outcome = if success { "success" } else { "error" };
+        self.increment(RPC_REQUESTS, &[("chain_id", &chain_id), ("outcome", outcome)]);
+        self.observe(RPC_DURATION, &[("chain_id", &chain_id)], duration);
+    }
+
+    /// Record how long `account` took to sign on `chain_id`
+    pub fn record_signing(&self, chain_id: u64, account: Address, duration: Duration, success: bool) {
+        let chain_id = chain_id.to_string();
+        let account = account.to_string();
+        let outcome = if success { "success" } else { "error" };
+        self.observe(
+            SIGNING_DURATION,
+            &[("chain_id", &chain_id), ("account", &account), ("outcome", outcome)],
+            duration,
+        );
+    }
+
+    /// Count `error` by category
+    pub fn record_error(&self, error: &VaughanError) {
+        let category = format!("{:?}", error.category()).to_lowercase();
+        self.increment(ERRORS, &[("category", &category)]);
+    }
+
+    /// All metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let counters = lock(&self.counters);
+        let histograms = lock(&self.histograms);
+        let mut out = String::new();
+
+        for (name, kind, help) in METRICS {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} {kind}");
+            for ((_, labels), value) in counters
+                .range((*name, Vec::new())..)
+                .take_while(|((n, _), _)| n == name)
+            {
+                let _ = writeln!(out, "{name}{} {value}", format_labels(labels, None));
+            }
+            for ((_, labels), histogram) in histograms
+                .range((*name, Vec::new())..)
+                .take_while(|((n, _), _)| n == name)
+            {
+                for (count, bound) in histogram.buckets.iter().zip(DURATION_BUCKETS) {
+                    let bound = bound.to_string();
+                    let labels = format_labels(labels, Some(("le", &bound)));
+                    let _ = writeln!(out, "{name}_bucket{labels} {count}");
+                }
+                let inf = format_labels(labels, Some(("le", "+Inf")));
+                let labels = format_labels(labels, None);
+                let _ = writeln!(out, "{name}_bucket{inf} {}", histogram.count);
+                let _ = writeln!(out, "{name}_sum{labels} {}", histogram.sum);
+                let _ = writeln!(out, "{name}_count{labels} {}", histogram.count);
+            }
+        }
+        out
+    }
+
+    /// Serve `GET /metrics` on `listener` until the task is cancelled
+    pub async fn serve(&'static self, listener: TcpListener) -> std::io::Result<()> {
+        tracing::info!("📈 Metrics available on http://{}/metrics", listener.local_addr()?);
+        loop {
+            let (mut stream, peer) = listener.accept().await?;
+            tokio::spawn(async move {
+                if let Err(e) = self.handle_connection(&mut stream).await {
+                    tracing::debug!("Metrics connection from {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection<S: AsyncRead + AsyncWrite + Unpin>(&self, stream: &mut S) -> std::io::Result<()> {
+        let mut request = Vec::new();
+        let mut chunk = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&chunk[..read]);
+        }
+
+        let request_line = request.split(|b| *b == b'\n').next().unwrap_or_default();
+        let (status, body) = if request_line.starts_with(b"GET /metrics ") {
+            ("200 OK", self.render())
+        } else {
+            ("404 Not Found", "Not found\n".to_string())
+        };
+        let head = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n",
+            body.len()
+        );
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(body.as_bytes()).await?;
+        stream.flush().await
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+static METRICS_REGISTRY: OnceLock<Option<MetricsRegistry>> = OnceLock::new();
+
+/// Apply the `telemetry` settings; call once at startup
+///
+/// Log sanitization follows `detailed` as well. When metrics are enabled the
+/// endpoint runs on its own thread, so callers need no runtime.
+pub fn init_metrics(settings: &TelemetrySettings) -> Option<&'static MetricsRegistry> {
+    set_privacy_mode(!settings.detailed);
+    let registry = METRICS_REGISTRY
+        .get_or_init(|| {
+            settings
+                .metrics_enabled
+                .then(|| MetricsRegistry::new(settings.detailed))
+        })
+        .as_ref()?;
+
+    let port = settings.metrics_port;
+    let spawned = std::thread::Builder::new()
+        .name("metrics-exporter".to_string())
+        .spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    tracing::error!("Metrics endpoint unavailable: {}", e);
+                    return;
+                }
+            };
+            let served = runtime.block_on(async {
+                let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port))).await?;
+                registry.serve(listener).await
+            });
+            if let Err(e) = served {
+                tracing::error!("Metrics endpoint on port {} failed: {}", port, e);
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::error!("Failed to start metrics endpoint: {}", e);
+    }
+    Some(registry)
+}
+
+/// The global registry, if metrics are enabled
+pub fn metrics() -> Option<&'static MetricsRegistry> {
+    METRICS_REGISTRY.get().and_then(Option::as_ref)
+}
+
+/// Record an RPC request in the global registry, if enabled
+pub fn record_rpc(chain_id: u64, duration: Duration, success: bool) {
+    if let Some(registry) = metrics() {
+        registry.record_rpc(chain_id, duration, success);
+    }
+}
+
+/// Record a transaction signature in the global registry, if enabled
+pub fn record_signing(chain_id: u64, account: Address, duration: Duration, success: bool) {
+    if let Some(registry) = metrics() {
+        registry.record_signing(chain_id, account, duration, success);
+    }
+}
+
+/// Count an error in the global registry, if enabled
+pub fn record_error(error: &VaughanError) {
+    if let Some(registry) = metrics() {
+        registry.record_error(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction() {
+        let address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+        assert_eq!(
+            redact(&format!("sent 1.5 ETH to {address}")),
+            "sent [amount] ETH to [address]"
+        );
+        assert_eq!(redact(&format!("tx 0x{}", "ab".repeat(32))), "tx [hash]");
+        assert_eq!(redact("value 1000000000000000000 wei"), "value [amount] wei");
+        assert_eq!(redact("chain 11155111 rpc2 v1"), "chain 11155111 rpc2 v1");
+    }
+
+    #[test]
+    fn test_render_prometheus_text() {
+        let account = Address::repeat_byte(0x11);
+        let registry = MetricsRegistry::new(false);
+        registry.record_rpc(369, Duration::from_millis(40), true);
+        registry.record_rpc(369, Duration::from_millis(400), false);
+        registry.record_signing(369, account, Duration::from_millis(3), true);
+
+        let text = registry.render();
+        assert!(text.contains("# TYPE vaughan_rpc_request_duration_seconds histogram"));
+        assert!(text.contains("vaughan_rpc_requests_total{chain_id=\"369\",outcome=\"error\"} 1"));
+        assert!(text.contains("vaughan_rpc_request_duration_seconds_bucket{chain_id=\"369\",le=\"0.05\"} 1"));
+        assert!(text.contains("vaughan_rpc_request_duration_seconds_bucket{chain_id=\"369\",le=\"+Inf\"} 2"));
+        assert!(text.contains("vaughan_rpc_request_duration_seconds_count{chain_id=\"369\"} 2"));
+        assert!(text.contains("account=\"[address]\""));
+        assert!(!text.contains(&account.to_string()));
+
+        let detailed = MetricsRegistry::new(true);
+        detailed.record_signing(369, account, Duration::from_millis(3), true);
+        assert!(detailed.render().contains(&account.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let registry = MetricsRegistry::new(false);
+        registry.record_rpc(1, Duration::from_millis(10), true);
+
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        client
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        registry.handle_connection(&mut server).await.unwrap();
+        drop(server);
+
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("vaughan_rpc_requests_total{chain_id=\"1\",outcome=\"success\"} 1"));
+    }
+}
@@ -16,8 +16,12 @@
 //! - Correlation IDs are UUIDs for unique operation tracking
 //! - Privacy mode sanitizes all sensitive data from logs
 //! - Span context propagates across async boundaries
+//! - Sensitive operations are also kept in an HMAC-chained local log, see [`audit`]
+//! - Opt-in RPC, signing and error metrics are served locally, see [`metrics`]
 
 pub mod account_events;
+pub mod audit;
+pub mod metrics;
 pub mod opentelemetry;
 
 pub use account_events::*;
@@ -0,0 +1,192 @@
+//! Degraded-mode pricing
+//!
+//! When every price provider is unreachable, showing `$0.00` is wrong and
+//! alarming. The [`MultiSourcePriceOracle`](super::oracle::MultiSourcePriceOracle)
+//! instead falls back to the last price it saw, marked [`PriceFreshness::Stale`]
+//! with its age so views can say "as of 3h ago".
+//!
+//! Fiat values of transactions that confirm during an outage can't be priced.
+//! They wait in a [`FiatBackfillQueue`] and are valued once providers
+//! recover, at the first price available after the outage.
+
+use super::oracle::PriceKey;
+use super::TokenPrice;
+use alloy::primitives::{Address, TxHash};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Displayed prices older than this are shown with their age
+///
+/// Views refresh prices every 30 seconds, so a price this old means the
+/// recent refreshes failed.
+pub const STALE_PRICE_AGE: Duration = Duration::from_secs(2 * 60);
+
+/// Whether a price came from a provider just now
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceFreshness {
+    Live,
+    /// Last known price, fetched `age` ago
+    Stale {
+        age: Duration,
+    },
+}
+
+/// A price with its freshness
+#[derive(Debug, Clone)]
+pub struct QuotedPrice {
+    pub price: TokenPrice,
+    pub freshness: PriceFreshness,
+}
+
+impl QuotedPrice {
+    pub fn is_stale(&self) -> bool {
+        matches!(self.freshness, PriceFreshness::Stale { .. })
+    }
+
+    /// Price for display, e.g. `$1.23` or `$1.23 (as of 3h ago)`
+    pub fn display(&self) -> String {
+        match self.freshness {
+            PriceFreshness::Live => format!("${:.2}", self.price.price_usd),
+            PriceFreshness::Stale { age } => format!("${:.2} ({})", self.price.price_usd, describe_age(age)),
+        }
+    }
+}
+
+/// Human age of a price, e.g. `as of 3h ago`
+pub fn describe_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..=59 => "as of just now".to_string(),
+        60..=3_599 => format!("as of {}m ago", secs / 60),
+        3_600..=86_399 => format!("as of {}h ago", secs / 3_600),
+        _ => format!("as of {}d ago", secs / 86_400),
+    }
+}
+
+/// A confirmed transaction whose fiat value is still unknown
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingFiatValue {
+    pub tx_hash: TxHash,
+    pub chain_id: u64,
+    /// Token moved, `Address::ZERO` for the native token
+    pub token: Address,
+    /// Amount in whole tokens
+    pub amount: f64,
+    pub confirmed_at: DateTime<Utc>,
+}
+
+impl PendingFiatValue {
+    pub fn price_key(&self) -> PriceKey {
+        (self.chain_id, self.token)
+    }
+}
+
+/// Fiat value filled in after an outage
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiatBackfill {
+    pub tx_hash: TxHash,
+    pub confirmed_at: DateTime<Utc>,
+    pub usd_value: f64,
+    /// When the price used was quoted; after `confirmed_at`
+    pub priced_at: DateTime<Utc>,
+}
+
+/// Transactions waiting for a fiat value
+#[derive(Debug, Clone, Default)]
+pub struct FiatBackfillQueue {
+    pending: Vec<PendingFiatValue>,
+}
+
+impl FiatBackfillQueue {
+    /// Queue a transaction; a transaction already queued is replaced
+    pub fn push(&mut self, entry: PendingFiatValue) {
+        self.pending.retain(|p| p.tx_hash != entry.tx_hash);
+        self.pending.push(entry);
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Price keys needed to resolve the queue, without duplicates
+    pub fn price_keys(&self) -> Vec<PriceKey> {
+        let mut keys: Vec<PriceKey> = self.pending.iter().map(PendingFiatValue::price_key).collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    /// Value every entry `price_of` can price and remove it from the queue
+    pub fn resolve(&mut self, price_of: impl Fn(PriceKey) -> Option<TokenPrice>) -> Vec<FiatBackfill> {
+        let mut resolved = Vec::new();
+        self.pending.retain(|entry| match price_of(entry.price_key()) {
+            Some(price) => {
+                resolved.push(FiatBackfill {
+                    tx_hash: entry.tx_hash,
+                    confirmed_at: entry.confirmed_at,
+                    usd_value: entry.amount * price.price_usd,
+                    priced_at: price.last_updated,
+                });
+                false
+            }
+            None => true,
+        });
+        resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(chain_id: u64, usd: f64) -> TokenPrice {
+        TokenPrice {
+            token_address: Address::ZERO,
+            chain_id,
+            price_usd: usd,
+            price_change_24h: None,
+            last_updated: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_stale_prices_show_their_age() {
+        assert_eq!(describe_age(Duration::from_secs(10)), "as of just now");
+        assert_eq!(describe_age(Duration::from_secs(3 * 3_600 + 100)), "as of 3h ago");
+        assert_eq!(describe_age(Duration::from_secs(2 * 86_400)), "as of 2d ago");
+
+        let quote = QuotedPrice {
+            price: price(1, 3_000.0),
+            freshness: PriceFreshness::Stale {
+                age: Duration::from_secs(600),
+            },
+        };
+        assert!(quote.is_stale());
+        assert_eq!(quote.display(), "$3000.00 (as of 10m ago)");
+    }
+
+    #[test]
+    fn test_backfill_resolves_priced_entries_only() {
+        let mut queue = FiatBackfillQueue::default();
+        for (byte, chain_id) in [(1u8, 1u64), (2, 369), (1, 1)] {
+            queue.push(PendingFiatValue {
+                tx_hash: TxHash::repeat_byte(byte),
+                chain_id,
+                token: Address::ZERO,
+                amount: 2.0,
+                confirmed_at: Utc::now(),
+            });
+        }
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.price_keys(), vec![(1, Address::ZERO), (369, Address::ZERO)]);
+
+        let resolved = queue.resolve(|(chain_id, _)| (chain_id == 1).then(|| price(1, 1_500.0)));
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].usd_value, 3_000.0);
+        assert_eq!(queue.len(), 1);
+    }
+}
@@ -0,0 +1,279 @@
+//! Display currencies and FX conversion
+//!
+//! Prices are fetched and stored in USD; portfolio history stays in USD too,
+//! so changing the display currency never rewrites it. Values are converted
+//! to the currency chosen in `pricing.currency` when they are shown, using
+//! rates from CoinGecko's `exchange_rates` endpoint (quoted against BTC and
+//! rebased on USD here).
+
+use crate::error::{NetworkError, Result, VaughanError};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Currencies values can be displayed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FiatCurrency {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+    Jpy,
+    Brl,
+    Cad,
+    Aud,
+    Chf,
+    Cny,
+    Inr,
+    Krw,
+    Mxn,
+}
+
+impl FiatCurrency {
+    pub const ALL: [FiatCurrency; 12] = [
+        Self::Usd,
+        Self::Eur,
+        Self::Gbp,
+        Self::Jpy,
+        Self::Brl,
+        Self::Cad,
+        Self::Aud,
+        Self::Chf,
+        Self::Cny,
+        Self::Inr,
+        Self::Krw,
+        Self::Mxn,
+    ];
+
+    /// ISO 4217 code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Usd => "USD",
+            Self::Eur => "EUR",
+            Self::Gbp => "GBP",
+            Self::Jpy => "JPY",
+            Self::Brl => "BRL",
+            Self::Cad => "CAD",
+            Self::Aud => "AUD",
+            Self::Chf => "CHF",
+            Self::Cny => "CNY",
+            Self::Inr => "INR",
+            Self::Krw => "KRW",
+            Self::Mxn => "MXN",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Usd => "$",
+            Self::Eur => "€",
+            Self::Gbp => "£",
+            Self::Jpy | Self::Cny => "¥",
+            Self::Brl => "R$",
+            Self::Cad => "CA$",
+            Self::Aud => "A$",
+            Self::Chf => "CHF ",
+            Self::Inr => "₹",
+            Self::Krw => "₩",
+            Self::Mxn => "MX$",
+        }
+    }
+
+    /// Decimal places shown for amounts
+    pub fn decimals(&self) -> usize {
+        match self {
+            Self::Jpy | Self::Krw => 0,
+            _ => 2,
+        }
+    }
+
+    /// `amount` with the currency symbol, e.g. `€12.50` or `¥1234`
+    pub fn format(&self, amount: f64) -> String {
+        let sign = if amount < 0.0 { "-" } else { "" };
+        format!("{sign}{}{:.*}", self.symbol(), self.decimals(), amount.abs())
+    }
+}
+
+impl fmt::Display for FiatCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for FiatCurrency {
+    type Err = VaughanError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.code().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| VaughanError::ValidationError(format!("Unsupported display currency: {s}")))
+    }
+}
+
+/// An amount in a display currency
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FiatAmount {
+    pub amount: f64,
+    pub currency: FiatCurrency,
+}
+
+impl fmt::Display for FiatAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.currency.format(self.amount))
+    }
+}
+
+/// Units of each currency per US dollar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FxRates {
+    pub rates: HashMap<FiatCurrency, f64>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl FxRates {
+    /// Rates holding only USD, enough to display USD without a fetch
+    pub fn usd_only() -> Self {
+        Self {
+            rates: HashMap::from([(FiatCurrency::Usd, 1.0)]),
+            fetched_at: Utc::now(),
+        }
+    }
+
+    pub fn rate(&self, currency: FiatCurrency) -> Option<f64> {
+        match currency {
+            FiatCurrency::Usd => Some(1.0),
+            _ => self.rates.get(&currency).copied(),
+        }
+    }
+
+    /// Convert a USD value; `None` when the currency has no rate
+    pub fn convert(&self, usd: f64, currency: FiatCurrency) -> Option<FiatAmount> {
+        self.rate(currency).map(|rate| FiatAmount {
+            amount: usd * rate,
+            currency,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExchangeRatesResponse {
+    rates: HashMap<String, ExchangeRate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExchangeRate {
+    value: f64,
+}
+
+/// Parse a CoinGecko `exchange_rates` response into rates per USD
+pub fn parse_exchange_rates(body: &str) -> Result<FxRates> {
+    let response: ExchangeRatesResponse = serde_json::from_str(body).map_err(|e| NetworkError::RpcError {
+        message: format!("Failed to parse exchange rates: {e}"),
+    })?;
+    let usd = response
+        .rates
+        .get("usd")
+        .map(|r| r.value)
+        .filter(|v| *v > 0.0)
+        .ok_or_else(|| NetworkError::RpcError {
+            message: "Exchange rates have no USD rate".to_string(),
+        })?;
+
+    let rates = FiatCurrency::ALL
+        .into_iter()
+        .filter_map(|currency| {
+            let rate = response.rates.get(&currency.code().to_ascii_lowercase())?;
+            Some((currency, rate.value / usd))
+        })
+        .collect();
+    Ok(FxRates {
+        rates,
+        fetched_at: Utc::now(),
+    })
+}
+
+/// Fetches FX rates from CoinGecko
+pub struct FxRateSource {
+    client: HttpClient,
+    base_url: String,
+}
+
+impl FxRateSource {
+    pub fn new() -> Self {
+        Self {
+            client: shared_client(),
+            base_url: "https://api.coingecko.com/api/v3".to_string(),
+        }
+    }
+
+    pub async fn fetch(&self) -> Result<FxRates> {
+        let url = format!("{}/exchange_rates", self.base_url);
+        egress().check(EgressCategory::PriceData, &url, "fetch exchange rates")?;
+
+        let response = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to fetch exchange rates: {e}"),
+            })?;
+        if !response.status().is_success() {
+            return Err(NetworkError::RpcError {
+                message: format!("Exchange rate API error {}", response.status()),
+            }
+            .into());
+        }
+        let body = response.text().await.map_err(|e| NetworkError::RpcError {
+            message: format!("Failed to read exchange rates: {e}"),
+        })?;
+        parse_exchange_rates(&body)
+    }
+}
+
+impl Default for FxRateSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_codes_and_formatting() {
+        assert_eq!("eur".parse::<FiatCurrency>().unwrap(), FiatCurrency::Eur);
+        assert!("XYZ".parse::<FiatCurrency>().is_err());
+        assert_eq!(FiatCurrency::Eur.format(12.5), "€12.50");
+        assert_eq!(FiatCurrency::Jpy.format(1234.4), "¥1234");
+        assert_eq!(FiatCurrency::Usd.format(-3.0), "-$3.00");
+        assert_eq!(serde_json::to_string(&FiatCurrency::Brl).unwrap(), "\"BRL\"");
+    }
+
+    #[test]
+    fn test_parse_exchange_rates() {
+        let body = r#"{"rates": {
+            "btc": {"name": "Bitcoin", "unit": "BTC", "value": 1.0, "type": "crypto"},
+            "usd": {"name": "US Dollar", "unit": "$", "value": 60000.0, "type": "fiat"},
+            "eur": {"name": "Euro", "unit": "€", "value": 54000.0, "type": "fiat"},
+            "jpy": {"name": "Japanese Yen", "unit": "¥", "value": 9000000.0, "type": "fiat"}
+        }}"#;
+        let rates = parse_exchange_rates(body).unwrap();
+        assert!((rates.rate(FiatCurrency::Eur).unwrap() - 0.9).abs() < 1e-9);
+        let yen = rates.convert(10.0, FiatCurrency::Jpy).unwrap();
+        assert!((yen.amount - 1500.0).abs() < 1e-6);
+        assert_eq!(rates.convert(10.0, FiatCurrency::Usd).unwrap().amount, 10.0);
+        assert!(rates.convert(10.0, FiatCurrency::Brl).is_none());
+
+        assert!(parse_exchange_rates(r#"{"rates": {}}"#).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Hidden tokens
+//!
+//! Users can hide tokens they never want to see (scam airdrops, dust, spam
+//! NFTs wrapped as ERC-20s). Hidden tokens are excluded from balances, token
+//! pickers and portfolio totals, and can be reviewed and unhidden again.
+//!
+//! The hidden set is stored per network in `<config_dir>/vaughan/hidden_tokens.json`.
+
+use super::{TokenBalance, TokenInfo};
+use crate::error::{ConfigurationError, Result};
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Get the storage path for hidden tokens
+pub fn get_hidden_tokens_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("vaughan");
+    path.push("hidden_tokens.json");
+    path
+}
+
+/// A token the user chose to hide
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HiddenToken {
+    pub address: Address,
+    pub symbol: String,
+    pub name: String,
+    pub hidden_at: DateTime<Utc>,
+    /// Optional user note, e.g. "scam airdrop"
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Hidden token set, keyed by chain ID
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HiddenTokens {
+    #[serde(default)]
+    networks: HashMap<u64, BTreeMap<Address, HiddenToken>>,
+}
+
+impl HiddenTokens {
+    /// Load the hidden set from the default location
+    pub fn load() -> Self {
+        Self::load_from(&get_hidden_tokens_path())
+    }
+
+    /// Load the hidden set from a file, falling back to an empty set
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid hidden tokens file {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the hidden set to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_hidden_tokens_path())
+    }
+
+    /// Save the hidden set to a file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self).map_err(|e| ConfigurationError::ParseError {
+            message: format!("Failed to serialize hidden tokens: {e}"),
+        })?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Hide a token; returns `false` for native tokens, which can't be hidden
+    pub fn hide(&mut self, token: &TokenInfo, reason: Option<String>) -> bool {
+        if token.is_native {
+            return false;
+        }
+
+        self.networks.entry(token.chain_id).or_default().insert(
+            token.address,
+            HiddenToken {
+                address: token.address,
+                symbol: token.symbol.clone(),
+                name: token.name.clone(),
+                hidden_at: Utc::now(),
+                reason,
+            },
+        );
+        true
+    }
+
+    /// Unhide a token; returns whether it was hidden
+    pub fn unhide(&mut self, chain_id: u64, address: Address) -> bool {
+        let Some(hidden) = self.networks.get_mut(&chain_id) else {
+            return false;
+        };
+        let removed = hidden.remove(&address).is_some();
+        if hidden.is_empty() {
+            self.networks.remove(&chain_id);
+        }
+        removed
+    }
+
+    /// The hidden entry for a token, if hidden
+    pub fn get(&self, chain_id: u64, address: Address) -> Option<&HiddenToken> {
+        self.networks.get(&chain_id)?.get(&address)
+    }
+
+    /// Put back a previously removed entry, keeping its original details
+    pub fn restore(&mut self, chain_id: u64, token: HiddenToken) {
+        self.networks.entry(chain_id).or_default().insert(token.address, token);
+    }
+
+    /// Check whether a token is hidden on a network
+    pub fn is_hidden(&self, chain_id: u64, address: Address) -> bool {
+        self.networks
+            .get(&chain_id)
+            .is_some_and(|hidden| hidden.contains_key(&address))
+    }
+
+    /// Hidden tokens on a network, most recently hidden first
+    pub fn hidden_for_network(&self, chain_id: u64) -> Vec<HiddenToken> {
+        let mut tokens: Vec<HiddenToken> = self
+            .networks
+            .get(&chain_id)
+            .map(|hidden| hidden.values().cloned().collect())
+            .unwrap_or_default();
+        tokens.sort_by_key(|token| std::cmp::Reverse(token.hidden_at));
+        tokens
+    }
+
+    /// Total number of hidden tokens across all networks
+    pub fn len(&self) -> usize {
+        self.networks.values().map(|hidden| hidden.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove hidden tokens from a token list
+    pub fn retain_visible(&self, tokens: &mut Vec<TokenInfo>) {
+        tokens.retain(|token| !self.is_hidden(token.chain_id, token.address));
+    }
+
+    /// Remove hidden tokens from a balance list
+    pub fn retain_visible_balances(&self, balances: &mut Vec<TokenBalance>) {
+        balances.retain(|balance| !self.is_hidden(balance.token.chain_id, balance.token.address));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(chain_id: u64, byte: u8, symbol: &str) -> TokenInfo {
+        TokenInfo::new(
+            Address::repeat_byte(byte),
+            chain_id,
+            symbol.to_string(),
+            symbol.to_string(),
+            18,
+        )
+    }
+
+    #[test]
+    fn test_hide_is_per_network() {
+        let mut hidden = HiddenTokens::default();
+        let scam = token(369, 0xaa, "SCAM");
+
+        assert!(hidden.hide(&scam, Some("airdrop".to_string())));
+        assert!(hidden.is_hidden(369, scam.address));
+        assert!(!hidden.is_hidden(1, scam.address));
+
+        let mut tokens = vec![scam.clone(), token(369, 0xbb, "HEX")];
+        hidden.retain_visible(&mut tokens);
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].symbol, "HEX");
+
+        assert!(hidden.unhide(369, scam.address));
+        assert!(!hidden.unhide(369, scam.address));
+        assert!(hidden.is_empty());
+    }
+
+    #[test]
+    fn test_native_token_cannot_be_hidden() {
+        let mut hidden = HiddenTokens::default();
+        let mut native = token(1, 0, "ETH");
+        native.is_native = true;
+
+        assert!(!hidden.hide(&native, None));
+        assert!(hidden.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hidden_tokens.json");
+
+        let mut hidden = HiddenTokens::default();
+        hidden.hide(&token(56, 0xcc, "SPAM"), None);
+        hidden.save_to(&path).unwrap();
+
+        let loaded = HiddenTokens::load_from(&path);
+        assert_eq!(loaded, hidden);
+        assert_eq!(loaded.hidden_for_network(56)[0].symbol, "SPAM");
+    }
+}
@@ -0,0 +1,283 @@
+//! Historical token balances
+//!
+//! Airdrops are usually decided by a holder snapshot at a fixed block.
+//! [`HistoricalBalanceReader`] answers "how much of this token did the
+//! account hold at block N" so users can check an eligibility claim before
+//! connecting to a claim site:
+//!
+//! - **archive**: `balanceOf` is called at the snapshot block, which needs an
+//!   endpoint that keeps historical state
+//! - **transfer logs**: on pruned endpoints the balance is rebuilt from the
+//!   token's `Transfer` events between a start block and the snapshot block.
+//!   The account is assumed to hold nothing at the start block, so it should
+//!   be the token's deployment block or earlier. Tokens that rebase or mint
+//!   without `Transfer` events cannot be rebuilt this way.
+
+use super::nft::MAX_LOG_BLOCK_RANGE;
+use crate::error::{Result, VaughanError};
+use alloy::eips::BlockId;
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log, TransactionRequest};
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolEvent};
+use std::fmt;
+
+sol! {
+    interface IErc20History {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
+
+/// Where a historical balance came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceSource {
+    Archive,
+    /// Rebuilt from `Transfer` events since `from_block`
+    TransferLogs {
+        from_block: u64,
+    },
+}
+
+/// An account's token balance at one block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoricalBalance {
+    pub token: Address,
+    pub account: Address,
+    pub block: u64,
+    pub balance: U256,
+    pub source: BalanceSource,
+}
+
+impl HistoricalBalance {
+    /// Compare against the minimum holding an airdrop requires
+    pub fn check(&self, required: U256) -> AirdropCheck {
+        AirdropCheck {
+            balance: self.clone(),
+            required,
+        }
+    }
+}
+
+/// Result of checking a balance against an airdrop's minimum holding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirdropCheck {
+    pub balance: HistoricalBalance,
+    pub required: U256,
+}
+
+impl AirdropCheck {
+    pub fn is_eligible(&self) -> bool {
+        self.balance.balance >= self.required
+    }
+
+    /// Amount missing to reach the minimum, zero when eligible
+    pub fn shortfall(&self) -> U256 {
+        self.required.saturating_sub(self.balance.balance)
+    }
+}
+
+impl fmt::Display for AirdropCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = &self.balance;
+        let source = match b.source {
+            BalanceSource::Archive => "archive state".to_string(),
+            BalanceSource::TransferLogs { from_block } => format!("transfer logs since block {from_block}"),
+        };
+        writeln!(
+            f,
+            "{} held {} of {} at block {} ({source})",
+            b.account, b.balance, b.token, b.block
+        )?;
+        if self.is_eligible() {
+            write!(f, "Eligible: meets the minimum of {}", self.required)
+        } else {
+            write!(
+                f,
+                "Not eligible: {} short of the minimum of {}",
+                self.shortfall(),
+                self.required
+            )
+        }
+    }
+}
+
+/// Net balance change of `account` from ERC-20 `Transfer` logs
+///
+/// Logs that are not ERC-20 transfers are ignored, as are duplicates of the
+/// same log, which self-transfers produce when from and to are queried apart.
+pub fn balance_from_transfers(account: Address, logs: &[Log]) -> U256 {
+    let mut seen = std::collections::HashSet::new();
+    let mut received = U256::ZERO;
+    let mut sent = U256::ZERO;
+    for log in logs {
+        if log.topics().len() != 3 || log.topics()[0] != IErc20History::Transfer::SIGNATURE_HASH {
+            continue;
+        }
+        if !seen.insert((log.block_number, log.transaction_index, log.log_index)) {
+            continue;
+        }
+        let Ok(event) = IErc20History::Transfer::decode_log_data(log.data()) else {
+            continue;
+        };
+        if event.to == account {
+            received = received.saturating_add(event.value);
+        }
+        if event.from == account {
+            sent = sent.saturating_add(event.value);
+        }
+    }
+    received.saturating_sub(sent)
+}
+
+/// Reads token balances at past blocks
+pub struct HistoricalBalanceReader<P> {
+    provider: P,
+}
+
+impl<P: Provider> HistoricalBalanceReader<P> {
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    /// Balance of `account` at `block`
+    ///
+    /// Archive state is tried first. When the endpoint has pruned it and a
+    /// `from_block` is given, the balance is rebuilt from transfer logs.
+    pub async fn balance_at(
+        &self,
+        token: Address,
+        account: Address,
+        block: u64,
+        from_block: Option<u64>,
+    ) -> Result<HistoricalBalance> {
+        let (balance, source) = match self.archive_balance(token, account, block).await {
+            Ok(balance) => (balance, BalanceSource::Archive),
+            Err(e) => {
+                let Some(from_block) = from_block else {
+                    return Err(e);
+                };
+                tracing::warn!("⚠️ No archive state at block {}, rebuilding from logs: {}", block, e);
+                let balance = self.balance_from_logs(token, account, from_block, block).await?;
+                (balance, BalanceSource::TransferLogs { from_block })
+            }
+        };
+        Ok(HistoricalBalance {
+            token,
+            account,
+            block,
+            balance,
+            source,
+        })
+    }
+
+    /// `balanceOf` at `block`; fails on endpoints without archive state
+    pub async fn archive_balance(&self, token: Address, account: Address, block: u64) -> Result<U256> {
+        let call = TransactionRequest::default()
+            .to(token)
+            .input(Bytes::from(IErc20History::balanceOfCall { account }.abi_encode()).into());
+        let data = self
+            .provider
+            .call(call)
+            .block(BlockId::number(block))
+            .await
+            .map_err(|e| VaughanError::rpc(&format!("Failed to read balance at block {block}"), e))?;
+        IErc20History::balanceOfCall::abi_decode_returns(&data)
+            .map_err(|e| VaughanError::rpc("Invalid balanceOf response", e))
+    }
+
+    /// Rebuild the balance at `to_block` from transfers since `from_block`
+    ///
+    /// The range is queried in chunks of [`MAX_LOG_BLOCK_RANGE`] blocks.
+    pub async fn balance_from_logs(
+        &self,
+        token: Address,
+        account: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<U256> {
+        if from_block > to_block {
+            return Err(VaughanError::ValidationError(format!(
+                "Start block {from_block} is after the snapshot block {to_block}"
+            )));
+        }
+        let account_topic = account.into_word();
+        let mut logs = Vec::new();
+        let mut start = from_block;
+        while start <= to_block {
+            let end = to_block.min(start.saturating_add(MAX_LOG_BLOCK_RANGE - 1));
+            let base = Filter::new()
+                .from_block(start)
+                .to_block(end)
+                .address(token)
+                .event_signature(IErc20History::Transfer::SIGNATURE_HASH);
+            for filter in [base.clone().topic1(account_topic), base.topic2(account_topic)] {
+                let chunk = self
+                    .provider
+                    .get_logs(&filter)
+                    .await
+                    .map_err(|e| VaughanError::rpc("Failed to fetch token transfer logs", e))?;
+                logs.extend(chunk);
+            }
+            start = end + 1;
+        }
+        Ok(balance_from_transfers(account, &logs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOLDER: Address = Address::repeat_byte(0xaa);
+    const OTHER: Address = Address::repeat_byte(0xbb);
+
+    fn transfer(from: Address, to: Address, value: u64, block: u64) -> Log {
+        let event = IErc20History::Transfer {
+            from,
+            to,
+            value: U256::from(value),
+        };
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xcc),
+                data: event.encode_log_data(),
+            },
+            block_number: Some(block),
+            log_index: Some(0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_balance_from_transfers() {
+        let self_transfer = transfer(HOLDER, HOLDER, 40, 13);
+        let logs = [
+            transfer(Address::ZERO, HOLDER, 100, 10),
+            transfer(HOLDER, OTHER, 30, 11),
+            transfer(OTHER, HOLDER, 5, 12),
+            // Matched by both the from and the to filter
+            self_transfer.clone(),
+            self_transfer,
+        ];
+        assert_eq!(balance_from_transfers(HOLDER, &logs), U256::from(75));
+        assert_eq!(balance_from_transfers(OTHER, &logs), U256::from(25));
+    }
+
+    #[test]
+    fn test_airdrop_check() {
+        let balance = HistoricalBalance {
+            token: Address::repeat_byte(0xcc),
+            account: HOLDER,
+            block: 100,
+            balance: U256::from(75),
+            source: BalanceSource::Archive,
+        };
+        assert!(balance.check(U256::from(75)).is_eligible());
+        let check = balance.check(U256::from(100));
+        assert!(!check.is_eligible());
+        assert_eq!(check.shortfall(), U256::from(25));
+        assert!(check.to_string().contains("Not eligible"));
+    }
+}
@@ -0,0 +1,207 @@
+//! On-chain token metadata discovery
+//!
+//! Reads `name()`, `symbol()` and `decimals()` straight from a token contract
+//! so custom tokens get their real metadata. Older tokens such as MKR return
+//! `bytes32` instead of `string` for name and symbol; both encodings are
+//! accepted. Addresses without contract code, or contracts that don't answer
+//! the ERC-20 calls, are rejected with a validation error.
+
+use super::TokenInfo;
+use crate::error::{NetworkError, Result, TokenError, VaughanError};
+use alloy::primitives::{Address, Bytes};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolValue};
+
+/// Largest decimals value a uint256 amount can meaningfully use
+pub const MAX_TOKEN_DECIMALS: u8 = 77;
+
+sol! {
+    interface IERC20Metadata {
+        function name() external view returns (string);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+        function totalSupply() external view returns (uint256);
+    }
+}
+
+/// Decode a `string` return value, falling back to a NUL-padded `bytes32`
+pub fn decode_string_or_bytes32(raw: &[u8]) -> Option<String> {
+    let text = match String::abi_decode(raw) {
+        Ok(text) => text,
+        Err(_) if raw.len() == 32 => {
+            let end = raw.iter().position(|b| *b == 0).unwrap_or(32);
+            std::str::from_utf8(&raw[..end]).ok()?.to_string()
+        }
+        Err(_) => return None,
+    };
+
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+fn not_erc20(address: Address, reason: &str) -> VaughanError {
+    VaughanError::ValidationError(format!("{address} is not an ERC-20 token: {reason}"))
+}
+
+/// Call a view function; `Ok(None)` if the contract reverted or returned nothing
+async fn try_call<P: Provider, C: SolCall>(provider: &P, address: Address, call: C) -> Result<Option<Bytes>> {
+    let request = TransactionRequest::default()
+        .to(address)
+        .input(call.abi_encode().into());
+    match provider.call(request).await {
+        Ok(raw) if raw.is_empty() => Ok(None),
+        Ok(raw) => Ok(Some(raw)),
+        Err(e) if e.as_error_resp().is_some() => Ok(None),
+        Err(e) => Err(NetworkError::RpcError {
+            message: format!("Token metadata call to {address} failed: {e}"),
+        }
+        .into()),
+    }
+}
+
+/// Discover a token's metadata from its contract
+pub async fn discover_token_metadata<P: Provider>(provider: &P, chain_id: u64, address: Address) -> Result<TokenInfo> {
+    if address == Address::ZERO {
+        return Err(TokenError::InvalidAddress("the zero address is not a token contract".to_string()).into());
+    }
+
+    let code = provider
+        .get_code_at(address)
+        .await
+        .map_err(|e| NetworkError::RpcError {
+            message: format!("Failed to fetch code for {address}: {e}"),
+        })?;
+    if code.is_empty() {
+        return Err(not_erc20(address, "no contract code at this address"));
+    }
+
+    let decimals = try_call(provider, address, IERC20Metadata::decimalsCall {})
+        .await?
+        .and_then(|raw| IERC20Metadata::decimalsCall::abi_decode_returns(&raw).ok())
+        .ok_or_else(|| not_erc20(address, "decimals() is not implemented"))?;
+    if decimals > MAX_TOKEN_DECIMALS {
+        return Err(not_erc20(address, &format!("unsupported decimals {decimals}")));
+    }
+
+    if try_call(provider, address, IERC20Metadata::totalSupplyCall {})
+        .await?
+        .is_none_or(|raw| IERC20Metadata::totalSupplyCall::abi_decode_returns(&raw).is_err())
+    {
+        return Err(not_erc20(address, "totalSupply() is not implemented"));
+    }
+
+    let symbol = try_call(provider, address, IERC20Metadata::symbolCall {})
+        .await?
+        .and_then(|raw| decode_string_or_bytes32(&raw));
+    let name = try_call(provider, address, IERC20Metadata::nameCall {})
+        .await?
+        .and_then(|raw| decode_string_or_bytes32(&raw));
+
+    let (name, symbol) = match (name, symbol) {
+        (Some(name), Some(symbol)) => (name, symbol),
+        (Some(name), None) => (name.clone(), name),
+        (None, Some(symbol)) => (symbol.clone(), symbol),
+        (None, None) => return Err(TokenError::MetadataDiscoveryFailed(address).into()),
+    };
+
+    tracing::info!(
+        "Discovered token {} ({}) with {} decimals at {}",
+        name,
+        symbol,
+        decimals,
+        address
+    );
+    Ok(TokenInfo::new(address, chain_id, name, symbol, decimals))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{B256, U256};
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+
+    const TOKEN: Address = Address::repeat_byte(0x9f);
+
+    fn bytes(value: impl SolValue) -> Bytes {
+        value.abi_encode().into()
+    }
+
+    #[test]
+    fn test_decode_standard_string() {
+        let raw = "Dai Stablecoin".to_string().abi_encode();
+        assert_eq!(decode_string_or_bytes32(&raw).as_deref(), Some("Dai Stablecoin"));
+    }
+
+    #[test]
+    fn test_decode_bytes32_symbol() {
+        // MKR's symbol() returns bytes32("MKR")
+        let mut word = [0u8; 32];
+        word[..3].copy_from_slice(b"MKR");
+        assert_eq!(
+            decode_string_or_bytes32(B256::from(word).as_slice()).as_deref(),
+            Some("MKR")
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert_eq!(decode_string_or_bytes32(&[]), None);
+        assert_eq!(decode_string_or_bytes32(&[0u8; 32]), None);
+        assert_eq!(decode_string_or_bytes32(&[0xffu8; 32]), None);
+        assert_eq!(decode_string_or_bytes32(&[1, 2, 3]), None);
+    }
+
+    #[tokio::test]
+    async fn test_discover_bytes32_token() {
+        let asserter = Asserter::new();
+        asserter.push_success(&Bytes::from_static(&[0x60, 0x80]));
+        asserter.push_success(&bytes(U256::from(18)));
+        asserter.push_success(&bytes(U256::from(1_000_000)));
+        let mut symbol = [0u8; 32];
+        symbol[..3].copy_from_slice(b"MKR");
+        asserter.push_success(&Bytes::from(symbol.to_vec()));
+        asserter.push_success(&bytes("Maker".to_string()));
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+
+        let token = discover_token_metadata(&provider, 1, TOKEN).await.unwrap();
+        assert_eq!(
+            (token.name.as_str(), token.symbol.as_str(), token.decimals),
+            ("Maker", "MKR", 18)
+        );
+        assert!(!token.is_native);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_non_contracts() {
+        let asserter = Asserter::new();
+        asserter.push_success(&Bytes::new());
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+
+        let error = discover_token_metadata(&provider, 1, TOKEN).await.unwrap_err();
+        assert!(matches!(error, VaughanError::ValidationError(ref msg) if msg.contains("no contract code")));
+        assert!(discover_token_metadata(&provider, 1, Address::ZERO).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_contracts_without_decimals() {
+        let asserter = Asserter::new();
+        asserter.push_success(&Bytes::from_static(&[0x60, 0x80]));
+        // Reverts come back as a JSON-RPC error response
+        asserter.push_failure(
+            serde_json::from_value(serde_json::json!({ "code": 3, "message": "execution reverted" })).unwrap(),
+        );
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+
+        let error = discover_token_metadata(&provider, 1, TOKEN).await.unwrap_err();
+        assert!(matches!(error, VaughanError::ValidationError(ref msg) if msg.contains("decimals()")));
+    }
+}
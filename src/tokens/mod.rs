@@ -10,8 +10,18 @@ use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub mod degraded;
+pub mod fx;
+pub mod hidden;
+pub mod historical;
 pub mod lists;
+pub mod metadata;
+pub mod nft;
+pub mod oracle;
+pub mod preflight;
 pub mod pricing;
+pub mod sorting;
+pub mod wrapped;
 
 /// Token metadata information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -300,13 +310,28 @@ impl TokenFilter {
     }
 }
 
-/// Token balance with USD value
+/// Token balance with USD value and value in the display currency
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenBalance {
     pub token: TokenInfo,
     pub balance: String,        // Raw balance as string to preserve precision
     pub formatted: String,      // Human-readable balance (e.g., "1.234567")
     pub usd_value: Option<f64>, // USD value if price is available
+    /// Value in the display currency if price and exchange rate are available
+    #[serde(default)]
+    pub fiat_value: Option<fx::FiatAmount>,
+}
+
+impl TokenBalance {
+    /// Update `usd_value` from a price quote for this token
+    pub fn apply_price(&mut self, price: &TokenPrice) {
+        self.usd_value = self.formatted.parse::<f64>().ok().map(|amount| amount * price.price_usd);
+    }
+
+    /// Update `fiat_value` from `usd_value` in `currency`
+    pub fn apply_fx(&mut self, rates: &fx::FxRates, currency: fx::FiatCurrency) {
+        self.fiat_value = self.usd_value.and_then(|usd| rates.convert(usd, currency));
+    }
 }
 
 /// Token price information
@@ -352,9 +377,17 @@ pub struct TokenManager {
     /// Cached prices by (chain_id, token_address)
     token_prices: HashMap<(u64, Address), TokenPrice>,
     /// HTTP client for API calls
-    client: reqwest::Client,
+    client: crate::network::http::HttpClient,
     /// Custom tokens added by user
     custom_tokens: HashMap<NetworkId, Vec<TokenInfo>>,
+    /// Tokens hidden by the user, excluded from lists and pickers
+    hidden_tokens: hidden::HiddenTokens,
+    /// Where the hidden token set is persisted
+    hidden_tokens_path: std::path::PathBuf,
+    /// How balance views order tokens
+    sort_preference: sorting::TokenSortPreference,
+    /// Where the sort preference is persisted
+    sort_preference_path: std::path::PathBuf,
 }
 
 impl TokenManager {
@@ -363,13 +396,56 @@ impl TokenManager {
         Self {
             token_lists: HashMap::new(),
             token_prices: HashMap::new(),
-            client: reqwest::Client::new(),
+            client: crate::network::http::shared_client(),
             custom_tokens: HashMap::new(),
+            hidden_tokens: hidden::HiddenTokens::load(),
+            hidden_tokens_path: hidden::get_hidden_tokens_path(),
+            sort_preference: sorting::TokenSortPreference::load(),
+            sort_preference_path: sorting::get_token_sort_path(),
         }
     }
 
-    /// Get token list for a specific network
+    /// Persist hidden tokens to a custom file instead of the default location
+    pub fn with_hidden_tokens_path(mut self, path: std::path::PathBuf) -> Self {
+        self.hidden_tokens = hidden::HiddenTokens::load_from(&path);
+        self.hidden_tokens_path = path;
+        self
+    }
+
+    /// Persist the sort preference to a custom file instead of the default location
+    pub fn with_sort_preference_path(mut self, path: std::path::PathBuf) -> Self {
+        self.sort_preference = sorting::TokenSortPreference::load_from(&path);
+        self.sort_preference_path = path;
+        self
+    }
+
+    /// The sort strategy the user chose for balance views
+    pub fn sort_strategy(&self) -> sorting::TokenSortStrategy {
+        self.sort_preference.strategy
+    }
+
+    /// Change and persist the sort strategy
+    pub fn set_sort_strategy(&mut self, strategy: sorting::TokenSortStrategy) -> Result<()> {
+        self.sort_preference.strategy = strategy;
+        tracing::info!("↕️ Token sort strategy set to {}", strategy);
+        self.sort_preference.save_to(&self.sort_preference_path)
+    }
+
+    /// Sort balances with the chosen strategy, using cached prices for 24h changes
+    pub fn sort_balances(&self, balances: &mut [TokenBalance], context: sorting::TokenSortContext) {
+        let context = context.with_prices(self.token_prices.values());
+        sorting::sort_tokens(balances, self.sort_strategy(), &context);
+    }
+
+    /// Get token list for a specific network, excluding hidden tokens
     pub fn get_tokens_for_network(&self, network_id: NetworkId) -> Vec<TokenInfo> {
+        let mut tokens = self.get_all_tokens_for_network(network_id);
+        self.hidden_tokens.retain_visible(&mut tokens);
+        tokens
+    }
+
+    /// Get token list for a specific network, including hidden tokens
+    pub fn get_all_tokens_for_network(&self, network_id: NetworkId) -> Vec<TokenInfo> {
         let mut tokens = self.token_lists.get(&network_id).cloned().unwrap_or_default();
 
         // Add custom tokens
@@ -380,6 +456,54 @@ impl TokenManager {
         tokens
     }
 
+    /// Hide a token from balances, pickers and portfolio totals
+    pub fn hide_token(&mut self, token: &TokenInfo, reason: Option<String>) -> Result<()> {
+        if !self.hidden_tokens.hide(token, reason) {
+            return Err(crate::error::VaughanError::ValidationError(format!(
+                "Native token {} cannot be hidden",
+                token.symbol
+            )));
+        }
+        tracing::info!("🙈 Hidden token {} on chain {}", token.symbol, token.chain_id);
+        self.hidden_tokens.save_to(&self.hidden_tokens_path)
+    }
+
+    /// Unhide a previously hidden token
+    pub fn unhide_token(&mut self, network_id: NetworkId, address: Address) -> Result<()> {
+        if !self.hidden_tokens.unhide(network_id.0, address) {
+            return Err(TokenError::NotFound(address).into());
+        }
+        tracing::info!("👁️ Unhidden token {} on chain {}", address, network_id.0);
+        self.hidden_tokens.save_to(&self.hidden_tokens_path)
+    }
+
+    /// Check whether a token is hidden
+    pub fn is_token_hidden(&self, network_id: NetworkId, address: Address) -> bool {
+        self.hidden_tokens.is_hidden(network_id.0, address)
+    }
+
+    /// The hidden entry for a token, if hidden
+    pub fn hidden_token(&self, network_id: NetworkId, address: Address) -> Option<hidden::HiddenToken> {
+        self.hidden_tokens.get(network_id.0, address).cloned()
+    }
+
+    /// Hide a token again with the details it was originally hidden with
+    pub fn restore_hidden_token(&mut self, network_id: NetworkId, token: hidden::HiddenToken) -> Result<()> {
+        tracing::info!("🙈 Restored hidden token {} on chain {}", token.symbol, network_id.0);
+        self.hidden_tokens.restore(network_id.0, token);
+        self.hidden_tokens.save_to(&self.hidden_tokens_path)
+    }
+
+    /// Hidden tokens on a network for review in the management screen
+    pub fn get_hidden_tokens(&self, network_id: NetworkId) -> Vec<hidden::HiddenToken> {
+        self.hidden_tokens.hidden_for_network(network_id.0)
+    }
+
+    /// The full hidden token set (e.g. to share with the portfolio manager)
+    pub fn hidden_tokens(&self) -> &hidden::HiddenTokens {
+        &self.hidden_tokens
+    }
+
     /// Search tokens by name or symbol
     pub async fn search_tokens(&self, network_id: NetworkId, query: String) -> Vec<TokenInfo> {
         let tokens = self.get_tokens_for_network(network_id);
@@ -525,29 +649,23 @@ impl TokenManager {
     }
 
     /// Add custom token with automatic metadata discovery
-    pub async fn add_custom_token_with_metadata(
+    ///
+    /// Reads name, symbol and decimals from the contract through `provider`,
+    /// which must be connected to `network_id`.
+    pub async fn add_custom_token_with_metadata<P: alloy::providers::Provider>(
         &mut self,
+        provider: &P,
         network_id: NetworkId,
         address: Address,
     ) -> Result<TokenInfo> {
         tracing::info!("Adding custom token: {:?} on network: {:?}", address, network_id);
 
         // First check if token already exists
-        if self.get_token_info(network_id, address).is_some() {
-            return self
-                .get_token_info(network_id, address)
-                .cloned()
-                .ok_or(crate::error::VaughanError::Token(TokenError::NotFound(address)));
+        if let Some(token) = self.get_token_info(network_id, address) {
+            return Ok(token.clone());
         }
 
-        // Create basic token info for now (metadata discovery can be added later)
-        let token_info = TokenInfo::new(
-            address,
-            network_id.0,
-            "Custom Token".to_string(),
-            "CUSTOM".to_string(),
-            18, // Default to 18 decimals
-        );
+        let token_info = metadata::discover_token_metadata(provider, network_id.0, address).await?;
 
         // Add to custom tokens
         self.add_custom_token(network_id, token_info.clone());
@@ -564,6 +682,11 @@ impl TokenManager {
     /// Load token list from URL
     pub async fn load_token_list_from_url(&mut self, url: &str) -> Result<TokenList> {
         tracing::info!("Loading token list from: {}", url);
+        crate::network::egress::egress().check(
+            crate::network::egress::EgressCategory::TokenLists,
+            url,
+            "load token list",
+        )?;
 
         let response = self
             .client
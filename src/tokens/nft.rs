@@ -0,0 +1,671 @@
+//! NFT Module
+//!
+//! ERC-721 and ERC-1155 support:
+//!
+//! - **Discovery**: owned tokens are enumerated through `ERC721Enumerable` when a
+//!   collection supports it, and reconstructed from `Transfer` /
+//!   `TransferSingle` / `TransferBatch` logs otherwise
+//! - **Metadata**: token URIs are resolved (`ipfs://`, `ar://`, ERC-1155 `{id}`
+//!   substitution, inline `data:` URIs) and their JSON metadata fetched
+//! - **Transfers**: `safeTransferFrom` transactions are built for both standards
+
+use crate::blockchain::probe::ContractProber;
+use crate::error::{NetworkError, Result, TokenError, VaughanError};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient};
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log, TransactionRequest};
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolEvent};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Default gateway used for `ipfs://` URIs
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// Maximum block range per `eth_getLogs` request
+pub const MAX_LOG_BLOCK_RANGE: u64 = 10_000;
+
+/// ERC-165 interface IDs
+pub const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+pub const ERC721_ENUMERABLE_INTERFACE_ID: [u8; 4] = [0x78, 0x0e, 0x9d, 0x63];
+pub const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+
+sol! {
+    interface IERC721 {
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+
+        function balanceOf(address owner) external view returns (uint256);
+        function ownerOf(uint256 tokenId) external view returns (address);
+        function tokenURI(uint256 tokenId) external view returns (string);
+        function tokenOfOwnerByIndex(address owner, uint256 index) external view returns (uint256);
+        function safeTransferFrom(address from, address to, uint256 tokenId) external;
+    }
+
+    interface IERC1155 {
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+
+        function balanceOf(address account, uint256 id) external view returns (uint256);
+        function uri(uint256 id) external view returns (string);
+        function safeTransferFrom(address from, address to, uint256 id, uint256 amount, bytes data) external;
+    }
+}
+
+/// NFT token standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NftStandard {
+    Erc721,
+    Erc1155,
+}
+
+/// An NFT held by an account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Nft {
+    pub chain_id: u64,
+    pub contract: Address,
+    pub token_id: U256,
+    pub standard: NftStandard,
+    /// Units held; always 1 for ERC-721
+    pub balance: U256,
+}
+
+/// Attribute entry of NFT metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftAttribute {
+    #[serde(default)]
+    pub trait_type: Option<String>,
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+/// Token metadata JSON (ERC-721 / ERC-1155 metadata schema)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NftMetadata {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, alias = "image_url")]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub animation_url: Option<String>,
+    #[serde(default)]
+    pub attributes: Vec<NftAttribute>,
+}
+
+/// A decoded NFT transfer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftTransfer {
+    pub contract: Address,
+    pub standard: NftStandard,
+    pub from: Address,
+    pub to: Address,
+    pub token_id: U256,
+    pub amount: U256,
+    pub block_number: Option<u64>,
+    pub log_index: Option<u64>,
+}
+
+/// Decode an ERC-721 or ERC-1155 transfer log
+///
+/// ERC-20 `Transfer` logs share the ERC-721 topic but only index two
+/// parameters; they are ignored.
+pub fn decode_transfer_log(log: &Log) -> Vec<NftTransfer> {
+    let topics = log.topics();
+    let Some(signature) = topics.first() else {
+        return Vec::new();
+    };
+
+    let transfer = |standard, from, to, token_id, amount| NftTransfer {
+        contract: log.address(),
+        standard,
+        from,
+        to,
+        token_id,
+        amount,
+        block_number: log.block_number,
+        log_index: log.log_index,
+    };
+
+    match *signature {
+        IERC721::Transfer::SIGNATURE_HASH if topics.len() == 4 => IERC721::Transfer::decode_log_data(log.data())
+            .map(|e| vec![transfer(NftStandard::Erc721, e.from, e.to, e.tokenId, U256::from(1))])
+            .unwrap_or_default(),
+        IERC1155::TransferSingle::SIGNATURE_HASH => IERC1155::TransferSingle::decode_log_data(log.data())
+            .map(|e| vec![transfer(NftStandard::Erc1155, e.from, e.to, e.id, e.value)])
+            .unwrap_or_default(),
+        IERC1155::TransferBatch::SIGNATURE_HASH => IERC1155::TransferBatch::decode_log_data(log.data())
+            .map(|e| {
+                e.ids
+                    .iter()
+                    .zip(&e.values)
+                    .map(|(id, value)| transfer(NftStandard::Erc1155, e.from, e.to, *id, *value))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Replay transfers (in chain order) into the tokens `owner` ends up holding
+pub fn holdings_from_transfers(chain_id: u64, owner: Address, transfers: &[NftTransfer]) -> Vec<Nft> {
+    let mut ordered: Vec<&NftTransfer> = transfers.iter().collect();
+    ordered.sort_by_key(|t| (t.block_number, t.log_index));
+
+    let mut balances: HashMap<(Address, U256), (NftStandard, U256)> = HashMap::new();
+    for transfer in ordered {
+        let entry = balances
+            .entry((transfer.contract, transfer.token_id))
+            .or_insert((transfer.standard, U256::ZERO));
+        if transfer.from == owner {
+            entry.1 = entry.1.saturating_sub(transfer.amount);
+        }
+        if transfer.to == owner {
+            entry.1 = entry.1.saturating_add(transfer.amount);
+        }
+    }
+
+    let mut nfts: Vec<Nft> = balances
+        .into_iter()
+        .filter(|(_, (_, balance))| !balance.is_zero())
+        .map(|((contract, token_id), (standard, balance))| Nft {
+            chain_id,
+            contract,
+            token_id,
+            standard,
+            balance,
+        })
+        .collect();
+    nfts.sort_by_key(|n| (n.contract, n.token_id));
+    nfts
+}
+
+/// Turn a token URI into a fetchable URL
+///
+/// Substitutes the ERC-1155 `{id}` placeholder and maps `ipfs://` and `ar://`
+/// to HTTP gateways. `data:` URIs are returned unchanged.
+pub fn resolve_uri(uri: &str, token_id: U256, ipfs_gateway: &str) -> String {
+    let uri = uri.trim().replace("{id}", &format!("{token_id:064x}"));
+    let gateway = ipfs_gateway.trim_end_matches('/');
+
+    if let Some(path) = uri.strip_prefix("ipfs://") {
+        format!("{gateway}/{}", path.trim_start_matches("ipfs/"))
+    } else if let Some(path) = uri.strip_prefix("ar://") {
+        format!("https://arweave.net/{path}")
+    } else {
+        uri
+    }
+}
+
+/// Decode metadata embedded in a `data:application/json` URI
+pub fn parse_data_uri(uri: &str) -> Option<Result<NftMetadata>> {
+    let rest = uri.strip_prefix("data:application/json")?;
+    let (params, payload) = rest.split_once(',')?;
+
+    let json = if params.ends_with(";base64") {
+        match base64::engine::general_purpose::STANDARD.decode(payload) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return Some(Err(VaughanError::ValidationError(format!(
+                    "Invalid base64 token metadata: {e}"
+                ))))
+            }
+        }
+    } else {
+        payload.as_bytes().to_vec()
+    };
+
+    Some(parse_metadata(&json))
+}
+
+/// Parse token metadata JSON
+pub fn parse_metadata(json: &[u8]) -> Result<NftMetadata> {
+    serde_json::from_slice(json).map_err(|e| VaughanError::ValidationError(format!("Invalid token metadata: {e}")))
+}
+
+/// Build a `safeTransferFrom` transaction for an NFT
+///
+/// `amount` must be 1 for ERC-721 tokens and at most the held balance for
+/// ERC-1155 tokens.
+pub fn transfer_request(nft: &Nft, from: Address, to: Address, amount: U256) -> Result<TransactionRequest> {
+    if to == Address::ZERO {
+        return Err(TokenError::InvalidAddress("cannot transfer an NFT to the zero address".to_string()).into());
+    }
+    if amount.is_zero() {
+        return Err(VaughanError::ValidationError(
+            "Transfer amount must be positive".to_string(),
+        ));
+    }
+    if amount > nft.balance {
+        return Err(TokenError::InsufficientBalance.into());
+    }
+
+    let input = match nft.standard {
+        NftStandard::Erc721 => {
+            if amount != U256::from(1) {
+                return Err(VaughanError::ValidationError(
+                    "ERC-721 tokens are transferred one at a time".to_string(),
+                ));
+            }
+            IERC721::safeTransferFromCall {
+                from,
+                to,
+                tokenId: nft.token_id,
+            }
+            .abi_encode()
+        }
+        NftStandard::Erc1155 => IERC1155::safeTransferFromCall {
+            from,
+            to,
+            id: nft.token_id,
+            amount,
+            data: Bytes::new(),
+        }
+        .abi_encode(),
+    };
+
+    Ok(TransactionRequest::default()
+        .from(from)
+        .to(nft.contract)
+        .input(input.into()))
+}
+
+/// Reads NFT ownership and metadata from a chain
+pub struct NftScanner<P> {
+    provider: P,
+    chain_id: u64,
+    client: HttpClient,
+    ipfs_gateway: String,
+}
+
+impl<P: Provider> NftScanner<P> {
+    pub fn new(provider: P, chain_id: u64) -> Self {
+        Self {
+            provider,
+            chain_id,
+            client: shared_client(),
+            ipfs_gateway: DEFAULT_IPFS_GATEWAY.to_string(),
+        }
+    }
+
+    pub fn with_ipfs_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.ipfs_gateway = gateway.into();
+        self
+    }
+
+    async fn eth_call<C: SolCall>(&self, contract: Address, call: C) -> Result<C::Return> {
+        let request = TransactionRequest::default()
+            .to(contract)
+            .input(call.abi_encode().into());
+        let raw = self.provider.call(request).await.map_err(|e| NetworkError::RpcError {
+            message: format!("NFT contract call failed: {e}"),
+        })?;
+        C::abi_decode_returns(&raw).map_err(|e| {
+            NetworkError::RpcError {
+                message: format!("Failed to decode NFT contract response: {e}"),
+            }
+            .into()
+        })
+    }
+
+    /// Whether `contract` reports support for an ERC-165 interface
+    ///
+    /// Contracts without ERC-165 (or that revert) are treated as not supporting it.
+    pub async fn supports_interface(&self, contract: Address, interface_id: [u8; 4]) -> bool {
+        ContractProber::new(&self.provider)
+            .supports_interface(contract, interface_id)
+            .await
+    }
+
+    /// Detect whether a contract is an ERC-721 or ERC-1155 collection
+    ///
+    /// Uses the shared [`ContractProber`], so proxied collections are
+    /// recognised and contracts failing the ERC-165 self-check are not.
+    pub async fn detect_standard(&self, contract: Address) -> Option<NftStandard> {
+        match ContractProber::new(&self.provider).probe(contract).await {
+            Ok(profile) => profile.nft_standard(),
+            Err(e) => {
+                tracing::debug!("Could not probe NFT contract {}: {}", contract, e);
+                None
+            }
+        }
+    }
+
+    /// Enumerate an owner's tokens through `ERC721Enumerable`
+    pub async fn enumerate_erc721(&self, contract: Address, owner: Address) -> Result<Vec<Nft>> {
+        let count = self.eth_call(contract, IERC721::balanceOfCall { owner }).await?;
+        let count: u64 = count.try_into().map_err(|_| NetworkError::RpcError {
+            message: format!("Implausible NFT balance {count} reported by {contract}"),
+        })?;
+
+        let mut nfts = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let token_id = self
+                .eth_call(
+                    contract,
+                    IERC721::tokenOfOwnerByIndexCall {
+                        owner,
+                        index: U256::from(index),
+                    },
+                )
+                .await?;
+            nfts.push(Nft {
+                chain_id: self.chain_id,
+                contract,
+                token_id,
+                standard: NftStandard::Erc721,
+                balance: U256::from(1),
+            });
+        }
+        Ok(nfts)
+    }
+
+    /// Collect NFT transfers from or to `owner` in a block range
+    ///
+    /// Restricted to `contracts` when non-empty. The range is queried in
+    /// chunks of [`MAX_LOG_BLOCK_RANGE`] blocks.
+    pub async fn scan_transfers(
+        &self,
+        owner: Address,
+        contracts: &[Address],
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<NftTransfer>> {
+        let owner_topic = owner.into_word();
+        let erc721 = vec![IERC721::Transfer::SIGNATURE_HASH];
+        let erc1155 = vec![
+            IERC1155::TransferSingle::SIGNATURE_HASH,
+            IERC1155::TransferBatch::SIGNATURE_HASH,
+        ];
+
+        let mut transfers = Vec::new();
+        let mut start = from_block;
+        while start <= to_block {
+            let end = to_block.min(start.saturating_add(MAX_LOG_BLOCK_RANGE - 1));
+            let base = || {
+                let filter = Filter::new().from_block(start).to_block(end);
+                if contracts.is_empty() {
+                    filter
+                } else {
+                    filter.address(contracts.to_vec())
+                }
+            };
+
+            // ERC-721 indexes from/to as topics 1/2, ERC-1155 as topics 2/3
+            let filters = [
+                base().event_signature(erc721.clone()).topic1(owner_topic),
+                base().event_signature(erc721.clone()).topic2(owner_topic),
+                base().event_signature(erc1155.clone()).topic2(owner_topic),
+                base().event_signature(erc1155.clone()).topic3(owner_topic),
+            ];
+
+            for filter in &filters {
+                let logs = self
+                    .provider
+                    .get_logs(filter)
+                    .await
+                    .map_err(|e| NetworkError::RpcError {
+                        message: format!("Failed to fetch NFT transfer logs: {e}"),
+                    })?;
+                transfers.extend(logs.iter().flat_map(decode_transfer_log));
+            }
+
+            start = end + 1;
+        }
+
+        // Self-transfers match both the from and to filters
+        transfers.sort_by_key(|t| (t.block_number, t.log_index));
+        transfers.dedup_by_key(|t| (t.block_number, t.log_index, t.token_id, t.contract));
+        Ok(transfers)
+    }
+
+    /// Tokens held by `owner` in the given collections
+    ///
+    /// Enumerable ERC-721 collections are read directly; the others are
+    /// reconstructed from transfer logs since `from_block`.
+    pub async fn owned_nfts(&self, owner: Address, contracts: &[Address], from_block: u64) -> Result<Vec<Nft>> {
+        let mut nfts = Vec::new();
+        let mut scan = Vec::new();
+
+        for &contract in contracts {
+            if self.supports_interface(contract, ERC721_ENUMERABLE_INTERFACE_ID).await {
+                match self.enumerate_erc721(contract, owner).await {
+                    Ok(owned) => {
+                        nfts.extend(owned);
+                        continue;
+                    }
+                    Err(e) => tracing::warn!("⚠️ Enumeration of {} failed, scanning logs: {}", contract, e),
+                }
+            }
+            scan.push(contract);
+        }
+
+        if !scan.is_empty() {
+            let head = self
+                .provider
+                .get_block_number()
+                .await
+                .map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to get block number: {e}"),
+                })?;
+            let transfers = self.scan_transfers(owner, &scan, from_block, head).await?;
+            nfts.extend(holdings_from_transfers(self.chain_id, owner, &transfers));
+        }
+
+        Ok(nfts)
+    }
+
+    /// Current on-chain balance of an NFT for `owner`
+    pub async fn balance_of(&self, nft: &Nft, owner: Address) -> Result<U256> {
+        match nft.standard {
+            NftStandard::Erc721 => {
+                let holder = self
+                    .eth_call(nft.contract, IERC721::ownerOfCall { tokenId: nft.token_id })
+                    .await?;
+                Ok(U256::from(u8::from(holder == owner)))
+            }
+            NftStandard::Erc1155 => {
+                self.eth_call(
+                    nft.contract,
+                    IERC1155::balanceOfCall {
+                        account: owner,
+                        id: nft.token_id,
+                    },
+                )
+                .await
+            }
+        }
+    }
+
+    /// Raw token URI as reported by the contract
+    pub async fn token_uri(&self, nft: &Nft) -> Result<String> {
+        match nft.standard {
+            NftStandard::Erc721 => {
+                self.eth_call(nft.contract, IERC721::tokenURICall { tokenId: nft.token_id })
+                    .await
+            }
+            NftStandard::Erc1155 => {
+                self.eth_call(nft.contract, IERC1155::uriCall { id: nft.token_id })
+                    .await
+            }
+        }
+    }
+
+    /// Fetch and parse an NFT's metadata, with the image URL resolved
+    pub async fn metadata(&self, nft: &Nft) -> Result<NftMetadata> {
+        let uri = self.token_uri(nft).await?;
+
+        let mut metadata = match parse_data_uri(&uri) {
+            Some(parsed) => parsed?,
+            None => {
+                let url = resolve_uri(&uri, nft.token_id, &self.ipfs_gateway);
+                egress().check(EgressCategory::NftMetadata, &url, "fetch NFT metadata")?;
+                let response = self
+                    .client
+                    .get(&url)
+                    .timeout(Duration::from_secs(15))
+                    .send()
+                    .await
+                    .map_err(|e| NetworkError::RpcError {
+                        message: format!("Failed to fetch NFT metadata: {e}"),
+                    })?;
+                if !response.status().is_success() {
+                    return Err(NetworkError::RpcError {
+                        message: format!("NFT metadata request failed with {}", response.status()),
+                    }
+                    .into());
+                }
+                let body = response.bytes().await.map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to read NFT metadata: {e}"),
+                })?;
+                parse_metadata(&body)?
+            }
+        };
+
+        metadata.image = metadata
+            .image
+            .map(|image| resolve_uri(&image, nft.token_id, &self.ipfs_gateway));
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::LogData;
+
+    const OWNER: Address = Address::repeat_byte(0xaa);
+    const OTHER: Address = Address::repeat_byte(0xbb);
+    const COLLECTION: Address = Address::repeat_byte(0xcc);
+
+    fn rpc_log(data: LogData, block: u64, index: u64) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: COLLECTION,
+                data,
+            },
+            block_number: Some(block),
+            log_index: Some(index),
+            ..Default::default()
+        }
+    }
+
+    fn erc721_transfer(from: Address, to: Address, id: u64, block: u64) -> Log {
+        let event = IERC721::Transfer {
+            from,
+            to,
+            tokenId: U256::from(id),
+        };
+        rpc_log(event.encode_log_data(), block, 0)
+    }
+
+    #[test]
+    fn test_erc721_holdings_from_logs() {
+        let logs = [
+            erc721_transfer(Address::ZERO, OWNER, 1, 10),
+            erc721_transfer(Address::ZERO, OWNER, 2, 11),
+            erc721_transfer(OWNER, OTHER, 1, 12),
+        ];
+        let transfers: Vec<_> = logs.iter().flat_map(decode_transfer_log).collect();
+        let held = holdings_from_transfers(369, OWNER, &transfers);
+
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].token_id, U256::from(2));
+        assert_eq!(held[0].standard, NftStandard::Erc721);
+    }
+
+    #[test]
+    fn test_erc1155_batch_holdings() {
+        let batch = IERC1155::TransferBatch {
+            operator: OTHER,
+            from: Address::ZERO,
+            to: OWNER,
+            ids: vec![U256::from(7), U256::from(8)],
+            values: vec![U256::from(5), U256::from(1)],
+        };
+        let single = IERC1155::TransferSingle {
+            operator: OWNER,
+            from: OWNER,
+            to: OTHER,
+            id: U256::from(7),
+            value: U256::from(2),
+        };
+        let logs = [
+            rpc_log(batch.encode_log_data(), 1, 0),
+            rpc_log(single.encode_log_data(), 2, 0),
+        ];
+        let transfers: Vec<_> = logs.iter().flat_map(decode_transfer_log).collect();
+        let held = holdings_from_transfers(1, OWNER, &transfers);
+
+        assert_eq!(held.len(), 2);
+        assert_eq!((held[0].token_id, held[0].balance), (U256::from(7), U256::from(3)));
+        assert_eq!((held[1].token_id, held[1].balance), (U256::from(8), U256::from(1)));
+    }
+
+    #[test]
+    fn test_erc20_transfer_logs_are_ignored() {
+        let topics = vec![IERC721::Transfer::SIGNATURE_HASH, OWNER.into_word(), OTHER.into_word()];
+        let data = LogData::new_unchecked(topics, U256::from(100).to_be_bytes::<32>().to_vec().into());
+        assert!(decode_transfer_log(&rpc_log(data, 1, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_uri() {
+        assert_eq!(
+            resolve_uri("ipfs://ipfs/QmHash/1.json", U256::from(1), DEFAULT_IPFS_GATEWAY),
+            "https://ipfs.io/ipfs/QmHash/1.json"
+        );
+        assert_eq!(
+            resolve_uri("ar://tx", U256::from(1), DEFAULT_IPFS_GATEWAY),
+            "https://arweave.net/tx"
+        );
+        assert_eq!(
+            resolve_uri("https://api.example/{id}.json", U256::from(0x4cd), DEFAULT_IPFS_GATEWAY),
+            format!("https://api.example/{}4cd.json", "0".repeat(61))
+        );
+    }
+
+    #[test]
+    fn test_data_uri_metadata() {
+        let json = r#"{"name":"Beat #1","image":"ipfs://QmImage","attributes":[{"trait_type":"bpm","value":120}]}"#;
+        let encoded = base64::engine::general_purpose::STANDARD.encode(json);
+
+        let metadata = parse_data_uri(&format!("data:application/json;base64,{encoded}"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Beat #1"));
+        assert_eq!(metadata.attributes[0].value, serde_json::json!(120));
+
+        assert!(parse_data_uri("https://example.com/1.json").is_none());
+    }
+
+    #[test]
+    fn test_transfer_request_validation() {
+        let nft = Nft {
+            chain_id: 1,
+            contract: COLLECTION,
+            token_id: U256::from(9),
+            standard: NftStandard::Erc1155,
+            balance: U256::from(3),
+        };
+
+        let tx = transfer_request(&nft, OWNER, OTHER, U256::from(2)).unwrap();
+        let input = tx.input.input().unwrap();
+        assert_eq!(&input[..4], IERC1155::safeTransferFromCall::SELECTOR.as_slice());
+
+        assert!(transfer_request(&nft, OWNER, OTHER, U256::from(4)).is_err());
+        assert!(transfer_request(&nft, OWNER, Address::ZERO, U256::from(1)).is_err());
+
+        let erc721 = Nft {
+            standard: NftStandard::Erc721,
+            balance: U256::from(1),
+            ..nft
+        };
+        assert!(transfer_request(&erc721, OWNER, OTHER, U256::from(1)).is_ok());
+        assert!(transfer_request(&erc721, OWNER, OTHER, U256::from(2)).is_err());
+    }
+}
@@ -0,0 +1,1156 @@
+//! Price Oracle Module
+//!
+//! This module aggregates token prices from several independent sources behind a
+//! single [`PriceOracle`] trait:
+//!
+//! - **CoinGecko**: curated market prices (free tier, optional pro key)
+//! - **DexScreener**: DEX pair prices, useful for long-tail tokens
+//! - **On-chain DEX pools**: Uniswap V2 reserves and V3 `slot0` read straight from the chain
+//!
+//! [`MultiSourcePriceOracle`] queries the sources in priority order, only asking the
+//! next source for the tokens the previous ones could not price, and keeps a TTL
+//! cache so repeated balance refreshes do not hammer external APIs. When every
+//! provider fails it keeps serving last-known prices; see [`super::degraded`].
+
+use super::degraded::{FiatBackfill, FiatBackfillQueue, PendingFiatValue, PriceFreshness, QuotedPrice};
+use super::fx::{FiatAmount, FiatCurrency, FxRateSource, FxRates};
+use super::pricing::{CoinGeckoPriceProvider, PriceProvider};
+use super::{TokenBalance, TokenPrice};
+use crate::config::{PricingSettings, SectionWatch};
+use crate::error::{NetworkError, Result};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient};
+use alloy::primitives::{Address, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Cache key for a token price: `(chain_id, token_address)`
+pub type PriceKey = (u64, Address);
+
+/// Default time-to-live for cached prices
+pub const DEFAULT_PRICE_TTL: Duration = Duration::from_secs(300);
+
+/// How long fetched exchange rates stay fresh
+pub const FX_RATE_TTL: Duration = Duration::from_secs(3600);
+
+/// Maximum number of addresses sent to a batch price endpoint in one request
+const MAX_BATCH_SIZE: usize = 30;
+
+sol! {
+    interface IUniswapV2Pair {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
+    }
+
+    interface IUniswapV3Pool {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function slot0() external view returns (
+            uint160 sqrtPriceX96,
+            int24 tick,
+            uint16 observationIndex,
+            uint16 observationCardinality,
+            uint16 observationCardinalityNext,
+            uint8 feeProtocol,
+            bool unlocked
+        );
+    }
+}
+
+/// A source of USD token prices
+///
+/// Implementations return prices only for the tokens they know about; tokens
+/// they cannot price are simply omitted from the result so that callers can
+/// fall back to another source.
+#[async_trait::async_trait]
+pub trait PriceOracle: Send + Sync {
+    /// Short provider name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Fetch prices for the given tokens on a single chain
+    ///
+    /// `Address::ZERO` denotes the chain's native token.
+    async fn get_prices(&self, chain_id: u64, token_addresses: &[Address]) -> Result<Vec<TokenPrice>>;
+}
+
+// ============================================================================
+// CoinGecko
+// ============================================================================
+
+/// CoinGecko-backed oracle
+///
+/// Thin adapter over [`CoinGeckoPriceProvider`] that handles native tokens and
+/// request batching.
+pub struct CoinGeckoOracle {
+    provider: CoinGeckoPriceProvider,
+}
+
+impl CoinGeckoOracle {
+    pub fn new(api_key: Option<String>) -> Self {
+        Self {
+            provider: CoinGeckoPriceProvider::new(api_key),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceOracle for CoinGeckoOracle {
+    fn name(&self) -> &'static str {
+        "coingecko"
+    }
+
+    async fn get_prices(&self, chain_id: u64, token_addresses: &[Address]) -> Result<Vec<TokenPrice>> {
+        let mut prices = Vec::new();
+
+        if token_addresses.contains(&Address::ZERO) {
+            if let Some(native) = self.provider.get_native_token_price(chain_id).await? {
+                prices.push(native);
+            }
+        }
+
+        let erc20: Vec<Address> = token_addresses
+            .iter()
+            .copied()
+            .filter(|address| *address != Address::ZERO)
+            .collect();
+
+        for chunk in erc20.chunks(MAX_BATCH_SIZE) {
+            prices.extend(self.provider.get_token_prices(chain_id, chunk).await?);
+        }
+
+        Ok(prices)
+    }
+}
+
+// ============================================================================
+// DexScreener
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct DexScreenerPair {
+    #[serde(rename = "baseToken")]
+    base_token: DexScreenerToken,
+    #[serde(rename = "priceUsd")]
+    price_usd: Option<String>,
+    #[serde(rename = "priceChange", default)]
+    price_change: Option<DexScreenerPriceChange>,
+    #[serde(default)]
+    liquidity: Option<DexScreenerLiquidity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DexScreenerToken {
+    address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DexScreenerPriceChange {
+    h24: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DexScreenerLiquidity {
+    usd: Option<f64>,
+}
+
+/// DexScreener-backed oracle
+///
+/// Prices each token from its most liquid pair. Native tokens are not
+/// supported and are left for other sources.
+pub struct DexScreenerOracle {
+    client: HttpClient,
+    base_url: String,
+}
+
+impl DexScreenerOracle {
+    pub fn new() -> Self {
+        Self {
+            client: shared_client(),
+            base_url: "https://api.dexscreener.com".to_string(),
+        }
+    }
+
+    /// Get DexScreener chain identifier for a chain ID
+    fn get_chain_slug(chain_id: u64) -> Option<&'static str> {
+        match chain_id {
+            1 => Some("ethereum"),
+            10 => Some("optimism"),
+            56 => Some("bsc"),
+            137 => Some("polygon"),
+            369 => Some("pulsechain"),
+            8453 => Some("base"),
+            42161 => Some("arbitrum"),
+            _ => None,
+        }
+    }
+
+    /// Pick the most liquid pair per base token and convert it to a price
+    fn best_prices(chain_id: u64, pairs: Vec<DexScreenerPair>) -> Vec<TokenPrice> {
+        let now = chrono::Utc::now();
+        let mut best: HashMap<Address, (f64, TokenPrice)> = HashMap::new();
+
+        for pair in pairs {
+            let Ok(address) = pair.base_token.address.parse::<Address>() else {
+                continue;
+            };
+            let Some(price_usd) = pair.price_usd.as_deref().and_then(|p| p.parse::<f64>().ok()) else {
+                continue;
+            };
+            let liquidity = pair.liquidity.and_then(|l| l.usd).unwrap_or(0.0);
+
+            if best.get(&address).is_some_and(|(current, _)| *current >= liquidity) {
+                continue;
+            }
+
+            best.insert(
+                address,
+                (
+                    liquidity,
+                    TokenPrice {
+                        token_address: address,
+                        chain_id,
+                        price_usd,
+                        price_change_24h: pair.price_change.and_then(|c| c.h24),
+                        last_updated: now,
+                    },
+                ),
+            );
+        }
+
+        best.into_values().map(|(_, price)| price).collect()
+    }
+}
+
+impl Default for DexScreenerOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceOracle for DexScreenerOracle {
+    fn name(&self) -> &'static str {
+        "dexscreener"
+    }
+
+    async fn get_prices(&self, chain_id: u64, token_addresses: &[Address]) -> Result<Vec<TokenPrice>> {
+        let Some(chain) = Self::get_chain_slug(chain_id) else {
+            tracing::debug!("Unsupported chain ID for DexScreener: {}", chain_id);
+            return Ok(vec![]);
+        };
+
+        let erc20: Vec<Address> = token_addresses
+            .iter()
+            .copied()
+            .filter(|address| *address != Address::ZERO)
+            .collect();
+
+        let mut prices = Vec::new();
+
+        for chunk in erc20.chunks(MAX_BATCH_SIZE) {
+            let addresses_str = chunk.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(",");
+            let url = format!("{}/tokens/v1/{}/{}", self.base_url, chain, addresses_str);
+            egress().check(EgressCategory::PriceData, &url, "fetch DexScreener prices")?;
+
+            let response = self
+                .client
+                .get(&url)
+                .timeout(Duration::from_secs(10))
+                .send()
+                .await
+                .map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to fetch DexScreener prices: {e}"),
+                })?;
+
+            if !response.status().is_success() {
+                return Err(NetworkError::RpcError {
+                    message: format!("DexScreener API error {}", response.status()),
+                }
+                .into());
+            }
+
+            let pairs: Vec<DexScreenerPair> = response.json().await.map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to parse DexScreener response: {e}"),
+            })?;
+
+            prices.extend(Self::best_prices(chain_id, pairs));
+        }
+
+        Ok(prices)
+    }
+}
+
+// ============================================================================
+// On-chain DEX pools
+// ============================================================================
+
+/// Uniswap pool flavour used to price a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DexPoolKind {
+    /// Constant-product pair exposing `getReserves()`
+    UniswapV2,
+    /// Concentrated-liquidity pool exposing `slot0()`
+    UniswapV3,
+}
+
+/// A pool used to derive a token price on-chain
+#[derive(Debug, Clone)]
+pub struct DexPool {
+    pub chain_id: u64,
+    /// Token being priced
+    pub token: Address,
+    pub token_decimals: u8,
+    /// Pool contract address
+    pub pool: Address,
+    pub kind: DexPoolKind,
+    /// Token the price is quoted in
+    pub quote_token: Address,
+    pub quote_decimals: u8,
+    /// USD price of one quote token (1.0 for stablecoins)
+    pub quote_price_usd: f64,
+}
+
+impl DexPool {
+    /// Pool quoting the token against a USD stablecoin
+    pub fn stable_pair(
+        chain_id: u64,
+        token: Address,
+        token_decimals: u8,
+        pool: Address,
+        kind: DexPoolKind,
+        stable: Address,
+        stable_decimals: u8,
+    ) -> Self {
+        Self {
+            chain_id,
+            token,
+            token_decimals,
+            pool,
+            kind,
+            quote_token: stable,
+            quote_decimals: stable_decimals,
+            quote_price_usd: 1.0,
+        }
+    }
+}
+
+/// Whether the priced token is `token0` of a pool holding `token0`/`token1`
+///
+/// A pool that doesn't trade exactly the configured token against its quote
+/// token would yield a meaningless price, so it is an error.
+pub fn token_is_token0(pool: &DexPool, token0: Address, token1: Address) -> Result<bool> {
+    if (token0, token1) == (pool.token, pool.quote_token) {
+        Ok(true)
+    } else if (token0, token1) == (pool.quote_token, pool.token) {
+        Ok(false)
+    } else {
+        Err(NetworkError::RpcError {
+            message: format!(
+                "Pool {} trades {token0}/{token1}, not {}/{}",
+                pool.pool, pool.token, pool.quote_token
+            ),
+        }
+        .into())
+    }
+}
+
+/// Convert an unsigned integer to `f64` (precision loss is acceptable for prices)
+fn u256_to_f64(value: U256) -> f64 {
+    value.to_string().parse::<f64>().unwrap_or(0.0)
+}
+
+/// Price of one `token` in quote units from Uniswap V2 reserves
+pub fn v2_price_from_reserves(
+    token_reserve: U256,
+    quote_reserve: U256,
+    token_decimals: u8,
+    quote_decimals: u8,
+) -> Option<f64> {
+    if token_reserve.is_zero() {
+        return None;
+    }
+    let token_amount = u256_to_f64(token_reserve) / 10f64.powi(token_decimals as i32);
+    let quote_amount = u256_to_f64(quote_reserve) / 10f64.powi(quote_decimals as i32);
+    Some(quote_amount / token_amount)
+}
+
+/// Price of one `token` in quote units from a Uniswap V3 `sqrtPriceX96`
+pub fn v3_price_from_sqrt(
+    sqrt_price_x96: U256,
+    token_is_token0: bool,
+    token_decimals: u8,
+    quote_decimals: u8,
+) -> Option<f64> {
+    if sqrt_price_x96.is_zero() {
+        return None;
+    }
+    let ratio = u256_to_f64(sqrt_price_x96) / 2f64.powi(96);
+    // Raw price of token0 denominated in token1
+    let raw = ratio * ratio;
+    let price = if token_is_token0 {
+        raw * 10f64.powi(token_decimals as i32 - quote_decimals as i32)
+    } else {
+        (1.0 / raw) * 10f64.powi(token_decimals as i32 - quote_decimals as i32)
+    };
+    price.is_finite().then_some(price)
+}
+
+/// Oracle reading prices directly from configured Uniswap V2/V3 pools
+pub struct OnChainDexOracle {
+    rpc_urls: HashMap<u64, String>,
+    pools: HashMap<PriceKey, DexPool>,
+}
+
+impl OnChainDexOracle {
+    pub fn new() -> Self {
+        Self {
+            rpc_urls: HashMap::new(),
+            pools: HashMap::new(),
+        }
+    }
+
+    /// Set the RPC endpoint used for a chain
+    pub fn with_rpc_url(mut self, chain_id: u64, rpc_url: impl Into<String>) -> Self {
+        self.rpc_urls.insert(chain_id, rpc_url.into());
+        self
+    }
+
+    /// Register a pool used to price its token
+    pub fn with_pool(mut self, pool: DexPool) -> Self {
+        self.pools.insert((pool.chain_id, pool.token), pool);
+        self
+    }
+
+    async fn eth_call(rpc_url: &str, to: Address, data: Vec<u8>) -> Result<alloy::primitives::Bytes> {
+        let provider = crate::network::connect_rpc(rpc_url, "DEX pool price")?;
+        let request = TransactionRequest::default().to(to).input(data.into());
+
+        provider.call(request).await.map_err(|e| {
+            NetworkError::RpcError {
+                message: format!("Pool call failed: {e}"),
+            }
+            .into()
+        })
+    }
+
+    async fn price_from_pool(rpc_url: &str, pool: &DexPool) -> Result<Option<f64>> {
+        let decode_err = |e: alloy::sol_types::Error| NetworkError::RpcError {
+            message: format!("Failed to decode pool response: {e}"),
+        };
+
+        match pool.kind {
+            DexPoolKind::UniswapV2 => {
+                let token0_raw = Self::eth_call(rpc_url, pool.pool, IUniswapV2Pair::token0Call {}.abi_encode()).await?;
+                let token0 = IUniswapV2Pair::token0Call::abi_decode_returns(&token0_raw).map_err(decode_err)?;
+                let token1_raw = Self::eth_call(rpc_url, pool.pool, IUniswapV2Pair::token1Call {}.abi_encode()).await?;
+                let token1 = IUniswapV2Pair::token1Call::abi_decode_returns(&token1_raw).map_err(decode_err)?;
+                let token_is_token0 = token_is_token0(pool, token0, token1)?;
+
+                let reserves_raw =
+                    Self::eth_call(rpc_url, pool.pool, IUniswapV2Pair::getReservesCall {}.abi_encode()).await?;
+                let reserves =
+                    IUniswapV2Pair::getReservesCall::abi_decode_returns(&reserves_raw).map_err(decode_err)?;
+
+                let reserve0 = U256::from(reserves.reserve0);
+                let reserve1 = U256::from(reserves.reserve1);
+                let (token_reserve, quote_reserve) = if token_is_token0 {
+                    (reserve0, reserve1)
+                } else {
+                    (reserve1, reserve0)
+                };
+
+                Ok(v2_price_from_reserves(
+                    token_reserve,
+                    quote_reserve,
+                    pool.token_decimals,
+                    pool.quote_decimals,
+                ))
+            }
+            DexPoolKind::UniswapV3 => {
+                let token0_raw = Self::eth_call(rpc_url, pool.pool, IUniswapV3Pool::token0Call {}.abi_encode()).await?;
+                let token0 = IUniswapV3Pool::token0Call::abi_decode_returns(&token0_raw).map_err(decode_err)?;
+                let token1_raw = Self::eth_call(rpc_url, pool.pool, IUniswapV3Pool::token1Call {}.abi_encode()).await?;
+                let token1 = IUniswapV3Pool::token1Call::abi_decode_returns(&token1_raw).map_err(decode_err)?;
+                let token_is_token0 = token_is_token0(pool, token0, token1)?;
+
+                let slot0_raw = Self::eth_call(rpc_url, pool.pool, IUniswapV3Pool::slot0Call {}.abi_encode()).await?;
+                let slot0 = IUniswapV3Pool::slot0Call::abi_decode_returns(&slot0_raw).map_err(decode_err)?;
+
+                Ok(v3_price_from_sqrt(
+                    U256::from(slot0.sqrtPriceX96),
+                    token_is_token0,
+                    pool.token_decimals,
+                    pool.quote_decimals,
+                ))
+            }
+        }
+    }
+}
+
+impl Default for OnChainDexOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceOracle for OnChainDexOracle {
+    fn name(&self) -> &'static str {
+        "onchain-dex"
+    }
+
+    async fn get_prices(&self, chain_id: u64, token_addresses: &[Address]) -> Result<Vec<TokenPrice>> {
+        let Some(rpc_url) = self.rpc_urls.get(&chain_id) else {
+            return Ok(vec![]);
+        };
+
+        let mut prices = Vec::new();
+
+        for address in token_addresses {
+            let Some(pool) = self.pools.get(&(chain_id, *address)) else {
+                continue;
+            };
+
+            match Self::price_from_pool(rpc_url, pool).await {
+                Ok(Some(quote_price)) => prices.push(TokenPrice {
+                    token_address: *address,
+                    chain_id,
+                    price_usd: quote_price * pool.quote_price_usd,
+                    price_change_24h: None,
+                    last_updated: chrono::Utc::now(),
+                }),
+                Ok(None) => tracing::debug!("Pool {} for {} has no liquidity", pool.pool, address),
+                Err(e) => tracing::warn!("Failed to read pool {} for {}: {}", pool.pool, address, e),
+            }
+        }
+
+        Ok(prices)
+    }
+}
+
+// ============================================================================
+// Multi-source oracle with caching
+// ============================================================================
+
+#[derive(Debug, Clone)]
+struct CachedPrice {
+    price: TokenPrice,
+    fetched_at: Instant,
+}
+
+/// Price oracle combining several sources with TTL caching and fallback
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use vaughan::tokens::oracle::{CoinGeckoOracle, DexScreenerOracle, MultiSourcePriceOracle};
+/// use alloy::primitives::Address;
+///
+/// # async fn example() -> vaughan::Result<()> {
+/// let oracle = MultiSourcePriceOracle::new()
+///     .with_provider(CoinGeckoOracle::new(None))
+///     .with_provider(DexScreenerOracle::new());
+///
+/// let prices = oracle.get_prices(vec![(1, Address::ZERO), (369, Address::ZERO)]).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MultiSourcePriceOracle {
+    providers: Vec<Arc<dyn PriceOracle>>,
+    cache: Arc<RwLock<HashMap<PriceKey, CachedPrice>>>,
+    /// Most recent price of every token, kept past the TTL for outages
+    last_known: Arc<RwLock<HashMap<PriceKey, CachedPrice>>>,
+    /// Set while every provider is failing
+    outage_since: Arc<RwLock<Option<chrono::DateTime<chrono::Utc>>>>,
+    backfill: Arc<RwLock<FiatBackfillQueue>>,
+    ttl: Duration,
+    /// Currency values are displayed in; prices stay in USD
+    currency: Arc<RwLock<FiatCurrency>>,
+    fx_source: Option<Arc<FxRateSource>>,
+    fx_rates: Arc<RwLock<Option<FxRates>>>,
+}
+
+impl MultiSourcePriceOracle {
+    /// Create an oracle with no providers and the default TTL
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            last_known: Arc::new(RwLock::new(HashMap::new())),
+            outage_since: Arc::new(RwLock::new(None)),
+            backfill: Arc::new(RwLock::new(FiatBackfillQueue::default())),
+            ttl: DEFAULT_PRICE_TTL,
+            currency: Arc::new(RwLock::new(FiatCurrency::Usd)),
+            fx_source: None,
+            fx_rates: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Create an oracle with the default public sources (CoinGecko, then DexScreener)
+    ///
+    /// Display currency and TTL come from the `pricing` settings; while
+    /// `pricing.enabled` is off the oracle has no sources and prices nothing.
+    pub fn with_default_providers() -> Self {
+        let pricing = crate::config::settings_store().section::<PricingSettings>();
+        let oracle = Self::new().with_settings(&pricing);
+        if !pricing.enabled {
+            tracing::info!("💱 Price fetching is disabled in the settings");
+            return oracle;
+        }
+        oracle
+            .with_provider(CoinGeckoOracle::new(None))
+            .with_provider(DexScreenerOracle::new())
+            .with_fx_source(FxRateSource::new())
+    }
+
+    /// Take the display currency and TTL from the `pricing` settings
+    pub fn with_settings(self, pricing: &PricingSettings) -> Self {
+        self.with_ttl(pricing.cache_ttl()).with_currency(pricing.fiat_currency())
+    }
+
+    /// Append a provider; providers are queried in insertion order
+    pub fn with_provider(mut self, provider: impl PriceOracle + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    /// Override the cache time-to-live
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Display values in `currency`
+    pub fn with_currency(self, currency: FiatCurrency) -> Self {
+        Self {
+            currency: Arc::new(RwLock::new(currency)),
+            ..self
+        }
+    }
+
+    /// Fetch exchange rates for non-USD display currencies from `source`
+    pub fn with_fx_source(mut self, source: FxRateSource) -> Self {
+        self.fx_source = Some(Arc::new(source));
+        self
+    }
+
+    /// Names of the configured providers in priority order
+    pub fn provider_names(&self) -> Vec<&'static str> {
+        self.providers.iter().map(|p| p.name()).collect()
+    }
+
+    /// Get prices for a batch of `(chain_id, address)` pairs
+    ///
+    /// Fresh cached prices are returned without network access. Missing tokens are
+    /// requested from each provider in turn until all are priced or providers are
+    /// exhausted. Tokens no provider could price are absent from the result.
+    pub async fn get_prices(&self, tokens: Vec<PriceKey>) -> Result<HashMap<PriceKey, TokenPrice>> {
+        let mut result = HashMap::new();
+        let mut missing: HashMap<u64, Vec<Address>> = HashMap::new();
+
+        {
+            let cache = self.cache.read().await;
+            for key in tokens {
+                match cache.get(&key) {
+                    Some(entry) if entry.fetched_at.elapsed() <= self.ttl => {
+                        result.insert(key, entry.price.clone());
+                    }
+                    _ => {
+                        let addresses = missing.entry(key.0).or_default();
+                        if !addresses.contains(&key.1) {
+                            addresses.push(key.1);
+                        }
+                    }
+                }
+            }
+        }
+
+        let (mut attempted, mut succeeded) = (0usize, 0usize);
+        for (chain_id, mut addresses) in missing {
+            for provider in &self.providers {
+                if addresses.is_empty() {
+                    break;
+                }
+
+                attempted += 1;
+                match provider.get_prices(chain_id, &addresses).await {
+                    Ok(prices) => {
+                        succeeded += 1;
+                        let mut cache = self.cache.write().await;
+                        let mut last_known = self.last_known.write().await;
+                        for price in prices {
+                            if !addresses.contains(&price.token_address) {
+                                continue;
+                            }
+                            let key = (chain_id, price.token_address);
+                            let entry = CachedPrice {
+                                price: price.clone(),
+                                fetched_at: Instant::now(),
+                            };
+                            cache.insert(key, entry.clone());
+                            last_known.insert(key, entry);
+                            result.insert(key, price);
+                        }
+                        addresses.retain(|address| !result.contains_key(&(chain_id, *address)));
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Price provider {} failed for chain {}: {}",
+                            provider.name(),
+                            chain_id,
+                            e
+                        );
+                    }
+                }
+            }
+
+            if !addresses.is_empty() {
+                tracing::debug!("No price source for {} tokens on chain {}", addresses.len(), chain_id);
+            }
+        }
+
+        if attempted > 0 {
+            self.record_availability(succeeded > 0).await;
+        }
+        Ok(result)
+    }
+
+    async fn record_availability(&self, available: bool) {
+        let mut outage_since = self.outage_since.write().await;
+        match (available, outage_since.is_some()) {
+            (false, false) => {
+                tracing::warn!("💸 All price providers failed; showing last-known prices");
+                *outage_since = Some(chrono::Utc::now());
+            }
+            (true, true) => {
+                tracing::info!("💸 Price providers recovered");
+                *outage_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Prices for `tokens`, falling back to the last known price
+    ///
+    /// Tokens no provider can price right now get their most recent price,
+    /// marked stale with its age. Tokens never priced are absent.
+    pub async fn quote_prices(&self, tokens: Vec<PriceKey>) -> Result<HashMap<PriceKey, QuotedPrice>> {
+        let live = self.get_prices(tokens.clone()).await?;
+        let last_known = self.last_known.read().await;
+
+        Ok(tokens
+            .into_iter()
+            .filter_map(|key| {
+                let quote = match live.get(&key) {
+                    Some(price) => QuotedPrice {
+                        price: price.clone(),
+                        freshness: PriceFreshness::Live,
+                    },
+                    None => {
+                        let entry = last_known.get(&key)?;
+                        QuotedPrice {
+                            price: entry.price.clone(),
+                            freshness: PriceFreshness::Stale {
+                                age: entry.fetched_at.elapsed(),
+                            },
+                        }
+                    }
+                };
+                Some((key, quote))
+            })
+            .collect())
+    }
+
+    /// Price of a single token, falling back to the last known price
+    pub async fn quote_price(&self, chain_id: u64, address: Address) -> Result<Option<QuotedPrice>> {
+        let mut quotes = self.quote_prices(vec![(chain_id, address)]).await?;
+        Ok(quotes.remove(&(chain_id, address)))
+    }
+
+    /// Whether every provider failed on the last request that needed one
+    pub async fn is_degraded(&self) -> bool {
+        self.outage_since.read().await.is_some()
+    }
+
+    /// When the current provider outage started
+    pub async fn outage_since(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        *self.outage_since.read().await
+    }
+
+    /// Value a transaction once prices are available again
+    pub async fn queue_fiat_backfill(&self, entry: PendingFiatValue) {
+        self.backfill.write().await.push(entry);
+    }
+
+    /// Transactions still waiting for a fiat value
+    pub async fn pending_backfill_count(&self) -> usize {
+        self.backfill.read().await.len()
+    }
+
+    /// Value queued transactions if providers have recovered
+    ///
+    /// Returns the transactions valued; the rest stay queued. Call after
+    /// price refreshes.
+    pub async fn backfill_fiat_values(&self) -> Result<Vec<FiatBackfill>> {
+        let keys = self.backfill.read().await.price_keys();
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let prices = self.get_prices(keys).await?;
+        if self.is_degraded().await {
+            return Ok(Vec::new());
+        }
+
+        let resolved = self.backfill.write().await.resolve(|key| prices.get(&key).cloned());
+        if !resolved.is_empty() {
+            tracing::info!("💸 Backfilled fiat values of {} transactions", resolved.len());
+        }
+        Ok(resolved)
+    }
+
+    /// Get the price of a single token
+    pub async fn get_price(&self, chain_id: u64, address: Address) -> Result<Option<TokenPrice>> {
+        let mut prices = self.get_prices(vec![(chain_id, address)]).await?;
+        Ok(prices.remove(&(chain_id, address)))
+    }
+
+    /// Fill `usd_value` and `fiat_value` on each balance using current prices
+    pub async fn apply_to_balances(&self, balances: &mut [TokenBalance]) -> Result<()> {
+        let keys: Vec<PriceKey> = balances.iter().map(|b| (b.token.chain_id, b.token.address)).collect();
+        let prices = self.get_prices(keys).await?;
+        let currency = self.currency().await;
+        let rates = match currency {
+            FiatCurrency::Usd => FxRates::usd_only(),
+            _ => self.fx_rates().await,
+        };
+
+        for balance in balances.iter_mut() {
+            if let Some(price) = prices.get(&(balance.token.chain_id, balance.token.address)) {
+                balance.apply_price(price);
+            }
+            balance.apply_fx(&rates, currency);
+        }
+
+        Ok(())
+    }
+
+    /// Currency values are displayed in
+    pub async fn currency(&self) -> FiatCurrency {
+        *self.currency.read().await
+    }
+
+    pub async fn set_currency(&self, currency: FiatCurrency) {
+        *self.currency.write().await = currency;
+    }
+
+    /// Keep the display currency in step with `pricing.currency`
+    ///
+    /// Stops following once the oracle is dropped.
+    pub fn follow_settings(self: &Arc<Self>, mut settings: SectionWatch<PricingSettings>) {
+        let oracle = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut pricing = settings.current().clone();
+            loop {
+                let Some(oracle) = oracle.upgrade() else {
+                    break;
+                };
+                oracle.set_currency(pricing.fiat_currency()).await;
+                drop(oracle);
+                match settings.changed().await {
+                    Some(changed) => {
+                        tracing::info!("💱 Display currency set to {}", changed.currency);
+                        pricing = changed;
+                    }
+                    None => break,
+                }
+            }
+        });
+    }
+
+    /// Use `rates` until they expire, e.g. rates restored from disk
+    pub async fn set_fx_rates(&self, rates: FxRates) {
+        *self.fx_rates.write().await = Some(rates);
+    }
+
+    /// Exchange rates from USD, refreshed once they are older than [`FX_RATE_TTL`]
+    ///
+    /// While the source fails, the last rates are kept. Without any rates
+    /// only USD can be converted.
+    pub async fn fx_rates(&self) -> FxRates {
+        let cached = self.fx_rates.read().await.clone();
+        let fresh = cached.as_ref().is_some_and(|rates| {
+            (chrono::Utc::now() - rates.fetched_at)
+                .to_std()
+                .is_ok_and(|age| age <= FX_RATE_TTL)
+        });
+        let Some(source) = self.fx_source.as_ref().filter(|_| !fresh) else {
+            return cached.unwrap_or_else(FxRates::usd_only);
+        };
+        match source.fetch().await {
+            Ok(rates) => {
+                *self.fx_rates.write().await = Some(rates.clone());
+                rates
+            }
+            Err(e) => {
+                tracing::warn!("💱 Exchange rates unavailable, using last known: {}", e);
+                cached.unwrap_or_else(FxRates::usd_only)
+            }
+        }
+    }
+
+    /// A USD value in the display currency
+    ///
+    /// `None` when no exchange rate for the display currency is available.
+    pub async fn to_fiat(&self, usd: f64) -> Option<FiatAmount> {
+        let currency = self.currency().await;
+        if currency == FiatCurrency::Usd {
+            return FxRates::usd_only().convert(usd, currency);
+        }
+        self.fx_rates().await.convert(usd, currency)
+    }
+
+    /// Drop expired entries from the cache
+    pub async fn prune_expired(&self) {
+        let ttl = self.ttl;
+        self.cache
+            .write()
+            .await
+            .retain(|_, entry| entry.fetched_at.elapsed() <= ttl);
+    }
+
+    /// Clear all cached prices, including last-known ones
+    pub async fn clear_cache(&self) {
+        self.cache.write().await.clear();
+        self.last_known.write().await.clear();
+    }
+
+    /// Number of cached prices (including expired ones not yet pruned)
+    pub async fn cached_count(&self) -> usize {
+        self.cache.read().await.len()
+    }
+}
+
+impl Default for MultiSourcePriceOracle {
+    fn default() -> Self {
+        Self::with_default_providers()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::TokenInfo;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct MockOracle {
+        name: &'static str,
+        prices: HashMap<PriceKey, f64>,
+        calls: Arc<AtomicUsize>,
+        fail: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl PriceOracle for MockOracle {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn get_prices(&self, chain_id: u64, token_addresses: &[Address]) -> Result<Vec<TokenPrice>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(NetworkError::Timeout.into());
+            }
+            Ok(token_addresses
+                .iter()
+                .filter_map(|a| {
+                    self.prices.get(&(chain_id, *a)).map(|p| TokenPrice {
+                        token_address: *a,
+                        chain_id,
+                        price_usd: *p,
+                        price_change_24h: None,
+                        last_updated: chrono::Utc::now(),
+                    })
+                })
+                .collect())
+        }
+    }
+
+    fn mock(name: &'static str, prices: &[(PriceKey, f64)], fail: bool) -> (MockOracle, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        (
+            MockOracle {
+                name,
+                prices: prices.iter().copied().collect(),
+                calls: calls.clone(),
+                fail,
+            },
+            calls,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_fallback_to_second_provider() {
+        let token_a = Address::from([1u8; 20]);
+        let token_b = Address::from([2u8; 20]);
+        let (first, _) = mock("first", &[((1, token_a), 2.0)], false);
+        let (second, second_calls) = mock("second", &[((1, token_a), 99.0), ((1, token_b), 3.0)], false);
+
+        let oracle = MultiSourcePriceOracle::new().with_provider(first).with_provider(second);
+        let prices = oracle.get_prices(vec![(1, token_a), (1, token_b)]).await.unwrap();
+
+        assert_eq!(prices[&(1, token_a)].price_usd, 2.0);
+        assert_eq!(prices[&(1, token_b)].price_usd, 3.0);
+        assert_eq!(second_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failing_provider_is_skipped() {
+        let token = Address::from([3u8; 20]);
+        let (broken, _) = mock("broken", &[], true);
+        let (working, _) = mock("working", &[((56, token), 0.5)], false);
+
+        let oracle = MultiSourcePriceOracle::new()
+            .with_provider(broken)
+            .with_provider(working);
+        let price = oracle.get_price(56, token).await.unwrap();
+
+        assert_eq!(price.map(|p| p.price_usd), Some(0.5));
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_and_expiry() {
+        let token = Address::from([4u8; 20]);
+        let (provider, calls) = mock("only", &[((1, token), 1.0)], false);
+        let oracle = MultiSourcePriceOracle::new().with_provider(provider);
+
+        oracle.get_price(1, token).await.unwrap();
+        oracle.get_price(1, token).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let (provider, calls) = mock("only", &[((1, token), 1.0)], false);
+        let oracle = MultiSourcePriceOracle::new()
+            .with_provider(provider)
+            .with_ttl(Duration::ZERO);
+        oracle.get_price(1, token).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        oracle.get_price(1, token).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_outage_serves_last_known_prices_and_backfills() {
+        let token = Address::from([6u8; 20]);
+        let (working, _) = mock("working", &[((1, token), 4.0)], false);
+        let mut oracle = MultiSourcePriceOracle::new()
+            .with_provider(working)
+            .with_ttl(Duration::ZERO);
+        oracle.get_price(1, token).await.unwrap();
+
+        let (broken, _) = mock("broken", &[], true);
+        oracle.providers = vec![Arc::new(broken)];
+        tokio::time::sleep(Duration::from_millis(2)).await;
+
+        assert!(oracle.get_price(1, token).await.unwrap().is_none());
+        assert!(oracle.is_degraded().await);
+        let quote = oracle.quote_price(1, token).await.unwrap().unwrap();
+        assert!(quote.is_stale());
+        assert_eq!(quote.price.price_usd, 4.0);
+
+        oracle
+            .queue_fiat_backfill(PendingFiatValue {
+                tx_hash: alloy::primitives::TxHash::repeat_byte(1),
+                chain_id: 1,
+                token,
+                amount: 3.0,
+                confirmed_at: chrono::Utc::now(),
+            })
+            .await;
+        assert!(oracle.backfill_fiat_values().await.unwrap().is_empty());
+
+        let (recovered, _) = mock("recovered", &[((1, token), 5.0)], false);
+        oracle.providers = vec![Arc::new(recovered)];
+        let backfilled = oracle.backfill_fiat_values().await.unwrap();
+        assert!(!oracle.is_degraded().await);
+        assert_eq!(backfilled.len(), 1);
+        assert_eq!(backfilled[0].usd_value, 15.0);
+        assert_eq!(oracle.pending_backfill_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_apply_to_balances() {
+        let token = Address::from([5u8; 20]);
+        let (provider, _) = mock("only", &[((1, token), 2.5)], false);
+        let oracle = MultiSourcePriceOracle::new().with_provider(provider);
+
+        let mut balances = vec![TokenBalance {
+            token: TokenInfo::new(token, 1, "Test".to_string(), "TST".to_string(), 18),
+            balance: "4000000000000000000".to_string(),
+            formatted: "4.0".to_string(),
+            usd_value: None,
+            fiat_value: None,
+        }];
+        oracle.apply_to_balances(&mut balances).await.unwrap();
+
+        assert_eq!(balances[0].usd_value, Some(10.0));
+        assert_eq!(balances[0].fiat_value.unwrap().to_string(), "$10.00");
+
+        oracle.set_currency(FiatCurrency::Eur).await;
+        oracle.apply_to_balances(&mut balances).await.unwrap();
+        // No exchange rates yet: the USD value stays, the EUR value is unknown
+        assert_eq!(balances[0].usd_value, Some(10.0));
+        assert!(balances[0].fiat_value.is_none());
+
+        oracle
+            .set_fx_rates(FxRates {
+                rates: HashMap::from([(FiatCurrency::Eur, 0.9)]),
+                fetched_at: chrono::Utc::now(),
+            })
+            .await;
+        oracle.apply_to_balances(&mut balances).await.unwrap();
+        assert_eq!(balances[0].fiat_value.unwrap().to_string(), "€9.00");
+        assert!((oracle.to_fiat(20.0).await.unwrap().amount - 18.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_v2_price_from_reserves() {
+        // 1,000 tokens (18 decimals) against 2,000 USDC (6 decimals) => $2
+        let token_reserve = U256::from(1_000u64) * U256::from(10u64).pow(U256::from(18));
+        let quote_reserve = U256::from(2_000_000_000u64);
+        let price = v2_price_from_reserves(token_reserve, quote_reserve, 18, 6).unwrap();
+        assert!((price - 2.0).abs() < 1e-9);
+
+        assert!(v2_price_from_reserves(U256::ZERO, quote_reserve, 18, 6).is_none());
+    }
+
+    #[test]
+    fn test_pool_must_trade_the_configured_pair() {
+        let (token, stable, other) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+        let pool = DexPool::stable_pair(1, token, 18, Address::repeat_byte(9), DexPoolKind::UniswapV2, stable, 6);
+
+        assert!(token_is_token0(&pool, token, stable).unwrap());
+        assert!(!token_is_token0(&pool, stable, token).unwrap());
+        assert!(token_is_token0(&pool, token, other).is_err());
+        assert!(token_is_token0(&pool, other, token).is_err());
+    }
+
+    #[test]
+    fn test_v3_price_from_sqrt() {
+        // sqrtPriceX96 = 2^96 means a raw price of 1 for equal decimals
+        let sqrt_price = U256::from(1u64) << 96;
+        let price = v3_price_from_sqrt(sqrt_price, true, 18, 18).unwrap();
+        assert!((price - 1.0).abs() < 1e-9);
+
+        // Raw price 4 (sqrt 2) inverted when the priced token is token1
+        let sqrt_price = U256::from(2u64) << 96;
+        let price = v3_price_from_sqrt(sqrt_price, false, 18, 18).unwrap();
+        assert!((price - 0.25).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,376 @@
+//! Token pre-flight checks
+//!
+//! Before a token transfer or swap is built, [`TokenPreflight`] looks for
+//! honeypot-style behaviour, common among fresh tokens on BSC and PulseChain:
+//!
+//! - **transfer tax**: the transfer is simulated with `eth_simulateV1` and the
+//!   recipient's balance change compared with the amount sent; a transfer
+//!   that reverts usually means the token cannot be sold
+//! - **pausing**: the token is paused, or exposes a `pause()` its owner can call
+//! - **blacklisting**: the sender or recipient is blacklisted, or the token
+//!   exposes functions its owner can blacklist holders with
+//!
+//! Owner-only functions are found by their selectors in the contract's
+//! dispatch code, so they are detected without calling them. Findings are
+//! advisory apart from those [`TokenRisk::is_blocking`] marks: a transfer
+//! that would fail anyway.
+
+use crate::defi::swap::SwapQuote;
+use crate::error::{Result, VaughanError};
+use crate::wallet::transaction::erc20::TokenTransfer;
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::simulate::{SimBlock, SimCallResult, SimulatePayload};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use std::fmt;
+
+sol! {
+    interface ITokenControls {
+        function balanceOf(address account) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function transfer(address to, uint256 amount) external returns (bool);
+        function paused() external view returns (bool);
+        function pause() external;
+        function isBlacklisted(address account) external view returns (bool);
+        function isBlackListed(address account) external view returns (bool);
+        function isBot(address account) external view returns (bool);
+        function blacklist(address account) external;
+        function addToBlacklist(address account) external;
+        function setBlacklist(address account, bool value) external;
+        function setBots(address[] accounts, bool value) external;
+    }
+}
+
+/// Transfer tax from which a token is treated as a honeypot: 25%
+pub const HONEYPOT_TAX_BPS: u32 = 2_500;
+
+/// Owner functions that add holders to a blacklist
+const BLACKLIST_SETTERS: [[u8; 4]; 4] = [
+    ITokenControls::blacklistCall::SELECTOR,
+    ITokenControls::addToBlacklistCall::SELECTOR,
+    ITokenControls::setBlacklistCall::SELECTOR,
+    ITokenControls::setBotsCall::SELECTOR,
+];
+
+/// Something about a token the user should know before sending or swapping it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenRisk {
+    /// The recipient receives `tax_bps` less than is sent
+    TransferTax {
+        tax_bps: u32,
+    },
+    TransferReverts {
+        reason: String,
+    },
+    Paused,
+    /// The owner can pause transfers at any time
+    Pausable,
+    Blacklisted {
+        account: Address,
+    },
+    /// The owner can blacklist holders at any time
+    BlacklistFunctions,
+    /// The node does not support `eth_simulateV1`, so taxes are unknown
+    SimulationUnavailable,
+}
+
+impl TokenRisk {
+    /// Whether the transfer would fail
+    pub fn is_blocking(&self) -> bool {
+        matches!(
+            self,
+            Self::TransferReverts { .. } | Self::Paused | Self::Blacklisted { .. }
+        )
+    }
+}
+
+impl fmt::Display for TokenRisk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TransferTax { tax_bps } => write!(
+                f,
+                "Transfers are taxed {:.2}%: the recipient receives less than is sent",
+                *tax_bps as f64 / 100.0
+            ),
+            Self::TransferReverts { reason } => write!(f, "A transfer of this token fails: {reason}"),
+            Self::Paused => f.write_str("Token transfers are paused"),
+            Self::Pausable => f.write_str("The token owner can pause all transfers"),
+            Self::Blacklisted { account } => write!(f, "{account} is blacklisted by the token"),
+            Self::BlacklistFunctions => f.write_str("The token owner can blacklist holders"),
+            Self::SimulationUnavailable => {
+                f.write_str("The node cannot simulate transfers; transfer taxes were not checked")
+            }
+        }
+    }
+}
+
+/// Findings for one token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflightReport {
+    pub token: Address,
+    /// Amount sent in the simulated transfer, zero when none was simulated
+    pub sent: U256,
+    /// Increase of the recipient's balance in the simulation
+    pub received: Option<U256>,
+    /// Current allowance of the spender that moves the tokens, for swaps
+    pub allowance: Option<U256>,
+    pub risks: Vec<TokenRisk>,
+}
+
+impl PreflightReport {
+    pub fn is_clear(&self) -> bool {
+        self.risks.is_empty()
+    }
+
+    /// Whether the token looks built to trap buyers
+    pub fn is_honeypot(&self) -> bool {
+        self.risks.iter().any(|risk| match risk {
+            TokenRisk::TransferTax { tax_bps } => *tax_bps >= HONEYPOT_TAX_BPS,
+            risk => risk.is_blocking(),
+        })
+    }
+
+    /// Whether the spender needs a larger allowance to move `sent`
+    pub fn needs_approval(&self) -> bool {
+        self.allowance.is_some_and(|allowance| allowance < self.sent)
+    }
+}
+
+/// Share of `sent` that did not arrive, in basis points
+pub fn tax_bps(sent: U256, received: U256) -> u32 {
+    if sent.is_zero() || received >= sent {
+        return 0;
+    }
+    ((sent - received).saturating_mul(U256::from(10_000)) / sent).saturating_to()
+}
+
+/// Whether `code` dispatches to `selector`, i.e. contains `PUSH4 selector`
+fn has_selector(code: &[u8], selector: [u8; 4]) -> bool {
+    code.windows(5).any(|w| w[0] == 0x63 && w[1..] == selector)
+}
+
+async fn read<P: Provider, C: SolCall>(provider: &P, token: Address, call: C) -> Result<C::Return> {
+    let raw = provider
+        .call(
+            TransactionRequest::default()
+                .to(token)
+                .input(Bytes::from(call.abi_encode()).into()),
+        )
+        .await
+        .map_err(|e| VaughanError::rpc(&format!("Failed to call {} on {token}", C::SIGNATURE), e))?;
+    C::abi_decode_returns(&raw).map_err(|e| VaughanError::rpc(&format!("Invalid {} response", C::SIGNATURE), e))
+}
+
+/// Runs the pre-flight checks against one chain
+pub struct TokenPreflight<P> {
+    provider: P,
+}
+
+impl<P: Provider> TokenPreflight<P> {
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    /// Check a token transfer from `from` before building it
+    ///
+    /// With a `spender`, as for a swap router pulling the tokens, its
+    /// current allowance from `from` is reported too.
+    pub async fn check(
+        &self,
+        transfer: &TokenTransfer,
+        from: Address,
+        spender: Option<Address>,
+    ) -> Result<PreflightReport> {
+        let mut report = self.inspect(transfer.token, &[from, transfer.to]).await?;
+        report.sent = transfer.amount;
+
+        if transfer.to != from {
+            match self.simulate_transfer(transfer, from).await {
+                Ok(Ok(received)) => {
+                    let tax_bps = tax_bps(transfer.amount, received);
+                    if tax_bps > 0 {
+                        report.risks.push(TokenRisk::TransferTax { tax_bps });
+                    }
+                    report.received = Some(received);
+                }
+                Ok(Err(reason)) => report.risks.push(TokenRisk::TransferReverts { reason }),
+                Err(e) => {
+                    tracing::debug!("Transfer simulation of {} unavailable: {}", transfer.token, e);
+                    report.risks.push(TokenRisk::SimulationUnavailable);
+                }
+            }
+        }
+
+        if let Some(spender) = spender {
+            let call = ITokenControls::allowanceCall { owner: from, spender };
+            report.allowance = Some(read(&self.provider, transfer.token, call).await?);
+        }
+        Ok(report)
+    }
+
+    /// Check both tokens of a swap before building it
+    ///
+    /// The sell token's transfer to the allowance target is simulated, which
+    /// misses taxes a token applies only on transfers into its pool. The
+    /// buy token can't be received in a simulation without the pool, so only
+    /// its pausing and blacklisting are checked.
+    pub async fn check_swap(&self, quote: &SwapQuote) -> Result<Vec<PreflightReport>> {
+        let mut reports = Vec::new();
+        if !quote.sell_token.is_native {
+            if let Some(spender) = quote.allowance_target {
+                let transfer = TokenTransfer {
+                    token: quote.sell_token.address,
+                    to: spender,
+                    amount: quote.sell_amount,
+                };
+                reports.push(self.check(&transfer, quote.taker, Some(spender)).await?);
+            } else {
+                reports.push(self.inspect(quote.sell_token.address, &[quote.taker]).await?);
+            }
+        }
+        if !quote.buy_token.is_native {
+            reports.push(self.inspect(quote.buy_token.address, &[quote.taker]).await?);
+        }
+        Ok(reports)
+    }
+
+    /// Pausing and blacklisting of `token` for `accounts`
+    async fn inspect(&self, token: Address, accounts: &[Address]) -> Result<PreflightReport> {
+        let code = self
+            .provider
+            .get_code_at(token)
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to fetch token code", e))?;
+        let mut risks = Vec::new();
+
+        if has_selector(&code, ITokenControls::pausedCall::SELECTOR)
+            && read(&self.provider, token, ITokenControls::pausedCall {})
+                .await
+                .unwrap_or(false)
+        {
+            risks.push(TokenRisk::Paused);
+        } else if has_selector(&code, ITokenControls::pauseCall::SELECTOR) {
+            risks.push(TokenRisk::Pausable);
+        }
+
+        for &account in accounts {
+            if self.is_blacklisted(&code, token, account).await {
+                risks.push(TokenRisk::Blacklisted { account });
+            }
+        }
+        if BLACKLIST_SETTERS.iter().any(|selector| has_selector(&code, *selector)) {
+            risks.push(TokenRisk::BlacklistFunctions);
+        }
+
+        Ok(PreflightReport {
+            token,
+            sent: U256::ZERO,
+            received: None,
+            allowance: None,
+            risks,
+        })
+    }
+
+    /// Whether any blacklist getter the token exposes lists `account`
+    async fn is_blacklisted(&self, code: &[u8], token: Address, account: Address) -> bool {
+        let provider = &self.provider;
+        (has_selector(code, ITokenControls::isBlacklistedCall::SELECTOR)
+            && read(provider, token, ITokenControls::isBlacklistedCall { account })
+                .await
+                .unwrap_or(false))
+            || (has_selector(code, ITokenControls::isBlackListedCall::SELECTOR)
+                && read(provider, token, ITokenControls::isBlackListedCall { account })
+                    .await
+                    .unwrap_or(false))
+            || (has_selector(code, ITokenControls::isBotCall::SELECTOR)
+                && read(provider, token, ITokenControls::isBotCall { account })
+                    .await
+                    .unwrap_or(false))
+    }
+
+    /// Amount the recipient would receive, or why the transfer fails
+    async fn simulate_transfer(
+        &self,
+        transfer: &TokenTransfer,
+        from: Address,
+    ) -> Result<std::result::Result<U256, String>> {
+        let balance = || {
+            TransactionRequest::default()
+                .to(transfer.token)
+                .input(Bytes::from(ITokenControls::balanceOfCall { account: transfer.to }.abi_encode()).into())
+        };
+        let payload = SimulatePayload::default().extend(
+            SimBlock::default()
+                .call(balance())
+                .call(transfer.to_request(from))
+                .call(balance()),
+        );
+        let blocks = self
+            .provider
+            .simulate(&payload)
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to simulate transfer", e))?;
+        let Some([before, sent, after]) = blocks.first().map(|block| block.calls.as_slice()) else {
+            return Err(VaughanError::rpc("Failed to simulate transfer", "missing block"));
+        };
+        if !sent.status {
+            let reason = sent.error.as_ref().map(|e| e.message.clone());
+            return Ok(Err(reason.unwrap_or_else(|| "transfer reverted".to_string())));
+        }
+        if !sent.return_data.is_empty()
+            && !ITokenControls::transferCall::abi_decode_returns(&sent.return_data).unwrap_or(false)
+        {
+            return Ok(Err("transfer returned false".to_string()));
+        }
+
+        let balance_of = |result: &SimCallResult| {
+            ITokenControls::balanceOfCall::abi_decode_returns(&result.return_data)
+                .map_err(|e| VaughanError::rpc("Invalid balanceOf response", e))
+        };
+        Ok(Ok(balance_of(after)?.saturating_sub(balance_of(before)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tax_and_honeypot_classification() {
+        let sent = U256::from(1_000_000);
+        assert_eq!(tax_bps(sent, sent), 0);
+        assert_eq!(tax_bps(sent, U256::from(950_000)), 500);
+        assert_eq!(tax_bps(sent, U256::ZERO), 10_000);
+
+        let mut report = PreflightReport {
+            token: Address::repeat_byte(0x70),
+            sent,
+            received: Some(U256::from(950_000)),
+            allowance: Some(U256::from(10)),
+            risks: vec![TokenRisk::TransferTax { tax_bps: 500 }, TokenRisk::Pausable],
+        };
+        assert!(!report.is_honeypot());
+        assert!(report.needs_approval());
+
+        report.risks.push(TokenRisk::Blacklisted {
+            account: Address::repeat_byte(0x42),
+        });
+        assert!(report.is_honeypot());
+        report.risks = vec![TokenRisk::TransferTax { tax_bps: 9_900 }];
+        assert!(report.is_honeypot());
+    }
+
+    #[test]
+    fn test_selectors_found_in_dispatch_code() {
+        let mut code = vec![0x60, 0x80, 0x60, 0x40, 0x52, 0x80, 0x63];
+        code.extend_from_slice(&ITokenControls::setBotsCall::SELECTOR);
+        code.extend_from_slice(&[0x14, 0x61, 0x01, 0x23, 0x57]);
+        assert!(has_selector(&code, ITokenControls::setBotsCall::SELECTOR));
+        assert!(!has_selector(&code, ITokenControls::pauseCall::SELECTOR));
+        // Selector bytes not preceded by PUSH4 are data, not dispatch
+        let data = ITokenControls::pauseCall::SELECTOR.to_vec();
+        assert!(!has_selector(&data, ITokenControls::pauseCall::SELECTOR));
+    }
+}
@@ -5,6 +5,8 @@
 
 use super::{TokenManager, TokenPrice};
 use crate::error::Result;
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient};
 use alloy::primitives::Address;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -48,7 +50,7 @@ pub trait PriceProvider {
 
 /// CoinGecko price provider implementation
 pub struct CoinGeckoPriceProvider {
-    client: reqwest::Client,
+    client: HttpClient,
     api_key: Option<String>,
     base_url: String,
 }
@@ -62,7 +64,7 @@ impl CoinGeckoPriceProvider {
         };
 
         Self {
-            client: reqwest::Client::new(),
+            client: shared_client(),
             api_key,
             base_url,
         }
@@ -124,6 +126,7 @@ impl PriceProvider for CoinGeckoPriceProvider {
         }
 
         tracing::debug!("Fetching token prices from CoinGecko: {}", url);
+        egress().check(EgressCategory::PriceData, &url, "fetch token prices")?;
 
         let response = self
             .client
@@ -188,6 +191,7 @@ impl PriceProvider for CoinGeckoPriceProvider {
         if let Some(api_key) = &self.api_key {
             url.push_str(&format!("&x_cg_pro_api_key={api_key}"));
         }
+        egress().check(EgressCategory::PriceData, &url, "fetch native token price")?;
 
         let response = self
             .client
@@ -223,7 +227,7 @@ impl PriceProvider for CoinGeckoPriceProvider {
 
 /// Moralis price provider implementation
 pub struct MoralisPriceProvider {
-    client: reqwest::Client,
+    client: HttpClient,
     api_key: String,
     base_url: String,
 }
@@ -231,7 +235,7 @@ pub struct MoralisPriceProvider {
 impl MoralisPriceProvider {
     pub fn new(api_key: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: shared_client(),
             api_key,
             base_url: "https://deep-index.moralis.io/api/v2".to_string(),
         }
@@ -292,6 +296,7 @@ impl PriceProvider for MoralisPriceProvider {
             let url = format!("{}/erc20/{}/price?chain={}", self.base_url, token_address, chain);
 
             tracing::debug!("Fetching token price from Moralis: {}", url);
+            egress().check(EgressCategory::PriceData, &url, "fetch token prices")?;
 
             match self
                 .client
@@ -352,6 +357,7 @@ impl PriceProvider for MoralisPriceProvider {
         };
 
         let url = format!("{}/market-data/evm/{}/price", self.base_url, chain);
+        egress().check(EgressCategory::PriceData, &url, "fetch native token price")?;
 
         match self
             .client
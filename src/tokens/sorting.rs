@@ -0,0 +1,288 @@
+//! Token sort strategies
+//!
+//! Every balance view sorts its token list through [`sort_tokens`] with the
+//! strategy the user picked, so the wallet, portfolio and pickers agree on
+//! the order. Strategies: by USD value, by 24h price change, alphabetical and
+//! recently transacted. Ties, and tokens without the data a strategy needs,
+//! fall back to alphabetical order after the ones that have it.
+//!
+//! The chosen strategy is stored in `<config_dir>/vaughan/token_sort.json`.
+
+use super::{TokenBalance, TokenPrice};
+use crate::error::{ConfigurationError, Result};
+use crate::wallet::portfolio::TokenAllocation;
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Get the storage path for the token sort preference
+pub fn get_token_sort_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("vaughan");
+    path.push("token_sort.json");
+    path
+}
+
+/// How token lists are ordered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenSortStrategy {
+    /// Highest USD value first
+    #[default]
+    UsdValue,
+    /// Largest 24h price gain first
+    Change24h,
+    /// By symbol, A to Z
+    Alphabetical,
+    /// Most recently sent or received first
+    RecentlyTransacted,
+}
+
+impl TokenSortStrategy {
+    pub const ALL: [TokenSortStrategy; 4] = [
+        TokenSortStrategy::UsdValue,
+        TokenSortStrategy::Change24h,
+        TokenSortStrategy::Alphabetical,
+        TokenSortStrategy::RecentlyTransacted,
+    ];
+
+    /// Compare two tokens; smaller sorts first
+    pub fn compare<T: SortableToken>(&self, a: &T, b: &T, context: &TokenSortContext) -> Ordering {
+        let key = |token: &T| (token.sort_chain_id(), token.sort_address());
+        let primary = match self {
+            TokenSortStrategy::UsdValue => descending(a.sort_usd_value(), b.sort_usd_value()),
+            TokenSortStrategy::Change24h => descending(
+                context.price_changes.get(&key(a)).copied(),
+                context.price_changes.get(&key(b)).copied(),
+            ),
+            TokenSortStrategy::Alphabetical => Ordering::Equal,
+            TokenSortStrategy::RecentlyTransacted => {
+                match (
+                    context.last_transacted.get(&key(a)),
+                    context.last_transacted.get(&key(b)),
+                ) {
+                    (Some(a), Some(b)) => b.cmp(a),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }
+        };
+        primary
+            .then_with(|| a.sort_symbol().to_lowercase().cmp(&b.sort_symbol().to_lowercase()))
+            .then_with(|| key(a).cmp(&key(b)))
+    }
+}
+
+impl fmt::Display for TokenSortStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            TokenSortStrategy::UsdValue => "Value",
+            TokenSortStrategy::Change24h => "24h change",
+            TokenSortStrategy::Alphabetical => "Name",
+            TokenSortStrategy::RecentlyTransacted => "Recent activity",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Larger values first, missing values last
+fn descending(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// A token list entry that can be sorted
+pub trait SortableToken {
+    fn sort_symbol(&self) -> &str;
+    fn sort_chain_id(&self) -> u64;
+    /// Contract address, `Address::ZERO` for the native token
+    fn sort_address(&self) -> Address;
+    fn sort_usd_value(&self) -> Option<f64>;
+}
+
+impl SortableToken for TokenBalance {
+    fn sort_symbol(&self) -> &str {
+        &self.token.symbol
+    }
+
+    fn sort_chain_id(&self) -> u64 {
+        self.token.chain_id
+    }
+
+    fn sort_address(&self) -> Address {
+        self.token.address
+    }
+
+    fn sort_usd_value(&self) -> Option<f64> {
+        self.usd_value
+    }
+}
+
+impl SortableToken for TokenAllocation {
+    fn sort_symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn sort_chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn sort_address(&self) -> Address {
+        self.token_address
+    }
+
+    fn sort_usd_value(&self) -> Option<f64> {
+        self.priced.then_some(self.usd_value)
+    }
+}
+
+/// Market and activity data the strategies sort by, keyed by `(chain_id, address)`
+#[derive(Debug, Clone, Default)]
+pub struct TokenSortContext {
+    pub price_changes: HashMap<(u64, Address), f64>,
+    pub last_transacted: HashMap<(u64, Address), DateTime<Utc>>,
+}
+
+impl TokenSortContext {
+    /// Take 24h changes from price quotes
+    pub fn with_prices<'a>(mut self, prices: impl IntoIterator<Item = &'a TokenPrice>) -> Self {
+        for price in prices {
+            if let Some(change) = price.price_change_24h {
+                self.price_changes.insert((price.chain_id, price.token_address), change);
+            }
+        }
+        self
+    }
+
+    /// Note a transfer of a token, keeping the latest per token
+    pub fn record_transfer(&mut self, chain_id: u64, address: Address, at: DateTime<Utc>) {
+        let latest = self.last_transacted.entry((chain_id, address)).or_insert(at);
+        if at > *latest {
+            *latest = at;
+        }
+    }
+}
+
+/// Sort `tokens` in place with `strategy`
+pub fn sort_tokens<T: SortableToken>(tokens: &mut [T], strategy: TokenSortStrategy, context: &TokenSortContext) {
+    tokens.sort_by(|a, b| strategy.compare(a, b, context));
+}
+
+/// The user's chosen strategy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenSortPreference {
+    #[serde(default)]
+    pub strategy: TokenSortStrategy,
+}
+
+impl TokenSortPreference {
+    /// Load the preference from the default location
+    pub fn load() -> Self {
+        Self::load_from(&get_token_sort_path())
+    }
+
+    /// Load the preference from a file, falling back to the default strategy
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid token sort file {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the preference to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_token_sort_path())
+    }
+
+    /// Save the preference to a file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self).map_err(|e| ConfigurationError::ParseError {
+            message: format!("Failed to serialize token sort preference: {e}"),
+        })?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::TokenInfo;
+
+    fn balance(symbol: &str, byte: u8, usd_value: Option<f64>) -> TokenBalance {
+        let address = Address::repeat_byte(byte);
+        TokenBalance {
+            token: TokenInfo::new(address, 1, symbol.to_string(), symbol.to_string(), 18),
+            balance: "0".to_string(),
+            formatted: "0".to_string(),
+            usd_value,
+            fiat_value: None,
+        }
+    }
+
+    fn symbols(tokens: &[TokenBalance]) -> Vec<&str> {
+        tokens.iter().map(|t| t.token.symbol.as_str()).collect()
+    }
+
+    #[test]
+    fn test_strategies() {
+        let mut tokens = vec![
+            balance("dai", 1, Some(50.0)),
+            balance("WETH", 2, Some(900.0)),
+            balance("SPAM", 3, None),
+            balance("USDC", 4, Some(50.0)),
+        ];
+        let mut context = TokenSortContext::default();
+        context.price_changes.insert((1, Address::repeat_byte(1)), -1.0);
+        context.price_changes.insert((1, Address::repeat_byte(3)), 40.0);
+        let now = Utc::now();
+        context.record_transfer(1, Address::repeat_byte(4), now - chrono::Duration::days(2));
+        context.record_transfer(1, Address::repeat_byte(2), now - chrono::Duration::days(1));
+        context.record_transfer(1, Address::repeat_byte(4), now);
+
+        sort_tokens(&mut tokens, TokenSortStrategy::UsdValue, &context);
+        assert_eq!(symbols(&tokens), ["WETH", "dai", "USDC", "SPAM"]);
+
+        sort_tokens(&mut tokens, TokenSortStrategy::Change24h, &context);
+        assert_eq!(symbols(&tokens), ["SPAM", "dai", "USDC", "WETH"]);
+
+        sort_tokens(&mut tokens, TokenSortStrategy::Alphabetical, &context);
+        assert_eq!(symbols(&tokens), ["dai", "SPAM", "USDC", "WETH"]);
+
+        sort_tokens(&mut tokens, TokenSortStrategy::RecentlyTransacted, &context);
+        assert_eq!(symbols(&tokens), ["USDC", "WETH", "dai", "SPAM"]);
+    }
+
+    #[test]
+    fn test_preference_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token_sort.json");
+        assert_eq!(
+            TokenSortPreference::load_from(&path).strategy,
+            TokenSortStrategy::UsdValue
+        );
+
+        let preference = TokenSortPreference {
+            strategy: TokenSortStrategy::RecentlyTransacted,
+        };
+        preference.save_to(&path).unwrap();
+        assert_eq!(TokenSortPreference::load_from(&path), preference);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("recently_transacted"));
+    }
+}
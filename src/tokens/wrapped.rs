@@ -0,0 +1,145 @@
+//! Canonical wrapped native tokens
+//!
+//! One WETH-style contract per chain (WETH, WPLS, WBNB, ...) that holds the
+//! native currency 1:1 through `deposit()` and `withdraw(uint256)`. Wrap and
+//! unwrap transactions are built against these addresses, see
+//! [`crate::wallet::transaction::wrap`], and the portfolio counts balances of
+//! them together with the native currency.
+
+use super::TokenInfo;
+use alloy::primitives::{address, Address};
+
+/// Wrapped native token of one chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrappedNative {
+    pub chain_id: u64,
+    pub address: Address,
+    pub symbol: &'static str,
+    pub name: &'static str,
+    /// Symbol of the native currency it wraps
+    pub native_symbol: &'static str,
+}
+
+impl WrappedNative {
+    pub fn token_info(&self) -> TokenInfo {
+        let mut token = TokenInfo::new(
+            self.address,
+            self.chain_id,
+            self.name.to_string(),
+            self.symbol.to_string(),
+            18,
+        );
+        token.tags.push("wrapped-native".to_string());
+        token
+    }
+}
+
+/// Wrapped native tokens by chain
+pub const WRAPPED_NATIVE_TOKENS: &[WrappedNative] = &[
+    WrappedNative {
+        chain_id: 1,
+        address: address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+        symbol: "WETH",
+        name: "Wrapped Ether",
+        native_symbol: "ETH",
+    },
+    WrappedNative {
+        chain_id: 11155111,
+        address: address!("fFf9976782d46CC05630D1f6eBAb18b2324d6B14"),
+        symbol: "WETH",
+        name: "Wrapped Ether",
+        native_symbol: "ETH",
+    },
+    WrappedNative {
+        chain_id: 369,
+        address: address!("A1077a294dDE1B09bB078844df40758a5D0f9a27"),
+        symbol: "WPLS",
+        name: "Wrapped Pulse",
+        native_symbol: "PLS",
+    },
+    WrappedNative {
+        chain_id: 943,
+        address: address!("cF1Fc503CA35618E9b4C08b7847980b3e10FB53B"),
+        symbol: "WPLS",
+        name: "Wrapped Pulse",
+        native_symbol: "tPLS",
+    },
+    WrappedNative {
+        chain_id: 56,
+        address: address!("bb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c"),
+        symbol: "WBNB",
+        name: "Wrapped BNB",
+        native_symbol: "BNB",
+    },
+    WrappedNative {
+        chain_id: 97,
+        address: address!("ae13d989daC2f0dEbFf460aC112a837C89BAa7cd"),
+        symbol: "WBNB",
+        name: "Wrapped BNB",
+        native_symbol: "tBNB",
+    },
+    WrappedNative {
+        chain_id: 137,
+        address: address!("0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270"),
+        symbol: "WPOL",
+        name: "Wrapped POL",
+        native_symbol: "POL",
+    },
+    WrappedNative {
+        chain_id: 10,
+        address: address!("4200000000000000000000000000000000000006"),
+        symbol: "WETH",
+        name: "Wrapped Ether",
+        native_symbol: "ETH",
+    },
+    WrappedNative {
+        chain_id: 8453,
+        address: address!("4200000000000000000000000000000000000006"),
+        symbol: "WETH",
+        name: "Wrapped Ether",
+        native_symbol: "ETH",
+    },
+    WrappedNative {
+        chain_id: 42161,
+        address: address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+        symbol: "WETH",
+        name: "Wrapped Ether",
+        native_symbol: "ETH",
+    },
+];
+
+/// The wrapped native token of `chain_id`
+pub fn wrapped_native(chain_id: u64) -> Option<&'static WrappedNative> {
+    WRAPPED_NATIVE_TOKENS.iter().find(|w| w.chain_id == chain_id)
+}
+
+/// Whether `address` is the wrapped native token of `chain_id`
+pub fn is_wrapped_native(chain_id: u64, address: Address) -> bool {
+    wrapped_native(chain_id).is_some_and(|w| w.address == address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_lookup() {
+        let wpls = wrapped_native(369).unwrap();
+        assert_eq!((wpls.symbol, wpls.native_symbol), ("WPLS", "PLS"));
+        assert!(is_wrapped_native(
+            56,
+            address!("bb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c")
+        ));
+        assert!(!is_wrapped_native(1, wpls.address));
+        assert!(wrapped_native(999_999).is_none());
+
+        let token = wpls.token_info();
+        assert!(!token.is_native);
+        assert_eq!(token.decimals, 18);
+
+        // One entry per chain
+        for (i, w) in WRAPPED_NATIVE_TOKENS.iter().enumerate() {
+            assert!(WRAPPED_NATIVE_TOKENS[i + 1..].iter().all(|o| o.chain_id != w.chain_id));
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Deterministic account identity
+//!
+//! Derives a color and a blockies-style identicon from an address so every
+//! surface that shows an account (the GUI account list, confirmation dialogs,
+//! exported reports) gives it the same visual cue. Everything is seeded from
+//! the SHA-256 of the address bytes; nothing is stored.
+
+use alloy::primitives::Address;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Cells per identicon side
+pub const IDENTICON_GRID: usize = 5;
+/// Palette size of an identicon
+const PALETTE_SIZE: usize = 3;
+/// SVG units per identicon cell
+const SVG_CELL: usize = 10;
+
+/// An 8-bit RGB color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Convert hue (degrees), saturation and lightness (percent)
+    fn from_hsl(h: u32, s: u32, l: u32) -> Self {
+        let s = s as f32 / 100.0;
+        let l = l as f32 / 100.0;
+
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = c * (1.0 - ((h as f32 / 60.0) % 2.0 - 1.0).abs());
+        let m = l - c / 2.0;
+
+        let (r, g, b) = match h {
+            0..=59 => (c, x, 0.0),
+            60..=119 => (x, c, 0.0),
+            120..=179 => (0.0, c, x),
+            180..=239 => (0.0, x, c),
+            240..=299 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let channel = |v: f32| ((v + m) * 255.0) as u8;
+        Self {
+            r: channel(r),
+            g: channel(g),
+            b: channel(b),
+        }
+    }
+
+    /// CSS hex notation, e.g. `#1a2b3c`
+    pub fn hex(self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Visual identity of one address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountIdentity {
+    seed: [u8; 32],
+    palette: [Rgb; PALETTE_SIZE],
+    /// Palette index of each filled cell, by row then column
+    cells: [[Option<usize>; IDENTICON_GRID]; IDENTICON_GRID],
+    background: Rgb,
+}
+
+impl AccountIdentity {
+    pub fn for_address(address: Address) -> Self {
+        let seed: [u8; 32] = Sha256::digest(address.as_slice()).into();
+        let mut rng = ChaCha20Rng::from_seed(seed);
+
+        let palette = [(); PALETTE_SIZE].map(|_| {
+            let h = rng.gen_range(0..360);
+            let s = rng.gen_range(50..100);
+            let l = rng.gen_range(40..80);
+            Rgb::from_hsl(h, s, l)
+        });
+
+        // Left half plus center, mirrored to the right
+        let mut cells = [[None; IDENTICON_GRID]; IDENTICON_GRID];
+        for row in cells.iter_mut() {
+            for x in 0..=(IDENTICON_GRID / 2) {
+                if rng.gen_bool(0.5) {
+                    let color = rng.gen_range(0..PALETTE_SIZE);
+                    row[x] = Some(color);
+                    row[IDENTICON_GRID - 1 - x] = Some(color);
+                }
+            }
+        }
+        let background = palette[rng.gen_range(0..PALETTE_SIZE)];
+
+        Self {
+            seed,
+            palette,
+            cells,
+            background,
+        }
+    }
+
+    /// The account's color, also the identicon background
+    pub fn color(&self) -> Rgb {
+        self.background
+    }
+
+    /// Seed other renderers can derive the same identicon from
+    pub fn seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    pub fn seed_hex(&self) -> String {
+        alloy::hex::encode(self.seed)
+    }
+
+    /// Color of the cell at `x`, `y`, or `None` for background
+    pub fn cell(&self, x: usize, y: usize) -> Option<Rgb> {
+        self.cells.get(y)?.get(x).copied().flatten().map(|i| self.palette[i])
+    }
+
+    /// Identicon as a 50×50 SVG
+    pub fn to_svg(&self) -> String {
+        let mut rects = String::new();
+        for y in 0..IDENTICON_GRID {
+            for x in 0..IDENTICON_GRID {
+                if let Some(color) = self.cell(x, y) {
+                    rects.push_str(&format!(
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" />"#,
+                        x * SVG_CELL,
+                        y * SVG_CELL,
+                        SVG_CELL,
+                        SVG_CELL,
+                        color
+                    ));
+                }
+            }
+        }
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 50 50" style="background-color: {};">{}</svg>"#,
+            self.background, rects
+        )
+    }
+
+    /// Identicon as RGBA pixels, `scale` pixels per cell, returned with its side length
+    pub fn to_rgba(&self, scale: usize) -> (u32, Vec<u8>) {
+        let side = IDENTICON_GRID * scale;
+        let mut pixels = Vec::with_capacity(side * side * 4);
+        for py in 0..side {
+            for px in 0..side {
+                let color = self.cell(px / scale, py / scale).unwrap_or(self.background);
+                pixels.extend_from_slice(&[color.r, color.g, color.b, 0xff]);
+            }
+        }
+        (side as u32, pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_is_deterministic_and_symmetric() {
+        let identity = AccountIdentity::for_address(Address::repeat_byte(1));
+        assert_eq!(identity, AccountIdentity::for_address(Address::repeat_byte(1)));
+        assert_ne!(identity, AccountIdentity::for_address(Address::repeat_byte(2)));
+
+        for y in 0..IDENTICON_GRID {
+            for x in 0..IDENTICON_GRID {
+                assert_eq!(identity.cell(x, y), identity.cell(IDENTICON_GRID - 1 - x, y));
+            }
+        }
+        assert!(identity
+            .to_svg()
+            .contains(&format!("background-color: {};", identity.color())));
+        assert_eq!(identity.seed_hex().len(), 64);
+
+        let (side, pixels) = identity.to_rgba(4);
+        assert_eq!(side, 20);
+        assert_eq!(pixels.len(), 20 * 20 * 4);
+        let corner = identity.cell(0, 0).unwrap_or(identity.color());
+        assert_eq!(pixels[..4], [corner.r, corner.g, corner.b, 0xff]);
+    }
+
+    #[test]
+    fn test_hsl_conversion() {
+        assert_eq!(Rgb::from_hsl(0, 100, 50).hex(), "#ff0000");
+        assert_eq!(Rgb::from_hsl(120, 100, 50).hex(), "#00ff00");
+        assert_eq!(Rgb::from_hsl(240, 100, 50).hex(), "#0000ff");
+    }
+}
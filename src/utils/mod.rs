@@ -2,6 +2,8 @@
 //!
 //! This module provides common utilities used throughout the application.
 
+pub mod identity;
+
 use alloy::primitives::{Address, U256};
 use std::str::FromStr;
 
@@ -0,0 +1,367 @@
+//! Simple smart accounts owned by an EOA
+//!
+//! Each EOA can own one `SimpleAccount` per chain, created by a
+//! `SimpleAccountFactory` at an address known before deployment. The account
+//! is deployed either by its first operation (the factory call travels in
+//! the operation) or up front by a transaction from the owner.
+
+use super::bundler::BundlerPool;
+use super::paymaster::PaymasterClient;
+use super::user_operation::UserOperation;
+use crate::error::{Result, VaughanError};
+use crate::wallet::transaction::fees::{FeeEstimator, FeePriority};
+use crate::wallet::transaction::sponsorship::ENTRY_POINT_V07;
+use alloy::primitives::aliases::U192;
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::{Signer, SignerSync};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use serde::{Deserialize, Serialize};
+
+sol! {
+    interface ISimpleAccountFactory {
+        function createAccount(address owner, uint256 salt) external returns (address);
+        function getAddress(address owner, uint256 salt) external view returns (address);
+    }
+
+    interface ISimpleAccount {
+        function execute(address dest, uint256 value, bytes func) external;
+        function executeBatch(address[] dest, uint256[] value, bytes[] func) external;
+    }
+
+    interface IEntryPointNonces {
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+    }
+}
+
+/// A call made by the smart account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// Smart account details for one owner on one chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmartAccount {
+    pub owner: Address,
+    /// Counterfactual address, valid before deployment
+    pub address: Address,
+    pub chain_id: u64,
+    pub factory: Address,
+    pub salt: U256,
+    pub entry_point: Address,
+}
+
+impl SmartAccount {
+    /// Look up the address `factory` deploys `owner`'s account to
+    pub async fn counterfactual<P: Provider>(
+        provider: &P,
+        chain_id: u64,
+        owner: Address,
+        factory: Address,
+        salt: U256,
+    ) -> Result<Self> {
+        let call = TransactionRequest::default()
+            .to(factory)
+            .input(Bytes::from(ISimpleAccountFactory::getAddressCall { owner, salt }.abi_encode()).into());
+        let raw = provider
+            .call(call)
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to compute smart account address", e))?;
+        let address = ISimpleAccountFactory::getAddressCall::abi_decode_returns(&raw)
+            .map_err(|e| VaughanError::rpc("Invalid smart account factory response", e))?;
+        Ok(Self {
+            owner,
+            address,
+            chain_id,
+            factory,
+            salt,
+            entry_point: ENTRY_POINT_V07,
+        })
+    }
+
+    pub async fn is_deployed<P: Provider>(&self, provider: &P) -> Result<bool> {
+        let code = provider
+            .get_code_at(self.address)
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to fetch smart account code", e))?;
+        Ok(!code.is_empty())
+    }
+
+    fn factory_data(&self) -> Bytes {
+        ISimpleAccountFactory::createAccountCall {
+            owner: self.owner,
+            salt: self.salt,
+        }
+        .abi_encode()
+        .into()
+    }
+
+    /// Transaction the owner sends to deploy the account up front
+    pub fn deployment_request(&self) -> TransactionRequest {
+        TransactionRequest::default()
+            .from(self.owner)
+            .to(self.factory)
+            .input(self.factory_data().into())
+    }
+
+    /// Account calldata performing `calls`
+    pub fn encode_calls(calls: &[Call]) -> Result<Bytes> {
+        match calls {
+            [] => Err(VaughanError::ValidationError(
+                "A user operation needs at least one call".into(),
+            )),
+            [call] => Ok(ISimpleAccount::executeCall {
+                dest: call.to,
+                value: call.value,
+                func: call.data.clone(),
+            }
+            .abi_encode()
+            .into()),
+            _ => Ok(ISimpleAccount::executeBatchCall {
+                dest: calls.iter().map(|c| c.to).collect(),
+                value: calls.iter().map(|c| c.value).collect(),
+                func: calls.iter().map(|c| c.data.clone()).collect(),
+            }
+            .abi_encode()
+            .into()),
+        }
+    }
+
+    /// Unsigned operation performing `calls`, with fees but no gas limits
+    ///
+    /// Includes the factory call while the account is not deployed. The
+    /// signature is a placeholder of the right shape for gas estimation.
+    pub async fn build_user_operation<P: Provider>(
+        &self,
+        provider: &P,
+        calls: &[Call],
+        priority: FeePriority,
+    ) -> Result<UserOperation> {
+        let call_data = Self::encode_calls(calls)?;
+        let deployed = self.is_deployed(provider).await?;
+
+        let nonce_call = IEntryPointNonces::getNonceCall {
+            sender: self.address,
+            key: U192::ZERO,
+        };
+        let raw = provider
+            .call(
+                TransactionRequest::default()
+                    .to(self.entry_point)
+                    .input(Bytes::from(nonce_call.abi_encode()).into()),
+            )
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to read smart account nonce", e))?;
+        let nonce = IEntryPointNonces::getNonceCall::abi_decode_returns(&raw)
+            .map_err(|e| VaughanError::rpc("Invalid EntryPoint nonce response", e))?;
+
+        let fees = FeeEstimator::estimate_fees(provider, priority).await?;
+        let mut op = UserOperation {
+            sender: self.address,
+            nonce,
+            factory: (!deployed).then_some(self.factory),
+            factory_data: (!deployed).then(|| self.factory_data()),
+            call_data,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+            ..Default::default()
+        };
+        op.signature = self.placeholder_signature(&op)?;
+        Ok(op)
+    }
+
+    /// A well-formed signature by a throwaway key, so validation runs its
+    /// full path during estimation without the owner signing
+    fn placeholder_signature(&self, op: &UserOperation) -> Result<Bytes> {
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x01))
+            .map_err(|e| VaughanError::ValidationError(e.to_string()))?;
+        let signature = signer
+            .sign_message_sync(self.user_operation_hash(op).as_slice())
+            .map_err(|e| VaughanError::ValidationError(e.to_string()))?;
+        Ok(Bytes::from(signature.as_bytes().to_vec()))
+    }
+
+    pub fn user_operation_hash(&self, op: &UserOperation) -> B256 {
+        op.hash(self.entry_point, self.chain_id)
+    }
+
+    /// Sign `op` with the owner; `SimpleAccount` expects an EIP-191 signature of the hash
+    pub async fn sign_user_operation(&self, op: &mut UserOperation, owner: &(dyn Signer + Send + Sync)) -> Result<()> {
+        if owner.address() != self.owner {
+            return Err(VaughanError::ValidationError(format!(
+                "{} does not own smart account {}",
+                owner.address(),
+                self.address
+            )));
+        }
+        let signature = owner
+            .sign_message(self.user_operation_hash(op).as_slice())
+            .await
+            .map_err(|e| VaughanError::ValidationError(format!("Failed to sign user operation: {e}")))?;
+        op.signature = Bytes::from(signature.as_bytes().to_vec());
+        Ok(())
+    }
+
+    /// Build, sponsor, estimate, sign and submit `calls`, returning the operation hash
+    ///
+    /// With a paymaster the operation is gasless for the account.
+    pub async fn send_calls<P: Provider, B: Provider, M: Provider>(
+        &self,
+        provider: &P,
+        calls: &[Call],
+        bundlers: &BundlerPool<B>,
+        paymaster: Option<&PaymasterClient<M>>,
+        owner: &(dyn Signer + Send + Sync),
+    ) -> Result<B256> {
+        let mut op = self
+            .build_user_operation(provider, calls, FeePriority::Standard)
+            .await?;
+        if let Some(paymaster) = paymaster {
+            op.apply_paymaster(&paymaster.stub_data(&op, self.chain_id).await?);
+        }
+
+        let estimate = bundlers.estimate_user_operation_gas(&op).await?;
+        op.apply_gas_estimate(&estimate);
+
+        if let Some(paymaster) = paymaster {
+            op.apply_paymaster(&paymaster.data(&op, self.chain_id).await?);
+        }
+        self.sign_user_operation(&mut op, owner).await?;
+
+        tracing::info!(
+            "📨 Sending user operation from {} ({} call(s), sponsored: {})",
+            self.address,
+            calls.len(),
+            op.is_sponsored()
+        );
+        bundlers.send_user_operation(&op).await
+    }
+}
+
+/// Smart accounts known to the wallet, at most one per owner and chain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmartAccountRegistry {
+    accounts: Vec<SmartAccount>,
+}
+
+impl SmartAccountRegistry {
+    /// Add `account`, replacing the owner's account on the same chain
+    pub fn insert(&mut self, account: SmartAccount) {
+        self.accounts
+            .retain(|a| !(a.owner == account.owner && a.chain_id == account.chain_id));
+        self.accounts.push(account);
+    }
+
+    pub fn get(&self, chain_id: u64, owner: Address) -> Option<&SmartAccount> {
+        self.accounts
+            .iter()
+            .find(|a| a.chain_id == chain_id && a.owner == owner)
+    }
+
+    /// Account deployed at `address` on `chain_id`
+    pub fn by_address(&self, chain_id: u64, address: Address) -> Option<&SmartAccount> {
+        self.accounts
+            .iter()
+            .find(|a| a.chain_id == chain_id && a.address == address)
+    }
+
+    pub fn remove(&mut self, chain_id: u64, owner: Address) -> Option<SmartAccount> {
+        let index = self
+            .accounts
+            .iter()
+            .position(|a| a.chain_id == chain_id && a.owner == owner)?;
+        Some(self.accounts.remove(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SmartAccount> {
+        self.accounts.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Signature;
+    use alloy::providers::ProviderBuilder;
+    use alloy::sol_types::SolValue;
+    use alloy::transports::mock::Asserter;
+
+    const FACTORY: Address = Address::repeat_byte(0xfa);
+    const ACCOUNT: Address = Address::repeat_byte(0x5a);
+
+    #[tokio::test]
+    async fn test_first_operation_deploys_and_is_signed_by_owner() {
+        let owner = PrivateKeySigner::random();
+        let asserter = Asserter::new();
+        asserter.push_success(&Bytes::from(ACCOUNT.abi_encode())); // getAddress
+        asserter.push_success(&Bytes::new()); // no code yet
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+
+        let account = SmartAccount::counterfactual(&provider, 369, owner.address(), FACTORY, U256::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(account.address, ACCOUNT);
+        assert!(!account.is_deployed(&provider).await.unwrap());
+
+        let call = Call {
+            to: Address::repeat_byte(0x42),
+            value: U256::from(1),
+            data: Bytes::new(),
+        };
+        let mut op = UserOperation {
+            sender: account.address,
+            factory: Some(account.factory),
+            factory_data: Some(account.factory_data()),
+            call_data: SmartAccount::encode_calls(std::slice::from_ref(&call)).unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(op.init_code().len(), 20 + 4 + 64);
+        let decoded = ISimpleAccount::executeCall::abi_decode(&op.call_data).unwrap();
+        assert_eq!((decoded.dest, decoded.value), (call.to, call.value));
+
+        account.sign_user_operation(&mut op, &owner).await.unwrap();
+        let signature = Signature::try_from(op.signature.as_ref()).unwrap();
+        let recovered = signature
+            .recover_address_from_msg(account.user_operation_hash(&op).as_slice())
+            .unwrap();
+        assert_eq!(recovered, owner.address());
+
+        assert!(account
+            .sign_user_operation(&mut op, &PrivateKeySigner::random())
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_registry_keeps_one_account_per_owner_and_chain() {
+        let owner = Address::repeat_byte(0x0a);
+        let account = |chain_id, address| SmartAccount {
+            owner,
+            address,
+            chain_id,
+            factory: FACTORY,
+            salt: U256::ZERO,
+            entry_point: ENTRY_POINT_V07,
+        };
+        let mut registry = SmartAccountRegistry::default();
+        registry.insert(account(369, ACCOUNT));
+        registry.insert(account(1, ACCOUNT));
+        registry.insert(account(369, Address::repeat_byte(0x5b)));
+
+        assert_eq!(registry.iter().count(), 2);
+        assert_eq!(registry.get(369, owner).unwrap().address, Address::repeat_byte(0x5b));
+        assert!(registry.by_address(369, ACCOUNT).is_none());
+        assert!(registry.remove(1, owner).is_some());
+        assert!(registry.get(1, owner).is_none());
+
+        assert!(SmartAccount::encode_calls(&[]).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Bundler RPC clients
+//!
+//! Operations go to a bundler rather than a node. [`BundlerPool`] holds the
+//! bundlers configured for a chain in order of preference and fails over to
+//! the next one when a bundler is unreachable or rejects an operation.
+
+use super::user_operation::{UserOperation, UserOperationGasEstimate, UserOperationReceipt};
+use crate::error::{NetworkError, Result, VaughanError};
+use alloy::primitives::{Address, B256};
+use alloy::providers::{Provider, RootProvider};
+use url::Url;
+
+/// ERC-4337 `eth_*UserOperation*` methods of one bundler
+#[derive(Debug, Clone)]
+pub struct BundlerClient<P> {
+    provider: P,
+    entry_point: Address,
+    /// Shown in logs and errors, usually the host
+    name: String,
+}
+
+impl BundlerClient<RootProvider> {
+    /// Bundler at `url` serving `entry_point`
+    pub fn http(url: &str, entry_point: Address) -> Result<Self> {
+        let parsed = Url::parse(url).map_err(|_| NetworkError::RpcConnectionFailed { url: url.to_string() })?;
+        let name = parsed.host_str().unwrap_or(url).to_string();
+        Ok(Self::new(RootProvider::new_http(parsed), entry_point, name))
+    }
+}
+
+impl<P: Provider> BundlerClient<P> {
+    pub fn new(provider: P, entry_point: Address, name: impl Into<String>) -> Self {
+        Self {
+            provider,
+            entry_point,
+            name: name.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn rpc_error(&self, method: &str, e: impl std::fmt::Display) -> VaughanError {
+        VaughanError::Network(NetworkError::RpcError {
+            message: format!("Bundler {} failed {}: {}", self.name, method, e),
+        })
+    }
+
+    /// Entry points the bundler accepts operations for
+    pub async fn supported_entry_points(&self) -> Result<Vec<Address>> {
+        self.provider
+            .raw_request("eth_supportedEntryPoints".into(), ())
+            .await
+            .map_err(|e| self.rpc_error("eth_supportedEntryPoints", e))
+    }
+
+    pub async fn estimate_user_operation_gas(&self, op: &UserOperation) -> Result<UserOperationGasEstimate> {
+        self.provider
+            .raw_request("eth_estimateUserOperationGas".into(), (op, self.entry_point))
+            .await
+            .map_err(|e| self.rpc_error("eth_estimateUserOperationGas", e))
+    }
+
+    /// Submit a signed operation, returning its hash
+    pub async fn send_user_operation(&self, op: &UserOperation) -> Result<B256> {
+        self.provider
+            .raw_request("eth_sendUserOperation".into(), (op, self.entry_point))
+            .await
+            .map_err(|e| self.rpc_error("eth_sendUserOperation", e))
+    }
+
+    /// Receipt of an operation, `None` until it is included
+    pub async fn get_user_operation_receipt(&self, hash: B256) -> Result<Option<UserOperationReceipt>> {
+        self.provider
+            .raw_request("eth_getUserOperationReceipt".into(), (hash,))
+            .await
+            .map_err(|e| self.rpc_error("eth_getUserOperationReceipt", e))
+    }
+}
+
+/// Bundlers for one chain, tried in order
+#[derive(Debug, Clone)]
+pub struct BundlerPool<P> {
+    bundlers: Vec<BundlerClient<P>>,
+}
+
+impl<P> Default for BundlerPool<P> {
+    fn default() -> Self {
+        Self { bundlers: Vec::new() }
+    }
+}
+
+impl BundlerPool<RootProvider> {
+    /// Pool of HTTP bundlers from their URLs, in order of preference
+    pub fn from_urls<S: AsRef<str>>(urls: &[S], entry_point: Address) -> Result<Self> {
+        let bundlers = urls
+            .iter()
+            .map(|url| BundlerClient::http(url.as_ref(), entry_point))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { bundlers })
+    }
+}
+
+impl<P: Provider> BundlerPool<P> {
+    pub fn with_bundler(mut self, bundler: BundlerClient<P>) -> Self {
+        self.bundlers.push(bundler);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bundlers.is_empty()
+    }
+
+    fn none_configured() -> VaughanError {
+        VaughanError::Network(NetworkError::InvalidConfiguration)
+    }
+
+    pub async fn estimate_user_operation_gas(&self, op: &UserOperation) -> Result<UserOperationGasEstimate> {
+        let mut last_error = None;
+        for bundler in &self.bundlers {
+            match bundler.estimate_user_operation_gas(op).await {
+                Ok(estimate) => return Ok(estimate),
+                Err(e) => {
+                    tracing::warn!("⚠️ {}", e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(Self::none_configured))
+    }
+
+    /// Submit through the first bundler that accepts the operation
+    pub async fn send_user_operation(&self, op: &UserOperation) -> Result<B256> {
+        let mut last_error = None;
+        for bundler in &self.bundlers {
+            match bundler.send_user_operation(op).await {
+                Ok(hash) => {
+                    tracing::info!("📦 User operation {} submitted via {}", hash, bundler.name());
+                    return Ok(hash);
+                }
+                Err(e) => {
+                    tracing::warn!("⚠️ {}", e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(Self::none_configured))
+    }
+
+    /// Receipt from the first bundler that knows the operation
+    pub async fn get_user_operation_receipt(&self, hash: B256) -> Result<Option<UserOperationReceipt>> {
+        for bundler in &self.bundlers {
+            if let Ok(Some(receipt)) = bundler.get_user_operation_receipt(hash).await {
+                return Ok(Some(receipt));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::transaction::sponsorship::ENTRY_POINT_V07;
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+
+    fn bundler(asserter: Asserter, name: &str) -> BundlerClient<impl Provider> {
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+        BundlerClient::new(provider, ENTRY_POINT_V07, name)
+    }
+
+    #[tokio::test]
+    async fn test_pool_fails_over_to_next_bundler() {
+        let down = Asserter::new();
+        down.push_failure_msg("bundler overloaded");
+        let up = Asserter::new();
+        let hash = B256::repeat_byte(0x11);
+        up.push_success(&hash);
+
+        let pool = BundlerPool::default()
+            .with_bundler(bundler(down, "primary"))
+            .with_bundler(bundler(up, "backup"));
+        assert_eq!(pool.send_user_operation(&UserOperation::default()).await.unwrap(), hash);
+    }
+
+    #[tokio::test]
+    async fn test_empty_pool_and_last_error() {
+        let pool: BundlerPool<RootProvider> = BundlerPool::default();
+        assert!(pool.is_empty());
+        assert!(pool
+            .estimate_user_operation_gas(&UserOperation::default())
+            .await
+            .is_err());
+
+        let down = Asserter::new();
+        down.push_failure_msg("AA21 didn't pay prefund");
+        let pool = BundlerPool::default().with_bundler(bundler(down, "primary"));
+        let error = pool.send_user_operation(&UserOperation::default()).await.unwrap_err();
+        assert!(error.to_string().contains("primary"));
+    }
+}
@@ -0,0 +1,25 @@
+//! ERC-4337 account abstraction
+//!
+//! Gives an EOA a `SimpleAccount` smart account on each chain and sends
+//! calls from it as user operations through bundlers instead of as
+//! transactions. A paymaster can pay the gas, making operations gasless.
+//!
+//! - [`user_operation`] - v0.7 user operations, packing and hashing
+//! - [`account`] - the owner's smart account and the registry of accounts
+//! - [`bundler`] - bundler RPC clients with failover
+//! - [`paymaster`] - ERC-7677 paymaster services
+//!
+//! Smart accounts appear in the account manager as
+//! [`AccountType::SmartAccount`](crate::wallet::account_manager::AccountType::SmartAccount).
+//! Their owner signs for them, so they are added from an existing account
+//! rather than created with keys of their own.
+
+pub mod account;
+pub mod bundler;
+pub mod paymaster;
+pub mod user_operation;
+
+pub use account::{Call, SmartAccount, SmartAccountRegistry};
+pub use bundler::{BundlerClient, BundlerPool};
+pub use paymaster::PaymasterClient;
+pub use user_operation::{PaymasterData, UserOperation, UserOperationGasEstimate, UserOperationReceipt};
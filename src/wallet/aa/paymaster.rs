@@ -0,0 +1,104 @@
+//! Paymaster web service client (ERC-7677)
+//!
+//! A paymaster pays an operation's gas, making it gasless for the user.
+//! Sponsorship is requested twice: stub data with realistic sizes before gas
+//! estimation, then the final signed data once the gas limits are known,
+//! since the paymaster signs over them.
+
+use super::user_operation::{PaymasterData, UserOperation};
+use crate::error::{NetworkError, Result, VaughanError};
+use alloy::primitives::{Address, U64};
+use alloy::providers::{Provider, RootProvider};
+use serde_json::Value;
+use url::Url;
+
+/// ERC-7677 `pm_*` methods of one paymaster service
+#[derive(Debug, Clone)]
+pub struct PaymasterClient<P> {
+    provider: P,
+    entry_point: Address,
+    /// Service-specific policy, e.g. a sponsorship policy ID
+    context: Value,
+}
+
+impl PaymasterClient<RootProvider> {
+    pub fn http(url: &str, entry_point: Address) -> Result<Self> {
+        let parsed = Url::parse(url).map_err(|_| NetworkError::RpcConnectionFailed { url: url.to_string() })?;
+        Ok(Self::new(RootProvider::new_http(parsed), entry_point))
+    }
+}
+
+impl<P: Provider> PaymasterClient<P> {
+    pub fn new(provider: P, entry_point: Address) -> Self {
+        Self {
+            provider,
+            entry_point,
+            context: Value::Object(Default::default()),
+        }
+    }
+
+    pub fn with_context(mut self, context: Value) -> Self {
+        self.context = context;
+        self
+    }
+
+    async fn request(&self, method: &'static str, op: &UserOperation, chain_id: u64) -> Result<PaymasterData> {
+        let params = (op, self.entry_point, U64::from(chain_id), &self.context);
+        self.provider.raw_request(method.into(), params).await.map_err(|e| {
+            VaughanError::Network(NetworkError::RpcError {
+                message: format!("Paymaster {method} failed: {e}"),
+            })
+        })
+    }
+
+    /// Placeholder sponsorship used for gas estimation
+    pub async fn stub_data(&self, op: &UserOperation, chain_id: u64) -> Result<PaymasterData> {
+        self.request("pm_getPaymasterStubData", op, chain_id).await
+    }
+
+    /// Final sponsorship for an operation with its gas limits set
+    pub async fn data(&self, op: &UserOperation, chain_id: u64) -> Result<PaymasterData> {
+        self.request("pm_getPaymasterData", op, chain_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::transaction::sponsorship::ENTRY_POINT_V07;
+    use alloy::primitives::{bytes, U256};
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_stub_then_final_data() {
+        let asserter = Asserter::new();
+        asserter.push_success(&json!({
+            "paymaster": Address::repeat_byte(0xbb),
+            "paymasterData": "0x00",
+            "paymasterVerificationGasLimit": "0xc350",
+            "paymasterPostOpGasLimit": "0x2710",
+            "isFinal": false
+        }));
+        asserter.push_success(&json!({
+            "paymaster": Address::repeat_byte(0xbb),
+            "paymasterData": "0xfeed"
+        }));
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+        let paymaster = PaymasterClient::new(provider, ENTRY_POINT_V07).with_context(json!({"policyId": "p1"}));
+
+        let mut op = UserOperation::default();
+        let stub = paymaster.stub_data(&op, 369).await.unwrap();
+        op.apply_paymaster(&stub);
+        assert_eq!(op.paymaster_verification_gas_limit, Some(U256::from(50_000)));
+
+        let data = paymaster.data(&op, 369).await.unwrap();
+        op.apply_paymaster(&data);
+        assert_eq!(op.paymaster_data, Some(bytes!("feed")));
+        // The final data keeps the limits from the stub
+        assert_eq!(op.paymaster_post_op_gas_limit, Some(U256::from(10_000)));
+    }
+}
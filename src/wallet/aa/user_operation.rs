@@ -0,0 +1,275 @@
+//! ERC-4337 v0.7 user operations
+//!
+//! Bundler and paymaster RPCs take operations in the unpacked JSON form of
+//! [`UserOperation`]; the EntryPoint hashes and executes the
+//! [`PackedUserOperation`] form, where pairs of 128-bit gas values share a
+//! word and factory and paymaster fields are concatenated into bytes.
+
+use alloy::primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy::sol;
+use alloy::sol_types::SolValue;
+use serde::{Deserialize, Serialize};
+
+sol! {
+    /// `PackedUserOperation` as the v0.7 EntryPoint takes it
+    #[derive(Debug, PartialEq, Eq)]
+    struct PackedUserOperation {
+        address sender;
+        uint256 nonce;
+        bytes initCode;
+        bytes callData;
+        bytes32 accountGasLimits;
+        uint256 preVerificationGas;
+        bytes32 gasFees;
+        bytes paymasterAndData;
+        bytes signature;
+    }
+}
+
+/// A user operation in the form bundler RPCs exchange
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    /// Factory deploying `sender` with the first operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+/// Gas limits estimated by a bundler
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGasEstimate {
+    pub pre_verification_gas: U256,
+    pub verification_gas_limit: U256,
+    pub call_gas_limit: U256,
+    #[serde(default)]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(default)]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+}
+
+/// Paymaster fields sponsoring an operation (ERC-7677)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymasterData {
+    pub paymaster: Address,
+    pub paymaster_data: Bytes,
+    #[serde(default)]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(default)]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+}
+
+/// Outcome of an included operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    pub user_op_hash: B256,
+    pub sender: Address,
+    pub success: bool,
+    pub actual_gas_cost: U256,
+    pub actual_gas_used: U256,
+    /// Revert reason of a failed execution
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Two 128-bit values in one word, `high` first
+fn pack_u128_pair(high: U256, low: U256) -> B256 {
+    let mut word = [0u8; 32];
+    word[..16].copy_from_slice(&high.to_be_bytes::<32>()[16..]);
+    word[16..].copy_from_slice(&low.to_be_bytes::<32>()[16..]);
+    B256::from(word)
+}
+
+impl UserOperation {
+    /// Factory address followed by its calldata; empty for deployed accounts
+    pub fn init_code(&self) -> Bytes {
+        match self.factory {
+            Some(factory) => {
+                let data = self.factory_data.clone().unwrap_or_default();
+                [factory.as_slice(), data.as_ref()].concat().into()
+            }
+            None => Bytes::new(),
+        }
+    }
+
+    /// Paymaster, its two gas limits and its data; empty when unsponsored
+    pub fn paymaster_and_data(&self) -> Bytes {
+        match self.paymaster {
+            Some(paymaster) => {
+                let limits = pack_u128_pair(
+                    self.paymaster_verification_gas_limit.unwrap_or_default(),
+                    self.paymaster_post_op_gas_limit.unwrap_or_default(),
+                );
+                let data = self.paymaster_data.clone().unwrap_or_default();
+                [paymaster.as_slice(), limits.as_slice(), data.as_ref()].concat().into()
+            }
+            None => Bytes::new(),
+        }
+    }
+
+    pub fn pack(&self) -> PackedUserOperation {
+        PackedUserOperation {
+            sender: self.sender,
+            nonce: self.nonce,
+            initCode: self.init_code(),
+            callData: self.call_data.clone(),
+            accountGasLimits: pack_u128_pair(self.verification_gas_limit, self.call_gas_limit),
+            preVerificationGas: self.pre_verification_gas,
+            gasFees: pack_u128_pair(self.max_priority_fee_per_gas, self.max_fee_per_gas),
+            paymasterAndData: self.paymaster_and_data(),
+            signature: self.signature.clone(),
+        }
+    }
+
+    /// Hash the account signs, as computed by `EntryPoint.getUserOpHash`
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let packed = self.pack();
+        let inner = keccak256(
+            (
+                packed.sender,
+                packed.nonce,
+                keccak256(&packed.initCode),
+                keccak256(&packed.callData),
+                packed.accountGasLimits,
+                packed.preVerificationGas,
+                packed.gasFees,
+                keccak256(&packed.paymasterAndData),
+            )
+                .abi_encode(),
+        );
+        keccak256((inner, entry_point, U256::from(chain_id)).abi_encode())
+    }
+
+    pub fn apply_gas_estimate(&mut self, estimate: &UserOperationGasEstimate) {
+        self.pre_verification_gas = estimate.pre_verification_gas;
+        self.verification_gas_limit = estimate.verification_gas_limit;
+        self.call_gas_limit = estimate.call_gas_limit;
+        if self.paymaster.is_some() {
+            if let Some(limit) = estimate.paymaster_verification_gas_limit {
+                self.paymaster_verification_gas_limit = Some(limit);
+            }
+            if let Some(limit) = estimate.paymaster_post_op_gas_limit {
+                self.paymaster_post_op_gas_limit = Some(limit);
+            }
+        }
+    }
+
+    /// Sponsor the operation; limits the paymaster leaves out are kept
+    pub fn apply_paymaster(&mut self, paymaster: &PaymasterData) {
+        self.paymaster = Some(paymaster.paymaster);
+        self.paymaster_data = Some(paymaster.paymaster_data.clone());
+        if paymaster.paymaster_verification_gas_limit.is_some() {
+            self.paymaster_verification_gas_limit = paymaster.paymaster_verification_gas_limit;
+        }
+        if paymaster.paymaster_post_op_gas_limit.is_some() {
+            self.paymaster_post_op_gas_limit = paymaster.paymaster_post_op_gas_limit;
+        }
+    }
+
+    pub fn is_sponsored(&self) -> bool {
+        self.paymaster.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, b256, bytes};
+
+    #[test]
+    fn test_packing_and_hash() {
+        let op = UserOperation {
+            sender: Address::repeat_byte(0x5a),
+            nonce: U256::from(1),
+            factory: Some(Address::repeat_byte(0xfa)),
+            factory_data: Some(bytes!("abcd")),
+            call_data: bytes!("b61d27f6"),
+            call_gas_limit: U256::from(0x10),
+            verification_gas_limit: U256::from(0x20),
+            pre_verification_gas: U256::from(0x30),
+            max_fee_per_gas: U256::from(0x40),
+            max_priority_fee_per_gas: U256::from(0x50),
+            paymaster: Some(Address::repeat_byte(0xbb)),
+            paymaster_verification_gas_limit: Some(U256::from(0x60)),
+            paymaster_post_op_gas_limit: Some(U256::from(0x70)),
+            paymaster_data: Some(bytes!("01")),
+            signature: Bytes::new(),
+        };
+
+        let packed = op.pack();
+        assert_eq!(packed.initCode.len(), 22);
+        assert_eq!(
+            packed.accountGasLimits,
+            b256!("0000000000000000000000000000002000000000000000000000000000000010")
+        );
+        assert_eq!(
+            packed.gasFees,
+            b256!("0000000000000000000000000000005000000000000000000000000000000040")
+        );
+        // paymaster, two 16-byte limits, data
+        assert_eq!(packed.paymasterAndData.len(), 20 + 32 + 1);
+        assert_eq!(packed.paymasterAndData[35], 0x60);
+        assert_eq!(packed.paymasterAndData[51], 0x70);
+
+        // The signature is not part of the hash, the chain and entry point are
+        let entry_point = address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+        let signed = UserOperation {
+            signature: bytes!("ff"),
+            ..op.clone()
+        };
+        assert_eq!(op.hash(entry_point, 1), signed.hash(entry_point, 1));
+        assert_ne!(op.hash(entry_point, 1), op.hash(entry_point, 369));
+    }
+
+    #[test]
+    fn test_rpc_form_omits_unset_fields() {
+        let mut op = UserOperation {
+            sender: Address::repeat_byte(0x5a),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&op).unwrap();
+        assert_eq!(json["callGasLimit"], "0x0");
+        assert!(json.get("factory").is_none());
+        assert!(json.get("paymaster").is_none());
+
+        op.apply_paymaster(&PaymasterData {
+            paymaster: Address::repeat_byte(0xbb),
+            paymaster_data: bytes!("01"),
+            paymaster_verification_gas_limit: Some(U256::from(50_000)),
+            paymaster_post_op_gas_limit: None,
+        });
+        op.apply_gas_estimate(&UserOperationGasEstimate {
+            call_gas_limit: U256::from(21_000),
+            paymaster_post_op_gas_limit: Some(U256::from(10_000)),
+            ..Default::default()
+        });
+        assert!(op.is_sponsored());
+        assert_eq!(op.paymaster_verification_gas_limit, Some(U256::from(50_000)));
+        assert_eq!(op.paymaster_post_op_gas_limit, Some(U256::from(10_000)));
+        assert_eq!(
+            serde_json::from_value::<UserOperation>(serde_json::to_value(&op).unwrap()).unwrap(),
+            op
+        );
+    }
+}
@@ -26,6 +26,7 @@ use uuid::Uuid;
 
 use crate::error::{Result, SecurityError};
 use crate::telemetry::{AccountLogger, OperationSpan, PrivacyMode};
+use crate::wallet::progress::{NoProgress, ProgressOperation, ProgressReporter, ProgressTracker};
 
 /// Default Ethereum derivation path (BIP-44)
 pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
@@ -324,9 +325,23 @@ impl AccountCreator {
         passphrase: Option<&SecretString>,
         base_path: &str,
         count: u32,
+    ) -> Result<Vec<(CreatedAccount, PrivateKeySigner)>> {
+        self.create_multiple_from_seed_with_progress(phrase, passphrase, base_path, count, &NoProgress)
+    }
+
+    /// Create multiple accounts from a seed phrase, reporting one step per account
+    pub fn create_multiple_from_seed_with_progress(
+        &self,
+        phrase: &SecretString,
+        passphrase: Option<&SecretString>,
+        base_path: &str,
+        count: u32,
+        reporter: &dyn ProgressReporter,
     ) -> Result<Vec<(CreatedAccount, PrivateKeySigner)>> {
         let span = OperationSpan::new("create_multiple_from_seed");
         self.logger.log_operation_start(&span, &format!("Creating {} accounts from seed", count));
+        let mut progress = ProgressTracker::start(reporter, ProgressOperation::Creation, count as u64, "Deriving accounts")
+            .with_operation_id(span.correlation_id);
 
         // Validate seed phrase first
         let phrase_str = phrase.expose_secret();
@@ -377,8 +392,10 @@ impl AccountCreator {
                 .with_index(index);
 
             accounts.push((account, signer));
+            progress.step(&format!("Derived account {} of {}", index + 1, count));
         }
 
+        progress.finish(&format!("Created {} accounts", count));
         self.logger.log_operation_complete(&span, &format!("Created {} accounts", count));
 
         Ok(accounts)
@@ -584,6 +601,25 @@ mod tests {
         assert_eq!(unique.len(), 3);
     }
 
+    #[test]
+    fn test_create_multiple_from_seed_reports_progress() {
+        let creator = AccountCreator::new();
+        let phrase = SecretString::from(TEST_MNEMONIC.to_string());
+        let events = std::sync::Mutex::new(Vec::new());
+        let reporter = |event: crate::wallet::progress::ProgressEvent| events.lock().unwrap().push(event);
+
+        creator
+            .create_multiple_from_seed_with_progress(&phrase, None, "m/44'/60'/0'/0", 3, &reporter)
+            .unwrap();
+
+        let events = events.into_inner().unwrap();
+        // start + one per account + finish
+        assert_eq!(events.len(), 5);
+        assert!(events.iter().all(|e| e.total == 3));
+        assert_eq!(events[2].completed, 2);
+        assert!(events.last().unwrap().finished);
+    }
+
     #[test]
     fn test_validate_seed_phrase() {
         let creator = AccountCreator::new();
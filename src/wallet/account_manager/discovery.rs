@@ -2,6 +2,7 @@
 use crate::error::account::{AccountError, AccountResult};
 use crate::performance::multicall::{MulticallBuilder, decode_balance_results};
 use crate::wallet::hardware::derivation::DerivationStandard;
+use crate::wallet::progress::{NoProgress, ProgressOperation, ProgressReporter, ProgressTracker};
 use alloy::primitives::{Address, Bytes, U256};
 use alloy::providers::Provider;
 use alloy::rpc::types::TransactionRequest;
@@ -121,6 +122,26 @@ impl AccountDiscovery {
         seed_phrase: &str,
         config: DiscoveryConfig,
     ) -> AccountResult<Vec<DiscoveredAccount>> {
+        self.discover_accounts_with_progress(seed_phrase, config, &NoProgress).await
+    }
+
+    /// Discover active accounts, reporting progress after each batch
+    ///
+    /// The total is an upper estimate (indices scanned plus the remaining gap),
+    /// so it grows whenever an active account resets the gap counter.
+    #[instrument(skip(self, seed_phrase, reporter), fields(correlation_id = %Uuid::new_v4()))]
+    pub async fn discover_accounts_with_progress(
+        &self,
+        seed_phrase: &str,
+        config: DiscoveryConfig,
+        reporter: &dyn ProgressReporter,
+    ) -> AccountResult<Vec<DiscoveredAccount>> {
+        let mut progress = ProgressTracker::start(
+            reporter,
+            ProgressOperation::Discovery,
+            config.gap_limit as u64,
+            "Scanning accounts",
+        );
         let mut discovered = Vec::new();
         let mut gap_count = 0;
         let mut current_index = 0;
@@ -164,6 +185,11 @@ impl AccountDiscovery {
             );
 
             current_index = end_index;
+            progress.update(
+                current_index as u64,
+                current_index as u64 + config.gap_limit.saturating_sub(gap_count) as u64,
+                &format!("Scanned {} addresses, found {} active", current_index, discovered.len()),
+            );
             
             // Safety break 
              if current_index > 2000 {
@@ -177,6 +203,7 @@ impl AccountDiscovery {
             scanned_indices = current_index,
             "Account discovery completed"
         );
+        progress.finish(&format!("Found {} active accounts", discovered.len()));
 
         Ok(discovered)
     }
@@ -192,7 +219,7 @@ impl AccountDiscovery {
         let mut accounts = Vec::new();
 
         for &index in indices {
-            let path_string = standard.path_template().replace("{index}", &index.to_string());
+            let path_string = standard.path_for_index(index);
             
             // Use MnemonicBuilder to derive address
             let signer = MnemonicBuilder::<English>::default()
@@ -2,11 +2,14 @@
 //!
 //! This module provides secure export capabilities for sensitive account data.
 //! All export operations are strictly controlled via `ExportAuthenticator` and require valid tokens.
+//! Exports are rate limited per hour, blocked during the cooldown that follows a
+//! failed password attempt, and every attempt is recorded in the audit log.
 
-use crate::error::{Result, SecurityError, VaughanError};
+use crate::error::Result;
 use crate::security::{ExportAuthenticator, SecureKeystore, AuthToken};
+use crate::wallet::keystore_v3::{self, KeystoreKdf};
 use alloy::primitives::Address;
-use secrecy::{SecretString, ExposeSecret};
+use secrecy::SecretString;
 use uuid::Uuid;
 
 /// Manages secure export of account secrets
@@ -47,14 +50,13 @@ impl<'a> AccountExporter<'a> {
             token.id
         );
 
-        // 1. Verify Authentication Token
-        self.authenticator.validate_token(token)?;
+        // 1. Verify Authentication Token, failure cooldown and export rate limit (Task 10.2)
+        self.authenticator.authorize_export("export_seed", address, token)?;
 
-        // 2. Check Export Rate Limit (Task 10.2)
-        self.authenticator.check_export_limit()?;
-
-        // 3. Retrieve Seed
-        let seed = self.keystore.get_decrypted_seed_phrase(&address, password).await.map_err(|e| {
+        // 2. Retrieve Seed
+        let seed = self.keystore.get_decrypted_seed_phrase(&address, password).await;
+        self.authenticator.record_export_result("export_seed", address, seed.as_ref().map(|_| ()));
+        let seed = seed.map_err(|e| {
              tracing::error!("❌ Export failed for {}: {}", correlation_id, e);
              e
         })?;
@@ -88,14 +90,13 @@ impl<'a> AccountExporter<'a> {
              token.id
         );
 
-        // 1. Verify Authentication Token
-        self.authenticator.validate_token(token)?;
-
-        // 2. Check Export Rate Limit (Task 10.2)
-        self.authenticator.check_export_limit()?;
+        // 1. Verify Authentication Token, failure cooldown and export rate limit (Task 10.2)
+        self.authenticator.authorize_export("export_private_key", address, token)?;
 
-        // 3. Retrieve Private Key
-        let pk = self.keystore.get_decrypted_private_key(&address, password).await.map_err(|e| {
+        // 2. Retrieve Private Key
+        let pk = self.keystore.get_decrypted_private_key(&address, password).await;
+        self.authenticator.record_export_result("export_private_key", address, pk.as_ref().map(|_| ()));
+        let pk = pk.map_err(|e| {
              tracing::error!("❌ Export failed for {}: {}", correlation_id, e);
              e
         })?;
@@ -104,13 +105,15 @@ impl<'a> AccountExporter<'a> {
         Ok(pk)
     }
 
-    /// Export to MetaMask V3 Keystore JSON
+    /// Export to a V3 JSON keystore (geth, MetaMask, MyCrypto)
     ///
     /// Requires:
     /// 1. A valid authentication token.
     /// 2. The wallet password (to decrypt the private key).
     /// 3. A *new* password for the keystore encryption (can be same as wallet, but separate arg).
     ///
+    /// The key is protected with geth's standard scrypt parameters.
+    ///
     /// # Returns
     /// The keystore JSON string.
     pub async fn export_to_v3_keystore(
@@ -120,8 +123,19 @@ impl<'a> AccountExporter<'a> {
         wallet_password: Option<&SecretString>,
         keystore_password: &SecretString,
     ) -> Result<String> {
-        use tempfile::tempdir;
+        self.export_to_v3_keystore_with_kdf(address, token, wallet_password, keystore_password, KeystoreKdf::standard())
+            .await
+    }
 
+    /// Export to a V3 JSON keystore using the given KDF parameters
+    pub async fn export_to_v3_keystore_with_kdf(
+        &self,
+        address: Address,
+        token: &AuthToken,
+        wallet_password: Option<&SecretString>,
+        keystore_password: &SecretString,
+        kdf: KeystoreKdf,
+    ) -> Result<String> {
         let correlation_id = Uuid::new_v4();
         tracing::warn!(
              "🚨 EXPORT_KEYSTORE attempt. ID: {}, Address: {}, Token: {}",
@@ -130,48 +144,21 @@ impl<'a> AccountExporter<'a> {
              token.id
         );
 
-        // 1. Verify Authentication Token
-        self.authenticator.validate_token(token)?;
+        // 1. Verify Authentication Token, failure cooldown and export rate limit (Task 10.2)
+        self.authenticator.authorize_export("export_keystore", address, token)?;
 
-        // 2. Check Export Rate Limit (Task 10.2)
-        self.authenticator.check_export_limit()?;
-
-        // 3. Retrieve Private Key
+        // 2. Retrieve Private Key
         // We get it as a SecretString (hex)
-        let pk_secret = self.keystore.get_decrypted_private_key(&address, wallet_password).await?;
-        let pk_hex = pk_secret.expose_secret();
-        
-        // 3. Encrypt using eth-keystore
-        // eth-keystore::encrypt_key writes to a directory. We'll use a temp dir.
-        let dir = tempdir().map_err(VaughanError::from)?;
-        
-        // eth-keystore expects bytes or hex? Documentation says:
-        // encrypt_key<P: AsRef<Path>, R: Rng + CryptoRng>(dir: P, rng: &mut R, pk: &[u8], password: &str) -> Result<String, Error>
-        // Ideally we pass it the key bytes.
-        let pk_bytes = hex::decode(pk_hex).map_err(|_| {
-             VaughanError::Security(SecurityError::InvalidPrivateKey)
-        })?;
+        let pk_secret = self.keystore.get_decrypted_private_key(&address, wallet_password).await;
+        self.authenticator.record_export_result("export_keystore", address, pk_secret.as_ref().map(|_| ()));
+        let pk_secret = pk_secret?;
 
-        // Use a random generator
-        let mut rng = rand::thread_rng();
-
-        let filename = eth_keystore::encrypt_key(
-            dir.path(),
-            &mut rng,
-            &pk_bytes,
-            keystore_password.expose_secret(),
-            None, // Use default options (standard pbkdf2)
-        ).map_err(|e| {
-             tracing::error!("eth-keystore encryption failed: {}", e);
-             VaughanError::Security(SecurityError::KeystoreError { 
-                 message: format!("Encryption failed: {}", e) 
-             })
+        // 3. Encrypt into the Web3 Secret Storage format
+        let json_content = keystore_v3::encrypt_keystore(&pk_secret, keystore_password, kdf).map_err(|e| {
+             tracing::error!("V3 keystore encryption failed: {}", e);
+             e
         })?;
 
-        // 4. Read the file back
-        let file_path = dir.path().join(filename);
-        let json_content = std::fs::read_to_string(file_path).map_err(VaughanError::from)?;
-
         tracing::warn!("✅ KEYSTORE EXPORT SUCCESS. ID: {}", correlation_id);
         Ok(json_content)
     }
@@ -182,8 +169,11 @@ mod tests {
     use super::*;
     use crate::security::keychain::MockKeychain;
     use crate::security::SecureKeystoreImpl;
+    use crate::error::SecurityError;
     use crate::security::ExportAuthenticator;
+    use crate::telemetry::audit::{AuditEvent, AuditLog};
     use secrecy::ExposeSecret;
+    use std::sync::Arc;
 
     // Helper to create functional environment
     async fn setup_env() -> (SecureKeystoreImpl, ExportAuthenticator) {
@@ -224,6 +214,9 @@ mod tests {
     #[tokio::test]
     async fn test_export_private_key_success() {
         let (mut keystore, auth) = setup_env().await;
+        let dir = tempfile::tempdir().unwrap();
+        let log = Arc::new(AuditLog::open(dir.path().join("audit.log"), &[7u8; 32]).unwrap());
+        let auth = auth.with_audit_log(log.clone());
         let account = keystore.create_account("Test".into()).await.unwrap();
         let exporter = AccountExporter::new(&keystore, &auth);
         
@@ -231,5 +224,15 @@ mod tests {
         
         let pk = exporter.export_private_key(account.address, &token, None).await.unwrap();
         assert_eq!(pk.expose_secret().len(), 64);
+        let recorded = log.entries().unwrap().into_iter().any(|entry| {
+            entry.event
+                == AuditEvent::ExportAttempt {
+                    outcome: crate::security::ExportAuditKind::ExportSucceeded,
+                    operation: "export_private_key".into(),
+                    account: Some(account.address),
+                    detail: String::new(),
+                }
+        });
+        assert!(recorded);
     }
 }
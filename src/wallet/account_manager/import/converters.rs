@@ -15,6 +15,7 @@ use std::str::FromStr;
 
 use crate::error::WalletError;
 use crate::wallet::account_manager::types::{Account, ImportMetadata, ImportSourceType};
+use crate::wallet::keystore_v3;
 
 /// Convert a validated seed phrase into an Account and Signer
 ///
@@ -103,11 +104,11 @@ pub fn private_key_to_account(
 
 /// Convert a keystore JSON into an Account and Signer
 ///
-/// Decrypts EIP-2335 keystore format using provided password
-/// Uses Alloy's keystore utilities for decryption
+/// Decrypts a Web3 Secret Storage (V3) keystore as written by geth, MetaMask
+/// and MyCrypto, using either scrypt or PBKDF2 key derivation
 ///
 /// # Arguments
-/// * `keystore_json` - EIP-2335 keystore JSON string
+/// * `keystore_json` - V3 keystore JSON string
 /// * `password` - Keystore decryption password
 /// * `metadata` - Import metadata (name, tags, etc.)
 pub fn keystore_to_account(
@@ -115,21 +116,13 @@ pub fn keystore_to_account(
     password: &SecretString,
     metadata: ImportMetadata,
 ) -> Result<(Account, PrivateKeySigner), WalletError> {
-    // Parse keystore JSON
-    let _keystore: eth_keystore::EthKeystore = serde_json::from_str(keystore_json)
-        .map_err(|e| WalletError::WalletError { 
-            message: format!("Invalid keystore JSON: {}", e) 
-        })?;
-
     // Decrypt keystore to get private key
-    let private_key_bytes = eth_keystore::decrypt_key(keystore_json, password.expose_secret())
+    let decrypted = keystore_v3::decrypt_keystore(keystore_json, password)
         .map_err(|e| WalletError::WalletError { 
             message: format!("Decryption failed: {}", e) 
         })?;
 
-    // Convert bytes to hex string for PrivateKeySigner
-    let key_hex = hex::encode(private_key_bytes);
-    let signer = PrivateKeySigner::from_str(&key_hex)
+    let signer = PrivateKeySigner::from_str(decrypted.private_key.expose_secret())
         .map_err(|_| WalletError::InvalidPrivateKey)?;
 
     // Create account from signer
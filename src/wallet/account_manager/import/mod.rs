@@ -4,11 +4,13 @@
 // - BIP39 seed phrases (12/15/18/21/24 words)
 // - Private keys (hex format)
 // - Keystore files (EIP-2335 format)
+// - Trust Wallet and Coinbase Wallet backups
 //
 // Architecture:
 // - parsers.rs: Format detection and parsing
 // - validators.rs: Input validation and error checking
 // - converters.rs: Conversion to Account/Signer pairs
+// - wallet_backups.rs: Third-party wallet backup formats and derivation conventions
 //
 // Attribution: Uses Alloy libraries for all cryptographic operations
 // Validation patterns inspired by MetaMask's import flow
@@ -16,6 +18,7 @@
 mod parsers;
 mod validators;
 mod converters;
+mod wallet_backups;
 
 // Re-export types for convenience
 pub use crate::wallet::account_manager::types::{Account, ImportMetadata, ImportSourceType};
@@ -23,10 +26,16 @@ pub use crate::wallet::account_manager::types::{Account, ImportMetadata, ImportS
 // Re-export validation result for external use
 pub use validators::ValidationResult;
 
+pub use wallet_backups::{
+    parse_coinbase_wallet_backup, parse_mnemonic_container, parse_trust_wallet_backup, BackupSecret, WalletBackup,
+    WalletBackupSource,
+};
+
 use alloy::signers::local::PrivateKeySigner;
 use secrecy::SecretString;
 
 use crate::error::WalletError;
+use crate::wallet::progress::{ProgressOperation, ProgressReporter, ProgressTracker};
 
 /// Main account importer providing unified import interface
 ///
@@ -130,6 +139,32 @@ impl AccountImporter {
         converters::keystore_to_account(keystore_json, password, metadata)
     }
 
+    /// Import account from a keystore file, reporting progress
+    ///
+    /// Keystore decryption (scrypt/PBKDF2) is the slow step; the reporter
+    /// receives an event before and after it.
+    pub fn import_from_keystore_with_progress(
+        &self,
+        keystore_json: &str,
+        password: &SecretString,
+        metadata: ImportMetadata,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(Account, PrivateKeySigner), WalletError> {
+        let mut progress = ProgressTracker::start(reporter, ProgressOperation::Import, 2, "Validating keystore");
+
+        let validation = validators::validate_import_data(keystore_json);
+        if !validation.is_valid {
+            return Err(WalletError::WalletError {
+                message: validation.error.unwrap_or_else(|| "Invalid keystore".to_string()),
+            });
+        }
+        progress.step("Decrypting keystore");
+
+        let imported = converters::keystore_to_account(keystore_json, password, metadata)?;
+        progress.finish("Keystore imported");
+        Ok(imported)
+    }
+
     /// Validate import data without actually importing
     ///
     /// Useful for UI validation before user confirms import
@@ -167,6 +202,52 @@ impl AccountImporter {
         // Derive accounts
         converters::derive_multiple_accounts(phrase, password, count, start_index)
     }
+
+    /// Derive multiple accounts from a seed phrase, reporting one step per account
+    pub fn derive_multiple_accounts_with_progress(
+        &self,
+        phrase: &SecretString,
+        password: Option<&str>,
+        count: u32,
+        start_index: u32,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<Vec<(Account, PrivateKeySigner)>, WalletError> {
+        parsers::parse_seed_phrase(phrase)?;
+
+        let mut progress = ProgressTracker::start(reporter, ProgressOperation::Import, count as u64, "Deriving accounts");
+        let mut accounts = Vec::with_capacity(count as usize);
+
+        for i in 0..count {
+            accounts.extend(converters::derive_multiple_accounts(phrase, password, 1, start_index + i)?);
+            progress.step(&format!("Derived account {} of {}", i + 1, count));
+        }
+
+        progress.finish(&format!("Derived {} accounts", count));
+        Ok(accounts)
+    }
+
+    /// Import the accounts of a parsed Trust Wallet or Coinbase Wallet backup
+    ///
+    /// Derives one account per path the source wallet used and checks that
+    /// every address recorded in the backup was reproduced.
+    pub fn import_from_wallet_backup(
+        &self,
+        backup: &WalletBackup,
+        metadata: ImportMetadata,
+    ) -> Result<Vec<(Account, PrivateKeySigner)>, WalletError> {
+        let accounts = match &backup.secret {
+            BackupSecret::Mnemonic(phrase) => backup
+                .derivation_paths
+                .iter()
+                .map(|path| converters::seed_phrase_to_account(phrase, None, Some(path), metadata.clone()))
+                .collect::<Result<Vec<_>, _>>()?,
+            BackupSecret::PrivateKey(key) => vec![converters::private_key_to_account(key, metadata)?],
+        };
+
+        let derived: Vec<_> = accounts.iter().map(|(account, _)| account.address).collect();
+        wallet_backups::verify_addresses(backup, &derived)?;
+        Ok(accounts)
+    }
 }
 
 impl Default for AccountImporter {
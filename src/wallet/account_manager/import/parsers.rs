@@ -64,8 +64,8 @@ impl ParseResult {
 pub fn detect_import_format(data: &str) -> ParseResult {
     let trimmed = data.trim();
 
-    // Check for keystore JSON format
-    if trimmed.starts_with('{') && trimmed.contains("\"crypto\"") {
+    // Check for keystore JSON format (geth writes the section as "Crypto")
+    if trimmed.starts_with('{') && (trimmed.contains("\"crypto\"") || trimmed.contains("\"Crypto\"")) {
         return ParseResult::valid(ImportSourceType::Keystore);
     }
 
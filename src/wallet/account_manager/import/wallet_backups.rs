@@ -0,0 +1,360 @@
+// Third-Party Wallet Backup Parsers
+//
+// Reads the recovery material other wallets give their users, so people
+// migrating to Vaughan land on the addresses they already had:
+// - Trust Wallet: the recovery phrase, or a wallet-core keystore JSON (a V3
+//   `crypto` section around the mnemonic or private key, plus the wallet's
+//   active accounts with their derivation paths)
+// - Coinbase Wallet: the recovery phrase, or a JSON backup holding the phrase
+//   (`mnemonic` / `recoveryPhrase`) and the accounts the wallet had created
+//
+// Derivation conventions:
+// - Trust Wallet has a single Ethereum account per wallet at m/44'/60'/0'/0/0,
+//   shared by every EVM chain it lists (BSC, Polygon, ... all use coin type 60)
+// - Coinbase Wallet adds accounts by address index: m/44'/60'/0'/0/{i}
+//
+// Phrases copied out of either app often carry numbering ("1. word"),
+// commas or line breaks; these are stripped before BIP39 validation.
+
+use alloy::primitives::Address;
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use zeroize::Zeroizing;
+
+use super::parsers;
+use crate::error::WalletError;
+use crate::wallet::keystore_v3;
+
+/// Wallet a backup was exported from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WalletBackupSource {
+    TrustWallet,
+    CoinbaseWallet,
+}
+
+impl WalletBackupSource {
+    /// Derivation path of the source wallet's `index`-th Ethereum account
+    pub fn derivation_path(self, index: u32) -> String {
+        format!("m/44'/60'/0'/0/{index}")
+    }
+}
+
+impl fmt::Display for WalletBackupSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TrustWallet => write!(f, "Trust Wallet"),
+            Self::CoinbaseWallet => write!(f, "Coinbase Wallet"),
+        }
+    }
+}
+
+/// Secret recovered from a third-party backup
+#[derive(Debug, Clone)]
+pub enum BackupSecret {
+    Mnemonic(SecretString),
+    /// Hex private key
+    PrivateKey(SecretString),
+}
+
+/// A parsed third-party wallet backup
+#[derive(Debug, Clone)]
+pub struct WalletBackup {
+    pub source: WalletBackupSource,
+    pub name: Option<String>,
+    pub secret: BackupSecret,
+    /// Derivation paths of the accounts the source wallet used (mnemonics only)
+    pub derivation_paths: Vec<String>,
+    /// Addresses recorded in the backup; derived accounts must cover them
+    pub expected_addresses: Vec<Address>,
+}
+
+impl WalletBackup {
+    fn from_mnemonic(source: WalletBackupSource, phrase: SecretString, account_count: u32) -> Self {
+        Self {
+            source,
+            name: None,
+            secret: BackupSecret::Mnemonic(phrase),
+            derivation_paths: (0..account_count.max(1)).map(|i| source.derivation_path(i)).collect(),
+            expected_addresses: Vec::new(),
+        }
+    }
+}
+
+/// Normalize a copied recovery phrase and validate it as BIP39
+///
+/// Accepts numbered lists ("1. abandon 2. ability"), comma or newline
+/// separated words, surrounding quotes or brackets and mixed case.
+pub fn parse_mnemonic_container(text: &str) -> Result<SecretString, WalletError> {
+    let words: Zeroizing<Vec<String>> = Zeroizing::new(
+        text.split(|c: char| c.is_whitespace() || matches!(c, ',' | ';'))
+            .map(|token| {
+                token
+                    .trim_matches(|c: char| matches!(c, '"' | '\'' | '[' | ']' | '{' | '}'))
+                    .trim_start_matches(|c: char| c.is_ascii_digit())
+                    .trim_start_matches(['.', ')', ':', '-'])
+                    .to_lowercase()
+            })
+            .filter(|word| !word.is_empty())
+            .collect(),
+    );
+    let phrase = SecretString::new(words.join(" "));
+    parsers::parse_seed_phrase(&phrase)?;
+    Ok(phrase)
+}
+
+#[derive(Deserialize)]
+struct TrustStoredKey {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default, rename = "activeAccounts")]
+    active_accounts: Vec<TrustAccount>,
+}
+
+#[derive(Deserialize)]
+struct TrustAccount {
+    address: String,
+    #[serde(default, rename = "derivationPath")]
+    derivation_path: Option<String>,
+}
+
+/// Parse a Trust Wallet recovery phrase or wallet-core keystore JSON
+///
+/// `password` decrypts the keystore and is ignored for plain phrases.
+pub fn parse_trust_wallet_backup(data: &str, password: Option<&SecretString>) -> Result<WalletBackup, WalletError> {
+    const SOURCE: WalletBackupSource = WalletBackupSource::TrustWallet;
+    if !data.trim_start().starts_with('{') {
+        return Ok(WalletBackup::from_mnemonic(SOURCE, parse_mnemonic_container(data)?, 1));
+    }
+
+    let stored: TrustStoredKey = serde_json::from_str(data).map_err(|e| WalletError::WalletError {
+        message: format!("Invalid Trust Wallet keystore: {}", e),
+    })?;
+    let password = password.ok_or_else(|| WalletError::WalletError {
+        message: "Trust Wallet keystore requires its password".to_string(),
+    })?;
+    let plaintext = keystore_v3::decrypt_keystore_data(data, password).map_err(|e| WalletError::WalletError {
+        message: format!("Decryption failed: {}", e),
+    })?;
+
+    // Only Ethereum-derived accounts matter; other chains use other coin types
+    let evm_accounts: Vec<&TrustAccount> = stored
+        .active_accounts
+        .iter()
+        .filter(|a| a.address.starts_with("0x"))
+        .collect();
+    let mut expected_addresses = Vec::new();
+    for account in &evm_accounts {
+        let address: Address = account.address.parse().map_err(|_| WalletError::WalletError {
+            message: format!("Invalid address in Trust Wallet keystore: {}", account.address),
+        })?;
+        if !expected_addresses.contains(&address) {
+            expected_addresses.push(address);
+        }
+    }
+
+    let (secret, derivation_paths) = match stored.kind.as_str() {
+        "mnemonic" => {
+            let phrase = std::str::from_utf8(&plaintext).map_err(|_| WalletError::WalletError {
+                message: "Trust Wallet keystore does not contain a valid mnemonic".to_string(),
+            })?;
+            let mut paths: Vec<String> = Vec::new();
+            for path in evm_accounts.iter().filter_map(|a| a.derivation_path.as_deref()) {
+                if path.starts_with("m/44'/60'/") && !paths.iter().any(|p| p == path) {
+                    paths.push(path.to_string());
+                }
+            }
+            if paths.is_empty() {
+                paths.push(SOURCE.derivation_path(0));
+            }
+            (BackupSecret::Mnemonic(parse_mnemonic_container(phrase)?), paths)
+        }
+        "private-key" => {
+            let key = SecretString::new(hex::encode(plaintext.as_slice()));
+            parsers::parse_private_key(&key)?;
+            (BackupSecret::PrivateKey(key), Vec::new())
+        }
+        other => {
+            return Err(WalletError::WalletError {
+                message: format!("Unsupported Trust Wallet keystore type: {}", other),
+            })
+        }
+    };
+
+    Ok(WalletBackup {
+        source: SOURCE,
+        name: stored.name.filter(|n| !n.is_empty()),
+        secret,
+        derivation_paths,
+        expected_addresses,
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CoinbaseAccount {
+    Address(String),
+    Detailed {
+        address: String,
+        #[serde(default)]
+        index: Option<u32>,
+    },
+}
+
+impl CoinbaseAccount {
+    fn address(&self) -> &str {
+        match self {
+            Self::Address(address) | Self::Detailed { address, .. } => address,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CoinbaseBackup {
+    #[serde(alias = "recoveryPhrase", alias = "seedPhrase")]
+    mnemonic: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default, alias = "addresses")]
+    accounts: Vec<CoinbaseAccount>,
+}
+
+/// Parse a Coinbase Wallet recovery phrase or JSON backup
+pub fn parse_coinbase_wallet_backup(data: &str) -> Result<WalletBackup, WalletError> {
+    const SOURCE: WalletBackupSource = WalletBackupSource::CoinbaseWallet;
+    if !data.trim_start().starts_with('{') {
+        return Ok(WalletBackup::from_mnemonic(SOURCE, parse_mnemonic_container(data)?, 1));
+    }
+
+    let backup: CoinbaseBackup = serde_json::from_str(data).map_err(|e| WalletError::WalletError {
+        message: format!("Invalid Coinbase Wallet backup: {}", e),
+    })?;
+    let phrase = parse_mnemonic_container(&Zeroizing::new(backup.mnemonic.clone()))?;
+
+    let mut wallet_backup = WalletBackup::from_mnemonic(SOURCE, phrase, 1);
+    wallet_backup.name = backup.name.clone().filter(|n| !n.is_empty());
+    if !backup.accounts.is_empty() {
+        // Accounts without an explicit index were created in order
+        wallet_backup.derivation_paths = backup
+            .accounts
+            .iter()
+            .enumerate()
+            .map(|(position, account)| match account {
+                CoinbaseAccount::Detailed { index: Some(index), .. } => SOURCE.derivation_path(*index),
+                _ => SOURCE.derivation_path(position as u32),
+            })
+            .collect();
+        wallet_backup.expected_addresses = backup
+            .accounts
+            .iter()
+            .map(|account| {
+                account.address().parse().map_err(|_| WalletError::WalletError {
+                    message: format!("Invalid address in Coinbase Wallet backup: {}", account.address()),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+    }
+    Ok(wallet_backup)
+}
+
+impl Drop for CoinbaseBackup {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.mnemonic);
+    }
+}
+
+/// Check that derived addresses cover every address recorded in the backup
+pub(super) fn verify_addresses(backup: &WalletBackup, derived: &[Address]) -> Result<(), WalletError> {
+    let missing: Vec<String> = backup
+        .expected_addresses
+        .iter()
+        .filter(|address| !derived.contains(address))
+        .map(|address| address.to_string())
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(WalletError::WalletError {
+        message: format!(
+            "{} backup lists addresses that were not derived ({}); the backup may use a BIP39 passphrase",
+            backup.source,
+            missing.join(", ")
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::ExposeSecret;
+    use crate::wallet::account_manager::import::{AccountImporter, ImportMetadata};
+    use crate::wallet::keystore_v3::KeystoreKdf;
+
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_numbered_phrase_is_normalized() {
+        let copied =
+            "1. Test 2. test 3. test 4. test\n5) test 6) test, 7) test, 8) test\n9:test 10:test 11:test 12:JUNK";
+        let phrase = parse_mnemonic_container(copied).unwrap();
+        assert_eq!(phrase.expose_secret(), PHRASE);
+        assert!(parse_mnemonic_container("1. test 2. junk").is_err());
+    }
+
+    #[test]
+    fn test_trust_wallet_keystore() {
+        let password = SecretString::new("trust password".into());
+        let mut stored: serde_json::Value = serde_json::from_str(
+            &keystore_v3::encrypt_keystore_data(PHRASE.as_bytes(), &password, KeystoreKdf::Pbkdf2 { c: 1024 }).unwrap(),
+        )
+        .unwrap();
+        stored["type"] = "mnemonic".into();
+        stored["name"] = "Main wallet".into();
+        stored["activeAccounts"] = serde_json::json!([
+            { "address": "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "coin": 60, "derivationPath": "m/44'/60'/0'/0/0" },
+            { "address": "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "coin": 20000714, "derivationPath": "m/44'/60'/0'/0/0" },
+            { "address": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh", "coin": 0, "derivationPath": "m/84'/0'/0'/0/0" }
+        ]);
+        let json = stored.to_string();
+
+        let backup = parse_trust_wallet_backup(&json, Some(&password)).unwrap();
+        assert_eq!(backup.name.as_deref(), Some("Main wallet"));
+        assert_eq!(backup.derivation_paths, vec!["m/44'/60'/0'/0/0".to_string()]);
+        assert_eq!(backup.expected_addresses.len(), 1);
+        assert!(matches!(&backup.secret, BackupSecret::Mnemonic(p) if p.expose_secret() == PHRASE));
+
+        assert!(parse_trust_wallet_backup(&json, None).is_err());
+        assert!(parse_trust_wallet_backup(&json, Some(&SecretString::new("wrong".into()))).is_err());
+    }
+
+    #[test]
+    fn test_coinbase_backup_maps_account_indexes() {
+        let json = serde_json::json!({
+            "recoveryPhrase": PHRASE,
+            "accounts": [
+                { "address": "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266" },
+                { "address": "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC", "index": 2 }
+            ]
+        })
+        .to_string();
+
+        let backup = parse_coinbase_wallet_backup(&json).unwrap();
+        assert_eq!(backup.derivation_paths, vec!["m/44'/60'/0'/0/0", "m/44'/60'/0'/0/2"]);
+
+        let importer = AccountImporter::new();
+        let accounts = importer
+            .import_from_wallet_backup(&backup, ImportMetadata::new())
+            .unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[1].0.address, backup.expected_addresses[1]);
+
+        // An address the phrase does not derive (e.g. a passphrase-protected wallet)
+        let mut mismatched = backup.clone();
+        mismatched.expected_addresses.push(Address::repeat_byte(0x42));
+        assert!(importer
+            .import_from_wallet_backup(&mismatched, ImportMetadata::new())
+            .is_err());
+    }
+}
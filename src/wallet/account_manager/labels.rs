@@ -0,0 +1,265 @@
+//! Derived account labels
+//!
+//! Turns discovery scan results into informative default names such as
+//! `"Account 3 – 2.1 ETH, last active 2023-05"` instead of generic ones, so
+//! users can tell restored accounts apart. Suggestions are reviewed (accepted,
+//! renamed or skipped) before they are imported.
+
+use super::discovery::DiscoveredAccount;
+use super::types::ImportMetadata;
+use crate::blockchain::explorer_apis::ExplorerApiManager;
+use crate::error::account::{AccountError, AccountResult};
+use crate::network::NetworkId;
+use alloy::primitives::utils::format_units;
+use alloy::primitives::{Address, U256};
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use tokio::sync::Mutex;
+
+/// Significant fractional digits shown for balances
+const BALANCE_DECIMALS: usize = 4;
+
+/// Source of the most recent activity time for an address
+#[async_trait]
+pub trait ActivityLookup: Send + Sync {
+    async fn last_active(&self, address: Address) -> AccountResult<Option<DateTime<Utc>>>;
+}
+
+/// Last activity from a block explorer's transaction list
+pub struct ExplorerActivityLookup {
+    explorer: Mutex<ExplorerApiManager>,
+    network: NetworkId,
+}
+
+impl ExplorerActivityLookup {
+    pub fn new(explorer: ExplorerApiManager, network: NetworkId) -> Self {
+        Self {
+            explorer: Mutex::new(explorer),
+            network,
+        }
+    }
+}
+
+#[async_trait]
+impl ActivityLookup for ExplorerActivityLookup {
+    async fn last_active(&self, address: Address) -> AccountResult<Option<DateTime<Utc>>> {
+        let transactions = self
+            .explorer
+            .lock()
+            .await
+            .get_transactions(self.network, &format!("{address:?}"))
+            .await
+            .map_err(|e| AccountError::operation_failed("last_active", e))?;
+
+        Ok(transactions
+            .iter()
+            .map(|tx| tx.timestamp)
+            .max()
+            .and_then(|timestamp| Utc.timestamp_opt(timestamp as i64, 0).single()))
+    }
+}
+
+/// Compact balance for labels: `2.1`, `0.0042`, `1250`
+pub fn format_label_balance(balance: U256, decimals: u8) -> String {
+    let formatted = format_units(balance, decimals).unwrap_or_else(|_| balance.to_string());
+    let Some((whole, fraction)) = formatted.split_once('.') else {
+        return formatted;
+    };
+
+    // Keep BALANCE_DECIMALS significant digits after leading zeros
+    let leading_zeros = fraction.chars().take_while(|c| *c == '0').count();
+    let keep = if whole == "0" {
+        leading_zeros + BALANCE_DECIMALS
+    } else {
+        BALANCE_DECIMALS.min(fraction.len())
+    };
+    let fraction = fraction[..keep.min(fraction.len())].trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+/// Build a label from an account's scan results
+///
+/// Accounts are numbered from 1, so index 2 becomes "Account 3".
+pub fn derive_label(account: &DiscoveredAccount, symbol: &str, last_active: Option<DateTime<Utc>>) -> String {
+    let mut details = vec![format!("{} {symbol}", format_label_balance(account.balance, 18))];
+    match last_active {
+        Some(when) => details.push(format!("last active {}", when.format("%Y-%m"))),
+        None if account.nonce > 0 => {
+            let plural = if account.nonce == 1 { "" } else { "s" };
+            details.push(format!("{} tx{plural}", account.nonce));
+        }
+        None => {}
+    }
+    format!("Account {} – {}", account.index + 1, details.join(", "))
+}
+
+/// A proposed name for a discovered account, pending review
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelSuggestion {
+    pub index: u32,
+    pub address: Address,
+    pub derivation_path: String,
+    /// Generated label
+    pub suggested: String,
+    /// Name that will be used on import; starts as `suggested`
+    pub name: String,
+    /// Whether the account should be imported
+    pub accepted: bool,
+}
+
+impl LabelSuggestion {
+    pub fn new(account: &DiscoveredAccount, suggested: String) -> Self {
+        Self {
+            index: account.index,
+            address: account.address,
+            derivation_path: account.derivation_path.clone(),
+            name: suggested.clone(),
+            suggested,
+            accepted: true,
+        }
+    }
+
+    /// Replace the name; blank names fall back to the suggestion
+    pub fn rename(&mut self, name: &str) {
+        let name = name.trim();
+        self.name = if name.is_empty() {
+            self.suggested.clone()
+        } else {
+            name.to_string()
+        };
+    }
+
+    pub fn is_edited(&self) -> bool {
+        self.name != self.suggested
+    }
+
+    /// Import metadata carrying the chosen name
+    pub fn import_metadata(&self) -> ImportMetadata {
+        ImportMetadata::new().with_name(&self.name).with_tag("discovered")
+    }
+}
+
+/// Generate label suggestions for discovered accounts
+///
+/// `activity` adds a "last active" date where available; lookup failures only
+/// drop the date from that account's label.
+pub async fn suggest_labels(
+    accounts: &[DiscoveredAccount],
+    symbol: &str,
+    activity: Option<&dyn ActivityLookup>,
+) -> Vec<LabelSuggestion> {
+    let mut suggestions = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let last_active = match activity {
+            Some(lookup) if account.nonce > 0 => lookup.last_active(account.address).await.unwrap_or_else(|e| {
+                tracing::debug!(address = %account.address, "Activity lookup failed: {}", e);
+                None
+            }),
+            _ => None,
+        };
+        suggestions.push(LabelSuggestion::new(
+            account,
+            derive_label(account, symbol, last_active),
+        ));
+    }
+    suggestions
+}
+
+/// Accepted suggestions, in derivation order
+pub fn accepted(suggestions: &[LabelSuggestion]) -> Vec<&LabelSuggestion> {
+    let mut accepted: Vec<_> = suggestions.iter().filter(|s| s.accepted).collect();
+    accepted.sort_by_key(|s| s.index);
+    accepted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn account(index: u32, balance: U256, nonce: u64) -> DiscoveredAccount {
+        DiscoveredAccount {
+            address: Address::repeat_byte(index as u8 + 1),
+            index,
+            balance,
+            nonce,
+            active: true,
+            derivation_path: format!("m/44'/60'/0'/0/{index}"),
+        }
+    }
+
+    fn eth(milli: u64) -> U256 {
+        U256::from(milli) * U256::from(10u64.pow(15))
+    }
+
+    struct FixedActivity(HashMap<Address, DateTime<Utc>>);
+
+    #[async_trait]
+    impl ActivityLookup for FixedActivity {
+        async fn last_active(&self, address: Address) -> AccountResult<Option<DateTime<Utc>>> {
+            match self.0.get(&address) {
+                Some(when) => Ok(Some(*when)),
+                None => Err(AccountError::operation_failed("last_active", "explorer down")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_label_balance() {
+        assert_eq!(format_label_balance(eth(2_100), 18), "2.1");
+        assert_eq!(format_label_balance(eth(1_250_000), 18), "1250");
+        assert_eq!(format_label_balance(U256::from(4_200_000_000_000_000u64), 18), "0.0042");
+        assert_eq!(format_label_balance(U256::from(1_234_567u64), 6), "1.2345");
+        assert_eq!(format_label_balance(U256::ZERO, 18), "0");
+    }
+
+    #[test]
+    fn test_derive_label() {
+        let when = Utc.with_ymd_and_hms(2023, 5, 14, 12, 0, 0).unwrap();
+        assert_eq!(
+            derive_label(&account(2, eth(2_100), 7), "ETH", Some(when)),
+            "Account 3 – 2.1 ETH, last active 2023-05"
+        );
+        assert_eq!(
+            derive_label(&account(0, U256::ZERO, 1), "PLS", None),
+            "Account 1 – 0 PLS, 1 tx"
+        );
+        assert_eq!(
+            derive_label(&account(4, eth(500), 0), "ETH", None),
+            "Account 5 – 0.5 ETH"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_suggest_labels_with_activity() {
+        let active = account(0, eth(1_000), 3);
+        let failing = account(1, eth(1_000), 2);
+        let when = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        let lookup = FixedActivity(HashMap::from([(active.address, when)]));
+
+        let suggestions = suggest_labels(&[active, failing], "ETH", Some(&lookup)).await;
+        assert_eq!(suggestions[0].name, "Account 1 – 1 ETH, last active 2024-01");
+        assert_eq!(suggestions[1].name, "Account 2 – 1 ETH, 2 txs");
+        assert!(suggestions.iter().all(|s| s.accepted && !s.is_edited()));
+    }
+
+    #[tokio::test]
+    async fn test_review_before_import() {
+        let accounts = [account(3, eth(1), 1), account(0, eth(2), 1), account(1, eth(3), 1)];
+        let mut suggestions = suggest_labels(&accounts, "ETH", None).await;
+
+        suggestions[1].rename("  Savings ");
+        suggestions[2].accepted = false;
+        suggestions[0].rename("   ");
+
+        let accepted = accepted(&suggestions);
+        assert_eq!(accepted.iter().map(|s| s.index).collect::<Vec<_>>(), vec![0, 3]);
+        assert_eq!(accepted[0].import_metadata().name.as_deref(), Some("Savings"));
+        assert!(accepted[0].is_edited());
+        assert!(!accepted[1].is_edited());
+    }
+}
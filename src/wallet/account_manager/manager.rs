@@ -0,0 +1,888 @@
+//! Keystore-backed Account Manager
+//!
+//! [`AccountManager`] is the production implementation of [`AccountManagerTrait`].
+//! Accounts are stored in the shared [`SecureKeystore`]; seed-based accounts go
+//! through the [`SeedManager`], which keeps the mnemonic in the keychain encrypted
+//! with the wallet password. Private-key accounts are protected by the OS keychain
+//! alone, so the password only matters for seed-based accounts and exports.
+
+use alloy::primitives::{Address, U256};
+use async_trait::async_trait;
+use secrecy::{ExposeSecret, SecretString};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use super::{
+    AccountConfig, AccountImporter, AccountManagerTrait, AccountMetadataQueries, AccountType, AuthToken,
+    AuthorizedOperation, ImportMetadata, ImportSource,
+};
+use crate::error::{AccountError, VaughanError};
+use crate::security::keystore::encryption::encrypt_with_password;
+use crate::security::seed::derivation::{account_passphrase, effective_passphrase, ValidatedPath};
+use crate::security::{
+    EncryptionType, ExportAuthenticator, KeychainInterface, SecureAccount, SecureExport, SecureKeystore,
+    SecurityProfile, SeedManager,
+};
+use crate::wallet::hardware::{AddressActivity, DerivationStandard};
+use crate::wallet::keystore_v3::{self, KeystoreKdf};
+
+/// Account manager backed by the secure keystore
+#[derive(Debug)]
+pub struct AccountManager {
+    keystore: Arc<RwLock<SecureKeystore>>,
+    /// Keychain shared with the keystore, used for seed storage
+    keychain: Box<dyn KeychainInterface>,
+    /// Security profile of the keystore, whose seed service holds new seeds
+    profile: SecurityProfile,
+    current: Option<SecureAccount>,
+    locked: bool,
+    /// Cooldown and hourly allowance for seed and key exports
+    exports: ExportAuthenticator,
+}
+
+impl AccountManager {
+    /// Create a manager over `keystore`; `keychain` must be the keystore's keychain
+    ///
+    /// Exports draw on [`ExportAuthenticator::shared`].
+    pub fn new(keystore: Arc<RwLock<SecureKeystore>>, keychain: Box<dyn KeychainInterface>) -> Self {
+        Self {
+            keystore,
+            keychain,
+            profile: SecurityProfile::Primary,
+            current: None,
+            locked: false,
+            exports: ExportAuthenticator::shared().clone(),
+        }
+    }
+
+    /// Manage the accounts of `profile`, which the keystore must hold
+    pub fn with_profile(mut self, profile: SecurityProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Follow the keystore to another security profile
+    ///
+    /// `keychain` must be the keystore's new keychain. The current account
+    /// belongs to the old profile and is deselected.
+    pub fn switch_profile(&mut self, keychain: Box<dyn KeychainInterface>, profile: SecurityProfile) {
+        self.keychain = keychain;
+        self.profile = profile;
+        self.current = None;
+    }
+
+    /// Limit exports with `exports` instead of the shared authenticator
+    pub fn with_export_authenticator(mut self, exports: ExportAuthenticator) -> Self {
+        self.exports = exports;
+        self
+    }
+
+    /// The underlying keystore
+    pub fn keystore(&self) -> Arc<RwLock<SecureKeystore>> {
+        Arc::clone(&self.keystore)
+    }
+
+    /// The current account, if one is selected
+    pub fn current(&self) -> Option<&SecureAccount> {
+        self.current.as_ref()
+    }
+
+    /// Make `account` current without looking it up in the keystore
+    pub fn select(&mut self, account: SecureAccount) {
+        self.current = Some(account);
+    }
+
+    /// Unlock with an account the caller has already authenticated (GUI login)
+    pub fn unlock_with_account(&mut self, account: SecureAccount) {
+        self.current = Some(account);
+        self.locked = false;
+    }
+
+    fn seed_manager(&self) -> SeedManager {
+        SeedManager::for_profile(self.keychain.clone_box(), self.profile)
+    }
+
+    fn ensure_unlocked(&self) -> Result<(), AccountError> {
+        if self.locked {
+            return Err(AccountError::account_locked());
+        }
+        Ok(())
+    }
+
+    /// Store a seed-based account at `derivation_path` (default BIP-44 index 0)
+    ///
+    /// An account derived with a BIP-39 `passphrase` is tagged as needing it;
+    /// the passphrase itself is not stored.
+    async fn add_seed_account(
+        &mut self,
+        name: String,
+        phrase: &SecretString,
+        password: &SecretString,
+        derivation_path: Option<String>,
+        passphrase: Option<&SecretString>,
+    ) -> crate::error::Result<SecureAccount> {
+        let passphrase = effective_passphrase(passphrase);
+        let seed_manager = self.seed_manager();
+        let mut account = seed_manager
+            .create_wallet_from_seed_encrypted(name, phrase, password, passphrase)
+            .await?;
+        if let Some(path) = derivation_path {
+            account.address = seed_manager
+                .derive_wallet_from_seed(phrase, passphrase, Some(&path))?
+                .address();
+            account.derivation_path = Some(path);
+        }
+
+        // The encrypted seed is already in the keychain; only the account record is added
+        let mut keystore = self.keystore.write().await;
+        if let Err(e) = keystore.restore_account(account.clone(), None).await {
+            let _ = self.keychain.delete(&account.key_reference);
+            return Err(e);
+        }
+        Ok(account)
+    }
+
+    /// Select a newly added account when nothing is selected yet
+    fn adopt(&mut self, account: &SecureAccount) {
+        if self.current.is_none() {
+            self.current = Some(account.clone());
+        }
+    }
+
+    /// Decrypt the seed behind a seed-based account
+    async fn seed_of(
+        &self,
+        seed_account: Address,
+        password: &SecretString,
+    ) -> Result<(SecureAccount, SecretString), AccountError> {
+        let keystore = self.keystore.read().await;
+        let account = keystore
+            .get_account(seed_account)
+            .await
+            .map_err(|_| AccountError::account_not_found(seed_account.to_string()))?;
+        if account.key_reference.service != self.profile.seed_service() {
+            return Err(AccountError::validation_failed(format!(
+                "Account {seed_account} is not seed-based"
+            )));
+        }
+        let phrase = keystore
+            .get_decrypted_seed_phrase(&seed_account, password)
+            .await
+            .map_err(|_| AccountError::invalid_credentials())?;
+        Ok((account, phrase))
+    }
+
+    /// Add the BIP-44 child `m/44'/60'/0'/0/{index}` of a seed account as its own account
+    ///
+    /// The child shares the parent's encrypted seed, so the mnemonic does not need
+    /// to be entered again; `password` decrypts it. Parents created with a BIP-39
+    /// passphrase need it again as `passphrase`. Returns the existing account if
+    /// that child was already added.
+    pub async fn derive_additional_account(
+        &mut self,
+        seed_account: Address,
+        index: u32,
+        password: &SecretString,
+        passphrase: Option<&SecretString>,
+    ) -> Result<SecureAccount, AccountError> {
+        self.ensure_unlocked()?;
+        let (parent, phrase) = self.seed_of(seed_account, password).await?;
+        let passphrase = account_passphrase(&parent, passphrase).map_err(failed("derive_account"))?;
+        let derivation_path = DerivationStandard::Bip44.path_for_index(index);
+        let address = self
+            .seed_manager()
+            .derive_wallet_from_seed(&phrase, passphrase, Some(&derivation_path))
+            .map_err(failed("derive_account"))?
+            .address();
+
+        if let Some(existing) = self.get_account(address).await? {
+            return Ok(existing);
+        }
+
+        let account = SecureAccount {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: format!("{} #{index}", parent.name),
+            address,
+            key_reference: parent.key_reference.clone(),
+            created_at: chrono::Utc::now(),
+            is_hardware: false,
+            derivation_path: Some(derivation_path),
+            tags: parent.tags.clone(),
+            last_used: None,
+            transaction_count: 0,
+        };
+        let mut keystore = self.keystore.write().await;
+        keystore
+            .restore_account(account.clone(), None)
+            .await
+            .map_err(failed("derive_account"))?;
+
+        tracing::info!(
+            "✅ Derived account {} ({}) from {}",
+            account.name,
+            address,
+            parent.address
+        );
+        Ok(account)
+    }
+
+    /// Scan BIP-44 children of a seed account for on-chain activity
+    ///
+    /// Indexes are checked in order until `gap_limit` consecutive unused addresses
+    /// have been seen. The result suggests how many children to derive.
+    pub async fn scan_hd_accounts(
+        &self,
+        seed_account: Address,
+        password: &SecretString,
+        passphrase: Option<&SecretString>,
+        activity: &dyn AddressActivity,
+        gap_limit: u32,
+    ) -> Result<HdAccountScan, AccountError> {
+        let (parent, phrase) = self.seed_of(seed_account, password).await?;
+        let passphrase = account_passphrase(&parent, passphrase).map_err(failed("scan_accounts"))?;
+        let seed_manager = self.seed_manager();
+        let existing: Vec<Address> = self.list_accounts().await?.iter().map(|a| a.address).collect();
+
+        let mut candidates = Vec::new();
+        let mut unused_run = 0;
+        let mut index = 0;
+        while unused_run < gap_limit.max(1) {
+            let derivation_path = DerivationStandard::Bip44.path_for_index(index);
+            let address = seed_manager
+                .derive_wallet_from_seed(&phrase, passphrase, Some(&derivation_path))
+                .map_err(failed("scan_accounts"))?
+                .address();
+            let (balance, nonce) = activity
+                .balance_and_nonce(address)
+                .await
+                .map_err(failed("scan_accounts"))?;
+            let candidate = HdAccountCandidate {
+                index,
+                address,
+                derivation_path,
+                balance,
+                nonce,
+                already_added: existing.contains(&address),
+            };
+            unused_run = if candidate.is_used() { 0 } else { unused_run + 1 };
+            candidates.push(candidate);
+            index += 1;
+        }
+
+        let suggested_count = candidates
+            .iter()
+            .rposition(HdAccountCandidate::is_used)
+            .map_or(1, |last_used| last_used as u32 + 1);
+        Ok(HdAccountScan {
+            candidates,
+            suggested_count,
+        })
+    }
+
+    /// Export an account's private key as a V3 JSON keystore
+    ///
+    /// The file opens in geth, MetaMask, MyCrypto and other wallets using
+    /// `keystore_password`. Requires an `ExportPrivateKey` token.
+    pub async fn export_keystore_v3(
+        &self,
+        address: Address,
+        password: &SecretString,
+        keystore_password: &SecretString,
+        kdf: KeystoreKdf,
+        auth_token: AuthToken,
+    ) -> Result<String, AccountError> {
+        if !auth_token.is_valid_for(&AuthorizedOperation::ExportPrivateKey) {
+            return Err(AccountError::invalid_credentials());
+        }
+        self.ensure_unlocked()?;
+
+        let keystore = self.keystore.read().await;
+        let key = keystore
+            .get_decrypted_private_key(&address, Some(password))
+            .await
+            .map_err(|e| AccountError::export_failed(e.to_string()))?;
+        keystore_v3::encrypt_keystore(&key, keystore_password, kdf)
+            .map_err(|e| AccountError::export_failed(e.to_string()))
+    }
+
+    fn encrypt_export(secret: &SecretString, password: &SecretString) -> Result<SecureExport, AccountError> {
+        let encrypted_data =
+            encrypt_with_password(secret.expose_secret().as_bytes(), password.expose_secret().as_bytes())
+                .map_err(|e| AccountError::export_failed(e.to_string()))?;
+        Ok(SecureExport {
+            encrypted_data,
+            encryption_type: EncryptionType::Aes256Gcm,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+        })
+    }
+}
+
+/// A BIP-44 child found while scanning a seed account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HdAccountCandidate {
+    pub index: u32,
+    pub address: Address,
+    pub derivation_path: String,
+    pub balance: U256,
+    pub nonce: u64,
+    /// Whether the address is already in the wallet
+    pub already_added: bool,
+}
+
+impl HdAccountCandidate {
+    /// Whether the address has a balance or has sent transactions
+    pub fn is_used(&self) -> bool {
+        self.balance > U256::ZERO || self.nonce > 0
+    }
+}
+
+/// Result of [`AccountManager::scan_hd_accounts`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HdAccountScan {
+    /// Every index checked, in order
+    pub candidates: Vec<HdAccountCandidate>,
+    /// Number of children to derive so every used one is included (at least 1)
+    pub suggested_count: u32,
+}
+
+fn failed(operation: &'static str) -> impl Fn(VaughanError) -> AccountError {
+    move |e| AccountError::operation_failed(operation, e.to_string())
+}
+
+/// Metadata stays readable while locked; the keystore only holds keychain references
+#[async_trait]
+impl AccountMetadataQueries for AccountManager {
+    async fn list_accounts(&self) -> Result<Vec<SecureAccount>, AccountError> {
+        let keystore = self.keystore.read().await;
+        keystore.list_accounts().await.map_err(failed("list_accounts"))
+    }
+
+    async fn get_account(&self, address: Address) -> Result<Option<SecureAccount>, AccountError> {
+        let keystore = self.keystore.read().await;
+        Ok(keystore.get_account(address).await.ok())
+    }
+}
+
+#[async_trait]
+impl AccountManagerTrait for AccountManager {
+    async fn create_account(
+        &mut self,
+        config: AccountConfig,
+        password: &SecretString,
+    ) -> Result<SecureAccount, AccountError> {
+        self.ensure_unlocked()?;
+        let account = match config.account_type {
+            AccountType::SeedBased => {
+                let strength = config.seed_strength.unwrap_or(super::SeedStrength::Words12);
+                let strength = crate::security::SeedStrength::from_word_count(strength.word_count())
+                    .map_err(|e| AccountError::validation_failed(e.to_string()))?;
+                let path = config
+                    .derivation_standard
+                    .map(|standard| standard.path_for_index(0))
+                    .or(config.derivation_path);
+                if let Some(path) = &path {
+                    ValidatedPath::parse(path).map_err(|e| {
+                        AccountError::validation_failed(format!("Invalid derivation path '{path}': {e}"))
+                    })?;
+                }
+                let seed_manager = self.seed_manager();
+                let phrase = seed_manager
+                    .generate_seed_phrase(strength)
+                    .map_err(failed("create_account"))?;
+                self.add_seed_account(config.name, &phrase, password, path, config.bip39_passphrase.as_ref())
+                    .await
+                    .map_err(failed("create_account"))?
+            }
+            AccountType::PrivateKey => {
+                let mut keystore = self.keystore.write().await;
+                keystore
+                    .create_account(config.name)
+                    .await
+                    .map_err(failed("create_account"))?
+            }
+            AccountType::Hardware => {
+                return Err(AccountError::validation_failed(
+                    "Hardware accounts are added through hardware discovery",
+                ))
+            }
+            AccountType::SmartAccount => {
+                return Err(AccountError::validation_failed(
+                    "Smart accounts are deployed for an owner account through the aa module",
+                ))
+            }
+        };
+
+        tracing::info!("✅ Created account {} ({})", account.name, account.address);
+        self.adopt(&account);
+        Ok(account)
+    }
+
+    async fn import_account(&mut self, source: ImportSource) -> Result<SecureAccount, AccountError> {
+        self.ensure_unlocked()?;
+        let account = match source {
+            ImportSource::SeedPhrase {
+                mnemonic,
+                name,
+                derivation_path,
+                passphrase,
+                password,
+            } => {
+                self.seed_manager()
+                    .validate_seed_phrase(&mnemonic)
+                    .map_err(|e| AccountError::import_failed(e.to_string(), "seed_phrase"))?;
+                self.add_seed_account(name, &mnemonic, &password, derivation_path, passphrase.as_ref())
+                    .await
+                    .map_err(|e| AccountError::import_failed(e.to_string(), "seed_phrase"))?
+            }
+            ImportSource::PrivateKey { key, name, .. } => {
+                let mut keystore = self.keystore.write().await;
+                keystore
+                    .import_account(key, name)
+                    .await
+                    .map_err(|e| AccountError::import_failed(e.to_string(), "private_key"))?
+            }
+            ImportSource::MetaMaskKeystore {
+                keystore_json,
+                keystore_password,
+                name,
+                ..
+            }
+            | ImportSource::KeystoreV3 {
+                keystore_json,
+                keystore_password,
+                name,
+            } => {
+                let (_, signer) = AccountImporter::new()
+                    .import_from_keystore(&keystore_json, &keystore_password, ImportMetadata::new())
+                    .map_err(|e| AccountError::import_failed(e.to_string(), "keystore"))?;
+                let key = SecretString::new(hex::encode(signer.to_bytes()));
+                let mut keystore = self.keystore.write().await;
+                keystore
+                    .import_account(key, name)
+                    .await
+                    .map_err(|e| AccountError::import_failed(e.to_string(), "keystore"))?
+            }
+        };
+
+        tracing::info!("✅ Imported account {} ({})", account.name, account.address);
+        self.adopt(&account);
+        Ok(account)
+    }
+
+    async fn remove_account(&mut self, address: Address, auth_token: AuthToken) -> Result<(), AccountError> {
+        if !auth_token.is_valid_for(&AuthorizedOperation::RemoveAccount) {
+            return Err(AccountError::invalid_credentials());
+        }
+        self.ensure_unlocked()?;
+
+        let mut keystore = self.keystore.write().await;
+        if keystore.get_account(address).await.is_err() {
+            return Err(AccountError::account_not_found(address.to_string()));
+        }
+        keystore
+            .remove_account(address)
+            .await
+            .map_err(failed("remove_account"))?;
+        drop(keystore);
+
+        if self.current.as_ref().is_some_and(|account| account.address == address) {
+            self.current = None;
+        }
+        Ok(())
+    }
+
+    async fn get_current_account(&self) -> Result<Option<SecureAccount>, AccountError> {
+        Ok(self.current.clone())
+    }
+
+    async fn set_current_account(&mut self, address: Address) -> Result<(), AccountError> {
+        let account = self
+            .get_account(address)
+            .await?
+            .ok_or_else(|| AccountError::account_not_found(address.to_string()))?;
+        self.current = Some(account);
+        Ok(())
+    }
+
+    async fn lock(&mut self) -> Result<(), AccountError> {
+        // The keystore only holds encrypted seeds and keychain references; private
+        // keys are decrypted per operation, so dropping the selection is enough.
+        self.locked = true;
+        self.current = None;
+        tracing::info!("🔒 Account manager locked");
+        Ok(())
+    }
+
+    /// Unlock, verifying `password` against the newest seed-based account when there is one
+    async fn unlock(&mut self, password: &SecretString) -> Result<(), AccountError> {
+        let keystore = self.keystore.read().await;
+        let accounts = keystore.list_accounts().await.map_err(failed("unlock"))?;
+        if let Some(seed_account) = accounts
+            .iter()
+            .filter(|account| account.key_reference.service == self.profile.seed_service())
+            .max_by_key(|account| account.created_at)
+        {
+            keystore
+                .get_decrypted_seed_phrase(&seed_account.address, password)
+                .await
+                .map_err(|_| AccountError::invalid_credentials())?;
+        }
+        drop(keystore);
+
+        if self.current.is_none() {
+            self.current = accounts.into_iter().next();
+        }
+        self.locked = false;
+        tracing::info!("🔓 Account manager unlocked");
+        Ok(())
+    }
+
+    fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    async fn export_seed(
+        &self,
+        address: Address,
+        password: &SecretString,
+        auth_token: AuthToken,
+    ) -> Result<SecureExport, AccountError> {
+        if !auth_token.is_valid_for(&AuthorizedOperation::ExportSeed) {
+            return Err(AccountError::invalid_credentials());
+        }
+        self.ensure_unlocked()?;
+        self.exports
+            .authorize_export_attempt("export_seed", address)
+            .map_err(|e| AccountError::operation_failed("export_seed", e.to_string()))?;
+
+        let keystore = self.keystore.read().await;
+        let phrase = keystore.get_decrypted_seed_phrase(&address, password).await;
+        self.exports
+            .record_export_result("export_seed", address, phrase.as_ref().map(|_| ()));
+        let phrase = phrase.map_err(|e| AccountError::export_failed(e.to_string()))?;
+        Self::encrypt_export(&phrase, password)
+    }
+
+    async fn export_private_key(
+        &self,
+        address: Address,
+        password: &SecretString,
+        auth_token: AuthToken,
+    ) -> Result<SecureExport, AccountError> {
+        if !auth_token.is_valid_for(&AuthorizedOperation::ExportPrivateKey) {
+            return Err(AccountError::invalid_credentials());
+        }
+        self.ensure_unlocked()?;
+        self.exports
+            .authorize_export_attempt("export_private_key", address)
+            .map_err(|e| AccountError::operation_failed("export_private_key", e.to_string()))?;
+
+        let keystore = self.keystore.read().await;
+        let key = keystore.get_decrypted_private_key(&address, Some(password)).await;
+        self.exports
+            .record_export_result("export_private_key", address, key.as_ref().map(|_| ()));
+        let key = key.map_err(|e| AccountError::export_failed(e.to_string()))?;
+        Self::encrypt_export(&key, password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::TestKeychain;
+    use crate::security::keystore::encryption::decrypt_with_password;
+    use crate::security::SecureKeystoreImpl;
+
+    async fn manager() -> AccountManager {
+        let keychain = TestKeychain::new();
+        let keystore = SecureKeystoreImpl::new(keychain.clone_box()).await.unwrap();
+        AccountManager::new(Arc::new(RwLock::new(keystore)), Box::new(keychain))
+            .with_export_authenticator(ExportAuthenticator::new())
+    }
+
+    #[tokio::test]
+    async fn test_seed_account_lifecycle() {
+        let mut manager = manager().await;
+        let password = SecretString::new("manager password".into());
+
+        let config = AccountConfig::seed_based("Managed Seed").with_derivation_path("m/44'/60'/0'/0/3");
+        let account = manager.create_account(config, &password).await.unwrap();
+        assert_eq!(account.derivation_path.as_deref(), Some("m/44'/60'/0'/0/3"));
+        assert_eq!(manager.current().map(|a| a.address), Some(account.address));
+
+        manager.lock().await.unwrap();
+        assert!(manager.is_locked());
+        assert!(manager.current().is_none());
+        // Metadata stays readable while locked, key operations do not
+        assert_eq!(manager.list_accounts().await.unwrap().len(), 1);
+        assert!(manager.get_account(account.address).await.unwrap().is_some());
+        assert!(matches!(
+            manager.create_account(AccountConfig::private_key("Locked"), &password).await,
+            Err(AccountError::AccountLocked { .. })
+        ));
+        assert!(matches!(
+            manager.unlock(&SecretString::new("wrong".into())).await,
+            Err(AccountError::InvalidCredentials { .. })
+        ));
+        manager.unlock(&password).await.unwrap();
+        assert!(!manager.is_locked());
+
+        let wrong_token = AuthToken::new(AuthorizedOperation::ExportPrivateKey);
+        assert!(manager
+            .export_seed(account.address, &password, wrong_token)
+            .await
+            .is_err());
+        let export = manager
+            .export_seed(
+                account.address,
+                &password,
+                AuthToken::new(AuthorizedOperation::ExportSeed),
+            )
+            .await
+            .unwrap();
+        let phrase = decrypt_with_password(&export.encrypted_data, password.expose_secret().as_bytes()).unwrap();
+        assert_eq!(String::from_utf8(phrase).unwrap().split_whitespace().count(), 12);
+
+        manager
+            .remove_account(account.address, AuthToken::new(AuthorizedOperation::RemoveAccount))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_exports_draw_on_the_hourly_allowance() {
+        use crate::security::ExportRateLimitPolicy;
+
+        let exports = ExportAuthenticator::with_policy(ExportRateLimitPolicy {
+            max_exports_per_hour: 1,
+            ..Default::default()
+        });
+        let mut manager = manager().await.with_export_authenticator(exports.clone());
+        let password = SecretString::new("export password".into());
+        let account = manager
+            .create_account(AccountConfig::seed_based("Allowance"), &password)
+            .await
+            .unwrap();
+
+        // A wrong password starts the cooldown
+        let wrong = SecretString::new("wrong".into());
+        let token = || AuthToken::new(AuthorizedOperation::ExportSeed);
+        assert!(manager.export_seed(account.address, &wrong, token()).await.is_err());
+        assert!(exports.cooldown_remaining().is_some());
+        assert!(manager.export_seed(account.address, &password, token()).await.is_err());
+
+        let manager = manager.with_export_authenticator(ExportAuthenticator::with_policy(ExportRateLimitPolicy {
+            max_exports_per_hour: 1,
+            ..Default::default()
+        }));
+        let key_token = || AuthToken::new(AuthorizedOperation::ExportPrivateKey);
+        manager
+            .export_private_key(account.address, &password, key_token())
+            .await
+            .unwrap();
+        let refused = manager
+            .export_seed(account.address, &password, token())
+            .await
+            .unwrap_err();
+        assert!(refused.to_string().contains("export_seed"));
+    }
+
+    #[tokio::test]
+    async fn test_import_select_and_remove_private_key() {
+        let mut manager = manager().await;
+        let source = ImportSource::PrivateKey {
+            key: SecretString::new("0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".into()),
+            name: "Managed Key".into(),
+            password: SecretString::new("unused".into()),
+        };
+        let account = manager.import_account(source).await.unwrap();
+        manager.set_current_account(account.address).await.unwrap();
+        assert!(manager.get_account(account.address).await.unwrap().is_some());
+
+        let wrong_token = AuthToken::new(AuthorizedOperation::ExportSeed);
+        assert!(matches!(
+            manager.remove_account(account.address, wrong_token).await,
+            Err(AccountError::InvalidCredentials { .. })
+        ));
+        manager
+            .remove_account(account.address, AuthToken::new(AuthorizedOperation::RemoveAccount))
+            .await
+            .unwrap();
+        assert!(manager.current().is_none());
+        assert!(manager.get_account(account.address).await.unwrap().is_none());
+        assert!(matches!(
+            manager.set_current_account(account.address).await,
+            Err(AccountError::AccountNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_keystore_v3_round_trip() {
+        let mut manager = manager().await;
+        let key = SecretString::new("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".into());
+        let file_password = SecretString::new("geth password".into());
+        let keystore_json = keystore_v3::encrypt_keystore(&key, &file_password, KeystoreKdf::light()).unwrap();
+
+        let wrong = ImportSource::KeystoreV3 {
+            keystore_json: keystore_json.clone(),
+            keystore_password: SecretString::new("wrong".into()),
+            name: "Geth Key".into(),
+        };
+        assert!(manager.import_account(wrong).await.is_err());
+
+        let source = ImportSource::KeystoreV3 {
+            keystore_json,
+            keystore_password: file_password.clone(),
+            name: "Geth Key".into(),
+        };
+        let account = manager.import_account(source).await.unwrap();
+        assert_eq!(
+            account.address,
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse::<Address>().unwrap()
+        );
+
+        let unused = SecretString::new("unused".into());
+        let exported = manager
+            .export_keystore_v3(
+                account.address,
+                &unused,
+                &file_password,
+                KeystoreKdf::light(),
+                AuthToken::new(AuthorizedOperation::ExportPrivateKey),
+            )
+            .await
+            .unwrap();
+        let decrypted = keystore_v3::decrypt_keystore(&exported, &file_password).unwrap();
+        assert_eq!(decrypted.address, account.address);
+        assert_eq!(decrypted.private_key.expose_secret(), key.expose_secret());
+
+        manager
+            .remove_account(account.address, AuthToken::new(AuthorizedOperation::RemoveAccount))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_derive_and_scan_hd_children() {
+        struct FakeChain(Address);
+
+        #[async_trait]
+        impl AddressActivity for FakeChain {
+            async fn balance_and_nonce(&self, address: Address) -> crate::error::Result<(U256, u64)> {
+                Ok(if address == self.0 {
+                    (U256::ZERO, 3)
+                } else {
+                    (U256::ZERO, 0)
+                })
+            }
+        }
+
+        let mut manager = manager().await;
+        let password = SecretString::new("hd password".into());
+        let source = ImportSource::SeedPhrase {
+            mnemonic: SecretString::new("test test test test test test test test test test test junk".into()),
+            name: "HD".into(),
+            derivation_path: None,
+            passphrase: None,
+            password: password.clone(),
+        };
+        let parent = manager.import_account(source).await.unwrap();
+        let child_1: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".parse().unwrap();
+
+        let scan = manager
+            .scan_hd_accounts(parent.address, &password, None, &FakeChain(child_1), 2)
+            .await
+            .unwrap();
+        assert_eq!(scan.candidates.len(), 4);
+        assert_eq!(scan.suggested_count, 2);
+        assert!(scan.candidates[0].already_added);
+        assert_eq!(scan.candidates[1].address, child_1);
+        assert!(!scan.candidates[1].already_added);
+
+        let child = manager
+            .derive_additional_account(parent.address, 2, &password, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            child.address,
+            "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC".parse::<Address>().unwrap()
+        );
+        assert_eq!(child.derivation_path.as_deref(), Some("m/44'/60'/0'/0/2"));
+        assert_eq!(child.name, "HD #2");
+        assert_eq!(
+            manager
+                .derive_additional_account(parent.address, 2, &password, None)
+                .await
+                .unwrap()
+                .id,
+            child.id
+        );
+
+        // Removing a child keeps the shared seed for the parent
+        let token = || AuthToken::new(AuthorizedOperation::RemoveAccount);
+        manager.remove_account(child.address, token()).await.unwrap();
+        manager
+            .export_seed(
+                parent.address,
+                &password,
+                AuthToken::new(AuthorizedOperation::ExportSeed),
+            )
+            .await
+            .unwrap();
+        manager.remove_account(parent.address, token()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bip39_passphrase_account() {
+        use crate::error::SecurityError;
+        use crate::security::seed::derivation::BIP39_PASSPHRASE_TAG;
+
+        let mut manager = manager().await;
+        let password = SecretString::new("passphrase password".into());
+        let passphrase = SecretString::new("correct horse".into());
+        let source = ImportSource::SeedPhrase {
+            mnemonic: SecretString::new("test test test test test test test test test test test junk".into()),
+            name: "Hidden".into(),
+            derivation_path: None,
+            passphrase: Some(passphrase.clone()),
+            password: password.clone(),
+        };
+        let account = manager.import_account(source).await.unwrap();
+        // The same phrase without a passphrase is a different wallet
+        assert_ne!(
+            account.address,
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse::<Address>().unwrap()
+        );
+        assert!(account.tags.iter().any(|t| t == BIP39_PASSPHRASE_TAG));
+
+        let keystore = manager.keystore();
+        let keystore = keystore.read().await;
+        let missing = keystore
+            .derive_signing_key(&account.address, Some(&password), None)
+            .await;
+        assert!(matches!(
+            missing,
+            Err(VaughanError::Security(SecurityError::Bip39PassphraseRequired { .. }))
+        ));
+        let wrong = SecretString::new("wrong horse".into());
+        assert!(keystore
+            .get_decrypted_private_key_with_passphrase(&account.address, Some(&password), Some(&wrong))
+            .await
+            .is_err());
+        assert!(keystore
+            .get_decrypted_private_key_with_passphrase(&account.address, Some(&password), Some(&passphrase))
+            .await
+            .is_ok());
+        drop(keystore);
+
+        assert!(manager
+            .derive_additional_account(account.address, 1, &password, None)
+            .await
+            .is_err());
+        let child = manager
+            .derive_additional_account(account.address, 1, &password, Some(&passphrase))
+            .await
+            .unwrap();
+        assert!(child.tags.iter().any(|t| t == BIP39_PASSPHRASE_TAG));
+    }
+}
@@ -7,15 +7,52 @@
 //! - Requirement 12.2: Deterministic avatar generation
 //! - Requirement 12.3: Tag management
 //! - Requirement 12.4: Activity tracking
+//!
+//! [`AccountMetadataStore`] reads the persisted account list without touching
+//! the keychain, so it can serve [`AccountMetadataQueries`] while locked.
 
-use crate::error::{Result, WalletError};
-use crate::security::SecureAccount;
+use super::AccountMetadataQueries;
+use crate::error::{AccountError, Result, WalletError};
+use crate::security::{keystore::storage, SecureAccount, SecurityProfile};
+use crate::utils::identity::AccountIdentity;
 use alloy::primitives::Address;
-use rand::{Rng, SeedableRng};
-use rand_chacha::ChaCha20Rng;
+use async_trait::async_trait;
 use regex::Regex;
 use std::collections::HashSet;
-use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+/// Read-only view of the persisted account list
+///
+/// Never touches the keychain, so it can be used before the wallet is
+/// unlocked. Accounts whose key is missing are still listed.
+#[derive(Debug, Clone)]
+pub struct AccountMetadataStore {
+    path: PathBuf,
+}
+
+impl AccountMetadataStore {
+    /// Accounts file of `profile` in the `.vaughan` directory
+    pub fn new(profile: SecurityProfile) -> Self {
+        Self::at(storage::get_vaughan_dir().join(profile.accounts_file()))
+    }
+
+    /// Accounts file at an explicit path
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl AccountMetadataQueries for AccountMetadataStore {
+    async fn list_accounts(&self) -> std::result::Result<Vec<SecureAccount>, AccountError> {
+        storage::read_account_metadata(&self.path)
+            .map_err(|e| AccountError::operation_failed("list_accounts", e.to_string()))
+    }
+
+    async fn get_account(&self, address: Address) -> std::result::Result<Option<SecureAccount>, AccountError> {
+        Ok(self.list_accounts().await?.into_iter().find(|a| a.address == address))
+    }
+}
 
 pub struct MetadataManager;
 
@@ -70,99 +107,10 @@ impl MetadataManager {
     /// Generate deterministic avatar (SVG) based on address (Requirement 12.2)
     /// Inspired by MetaMask's Jazzicon/Blockies
     pub fn generate_avatar(address: Address) -> String {
-        // Use address hash as seed for determinism
-        let mut hasher = Sha256::new();
-        hasher.update(address.as_slice());
-        let hash = hasher.finalize();
-        
-        // Seed RNG with hash (hash is always 32 bytes from SHA256)
-        #[allow(clippy::expect_used)]
-        let seed = <[u8; 32]>::try_from(hash.as_slice())
-            .expect("SHA256 hash is always 32 bytes");
-        let mut rng = ChaCha20Rng::from_seed(seed);
-
-        // Generate colors (HSL -> RGB hex)
-        // MetaMask uses generally 3-4 colors. We'll use 3.
-        let colors: Vec<String> = (0..3).map(|_| {
-            let h = rng.gen_range(0..360);
-            let s = rng.gen_range(50..100);
-            let l = rng.gen_range(40..80);
-            hsl_to_hex(h, s, l)
-        }).collect();
-
-        // Simple Geometric Pattern (5x5 grid like blockies)
-        let mut rects = String::new();
-        let size = 5;
-        let scale = 10;
-        
-        // Generate grid with symmetry
-        let mut grid = vec![vec![false; size]; size];
-        let mut grid_colors = vec![vec![0; size]; size];
-
-        for y in 0..size {
-            for x in 0..=(size / 2) { // Iterate through left half + center
-                if rng.gen_bool(0.5) {
-                    let color_idx = rng.gen_range(0..colors.len());
-                    
-                    // Set left side
-                    grid[x][y] = true;
-                    grid_colors[x][y] = color_idx;
-
-                    // Mirror to right side
-                    let mirror_x = size - 1 - x;
-                    if x != mirror_x {
-                        grid[mirror_x][y] = true;
-                        grid_colors[mirror_x][y] = color_idx;
-                    }
-                }
-            }
-        }
-
-        for y in 0..size {
-            for x in 0..size {
-                if grid[x][y] {
-                    let color = &colors[grid_colors[x][y]];
-                    rects.push_str(&format!(
-                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" />"#,
-                        x * scale, y * scale, scale, scale, color
-                    ));
-                }
-            }
-        }
-
-        let bg = &colors[rng.gen_range(0..colors.len())];
-        
-        format!(
-            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 50 50" style="background-color: {};">{}</svg>"#,
-            bg, rects
-        )
+        AccountIdentity::for_address(address).to_svg()
     }
 }
 
-fn hsl_to_hex(h: i32, s: i32, l: i32) -> String {
-    let s = s as f32 / 100.0;
-    let l = l as f32 / 100.0;
-    
-    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
-    let x = c * (1.0 - ((h as f32 / 60.0) % 2.0 - 1.0).abs());
-    let m = l - c / 2.0;
-    
-    let (r, g, b) = match h {
-        0..=59 => (c, x, 0.0),
-        60..=119 => (x, c, 0.0),
-        120..=179 => (0.0, c, x),
-        180..=239 => (0.0, x, c),
-        240..=299 => (x, 0.0, c),
-        _ => (c, 0.0, x),
-    };
-    
-    let r = ((r + m) * 255.0) as u8;
-    let g = ((g + m) * 255.0) as u8;
-    let b = ((b + m) * 255.0) as u8;
-    
-    format!("#{:02x}{:02x}{:02x}", r, g, b)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,6 +127,36 @@ mod tests {
         assert!(MetadataManager::validate_nickname("A".repeat(33).as_str(), &existing).is_err()); // Too long
     }
 
+    #[tokio::test]
+    async fn test_metadata_store_reads_without_keychain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("accounts.json");
+        let store = AccountMetadataStore::at(&path);
+        assert!(store.list_accounts().await.unwrap().is_empty());
+
+        let address = Address::from_str("0x0000000000000000000000000000000000000001").unwrap();
+        let stored = serde_json::json!([{
+            "id": "a1",
+            "name": "Main",
+            "address": address,
+            "key_reference": { "id": "missing", "service": "vaughan-wallet", "account": "missing" },
+            "created_at": "2024-01-01T00:00:00Z",
+            "is_hardware": false,
+            "derivation_path": null
+        }]);
+        std::fs::write(&path, stored.to_string()).unwrap();
+
+        // The key reference points nowhere, but metadata is still listed
+        let accounts = store.list_accounts().await.unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].name, "Main");
+        assert!(store.get_account(address).await.unwrap().is_some());
+        assert!(store.get_account(Address::ZERO).await.unwrap().is_none());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(store.list_accounts().await.is_err());
+    }
+
     #[test]
     fn test_avatar_determinism() {
         let addr1 = Address::from_str("0x0000000000000000000000000000000000000001").unwrap();
@@ -78,12 +78,17 @@ pub mod export;
 pub mod metadata;
 pub mod signer_integration;
 pub mod discovery;
+pub mod labels;
+pub mod manager;
+pub mod progressive;
 pub mod eip712;
 pub mod types;
 
 pub use creation::{AccountCreator, AccountCreationConfig, CreatedAccount, KeyValidation, SeedValidation};
 pub use import::{AccountImporter, ImportMetadata, ImportSourceType, ValidationResult};
 pub use export::*;
+pub use manager::{AccountManager, HdAccountCandidate, HdAccountScan};
+pub use metadata::AccountMetadataStore;
 pub use types::*;
 
 use alloy::primitives::Address;
@@ -93,10 +98,40 @@ use secrecy::SecretString;
 use crate::error::AccountError;
 use crate::security::{SecureAccount, SecureExport};
 
+/// Read-only account metadata queries
+///
+/// These only read stored account metadata (names, addresses, creation
+/// dates, derivation paths) and never touch key material, so they work
+/// while the wallet is locked. The GUI account list renders from this path.
+#[async_trait]
+pub trait AccountMetadataQueries: Send + Sync {
+    /// List all accounts
+    ///
+    /// Returns all accounts managed by this wallet.
+    ///
+    /// # Returns
+    /// * `Vec<SecureAccount>` - List of all accounts
+    async fn list_accounts(&self) -> Result<Vec<SecureAccount>, AccountError>;
+
+    /// Get a specific account by address
+    ///
+    /// # Arguments
+    /// * `address` - The account address to look up
+    ///
+    /// # Returns
+    /// * `Option<SecureAccount>` - The account if found
+    async fn get_account(
+        &self,
+        address: Address,
+    ) -> Result<Option<SecureAccount>, AccountError>;
+}
+
 /// Unified Account Manager Trait
 ///
 /// This trait defines the interface for all account management operations.
 /// All implementations must be `Send + Sync` for safe concurrent access.
+/// Metadata queries come from [`AccountMetadataQueries`]; the operations
+/// here create, import or export keys and fail while the wallet is locked.
 ///
 /// ## Requirements
 ///
@@ -105,7 +140,7 @@ use crate::security::{SecureAccount, SecureExport};
 /// - **1.4**: Safe concurrent operation handling with async patterns
 /// - **1.5**: Uses Alloy primitives for blockchain operations
 #[async_trait]
-pub trait AccountManagerTrait: Send + Sync {
+pub trait AccountManagerTrait: AccountMetadataQueries {
     // ========== Lifecycle Operations ==========
 
     /// Create a new account
@@ -157,26 +192,6 @@ pub trait AccountManagerTrait: Send + Sync {
 
     // ========== Query Operations ==========
 
-    /// List all accounts
-    ///
-    /// Returns all accounts managed by this wallet.
-    ///
-    /// # Returns
-    /// * `Vec<SecureAccount>` - List of all accounts
-    async fn list_accounts(&self) -> Result<Vec<SecureAccount>, AccountError>;
-
-    /// Get a specific account by address
-    ///
-    /// # Arguments
-    /// * `address` - The account address to look up
-    ///
-    /// # Returns
-    /// * `Option<SecureAccount>` - The account if found
-    async fn get_account(
-        &self,
-        address: Address,
-    ) -> Result<Option<SecureAccount>, AccountError>;
-
     /// Get the currently active account
     ///
     /// # Returns
@@ -360,6 +375,34 @@ mod interface_tests {
         }
     }
 
+    #[async_trait]
+    impl AccountMetadataQueries for MockAccountManager {
+        async fn list_accounts(&self) -> Result<Vec<SecureAccount>, AccountError> {
+            {
+                let mut count = self.operation_count.write().await;
+                *count += 1;
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_micros(50)).await;
+
+            let accounts = self.accounts.read().await;
+            Ok(accounts.values().cloned().collect())
+        }
+
+        async fn get_account(
+            &self,
+            address: Address,
+        ) -> Result<Option<SecureAccount>, AccountError> {
+            {
+                let mut count = self.operation_count.write().await;
+                *count += 1;
+            }
+
+            let accounts = self.accounts.read().await;
+            Ok(accounts.get(&address).cloned())
+        }
+    }
+
     #[async_trait]
     impl AccountManagerTrait for MockAccountManager {
         async fn create_account(
@@ -400,6 +443,7 @@ mod interface_tests {
                 ImportSource::SeedPhrase { name, .. } => name.clone(),
                 ImportSource::PrivateKey { name, .. } => name.clone(),
                 ImportSource::MetaMaskKeystore { name, .. } => name.clone(),
+                ImportSource::KeystoreV3 { name, .. } => name.clone(),
             };
 
             let address = Self::test_address(Uuid::new_v4().as_u128() as u64);
@@ -433,31 +477,6 @@ mod interface_tests {
             Ok(())
         }
 
-        async fn list_accounts(&self) -> Result<Vec<SecureAccount>, AccountError> {
-            {
-                let mut count = self.operation_count.write().await;
-                *count += 1;
-            }
-
-            tokio::time::sleep(tokio::time::Duration::from_micros(50)).await;
-
-            let accounts = self.accounts.read().await;
-            Ok(accounts.values().cloned().collect())
-        }
-
-        async fn get_account(
-            &self,
-            address: Address,
-        ) -> Result<Option<SecureAccount>, AccountError> {
-            {
-                let mut count = self.operation_count.write().await;
-                *count += 1;
-            }
-
-            let accounts = self.accounts.read().await;
-            Ok(accounts.get(&address).cloned())
-        }
-
         async fn get_current_account(&self) -> Result<Option<SecureAccount>, AccountError> {
             {
                 let mut count = self.operation_count.write().await;
@@ -837,6 +856,7 @@ mod interface_tests {
             mnemonic: SecretString::from("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string()),
             name: "Seed Import".to_string(),
             derivation_path: None,
+            passphrase: None,
             password: SecretString::from("test".to_string()),
         };
         let result = manager.import_account(seed_import).await;
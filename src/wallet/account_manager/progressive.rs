@@ -0,0 +1,167 @@
+//! Progressive Account Loading
+//!
+//! Wallets with dozens of accounts shouldn't make the user wait for every
+//! account and balance before anything is shown. Accounts are ordered so the
+//! ones on screen come first, then by most recent use, and delivered in
+//! pages. Balances are fetched in the same order a few at a time, each
+//! reported as soon as it arrives.
+
+use crate::error::Result;
+use crate::security::SecureAccount;
+use alloy::primitives::{Address, U256};
+use futures_util::{Stream, StreamExt};
+use std::cmp::Reverse;
+use std::future::Future;
+
+/// Accounts per page
+pub const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// Balance requests in flight at once
+pub const BALANCE_CONCURRENCY: usize = 4;
+
+/// A slice of the prioritized account list
+#[derive(Debug, Clone)]
+pub struct AccountPage {
+    pub accounts: Vec<SecureAccount>,
+    /// Position of the first account in the full list
+    pub offset: usize,
+    /// Accounts in the full list
+    pub total: usize,
+}
+
+impl AccountPage {
+    pub fn is_first(&self) -> bool {
+        self.offset == 0
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.offset + self.accounts.len() >= self.total
+    }
+}
+
+/// Order `accounts` with `visible` ones first, in their given order, then by most recent use
+pub fn prioritize(mut accounts: Vec<SecureAccount>, visible: &[Address]) -> Vec<SecureAccount> {
+    accounts.sort_by_key(|account| {
+        (
+            visible.iter().position(|a| *a == account.address).unwrap_or(usize::MAX),
+            Reverse(account.last_used),
+            account.created_at,
+        )
+    });
+    accounts
+}
+
+/// Split `accounts` into pages of `page_size`
+///
+/// An empty list still yields one empty page, so consumers always see a last page.
+pub fn paginate(accounts: Vec<SecureAccount>, page_size: usize) -> Vec<AccountPage> {
+    let total = accounts.len();
+    if total == 0 {
+        return vec![AccountPage {
+            accounts: Vec::new(),
+            offset: 0,
+            total,
+        }];
+    }
+    accounts
+        .chunks(page_size.max(1))
+        .enumerate()
+        .map(|(i, chunk)| AccountPage {
+            accounts: chunk.to_vec(),
+            offset: i * page_size.max(1),
+            total,
+        })
+        .collect()
+}
+
+/// Prioritized pages of `accounts` as a stream
+pub fn account_pages(
+    accounts: Vec<SecureAccount>,
+    visible: &[Address],
+    page_size: usize,
+) -> impl Stream<Item = AccountPage> + Send + 'static {
+    futures_util::stream::iter(paginate(prioritize(accounts, visible), page_size))
+}
+
+/// Balances of `addresses` in the given order, `concurrency` requests at a time
+pub fn balance_stream<F, Fut>(
+    addresses: Vec<Address>,
+    concurrency: usize,
+    fetch: F,
+) -> impl Stream<Item = (Address, Result<U256>)>
+where
+    F: Fn(Address) -> Fut,
+    Fut: Future<Output = Result<U256>>,
+{
+    futures_util::stream::iter(addresses)
+        .map(move |address| {
+            let balance = fetch(address);
+            async move { (address, balance.await) }
+        })
+        .buffered(concurrency.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::KeyReference;
+    use chrono::{Duration, Utc};
+
+    fn account(byte: u8, last_used: Option<i64>) -> SecureAccount {
+        SecureAccount {
+            id: format!("account-{byte}"),
+            name: format!("Account {byte}"),
+            address: Address::repeat_byte(byte),
+            key_reference: KeyReference {
+                id: "test".to_string(),
+                service: "vaughan-wallet".to_string(),
+                account: "test".to_string(),
+            },
+            created_at: Utc::now() + Duration::seconds(byte as i64),
+            is_hardware: false,
+            derivation_path: None,
+            tags: Vec::new(),
+            last_used,
+            transaction_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_prioritize_and_paginate() {
+        let accounts = (1..=25)
+            .map(|b| account(b, None))
+            .chain([account(30, Some(100))])
+            .collect();
+        let visible = [Address::repeat_byte(7), Address::repeat_byte(3)];
+        let ordered = prioritize(accounts, &visible);
+        let order: Vec<u8> = ordered.iter().take(4).map(|a| a.address[0]).collect();
+        // Visible accounts, then the recently used one, then by creation
+        assert_eq!(order, vec![7, 3, 30, 1]);
+
+        let pages = paginate(ordered, DEFAULT_PAGE_SIZE);
+        assert_eq!(pages.len(), 3);
+        assert!(pages[0].is_first() && !pages[0].is_last());
+        assert_eq!(pages[2].offset, 20);
+        assert_eq!(pages[2].accounts.len(), 6);
+        assert!(pages[2].is_last());
+
+        let empty = paginate(Vec::new(), DEFAULT_PAGE_SIZE);
+        assert_eq!(empty.len(), 1);
+        assert!(empty[0].is_first() && empty[0].is_last());
+    }
+
+    #[tokio::test]
+    async fn test_balance_stream_keeps_priority_order() {
+        let addresses: Vec<Address> = (1..=6).map(Address::repeat_byte).collect();
+        let balances: Vec<_> = balance_stream(addresses.clone(), 3, |address| async move {
+            // Later accounts answer sooner; results still come back in order
+            tokio::time::sleep(std::time::Duration::from_millis(10 * (7 - address[0] as u64))).await;
+            Ok(U256::from(address[0]))
+        })
+        .collect()
+        .await;
+        let order: Vec<Address> = balances.iter().map(|(a, _)| *a).collect();
+        assert_eq!(order, addresses);
+        assert_eq!(*balances[5].1.as_ref().unwrap(), U256::from(6));
+    }
+}
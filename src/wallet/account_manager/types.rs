@@ -79,6 +79,11 @@ pub struct AccountConfig {
     pub derivation_path: Option<String>,
     /// Derivation standard to use (optional, overrides derivation_path if set)
     pub derivation_standard: Option<crate::wallet::hardware::DerivationStandard>,
+    /// BIP-39 passphrase ("25th word") for SeedBased accounts
+    ///
+    /// Each passphrase yields a different wallet from the same seed phrase.
+    /// It is not stored and must be entered again to sign.
+    pub bip39_passphrase: Option<SecretString>,
 }
 
 impl AccountConfig {
@@ -90,6 +95,7 @@ impl AccountConfig {
             seed_strength: Some(SeedStrength::Words12),
             derivation_path: None,
             derivation_standard: None,
+            bip39_passphrase: None,
         }
     }
 
@@ -101,6 +107,7 @@ impl AccountConfig {
             seed_strength: None,
             derivation_path: None,
             derivation_standard: None,
+            bip39_passphrase: None,
         }
     }
 
@@ -112,6 +119,7 @@ impl AccountConfig {
             seed_strength: None,
             derivation_path: None,
             derivation_standard: Some(crate::wallet::hardware::DerivationStandard::Bip44),
+            bip39_passphrase: None,
         }
     }
 
@@ -127,9 +135,16 @@ impl AccountConfig {
         self
     }
 
-    /// Set the derivation standard
-    pub fn with_standard(mut self, standard: crate::wallet::hardware::DerivationStandard) -> Self {
-        self.derivation_standard = Some(standard);
+    /// Derive the account with a BIP-39 passphrase; an empty one means none
+    pub fn with_bip39_passphrase(mut self, passphrase: SecretString) -> Self {
+        self.bip39_passphrase = Some(passphrase);
+        self
+    }
+
+    /// Set the derivation standard, or a preset such as
+    /// [`crate::security::seed::derivation::DerivationPreset::LedgerLive`]
+    pub fn with_standard(mut self, standard: impl Into<crate::wallet::hardware::DerivationStandard>) -> Self {
+        self.derivation_standard = Some(standard.into());
         self
     }
 }
@@ -143,6 +158,8 @@ pub enum AccountType {
     PrivateKey,
     /// Hardware wallet account (Ledger/Trezor)
     Hardware,
+    /// ERC-4337 smart account signed for by an owner account
+    SmartAccount,
 }
 
 /// Seed phrase strength (word count)
@@ -200,6 +217,8 @@ pub enum ImportSource {
         name: String,
         /// Custom derivation path (optional)
         derivation_path: Option<String>,
+        /// BIP-39 passphrase (optional); a different one imports a different wallet
+        passphrase: Option<SecretString>,
         /// Password to encrypt the imported account
         password: SecretString,
     },
@@ -223,6 +242,16 @@ pub enum ImportSource {
         /// New password to encrypt the imported account
         new_password: SecretString,
     },
+    /// Import from a Web3 Secret Storage (V3) keystore written by geth,
+    /// MyCrypto or another wallet; both scrypt and PBKDF2 are accepted
+    KeystoreV3 {
+        /// The keystore JSON content
+        keystore_json: String,
+        /// Password to decrypt the keystore
+        keystore_password: SecretString,
+        /// Account name
+        name: String,
+    },
 }
 
 /// Import source type for tracking account origin
@@ -0,0 +1,326 @@
+//! Signing approvals between the wallet core and its frontends
+//!
+//! Before the wallet signs anything it sends an [`ApprovalRequest`] describing
+//! what is about to be signed, decoded for display: transaction calldata as a
+//! call preview, personal messages as text, typed data with its domain. The
+//! frontend answers with an [`ApprovalResponse`] and signing only proceeds on
+//! approval.
+//!
+//! A frontend such as the GUI takes requests with [`ApprovalBroker::connect`];
+//! connecting again replaces the previous receiver. Frontends that collect
+//! consent themselves before calling into the wallet, like the headless RPC
+//! servers, call [`ApprovalBroker::delegate`] instead so their requests are
+//! approved and logged under the frontend's name. Without either, every
+//! request is rejected.
+
+use crate::error::{Result, VaughanError, WalletError};
+use alloy::dyn_abi::TypedData;
+use alloy::primitives::utils::format_ether;
+use alloy::primitives::{Address, Bytes, B256};
+use alloy::rpc::types::TransactionRequest;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, oneshot};
+
+/// Identifies one approval request within a wallet session
+pub type ApprovalId = u64;
+
+/// What the wallet is asked to sign
+#[derive(Debug, Clone)]
+pub enum ApprovalKind {
+    /// A transaction and its decoded calldata, if any
+    Transaction {
+        tx: Box<TransactionRequest>,
+        call: Option<String>,
+    },
+    /// An EIP-191 personal message; `text` when it is valid UTF-8
+    Message { message: Bytes, text: Option<String> },
+    /// EIP-712 typed data and the hash that gets signed
+    TypedData { data: Box<TypedData>, hash: B256 },
+}
+
+impl ApprovalKind {
+    pub fn transaction(tx: &TransactionRequest) -> Self {
+        Self::Transaction {
+            tx: Box::new(tx.clone()),
+            call: crate::abi::describe_transaction(tx),
+        }
+    }
+
+    pub fn message(message: &[u8]) -> Self {
+        Self::Message {
+            message: Bytes::copy_from_slice(message),
+            text: std::str::from_utf8(message).ok().map(str::to_string),
+        }
+    }
+
+    /// Also returns the EIP-712 hash that gets signed
+    pub fn typed_data(data: &TypedData) -> Result<(Self, B256)> {
+        let hash = data
+            .eip712_signing_hash()
+            .map_err(|e| VaughanError::ValidationError(format!("Invalid typed data: {e}")))?;
+        let kind = Self::TypedData {
+            data: Box::new(data.clone()),
+            hash,
+        };
+        Ok((kind, hash))
+    }
+
+    /// Short name, e.g. for logs
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Transaction { .. } => "transaction",
+            Self::Message { .. } => "message",
+            Self::TypedData { .. } => "typed data",
+        }
+    }
+}
+
+/// A signature the wallet wants approved
+#[derive(Debug, Clone)]
+pub struct ApprovalRequest {
+    pub id: ApprovalId,
+    /// Account that would sign
+    pub account: Address,
+    pub chain_id: u64,
+    pub kind: ApprovalKind,
+}
+
+impl ApprovalRequest {
+    /// Lines describing the request for a confirmation screen
+    pub fn details(&self) -> Vec<String> {
+        let mut details = vec![
+            format!("Account: {}", self.account),
+            format!("Chain: {}", self.chain_id),
+        ];
+        match &self.kind {
+            ApprovalKind::Transaction { tx, call } => {
+                let to = tx.to.and_then(|to| to.to().copied());
+                details.push(match to {
+                    Some(to) => format!("To: {to}"),
+                    None => "To: new contract".to_string(),
+                });
+                details.push(format!("Value: {}", format_ether(tx.value.unwrap_or_default())));
+                if let Some(call) = call {
+                    details.push(format!("Call: {call}"));
+                }
+            }
+            ApprovalKind::Message { message, text } => details.push(match text {
+                Some(text) => format!("Message: {text}"),
+                None => format!("Message: {message}"),
+            }),
+            ApprovalKind::TypedData { data, hash } => {
+                if let Some(name) = &data.domain.name {
+                    details.push(format!("Domain: {name}"));
+                }
+                if let Some(contract) = data.domain.verifying_contract {
+                    details.push(format!("Contract: {contract}"));
+                }
+                details.push(format!("Type: {}", data.primary_type));
+                details.push(format!("Message: {}", data.message));
+                details.push(format!("Hash: {hash}"));
+            }
+        }
+        details
+    }
+}
+
+impl fmt::Display for ApprovalRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sign {} with {} on chain {}",
+            self.kind.label(),
+            self.account,
+            self.chain_id
+        )
+    }
+}
+
+/// The frontend's answer to an [`ApprovalRequest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalResponse {
+    Approve,
+    Reject { reason: Option<String> },
+}
+
+/// A request waiting for the frontend's answer
+///
+/// Dropping it unanswered rejects the request.
+#[derive(Debug)]
+pub struct PendingApproval {
+    request: ApprovalRequest,
+    responder: oneshot::Sender<ApprovalResponse>,
+}
+
+impl PendingApproval {
+    pub fn request(&self) -> &ApprovalRequest {
+        &self.request
+    }
+
+    pub fn respond(self, response: ApprovalResponse) {
+        // The wallet stops waiting only when the signing call is dropped
+        let _ = self.responder.send(response);
+    }
+
+    pub fn approve(self) {
+        self.respond(ApprovalResponse::Approve);
+    }
+
+    pub fn reject(self, reason: Option<String>) {
+        self.respond(ApprovalResponse::Reject { reason });
+    }
+}
+
+/// Frontend end of the approval channel
+#[derive(Debug)]
+pub struct ApprovalReceiver(mpsc::UnboundedReceiver<PendingApproval>);
+
+impl ApprovalReceiver {
+    /// Next request, or `None` once another frontend has connected
+    pub async fn recv(&mut self) -> Option<PendingApproval> {
+        self.0.recv().await
+    }
+}
+
+#[derive(Debug, Default)]
+enum Handler {
+    #[default]
+    Disconnected,
+    Frontend(mpsc::UnboundedSender<PendingApproval>),
+    Delegated(String),
+}
+
+/// Wallet end of the approval channel
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalBroker {
+    handler: Arc<Mutex<Handler>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl ApprovalBroker {
+    /// Send future requests to the returned receiver
+    pub fn connect(&self) -> ApprovalReceiver {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.set_handler(Handler::Frontend(sender));
+        ApprovalReceiver(receiver)
+    }
+
+    /// Approve future requests on behalf of `frontend`, which asks the user itself
+    pub fn delegate(&self, frontend: impl Into<String>) {
+        self.set_handler(Handler::Delegated(frontend.into()));
+    }
+
+    /// Reject future requests
+    pub fn disconnect(&self) {
+        self.set_handler(Handler::Disconnected);
+    }
+
+    fn set_handler(&self, handler: Handler) {
+        *self.handler.lock().unwrap_or_else(|e| e.into_inner()) = handler;
+    }
+
+    /// Ask for approval to sign `kind` with `account`, failing unless approved
+    pub async fn request(&self, account: Address, chain_id: u64, kind: ApprovalKind) -> Result<ApprovalId> {
+        let request = ApprovalRequest {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            account,
+            chain_id,
+            kind,
+        };
+        let (id, summary) = (request.id, request.to_string());
+
+        let frontend = match &*self.handler.lock().unwrap_or_else(|e| e.into_inner()) {
+            Handler::Disconnected => None,
+            Handler::Frontend(sender) => Some(sender.clone()),
+            Handler::Delegated(frontend) => {
+                tracing::warn!(target: "vaughan::audit", "Approval {} ({}) delegated to {}", id, summary, frontend);
+                return Ok(id);
+            }
+        };
+
+        let (responder, response) = oneshot::channel();
+        let sent = frontend.is_some_and(|sender| sender.send(PendingApproval { request, responder }).is_ok());
+        let response = if sent {
+            response.await.unwrap_or(ApprovalResponse::Reject {
+                reason: Some("approval request was dropped".to_string()),
+            })
+        } else {
+            ApprovalResponse::Reject {
+                reason: Some("no approval frontend connected".to_string()),
+            }
+        };
+
+        tracing::warn!(target: "vaughan::audit", "Approval {} ({}): {:?}", id, summary, response);
+        match response {
+            ApprovalResponse::Approve => Ok(id),
+            ApprovalResponse::Reject { reason } => Err(WalletError::ApprovalRejected {
+                reason: reason.unwrap_or_else(|| "rejected by user".to_string()),
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    #[tokio::test]
+    async fn test_signing_waits_for_frontend() {
+        let broker = ApprovalBroker::default();
+        assert!(broker
+            .request(Address::ZERO, 1, ApprovalKind::message(b"hi"))
+            .await
+            .is_err());
+
+        let mut receiver = broker.connect();
+        let frontend = tokio::spawn(async move {
+            let pending = receiver.recv().await.unwrap();
+            assert!(pending.request().details().contains(&"Message: gm".to_string()));
+            pending.approve();
+
+            let pending = receiver.recv().await.unwrap();
+            assert!(pending
+                .request()
+                .details()
+                .contains(&"Value: 1.000000000000000000".to_string()));
+            pending.reject(Some("wrong amount".to_string()));
+        });
+
+        let account = Address::repeat_byte(1);
+        assert_eq!(
+            broker
+                .request(account, 369, ApprovalKind::message(b"gm"))
+                .await
+                .unwrap(),
+            1
+        );
+        let tx = TransactionRequest::default()
+            .to(Address::repeat_byte(2))
+            .value(U256::from(10u64.pow(18)));
+        let rejected = broker.request(account, 369, ApprovalKind::transaction(&tx)).await;
+        assert!(rejected.unwrap_err().to_string().contains("wrong amount"));
+        frontend.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delegated_and_dropped_requests() {
+        let broker = ApprovalBroker::default();
+        let mut receiver = broker.connect();
+        let frontend = tokio::spawn(async move { drop(receiver.recv().await) });
+        assert!(broker
+            .request(Address::ZERO, 1, ApprovalKind::message(b"gm"))
+            .await
+            .is_err());
+        frontend.await.unwrap();
+
+        broker.delegate("stdio host");
+        assert!(broker
+            .request(Address::ZERO, 1, ApprovalKind::message(b"gm"))
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,174 @@
+//! Canonical JSON for backups and exports
+//!
+//! Backups and exports are written in one canonical form so the same wallet
+//! state always produces byte-identical output:
+//!
+//! - object keys are sorted at every level, so `HashMap` iteration order
+//!   never leaks into a file
+//! - two-space indentation, `\n` line endings and a trailing newline
+//! - numbers and strings as `serde_json` prints them (shortest round-trip
+//!   floats, minimal escaping)
+//!
+//! Documents also carry a `schema` name and `schema_version` at the top level.
+//! Readers accept documents written before these fields existed and refuse
+//! versions newer than they understand.
+
+use crate::error::{Result, WalletError};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Top-level key naming the document type
+pub const SCHEMA_FIELD: &str = "schema";
+/// Top-level key holding the document layout version
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// Sort object keys recursively
+///
+/// Entries are re-inserted in key order, which keeps the result sorted even
+/// when `serde_json` is built with `preserve_order`.
+pub fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().map(|(k, v)| (k, canonicalize(v))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
+}
+
+fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|e| WalletError::SerializationError(e.to_string()).into())
+}
+
+fn write_canonical(value: &Value) -> Result<String> {
+    let mut out = serde_json::to_string_pretty(value).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// Serialize `value` in canonical form
+pub fn to_canonical_string<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    write_canonical(&canonicalize(to_value(value)?))
+}
+
+/// Serialize `value` in canonical form as bytes
+pub fn to_canonical_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    to_canonical_string(value).map(String::into_bytes)
+}
+
+/// SHA-256 of the canonical form, for integrity checks
+pub fn canonical_digest<T: Serialize + ?Sized>(value: &T) -> Result<[u8; 32]> {
+    Ok(Sha256::digest(to_canonical_vec(value)?).into())
+}
+
+/// Serialize `value` as a canonical document tagged with `schema` and `version`
+///
+/// `value` must serialize to a JSON object without its own `schema` or
+/// `schema_version` keys.
+pub fn to_versioned_document<T: Serialize + ?Sized>(schema: &str, version: u32, value: &T) -> Result<String> {
+    let Value::Object(mut map) = to_value(value)? else {
+        return Err(WalletError::SerializationError(format!("{schema} document must be a JSON object")).into());
+    };
+    if map.contains_key(SCHEMA_FIELD) || map.contains_key(SCHEMA_VERSION_FIELD) {
+        return Err(WalletError::SerializationError(format!("{schema} document already has schema fields")).into());
+    }
+    map.insert(SCHEMA_FIELD.to_string(), Value::from(schema));
+    map.insert(SCHEMA_VERSION_FIELD.to_string(), Value::from(version));
+    write_canonical(&canonicalize(Value::Object(map)))
+}
+
+/// Parse a document written by [`to_versioned_document`]
+///
+/// Returns the schema version alongside the value; documents without schema
+/// fields predate them and are reported as version 0. A different schema
+/// name or a version above `max_version` is rejected.
+pub fn from_versioned_document<T: DeserializeOwned>(schema: &str, max_version: u32, json: &str) -> Result<(u32, T)> {
+    let value: Value = serde_json::from_str(json).map_err(|e| WalletError::DeserializationError(e.to_string()))?;
+    let Value::Object(mut map) = value else {
+        return Err(WalletError::DeserializationError(format!("{schema} document must be a JSON object")).into());
+    };
+
+    match map.remove(SCHEMA_FIELD) {
+        None => {}
+        Some(Value::String(found)) if found == schema => {}
+        Some(found) => {
+            return Err(
+                WalletError::DeserializationError(format!("Expected a {schema} document, found {found}")).into(),
+            )
+        }
+    }
+    let version = match map.remove(SCHEMA_VERSION_FIELD) {
+        None => 0,
+        Some(found) => found
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| WalletError::DeserializationError(format!("Invalid {schema} schema version: {found}")))?,
+    };
+    if version > max_version {
+        return Err(WalletError::DeserializationError(format!(
+            "{schema} schema version {version} is newer than the supported version {max_version}"
+        ))
+        .into());
+    }
+
+    let value =
+        serde_json::from_value(Value::Object(map)).map_err(|e| WalletError::DeserializationError(e.to_string()))?;
+    Ok((version, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        zeta: u32,
+        alpha: HashMap<String, Vec<u8>>,
+    }
+
+    fn sample(keys: &[&str]) -> Sample {
+        Sample {
+            zeta: 7,
+            alpha: keys.iter().map(|k| (k.to_string(), vec![1, 2])).collect(),
+        }
+    }
+
+    #[test]
+    fn test_canonical_output_is_byte_identical() {
+        let a = to_canonical_string(&sample(&["b", "a", "c", "d", "e"])).unwrap();
+        let b = to_canonical_string(&sample(&["e", "d", "c", "a", "b"])).unwrap();
+        assert_eq!(a, b);
+        assert!(a.ends_with("}\n"));
+        assert!(a.find("\"alpha\"").unwrap() < a.find("\"zeta\"").unwrap());
+        assert!(a.find("\"a\"").unwrap() < a.find("\"e\"").unwrap());
+        assert_eq!(
+            canonical_digest(&sample(&["x", "y"])).unwrap(),
+            canonical_digest(&sample(&["y", "x"])).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_versioned_document_roundtrip() {
+        let value = sample(&["a"]);
+        let json = to_versioned_document("vaughan.test", 2, &value).unwrap();
+        assert!(json.contains("\"schema_version\": 2"));
+
+        let (version, parsed): (u32, Sample) = from_versioned_document("vaughan.test", 2, &json).unwrap();
+        assert_eq!((version, parsed), (2, value));
+
+        // Older readers refuse newer documents, and schemas are not interchangeable
+        assert!(from_versioned_document::<Sample>("vaughan.test", 1, &json).is_err());
+        assert!(from_versioned_document::<Sample>("vaughan.other", 2, &json).is_err());
+
+        // Documents written before schema fields existed are version 0
+        let legacy = serde_json::to_string(&sample(&["a"])).unwrap();
+        let (version, _): (u32, Sample) = from_versioned_document("vaughan.test", 2, &legacy).unwrap();
+        assert_eq!(version, 0);
+    }
+}
@@ -1,30 +1,47 @@
 //! Backup and Recovery System
 //!
 //! Implements secure backup standards compatible with MetaMask Vault format (encrypted)
-//! and robust recovery mechanisms including Shamir's Secret Sharing.
+//! and robust recovery mechanisms including Shamir's Secret Sharing, either as
+//! raw `sharks` shares (`shamir` feature) or SLIP-39 mnemonics (see [`slip39`]).
+//! Backups can also carry the wallet's transaction and address notes
+//! ([`crate::wallet::metadata`]).
 //!
 //! # Requirements
 //! - Requirement 11.1: Encrypted backup
 //! - Requirement 11.2: Shamir's Secret Sharing
 //! - Requirement 11.4: Integrity verification
 
+pub mod canonical;
+pub mod recovery;
+pub mod slip39;
+
+pub use recovery::{get_recovery_dir, DestructiveOperation, RecoveryPoint, RecoveryRegistry};
+pub use slip39::{Slip39Group, Slip39ShareInfo};
+
 use crate::error::{Result, SecurityError, WalletError};
-use crate::security::SecureKeystore;
+use crate::security::cold_account;
+use crate::security::{
+    ExportAuthenticator, SecureAccount, SecureKeystore, SecureSeedStorage, SERVICE_NAME_ENCRYPTED_SEEDS,
+};
+use crate::telemetry::audit::{self, AuditEvent};
+use crate::wallet::metadata::NoteBook;
+use crate::wallet::password_change::{verify_master_password, PasswordStores};
+use crate::wallet::progress::{NoProgress, ProgressOperation, ProgressReporter, ProgressTracker};
 use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm, Nonce,
 };
 use hmac::{Hmac, Mac};
-use secrecy::SecretString;
+use secrecy::{ExposeSecret, SecretString, SecretVec};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
+use std::collections::HashMap;
 use uuid::Uuid;
+use zeroize::{Zeroize, Zeroizing};
 
 #[cfg(feature = "shamir")]
 use crate::VaughanError;
 #[cfg(feature = "shamir")]
-use secrecy::{ExposeSecret, SecretVec};
-#[cfg(feature = "shamir")]
 use sharks::{Share, Sharks};
 
 /// Encrypted Backup Container (MetaMask-style Vault)
@@ -39,47 +56,329 @@ pub struct BackupContainer {
     pub hmac: String, // Hex encoded HMAC-SHA256
 }
 
+impl BackupContainer {
+    /// Schema name embedded in backup files
+    pub const SCHEMA: &'static str = "vaughan.backup";
+    /// Current layout of backup files
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    /// Serialize as a canonical, schema-tagged backup file
+    pub fn to_canonical_json(&self) -> Result<String> {
+        canonical::to_versioned_document(Self::SCHEMA, Self::SCHEMA_VERSION, self)
+    }
+
+    /// Parse a backup file, including ones written before schema tagging
+    pub fn from_json(json: &str) -> Result<Self> {
+        canonical::from_versioned_document(Self::SCHEMA, Self::SCHEMA_VERSION, json).map(|(_, container)| container)
+    }
+}
+
+/// Decrypted backup contents
+///
+/// Seeds and private keys are stored in plaintext here; the container
+/// encrypts the whole payload under the backup password.
+#[derive(Serialize, Deserialize)]
+struct BackupPayload {
+    accounts: Vec<SecureAccount>,
+    /// Seed phrases keyed by key reference id
+    #[serde(default)]
+    seeds: HashMap<String, String>,
+    /// Hex private keys keyed by key reference id
+    #[serde(default)]
+    private_keys: HashMap<String, String>,
+    /// Notes and labels on transactions and addresses
+    #[serde(default, skip_serializing_if = "NoteBook::is_empty")]
+    notes: NoteBook,
+}
+
+impl Drop for BackupPayload {
+    fn drop(&mut self) {
+        self.seeds.values_mut().chain(self.private_keys.values_mut()).for_each(|secret| secret.zeroize());
+    }
+}
+
+/// Backups written before secrets were included hold only the account list
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredPayload {
+    Full(BackupPayload),
+    MetadataOnly(Vec<SecureAccount>),
+}
+
+impl StoredPayload {
+    fn into_payload(self) -> BackupPayload {
+        match self {
+            Self::Full(payload) => payload,
+            Self::MetadataOnly(accounts) => BackupPayload {
+                accounts,
+                seeds: HashMap::new(),
+                private_keys: HashMap::new(),
+                notes: NoteBook::default(),
+            },
+        }
+    }
+}
+
+fn is_seed_account(account: &SecureAccount) -> bool {
+    account.key_reference.service == SERVICE_NAME_ENCRYPTED_SEEDS
+}
+
+/// Export operation name of a full backup in the audit log
+const BACKUP_OPERATION: &str = "create_backup";
+
 /// Manager for secure backup and recovery operations
+///
+/// A full backup carries every seed and private key, so creating one checks
+/// the master password and draws on the export allowance like any other
+/// export. Restoring needs only the backup password.
 #[derive(Debug, Clone)]
-pub struct BackupManager;
+pub struct BackupManager {
+    /// Stores the master password is checked against
+    stores: PasswordStores,
+    /// Cooldown and hourly allowance shared with seed and key exports
+    exports: ExportAuthenticator,
+}
+
+impl Default for BackupManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl BackupManager {
-    /// Create a new encrypted backup of the keystore
+    /// Check the master password against the default stores
+    ///
+    /// Backups draw on [`ExportAuthenticator::shared`].
+    pub fn new() -> Self {
+        Self {
+            stores: PasswordStores::default(),
+            exports: ExportAuthenticator::shared().clone(),
+        }
+    }
+
+    /// Check the master password against `stores` instead of the default ones
+    pub fn with_password_stores(mut self, stores: PasswordStores) -> Self {
+        self.stores = stores;
+        self
+    }
+
+    /// Limit backups with `exports` instead of the shared authenticator
+    pub fn with_export_authenticator(mut self, exports: ExportAuthenticator) -> Self {
+        self.exports = exports;
+        self
+    }
+
+    /// Create a new encrypted backup of the keystore, including its secrets
+    ///
+    /// `wallet_password` must be the master password; it also decrypts the
+    /// seed accounts. The backup itself is sealed with `backup_password`.
     pub async fn create_encrypted_backup(
-        keystore: &SecureKeystore,
-        password: &SecretString,
+        &self,
+        keystore: &mut SecureKeystore,
+        wallet_password: &SecretString,
+        backup_password: &SecretString,
+    ) -> Result<BackupContainer> {
+        self.create_encrypted_backup_with_progress(keystore, wallet_password, backup_password, &NoProgress)
+            .await
+    }
+
+    /// Create a new encrypted backup, reporting progress for each stage
+    pub async fn create_encrypted_backup_with_progress(
+        &self,
+        keystore: &mut SecureKeystore,
+        wallet_password: &SecretString,
+        backup_password: &SecretString,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<BackupContainer> {
+        self.create_backup(keystore, wallet_password, backup_password, NoteBook::default(), reporter)
+            .await
+    }
+
+    /// Create a new encrypted backup that also carries the wallet's notes and labels
+    pub async fn create_encrypted_backup_with_notes(
+        &self,
+        keystore: &mut SecureKeystore,
+        wallet_password: &SecretString,
+        backup_password: &SecretString,
+        notes: &NoteBook,
+    ) -> Result<BackupContainer> {
+        self.create_backup(keystore, wallet_password, backup_password, notes.clone(), &NoProgress)
+            .await
+    }
+
+    async fn create_backup(
+        &self,
+        keystore: &mut SecureKeystore,
+        wallet_password: &SecretString,
+        backup_password: &SecretString,
+        notes: NoteBook,
+        reporter: &dyn ProgressReporter,
     ) -> Result<BackupContainer> {
         let correlation_id = Uuid::new_v4();
         tracing::info!(correlation_id = %correlation_id, "📦 Starting encrypted backup creation");
-
-        // 1. Serialize Keystore State
-        // For backup, we ideally export all accounts data. 
-        // Since SecureKeystore holds accounts in memory and keys in Keychain, 
-        // a "Full Backup" implies exporting the *keys* too. 
-        // However, SecureKeystore doesn't expose raw keys easily without password.
-        // Assuming we just want to backup the *list of accounts* and their metadata 
-        // OR we want a full Vault export. 
-        // 
-        // The requirements imply "Export entire wallet state". 
-        // For this implementation, we will serialize the Account list. 
-        // Real private keys are in the backend keychain (OS specific).
-        // 
-        // CRITICAL NOTE: If the keys are in OS Keychain, a file backup of just struct memory 
-        // is insufficient for cross-device recovery. 
-        // We really need to export the SECRETS.
-        // 
-        // Given `AccountExporter` exists for single accounts, `BackupManager` should probably 
-        // iterate and export ALL accounts if possible, OR assumes `SecureKeystore` has a method 
-        // to dump everything (which it usually doesn't for security).
-        // 
-        // Let's assume we backup the *metadata* for now, as specific key extraction requires 
-        // per-key password in some designs, or Master Password in others. 
-        // If `SecureKeystore` uses a Master Password for `SecureSeedStorage`, we can backup the 
-        // Encrypted Seed.
-        
-        // Strategy: Serialize the entire list of accounts.
+        let mut progress = ProgressTracker::start(reporter, ProgressOperation::Backup, 5, "Collecting accounts")
+            .with_operation_id(correlation_id);
+
+        // Private keys are read as stored, so nothing else proves the caller
+        // knows the master password before every secret leaves the wallet
+        let password_valid = verify_master_password(keystore, &self.stores, wallet_password).await?;
+        self.exports.authenticate(password_valid).await?;
+        self.exports.take_export_allowance(BACKUP_OPERATION, None)?;
+
+        // Keys live in the OS keychain, so a metadata-only backup cannot be
+        // restored on another machine. Pull every secret out alongside the
+        // account list: seeds are decrypted with the wallet password, private
+        // keys are read as stored. Hardware accounts have nothing to export.
         let accounts = keystore.list_accounts().await?;
-        let data = serde_json::to_string(&accounts).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+        let mut seeds = HashMap::new();
+        let mut private_keys = HashMap::new();
+        for account in accounts.iter().filter(|a| !a.is_hardware) {
+            let id = &account.key_reference.id;
+            if is_seed_account(account) {
+                if !seeds.contains_key(id) {
+                    let phrase = keystore
+                        .get_decrypted_seed_phrase(&account.address, wallet_password)
+                        .await
+                        .map_err(|e| WalletError::Generic(format!("Cannot back up seed of {}: {}", account.name, e)))?;
+                    seeds.insert(id.clone(), phrase.expose_secret().clone());
+                }
+            } else {
+                let key = keystore
+                    .retrieve(&account.key_reference)
+                    .map_err(|e| WalletError::Generic(format!("Cannot back up key of {}: {}", account.name, e)))?;
+                private_keys.insert(id.clone(), key.expose_secret().clone());
+            }
+        }
+        progress.step("Collecting secrets");
+
+        let payload = BackupPayload {
+            accounts,
+            seeds,
+            private_keys,
+            notes,
+        };
+        let data = Zeroizing::new(canonical::to_canonical_vec(&payload)?);
+        let container = Self::seal(correlation_id, &data, backup_password, &mut progress)?;
+
+        // Every secret is now out of the wallet, as after a single export
+        for account in payload.accounts.iter().filter(|a| !a.is_hardware) {
+            let event = if is_seed_account(account) {
+                AuditEvent::ExportSeed {
+                    account: account.address,
+                }
+            } else {
+                AuditEvent::ExportPrivateKey {
+                    account: account.address,
+                }
+            };
+            audit::record(event, correlation_id);
+            cold_account::mark_key_exported(keystore, account.address).await;
+        }
+
+        progress.finish("Backup created");
+        tracing::info!(
+            correlation_id = %correlation_id,
+            "✅ Backup created successfully ({} accounts, {} seeds, {} keys)",
+            payload.accounts.len(),
+            payload.seeds.len(),
+            payload.private_keys.len()
+        );
+        Ok(container)
+    }
+
+    /// List the accounts in an encrypted backup without restoring them
+    pub fn accounts_in_backup(container: &BackupContainer, password: &SecretString) -> Result<Vec<SecureAccount>> {
+        let mut progress = ProgressTracker::start(&NoProgress, ProgressOperation::Restore, 4, "Reading backup");
+        let payload = Self::read_payload(container, password, &mut progress)?;
+        progress.finish("Backup opened");
+        Ok(payload.accounts.clone())
+    }
+
+    /// Notes and labels in an encrypted backup; empty for backups made without them
+    pub fn notes_in_backup(container: &BackupContainer, password: &SecretString) -> Result<NoteBook> {
+        let mut progress = ProgressTracker::start(&NoProgress, ProgressOperation::Restore, 4, "Reading backup");
+        let payload = Self::read_payload(container, password, &mut progress)?;
+        progress.finish("Backup opened");
+        Ok(payload.notes.clone())
+    }
+
+    /// Restore from encrypted backup into `keystore`
+    ///
+    /// Re-provisions the keychain entry of every account missing from the
+    /// keystore and returns the restored accounts. Seeds are re-encrypted
+    /// under the backup password, which then unlocks them on this machine.
+    pub async fn restore_from_backup(
+        container: &BackupContainer,
+        password: &SecretString,
+        keystore: &mut SecureKeystore,
+    ) -> Result<Vec<SecureAccount>> {
+        Self::restore_from_backup_with_progress(container, password, keystore, &NoProgress).await
+    }
+
+    /// Restore from encrypted backup, reporting progress for each stage
+    pub async fn restore_from_backup_with_progress(
+        container: &BackupContainer,
+        password: &SecretString,
+        keystore: &mut SecureKeystore,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<Vec<SecureAccount>> {
+        tracing::info!(backup_id = %container.id, "♻️ Restoring from backup");
+        let mut progress = ProgressTracker::start(reporter, ProgressOperation::Restore, 5, "Reading backup");
+
+        let payload = Self::read_payload(container, password, &mut progress)?;
+        progress.step("Restoring accounts");
+
+        let existing: Vec<_> = keystore.list_accounts().await?.iter().map(|a| a.address).collect();
+        // Accounts derived from one seed share its keychain entry; encrypt it once
+        let mut sealed_seeds: HashMap<String, SecretString> = HashMap::new();
+        let mut restored = Vec::new();
+        for account in payload.accounts.iter().filter(|a| !existing.contains(&a.address)) {
+            let id = &account.key_reference.id;
+            let secret = if is_seed_account(account) {
+                match (sealed_seeds.get(id), payload.seeds.get(id)) {
+                    (Some(sealed), _) => Some(sealed.clone()),
+                    (None, Some(phrase)) => {
+                        let sealed =
+                            SecureSeedStorage::encrypt_for_keychain(&SecretString::new(phrase.clone()), password)?;
+                        sealed_seeds.insert(id.clone(), sealed.clone());
+                        Some(sealed)
+                    }
+                    (None, None) => None,
+                }
+            } else {
+                payload.private_keys.get(id).map(|key| SecretString::new(key.clone()))
+            };
+            if secret.is_none() && !account.is_hardware {
+                tracing::warn!("Backup has no key material for {} ({})", account.name, account.address);
+            }
+            keystore.restore_account(account.clone(), secret).await?;
+            restored.push(account.clone());
+        }
+
+        progress.finish("Backup restored");
+        tracing::info!("✅ Backup restored successfully ({} accounts)", restored.len());
+        Ok(restored)
+    }
+
+    fn read_payload(
+        container: &BackupContainer,
+        password: &SecretString,
+        progress: &mut ProgressTracker<'_>,
+    ) -> Result<BackupPayload> {
+        let plaintext = Zeroizing::new(Self::open(container, password, progress)?);
+        let stored: StoredPayload =
+            serde_json::from_slice(&plaintext).map_err(|e| WalletError::DeserializationError(e.to_string()))?;
+        Ok(stored.into_payload())
+    }
+
+    /// Encrypt and authenticate `data` into a backup container
+    pub(crate) fn seal(
+        id: Uuid,
+        data: &[u8],
+        password: &SecretString,
+        progress: &mut ProgressTracker<'_>,
+    ) -> Result<BackupContainer> {
+        progress.step("Deriving encryption key");
 
         // 2. Derive Encryption Key (Argon2id)
         let salt = Uuid::new_v4().as_bytes().to_vec(); // Simple random salt
@@ -90,14 +389,16 @@ impl BackupManager {
             3,     // 3 iterations
             4      // 4 parallelism
         )?;
+        progress.step("Encrypting");
 
         // 3. Encrypt (AES-256-GCM)
         let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| SecurityError::EncryptionError { message: "Invalid key length".into() })?;
         let nonce_bytes = rand::random::<[u8; 12]>();
         let nonce = Nonce::from_slice(&nonce_bytes);
         
-        let ciphertext = cipher.encrypt(nonce, data.as_bytes())
+        let ciphertext = cipher.encrypt(nonce, data)
             .map_err(|e| SecurityError::EncryptionError { message: format!("Encryption failed: {}", e) })?;
+        progress.step("Signing backup");
 
         // 4. Calculate HMAC (Integrity)
         // Use the same derived key for HMAC (MetaMask often uses distinct, but we'll use same for simplicity or derive another)
@@ -108,32 +409,29 @@ impl BackupManager {
         mac.update(&ciphertext);
         let hmac_result = mac.finalize().into_bytes();
 
-        let container = BackupContainer {
+        Ok(BackupContainer {
             version: 1,
-            id: correlation_id,
+            id,
             timestamp: chrono::Utc::now().timestamp(),
             salt: hex::encode(salt),
             nonce: hex::encode(nonce_bytes),
             ciphertext: hex::encode(ciphertext),
             hmac: hex::encode(hmac_result),
-        };
-
-        tracing::info!(correlation_id = %correlation_id, "✅ Backup created successfully");
-        Ok(container)
+        })
     }
 
-    /// Restore from encrypted backup
-    pub fn restore_from_backup(
+    /// Verify and decrypt a backup container
+    pub(crate) fn open(
         container: &BackupContainer,
         password: &SecretString,
-    ) -> Result<Vec<crate::security::SecureAccount>> {
-        tracing::info!(backup_id = %container.id, "♻️ Restoring from backup");
-
+        progress: &mut ProgressTracker<'_>,
+    ) -> Result<Vec<u8>> {
         // 1. Decode fields
         let salt = hex::decode(&container.salt).map_err(|_| WalletError::DeserializationError("Invalid salt".into()))?;
         let nonce_bytes = hex::decode(&container.nonce).map_err(|_| WalletError::DeserializationError("Invalid nonce".into()))?;
         let ciphertext = hex::decode(&container.ciphertext).map_err(|_| WalletError::DeserializationError("Invalid ciphertext".into()))?;
         let stored_hmac = hex::decode(&container.hmac).map_err(|_| WalletError::DeserializationError("Invalid HMAC".into()))?;
+        progress.step("Deriving decryption key");
 
         // 2. Derive Key
         let key = crate::security::seed::encryption::derive_key_argon2id(
@@ -143,6 +441,7 @@ impl BackupManager {
             3,
             4
         )?;
+        progress.step("Verifying integrity");
 
         // 3. Verify HMAC
         let mut mac = <Hmac::<Sha256> as Mac>::new_from_slice(&key)
@@ -153,6 +452,7 @@ impl BackupManager {
             tracing::error!("❌ HMAC validation failed - backup integrity compromised");
             return Err(SecurityError::IntegrityCheckFailed { message: "Backup corrupted or tampered".into() }.into());
         }
+        progress.step("Decrypting");
 
         // 4. Decrypt
         let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| SecurityError::EncryptionError { message: "Invalid key".into() })?;
@@ -161,15 +461,7 @@ impl BackupManager {
         let plaintext = cipher.decrypt(nonce, ciphertext.as_ref())
             .map_err(|_| SecurityError::InvalidPassword)?; // Usually implies wrong key/password
 
-        let plaintext_str = String::from_utf8(plaintext)
-            .map_err(|_| WalletError::DeserializationError("Invalid UTF-8".into()))?;
-
-        // 5. Deserialize
-        let accounts: Vec<crate::security::SecureAccount> = serde_json::from_str(&plaintext_str)
-            .map_err(|e| WalletError::DeserializationError(e.to_string()))?;
-
-        tracing::info!("✅ Backup restored successfully ({} accounts)", accounts.len());
-        Ok(accounts)
+        Ok(plaintext)
     }
 
     /// Create Shamir's Secret Sharing shares (Requirement 11.2)
@@ -218,52 +510,154 @@ impl BackupManager {
 
         Ok(SecretVec::new(secret))
     }
+
+    /// Create SLIP-39 mnemonic shares, one list of word lists per group
+    ///
+    /// The shares can be restored with Trezor or any other SLIP-39 tool;
+    /// Trezor expects a 16- or 32-byte secret.
+    pub fn create_slip39_shares(
+        secret: &SecretVec<u8>,
+        passphrase: &str,
+        group_threshold: u8,
+        groups: &[Slip39Group],
+    ) -> Result<Vec<Vec<SecretString>>> {
+        slip39::generate_mnemonics(
+            secret.expose_secret(),
+            passphrase,
+            group_threshold,
+            groups,
+            slip39::DEFAULT_ITERATION_EXPONENT,
+        )
+    }
+
+    /// Recover a secret from SLIP-39 mnemonic shares
+    pub fn recover_from_slip39_shares(shares: &[SecretString], passphrase: &str) -> Result<SecretVec<u8>> {
+        slip39::combine_mnemonics(shares, passphrase)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::security::keychain::MockKeychain;
-    use crate::security::SecureKeystoreImpl;
+    use crate::security::{
+        ExportRateLimitPolicy, KeychainInterface, SecureKeystoreImpl, SeedManager, TestKeychain, WalletConfigStorage,
+    };
+    use crate::wallet::password_change::write_legacy_wallet;
+    use crate::VaughanError;
+    use std::path::Path;
+
+    /// Exports limited only by `max_exports_per_hour`, kept off the user's allowance
+    fn exports(max_exports_per_hour: u32) -> ExportAuthenticator {
+        ExportAuthenticator::in_memory(ExportRateLimitPolicy {
+            max_exports_per_hour,
+            failure_cooldown_seconds: 0,
+            max_cooldown_seconds: 0,
+            ..ExportRateLimitPolicy::default()
+        })
+    }
+
+    /// Backup manager over stores in `dir` whose master password is `password`
+    pub(super) fn backup_manager(dir: &Path, password: &SecretString) -> BackupManager {
+        let mut config_storage = WalletConfigStorage::new_with_keychain(Box::new(MockKeychain::new())).unwrap();
+        config_storage.set_config_path(dir.join("wallet_metadata.json"));
+        let stores = PasswordStores {
+            wallet_config: Some(config_storage),
+            ..PasswordStores::in_dir(dir)
+        };
+        write_legacy_wallet(&stores.legacy_wallet_file, password, &[1u8; 32]);
+        BackupManager {
+            stores,
+            exports: exports(100),
+        }
+    }
 
     #[tokio::test]
     async fn test_encrypted_backup_roundtrip() {
-        // Setup
-        let keychain = Box::new(MockKeychain::new());
-        let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
-        let _ = keystore.create_account("TestUser".into()).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let phrase = SecretString::new("test test test test test test test test test test test junk".into());
+        let wallet_password = SecretString::new("wallet password".into());
         let password = SecretString::new("strong_password".into());
 
-        // Create Backup
-        let backup = BackupManager::create_encrypted_backup(&keystore, &password).await.unwrap();
-        
-        // Restore
-        let restored_accounts = BackupManager::restore_from_backup(&backup, &password).unwrap();
-        assert_eq!(restored_accounts.len(), 1);
-        assert_eq!(restored_accounts[0].name, "TestUser");
+        // Source wallet with a private key account and a seed account
+        let keychain = TestKeychain::new();
+        let mut keystore = SecureKeystoreImpl::new(keychain.clone_box()).await.unwrap();
+        let key_account = keystore.create_account("TestUser".into()).await.unwrap();
+        let seed_account = SeedManager::new(Box::new(keychain))
+            .create_wallet_from_seed_encrypted("Seed User".into(), &phrase, &wallet_password, None)
+            .await
+            .unwrap();
+        keystore.restore_account(seed_account.clone(), None).await.unwrap();
+
+        let mut notes = NoteBook::new();
+        let exchange = crate::wallet::metadata::NoteTarget::Address(alloy::primitives::Address::repeat_byte(0xce));
+        notes.add_label(exchange, "CEX deposit").unwrap();
+        let backups = backup_manager(dir.path(), &wallet_password);
+        let backup = backups
+            .create_encrypted_backup_with_notes(&mut keystore, &wallet_password, &password, &notes)
+            .await
+            .unwrap();
+        assert_eq!(BackupManager::notes_in_backup(&backup, &password).unwrap(), notes);
+        assert!(backups
+            .create_encrypted_backup(&mut keystore, &password, &password)
+            .await
+            .is_err());
+        let private_key = keystore.retrieve(&key_account.key_reference).unwrap();
+        keystore.remove_account(key_account.address).await.unwrap();
+        keystore.remove_account(seed_account.address).await.unwrap();
+
+        // Restore on a machine whose keychain has never seen these accounts
+        let mut fresh = SecureKeystoreImpl::new(Box::new(MockKeychain::new())).await.unwrap();
+        let restored = BackupManager::restore_from_backup(&backup, &password, &mut fresh).await.unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(
+            fresh.retrieve(&key_account.key_reference).unwrap().expose_secret(),
+            private_key.expose_secret()
+        );
+        let restored_phrase = fresh
+            .get_decrypted_seed_phrase(&seed_account.address, &password)
+            .await
+            .unwrap();
+        assert_eq!(restored_phrase.expose_secret(), phrase.expose_secret());
+
+        // Accounts already present are left alone
+        assert!(BackupManager::restore_from_backup(&backup, &password, &mut fresh)
+            .await
+            .unwrap()
+            .is_empty());
+        fresh.remove_account(key_account.address).await.unwrap();
+        fresh.remove_account(seed_account.address).await.unwrap();
     }
 
     #[tokio::test]
     async fn test_backup_bad_password() {
+        let dir = tempfile::tempdir().unwrap();
         let keychain = Box::new(MockKeychain::new());
-        let keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
+        let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
         let password = SecretString::new("correct".into());
         let bad_password = SecretString::new("wrong".into());
 
-        let backup = BackupManager::create_encrypted_backup(&keystore, &password).await.unwrap();
-        
-        let result = BackupManager::restore_from_backup(&backup, &bad_password);
+        let backup = backup_manager(dir.path(), &password)
+            .create_encrypted_backup(&mut keystore, &password, &password)
+            .await
+            .unwrap();
+
+        let result = BackupManager::accounts_in_backup(&backup, &bad_password);
         assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_backup_tampering() {
+        let dir = tempfile::tempdir().unwrap();
         let keychain = Box::new(MockKeychain::new());
-        let keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
+        let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
         let password = SecretString::new("strong_password".into());
 
-        let mut backup = BackupManager::create_encrypted_backup(&keystore, &password).await.unwrap();
-        
+        let mut backup = backup_manager(dir.path(), &password)
+            .create_encrypted_backup(&mut keystore, &password, &password)
+            .await
+            .unwrap();
+
         // Tamper with ciphertext
         let mut corrupted = hex::decode(&backup.ciphertext).unwrap();
         if let Some(byte) = corrupted.get_mut(0) {
@@ -271,13 +665,55 @@ mod tests {
         }
         backup.ciphertext = hex::encode(corrupted);
 
-        let result = BackupManager::restore_from_backup(&backup, &password);
+        let result = BackupManager::accounts_in_backup(&backup, &password);
         // Should fail integrity check logic (HMAC)
         // If HMAC uses ciphertext, modifying ciphertext invalidates HMAC.
         // Our restore logic checks HMAC first.
         assert!(matches!(result, Err(crate::error::VaughanError::Security(SecurityError::IntegrityCheckFailed { .. }))));
     }
+
+    #[tokio::test]
+    async fn test_backup_needs_master_password_and_export_allowance() {
+        let dir = tempfile::tempdir().unwrap();
+        let password = SecretString::new("master password".into());
+        let mut keystore = SecureKeystoreImpl::new(Box::new(MockKeychain::new())).await.unwrap();
+        keystore.create_account("Key".into()).await.unwrap();
+        let backups = backup_manager(dir.path(), &password).with_export_authenticator(exports(1));
+
+        // Private keys are readable without a password; the master password still has to match
+        let wrong = SecretString::new("wrong password".into());
+        assert!(matches!(
+            backups.create_encrypted_backup(&mut keystore, &wrong, &wrong).await,
+            Err(VaughanError::Security(SecurityError::InvalidPassword))
+        ));
+
+        backups.create_encrypted_backup(&mut keystore, &password, &password).await.unwrap();
+        assert!(matches!(
+            backups.create_encrypted_backup(&mut keystore, &password, &password).await,
+            Err(VaughanError::Security(SecurityError::RateLimitExceeded { .. }))
+        ));
+    }
     
+    #[tokio::test]
+    async fn test_backup_marks_cold_account_keys_exported() {
+        let dir = tempfile::tempdir().unwrap();
+        let password = SecretString::new("master password".into());
+        let mut keystore = SecureKeystoreImpl::new(Box::new(MockKeychain::new())).await.unwrap();
+        let account = keystore.create_account("Vault".into()).await.unwrap();
+        keystore
+            .update_account_tags(account.address, vec![cold_account::COLD_TAG.to_string()])
+            .await
+            .unwrap();
+
+        backup_manager(dir.path(), &password)
+            .create_encrypted_backup(&mut keystore, &password, &password)
+            .await
+            .unwrap();
+        let account = keystore.get_account(account.address).await.unwrap();
+        assert!(cold_account::key_exported(&account));
+        assert_eq!(cold_account::cold_warnings(&account).len(), 1);
+    }
+
     #[cfg(feature = "shamir")]
     #[test]
     fn test_shamir_roundtrip() {
@@ -296,6 +732,7 @@ mod tests {
 
 #[cfg(test)]
 mod property_tests {
+    use super::tests::backup_manager;
     use super::*;
     use crate::security::keychain::MockKeychain;
     use crate::security::SecureKeystoreImpl;
@@ -321,6 +758,7 @@ mod property_tests {
 
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
+                let dir = tempfile::tempdir().unwrap();
                 // Setup keystore with an account
                 let keychain = Box::new(MockKeychain::new());
                 let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
@@ -330,7 +768,8 @@ mod property_tests {
                 let wrong_password_secret = SecretString::new(wrong_password.clone());
 
                 // Create encrypted backup
-                let backup = BackupManager::create_encrypted_backup(&keystore, &password_secret)
+                let backup = backup_manager(dir.path(), &password_secret)
+                    .create_encrypted_backup(&mut keystore, &password_secret, &password_secret)
                     .await
                     .unwrap();
 
@@ -349,7 +788,7 @@ mod property_tests {
                 prop_assert!(!backup.hmac.is_empty(), "Backup should have HMAC");
 
                 // Correct password should restore successfully
-                let restored = BackupManager::restore_from_backup(&backup, &password_secret);
+                let restored = BackupManager::accounts_in_backup(&backup, &password_secret);
                 prop_assert!(restored.is_ok(), "Correct password should restore backup");
                 
                 let accounts = restored.unwrap();
@@ -357,7 +796,7 @@ mod property_tests {
                 prop_assert_eq!(&accounts[0].name, &account_name, "Account name should match");
 
                 // Wrong password should fail
-                let wrong_restore = BackupManager::restore_from_backup(&backup, &wrong_password_secret);
+                let wrong_restore = BackupManager::accounts_in_backup(&backup, &wrong_password_secret);
                 prop_assert!(
                     wrong_restore.is_err(),
                     "Wrong password should fail to restore backup"
@@ -382,6 +821,7 @@ mod property_tests {
         ) {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
+                let dir = tempfile::tempdir().unwrap();
                 // Setup keystore with an account
                 let keychain = Box::new(MockKeychain::new());
                 let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
@@ -390,7 +830,8 @@ mod property_tests {
                 let password_secret = SecretString::new(password.clone());
 
                 // Create valid backup
-                let mut backup = BackupManager::create_encrypted_backup(&keystore, &password_secret)
+                let mut backup = backup_manager(dir.path(), &password_secret)
+                    .create_encrypted_backup(&mut keystore, &password_secret, &password_secret)
                     .await
                     .unwrap();
 
@@ -403,7 +844,7 @@ mod property_tests {
                     backup.ciphertext = hex::encode(&ciphertext_bytes);
 
                     // Attempt to restore corrupted backup
-                    let result = BackupManager::restore_from_backup(&backup, &password_secret);
+                    let result = BackupManager::accounts_in_backup(&backup, &password_secret);
 
                     // Should fail with integrity error
                     prop_assert!(
@@ -437,13 +878,15 @@ mod property_tests {
         ) {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
+                let dir = tempfile::tempdir().unwrap();
                 let keychain = Box::new(MockKeychain::new());
                 let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
                 let _ = keystore.create_account(account_name).await.unwrap();
                 
                 let password_secret = SecretString::new(password);
 
-                let backup = BackupManager::create_encrypted_backup(&keystore, &password_secret)
+                let backup = backup_manager(dir.path(), &password_secret)
+                    .create_encrypted_backup(&mut keystore, &password_secret, &password_secret)
                     .await
                     .unwrap();
 
@@ -472,6 +915,8 @@ mod property_tests {
         ) {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
+                let dir = tempfile::tempdir().unwrap();
+                let backups = backup_manager(dir.path(), &SecretString::new(password.clone()));
                 let keychain1 = Box::new(MockKeychain::new());
                 let mut keystore1 = SecureKeystoreImpl::new(keychain1).await.unwrap();
                 let _ = keystore1.create_account(account_name.clone()).await.unwrap();
@@ -483,10 +928,12 @@ mod property_tests {
                 let password_secret = SecretString::new(password);
 
                 // Create two backups
-                let backup1 = BackupManager::create_encrypted_backup(&keystore1, &password_secret)
+                let backup1 = backups
+                    .create_encrypted_backup(&mut keystore1, &password_secret, &password_secret)
                     .await
                     .unwrap();
-                let backup2 = BackupManager::create_encrypted_backup(&keystore2, &password_secret)
+                let backup2 = backups
+                    .create_encrypted_backup(&mut keystore2, &password_secret, &password_secret)
                     .await
                     .unwrap();
 
@@ -0,0 +1,303 @@
+//! Recovery points for destructive keystore operations
+//!
+//! Removing accounts, changing passwords and migrating storage formats first
+//! take an encrypted snapshot of the keystore (account list plus keychain
+//! entries) and record it in a recovery registry. Any such operation can then
+//! be undone from a known snapshot.
+//!
+//! Snapshots live in `<config_dir>/vaughan/recovery/<id>.json`, the registry
+//! in `<config_dir>/vaughan/recovery/registry.json`.
+
+use super::{canonical, BackupContainer, BackupManager};
+use crate::error::{Result, VaughanError, WalletError};
+use crate::security::keystore::storage::write_secure_file;
+use crate::security::{SecureAccount, SecureKeystore};
+use crate::wallet::progress::{NoProgress, ProgressOperation, ProgressTracker};
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Get the directory holding recovery snapshots and their registry
+pub fn get_recovery_dir() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("vaughan");
+    path.push("recovery");
+    path
+}
+
+/// An operation that can lose keystore data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DestructiveOperation {
+    RemoveAccount { address: Address },
+    ChangePassword,
+    MigrateStorage { description: String },
+}
+
+impl fmt::Display for DestructiveOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RemoveAccount { address } => write!(f, "remove account {address}"),
+            Self::ChangePassword => write!(f, "change password"),
+            Self::MigrateStorage { description } => write!(f, "storage migration: {description}"),
+        }
+    }
+}
+
+/// A snapshot taken before a destructive operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecoveryPoint {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub operation: DestructiveOperation,
+    /// Encrypted snapshot file
+    pub file: PathBuf,
+    pub account_count: usize,
+    /// Accounts whose keychain entry could not be read (e.g. hardware accounts)
+    #[serde(default)]
+    pub accounts_without_keys: Vec<Address>,
+}
+
+/// Decrypted snapshot contents
+#[derive(Serialize, Deserialize)]
+struct SnapshotPayload {
+    accounts: Vec<SecureAccount>,
+    /// Keychain entries keyed by key reference id
+    secrets: HashMap<String, String>,
+}
+
+impl Drop for SnapshotPayload {
+    fn drop(&mut self) {
+        self.secrets.values_mut().for_each(|secret| secret.zeroize());
+    }
+}
+
+/// Registry of recovery points, newest last
+#[derive(Debug)]
+pub struct RecoveryRegistry {
+    dir: PathBuf,
+    points: Vec<RecoveryPoint>,
+}
+
+impl RecoveryRegistry {
+    /// Open the registry in the default recovery directory
+    pub fn open_default() -> Self {
+        Self::open(get_recovery_dir())
+    }
+
+    /// Open the registry in `dir`; a missing or unreadable registry starts empty
+    pub fn open(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let points = match std::fs::read_to_string(dir.join("registry.json")) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid recovery registry in {}: {}", dir.display(), e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { dir, points }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn points(&self) -> &[RecoveryPoint] {
+        &self.points
+    }
+
+    pub fn latest(&self) -> Option<&RecoveryPoint> {
+        self.points.last()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&RecoveryPoint> {
+        self.points.iter().find(|point| point.id == id)
+    }
+
+    /// Snapshot the keystore before `operation` and record the recovery point
+    pub async fn snapshot(
+        &mut self,
+        keystore: &SecureKeystore,
+        password: &SecretString,
+        operation: DestructiveOperation,
+    ) -> Result<RecoveryPoint> {
+        let id = Uuid::new_v4();
+        tracing::info!(recovery_id = %id, "📸 Creating recovery point before {}", operation);
+
+        let accounts = keystore.list_accounts().await?;
+        let mut secrets = HashMap::new();
+        let mut accounts_without_keys = Vec::new();
+        for account in &accounts {
+            match keystore.retrieve(&account.key_reference) {
+                Ok(secret) => {
+                    use secrecy::ExposeSecret;
+                    secrets.insert(account.key_reference.id.clone(), secret.expose_secret().clone());
+                }
+                Err(_) => accounts_without_keys.push(account.address),
+            }
+        }
+        let payload = SnapshotPayload { accounts, secrets };
+        let data = Zeroizing::new(canonical::to_canonical_vec(&payload)?);
+
+        let mut progress = ProgressTracker::start(&NoProgress, ProgressOperation::Backup, 4, "Collecting accounts")
+            .with_operation_id(id);
+        let container = BackupManager::seal(id, &data, password, &mut progress)?;
+        progress.finish("Recovery point created");
+
+        std::fs::create_dir_all(&self.dir)?;
+        let file = self.dir.join(format!("{id}.json"));
+        let contents = container.to_canonical_json()?;
+        write_secure_file(&file.to_string_lossy(), &contents)?;
+
+        let point = RecoveryPoint {
+            id,
+            created_at: Utc::now(),
+            operation,
+            file,
+            account_count: payload.accounts.len(),
+            accounts_without_keys,
+        };
+        self.points.push(point.clone());
+        self.save()?;
+        Ok(point)
+    }
+
+    fn read_snapshot(&self, id: Uuid, password: &SecretString) -> Result<SnapshotPayload> {
+        let point = self
+            .get(id)
+            .ok_or_else(|| VaughanError::NotFound(format!("Recovery point {id}")))?;
+        let contents = std::fs::read_to_string(&point.file)?;
+        let container = BackupContainer::from_json(&contents)?;
+
+        let mut progress = ProgressTracker::start(&NoProgress, ProgressOperation::Restore, 4, "Reading backup");
+        let data = Zeroizing::new(BackupManager::open(&container, password, &mut progress)?);
+        progress.finish("Recovery point opened");
+        serde_json::from_slice(&data).map_err(|e| WalletError::DeserializationError(e.to_string()).into())
+    }
+
+    /// Accounts captured in a recovery point
+    pub fn accounts_in(&self, id: Uuid, password: &SecretString) -> Result<Vec<SecureAccount>> {
+        Ok(self.read_snapshot(id, password)?.accounts.clone())
+    }
+
+    /// Put back every account from a recovery point that is missing from the keystore
+    ///
+    /// Returns the restored addresses. Accounts still present are left untouched.
+    pub async fn restore(
+        &self,
+        id: Uuid,
+        password: &SecretString,
+        keystore: &mut SecureKeystore,
+    ) -> Result<Vec<Address>> {
+        let payload = self.read_snapshot(id, password)?;
+        let existing: Vec<Address> = keystore.list_accounts().await?.iter().map(|a| a.address).collect();
+
+        let mut restored = Vec::new();
+        for account in payload.accounts.iter().filter(|a| !existing.contains(&a.address)) {
+            let secret = payload
+                .secrets
+                .get(&account.key_reference.id)
+                .map(|secret| SecretString::new(secret.clone()));
+            keystore.restore_account(account.clone(), secret).await?;
+            restored.push(account.address);
+        }
+
+        tracing::info!(recovery_id = %id, "♻️ Restored {} accounts from recovery point", restored.len());
+        Ok(restored)
+    }
+
+    /// Delete all but the newest `keep` recovery points; returns how many were removed
+    pub fn prune(&mut self, keep: usize) -> Result<usize> {
+        let excess = self.points.len().saturating_sub(keep);
+        for point in self.points.drain(..excess) {
+            if let Err(e) = std::fs::remove_file(&point.file) {
+                tracing::warn!("Failed to delete recovery snapshot {}: {}", point.file.display(), e);
+            }
+        }
+        self.save()?;
+        Ok(excess)
+    }
+
+    fn save(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let contents = canonical::to_canonical_string(&self.points)?;
+        write_secure_file(&self.dir.join("registry.json").to_string_lossy(), &contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::keychain::MockKeychain;
+    use crate::security::SecureKeystoreImpl;
+
+    #[tokio::test]
+    async fn test_snapshot_and_restore_removed_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut keystore = SecureKeystoreImpl::new(Box::new(MockKeychain::new())).await.unwrap();
+        let account = keystore.create_account("Recoverable".into()).await.unwrap();
+        let password = SecretString::new("recovery password".into());
+
+        let mut registry = RecoveryRegistry::open(dir.path());
+        let operation = DestructiveOperation::RemoveAccount {
+            address: account.address,
+        };
+        let point = registry
+            .snapshot(&keystore, &password, operation.clone())
+            .await
+            .unwrap();
+        assert!(point.file.exists());
+        assert!(point.accounts_without_keys.is_empty());
+
+        keystore.remove_account(account.address).await.unwrap();
+        assert!(keystore.retrieve(&account.key_reference).is_err());
+
+        // The registry survives a reload
+        let registry = RecoveryRegistry::open(dir.path());
+        assert_eq!(registry.latest().map(|p| &p.operation), Some(&operation));
+
+        let restored = registry.restore(point.id, &password, &mut keystore).await.unwrap();
+        assert_eq!(restored, vec![account.address]);
+        assert_eq!(keystore.get_account(account.address).await.unwrap().name, "Recoverable");
+        assert!(keystore.retrieve(&account.key_reference).is_ok());
+
+        // Restoring again is a no-op
+        assert!(registry
+            .restore(point.id, &password, &mut keystore)
+            .await
+            .unwrap()
+            .is_empty());
+        keystore.remove_account(account.address).await.unwrap();
+    }
+
+    #[test]
+    fn test_prune_keeps_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut registry = RecoveryRegistry::open(dir.path());
+        for _ in 0..3 {
+            let id = Uuid::new_v4();
+            let file = dir.path().join(format!("{id}.json"));
+            std::fs::write(&file, "{}").unwrap();
+            registry.points.push(RecoveryPoint {
+                id,
+                created_at: Utc::now(),
+                operation: DestructiveOperation::ChangePassword,
+                file,
+                account_count: 0,
+                accounts_without_keys: Vec::new(),
+            });
+        }
+        let newest = registry.latest().unwrap().clone();
+
+        assert_eq!(registry.prune(1).unwrap(), 2);
+        assert_eq!(RecoveryRegistry::open(dir.path()).points(), std::slice::from_ref(&newest));
+        assert!(newest.file.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+}
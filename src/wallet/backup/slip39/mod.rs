@@ -0,0 +1,585 @@
+//! SLIP-39 Shamir backup shares
+//!
+//! Splits a master secret into mnemonic word lists following
+//! [SLIP-0039](https://github.com/satoshilabs/slips/blob/master/slip-0039.md),
+//! so shares can be written down and restored with Trezor or any other
+//! SLIP-39 tool. Unlike the raw `sharks` shares, each mnemonic carries its
+//! own metadata: a common identifier, group index and threshold, member
+//! index and threshold, and an RS1024 checksum.
+//!
+//! Sharing is two-level: the secret is split across groups, and each group
+//! share across members. Recovery needs `group_threshold` groups, each with
+//! `member_threshold` of its member shares. The master secret is encrypted
+//! with the passphrase (4-round Feistel over PBKDF2-HMAC-SHA256) before
+//! splitting; a wrong passphrase yields a different secret, not an error.
+//!
+//! Trezor accepts 128- and 256-bit master secrets. Shares are generated in
+//! the extendable format; non-extendable shares are recovered as well.
+
+mod wordlist;
+
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use secrecy::{ExposeSecret, SecretString, SecretVec};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
+
+use crate::error::{Result, SecurityError, WalletError};
+use wordlist::WORDLIST;
+
+const RADIX_BITS: usize = 10;
+const ID_LENGTH_BITS: u32 = 15;
+const CHECKSUM_LENGTH_WORDS: usize = 3;
+const METADATA_LENGTH_WORDS: usize = 4 + CHECKSUM_LENGTH_WORDS;
+const MIN_STRENGTH_BYTES: usize = 16;
+const MIN_MNEMONIC_LENGTH_WORDS: usize = METADATA_LENGTH_WORDS + (MIN_STRENGTH_BYTES * 8).div_ceil(RADIX_BITS);
+const MAX_SHARE_COUNT: u8 = 16;
+const DIGEST_LENGTH_BYTES: usize = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const BASE_ITERATION_COUNT: u32 = 10_000;
+const ROUND_COUNT: u8 = 4;
+const CUSTOMIZATION_STRING_ORIG: &[u8] = b"shamir";
+const CUSTOMIZATION_STRING_EXTENDABLE: &[u8] = b"shamir_extendable";
+
+/// Iteration exponent used for new shares (20,000 PBKDF2 iterations)
+pub const DEFAULT_ITERATION_EXPONENT: u8 = 1;
+
+/// Member sharing of one group: `threshold` of `count` shares recover it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slip39Group {
+    pub threshold: u8,
+    pub count: u8,
+}
+
+impl Slip39Group {
+    pub fn new(threshold: u8, count: u8) -> Self {
+        Self { threshold, count }
+    }
+}
+
+/// Metadata embedded in a share mnemonic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slip39ShareInfo {
+    /// Random identifier common to all shares of one secret
+    pub identifier: u16,
+    pub extendable: bool,
+    pub iteration_exponent: u8,
+    pub group_index: u8,
+    pub group_threshold: u8,
+    pub group_count: u8,
+    pub member_index: u8,
+    pub member_threshold: u8,
+}
+
+fn invalid(message: impl Into<String>) -> WalletError {
+    WalletError::DeserializationError(format!("Invalid SLIP-39 share: {}", message.into()))
+}
+
+/// Split `master_secret` into SLIP-39 mnemonics, one list per group
+pub fn generate_mnemonics(
+    master_secret: &[u8],
+    passphrase: &str,
+    group_threshold: u8,
+    groups: &[Slip39Group],
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<SecretString>>> {
+    if master_secret.len() < MIN_STRENGTH_BYTES || !master_secret.len().is_multiple_of(2) {
+        return Err(WalletError::Generic(format!(
+            "Master secret must be an even number of bytes, at least {MIN_STRENGTH_BYTES}"
+        ))
+        .into());
+    }
+    validate_passphrase(passphrase)?;
+    if iteration_exponent > 15 {
+        return Err(WalletError::Generic("Iteration exponent must be at most 15".into()).into());
+    }
+    if groups.is_empty() || groups.len() > MAX_SHARE_COUNT as usize {
+        return Err(WalletError::Generic(format!("Group count must be between 1 and {MAX_SHARE_COUNT}")).into());
+    }
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        return Err(WalletError::Generic("Group threshold cannot be more than the group count".into()).into());
+    }
+    for group in groups {
+        if group.threshold == 0 || group.threshold > group.count || group.count > MAX_SHARE_COUNT {
+            return Err(WalletError::Generic(format!(
+                "Invalid member sharing {}-of-{}; at most {MAX_SHARE_COUNT} shares per group",
+                group.threshold, group.count
+            ))
+            .into());
+        }
+        if group.threshold == 1 && group.count > 1 {
+            return Err(WalletError::Generic("Use 1-of-1 instead of 1-of-N member sharing".into()).into());
+        }
+    }
+
+    let identifier = (rand::thread_rng().next_u32() & ((1 << ID_LENGTH_BITS) - 1)) as u16;
+    let extendable = true;
+    let encrypted = encrypt(master_secret, passphrase, iteration_exponent, identifier, extendable);
+
+    let group_shares = split_secret(group_threshold, groups.len() as u8, &encrypted)?;
+    groups
+        .iter()
+        .zip(group_shares)
+        .map(|(group, (group_index, group_secret))| {
+            split_secret(group.threshold, group.count, &group_secret)?
+                .into_iter()
+                .map(|(member_index, value)| {
+                    let info = Slip39ShareInfo {
+                        identifier,
+                        extendable,
+                        iteration_exponent,
+                        group_index,
+                        group_threshold,
+                        group_count: groups.len() as u8,
+                        member_index,
+                        member_threshold: group.threshold,
+                    };
+                    Ok(SecretString::new(encode_share(&info, &value)))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Recover the master secret from enough SLIP-39 mnemonics
+///
+/// Shares from incomplete groups are ignored as long as enough groups are
+/// complete.
+pub fn combine_mnemonics(mnemonics: &[SecretString], passphrase: &str) -> Result<SecretVec<u8>> {
+    validate_passphrase(passphrase)?;
+    let shares = mnemonics
+        .iter()
+        .map(|m| decode_share(m.expose_secret()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let first = shares.first().ok_or_else(|| invalid("no shares given"))?.0;
+
+    let mut groups: BTreeMap<u8, Vec<&(Slip39ShareInfo, Zeroizing<Vec<u8>>)>> = BTreeMap::new();
+    for share in &shares {
+        let info = &share.0;
+        if (info.identifier, info.extendable, info.iteration_exponent)
+            != (first.identifier, first.extendable, first.iteration_exponent)
+        {
+            return Err(invalid("shares belong to different secrets").into());
+        }
+        if (info.group_threshold, info.group_count) != (first.group_threshold, first.group_count) {
+            return Err(invalid("shares disagree on the group threshold").into());
+        }
+        groups.entry(info.group_index).or_default().push(share);
+    }
+
+    let mut group_secrets: Vec<(u8, Zeroizing<Vec<u8>>)> = Vec::new();
+    for (&group_index, members) in &groups {
+        let member_threshold = members[0].0.member_threshold;
+        if members.iter().any(|s| s.0.member_threshold != member_threshold) {
+            return Err(invalid(format!(
+                "group {} shares disagree on the member threshold",
+                group_index + 1
+            ))
+            .into());
+        }
+        if members.len() < member_threshold as usize {
+            continue;
+        }
+        let member_shares: Vec<(u8, &[u8])> = members
+            .iter()
+            .take(member_threshold as usize)
+            .map(|s| (s.0.member_index, s.1.as_slice()))
+            .collect();
+        group_secrets.push((group_index, recover_secret(member_threshold, &member_shares)?));
+        if group_secrets.len() == first.group_threshold as usize {
+            break;
+        }
+    }
+    if group_secrets.len() < first.group_threshold as usize {
+        return Err(WalletError::Generic(format!(
+            "Not enough shares to recover: {} of {} required groups are complete",
+            group_secrets.len(),
+            first.group_threshold
+        ))
+        .into());
+    }
+
+    let group_shares: Vec<(u8, &[u8])> = group_secrets.iter().map(|(i, s)| (*i, s.as_slice())).collect();
+    let encrypted = recover_secret(first.group_threshold, &group_shares)?;
+    let secret = decrypt(
+        &encrypted,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+    );
+    Ok(SecretVec::new(secret.to_vec()))
+}
+
+/// Read the metadata of a share mnemonic, verifying its checksum
+pub fn share_info(mnemonic: &str) -> Result<Slip39ShareInfo> {
+    Ok(decode_share(mnemonic)?.0)
+}
+
+fn validate_passphrase(passphrase: &str) -> Result<()> {
+    if passphrase.bytes().all(|b| (32..=126).contains(&b)) {
+        Ok(())
+    } else {
+        Err(WalletError::Generic("SLIP-39 passphrase must be printable ASCII".into()).into())
+    }
+}
+
+// === Mnemonic encoding ===
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        CUSTOMIZATION_STRING_EXTENDABLE
+    } else {
+        CUSTOMIZATION_STRING_ORIG
+    }
+}
+
+fn rs1024_polymod(values: impl IntoIterator<Item = u32>) -> u32 {
+    const GEN: [u32; 10] = [
+        0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48, 0x21B1F890, 0x3F3F120,
+    ];
+    let mut chk = 1u32;
+    for value in values {
+        let b = chk >> 20;
+        chk = ((chk & 0xFFFFF) << 10) ^ value;
+        for (i, generator) in GEN.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn with_customization<'a>(extendable: bool, indices: &'a [u16]) -> impl Iterator<Item = u32> + 'a {
+    customization(extendable)
+        .iter()
+        .map(|&b| b as u32)
+        .chain(indices.iter().map(|&i| i as u32))
+}
+
+fn encode_share(info: &Slip39ShareInfo, value: &[u8]) -> String {
+    let id_exp = ((info.identifier as u32) << 5) | ((info.extendable as u32) << 4) | info.iteration_exponent as u32;
+    let mut indices: Zeroizing<Vec<u16>> = Zeroizing::new(vec![
+        (id_exp >> 10) as u16,
+        (id_exp & 0x3FF) as u16,
+        ((info.group_index as u16) << 6)
+            | (((info.group_threshold - 1) as u16) << 2)
+            | ((info.group_count - 1) >> 2) as u16,
+        ((((info.group_count - 1) & 3) as u16) << 8)
+            | ((info.member_index as u16) << 4)
+            | (info.member_threshold - 1) as u16,
+    ]);
+    indices.extend(bytes_to_indices(value).iter());
+
+    let checksum = rs1024_polymod(with_customization(info.extendable, &indices).chain([0; CHECKSUM_LENGTH_WORDS])) ^ 1;
+    indices.extend((0..CHECKSUM_LENGTH_WORDS).map(|i| ((checksum >> (10 * (2 - i))) & 0x3FF) as u16));
+
+    indices
+        .iter()
+        .map(|&i| WORDLIST[i as usize])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decode_share(mnemonic: &str) -> std::result::Result<(Slip39ShareInfo, Zeroizing<Vec<u8>>), WalletError> {
+    let indices: Zeroizing<Vec<u16>> = Zeroizing::new(
+        mnemonic
+            .split_whitespace()
+            .map(|word| {
+                let word = word.to_lowercase();
+                WORDLIST
+                    .binary_search(&word.as_str())
+                    .map(|i| i as u16)
+                    .map_err(|_| invalid(format!("unknown word \"{word}\"")))
+            })
+            .collect::<std::result::Result<_, _>>()?,
+    );
+    if indices.len() < MIN_MNEMONIC_LENGTH_WORDS {
+        return Err(invalid(format!("expected at least {MIN_MNEMONIC_LENGTH_WORDS} words")));
+    }
+    let value_words = indices.len() - METADATA_LENGTH_WORDS;
+    let padding_bits = (RADIX_BITS * value_words) % 16;
+    if padding_bits > 8 {
+        return Err(invalid("wrong number of words"));
+    }
+
+    let id_exp = ((indices[0] as u32) << 10) | indices[1] as u32;
+    let extendable = (id_exp >> 4) & 1 == 1;
+    if rs1024_polymod(with_customization(extendable, &indices)) != 1 {
+        return Err(invalid("checksum mismatch"));
+    }
+
+    let info = Slip39ShareInfo {
+        identifier: (id_exp >> 5) as u16,
+        extendable,
+        iteration_exponent: (id_exp & 0xF) as u8,
+        group_index: (indices[2] >> 6) as u8,
+        group_threshold: ((indices[2] >> 2) & 0xF) as u8 + 1,
+        group_count: ((((indices[2] & 3) << 2) | (indices[3] >> 8)) as u8) + 1,
+        member_index: ((indices[3] >> 4) & 0xF) as u8,
+        member_threshold: (indices[3] & 0xF) as u8 + 1,
+    };
+    if info.group_threshold > info.group_count {
+        return Err(invalid("group threshold is greater than the group count"));
+    }
+
+    let value_bytes = (RADIX_BITS * value_words - padding_bits) / 8;
+    let value = indices_to_bytes(&indices[4..indices.len() - CHECKSUM_LENGTH_WORDS], value_bytes)?;
+    Ok((info, value))
+}
+
+/// Pack bytes into 10-bit word indices, zero padding at the front
+fn bytes_to_indices(value: &[u8]) -> Zeroizing<Vec<u16>> {
+    let word_count = (value.len() * 8).div_ceil(RADIX_BITS);
+    let padding = word_count * RADIX_BITS - value.len() * 8;
+    let bit = |n: usize| -> u16 {
+        if n < padding {
+            0
+        } else {
+            let n = n - padding;
+            ((value[n / 8] >> (7 - n % 8)) & 1) as u16
+        }
+    };
+    Zeroizing::new(
+        (0..word_count)
+            .map(|w| (0..RADIX_BITS).fold(0u16, |acc, b| (acc << 1) | bit(w * RADIX_BITS + b)))
+            .collect(),
+    )
+}
+
+fn indices_to_bytes(indices: &[u16], byte_count: usize) -> std::result::Result<Zeroizing<Vec<u8>>, WalletError> {
+    let padding = indices.len() * RADIX_BITS - byte_count * 8;
+    let bit = |n: usize| (indices[n / RADIX_BITS] >> (RADIX_BITS - 1 - n % RADIX_BITS)) & 1;
+    if (0..padding).any(|n| bit(n) != 0) {
+        return Err(invalid("padding bits are not zero"));
+    }
+    Ok(Zeroizing::new(
+        (0..byte_count)
+            .map(|i| (0..8).fold(0u8, |acc, b| (acc << 1) | bit(padding + i * 8 + b) as u8))
+            .collect(),
+    ))
+}
+
+// === Encryption ===
+
+fn feistel(
+    input: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: impl Iterator<Item = u8>,
+) -> Zeroizing<Vec<u8>> {
+    let half = input.len() / 2;
+    let mut left = Zeroizing::new(input[..half].to_vec());
+    let mut right = Zeroizing::new(input[half..].to_vec());
+    let mut salt = Vec::new();
+    if !extendable {
+        salt.extend_from_slice(CUSTOMIZATION_STRING_ORIG);
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32;
+
+    for round in rounds {
+        let mut password = Zeroizing::new(vec![round]);
+        password.extend_from_slice(passphrase.as_bytes());
+        let mut round_salt = Zeroizing::new(salt.clone());
+        round_salt.extend_from_slice(&right);
+        let mut f = Zeroizing::new(vec![0u8; right.len()]);
+        pbkdf2_hmac::<Sha256>(&password, &round_salt, iterations, &mut f);
+
+        let next_right = Zeroizing::new(left.iter().zip(f.iter()).map(|(l, f)| l ^ f).collect::<Vec<_>>());
+        left = std::mem::replace(&mut right, next_right);
+    }
+
+    let mut output = Zeroizing::new(right.to_vec());
+    output.extend_from_slice(&left);
+    output
+}
+
+fn encrypt(secret: &[u8], passphrase: &str, exponent: u8, identifier: u16, extendable: bool) -> Zeroizing<Vec<u8>> {
+    feistel(secret, passphrase, exponent, identifier, extendable, 0..ROUND_COUNT)
+}
+
+fn decrypt(encrypted: &[u8], passphrase: &str, exponent: u8, identifier: u16, extendable: bool) -> Zeroizing<Vec<u8>> {
+    feistel(
+        encrypted,
+        passphrase,
+        exponent,
+        identifier,
+        extendable,
+        (0..ROUND_COUNT).rev(),
+    )
+}
+
+// === Shamir sharing over GF(256) ===
+
+const fn gf256_tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut poly: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = poly as u8;
+        log[poly as usize] = i as u8;
+        // Multiply by the generator 3 modulo the Rijndael polynomial
+        poly = (poly << 1) ^ poly;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11B;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+const GF256: ([u8; 255], [u8; 256]) = gf256_tables();
+
+fn interpolate(shares: &[(u8, &[u8])], x: u8) -> std::result::Result<Zeroizing<Vec<u8>>, WalletError> {
+    let (exp, log) = (&GF256.0, &GF256.1);
+    for (i, (xi, value)) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|(xj, _)| xj == xi) {
+            return Err(invalid("duplicate share index"));
+        }
+        if value.len() != shares[0].1.len() {
+            return Err(invalid("shares have different lengths"));
+        }
+    }
+    if let Some((_, value)) = shares.iter().find(|(xi, _)| *xi == x) {
+        return Ok(Zeroizing::new(value.to_vec()));
+    }
+
+    let log_prod: i64 = shares.iter().map(|(xi, _)| log[(xi ^ x) as usize] as i64).sum();
+    let mut result = Zeroizing::new(vec![0u8; shares[0].1.len()]);
+    for (xi, value) in shares {
+        let others: i64 = shares.iter().map(|(xj, _)| log[(xi ^ xj) as usize] as i64).sum();
+        let log_basis = (log_prod - log[(xi ^ x) as usize] as i64 - others).rem_euclid(255) as usize;
+        for (out, &v) in result.iter_mut().zip(value.iter()) {
+            if v != 0 {
+                *out ^= exp[(log[v as usize] as usize + log_basis) % 255];
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn digest(random_part: &[u8], secret: &[u8]) -> Result<[u8; DIGEST_LENGTH_BYTES]> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(random_part).map_err(|_| SecurityError::EncryptionError {
+        message: "HMAC init failed".into(),
+    })?;
+    mac.update(secret);
+    let mut out = [0u8; DIGEST_LENGTH_BYTES];
+    out.copy_from_slice(&mac.finalize().into_bytes()[..DIGEST_LENGTH_BYTES]);
+    Ok(out)
+}
+
+fn split_secret(threshold: u8, count: u8, secret: &[u8]) -> Result<Vec<(u8, Zeroizing<Vec<u8>>)>> {
+    if threshold == 1 {
+        return Ok((0..count).map(|i| (i, Zeroizing::new(secret.to_vec()))).collect());
+    }
+
+    let mut rng = rand::thread_rng();
+    let random_share_count = threshold - 2;
+    let mut shares: Vec<(u8, Zeroizing<Vec<u8>>)> = (0..random_share_count)
+        .map(|i| {
+            let mut value = Zeroizing::new(vec![0u8; secret.len()]);
+            rng.fill_bytes(&mut value);
+            (i, value)
+        })
+        .collect();
+
+    let mut digest_share = Zeroizing::new(vec![0u8; secret.len()]);
+    rng.fill_bytes(&mut digest_share[DIGEST_LENGTH_BYTES..]);
+    let checksum = digest(&digest_share[DIGEST_LENGTH_BYTES..], secret)?;
+    digest_share[..DIGEST_LENGTH_BYTES].copy_from_slice(&checksum);
+
+    let mut base: Vec<(u8, &[u8])> = shares.iter().map(|(i, v)| (*i, v.as_slice())).collect();
+    base.push((DIGEST_INDEX, &digest_share));
+    base.push((SECRET_INDEX, secret));
+    let derived = (random_share_count..count)
+        .map(|i| interpolate(&base, i).map(|value| (i, value)))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    shares.extend(derived);
+    Ok(shares)
+}
+
+fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> Result<Zeroizing<Vec<u8>>> {
+    if threshold == 1 {
+        return Ok(Zeroizing::new(shares[0].1.to_vec()));
+    }
+    let secret = interpolate(shares, SECRET_INDEX)?;
+    let digest_share = interpolate(shares, DIGEST_INDEX)?;
+    if digest_share[..DIGEST_LENGTH_BYTES] != digest(&digest_share[DIGEST_LENGTH_BYTES..], &secret)? {
+        return Err(invalid("share digest mismatch; the shares do not belong together").into());
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phrases(words: &[&str]) -> Vec<SecretString> {
+        words.iter().map(|w| SecretString::new(w.to_string())).collect()
+    }
+
+    #[test]
+    fn test_spec_vectors() {
+        // 1-of-1, original (non-extendable) format
+        let secret = combine_mnemonics(
+            &phrases(&["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"]),
+            "TREZOR",
+        )
+        .unwrap();
+        assert_eq!(hex::encode(secret.expose_secret()), "bb54aac4b89dc868ba37d9cc21b2cece");
+
+        // 2-of-3 member shares
+        let secret = combine_mnemonics(
+            &phrases(&[
+                "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+            ]),
+            "TREZOR",
+        )
+        .unwrap();
+        assert_eq!(hex::encode(secret.expose_secret()), "b43ceb7e57a0ea8766221624d01b0864");
+    }
+
+    #[test]
+    fn test_two_level_roundtrip() {
+        let master = hex::decode("0c94ab1a3b2eea1a1b35e1c3a56a8f0b9ee2d7a1c6e3b2f9d8a47c1e0b3f5d2a").unwrap();
+        let groups = [Slip39Group::new(1, 1), Slip39Group::new(2, 3), Slip39Group::new(3, 5)];
+        let shares = generate_mnemonics(&master, "", 2, &groups, 0).unwrap();
+        assert_eq!(shares.iter().map(Vec::len).collect::<Vec<_>>(), vec![1, 3, 5]);
+
+        let info = share_info(shares[1][2].expose_secret()).unwrap();
+        assert_eq!((info.group_index, info.group_threshold, info.group_count), (1, 2, 3));
+        assert_eq!((info.member_index, info.member_threshold), (2, 2));
+        assert!(info.extendable);
+
+        // Group 1 alone plus two members of group 2, with a stray share of group 3
+        let chosen = vec![
+            shares[0][0].clone(),
+            shares[1][2].clone(),
+            shares[1][0].clone(),
+            shares[2][4].clone(),
+        ];
+        assert_eq!(combine_mnemonics(&chosen, "").unwrap().expose_secret(), &master);
+
+        // One complete group is not enough
+        assert!(combine_mnemonics(&[shares[0][0].clone(), shares[1][0].clone()], "").is_err());
+    }
+
+    #[test]
+    fn test_rejects_corrupted_share() {
+        let shares = generate_mnemonics(&[7u8; 16], "", 1, &[Slip39Group::new(1, 1)], 0).unwrap();
+        let mut words: Vec<&str> = shares[0][0].expose_secret().split(' ').collect();
+        words[6] = if words[6] == "academic" { "acid" } else { "academic" };
+        assert!(share_info(&words.join(" ")).is_err());
+        assert!(generate_mnemonics(&[7u8; 15], "", 1, &[Slip39Group::new(1, 1)], 0).is_err());
+        assert!(generate_mnemonics(&[7u8; 16], "", 1, &[Slip39Group::new(1, 3)], 0).is_err());
+    }
+}
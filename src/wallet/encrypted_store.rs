@@ -0,0 +1,136 @@
+//! Encrypted JSON Files
+//!
+//! The note book, the transaction drafts and the CSV batch in progress are
+//! each a JSON value encrypted under the master password, stored together with
+//! the Argon2 parameters it was encrypted with. [`EncryptedJsonStore`] reads,
+//! writes and re-encrypts one such file; the master password change goes
+//! through every store listed in
+//! [`PasswordStores`](crate::wallet::password_change::PasswordStores).
+
+use crate::error::{Result, WalletError};
+use crate::security::keystore::storage;
+use crate::security::{Argon2Params, EncryptedData, WalletConfig};
+use secrecy::SecretString;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// On-disk layout of an encrypted file
+#[derive(Serialize, Deserialize)]
+struct StoredJson {
+    argon2_params: Argon2Params,
+    data: EncryptedData,
+}
+
+/// File holding a JSON value encrypted under the master password
+#[derive(Debug, Clone)]
+pub struct EncryptedJsonStore {
+    path: PathBuf,
+    argon2_params: Argon2Params,
+}
+
+impl EncryptedJsonStore {
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            argon2_params: Argon2Params::default(),
+        }
+    }
+
+    /// Key derivation cost used when saving
+    pub fn with_argon2_params(mut self, params: Argon2Params) -> Self {
+        self.argon2_params = params;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Decrypt the value; none when nothing has been saved yet
+    pub fn load<T: DeserializeOwned>(&self, master_password: &SecretString) -> Result<Option<T>> {
+        let Some(json) = self.read()? else {
+            return Ok(None);
+        };
+        let plaintext = decrypt(&json, master_password)?.1;
+        serde_json::from_slice(&plaintext)
+            .map(Some)
+            .map_err(|e| WalletError::DeserializationError(e.to_string()).into())
+    }
+
+    /// Encrypt and write `value`
+    pub fn save<T: Serialize>(&self, value: &T, master_password: &SecretString) -> Result<()> {
+        let plaintext =
+            Zeroizing::new(serde_json::to_vec(value).map_err(|e| WalletError::SerializationError(e.to_string()))?);
+        self.write_encrypted(&plaintext, master_password, &self.argon2_params)
+    }
+
+    /// Re-encrypt the file under `new_password`, keeping its key derivation cost
+    ///
+    /// Returns the previous file contents for [`Self::restore`], or `None`
+    /// when nothing has been saved yet.
+    pub fn change_password(
+        &self,
+        old_password: &SecretString,
+        new_password: &SecretString,
+    ) -> Result<Option<Zeroizing<String>>> {
+        let Some(previous) = self.read()? else {
+            return Ok(None);
+        };
+        let (argon2_params, plaintext) = decrypt(&previous, old_password)?;
+        self.write_encrypted(&plaintext, new_password, &argon2_params)?;
+        Ok(Some(previous))
+    }
+
+    /// Put back the file contents [`Self::change_password`] replaced
+    pub fn restore(&self, previous: &str) -> Result<()> {
+        self.write(previous)
+    }
+
+    /// Delete the file; nothing to do when it doesn't exist
+    pub fn clear(&self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(WalletError::Generic(format!("Failed to remove {}: {e}", self.path.display())).into()),
+        }
+    }
+
+    fn write_encrypted(&self, plaintext: &[u8], password: &SecretString, argon2_params: &Argon2Params) -> Result<()> {
+        let stored = StoredJson {
+            data: WalletConfig::encrypt_data(plaintext, password, argon2_params)?,
+            argon2_params: argon2_params.clone(),
+        };
+        let json = serde_json::to_string(&stored).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+        self.write(&json)
+    }
+
+    fn read(&self) -> Result<Option<Zeroizing<String>>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(json) => Ok(Some(Zeroizing::new(json))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(WalletError::Generic(format!("Failed to read {}: {e}", self.path.display())).into()),
+        }
+    }
+
+    fn write(&self, json: &str) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| WalletError::Generic(format!("Failed to create {}: {e}", dir.display())))?;
+        }
+        storage::write_secure_file(&self.path.to_string_lossy(), json)
+    }
+}
+
+/// Key derivation parameters and plaintext of a stored file
+fn decrypt(json: &str, password: &SecretString) -> Result<(Argon2Params, Zeroizing<Vec<u8>>)> {
+    let stored: StoredJson =
+        serde_json::from_str(json).map_err(|e| WalletError::DeserializationError(e.to_string()))?;
+    let plaintext = Zeroizing::new(WalletConfig::decrypt_data(
+        &stored.data,
+        password,
+        &stored.argon2_params,
+    )?);
+    Ok((stored.argon2_params, plaintext))
+}
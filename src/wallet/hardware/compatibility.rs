@@ -0,0 +1,517 @@
+//! Hardware wallet firmware and app compatibility
+//!
+//! Compares the firmware (and, for Ledger, Ethereum app) versions reported by
+//! a device against a compatibility matrix of known version ranges:
+//!
+//! - **Block**: the operation is known to fail or to be unsafe on this
+//!   version, so it is refused before the device is asked to sign
+//! - **Warn**: the operation works but has known caveats (e.g. blind signing)
+//!
+//! Every finding carries upgrade guidance that can be shown to the user as is.
+
+use crate::error::{HardwareWalletError, Result};
+use crate::security::hardware::HardwareWalletInfo;
+use crate::wallet::hardware::device_manager::{DeviceType, FirmwareStatus, HardwareDevice};
+use alloy::rpc::types::TransactionRequest;
+use std::fmt;
+
+/// `major.minor.patch` version as reported by a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parse a device version string
+    ///
+    /// Tolerates a leading `v`, missing minor/patch parts and suffixes such as
+    /// `-rc1`, `+` or `-sim`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim().trim_start_matches(['v', 'V']);
+        let mut parts = input.split('.').map(|part| {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse::<u32>().ok()
+        });
+
+        let major = parts.next()??;
+        let minor = parts.next().flatten().unwrap_or(0);
+        let patch = parts.next().flatten().unwrap_or(0);
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Versioned component of a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionComponent {
+    Firmware,
+    /// Ledger Ethereum app
+    EthereumApp,
+}
+
+impl fmt::Display for VersionComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionComponent::Firmware => write!(f, "firmware"),
+            VersionComponent::EthereumApp => write!(f, "Ethereum app"),
+        }
+    }
+}
+
+/// Operation requested from a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardwareOperation {
+    GetAddress,
+    SignLegacyTransaction,
+    SignEip1559Transaction,
+    SignPersonalMessage,
+    SignTypedData,
+}
+
+impl HardwareOperation {
+    /// Signing operation needed for a transaction request
+    pub fn for_transaction(tx: &TransactionRequest) -> Self {
+        if tx.max_fee_per_gas.is_some() || tx.max_priority_fee_per_gas.is_some() {
+            HardwareOperation::SignEip1559Transaction
+        } else {
+            HardwareOperation::SignLegacyTransaction
+        }
+    }
+}
+
+/// How a matching rule affects an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warn,
+    Block,
+}
+
+/// Entry of the compatibility matrix
+///
+/// Applies to versions in `[from, below)` of `component` on `device`, for the
+/// listed operations (all operations when empty).
+#[derive(Debug, Clone, Copy)]
+pub struct CompatibilityRule {
+    pub device: DeviceType,
+    pub component: VersionComponent,
+    pub from: Version,
+    pub below: Version,
+    pub operations: &'static [HardwareOperation],
+    pub severity: Severity,
+    pub issue: &'static str,
+    pub guidance: &'static str,
+}
+
+impl CompatibilityRule {
+    fn applies(
+        &self,
+        device: DeviceType,
+        component: VersionComponent,
+        version: Version,
+        op: HardwareOperation,
+    ) -> bool {
+        self.device == device
+            && self.component == component
+            && (self.from..self.below).contains(&version)
+            && (self.operations.is_empty() || self.operations.contains(&op))
+    }
+}
+
+const LEDGER_FIRMWARE_GUIDANCE: &str = "Update the device firmware with Ledger Live (My Ledger → Firmware update).";
+const LEDGER_APP_GUIDANCE: &str = "Update the Ethereum app with Ledger Live (My Ledger → Apps installed → Update).";
+const TREZOR_GUIDANCE: &str = "Update the device firmware with Trezor Suite (Settings → Device → Firmware).";
+
+/// Known version ranges with problems
+pub static COMPATIBILITY_MATRIX: &[CompatibilityRule] = &[
+    CompatibilityRule {
+        device: DeviceType::Ledger,
+        component: VersionComponent::Firmware,
+        from: Version::new(0, 0, 0),
+        below: Version::new(2, 0, 0),
+        operations: &[],
+        severity: Severity::Block,
+        issue: "Ledger firmware older than 2.0 is not supported by current Ethereum app releases",
+        guidance: LEDGER_FIRMWARE_GUIDANCE,
+    },
+    CompatibilityRule {
+        device: DeviceType::Ledger,
+        component: VersionComponent::EthereumApp,
+        from: Version::new(0, 0, 0),
+        below: Version::new(1, 9, 0),
+        operations: &[HardwareOperation::SignEip1559Transaction],
+        severity: Severity::Block,
+        issue: "Ethereum app versions before 1.9.0 cannot sign EIP-1559 transactions",
+        guidance: LEDGER_APP_GUIDANCE,
+    },
+    CompatibilityRule {
+        device: DeviceType::Ledger,
+        component: VersionComponent::EthereumApp,
+        from: Version::new(0, 0, 0),
+        below: Version::new(1, 5, 0),
+        operations: &[HardwareOperation::SignTypedData],
+        severity: Severity::Block,
+        issue: "Ethereum app versions before 1.5.0 cannot sign EIP-712 typed data",
+        guidance: LEDGER_APP_GUIDANCE,
+    },
+    CompatibilityRule {
+        device: DeviceType::Ledger,
+        component: VersionComponent::EthereumApp,
+        from: Version::new(1, 5, 0),
+        below: Version::new(1, 10, 0),
+        operations: &[HardwareOperation::SignTypedData],
+        severity: Severity::Warn,
+        issue: "This Ethereum app version only shows EIP-712 message hashes, so the message cannot be reviewed on the device",
+        guidance: LEDGER_APP_GUIDANCE,
+    },
+    CompatibilityRule {
+        device: DeviceType::Trezor,
+        component: VersionComponent::Firmware,
+        from: Version::new(1, 0, 0),
+        below: Version::new(1, 10, 4),
+        operations: &[HardwareOperation::SignEip1559Transaction],
+        severity: Severity::Block,
+        issue: "Trezor One firmware before 1.10.4 cannot sign EIP-1559 transactions",
+        guidance: TREZOR_GUIDANCE,
+    },
+    CompatibilityRule {
+        device: DeviceType::Trezor,
+        component: VersionComponent::Firmware,
+        from: Version::new(1, 0, 0),
+        below: Version::new(1, 10, 5),
+        operations: &[HardwareOperation::SignTypedData],
+        severity: Severity::Block,
+        issue: "Trezor One firmware before 1.10.5 cannot sign EIP-712 typed data",
+        guidance: TREZOR_GUIDANCE,
+    },
+    CompatibilityRule {
+        device: DeviceType::Trezor,
+        component: VersionComponent::Firmware,
+        from: Version::new(2, 0, 0),
+        below: Version::new(2, 4, 2),
+        operations: &[HardwareOperation::SignEip1559Transaction],
+        severity: Severity::Block,
+        issue: "Trezor Model T firmware before 2.4.2 cannot sign EIP-1559 transactions",
+        guidance: TREZOR_GUIDANCE,
+    },
+    CompatibilityRule {
+        device: DeviceType::Trezor,
+        component: VersionComponent::Firmware,
+        from: Version::new(2, 0, 0),
+        below: Version::new(2, 4, 3),
+        operations: &[HardwareOperation::SignTypedData],
+        severity: Severity::Block,
+        issue: "Trezor Model T firmware before 2.4.3 cannot sign EIP-712 typed data",
+        guidance: TREZOR_GUIDANCE,
+    },
+];
+
+/// Minimum known-good versions per device and component; matched by major
+/// version since Trezor One (1.x) and Model T (2.x) have separate lines
+const RECOMMENDED_VERSIONS: &[(DeviceType, VersionComponent, Version)] = &[
+    (DeviceType::Ledger, VersionComponent::Firmware, Version::new(2, 1, 0)),
+    (
+        DeviceType::Ledger,
+        VersionComponent::EthereumApp,
+        Version::new(1, 10, 0),
+    ),
+    (DeviceType::Trezor, VersionComponent::Firmware, Version::new(1, 10, 5)),
+    (DeviceType::Trezor, VersionComponent::Firmware, Version::new(2, 4, 3)),
+];
+
+fn recommended(device: DeviceType, component: VersionComponent, version: Version) -> Option<Version> {
+    RECOMMENDED_VERSIONS
+        .iter()
+        .filter(|(d, c, _)| *d == device && *c == component)
+        .map(|(_, _, v)| *v)
+        .find(|v| v.major == version.major)
+}
+
+/// Versions reported by a device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceVersions {
+    pub device_type: DeviceType,
+    pub firmware: Option<Version>,
+    /// Ethereum app version (Ledger only)
+    pub app: Option<Version>,
+}
+
+impl DeviceVersions {
+    pub fn from_device(device: &HardwareDevice) -> Self {
+        Self {
+            device_type: device.device_type,
+            firmware: device.firmware_version.as_deref().and_then(Version::parse),
+            app: device.app_version.as_deref().and_then(Version::parse),
+        }
+    }
+
+    /// From security-layer device info; `None` for unrecognized device types
+    pub fn from_info(info: &HardwareWalletInfo) -> Option<Self> {
+        let device_type = if info.device_type.starts_with("Ledger") {
+            DeviceType::Ledger
+        } else if info.device_type.starts_with("Trezor") {
+            DeviceType::Trezor
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            device_type,
+            firmware: Version::parse(&info.firmware_version),
+            app: None,
+        })
+    }
+
+    fn component(&self, component: VersionComponent) -> Option<Version> {
+        match component {
+            VersionComponent::Firmware => self.firmware,
+            VersionComponent::EthereumApp => self.app,
+        }
+    }
+}
+
+/// A matrix rule matching a device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatibilityFinding {
+    pub severity: Severity,
+    pub component: VersionComponent,
+    /// Detected version, `None` if it could not be determined
+    pub version: Option<Version>,
+    pub issue: String,
+    pub guidance: String,
+}
+
+impl fmt::Display for CompatibilityFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}. {}", self.issue, self.guidance)
+    }
+}
+
+/// Result of checking a device for an operation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompatibilityReport {
+    pub findings: Vec<CompatibilityFinding>,
+}
+
+impl CompatibilityReport {
+    pub fn is_blocked(&self) -> bool {
+        self.findings.iter().any(|f| f.severity == Severity::Block)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &CompatibilityFinding> {
+        self.findings.iter().filter(|f| f.severity == Severity::Warn)
+    }
+
+    /// Error for the first blocking finding, if any; warnings are logged
+    pub fn ensure_allowed(&self) -> Result<()> {
+        for warning in self.warnings() {
+            tracing::warn!("⚠️ Hardware wallet compatibility: {}", warning);
+        }
+
+        match self.findings.iter().find(|f| f.severity == Severity::Block) {
+            Some(block) => Err(HardwareWalletError::IncompatibleVersion {
+                reason: block.issue.clone(),
+                guidance: block.guidance.clone(),
+            }
+            .into()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Check a device against [`COMPATIBILITY_MATRIX`] for an operation
+pub fn check(versions: &DeviceVersions, operation: HardwareOperation) -> CompatibilityReport {
+    check_with(COMPATIBILITY_MATRIX, versions, operation)
+}
+
+/// Check a device against a custom matrix
+pub fn check_with(
+    matrix: &[CompatibilityRule],
+    versions: &DeviceVersions,
+    operation: HardwareOperation,
+) -> CompatibilityReport {
+    let mut findings = Vec::new();
+
+    let Some(firmware) = versions.firmware else {
+        findings.push(CompatibilityFinding {
+            severity: Severity::Warn,
+            component: VersionComponent::Firmware,
+            version: None,
+            issue: "The device firmware version could not be determined, so compatibility is unverified".to_string(),
+            guidance: "Make sure the device is unlocked and up to date.".to_string(),
+        });
+        return CompatibilityReport { findings };
+    };
+
+    for component in [VersionComponent::Firmware, VersionComponent::EthereumApp] {
+        let Some(version) = versions.component(component) else {
+            continue;
+        };
+        findings.extend(
+            matrix
+                .iter()
+                .filter(|rule| rule.applies(versions.device_type, component, version, operation))
+                .map(|rule| CompatibilityFinding {
+                    severity: rule.severity,
+                    component,
+                    version: Some(version),
+                    issue: format!("{} (detected {component} {version})", rule.issue),
+                    guidance: rule.guidance.to_string(),
+                }),
+        );
+    }
+
+    tracing::debug!(
+        "🔍 {} firmware {} checked for {:?}: {} finding(s)",
+        versions.device_type,
+        firmware,
+        operation,
+        findings.len()
+    );
+
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    CompatibilityReport { findings }
+}
+
+/// Firmware status of a device according to the matrix
+pub fn firmware_status(versions: &DeviceVersions) -> FirmwareStatus {
+    let Some(firmware) = versions.firmware else {
+        return FirmwareStatus::Unknown;
+    };
+
+    // Blocked for every operation: below the supported minimum
+    if let Some(rule) = COMPATIBILITY_MATRIX.iter().find(|rule| {
+        rule.operations.is_empty()
+            && rule.severity == Severity::Block
+            && rule.applies(
+                versions.device_type,
+                VersionComponent::Firmware,
+                firmware,
+                HardwareOperation::GetAddress,
+            )
+    }) {
+        return FirmwareStatus::Outdated {
+            current: firmware.to_string(),
+            minimum: rule.below.to_string(),
+        };
+    }
+
+    match recommended(versions.device_type, VersionComponent::Firmware, firmware) {
+        Some(recommended) if firmware < recommended => FirmwareStatus::UpdateAvailable {
+            current: firmware.to_string(),
+            available: recommended.to_string(),
+        },
+        _ => FirmwareStatus::UpToDate,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(firmware: &str, app: &str) -> DeviceVersions {
+        DeviceVersions {
+            device_type: DeviceType::Ledger,
+            firmware: Version::parse(firmware),
+            app: Version::parse(app),
+        }
+    }
+
+    fn trezor(firmware: &str) -> DeviceVersions {
+        DeviceVersions {
+            device_type: DeviceType::Trezor,
+            firmware: Version::parse(firmware),
+            app: None,
+        }
+    }
+
+    #[test]
+    fn test_version_parsing() {
+        assert_eq!(Version::parse("2.1.0+"), Some(Version::new(2, 1, 0)));
+        assert_eq!(Version::parse("v1.10.4-rc1"), Some(Version::new(1, 10, 4)));
+        assert_eq!(Version::parse("2.4"), Some(Version::new(2, 4, 0)));
+        assert_eq!(Version::parse("unknown"), None);
+        assert!(Version::new(1, 10, 0) > Version::new(1, 9, 19));
+    }
+
+    #[test]
+    fn test_old_ledger_app_blocks_eip1559_only() {
+        let device = ledger("2.1.0", "1.8.5");
+
+        let report = check(&device, HardwareOperation::SignEip1559Transaction);
+        assert!(report.is_blocked());
+        let error = report.ensure_allowed().unwrap_err().to_string();
+        assert!(error.contains("1.9.0"));
+        assert!(error.contains("Ledger Live"));
+
+        assert!(!check(&device, HardwareOperation::SignLegacyTransaction).is_blocked());
+    }
+
+    #[test]
+    fn test_typed_data_warning_without_block() {
+        let report = check(&ledger("2.1.0", "1.9.5"), HardwareOperation::SignTypedData);
+        assert!(!report.is_blocked());
+        assert_eq!(report.warnings().count(), 1);
+        assert!(report.ensure_allowed().is_ok());
+
+        assert!(check(&ledger("2.2.0", "1.11.0"), HardwareOperation::SignTypedData)
+            .findings
+            .is_empty());
+    }
+
+    #[test]
+    fn test_trezor_lines_are_checked_separately() {
+        let op = HardwareOperation::SignEip1559Transaction;
+        assert!(check(&trezor("1.10.3"), op).is_blocked());
+        assert!(!check(&trezor("1.10.4"), op).is_blocked());
+        assert!(check(&trezor("2.4.1"), op).is_blocked());
+        assert!(!check(&trezor("2.5.3"), op).is_blocked());
+    }
+
+    #[test]
+    fn test_unknown_firmware_warns() {
+        let report = check(&trezor("unknown"), HardwareOperation::SignLegacyTransaction);
+        assert!(!report.is_blocked());
+        assert_eq!(report.warnings().count(), 1);
+    }
+
+    #[test]
+    fn test_firmware_status() {
+        assert!(matches!(
+            firmware_status(&ledger("1.6.1", "")),
+            FirmwareStatus::Outdated { minimum, .. } if minimum == "2.0.0"
+        ));
+        assert!(matches!(
+            firmware_status(&trezor("2.4.2")),
+            FirmwareStatus::UpdateAvailable { available, .. } if available == "2.4.3"
+        ));
+        assert_eq!(firmware_status(&trezor("1.12.1")), FirmwareStatus::UpToDate);
+        assert_eq!(firmware_status(&trezor("")), FirmwareStatus::Unknown);
+    }
+
+    #[test]
+    fn test_operation_for_transaction() {
+        let legacy = TransactionRequest::default().gas_price(1);
+        let eip1559 = TransactionRequest::default()
+            .max_fee_per_gas(2)
+            .max_priority_fee_per_gas(1);
+        assert_eq!(
+            HardwareOperation::for_transaction(&legacy),
+            HardwareOperation::SignLegacyTransaction
+        );
+        assert_eq!(
+            HardwareOperation::for_transaction(&eip1559),
+            HardwareOperation::SignEip1559Transaction
+        );
+    }
+}
@@ -38,6 +38,10 @@ pub enum DerivationPathError {
     MissingPurpose,
     /// Missing Ethereum coin type (60')
     MissingCoinType,
+    /// Index does not fit below the hardened offset (2^31)
+    IndexOutOfRange(String),
+    /// The account level must be hardened
+    NotHardened(String),
 }
 
 impl fmt::Display for DerivationPathError {
@@ -50,6 +54,8 @@ impl fmt::Display for DerivationPathError {
             Self::TooLong => write!(f, "Path is too long, maximum 10 components"),
             Self::MissingPurpose => write!(f, "Path must include purpose 44'"),
             Self::MissingCoinType => write!(f, "Path must include Ethereum coin type 60'"),
+            Self::IndexOutOfRange(s) => write!(f, "Path index out of range: '{}'", s),
+            Self::NotHardened(s) => write!(f, "Account level must be hardened: '{}'", s),
         }
     }
 }
@@ -119,6 +125,11 @@ impl DerivationStandard {
         }
     }
 
+    /// Concrete path for an account index (custom paths are returned unchanged)
+    pub fn path_for_index(&self, index: u32) -> String {
+        self.path_template().replace("{index}", &index.to_string())
+    }
+
     /// Check if this is a custom path
     pub fn is_custom(&self) -> bool {
         matches!(self, Self::Custom(_))
@@ -256,6 +267,17 @@ mod tests {
         assert_eq!(custom.path_template(), "m/44'/60'/1'/0/5");
     }
 
+    #[test]
+    fn test_path_for_index() {
+        assert_eq!(DerivationStandard::Bip44.path_for_index(3), "m/44'/60'/0'/0/3");
+        assert_eq!(DerivationStandard::LedgerLive.path_for_index(3), "m/44'/60'/3'/0/0");
+        assert_eq!(DerivationStandard::Legacy.path_for_index(3), "m/44'/60'/0'/3");
+        assert_eq!(
+            DerivationStandard::Custom("m/44'/60'/1'/0/5".into()).path_for_index(3),
+            "m/44'/60'/1'/0/5"
+        );
+    }
+
     #[test]
     fn test_is_custom() {
         assert!(!DerivationStandard::Bip44.is_custom());
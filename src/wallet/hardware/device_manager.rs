@@ -42,6 +42,7 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::error::Result;
+use crate::wallet::hardware::compatibility::{self, CompatibilityReport, DeviceVersions, HardwareOperation};
 
 /// Unique identifier for a hardware device
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -66,7 +67,7 @@ impl std::fmt::Display for DeviceId {
 }
 
 /// Type of hardware device
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DeviceType {
     /// Ledger hardware wallet
     Ledger,
@@ -122,6 +123,8 @@ pub struct HardwareDevice {
     pub model: String,
     /// Current firmware version
     pub firmware_version: Option<String>,
+    /// Ethereum app version (Ledger only)
+    pub app_version: Option<String>,
     /// Connection status
     pub connection_status: ConnectionStatus,
     /// Firmware status
@@ -147,6 +150,7 @@ impl HardwareDevice {
             device_type,
             model: model.into(),
             firmware_version: None,
+            app_version: None,
             connection_status: ConnectionStatus::Connecting,
             firmware_status: FirmwareStatus::Unknown,
             addresses: Vec::new(),
@@ -170,6 +174,13 @@ impl HardwareDevice {
     /// Set firmware version
     pub fn with_firmware(mut self, version: impl Into<String>) -> Self {
         self.firmware_version = Some(version.into());
+        self.firmware_status = compatibility::firmware_status(&DeviceVersions::from_device(&self));
+        self
+    }
+
+    /// Set Ethereum app version
+    pub fn with_app_version(mut self, version: impl Into<String>) -> Self {
+        self.app_version = Some(version.into());
         self
     }
 
@@ -501,7 +512,7 @@ impl DeviceManager {
         let registry = self.registry.read().await;
 
         if let Some(device) = registry.get(device_id) {
-            let device_type = device.device_type.clone();
+            let device_type = device.device_type;
             drop(registry);
 
             // Attempt reconnection based on device type
@@ -614,6 +625,21 @@ impl DeviceManager {
         registry.get(device_id).map(|d| d.firmware_status.clone())
     }
 
+    /// Check a device's firmware/app versions against the compatibility matrix
+    ///
+    /// Returns `None` for unknown devices. Use
+    /// [`CompatibilityReport::ensure_allowed`] to refuse blocked operations.
+    pub async fn check_compatibility(
+        &self,
+        device_id: &DeviceId,
+        operation: HardwareOperation,
+    ) -> Option<CompatibilityReport> {
+        let registry = self.registry.read().await;
+        registry
+            .get(device_id)
+            .map(|d| compatibility::check(&DeviceVersions::from_device(d), operation))
+    }
+
     /// Get firmware upgrade guidance
     pub fn get_firmware_upgrade_guidance(&self, status: &FirmwareStatus) -> Option<String> {
         match status {
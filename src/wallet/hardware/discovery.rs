@@ -0,0 +1,234 @@
+//! Hardware account discovery across derivation standards
+//!
+//! Ledger devices have been used with several path layouts over the years:
+//! Ledger Live (`m/44'/60'/x'/0/0`), legacy MEW (`m/44'/60'/0'/x`) and plain
+//! BIP-44 (`m/44'/60'/0'/0/x`). This module derives the first few addresses of
+//! each layout, looks up their balance and nonce through the [`NetworkManager`],
+//! and returns a ranked list so the user can pick which accounts to import.
+
+use super::derivation::DerivationStandard;
+use crate::error::Result;
+use crate::network::NetworkManager;
+use alloy::primitives::{Address, U256};
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use std::collections::HashSet;
+
+/// Derives addresses on a hardware device
+#[async_trait]
+pub trait HardwareAddressSource: Send + Sync {
+    async fn address_at(&self, derivation_path: &str) -> Result<Address>;
+}
+
+#[cfg(feature = "hardware-wallets")]
+#[async_trait]
+impl HardwareAddressSource for crate::security::hardware::LedgerWallet {
+    async fn address_at(&self, derivation_path: &str) -> Result<Address> {
+        self.get_address_at(derivation_path).await
+    }
+}
+
+/// Balance and nonce lookups for discovered addresses
+#[async_trait]
+pub trait AddressActivity: Send + Sync {
+    async fn balance_and_nonce(&self, address: Address) -> Result<(U256, u64)>;
+}
+
+#[async_trait]
+impl AddressActivity for NetworkManager {
+    async fn balance_and_nonce(&self, address: Address) -> Result<(U256, u64)> {
+        let balance = self.get_balance(address, None).await?;
+        let nonce = self.get_transaction_count(address).await?;
+        Ok((balance, nonce))
+    }
+}
+
+/// Configuration for a hardware account scan
+#[derive(Debug, Clone)]
+pub struct HardwareDiscoveryConfig {
+    /// Standards to scan, in order of preference for ties
+    pub standards: Vec<DerivationStandard>,
+    /// Account indexes scanned per standard
+    pub accounts_per_standard: u32,
+}
+
+impl Default for HardwareDiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            standards: vec![
+                DerivationStandard::LedgerLive,
+                DerivationStandard::Legacy,
+                DerivationStandard::Bip44,
+            ],
+            accounts_per_standard: 5,
+        }
+    }
+}
+
+/// An address found on the device, with its on-chain activity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardwareAccountCandidate {
+    pub address: Address,
+    pub standard: DerivationStandard,
+    pub index: u32,
+    pub derivation_path: String,
+    pub balance: U256,
+    pub nonce: u64,
+}
+
+impl HardwareAccountCandidate {
+    /// Whether the account has ever been used
+    pub fn is_active(&self) -> bool {
+        self.balance > U256::ZERO || self.nonce > 0
+    }
+}
+
+/// Scan a hardware device and rank the accounts found
+///
+/// Paths shared by several standards (e.g. index 0 of Ledger Live and BIP-44)
+/// are only derived once. Active accounts come first, ordered by balance and
+/// then nonce; ties keep the configured standard order and index order.
+pub async fn discover_hardware_accounts(
+    device: &dyn HardwareAddressSource,
+    activity: &dyn AddressActivity,
+    config: &HardwareDiscoveryConfig,
+) -> Result<Vec<HardwareAccountCandidate>> {
+    let mut seen_paths = HashSet::new();
+    let mut derived = Vec::new();
+    for (rank, standard) in config.standards.iter().enumerate() {
+        for index in 0..config.accounts_per_standard {
+            let path = standard.path_for_index(index);
+            if !seen_paths.insert(path.clone()) {
+                continue;
+            }
+            let address = device.address_at(&path).await?;
+            derived.push((rank, standard.clone(), index, path, address));
+            if standard.is_custom() {
+                break;
+            }
+        }
+    }
+    tracing::info!("🔍 Derived {} hardware addresses, checking activity", derived.len());
+
+    let lookups = join_all(derived.iter().map(|(.., address)| activity.balance_and_nonce(*address))).await;
+
+    let mut ranked = Vec::with_capacity(derived.len());
+    for ((rank, standard, index, derivation_path, address), lookup) in derived.into_iter().zip(lookups) {
+        let (balance, nonce) = lookup?;
+        ranked.push((
+            rank,
+            HardwareAccountCandidate {
+                address,
+                standard,
+                index,
+                derivation_path,
+                balance,
+                nonce,
+            },
+        ));
+    }
+
+    ranked.sort_by(|(rank_a, a), (rank_b, b)| {
+        b.is_active()
+            .cmp(&a.is_active())
+            .then(b.balance.cmp(&a.balance))
+            .then(b.nonce.cmp(&a.nonce))
+            .then(rank_a.cmp(rank_b))
+            .then(a.index.cmp(&b.index))
+    });
+    Ok(ranked.into_iter().map(|(_, candidate)| candidate).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{HardwareWalletError, VaughanError};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Device whose addresses are derived from the path text
+    #[derive(Default)]
+    struct FakeDevice {
+        requested: Mutex<Vec<String>>,
+    }
+
+    fn address_for(path: &str) -> Address {
+        Address::from_word(alloy::primitives::keccak256(path.as_bytes()))
+    }
+
+    #[async_trait]
+    impl HardwareAddressSource for FakeDevice {
+        async fn address_at(&self, derivation_path: &str) -> Result<Address> {
+            self.requested.lock().unwrap().push(derivation_path.to_string());
+            Ok(address_for(derivation_path))
+        }
+    }
+
+    struct FakeChain(HashMap<Address, (U256, u64)>);
+
+    #[async_trait]
+    impl AddressActivity for FakeChain {
+        async fn balance_and_nonce(&self, address: Address) -> Result<(U256, u64)> {
+            Ok(self.0.get(&address).copied().unwrap_or_default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scans_all_standards_and_ranks_activity() {
+        let device = FakeDevice::default();
+        let chain = FakeChain(HashMap::from([
+            (address_for("m/44'/60'/0'/2"), (U256::from(5), 1)),
+            (address_for("m/44'/60'/1'/0/0"), (U256::from(100), 4)),
+            (address_for("m/44'/60'/0'/0/1"), (U256::ZERO, 7)),
+        ]));
+        let config = HardwareDiscoveryConfig {
+            accounts_per_standard: 3,
+            ..Default::default()
+        };
+
+        let candidates = discover_hardware_accounts(&device, &chain, &config).await.unwrap();
+
+        // m/44'/60'/0'/0/0 is shared by Ledger Live and BIP-44
+        assert_eq!(device.requested.lock().unwrap().len(), 8);
+        assert_eq!(candidates.len(), 8);
+
+        let top: Vec<_> = candidates.iter().take(4).map(|c| c.derivation_path.as_str()).collect();
+        assert_eq!(
+            top,
+            vec![
+                "m/44'/60'/1'/0/0",
+                "m/44'/60'/0'/2",
+                "m/44'/60'/0'/0/1",
+                "m/44'/60'/0'/0/0"
+            ]
+        );
+        assert_eq!(candidates[0].standard, DerivationStandard::LedgerLive);
+        assert_eq!(candidates[1].standard, DerivationStandard::Legacy);
+        assert_eq!(candidates[2].standard, DerivationStandard::Bip44);
+        assert!(candidates[..3].iter().all(|c| c.is_active()));
+        assert!(candidates[3..].iter().all(|c| !c.is_active()));
+    }
+
+    #[tokio::test]
+    async fn test_device_errors_abort_the_scan() {
+        struct Unplugged;
+
+        #[async_trait]
+        impl HardwareAddressSource for Unplugged {
+            async fn address_at(&self, _derivation_path: &str) -> Result<Address> {
+                Err(HardwareWalletError::DeviceNotFound.into())
+            }
+        }
+
+        let result = discover_hardware_accounts(
+            &Unplugged,
+            &FakeChain(HashMap::new()),
+            &HardwareDiscoveryConfig::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(VaughanError::HardwareWallet(HardwareWalletError::DeviceNotFound))
+        ));
+    }
+}
@@ -47,6 +47,7 @@ use tokio::sync::RwLock;
 use crate::error::{HardwareWalletError, Result};
 
 use crate::security::hardware::HardwareWalletInfo;
+use crate::wallet::hardware::compatibility::{self, DeviceVersions, HardwareOperation};
 #[cfg(feature = "hardware-wallets")]
 use crate::security::hardware::HardwareWallet;
 #[cfg(feature = "hardware-wallets")]
@@ -77,6 +78,8 @@ pub struct TransactionAuditFeedback {
     pub security_warnings: Vec<String>,
     pub recommendations: Vec<String>,
     pub risk_level: RiskLevel,
+    /// Decoded calldata to compare against the device screen
+    pub call_preview: Option<String>,
 }
 
 /// Device recovery feedback
@@ -237,6 +240,12 @@ impl HardwareManager {
         tx: &TransactionRequest,
         derivation_path: &str,
     ) -> Result<Signature> {
+        // Refuse operations the device's firmware is known to fail
+        let info = self.get_device_info(device_index).await.ok();
+        if let Some(versions) = info.as_ref().and_then(DeviceVersions::from_info) {
+            compatibility::check(&versions, HardwareOperation::for_transaction(tx)).ensure_allowed()?;
+        }
+
         #[cfg(feature = "hardware-wallets")]
         {
             let security_manager = self.security_manager.read().await;
@@ -389,6 +398,7 @@ impl HardwareManager {
             tracing::info!("🔍 Starting comprehensive transaction audit");
 
             let start_time = std::time::Instant::now();
+            let call_preview = crate::abi::describe_transaction(tx);
 
             // Get device type
             let device_info = self.get_device_info(device_index).await?;
@@ -402,18 +412,23 @@ impl HardwareManager {
 
             match audit_result {
                 Ok(()) => {
+                    let mut recommendations = vec![
+                        "Review transaction details on hardware device".to_string(),
+                        "Verify recipient address matches intended destination".to_string(),
+                        "Confirm transaction amount is correct".to_string(),
+                    ];
+                    if let Some(preview) = &call_preview {
+                        recommendations.push(format!("Check the device shows the call {preview}"));
+                    }
                     let feedback = TransactionAuditFeedback {
                         passed: true,
                         device_type: device_info.device_type.clone(),
                         duration_ms: duration.as_millis() as u64,
                         user_message: "✅ Transaction passed all security checks".to_string(),
                         security_warnings: Vec::new(),
-                        recommendations: vec![
-                            "Review transaction details on hardware device".to_string(),
-                            "Verify recipient address matches intended destination".to_string(),
-                            "Confirm transaction amount is correct".to_string(),
-                        ],
+                        recommendations,
                         risk_level: RiskLevel::Low,
+                        call_preview,
                     };
 
                     tracing::info!("✅ Transaction audit passed in {}ms", duration.as_millis());
@@ -433,6 +448,7 @@ impl HardwareManager {
                             "Contact support if this appears to be an error".to_string(),
                         ],
                         risk_level: RiskLevel::High,
+                        call_preview,
                     };
 
                     tracing::error!("❌ Transaction audit failed after {}ms: {}", duration.as_millis(), e);
@@ -450,6 +466,7 @@ impl HardwareManager {
                 security_warnings: vec!["Feature disabled".to_string()],
                 recommendations: vec!["Enable feature in Cargo.toml".to_string()],
                 risk_level: RiskLevel::Critical,
+                call_preview: crate::abi::describe_transaction(tx),
             })       
         }
     }
@@ -3,10 +3,13 @@
 //! This module provides comprehensive hardware wallet support including
 //! device management for multiple simultaneous connections.
 
+pub mod compatibility;
 pub mod device_manager;
+pub mod discovery;
 pub mod manager;
 pub mod derivation;
 
 pub use device_manager::*;
+pub use discovery::*;
 pub use manager::*;
 pub use derivation::*;
@@ -10,6 +10,7 @@ use std::collections::HashMap;
 
 use crate::error::{Result, WalletError};
 use crate::security::{SecureAccount, SecureExport, SecureKeystore};
+use crate::wallet::backup::{DestructiveOperation, RecoveryPoint, RecoveryRegistry};
 
 /// Wallet keystore configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +38,7 @@ pub struct WalletKeystore {
     config: KeystoreConfig,
     last_activity: std::time::Instant,
     backup_metadata: HashMap<Address, BackupMetadata>,
+    recovery: RecoveryRegistry,
 }
 
 /// Backup metadata for accounts
@@ -55,9 +57,44 @@ impl WalletKeystore {
             config,
             last_activity: std::time::Instant::now(),
             backup_metadata: HashMap::new(),
+            recovery: RecoveryRegistry::open_default(),
         })
     }
 
+    /// Use a different recovery registry for pre-operation snapshots
+    pub fn with_recovery_registry(mut self, registry: RecoveryRegistry) -> Self {
+        self.recovery = registry;
+        self
+    }
+
+    /// Recovery points taken before destructive operations
+    pub fn recovery_registry(&self) -> &RecoveryRegistry {
+        &self.recovery
+    }
+
+    /// Snapshot the keystore before a destructive operation
+    ///
+    /// Returns `None` when backups are disabled in the keystore config. Callers
+    /// changing passwords or migrating storage call this before they start.
+    pub async fn create_recovery_point(
+        &mut self,
+        operation: DestructiveOperation,
+        password: &SecretString,
+    ) -> Result<Option<RecoveryPoint>> {
+        if !self.config.backup_enabled {
+            tracing::warn!("Backups disabled - no recovery point before {}", operation);
+            return Ok(None);
+        }
+        let point = self.recovery.snapshot(&self.secure_keystore, password, operation).await?;
+        Ok(Some(point))
+    }
+
+    /// Restore accounts removed since a recovery point was taken
+    pub async fn restore_recovery_point(&mut self, id: uuid::Uuid, password: &SecretString) -> Result<Vec<Address>> {
+        self.update_activity();
+        self.recovery.restore(id, password, &mut self.secure_keystore).await
+    }
+
     /// Create a new account with activity tracking
     pub async fn create_account(&mut self, name: String) -> Result<SecureAccount> {
         self.update_activity();
@@ -120,12 +157,9 @@ impl WalletKeystore {
         tx: &alloy::rpc::types::TransactionRequest,
         address: &Address,
         password: Option<&secrecy::SecretString>,
-        key_cache: Option<&mut crate::security::KeyCache>,
     ) -> Result<Vec<u8>> {
         self.update_activity();
-        self.secure_keystore
-            .sign_transaction(tx, address, password, key_cache)
-            .await
+        self.secure_keystore.sign_transaction(tx, address, password).await
     }
 
     /// Get an account
@@ -139,8 +173,14 @@ impl WalletKeystore {
     }
 
     /// Remove an account
-    pub async fn remove_account(&mut self, address: Address) -> Result<()> {
+    ///
+    /// An encrypted recovery point (protected by `backup_password`) is taken first,
+    /// and the account is only removed if that snapshot succeeded.
+    pub async fn remove_account(&mut self, address: Address, backup_password: &SecretString) -> Result<()> {
         self.update_activity();
+        self.secure_keystore.get_account(address).await?;
+        self.create_recovery_point(DestructiveOperation::RemoveAccount { address }, backup_password)
+            .await?;
         self.secure_keystore.remove_account(address).await?;
         self.backup_metadata.remove(&address);
         Ok(())
@@ -194,6 +234,30 @@ impl WalletKeystore {
         }
     }
 
+    /// Check a re-entered seed phrase against the stored seed
+    ///
+    /// A match marks the account's backup as verified.
+    pub async fn verify_seed_backup(
+        &mut self,
+        address: Address,
+        password: &SecretString,
+        entered_phrase: &SecretString,
+    ) -> Result<bool> {
+        self.update_activity();
+
+        let matches = self
+            .secure_keystore
+            .verify_seed_backup(&address, password, entered_phrase)
+            .await?;
+        if matches {
+            if let Some(metadata) = self.backup_metadata.get_mut(&address) {
+                metadata.backup_verified = true;
+            }
+        }
+
+        Ok(matches)
+    }
+
     /// Get accounts that need backup
     pub fn get_accounts_needing_backup(&self) -> Vec<Address> {
         let now = std::time::SystemTime::now();
@@ -23,6 +23,7 @@ use sha2::{Digest, Sha256};
 use std::path::PathBuf;
 use uuid::Uuid;
 use tracing::instrument;
+use zeroize::Zeroizing;
 
 /// PBKDF2 iteration count - follows MetaMask standard
 /// 262144 iterations provides strong key stretching while remaining reasonable for UX
@@ -262,6 +263,47 @@ impl WalletManager {
         }
     }
 
+    /// Re-encrypt the keystore file under a new password
+    ///
+    /// Returns the previous keystore, which [`Self::restore_keystore`] puts
+    /// back, or `None` when there is no keystore file. A wrong `old_password`
+    /// changes nothing.
+    #[instrument(skip(self, old_password, new_password), fields(keystore_path = ?self.keystore_path))]
+    pub fn change_password(
+        &mut self,
+        old_password: &SecretString,
+        new_password: &SecretString,
+    ) -> WalletResult<Option<MetaMaskKeystore>> {
+        if self.keystore.is_none() {
+            self.load_keystore()?;
+        }
+        let Some(previous) = self.keystore.clone() else {
+            return Ok(None);
+        };
+
+        let private_key = Zeroizing::new(self.decrypt_with_password(&previous.crypto, old_password)?);
+        let private_key_hex = Zeroizing::new(hex::encode(private_key.as_slice()));
+        let crypto = self.encrypt_with_password(&private_key_hex, new_password)?;
+
+        self.keystore = Some(MetaMaskKeystore {
+            crypto,
+            ..previous.clone()
+        });
+        if let Err(e) = self.save_keystore() {
+            self.keystore = Some(previous);
+            return Err(e);
+        }
+
+        tracing::info!("🔑 Keystore re-encrypted under the new password");
+        Ok(Some(previous))
+    }
+
+    /// Put back a keystore replaced by [`Self::change_password`]
+    pub fn restore_keystore(&mut self, keystore: MetaMaskKeystore) -> WalletResult<()> {
+        self.keystore = Some(keystore);
+        self.save_keystore()
+    }
+
     /// Encrypt data with password
     fn encrypt_with_password(&self, data: &str, password: &SecretString) -> WalletResult<CryptoSection> {
         let password_bytes = password.expose_secret().as_bytes();
@@ -637,6 +679,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_change_password() {
+        let (mut manager, password, dir) = create_test_wallet();
+        let address = manager.create_wallet(password.clone()).unwrap();
+        let new_password = SecretString::new("5678".to_string());
+
+        // A wrong old password leaves the keystore alone
+        let wrong = SecretString::new("wrong".to_string());
+        assert!(manager.change_password(&wrong, &new_password).is_err());
+
+        let previous = manager.change_password(&password, &new_password).unwrap().unwrap();
+        let path = dir.path().join("keystore.json");
+        assert!(WalletManager::new(path.clone()).unlock(password.clone()).is_err());
+        let mut reopened = WalletManager::new(path.clone());
+        reopened.unlock(new_password).unwrap();
+        assert_eq!(reopened.address().unwrap(), address);
+
+        // Restoring the previous keystore brings the old password back
+        manager.restore_keystore(previous).unwrap();
+        WalletManager::new(path).unlock(password).unwrap();
+    }
+
     // ========== Task 5.3: Security Tests ==========
 
     #[test]
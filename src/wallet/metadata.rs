@@ -0,0 +1,341 @@
+//! Transaction and Address Notes
+//!
+//! Users can attach a free-text note and any number of labels ("payroll",
+//! "CEX deposit") to transaction hashes and addresses. The [`NoteBook`] is
+//! kept on disk by [`NoteStore`], encrypted under the master password, and
+//! travels inside encrypted backups (see
+//! [`BackupManager::create_encrypted_backup_with_notes`](crate::wallet::backup::BackupManager::create_encrypted_backup_with_notes)).
+//!
+//! Labels match case-insensitively. History filters accept a transaction
+//! when its hash, sender or recipient carries the label.
+
+use crate::blockchain::explorer_apis::ApiTransaction;
+use crate::error::{Result, VaughanError};
+use crate::security::keystore::storage;
+use crate::security::{Argon2Params, SecurityProfile};
+use crate::wallet::encrypted_store::EncryptedJsonStore;
+use alloy::primitives::{Address, TxHash};
+use chrono::{DateTime, Utc};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Longest note accepted, in characters
+pub const MAX_NOTE_LENGTH: usize = 2_000;
+
+/// Longest label accepted, in characters
+pub const MAX_LABEL_LENGTH: usize = 40;
+
+/// Note book of the primary profile in the `.vaughan` directory
+pub const NOTES_FILE: &str = "notes.json";
+
+/// What a note is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum NoteTarget {
+    Transaction(TxHash),
+    Address(Address),
+}
+
+/// Note and labels of one transaction or address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub target: NoteTarget,
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub labels: BTreeSet<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Annotation {
+    fn new(target: NoteTarget) -> Self {
+        Self {
+            target,
+            note: String::new(),
+            labels: BTreeSet::new(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn has_label(&self, label: &str) -> bool {
+        let label = label.trim();
+        self.labels.iter().any(|l| l.eq_ignore_ascii_case(label))
+    }
+
+    /// Whether the note or a label contains `text`, ignoring case
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.trim().to_lowercase();
+        self.note.to_lowercase().contains(&text) || self.labels.iter().any(|l| l.to_lowercase().contains(&text))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.note.is_empty() && self.labels.is_empty()
+    }
+}
+
+fn invalid(message: String) -> VaughanError {
+    VaughanError::ValidationError(message)
+}
+
+/// All notes and labels of a wallet
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteBook {
+    entries: Vec<Annotation>,
+}
+
+impl NoteBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[Annotation] {
+        &self.entries
+    }
+
+    pub fn get(&self, target: NoteTarget) -> Option<&Annotation> {
+        self.entries.iter().find(|a| a.target == target)
+    }
+
+    fn entry(&mut self, target: NoteTarget) -> &mut Annotation {
+        let index = match self.entries.iter().position(|a| a.target == target) {
+            Some(index) => index,
+            None => {
+                self.entries.push(Annotation::new(target));
+                self.entries.len() - 1
+            }
+        };
+        let entry = &mut self.entries[index];
+        entry.updated_at = Utc::now();
+        entry
+    }
+
+    /// Drop entries left with neither a note nor labels
+    fn prune(&mut self) {
+        self.entries.retain(|a| !a.is_empty());
+    }
+
+    /// Replace the note of `target`; an empty note removes it
+    pub fn set_note(&mut self, target: NoteTarget, note: &str) -> Result<()> {
+        let note = note.trim();
+        if note.chars().count() > MAX_NOTE_LENGTH {
+            return Err(invalid(format!("Notes are limited to {MAX_NOTE_LENGTH} characters")));
+        }
+        self.entry(target).note = note.to_string();
+        self.prune();
+        Ok(())
+    }
+
+    /// Attach `label` to `target`, keeping an existing spelling of the same label
+    pub fn add_label(&mut self, target: NoteTarget, label: &str) -> Result<()> {
+        let label = label.trim();
+        if label.is_empty() || label.chars().count() > MAX_LABEL_LENGTH {
+            return Err(invalid(format!("Labels must be 1 to {MAX_LABEL_LENGTH} characters")));
+        }
+        let label = self
+            .labels()
+            .into_iter()
+            .find(|existing| existing.eq_ignore_ascii_case(label))
+            .unwrap_or_else(|| label.to_string());
+        self.entry(target).labels.insert(label);
+        Ok(())
+    }
+
+    /// Detach `label` from `target`; returns whether it was attached
+    pub fn remove_label(&mut self, target: NoteTarget, label: &str) -> bool {
+        let Some(entry) = self.entries.iter_mut().find(|a| a.target == target) else {
+            return false;
+        };
+        let before = entry.labels.len();
+        entry.labels.retain(|l| !l.eq_ignore_ascii_case(label.trim()));
+        let removed = entry.labels.len() != before;
+        if removed {
+            entry.updated_at = Utc::now();
+        }
+        self.prune();
+        removed
+    }
+
+    /// Forget everything attached to `target`
+    pub fn remove(&mut self, target: NoteTarget) -> Option<Annotation> {
+        let index = self.entries.iter().position(|a| a.target == target)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Every label in use
+    pub fn labels(&self) -> BTreeSet<String> {
+        self.entries.iter().flat_map(|a| a.labels.iter().cloned()).collect()
+    }
+
+    /// Entries carrying `label`
+    pub fn with_label(&self, label: &str) -> Vec<&Annotation> {
+        self.entries.iter().filter(|a| a.has_label(label)).collect()
+    }
+
+    /// Entries whose note or labels contain `text`, most recently updated first
+    pub fn search(&self, text: &str) -> Vec<&Annotation> {
+        let mut found: Vec<_> = self.entries.iter().filter(|a| a.matches(text)).collect();
+        found.sort_by_key(|a| std::cmp::Reverse(a.updated_at));
+        found
+    }
+
+    fn labelled(&self, target: NoteTarget, label: &str) -> bool {
+        self.get(target).is_some_and(|a| a.has_label(label))
+    }
+
+    /// Transactions of a history query whose hash, sender or recipient carries `label`
+    pub fn filter_history<'a>(&self, transactions: &'a [ApiTransaction], label: &str) -> Vec<&'a ApiTransaction> {
+        transactions
+            .iter()
+            .filter(|tx| {
+                let hash = TxHash::from_str(&tx.hash).ok().map(NoteTarget::Transaction);
+                let parties = [&tx.from, &tx.to]
+                    .into_iter()
+                    .filter_map(|a| Address::from_str(a).ok().map(NoteTarget::Address));
+                hash.into_iter()
+                    .chain(parties)
+                    .any(|target| self.labelled(target, label))
+            })
+            .collect()
+    }
+
+    /// Add entries from `other` for targets this book doesn't annotate yet
+    pub fn merge(&mut self, other: NoteBook) -> usize {
+        let mut added = 0;
+        for entry in other.entries {
+            if self.get(entry.target).is_none() {
+                self.entries.push(entry);
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
+/// Encrypted note book file
+#[derive(Debug, Clone)]
+pub struct NoteStore {
+    file: EncryptedJsonStore,
+}
+
+impl NoteStore {
+    /// Notes file of `profile` in the `.vaughan` directory
+    pub fn new(profile: SecurityProfile) -> Self {
+        let file = match profile {
+            SecurityProfile::Primary => NOTES_FILE,
+            SecurityProfile::Decoy => "notes-2.json",
+        };
+        Self::at(storage::get_vaughan_dir().join(file))
+    }
+
+    /// Notes file at an explicit path
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self {
+            file: EncryptedJsonStore::at(path),
+        }
+    }
+
+    /// Key derivation cost used when saving
+    pub fn with_argon2_params(mut self, params: Argon2Params) -> Self {
+        self.file = self.file.with_argon2_params(params);
+        self
+    }
+
+    /// Decrypt the note book; an empty one when nothing has been saved yet
+    pub fn load(&self, master_password: &SecretString) -> Result<NoteBook> {
+        Ok(self.file.load(master_password)?.unwrap_or_else(NoteBook::new))
+    }
+
+    /// Encrypt and write the note book
+    pub fn save(&self, notes: &NoteBook, master_password: &SecretString) -> Result<()> {
+        self.file.save(notes, master_password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(hash: TxHash, from: Address, to: Address) -> ApiTransaction {
+        ApiTransaction {
+            hash: hash.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            value: "0".to_string(),
+            timestamp: 0,
+            block_number: 1,
+            gas_used: None,
+            gas_price: None,
+            status: "1".to_string(),
+            method_name: None,
+        }
+    }
+
+    #[test]
+    fn test_labels_search_and_history_filter() {
+        let exchange = Address::repeat_byte(0xce);
+        let salary = TxHash::repeat_byte(0x01);
+        let mut notes = NoteBook::new();
+        notes.add_label(NoteTarget::Address(exchange), "CEX deposit").unwrap();
+        notes.add_label(NoteTarget::Transaction(salary), "Payroll").unwrap();
+        notes.add_label(NoteTarget::Transaction(salary), "payroll").unwrap();
+        notes.set_note(NoteTarget::Transaction(salary), "March salary").unwrap();
+        assert!(notes.add_label(NoteTarget::Transaction(salary), " ").is_err());
+
+        // Labels match regardless of case and keep their first spelling
+        assert_eq!(
+            notes.labels().into_iter().collect::<Vec<_>>(),
+            vec!["CEX deposit", "Payroll"]
+        );
+        assert_eq!(notes.with_label("PAYROLL").len(), 1);
+        assert_eq!(notes.search("march")[0].target, NoteTarget::Transaction(salary));
+
+        let me = Address::repeat_byte(0xaa);
+        let history = [
+            tx(salary, Address::repeat_byte(0xbb), me),
+            tx(TxHash::repeat_byte(0x02), me, exchange),
+            tx(TxHash::repeat_byte(0x03), me, Address::repeat_byte(0xdd)),
+        ];
+        assert_eq!(notes.filter_history(&history, "payroll").len(), 1);
+        assert_eq!(notes.filter_history(&history, "cex deposit")[0].hash, history[1].hash);
+
+        // Entries with nothing left are dropped
+        assert!(notes.remove_label(NoteTarget::Address(exchange), "cex DEPOSIT"));
+        notes.set_note(NoteTarget::Transaction(salary), "").unwrap();
+        assert_eq!(notes.len(), 1);
+    }
+
+    #[test]
+    fn test_note_store_is_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = NoteStore::at(dir.path().join("notes.json")).with_argon2_params(Argon2Params {
+            memory_cost: 8,
+            time_cost: 1,
+            parallelism: 1,
+            output_length: 32,
+        });
+        let password = SecretString::new("master password".to_string());
+        assert!(store.load(&password).unwrap().is_empty());
+
+        let mut notes = NoteBook::new();
+        notes
+            .set_note(NoteTarget::Address(Address::ZERO), "burn address")
+            .unwrap();
+        store.save(&notes, &password).unwrap();
+
+        let raw = std::fs::read_to_string(dir.path().join("notes.json")).unwrap();
+        assert!(!raw.contains("burn address"));
+        assert_eq!(store.load(&password).unwrap(), notes);
+        assert!(store.load(&SecretString::new("wrong".to_string())).is_err());
+    }
+}
@@ -11,26 +11,51 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::config::{SectionWatch, SecurityPolicySettings};
 use crate::error::{Result, WalletError};
+use crate::network::egress::{egress, CategoryMode, EgressCategory, EgressPolicy};
+use crate::network::poller::{BalanceChanged, BalancePoller};
 use crate::network::{NetworkChangeCallback, NetworkConfig, NetworkId, NetworkManager};
-use crate::security::{SecureAccount, SecureExport, SecureKeystore};
+use crate::security::cold_account::{self, ColdAccountGuard};
+use crate::security::keystore::{encode_signed_transaction, resolve_chain_id};
+use crate::security::seed::derivation::uses_bip39_passphrase;
+use crate::security::tx_policy::{PolicyContext, TransactionPolicy};
+use crate::telemetry::audit::{self, AuditEvent};
+use crate::telemetry::metrics;
+use crate::security::{
+    DuressGuard, HighRiskOperation, KeychainInterface, LockLevel, OsConfirmation, OsConfirmationPolicy, PasswordPolicy,
+    SecureAccount, SecureExport, SecureKeystore, SecurityProfile, SessionConfig, SessionEvent, SessionManager,
+    UnlockThrottle, DEFAULT_KEY_TTL,
+};
+use crate::tokens::{TokenInfo, TokenManager};
 
+pub mod aa;
 pub mod account;
 pub mod account_manager;
+pub mod approval;
+pub mod encrypted_store;
 pub mod errors;
 pub mod hardware;
 pub mod keystore;
 pub mod keystore_format;
+pub mod keystore_v3;
 pub mod manager;
+pub mod metadata;
+pub mod multisig;
+pub mod password_change;
+pub mod portfolio;
+pub mod progress;
 pub mod backup;
 pub mod provider;
 pub mod transaction;
+pub mod undo;
 
 pub use account::*;
 pub use account_manager::{
-    AccountConfig, AccountManagerResult, AccountManagerTrait, AccountType, AuthToken,
-    AuthorizedOperation, ImportSource, SeedStrength,
+    AccountConfig, AccountManagerResult, AccountManagerTrait, AccountMetadataQueries, AccountMetadataStore,
+    AccountType, AuthToken, AuthorizedOperation, ImportSource, SeedStrength,
 };
+pub use approval::{ApprovalBroker, ApprovalKind, ApprovalReceiver, ApprovalRequest, ApprovalResponse, PendingApproval};
 pub use errors::*;
 pub use hardware::{
     AddressVerificationFeedback, DeviceRecoveryFeedback, HardwareManager, HardwareWalletStatus,
@@ -38,20 +63,55 @@ pub use hardware::{
 };
 pub use keystore_format::*;
 pub use manager::*;
+pub use undo::{ConfigChange, ConfigHistory};
 /// Main wallet configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WalletConfig {
-    pub default_network: NetworkId,
+    /// Network to start on (None = the stored startup preference)
+    pub default_network: Option<NetworkId>,
+    /// Lock the wallet and clear cached keys after this much inactivity (None = never)
     pub auto_lock_timeout: Option<std::time::Duration>,
+    /// Publish [`SessionEvent::LockWarning`] this long before auto-lock (None = no warning)
+    pub auto_lock_warning: Option<std::time::Duration>,
+    /// Lock signing but keep balances viewable after this much inactivity (None = never)
+    pub signing_lock_timeout: Option<std::time::Duration>,
     pub hardware_wallet_enabled: bool,
+    /// Start with signing locked and no account selected, so nothing is signed
+    /// before [`Vaughan::unlock_signing`] and an explicit account choice
+    ///
+    /// On by default in release builds. The GUI unlocks with the password from
+    /// its signing dialog, and the HTTP server with one typed on the terminal
+    /// or passed in [`provider::http::WALLET_PASSWORD_ENV`].
+    pub strict_lock: bool,
 }
 
 impl Default for WalletConfig {
     fn default() -> Self {
         Self {
-            default_network: NetworkId(1), // Ethereum mainnet
-            auto_lock_timeout: None,       // Disabled for testing - no auto-lock
+            default_network: None,
+            auto_lock_timeout: Some(std::time::Duration::from_secs(300)), // 5 minutes
+            auto_lock_warning: Some(std::time::Duration::from_secs(30)),
+            signing_lock_timeout: None,
             hardware_wallet_enabled: true,
+            strict_lock: !cfg!(debug_assertions),
+        }
+    }
+}
+
+/// Where the keychain of each security profile comes from
+#[derive(Debug)]
+enum ProfileKeychains {
+    /// The configured backend, see [`SecurityProfile::keychain`]
+    Configured,
+    /// One keychain for every profile, given to [`Vaughan::with_keychain`]
+    Fixed(Box<dyn KeychainInterface>),
+}
+
+impl ProfileKeychains {
+    fn get(&self, profile: SecurityProfile) -> Result<Box<dyn KeychainInterface>> {
+        match self {
+            Self::Configured => profile.keychain(),
+            Self::Fixed(keychain) => Ok(keychain.clone_box()),
         }
     }
 }
@@ -60,216 +120,641 @@ impl Default for WalletConfig {
 #[derive(Debug)]
 pub struct Vaughan {
     network_config: Arc<RwLock<NetworkManager>>,
-    current_account: Arc<RwLock<Option<SecureAccount>>>,
     keystore: Arc<RwLock<SecureKeystore>>,
+    /// Account operations, current account selection and lock state
+    account_manager: Arc<RwLock<account_manager::AccountManager>>,
     hardware_manager: Arc<RwLock<Option<HardwareManager>>>,
-    /// Wallet lock state - tracks whether the wallet is locked
-    locked: Arc<RwLock<bool>>,
+    /// Idle tracking for auto-lock, master password and cached signing keys
+    session: Arc<SessionManager>,
+    auto_lock_monitor: Option<tokio::task::JoinHandle<()>>,
+    /// Publishes balance changes of the wallet's accounts
+    balance_poller: BalancePoller,
+    balance_poll_task: Option<tokio::task::JoinHandle<()>>,
     config: WalletConfig,
+    /// Configuration changes that can be undone
+    config_history: ConfigHistory,
+    /// Rules checked before signing, or why the policy file could not be loaded
+    tx_policy: Result<TransactionPolicy>,
+    /// Unlock rituals of cold accounts, checked before signing
+    cold_accounts: ColdAccountGuard,
+    /// Chain ID to sign for instead of the current network's
+    forced_chain_id: Option<u64>,
+    /// OS authentication prompts for exports and large transfers
+    os_confirmation: OsConfirmation,
+    /// Frontend that approves every signature
+    approvals: ApprovalBroker,
+    /// Stores re-encrypted by [`Self::change_master_password`]
+    password_stores: password_change::PasswordStores,
+    /// Failed master password counter, held across each unlock attempt
+    unlock_throttle: tokio::sync::Mutex<UnlockThrottle>,
+    /// Keychains the security profiles are opened with
+    keychains: ProfileKeychains,
+    /// Duress password, which [`Self::unlock_signing`] answers with the decoy profile
+    duress: DuressGuard,
 }
 
 impl Vaughan {
     /// Create a new Vaughan wallet instance
     pub async fn new(config: WalletConfig) -> Result<Self> {
-        let network_manager = NetworkManager::new().await?;
-        let keychain = crate::security::create_keychain_interface()?;
-        let mut keystore = SecureKeystore::new(keychain).await?;
+        Self::for_profile(config, SecurityProfile::Primary).await
+    }
 
-        // Auto-unlock keystore for testing
+    /// Create a wallet over the accounts of one security profile
+    ///
+    /// The GUI resolves the profile from the password it unlocked with.
+    pub async fn for_profile(config: WalletConfig, profile: SecurityProfile) -> Result<Self> {
+        Self::build(config, ProfileKeychains::Configured, profile).await
+    }
+
+    /// Create a wallet whose keys live in `keychain`
+    pub async fn with_keychain(config: WalletConfig, keychain: Box<dyn KeychainInterface>) -> Result<Self> {
+        Self::build(config, ProfileKeychains::Fixed(keychain), SecurityProfile::Primary).await
+    }
+
+    async fn build(config: WalletConfig, keychains: ProfileKeychains, profile: SecurityProfile) -> Result<Self> {
+        let network_manager = match config.default_network {
+            Some(network) => NetworkManager::with_startup_network(network).await?,
+            None => NetworkManager::new().await?,
+        };
+        let keychain = keychains.get(profile)?;
+        let mut keystore = SecureKeystore::with_profile(keychain.clone_box(), profile).await?;
+
+        // Refuse a locked keystore and make sure its accounts are loaded
         keystore.ensure_unlocked().await?;
 
-        // Get all available accounts
+        // Outside strict mode the first account starts selected
         let accounts = keystore.list_accounts().await?;
-        let initial_account = if !accounts.is_empty() {
-            tracing::info!(
-                "🔓 Auto-initializing wallet with first account: {} ({})",
-                accounts[0].name,
-                accounts[0].address
-            );
-            Some(accounts[0].clone())
-        } else {
-            tracing::info!("📋 No accounts available for auto-initialization");
-            None
+        let initial_account = match accounts.first() {
+            Some(account) if !config.strict_lock => {
+                tracing::info!("👤 Selecting first account: {} ({})", account.name, account.address);
+                Some(account.clone())
+            }
+            _ => None,
         };
 
+        let keystore = Arc::new(RwLock::new(keystore));
+        let mut account_manager =
+            account_manager::AccountManager::new(Arc::clone(&keystore), keychain).with_profile(profile);
+        if let Some(account) = initial_account {
+            account_manager.select(account);
+        }
+
+        let session = SessionManager::new(session_config(&config)).with_key_source(keystore.clone());
+        if config.strict_lock {
+            session.lock_signing().await;
+        }
+
+        // The `security` settings decide which operations need the OS prompt
+        let os_confirmation = OsConfirmation::default();
+        os_confirmation.follow_settings(crate::config::settings_store().subscribe_section());
+
         let mut wallet = Self {
             network_config: Arc::new(RwLock::new(network_manager)),
-            current_account: Arc::new(RwLock::new(initial_account)),
-            keystore: Arc::new(RwLock::new(keystore)),
+            keystore,
+            account_manager: Arc::new(RwLock::new(account_manager)),
             hardware_manager: Arc::new(RwLock::new(None)),
-            locked: Arc::new(RwLock::new(false)),
+            session: Arc::new(session),
+            auto_lock_monitor: None,
+            balance_poller: BalancePoller::default(),
+            balance_poll_task: None,
             config,
+            config_history: ConfigHistory::default(),
+            tx_policy: TransactionPolicy::load_default(),
+            cold_accounts: ColdAccountGuard::default(),
+            forced_chain_id: None,
+            os_confirmation,
+            approvals: ApprovalBroker::default(),
+            password_stores: password_change::PasswordStores::default(),
+            unlock_throttle: tokio::sync::Mutex::new(UnlockThrottle::from_settings()),
+            keychains,
+            duress: DuressGuard::open_default(),
         };
 
         // Initialize hardware wallet manager if enabled
         wallet.initialize_hardware_manager().await?;
+        wallet.start_auto_lock().await;
 
         Ok(wallet)
     }
 
-    /// Create a new account
-    pub async fn create_account(&mut self, name: String) -> Result<Address> {
-        let mut keystore = self.keystore.write().await;
-        let account = keystore.create_account(name).await?;
-        let address = account.address;
+    /// Start enforcing `auto_lock_timeout` and `signing_lock_timeout`
+    ///
+    /// Runs until the wallet is dropped; on timeout the session drops its
+    /// master password and signing keys, then the account manager is locked.
+    async fn start_auto_lock(&mut self) {
+        if self.config.auto_lock_timeout.is_none() && self.config.signing_lock_timeout.is_none() {
+            tracing::info!("🔓 Auto-lock disabled by configuration");
+            return;
+        }
+        let account_manager = Arc::clone(&self.account_manager);
+        let handle = self
+            .session
+            .start_auto_lock_monitor(move || {
+                let account_manager = Arc::clone(&account_manager);
+                async move {
+                    if let Err(e) = account_manager.write().await.lock().await {
+                        tracing::error!("❌ Auto-lock failed: {}", e);
+                    }
+                }
+            })
+            .await;
+        self.auto_lock_monitor = Some(handle);
+    }
 
-        // Set as current account if none is set
-        let mut current = self.current_account.write().await;
-        if current.is_none() {
-            *current = Some(account);
+    /// Push changed timeouts into the session and restart the auto-lock monitor
+    async fn apply_session_config(&mut self, before: &WalletConfig) {
+        let unchanged = before.auto_lock_timeout == self.config.auto_lock_timeout
+            && before.auto_lock_warning == self.config.auto_lock_warning
+            && before.signing_lock_timeout == self.config.signing_lock_timeout;
+        if unchanged {
+            return;
         }
+        if let Some(monitor) = self.auto_lock_monitor.take() {
+            monitor.abort();
+        }
+        self.session.stop_monitor().await;
+        self.session.reconfigure(session_config(&self.config)).await;
+        self.start_auto_lock().await;
+    }
 
-        Ok(address)
+    /// Record user activity, postponing auto-lock
+    ///
+    /// The GUI calls this on user input.
+    pub async fn record_activity(&self) {
+        self.session.record_activity().await;
     }
 
-    /// Import an account from a private key
-    pub async fn import_account(&mut self, private_key: SecretString, name: String) -> Result<Address> {
-        let mut keystore = self.keystore.write().await;
-        let account = keystore.import_account(private_key, name).await?;
-        let address = account.address;
+    /// Subscribe to auto-lock warnings and lock notifications
+    pub fn subscribe_idle_events(&self) -> tokio::sync::broadcast::Receiver<SessionEvent> {
+        self.session.subscribe()
+    }
 
-        // Set as current account if none is set
-        let mut current = self.current_account.write().await;
-        if current.is_none() {
-            *current = Some(account);
+    /// Poll the balances of all accounts in the background
+    ///
+    /// Restarts the poller if it is already running, picking up new accounts.
+    pub async fn start_balance_polling(&mut self) -> Result<()> {
+        let accounts = self.list_accounts().await?;
+        self.balance_poller.set_accounts(accounts.iter().map(|account| account.address).collect());
+        if let Some(task) = self.balance_poll_task.take() {
+            task.abort();
         }
+        self.balance_poll_task = Some(self.balance_poller.start(Arc::clone(&self.network_config)));
+        Ok(())
+    }
 
-        Ok(address)
+    /// Subscribe to balance changes found by the background poller
+    pub fn subscribe_balance_changes(&self) -> tokio::sync::broadcast::Receiver<BalanceChanged> {
+        self.balance_poller.subscribe()
     }
 
-    /// Export an account
-    pub async fn export_account(&self, address: Address, password: SecretString) -> Result<SecureExport> {
-        let keystore = self.keystore.read().await;
-        keystore.export_account(address, password).await
+    /// Background balance poller, e.g. to watch additional tokens
+    pub fn balance_poller(&self) -> &BalancePoller {
+        &self.balance_poller
     }
 
-    /// Sign a transaction
-    pub async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Vec<u8>> {
-        self.sign_transaction_with_password(tx, None).await
+    /// Time left before auto-lock (None when auto-lock is disabled)
+    pub async fn time_until_lock(&self) -> Option<std::time::Duration> {
+        self.session.time_until_lock().await
     }
 
-    pub async fn sign_transaction_with_password(
-        &self,
-        tx: &TransactionRequest,
-        password: Option<&secrecy::SecretString>,
-    ) -> Result<Vec<u8>> {
-        tracing::info!(
-            "🔐 Wallet sign_transaction_with_password called (password provided: {})",
-            password.is_some()
-        );
+    /// Current lock level of the session
+    pub async fn lock_level(&self) -> LockLevel {
+        self.session.lock_level().await
+    }
+
+    /// Lock signing only; balances and history stay viewable
+    ///
+    /// Drops the master password and signing keys. [`Self::unlock_signing`]
+    /// with the password restores signing.
+    pub async fn lock_signing(&self) {
+        self.session.lock_signing().await;
+    }
+
+    /// Shared handle to the signing session
+    pub fn session(&self) -> Arc<SessionManager> {
+        Arc::clone(&self.session)
+    }
 
-        let current_account = self.current_account.read().await;
-
-        // If no current account, try to auto-select the first available account
-        let account = match current_account.as_ref() {
-            Some(acc) => acc.clone(),
-            None => {
-                // Auto-select first account if available
-                drop(current_account);
-                let keystore = self.keystore.read().await;
-                let accounts = keystore.list_accounts().await?;
-                if !accounts.is_empty() {
-                    let first_account = accounts[0].clone();
-                    drop(keystore);
-                    let mut current = self.current_account.write().await;
-                    *current = Some(first_account.clone());
-                    tracing::info!(
-                        "🔓 Auto-unlocked with first account for signing: {} ({})",
-                        first_account.name,
-                        first_account.address
-                    );
-                    first_account
-                } else {
-                    tracing::error!("❌ No accounts available for auto-unlock");
-                    return Err(WalletError::WalletLocked.into());
+    /// Hand the master password to the signing session
+    ///
+    /// The password is checked against the stores encrypted under it first;
+    /// a wrong one returns `false` and signing stays locked. Wrong passwords
+    /// count towards the unlock throttle; while it imposes a wait, attempts
+    /// fail with [`crate::error::SecurityError::RateLimitExceeded`]. Seed-based
+    /// accounts derive their keys with it on first use; the password and
+    /// derived keys are dropped when the wallet locks.
+    ///
+    /// The duress password unlocks the same way, but switches the wallet to
+    /// the decoy profile; the master password switches it back.
+    pub async fn unlock_signing(&self, password: SecretString) -> Result<bool> {
+        // The counter file is shared with the GUI, so read it afresh
+        let mut throttle = self.unlock_throttle.lock().await;
+        *throttle = throttle.reload();
+        if let Some(wait) = throttle.wait() {
+            tracing::warn!("⏳ Unlock refused for {:?} after failed attempts", wait.remaining());
+            return Err(crate::error::SecurityError::RateLimitExceeded {
+                operation: "unlock".to_string(),
+                wait_time_seconds: wait.remaining().as_secs().max(1),
+            }
+            .into());
+        }
+
+        // The decoy profile is opened by the duress verifier alone; the primary
+        // one is only opened once the master password checks out against it
+        let profile = self.duress.resolve(&password);
+        let reopened = if profile == self.security_profile().await {
+            None
+        } else {
+            Some(self.open_keystore(profile).await?)
+        };
+        if profile == SecurityProfile::Primary {
+            let valid = match &reopened {
+                Some((keystore, _)) => {
+                    password_change::verify_master_password(keystore, &self.password_stores, &password).await?
                 }
+                None => {
+                    let keystore = self.keystore.read().await;
+                    password_change::verify_master_password(&keystore, &self.password_stores, &password).await?
+                }
+            };
+            if !valid {
+                tracing::warn!("🔒 Wrong master password, signing stays locked");
+                if let Err(e) = throttle.record_failure() {
+                    tracing::warn!("⚠️ Failed to persist unlock attempt counter: {}", e);
+                }
+                return Ok(false);
             }
+        }
+        if let Some((keystore, keychain)) = reopened {
+            self.switch_profile(keystore, keychain).await?;
+        }
+        if let Err(e) = throttle.record_success() {
+            tracing::warn!("⚠️ Failed to reset unlock attempt counter: {}", e);
+        }
+        self.session.unlock(password).await;
+        Ok(true)
+    }
+
+    /// Security profile whose accounts the wallet holds
+    pub async fn security_profile(&self) -> SecurityProfile {
+        self.keystore.read().await.profile()
+    }
+
+    /// Set the duress password, which unlocks the decoy accounts instead of the real ones
+    ///
+    /// `master_password` must be the current master password, and the two
+    /// must differ. Accounts created after unlocking with the duress password
+    /// are decoy accounts.
+    pub async fn set_duress_password(
+        &mut self,
+        duress_password: &SecretString,
+        master_password: &SecretString,
+    ) -> Result<()> {
+        self.check_master_password(master_password).await?;
+        self.duress.set(duress_password, master_password)
+    }
+
+    /// Remove the duress password; the decoy accounts are kept
+    pub async fn clear_duress_password(&mut self, master_password: &SecretString) -> Result<()> {
+        self.check_master_password(master_password).await?;
+        self.duress.clear()
+    }
+
+    /// Use `guard` for the duress password instead of the one in the `.vaughan` directory
+    pub fn with_duress_guard(mut self, guard: DuressGuard) -> Self {
+        self.duress = guard;
+        self
+    }
+
+    /// Refuse anything but the master password of the primary profile
+    ///
+    /// In the decoy profile every password is refused like a wrong one.
+    async fn check_master_password(&self, password: &SecretString) -> Result<()> {
+        let keystore = self.keystore.read().await;
+        if keystore.profile() != SecurityProfile::Primary
+            || !password_change::verify_master_password(&keystore, &self.password_stores, password).await?
+        {
+            return Err(crate::error::SecurityError::InvalidPassword.into());
+        }
+        Ok(())
+    }
+
+    /// Open the keystore of `profile`, with the keychain it was opened with
+    async fn open_keystore(&self, profile: SecurityProfile) -> Result<(SecureKeystore, Box<dyn KeychainInterface>)> {
+        let keychain = self.keychains.get(profile)?;
+        let mut keystore = SecureKeystore::with_profile(keychain.clone_box(), profile).await?;
+        keystore.ensure_unlocked().await?;
+        Ok((keystore, keychain))
+    }
+
+    /// Replace the open keystore with one of another security profile
+    ///
+    /// Signing keys of the old profile are dropped and its current account is
+    /// deselected; outside strict mode the new profile's first account is selected.
+    async fn switch_profile(&self, keystore: SecureKeystore, keychain: Box<dyn KeychainInterface>) -> Result<()> {
+        let profile = keystore.profile();
+        let accounts = keystore.list_accounts().await?;
+        self.session.lock_signing().await;
+        *self.keystore.write().await = keystore;
+
+        let mut manager = self.account_manager.write().await;
+        manager.switch_profile(keychain, profile);
+        if let Some(account) = accounts.first().filter(|_| !self.config.strict_lock) {
+            manager.select(account.clone());
+        }
+        Ok(())
+    }
+
+    /// Hand the BIP-39 passphrase of a seed account to the signing session
+    ///
+    /// Accounts created with a passphrase refuse to sign with
+    /// [`crate::error::SecurityError::Bip39PassphraseRequired`] until it is
+    /// entered. Like the master password it is dropped when the wallet locks.
+    pub async fn unlock_bip39_passphrase(&self, address: Address, passphrase: SecretString) {
+        self.session.set_bip39_passphrase(address, passphrase).await;
+    }
+
+    /// Get the account manager as a trait object for external consumers
+    pub fn account_manager(&self) -> Arc<RwLock<dyn AccountManagerTrait>> {
+        self.account_manager.clone()
+    }
+
+    /// Create a new account
+    ///
+    /// The first account created becomes the current account.
+    pub async fn create_account(&mut self, config: AccountConfig, password: &SecretString) -> Result<Address> {
+        let passphrase = config.bip39_passphrase.clone();
+        let mut manager = self.account_manager.write().await;
+        let account = manager.create_account(config, password).await?;
+        if let Some(passphrase) = passphrase.filter(|_| uses_bip39_passphrase(&account)) {
+            self.session.set_bip39_passphrase(account.address, passphrase).await;
+        }
+        Ok(account.address)
+    }
+
+    /// Import an account from a seed phrase, private key or keystore file
+    ///
+    /// The first account imported becomes the current account.
+    pub async fn import_account(&mut self, source: ImportSource) -> Result<Address> {
+        let passphrase = match &source {
+            ImportSource::SeedPhrase { passphrase, .. } => passphrase.clone(),
+            _ => None,
         };
+        let mut manager = self.account_manager.write().await;
+        let account = manager.import_account(source).await?;
+        if let Some(passphrase) = passphrase.filter(|_| uses_bip39_passphrase(&account)) {
+            self.session.set_bip39_passphrase(account.address, passphrase).await;
+        }
+        Ok(account.address)
+    }
 
-        tracing::info!("👤 Current account for signing: {} ({})", account.name, account.address);
-        tracing::info!(
-            "📝 Transaction details: to={:?}, value={:?}, gas={:?}",
-            tx.to,
-            tx.value,
-            tx.gas
-        );
+    /// Export an account's private key, encrypted with `password`
+    ///
+    /// Like [`Self::export_seed`], this draws on the shared export allowance
+    /// and cooldown, see [`crate::security::ExportAuthenticator::shared`].
+    pub async fn export_account(&self, address: Address, password: SecretString) -> Result<SecureExport> {
+        self.os_confirmation
+            .confirm(&HighRiskOperation::PrivateKeyExport { address })
+            .await?;
+        let manager = self.account_manager.read().await;
+        let token = AuthToken::new(AuthorizedOperation::ExportPrivateKey);
+        let export = manager.export_private_key(address, &password, token).await?;
+        drop(manager);
+        audit::record(AuditEvent::ExportPrivateKey { account: address }, Uuid::new_v4());
+        self.mark_key_exported(address).await;
+        Ok(export)
+    }
+
+    /// Export an account's seed phrase, encrypted with `password`
+    pub async fn export_seed(&self, address: Address, password: &SecretString) -> Result<SecureExport> {
+        self.os_confirmation
+            .confirm(&HighRiskOperation::SeedExport { address })
+            .await?;
+        let manager = self.account_manager.read().await;
+        let token = AuthToken::new(AuthorizedOperation::ExportSeed);
+        let export = manager.export_seed(address, password, token).await?;
+        drop(manager);
+        audit::record(AuditEvent::ExportSeed { account: address }, Uuid::new_v4());
+        self.mark_key_exported(address).await;
+        Ok(export)
+    }
+
+    /// Tag an account whose key left the wallet, so cold accounts can warn about it
+    async fn mark_key_exported(&self, address: Address) {
+        cold_account::mark_key_exported(&mut *self.keystore.write().await, address).await;
+    }
+
+    /// Check a re-entered seed phrase against the account's stored seed
+    ///
+    /// Confirms a paper backup still matches without exporting the phrase.
+    pub async fn verify_seed_backup(
+        &self,
+        address: Address,
+        password: &SecretString,
+        entered_phrase: &SecretString,
+    ) -> Result<bool> {
+        let keystore = self.keystore.read().await;
+        keystore.verify_seed_backup(&address, password, entered_phrase).await
+    }
+
+    /// Sign an auditor bundle attesting to each statement with its account's key
+    ///
+    /// Each attestation is a personal message and is approved like one. The
+    /// bundle carries no key material and can be checked with
+    /// [`portfolio::audit::AuditorBundle::verify`].
+    pub async fn auditor_bundle(
+        &self,
+        statements: Vec<portfolio::audit::AccountStatement>,
+    ) -> Result<portfolio::audit::AuditorBundle> {
+        let generated_at = chrono::Utc::now();
+        let mut accounts = Vec::with_capacity(statements.len());
+        for statement in statements {
+            let address = statement.address;
+            let attestation = statement.attestation_message(generated_at)?;
+            self.authorize_signature(address, ApprovalKind::message(attestation.as_bytes())).await?;
+            let account = self
+                .session
+                .with_signer(address, |signer| {
+                    portfolio::audit::AuditedAccount::sign(statement, generated_at, signer)
+                })
+                .await??;
+            audit::record(AuditEvent::SignAuditorStatement { account: address, generated_at }, Uuid::new_v4());
+            accounts.push(account);
+        }
+        Ok(portfolio::audit::AuditorBundle { generated_at, accounts })
+    }
+
+    /// Chain ID transactions are signed for
+    ///
+    /// The current network's, unless overridden with [`Self::force_chain_id`].
+    pub async fn signing_chain_id(&self) -> u64 {
+        match self.forced_chain_id {
+            Some(chain_id) => chain_id,
+            None => self.network_config.read().await.current_network().chain_id(),
+        }
+    }
+
+    /// Sign for `chain_id` regardless of the current network; `None` restores the default
+    ///
+    /// For advanced cases such as preparing transactions for another chain
+    /// offline. Requests carrying a different chain ID are still rejected.
+    pub fn force_chain_id(&mut self, chain_id: Option<u64>) {
+        if let Some(chain_id) = chain_id {
+            tracing::warn!("⛓️ Signing forced to chain ID {}", chain_id);
+        }
+        self.forced_chain_id = chain_id;
+    }
+
+    pub fn forced_chain_id(&self) -> Option<u64> {
+        self.forced_chain_id
+    }
+
+    /// Approval channel every signature goes through
+    pub fn approvals(&self) -> &ApprovalBroker {
+        &self.approvals
+    }
+
+    /// Sign a transaction with the current account
+    ///
+    /// Keys come from the signing session; seed-based accounts need
+    /// [`Self::unlock_signing`] first. The transaction is pinned to
+    /// [`Self::signing_chain_id`] and rejected if it names another chain.
+    /// Signing waits for the approval frontend, see [`Self::approvals`].
+    pub async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Vec<u8>> {
+        let account = self.current_account().await.ok_or(WalletError::NoAccountSelected)?;
+        self.sign_transaction_with(account, tx).await
+    }
+
+    /// Sign a transaction with `from`, leaving the current account alone
+    ///
+    /// Fails unless `from` is one of the wallet's accounts. Servers handling
+    /// requests concurrently sign through this, so another request switching
+    /// accounts cannot change the key a transaction is signed with.
+    pub async fn sign_transaction_as(&self, from: Address, tx: &TransactionRequest) -> Result<Vec<u8>> {
+        let account = self.keystore.read().await.get_account(from).await?;
+        self.sign_transaction_with(account, tx).await
+    }
+
+    async fn sign_transaction_with(&self, account: SecureAccount, tx: &TransactionRequest) -> Result<Vec<u8>> {
+        let chain_id = self.signing_chain_id().await;
+        let tx = &resolve_chain_id(tx, chain_id)?;
+        self.transaction_policy()?.check(&PolicyContext::from_transaction(tx), chrono::Utc::now(), false)?;
+
+        let stored = self.keystore.read().await.get_account(account.address).await;
+        self.cold_accounts
+            .authorize(stored.as_ref().unwrap_or(&account), chrono::Utc::now())?;
+        self.approvals
+            .request(account.address, chain_id, ApprovalKind::transaction(tx))
+            .await?;
+        self.os_confirmation
+            .confirm(&HighRiskOperation::Transfer {
+                value: tx.value.unwrap_or_default(),
+            })
+            .await?;
+
+        tracing::debug!("📝 Signing transaction for chain {}", chain_id);
 
         // Ensure keystore is unlocked and accounts are loaded
         let mut keystore = self.keystore.write().await;
-        keystore.ensure_unlocked().await.map_err(|e| {
-            tracing::error!("❌ Failed to ensure keystore is unlocked: {}", e);
-            e
-        })?;
-
-        // Verify the account exists in the keystore before trying to sign
-        let keystore_accounts = keystore.list_accounts().await?;
-        tracing::info!("🔍 Keystore has {} accounts loaded", keystore_accounts.len());
-        for acc in &keystore_accounts {
-            tracing::info!("   - {} ({})", acc.name, acc.address);
-        }
-
-        let account_exists = keystore_accounts.iter().any(|a| a.address == account.address);
-        if !account_exists {
-            tracing::error!(
-                "❌ Account {} ({}) not found in keystore!",
-                account.name,
-                account.address
-            );
-            tracing::error!("   This account exists in GUI but not in wallet's keystore");
-            tracing::error!(
-                "   Available accounts: {:?}",
-                keystore_accounts.iter().map(|a| a.address).collect::<Vec<_>>()
-            );
+        keystore.ensure_unlocked().await?;
+        if !keystore.list_accounts().await?.iter().any(|a| a.address == account.address) {
             return Err(WalletError::AccountNotFound {
                 address: format!("{}", account.address),
             }
             .into());
         }
 
-        // Add diagnostics before calling keystore sign_transaction
-        tracing::info!(
-            "🔍 About to call keystore.sign_transaction with address: {}",
-            account.address
+        // The session's key source reads the keystore
+        drop(keystore);
+        let signing_started = std::time::Instant::now();
+        let signed = self
+            .session
+            .with_signer(account.address, |signer| encode_signed_transaction(tx, signer))
+            .await
+            .and_then(|signed| signed);
+        metrics::record_signing(chain_id, account.address, signing_started.elapsed(), signed.is_ok());
+        let signed = signed.map_err(|e| {
+            metrics::record_error(&e);
+            tracing::error!("❌ Keystore signing failed: {}", e);
+            e
+        })?;
+        audit::record(AuditEvent::sign_transaction(account.address, chain_id, tx), Uuid::new_v4());
+        Ok(signed)
+    }
+
+    /// Checks an off-chain signature by `address` passes before it is signed
+    ///
+    /// The same as for transactions: signing hours, the cold account unlock,
+    /// the approval frontend and the OS prompt. Signed messages and typed data
+    /// such as permits can authorize transfers too. Returns the signing chain ID.
+    async fn authorize_signature(&self, address: Address, kind: ApprovalKind) -> Result<u64> {
+        self.transaction_policy()?.check(&PolicyContext::default(), chrono::Utc::now(), false)?;
+        let account = self.keystore.read().await.get_account(address).await?;
+        self.cold_accounts.authorize(&account, chrono::Utc::now())?;
+        let chain_id = self.signing_chain_id().await;
+        self.approvals.request(address, chain_id, kind).await?;
+        self.os_confirmation
+            .confirm(&HighRiskOperation::Signature { address })
+            .await?;
+        Ok(chain_id)
+    }
+
+    /// Sign an EIP-191 `personal_sign` message with `address` once approved
+    pub async fn sign_message(&self, address: Address, message: &[u8]) -> Result<alloy::primitives::Signature> {
+        use alloy::signers::SignerSync;
+
+        let chain_id = self.authorize_signature(address, ApprovalKind::message(message)).await?;
+        let signature = self
+            .session
+            .with_signer(address, |signer| signer.sign_message_sync(message))
+            .await?
+            .map_err(|e| crate::error::SecurityError::KeystoreError {
+                message: format!("Failed to sign message: {e}"),
+            })?;
+        audit::record(
+            AuditEvent::SignMessage {
+                account: address,
+                chain_id,
+                hash: alloy::primitives::eip191_hash_message(message),
+            },
+            Uuid::new_v4(),
         );
+        Ok(signature)
+    }
 
-        // Pass the password for seed-based accounts
-        keystore
-            .sign_transaction(tx, &account.address, password, None)
-            .await
-            .map_err(|e| {
-                tracing::error!("❌ Keystore signing failed: {}", e);
-                tracing::error!("   Account: {} ({})", account.name, account.address);
-                e
-            })
+    /// Sign EIP-712 typed data with `address` once approved
+    pub async fn sign_typed_data(
+        &self,
+        address: Address,
+        data: &alloy::dyn_abi::TypedData,
+    ) -> Result<alloy::primitives::Signature> {
+        use alloy::signers::SignerSync;
+
+        let (kind, hash) = ApprovalKind::typed_data(data)?;
+        let chain_id = self.authorize_signature(address, kind).await?;
+        let signature = self
+            .session
+            .with_signer(address, |signer| signer.sign_hash_sync(&hash))
+            .await?
+            .map_err(|e| crate::error::SecurityError::KeystoreError {
+                message: format!("Failed to sign typed data: {e}"),
+            })?;
+        audit::record(
+            AuditEvent::SignTypedData {
+                account: address,
+                chain_id,
+                primary_type: data.primary_type.clone(),
+                verifying_contract: data.domain.verifying_contract,
+                hash,
+            },
+            Uuid::new_v4(),
+        );
+        Ok(signature)
     }
 
     /// Get balance for current account
     pub async fn get_balance(&self, token: Option<Address>) -> Result<U256> {
-        let current_account = self.current_account.read().await;
-
-        // If no current account, try to auto-select the first available account
-        let account = match current_account.as_ref() {
-            Some(acc) => acc.clone(),
-            None => {
-                // Auto-select first account if available
-                drop(current_account);
-                let keystore = self.keystore.read().await;
-                let accounts = keystore.list_accounts().await?;
-                if !accounts.is_empty() {
-                    let first_account = accounts[0].clone();
-                    let mut current = self.current_account.write().await;
-                    *current = Some(first_account.clone());
-                    tracing::info!(
-                        "🔓 Auto-unlocked with first account: {} ({})",
-                        first_account.name,
-                        first_account.address
-                    );
-                    first_account
-                } else {
-                    return Err(WalletError::WalletLocked.into());
-                }
-            }
-        };
+        let account = self.current_account().await.ok_or(WalletError::NoAccountSelected)?;
 
         let network_manager = self.network_config.read().await;
         network_manager.get_balance(account.address, token).await
@@ -290,24 +775,19 @@ impl Vaughan {
 
     /// Get current account
     pub async fn current_account(&self) -> Option<SecureAccount> {
-        self.current_account.read().await.clone()
+        self.account_manager.read().await.current().cloned()
     }
 
     /// List all accounts
     pub async fn list_accounts(&self) -> Result<Vec<SecureAccount>> {
-        let keystore = self.keystore.read().await;
-        keystore.list_accounts().await
+        let manager = self.account_manager.read().await;
+        Ok(manager.list_accounts().await?)
     }
 
     /// Switch to a different account
     pub async fn switch_account(&mut self, address: Address) -> Result<()> {
-        let keystore = self.keystore.read().await;
-        let account = keystore.get_account(address).await?;
-
-        let mut current = self.current_account.write().await;
-        *current = Some(account);
-
-        Ok(())
+        let mut manager = self.account_manager.write().await;
+        Ok(manager.set_current_account(address).await?)
     }
 
     // ========== Lock/Unlock Implementation with Conditional Compilation ==========
@@ -329,23 +809,14 @@ impl Vaughan {
             "🔒 Locking wallet - clearing sensitive data from memory"
         );
 
-        // Set locked state first
-        {
-            let mut locked = self.locked.write().await;
-            *locked = true;
-        }
-
-        // Clear current account (sensitive data)
-        {
-            let mut current = self.current_account.write().await;
-            // Note: SecureAccount doesn't contain raw private keys, but clearing
-            // the reference prevents unauthorized access to account operations
-            *current = None;
-        }
+        // Set locked state and clear the current account (sensitive data)
+        // Note: SecureAccount doesn't contain raw private keys, but clearing
+        // the reference prevents unauthorized access to account operations
+        self.account_manager.write().await.lock().await?;
 
-        // Note: The keystore itself holds encrypted data, not raw private keys
-        // Real private keys are only decrypted momentarily for signing operations
-        // and are not cached in the Vaughan struct
+        // The master password and keys derived for signing are the only
+        // plaintext secrets held
+        self.session.lock().await;
 
         tracing::info!(
             correlation_id = %correlation_id,
@@ -375,8 +846,7 @@ impl Vaughan {
     /// In test mode, always returns false.
     #[cfg(not(test))]
     pub async fn is_locked(&self) -> bool {
-        let locked = self.locked.read().await;
-        *locked
+        self.account_manager.read().await.is_locked()
     }
 
     /// Check if wallet is locked - ALWAYS RETURNS FALSE FOR TESTING
@@ -404,23 +874,16 @@ impl Vaughan {
         let account = keystore.get_account(address).await?;
         drop(keystore);
 
-        // Set current account
-        {
-            let mut current = self.current_account.write().await;
-            *current = Some(account);
-        }
-
-        // Clear locked state
-        {
-            let mut locked = self.locked.write().await;
-            *locked = false;
-        }
+        // Set current account and clear locked state
+        self.account_manager.write().await.unlock_with_account(account);
+        self.session.reactivate().await;
 
         tracing::info!(
             correlation_id = %correlation_id,
             address = %address,
             "🔓 Wallet unlocked successfully"
         );
+        audit::record(AuditEvent::Unlock { account: address }, correlation_id);
 
         Ok(())
     }
@@ -428,13 +891,7 @@ impl Vaughan {
     /// Unlock the wallet by setting a current account - TEST MODE
     #[cfg(test)]
     pub async fn unlock(&mut self, address: Address) -> Result<()> {
-        let keystore = self.keystore.read().await;
-        let account = keystore.get_account(address).await?;
-
-        let mut current = self.current_account.write().await;
-        *current = Some(account);
-
-        Ok(())
+        self.switch_account(address).await
     }
 
     /// Unlock the wallet with a provided account (for GUI integration)
@@ -450,23 +907,17 @@ impl Vaughan {
             name = %account.name,
             "🔓 Unlocking wallet with provided account"
         );
+        let address = account.address;
 
-        // Set current account
-        {
-            let mut current = self.current_account.write().await;
-            *current = Some(account.clone());
-        }
-
-        // Clear locked state
-        {
-            let mut locked = self.locked.write().await;
-            *locked = false;
-        }
+        // Set current account and clear locked state
+        self.account_manager.write().await.unlock_with_account(account);
+        self.session.reactivate().await;
 
         tracing::info!(
             correlation_id = %correlation_id,
             "🔓 Wallet unlocked successfully with provided account"
         );
+        audit::record(AuditEvent::Unlock { account: address }, correlation_id);
 
         Ok(())
     }
@@ -474,9 +925,7 @@ impl Vaughan {
     /// Unlock the wallet with a provided account - TEST MODE
     #[cfg(test)]
     pub async fn unlock_with_account(&mut self, account: SecureAccount) -> Result<()> {
-        let mut current = self.current_account.write().await;
-        *current = Some(account);
-
+        self.account_manager.write().await.select(account);
         Ok(())
     }
 
@@ -495,13 +944,28 @@ impl Vaughan {
     /// Add a custom network
     pub async fn add_custom_network(&mut self, config: NetworkConfig) -> Result<()> {
         let mut network_manager = self.network_config.write().await;
-        network_manager.add_custom_network(config).await
+        network_manager.add_custom_network(config.clone()).await?;
+        audit::record(
+            AuditEvent::AddNetwork {
+                chain_id: config.chain_id,
+                name: config.name.clone(),
+            },
+            Uuid::new_v4(),
+        );
+        self.config_history.record(ConfigChange::NetworkAdded(config));
+        Ok(())
     }
 
     /// Update or replace a custom network (handles chain ID changes)
     pub async fn update_or_replace_custom_network(&mut self, old_id: NetworkId, config: NetworkConfig) -> Result<()> {
         let mut network_manager = self.network_config.write().await;
-        network_manager.update_or_replace_custom_network(old_id, config).await
+        let before = network_manager.get_all_networks().get(&old_id).cloned();
+        network_manager.update_or_replace_custom_network(old_id, config.clone()).await?;
+        match before {
+            Some(before) => self.config_history.record(ConfigChange::NetworkUpdated { before, after: config }),
+            None => self.config_history.record(ConfigChange::NetworkAdded(config)),
+        }
+        Ok(())
     }
 
     /// Get gas price for current network
@@ -522,33 +986,286 @@ impl Vaughan {
         network_manager.get_transaction_count(address).await
     }
 
+    /// Reserve a nonce for the next transaction, accounting for in-flight sends
+    pub async fn reserve_nonce(&self, address: Address) -> Result<u64> {
+        let network_manager = self.network_config.read().await;
+        network_manager.reserve_nonce(address).await
+    }
+
+    /// Give back a nonce from [`Self::reserve_nonce`] whose transaction was never broadcast
+    pub async fn release_nonce(&self, address: Address, nonce: u64) {
+        let network_manager = self.network_config.read().await;
+        network_manager.release_nonce(address, nonce);
+    }
+
     /// Broadcast a signed transaction to the network
+    ///
+    /// The transaction is watched until final; see [`Self::subscribe_transaction_events`].
     pub async fn broadcast_transaction(&self, signed_tx: &[u8]) -> Result<alloy::primitives::TxHash> {
         let network_manager = self.network_config.read().await;
         network_manager.send_raw_transaction(signed_tx).await
     }
 
+    /// Subscribe to inclusion, confirmation, failure and drop events of broadcast transactions
+    pub async fn subscribe_transaction_events(
+        &self,
+    ) -> tokio::sync::broadcast::Receiver<crate::network::tx_watcher::TxEvent> {
+        let network_manager = self.network_config.read().await;
+        network_manager.subscribe_tx_events()
+    }
+
     /// Remove a custom network (handles current selection fallback)
     pub async fn remove_custom_network(&mut self, network_id: NetworkId) -> Result<()> {
         let mut network_manager = self.network_config.write().await;
-        network_manager.remove_custom_network(network_id).await
+        let removed = network_manager.get_all_networks().get(&network_id).cloned();
+        network_manager.remove_custom_network(network_id).await?;
+        if let Some(removed) = removed {
+            self.config_history.record(ConfigChange::NetworkRemoved(removed));
+        }
+        Ok(())
     }
 
-    /// Remove an account from the wallet
-    pub async fn remove_account(&mut self, address: Address) -> Result<()> {
-        // Check if this is the current account
-        let current_account = self.current_account.read().await;
-        if let Some(ref account) = *current_account {
-            if account.address == address {
-                drop(current_account);
-                // Clear current account if we're removing it
-                let mut current = self.current_account.write().await;
-                *current = None;
-            }
+    /// Hide a token, recording the change for undo
+    pub fn hide_token(
+        &mut self,
+        tokens: &mut TokenManager,
+        token: &TokenInfo,
+        reason: Option<String>,
+    ) -> Result<()> {
+        let network_id = NetworkId(token.chain_id);
+        tokens.hide_token(token, reason)?;
+        if let Some(hidden) = tokens.hidden_token(network_id, token.address) {
+            self.config_history.record(ConfigChange::TokenHidden {
+                chain_id: token.chain_id,
+                token: hidden,
+            });
+        }
+        Ok(())
+    }
+
+    /// Unhide a token, recording the change for undo
+    pub fn unhide_token(&mut self, tokens: &mut TokenManager, network_id: NetworkId, address: Address) -> Result<()> {
+        let hidden = tokens.hidden_token(network_id, address);
+        tokens.unhide_token(network_id, address)?;
+        if let Some(hidden) = hidden {
+            self.config_history.record(ConfigChange::TokenUnhidden {
+                chain_id: network_id.0,
+                token: hidden,
+            });
+        }
+        Ok(())
+    }
+
+    /// Change how a category of outbound connections is handled, recording the change for undo
+    pub fn set_egress_category_mode(&mut self, category: EgressCategory, mode: CategoryMode) -> Result<()> {
+        let before = egress().policy();
+        egress().set_category_mode(category, mode)?;
+        self.record_egress_change(before);
+        Ok(())
+    }
+
+    /// Approve or block a host, recording the change for undo
+    pub fn set_egress_host(&mut self, host: &str, allowed: bool) -> Result<()> {
+        let before = egress().policy();
+        egress().set_host(host, allowed)?;
+        self.record_egress_change(before);
+        Ok(())
+    }
+
+    fn record_egress_change(&mut self, before: EgressPolicy) {
+        let after = egress().policy();
+        if after != before {
+            self.config_history.record(ConfigChange::EgressPolicyChanged { before, after });
         }
+    }
+
+    /// Recorded configuration changes
+    pub fn config_history(&self) -> &ConfigHistory {
+        &self.config_history
+    }
+
+    /// Rules checked before signing
+    ///
+    /// Fails while the policy file is invalid, which refuses all signing
+    /// until the file is fixed or [`Self::set_transaction_policy`] replaces it.
+    pub fn transaction_policy(&self) -> Result<&TransactionPolicy> {
+        self.tx_policy.as_ref().map_err(Clone::clone)
+    }
+
+    pub fn os_confirmation_policy(&self) -> OsConfirmationPolicy {
+        self.os_confirmation.policy()
+    }
+
+    /// Require OS authentication for the operations `policy` covers
+    pub fn set_os_confirmation_policy(&mut self, policy: OsConfirmationPolicy) {
+        self.os_confirmation.set_policy(policy);
+    }
 
+    /// Keep the OS confirmation policy in step with the `security` settings
+    pub fn follow_security_settings(&self, settings: SectionWatch<SecurityPolicySettings>) {
+        self.os_confirmation.follow_settings(settings);
+    }
+
+    /// Unlock rituals for signing from cold accounts
+    pub fn cold_accounts(&self) -> &ColdAccountGuard {
+        &self.cold_accounts
+    }
+
+    /// Mark `address` as a cold account, or clear the mark
+    ///
+    /// Clearing the mark needs a completed unlock ritual, like signing does.
+    pub async fn set_cold_account(&self, address: Address, cold: bool) -> Result<()> {
         let mut keystore = self.keystore.write().await;
-        keystore.remove_account(address).await
+        let account = keystore.get_account(address).await?;
+        if cold_account::is_cold(&account) == cold {
+            return Ok(());
+        }
+        let mut tags = account.tags.clone();
+        if cold {
+            tags.push(cold_account::COLD_TAG.to_string());
+        } else {
+            self.cold_accounts.authorize(&account, chrono::Utc::now())?;
+            tags.retain(|t| t != cold_account::COLD_TAG);
+        }
+        keystore.update_account_tags(address, tags).await?;
+        tracing::info!("🧊 Account {} cold: {}", address, cold);
+        Ok(())
+    }
+
+    /// Replace the transaction policy and save it to the default file
+    pub fn set_transaction_policy(&mut self, policy: TransactionPolicy) -> Result<()> {
+        policy.save_to(&TransactionPolicy::default_path())?;
+        self.tx_policy = Ok(policy);
+        Ok(())
+    }
+
+    /// Revert up to `count` of the most recent configuration changes
+    ///
+    /// Token changes need `tokens`; undoing stops at the first one when it is
+    /// `None`, or at the first change that fails to revert. Returns the
+    /// changes that were undone, most recent first.
+    pub async fn undo_config_changes(
+        &mut self,
+        count: usize,
+        mut tokens: Option<&mut TokenManager>,
+    ) -> Result<Vec<ConfigChange>> {
+        let mut undone = Vec::new();
+        while undone.len() < count {
+            let Some(change) = self.config_history.next_undo().cloned() else {
+                break;
+            };
+            self.apply_config_change(&change.inverse(), tokens.as_deref_mut()).await?;
+            self.config_history.mark_undone();
+            tracing::info!("↩️ Undid: {}", change.description());
+            undone.push(change);
+        }
+        Ok(undone)
+    }
+
+    /// Re-apply up to `count` of the most recently undone configuration changes
+    pub async fn redo_config_changes(
+        &mut self,
+        count: usize,
+        mut tokens: Option<&mut TokenManager>,
+    ) -> Result<Vec<ConfigChange>> {
+        let mut redone = Vec::new();
+        while redone.len() < count {
+            let Some(change) = self.config_history.next_redo().cloned() else {
+                break;
+            };
+            self.apply_config_change(&change, tokens.as_deref_mut()).await?;
+            self.config_history.mark_redone();
+            tracing::info!("↪️ Redid: {}", change.description());
+            redone.push(change);
+        }
+        Ok(redone)
+    }
+
+    async fn apply_config_change(&mut self, change: &ConfigChange, tokens: Option<&mut TokenManager>) -> Result<()> {
+        match change {
+            ConfigChange::NetworkAdded(config) => {
+                let mut network_manager = self.network_config.write().await;
+                network_manager.restore_custom_network(config.id, config.clone()).await
+            }
+            ConfigChange::NetworkRemoved(config) => {
+                let mut network_manager = self.network_config.write().await;
+                network_manager.remove_custom_network(config.id).await
+            }
+            ConfigChange::NetworkUpdated { before, after } => {
+                let mut network_manager = self.network_config.write().await;
+                network_manager.restore_custom_network(before.id, after.clone()).await
+            }
+            ConfigChange::TokenHidden { chain_id, token } => {
+                let tokens = tokens.ok_or_else(token_manager_required)?;
+                tokens.restore_hidden_token(NetworkId(*chain_id), token.clone())
+            }
+            ConfigChange::TokenUnhidden { chain_id, token } => {
+                let tokens = tokens.ok_or_else(token_manager_required)?;
+                tokens.unhide_token(NetworkId(*chain_id), token.address)
+            }
+            ConfigChange::EgressPolicyChanged { after, .. } => egress().set_policy(after.clone()),
+            ConfigChange::WalletConfigChanged { after, .. } => {
+                let before = std::mem::replace(&mut self.config, after.clone());
+                self.apply_session_config(&before).await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Remove an account from the wallet
+    ///
+    /// A recovery point encrypted with `backup_password` is created first.
+    pub async fn remove_account(&mut self, address: Address, backup_password: &SecretString) -> Result<()> {
+        let keystore = self.keystore.read().await;
+        keystore.get_account(address).await?;
+        backup::RecoveryRegistry::open_default()
+            .snapshot(&keystore, backup_password, backup::DestructiveOperation::RemoveAccount { address })
+            .await?;
+        drop(keystore);
+
+        // Also clears the current account if we removed it
+        let mut manager = self.account_manager.write().await;
+        let token = AuthToken::new(AuthorizedOperation::RemoveAccount);
+        manager.remove_account(address, token).await?;
+        audit::record(AuditEvent::RemoveAccount { account: address }, Uuid::new_v4());
+        Ok(())
+    }
+
+    /// Re-encrypt under the master password in `stores` instead of the default locations
+    pub fn with_password_stores(mut self, stores: password_change::PasswordStores) -> Self {
+        self.password_stores = stores;
+        self
+    }
+
+    /// Count failed unlocks with `throttle` instead of the one in the `.vaughan` directory
+    pub fn with_unlock_throttle(mut self, throttle: UnlockThrottle) -> Self {
+        self.unlock_throttle = tokio::sync::Mutex::new(throttle);
+        self
+    }
+
+    /// Change the master password
+    ///
+    /// The new password must pass the default [`PasswordPolicy`]. A recovery
+    /// point encrypted with the old password is taken first. Every stored
+    /// seed, `keystore.json`, the legacy `wallet.json` and the wallet
+    /// configuration are then re-encrypted; if any step fails, everything
+    /// already changed is rolled back to the old password.
+    pub async fn change_master_password(&self, old_password: &SecretString, new_password: &SecretString) -> Result<()> {
+        PasswordPolicy::default().check(new_password)?;
+
+        let keystore = self.keystore.read().await;
+        backup::RecoveryRegistry::open(&self.password_stores.recovery_dir)
+            .snapshot(&keystore, old_password, backup::DestructiveOperation::ChangePassword)
+            .await?;
+        password_change::reencrypt_all(&keystore, &self.password_stores, old_password, new_password).await?;
+        drop(keystore);
+
+        // Seeds are now only readable with the new password
+        if self.session.has_master_password().await {
+            self.session.unlock(new_password.clone()).await;
+        }
+        tracing::info!("🔑 Master password changed");
+        Ok(())
     }
 
     /// Get wallet configuration
@@ -557,8 +1274,14 @@ impl Vaughan {
     }
 
     /// Update wallet configuration
-    pub fn update_config(&mut self, config: WalletConfig) {
-        self.config = config;
+    ///
+    /// New auto-lock and signing lock timeouts take effect immediately.
+    pub async fn update_config(&mut self, config: WalletConfig) {
+        if config != self.config {
+            let before = std::mem::replace(&mut self.config, config.clone());
+            self.apply_session_config(&before).await;
+            self.config_history.record(ConfigChange::WalletConfigChanged { before, after: config });
+        }
     }
 
     /// Get current account address (for GUI compatibility)
@@ -570,8 +1293,7 @@ impl Vaughan {
 
     /// Get current secure account (async version for deployment operations)
     pub async fn get_current_secure_account(&self) -> Option<SecureAccount> {
-        let current = self.current_account.read().await;
-        current.clone()
+        self.current_account().await
     }
 
     /// Check if wallet is connected (for GUI compatibility)
@@ -594,8 +1316,7 @@ impl Vaughan {
     /// Get private key for current account (for forge deployment)
     /// ⚠️ USE WITH CAUTION - exposes raw private key
     pub async fn get_private_key_for_deployment(&self) -> Result<SecretString> {
-        let current_account = self.current_account.read().await;
-        let account = current_account.as_ref().ok_or(WalletError::WalletLocked)?;
+        let account = self.current_account().await.ok_or(WalletError::WalletLocked)?;
 
         // Retrieve private key from keystore
         let keystore = self.keystore.read().await;
@@ -663,16 +1384,36 @@ impl Vaughan {
     }
 
     /// Sign transaction with hardware wallet
+    ///
+    /// Confirming on the device overrides transaction policy rules that allow it.
+    /// Signs as `tx.from`, or the current account when it is unset, and waits
+    /// for the approval frontend like software signing.
     pub async fn sign_transaction_with_hardware(
         &self,
         device_index: usize,
         tx: &TransactionRequest,
         derivation_path: &str,
     ) -> Result<Vec<u8>> {
+        let chain_id = self.signing_chain_id().await;
+        let tx = &resolve_chain_id(tx, chain_id)?;
+        self.transaction_policy()?.check(&PolicyContext::from_transaction(tx), chrono::Utc::now(), true)?;
+        if let Some(from) = tx.from {
+            if let Ok(account) = self.keystore.read().await.get_account(from).await {
+                self.cold_accounts.authorize(&account, chrono::Utc::now())?;
+            }
+        }
+        let signer = match tx.from {
+            Some(from) => from,
+            None => self.current_account().await.ok_or(WalletError::NoAccountSelected)?.address,
+        };
+        self.approvals
+            .request(signer, chain_id, ApprovalKind::transaction(tx))
+            .await?;
         let hw_manager_guard = self.hardware_manager.read().await;
 
         if let Some(ref hw_manager) = *hw_manager_guard {
             let signature = hw_manager.sign_transaction(device_index, tx, derivation_path).await?;
+            audit::record(AuditEvent::sign_transaction(signer, chain_id, tx), Uuid::new_v4());
 
             // Convert Signature to bytes
             // Note: This is a simplified conversion - in practice you might need
@@ -791,6 +1532,7 @@ impl Vaughan {
                     "Use software wallet as fallback".to_string(),
                 ],
                 risk_level: RiskLevel::High,
+                call_preview: crate::abi::describe_transaction(tx),
             })
         }
     }
@@ -864,3 +1606,29 @@ impl Vaughan {
         }
     }
 }
+
+impl Drop for Vaughan {
+    fn drop(&mut self) {
+        if let Some(monitor) = self.auto_lock_monitor.take() {
+            monitor.abort();
+        }
+        if let Some(task) = self.balance_poll_task.take() {
+            task.abort();
+        }
+    }
+}
+
+/// Session timeouts for `config`
+fn session_config(config: &WalletConfig) -> SessionConfig {
+    SessionConfig {
+        auto_lock_timeout: config.auto_lock_timeout,
+        warning_before: config.auto_lock_warning,
+        signing_lock_timeout: config.signing_lock_timeout,
+        key_ttl: config.auto_lock_timeout.unwrap_or(DEFAULT_KEY_TTL),
+        ..SessionConfig::default()
+    }
+}
+
+fn token_manager_required() -> crate::error::VaughanError {
+    crate::error::VaughanError::ValidationError("Reverting token changes needs the token manager".to_string())
+}
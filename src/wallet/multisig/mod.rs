@@ -0,0 +1,22 @@
+//! Multisig wallets
+//!
+//! Lets a wallet account act as one owner of a multisig:
+//!
+//! - [`owners`] - checked owner add, remove and swap and threshold changes
+//! - [`safe`] - Safe detection, owners and threshold, EIP-712 transaction
+//!   hashes, owner signatures and `execTransaction`
+//! - [`service`] - the Safe Transaction Service, where owners propose
+//!   transactions and collect each other's signatures
+//!
+//! A typical flow: one owner builds a [`SafeTx`] with the Safe's current
+//! nonce, signs it and proposes it; the other owners fetch and confirm it;
+//! once the threshold is met any account submits
+//! [`Safe::execution_request`] as a regular transaction.
+
+pub mod owners;
+pub mod safe;
+pub mod service;
+
+pub use owners::{OwnerChange, OwnerChangePlan};
+pub use safe::{Safe, SafeInfo, SafeOperation, SafeSignature, SafeTx};
+pub use service::SafeTransactionService;
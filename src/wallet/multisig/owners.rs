@@ -0,0 +1,242 @@
+//! Safe owner and threshold changes
+//!
+//! Adding, removing and swapping owners or changing the threshold is a Safe
+//! transaction to the Safe itself. [`Safe::owner_change`] builds it from the
+//! current [`SafeInfo`] and refuses changes that would lock the Safe: a
+//! threshold of zero, a threshold above the owner count, or removing an owner
+//! the threshold still needs. The resulting [`SafeTx`] is signed, proposed and
+//! executed like any other through [`Safe::sign`].
+
+use super::safe::{Safe, SafeInfo, SafeTx};
+use crate::error::{Result, VaughanError};
+use alloy::primitives::{address, Address, Bytes, U256};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use serde::{Deserialize, Serialize};
+
+sol! {
+    interface ISafeOwnerManager {
+        function addOwnerWithThreshold(address owner, uint256 _threshold) external;
+        function removeOwner(address prevOwner, address owner, uint256 _threshold) external;
+        function swapOwner(address prevOwner, address oldOwner, address newOwner) external;
+        function changeThreshold(uint256 _threshold) external;
+    }
+}
+
+/// Head of the Safe's owner linked list
+pub const SENTINEL_OWNERS: Address = address!("0000000000000000000000000000000000000001");
+
+/// A change to a Safe's owners or threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OwnerChange {
+    Add { owner: Address, threshold: u64 },
+    Remove { owner: Address, threshold: u64 },
+    Swap { old_owner: Address, new_owner: Address },
+    ChangeThreshold { threshold: u64 },
+}
+
+/// A checked owner change, ready to sign
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnerChangePlan {
+    /// Transaction from the Safe to itself
+    pub tx: SafeTx,
+    pub owners_after: Vec<Address>,
+    pub threshold_after: u64,
+    /// Risks that do not block the change, shown to the user before signing
+    pub warnings: Vec<String>,
+}
+
+fn invalid(message: String) -> VaughanError {
+    VaughanError::ValidationError(message)
+}
+
+/// Owner before `owner` in the Safe's linked list, which `getOwners` returns in order
+fn previous_owner(owners: &[Address], owner: Address) -> Result<Address> {
+    match owners.iter().position(|o| *o == owner) {
+        Some(0) => Ok(SENTINEL_OWNERS),
+        Some(i) => Ok(owners[i - 1]),
+        None => Err(invalid(format!("{owner} is not an owner of this Safe"))),
+    }
+}
+
+impl Safe {
+    /// Build the Safe transaction for `change` at the Safe's current nonce
+    pub fn owner_change(&self, info: &SafeInfo, change: OwnerChange) -> Result<OwnerChangePlan> {
+        let check_new_owner = |owner: Address| {
+            if owner == Address::ZERO || owner == SENTINEL_OWNERS || owner == self.address {
+                return Err(invalid(format!("{owner} cannot be a Safe owner")));
+            }
+            if info.is_owner(owner) {
+                return Err(invalid(format!("{owner} is already an owner of this Safe")));
+            }
+            Ok(())
+        };
+
+        let mut owners = info.owners.clone();
+        let (data, threshold) = match change {
+            OwnerChange::Add { owner, threshold } => {
+                check_new_owner(owner)?;
+                owners.insert(0, owner);
+                let call = ISafeOwnerManager::addOwnerWithThresholdCall {
+                    owner,
+                    _threshold: U256::from(threshold),
+                };
+                (call.abi_encode(), threshold)
+            }
+            OwnerChange::Remove { owner, threshold } => {
+                let prev_owner = previous_owner(&owners, owner)?;
+                owners.retain(|o| *o != owner);
+                if owners.is_empty() {
+                    return Err(invalid("Cannot remove the last owner of a Safe".to_string()));
+                }
+                let call = ISafeOwnerManager::removeOwnerCall {
+                    prevOwner: prev_owner,
+                    owner,
+                    _threshold: U256::from(threshold),
+                };
+                (call.abi_encode(), threshold)
+            }
+            OwnerChange::Swap { old_owner, new_owner } => {
+                let prev_owner = previous_owner(&owners, old_owner)?;
+                check_new_owner(new_owner)?;
+                for o in owners.iter_mut().filter(|o| **o == old_owner) {
+                    *o = new_owner;
+                }
+                let call = ISafeOwnerManager::swapOwnerCall {
+                    prevOwner: prev_owner,
+                    oldOwner: old_owner,
+                    newOwner: new_owner,
+                };
+                (call.abi_encode(), info.threshold)
+            }
+            OwnerChange::ChangeThreshold { threshold } => {
+                let call = ISafeOwnerManager::changeThresholdCall {
+                    _threshold: U256::from(threshold),
+                };
+                (call.abi_encode(), threshold)
+            }
+        };
+
+        if threshold == 0 {
+            return Err(invalid("Safe threshold must be at least 1".to_string()));
+        }
+        if threshold > owners.len() as u64 {
+            return Err(invalid(format!(
+                "Threshold {threshold} exceeds the {} owners the Safe would have",
+                owners.len()
+            )));
+        }
+
+        let mut warnings = Vec::new();
+        if owners.len() == 1 {
+            warnings.push("The Safe would have a single owner; losing that key loses the Safe".to_string());
+        } else if threshold == 1 {
+            warnings.push(format!("Any one of the {} owners could move funds alone", owners.len()));
+        }
+
+        Ok(OwnerChangePlan {
+            tx: SafeTx::call(self.address, U256::ZERO, Bytes::from(data), info.nonce),
+            owners_after: owners,
+            threshold_after: threshold,
+            warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (Safe, SafeInfo) {
+        let safe = Safe {
+            address: Address::repeat_byte(0x5a),
+            chain_id: 1,
+            version: "1.4.1".to_string(),
+        };
+        let info = SafeInfo {
+            owners: vec![
+                Address::repeat_byte(1),
+                Address::repeat_byte(2),
+                Address::repeat_byte(3),
+            ],
+            threshold: 2,
+            nonce: U256::from(4),
+        };
+        (safe, info)
+    }
+
+    #[test]
+    fn test_remove_and_swap_use_previous_owner() {
+        let (safe, info) = setup();
+        let plan = safe
+            .owner_change(
+                &info,
+                OwnerChange::Remove {
+                    owner: Address::repeat_byte(2),
+                    threshold: 2,
+                },
+            )
+            .unwrap();
+        assert_eq!(plan.tx.to, safe.address);
+        assert_eq!(plan.tx.nonce, U256::from(4));
+        let call = ISafeOwnerManager::removeOwnerCall::abi_decode(&plan.tx.data).unwrap();
+        assert_eq!(call.prevOwner, Address::repeat_byte(1));
+        assert_eq!(plan.owners_after, [Address::repeat_byte(1), Address::repeat_byte(3)]);
+        assert!(plan.warnings.is_empty());
+
+        let plan = safe
+            .owner_change(
+                &info,
+                OwnerChange::Swap {
+                    old_owner: Address::repeat_byte(1),
+                    new_owner: Address::repeat_byte(9),
+                },
+            )
+            .unwrap();
+        let call = ISafeOwnerManager::swapOwnerCall::abi_decode(&plan.tx.data).unwrap();
+        assert_eq!(call.prevOwner, SENTINEL_OWNERS);
+        assert_eq!(plan.owners_after[0], Address::repeat_byte(9));
+    }
+
+    #[test]
+    fn test_sanity_checks() {
+        let (safe, mut info) = setup();
+        // Two owners left cannot meet a threshold of 3
+        assert!(safe
+            .owner_change(
+                &info,
+                OwnerChange::Remove {
+                    owner: Address::repeat_byte(3),
+                    threshold: 3,
+                },
+            )
+            .is_err());
+        assert!(safe
+            .owner_change(&info, OwnerChange::ChangeThreshold { threshold: 0 })
+            .is_err());
+        assert!(safe
+            .owner_change(
+                &info,
+                OwnerChange::Add {
+                    owner: Address::repeat_byte(2),
+                    threshold: 2,
+                },
+            )
+            .is_err());
+
+        info.owners.truncate(2);
+        info.threshold = 1;
+        let plan = safe
+            .owner_change(
+                &info,
+                OwnerChange::Remove {
+                    owner: Address::repeat_byte(1),
+                    threshold: 1,
+                },
+            )
+            .unwrap();
+        assert_eq!(plan.owners_after, [Address::repeat_byte(2)]);
+        assert_eq!(plan.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,394 @@
+//! Safe (formerly Gnosis Safe) multisig accounts
+//!
+//! A Safe executes a transaction once `threshold` of its owners have signed
+//! the transaction's EIP-712 hash. Owners sign independently, usually
+//! sharing signatures through the Safe Transaction Service, and any account
+//! can then submit `execTransaction` with the collected signatures.
+
+use crate::error::{Result, VaughanError};
+use alloy::primitives::{Address, Bytes, Signature, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::Signer;
+use alloy::sol;
+use alloy::sol_types::{eip712_domain, Eip712Domain, SolCall, SolStruct};
+use serde::{Deserialize, Serialize};
+
+sol! {
+    interface ISafe {
+        function VERSION() external view returns (string);
+        function getOwners() external view returns (address[]);
+        function getThreshold() external view returns (uint256);
+        function nonce() external view returns (uint256);
+        function execTransaction(
+            address to,
+            uint256 value,
+            bytes data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            bytes signatures
+        ) external payable returns (bool success);
+    }
+
+    /// Transaction the owners sign, hashed per EIP-712
+    #[derive(Debug, PartialEq, Eq)]
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+}
+
+/// How the Safe performs a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum SafeOperation {
+    Call = 0,
+    /// Runs the target's code in the Safe's context; only for trusted modules
+    DelegateCall = 1,
+}
+
+impl SafeTx {
+    /// Plain call from the Safe, paying its own gas without refunds
+    pub fn call(to: Address, value: U256, data: Bytes, nonce: U256) -> Self {
+        Self {
+            to,
+            value,
+            data,
+            operation: SafeOperation::Call as u8,
+            safeTxGas: U256::ZERO,
+            baseGas: U256::ZERO,
+            gasPrice: U256::ZERO,
+            gasToken: Address::ZERO,
+            refundReceiver: Address::ZERO,
+            nonce,
+        }
+    }
+}
+
+/// One owner's signature of a Safe transaction hash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafeSignature {
+    pub signer: Address,
+    pub signature: Bytes,
+}
+
+impl SafeSignature {
+    /// Owner that produced the signature, for ECDSA and `eth_sign` signatures
+    pub fn recover(&self, safe_tx_hash: B256) -> Option<Address> {
+        let bytes = self.signature.as_ref();
+        if bytes.len() != 65 {
+            return None;
+        }
+        match bytes[64] {
+            27 | 28 => Signature::try_from(bytes)
+                .ok()?
+                .recover_address_from_prehash(&safe_tx_hash)
+                .ok(),
+            // eth_sign signatures have 4 added to v and sign the EIP-191 message
+            31 | 32 => {
+                let mut adjusted = bytes.to_vec();
+                adjusted[64] -= 4;
+                Signature::try_from(adjusted.as_slice())
+                    .ok()?
+                    .recover_address_from_msg(safe_tx_hash.as_slice())
+                    .ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Owners, threshold and nonce read from the chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafeInfo {
+    pub owners: Vec<Address>,
+    pub threshold: u64,
+    /// Nonce the next transaction must use
+    pub nonce: U256,
+}
+
+impl SafeInfo {
+    pub fn is_owner(&self, address: Address) -> bool {
+        self.owners.contains(&address)
+    }
+}
+
+/// A Safe on one chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Safe {
+    pub address: Address,
+    pub chain_id: u64,
+    /// Contract version, e.g. `1.4.1`
+    pub version: String,
+}
+
+async fn read<P: Provider, C: SolCall>(provider: &P, address: Address, call: C) -> Result<C::Return> {
+    let raw = provider
+        .call(
+            TransactionRequest::default()
+                .to(address)
+                .input(Bytes::from(call.abi_encode()).into()),
+        )
+        .await
+        .map_err(|e| VaughanError::rpc(&format!("Failed to call {} on Safe {address}", C::SIGNATURE), e))?;
+    C::abi_decode_returns(&raw).map_err(|e| VaughanError::rpc(&format!("Invalid {} response", C::SIGNATURE), e))
+}
+
+impl Safe {
+    /// The Safe at `address`, or `None` when no Safe is deployed there
+    ///
+    /// Works through proxies: a contract counts as a Safe when it answers
+    /// `VERSION()`, `getThreshold()` and `getOwners()`.
+    pub async fn detect<P: Provider>(provider: &P, address: Address, chain_id: u64) -> Result<Option<Self>> {
+        let code = provider
+            .get_code_at(address)
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to fetch contract code", e))?;
+        if code.is_empty() {
+            return Ok(None);
+        }
+        let Ok(version) = read(provider, address, ISafe::VERSIONCall {}).await else {
+            return Ok(None);
+        };
+        let is_safe = read(provider, address, ISafe::getThresholdCall {}).await.is_ok()
+            && read(provider, address, ISafe::getOwnersCall {}).await.is_ok();
+        Ok(is_safe.then_some(Self {
+            address,
+            chain_id,
+            version,
+        }))
+    }
+
+    pub async fn info<P: Provider>(&self, provider: &P) -> Result<SafeInfo> {
+        let owners = read(provider, self.address, ISafe::getOwnersCall {}).await?;
+        let threshold = read(provider, self.address, ISafe::getThresholdCall {}).await?;
+        let nonce = read(provider, self.address, ISafe::nonceCall {}).await?;
+        Ok(SafeInfo {
+            owners,
+            threshold: threshold.saturating_to(),
+            nonce,
+        })
+    }
+
+    /// Versions before 1.3.0 leave the chain ID out of the domain
+    fn domain(&self) -> Eip712Domain {
+        let mut parts = self.version.split('.').map(|p| p.parse::<u32>().unwrap_or(0));
+        let (major, minor) = (parts.next().unwrap_or(0), parts.next().unwrap_or(0));
+        if (major, minor) >= (1, 3) {
+            eip712_domain! {
+                chain_id: self.chain_id,
+                verifying_contract: self.address,
+            }
+        } else {
+            eip712_domain! {
+                verifying_contract: self.address,
+            }
+        }
+    }
+
+    /// Hash the owners sign, as computed by `getTransactionHash`
+    pub fn transaction_hash(&self, tx: &SafeTx) -> B256 {
+        tx.eip712_signing_hash(&self.domain())
+    }
+
+    /// Sign `tx` as one of the owners
+    pub async fn sign(&self, tx: &SafeTx, owner: &(dyn Signer + Send + Sync)) -> Result<SafeSignature> {
+        let signature = owner
+            .sign_hash(&self.transaction_hash(tx))
+            .await
+            .map_err(|e| VaughanError::ValidationError(format!("Failed to sign Safe transaction: {e}")))?;
+        Ok(SafeSignature {
+            signer: owner.address(),
+            signature: Bytes::from(signature.as_bytes().to_vec()),
+        })
+    }
+
+    /// Distinct owners whose signatures of `tx` are valid
+    pub fn valid_signers(&self, tx: &SafeTx, signatures: &[SafeSignature], info: &SafeInfo) -> Vec<Address> {
+        let hash = self.transaction_hash(tx);
+        let mut signers: Vec<Address> = signatures
+            .iter()
+            .filter(|s| s.recover(hash) == Some(s.signer) && info.is_owner(s.signer))
+            .map(|s| s.signer)
+            .collect();
+        signers.sort();
+        signers.dedup();
+        signers
+    }
+
+    /// `execTransaction` call sent by `executor` once the threshold is met
+    ///
+    /// Signatures are ordered by owner address as the Safe requires; invalid
+    /// signatures and ones from non-owners are dropped.
+    pub fn execution_request(
+        &self,
+        tx: &SafeTx,
+        signatures: &[SafeSignature],
+        info: &SafeInfo,
+        executor: Address,
+    ) -> Result<TransactionRequest> {
+        if tx.nonce != info.nonce {
+            return Err(VaughanError::ValidationError(format!(
+                "Safe transaction nonce {} does not match the Safe's nonce {}",
+                tx.nonce, info.nonce
+            )));
+        }
+        let signers = self.valid_signers(tx, signatures, info);
+        if (signers.len() as u64) < info.threshold {
+            return Err(VaughanError::ValidationError(format!(
+                "Safe {} needs {} signatures, has {}",
+                self.address,
+                info.threshold,
+                signers.len()
+            )));
+        }
+
+        let mut packed = Vec::with_capacity(signers.len() * 65);
+        for signer in &signers {
+            if let Some(s) = signatures.iter().find(|s| s.signer == *signer) {
+                packed.extend_from_slice(&s.signature);
+            }
+        }
+        let call = ISafe::execTransactionCall {
+            to: tx.to,
+            value: tx.value,
+            data: tx.data.clone(),
+            operation: tx.operation,
+            safeTxGas: tx.safeTxGas,
+            baseGas: tx.baseGas,
+            gasPrice: tx.gasPrice,
+            gasToken: tx.gasToken,
+            refundReceiver: tx.refundReceiver,
+            signatures: packed.into(),
+        };
+        Ok(TransactionRequest::default()
+            .from(executor)
+            .to(self.address)
+            .input(Bytes::from(call.abi_encode()).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::ProviderBuilder;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::sol_types::SolValue;
+    use alloy::transports::mock::Asserter;
+
+    const SAFE: Address = Address::repeat_byte(0x5a);
+
+    fn safe_at(version: &str) -> Safe {
+        Safe {
+            address: SAFE,
+            chain_id: 1,
+            version: version.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_detect_and_load_info() {
+        let owners = vec![Address::repeat_byte(0x01), Address::repeat_byte(0x02)];
+        let asserter = Asserter::new();
+        asserter.push_success(&Bytes::from_static(&[0x60, 0x80])); // code
+        asserter.push_success(&Bytes::from("1.4.1".to_string().abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(2).abi_encode()));
+        asserter.push_success(&Bytes::from(owners.abi_encode()));
+        asserter.push_success(&Bytes::from(owners.abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(2).abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(7).abi_encode()));
+        asserter.push_success(&Bytes::new()); // plain account
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+
+        let found = Safe::detect(&provider, SAFE, 1).await.unwrap().unwrap();
+        assert_eq!(found, safe_at("1.4.1"));
+        let info = found.info(&provider).await.unwrap();
+        assert_eq!((info.owners, info.threshold, info.nonce), (owners, 2, U256::from(7)));
+
+        assert!(Safe::detect(&provider, Address::repeat_byte(0x0e), 1)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execution_needs_threshold_of_owner_signatures() {
+        let mut owners = [
+            PrivateKeySigner::random(),
+            PrivateKeySigner::random(),
+            PrivateKeySigner::random(),
+        ];
+        owners.sort_by_key(|o| o.address());
+        let info = SafeInfo {
+            owners: owners.iter().map(|o| o.address()).collect(),
+            threshold: 2,
+            nonce: U256::from(3),
+        };
+        let safe = safe_at("1.3.0");
+        let tx = SafeTx::call(Address::repeat_byte(0x42), U256::from(1), Bytes::new(), U256::from(3));
+        assert_ne!(
+            safe.transaction_hash(&tx),
+            Safe {
+                chain_id: 369,
+                ..safe.clone()
+            }
+            .transaction_hash(&tx)
+        );
+        assert_eq!(
+            safe_at("1.2.0").transaction_hash(&tx),
+            Safe {
+                chain_id: 369,
+                ..safe_at("1.2.0")
+            }
+            .transaction_hash(&tx)
+        );
+
+        let outsider = safe.sign(&tx, &PrivateKeySigner::random()).await.unwrap();
+        let last = safe.sign(&tx, &owners[2]).await.unwrap();
+        let first = safe.sign(&tx, &owners[0]).await.unwrap();
+        assert!(safe
+            .execution_request(
+                &tx,
+                &[outsider.clone(), last.clone(), last.clone()],
+                &info,
+                owners[0].address()
+            )
+            .is_err());
+
+        let request = safe
+            .execution_request(
+                &tx,
+                &[outsider, last.clone(), first.clone()],
+                &info,
+                owners[0].address(),
+            )
+            .unwrap();
+        let input = request.input.input().unwrap();
+        let call = ISafe::execTransactionCall::abi_decode(input).unwrap();
+        // Sorted by owner address
+        assert_eq!(
+            call.signatures,
+            Bytes::from([first.signature.as_ref(), last.signature.as_ref()].concat())
+        );
+
+        let stale = SafeTx {
+            nonce: U256::from(2),
+            ..tx
+        };
+        assert!(safe.execution_request(&stale, &[], &info, owners[0].address()).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Safe Transaction Service client
+//!
+//! The service stores proposed Safe transactions and the owners' signatures
+//! so co-signers on other machines can find and confirm them.
+
+use super::safe::{Safe, SafeSignature, SafeTx};
+use crate::error::{NetworkError, Result};
+use crate::network::egress::{egress, EgressCategory};
+use crate::network::http::{shared_client, HttpClient, HttpRequest};
+use alloy::primitives::{Address, Bytes, B256};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Name recorded as the origin of proposals
+const ORIGIN: &str = "Vaughan Wallet";
+
+/// Safe Transaction Service of one chain
+#[derive(Debug, Clone)]
+pub struct SafeTransactionService {
+    base_url: String,
+    client: HttpClient,
+}
+
+#[derive(Debug, Deserialize)]
+struct Confirmation {
+    owner: Address,
+    signature: Bytes,
+}
+
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    results: Vec<T>,
+}
+
+/// Body of a proposal, with amounts as decimal strings as the service expects
+fn proposal_body(safe_tx_hash: B256, tx: &SafeTx, signature: &SafeSignature) -> Value {
+    json!({
+        "to": tx.to,
+        "value": tx.value.to_string(),
+        "data": (!tx.data.is_empty()).then_some(&tx.data),
+        "operation": tx.operation,
+        "safeTxGas": tx.safeTxGas.to_string(),
+        "baseGas": tx.baseGas.to_string(),
+        "gasPrice": tx.gasPrice.to_string(),
+        "gasToken": tx.gasToken,
+        "refundReceiver": tx.refundReceiver,
+        "nonce": tx.nonce.to_string(),
+        "contractTransactionHash": safe_tx_hash,
+        "sender": signature.signer,
+        "signature": signature.signature,
+        "origin": ORIGIN,
+    })
+}
+
+impl SafeTransactionService {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: shared_client(),
+        }
+    }
+
+    /// The official service for `chain_id`, if Safe runs one
+    pub fn for_chain(chain_id: u64) -> Option<Self> {
+        let network = match chain_id {
+            1 => "mainnet",
+            10 => "optimism",
+            56 => "bsc",
+            100 => "gnosis-chain",
+            137 => "polygon",
+            8453 => "base",
+            42161 => "arbitrum",
+            11155111 => "sepolia",
+            _ => return None,
+        };
+        Some(Self::new(format!("https://safe-transaction-{network}.safe.global")))
+    }
+
+    async fn send(&self, request: HttpRequest, url: &str, purpose: &str) -> Result<reqwest::Response> {
+        egress().check(EgressCategory::Multisig, url, purpose)?;
+        let response = request.send().await.map_err(|e| NetworkError::RpcError {
+            message: format!("Failed to reach Safe Transaction Service: {e}"),
+        })?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(NetworkError::RpcError {
+                message: format!("Safe Transaction Service error {status}: {body}"),
+            }
+            .into());
+        }
+        Ok(response)
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str, purpose: &str) -> Result<T> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self.send(self.client.get(&url), &url, purpose).await?;
+        response.json().await.map_err(|e| {
+            NetworkError::RpcError {
+                message: format!("Failed to parse Safe Transaction Service response: {e}"),
+            }
+            .into()
+        })
+    }
+
+    async fn post(&self, path: &str, body: &Value, purpose: &str) -> Result<()> {
+        let url = format!("{}{path}", self.base_url);
+        self.send(self.client.post(&url).json(body), &url, purpose).await?;
+        Ok(())
+    }
+
+    /// Propose `tx` with the proposer's signature, returning its Safe transaction hash
+    pub async fn propose(&self, safe: &Safe, tx: &SafeTx, signature: &SafeSignature) -> Result<B256> {
+        let hash = safe.transaction_hash(tx);
+        let path = format!("/api/v1/safes/{}/multisig-transactions/", safe.address);
+        self.post(&path, &proposal_body(hash, tx, signature), "propose Safe transaction")
+            .await?;
+        tracing::info!("🤝 Proposed Safe transaction {} for {}", hash, safe.address);
+        Ok(hash)
+    }
+
+    /// Add an owner's signature to a proposed transaction
+    pub async fn confirm(&self, safe_tx_hash: B256, signature: &SafeSignature) -> Result<()> {
+        let path = format!("/api/v1/multisig-transactions/{safe_tx_hash}/confirmations/");
+        self.post(
+            &path,
+            &json!({ "signature": signature.signature }),
+            "confirm Safe transaction",
+        )
+        .await
+    }
+
+    /// Signatures collected so far for a proposed transaction
+    pub async fn confirmations(&self, safe_tx_hash: B256) -> Result<Vec<SafeSignature>> {
+        let path = format!("/api/v1/multisig-transactions/{safe_tx_hash}/confirmations/");
+        let page: Page<Confirmation> = self.get_json(&path, "fetch Safe confirmations").await?;
+        Ok(page
+            .results
+            .into_iter()
+            .map(|c| SafeSignature {
+                signer: c.owner,
+                signature: c.signature,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    #[test]
+    fn test_proposal_body_and_confirmations() {
+        let tx = SafeTx::call(
+            Address::repeat_byte(0x42),
+            U256::from(10).pow(U256::from(18)),
+            Bytes::new(),
+            U256::from(5),
+        );
+        let signature = SafeSignature {
+            signer: Address::repeat_byte(0x01),
+            signature: Bytes::from(vec![0xab; 65]),
+        };
+        let body = proposal_body(B256::repeat_byte(0x11), &tx, &signature);
+        assert_eq!(body["value"], "1000000000000000000");
+        assert_eq!(body["nonce"], "5");
+        assert_eq!(body["operation"], 0);
+        assert!(body["data"].is_null());
+        assert_eq!(body["sender"], json!(signature.signer));
+
+        let page: Page<Confirmation> = serde_json::from_value(json!({
+            "count": 1,
+            "results": [{
+                "owner": "0x0101010101010101010101010101010101010101",
+                "signature": format!("0x{}", "ab".repeat(65)),
+                "signatureType": "EOA"
+            }]
+        }))
+        .unwrap();
+        assert_eq!(page.results[0].owner, signature.signer);
+        assert_eq!(page.results[0].signature, signature.signature);
+
+        assert!(SafeTransactionService::for_chain(1).is_some());
+        assert!(SafeTransactionService::for_chain(369).is_none());
+    }
+}
@@ -0,0 +1,564 @@
+//! Master Password Change
+//!
+//! Everything encrypted under the master password is re-encrypted together:
+//! the keystore seeds, the MetaMask-compatible `keystore.json`, the note
+//! book, the transaction drafts, the CSV batch in progress, the private key
+//! in the legacy `wallet.json` and the wallet configuration. Each step keeps
+//! what it replaced; when a later step fails, the earlier ones are undone
+//! newest first, so no store is left under the new password alone.
+//!
+//! The same stores are what a password is checked against before signing is
+//! unlocked with it.
+
+use crate::error::{Result, SecurityError, VaughanError, WalletError};
+use crate::security::keystore::storage::{get_vaughan_dir, write_secure_file};
+use crate::security::keystore::SeedPasswordChange;
+use crate::security::{SecureKeystore, WalletConfigStorage};
+use crate::wallet::backup::get_recovery_dir;
+use crate::wallet::errors::WalletManagerError;
+use crate::wallet::keystore_format::MetaMaskKeystore;
+use crate::wallet::manager::WalletManager;
+use crate::wallet::encrypted_store::EncryptedJsonStore;
+use crate::wallet::metadata::NOTES_FILE;
+use crate::wallet::transaction::csv_batch::CSV_BATCH_FILE;
+use crate::wallet::transaction::drafts::DRAFTS_FILE;
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// WalletManager keystore in the `.vaughan` directory
+pub const KEY_FILE: &str = "keystore.json";
+
+/// Legacy private key wallet in the `.vaughan` directory
+pub const LEGACY_WALLET_FILE: &str = "wallet.json";
+
+/// Where the stores encrypted under the master password live
+#[derive(Debug, Clone)]
+pub struct PasswordStores {
+    /// Recovery points taken before the change
+    pub recovery_dir: PathBuf,
+    pub key_file: PathBuf,
+    pub legacy_wallet_file: PathBuf,
+    /// Note book, transaction drafts and CSV batch in progress of the primary profile
+    pub json_stores: Vec<EncryptedJsonStore>,
+    /// Wallet configuration storage (None = the default one)
+    pub wallet_config: Option<WalletConfigStorage>,
+}
+
+impl Default for PasswordStores {
+    fn default() -> Self {
+        Self {
+            recovery_dir: get_recovery_dir(),
+            ..Self::in_dir(&get_vaughan_dir())
+        }
+    }
+}
+
+impl PasswordStores {
+    /// Stores in `dir` rather than the `.vaughan` directory
+    ///
+    /// The wallet configuration stays the default one.
+    pub fn in_dir(dir: &Path) -> Self {
+        Self {
+            recovery_dir: dir.join("recovery"),
+            key_file: dir.join(KEY_FILE),
+            legacy_wallet_file: dir.join(LEGACY_WALLET_FILE),
+            json_stores: [NOTES_FILE, DRAFTS_FILE, CSV_BATCH_FILE]
+                .into_iter()
+                .map(|file| EncryptedJsonStore::at(dir.join(file)))
+                .collect(),
+            wallet_config: None,
+        }
+    }
+}
+
+/// A store already re-encrypted, with what it held before
+enum Replaced {
+    Seeds(SeedPasswordChange),
+    KeyFile(Box<MetaMaskKeystore>),
+    /// Previous contents of one of the encrypted JSON files
+    JsonStore(EncryptedJsonStore, Zeroizing<String>),
+    /// Previous contents of `wallet.json`
+    LegacyWallet(Zeroizing<String>),
+}
+
+/// Re-encrypt every store under `new_password`, or none of them
+pub(crate) async fn reencrypt_all(
+    keystore: &SecureKeystore,
+    stores: &PasswordStores,
+    old_password: &SecretString,
+    new_password: &SecretString,
+) -> Result<()> {
+    let mut replaced = Vec::new();
+    let result = reencrypt_each(keystore, stores, old_password, new_password, &mut replaced).await;
+    if let Err(e) = &result {
+        tracing::error!("Master password change failed, restoring the old password: {}", e);
+        for step in replaced.into_iter().rev() {
+            if let Err(rollback) = undo(step, keystore, stores) {
+                tracing::error!("Restoring the old password failed: {}", rollback);
+            }
+        }
+    }
+    result
+}
+
+async fn reencrypt_each(
+    keystore: &SecureKeystore,
+    stores: &PasswordStores,
+    old_password: &SecretString,
+    new_password: &SecretString,
+    replaced: &mut Vec<Replaced>,
+) -> Result<()> {
+    replaced.push(Replaced::Seeds(
+        keystore.reencrypt_seeds(old_password, new_password).await?,
+    ));
+
+    let mut key_file = WalletManager::new(stores.key_file.clone());
+    if let Some(previous) = key_file
+        .change_password(old_password, new_password)
+        .map_err(key_file_error)?
+    {
+        replaced.push(Replaced::KeyFile(Box::new(previous)));
+    }
+
+    for store in &stores.json_stores {
+        if let Some(previous) = store.change_password(old_password, new_password)? {
+            replaced.push(Replaced::JsonStore(store.clone(), previous));
+        }
+    }
+
+    if let Some(previous) = reencrypt_legacy_wallet(&stores.legacy_wallet_file, old_password, new_password)? {
+        replaced.push(Replaced::LegacyWallet(previous));
+    }
+
+    // Last step, so nothing after it can fail and leave it changed
+    let config_storage = match &stores.wallet_config {
+        Some(storage) => storage.clone(),
+        None => WalletConfigStorage::new()?,
+    };
+    if config_storage.wallet_exists()? {
+        config_storage
+            .change_master_password(old_password, new_password)
+            .await?;
+    }
+    Ok(())
+}
+
+fn undo(step: Replaced, keystore: &SecureKeystore, stores: &PasswordStores) -> Result<()> {
+    match step {
+        Replaced::Seeds(change) => keystore.revert_seed_password_change(change),
+        Replaced::KeyFile(previous) => WalletManager::new(stores.key_file.clone())
+            .restore_keystore(*previous)
+            .map_err(key_file_error),
+        Replaced::JsonStore(store, previous) => store.restore(&previous),
+        Replaced::LegacyWallet(previous) => write_secure_file(&stores.legacy_wallet_file.to_string_lossy(), &previous),
+    }
+}
+
+/// Check `password` against the first store encrypted under the master password
+///
+/// The wallet configuration is tried first, then the seeds, `keystore.json`
+/// and the legacy `wallet.json`. Fails when none of them exists, as there is
+/// then nothing to check the password against.
+pub(crate) async fn verify_master_password(
+    keystore: &SecureKeystore,
+    stores: &PasswordStores,
+    password: &SecretString,
+) -> Result<bool> {
+    let config_storage = match &stores.wallet_config {
+        Some(storage) => storage.clone(),
+        None => WalletConfigStorage::new()?,
+    };
+    if let Some(valid) = config_storage.verify_master_password(password).await? {
+        return Ok(valid);
+    }
+    if let Some(valid) = keystore.verify_seed_password(password).await? {
+        return Ok(valid);
+    }
+
+    if stores.key_file.exists() {
+        return match WalletManager::new(stores.key_file.clone()).unlock(password.clone()) {
+            Ok(()) => Ok(true),
+            Err(WalletManagerError::InvalidPassword) => Ok(false),
+            Err(e) => Err(WalletError::Generic(format!("Failed to open {KEY_FILE}: {e}")).into()),
+        };
+    }
+
+    match std::fs::read_to_string(&stores.legacy_wallet_file) {
+        Ok(contents) => {
+            let wallet = serde_json::from_str(&contents)
+                .map_err(|_| WalletError::Generic(format!("{LEGACY_WALLET_FILE} is corrupted")))?;
+            match decrypt_legacy_wallet(&wallet, password) {
+                Ok(_) => Ok(true),
+                Err(VaughanError::Security(SecurityError::InvalidPassword)) => Ok(false),
+                Err(e) => Err(e),
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(SecurityError::KeystoreError {
+            message: "No master password is set".to_string(),
+        }
+        .into()),
+        Err(e) => Err(WalletError::Generic(format!("Failed to read {LEGACY_WALLET_FILE}: {e}")).into()),
+    }
+}
+
+fn key_file_error(e: WalletManagerError) -> VaughanError {
+    match e {
+        WalletManagerError::InvalidPassword => SecurityError::InvalidPassword.into(),
+        e => WalletError::Generic(format!("Failed to re-encrypt {KEY_FILE}: {e}")).into(),
+    }
+}
+
+/// AES-256-GCM keyed with SHA-256 of the password, as `wallet.json` uses
+fn legacy_cipher(password: &SecretString) -> Aes256Gcm {
+    Aes256Gcm::new(&Sha256::digest(password.expose_secret().as_bytes()))
+}
+
+/// Decrypt the private key in the contents of a legacy `wallet.json`
+fn decrypt_legacy_wallet(
+    wallet: &serde_json::Map<String, serde_json::Value>,
+    password: &SecretString,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let corrupt = || WalletError::Generic(format!("{LEGACY_WALLET_FILE} is corrupted"));
+    let hex_field = |name: &str| {
+        wallet
+            .get(name)
+            .and_then(serde_json::Value::as_str)
+            .and_then(|hex| alloy::hex::decode(hex).ok())
+            .ok_or_else(corrupt)
+    };
+    let ciphertext = hex_field("encrypted_private_key")?;
+    let nonce: [u8; 12] = hex_field("nonce")?.try_into().map_err(|_| corrupt())?;
+
+    let private_key = legacy_cipher(password)
+        .decrypt(&Nonce::from(nonce), ciphertext.as_slice())
+        .map_err(|_| SecurityError::InvalidPassword)?;
+    Ok(Zeroizing::new(private_key))
+}
+
+/// Re-encrypt the private key in a legacy `wallet.json`
+///
+/// Returns the previous file contents, or `None` when there is no legacy wallet.
+fn reencrypt_legacy_wallet(
+    path: &Path,
+    old_password: &SecretString,
+    new_password: &SecretString,
+) -> Result<Option<Zeroizing<String>>> {
+    let previous = match std::fs::read_to_string(path) {
+        Ok(contents) => Zeroizing::new(contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(WalletError::Generic(format!("Failed to read {LEGACY_WALLET_FILE}: {e}")).into()),
+    };
+    let mut wallet: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&previous)
+        .map_err(|_| WalletError::Generic(format!("{LEGACY_WALLET_FILE} is corrupted")))?;
+
+    let private_key = decrypt_legacy_wallet(&wallet, old_password)?;
+    let nonce = crate::security::seed::encryption::generate_nonce()?;
+    let sealed = legacy_cipher(new_password)
+        .encrypt(&Nonce::from(nonce), private_key.as_slice())
+        .map_err(|e| SecurityError::EncryptionError {
+            message: format!("Failed to encrypt {LEGACY_WALLET_FILE}: {e}"),
+        })?;
+
+    wallet.insert("encrypted_private_key".to_string(), alloy::hex::encode(sealed).into());
+    wallet.insert("nonce".to_string(), alloy::hex::encode(nonce).into());
+    let json = serde_json::to_string_pretty(&wallet).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    write_secure_file(&path.to_string_lossy(), &json)?;
+    Ok(Some(previous))
+}
+
+/// Write a legacy `wallet.json` holding `private_key` under `password`
+///
+/// Checking a password against it is cheap, which keeps tests that only need
+/// some master password fast.
+#[cfg(test)]
+pub(crate) fn write_legacy_wallet(path: &Path, password: &SecretString, private_key: &[u8; 32]) {
+    let nonce = [3u8; 12];
+    let sealed = legacy_cipher(password)
+        .encrypt(&Nonce::from(nonce), private_key.as_slice())
+        .unwrap();
+    let wallet = serde_json::json!({
+        "address": "0x0000000000000000000000000000000000000000",
+        "encrypted_private_key": alloy::hex::encode(sealed),
+        "nonce": alloy::hex::encode(nonce),
+    });
+    std::fs::write(path, wallet.to_string()).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::profile::AUTH_PARAMS_FILE;
+    use crate::security::unlock_throttle::UNLOCK_ATTEMPTS_FILE;
+    use crate::security::{
+        Argon2Params, DuressGuard, KeyReference, SecureAccount, SecureSeedStorage, SecurityProfile, TestKeychain,
+        UnlockPolicy, UnlockThrottle, SERVICE_NAME_ENCRYPTED_SEEDS,
+    };
+    use crate::wallet::metadata::{NoteBook, NoteStore, NoteTarget};
+    use crate::wallet::transaction::csv_batch::{CsvBatch, CsvBatchStore};
+    use crate::wallet::transaction::disperse::DisperseAsset;
+    use crate::wallet::transaction::drafts::{DraftBook, DraftStore, TransactionDraft};
+    use crate::wallet::{Vaughan, WalletConfig};
+    use alloy::primitives::Address;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn seed_account() -> SecureAccount {
+        SecureAccount {
+            id: "seed".to_string(),
+            name: "Seed".to_string(),
+            address: Address::repeat_byte(0xa1),
+            key_reference: KeyReference {
+                id: "seed".to_string(),
+                service: SERVICE_NAME_ENCRYPTED_SEEDS.to_string(),
+                account: "seed".to_string(),
+            },
+            created_at: chrono::Utc::now(),
+            is_hardware: false,
+            derivation_path: None,
+            tags: Vec::new(),
+            last_used: None,
+            transaction_count: 0,
+        }
+    }
+
+    fn legacy_wallet_opens(path: &Path, password: &SecretString) -> bool {
+        let wallet = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        decrypt_legacy_wallet(&wallet, password).is_ok()
+    }
+
+    #[tokio::test]
+    async fn test_change_master_password_reencrypts_every_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = SecretString::new("old master password".to_string());
+        let new = SecretString::new("New-master-passw0rd".to_string());
+        let keychain = TestKeychain::new();
+
+        let mut config_storage = WalletConfigStorage::new_with_keychain(Box::new(keychain.clone())).unwrap();
+        config_storage.set_config_path(dir.path().join("wallet_metadata.json"));
+        config_storage
+            .create_wallet_config("Test".to_string(), &old)
+            .await
+            .unwrap();
+
+        let stores = PasswordStores {
+            wallet_config: Some(config_storage.clone()),
+            ..PasswordStores::in_dir(dir.path())
+        };
+        WalletManager::new(stores.key_file.clone())
+            .create_wallet(old.clone())
+            .unwrap();
+        // Unreadable until the first attempt has failed
+        std::fs::write(&stores.legacy_wallet_file, "{}").unwrap();
+        let cheap_kdf = Argon2Params {
+            memory_cost: 8,
+            time_cost: 1,
+            parallelism: 1,
+            output_length: 32,
+        };
+        let notes = NoteStore::at(dir.path().join(NOTES_FILE)).with_argon2_params(cheap_kdf.clone());
+        let mut book = NoteBook::new();
+        book.set_note(NoteTarget::Address(Address::repeat_byte(0xce)), "exchange")
+            .unwrap();
+        notes.save(&book, &old).unwrap();
+        let drafts = DraftStore::at(dir.path().join(DRAFTS_FILE)).with_argon2_params(cheap_kdf.clone());
+        let mut draft_book = DraftBook::new();
+        draft_book
+            .save(TransactionDraft {
+                recipient: Address::repeat_byte(0xce).to_string(),
+                amount: "1.5".to_string(),
+                ..TransactionDraft::new(1)
+            })
+            .unwrap();
+        drafts.save(&draft_book, &old).unwrap();
+        let csv_batches = CsvBatchStore::at(dir.path().join(CSV_BATCH_FILE)).with_argon2_params(cheap_kdf);
+        let csv_batch =
+            CsvBatch::parse(1, DisperseAsset::Native, &format!("{},1", Address::repeat_byte(0xce))).unwrap();
+        csv_batches.save(&csv_batch, &old).unwrap();
+
+        let config = WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: false,
+            ..WalletConfig::default()
+        };
+        let wallet = Vaughan::with_keychain(config, Box::new(keychain))
+            .await
+            .unwrap()
+            .with_password_stores(stores.clone());
+        let sealed = SecureSeedStorage::encrypt_for_keychain(&SecretString::new(PHRASE.to_string()), &old).unwrap();
+        wallet
+            .keystore()
+            .write()
+            .await
+            .restore_account(seed_account(), Some(sealed))
+            .await
+            .unwrap();
+        let seed_opens = |password: SecretString| {
+            let keystore = wallet.keystore();
+            async move {
+                let phrase = SecretString::new(PHRASE.to_string());
+                let keystore = keystore.read().await;
+                keystore
+                    .verify_seed_backup(&seed_account().address, &password, &phrase)
+                    .await
+                    .is_ok()
+            }
+        };
+
+        // The corrupt legacy wallet fails the change after the seeds and
+        // keystore.json were re-encrypted; both go back to the old password
+        assert!(wallet.change_master_password(&old, &new).await.is_err());
+        assert!(seed_opens(old.clone()).await);
+        assert!(WalletManager::new(stores.key_file.clone()).unlock(old.clone()).is_ok());
+        assert!(config_storage.load_wallet_config(&old).await.unwrap().is_some());
+        assert_eq!(notes.load(&old).unwrap(), book);
+        assert_eq!(drafts.load(&old).unwrap(), draft_book);
+        assert_eq!(csv_batches.load(&old).unwrap(), Some(csv_batch.clone()));
+
+        write_legacy_wallet(&stores.legacy_wallet_file, &old, &[7u8; 32]);
+        wallet.change_master_password(&old, &new).await.unwrap();
+        assert!(seed_opens(new.clone()).await);
+        assert!(!seed_opens(old.clone()).await);
+        assert!(WalletManager::new(stores.key_file.clone()).unlock(new.clone()).is_ok());
+        assert!(WalletManager::new(stores.key_file.clone()).unlock(old.clone()).is_err());
+        assert!(legacy_wallet_opens(&stores.legacy_wallet_file, &new));
+        assert!(!legacy_wallet_opens(&stores.legacy_wallet_file, &old));
+        assert!(config_storage.load_wallet_config(&new).await.unwrap().is_some());
+        assert_eq!(notes.load(&new).unwrap(), book);
+        assert!(notes.load(&old).is_err());
+        assert_eq!(drafts.load(&new).unwrap(), draft_book);
+        assert!(drafts.load(&old).is_err());
+        assert_eq!(csv_batches.load(&new).unwrap(), Some(csv_batch));
+        assert!(csv_batches.load(&old).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unlock_signing_checks_the_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let password = SecretString::new("master password".to_string());
+        let keychain = TestKeychain::new();
+        let mut config_storage = WalletConfigStorage::new_with_keychain(Box::new(keychain.clone())).unwrap();
+        config_storage.set_config_path(dir.path().join("wallet_metadata.json"));
+        let stores = PasswordStores {
+            wallet_config: Some(config_storage),
+            ..PasswordStores::in_dir(dir.path())
+        };
+
+        let config = WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: true,
+            ..WalletConfig::default()
+        };
+        let wallet = Vaughan::with_keychain(config, Box::new(keychain))
+            .await
+            .unwrap()
+            .with_password_stores(stores.clone())
+            .with_unlock_throttle(UnlockThrottle::at(dir.path().join(UNLOCK_ATTEMPTS_FILE)));
+
+        // Nothing to check a password against yet
+        assert!(wallet.unlock_signing(password.clone()).await.is_err());
+
+        // Only keystore.json is encrypted under the master password
+        WalletManager::new(stores.key_file.clone())
+            .create_wallet(password.clone())
+            .unwrap();
+        let wrong = SecretString::new("wrong password".to_string());
+        assert!(!wallet.unlock_signing(wrong).await.unwrap());
+        assert!(!wallet.lock_level().await.can_sign());
+        assert!(wallet.unlock_signing(password).await.unwrap());
+        assert!(wallet.lock_level().await.can_sign());
+    }
+
+    #[tokio::test]
+    async fn test_unlock_signing_is_throttled() {
+        let dir = tempfile::tempdir().unwrap();
+        let password = SecretString::new("master password".to_string());
+        let stores = PasswordStores::in_dir(dir.path());
+        WalletManager::new(stores.key_file.clone())
+            .create_wallet(password.clone())
+            .unwrap();
+        let counter = dir.path().join(UNLOCK_ATTEMPTS_FILE);
+        let policy = UnlockPolicy {
+            free_attempts: 1,
+            ..UnlockPolicy::default()
+        };
+
+        let config = WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: true,
+            ..WalletConfig::default()
+        };
+        let wallet = Vaughan::with_keychain(config, Box::new(TestKeychain::new()))
+            .await
+            .unwrap()
+            .with_password_stores(stores)
+            .with_unlock_throttle(UnlockThrottle::at(&counter).with_policy(policy));
+
+        // The second wrong password imposes a back-off, refusing even the right one
+        let wrong = SecretString::new("wrong password".to_string());
+        assert!(!wallet.unlock_signing(wrong.clone()).await.unwrap());
+        assert!(!wallet.unlock_signing(wrong).await.unwrap());
+        assert!(matches!(
+            wallet.unlock_signing(password.clone()).await,
+            Err(VaughanError::Security(SecurityError::RateLimitExceeded { .. }))
+        ));
+        assert!(!wallet.lock_level().await.can_sign());
+
+        // A corrupt counter starts over instead of locking out
+        std::fs::write(&counter, "{ not json").unwrap();
+        assert!(wallet.unlock_signing(password).await.unwrap());
+        assert_eq!(UnlockThrottle::at(&counter).attempts().failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_duress_password_unlocks_the_decoy_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let master = SecretString::new("master password".to_string());
+        let duress = SecretString::new("duress password".to_string());
+        let stores = PasswordStores::in_dir(dir.path());
+        WalletManager::new(stores.key_file.clone())
+            .create_wallet(master.clone())
+            .unwrap();
+
+        let config = WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: true,
+            ..WalletConfig::default()
+        };
+        let mut wallet = Vaughan::with_keychain(config, Box::new(TestKeychain::new()))
+            .await
+            .unwrap()
+            .with_password_stores(stores)
+            .with_unlock_throttle(UnlockThrottle::at(dir.path().join(UNLOCK_ATTEMPTS_FILE)))
+            .with_duress_guard(DuressGuard::open(dir.path().join(AUTH_PARAMS_FILE)));
+
+        // Only the master password may set it
+        assert!(wallet.set_duress_password(&duress, &duress).await.is_err());
+        wallet.set_duress_password(&duress, &master).await.unwrap();
+
+        assert!(wallet.unlock_signing(duress.clone()).await.unwrap());
+        assert_eq!(wallet.security_profile().await, SecurityProfile::Decoy);
+        assert!(wallet.lock_level().await.can_sign());
+        // From the decoy profile, the duress password cannot be changed or removed
+        assert!(wallet.clear_duress_password(&master).await.is_err());
+
+        // A wrong password keeps the decoy profile open
+        wallet.lock_signing().await;
+        let wrong = SecretString::new("wrong password".to_string());
+        assert!(!wallet.unlock_signing(wrong).await.unwrap());
+        assert_eq!(wallet.security_profile().await, SecurityProfile::Decoy);
+
+        assert!(wallet.unlock_signing(master.clone()).await.unwrap());
+        assert_eq!(wallet.security_profile().await, SecurityProfile::Primary);
+        wallet.clear_duress_password(&master).await.unwrap();
+        assert!(!wallet.unlock_signing(duress).await.unwrap());
+    }
+}
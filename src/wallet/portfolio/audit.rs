@@ -0,0 +1,323 @@
+//! Auditor bundles
+//!
+//! A read-only package third parties such as accountants and auditors can
+//! check without any key access. For each account it holds:
+//!
+//! - a statement of balances and transaction history
+//! - an EIP-191 signed attestation, made with the account's own key, that
+//!   names the account and the SHA-256 of the statement's canonical JSON
+//!
+//! Verifying a bundle recomputes each statement digest, checks the attestation
+//! text and recovers the signer, so altering any balance or transaction, or
+//! signing with a different key, is detected. The attestation is a plain
+//! `personal_sign` message and can also be checked with any standard tool.
+
+use crate::blockchain::explorer_apis::ApiTransaction;
+use crate::error::{Result, SecurityError, VaughanError};
+use crate::tokens::TokenBalance;
+use crate::wallet::backup::canonical;
+use alloy::primitives::{Address, Bytes, Signature, B256};
+use alloy::signers::SignerSync;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Schema name embedded in auditor bundles
+const BUNDLE_SCHEMA: &str = "vaughan.auditor-bundle";
+/// Current layout of auditor bundles
+const BUNDLE_SCHEMA_VERSION: u32 = 1;
+
+/// Token balance of an account on one chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditedBalance {
+    pub chain_id: u64,
+    /// Token contract, the zero address for the native currency
+    pub token_address: Address,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Raw balance in the token's smallest unit
+    pub balance: String,
+}
+
+impl From<&TokenBalance> for AuditedBalance {
+    fn from(balance: &TokenBalance) -> Self {
+        Self {
+            chain_id: balance.token.chain_id,
+            token_address: balance.token.address,
+            symbol: balance.token.symbol.clone(),
+            decimals: balance.token.decimals,
+            balance: balance.balance.clone(),
+        }
+    }
+}
+
+/// Transaction involving an account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditedTransaction {
+    pub chain_id: u64,
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    /// Native value in wei
+    pub value: String,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub status: String,
+}
+
+impl AuditedTransaction {
+    pub fn from_api(chain_id: u64, tx: &ApiTransaction) -> Self {
+        Self {
+            chain_id,
+            hash: tx.hash.clone(),
+            from: tx.from.clone(),
+            to: tx.to.clone(),
+            value: tx.value.clone(),
+            block_number: tx.block_number,
+            timestamp: tx.timestamp,
+            status: tx.status.clone(),
+        }
+    }
+}
+
+/// Balances and history of one account, the data its attestation covers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountStatement {
+    pub address: Address,
+    pub balances: Vec<AuditedBalance>,
+    pub transactions: Vec<AuditedTransaction>,
+}
+
+impl AccountStatement {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            balances: Vec::new(),
+            transactions: Vec::new(),
+        }
+    }
+
+    pub fn with_balances(mut self, balances: &[TokenBalance]) -> Self {
+        self.balances.extend(balances.iter().map(AuditedBalance::from));
+        self
+    }
+
+    pub fn with_transactions(mut self, chain_id: u64, transactions: &[ApiTransaction]) -> Self {
+        self.transactions
+            .extend(transactions.iter().map(|tx| AuditedTransaction::from_api(chain_id, tx)));
+        self
+    }
+
+    /// SHA-256 of the canonical JSON of the statement
+    pub fn digest(&self) -> Result<B256> {
+        canonical::canonical_digest(self).map(B256::from)
+    }
+
+    /// Text the account owner signs for this statement
+    pub fn attestation_message(&self, generated_at: DateTime<Utc>) -> Result<String> {
+        Ok(format!(
+            "Vaughan auditor attestation\n\
+             I control account {} and attest to the balances and transaction history in this bundle.\n\
+             Generated: {}\n\
+             Statement SHA-256: {}",
+            self.address,
+            generated_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.digest()?
+        ))
+    }
+}
+
+/// Statement of one account with the owner's signature over it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditedAccount {
+    pub statement: AccountStatement,
+    /// Signed text, kept so the attestation can be checked by other tools
+    pub message: String,
+    /// 65-byte EIP-191 signature of `message`
+    pub signature: Bytes,
+}
+
+impl AuditedAccount {
+    /// Attest to `statement` with the key of the account it describes
+    pub fn sign(statement: AccountStatement, generated_at: DateTime<Utc>, signer: &impl SignerSync) -> Result<Self> {
+        let message = statement.attestation_message(generated_at)?;
+        let signature = signer
+            .sign_message_sync(message.as_bytes())
+            .map_err(|e| VaughanError::ValidationError(format!("Failed to sign attestation: {e}")))?;
+        Ok(Self {
+            statement,
+            message,
+            signature: Bytes::from(signature.as_bytes().to_vec()),
+        })
+    }
+
+    fn verify(&self, generated_at: DateTime<Utc>) -> Result<()> {
+        let address = self.statement.address;
+        let failed = |reason: &str| -> VaughanError {
+            SecurityError::IntegrityCheckFailed {
+                message: format!("Attestation for {address} {reason}"),
+            }
+            .into()
+        };
+
+        if self.message != self.statement.attestation_message(generated_at)? {
+            return Err(failed("does not match its statement"));
+        }
+        let signature =
+            Signature::try_from(self.signature.as_ref()).map_err(|_| failed("has a malformed signature"))?;
+        match signature.recover_address_from_msg(self.message.as_bytes()) {
+            Ok(signer) if signer == address => Ok(()),
+            _ => Err(failed("was not signed by the account")),
+        }
+    }
+}
+
+/// Verifiable holdings and history of one or more accounts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditorBundle {
+    pub generated_at: DateTime<Utc>,
+    pub accounts: Vec<AuditedAccount>,
+}
+
+impl AuditorBundle {
+    /// Check every account's statement against its attestation
+    ///
+    /// Returns the attested addresses; any mismatch fails the whole bundle.
+    pub fn verify(&self) -> Result<Vec<Address>> {
+        self.accounts
+            .iter()
+            .map(|account| {
+                account.verify(self.generated_at)?;
+                Ok(account.statement.address)
+            })
+            .collect()
+    }
+
+    /// Canonical, schema-tagged JSON
+    pub fn to_json(&self) -> Result<String> {
+        canonical::to_versioned_document(BUNDLE_SCHEMA, BUNDLE_SCHEMA_VERSION, self)
+    }
+
+    /// Parse a bundle; call [`Self::verify`] before trusting its contents
+    pub fn from_json(json: &str) -> Result<Self> {
+        canonical::from_versioned_document(BUNDLE_SCHEMA, BUNDLE_SCHEMA_VERSION, json).map(|(_, bundle)| bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::TokenInfo;
+    use alloy::signers::local::PrivateKeySigner;
+
+    fn statement(address: Address) -> AccountStatement {
+        let native = TokenBalance {
+            token: TokenInfo::new(Address::ZERO, 369, "Pulse".into(), "PLS".into(), 18),
+            balance: "2500000000000000000".into(),
+            formatted: "2.5".into(),
+            usd_value: None,
+            fiat_value: None,
+        };
+        let transfer = ApiTransaction {
+            hash: format!("0x{}", "ab".repeat(32)),
+            from: "0x0101010101010101010101010101010101010101".into(),
+            to: address.to_string(),
+            value: "2500000000000000000".into(),
+            timestamp: 1_700_000_000,
+            block_number: 19_000_000,
+            gas_used: Some(21_000),
+            gas_price: None,
+            status: "1".into(),
+            method_name: None,
+        };
+        AccountStatement::new(address)
+            .with_balances(&[native])
+            .with_transactions(369, &[transfer])
+    }
+
+    fn bundle(signer: &PrivateKeySigner) -> AuditorBundle {
+        let generated_at = Utc::now();
+        let account = AuditedAccount::sign(statement(signer.address()), generated_at, signer).unwrap();
+        AuditorBundle {
+            generated_at,
+            accounts: vec![account],
+        }
+    }
+
+    #[test]
+    fn test_bundle_round_trip_verifies() {
+        let signer = PrivateKeySigner::random();
+        let json = bundle(&signer).to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema"], BUNDLE_SCHEMA);
+        assert!(!json.contains(&alloy::hex::encode(signer.to_bytes())));
+
+        let parsed = AuditorBundle::from_json(&json).unwrap();
+        assert_eq!(parsed.verify().unwrap(), vec![signer.address()]);
+        assert!(parsed.accounts[0]
+            .message
+            .contains(&parsed.accounts[0].statement.digest().unwrap().to_string()));
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let signer = PrivateKeySigner::random();
+
+        let mut inflated = bundle(&signer);
+        inflated.accounts[0].statement.balances[0].balance = "9000000000000000000000".into();
+        assert!(inflated.verify().is_err());
+
+        let mut backdated = bundle(&signer);
+        backdated.generated_at -= chrono::Duration::days(365);
+        assert!(backdated.verify().is_err());
+
+        // Someone else's key attesting to this account
+        let mut impostor = bundle(&signer);
+        let other = PrivateKeySigner::random();
+        let forged =
+            AuditedAccount::sign(impostor.accounts[0].statement.clone(), impostor.generated_at, &other).unwrap();
+        impostor.accounts[0].signature = forged.signature;
+        assert!(impostor.verify().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wallet_asks_before_attesting() {
+        use crate::wallet::{Vaughan, WalletConfig};
+        use secrecy::SecretString;
+
+        let config = WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: false,
+            ..Default::default()
+        };
+        let wallet = Vaughan::with_keychain(config, Box::new(crate::security::TestKeychain::new()))
+            .await
+            .unwrap();
+        let signer = PrivateKeySigner::random();
+        let key = SecretString::new(alloy::hex::encode(signer.to_bytes()));
+        wallet
+            .keystore()
+            .write()
+            .await
+            .import_account(key, "Treasury".to_string())
+            .await
+            .unwrap();
+
+        let mut approvals = wallet.approvals().connect();
+        let frontend = tokio::spawn(async move {
+            let pending = approvals.recv().await.unwrap();
+            assert!(pending
+                .request()
+                .details()
+                .iter()
+                .any(|line| line.contains("Vaughan auditor attestation")));
+            pending.reject(None);
+            approvals.recv().await.unwrap().approve();
+        });
+
+        assert!(wallet.auditor_bundle(vec![statement(signer.address())]).await.is_err());
+        let bundle = wallet.auditor_bundle(vec![statement(signer.address())]).await.unwrap();
+        assert_eq!(bundle.verify().unwrap(), vec![signer.address()]);
+        frontend.await.unwrap();
+    }
+}
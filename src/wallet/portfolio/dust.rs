@@ -0,0 +1,296 @@
+//! Dust detection and consolidation suggestions
+//!
+//! A holding is dust when moving it costs more gas than it is worth, or when
+//! it is worth less than a user-chosen threshold. For each network the
+//! analyzer proposes sweeping dust tokens into the native token in a single
+//! batch, estimates what that costs against what it recovers, and reports the
+//! gas price at which sweeping everything would break even.
+//!
+//! The report is advisory only; nothing here builds or sends transactions.
+
+use super::{PortfolioSnapshot, TokenAllocation};
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Gas for a plain ERC-20 `transfer`
+pub const ERC20_TRANSFER_GAS: u64 = 65_000;
+/// Fixed gas of the batch transaction carrying a sweep
+pub const SWEEP_BASE_GAS: u64 = 21_000;
+/// Marginal gas per token swept: `approve` plus one router swap
+pub const SWEEP_GAS_PER_TOKEN: u64 = 46_000 + 120_000;
+
+/// Default value below which holdings count as dust regardless of gas cost
+const DEFAULT_THRESHOLD_USD: f64 = 1.0;
+
+/// Current cost of gas on one network
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NetworkGasCost {
+    pub chain_id: u64,
+    pub gas_price_gwei: f64,
+    /// USD price of the native token
+    pub native_usd: f64,
+}
+
+impl NetworkGasCost {
+    /// USD cost of `gas` units at the current gas price
+    pub fn gas_cost_usd(&self, gas: u64) -> f64 {
+        gas as f64 * self.gas_price_gwei * 1e-9 * self.native_usd
+    }
+
+    /// Gas price at which spending `gas` costs exactly `value_usd`
+    fn break_even_gwei(&self, gas: u64, value_usd: f64) -> Option<f64> {
+        (gas > 0 && self.native_usd > 0.0).then(|| value_usd / (gas as f64 * 1e-9 * self.native_usd))
+    }
+}
+
+/// A holding flagged as dust
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DustHolding {
+    pub chain_id: u64,
+    pub token_address: Address,
+    pub symbol: String,
+    pub amount: f64,
+    pub usd_value: f64,
+    /// Cost of a single ERC-20 transfer of this token
+    pub transfer_cost_usd: f64,
+}
+
+/// Suggested sweep of dust on one network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsolidationPlan {
+    pub chain_id: u64,
+    /// Tokens worth swapping into the native token together, largest first
+    pub sweep: Vec<DustHolding>,
+    /// Dust worth less than the gas to sweep it
+    pub leave: Vec<DustHolding>,
+    /// Estimated cost of the batch sweeping `sweep`
+    pub estimated_cost_usd: f64,
+    /// Value of the tokens in `sweep`, before swap slippage
+    pub recovered_value_usd: f64,
+    /// Gas price at which sweeping all dust here would break even
+    pub break_even_gas_gwei: Option<f64>,
+}
+
+impl ConsolidationPlan {
+    /// Recovered value minus cost
+    pub fn net_usd(&self) -> f64 {
+        self.recovered_value_usd - self.estimated_cost_usd
+    }
+
+    /// Whether sweeping now recovers more than it costs
+    pub fn is_worthwhile(&self) -> bool {
+        !self.sweep.is_empty() && self.net_usd() > 0.0
+    }
+}
+
+/// Dust found across the portfolio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DustReport {
+    pub generated_at: DateTime<Utc>,
+    pub threshold_usd: f64,
+    /// One plan per network with dust, ordered by chain ID
+    pub plans: Vec<ConsolidationPlan>,
+    /// Networks holding tokens that could not be assessed for lack of gas cost data
+    pub unassessed_networks: Vec<u64>,
+}
+
+impl DustReport {
+    /// Value of every holding flagged as dust
+    pub fn total_dust_usd(&self) -> f64 {
+        self.plans
+            .iter()
+            .flat_map(|p| p.sweep.iter().chain(&p.leave))
+            .map(|d| d.usd_value)
+            .sum()
+    }
+
+    /// Plans that pay off at current gas prices
+    pub fn worthwhile(&self) -> impl Iterator<Item = &ConsolidationPlan> {
+        self.plans.iter().filter(|p| p.is_worthwhile())
+    }
+}
+
+/// Finds dust in a portfolio snapshot given per-network gas costs
+#[derive(Debug, Clone)]
+pub struct DustAnalyzer {
+    costs: HashMap<u64, NetworkGasCost>,
+    threshold_usd: f64,
+}
+
+impl Default for DustAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DustAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            costs: HashMap::new(),
+            threshold_usd: DEFAULT_THRESHOLD_USD,
+        }
+    }
+
+    pub fn with_network_cost(mut self, cost: NetworkGasCost) -> Self {
+        self.costs.insert(cost.chain_id, cost);
+        self
+    }
+
+    /// Treat holdings worth less than `usd` as dust even when cheap to move
+    pub fn with_threshold(mut self, usd: f64) -> Self {
+        self.threshold_usd = usd.max(0.0);
+        self
+    }
+
+    /// Build per-network consolidation plans
+    ///
+    /// Native tokens are the sweep target and unpriced tokens cannot be
+    /// valued, so neither is ever reported as dust.
+    pub fn analyze(&self, snapshot: &PortfolioSnapshot) -> DustReport {
+        let mut dust: BTreeMap<u64, Vec<DustHolding>> = BTreeMap::new();
+        let mut unassessed: Vec<u64> = Vec::new();
+
+        for allocation in snapshot.allocations.iter().filter(|a| is_candidate(a)) {
+            let Some(cost) = self.costs.get(&allocation.chain_id) else {
+                if !unassessed.contains(&allocation.chain_id) {
+                    unassessed.push(allocation.chain_id);
+                }
+                continue;
+            };
+            let transfer_cost_usd = cost.gas_cost_usd(ERC20_TRANSFER_GAS);
+            if allocation.usd_value < transfer_cost_usd.max(self.threshold_usd) {
+                dust.entry(allocation.chain_id).or_default().push(DustHolding {
+                    chain_id: allocation.chain_id,
+                    token_address: allocation.token_address,
+                    symbol: allocation.symbol.clone(),
+                    amount: allocation.amount,
+                    usd_value: allocation.usd_value,
+                    transfer_cost_usd,
+                });
+            }
+        }
+        unassessed.sort_unstable();
+
+        let plans = dust
+            .into_iter()
+            .map(|(chain_id, holdings)| plan(&self.costs[&chain_id], holdings))
+            .collect();
+
+        DustReport {
+            generated_at: Utc::now(),
+            threshold_usd: self.threshold_usd,
+            plans,
+            unassessed_networks: unassessed,
+        }
+    }
+}
+
+fn is_candidate(allocation: &TokenAllocation) -> bool {
+    allocation.priced && allocation.amount > 0.0 && allocation.token_address != Address::ZERO
+}
+
+fn plan(cost: &NetworkGasCost, mut holdings: Vec<DustHolding>) -> ConsolidationPlan {
+    holdings.sort_by(|a, b| b.usd_value.total_cmp(&a.usd_value));
+    let per_token_usd = cost.gas_cost_usd(SWEEP_GAS_PER_TOKEN);
+    let (sweep, leave): (Vec<_>, Vec<_>) = holdings.iter().cloned().partition(|h| h.usd_value > per_token_usd);
+
+    let estimated_cost_usd = if sweep.is_empty() {
+        0.0
+    } else {
+        cost.gas_cost_usd(SWEEP_BASE_GAS + SWEEP_GAS_PER_TOKEN * sweep.len() as u64)
+    };
+    let all_gas = SWEEP_BASE_GAS + SWEEP_GAS_PER_TOKEN * holdings.len() as u64;
+    let all_value: f64 = holdings.iter().map(|h| h.usd_value).sum();
+
+    ConsolidationPlan {
+        chain_id: cost.chain_id,
+        recovered_value_usd: sweep.iter().map(|h| h.usd_value).sum(),
+        sweep,
+        leave,
+        estimated_cost_usd,
+        break_even_gas_gwei: cost.break_even_gwei(all_gas, all_value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocation(chain_id: u64, byte: u8, usd: f64) -> TokenAllocation {
+        TokenAllocation {
+            chain_id,
+            token_address: Address::repeat_byte(byte),
+            symbol: format!("T{byte}"),
+            amount: 1.0,
+            usd_value: usd,
+            percentage: 0.0,
+            priced: true,
+        }
+    }
+
+    fn snapshot(allocations: Vec<TokenAllocation>) -> PortfolioSnapshot {
+        PortfolioSnapshot {
+            timestamp: Utc::now(),
+            total_usd: allocations.iter().map(|a| a.usd_value).sum(),
+            allocations,
+            account_totals: HashMap::new(),
+            network_totals: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_sweeps_dust_worth_more_than_its_gas() {
+        // 1 gwei at $1000: transfer ≈ $0.065, sweep ≈ $0.166 per token
+        let analyzer = DustAnalyzer::new().with_network_cost(NetworkGasCost {
+            chain_id: 56,
+            gas_price_gwei: 1.0,
+            native_usd: 1000.0,
+        });
+        let mut native = allocation(56, 0, 0.01);
+        native.token_address = Address::ZERO;
+        let mut unpriced = allocation(56, 4, 0.0);
+        unpriced.priced = false;
+
+        let report = analyzer.analyze(&snapshot(vec![
+            allocation(56, 1, 50.0),
+            allocation(56, 2, 0.9),
+            allocation(56, 3, 0.05),
+            native,
+            unpriced,
+            allocation(1, 5, 0.5),
+        ]));
+
+        assert_eq!(report.unassessed_networks, vec![1]);
+        assert_eq!(report.plans.len(), 1);
+        let plan = &report.plans[0];
+        assert_eq!(plan.sweep.len(), 1);
+        assert_eq!(plan.sweep[0].token_address, Address::repeat_byte(2));
+        assert_eq!(plan.leave.len(), 1);
+        assert!(plan.is_worthwhile());
+        assert!((plan.estimated_cost_usd - 0.187).abs() < 1e-9);
+        assert!((report.total_dust_usd() - 0.95).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_expensive_gas_reports_break_even() {
+        // 50 gwei at $2000: a transfer costs $6.50
+        let analyzer = DustAnalyzer::new()
+            .with_threshold(0.0)
+            .with_network_cost(NetworkGasCost {
+                chain_id: 1,
+                gas_price_gwei: 50.0,
+                native_usd: 2000.0,
+            });
+        let report = analyzer.analyze(&snapshot(vec![allocation(1, 1, 5.0), allocation(1, 2, 10.0)]));
+
+        let plan = &report.plans[0];
+        assert!(plan.sweep.is_empty());
+        assert!(!plan.is_worthwhile());
+        assert_eq!(report.worthwhile().count(), 0);
+        // $5 over 187k gas at $2000
+        let break_even = plan.break_even_gas_gwei.unwrap();
+        assert!((break_even - 5.0 / (187_000.0 * 1e-9 * 2000.0)).abs() < 1e-9);
+    }
+}
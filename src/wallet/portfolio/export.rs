@@ -0,0 +1,384 @@
+//! Portfolio export to tracker formats
+//!
+//! Turns a [`PortfolioSnapshot`] into one row per token and network
+//! (symbol, quantity, value, cost basis where known, chain) and writes it as
+//! CSV or JSON for import into external tax and portfolio tracking software.
+//!
+//! JSON exports also list each account with the color and identicon seed
+//! the GUI shows for it, see [`AccountIdentity`].
+//!
+//! Cost basis is never derived from chain data; it is whatever the user has
+//! entered in the [`CostBasisBook`], persisted to `~/.vaughan/cost_basis.json`.
+
+use super::PortfolioSnapshot;
+use crate::error::{Result, VaughanError};
+use crate::network::NetworkConfig;
+use crate::utils::identity::AccountIdentity;
+use crate::wallet::backup::canonical;
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Schema name embedded in JSON exports
+const EXPORT_SCHEMA: &str = "vaughan.portfolio-export";
+/// Current layout of JSON exports
+const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Columns of the CSV export, in order
+pub const CSV_HEADER: [&str; 9] = [
+    "Chain ID",
+    "Chain",
+    "Symbol",
+    "Token Address",
+    "Quantity",
+    "Price (USD)",
+    "Value (USD)",
+    "Cost Basis (USD)",
+    "Unrealized PnL (USD)",
+];
+
+/// Output format of a portfolio export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortfolioExportFormat {
+    Csv,
+    Json,
+}
+
+impl PortfolioExportFormat {
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// What the user paid for a holding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostBasisEntry {
+    pub chain_id: u64,
+    pub token_address: Address,
+    /// Total USD paid for the current position
+    pub total_usd: f64,
+}
+
+/// User-entered cost basis per token and network
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostBasisBook {
+    entries: Vec<CostBasisEntry>,
+}
+
+/// Get the default storage path for cost basis entries
+pub fn get_cost_basis_path() -> PathBuf {
+    let mut path = crate::security::keystore::storage::get_vaughan_dir();
+    path.push("cost_basis.json");
+    path
+}
+
+impl CostBasisBook {
+    /// Load entries from `path`; a missing or unreadable file gives an empty book
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable cost basis file {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, canonical::to_canonical_string(self)?)?;
+        Ok(())
+    }
+
+    /// Record the cost basis of a holding, replacing any earlier entry
+    pub fn set(&mut self, chain_id: u64, token_address: Address, total_usd: f64) -> Result<()> {
+        if !total_usd.is_finite() || total_usd < 0.0 {
+            return Err(VaughanError::ValidationError(format!(
+                "Invalid cost basis: {total_usd}"
+            )));
+        }
+        self.remove(chain_id, token_address);
+        self.entries.push(CostBasisEntry {
+            chain_id,
+            token_address,
+            total_usd,
+        });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, chain_id: u64, token_address: Address) {
+        self.entries
+            .retain(|e| !(e.chain_id == chain_id && e.token_address == token_address));
+    }
+
+    pub fn get(&self, chain_id: u64, token_address: Address) -> Option<f64> {
+        self.entries
+            .iter()
+            .find(|e| e.chain_id == chain_id && e.token_address == token_address)
+            .map(|e| e.total_usd)
+    }
+}
+
+/// One exported holding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoldingRow {
+    pub chain_id: u64,
+    pub chain: String,
+    pub symbol: String,
+    pub token_address: Address,
+    pub quantity: f64,
+    /// `None` when no price was available
+    pub price_usd: Option<f64>,
+    pub value_usd: Option<f64>,
+    pub cost_basis_usd: Option<f64>,
+    /// Value minus cost basis, when both are known
+    pub unrealized_pnl_usd: Option<f64>,
+}
+
+/// One account in the JSON export, with the identity the GUI shows for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountRow {
+    pub address: Address,
+    pub value_usd: f64,
+    /// Account color as `#rrggbb`
+    pub color: String,
+    /// Hex seed of the account's identicon
+    pub identicon_seed: String,
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    generated_at: DateTime<Utc>,
+    snapshot_at: DateTime<Utc>,
+    currency: &'static str,
+    accounts: &'a [AccountRow],
+    holdings: &'a [HoldingRow],
+}
+
+/// Builds tracker exports from a portfolio snapshot
+pub struct PortfolioExporter<'a> {
+    snapshot: &'a PortfolioSnapshot,
+    cost_basis: Option<&'a CostBasisBook>,
+    chain_names: HashMap<u64, String>,
+}
+
+impl<'a> PortfolioExporter<'a> {
+    pub fn new(snapshot: &'a PortfolioSnapshot) -> Self {
+        let chain_names = [
+            NetworkConfig::ethereum_mainnet(),
+            NetworkConfig::pulsechain(),
+            NetworkConfig::pulsechain_testnet(),
+            NetworkConfig::bsc(),
+            NetworkConfig::polygon(),
+        ]
+        .into_iter()
+        .map(|n| (n.chain_id, n.name))
+        .collect();
+        Self {
+            snapshot,
+            cost_basis: None,
+            chain_names,
+        }
+    }
+
+    /// Fill in cost basis and unrealized PnL from `book`
+    pub fn with_cost_basis(mut self, book: &'a CostBasisBook) -> Self {
+        self.cost_basis = Some(book);
+        self
+    }
+
+    /// Name chains after these networks, e.g. custom ones
+    pub fn with_networks(mut self, networks: &[NetworkConfig]) -> Self {
+        self.chain_names
+            .extend(networks.iter().map(|n| (n.chain_id, n.name.clone())));
+        self
+    }
+
+    /// Rows ordered by chain, then symbol, then token address
+    pub fn rows(&self) -> Vec<HoldingRow> {
+        let mut rows: Vec<HoldingRow> = self
+            .snapshot
+            .allocations
+            .iter()
+            .map(|a| {
+                let value_usd = a.priced.then_some(a.usd_value);
+                let cost_basis_usd = self.cost_basis.and_then(|b| b.get(a.chain_id, a.token_address));
+                HoldingRow {
+                    chain_id: a.chain_id,
+                    chain: self
+                        .chain_names
+                        .get(&a.chain_id)
+                        .cloned()
+                        .unwrap_or_else(|| format!("Chain {}", a.chain_id)),
+                    symbol: a.symbol.clone(),
+                    token_address: a.token_address,
+                    quantity: a.amount,
+                    price_usd: value_usd.filter(|_| a.amount > 0.0).map(|v| v / a.amount),
+                    value_usd,
+                    cost_basis_usd,
+                    unrealized_pnl_usd: value_usd.zip(cost_basis_usd).map(|(v, c)| v - c),
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| (a.chain_id, &a.symbol, a.token_address).cmp(&(b.chain_id, &b.symbol, b.token_address)));
+        rows
+    }
+
+    /// Accounts ordered by address
+    pub fn accounts(&self) -> Vec<AccountRow> {
+        let mut accounts: Vec<AccountRow> = self
+            .snapshot
+            .account_totals
+            .iter()
+            .map(|(address, value_usd)| {
+                let identity = AccountIdentity::for_address(*address);
+                AccountRow {
+                    address: *address,
+                    value_usd: *value_usd,
+                    color: identity.color().hex(),
+                    identicon_seed: identity.seed_hex(),
+                }
+            })
+            .collect();
+        accounts.sort_by_key(|a| a.address);
+        accounts
+    }
+
+    pub fn export(&self, format: PortfolioExportFormat) -> Result<String> {
+        match format {
+            PortfolioExportFormat::Csv => Ok(self.to_csv()),
+            PortfolioExportFormat::Json => self.to_json(),
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = CSV_HEADER.join(",");
+        out.push('\n');
+        for row in self.rows() {
+            let fields = [
+                row.chain_id.to_string(),
+                csv_field(&row.chain),
+                csv_field(&row.symbol),
+                row.token_address.to_string(),
+                row.quantity.to_string(),
+                optional(row.price_usd),
+                optional(row.value_usd),
+                optional(row.cost_basis_usd),
+                optional(row.unrealized_pnl_usd),
+            ];
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Canonical, schema-tagged JSON
+    pub fn to_json(&self) -> Result<String> {
+        let rows = self.rows();
+        let accounts = self.accounts();
+        let export = JsonExport {
+            generated_at: Utc::now(),
+            snapshot_at: self.snapshot.timestamp,
+            currency: "USD",
+            accounts: &accounts,
+            holdings: &rows,
+        };
+        canonical::to_versioned_document(EXPORT_SCHEMA, EXPORT_SCHEMA_VERSION, &export)
+    }
+}
+
+fn optional(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Quote a free-text field
+///
+/// Token symbols and network names come from untrusted sources, so leading
+/// formula characters are neutralised before a spreadsheet can evaluate them.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::portfolio::TokenAllocation;
+
+    fn allocation(chain_id: u64, byte: u8, symbol: &str, amount: f64, usd: Option<f64>) -> TokenAllocation {
+        TokenAllocation {
+            chain_id,
+            token_address: Address::repeat_byte(byte),
+            symbol: symbol.to_string(),
+            amount,
+            usd_value: usd.unwrap_or(0.0),
+            percentage: 0.0,
+            priced: usd.is_some(),
+        }
+    }
+
+    fn snapshot() -> PortfolioSnapshot {
+        PortfolioSnapshot {
+            timestamp: Utc::now(),
+            total_usd: 300.0,
+            allocations: vec![
+                allocation(369, 2, "=HYPERLINK(\"x\")", 10.0, None),
+                allocation(1, 1, "USDC", 200.0, Some(200.0)),
+                allocation(1, 0, "ETH", 0.05, Some(100.0)),
+            ],
+            account_totals: HashMap::from([(Address::repeat_byte(9), 300.0)]),
+            network_totals: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_csv_rows_with_cost_basis() {
+        let mut book = CostBasisBook::default();
+        book.set(1, Address::repeat_byte(0), 80.0).unwrap();
+        assert!(book.set(1, Address::repeat_byte(1), f64::NAN).is_err());
+
+        let snapshot = snapshot();
+        let csv = PortfolioExporter::new(&snapshot).with_cost_basis(&book).to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], CSV_HEADER.join(","));
+        assert!(lines[1].starts_with("1,Ethereum Mainnet,ETH,"));
+        assert!(lines[1].ends_with(",0.05,2000,100,80,20"));
+        // Unknown price and cost basis stay empty
+        assert!(lines[2].ends_with(",200,1,200,,"));
+        // Untrusted symbols cannot become spreadsheet formulas
+        assert!(lines[3].starts_with("369,PulseChain,\"'=HYPERLINK(\"\"x\"\")\","));
+        assert!(lines[3].ends_with(",10,,,,"));
+    }
+
+    #[test]
+    fn test_json_export_is_versioned() {
+        let snapshot = snapshot();
+        let json = PortfolioExporter::new(&snapshot).to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["schema"], EXPORT_SCHEMA);
+        assert_eq!(value["holdings"].as_array().unwrap().len(), 3);
+        assert_eq!(value["holdings"][0]["symbol"], "ETH");
+        assert!(value["holdings"][0]["cost_basis_usd"].is_null());
+
+        let identity = AccountIdentity::for_address(Address::repeat_byte(9));
+        assert_eq!(value["accounts"][0]["color"], identity.color().hex());
+        assert_eq!(value["accounts"][0]["identicon_seed"], identity.seed_hex());
+    }
+}
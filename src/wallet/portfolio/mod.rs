@@ -0,0 +1,824 @@
+//! Portfolio valuation and PnL tracking
+//!
+//! Aggregates native and ERC-20 balances across all accounts and networks into a
+//! single USD valuation, and keeps a local history of snapshots so the GUI can
+//! show 24h / 7d / 30d changes and per-token allocation breakdowns. History is
+//! kept in USD and converted to the display currency when shown, see
+//! [`crate::tokens::fx`]. The native currency and its wrapped token (ETH and
+//! WETH, PLS and WPLS, ...) can also be shown as one holding, see
+//! [`PortfolioSnapshot::native_holdings`].
+//!
+//! Snapshots are persisted to `~/.vaughan/portfolio_history.json`. Holdings can
+//! be exported for external trackers, see [`export`], and screened for dust
+//! worth consolidating, see [`dust`]. Signed, verifiable statements for
+//! auditors are built with [`audit`]. LP, stake and lending positions are
+//! added with [`PortfolioSnapshot::add_positions`].
+
+pub mod audit;
+pub mod dust;
+pub mod export;
+
+use crate::config::{PricingSettings, SectionWatch};
+use crate::defi::positions::Position;
+use crate::error::{NetworkError, Result};
+use crate::tokens::fx::{FiatAmount, FiatCurrency, FxRates};
+use crate::tokens::hidden::HiddenTokens;
+use crate::tokens::oracle::MultiSourcePriceOracle;
+use crate::tokens::sorting::{sort_tokens, TokenSortContext, TokenSortStrategy};
+use crate::tokens::wrapped::wrapped_native;
+use crate::tokens::{TokenBalance, TokenInfo};
+use alloy::primitives::{Address, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Snapshots older than this are dropped when history is saved
+const HISTORY_RETENTION_DAYS: i64 = 90;
+
+/// Source of raw balances for portfolio valuation
+#[async_trait::async_trait]
+pub trait PortfolioBalanceSource: Send + Sync {
+    /// Fetch native and token balances for `account` on `chain_id`
+    async fn fetch_balances(&self, account: Address, chain_id: u64) -> Result<Vec<TokenBalance>>;
+}
+
+/// Balance source reading directly from per-chain RPC endpoints
+pub struct RpcBalanceSource {
+    rpc_urls: HashMap<u64, String>,
+    tokens: HashMap<u64, Vec<TokenInfo>>,
+}
+
+impl RpcBalanceSource {
+    pub fn new() -> Self {
+        Self {
+            rpc_urls: HashMap::new(),
+            tokens: HashMap::new(),
+        }
+    }
+
+    /// Set the RPC endpoint for a chain
+    pub fn with_rpc_url(mut self, chain_id: u64, rpc_url: impl Into<String>) -> Self {
+        self.rpc_urls.insert(chain_id, rpc_url.into());
+        self
+    }
+
+    /// Track a token; native tokens (`is_native`) are queried with `eth_getBalance`
+    pub fn with_token(mut self, token: TokenInfo) -> Self {
+        self.tokens.entry(token.chain_id).or_default().push(token);
+        self
+    }
+
+    /// Track the chain's wrapped native token from the registry, if it has one
+    pub fn with_wrapped_native(self, chain_id: u64) -> Self {
+        match wrapped_native(chain_id) {
+            Some(wrapped) => self.with_token(wrapped.token_info()),
+            None => self,
+        }
+    }
+}
+
+impl Default for RpcBalanceSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PortfolioBalanceSource for RpcBalanceSource {
+    async fn fetch_balances(&self, account: Address, chain_id: u64) -> Result<Vec<TokenBalance>> {
+        let rpc_url = self
+            .rpc_urls
+            .get(&chain_id)
+            .ok_or(NetworkError::UnsupportedNetwork { network_id: chain_id })?;
+        let provider = crate::network::connect_rpc(rpc_url, "portfolio balances")?;
+
+        let mut balances = Vec::new();
+
+        for token in self.tokens.get(&chain_id).into_iter().flatten() {
+            let raw = if token.is_native {
+                provider.get_balance(account).await
+            } else {
+                // balanceOf(address) = 0x70a08231 + padded address
+                let mut call_data = Vec::with_capacity(36);
+                call_data.extend_from_slice(&[0x70, 0xa0, 0x82, 0x31]);
+                call_data.extend_from_slice(&[0u8; 12]);
+                call_data.extend_from_slice(account.as_slice());
+                let request = TransactionRequest::default().to(token.address).input(call_data.into());
+                provider.call(request).await.map(|result| {
+                    if result.len() >= 32 {
+                        U256::from_be_slice(&result[result.len() - 32..])
+                    } else {
+                        U256::ZERO
+                    }
+                })
+            };
+
+            match raw {
+                Ok(raw) => balances.push(token_balance(token.clone(), raw)),
+                Err(e) => tracing::warn!("Failed to fetch {} balance on chain {}: {}", token.symbol, chain_id, e),
+            }
+        }
+
+        Ok(balances)
+    }
+}
+
+/// Build a `TokenBalance` from a raw on-chain amount
+pub fn token_balance(token: TokenInfo, raw: U256) -> TokenBalance {
+    let formatted = alloy::primitives::utils::format_units(raw, token.decimals).unwrap_or_else(|_| "0".to_string());
+    TokenBalance {
+        token,
+        balance: raw.to_string(),
+        formatted,
+        usd_value: None,
+        fiat_value: None,
+    }
+}
+
+/// Time window for history queries and change calculations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryRange {
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl HistoryRange {
+    /// Duration covered by this range (`None` for all history)
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        match self {
+            HistoryRange::Day => Some(chrono::Duration::hours(24)),
+            HistoryRange::Week => Some(chrono::Duration::days(7)),
+            HistoryRange::Month => Some(chrono::Duration::days(30)),
+            HistoryRange::All => None,
+        }
+    }
+}
+
+/// Share of the portfolio held in a single token on a single network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenAllocation {
+    pub chain_id: u64,
+    pub token_address: Address,
+    pub symbol: String,
+    /// Total amount held across all accounts
+    pub amount: f64,
+    /// USD value (0 when no price is available)
+    pub usd_value: f64,
+    /// Percentage of the total portfolio value (0-100)
+    pub percentage: f64,
+    /// Whether a price was available for this token
+    pub priced: bool,
+}
+
+impl TokenAllocation {
+    /// Value in `currency`, `None` when unpriced or without an exchange rate
+    pub fn value_in(&self, rates: &FxRates, currency: FiatCurrency) -> Option<FiatAmount> {
+        self.priced.then(|| rates.convert(self.usd_value, currency)).flatten()
+    }
+}
+
+/// Point-in-time portfolio valuation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub total_usd: f64,
+    /// Per-token allocations sorted by USD value, largest first
+    pub allocations: Vec<TokenAllocation>,
+    /// USD value per account
+    pub account_totals: HashMap<Address, f64>,
+    /// USD value per network
+    pub network_totals: HashMap<u64, f64>,
+}
+
+impl PortfolioSnapshot {
+    /// Aggregate priced balances into a snapshot
+    pub fn from_balances(balances: &[(Address, TokenBalance)], timestamp: DateTime<Utc>) -> Self {
+        let mut allocations: HashMap<(u64, Address), TokenAllocation> = HashMap::new();
+        let mut account_totals: HashMap<Address, f64> = HashMap::new();
+        let mut network_totals: HashMap<u64, f64> = HashMap::new();
+
+        for (account, balance) in balances {
+            let amount = balance.formatted.parse::<f64>().unwrap_or(0.0);
+            let usd_value = balance.usd_value.unwrap_or(0.0);
+            let chain_id = balance.token.chain_id;
+
+            let entry = allocations
+                .entry((chain_id, balance.token.address))
+                .or_insert_with(|| TokenAllocation {
+                    chain_id,
+                    token_address: balance.token.address,
+                    symbol: balance.token.symbol.clone(),
+                    amount: 0.0,
+                    usd_value: 0.0,
+                    percentage: 0.0,
+                    priced: balance.usd_value.is_some(),
+                });
+            entry.amount += amount;
+            entry.usd_value += usd_value;
+            entry.priced |= balance.usd_value.is_some();
+
+            *account_totals.entry(*account).or_default() += usd_value;
+            *network_totals.entry(chain_id).or_default() += usd_value;
+        }
+
+        let total_usd: f64 = allocations.values().map(|a| a.usd_value).sum();
+        let mut allocations: Vec<TokenAllocation> = allocations
+            .into_values()
+            .filter(|a| a.amount > 0.0)
+            .map(|mut a| {
+                a.percentage = if total_usd > 0.0 {
+                    a.usd_value / total_usd * 100.0
+                } else {
+                    0.0
+                };
+                a
+            })
+            .collect();
+        allocations.sort_by(|a, b| b.usd_value.total_cmp(&a.usd_value));
+
+        Self {
+            timestamp,
+            total_usd,
+            allocations,
+            account_totals,
+            network_totals,
+        }
+    }
+
+    /// Total value in `currency`, `None` without an exchange rate
+    pub fn total_in(&self, rates: &FxRates, currency: FiatCurrency) -> Option<FiatAmount> {
+        rates.convert(self.total_usd, currency)
+    }
+}
+
+/// Native currency and its wrapped token held on one network
+#[derive(Debug, Clone, PartialEq)]
+pub struct NativeHolding {
+    pub chain_id: u64,
+    pub native_symbol: String,
+    pub wrapped_symbol: String,
+    pub native_amount: f64,
+    pub wrapped_amount: f64,
+    /// USD value of both together
+    pub usd_value: f64,
+}
+
+impl NativeHolding {
+    /// Native and wrapped amounts together, they convert 1:1
+    pub fn total_amount(&self) -> f64 {
+        self.native_amount + self.wrapped_amount
+    }
+}
+
+impl PortfolioSnapshot {
+    /// Count the USD value of DeFi positions, see [`crate::defi::positions`]
+    ///
+    /// A position replaces the unpriced allocation of its LP token, aToken or
+    /// cToken; positions without a value and tokens that already had a price
+    /// are left alone.
+    pub fn add_positions(&mut self, positions: &[Position]) {
+        for position in positions {
+            let Some(usd_value) = position.usd_value else {
+                continue;
+            };
+            let existing = self
+                .allocations
+                .iter_mut()
+                .find(|a| a.chain_id == position.chain_id && a.token_address == position.contract);
+            match existing {
+                Some(allocation) if allocation.priced => continue,
+                Some(allocation) => {
+                    allocation.symbol = position.symbol.clone();
+                    allocation.usd_value = usd_value;
+                    allocation.priced = true;
+                }
+                None => self.allocations.push(TokenAllocation {
+                    chain_id: position.chain_id,
+                    token_address: position.contract,
+                    symbol: position.symbol.clone(),
+                    amount: position.balance_f64(),
+                    usd_value,
+                    percentage: 0.0,
+                    priced: true,
+                }),
+            }
+            *self.account_totals.entry(position.account).or_default() += usd_value;
+            *self.network_totals.entry(position.chain_id).or_default() += usd_value;
+            self.total_usd += usd_value;
+        }
+
+        for allocation in &mut self.allocations {
+            allocation.percentage = if self.total_usd > 0.0 {
+                allocation.usd_value / self.total_usd * 100.0
+            } else {
+                0.0
+            };
+        }
+        self.allocations.sort_by(|a, b| b.usd_value.total_cmp(&a.usd_value));
+    }
+
+    /// Allocations ordered with the user's sort strategy, see [`crate::tokens::sorting`]
+    pub fn sorted_allocations(&self, strategy: TokenSortStrategy, context: &TokenSortContext) -> Vec<TokenAllocation> {
+        let mut allocations = self.allocations.clone();
+        sort_tokens(&mut allocations, strategy, context);
+        allocations
+    }
+
+    /// Native plus wrapped native holdings per network, largest USD value first
+    ///
+    /// Only networks with a wrapped token in [`crate::tokens::wrapped`] are
+    /// included; their allocations stay listed separately as well.
+    pub fn native_holdings(&self) -> Vec<NativeHolding> {
+        let mut holdings: HashMap<u64, NativeHolding> = HashMap::new();
+        for allocation in &self.allocations {
+            let Some(wrapped) = wrapped_native(allocation.chain_id) else {
+                continue;
+            };
+            let is_native = allocation.token_address == Address::ZERO;
+            if !is_native && allocation.token_address != wrapped.address {
+                continue;
+            }
+
+            let holding = holdings.entry(allocation.chain_id).or_insert_with(|| NativeHolding {
+                chain_id: allocation.chain_id,
+                native_symbol: wrapped.native_symbol.to_string(),
+                wrapped_symbol: wrapped.symbol.to_string(),
+                native_amount: 0.0,
+                wrapped_amount: 0.0,
+                usd_value: 0.0,
+            });
+            if is_native {
+                holding.native_symbol = allocation.symbol.clone();
+                holding.native_amount += allocation.amount;
+            } else {
+                holding.wrapped_amount += allocation.amount;
+            }
+            holding.usd_value += allocation.usd_value;
+        }
+
+        let mut holdings: Vec<NativeHolding> = holdings.into_values().collect();
+        holdings.sort_by(|a, b| b.usd_value.total_cmp(&a.usd_value));
+        holdings
+    }
+}
+
+/// Change in portfolio value over a time window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PortfolioChange {
+    pub range: HistoryRange,
+    pub start_usd: f64,
+    pub end_usd: f64,
+    pub absolute: f64,
+    /// Percentage change, `None` when the starting value was zero
+    pub percent: Option<f64>,
+}
+
+/// Get the default storage path for portfolio history
+pub fn get_portfolio_history_path() -> PathBuf {
+    let mut path = crate::security::keystore::storage::get_vaughan_dir();
+    path.push("portfolio_history.json");
+    path
+}
+
+/// Portfolio manager - values holdings and tracks history
+pub struct PortfolioManager {
+    accounts: Vec<Address>,
+    chain_ids: Vec<u64>,
+    source: Arc<dyn PortfolioBalanceSource>,
+    oracle: Arc<MultiSourcePriceOracle>,
+    history: Vec<PortfolioSnapshot>,
+    storage_path: Option<PathBuf>,
+    hidden_tokens: HiddenTokens,
+}
+
+impl PortfolioManager {
+    /// Create a manager persisting history to the default location
+    ///
+    /// Values are shown in the `pricing.currency` of the global settings,
+    /// following changes; call from within a Tokio runtime.
+    pub fn new(source: Arc<dyn PortfolioBalanceSource>, oracle: Arc<MultiSourcePriceOracle>) -> Self {
+        let storage_path = get_portfolio_history_path();
+        let history = Self::load_history(&storage_path);
+        oracle.follow_settings(crate::config::settings_store().subscribe_section());
+        Self {
+            accounts: Vec::new(),
+            chain_ids: Vec::new(),
+            source,
+            oracle,
+            history,
+            storage_path: Some(storage_path),
+            hidden_tokens: HiddenTokens::load(),
+        }
+    }
+
+    /// Create a manager with in-memory history only
+    pub fn in_memory(source: Arc<dyn PortfolioBalanceSource>, oracle: Arc<MultiSourcePriceOracle>) -> Self {
+        Self {
+            accounts: Vec::new(),
+            chain_ids: Vec::new(),
+            source,
+            oracle,
+            history: Vec::new(),
+            storage_path: None,
+            hidden_tokens: HiddenTokens::default(),
+        }
+    }
+
+    /// Show values in the currency of these `pricing` settings, following changes
+    pub fn follow_settings(&self, settings: SectionWatch<PricingSettings>) {
+        self.oracle.follow_settings(settings);
+    }
+
+    /// Persist history to a custom file instead of the default location
+    pub fn with_storage_path(mut self, path: PathBuf) -> Self {
+        self.history = Self::load_history(&path);
+        self.storage_path = Some(path);
+        self
+    }
+
+    /// Set the accounts included in valuations
+    pub fn set_accounts(&mut self, accounts: Vec<Address>) {
+        self.accounts = accounts;
+    }
+
+    /// Set the networks included in valuations
+    pub fn set_networks(&mut self, chain_ids: Vec<u64>) {
+        self.chain_ids = chain_ids;
+    }
+
+    /// Set the tokens excluded from valuations
+    pub fn set_hidden_tokens(&mut self, hidden_tokens: HiddenTokens) {
+        self.hidden_tokens = hidden_tokens;
+    }
+
+    /// Value all accounts on all networks, record and return the snapshot
+    pub async fn snapshot(&mut self) -> Result<PortfolioSnapshot> {
+        let mut holdings: Vec<(Address, TokenBalance)> = Vec::new();
+
+        for account in &self.accounts {
+            for chain_id in &self.chain_ids {
+                match self.source.fetch_balances(*account, *chain_id).await {
+                    Ok(mut balances) => {
+                        self.hidden_tokens.retain_visible_balances(&mut balances);
+                        holdings.extend(balances.into_iter().map(|b| (*account, b)))
+                    }
+                    Err(e) => tracing::warn!("Skipping {} on chain {} in portfolio: {}", account, chain_id, e),
+                }
+            }
+        }
+
+        let mut balances: Vec<TokenBalance> = holdings.iter().map(|(_, b)| b.clone()).collect();
+        self.oracle.apply_to_balances(&mut balances).await?;
+        for ((_, holding), priced) in holdings.iter_mut().zip(balances) {
+            holding.usd_value = priced.usd_value;
+        }
+
+        let snapshot = PortfolioSnapshot::from_balances(&holdings, Utc::now());
+        self.record(snapshot.clone())?;
+
+        let total = match self.oracle.to_fiat(snapshot.total_usd).await {
+            Some(value) => value.to_string(),
+            None => FiatCurrency::Usd.format(snapshot.total_usd),
+        };
+        tracing::info!(
+            "📊 Portfolio snapshot: {} across {} tokens",
+            total,
+            snapshot.allocations.len()
+        );
+        Ok(snapshot)
+    }
+
+    /// Latest total value in the oracle's display currency
+    pub async fn latest_value(&self) -> Option<FiatAmount> {
+        let latest = self.latest()?;
+        self.oracle.to_fiat(latest.total_usd).await
+    }
+
+    /// Add a snapshot to history and persist it
+    pub fn record(&mut self, snapshot: PortfolioSnapshot) -> Result<()> {
+        self.history.push(snapshot);
+        self.history.sort_by_key(|s| s.timestamp);
+
+        let cutoff = Utc::now() - chrono::Duration::days(HISTORY_RETENTION_DAYS);
+        self.history.retain(|s| s.timestamp >= cutoff);
+
+        self.save_history()
+    }
+
+    /// Snapshots within `range`, oldest first
+    pub fn get_history(&self, range: HistoryRange) -> Vec<PortfolioSnapshot> {
+        match range.duration() {
+            Some(duration) => {
+                let cutoff = Utc::now() - duration;
+                self.history.iter().filter(|s| s.timestamp >= cutoff).cloned().collect()
+            }
+            None => self.history.clone(),
+        }
+    }
+
+    /// Most recent snapshot
+    pub fn latest(&self) -> Option<&PortfolioSnapshot> {
+        self.history.last()
+    }
+
+    /// Value change between the start of `range` and the latest snapshot
+    ///
+    /// The baseline is the newest snapshot taken at or before the start of the
+    /// window, or the oldest snapshot inside it if history is shorter.
+    pub fn change(&self, range: HistoryRange) -> Option<PortfolioChange> {
+        let latest = self.history.last()?;
+        let baseline = match range.duration() {
+            Some(duration) => {
+                let cutoff = latest.timestamp - duration;
+                self.history
+                    .iter()
+                    .rev()
+                    .find(|s| s.timestamp <= cutoff)
+                    .or_else(|| self.history.first())?
+            }
+            None => self.history.first()?,
+        };
+
+        let absolute = latest.total_usd - baseline.total_usd;
+        Some(PortfolioChange {
+            range,
+            start_usd: baseline.total_usd,
+            end_usd: latest.total_usd,
+            absolute,
+            percent: (baseline.total_usd > 0.0).then(|| absolute / baseline.total_usd * 100.0),
+        })
+    }
+
+    /// Per-token allocation from the latest snapshot
+    pub fn allocation(&self) -> Vec<TokenAllocation> {
+        self.latest().map(|s| s.allocations.clone()).unwrap_or_default()
+    }
+
+    fn load_history(path: &PathBuf) -> Vec<PortfolioSnapshot> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable portfolio history {}: {}", path.display(), e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn save_history(&self) -> Result<()> {
+        let Some(path) = &self.storage_path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(&self.history)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(chain_id: u64, address: Address, symbol: &str, amount: &str, usd: Option<f64>) -> TokenBalance {
+        TokenBalance {
+            token: TokenInfo::new(address, chain_id, symbol.to_string(), symbol.to_string(), 18),
+            balance: "0".to_string(),
+            formatted: amount.to_string(),
+            usd_value: usd,
+            fiat_value: None,
+        }
+    }
+
+    fn snapshot_at(total: f64, hours_ago: i64) -> PortfolioSnapshot {
+        PortfolioSnapshot {
+            timestamp: Utc::now() - chrono::Duration::hours(hours_ago),
+            total_usd: total,
+            allocations: Vec::new(),
+            account_totals: HashMap::new(),
+            network_totals: HashMap::new(),
+        }
+    }
+
+    struct EmptySource;
+
+    #[async_trait::async_trait]
+    impl PortfolioBalanceSource for EmptySource {
+        async fn fetch_balances(&self, _account: Address, _chain_id: u64) -> Result<Vec<TokenBalance>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn manager() -> PortfolioManager {
+        PortfolioManager::in_memory(Arc::new(EmptySource), Arc::new(MultiSourcePriceOracle::new()))
+    }
+
+    #[test]
+    fn test_snapshot_aggregates_across_accounts() {
+        let alice = Address::from([1u8; 20]);
+        let bob = Address::from([2u8; 20]);
+        let token = Address::from([9u8; 20]);
+
+        let holdings = vec![
+            (alice, balance(1, Address::ZERO, "ETH", "1.0", Some(300.0))),
+            (bob, balance(1, Address::ZERO, "ETH", "2.0", Some(600.0))),
+            (alice, balance(369, token, "HEX", "100", Some(100.0))),
+            (bob, balance(369, Address::from([8u8; 20]), "NOPRICE", "5", None)),
+        ];
+
+        let snapshot = PortfolioSnapshot::from_balances(&holdings, Utc::now());
+
+        assert_eq!(snapshot.total_usd, 1000.0);
+        assert_eq!(snapshot.allocations[0].symbol, "ETH");
+        assert_eq!(snapshot.allocations[0].amount, 3.0);
+        assert!((snapshot.allocations[0].percentage - 90.0).abs() < 1e-9);
+        assert!(
+            !snapshot
+                .allocations
+                .iter()
+                .find(|a| a.symbol == "NOPRICE")
+                .unwrap()
+                .priced
+        );
+        assert_eq!(snapshot.account_totals[&alice], 400.0);
+        assert_eq!(snapshot.network_totals[&369], 100.0);
+
+        let rates = FxRates {
+            rates: HashMap::from([(FiatCurrency::Gbp, 0.8)]),
+            fetched_at: Utc::now(),
+        };
+        assert_eq!(
+            snapshot.total_in(&rates, FiatCurrency::Gbp).unwrap().to_string(),
+            "£800.00"
+        );
+        assert!(snapshot.total_in(&rates, FiatCurrency::Brl).is_none());
+        assert!(snapshot.allocations[2].value_in(&rates, FiatCurrency::Gbp).is_none());
+    }
+
+    #[test]
+    fn test_native_and_wrapped_combined() {
+        let alice = Address::from([1u8; 20]);
+        let wpls = wrapped_native(369).unwrap().address;
+
+        let holdings = vec![
+            (alice, balance(369, Address::ZERO, "PLS", "1000", Some(10.0))),
+            (alice, balance(369, wpls, "WPLS", "500", Some(5.0))),
+            (alice, balance(369, Address::from([9u8; 20]), "HEX", "100", Some(100.0))),
+            (alice, balance(1, Address::ZERO, "ETH", "1", Some(3000.0))),
+        ];
+
+        let combined = PortfolioSnapshot::from_balances(&holdings, Utc::now()).native_holdings();
+        assert_eq!(combined.len(), 2);
+        assert_eq!(combined[0].native_symbol, "ETH");
+        assert_eq!(combined[0].wrapped_amount, 0.0);
+
+        let pulse = &combined[1];
+        assert_eq!((pulse.native_amount, pulse.wrapped_amount), (1000.0, 500.0));
+        assert_eq!(pulse.total_amount(), 1500.0);
+        assert_eq!(pulse.usd_value, 15.0);
+        assert_eq!(pulse.wrapped_symbol, "WPLS");
+    }
+
+    #[test]
+    fn test_positions_replace_unpriced_lp_tokens() {
+        use crate::defi::positions::PositionKind;
+
+        let alice = Address::from([1u8; 20]);
+        let pair = Address::from([7u8; 20]);
+        let holdings = vec![
+            (alice, balance(369, Address::ZERO, "PLS", "1000", Some(10.0))),
+            (alice, balance(369, pair, "PLP", "2", None)),
+        ];
+        let mut snapshot = PortfolioSnapshot::from_balances(&holdings, Utc::now());
+        let position = Position {
+            chain_id: 369,
+            account: alice,
+            kind: PositionKind::LiquidityPool,
+            protocol: "PLP".to_string(),
+            contract: pair,
+            symbol: "PLP WPLS/HEX".to_string(),
+            balance: U256::from(2) * U256::from(10u64.pow(18)),
+            decimals: 18,
+            underlying: Vec::new(),
+            usd_value: Some(30.0),
+        };
+        snapshot.add_positions(&[
+            position.clone(),
+            Position {
+                usd_value: None,
+                ..position
+            },
+        ]);
+
+        assert_eq!(snapshot.total_usd, 40.0);
+        assert_eq!(snapshot.allocations.len(), 2);
+        assert_eq!(snapshot.allocations[0].symbol, "PLP WPLS/HEX");
+        assert_eq!(snapshot.allocations[0].percentage, 75.0);
+        assert_eq!(snapshot.account_totals[&alice], 40.0);
+    }
+
+    #[test]
+    fn test_change_over_ranges() {
+        let mut manager = manager();
+        manager.record(snapshot_at(100.0, 24 * 10)).unwrap();
+        manager.record(snapshot_at(200.0, 30)).unwrap();
+        manager.record(snapshot_at(150.0, 0)).unwrap();
+
+        let day = manager.change(HistoryRange::Day).unwrap();
+        assert_eq!(day.start_usd, 200.0);
+        assert_eq!(day.absolute, -50.0);
+        assert_eq!(day.percent, Some(-25.0));
+
+        let week = manager.change(HistoryRange::Week).unwrap();
+        assert_eq!(week.start_usd, 100.0);
+
+        assert_eq!(manager.get_history(HistoryRange::Day).len(), 1);
+        assert_eq!(manager.get_history(HistoryRange::All).len(), 3);
+    }
+
+    #[test]
+    fn test_history_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("portfolio_history.json");
+
+        let mut first = manager().with_storage_path(path.clone());
+        first.record(snapshot_at(42.0, 1)).unwrap();
+
+        let second = manager().with_storage_path(path);
+        assert_eq!(second.latest().map(|s| s.total_usd), Some(42.0));
+    }
+
+    struct FixedSource(Vec<TokenBalance>);
+
+    #[async_trait::async_trait]
+    impl PortfolioBalanceSource for FixedSource {
+        async fn fetch_balances(&self, _account: Address, _chain_id: u64) -> Result<Vec<TokenBalance>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hidden_tokens_excluded_from_snapshot() {
+        let scam = balance(369, Address::from([7u8; 20]), "SCAM", "1000", None);
+        let hex = balance(369, Address::from([9u8; 20]), "HEX", "10", None);
+        let source = FixedSource(vec![scam.clone(), hex]);
+
+        let mut manager = PortfolioManager::in_memory(Arc::new(source), Arc::new(MultiSourcePriceOracle::new()));
+        manager.set_accounts(vec![Address::from([1u8; 20])]);
+        manager.set_networks(vec![369]);
+
+        let mut hidden = HiddenTokens::default();
+        hidden.hide(&scam.token, None);
+        manager.set_hidden_tokens(hidden);
+
+        let snapshot = manager.snapshot().await.unwrap();
+        assert_eq!(snapshot.allocations.len(), 1);
+        assert_eq!(snapshot.allocations[0].symbol, "HEX");
+    }
+
+    #[tokio::test]
+    async fn test_latest_value_in_the_configured_currency() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = crate::config::SettingsStore::open(dir.path().join("settings.json")).unwrap();
+        let mut settings = store.current().as_ref().clone();
+        settings.pricing.currency = "EUR".to_string();
+        store.update(settings).unwrap();
+
+        let oracle = Arc::new(MultiSourcePriceOracle::new());
+        oracle
+            .set_fx_rates(FxRates {
+                rates: HashMap::from([(FiatCurrency::Eur, 0.9)]),
+                fetched_at: Utc::now(),
+            })
+            .await;
+        let mut manager = PortfolioManager::in_memory(Arc::new(EmptySource), oracle.clone());
+        manager.follow_settings(store.subscribe_section());
+        manager.record(snapshot_at(100.0, 0)).unwrap();
+
+        for _ in 0..100 {
+            if oracle.currency().await == FiatCurrency::Eur {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let value = manager.latest_value().await.unwrap();
+        assert_eq!(value.currency, FiatCurrency::Eur);
+        assert!((value.amount - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_old_snapshots_pruned() {
+        let mut manager = manager();
+        manager
+            .record(snapshot_at(1.0, 24 * (HISTORY_RETENTION_DAYS + 1)))
+            .unwrap();
+        manager.record(snapshot_at(2.0, 0)).unwrap();
+        assert_eq!(manager.get_history(HistoryRange::All).len(), 1);
+    }
+}
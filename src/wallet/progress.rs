@@ -0,0 +1,195 @@
+//! Progress reporting for slow wallet operations
+//!
+//! Argon2id key derivation, keystore decryption and HD discovery scans can take
+//! several seconds. Operations that support progress accept a
+//! [`ProgressReporter`] and emit [`ProgressEvent`]s carrying a `completed` /
+//! `total` pair, so the GUI can render a determinate progress bar instead of an
+//! indefinite spinner.
+//!
+//! A reporter can be any `Fn(ProgressEvent)` closure, or the sending half of
+//! [`progress_channel`] when events should be streamed into a subscription.
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Kind of operation a progress event belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressOperation {
+    Import,
+    Creation,
+    Discovery,
+    Backup,
+    Restore,
+}
+
+/// Progress update for a running operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressEvent {
+    /// Identifies the operation run this event belongs to
+    pub operation_id: Uuid,
+    pub operation: ProgressOperation,
+    /// Human readable description of the current step
+    pub stage: String,
+    /// Steps completed so far
+    pub completed: u64,
+    /// Total number of steps (may grow during discovery scans)
+    pub total: u64,
+    /// Set on the final event of an operation
+    pub finished: bool,
+}
+
+impl ProgressEvent {
+    /// Completion ratio in the range 0.0 - 1.0
+    pub fn fraction(&self) -> f32 {
+        if self.finished {
+            return 1.0;
+        }
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.completed as f32 / self.total as f32).min(1.0)
+    }
+
+    /// Completion as a whole percentage (0-100)
+    pub fn percent(&self) -> u8 {
+        (self.fraction() * 100.0).round() as u8
+    }
+}
+
+/// Receiver of progress events
+pub trait ProgressReporter: Send + Sync {
+    fn report(&self, event: ProgressEvent);
+}
+
+impl<F> ProgressReporter for F
+where
+    F: Fn(ProgressEvent) + Send + Sync,
+{
+    fn report(&self, event: ProgressEvent) {
+        self(event)
+    }
+}
+
+/// Reporter that discards all events
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressReporter for NoProgress {
+    fn report(&self, _event: ProgressEvent) {}
+}
+
+/// Reporter forwarding events to an unbounded channel
+#[derive(Debug, Clone)]
+pub struct ChannelProgressReporter {
+    sender: mpsc::UnboundedSender<ProgressEvent>,
+}
+
+impl ProgressReporter for ChannelProgressReporter {
+    fn report(&self, event: ProgressEvent) {
+        // The receiver going away (e.g. dialog closed) must not abort the operation
+        let _ = self.sender.send(event);
+    }
+}
+
+/// Create a reporter streaming events to the returned receiver
+pub fn progress_channel() -> (ChannelProgressReporter, mpsc::UnboundedReceiver<ProgressEvent>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    (ChannelProgressReporter { sender }, receiver)
+}
+
+/// Helper tracking step counts for a single operation run
+pub struct ProgressTracker<'a> {
+    reporter: &'a dyn ProgressReporter,
+    operation_id: Uuid,
+    operation: ProgressOperation,
+    completed: u64,
+    total: u64,
+}
+
+impl<'a> ProgressTracker<'a> {
+    /// Start tracking an operation with `total` steps and emit the initial event
+    pub fn start(reporter: &'a dyn ProgressReporter, operation: ProgressOperation, total: u64, stage: &str) -> Self {
+        let tracker = Self {
+            reporter,
+            operation_id: Uuid::new_v4(),
+            operation,
+            completed: 0,
+            total,
+        };
+        tracker.emit(stage, false);
+        tracker
+    }
+
+    /// Use an existing correlation ID as the operation ID
+    pub fn with_operation_id(mut self, operation_id: Uuid) -> Self {
+        self.operation_id = operation_id;
+        self
+    }
+
+    /// Mark one step as completed
+    pub fn step(&mut self, stage: &str) {
+        self.completed = (self.completed + 1).min(self.total);
+        self.emit(stage, false);
+    }
+
+    /// Report absolute progress, adjusting the total if it changed
+    pub fn update(&mut self, completed: u64, total: u64, stage: &str) {
+        self.total = total.max(completed);
+        self.completed = completed;
+        self.emit(stage, false);
+    }
+
+    /// Emit the final event
+    pub fn finish(mut self, stage: &str) {
+        self.completed = self.total;
+        self.emit(stage, true);
+    }
+
+    fn emit(&self, stage: &str, finished: bool) {
+        self.reporter.report(ProgressEvent {
+            operation_id: self.operation_id,
+            operation: self.operation,
+            stage: stage.to_string(),
+            completed: self.completed,
+            total: self.total,
+            finished,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_tracker_emits_determinate_events() {
+        let events = Mutex::new(Vec::new());
+        let reporter = |event: ProgressEvent| events.lock().unwrap().push(event);
+
+        let mut tracker = ProgressTracker::start(&reporter, ProgressOperation::Backup, 4, "Serializing");
+        tracker.step("Deriving key");
+        tracker.step("Encrypting");
+        tracker.finish("Done");
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].completed, 0);
+        assert_eq!(events[2].percent(), 50);
+        assert!(events[3].finished);
+        assert_eq!(events[3].fraction(), 1.0);
+        assert!(events.iter().all(|e| e.operation_id == events[0].operation_id));
+    }
+
+    #[test]
+    fn test_update_grows_total() {
+        let (reporter, mut receiver) = progress_channel();
+        let mut tracker = ProgressTracker::start(&reporter, ProgressOperation::Discovery, 20, "Scanning");
+        tracker.update(30, 25, "Scanning");
+
+        let _ = receiver.try_recv().unwrap();
+        let event = receiver.try_recv().unwrap();
+        assert_eq!(event.completed, 30);
+        assert_eq!(event.total, 30);
+    }
+}
@@ -26,6 +26,7 @@ use uuid::Uuid;
 
 use crate::error::Result;
 use super::events::{ProviderEvent, EventEmitter};
+use super::history::{decode_personal_message, SignedMessageHistory, SignedMessageRecord};
 use super::permissions::PermissionManager;
 
 // ============================================================================
@@ -200,6 +201,8 @@ pub struct VaughanProvider {
     permissions: Arc<PermissionManager>,
     /// Event emitter for provider events
     events: Arc<EventEmitter>,
+    /// Log of messages signed on behalf of dApps
+    history: Arc<RwLock<SignedMessageHistory>>,
     /// Connection state
     connected: Arc<RwLock<bool>>,
 }
@@ -212,10 +215,17 @@ impl VaughanProvider {
             accounts: Arc::new(RwLock::new(Vec::new())),
             permissions: Arc::new(PermissionManager::new()),
             events: Arc::new(EventEmitter::new()),
+            history: Arc::new(RwLock::new(SignedMessageHistory::default())),
             connected: Arc::new(RwLock::new(true)),
         }
     }
 
+    /// Use a previously loaded signed message history
+    pub fn with_history(mut self, history: SignedMessageHistory) -> Self {
+        self.history = Arc::new(RwLock::new(history));
+        self
+    }
+
     /// Get a reference to the event emitter
     pub fn events(&self) -> Arc<EventEmitter> {
         Arc::clone(&self.events)
@@ -226,6 +236,19 @@ impl VaughanProvider {
         Arc::clone(&self.permissions)
     }
 
+    /// Get a reference to the signed message history
+    pub fn signed_messages(&self) -> Arc<RwLock<SignedMessageHistory>> {
+        Arc::clone(&self.history)
+    }
+
+    /// Revoke an origin's session, e.g. after reviewing what it had signed
+    ///
+    /// The origin's history entries are kept so the audit trail survives;
+    /// delete them separately if wanted.
+    pub async fn revoke_origin(&self, origin: &str) {
+        self.permissions.revoke_permission(origin).await;
+    }
+
     /// Set connected accounts
     pub async fn set_accounts(&self, accounts: Vec<Address>) {
         let old_accounts = self.accounts.read().await.clone();
@@ -372,11 +395,31 @@ impl VaughanProvider {
             "Signing message"
         );
 
+        let account = match address.parse::<Address>() {
+            Ok(account) => account,
+            Err(_) => {
+                return ProviderResponse::error(ProviderError {
+                    code: -32602,
+                    message: format!("Invalid params: bad address {address}"),
+                    data: None,
+                });
+            }
+        };
+
         // In real implementation, this would:
         // 1. Show message to user for approval
         // 2. Sign with the account's private key
         // For now, return a placeholder signature
         let placeholder_sig = "0x".to_string() + &"0".repeat(130);
+
+        let mut record = SignedMessageRecord::personal(&decode_personal_message(message), account)
+            .with_chain_id(*self.chain_id.read().await)
+            .with_signature(placeholder_sig.clone());
+        if let Some(origin) = &request.origin {
+            record = record.with_origin(origin.clone());
+        }
+        self.history.write().await.record(record);
+
         ProviderResponse::success(Value::String(placeholder_sig))
     }
 
@@ -650,6 +693,31 @@ mod tests {
         assert!(provider.permissions.is_authorized("test-dapp.com").await);
     }
 
+    #[tokio::test]
+    async fn test_personal_sign_is_recorded() {
+        let provider = VaughanProvider::new(369);
+        provider.permissions.grant_permission("https://dapp.example").await;
+
+        let request = ProviderRequest::new(
+            "personal_sign",
+            serde_json::json!(["0x68656c6c6f", format!("{:?}", Address::ZERO)]),
+        )
+        .with_origin("https://dapp.example".to_string());
+        assert!(provider.request(request).await.is_success());
+
+        let history = provider.signed_messages();
+        let history = history.read().await;
+        let records = history.list();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].preview, "hello");
+        assert_eq!(records[0].origin.as_deref(), Some("https://dapp.example"));
+        assert_eq!(records[0].chain_id, Some(369));
+        drop(history);
+
+        provider.revoke_origin("https://dapp.example").await;
+        assert!(!provider.permissions.is_authorized("https://dapp.example").await);
+    }
+
     #[test]
     fn test_provider_error_codes() {
         assert_eq!(Eip1193ErrorCode::UserRejectedRequest as i32, 4001);
@@ -0,0 +1,390 @@
+//! Signed Message History
+//!
+//! Every message and typed-data payload the wallet signs for a dApp is
+//! recorded here, so users can audit what they authorized and revoke the
+//! sessions of origins they no longer trust.
+//!
+//! Only a hash of the signed content is stored, plus a short preview for
+//! display. The history is kept in `<config_dir>/vaughan/signed_messages.json`.
+
+use crate::error::{ConfigurationError, Result};
+use alloy::primitives::{eip191_hash_message, Address, B256};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Maximum number of characters kept as a content preview
+pub const PREVIEW_CHARS: usize = 120;
+
+/// Get the storage path for the signed message history
+pub fn get_signed_messages_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("vaughan");
+    path.push("signed_messages.json");
+    path
+}
+
+/// What kind of payload was signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedMessageKind {
+    /// `personal_sign` / EIP-191 message
+    PersonalSign,
+    /// `eth_signTypedData_v4` / EIP-712 payload
+    TypedData,
+}
+
+/// A single signature the wallet produced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedMessageRecord {
+    pub id: Uuid,
+    pub kind: SignedMessageKind,
+    /// Digest that was actually signed (EIP-191 or EIP-712 hash)
+    pub content_hash: B256,
+    /// Human-readable excerpt of the content
+    #[serde(default)]
+    pub preview: String,
+    /// dApp origin, `None` for signatures requested from inside the wallet
+    #[serde(default)]
+    pub origin: Option<String>,
+    pub account: Address,
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+    pub signed_at: DateTime<Utc>,
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+impl SignedMessageRecord {
+    /// Record for an EIP-191 message; `message` is the raw payload bytes
+    pub fn personal(message: &[u8], account: Address) -> Self {
+        Self::new(
+            SignedMessageKind::PersonalSign,
+            eip191_hash_message(message),
+            message_preview(message),
+            account,
+        )
+    }
+
+    /// Record for an EIP-712 payload given its signing hash
+    pub fn typed_data(signing_hash: B256, preview: impl Into<String>, account: Address) -> Self {
+        Self::new(
+            SignedMessageKind::TypedData,
+            signing_hash,
+            truncate(&preview.into()),
+            account,
+        )
+    }
+
+    fn new(kind: SignedMessageKind, content_hash: B256, preview: String, account: Address) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            content_hash,
+            preview,
+            origin: None,
+            account,
+            chain_id: None,
+            signed_at: Utc::now(),
+            signature: None,
+        }
+    }
+
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    pub fn with_signature(mut self, signature: impl Into<String>) -> Self {
+        self.signature = Some(signature.into());
+        self
+    }
+}
+
+/// Decode a `personal_sign` parameter: `0x`-prefixed hex is treated as bytes,
+/// anything else as UTF-8 text (matching MetaMask)
+pub fn decode_personal_message(param: &str) -> Vec<u8> {
+    param
+        .strip_prefix("0x")
+        .and_then(|hex_str| hex::decode(hex_str).ok())
+        .unwrap_or_else(|| param.as_bytes().to_vec())
+}
+
+/// Preview of a signed payload: text if it is valid UTF-8, otherwise hex
+pub fn message_preview(message: &[u8]) -> String {
+    match std::str::from_utf8(message) {
+        Ok(text) => truncate(text),
+        Err(_) => truncate(&format!("0x{}", hex::encode(message))),
+    }
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Filter for [`SignedMessageHistory::search`]; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct SignedMessageQuery {
+    pub account: Option<Address>,
+    pub origin: Option<String>,
+    pub kind: Option<SignedMessageKind>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Case-insensitive match against the preview, origin or content hash
+    pub text: Option<String>,
+}
+
+impl SignedMessageQuery {
+    pub fn matches(&self, record: &SignedMessageRecord) -> bool {
+        if self.account.is_some_and(|account| account != record.account) {
+            return false;
+        }
+        if self.origin.is_some() && self.origin != record.origin {
+            return false;
+        }
+        if self.kind.is_some_and(|kind| kind != record.kind) {
+            return false;
+        }
+        if self.since.is_some_and(|since| record.signed_at < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| record.signed_at > until) {
+            return false;
+        }
+        match &self.text {
+            Some(text) => {
+                let needle = text.to_lowercase();
+                record.preview.to_lowercase().contains(&needle)
+                    || record
+                        .origin
+                        .as_deref()
+                        .is_some_and(|o| o.to_lowercase().contains(&needle))
+                    || format!("{:?}", record.content_hash).contains(&needle)
+            }
+            None => true,
+        }
+    }
+}
+
+/// Persistent log of signatures produced by the wallet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SignedMessageHistory {
+    #[serde(default)]
+    records: Vec<SignedMessageRecord>,
+}
+
+impl SignedMessageHistory {
+    /// Load the history from the default location
+    pub fn load() -> Self {
+        Self::load_from(&get_signed_messages_path())
+    }
+
+    /// Load the history from a file, falling back to an empty history
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid signed message history {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the history to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_signed_messages_path())
+    }
+
+    /// Save the history to a file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self).map_err(|e| ConfigurationError::ParseError {
+            message: format!("Failed to serialize signed message history: {e}"),
+        })?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Append a record, returning its ID
+    pub fn record(&mut self, record: SignedMessageRecord) -> Uuid {
+        let id = record.id;
+        self.records.push(record);
+        id
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&SignedMessageRecord> {
+        self.records.iter().find(|record| record.id == id)
+    }
+
+    /// All records, most recent first
+    pub fn list(&self) -> Vec<&SignedMessageRecord> {
+        self.search(&SignedMessageQuery::default())
+    }
+
+    /// Records matching `query`, most recent first
+    pub fn search(&self, query: &SignedMessageQuery) -> Vec<&SignedMessageRecord> {
+        let mut records: Vec<_> = self.records.iter().filter(|record| query.matches(record)).collect();
+        records.sort_by_key(|record| std::cmp::Reverse(record.signed_at));
+        records
+    }
+
+    /// Records whose signed digest is `hash`, e.g. to look up a signature seen elsewhere
+    pub fn find_by_hash(&self, hash: B256) -> Vec<&SignedMessageRecord> {
+        self.records
+            .iter()
+            .filter(|record| record.content_hash == hash)
+            .collect()
+    }
+
+    /// Distinct origins that have obtained signatures
+    pub fn origins(&self) -> BTreeSet<String> {
+        self.records.iter().filter_map(|record| record.origin.clone()).collect()
+    }
+
+    /// Delete a record; returns whether it existed
+    pub fn delete(&mut self, id: Uuid) -> bool {
+        let before = self.records.len();
+        self.records.retain(|record| record.id != id);
+        self.records.len() != before
+    }
+
+    /// Delete every record matching `query`, returning how many were removed
+    pub fn delete_matching(&mut self, query: &SignedMessageQuery) -> usize {
+        let before = self.records.len();
+        self.records.retain(|record| !query.matches(record));
+        before - self.records.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+
+    fn sample() -> SignedMessageHistory {
+        let mut history = SignedMessageHistory::default();
+        let mut old =
+            SignedMessageRecord::personal(b"Sign in to Uniswap", ALICE).with_origin("https://app.uniswap.org");
+        old.signed_at -= Duration::days(30);
+        history.record(old);
+        history.record(
+            SignedMessageRecord::typed_data(B256::repeat_byte(7), "Permit USDC", BOB)
+                .with_origin("https://app.1inch.io")
+                .with_chain_id(1),
+        );
+        history.record(SignedMessageRecord::personal(&[0xff, 0x00], ALICE));
+        history
+    }
+
+    #[test]
+    fn test_personal_record_hash_and_preview() {
+        let record = SignedMessageRecord::personal(b"hello", ALICE);
+        assert_eq!(record.kind, SignedMessageKind::PersonalSign);
+        assert_eq!(record.content_hash, eip191_hash_message(b"hello"));
+        assert_eq!(record.preview, "hello");
+
+        assert_eq!(message_preview(&[0xff, 0x00]), "0xff00");
+        let long = "a".repeat(PREVIEW_CHARS + 10);
+        assert_eq!(message_preview(long.as_bytes()).chars().count(), PREVIEW_CHARS + 1);
+    }
+
+    #[test]
+    fn test_decode_personal_message() {
+        assert_eq!(decode_personal_message("0x68656c6c6f"), b"hello");
+        assert_eq!(decode_personal_message("hello"), b"hello");
+        assert_eq!(decode_personal_message("0xnothex"), b"0xnothex");
+    }
+
+    #[test]
+    fn test_list_is_most_recent_first() {
+        let history = sample();
+        let list = history.list();
+        assert_eq!(list.len(), 3);
+        assert!(list.windows(2).all(|pair| pair[0].signed_at >= pair[1].signed_at));
+        assert_eq!(list[2].preview, "Sign in to Uniswap");
+    }
+
+    #[test]
+    fn test_search() {
+        let history = sample();
+
+        let by_account = SignedMessageQuery {
+            account: Some(ALICE),
+            ..Default::default()
+        };
+        assert_eq!(history.search(&by_account).len(), 2);
+
+        let by_text = SignedMessageQuery {
+            text: Some("PERMIT".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(history.search(&by_text)[0].account, BOB);
+
+        let recent = SignedMessageQuery {
+            since: Some(Utc::now() - Duration::days(1)),
+            kind: Some(SignedMessageKind::PersonalSign),
+            ..Default::default()
+        };
+        assert_eq!(history.search(&recent).len(), 1);
+
+        assert_eq!(history.find_by_hash(B256::repeat_byte(7)).len(), 1);
+        assert_eq!(history.origins().len(), 2);
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut history = sample();
+        let id = history.list()[0].id;
+        assert!(history.delete(id));
+        assert!(!history.delete(id));
+        assert_eq!(history.len(), 2);
+
+        let removed = history.delete_matching(&SignedMessageQuery {
+            origin: Some("https://app.uniswap.org".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(removed, 1);
+        assert!(history.origins().contains("https://app.1inch.io"));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signed_messages.json");
+
+        let history = sample();
+        history.save_to(&path).unwrap();
+        assert_eq!(SignedMessageHistory::load_from(&path), history);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(SignedMessageHistory::load_from(&path).is_empty());
+    }
+}
@@ -0,0 +1,816 @@
+//! Wallet JSON-RPC over localhost HTTP
+//!
+//! `vaughan --http-rpc [port]` lets browser dApps and local scripts use the
+//! wallet as their signer without WalletConnect. The server binds to the
+//! loopback interface only and answers JSON-RPC 2.0 `POST`s with the
+//! EIP-1193 methods dApps expect:
+//!
+//! - `eth_chainId` - Chain ID transactions are signed for
+//! - `eth_accounts` - Accounts, once the caller's origin is connected
+//! - `eth_requestAccounts` - Ask the user to connect the caller's origin
+//! - `eth_sendTransaction` - Sign and broadcast after the user approves
+//! - `personal_sign` - Sign an EIP-191 message after the user approves
+//!
+//! Every connection and every signature needs the user's consent through a
+//! [`ConfirmationGate`], which also keeps an audit log of the decisions. A
+//! transaction is shown with its sender, nonce, gas and fees filled in, so
+//! the user approves exactly what gets signed. A read-only server answers the
+//! first three methods and refuses to sign.
+//!
+//! Requests whose `Host` is not a loopback name are refused, so web pages
+//! cannot reach the server through DNS rebinding. Callers are identified by
+//! their `Origin` header; scripts sending none share one local identity.
+//! Connections that are slow to send their request are dropped after
+//! [`DEFAULT_READ_TIMEOUT`].
+
+use super::eip1193::ProviderError;
+use super::history::decode_personal_message;
+use super::stdio::{
+    invalid_params, param, rpc_error, RpcRequest, RpcResponse, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR,
+    WALLET_ERROR,
+};
+use crate::error::{Result, WalletError};
+use crate::security::confirmation::{ConfirmationGate, ConfirmationRequest, TerminalConfirmation};
+use crate::security::keystore::resolve_chain_id;
+use crate::wallet::Vaughan;
+use alloy::primitives::{Address, Bytes, Signature, TxHash};
+use alloy::rpc::types::TransactionRequest;
+use secrecy::SecretString;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+
+/// Port used when none is given
+pub const DEFAULT_HTTP_RPC_PORT: u16 = 8646;
+
+/// Master password signing is unlocked with when the server starts unattended
+///
+/// dApps cannot send it, so without it (or a password typed on the terminal)
+/// a wallet started with [`crate::wallet::WalletConfig::strict_lock`] refuses
+/// to sign. The server removes it from its environment once read.
+pub const WALLET_PASSWORD_ENV: &str = "VAUGHAN_WALLET_PASSWORD";
+
+/// Identity of callers that send no `Origin` header
+const LOCAL_ORIGIN: &str = "local client";
+
+/// How long a client may take to send the request head, and then its body
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// A dApp transaction with every field filled in, as the user approves it
+#[derive(Debug, Clone)]
+pub struct PreparedTransaction {
+    pub tx: TransactionRequest,
+    /// Nonce reserved for it, given back unless it is broadcast
+    pub reserved_nonce: Option<u64>,
+}
+
+/// Wallet operations exposed to dApps
+#[async_trait::async_trait]
+pub trait DappBackend: Send + Sync {
+    async fn accounts(&self) -> Result<Vec<Address>>;
+    async fn chain_id(&self) -> u64;
+    /// Fill in `from`, gas, fees, nonce and chain ID
+    async fn prepare_transaction(&self, tx: TransactionRequest) -> Result<PreparedTransaction>;
+    /// Sign a prepared transaction with its `from` and broadcast it
+    ///
+    /// The reserved nonce is given back when signing fails.
+    async fn send_transaction(&self, prepared: PreparedTransaction) -> Result<TxHash>;
+    /// Give back what [`Self::prepare_transaction`] reserved, e.g. after the user rejects
+    async fn discard_transaction(&self, prepared: PreparedTransaction);
+    async fn personal_sign(&self, account: Address, message: &[u8]) -> Result<Signature>;
+}
+
+#[async_trait::async_trait]
+impl DappBackend for RwLock<Vaughan> {
+    async fn accounts(&self) -> Result<Vec<Address>> {
+        let accounts = self.read().await.list_accounts().await?;
+        Ok(accounts.into_iter().map(|account| account.address).collect())
+    }
+
+    async fn chain_id(&self) -> u64 {
+        self.read().await.signing_chain_id().await
+    }
+
+    async fn prepare_transaction(&self, tx: TransactionRequest) -> Result<PreparedTransaction> {
+        // One lock throughout, so no other request can switch accounts midway
+        let wallet = self.read().await;
+        let mut tx = resolve_chain_id(&tx, wallet.signing_chain_id().await)?;
+        let from = match tx.from {
+            Some(from) => from,
+            None => {
+                let account = wallet.current_account().await;
+                account.ok_or(WalletError::NoAccountSelected)?.address
+            }
+        };
+        tx.from = Some(from);
+        if tx.gas.is_none() {
+            tx.gas = Some(wallet.estimate_gas(&tx).await?.saturating_to());
+        }
+        if tx.gas_price.is_none() && tx.max_fee_per_gas.is_none() {
+            tx.gas_price = Some(wallet.get_gas_price().await?.saturating_to());
+        }
+        // Last, so nothing after the reservation can fail
+        let reserved_nonce = match tx.nonce {
+            Some(_) => None,
+            None => Some(wallet.reserve_nonce(from).await?),
+        };
+        tx.nonce = tx.nonce.or(reserved_nonce);
+        Ok(PreparedTransaction { tx, reserved_nonce })
+    }
+
+    async fn send_transaction(&self, prepared: PreparedTransaction) -> Result<TxHash> {
+        let wallet = self.read().await;
+        let from = prepared.tx.from.ok_or(WalletError::NoAccountSelected)?;
+        let raw = match wallet.sign_transaction_as(from, &prepared.tx).await {
+            Ok(raw) => raw,
+            Err(e) => {
+                if let Some(nonce) = prepared.reserved_nonce {
+                    wallet.release_nonce(from, nonce).await;
+                }
+                return Err(e);
+            }
+        };
+        wallet.broadcast_transaction(&raw).await
+    }
+
+    async fn discard_transaction(&self, prepared: PreparedTransaction) {
+        if let (Some(from), Some(nonce)) = (prepared.tx.from, prepared.reserved_nonce) {
+            self.read().await.release_nonce(from, nonce).await;
+        }
+    }
+
+    async fn personal_sign(&self, account: Address, message: &[u8]) -> Result<Signature> {
+        self.read().await.sign_message(account, message).await
+    }
+}
+
+/// State shared by all connections
+struct Server<B> {
+    backend: Arc<B>,
+    confirmation: ConfirmationGate,
+    read_only: bool,
+    read_timeout: Duration,
+    /// Origins the user allowed to see accounts
+    connected: Mutex<HashSet<String>>,
+}
+
+impl<B: DappBackend> Server<B> {
+    fn is_connected(&self, origin: &str) -> bool {
+        self.connected
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .contains(origin)
+    }
+
+    /// Reject signing methods from read-only servers and unconnected origins
+    fn check_signing(&self, origin: &str) -> std::result::Result<(), ProviderError> {
+        if self.read_only {
+            return Err(ProviderError::new(
+                super::eip1193::Eip1193ErrorCode::Unauthorized,
+                Some("This wallet server is read-only".to_string()),
+            ));
+        }
+        if !self.is_connected(origin) {
+            return Err(ProviderError::unauthorized());
+        }
+        Ok(())
+    }
+
+    async fn dispatch(&self, origin: &str, method: &str, params: &Value) -> std::result::Result<Value, ProviderError> {
+        let wallet_error = |e: crate::error::VaughanError| rpc_error(WALLET_ERROR, e.to_string());
+
+        match method {
+            "eth_chainId" => Ok(json!(format!("0x{:x}", self.backend.chain_id().await))),
+            "eth_accounts" => {
+                if !self.is_connected(origin) {
+                    return Ok(json!([]));
+                }
+                Ok(json!(self.backend.accounts().await.map_err(wallet_error)?))
+            }
+            "eth_requestAccounts" => {
+                if !self.is_connected(origin) {
+                    let request = ConfirmationRequest::Connect {
+                        origin: origin.to_string(),
+                    };
+                    if !self.confirmation.confirm(&request).await {
+                        return Err(ProviderError::user_rejected());
+                    }
+                    self.connected
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .insert(origin.to_string());
+                }
+                Ok(json!(self.backend.accounts().await.map_err(wallet_error)?))
+            }
+            "eth_sendTransaction" => {
+                self.check_signing(origin)?;
+                let tx: TransactionRequest = param(params, 0)?.ok_or_else(|| invalid_params("expected transaction"))?;
+                if let Some(from) = tx.from {
+                    if !self.backend.accounts().await.map_err(wallet_error)?.contains(&from) {
+                        return Err(ProviderError::unauthorized());
+                    }
+                }
+                // The user approves the transaction as it will be signed
+                let prepared = self.backend.prepare_transaction(tx).await.map_err(wallet_error)?;
+                let request = ConfirmationRequest::SendTransaction {
+                    origin: origin.to_string(),
+                    tx: prepared.tx.clone(),
+                };
+                if !self.confirmation.confirm(&request).await {
+                    self.backend.discard_transaction(prepared).await;
+                    return Err(ProviderError::user_rejected());
+                }
+                let hash = self.backend.send_transaction(prepared).await.map_err(wallet_error)?;
+                Ok(json!(hash))
+            }
+            "personal_sign" => {
+                self.check_signing(origin)?;
+                let message: String = param(params, 0)?.ok_or_else(|| invalid_params("expected message"))?;
+                let account: Address = param(params, 1)?.ok_or_else(|| invalid_params("expected account address"))?;
+                if !self.backend.accounts().await.map_err(wallet_error)?.contains(&account) {
+                    return Err(ProviderError::unauthorized());
+                }
+                let message = decode_personal_message(&message);
+                let request = ConfirmationRequest::PersonalSign {
+                    origin: origin.to_string(),
+                    account,
+                    message: message.clone(),
+                };
+                if !self.confirmation.confirm(&request).await {
+                    return Err(ProviderError::user_rejected());
+                }
+                let signature = self
+                    .backend
+                    .personal_sign(account, &message)
+                    .await
+                    .map_err(wallet_error)?;
+                Ok(json!(Bytes::from(signature.as_bytes().to_vec())))
+            }
+            _ => Err(rpc_error(METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+        }
+    }
+
+    /// Answer one HTTP request on `stream`, then close it
+    async fn handle_connection<S>(&self, stream: S) -> std::io::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut stream = BufReader::new(stream);
+        let request = match read_request(&mut stream, self.read_timeout).await {
+            Ok(request) => request,
+            Err(e) => return write_response(&mut stream, "400 Bad Request", None, e.to_string().as_bytes()).await,
+        };
+        let origin = request.headers.get("origin").map(String::as_str);
+
+        if !request.headers.get("host").is_some_and(|host| is_loopback_host(host)) {
+            tracing::warn!("Refused wallet HTTP request for host {:?}", request.headers.get("host"));
+            return write_response(&mut stream, "403 Forbidden", None, b"Host not allowed").await;
+        }
+        match request.method.as_str() {
+            "OPTIONS" => return write_response(&mut stream, "204 No Content", origin, b"").await,
+            "POST" => {}
+            _ => return write_response(&mut stream, "405 Method Not Allowed", origin, b"").await,
+        }
+
+        let response = match serde_json::from_slice::<Value>(&request.body) {
+            Err(e) => RpcResponse::new(Value::Null, Err(rpc_error(PARSE_ERROR, e.to_string()))),
+            Ok(message) => match serde_json::from_value::<RpcRequest>(message.clone()) {
+                Err(e) => {
+                    let id = message.get("id").cloned().unwrap_or(Value::Null);
+                    RpcResponse::new(id, Err(rpc_error(INVALID_REQUEST, e.to_string())))
+                }
+                Ok(call) => {
+                    tracing::debug!("📨 HTTP RPC {} from {}", call.method, origin.unwrap_or(LOCAL_ORIGIN));
+                    let outcome = self
+                        .dispatch(origin.unwrap_or(LOCAL_ORIGIN), &call.method, &call.params)
+                        .await;
+                    RpcResponse::new(call.id.unwrap_or(Value::Null), outcome)
+                }
+            },
+        };
+        let body = serde_json::to_vec(&response).map_err(std::io::Error::other)?;
+        write_response(&mut stream, "200 OK", origin, &body).await
+    }
+}
+
+/// The parts of an HTTP request the server looks at
+#[derive(Debug)]
+struct HttpRequest {
+    method: String,
+    /// Header names lowercased
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Read one request, allowing `timeout` for the head and again for the body
+async fn read_request<R: tokio::io::AsyncBufRead + Unpin>(
+    reader: &mut R,
+    timeout: Duration,
+) -> std::io::Result<HttpRequest> {
+    let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string());
+    let timed_out =
+        |_: tokio::time::error::Elapsed| std::io::Error::new(std::io::ErrorKind::TimedOut, "Timed out reading request");
+
+    let lines = tokio::time::timeout(timeout, read_head(reader))
+        .await
+        .map_err(timed_out)??;
+
+    let mut lines = lines.into_iter();
+    let request_line = lines.next().ok_or_else(|| invalid("Missing request line"))?;
+    let method = request_line.split(' ').next().unwrap_or_default().to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        })
+        .collect();
+
+    let length = match headers.get("content-length") {
+        Some(length) => length.parse::<usize>().map_err(|_| invalid("Invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(invalid("Request body too large"));
+    }
+    let mut body = vec![0; length];
+    tokio::time::timeout(timeout, reader.read_exact(&mut body))
+        .await
+        .map_err(timed_out)??;
+
+    Ok(HttpRequest { method, headers, body })
+}
+
+/// Request line and header lines, up to the empty line ending the head
+async fn read_head<R: tokio::io::AsyncBufRead + Unpin>(reader: &mut R) -> std::io::Result<Vec<String>> {
+    let mut header_bytes = 0;
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        let read = (&mut *reader)
+            .take((MAX_HEADER_BYTES - header_bytes) as u64)
+            .read_line(&mut line)
+            .await?;
+        header_bytes += read;
+        if read == 0 || !line.ends_with('\n') {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Incomplete or oversized request head",
+            ));
+        }
+        let line = line.trim_end().to_string();
+        if line.is_empty() {
+            return Ok(lines);
+        }
+        lines.push(line);
+    }
+}
+
+/// Whether a `Host` header names this machine
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    name.eq_ignore_ascii_case("localhost") || name.parse::<std::net::IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+async fn write_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    status: &str,
+    origin: Option<&str>,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    if let Some(origin) = origin {
+        // Access is decided per origin by the user, not by CORS
+        head.push_str(&format!(
+            "Access-Control-Allow-Origin: {origin}\r\nAccess-Control-Allow-Methods: POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: content-type\r\nVary: Origin\r\n"
+        ));
+    }
+    head.push_str("\r\n");
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(body).await?;
+    writer.flush().await
+}
+
+/// Serves the wallet to dApps over localhost HTTP
+pub struct HttpRpcServer<B> {
+    backend: Arc<B>,
+    confirmation: ConfirmationGate,
+    read_only: bool,
+    read_timeout: Duration,
+}
+
+impl<B: DappBackend + 'static> HttpRpcServer<B> {
+    pub fn new(backend: Arc<B>, confirmation: ConfirmationGate) -> Self {
+        Self {
+            backend,
+            confirmation,
+            read_only: false,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+        }
+    }
+
+    /// Refuse `eth_sendTransaction` and `personal_sign` outright
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// How long a client may take to send the request head, and then its body
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    fn into_server(self) -> Server<B> {
+        Server {
+            backend: self.backend,
+            confirmation: self.confirmation,
+            read_only: self.read_only,
+            read_timeout: self.read_timeout,
+            connected: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Listen on `127.0.0.1:port` until the task is cancelled
+    pub async fn serve_port(self, port: u16) -> std::io::Result<()> {
+        let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port))).await?;
+        self.serve(listener).await
+    }
+
+    /// Accept connections on `listener` until the task is cancelled
+    pub async fn serve(self, listener: TcpListener) -> std::io::Result<()> {
+        let read_only = self.read_only;
+        let server = Arc::new(self.into_server());
+        tracing::info!(
+            "🔌 Wallet JSON-RPC listening on http://{}{}",
+            listener.local_addr()?,
+            if read_only { " (read-only)" } else { "" }
+        );
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let server = Arc::clone(&server);
+            tokio::spawn(async move {
+                if let Err(e) = server.handle_connection(stream).await {
+                    tracing::debug!("Wallet HTTP connection from {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+}
+
+impl HttpRpcServer<RwLock<Vaughan>> {
+    /// Serve `wallet`, asking for consent on the terminal
+    pub fn for_wallet(wallet: Vaughan) -> Self {
+        let confirmation = ConfirmationGate::new(Arc::new(TerminalConfirmation::default()));
+        Self::with_confirmation(wallet, confirmation)
+    }
+
+    /// Serve `wallet`, asking for consent through `confirmation`
+    pub fn with_confirmation(wallet: Vaughan, confirmation: ConfirmationGate) -> Self {
+        // Every signature passes the confirmation gate first
+        wallet.approvals().delegate("localhost JSON-RPC server");
+        Self::new(Arc::new(RwLock::new(wallet)), confirmation)
+    }
+
+    /// Unlock signing with `password`, warning when a signing server stays locked
+    ///
+    /// Fails on a wrong password rather than serving with signing locked.
+    pub async fn unlock_signing(&self, password: Option<SecretString>) -> Result<()> {
+        let wallet = self.backend.read().await;
+        if let Some(password) = password {
+            if !wallet.unlock_signing(password).await? {
+                return Err(crate::error::SecurityError::InvalidPassword.into());
+            }
+        } else if !self.read_only && !wallet.lock_level().await.can_sign() {
+            tracing::warn!(
+                "🔒 Signing is locked; enter the master password or set {} to sign over HTTP",
+                WALLET_PASSWORD_ENV
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::confirmation::ConfirmationChannel;
+    use crate::security::keystore::encode_signed_transaction;
+    use alloy::consensus::{Transaction, TxEnvelope};
+    use alloy::eips::Decodable2718;
+    use alloy::primitives::keccak256;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::signers::SignerSync;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Nonce the mock reserves for transactions without one
+    const RESERVED_NONCE: u64 = 7;
+
+    struct MockWallet {
+        signer: PrivateKeySigner,
+        /// Last transaction signed
+        sent: Mutex<Option<Vec<u8>>>,
+        /// Reserved nonces given back
+        released: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait::async_trait]
+    impl DappBackend for MockWallet {
+        async fn accounts(&self) -> Result<Vec<Address>> {
+            Ok(vec![self.signer.address()])
+        }
+
+        async fn chain_id(&self) -> u64 {
+            369
+        }
+
+        async fn prepare_transaction(&self, tx: TransactionRequest) -> Result<PreparedTransaction> {
+            let mut tx = resolve_chain_id(&tx, 369)?;
+            tx.from.get_or_insert(self.signer.address());
+            tx.gas.get_or_insert(21_000);
+            tx.gas_price.get_or_insert(1_000_000_000);
+            let reserved_nonce = tx.nonce.is_none().then_some(RESERVED_NONCE);
+            tx.nonce = tx.nonce.or(reserved_nonce);
+            Ok(PreparedTransaction { tx, reserved_nonce })
+        }
+
+        async fn send_transaction(&self, prepared: PreparedTransaction) -> Result<TxHash> {
+            let raw = match encode_signed_transaction(&prepared.tx, &self.signer) {
+                Ok(raw) => raw,
+                Err(e) => {
+                    self.released.lock().unwrap().extend(prepared.reserved_nonce);
+                    return Err(e);
+                }
+            };
+            let hash = keccak256(&raw);
+            *self.sent.lock().unwrap() = Some(raw);
+            Ok(hash)
+        }
+
+        async fn discard_transaction(&self, prepared: PreparedTransaction) {
+            self.released.lock().unwrap().extend(prepared.reserved_nonce);
+        }
+
+        async fn personal_sign(&self, _account: Address, message: &[u8]) -> Result<Signature> {
+            Ok(self.signer.sign_message_sync(message).unwrap())
+        }
+    }
+
+    /// Answers every prompt with the current setting, keeping what it was asked
+    #[derive(Default)]
+    struct MockApprover {
+        approve: AtomicBool,
+        requests: Mutex<Vec<ConfirmationRequest>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConfirmationChannel for MockApprover {
+        fn name(&self) -> &'static str {
+            "mock"
+        }
+
+        async fn confirm(&self, request: &ConfirmationRequest) -> bool {
+            self.requests.lock().unwrap().push(request.clone());
+            self.approve.load(Ordering::SeqCst)
+        }
+    }
+
+    fn server(read_only: bool) -> (Server<MockWallet>, Arc<MockApprover>, Address) {
+        let signer = PrivateKeySigner::random();
+        let address = signer.address();
+        let approver = Arc::new(MockApprover::default());
+        let confirmation = ConfirmationGate::new(approver.clone());
+        let wallet = MockWallet {
+            signer,
+            sent: Mutex::new(None),
+            released: Mutex::new(Vec::new()),
+        };
+        let server = HttpRpcServer::new(Arc::new(wallet), confirmation)
+            .read_only(read_only)
+            .read_timeout(Duration::from_millis(100))
+            .into_server();
+        (server, approver, address)
+    }
+
+    /// Send one raw HTTP request, returning the status line and JSON body
+    async fn call(server: &Server<MockWallet>, host: &str, body: Value) -> (String, Value) {
+        let body = body.to_string();
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: {host}\r\nOrigin: https://dapp.example\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let (mut client, wallet) = tokio::io::duplex(64 * 1024);
+        client.write_all(request.as_bytes()).await.unwrap();
+        server.handle_connection(wallet).await.unwrap();
+
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.lines().next().unwrap().to_string();
+        if status.ends_with("200 OK") {
+            assert!(head.contains("Access-Control-Allow-Origin: https://dapp.example"));
+        }
+        (status, serde_json::from_str(body).unwrap_or(Value::Null))
+    }
+
+    fn rpc(method: &str, params: Value) -> Value {
+        json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params})
+    }
+
+    #[tokio::test]
+    async fn test_connect_and_sign_need_approval() {
+        let (server, approver, address) = server(false);
+        let host = "127.0.0.1:8646";
+
+        let (_, response) = call(&server, host, rpc("eth_chainId", json!([]))).await;
+        assert_eq!(response["result"], "0x171");
+        let (_, response) = call(&server, host, rpc("eth_accounts", json!([]))).await;
+        assert_eq!(response["result"], json!([]));
+        let (_, response) = call(&server, host, rpc("personal_sign", json!(["hello", address]))).await;
+        assert_eq!(response["error"]["code"], 4100);
+
+        let (_, response) = call(&server, host, rpc("eth_requestAccounts", json!([]))).await;
+        assert_eq!(response["error"]["code"], 4001);
+        approver.approve.store(true, Ordering::SeqCst);
+        let (_, response) = call(&server, host, rpc("eth_requestAccounts", json!([]))).await;
+        assert_eq!(response["result"], json!([address]));
+
+        let (_, response) = call(&server, host, rpc("personal_sign", json!(["hello", address]))).await;
+        let signature: Bytes = serde_json::from_value(response["result"].clone()).unwrap();
+        let signature = Signature::try_from(signature.as_ref()).unwrap();
+        assert_eq!(signature.recover_address_from_msg("hello").unwrap(), address);
+
+        approver.approve.store(false, Ordering::SeqCst);
+        let tx = json!({"from": address, "to": address, "value": "0x1"});
+        let (_, response) = call(&server, host, rpc("eth_sendTransaction", json!([tx]))).await;
+        assert_eq!(response["error"]["code"], 4001);
+        assert_eq!(*server.backend.released.lock().unwrap(), vec![RESERVED_NONCE]);
+        approver.approve.store(true, Ordering::SeqCst);
+        let (_, response) = call(&server, host, rpc("eth_sendTransaction", json!([tx]))).await;
+        let raw = server.backend.sent.lock().unwrap().clone().unwrap();
+        assert_eq!(response["result"], json!(keccak256(&raw)));
+        assert_eq!(server.confirmation.audit_log().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_signs_what_was_approved() {
+        let (server, approver, address) = server(false);
+        let host = "127.0.0.1:8646";
+        approver.approve.store(true, Ordering::SeqCst);
+        call(&server, host, rpc("eth_requestAccounts", json!([]))).await;
+
+        // dApps send calldata as `data`
+        let to = Address::repeat_byte(2);
+        let data = Bytes::from(vec![0xa9, 0x05, 0x9c, 0xbb, 1, 2, 3]);
+        let (_, response) = call(
+            &server,
+            host,
+            rpc("eth_sendTransaction", json!([{"to": to, "data": data}])),
+        )
+        .await;
+        let raw = server.backend.sent.lock().unwrap().take().unwrap();
+        assert_eq!(response["result"], json!(keccak256(&raw)));
+        let signed = TxEnvelope::decode_2718(&mut raw.as_slice()).unwrap();
+        assert_eq!(signed.input(), &data);
+        assert_eq!(signed.nonce(), RESERVED_NONCE);
+
+        // The user was shown the filled in transaction, sender included
+        let shown = approver.requests.lock().unwrap().last().cloned().unwrap();
+        let ConfirmationRequest::SendTransaction { tx, .. } = &shown else {
+            panic!("expected a transaction confirmation, got {shown:?}");
+        };
+        assert_eq!(tx.from, Some(address));
+        assert_eq!(
+            (tx.nonce, tx.gas, tx.chain_id),
+            (Some(RESERVED_NONCE), Some(21_000), Some(369))
+        );
+        assert!(shown.to_string().contains(&format!("from {address}")));
+
+        // Conflicting calldata is not signed and its nonce is given back
+        let tx = json!({"to": to, "input": "0x01", "data": "0x02"});
+        let (_, response) = call(&server, host, rpc("eth_sendTransaction", json!([tx]))).await;
+        assert_eq!(response["error"]["code"], WALLET_ERROR);
+        assert!(server.backend.sent.lock().unwrap().is_none());
+        assert_eq!(*server.backend.released.lock().unwrap(), vec![RESERVED_NONCE]);
+
+        // Only the wallet's own accounts can be named as sender
+        let tx = json!({"from": Address::repeat_byte(9), "to": to});
+        let (_, response) = call(&server, host, rpc("eth_sendTransaction", json!([tx]))).await;
+        assert_eq!(response["error"]["code"], 4100);
+    }
+
+    #[tokio::test]
+    async fn test_slow_requests_time_out() {
+        let (server, _, _) = server(false);
+        let (mut client, wallet) = tokio::io::duplex(64 * 1024);
+        client
+            .write_all(b"POST / HTTP/1.1\r\nHost: 127.0.0.1:8646\r\n")
+            .await
+            .unwrap();
+        server.handle_connection(wallet).await.unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 400"));
+        assert!(response.contains("Timed out"));
+
+        // A body announced but never sent times out too
+        let (mut client, wallet) = tokio::io::duplex(64 * 1024);
+        client
+            .write_all(b"POST / HTTP/1.1\r\nHost: 127.0.0.1:8646\r\nContent-Length: 10\r\n\r\n{}")
+            .await
+            .unwrap();
+        server.handle_connection(wallet).await.unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 400"));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_and_foreign_hosts_are_refused() {
+        let (server, approver, address) = server(true);
+        approver.approve.store(true, Ordering::SeqCst);
+
+        let (_, response) = call(&server, "localhost:8646", rpc("eth_requestAccounts", json!([]))).await;
+        assert_eq!(response["result"], json!([address]));
+        let (_, response) = call(
+            &server,
+            "localhost:8646",
+            rpc("personal_sign", json!(["hello", address])),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], 4100);
+
+        let (status, _) = call(&server, "evil.example:8646", rpc("eth_chainId", json!([]))).await;
+        assert!(status.contains("403"));
+        assert!(is_loopback_host("[::1]:8646"));
+        assert!(!is_loopback_host("127.0.0.1.evil.example"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_wallet_unlocks_from_the_password() {
+        let config = crate::wallet::WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: true,
+            ..Default::default()
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let password = SecretString::new("master password".to_string());
+        let keychain = crate::security::TestKeychain::new();
+        let mut config_storage =
+            crate::security::WalletConfigStorage::new_with_keychain(Box::new(keychain.clone())).unwrap();
+        config_storage.set_config_path(dir.path().join("wallet_metadata.json"));
+        config_storage
+            .create_wallet_config("Test".to_string(), &password)
+            .await
+            .unwrap();
+        let stores = crate::wallet::password_change::PasswordStores {
+            wallet_config: Some(config_storage),
+            ..crate::wallet::password_change::PasswordStores::in_dir(dir.path())
+        };
+        let wallet = Vaughan::with_keychain(config, Box::new(keychain))
+            .await
+            .unwrap()
+            .with_password_stores(stores)
+            .with_unlock_throttle(crate::security::UnlockThrottle::at(
+                dir.path().join("unlock-attempts.json"),
+            ));
+        let confirmation = ConfirmationGate::new(Arc::new(MockApprover {
+            approve: AtomicBool::new(true),
+            ..Default::default()
+        }));
+
+        let server = HttpRpcServer::with_confirmation(wallet, confirmation);
+        server.unlock_signing(None).await.unwrap();
+        assert!(!server.backend.read().await.lock_level().await.can_sign());
+
+        let wrong = SecretString::new("wrong password".to_string());
+        assert!(server.unlock_signing(Some(wrong)).await.is_err());
+        assert!(!server.backend.read().await.lock_level().await.can_sign());
+
+        server.unlock_signing(Some(password)).await.unwrap();
+        assert!(server.backend.read().await.lock_level().await.can_sign());
+
+        // Without `from` the selected account signs, and a strict wallet has none
+        let tx = TransactionRequest::default().to(Address::repeat_byte(2));
+        assert!(matches!(
+            server.backend.prepare_transaction(tx).await,
+            Err(crate::error::VaughanError::Wallet(WalletError::NoAccountSelected))
+        ));
+    }
+}
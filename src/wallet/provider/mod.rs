@@ -20,8 +20,14 @@
 
 pub mod eip1193;
 pub mod events;
+pub mod history;
+pub mod http;
 pub mod permissions;
+pub mod stdio;
 
 pub use eip1193::*;
 pub use events::*;
+pub use history::*;
+pub use http::{DappBackend, HttpRpcServer, DEFAULT_HTTP_RPC_PORT, WALLET_PASSWORD_ENV};
 pub use permissions::*;
+pub use stdio::{StdioRpcServer, WalletBackend, WALLET_API_VERSION};
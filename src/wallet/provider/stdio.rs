@@ -0,0 +1,658 @@
+//! Wallet API over stdio
+//!
+//! `vaughan --stdio-rpc` runs the wallet headless as a signing backend for
+//! other desktop apps and Electron/Tauri frontends. The host process speaks
+//! JSON-RPC 2.0 over the child's stdin/stdout, one message per line; logs go
+//! to stderr.
+//!
+//! # Methods
+//!
+//! - `vaughan_apiVersion` - API and wallet version
+//! - `vaughan_handshake` - Fail early if the host expects another major version
+//! - `vaughan_accounts` - Accounts in the keystore
+//! - `vaughan_chainId` - Chain ID transactions are signed for
+//! - `vaughan_getBalance` - Native or ERC-20 balance of an account
+//! - `vaughan_unlock` - Unlock signing with the wallet password, failing on a wrong one
+//! - `vaughan_signTransaction` - Sign after the host confirms; the transaction
+//!   must carry its nonce, gas limit and fees
+//!
+//! # Confirmation callbacks
+//!
+//! Nothing is signed without the host's approval. Before signing, Vaughan
+//! sends its own request, `vaughan_confirmSigning`, with the transaction as
+//! it will be signed, chain ID included, and waits for a `{"approved": bool}`
+//! result. No answer within the timeout, an
+//! error response or the host closing stdin count as a rejection.
+//!
+//! When the host cannot be trusted to ask the user itself, e.g. an unattended
+//! script, [`StdioRpcServer::with_confirmation`] sends signing requests to a
+//! [`ConfirmationGate`] such as a webhook instead, with its audit log.
+//!
+//! The API is versioned with [`WALLET_API_VERSION`]. Methods are only added
+//! within a major version; renames and removals bump it.
+
+use super::eip1193::ProviderError;
+use crate::error::Result;
+use crate::security::confirmation::{ConfirmationGate, ConfirmationRequest};
+use crate::security::keystore::resolve_chain_id;
+use crate::wallet::Vaughan;
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::TransactionRequest;
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, oneshot, RwLock};
+
+/// Version of the stdio wallet API, `major.minor`
+pub const WALLET_API_VERSION: &str = "1.0";
+
+/// How long the host has to answer a confirmation request
+pub const DEFAULT_CONFIRM_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+// JSON-RPC 2.0 error codes
+pub(super) const PARSE_ERROR: i32 = -32700;
+pub(super) const INVALID_REQUEST: i32 = -32600;
+pub(super) const METHOD_NOT_FOUND: i32 = -32601;
+pub(super) const INVALID_PARAMS: i32 = -32602;
+/// Wallet errors, with the wallet's message
+pub(super) const WALLET_ERROR: i32 = -32000;
+const UNSUPPORTED_VERSION: i32 = -32001;
+
+/// Account as reported to the host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAccount {
+    pub address: Address,
+    pub name: String,
+    pub is_hardware: bool,
+}
+
+/// Wallet operations exposed over stdio
+#[async_trait::async_trait]
+pub trait WalletBackend: Send + Sync {
+    async fn accounts(&self) -> Result<Vec<ApiAccount>>;
+    async fn chain_id(&self) -> u64;
+    async fn balance(&self, account: Address, token: Option<Address>) -> Result<U256>;
+    /// Unlock signing, returning whether the password was right
+    async fn unlock(&self, password: SecretString) -> Result<bool>;
+    /// Sign `tx` with its `from` account, or the current account
+    async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Vec<u8>>;
+}
+
+#[async_trait::async_trait]
+impl WalletBackend for RwLock<Vaughan> {
+    async fn accounts(&self) -> Result<Vec<ApiAccount>> {
+        let accounts = self.read().await.list_accounts().await?;
+        Ok(accounts
+            .into_iter()
+            .map(|account| ApiAccount {
+                address: account.address,
+                name: account.name,
+                is_hardware: account.is_hardware,
+            })
+            .collect())
+    }
+
+    async fn chain_id(&self) -> u64 {
+        self.read().await.signing_chain_id().await
+    }
+
+    async fn balance(&self, account: Address, token: Option<Address>) -> Result<U256> {
+        let network_manager = self.read().await.network_manager();
+        let network_manager = network_manager.read().await;
+        network_manager.get_balance(account, token).await
+    }
+
+    async fn unlock(&self, password: SecretString) -> Result<bool> {
+        self.read().await.unlock_signing(password).await
+    }
+
+    async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Vec<u8>> {
+        let wallet = self.read().await;
+        match tx.from {
+            Some(from) => wallet.sign_transaction_as(from, tx).await,
+            None => wallet.sign_transaction(tx).await,
+        }
+    }
+}
+
+/// Incoming JSON-RPC request or notification
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct RpcRequest {
+    /// Absent for notifications, which get no response
+    #[serde(default)]
+    pub(super) id: Option<Value>,
+    pub(super) method: String,
+    #[serde(default)]
+    pub(super) params: Value,
+}
+
+/// Outgoing JSON-RPC response
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ProviderError>,
+}
+
+impl RpcResponse {
+    pub(super) fn new(id: Value, outcome: std::result::Result<Value, ProviderError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(value) => (Some(value), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+pub(super) fn rpc_error(code: i32, message: impl Into<String>) -> ProviderError {
+    ProviderError {
+        code,
+        message: message.into(),
+        data: None,
+    }
+}
+
+pub(super) fn invalid_params(message: impl std::fmt::Display) -> ProviderError {
+    rpc_error(INVALID_PARAMS, format!("Invalid params: {message}"))
+}
+
+/// `n`th positional parameter, missing and `null` both being `None`
+pub(super) fn param<T: serde::de::DeserializeOwned>(
+    params: &Value,
+    n: usize,
+) -> std::result::Result<Option<T>, ProviderError> {
+    match params.get(n) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => serde_json::from_value(value.clone()).map(Some).map_err(invalid_params),
+    }
+}
+
+/// Check `tx` carries every field it is signed with and pin it to `chain_id`
+///
+/// Missing fields are refused rather than defaulted, so the host approves
+/// exactly the transaction that gets signed.
+fn complete_transaction(
+    tx: TransactionRequest,
+    chain_id: u64,
+) -> std::result::Result<TransactionRequest, ProviderError> {
+    let has_fees = tx.gas_price.is_some() || (tx.max_fee_per_gas.is_some() && tx.max_priority_fee_per_gas.is_some());
+    let missing = [
+        (tx.nonce.is_none(), "nonce"),
+        (tx.gas.is_none(), "gas"),
+        (!has_fees, "gasPrice or maxFeePerGas and maxPriorityFeePerGas"),
+    ];
+    if let Some((_, field)) = missing.iter().find(|(missing, _)| *missing) {
+        return Err(invalid_params(format!("transaction has no {field}")));
+    }
+    if tx.input.unique_input().is_err() {
+        return Err(invalid_params(
+            "transaction sets both input and data with different values",
+        ));
+    }
+    resolve_chain_id(&tx, chain_id).map_err(invalid_params)
+}
+
+/// Whether a host expecting `requested` can use this API
+fn is_compatible(requested: &str) -> bool {
+    let major = |version: &str| version.split('.').next().map(str::to_string);
+    major(requested) == major(WALLET_API_VERSION)
+}
+
+/// State shared by the request handlers of one session
+struct Session<B> {
+    backend: Arc<B>,
+    outgoing: mpsc::UnboundedSender<String>,
+    /// Confirmation requests awaiting the host's answer, by request ID
+    pending: Mutex<HashMap<String, oneshot::Sender<bool>>>,
+    next_confirmation: AtomicU64,
+    confirm_timeout: Duration,
+    /// Confirms signing instead of the host when set
+    confirmation: Option<ConfirmationGate>,
+}
+
+impl<B: WalletBackend> Session<B> {
+    fn send(&self, message: &impl Serialize) {
+        match serde_json::to_string(message) {
+            // The writer only stops once the session ends
+            Ok(line) => {
+                let _ = self.outgoing.send(line);
+            }
+            Err(e) => tracing::error!("Failed to serialize stdio RPC message: {}", e),
+        }
+    }
+
+    async fn handle(&self, request: RpcRequest) {
+        tracing::debug!("📨 stdio RPC {}", request.method);
+        let outcome = self.dispatch(&request.method, &request.params).await;
+        if let Err(error) = &outcome {
+            tracing::warn!("stdio RPC {} failed: {}", request.method, error);
+        }
+        if let Some(id) = request.id {
+            self.send(&RpcResponse::new(id, outcome));
+        }
+    }
+
+    async fn dispatch(&self, method: &str, params: &Value) -> std::result::Result<Value, ProviderError> {
+        let wallet_error = |e: crate::error::VaughanError| rpc_error(WALLET_ERROR, e.to_string());
+
+        match method {
+            "vaughan_apiVersion" => Ok(json!({
+                "apiVersion": WALLET_API_VERSION,
+                "wallet": crate::build_info().version_line(),
+            })),
+            "vaughan_handshake" => {
+                let requested: String = param(params, 0)?.ok_or_else(|| invalid_params("expected API version"))?;
+                if !is_compatible(&requested) {
+                    return Err(rpc_error(
+                        UNSUPPORTED_VERSION,
+                        format!("API version {requested} not supported, this wallet provides {WALLET_API_VERSION}"),
+                    ));
+                }
+                Ok(json!({ "apiVersion": WALLET_API_VERSION }))
+            }
+            "vaughan_accounts" => {
+                let accounts = self.backend.accounts().await.map_err(wallet_error)?;
+                Ok(json!(accounts))
+            }
+            "vaughan_chainId" => Ok(json!(format!("0x{:x}", self.backend.chain_id().await))),
+            "vaughan_getBalance" => {
+                let account: Address = param(params, 0)?.ok_or_else(|| invalid_params("expected account address"))?;
+                let token: Option<Address> = param(params, 1)?;
+                let balance = self.backend.balance(account, token).await.map_err(wallet_error)?;
+                Ok(json!(balance))
+            }
+            "vaughan_unlock" => {
+                let password: String = param(params, 0)?.ok_or_else(|| invalid_params("expected password"))?;
+                let unlocked = self.backend.unlock(SecretString::new(password)).await;
+                if !unlocked.map_err(wallet_error)? {
+                    return Err(rpc_error(WALLET_ERROR, "Wrong password"));
+                }
+                Ok(Value::Bool(true))
+            }
+            "vaughan_signTransaction" => {
+                let tx: TransactionRequest = param(params, 0)?.ok_or_else(|| invalid_params("expected transaction"))?;
+                let tx = complete_transaction(tx, self.backend.chain_id().await)?;
+                let approved = match &self.confirmation {
+                    Some(gate) => {
+                        let request = ConfirmationRequest::SignTransaction {
+                            origin: "stdio host".to_string(),
+                            tx: tx.clone(),
+                        };
+                        gate.confirm(&request).await
+                    }
+                    None => self.confirm(&tx).await,
+                };
+                if !approved {
+                    return Err(ProviderError::user_rejected());
+                }
+                let raw = self.backend.sign_transaction(&tx).await.map_err(wallet_error)?;
+                Ok(json!(alloy::primitives::Bytes::from(raw)))
+            }
+            _ => Err(rpc_error(METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+        }
+    }
+
+    /// Ask the host to approve signing `tx`
+    async fn confirm(&self, tx: &TransactionRequest) -> bool {
+        let id = format!("confirm-{}", self.next_confirmation.fetch_add(1, Ordering::Relaxed));
+        let (sender, receiver) = oneshot::channel();
+        self.lock_pending().insert(id.clone(), sender);
+
+        self.send(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "vaughan_confirmSigning",
+            "params": [tx],
+        }));
+
+        let approved = match tokio::time::timeout(self.confirm_timeout, receiver).await {
+            Ok(Ok(approved)) => approved,
+            // Host went away
+            Ok(Err(_)) => false,
+            Err(_) => {
+                tracing::warn!("⏰ Host did not answer confirmation {} in time", id);
+                false
+            }
+        };
+        self.lock_pending().remove(&id);
+        approved
+    }
+
+    /// Route the host's answer to a confirmation request
+    fn resolve(&self, message: &Value) {
+        let Some(id) = message.get("id").and_then(Value::as_str) else {
+            return;
+        };
+        let approved = message
+            .get("result")
+            .and_then(|result| result.get("approved"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        match self.lock_pending().remove(id) {
+            Some(sender) => {
+                let _ = sender.send(approved);
+            }
+            None => tracing::warn!("Ignoring answer to unknown confirmation {}", id),
+        }
+    }
+
+    fn lock_pending(&self) -> MutexGuard<'_, HashMap<String, oneshot::Sender<bool>>> {
+        self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Serves the wallet API to a host process
+pub struct StdioRpcServer<B> {
+    backend: Arc<B>,
+    confirm_timeout: Duration,
+    confirmation: Option<ConfirmationGate>,
+}
+
+impl<B: WalletBackend + 'static> StdioRpcServer<B> {
+    pub fn new(backend: Arc<B>) -> Self {
+        Self {
+            backend,
+            confirm_timeout: DEFAULT_CONFIRM_TIMEOUT,
+            confirmation: None,
+        }
+    }
+
+    pub fn with_confirm_timeout(mut self, timeout: Duration) -> Self {
+        self.confirm_timeout = timeout;
+        self
+    }
+
+    /// Confirm signing through `gate` rather than asking the host
+    pub fn with_confirmation(mut self, gate: ConfirmationGate) -> Self {
+        self.confirmation = Some(gate);
+        self
+    }
+
+    /// Serve the process's stdin and stdout until stdin closes
+    pub async fn serve_stdio(self) -> std::io::Result<()> {
+        self.serve(tokio::io::BufReader::new(tokio::io::stdin()), tokio::io::stdout())
+            .await
+    }
+
+    /// Serve requests read from `reader`, writing responses to `writer`
+    ///
+    /// Requests are handled concurrently, so the host can answer a
+    /// confirmation while its signing request is in flight. Returns when
+    /// `reader` reaches end of input and every response has been written.
+    pub async fn serve<R, W>(self, reader: R, mut writer: W) -> std::io::Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let (outgoing, mut lines_out) = mpsc::unbounded_channel::<String>();
+        let writer_task = tokio::spawn(async move {
+            while let Some(line) = lines_out.recv().await {
+                writer.write_all(line.as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+                writer.flush().await?;
+            }
+            Ok::<_, std::io::Error>(())
+        });
+
+        let session = Arc::new(Session {
+            backend: self.backend,
+            outgoing,
+            pending: Mutex::new(HashMap::new()),
+            next_confirmation: AtomicU64::new(0),
+            confirm_timeout: self.confirm_timeout,
+            confirmation: self.confirmation,
+        });
+        tracing::info!("🔌 Wallet API {} serving over stdio", WALLET_API_VERSION);
+
+        let mut handlers = tokio::task::JoinSet::new();
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let message: Value = match serde_json::from_str(&line) {
+                Ok(message) => message,
+                Err(e) => {
+                    session.send(&RpcResponse::new(
+                        Value::Null,
+                        Err(rpc_error(PARSE_ERROR, e.to_string())),
+                    ));
+                    continue;
+                }
+            };
+
+            if message.get("method").is_none() {
+                session.resolve(&message);
+                continue;
+            }
+            match serde_json::from_value::<RpcRequest>(message.clone()) {
+                Ok(request) => {
+                    let session = Arc::clone(&session);
+                    handlers.spawn(async move { session.handle(request).await });
+                }
+                Err(e) => {
+                    let id = message.get("id").cloned().unwrap_or(Value::Null);
+                    session.send(&RpcResponse::new(id, Err(rpc_error(INVALID_REQUEST, e.to_string()))));
+                }
+            }
+        }
+
+        // No more answers can arrive; reject what is still waiting
+        session.lock_pending().clear();
+        while handlers.join_next().await.is_some() {}
+        drop(session);
+
+        writer_task.await.unwrap_or_else(|e| Err(std::io::Error::other(e)))
+    }
+}
+
+impl StdioRpcServer<RwLock<Vaughan>> {
+    /// Serve `wallet`
+    pub fn for_wallet(wallet: Vaughan) -> Self {
+        // Every signature is confirmed by the host or the confirmation gate first
+        wallet.approvals().delegate("stdio host");
+        Self::new(Arc::new(RwLock::new(wallet)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::ExposeSecret;
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+
+    struct MockWallet;
+
+    #[async_trait::async_trait]
+    impl WalletBackend for MockWallet {
+        async fn accounts(&self) -> Result<Vec<ApiAccount>> {
+            Ok(vec![ApiAccount {
+                address: ALICE,
+                name: "Alice".to_string(),
+                is_hardware: false,
+            }])
+        }
+
+        async fn chain_id(&self) -> u64 {
+            369
+        }
+
+        async fn balance(&self, _account: Address, token: Option<Address>) -> Result<U256> {
+            Ok(U256::from(if token.is_some() { 5u64 } else { 1_000u64 }))
+        }
+
+        async fn unlock(&self, password: SecretString) -> Result<bool> {
+            Ok(password.expose_secret() == "secret")
+        }
+
+        async fn sign_transaction(&self, _tx: &TransactionRequest) -> Result<Vec<u8>> {
+            Ok(vec![0x02, 0xab])
+        }
+    }
+
+    type HostWriter = tokio::io::WriteHalf<tokio::io::DuplexStream>;
+    type HostLines = tokio::io::Lines<tokio::io::BufReader<tokio::io::ReadHalf<tokio::io::DuplexStream>>>;
+
+    /// Run a session over an in-memory pipe, returning the host's ends
+    fn spawn_server() -> (HostWriter, HostLines, tokio::task::JoinHandle<std::io::Result<()>>) {
+        let (host, wallet) = tokio::io::duplex(4096);
+        let (reader, writer) = tokio::io::split(wallet);
+        let server = StdioRpcServer::new(Arc::new(MockWallet)).with_confirm_timeout(Duration::from_secs(5));
+        let task = tokio::spawn(server.serve(tokio::io::BufReader::new(reader), writer));
+        let (host_reader, host_writer) = tokio::io::split(host);
+        (host_writer, tokio::io::BufReader::new(host_reader).lines(), task)
+    }
+
+    async fn send(host: &mut HostWriter, message: Value) {
+        host.write_all(format!("{message}\n").as_bytes()).await.unwrap();
+    }
+
+    async fn receive(lines: &mut HostLines) -> Value {
+        serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_queries_and_errors() {
+        let (mut host, mut lines, task) = spawn_server();
+
+        for message in [
+            json!({"jsonrpc": "2.0", "id": 1, "method": "vaughan_handshake", "params": ["1.3"]}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "vaughan_getBalance", "params": [ALICE]}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "vaughan_handshake", "params": ["2.0"]}),
+            json!({"jsonrpc": "2.0", "id": 4, "method": "eth_sign"}),
+            json!({"jsonrpc": "2.0", "id": 5, "method": "vaughan_unlock", "params": ["guess"]}),
+            json!({"jsonrpc": "2.0", "id": 6, "method": "vaughan_unlock", "params": ["secret"]}),
+        ] {
+            send(&mut host, message.clone()).await;
+            let response = receive(&mut lines).await;
+            assert_eq!(response["id"], message["id"]);
+            match message["id"].as_u64().unwrap() {
+                1 => assert_eq!(response["result"]["apiVersion"], WALLET_API_VERSION),
+                2 => assert_eq!(response["result"], "0x3e8"),
+                3 => assert_eq!(response["error"]["code"], UNSUPPORTED_VERSION),
+                4 => assert_eq!(response["error"]["code"], METHOD_NOT_FOUND),
+                5 => assert_eq!(response["error"]["code"], WALLET_ERROR),
+                _ => assert_eq!(response["result"], true),
+            }
+        }
+
+        host.write_all(b"not json\n").await.unwrap();
+        assert_eq!(receive(&mut lines).await["error"]["code"], PARSE_ERROR);
+
+        // Closing stdin ends the session; the pipe closes once both halves are gone
+        drop(host);
+        drop(lines);
+        task.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_signing_waits_for_host_confirmation() {
+        let (mut host, mut lines, _task) = spawn_server();
+        let tx =
+            json!({"from": ALICE, "to": ALICE, "value": "0x1", "nonce": "0x0", "gas": "0x5208", "gasPrice": "0x1"});
+
+        // Nothing is defaulted behind the host's back
+        let incomplete = json!({"from": ALICE, "to": ALICE, "value": "0x1", "gas": "0x5208", "gasPrice": "0x1"});
+        send(
+            &mut host,
+            json!({"jsonrpc": "2.0", "id": 0, "method": "vaughan_signTransaction", "params": [incomplete]}),
+        )
+        .await;
+        let response = receive(&mut lines).await;
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+        assert!(response["error"]["message"].as_str().unwrap().contains("nonce"));
+
+        for (id, approved) in [(1, false), (2, true)] {
+            send(
+                &mut host,
+                json!({"jsonrpc": "2.0", "id": id, "method": "vaughan_signTransaction", "params": [tx]}),
+            )
+            .await;
+            let confirmation = receive(&mut lines).await;
+            assert_eq!(confirmation["method"], "vaughan_confirmSigning");
+            assert_eq!(confirmation["params"][0]["value"], "0x1");
+            assert_eq!(confirmation["params"][0]["chainId"], "0x171");
+
+            send(
+                &mut host,
+                json!({"jsonrpc": "2.0", "id": confirmation["id"], "result": {"approved": approved}}),
+            )
+            .await;
+            let response = receive(&mut lines).await;
+            assert_eq!(response["id"], id);
+            if approved {
+                assert_eq!(response["result"], "0x02ab");
+            } else {
+                assert_eq!(response["error"]["code"], 4001);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wallet_signs_with_from_without_switching() {
+        use alloy::signers::local::PrivateKeySigner;
+
+        let config = crate::wallet::WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: false,
+            ..Default::default()
+        };
+        let keychain = Box::new(crate::security::TestKeychain::new());
+        let mut wallet = Vaughan::with_keychain(config, keychain).await.unwrap();
+        wallet.approvals().delegate("test host");
+        let (alice, bob) = (PrivateKeySigner::random(), PrivateKeySigner::random());
+        for (signer, name) in [(&alice, "Alice"), (&bob, "Bob")] {
+            let key = SecretString::new(alloy::hex::encode(signer.to_bytes()));
+            let keystore = wallet.keystore();
+            keystore
+                .write()
+                .await
+                .import_account(key, name.to_string())
+                .await
+                .unwrap();
+        }
+        wallet.switch_account(alice.address()).await.unwrap();
+        let wallet = RwLock::new(wallet);
+
+        // Calldata sent as `data` is signed
+        let data = alloy::primitives::Bytes::from(vec![0xde, 0xad]);
+        let tx = TransactionRequest::default()
+            .from(bob.address())
+            .to(Address::repeat_byte(2))
+            .nonce(0)
+            .gas_limit(30_000)
+            .gas_price(1)
+            .input(alloy::rpc::types::TransactionInput {
+                input: None,
+                data: Some(data.clone()),
+            });
+        let raw = WalletBackend::sign_transaction(&wallet, &tx).await.unwrap();
+        let signed = crate::network::broadcast::RawTransactionInfo::decode(&raw).unwrap();
+        assert_eq!(signed.sender, bob.address());
+        let envelope =
+            <alloy::consensus::TxEnvelope as alloy::eips::Decodable2718>::decode_2718(&mut raw.as_slice()).unwrap();
+        assert_eq!(alloy::consensus::Transaction::input(&envelope), &data);
+        let current = wallet.read().await.current_account().await.unwrap();
+        assert_eq!(current.address, alice.address());
+
+        // Accounts outside the wallet are refused, not switched to
+        let stranger = tx.from(Address::repeat_byte(0x55));
+        assert!(WalletBackend::sign_transaction(&wallet, &stranger).await.is_err());
+    }
+}
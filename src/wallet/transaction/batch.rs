@@ -0,0 +1,439 @@
+//! Batch Transaction Queue
+//!
+//! Queues transfers and approvals from one account and sends them as a batch:
+//!
+//! 1. **Prepare**: every item gets the next nonce from the [`NonceManager`],
+//!    fees and a gas limit, and is signed. All signing happens up front, so
+//!    one wallet unlock covers the whole batch; if any item fails to sign,
+//!    the reserved nonces are released and nothing is broadcast
+//! 2. **Broadcast**: items are sent in nonce order with a configurable pause
+//!    between them, reporting each item's status as a stream. A failed
+//!    broadcast skips the remaining items, since their nonces could not be
+//!    mined until the gap is filled
+
+use crate::error::{NetworkError, Result, VaughanError};
+use crate::network::broadcast::{resolve_rejection, BroadcastOutcome, BroadcastRejection, RawTransactionInfo};
+use crate::network::nonce_manager::NonceManager;
+use crate::network::NetworkId;
+use crate::wallet::transaction::erc20::TokenTransfer;
+use crate::wallet::transaction::fees::{FeeEstimator, FeePriority};
+use crate::wallet::transaction::replacement::TransactionSigner;
+use alloy::primitives::{Address, Bytes, TxHash, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use futures_util::Stream;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Gas margin applied to estimates, in percent
+const GAS_MARGIN_PERCENT: u64 = 20;
+
+sol! {
+    interface IERC20Approve {
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+}
+
+/// One queued transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchItem {
+    /// Native transfer of `value` wei
+    Transfer {
+        to: Address,
+        value: U256,
+    },
+    TokenTransfer(TokenTransfer),
+    /// ERC-20 allowance for `spender`
+    Approval {
+        token: Address,
+        spender: Address,
+        amount: U256,
+    },
+}
+
+impl BatchItem {
+    /// Transaction request for the item, without gas, fees or nonce
+    pub fn to_request(&self, from: Address) -> TransactionRequest {
+        match self {
+            Self::Transfer { to, value } => TransactionRequest::default().from(from).to(*to).value(*value),
+            Self::TokenTransfer(transfer) => transfer.to_request(from),
+            Self::Approval { token, spender, amount } => {
+                let input = IERC20Approve::approveCall {
+                    spender: *spender,
+                    amount: *amount,
+                }
+                .abi_encode();
+                TransactionRequest::default()
+                    .from(from)
+                    .to(*token)
+                    .input(Bytes::from(input).into())
+            }
+        }
+    }
+
+    /// Gas limit used when estimation fails, e.g. a transfer relying on an
+    /// approval earlier in the same batch
+    fn fallback_gas_limit(&self) -> u64 {
+        match self {
+            Self::Transfer { .. } => 21_000,
+            Self::TokenTransfer(_) => 65_000,
+            Self::Approval { .. } => 60_000,
+        }
+    }
+}
+
+/// Transactions waiting to be sent together
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchQueue {
+    items: Vec<BatchItem>,
+}
+
+impl BatchQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, item: BatchItem) {
+        self.items.push(item);
+    }
+
+    /// Remove the item at `index`, if there is one
+    pub fn remove(&mut self, index: usize) -> Option<BatchItem> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+
+    pub fn items(&self) -> &[BatchItem] {
+        &self.items
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+/// A queued item signed with its nonce, ready to broadcast
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedBatchItem {
+    /// Position in the queue
+    pub index: usize,
+    pub nonce: u64,
+    raw: Vec<u8>,
+}
+
+/// Outcome of broadcasting one item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchStatus {
+    Sent {
+        nonce: u64,
+        tx_hash: TxHash,
+    },
+    Failed {
+        nonce: u64,
+        error: String,
+    },
+    /// Not sent because an earlier item failed; its nonce was released
+    Skipped {
+        nonce: u64,
+    },
+}
+
+/// Status update for the item at `index` in the queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchUpdate {
+    pub index: usize,
+    pub status: BatchStatus,
+}
+
+/// Signs and broadcasts batches from one network
+pub struct BatchSender<P> {
+    provider: P,
+    signer: Arc<dyn TransactionSigner>,
+    nonces: Arc<NonceManager>,
+    network: NetworkId,
+    priority: FeePriority,
+    /// Pause between broadcasts
+    pacing: Duration,
+}
+
+impl<P: Provider> BatchSender<P> {
+    pub fn new(provider: P, signer: Arc<dyn TransactionSigner>, nonces: Arc<NonceManager>, network: NetworkId) -> Self {
+        Self {
+            provider,
+            signer,
+            nonces,
+            network,
+            priority: FeePriority::Standard,
+            pacing: Duration::ZERO,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: FeePriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_pacing(mut self, pacing: Duration) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// Release nonces highest first, so the manager's counter rolls back past all of them
+    fn release(&self, from: Address, nonces: impl DoubleEndedIterator<Item = u64>) {
+        for nonce in nonces.rev() {
+            self.nonces.release(from, self.network, nonce);
+        }
+    }
+
+    /// Reserve sequential nonces for every item in `queue` and sign them all
+    pub async fn prepare(&self, queue: &BatchQueue, from: Address) -> Result<Vec<SignedBatchItem>> {
+        if queue.is_empty() {
+            return Err(VaughanError::ValidationError("The batch has no transactions".into()));
+        }
+        let chain_nonce =
+            self.provider
+                .get_transaction_count(from)
+                .pending()
+                .await
+                .map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to get transaction count: {e}"),
+                })?;
+        let fees = FeeEstimator::estimate_fees(&self.provider, self.priority).await?;
+
+        let mut signed: Vec<SignedBatchItem> = Vec::with_capacity(queue.len());
+        for (index, item) in queue.items().iter().enumerate() {
+            let nonce = self.nonces.reserve(from, self.network, chain_nonce);
+            let mut request = item
+                .to_request(from)
+                .nonce(nonce)
+                .max_fee_per_gas(fees.max_fee_per_gas.saturating_to::<u128>())
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas.saturating_to::<u128>());
+            request.chain_id = Some(self.network.chain_id());
+
+            let gas_limit = match self.provider.estimate_gas(request.clone()).await {
+                Ok(estimate) => estimate.saturating_mul(100 + GAS_MARGIN_PERCENT) / 100,
+                Err(e) => {
+                    let fallback = item.fallback_gas_limit();
+                    tracing::warn!(
+                        "⚠️ Gas estimate for batch item {} failed ({}), using {}",
+                        index,
+                        e,
+                        fallback
+                    );
+                    fallback
+                }
+            };
+
+            match self.signer.sign_transaction(&request.gas_limit(gas_limit)).await {
+                Ok(raw) => signed.push(SignedBatchItem { index, nonce, raw }),
+                Err(e) => {
+                    tracing::error!("❌ Signing batch item {} failed, nothing was sent: {}", index, e);
+                    self.release(from, signed.iter().map(|s| s.nonce).chain([nonce]));
+                    return Err(e);
+                }
+            }
+        }
+
+        tracing::info!(
+            "📦 Signed batch of {} transaction(s) from {} starting at nonce {}",
+            signed.len(),
+            from,
+            signed[0].nonce
+        );
+        Ok(signed)
+    }
+
+    /// Send one signed item, counting a resend the chain already has as sent
+    async fn send_raw(&self, raw: &[u8]) -> std::result::Result<TxHash, String> {
+        let error = match self.provider.send_raw_transaction(raw).await {
+            Ok(pending) => return Ok(*pending.tx_hash()),
+            Err(e) => e.to_string(),
+        };
+        let (Some(rejection), Ok(tx)) = (BroadcastRejection::classify(&error), RawTransactionInfo::decode(raw)) else {
+            return Err(error);
+        };
+        match resolve_rejection(&self.provider, &tx, rejection).await {
+            Ok(Some(BroadcastOutcome::Replaced { .. })) => {
+                Err(format!("nonce {} was already used by another transaction", tx.nonce))
+            }
+            Ok(Some(_)) => Ok(tx.hash),
+            _ => Err(error),
+        }
+    }
+
+    /// Broadcast signed items in nonce order, yielding each item's status
+    pub fn broadcast(&self, from: Address, mut signed: Vec<SignedBatchItem>) -> impl Stream<Item = BatchUpdate> + '_ {
+        signed.sort_by_key(|item| item.nonce);
+        async_stream::stream! {
+            let mut items = signed.into_iter().peekable();
+            while let Some(item) = items.next() {
+                match self.send_raw(&item.raw).await {
+                    Ok(tx_hash) => {
+                        self.nonces.mark_sent(from, self.network, item.nonce, tx_hash);
+                        tracing::info!("✅ Batch item {} sent: {}", item.index, tx_hash);
+                        yield BatchUpdate {
+                            index: item.index,
+                            status: BatchStatus::Sent { nonce: item.nonce, tx_hash },
+                        };
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ Batch item {} failed: {}", item.index, e);
+                        let remaining: Vec<SignedBatchItem> = items.collect();
+                        self.release(from, std::iter::once(item.nonce).chain(remaining.iter().map(|s| s.nonce)));
+                        yield BatchUpdate {
+                            index: item.index,
+                            status: BatchStatus::Failed { nonce: item.nonce, error: e.to_string() },
+                        };
+                        for skipped in remaining {
+                            yield BatchUpdate {
+                                index: skipped.index,
+                                status: BatchStatus::Skipped { nonce: skipped.nonce },
+                            };
+                        }
+                        break;
+                    }
+                }
+                if items.peek().is_some() && !self.pacing.is_zero() {
+                    tokio::time::sleep(self.pacing).await;
+                }
+            }
+        }
+    }
+
+    /// Prepare and broadcast `queue`; fails before sending anything if any item cannot be signed
+    pub async fn send(&self, queue: &BatchQueue, from: Address) -> Result<impl Stream<Item = BatchUpdate> + '_> {
+        let signed = self.prepare(queue, from).await?;
+        Ok(self.broadcast(from, signed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+    use futures_util::StreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const FROM: Address = Address::repeat_byte(0x01);
+    const NETWORK: NetworkId = NetworkId(369);
+
+    /// Signs the first `limit` requests, as the nonce byte
+    struct CountingSigner {
+        limit: usize,
+        signed: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl TransactionSigner for CountingSigner {
+        async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Vec<u8>> {
+            if self.signed.fetch_add(1, Ordering::SeqCst) >= self.limit {
+                return Err(VaughanError::ValidationError("wallet locked".into()));
+            }
+            Ok(vec![tx.nonce.unwrap_or_default() as u8])
+        }
+    }
+
+    fn queue() -> BatchQueue {
+        let mut queue = BatchQueue::new();
+        queue.push(BatchItem::Approval {
+            token: Address::repeat_byte(0x70),
+            spender: Address::repeat_byte(0x5e),
+            amount: U256::MAX,
+        });
+        queue.push(BatchItem::Transfer {
+            to: Address::repeat_byte(0x22),
+            value: U256::from(1),
+        });
+        queue
+    }
+
+    /// Provider answering the nonce and fee lookups and two gas estimates
+    fn asserter() -> Asserter {
+        let asserter = Asserter::new();
+        asserter.push_success(&"0x5"); // pending transaction count
+        asserter.push_failure_msg("eth_feeHistory not supported");
+        asserter.push_success(&"0x3b9aca00"); // gas price
+        asserter.push_success(&"0xb411");
+        asserter.push_success(&"0x5208");
+        asserter
+    }
+
+    fn batch_sender(asserter: Asserter, limit: usize, nonces: Arc<NonceManager>) -> BatchSender<impl Provider> {
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+        let signer = Arc::new(CountingSigner {
+            limit,
+            signed: AtomicUsize::new(0),
+        });
+        BatchSender::new(provider, signer, nonces, NETWORK)
+    }
+
+    #[tokio::test]
+    async fn test_batch_sends_sequential_nonces() {
+        let asserter = asserter();
+        let first = TxHash::repeat_byte(0xa1);
+        let second = TxHash::repeat_byte(0xa2);
+        asserter.push_success(&first);
+        asserter.push_success(&second);
+        let nonces = Arc::new(NonceManager::new());
+        let sender = batch_sender(asserter, 2, Arc::clone(&nonces));
+
+        let signed = sender.prepare(&queue(), FROM).await.unwrap();
+        assert_eq!(signed.iter().map(|s| s.nonce).collect::<Vec<_>>(), vec![5, 6]);
+        assert_eq!(signed[1].raw, vec![6]);
+
+        let updates: Vec<BatchUpdate> = sender.broadcast(FROM, signed).collect().await;
+        assert_eq!(
+            updates,
+            vec![
+                BatchUpdate {
+                    index: 0,
+                    status: BatchStatus::Sent {
+                        nonce: 5,
+                        tx_hash: first
+                    },
+                },
+                BatchUpdate {
+                    index: 1,
+                    status: BatchStatus::Sent {
+                        nonce: 6,
+                        tx_hash: second
+                    },
+                },
+            ]
+        );
+        let pending = nonces.pending(FROM, NETWORK);
+        assert_eq!(pending[1].tx_hash, Some(second));
+        assert_eq!(nonces.peek_next(FROM, NETWORK), Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_failures_release_nonces() {
+        // Signing the second item fails: nothing is sent
+        let nonces = Arc::new(NonceManager::new());
+        let sender = batch_sender(asserter(), 1, Arc::clone(&nonces));
+        assert!(sender.prepare(&queue(), FROM).await.is_err());
+        assert!(nonces.pending(FROM, NETWORK).is_empty());
+        assert_eq!(nonces.peek_next(FROM, NETWORK), Some(5));
+
+        // The first broadcast fails: the rest are skipped
+        let asserter = asserter();
+        asserter.push_failure_msg("insufficient funds");
+        let sender = batch_sender(asserter, 2, Arc::clone(&nonces));
+        let updates: Vec<BatchUpdate> = sender.send(&queue(), FROM).await.unwrap().collect().await;
+        assert!(matches!(updates[0].status, BatchStatus::Failed { nonce: 5, .. }));
+        assert_eq!(updates[1].status, BatchStatus::Skipped { nonce: 6 });
+        assert_eq!(nonces.peek_next(FROM, NETWORK), Some(5));
+    }
+}
@@ -0,0 +1,819 @@
+//! CSV Batch Sends
+//!
+//! Payroll-style disbursements from a single account. A CSV of
+//! `address,amount[,reference]` rows becomes a [`CsvBatch`] in which every row
+//! carries its own status: invalid rows keep their validation error instead of
+//! rejecting the whole file, so a sheet can be run without them or fixed and
+//! re-imported.
+//!
+//! [`CsvBatchRunner`] sends the pending rows as plain transfers with
+//! sequential nonces, reporting each row as its status changes. A row is
+//! reported with its signed transaction before that transaction is broadcast,
+//! so a run that dies mid-row resumes by re-sending the same transaction. A
+//! run stops between rows when paused through [`BatchControl`] (or after
+//! repeated failures) and the next run resumes with the rows still pending; a
+//! row that already has a transaction is never signed again.
+//! [`CsvBatchStore`] keeps the batch on disk encrypted so a run survives
+//! restarts, and [`CsvBatch::report_csv`] exports the outcome of every row.
+
+use crate::error::{NetworkError, Result, VaughanError};
+use crate::security::keystore::storage;
+use crate::security::{Argon2Params, SecurityProfile};
+use crate::wallet::encrypted_store::EncryptedJsonStore;
+use crate::wallet::transaction::disperse::{DisperseAsset, DisperseRecipient, GAS_MARGIN_PERCENT};
+use crate::wallet::transaction::fees::{FeeEstimator, FeePriority};
+use crate::wallet::transaction::replacement::TransactionSigner;
+use alloy::primitives::utils::{format_units, parse_units, ParseUnits};
+use alloy::primitives::{keccak256, Address, Bytes, TxHash, U256};
+use alloy::providers::Provider;
+use chrono::{DateTime, Utc};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Failed rows in a row after which a run pauses itself
+pub const MAX_CONSECUTIVE_FAILURES: usize = 3;
+
+/// Batch of the primary profile in the `.vaughan` directory
+pub const CSV_BATCH_FILE: &str = "csv-batch.json";
+
+/// Where a row of the batch stands
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RowStatus {
+    /// Failed validation; never sent
+    Invalid { error: String },
+    /// Waiting to be sent
+    Pending,
+    /// Signed, broadcast possibly interrupted; resumed from `raw_tx`
+    Signing { nonce: u64, raw_tx: Bytes, tx_hash: TxHash },
+    /// Broadcast, not yet mined
+    Sent { tx_hash: TxHash, nonce: u64 },
+    /// Mined successfully
+    Confirmed { tx_hash: TxHash, block: u64 },
+    /// Rejected before broadcast, or reverted when `tx_hash` is set
+    Failed { tx_hash: Option<TxHash>, error: String },
+    /// Left out of the run by the user
+    Skipped,
+}
+
+impl RowStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Invalid { .. } => "invalid",
+            Self::Pending => "pending",
+            Self::Signing { .. } => "signing",
+            Self::Sent { .. } => "sent",
+            Self::Confirmed { .. } => "confirmed",
+            Self::Failed { .. } => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+
+    pub fn tx_hash(&self) -> Option<TxHash> {
+        match self {
+            Self::Signing { tx_hash, .. } | Self::Sent { tx_hash, .. } | Self::Confirmed { tx_hash, .. } => {
+                Some(*tx_hash)
+            }
+            Self::Failed { tx_hash, .. } => *tx_hash,
+            _ => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Self::Invalid { error } | Self::Failed { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// One line of the imported CSV
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRow {
+    /// 1-based line number in the imported file
+    pub line: usize,
+    /// Address and amount as written in the file
+    pub address: String,
+    pub amount: String,
+    /// Free-form payee reference, e.g. an employee id
+    pub reference: String,
+    /// Parsed payment; `None` for invalid rows
+    pub recipient: Option<DisperseRecipient>,
+    pub status: RowStatus,
+}
+
+/// Row counts of a batch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub invalid: usize,
+    pub pending: usize,
+    /// Signed rows whose broadcast is not known to have happened
+    pub signing: usize,
+    pub sent: usize,
+    pub confirmed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// Amount in sent and confirmed rows, in the asset's smallest unit
+    pub paid: U256,
+}
+
+impl BatchSummary {
+    /// Whether every row has reached a final status
+    pub fn is_done(&self) -> bool {
+        self.pending == 0 && self.signing == 0 && self.sent == 0
+    }
+}
+
+/// A CSV disbursement and the status of each of its rows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvBatch {
+    pub id: String,
+    pub chain_id: u64,
+    pub asset: DisperseAsset,
+    /// Account paying out, fixed by the first run
+    pub from: Option<Address>,
+    pub created_at: DateTime<Utc>,
+    rows: Vec<BatchRow>,
+}
+
+impl CsvBatch {
+    /// Import `address,amount[,reference]` lines
+    ///
+    /// Amounts are decimal values in whole units. Commas, semicolons and tabs
+    /// separate fields, so the reference is the rest of the line; blank lines,
+    /// `#` comments and a header row are skipped. A recipient listed twice is
+    /// invalid on its later lines, so nobody is paid twice by accident.
+    pub fn parse(chain_id: u64, asset: DisperseAsset, input: &str) -> Result<Self> {
+        let mut rows: Vec<BatchRow> = Vec::new();
+        let mut seen: HashMap<Address, usize> = HashMap::new();
+
+        for (index, raw) in input.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, [',', ';', '\t']).map(str::trim);
+            let address = fields.next().unwrap_or_default();
+            let amount = fields.next().unwrap_or_default();
+            let reference = fields.next().unwrap_or_default();
+
+            let parsed = parse_row(address, amount, asset.decimals());
+            if parsed.is_err() && rows.is_empty() && !address.starts_with("0x") {
+                // Header row
+                continue;
+            }
+            let status = match &parsed {
+                Ok(recipient) => match seen.entry(recipient.address) {
+                    Entry::Occupied(first) => RowStatus::Invalid {
+                        error: format!("recipient already paid on line {}", first.get()),
+                    },
+                    Entry::Vacant(entry) => {
+                        entry.insert(index + 1);
+                        RowStatus::Pending
+                    }
+                },
+                Err(error) => RowStatus::Invalid { error: error.clone() },
+            };
+            rows.push(BatchRow {
+                line: index + 1,
+                address: address.to_string(),
+                amount: amount.to_string(),
+                reference: reference.to_string(),
+                recipient: parsed.ok().filter(|_| status == RowStatus::Pending),
+                status,
+            });
+        }
+
+        if rows.is_empty() {
+            return Err(VaughanError::ValidationError("No payment rows in the CSV".to_string()));
+        }
+        Ok(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            chain_id,
+            asset,
+            from: None,
+            created_at: Utc::now(),
+            rows,
+        })
+    }
+
+    pub fn rows(&self) -> &[BatchRow] {
+        &self.rows
+    }
+
+    pub fn row(&self, line: usize) -> Option<&BatchRow> {
+        self.rows.iter().find(|r| r.line == line)
+    }
+
+    /// Take over the status of `row` as reported by a run of another copy
+    ///
+    /// Lets the caller keep a copy it can save while
+    /// [`CsvBatchRunner::run`] holds the batch.
+    pub fn update_row(&mut self, row: &BatchRow) {
+        if let Some(existing) = self.rows.iter_mut().find(|r| r.line == row.line) {
+            existing.status = row.status.clone();
+        }
+    }
+
+    /// Leave a pending row out of the run
+    pub fn skip(&mut self, line: usize) -> Result<()> {
+        let row = self
+            .rows
+            .iter_mut()
+            .find(|r| r.line == line)
+            .ok_or_else(|| VaughanError::ValidationError(format!("No row on line {line}")))?;
+        if row.status != RowStatus::Pending {
+            return Err(VaughanError::ValidationError(format!(
+                "Line {line} is {} and can't be skipped",
+                row.status.label()
+            )));
+        }
+        row.status = RowStatus::Skipped;
+        Ok(())
+    }
+
+    /// Put failed rows back in the queue, returning how many
+    ///
+    /// Only call this once the failures are understood: a row that failed
+    /// after a broadcast timed out may still be mined.
+    pub fn retry_failed(&mut self) -> usize {
+        let mut count = 0;
+        for row in &mut self.rows {
+            if matches!(row.status, RowStatus::Failed { .. }) {
+                row.status = RowStatus::Pending;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn summary(&self) -> BatchSummary {
+        let mut summary = BatchSummary::default();
+        for row in &self.rows {
+            match row.status {
+                RowStatus::Invalid { .. } => summary.invalid += 1,
+                RowStatus::Pending => summary.pending += 1,
+                RowStatus::Signing { .. } => summary.signing += 1,
+                RowStatus::Sent { .. } => summary.sent += 1,
+                RowStatus::Confirmed { .. } => summary.confirmed += 1,
+                RowStatus::Failed { .. } => summary.failed += 1,
+                RowStatus::Skipped => summary.skipped += 1,
+            }
+            if matches!(row.status, RowStatus::Sent { .. } | RowStatus::Confirmed { .. }) {
+                if let Some(recipient) = row.recipient {
+                    summary.paid = summary.paid.saturating_add(recipient.amount);
+                }
+            }
+        }
+        summary
+    }
+
+    /// Outcome of every row as CSV, one line per imported row
+    pub fn report_csv(&self) -> String {
+        let mut report = String::from("line,address,amount,reference,status,tx_hash,error\n");
+        for row in &self.rows {
+            let fields = [
+                row.line.to_string(),
+                row.address.clone(),
+                row.recipient
+                    .and_then(|r| format_units(r.amount, self.asset.decimals()).ok())
+                    .unwrap_or_else(|| row.amount.clone()),
+                row.reference.clone(),
+                row.status.label().to_string(),
+                row.status.tx_hash().map(|h| h.to_string()).unwrap_or_default(),
+                row.status.error().unwrap_or_default().to_string(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            report.push_str(&fields.join(","));
+            report.push('\n');
+        }
+        report
+    }
+
+    /// Write [`report_csv`](Self::report_csv) to `path`
+    pub fn export_report(&self, path: &Path) -> Result<()> {
+        storage::write_secure_file(&path.to_string_lossy(), &self.report_csv())
+    }
+}
+
+fn parse_row(address: &str, amount: &str, decimals: u8) -> std::result::Result<DisperseRecipient, String> {
+    if address.is_empty() || amount.is_empty() {
+        return Err("expected `address,amount[,reference]`".to_string());
+    }
+    let address = address
+        .parse::<Address>()
+        .map_err(|e| format!("invalid address `{address}`: {e}"))?;
+    if address == Address::ZERO {
+        return Err("recipient is the zero address".to_string());
+    }
+    match parse_units(amount, decimals) {
+        Ok(ParseUnits::U256(amount)) if !amount.is_zero() => Ok(DisperseRecipient { address, amount }),
+        Ok(_) => Err(format!("amount must be positive, found `{amount}`")),
+        Err(e) => Err(format!("invalid amount `{amount}`: {e}")),
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Pause switch shared between a running batch and the UI
+#[derive(Debug, Clone, Default)]
+pub struct BatchControl {
+    paused: Arc<AtomicBool>,
+}
+
+impl BatchControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop the run before its next row
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Allow the next run to proceed
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+}
+
+/// Signs and broadcasts the pending rows of CSV batches
+pub struct CsvBatchRunner<P> {
+    provider: P,
+    signer: Arc<dyn TransactionSigner>,
+    chain_id: u64,
+    priority: FeePriority,
+}
+
+impl<P: Provider> CsvBatchRunner<P> {
+    pub fn new(provider: P, signer: Arc<dyn TransactionSigner>, chain_id: u64) -> Self {
+        Self {
+            provider,
+            signer,
+            chain_id,
+            priority: FeePriority::Standard,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: FeePriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Send the pending rows of `batch` from `from`, in file order
+    ///
+    /// `on_update` sees every row whose status changed, so the caller can
+    /// persist the batch and refresh its view as the run goes. Each row is
+    /// reported as [`RowStatus::Signing`] before its broadcast, and rows left
+    /// in that state by an earlier run are settled first, see
+    /// [`Self::resume_signed`]. A row whose gas estimate or broadcast fails is
+    /// marked failed and the run moves on; [`MAX_CONSECUTIVE_FAILURES`]
+    /// failures in a row pause `control`.
+    pub async fn run(
+        &self,
+        batch: &mut CsvBatch,
+        from: Address,
+        control: &BatchControl,
+        mut on_update: impl FnMut(&BatchRow),
+    ) -> Result<BatchSummary> {
+        if batch.chain_id != self.chain_id {
+            return Err(VaughanError::ValidationError(format!(
+                "Batch is for chain {} but the wallet is on chain {}",
+                batch.chain_id, self.chain_id
+            )));
+        }
+        match batch.from {
+            Some(payer) if payer != from => {
+                return Err(VaughanError::ValidationError(format!(
+                    "Batch was started from {payer} and must be resumed from the same account"
+                )));
+            }
+            _ => batch.from = Some(from),
+        }
+        self.resume_signed(batch, from, &mut on_update).await?;
+        if batch.summary().pending == 0 || control.is_paused() {
+            return Ok(batch.summary());
+        }
+
+        let mut nonce =
+            self.provider
+                .get_transaction_count(from)
+                .pending()
+                .await
+                .map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to get transaction count: {e}"),
+                })?;
+        let fees = FeeEstimator::estimate_fees(&self.provider, self.priority).await?;
+
+        tracing::info!(
+            "📤 Running CSV batch {}: {} pending row(s) starting at nonce {}",
+            batch.id,
+            batch.summary().pending,
+            nonce
+        );
+
+        let mut consecutive_failures = 0;
+        for row in batch.rows.iter_mut() {
+            let (RowStatus::Pending, Some(recipient)) = (&row.status, row.recipient) else {
+                continue;
+            };
+            if control.is_paused() {
+                tracing::info!("⏸️ CSV batch paused before line {}", row.line);
+                break;
+            }
+
+            let mut request = batch
+                .asset
+                .transfer_request(&recipient)
+                .from(from)
+                .nonce(nonce)
+                .max_fee_per_gas(fees.max_fee_per_gas.saturating_to::<u128>())
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas.saturating_to::<u128>());
+            request.chain_id = Some(self.chain_id);
+
+            let signed = async {
+                // A failing estimate means the transfer would revert, e.g. for lack of funds
+                let estimate =
+                    self.provider
+                        .estimate_gas(request.clone())
+                        .await
+                        .map_err(|e| NetworkError::RpcError {
+                            message: format!("Transfer would fail: {e}"),
+                        })?;
+                let request = request.gas_limit(estimate.saturating_mul(100 + GAS_MARGIN_PERCENT) / 100);
+                self.signer.sign_transaction(&request).await
+            }
+            .await;
+
+            let sent = match signed {
+                Ok(raw_tx) => {
+                    // Reported first, so a crash after the broadcast resumes with this transaction
+                    let raw_tx = Bytes::from(raw_tx);
+                    row.status = RowStatus::Signing {
+                        nonce,
+                        tx_hash: keccak256(&raw_tx),
+                        raw_tx: raw_tx.clone(),
+                    };
+                    on_update(row);
+                    self.broadcast(&raw_tx).await
+                }
+                Err(e) => Err(e),
+            };
+
+            match sent {
+                Ok(tx_hash) => {
+                    tracing::info!("✅ CSV batch line {} sent: {}", row.line, tx_hash);
+                    row.status = RowStatus::Sent { tx_hash, nonce };
+                    nonce += 1;
+                    consecutive_failures = 0;
+                }
+                Err(e) => {
+                    tracing::error!("❌ CSV batch line {} failed: {}", row.line, e);
+                    row.status = RowStatus::Failed {
+                        tx_hash: None,
+                        error: e.to_string(),
+                    };
+                    consecutive_failures += 1;
+                }
+            }
+            on_update(row);
+
+            if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                tracing::warn!("⏸️ Pausing CSV batch after {} failures in a row", consecutive_failures);
+                control.pause();
+            }
+        }
+
+        Ok(batch.summary())
+    }
+
+    /// Settle rows an earlier run signed but may not have broadcast
+    ///
+    /// The signed transaction is never replaced by a new signature. When the
+    /// node knows it, the row is sent; when its nonce went to another
+    /// transaction, the row failed; otherwise the same transaction is broadcast
+    /// again. A row whose re-broadcast fails stays signed for the next run.
+    async fn resume_signed(
+        &self,
+        batch: &mut CsvBatch,
+        from: Address,
+        on_update: &mut impl FnMut(&BatchRow),
+    ) -> Result<()> {
+        for row in batch.rows.iter_mut() {
+            let RowStatus::Signing { nonce, ref raw_tx, tx_hash } = row.status else {
+                continue;
+            };
+            // Counted before the lookup, so a transaction mined in between is still found
+            let mined_nonce =
+                self.provider
+                    .get_transaction_count(from)
+                    .latest()
+                    .await
+                    .map_err(|e| NetworkError::RpcError {
+                        message: format!("Failed to get transaction count: {e}"),
+                    })?;
+            let known = self
+                .provider
+                .get_transaction_by_hash(tx_hash)
+                .await
+                .map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to look up transaction: {e}"),
+                })?
+                .is_some();
+
+            row.status = if known {
+                RowStatus::Sent { tx_hash, nonce }
+            } else if mined_nonce > nonce {
+                RowStatus::Failed {
+                    tx_hash: None,
+                    error: format!("nonce {nonce} was used by another transaction"),
+                }
+            } else {
+                match self.broadcast(raw_tx).await {
+                    Ok(tx_hash) => RowStatus::Sent { tx_hash, nonce },
+                    Err(e) => {
+                        tracing::warn!("⚠️ CSV batch line {} not re-sent yet: {}", row.line, e);
+                        continue;
+                    }
+                }
+            };
+            tracing::info!("♻️ CSV batch line {} resumed as {}", row.line, row.status.label());
+            on_update(row);
+        }
+        Ok(())
+    }
+
+    async fn broadcast(&self, raw_tx: &[u8]) -> Result<TxHash> {
+        let pending = self
+            .provider
+            .send_raw_transaction(raw_tx)
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to broadcast transfer: {e}"),
+            })?;
+        Ok(*pending.tx_hash())
+    }
+
+    /// Check receipts of sent rows, marking them confirmed or reverted
+    pub async fn refresh(&self, batch: &mut CsvBatch, mut on_update: impl FnMut(&BatchRow)) -> Result<BatchSummary> {
+        for row in batch.rows.iter_mut() {
+            let RowStatus::Sent { tx_hash, .. } = row.status else {
+                continue;
+            };
+            let receipt = self
+                .provider
+                .get_transaction_receipt(tx_hash)
+                .await
+                .map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to get transaction receipt: {e}"),
+                })?;
+            let Some(receipt) = receipt else {
+                continue;
+            };
+            row.status = if receipt.status() {
+                RowStatus::Confirmed {
+                    tx_hash,
+                    block: receipt.block_number.unwrap_or_default(),
+                }
+            } else {
+                RowStatus::Failed {
+                    tx_hash: Some(tx_hash),
+                    error: "transaction reverted".to_string(),
+                }
+            };
+            on_update(row);
+        }
+        Ok(batch.summary())
+    }
+}
+
+/// Encrypted file holding the batch in progress
+#[derive(Debug, Clone)]
+pub struct CsvBatchStore {
+    file: EncryptedJsonStore,
+}
+
+impl CsvBatchStore {
+    /// Batch file of `profile` in the `.vaughan` directory
+    pub fn new(profile: SecurityProfile) -> Self {
+        let file = match profile {
+            SecurityProfile::Primary => CSV_BATCH_FILE,
+            SecurityProfile::Decoy => "csv-batch-2.json",
+        };
+        Self::at(storage::get_vaughan_dir().join(file))
+    }
+
+    /// Batch file at an explicit path
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self {
+            file: EncryptedJsonStore::at(path),
+        }
+    }
+
+    /// Key derivation cost used when saving
+    pub fn with_argon2_params(mut self, params: Argon2Params) -> Self {
+        self.file = self.file.with_argon2_params(params);
+        self
+    }
+
+    /// Decrypt the saved batch; none when there is no batch in progress
+    pub fn load(&self, master_password: &SecretString) -> Result<Option<CsvBatch>> {
+        self.file.load(master_password)
+    }
+
+    /// Encrypt and write the batch
+    pub fn save(&self, batch: &CsvBatch, master_password: &SecretString) -> Result<()> {
+        self.file.save(batch, master_password)
+    }
+
+    /// Forget the batch once it is done
+    pub fn clear(&self) -> Result<()> {
+        self.file.clear()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::ProviderBuilder;
+    use alloy::rpc::types::TransactionRequest;
+    use alloy::transports::mock::Asserter;
+    use std::sync::atomic::AtomicUsize;
+
+    const CSV: &str = "address,amount,reference\n\
+                       # March payroll\n\
+                       0x1111111111111111111111111111111111111111,1500.5,EMP-001\n\
+                       0x123,10,EMP-002\n\
+                       0x2222222222222222222222222222222222222222;abc;EMP-003\n\
+                       0x3333333333333333333333333333333333333333\t250\tSmith, Jane\n\
+                       0x1111111111111111111111111111111111111111,20,EMP-001 bonus\n";
+
+    fn batch() -> CsvBatch {
+        CsvBatch::parse(
+            1,
+            DisperseAsset::Erc20 {
+                token: Address::repeat_byte(0x70),
+                decimals: 6,
+            },
+            CSV,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_keeps_invalid_rows() {
+        let batch = batch();
+        let lines: Vec<usize> = batch.rows().iter().map(|r| r.line).collect();
+        assert_eq!(lines, vec![3, 4, 5, 6, 7]);
+
+        let first = batch.row(3).unwrap();
+        assert_eq!(first.status, RowStatus::Pending);
+        assert_eq!(first.recipient.unwrap().amount, U256::from(1_500_500_000u64));
+        assert_eq!(batch.row(6).unwrap().reference, "Smith, Jane");
+
+        let error = |line| batch.row(line).unwrap().status.error().unwrap().to_string();
+        assert!(error(4).contains("invalid address"));
+        assert!(error(5).contains("invalid amount"));
+        assert!(error(7).contains("line 3"));
+        assert!(batch.row(7).unwrap().recipient.is_none());
+
+        let summary = batch.summary();
+        assert_eq!((summary.pending, summary.invalid), (2, 3));
+        assert!(CsvBatch::parse(1, DisperseAsset::Native, "address,amount\n").is_err());
+    }
+
+    #[test]
+    fn test_resume_and_report() {
+        let mut batch = batch();
+        let tx_hash = TxHash::repeat_byte(0xaa);
+        batch.rows[0].status = RowStatus::Sent { tx_hash, nonce: 7 };
+        batch.rows[3].status = RowStatus::Failed {
+            tx_hash: None,
+            error: "Transfer would fail: insufficient balance".to_string(),
+        };
+        assert!(batch.skip(3).is_err());
+
+        let summary = batch.summary();
+        assert_eq!(summary.paid, U256::from(1_500_500_000u64));
+        assert!(!summary.is_done());
+
+        let report = batch.report_csv();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[1],
+            format!("3,0x1111111111111111111111111111111111111111,1500.500000,EMP-001,sent,{tx_hash},")
+        );
+        assert!(lines[4].contains("\"Smith, Jane\",failed,,Transfer would fail"));
+
+        // Failed rows go back in the queue; sent rows are never requeued
+        assert_eq!(batch.retry_failed(), 1);
+        assert_eq!(batch.row(6).unwrap().status, RowStatus::Pending);
+        assert!(matches!(batch.row(3).unwrap().status, RowStatus::Sent { .. }));
+        batch.skip(6).unwrap();
+        assert!(batch.summary().pending == 0 && !batch.summary().is_done());
+
+        // A saved copy follows the rows a run reports
+        let mut copy = self::batch();
+        copy.update_row(batch.row(3).unwrap());
+        assert_eq!(copy.row(3), batch.row(3));
+    }
+
+    const FROM: Address = Address::repeat_byte(0x01);
+
+    /// Signs with the nonce as the only byte, counting signatures
+    #[derive(Default)]
+    struct CountingSigner {
+        signed: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl TransactionSigner for CountingSigner {
+        async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Vec<u8>> {
+            self.signed.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![tx.nonce.unwrap_or_default() as u8])
+        }
+    }
+
+    fn runner(asserter: Asserter, signer: Arc<CountingSigner>) -> CsvBatchRunner<impl Provider> {
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+        CsvBatchRunner::new(provider, signer, 1)
+    }
+
+    #[tokio::test]
+    async fn test_crash_after_broadcast_resends_the_signed_transaction() {
+        let mut batch =
+            CsvBatch::parse(1, DisperseAsset::Native, "0x1111111111111111111111111111111111111111,1").unwrap();
+        let tx_hash = TxHash::repeat_byte(0xa1);
+        let asserter = Asserter::new();
+        asserter.push_success(&"0x7"); // pending transaction count
+        asserter.push_failure_msg("eth_feeHistory not supported");
+        asserter.push_success(&"0x3b9aca00"); // gas price
+        asserter.push_success(&"0x5208");
+        asserter.push_success(&tx_hash);
+
+        // The process dies after the broadcast, before the sent row is saved
+        let mut on_disk = batch.clone();
+        let mut saves = 0;
+        runner(asserter, Arc::default())
+            .run(&mut batch, FROM, &BatchControl::new(), |row| {
+                if saves == 0 {
+                    on_disk.update_row(row);
+                }
+                saves += 1;
+            })
+            .await
+            .unwrap();
+        assert!(matches!(batch.rows[0].status, RowStatus::Sent { nonce: 7, .. }));
+        let RowStatus::Signing { nonce: 7, ref raw_tx, .. } = on_disk.rows[0].status else {
+            panic!("expected a signed row, found {:?}", on_disk.rows[0].status);
+        };
+        assert_eq!(raw_tx.as_ref(), [7]);
+        assert_eq!(on_disk.summary().signing, 1);
+
+        // Resuming broadcasts the same transaction while its nonce is free
+        let signer = Arc::new(CountingSigner::default());
+        let asserter = Asserter::new();
+        asserter.push_success(&"0x7"); // mined transaction count
+        asserter.push_success(&Option::<()>::None); // transaction unknown
+        asserter.push_success(&tx_hash);
+        let mut resumed = on_disk.clone();
+        let summary = runner(asserter, Arc::clone(&signer))
+            .run(&mut resumed, FROM, &BatchControl::new(), |_| {})
+            .await
+            .unwrap();
+        assert_eq!(resumed.rows[0].status, RowStatus::Sent { tx_hash, nonce: 7 });
+        assert_eq!((summary.sent, summary.pending), (1, 0));
+        assert_eq!(signer.signed.load(Ordering::SeqCst), 0);
+
+        // A nonce another transaction took fails the row instead of paying twice
+        let asserter = Asserter::new();
+        asserter.push_success(&"0x8");
+        asserter.push_success(&Option::<()>::None);
+        let mut resumed = on_disk.clone();
+        runner(asserter, Arc::clone(&signer))
+            .run(&mut resumed, FROM, &BatchControl::new(), |_| {})
+            .await
+            .unwrap();
+        assert!(matches!(&resumed.rows[0].status, RowStatus::Failed { error, .. } if error.contains("nonce 7")));
+        assert_eq!(signer.signed.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,623 @@
+//! Disperse (Multi-Recipient) Payment Module
+//!
+//! Sends native or ERC-20 amounts to many recipients in one flow:
+//!
+//! - **Contract mode**: a single call to a Disperse contract
+//!   (`disperseEther` / `disperseToken`), preceded by an `approve` when the
+//!   token allowance is too low
+//! - **Sequential mode**: one plain transfer per recipient, signed with
+//!   consecutive nonces and broadcast in order
+//!
+//! Recipient lists can be imported from CSV (`address,amount` per line).
+
+use crate::error::{NetworkError, Result, VaughanError};
+use crate::wallet::transaction::fees::{FeeEstimator, FeePriority};
+use crate::wallet::transaction::replacement::TransactionSigner;
+use alloy::primitives::utils::{parse_units, ParseUnits};
+use alloy::primitives::{address, Address, TxHash, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Disperse.app contract, deployed at the same address on Ethereum (and
+/// present on PulseChain through the state fork)
+pub const DISPERSE_APP: Address = address!("D152f549545093347A162Dce210e7293f1452150");
+
+/// Maximum recipients in a single disperse contract call
+pub const MAX_CONTRACT_RECIPIENTS: usize = 250;
+
+/// Gas margin applied to estimates, in percent
+pub(crate) const GAS_MARGIN_PERCENT: u64 = 20;
+
+sol! {
+    interface IDisperse {
+        function disperseEther(address[] recipients, uint256[] values) external payable;
+        function disperseToken(address token, address[] recipients, uint256[] values) external;
+    }
+
+    interface IERC20Disperse {
+        function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 amount) external returns (bool);
+        function transfer(address to, uint256 amount) external returns (bool);
+    }
+}
+
+/// Known disperse contract for a chain
+pub fn known_disperse_contract(chain_id: u64) -> Option<Address> {
+    match chain_id {
+        1 | 369 => Some(DISPERSE_APP),
+        _ => None,
+    }
+}
+
+/// Asset being dispersed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisperseAsset {
+    Native,
+    Erc20 { token: Address, decimals: u8 },
+}
+
+impl DisperseAsset {
+    pub fn decimals(&self) -> u8 {
+        match self {
+            DisperseAsset::Native => 18,
+            DisperseAsset::Erc20 { decimals, .. } => *decimals,
+        }
+    }
+
+    /// Plain transfer of `recipient.amount` to `recipient.address`
+    pub fn transfer_request(&self, recipient: &DisperseRecipient) -> TransactionRequest {
+        match self {
+            DisperseAsset::Native => TransactionRequest::default()
+                .to(recipient.address)
+                .value(recipient.amount),
+            DisperseAsset::Erc20 { token, .. } => {
+                let input = IERC20Disperse::transferCall {
+                    to: recipient.address,
+                    amount: recipient.amount,
+                }
+                .abi_encode();
+                TransactionRequest::default().to(*token).input(input.into())
+            }
+        }
+    }
+
+    /// Gas limit for a transfer whose estimate fails
+    pub(crate) fn fallback_transfer_gas(&self) -> u64 {
+        match self {
+            DisperseAsset::Native => 40_000,
+            DisperseAsset::Erc20 { .. } => 60_000,
+        }
+    }
+}
+
+/// One payment of a disperse plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisperseRecipient {
+    pub address: Address,
+    /// Amount in the asset's smallest unit
+    pub amount: U256,
+}
+
+/// A CSV line that could not be imported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvLineError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parse `address,amount` lines into recipients
+///
+/// Amounts are decimal values in whole units (e.g. `1.5`), converted with
+/// `decimals`. Commas, semicolons, tabs and spaces are accepted as separators;
+/// blank lines, `#` comments and a header row are skipped. Every invalid line
+/// is reported, not just the first.
+pub fn parse_recipients_csv(
+    input: &str,
+    decimals: u8,
+) -> std::result::Result<Vec<DisperseRecipient>, Vec<CsvLineError>> {
+    let mut recipients = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, raw) in input.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line
+            .split([',', ';', '\t', ' '])
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .collect();
+        let error = |message: String| CsvLineError {
+            line: index + 1,
+            message,
+        };
+
+        let [address, amount] = fields[..] else {
+            errors.push(error(format!(
+                "expected `address,amount`, found {} field(s)",
+                fields.len()
+            )));
+            continue;
+        };
+
+        let address = match address.parse::<Address>() {
+            Ok(address) => address,
+            // Header row
+            Err(_) if recipients.is_empty() && errors.is_empty() && !address.starts_with("0x") => continue,
+            Err(e) => {
+                errors.push(error(format!("invalid address `{address}`: {e}")));
+                continue;
+            }
+        };
+
+        match parse_units(amount, decimals) {
+            Ok(ParseUnits::U256(amount)) if !amount.is_zero() => recipients.push(DisperseRecipient { address, amount }),
+            Ok(_) => errors.push(error(format!("amount must be positive, found `{amount}`"))),
+            Err(e) => errors.push(error(format!("invalid amount `{amount}`: {e}"))),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(recipients)
+    } else {
+        Err(errors)
+    }
+}
+
+/// How a plan is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisperseMode {
+    /// Single call to the disperse contract at this address
+    Contract(Address),
+    /// One transfer per recipient
+    Sequential,
+}
+
+/// What a transaction of a plan does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisperseStepKind {
+    /// Token approval for the disperse contract
+    Approve,
+    /// Disperse contract call covering all recipients
+    Disperse,
+    /// Plain transfer to the recipient at this index
+    Transfer(usize),
+}
+
+/// A transaction of a plan, without nonce, gas or fees
+#[derive(Debug, Clone)]
+pub struct DisperseStep {
+    pub kind: DisperseStepKind,
+    pub request: TransactionRequest,
+}
+
+/// A validated multi-recipient payment
+#[derive(Debug, Clone)]
+pub struct DispersePlan {
+    asset: DisperseAsset,
+    recipients: Vec<DisperseRecipient>,
+    total: U256,
+}
+
+impl DispersePlan {
+    pub fn new(asset: DisperseAsset, recipients: Vec<DisperseRecipient>) -> Result<Self> {
+        if recipients.is_empty() {
+            return Err(VaughanError::ValidationError("No recipients to pay".to_string()));
+        }
+
+        let mut total = U256::ZERO;
+        for (index, recipient) in recipients.iter().enumerate() {
+            if recipient.address == Address::ZERO {
+                return Err(VaughanError::ValidationError(format!(
+                    "Recipient {} is the zero address",
+                    index + 1
+                )));
+            }
+            if recipient.amount.is_zero() {
+                return Err(VaughanError::ValidationError(format!(
+                    "Recipient {} has a zero amount",
+                    index + 1
+                )));
+            }
+            total = total
+                .checked_add(recipient.amount)
+                .ok_or_else(|| VaughanError::ValidationError("Total amount overflows".to_string()))?;
+        }
+
+        Ok(Self {
+            asset,
+            recipients,
+            total,
+        })
+    }
+
+    /// Parse a CSV recipient list into a plan
+    pub fn from_csv(asset: DisperseAsset, input: &str) -> Result<Self> {
+        let recipients = parse_recipients_csv(input, asset.decimals()).map_err(|errors| {
+            let lines: Vec<String> = errors
+                .iter()
+                .map(|e| format!("line {}: {}", e.line, e.message))
+                .collect();
+            VaughanError::ValidationError(format!("Invalid recipient list: {}", lines.join("; ")))
+        })?;
+        Self::new(asset, recipients)
+    }
+
+    pub fn asset(&self) -> DisperseAsset {
+        self.asset
+    }
+
+    pub fn recipients(&self) -> &[DisperseRecipient] {
+        &self.recipients
+    }
+
+    /// Sum of all amounts
+    pub fn total(&self) -> U256 {
+        self.total
+    }
+
+    /// Recipients that appear more than once, with their line count
+    pub fn duplicate_recipients(&self) -> Vec<(Address, usize)> {
+        let mut counts: HashMap<Address, usize> = HashMap::new();
+        for recipient in &self.recipients {
+            *counts.entry(recipient.address).or_default() += 1;
+        }
+        let mut duplicates: Vec<(Address, usize)> = counts.into_iter().filter(|(_, n)| *n > 1).collect();
+        duplicates.sort();
+        duplicates
+    }
+
+    /// Transactions that execute the plan, in order
+    ///
+    /// `allowance` is the sender's current token allowance for the disperse
+    /// contract; an approval step is added when it does not cover the total.
+    pub fn steps(&self, from: Address, mode: DisperseMode, allowance: U256) -> Result<Vec<DisperseStep>> {
+        let step = |kind, request: TransactionRequest| DisperseStep {
+            kind,
+            request: request.from(from),
+        };
+
+        match mode {
+            DisperseMode::Contract(contract) => {
+                if self.recipients.len() > MAX_CONTRACT_RECIPIENTS {
+                    return Err(VaughanError::ValidationError(format!(
+                        "A single disperse call supports at most {MAX_CONTRACT_RECIPIENTS} recipients, got {}",
+                        self.recipients.len()
+                    )));
+                }
+
+                let recipients: Vec<Address> = self.recipients.iter().map(|r| r.address).collect();
+                let values: Vec<U256> = self.recipients.iter().map(|r| r.amount).collect();
+
+                match self.asset {
+                    DisperseAsset::Native => {
+                        let input = IDisperse::disperseEtherCall { recipients, values }.abi_encode();
+                        Ok(vec![step(
+                            DisperseStepKind::Disperse,
+                            TransactionRequest::default()
+                                .to(contract)
+                                .value(self.total)
+                                .input(input.into()),
+                        )])
+                    }
+                    DisperseAsset::Erc20 { token, .. } => {
+                        let mut steps = Vec::new();
+                        if allowance < self.total {
+                            let input = IERC20Disperse::approveCall {
+                                spender: contract,
+                                amount: self.total,
+                            }
+                            .abi_encode();
+                            steps.push(step(
+                                DisperseStepKind::Approve,
+                                TransactionRequest::default().to(token).input(input.into()),
+                            ));
+                        }
+
+                        let input = IDisperse::disperseTokenCall {
+                            token,
+                            recipients,
+                            values,
+                        }
+                        .abi_encode();
+                        steps.push(step(
+                            DisperseStepKind::Disperse,
+                            TransactionRequest::default().to(contract).input(input.into()),
+                        ));
+                        Ok(steps)
+                    }
+                }
+            }
+            DisperseMode::Sequential => Ok(self
+                .recipients
+                .iter()
+                .enumerate()
+                .map(|(index, recipient)| {
+                    step(
+                        DisperseStepKind::Transfer(index),
+                        self.asset.transfer_request(recipient),
+                    )
+                })
+                .collect()),
+        }
+    }
+
+    /// Fallback gas limit for a step whose estimate fails (e.g. a disperse
+    /// call that depends on an approval that is not mined yet)
+    fn fallback_gas_limit(&self, kind: DisperseStepKind) -> u64 {
+        let per_recipient = self.asset.fallback_transfer_gas();
+        match kind {
+            DisperseStepKind::Approve => 60_000,
+            DisperseStepKind::Disperse => 50_000 + per_recipient * self.recipients.len() as u64,
+            DisperseStepKind::Transfer(_) => per_recipient,
+        }
+    }
+}
+
+/// A broadcast step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisperseSent {
+    pub kind: DisperseStepKind,
+    pub nonce: u64,
+    pub tx_hash: TxHash,
+}
+
+/// Outcome of executing a plan
+///
+/// Execution stops at the first failing step; the steps sent before it
+/// stay in `sent` so the caller can show what went through.
+#[derive(Debug, Clone, Default)]
+pub struct DisperseReport {
+    pub sent: Vec<DisperseSent>,
+    pub failed: Option<(DisperseStepKind, String)>,
+}
+
+impl DisperseReport {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_none()
+    }
+}
+
+/// Signs and broadcasts disperse plans
+pub struct Disperser<P> {
+    provider: P,
+    signer: Arc<dyn TransactionSigner>,
+    chain_id: u64,
+    priority: FeePriority,
+}
+
+impl<P: Provider> Disperser<P> {
+    pub fn new(provider: P, signer: Arc<dyn TransactionSigner>, chain_id: u64) -> Self {
+        Self {
+            provider,
+            signer,
+            chain_id,
+            priority: FeePriority::Standard,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: FeePriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Preferred mode for the chain: the known contract when there is one
+    pub fn default_mode(&self) -> DisperseMode {
+        known_disperse_contract(self.chain_id)
+            .map(DisperseMode::Contract)
+            .unwrap_or(DisperseMode::Sequential)
+    }
+
+    /// Execute a plan from `from`, sequencing nonces from the pending count
+    pub async fn execute(&self, plan: &DispersePlan, from: Address, mode: DisperseMode) -> Result<DisperseReport> {
+        let allowance = match (mode, plan.asset()) {
+            (DisperseMode::Contract(contract), DisperseAsset::Erc20 { token, .. }) => {
+                self.allowance(token, from, contract).await?
+            }
+            _ => U256::ZERO,
+        };
+        let steps = plan.steps(from, mode, allowance)?;
+
+        let mut nonce =
+            self.provider
+                .get_transaction_count(from)
+                .pending()
+                .await
+                .map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to get transaction count: {e}"),
+                })?;
+        let fees = FeeEstimator::estimate_fees(&self.provider, self.priority).await?;
+
+        tracing::info!(
+            "📤 Dispersing to {} recipients in {} transaction(s) starting at nonce {}",
+            plan.recipients().len(),
+            steps.len(),
+            nonce
+        );
+
+        let mut report = DisperseReport::default();
+        for step in steps {
+            let mut request = step
+                .request
+                .nonce(nonce)
+                .max_fee_per_gas(fees.max_fee_per_gas.saturating_to::<u128>())
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas.saturating_to::<u128>());
+            request.chain_id = Some(self.chain_id);
+
+            let gas_limit = match self.provider.estimate_gas(request.clone()).await {
+                Ok(estimate) => estimate.saturating_mul(100 + GAS_MARGIN_PERCENT) / 100,
+                Err(e) => {
+                    let fallback = plan.fallback_gas_limit(step.kind);
+                    tracing::warn!("⚠️ Gas estimate for {:?} failed ({}), using {}", step.kind, e, fallback);
+                    fallback
+                }
+            };
+            let request = request.gas_limit(gas_limit);
+
+            let sent = async {
+                let signed = self.signer.sign_transaction(&request).await?;
+                let pending =
+                    self.provider
+                        .send_raw_transaction(&signed)
+                        .await
+                        .map_err(|e| NetworkError::RpcError {
+                            message: format!("Failed to broadcast disperse transaction: {e}"),
+                        })?;
+                Ok::<_, VaughanError>(*pending.tx_hash())
+            }
+            .await;
+
+            match sent {
+                Ok(tx_hash) => {
+                    tracing::info!("✅ Disperse step {:?} sent: {}", step.kind, tx_hash);
+                    report.sent.push(DisperseSent {
+                        kind: step.kind,
+                        nonce,
+                        tx_hash,
+                    });
+                    nonce += 1;
+                }
+                Err(e) => {
+                    tracing::error!("❌ Disperse step {:?} failed: {}", step.kind, e);
+                    report.failed = Some((step.kind, e.to_string()));
+                    break;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn allowance(&self, token: Address, owner: Address, spender: Address) -> Result<U256> {
+        let input = IERC20Disperse::allowanceCall { owner, spender }.abi_encode();
+        let raw = self
+            .provider
+            .call(TransactionRequest::default().to(token).input(input.into()))
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to read token allowance: {e}"),
+            })?;
+        IERC20Disperse::allowanceCall::abi_decode_returns(&raw).map_err(|e| {
+            NetworkError::RpcError {
+                message: format!("Failed to decode token allowance: {e}"),
+            }
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FROM: Address = Address::repeat_byte(0x01);
+    const TOKEN: Address = Address::repeat_byte(0x70);
+
+    fn recipient(byte: u8, amount: u64) -> DisperseRecipient {
+        DisperseRecipient {
+            address: Address::repeat_byte(byte),
+            amount: U256::from(amount),
+        }
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "address,amount\n\
+                   # team payouts\n\
+                   0x1111111111111111111111111111111111111111, 1.5\n\
+                   \n\
+                   0x2222222222222222222222222222222222222222;0.25\n";
+        let recipients = parse_recipients_csv(csv, 6).unwrap();
+
+        assert_eq!(recipients, vec![recipient(0x11, 1_500_000), recipient(0x22, 250_000)]);
+    }
+
+    #[test]
+    fn test_parse_csv_reports_every_bad_line() {
+        let csv = "0x1111111111111111111111111111111111111111,1\n\
+                   0x123,1\n\
+                   0x2222222222222222222222222222222222222222,abc\n\
+                   0x3333333333333333333333333333333333333333,0\n\
+                   0x4444444444444444444444444444444444444444\n";
+        let errors = parse_recipients_csv(csv, 18).unwrap_err();
+
+        assert_eq!(errors.iter().map(|e| e.line).collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_plan_validation() {
+        assert!(DispersePlan::new(DisperseAsset::Native, vec![]).is_err());
+        assert!(DispersePlan::new(DisperseAsset::Native, vec![recipient(0x00, 1)]).is_err());
+
+        let plan = DispersePlan::new(
+            DisperseAsset::Native,
+            vec![recipient(0x11, 1), recipient(0x22, 2), recipient(0x11, 3)],
+        )
+        .unwrap();
+        assert_eq!(plan.total(), U256::from(6));
+        assert_eq!(plan.duplicate_recipients(), vec![(Address::repeat_byte(0x11), 2)]);
+    }
+
+    #[test]
+    fn test_native_contract_step_carries_total_value() {
+        let plan = DispersePlan::new(DisperseAsset::Native, vec![recipient(0x11, 5), recipient(0x22, 7)]).unwrap();
+        let steps = plan
+            .steps(FROM, DisperseMode::Contract(DISPERSE_APP), U256::ZERO)
+            .unwrap();
+
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].kind, DisperseStepKind::Disperse);
+        assert_eq!(steps[0].request.value, Some(U256::from(12)));
+
+        let input = steps[0].request.input.input().unwrap();
+        let call = IDisperse::disperseEtherCall::abi_decode(input).unwrap();
+        assert_eq!(call.values, vec![U256::from(5), U256::from(7)]);
+    }
+
+    #[test]
+    fn test_token_contract_steps_approve_only_when_needed() {
+        let asset = DisperseAsset::Erc20 {
+            token: TOKEN,
+            decimals: 18,
+        };
+        let plan = DispersePlan::new(asset, vec![recipient(0x11, 5), recipient(0x22, 7)]).unwrap();
+
+        let steps = plan
+            .steps(FROM, DisperseMode::Contract(DISPERSE_APP), U256::from(11))
+            .unwrap();
+        let kinds: Vec<_> = steps.iter().map(|s| s.kind).collect();
+        assert_eq!(kinds, vec![DisperseStepKind::Approve, DisperseStepKind::Disperse]);
+
+        let steps = plan
+            .steps(FROM, DisperseMode::Contract(DISPERSE_APP), U256::from(12))
+            .unwrap();
+        assert_eq!(steps.len(), 1);
+    }
+
+    #[test]
+    fn test_sequential_steps() {
+        let asset = DisperseAsset::Erc20 {
+            token: TOKEN,
+            decimals: 18,
+        };
+        let plan = DispersePlan::new(asset, vec![recipient(0x11, 5), recipient(0x22, 7)]).unwrap();
+        let steps = plan.steps(FROM, DisperseMode::Sequential, U256::ZERO).unwrap();
+
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].kind, DisperseStepKind::Transfer(1));
+        assert_eq!(steps[1].request.from, Some(FROM));
+
+        let input = steps[1].request.input.input().unwrap();
+        let call = IERC20Disperse::transferCall::abi_decode(input).unwrap();
+        assert_eq!(call.to, Address::repeat_byte(0x22));
+        assert_eq!(call.amount, U256::from(7));
+    }
+}
@@ -0,0 +1,249 @@
+//! Draft Transactions
+//!
+//! A partially composed send (recipient, amount, token, notes) can be kept as
+//! a [`TransactionDraft`] and resumed later. Drafts live in a [`DraftBook`]
+//! that [`DraftStore`] keeps on disk encrypted under the master password, so
+//! they survive restarts without exposing recipients or amounts.
+//!
+//! Drafts hold the form as typed; nothing is validated until the draft is
+//! resumed and sent.
+
+use crate::error::{Result, VaughanError};
+use crate::security::keystore::storage;
+use crate::security::{Argon2Params, SecurityProfile};
+use crate::wallet::encrypted_store::EncryptedJsonStore;
+use crate::wallet::metadata::MAX_NOTE_LENGTH;
+use chrono::{DateTime, Utc};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Drafts kept; saving another drops the least recently updated
+pub const MAX_DRAFTS: usize = 50;
+
+/// Drafts of the primary profile in the `.vaughan` directory
+pub const DRAFTS_FILE: &str = "drafts.json";
+
+/// A send form saved for later
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionDraft {
+    pub id: String,
+    /// Account the draft sends from, if one was selected
+    pub account_id: Option<String>,
+    pub chain_id: u64,
+    pub recipient: String,
+    pub amount: String,
+    /// Token as shown in the send form's token picker
+    pub token: String,
+    #[serde(default)]
+    pub custom_token_address: String,
+    #[serde(default)]
+    pub notes: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TransactionDraft {
+    pub fn new(chain_id: u64) -> Self {
+        let now = Utc::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            account_id: None,
+            chain_id,
+            recipient: String::new(),
+            amount: String::new(),
+            token: String::new(),
+            custom_token_address: String::new(),
+            notes: String::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether there is nothing worth keeping
+    pub fn is_blank(&self) -> bool {
+        self.recipient.trim().is_empty() && self.amount.trim().is_empty() && self.notes.trim().is_empty()
+    }
+
+    /// One-line description for the drafts list, e.g. `1.5 ETH to 0x742d…bEb0`
+    pub fn summary(&self) -> String {
+        let amount = match self.amount.trim() {
+            "" => "?",
+            amount => amount,
+        };
+        // Picker entries look like `NATIVE (ETH)` or `USDC (0xa0b8...)`
+        let token = match self.token.strip_prefix("NATIVE (") {
+            Some(native) => native.trim_end_matches(')'),
+            None => self.token.split(" (").next().unwrap_or_default(),
+        };
+        let asset = match token {
+            "" => amount.to_string(),
+            token => format!("{amount} {token}"),
+        };
+        let recipient = match self.recipient.trim() {
+            "" => "no recipient".to_string(),
+            r if r.is_ascii() && r.len() > 12 => format!("{}…{}", &r[..6], &r[r.len() - 4..]),
+            r => r.to_string(),
+        };
+        format!("{asset} to {recipient}")
+    }
+}
+
+/// Saved drafts, most recently updated first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftBook {
+    drafts: Vec<TransactionDraft>,
+}
+
+impl DraftBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.drafts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.drafts.is_empty()
+    }
+
+    pub fn drafts(&self) -> &[TransactionDraft] {
+        &self.drafts
+    }
+
+    pub fn get(&self, id: &str) -> Option<&TransactionDraft> {
+        self.drafts.iter().find(|d| d.id == id)
+    }
+
+    /// Add `draft` or replace the draft with its id, moving it to the top
+    pub fn save(&mut self, mut draft: TransactionDraft) -> Result<()> {
+        if draft.is_blank() {
+            return Err(VaughanError::ValidationError(
+                "Nothing to save in this draft".to_string(),
+            ));
+        }
+        if draft.notes.chars().count() > MAX_NOTE_LENGTH {
+            return Err(VaughanError::ValidationError(format!(
+                "Draft notes are limited to {MAX_NOTE_LENGTH} characters"
+            )));
+        }
+        if let Some(existing) = self.remove(&draft.id) {
+            draft.created_at = existing.created_at;
+        }
+        draft.updated_at = Utc::now();
+        self.drafts.insert(0, draft);
+        self.drafts.truncate(MAX_DRAFTS);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<TransactionDraft> {
+        let index = self.drafts.iter().position(|d| d.id == id)?;
+        Some(self.drafts.remove(index))
+    }
+}
+
+/// Encrypted drafts file
+#[derive(Debug, Clone)]
+pub struct DraftStore {
+    file: EncryptedJsonStore,
+}
+
+impl DraftStore {
+    /// Drafts file of `profile` in the `.vaughan` directory
+    pub fn new(profile: SecurityProfile) -> Self {
+        let file = match profile {
+            SecurityProfile::Primary => DRAFTS_FILE,
+            SecurityProfile::Decoy => "drafts-2.json",
+        };
+        Self::at(storage::get_vaughan_dir().join(file))
+    }
+
+    /// Drafts file at an explicit path
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self {
+            file: EncryptedJsonStore::at(path),
+        }
+    }
+
+    /// Key derivation cost used when saving
+    pub fn with_argon2_params(mut self, params: Argon2Params) -> Self {
+        self.file = self.file.with_argon2_params(params);
+        self
+    }
+
+    /// Decrypt the drafts; none when nothing has been saved yet
+    pub fn load(&self, master_password: &SecretString) -> Result<DraftBook> {
+        Ok(self.file.load(master_password)?.unwrap_or_else(DraftBook::new))
+    }
+
+    /// Encrypt and write the drafts
+    pub fn save(&self, drafts: &DraftBook, master_password: &SecretString) -> Result<()> {
+        self.file.save(drafts, master_password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(recipient: &str, amount: &str) -> TransactionDraft {
+        TransactionDraft {
+            recipient: recipient.to_string(),
+            amount: amount.to_string(),
+            token: "NATIVE (ETH)".to_string(),
+            ..TransactionDraft::new(1)
+        }
+    }
+
+    #[test]
+    fn test_draft_book_save_and_remove() {
+        let mut book = DraftBook::new();
+        assert!(book.save(TransactionDraft::new(1)).is_err());
+
+        let first = draft("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "1.5");
+        assert_eq!(first.summary(), "1.5 ETH to 0x742d…bEb0");
+        book.save(first.clone()).unwrap();
+        book.save(draft("", "2")).unwrap();
+        assert_eq!(book.drafts()[0].amount, "2");
+
+        // Saving again updates in place and moves the draft to the top
+        let mut edited = first.clone();
+        edited.notes = "rent".to_string();
+        book.save(edited).unwrap();
+        assert_eq!(book.len(), 2);
+        assert_eq!(book.drafts()[0].notes, "rent");
+        assert_eq!(book.drafts()[0].created_at, first.created_at);
+
+        assert!(book.remove(&first.id).is_some());
+        assert!(book.get(&first.id).is_none());
+
+        for i in 0..MAX_DRAFTS + 5 {
+            book.save(draft("", &i.to_string())).unwrap();
+        }
+        assert_eq!(book.len(), MAX_DRAFTS);
+    }
+
+    #[test]
+    fn test_draft_store_is_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DraftStore::at(dir.path().join("drafts.json")).with_argon2_params(Argon2Params {
+            memory_cost: 8,
+            time_cost: 1,
+            parallelism: 1,
+            output_length: 32,
+        });
+        let password = SecretString::new("master password".to_string());
+        assert!(store.load(&password).unwrap().is_empty());
+
+        let mut book = DraftBook::new();
+        book.save(draft("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "42"))
+            .unwrap();
+        store.save(&book, &password).unwrap();
+
+        let raw = std::fs::read_to_string(dir.path().join("drafts.json")).unwrap();
+        assert!(!raw.contains("742d35"));
+        assert_eq!(store.load(&password).unwrap(), book);
+        assert!(store.load(&SecretString::new("wrong".to_string())).is_err());
+    }
+}
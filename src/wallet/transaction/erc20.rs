@@ -0,0 +1,234 @@
+//! ERC-20 Transfer Builder
+//!
+//! Builds `transfer(address,uint256)` calls from human-readable amounts such
+//! as `"12.5"`, scaled by the token's decimals from [`TokenInfo`]. Amounts
+//! are parsed exactly: anything that cannot be represented in the token's
+//! smallest unit (too many decimal places, overflow) is rejected instead of
+//! being rounded.
+
+use crate::error::VaughanError;
+use crate::tokens::TokenInfo;
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use thiserror::Error;
+
+sol! {
+    interface IERC20Transfer {
+        function transfer(address to, uint256 amount) external returns (bool);
+    }
+}
+
+/// Errors building a token transfer
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TokenTransferError {
+    #[error("Amount is empty")]
+    EmptyAmount,
+
+    #[error("Invalid amount '{0}': expected a positive decimal number such as 12.5")]
+    InvalidAmount(String),
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount,
+
+    #[error("{symbol} supports at most {decimals} decimal places")]
+    TooPrecise { symbol: String, decimals: u8 },
+
+    #[error("Amount is too large for a uint256")]
+    Overflow,
+
+    #[error("Cannot send tokens to the zero address")]
+    ZeroRecipient,
+
+    #[error("Cannot build a token transfer for the native currency {0}")]
+    NativeToken(String),
+}
+
+impl From<TokenTransferError> for VaughanError {
+    fn from(error: TokenTransferError) -> Self {
+        VaughanError::ValidationError(error.to_string())
+    }
+}
+
+/// Parse a human amount into the token's smallest unit
+///
+/// Trailing fractional zeros beyond `decimals` are accepted (`"1.500"` with 1
+/// decimal), other excess precision is an error.
+pub fn parse_amount(amount: &str, decimals: u8, symbol: &str) -> Result<U256, TokenTransferError> {
+    let amount = amount.trim().replace('_', "");
+    if amount.is_empty() {
+        return Err(TokenTransferError::EmptyAmount);
+    }
+
+    let (whole, fraction) = amount.split_once('.').unwrap_or((&amount, ""));
+    if (whole.is_empty() && fraction.is_empty()) || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(TokenTransferError::InvalidAmount(amount.clone()));
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(TokenTransferError::TooPrecise {
+            symbol: symbol.to_string(),
+            decimals,
+        });
+    }
+
+    let digits = format!("{whole}{fraction:0<width$}", width = decimals as usize);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Err(TokenTransferError::ZeroAmount);
+    }
+    U256::from_str_radix(digits, 10).map_err(|_| TokenTransferError::Overflow)
+}
+
+/// Format a raw token amount with `decimals`, without trailing zeros
+pub fn format_amount(raw: U256, decimals: u8) -> String {
+    let digits = raw.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let padded = format!("{digits:0>width$}", width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+/// ABI-encoded `transfer(to, amount)` calldata
+pub fn transfer_calldata(to: Address, amount: U256) -> Bytes {
+    IERC20Transfer::transferCall { to, amount }.abi_encode().into()
+}
+
+/// A validated ERC-20 transfer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenTransfer {
+    pub token: Address,
+    pub to: Address,
+    /// Amount in the token's smallest unit
+    pub amount: U256,
+}
+
+impl TokenTransfer {
+    /// Build a transfer of a human amount of `token`
+    pub fn new(token: &TokenInfo, to: Address, amount: &str) -> Result<Self, TokenTransferError> {
+        if token.is_native {
+            return Err(TokenTransferError::NativeToken(token.symbol.clone()));
+        }
+        Self::from_raw(token.address, to, parse_amount(amount, token.decimals, &token.symbol)?)
+    }
+
+    /// Build a transfer of an amount already in the token's smallest unit
+    pub fn from_raw(token: Address, to: Address, amount: U256) -> Result<Self, TokenTransferError> {
+        if to == Address::ZERO {
+            return Err(TokenTransferError::ZeroRecipient);
+        }
+        if amount.is_zero() {
+            return Err(TokenTransferError::ZeroAmount);
+        }
+        Ok(Self { token, to, amount })
+    }
+
+    pub fn calldata(&self) -> Bytes {
+        transfer_calldata(self.to, self.amount)
+    }
+
+    /// Transaction request calling the token contract, without gas, fees or nonce
+    pub fn to_request(&self, from: Address) -> TransactionRequest {
+        TransactionRequest::default()
+            .from(from)
+            .to(self.token)
+            .value(U256::ZERO)
+            .input(self.calldata().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: Address = Address::repeat_byte(0x70);
+    const RECIPIENT: Address = Address::repeat_byte(0x42);
+
+    fn usdc() -> TokenInfo {
+        TokenInfo::new(TOKEN, 1, "USD Coin".to_string(), "USDC".to_string(), 6)
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("12.5", 6, "USDC"), Ok(U256::from(12_500_000)));
+        assert_eq!(parse_amount(".5", 6, "USDC"), Ok(U256::from(500_000)));
+        assert_eq!(parse_amount("1_000", 0, "X"), Ok(U256::from(1000)));
+        assert_eq!(parse_amount("1.2300000", 2, "X"), Ok(U256::from(123)));
+    }
+
+    #[test]
+    fn test_parse_amount_rejections() {
+        assert_eq!(parse_amount(" ", 6, "USDC"), Err(TokenTransferError::EmptyAmount));
+        assert_eq!(parse_amount("0.000", 6, "USDC"), Err(TokenTransferError::ZeroAmount));
+        assert!(matches!(
+            parse_amount("-1", 6, "USDC"),
+            Err(TokenTransferError::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            parse_amount("1e6", 6, "USDC"),
+            Err(TokenTransferError::InvalidAmount(_))
+        ));
+        assert_eq!(
+            parse_amount("0.0000001", 6, "USDC"),
+            Err(TokenTransferError::TooPrecise {
+                symbol: "USDC".to_string(),
+                decimals: 6
+            })
+        );
+        assert_eq!(
+            parse_amount(&"9".repeat(80), 18, "X"),
+            Err(TokenTransferError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_format_amount_round_trip() {
+        assert_eq!(format_amount(U256::from(12_500_000), 6), "12.5");
+        assert_eq!(format_amount(U256::from(5), 6), "0.000005");
+        assert_eq!(format_amount(U256::from(3_000_000), 6), "3");
+        assert_eq!(format_amount(U256::from(42), 0), "42");
+
+        let raw = parse_amount("1234.000567", 18, "X").unwrap();
+        assert_eq!(format_amount(raw, 18), "1234.000567");
+    }
+
+    #[test]
+    fn test_transfer_request() {
+        let transfer = TokenTransfer::new(&usdc(), RECIPIENT, "2.25").unwrap();
+        assert_eq!(transfer.amount, U256::from(2_250_000));
+
+        let request = transfer.to_request(Address::repeat_byte(1));
+        assert_eq!(request.to, Some(TOKEN.into()));
+        assert_eq!(request.value, Some(U256::ZERO));
+
+        let input = request.input.input().unwrap();
+        assert_eq!(&input[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+        let call = IERC20Transfer::transferCall::abi_decode(input).unwrap();
+        assert_eq!((call.to, call.amount), (RECIPIENT, U256::from(2_250_000)));
+    }
+
+    #[test]
+    fn test_transfer_validation() {
+        assert_eq!(
+            TokenTransfer::new(&usdc(), Address::ZERO, "1"),
+            Err(TokenTransferError::ZeroRecipient)
+        );
+
+        let native = TokenInfo::new(Address::ZERO, 369, "Pulse".to_string(), "PLS".to_string(), 18);
+        assert!(matches!(
+            TokenTransfer::new(&native, RECIPIENT, "1"),
+            Err(TokenTransferError::NativeToken(_))
+        ));
+    }
+}
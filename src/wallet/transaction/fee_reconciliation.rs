@@ -0,0 +1,302 @@
+//! Fee Reconciliation Module
+//!
+//! A speed-up or cancel sends several transactions with the same nonce, and
+//! at most one of them is mined. Reconciliation charges the fee from the
+//! mined transaction's receipt to the whole group. Every other attempt is
+//! recorded as refunded: it reserved gas but never paid for it. A reverted
+//! transaction still pays for the gas it used.
+//!
+//! [`FeeLedger`] keys settlements by sender and nonce. Recording the same
+//! group again, for example after a refresh, replaces the earlier entry
+//! instead of counting its fee twice.
+
+use crate::wallet::transaction::replacement::{ReplacementFees, ReplacementKind};
+use alloy::primitives::{Address, TxHash, U256};
+use alloy::rpc::types::TransactionReceipt;
+use std::collections::HashMap;
+
+/// One transaction sent for a nonce: the original or a replacement
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeAttempt {
+    pub hash: TxHash,
+    /// `None` for the original transaction
+    pub kind: Option<ReplacementKind>,
+    pub gas_limit: u64,
+    pub fees: ReplacementFees,
+}
+
+impl FeeAttempt {
+    /// Most this attempt could have cost (gas limit at the max fee per gas)
+    pub fn max_fee(&self) -> U256 {
+        let per_gas = match self.fees {
+            ReplacementFees::Legacy { gas_price } => gas_price,
+            ReplacementFees::Eip1559 { max_fee_per_gas, .. } => max_fee_per_gas,
+        };
+        U256::from(self.gas_limit) * U256::from(per_gas)
+    }
+}
+
+/// Fee-relevant parts of a mined transaction's receipt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinedReceipt {
+    pub hash: TxHash,
+    pub gas_used: u64,
+    pub effective_gas_price: u128,
+    pub success: bool,
+}
+
+impl MinedReceipt {
+    pub fn from_rpc(receipt: &TransactionReceipt) -> Self {
+        Self {
+            hash: receipt.transaction_hash,
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+            success: receipt.status(),
+        }
+    }
+
+    /// Fee actually charged: gas used times the effective gas price
+    pub fn fee(&self) -> U256 {
+        U256::from(self.gas_used) * U256::from(self.effective_gas_price)
+    }
+}
+
+/// What happened to one attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    /// Not mined yet, and no other attempt for the nonce has been either
+    Pending,
+    Mined,
+    /// Mined but reverted; the gas used is still paid
+    Reverted,
+    /// Another attempt for the nonce was mined instead
+    Dropped,
+}
+
+/// Fee paid and refunded for one attempt
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttemptFee {
+    pub hash: TxHash,
+    pub kind: Option<ReplacementKind>,
+    pub outcome: AttemptOutcome,
+    pub fee_paid: U256,
+    /// Reserved fee that was never charged
+    pub fee_refunded: U256,
+}
+
+/// Final state of a nonce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementStatus {
+    Pending,
+    /// The original call or a speed-up of it was mined
+    Confirmed,
+    /// A cancellation was mined, so the original call never ran
+    Cancelled,
+    /// The mined transaction reverted
+    Failed,
+}
+
+/// Reconciled fees of the original transaction and its replacements
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeSettlement {
+    pub from: Address,
+    pub nonce: u64,
+    pub original_hash: TxHash,
+    /// Hash of the attempt that was mined
+    pub settled_hash: Option<TxHash>,
+    pub status: SettlementStatus,
+    pub attempts: Vec<AttemptFee>,
+}
+
+impl FeeSettlement {
+    /// Fee charged for the nonce; only the mined attempt pays
+    pub fn fee_paid(&self) -> U256 {
+        self.attempts.iter().map(|a| a.fee_paid).sum()
+    }
+
+    /// Reserved fees released across all attempts
+    pub fn fee_refunded(&self) -> U256 {
+        self.attempts.iter().map(|a| a.fee_refunded).sum()
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.status != SettlementStatus::Pending
+    }
+}
+
+/// Reconcile the attempts for one nonce against the receipt of the mined one
+///
+/// `attempts` starts with the original transaction. A receipt for a hash that
+/// is not among the attempts leaves the settlement pending.
+pub fn reconcile(from: Address, nonce: u64, attempts: &[FeeAttempt], receipt: Option<&MinedReceipt>) -> FeeSettlement {
+    let mined = receipt.and_then(|r| attempts.iter().find(|a| a.hash == r.hash).map(|a| (a, r)));
+
+    let status = match mined {
+        None => SettlementStatus::Pending,
+        Some((_, receipt)) if !receipt.success => SettlementStatus::Failed,
+        Some((attempt, _)) if attempt.kind == Some(ReplacementKind::Cancel) => SettlementStatus::Cancelled,
+        Some(_) => SettlementStatus::Confirmed,
+    };
+
+    let fees: Vec<AttemptFee> = attempts
+        .iter()
+        .map(|attempt| {
+            let (outcome, fee_paid, fee_refunded) = match mined {
+                None => (AttemptOutcome::Pending, U256::ZERO, U256::ZERO),
+                Some((m, receipt)) if m.hash == attempt.hash => {
+                    let paid = receipt.fee();
+                    let outcome = if receipt.success {
+                        AttemptOutcome::Mined
+                    } else {
+                        AttemptOutcome::Reverted
+                    };
+                    (outcome, paid, attempt.max_fee().saturating_sub(paid))
+                }
+                Some(_) => (AttemptOutcome::Dropped, U256::ZERO, attempt.max_fee()),
+            };
+            AttemptFee {
+                hash: attempt.hash,
+                kind: attempt.kind,
+                outcome,
+                fee_paid,
+                fee_refunded,
+            }
+        })
+        .collect();
+
+    FeeSettlement {
+        from,
+        nonce,
+        original_hash: attempts.first().map(|a| a.hash).unwrap_or_default(),
+        settled_hash: mined.map(|(a, _)| a.hash),
+        status,
+        attempts: fees,
+    }
+}
+
+/// Reconciled fees per sender and nonce, for history and analytics totals
+#[derive(Debug, Default)]
+pub struct FeeLedger {
+    settlements: HashMap<(Address, u64), FeeSettlement>,
+}
+
+impl FeeLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a settlement, replacing any earlier one for the same nonce
+    pub fn record(&mut self, settlement: FeeSettlement) -> Option<FeeSettlement> {
+        self.settlements.insert((settlement.from, settlement.nonce), settlement)
+    }
+
+    pub fn get(&self, from: Address, nonce: u64) -> Option<&FeeSettlement> {
+        self.settlements.get(&(from, nonce))
+    }
+
+    /// Settlement containing `hash` as its original or a replacement
+    pub fn find_by_hash(&self, hash: TxHash) -> Option<&FeeSettlement> {
+        self.settlements
+            .values()
+            .find(|s| s.attempts.iter().any(|a| a.hash == hash))
+    }
+
+    pub fn settlements(&self) -> impl Iterator<Item = &FeeSettlement> {
+        self.settlements.values()
+    }
+
+    /// Total fees paid by `from`
+    pub fn fees_paid_by(&self, from: Address) -> U256 {
+        self.settlements()
+            .filter(|s| s.from == from)
+            .map(FeeSettlement::fee_paid)
+            .sum()
+    }
+
+    /// Total fees paid across all senders
+    pub fn total_fees_paid(&self) -> U256 {
+        self.settlements().map(FeeSettlement::fee_paid).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(byte: u8, kind: Option<ReplacementKind>, gas_price: u128) -> FeeAttempt {
+        FeeAttempt {
+            hash: TxHash::repeat_byte(byte),
+            kind,
+            gas_limit: 21_000,
+            fees: ReplacementFees::Legacy { gas_price },
+        }
+    }
+
+    #[test]
+    fn test_only_mined_attempt_pays() {
+        let from = Address::repeat_byte(0xaa);
+        let attempts = [attempt(1, None, 100), attempt(2, Some(ReplacementKind::SpeedUp), 113)];
+        let receipt = MinedReceipt {
+            hash: TxHash::repeat_byte(2),
+            gas_used: 21_000,
+            effective_gas_price: 110,
+            success: true,
+        };
+
+        let settlement = reconcile(from, 7, &attempts, Some(&receipt));
+        assert_eq!(settlement.status, SettlementStatus::Confirmed);
+        assert_eq!(settlement.settled_hash, Some(TxHash::repeat_byte(2)));
+        assert_eq!(settlement.fee_paid(), U256::from(21_000u64 * 110));
+        assert_eq!(settlement.attempts[0].outcome, AttemptOutcome::Dropped);
+        assert_eq!(settlement.attempts[0].fee_refunded, U256::from(21_000u64 * 100));
+        assert_eq!(settlement.attempts[1].fee_refunded, U256::from(21_000u64 * 3));
+
+        let pending = reconcile(from, 7, &attempts, None);
+        assert_eq!(pending.status, SettlementStatus::Pending);
+        assert_eq!(pending.fee_paid(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_cancel_and_revert_status() {
+        let from = Address::repeat_byte(0xaa);
+        let attempts = [attempt(1, None, 100), attempt(2, Some(ReplacementKind::Cancel), 113)];
+        let mut receipt = MinedReceipt {
+            hash: TxHash::repeat_byte(2),
+            gas_used: 21_000,
+            effective_gas_price: 113,
+            success: true,
+        };
+        assert_eq!(
+            reconcile(from, 1, &attempts, Some(&receipt)).status,
+            SettlementStatus::Cancelled
+        );
+
+        receipt.hash = TxHash::repeat_byte(1);
+        receipt.success = false;
+        let failed = reconcile(from, 1, &attempts, Some(&receipt));
+        assert_eq!(failed.status, SettlementStatus::Failed);
+        assert_eq!(failed.attempts[0].outcome, AttemptOutcome::Reverted);
+        assert_eq!(failed.fee_paid(), U256::from(21_000u64 * 113));
+    }
+
+    #[test]
+    fn test_ledger_never_double_counts_a_nonce() {
+        let from = Address::repeat_byte(0xaa);
+        let attempts = [attempt(1, None, 100), attempt(2, Some(ReplacementKind::SpeedUp), 113)];
+        let receipt = MinedReceipt {
+            hash: TxHash::repeat_byte(2),
+            gas_used: 21_000,
+            effective_gas_price: 100,
+            success: true,
+        };
+
+        let mut ledger = FeeLedger::new();
+        ledger.record(reconcile(from, 3, &attempts, None));
+        ledger.record(reconcile(from, 3, &attempts, Some(&receipt)));
+        ledger.record(reconcile(from, 3, &attempts, Some(&receipt)));
+
+        assert_eq!(ledger.total_fees_paid(), U256::from(2_100_000u64));
+        assert_eq!(ledger.fees_paid_by(from), U256::from(2_100_000u64));
+        assert_eq!(ledger.find_by_hash(TxHash::repeat_byte(1)).map(|s| s.nonce), Some(3));
+    }
+}
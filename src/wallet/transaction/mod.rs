@@ -4,6 +4,16 @@
 //! - Transaction simulation (dry-run execution)
 //! - Revert reason decoding
 //! - Gas estimation
+//! - Replace-by-fee speed-up and cancellation
+//! - Fee reconciliation across replaced transactions
+//! - Multi-recipient (disperse) payments
+//! - ERC-20 transfer calldata from human amounts
+//! - Wrapping and unwrapping the native currency (ETH↔WETH, PLS↔WPLS, ...)
+//! - Gas sponsorship by a separate fee payer (ERC-4337 or ERC-2771 relayer)
+//! - Batches of queued transactions signed together and sent with sequential nonces
+//! - Gas estimation with state overrides for approve-then-call flows
+//! - Encrypted drafts of partially composed sends
+//! - CSV batch sends with per-row status, pause/resume and a final report
 //!
 //! # Task Reference
 //!
@@ -12,6 +22,16 @@
 
 pub mod simulator;
 pub mod fees;
+pub mod replacement;
+pub mod fee_reconciliation;
+pub mod disperse;
+pub mod erc20;
+pub mod sponsorship;
+pub mod batch;
+pub mod wrap;
+pub mod overrides;
+pub mod drafts;
+pub mod csv_batch;
 
 pub use simulator::*;
 pub use fees::*;
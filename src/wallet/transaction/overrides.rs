@@ -0,0 +1,305 @@
+//! Gas Estimation With State Overrides
+//!
+//! Flows such as approve+swap need two transactions, and the second one
+//! reverts during estimation until the first is mined. Instead of guessing a
+//! gas limit, [`StateOverrideEstimator`] estimates the second step against
+//! state overridden so the allowance already exists:
+//!
+//! - the token's allowance mapping slot is found by probing: a marker value
+//!   is written to candidate slots through an override and the slot whose
+//!   marker `allowance(owner, spender)` reads back is the mapping
+//! - both Solidity and Vyper mapping layouts are probed
+//! - tokens whose allowance can't be located this way (proxies with
+//!   unstructured storage, non-standard layouts) fall back to a plain
+//!   estimate, and the preview reports the follow-up as unknown
+//!
+//! The endpoint must support the state override parameter of `eth_call` and
+//! `eth_estimateGas`, which geth, erigon, reth and most hosted RPCs do.
+
+use crate::defi::swap::SwapTransaction;
+use crate::error::{Result, VaughanError};
+use alloy::primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::state::{StateOverride, StateOverridesBuilder};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use std::fmt;
+
+sol! {
+    interface IAllowance {
+        function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+}
+
+/// Storage slots probed when looking for a token's allowance mapping
+pub const MAX_PROBED_SLOTS: u64 = 32;
+
+/// Value written while probing, unlikely to be a real allowance
+const PROBE_MARKER: U256 = U256::from_limbs([0x5eed_5eed_5eed_5eed, 0x7a11, 0, 0]);
+
+/// How a compiler lays out `mapping(address => mapping(address => uint256))`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingLayout {
+    /// `keccak(spender . keccak(owner . slot))`
+    Solidity,
+    /// `keccak(keccak(slot . owner) . spender)`
+    Vyper,
+}
+
+/// Location of a token's allowance mapping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowanceSlot {
+    pub slot: u64,
+    pub layout: MappingLayout,
+}
+
+/// Storage key holding `allowance(owner, spender)` for a mapping at `slot`
+pub fn allowance_storage_key(owner: Address, spender: Address, slot: AllowanceSlot) -> B256 {
+    let slot_word = B256::from(U256::from(slot.slot));
+    let concat = |a: B256, b: B256| {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(a.as_slice());
+        buf[32..].copy_from_slice(b.as_slice());
+        keccak256(buf)
+    };
+    match slot.layout {
+        MappingLayout::Solidity => concat(spender.into_word(), concat(owner.into_word(), slot_word)),
+        MappingLayout::Vyper => concat(concat(slot_word, owner.into_word()), spender.into_word()),
+    }
+}
+
+/// State override setting `allowance(owner, spender)` of `token` to `amount`
+pub fn allowance_override(
+    token: Address,
+    owner: Address,
+    spender: Address,
+    slot: AllowanceSlot,
+    amount: U256,
+) -> StateOverride {
+    let key = allowance_storage_key(owner, spender, slot);
+    StateOverridesBuilder::default()
+        .with_state_diff(token, [(key, B256::from(amount))])
+        .build()
+}
+
+/// Gas preview of an approval followed by the call that needs it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalFlowEstimate {
+    pub approval_gas: u64,
+    /// `None` when the follow-up couldn't be estimated before the approval is mined
+    pub follow_up_gas: Option<u64>,
+    /// Whether the follow-up was estimated against an overridden allowance
+    pub overridden: bool,
+}
+
+impl ApprovalFlowEstimate {
+    /// Gas of both steps, `None` while the follow-up is unknown
+    pub fn total_gas(&self) -> Option<u64> {
+        self.follow_up_gas.map(|gas| self.approval_gas.saturating_add(gas))
+    }
+
+    /// Cost of both steps at `gas_price` wei per gas
+    pub fn total_cost(&self, gas_price: u128) -> Option<U256> {
+        self.total_gas()
+            .map(|gas| U256::from(gas).saturating_mul(U256::from(gas_price)))
+    }
+}
+
+impl fmt::Display for ApprovalFlowEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "approval {} gas", self.approval_gas)?;
+        match self.follow_up_gas {
+            Some(gas) if self.overridden => write!(f, ", then {gas} gas (simulated with the approval in place)"),
+            Some(gas) => write!(f, ", then {gas} gas"),
+            None => write!(f, ", then unknown until the approval is mined"),
+        }
+    }
+}
+
+/// Estimates gas and runs calls against overridden state
+pub struct StateOverrideEstimator<P> {
+    provider: P,
+}
+
+impl<P: Provider> StateOverrideEstimator<P> {
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    /// `eth_estimateGas` with `overrides` applied
+    pub async fn estimate_gas_with(&self, tx: TransactionRequest, overrides: StateOverride) -> Result<u64> {
+        self.provider
+            .estimate_gas(tx)
+            .overrides(overrides)
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to estimate gas with state overrides", e))
+    }
+
+    /// `eth_call` with `overrides` applied
+    pub async fn call_with(&self, tx: TransactionRequest, overrides: StateOverride) -> Result<Bytes> {
+        self.provider
+            .call(tx)
+            .overrides(overrides)
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to call with state overrides", e))
+    }
+
+    async fn read_allowance(
+        &self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+        overrides: StateOverride,
+    ) -> Option<U256> {
+        let call = TransactionRequest::default()
+            .to(token)
+            .input(Bytes::from(IAllowance::allowanceCall { owner, spender }.abi_encode()).into());
+        let data = self.call_with(call, overrides).await.ok()?;
+        IAllowance::allowanceCall::abi_decode_returns(&data).ok()
+    }
+
+    /// Locate the allowance mapping of `token` by probing its storage
+    ///
+    /// `Ok(None)` when no slot below [`MAX_PROBED_SLOTS`] reads back the probe.
+    pub async fn find_allowance_slot(
+        &self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+    ) -> Result<Option<AllowanceSlot>> {
+        for slot in 0..MAX_PROBED_SLOTS {
+            for layout in [MappingLayout::Solidity, MappingLayout::Vyper] {
+                let candidate = AllowanceSlot { slot, layout };
+                let overrides = allowance_override(token, owner, spender, candidate, PROBE_MARKER);
+                if self.read_allowance(token, owner, spender, overrides).await == Some(PROBE_MARKER) {
+                    tracing::debug!(
+                        "🔎 Allowance mapping of {} found at slot {} ({:?})",
+                        token,
+                        slot,
+                        layout
+                    );
+                    return Ok(Some(candidate));
+                }
+            }
+        }
+        tracing::warn!("⚠️ Could not locate the allowance mapping of {}", token);
+        Ok(None)
+    }
+
+    /// Override making `allowance(owner, spender)` equal `amount`, if the slot can be found
+    pub async fn approval_override(
+        &self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<Option<StateOverride>> {
+        Ok(self
+            .find_allowance_slot(token, owner, spender)
+            .await?
+            .map(|slot| allowance_override(token, owner, spender, slot, amount)))
+    }
+
+    /// Estimate `approve(spender, amount)` on `token` and the `follow_up` call that spends it
+    ///
+    /// The sender of `follow_up` is the owner of the allowance.
+    pub async fn estimate_approval_flow(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+        follow_up: TransactionRequest,
+    ) -> Result<ApprovalFlowEstimate> {
+        let owner = follow_up
+            .from
+            .ok_or_else(|| VaughanError::ValidationError("Follow-up transaction has no sender".to_string()))?;
+        let approval = TransactionRequest::default()
+            .from(owner)
+            .to(token)
+            .input(Bytes::from(IAllowance::approveCall { spender, amount }.abi_encode()).into());
+        let approval_gas = self
+            .provider
+            .estimate_gas(approval)
+            .await
+            .map_err(|e| VaughanError::rpc("Failed to estimate approval gas", e))?;
+
+        let (follow_up_gas, overridden) = match self.approval_override(token, owner, spender, amount).await? {
+            Some(overrides) => (Some(self.estimate_gas_with(follow_up, overrides).await?), true),
+            None => match self.provider.estimate_gas(follow_up).await {
+                Ok(gas) => (Some(gas), false),
+                Err(e) => {
+                    tracing::warn!("⚠️ Follow-up gas unknown until the approval is mined: {}", e);
+                    (None, false)
+                }
+            },
+        };
+        Ok(ApprovalFlowEstimate {
+            approval_gas,
+            follow_up_gas,
+            overridden,
+        })
+    }
+
+    /// Estimate a swap together with the approval it needs
+    ///
+    /// `Ok(None)` when the swap needs no approval, as for native sells.
+    pub async fn estimate_swap(&self, swap: &SwapTransaction) -> Result<Option<ApprovalFlowEstimate>> {
+        let Some(spender) = swap.approval_spender else {
+            return Ok(None);
+        };
+        let mut request = swap.request.clone();
+        request.from.get_or_insert(swap.quote.taker);
+        self.estimate_approval_flow(swap.quote.sell_token.address, spender, swap.quote.sell_amount, request)
+            .await
+            .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    #[test]
+    fn test_allowance_storage_key() {
+        let owner = address!("00000000000000000000000000000000000000aa");
+        let spender = address!("00000000000000000000000000000000000000bb");
+        let solidity = AllowanceSlot {
+            slot: 1,
+            layout: MappingLayout::Solidity,
+        };
+        // keccak(spender . keccak(owner . 1))
+        let inner = keccak256([owner.into_word().as_slice(), B256::with_last_byte(1).as_slice()].concat());
+        let expected = keccak256([spender.into_word().as_slice(), inner.as_slice()].concat());
+        assert_eq!(allowance_storage_key(owner, spender, solidity), expected);
+
+        let vyper = AllowanceSlot {
+            slot: 1,
+            layout: MappingLayout::Vyper,
+        };
+        assert_ne!(allowance_storage_key(owner, spender, vyper), expected);
+        assert_ne!(allowance_storage_key(spender, owner, solidity), expected);
+    }
+
+    #[test]
+    fn test_approval_flow_totals() {
+        let known = ApprovalFlowEstimate {
+            approval_gas: 46_000,
+            follow_up_gas: Some(154_000),
+            overridden: true,
+        };
+        assert_eq!(known.total_gas(), Some(200_000));
+        assert_eq!(known.total_cost(10), Some(U256::from(2_000_000)));
+        assert!(known.to_string().contains("simulated"));
+
+        let unknown = ApprovalFlowEstimate {
+            follow_up_gas: None,
+            overridden: false,
+            ..known
+        };
+        assert_eq!(unknown.total_gas(), None);
+        assert_eq!(unknown.total_cost(10), None);
+    }
+}
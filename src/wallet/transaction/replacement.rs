@@ -0,0 +1,536 @@
+//! Transaction Replacement Module
+//!
+//! Builds replace-by-fee transactions for pending transactions:
+//!
+//! - **Speed up**: same nonce, same call, higher fees
+//! - **Cancel**: same nonce, 0-value self-transfer, higher fees
+//!
+//! Nodes only accept a replacement whose fees beat the original by a minimum
+//! margin (10% in geth); we bump by at least 12.5% so the replacement is also
+//! accepted by stricter mempools. Replacements are signed through the regular
+//! wallet signing path, and both the original and replacement hashes are kept
+//! so the UI can follow whichever ends up mined. Once one is mined,
+//! [`TransactionReplacer::reconcile_fees`] settles which fees were paid.
+
+use crate::error::{NetworkError, Result, VaughanError};
+use crate::wallet::transaction::fee_reconciliation::{self, FeeAttempt, FeeSettlement, MinedReceipt};
+use crate::wallet::transaction::fees::{FeeEstimator, FeePriority};
+use crate::wallet::Vaughan;
+use alloy::consensus::Transaction as _;
+use alloy::primitives::{Address, Bytes, TxHash, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+
+/// Minimum fee bump for replacements, in per mille (12.5%)
+pub const MIN_GAS_BUMP_PERMILLE: u64 = 125;
+
+/// Gas limit used for cancellation self-transfers
+const CANCEL_GAS_LIMIT: u64 = 21_000;
+
+/// Signing path used for replacement transactions
+#[async_trait::async_trait]
+pub trait TransactionSigner: Send + Sync {
+    /// Sign a transaction request and return the raw signed bytes
+    async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Vec<u8>>;
+}
+
+#[async_trait::async_trait]
+impl TransactionSigner for RwLock<Vaughan> {
+    async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Vec<u8>> {
+        let wallet = self.read().await;
+        match tx.from {
+            Some(from) => wallet.sign_transaction_as(from, tx).await,
+            None => wallet.sign_transaction(tx).await,
+        }
+    }
+}
+
+/// Kind of replacement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementKind {
+    SpeedUp,
+    Cancel,
+}
+
+/// Fee fields of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementFees {
+    Legacy {
+        gas_price: u128,
+    },
+    Eip1559 {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+}
+
+impl ReplacementFees {
+    /// Fees bumped by the minimum replacement margin
+    pub fn bumped(&self) -> Self {
+        match *self {
+            ReplacementFees::Legacy { gas_price } => ReplacementFees::Legacy {
+                gas_price: bump_fee(gas_price),
+            },
+            ReplacementFees::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => ReplacementFees::Eip1559 {
+                max_fee_per_gas: bump_fee(max_fee_per_gas),
+                max_priority_fee_per_gas: bump_fee(max_priority_fee_per_gas),
+            },
+        }
+    }
+
+    /// Bumped fees, raised further to the current market estimate if that is higher
+    pub fn for_replacement(&self, market_max_fee: u128, market_priority_fee: u128) -> Self {
+        match self.bumped() {
+            ReplacementFees::Legacy { gas_price } => ReplacementFees::Legacy {
+                gas_price: gas_price.max(market_max_fee),
+            },
+            ReplacementFees::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => {
+                let max_priority_fee_per_gas = max_priority_fee_per_gas.max(market_priority_fee);
+                ReplacementFees::Eip1559 {
+                    max_fee_per_gas: max_fee_per_gas.max(market_max_fee).max(max_priority_fee_per_gas),
+                    max_priority_fee_per_gas,
+                }
+            }
+        }
+    }
+}
+
+/// Increase `fee` by [`MIN_GAS_BUMP_PERMILLE`], rounding up
+pub fn bump_fee(fee: u128) -> u128 {
+    let bump = fee.saturating_mul(MIN_GAS_BUMP_PERMILLE as u128).div_ceil(1000);
+    fee.saturating_add(bump.max(1))
+}
+
+/// The parts of a pending transaction needed to replace it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplaceableTransaction {
+    pub hash: TxHash,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub chain_id: Option<u64>,
+    pub fees: ReplacementFees,
+}
+
+impl ReplaceableTransaction {
+    /// Extract replacement data from an RPC transaction
+    pub fn from_rpc(tx: &alloy::rpc::types::Transaction) -> Self {
+        let fees = if tx.is_dynamic_fee() {
+            ReplacementFees::Eip1559 {
+                max_fee_per_gas: tx.max_fee_per_gas(),
+                max_priority_fee_per_gas: tx.max_priority_fee_per_gas().unwrap_or_default(),
+            }
+        } else {
+            ReplacementFees::Legacy {
+                gas_price: tx.gas_price().unwrap_or_default(),
+            }
+        };
+
+        Self {
+            hash: *tx.inner.tx_hash(),
+            from: tx.inner.signer(),
+            to: tx.to(),
+            value: tx.value(),
+            input: tx.input().clone(),
+            nonce: tx.nonce(),
+            gas_limit: tx.gas_limit(),
+            chain_id: tx.chain_id(),
+            fees,
+        }
+    }
+
+    /// Build the replacement request with the given fees
+    pub fn build_replacement(&self, kind: ReplacementKind, fees: ReplacementFees) -> TransactionRequest {
+        let mut request = match kind {
+            ReplacementKind::SpeedUp => {
+                let mut request = TransactionRequest::default()
+                    .from(self.from)
+                    .value(self.value)
+                    .input(self.input.clone().into())
+                    .gas_limit(self.gas_limit);
+                if let Some(to) = self.to {
+                    request = request.to(to);
+                }
+                request
+            }
+            ReplacementKind::Cancel => TransactionRequest::default()
+                .from(self.from)
+                .to(self.from)
+                .value(U256::ZERO)
+                .gas_limit(CANCEL_GAS_LIMIT),
+        }
+        .nonce(self.nonce);
+
+        if let Some(chain_id) = self.chain_id {
+            request.chain_id = Some(chain_id);
+        }
+
+        match fees {
+            ReplacementFees::Legacy { gas_price } => request.gas_price(gas_price),
+            ReplacementFees::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => request
+                .max_fee_per_gas(max_fee_per_gas)
+                .max_priority_fee_per_gas(max_priority_fee_per_gas),
+        }
+    }
+}
+
+/// A broadcast replacement and the transaction it replaces
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplacementRecord {
+    pub kind: ReplacementKind,
+    /// Hash of the first transaction with this nonce
+    pub original_hash: TxHash,
+    /// Hash of the transaction that was replaced (original or an earlier replacement)
+    pub replaced_hash: TxHash,
+    pub replacement_hash: TxHash,
+    pub from: Address,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub fees: ReplacementFees,
+    /// Gas limit and fees of the replaced transaction
+    pub replaced_gas_limit: u64,
+    pub replaced_fees: ReplacementFees,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Replace-by-fee engine for speed-up and cancel
+pub struct TransactionReplacer<P> {
+    provider: P,
+    signer: Arc<dyn TransactionSigner>,
+    /// Replacements keyed by original transaction hash
+    replacements: Mutex<HashMap<TxHash, Vec<ReplacementRecord>>>,
+}
+
+impl<P: Provider> TransactionReplacer<P> {
+    pub fn new(provider: P, signer: Arc<dyn TransactionSigner>) -> Self {
+        Self {
+            provider,
+            signer,
+            replacements: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Re-send a pending transaction with higher fees for `new_priority`
+    pub async fn speed_up(&self, tx_hash: TxHash, new_priority: FeePriority) -> Result<ReplacementRecord> {
+        self.replace(tx_hash, ReplacementKind::SpeedUp, new_priority).await
+    }
+
+    /// Cancel a pending transaction with a 0-value self-transfer using the same nonce
+    pub async fn cancel(&self, tx_hash: TxHash) -> Result<ReplacementRecord> {
+        self.replace(tx_hash, ReplacementKind::Cancel, FeePriority::Fast).await
+    }
+
+    /// All replacements sent for an original transaction, oldest first
+    pub fn replacements_for(&self, original_hash: TxHash) -> Vec<ReplacementRecord> {
+        self.lock().get(&original_hash).cloned().unwrap_or_default()
+    }
+
+    /// Original transaction hash for a replacement hash (or the hash itself)
+    pub fn original_of(&self, tx_hash: TxHash) -> TxHash {
+        self.lock()
+            .values()
+            .flatten()
+            .find(|record| record.replacement_hash == tx_hash)
+            .map(|record| record.original_hash)
+            .unwrap_or(tx_hash)
+    }
+
+    /// Every hash that may end up mined for the original transaction's nonce
+    pub fn tracked_hashes(&self, original_hash: TxHash) -> Vec<TxHash> {
+        std::iter::once(original_hash)
+            .chain(
+                self.replacements_for(original_hash)
+                    .into_iter()
+                    .map(|r| r.replacement_hash),
+            )
+            .collect()
+    }
+
+    /// The original transaction and every replacement, as fee attempts
+    ///
+    /// Empty if no replacement was sent for `original_hash`.
+    pub fn fee_attempts(&self, original_hash: TxHash) -> Vec<FeeAttempt> {
+        let records = self.replacements_for(original_hash);
+        let original = records.first().map(|first| FeeAttempt {
+            hash: original_hash,
+            kind: None,
+            gas_limit: first.replaced_gas_limit,
+            fees: first.replaced_fees,
+        });
+        original
+            .into_iter()
+            .chain(records.iter().map(|r| FeeAttempt {
+                hash: r.replacement_hash,
+                kind: Some(r.kind),
+                gas_limit: r.gas_limit,
+                fees: r.fees,
+            }))
+            .collect()
+    }
+
+    /// Work out which of the original and its replacements was mined and what it paid
+    pub async fn reconcile_fees(&self, original_hash: TxHash) -> Result<FeeSettlement> {
+        let attempts = self.fee_attempts(original_hash);
+        let (from, nonce, attempts) = match self.replacements_for(original_hash).first() {
+            Some(record) => (record.from, record.nonce, attempts),
+            None => {
+                let tx = ReplaceableTransaction::from_rpc(&self.fetch_transaction(original_hash).await?);
+                let attempt = FeeAttempt {
+                    hash: original_hash,
+                    kind: None,
+                    gas_limit: tx.gas_limit,
+                    fees: tx.fees,
+                };
+                (tx.from, tx.nonce, vec![attempt])
+            }
+        };
+
+        let mut receipt = None;
+        for attempt in &attempts {
+            let found = self
+                .provider
+                .get_transaction_receipt(attempt.hash)
+                .await
+                .map_err(|e| NetworkError::RpcError {
+                    message: format!("Failed to fetch receipt for {}: {e}", attempt.hash),
+                })?;
+            if let Some(found) = found {
+                receipt = Some(MinedReceipt::from_rpc(&found));
+                break;
+            }
+        }
+
+        let settlement = fee_reconciliation::reconcile(from, nonce, &attempts, receipt.as_ref());
+        tracing::debug!(
+            "🧾 Reconciled fees for {} (nonce {}): {:?}, paid {}, refunded {}",
+            original_hash,
+            nonce,
+            settlement.status,
+            settlement.fee_paid(),
+            settlement.fee_refunded()
+        );
+        Ok(settlement)
+    }
+
+    async fn replace(
+        &self,
+        tx_hash: TxHash,
+        kind: ReplacementKind,
+        priority: FeePriority,
+    ) -> Result<ReplacementRecord> {
+        let original = self.fetch_pending(tx_hash).await?;
+
+        let market = FeeEstimator::estimate_fees(&self.provider, priority).await?;
+        let fees = original.fees.for_replacement(
+            market.max_fee_per_gas.saturating_to::<u128>(),
+            market.max_priority_fee_per_gas.saturating_to::<u128>(),
+        );
+        let request = original.build_replacement(kind, fees);
+
+        tracing::info!(
+            "🔁 {:?} replacement for {} (nonce {}) with fees {:?}",
+            kind,
+            tx_hash,
+            original.nonce,
+            fees
+        );
+
+        let signed = self.signer.sign_transaction(&request).await?;
+        let pending = self.provider.send_raw_transaction(&signed).await.map_err(|e| {
+            VaughanError::Network(NetworkError::RpcError {
+                message: format!("Failed to broadcast replacement transaction: {e}"),
+            })
+        })?;
+
+        let original_hash = self.original_of(tx_hash);
+        let record = ReplacementRecord {
+            kind,
+            original_hash,
+            replaced_hash: tx_hash,
+            replacement_hash: *pending.tx_hash(),
+            from: original.from,
+            nonce: original.nonce,
+            gas_limit: request.gas.unwrap_or(original.gas_limit),
+            fees,
+            replaced_gas_limit: original.gas_limit,
+            replaced_fees: original.fees,
+            created_at: Utc::now(),
+        };
+        self.lock().entry(original_hash).or_default().push(record.clone());
+
+        tracing::info!(
+            "✅ Replacement {} broadcast for {}",
+            record.replacement_hash,
+            original_hash
+        );
+        Ok(record)
+    }
+
+    async fn fetch_transaction(&self, tx_hash: TxHash) -> Result<alloy::rpc::types::Transaction> {
+        self.provider
+            .get_transaction_by_hash(tx_hash)
+            .await
+            .map_err(|e| NetworkError::RpcError {
+                message: format!("Failed to fetch transaction {tx_hash}: {e}"),
+            })?
+            .ok_or_else(|| VaughanError::NotFound(format!("Transaction {tx_hash}")))
+    }
+
+    async fn fetch_pending(&self, tx_hash: TxHash) -> Result<ReplaceableTransaction> {
+        let tx = self.fetch_transaction(tx_hash).await?;
+
+        if tx.block_number.is_some() {
+            return Err(VaughanError::ValidationError(format!(
+                "Transaction {tx_hash} is already confirmed and cannot be replaced"
+            )));
+        }
+
+        Ok(ReplaceableTransaction::from_rpc(&tx))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<TxHash, Vec<ReplacementRecord>>> {
+        self.replacements
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_tx(fees: ReplacementFees) -> ReplaceableTransaction {
+        ReplaceableTransaction {
+            hash: TxHash::repeat_byte(1),
+            from: Address::repeat_byte(0xaa),
+            to: Some(Address::repeat_byte(0xbb)),
+            value: U256::from(1_000u64),
+            input: Bytes::from(vec![0xde, 0xad]),
+            nonce: 7,
+            gas_limit: 60_000,
+            chain_id: Some(369),
+            fees,
+        }
+    }
+
+    #[test]
+    fn test_bump_is_at_least_twelve_and_a_half_percent() {
+        assert_eq!(bump_fee(1_000), 1_125);
+        assert_eq!(bump_fee(1_001), 1_127); // rounded up
+        assert_eq!(bump_fee(0), 1);
+        assert_eq!(bump_fee(u128::MAX), u128::MAX);
+    }
+
+    #[test]
+    fn test_replacement_uses_market_fees_when_higher() {
+        let fees = ReplacementFees::Eip1559 {
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: 10,
+        };
+
+        assert_eq!(
+            fees.for_replacement(0, 0),
+            ReplacementFees::Eip1559 {
+                max_fee_per_gas: 113,
+                max_priority_fee_per_gas: 12,
+            }
+        );
+        assert_eq!(
+            fees.for_replacement(500, 50),
+            ReplacementFees::Eip1559 {
+                max_fee_per_gas: 500,
+                max_priority_fee_per_gas: 50,
+            }
+        );
+    }
+
+    #[test]
+    fn test_speed_up_keeps_call_and_nonce() {
+        let tx = pending_tx(ReplacementFees::Legacy { gas_price: 1_000 });
+        let request = tx.build_replacement(ReplacementKind::SpeedUp, tx.fees.bumped());
+
+        assert_eq!(request.nonce, Some(7));
+        assert_eq!(request.to, Some(tx.to.unwrap().into()));
+        assert_eq!(request.value, Some(tx.value));
+        assert_eq!(request.input.input().cloned(), Some(tx.input.clone()));
+        assert_eq!(request.gas, Some(60_000));
+        assert_eq!(request.gas_price, Some(1_125));
+    }
+
+    #[test]
+    fn test_cancel_is_zero_value_self_transfer() {
+        let tx = pending_tx(ReplacementFees::Eip1559 {
+            max_fee_per_gas: 200,
+            max_priority_fee_per_gas: 20,
+        });
+        let request = tx.build_replacement(ReplacementKind::Cancel, tx.fees.bumped());
+
+        assert_eq!(request.nonce, Some(7));
+        assert_eq!(request.to, Some(tx.from.into()));
+        assert_eq!(request.value, Some(U256::ZERO));
+        assert_eq!(request.gas, Some(CANCEL_GAS_LIMIT));
+        assert_eq!(request.max_fee_per_gas, Some(225));
+        assert_eq!(request.max_priority_fee_per_gas, Some(23));
+        assert_eq!(request.chain_id, Some(369));
+    }
+
+    #[tokio::test]
+    async fn test_replacement_is_signed_by_the_original_sender() {
+        use alloy::signers::local::PrivateKeySigner;
+        use secrecy::SecretString;
+
+        let config = crate::wallet::WalletConfig {
+            auto_lock_timeout: None,
+            hardware_wallet_enabled: false,
+            strict_lock: false,
+            ..Default::default()
+        };
+        let keychain = Box::new(crate::security::TestKeychain::new());
+        let mut wallet = Vaughan::with_keychain(config, keychain).await.unwrap();
+        wallet.approvals().delegate("test host");
+        let (alice, bob) = (PrivateKeySigner::random(), PrivateKeySigner::random());
+        for (signer, name) in [(&alice, "Alice"), (&bob, "Bob")] {
+            let key = SecretString::new(alloy::hex::encode(signer.to_bytes()));
+            let keystore = wallet.keystore();
+            keystore
+                .write()
+                .await
+                .import_account(key, name.to_string())
+                .await
+                .unwrap();
+        }
+        wallet.switch_account(alice.address()).await.unwrap();
+        let signer: Arc<dyn TransactionSigner> = Arc::new(RwLock::new(wallet));
+
+        // Bob's pending transaction is replaced while Alice is selected
+        let tx = ReplaceableTransaction {
+            from: bob.address(),
+            value: U256::ZERO,
+            chain_id: None,
+            ..pending_tx(ReplacementFees::Legacy { gas_price: 1_000 })
+        };
+        for kind in [ReplacementKind::SpeedUp, ReplacementKind::Cancel] {
+            let request = tx.build_replacement(kind, tx.fees.bumped());
+            let raw = signer.sign_transaction(&request).await.unwrap();
+            let signed = crate::network::broadcast::RawTransactionInfo::decode(&raw).unwrap();
+            assert_eq!(signed.sender, bob.address());
+            assert_eq!(signed.nonce, 7);
+        }
+    }
+}
@@ -0,0 +1,443 @@
+//! Fee Payer Sponsorship Module
+//!
+//! Lets a funded account pay the gas for a transaction initiated from an
+//! account with no native balance. The mechanism is picked from what the
+//! active chain offers:
+//!
+//! - **ERC-4337**: when the sender is a smart account and the v0.7 EntryPoint
+//!   is deployed, the fee payer tops up the sender's EntryPoint deposit
+//!   (`depositTo`); the sender's operation then draws its gas from that deposit
+//!   when submitted through a bundler
+//! - **Relayer**: when an ERC-2771 forwarder is configured for the chain, the
+//!   sender signs an EIP-712 `ForwardRequest` and the fee payer submits it to
+//!   the forwarder, paying the gas
+//!
+//! Chains offering neither are rejected rather than silently falling back to a
+//! regular transaction that the sender cannot pay for.
+
+use crate::error::{NetworkError, Result, VaughanError};
+use crate::network::clock::clock;
+use crate::wallet::transaction::fees::{FeeEstimator, FeePriority};
+use alloy::primitives::aliases::U48;
+use alloy::primitives::{address, Address, Bytes, TxKind, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::Signer;
+use alloy::sol;
+use alloy::sol_types::{eip712_domain, SolCall, SolStruct};
+use std::collections::HashMap;
+
+/// ERC-4337 EntryPoint v0.7, deployed at the same address on every chain
+pub const ENTRY_POINT_V07: Address = address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// Gas added on top of the call estimate for 4337 validation and bundler overhead
+const USER_OPERATION_GAS_OVERHEAD: u64 = 150_000;
+
+/// Gas added on top of the call estimate for forwarder signature checks
+const FORWARDER_GAS_OVERHEAD: u64 = 60_000;
+
+/// Default validity of a signed forward request, in seconds
+const DEFAULT_FORWARD_DEADLINE_SECS: u64 = 15 * 60;
+
+sol! {
+    interface IEntryPointDeposit {
+        function depositTo(address account) external payable;
+    }
+
+    /// EIP-712 request signed by the sender (OpenZeppelin `ERC2771Forwarder`)
+    #[derive(Debug)]
+    struct ForwardRequest {
+        address from;
+        address to;
+        uint256 value;
+        uint256 gas;
+        uint256 nonce;
+        uint48 deadline;
+        bytes data;
+    }
+
+    interface IErc2771Forwarder {
+        struct ForwardRequestData {
+            address from;
+            address to;
+            uint256 value;
+            uint256 gas;
+            uint48 deadline;
+            bytes data;
+            bytes signature;
+        }
+
+        function execute(ForwardRequestData request) external payable;
+        function nonces(address owner) external view returns (uint256);
+    }
+}
+
+/// An ERC-2771 forwarder trusted by the contracts it relays to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwarderConfig {
+    pub address: Address,
+    /// EIP-712 domain name the forwarder was deployed with
+    pub domain_name: String,
+}
+
+/// How gas is sponsored on the active chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SponsorshipMechanism {
+    Erc4337 { entry_point: Address },
+    Relayer { forwarder: ForwarderConfig },
+}
+
+/// A transaction whose gas is paid by a different account
+#[derive(Debug, Clone)]
+pub struct SponsoredTransaction {
+    pub mechanism: SponsorshipMechanism,
+    pub sender: Address,
+    pub fee_payer: Address,
+    /// Transaction signed and sent by the fee payer
+    pub fee_payer_request: TransactionRequest,
+    /// For ERC-4337, the call the sender's smart account submits through a bundler
+    pub sender_call: Option<TransactionRequest>,
+    /// Native amount the fee payer commits to gas
+    pub gas_budget: U256,
+}
+
+/// Builds sponsored transactions, picking the mechanism available on the chain
+#[derive(Debug, Clone)]
+pub struct SponsoredTransactionBuilder {
+    request: TransactionRequest,
+    fee_payer: Address,
+    forwarders: HashMap<u64, ForwarderConfig>,
+    priority: FeePriority,
+    deadline_secs: u64,
+}
+
+impl SponsoredTransactionBuilder {
+    /// Sponsor `request` (whose `from` is the sender) with gas paid by `fee_payer`
+    pub fn new(request: TransactionRequest, fee_payer: Address) -> Self {
+        Self {
+            request,
+            fee_payer,
+            forwarders: HashMap::new(),
+            priority: FeePriority::Standard,
+            deadline_secs: DEFAULT_FORWARD_DEADLINE_SECS,
+        }
+    }
+
+    /// Register the ERC-2771 forwarder used on `chain_id`
+    pub fn with_forwarder(mut self, chain_id: u64, forwarder: ForwarderConfig) -> Self {
+        self.forwarders.insert(chain_id, forwarder);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: FeePriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// How long a signed forward request stays valid
+    pub fn with_deadline(mut self, secs: u64) -> Self {
+        self.deadline_secs = secs;
+        self
+    }
+
+    fn sender(&self) -> Result<Address> {
+        let sender = self
+            .request
+            .from
+            .ok_or_else(|| VaughanError::ValidationError("Sponsored transaction has no sender".into()))?;
+        if sender == self.fee_payer {
+            return Err(VaughanError::ValidationError(
+                "Fee payer must differ from the sender".into(),
+            ));
+        }
+        Ok(sender)
+    }
+
+    /// Pick the sponsorship mechanism available for the sender on `chain_id`
+    ///
+    /// ERC-4337 is preferred for smart-account senders; otherwise a configured
+    /// forwarder is used if it is deployed.
+    pub async fn select_mechanism<P: Provider>(&self, provider: &P, chain_id: u64) -> Result<SponsorshipMechanism> {
+        let sender = self.sender()?;
+        if has_code(provider, sender).await? && has_code(provider, ENTRY_POINT_V07).await? {
+            return Ok(SponsorshipMechanism::Erc4337 {
+                entry_point: ENTRY_POINT_V07,
+            });
+        }
+        if let Some(forwarder) = self.forwarders.get(&chain_id) {
+            if has_code(provider, forwarder.address).await? {
+                return Ok(SponsorshipMechanism::Relayer {
+                    forwarder: forwarder.clone(),
+                });
+            }
+            tracing::warn!(
+                "⚠️ Forwarder {} is not deployed on chain {}",
+                forwarder.address,
+                chain_id
+            );
+        }
+        Err(VaughanError::ValidationError(format!(
+            "Fee sponsorship is not available for {sender} on chain {chain_id}"
+        )))
+    }
+
+    /// Build the sponsored transaction; `sender_signer` signs relayer requests
+    pub async fn build<P: Provider>(
+        &self,
+        provider: &P,
+        chain_id: u64,
+        sender_signer: &(dyn Signer + Send + Sync),
+    ) -> Result<SponsoredTransaction> {
+        let sender = self.sender()?;
+        let to = match self.request.to {
+            Some(TxKind::Call(to)) => to,
+            _ => {
+                return Err(VaughanError::ValidationError(
+                    "Sponsored transactions must call an existing address".into(),
+                ))
+            }
+        };
+        let call_gas = match self.request.gas {
+            Some(gas) => gas,
+            None => provider.estimate_gas(self.request.clone()).await.map_err(|e| {
+                VaughanError::Network(NetworkError::RpcError {
+                    message: format!("Failed to estimate gas for sponsored call: {e}"),
+                })
+            })?,
+        };
+
+        let mechanism = self.select_mechanism(provider, chain_id).await?;
+        tracing::info!(
+            "⛽ Sponsoring {} -> {} via {:?}, fee payer {}",
+            sender,
+            to,
+            mechanism,
+            self.fee_payer
+        );
+
+        match &mechanism {
+            SponsorshipMechanism::Erc4337 { entry_point } => {
+                let fees = FeeEstimator::estimate_fees(provider, self.priority).await?;
+                let gas_budget = U256::from(call_gas + USER_OPERATION_GAS_OVERHEAD) * fees.max_fee_per_gas;
+                let deposit = IEntryPointDeposit::depositToCall { account: sender }.abi_encode();
+                let fee_payer_request = TransactionRequest::default()
+                    .from(self.fee_payer)
+                    .to(*entry_point)
+                    .value(gas_budget)
+                    .input(Bytes::from(deposit).into());
+                Ok(SponsoredTransaction {
+                    mechanism,
+                    sender,
+                    fee_payer: self.fee_payer,
+                    fee_payer_request,
+                    sender_call: Some(self.request.clone()),
+                    gas_budget,
+                })
+            }
+            SponsorshipMechanism::Relayer { forwarder } => {
+                // The forwarder would take `value` from the fee payer, who only pays for gas
+                let value = self.request.value.unwrap_or_default();
+                if !value.is_zero() {
+                    return Err(VaughanError::ValidationError(
+                        "Relayed transactions cannot carry native value".into(),
+                    ));
+                }
+                let data = self.request.input.input().cloned().unwrap_or_default();
+                let nonce = forwarder_nonce(provider, forwarder.address, sender).await?;
+                let deadline = clock().deadline(self.deadline_secs);
+
+                let forward = ForwardRequest {
+                    from: sender,
+                    to,
+                    value,
+                    gas: U256::from(call_gas),
+                    nonce,
+                    deadline: U48::from(deadline),
+                    data: data.clone(),
+                };
+                let hash = forward_request_hash(&forward, forwarder, chain_id);
+                let signature = sender_signer.sign_hash(&hash).await.map_err(|e| {
+                    VaughanError::ValidationError(format!("Sender failed to sign forward request: {e}"))
+                })?;
+
+                let execute = IErc2771Forwarder::executeCall {
+                    request: IErc2771Forwarder::ForwardRequestData {
+                        from: sender,
+                        to,
+                        value,
+                        gas: U256::from(call_gas),
+                        deadline: U48::from(deadline),
+                        data,
+                        signature: Bytes::from(signature.as_bytes().to_vec()),
+                    },
+                }
+                .abi_encode();
+                let fee_payer_request = TransactionRequest::default()
+                    .from(self.fee_payer)
+                    .to(forwarder.address)
+                    .gas_limit(call_gas + FORWARDER_GAS_OVERHEAD)
+                    .input(Bytes::from(execute).into());
+                Ok(SponsoredTransaction {
+                    mechanism,
+                    sender,
+                    fee_payer: self.fee_payer,
+                    fee_payer_request,
+                    sender_call: None,
+                    gas_budget: U256::ZERO,
+                })
+            }
+        }
+    }
+}
+
+/// EIP-712 hash of a forward request for `forwarder`'s domain
+pub fn forward_request_hash(request: &ForwardRequest, forwarder: &ForwarderConfig, chain_id: u64) -> B256 {
+    let domain = eip712_domain! {
+        name: forwarder.domain_name.clone(),
+        version: "1",
+        chain_id: chain_id,
+        verifying_contract: forwarder.address,
+    };
+    request.eip712_signing_hash(&domain)
+}
+
+async fn has_code<P: Provider>(provider: &P, address: Address) -> Result<bool> {
+    let code = provider
+        .get_code_at(address)
+        .await
+        .map_err(|e| NetworkError::RpcError {
+            message: format!("Failed to fetch code at {address}: {e}"),
+        })?;
+    Ok(!code.is_empty())
+}
+
+async fn forwarder_nonce<P: Provider>(provider: &P, forwarder: Address, owner: Address) -> Result<U256> {
+    let call = TransactionRequest::default()
+        .to(forwarder)
+        .input(Bytes::from(IErc2771Forwarder::noncesCall { owner }.abi_encode()).into());
+    let raw = provider.call(call).await.map_err(|e| NetworkError::RpcError {
+        message: format!("Failed to read forwarder nonce: {e}"),
+    })?;
+    IErc2771Forwarder::noncesCall::abi_decode_returns(&raw).map_err(|e| {
+        VaughanError::Network(NetworkError::RpcError {
+            message: format!("Invalid forwarder nonce response: {e}"),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::ProviderBuilder;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::sol_types::SolValue;
+    use alloy::transports::mock::Asserter;
+
+    const TARGET: Address = Address::repeat_byte(0x42);
+    const FEE_PAYER: Address = Address::repeat_byte(0xfe);
+
+    fn forwarder() -> ForwarderConfig {
+        ForwarderConfig {
+            address: Address::repeat_byte(0xf0),
+            domain_name: "ERC2771Forwarder".into(),
+        }
+    }
+
+    fn code() -> Bytes {
+        Bytes::from_static(&[0x60, 0x80])
+    }
+
+    #[tokio::test]
+    async fn test_smart_account_uses_erc4337() {
+        let asserter = Asserter::new();
+        asserter.push_success(&code());
+        asserter.push_success(&code());
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+
+        let request = TransactionRequest::default()
+            .from(Address::repeat_byte(0x5a))
+            .to(TARGET);
+        let builder = SponsoredTransactionBuilder::new(request, FEE_PAYER).with_forwarder(369, forwarder());
+        assert_eq!(
+            builder.select_mechanism(&provider, 369).await.unwrap(),
+            SponsorshipMechanism::Erc4337 {
+                entry_point: ENTRY_POINT_V07
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_eoa_is_relayed_through_forwarder() {
+        let sender = PrivateKeySigner::random();
+        let asserter = Asserter::new();
+        asserter.push_success(&Bytes::new()); // sender has no code
+        asserter.push_success(&code()); // forwarder is deployed
+        asserter.push_success(&Bytes::from(U256::from(7).abi_encode())); // forwarder nonce
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+
+        let request = TransactionRequest::default()
+            .from(sender.address())
+            .to(TARGET)
+            .gas_limit(50_000)
+            .input(Bytes::from_static(&[0xde, 0xad]).into());
+        let sponsored = SponsoredTransactionBuilder::new(request, FEE_PAYER)
+            .with_forwarder(369, forwarder())
+            .build(&provider, 369, &sender)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sponsored.mechanism,
+            SponsorshipMechanism::Relayer { forwarder: forwarder() }
+        );
+        assert_eq!(sponsored.fee_payer_request.from, Some(FEE_PAYER));
+        assert_eq!(sponsored.fee_payer_request.gas, Some(50_000 + FORWARDER_GAS_OVERHEAD));
+        assert!(sponsored.sender_call.is_none());
+
+        let input = sponsored.fee_payer_request.input.input().unwrap();
+        let execute = IErc2771Forwarder::executeCall::abi_decode(input).unwrap().request;
+        assert_eq!(
+            (execute.from, execute.to, execute.gas),
+            (sender.address(), TARGET, U256::from(50_000))
+        );
+
+        let forward = ForwardRequest {
+            from: execute.from,
+            to: execute.to,
+            value: execute.value,
+            gas: execute.gas,
+            nonce: U256::from(7),
+            deadline: execute.deadline,
+            data: execute.data,
+        };
+        let signature = alloy::primitives::Signature::try_from(execute.signature.as_ref()).unwrap();
+        let hash = forward_request_hash(&forward, &forwarder(), 369);
+        assert_eq!(signature.recover_address_from_prehash(&hash).unwrap(), sender.address());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unsupported_chains_and_self_sponsorship() {
+        let asserter = Asserter::new();
+        asserter.push_success(&Bytes::new());
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+
+        let sender = Address::repeat_byte(0x5a);
+        let request = TransactionRequest::default().from(sender).to(TARGET);
+        let error = SponsoredTransactionBuilder::new(request.clone(), FEE_PAYER)
+            .select_mechanism(&provider, 1)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, VaughanError::ValidationError(ref msg) if msg.contains("not available")));
+
+        assert!(SponsoredTransactionBuilder::new(request, sender)
+            .select_mechanism(&provider, 1)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,218 @@
+//! Native Token Wrap/Unwrap Builder
+//!
+//! One-click conversion between the native currency and its canonical
+//! wrapped token (ETH↔WETH, PLS↔WPLS, BNB↔WBNB, ...) from the registry in
+//! [`crate::tokens::wrapped`]. Wrapping sends the amount as value to
+//! `deposit()`; unwrapping calls `withdraw(amount)`.
+//!
+//! Balances are checked before the request is built: wrapping must leave
+//! enough native currency to pay for gas, unwrapping needs the wrapped
+//! balance plus gas.
+
+use super::erc20::{format_amount, parse_amount, TokenTransferError};
+use crate::error::{NetworkError, Result, VaughanError};
+use crate::tokens::wrapped::{wrapped_native, WrappedNative};
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use thiserror::Error;
+
+sol! {
+    interface IWrappedNative {
+        function deposit() external payable;
+        function withdraw(uint256 amount) external;
+        function balanceOf(address owner) external view returns (uint256);
+    }
+}
+
+/// Errors building a wrap or unwrap
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WrapError {
+    #[error("No wrapped native token is known for chain {0}")]
+    UnsupportedChain(u64),
+
+    #[error(transparent)]
+    Amount(#[from] TokenTransferError),
+
+    #[error("Insufficient {symbol}: {available} available, {required} required")]
+    InsufficientBalance {
+        symbol: String,
+        available: String,
+        required: String,
+    },
+}
+
+impl From<WrapError> for VaughanError {
+    fn from(error: WrapError) -> Self {
+        VaughanError::ValidationError(error.to_string())
+    }
+}
+
+/// Which way the conversion goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapDirection {
+    /// Native currency into the wrapped token
+    Wrap,
+    /// Wrapped token back into the native currency
+    Unwrap,
+}
+
+/// A validated wrap or unwrap of the chain's native currency
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NativeWrap {
+    pub token: WrappedNative,
+    pub direction: WrapDirection,
+    /// Amount in wei, the same for both sides
+    pub amount: U256,
+}
+
+impl NativeWrap {
+    /// Wrap a human amount of the native currency, e.g. `"1.5"` ETH into WETH
+    pub fn wrap(chain_id: u64, amount: &str) -> std::result::Result<Self, WrapError> {
+        Self::new(chain_id, WrapDirection::Wrap, amount)
+    }
+
+    /// Unwrap a human amount of the wrapped token, e.g. `"1.5"` WPLS into PLS
+    pub fn unwrap(chain_id: u64, amount: &str) -> std::result::Result<Self, WrapError> {
+        Self::new(chain_id, WrapDirection::Unwrap, amount)
+    }
+
+    pub fn new(chain_id: u64, direction: WrapDirection, amount: &str) -> std::result::Result<Self, WrapError> {
+        let token = *wrapped_native(chain_id).ok_or(WrapError::UnsupportedChain(chain_id))?;
+        let symbol = match direction {
+            WrapDirection::Wrap => token.native_symbol,
+            WrapDirection::Unwrap => token.symbol,
+        };
+        Ok(Self {
+            token,
+            direction,
+            amount: parse_amount(amount, 18, symbol)?,
+        })
+    }
+
+    /// Symbols converted from and to, e.g. `("ETH", "WETH")`
+    pub fn symbols(&self) -> (&'static str, &'static str) {
+        match self.direction {
+            WrapDirection::Wrap => (self.token.native_symbol, self.token.symbol),
+            WrapDirection::Unwrap => (self.token.symbol, self.token.native_symbol),
+        }
+    }
+
+    pub fn calldata(&self) -> Bytes {
+        match self.direction {
+            WrapDirection::Wrap => IWrappedNative::depositCall {}.abi_encode().into(),
+            WrapDirection::Unwrap => IWrappedNative::withdrawCall { amount: self.amount }.abi_encode().into(),
+        }
+    }
+
+    /// Native value sent with the call
+    pub fn value(&self) -> U256 {
+        match self.direction {
+            WrapDirection::Wrap => self.amount,
+            WrapDirection::Unwrap => U256::ZERO,
+        }
+    }
+
+    /// Check balances cover the conversion plus `gas_cost` in wei
+    pub fn check_balance(
+        &self,
+        native_balance: U256,
+        wrapped_balance: U256,
+        gas_cost: U256,
+    ) -> std::result::Result<(), WrapError> {
+        let insufficient = |symbol: &str, available: U256, required: U256| WrapError::InsufficientBalance {
+            symbol: symbol.to_string(),
+            available: format_amount(available, 18),
+            required: format_amount(required, 18),
+        };
+
+        let native_needed = self.value().saturating_add(gas_cost);
+        if native_balance < native_needed {
+            return Err(insufficient(self.token.native_symbol, native_balance, native_needed));
+        }
+        if self.direction == WrapDirection::Unwrap && wrapped_balance < self.amount {
+            return Err(insufficient(self.token.symbol, wrapped_balance, self.amount));
+        }
+        Ok(())
+    }
+
+    /// Read `from`'s native and wrapped balances and check them, see [`Self::check_balance`]
+    pub async fn check_balance_on_chain<P: Provider>(&self, provider: &P, from: Address, gas_cost: U256) -> Result<()> {
+        let native = provider.get_balance(from).await.map_err(|e| NetworkError::RpcError {
+            message: format!("Failed to read {} balance: {e}", self.token.native_symbol),
+        })?;
+        let call = TransactionRequest::default()
+            .to(self.token.address)
+            .input(Bytes::from(IWrappedNative::balanceOfCall { owner: from }.abi_encode()).into());
+        let wrapped = provider
+            .call(call)
+            .await
+            .ok()
+            .and_then(|data| IWrappedNative::balanceOfCall::abi_decode_returns(&data).ok())
+            .ok_or_else(|| NetworkError::RpcError {
+                message: format!("Failed to read {} balance", self.token.symbol),
+            })?;
+
+        Ok(self.check_balance(native, wrapped, gas_cost)?)
+    }
+
+    /// Transaction request calling the wrapped token contract, without gas, fees or nonce
+    pub fn to_request(&self, from: Address) -> TransactionRequest {
+        TransactionRequest::default()
+            .from(from)
+            .to(self.token.address)
+            .value(self.value())
+            .input(self.calldata().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eth(amount: u64) -> U256 {
+        U256::from(amount) * U256::from(10u64.pow(18))
+    }
+
+    #[test]
+    fn test_wrap_and_unwrap_requests() {
+        let wrap = NativeWrap::wrap(369, "2").unwrap();
+        assert_eq!(wrap.symbols(), ("PLS", "WPLS"));
+        let request = wrap.to_request(Address::repeat_byte(1));
+        assert_eq!(request.to, Some(wrap.token.address.into()));
+        assert_eq!(request.value, Some(eth(2)));
+        assert_eq!(request.input.input().unwrap()[..], [0xd0, 0xe3, 0x0d, 0xb0]);
+
+        let unwrap = NativeWrap::unwrap(56, "1.5").unwrap();
+        assert_eq!(unwrap.symbols(), ("WBNB", "BNB"));
+        let request = unwrap.to_request(Address::repeat_byte(1));
+        assert_eq!(request.value, Some(U256::ZERO));
+        let call = IWrappedNative::withdrawCall::abi_decode(request.input.input().unwrap()).unwrap();
+        assert_eq!(call.amount, eth(3) / U256::from(2));
+
+        assert_eq!(
+            NativeWrap::wrap(999_999, "1"),
+            Err(WrapError::UnsupportedChain(999_999))
+        );
+    }
+
+    #[test]
+    fn test_balance_checks_include_gas() {
+        let gas = U256::from(1_000);
+        let wrap = NativeWrap::wrap(1, "1").unwrap();
+        assert!(wrap.check_balance(eth(1) + gas, U256::ZERO, gas).is_ok());
+        assert!(matches!(
+            wrap.check_balance(eth(1), U256::ZERO, gas),
+            Err(WrapError::InsufficientBalance { symbol, .. }) if symbol == "ETH"
+        ));
+
+        let unwrap = NativeWrap::unwrap(1, "1").unwrap();
+        assert!(unwrap.check_balance(gas, eth(1), gas).is_ok());
+        assert!(matches!(
+            unwrap.check_balance(gas, eth(1) - U256::from(1), gas),
+            Err(WrapError::InsufficientBalance { symbol, .. }) if symbol == "WETH"
+        ));
+    }
+}
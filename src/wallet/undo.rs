@@ -0,0 +1,230 @@
+//! Undo/redo of wallet configuration changes
+//!
+//! Network edits, token hiding, egress policy and wallet settings are easy to
+//! get wrong during a long setup session. Each change made through
+//! [`Vaughan`](super::Vaughan) is recorded here with enough state to revert
+//! it, so the last few changes can be undone and redone.
+//!
+//! The history is kept in memory only and bounded; the oldest entries are
+//! dropped once it is full. Recording a new change clears the redo stack.
+
+use super::WalletConfig;
+use crate::network::egress::EgressPolicy;
+use crate::network::NetworkConfig;
+use crate::tokens::hidden::HiddenToken;
+use std::collections::VecDeque;
+
+/// Changes kept by default
+pub const DEFAULT_UNDO_DEPTH: usize = 50;
+
+/// A reversible configuration change
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    NetworkAdded(NetworkConfig),
+    NetworkRemoved(NetworkConfig),
+    /// A custom network was edited; the chain ID may have changed
+    NetworkUpdated {
+        before: NetworkConfig,
+        after: NetworkConfig,
+    },
+    TokenHidden {
+        chain_id: u64,
+        token: HiddenToken,
+    },
+    TokenUnhidden {
+        chain_id: u64,
+        token: HiddenToken,
+    },
+    EgressPolicyChanged {
+        before: EgressPolicy,
+        after: EgressPolicy,
+    },
+    WalletConfigChanged {
+        before: WalletConfig,
+        after: WalletConfig,
+    },
+}
+
+impl ConfigChange {
+    /// The change that reverts this one
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::NetworkAdded(config) => Self::NetworkRemoved(config.clone()),
+            Self::NetworkRemoved(config) => Self::NetworkAdded(config.clone()),
+            Self::NetworkUpdated { before, after } => Self::NetworkUpdated {
+                before: after.clone(),
+                after: before.clone(),
+            },
+            Self::TokenHidden { chain_id, token } => Self::TokenUnhidden {
+                chain_id: *chain_id,
+                token: token.clone(),
+            },
+            Self::TokenUnhidden { chain_id, token } => Self::TokenHidden {
+                chain_id: *chain_id,
+                token: token.clone(),
+            },
+            Self::EgressPolicyChanged { before, after } => Self::EgressPolicyChanged {
+                before: after.clone(),
+                after: before.clone(),
+            },
+            Self::WalletConfigChanged { before, after } => Self::WalletConfigChanged {
+                before: after.clone(),
+                after: before.clone(),
+            },
+        }
+    }
+
+    /// Short description for an undo menu, e.g. "Remove network PulseChain"
+    pub fn description(&self) -> String {
+        match self {
+            Self::NetworkAdded(config) => format!("Add network {}", config.name),
+            Self::NetworkRemoved(config) => format!("Remove network {}", config.name),
+            Self::NetworkUpdated { after, .. } => format!("Edit network {}", after.name),
+            Self::TokenHidden { token, .. } => format!("Hide token {}", token.symbol),
+            Self::TokenUnhidden { token, .. } => format!("Unhide token {}", token.symbol),
+            Self::EgressPolicyChanged { .. } => "Change connection policy".to_string(),
+            Self::WalletConfigChanged { .. } => "Change wallet settings".to_string(),
+        }
+    }
+
+    /// Whether applying this change needs the token manager
+    pub fn touches_tokens(&self) -> bool {
+        matches!(self, Self::TokenHidden { .. } | Self::TokenUnhidden { .. })
+    }
+}
+
+/// Bounded undo and redo stacks of configuration changes
+#[derive(Debug, Clone)]
+pub struct ConfigHistory {
+    undo: VecDeque<ConfigChange>,
+    redo: Vec<ConfigChange>,
+    capacity: usize,
+}
+
+impl Default for ConfigHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_UNDO_DEPTH)
+    }
+}
+
+impl ConfigHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Record a change that has just been applied
+    pub fn record(&mut self, change: ConfigChange) {
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(change);
+        self.redo.clear();
+    }
+
+    /// Most recent change that can be undone
+    pub fn next_undo(&self) -> Option<&ConfigChange> {
+        self.undo.back()
+    }
+
+    /// Most recently undone change that can be redone
+    pub fn next_redo(&self) -> Option<&ConfigChange> {
+        self.redo.last()
+    }
+
+    /// Move the change returned by [`Self::next_undo`] to the redo stack
+    ///
+    /// Call once its inverse has been applied.
+    pub fn mark_undone(&mut self) {
+        if let Some(change) = self.undo.pop_back() {
+            self.redo.push(change);
+        }
+    }
+
+    /// Move the change returned by [`Self::next_redo`] back to the undo stack
+    ///
+    /// Call once it has been applied again.
+    pub fn mark_redone(&mut self) {
+        if let Some(change) = self.redo.pop() {
+            self.undo.push_back(change);
+        }
+    }
+
+    /// Changes that can be undone, most recent first
+    pub fn undo_entries(&self) -> impl Iterator<Item = &ConfigChange> {
+        self.undo.iter().rev()
+    }
+
+    /// Changes that can be redone, most recently undone first
+    pub fn redo_entries(&self) -> impl Iterator<Item = &ConfigChange> {
+        self.redo.iter().rev()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NetworkId;
+
+    fn network(chain_id: u64) -> NetworkConfig {
+        NetworkConfig {
+            id: NetworkId(chain_id),
+            name: format!("Chain {chain_id}"),
+            rpc_url: "https://rpc.example.com".to_string(),
+            chain_id,
+            symbol: "TST".to_string(),
+            block_explorer_url: String::new(),
+            is_testnet: true,
+            is_custom: true,
+            fallback_rpc_urls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_redo_cleared() {
+        let mut history = ConfigHistory::new(2);
+        for chain_id in 1..=3 {
+            history.record(ConfigChange::NetworkAdded(network(chain_id)));
+        }
+        // Oldest entry dropped
+        let names: Vec<String> = history.undo_entries().map(ConfigChange::description).collect();
+        assert_eq!(names, vec!["Add network Chain 3", "Add network Chain 2"]);
+
+        history.mark_undone();
+        assert_eq!(history.next_redo(), Some(&ConfigChange::NetworkAdded(network(3))));
+        history.mark_redone();
+        assert_eq!(history.next_undo(), Some(&ConfigChange::NetworkAdded(network(3))));
+
+        history.mark_undone();
+        history.record(ConfigChange::NetworkRemoved(network(2)));
+        assert!(history.next_redo().is_none());
+    }
+
+    #[test]
+    fn test_inverse_round_trips() {
+        let update = ConfigChange::NetworkUpdated {
+            before: network(10),
+            after: network(11),
+        };
+        assert_eq!(
+            update.inverse(),
+            ConfigChange::NetworkUpdated {
+                before: network(11),
+                after: network(10),
+            }
+        );
+        assert_eq!(update.inverse().inverse(), update);
+
+        let added = ConfigChange::NetworkAdded(network(5));
+        assert_eq!(added.inverse(), ConfigChange::NetworkRemoved(network(5)));
+        assert!(!added.touches_tokens());
+    }
+}
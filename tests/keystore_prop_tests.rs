@@ -1,61 +1,10 @@
 use proptest::prelude::*;
 use secrecy::{ExposeSecret, SecretString};
 use tempfile::tempdir;
-use vaughan::security::{ExportAuthenticator, SecureKeystoreImpl, KeyReference, KeychainInterface};
+use vaughan::security::{ExportAuthenticator, ExportRateLimitPolicy, SecureKeystoreImpl, TestKeychain};
 use vaughan::wallet::account_manager::export::AccountExporter;
 use vaughan::wallet::account_manager::import::{AccountImporter, ImportMetadata};
-use vaughan::error::{Result, SecurityError};
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-
-// Local MockKeychain since the one in lib is cfg(test) internal
-#[derive(Debug, Clone)]
-pub struct LocalMockKeychain {
-    storage: Arc<Mutex<HashMap<String, String>>>,
-}
-
-impl LocalMockKeychain {
-    pub fn new() -> Self {
-        Self {
-            storage: Arc::new(Mutex::new(HashMap::new())),
-        }
-    }
-}
-
-impl KeychainInterface for LocalMockKeychain {
-    fn store(&self, key_ref: &KeyReference, key: SecretString) -> Result<()> {
-        let mut storage = self.storage.lock().unwrap();
-        storage.insert(key_ref.id.clone(), key.expose_secret().to_string());
-        Ok(())
-    }
-
-    fn retrieve(&self, key_ref: &KeyReference) -> Result<SecretString> {
-        let storage = self.storage.lock().map_err(|_| vaughan::error::VaughanError::Security(
-             SecurityError::KeystoreError { message: "Lock poisoned".into() }
-        ))?;
-        storage
-            .get(&key_ref.id)
-            .map(|s| SecretString::new(s.clone()))
-            .ok_or_else(|| {
-                SecurityError::KeystoreError {
-                    message: "Key not found".to_string(),
-                }
-                .into()
-            })
-    }
-
-    fn delete(&self, key_ref: &KeyReference) -> Result<()> {
-        let mut storage = self.storage.lock().map_err(|_| vaughan::error::VaughanError::Security(
-             SecurityError::KeystoreError { message: "Lock poisoned".into() }
-        ))?;
-        storage.remove(&key_ref.id);
-        Ok(())
-    }
-
-    fn clone_box(&self) -> Box<dyn KeychainInterface> {
-        Box::new(self.clone())
-    }
-}
+use vaughan::wallet::keystore_v3::KeystoreKdf;
 
 proptest! {
     // Property 2.4: V3 keystore round-trip with 100 iterations
@@ -74,9 +23,14 @@ proptest! {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
             // Setup
-            let keychain = Box::new(LocalMockKeychain::new());
+            let keychain = Box::new(TestKeychain::new());
             let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
-            let authenticator = ExportAuthenticator::new();
+            // Two exports per case, kept off the user's persisted allowance
+            let authenticator = ExportAuthenticator::in_memory(ExportRateLimitPolicy {
+                max_exports_per_hour: 1000,
+                max_auth_attempts_per_minute: 1000,
+                ..Default::default()
+            });
             let importer = AccountImporter::new();
 
             // Create account FIRST (mutation)
@@ -94,14 +48,15 @@ proptest! {
             let wallet_password = None; 
             let ks_password = SecretString::new(password.clone());
             
-            let json = exporter.export_to_v3_keystore(
+            let json = exporter.export_to_v3_keystore_with_kdf(
                 created_address,
                 &token,
                 wallet_password,
-                &ks_password
+                &ks_password,
+                KeystoreKdf::light()
             ).await.expect("Export failed");
             
-            // Import using eth-keystore logic (via import_from_keystore)
+            // Import back (via import_from_keystore)
             let dir = tempdir().unwrap();
             let file_path = dir.path().join("keystore.json");
             std::fs::write(&file_path, &json).unwrap();
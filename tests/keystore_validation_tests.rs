@@ -8,70 +8,12 @@
 //! Implements: Task 2.5 - Write unit tests for V3 keystore validation
 
 use secrecy::SecretString;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
 use tempfile::tempdir;
-use vaughan::error::{Result, SecurityError};
-use vaughan::security::{
-    ExportAuthenticator, KeyReference, KeychainInterface, SecureKeystoreImpl,
-};
+use vaughan::security::{ExportAuthenticator, ExportRateLimitPolicy, SecureKeystoreImpl, TestKeychain};
 use vaughan::wallet::account_manager::export::AccountExporter;
 use vaughan::wallet::account_manager::import::{AccountImporter, ImportMetadata};
 use vaughan::wallet::keystore_format::MetaMaskKeystore;
-
-/// Local mock keychain for testing
-#[derive(Debug, Clone)]
-pub struct TestKeychain {
-    storage: Arc<Mutex<HashMap<String, String>>>,
-}
-
-impl TestKeychain {
-    pub fn new() -> Self {
-        Self {
-            storage: Arc::new(Mutex::new(HashMap::new())),
-        }
-    }
-}
-
-impl KeychainInterface for TestKeychain {
-    fn store(&self, key_ref: &KeyReference, key: SecretString) -> Result<()> {
-        use secrecy::ExposeSecret;
-        let mut storage = self.storage.lock().unwrap();
-        storage.insert(key_ref.id.clone(), key.expose_secret().to_string());
-        Ok(())
-    }
-
-    fn retrieve(&self, key_ref: &KeyReference) -> Result<SecretString> {
-        let storage = self.storage.lock().map_err(|_| {
-            vaughan::error::VaughanError::Security(SecurityError::KeystoreError {
-                message: "Lock poisoned".into(),
-            })
-        })?;
-        storage
-            .get(&key_ref.id)
-            .map(|s| SecretString::new(s.clone()))
-            .ok_or_else(|| {
-                SecurityError::KeystoreError {
-                    message: "Key not found".to_string(),
-                }
-                .into()
-            })
-    }
-
-    fn delete(&self, key_ref: &KeyReference) -> Result<()> {
-        let mut storage = self.storage.lock().map_err(|_| {
-            vaughan::error::VaughanError::Security(SecurityError::KeystoreError {
-                message: "Lock poisoned".into(),
-            })
-        })?;
-        storage.remove(&key_ref.id);
-        Ok(())
-    }
-
-    fn clone_box(&self) -> Box<dyn KeychainInterface> {
-        Box::new(self.clone())
-    }
-}
+use vaughan::wallet::keystore_v3::KeystoreKdf;
 
 // ============================================================================
 // Task 2.5: Invalid JSON Rejection Tests
@@ -138,7 +80,7 @@ async fn test_corrupted_mac_detected() {
     // Setup
     let keychain = Box::new(TestKeychain::new());
     let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
-    let authenticator = ExportAuthenticator::new();
+    let authenticator = ExportAuthenticator::in_memory(ExportRateLimitPolicy::default());
 
     // Create account
     let account = keystore.create_account("test".into()).await.unwrap();
@@ -148,7 +90,7 @@ async fn test_corrupted_mac_detected() {
     let token = authenticator.authenticate(true).await.unwrap();
     let password = SecretString::new("testpassword123".to_string());
     let json = exporter
-        .export_to_v3_keystore(account.address, &token, None, &password)
+        .export_to_v3_keystore_with_kdf(account.address, &token, None, &password, KeystoreKdf::light())
         .await
         .unwrap();
 
@@ -180,7 +122,7 @@ async fn test_corrupted_ciphertext_detected() {
     // Setup
     let keychain = Box::new(TestKeychain::new());
     let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
-    let authenticator = ExportAuthenticator::new();
+    let authenticator = ExportAuthenticator::in_memory(ExportRateLimitPolicy::default());
 
     // Create account
     let account = keystore.create_account("test".into()).await.unwrap();
@@ -190,7 +132,7 @@ async fn test_corrupted_ciphertext_detected() {
     let token = authenticator.authenticate(true).await.unwrap();
     let password = SecretString::new("testpassword123".to_string());
     let json = exporter
-        .export_to_v3_keystore(account.address, &token, None, &password)
+        .export_to_v3_keystore_with_kdf(account.address, &token, None, &password, KeystoreKdf::light())
         .await
         .unwrap();
 
@@ -227,7 +169,7 @@ async fn test_wrong_password_rejected() {
     // Setup
     let keychain = Box::new(TestKeychain::new());
     let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
-    let authenticator = ExportAuthenticator::new();
+    let authenticator = ExportAuthenticator::in_memory(ExportRateLimitPolicy::default());
 
     // Create account
     let account = keystore.create_account("test".into()).await.unwrap();
@@ -237,7 +179,7 @@ async fn test_wrong_password_rejected() {
     let token = authenticator.authenticate(true).await.unwrap();
     let correct_password = SecretString::new("correctpassword123".to_string());
     let json = exporter
-        .export_to_v3_keystore(account.address, &token, None, &correct_password)
+        .export_to_v3_keystore_with_kdf(account.address, &token, None, &correct_password, KeystoreKdf::light())
         .await
         .unwrap();
 
@@ -259,7 +201,7 @@ async fn test_empty_password_rejected() {
     // Setup
     let keychain = Box::new(TestKeychain::new());
     let mut keystore = SecureKeystoreImpl::new(keychain).await.unwrap();
-    let authenticator = ExportAuthenticator::new();
+    let authenticator = ExportAuthenticator::in_memory(ExportRateLimitPolicy::default());
 
     // Create account
     let account = keystore.create_account("test".into()).await.unwrap();
@@ -269,7 +211,7 @@ async fn test_empty_password_rejected() {
     let token = authenticator.authenticate(true).await.unwrap();
     let password = SecretString::new("testpassword123".to_string());
     let json = exporter
-        .export_to_v3_keystore(account.address, &token, None, &password)
+        .export_to_v3_keystore_with_kdf(account.address, &token, None, &password, KeystoreKdf::light())
         .await
         .unwrap();
 
@@ -17,6 +17,7 @@ mod tests {
     use std::time::Duration;
     use vaughan::gui::state::auth_state::AuthState;
     use vaughan::security::key_cache::KeyCache;
+    use vaughan::security::SecretBuffer;
 
     #[test]
     fn test_session_timeout_detection() {
@@ -82,7 +83,7 @@ mod tests {
         let key_bytes = vec![1, 2, 3, 4, 5];
 
         // Insert key
-        cache.insert(address, key_bytes.clone()).unwrap();
+        cache.insert(address, SecretBuffer::from_slice(&key_bytes).unwrap());
 
         // Key should be available immediately
         assert!(cache.get(&address).is_some());
@@ -104,7 +105,7 @@ mod tests {
             addr_bytes[0] = i;
             let address = alloy::primitives::Address::from(addr_bytes);
             let key_bytes = vec![i; 32];
-            cache.insert(address, key_bytes).unwrap();
+            cache.insert(address, SecretBuffer::from_slice(&key_bytes).unwrap());
         }
 
         assert_eq!(cache.len(), 3);
@@ -259,8 +260,9 @@ proptest! {
         // Simulate user activity
         state.session.update_activity();
 
-        // Wait for the remaining time (which would have caused timeout without activity)
-        let remaining = timeout_ms - activity_delay_ms + 50;
+        // Wait past the original deadline (which would have caused timeout
+        // without activity) but half the delay short of the extended one
+        let remaining = timeout_ms - activity_delay_ms / 2;
         std::thread::sleep(Duration::from_millis(remaining));
 
         // Session should NOT be timed out because activity extended it
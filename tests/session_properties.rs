@@ -470,6 +470,8 @@ proptest! {
             let session = Arc::new(SessionManager::new(SessionConfig {
                 auto_lock_timeout: Some(Duration::from_millis(timeout_ms)),
                 check_interval: Duration::from_millis(20),
+                warning_before: None,
+                ..SessionConfig::default()
             }));
 
             let session_clone = Arc::clone(&session);
@@ -2,7 +2,7 @@
 // This tests our professional seed phrase encryption system independently
 
 use secrecy::{ExposeSecret, SecretString};
-use vaughan::security::keychain::OSKeychain;
+use vaughan::security::TestKeychain;
 use vaughan::security::seed::{SecureSeedStorage, SeedManager, SeedStrength};
 
 #[tokio::test]
@@ -10,9 +10,9 @@ async fn test_professional_seed_encryption_system() {
     println!("🔐 Testing Professional Seed Phrase Encryption System");
 
     // Initialize components
-    let keychain = Box::new(OSKeychain::new("test-service".to_string()).unwrap());
+    let keychain = Box::new(TestKeychain::with_service("test-service"));
     let seed_manager = SeedManager::new(keychain);
-    let storage_keychain = Box::new(OSKeychain::new("test-storage".to_string()).unwrap());
+    let storage_keychain = Box::new(TestKeychain::with_service("test-storage"));
     let secure_storage = SecureSeedStorage::new(storage_keychain);
 
     println!("✅ Initialized SeedManager and SecureSeedStorage");
@@ -122,7 +122,7 @@ async fn test_professional_seed_encryption_system() {
 async fn test_different_seed_strengths() {
     println!("💪 Testing different seed phrase strengths");
 
-    let keychain = Box::new(OSKeychain::new("test-service".to_string()).unwrap());
+    let keychain = Box::new(TestKeychain::with_service("test-service"));
     let seed_manager = SeedManager::new(keychain);
 
     let strengths = [
@@ -165,9 +165,8 @@ async fn test_concurrent_operations() {
 
     use std::sync::Arc;
 
-    let keychain = Arc::new(OSKeychain::new("test-service".to_string()).unwrap());
     let seed_manager = Arc::new(SeedManager::new(Box::new(
-        OSKeychain::new("test-service-concurrent".to_string()).unwrap(),
+        TestKeychain::with_service("test-service-concurrent"),
     )));
 
     let mut handles = Vec::new();
@@ -7,7 +7,7 @@
 mod tests {
     use secrecy::SecretString;
     use std::time::Duration;
-    use vaughan::security::{derive_key_from_seed, derive_wallet_from_seed, KeyCache, SecureSeedStorage};
+    use vaughan::security::{derive_key_from_seed, derive_wallet_from_seed, KeyCache, SecretBuffer, SecureSeedStorage};
 
     #[test]
     fn test_key_derivation_from_seed() {
@@ -17,16 +17,16 @@ mod tests {
         );
 
         // Create keychain
-        let keychain = Box::new(vaughan::security::keychain::OSKeychain::new("test".to_string()).unwrap());
+        let keychain = Box::new(vaughan::security::TestKeychain::new());
 
         // Derive key
-        let mut secure_key = derive_key_from_seed(keychain, &seed_phrase, None).unwrap();
+        let secure_key = derive_key_from_seed(keychain, &seed_phrase, None, None).unwrap();
 
         // Verify key is 32 bytes
-        assert_eq!(secure_key.as_mut_slice().len(), 32);
+        assert_eq!(secure_key.len(), 32);
 
         // Key should be non-zero
-        assert!(secure_key.as_mut_slice().iter().any(|&b| b != 0));
+        assert!(secure_key.expose_secret().iter().any(|&b| b != 0));
     }
 
     #[test]
@@ -36,8 +36,8 @@ mod tests {
             "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
         );
 
-        let keychain1 = Box::new(vaughan::security::keychain::OSKeychain::new("test".to_string()).unwrap());
-        let keychain2 = Box::new(vaughan::security::keychain::OSKeychain::new("test".to_string()).unwrap());
+        let keychain1 = Box::new(vaughan::security::TestKeychain::new());
+        let keychain2 = Box::new(vaughan::security::TestKeychain::new());
 
         let wallet1 = derive_wallet_from_seed(keychain1, &seed_phrase, None).unwrap();
         let wallet2 = derive_wallet_from_seed(keychain2, &seed_phrase, None).unwrap();
@@ -58,11 +58,11 @@ mod tests {
         assert!(cache.get(&address).is_none());
 
         // Derive and cache key
-        cache.insert(address, key_bytes.clone()).unwrap();
+        cache.insert(address, SecretBuffer::from_slice(&key_bytes).unwrap());
 
         // Second transaction - cache hit
         let cached_key = cache.get(&address).unwrap();
-        assert_eq!(cached_key, key_bytes);
+        assert_eq!(cached_key.expose_secret(), &key_bytes[..]);
 
         // Verify cache has 1 entry
         assert_eq!(cache.len(), 1);
@@ -77,7 +77,7 @@ mod tests {
         let key_bytes = vec![42u8; 32];
 
         // Cache key
-        cache.insert(address, key_bytes).unwrap();
+        cache.insert(address, SecretBuffer::from_slice(&key_bytes).unwrap());
 
         // Immediate access - should work
         assert!(cache.get(&address).is_some());
@@ -103,17 +103,17 @@ mod tests {
         let key3 = vec![3u8; 32];
 
         // Cache keys for different accounts
-        cache.insert(addr1, key1.clone()).unwrap();
-        cache.insert(addr2, key2.clone()).unwrap();
-        cache.insert(addr3, key3.clone()).unwrap();
+        cache.insert(addr1, SecretBuffer::from_slice(&key1).unwrap());
+        cache.insert(addr2, SecretBuffer::from_slice(&key2).unwrap());
+        cache.insert(addr3, SecretBuffer::from_slice(&key3).unwrap());
 
         // Verify all keys are cached
         assert_eq!(cache.len(), 3);
 
         // Verify each key is correct
-        assert_eq!(cache.get(&addr1).unwrap(), key1);
-        assert_eq!(cache.get(&addr2).unwrap(), key2);
-        assert_eq!(cache.get(&addr3).unwrap(), key3);
+        assert_eq!(cache.get(&addr1).unwrap().expose_secret(), &key1[..]);
+        assert_eq!(cache.get(&addr2).unwrap().expose_secret(), &key2[..]);
+        assert_eq!(cache.get(&addr3).unwrap().expose_secret(), &key3[..]);
     }
 
     #[test]
@@ -127,7 +127,7 @@ mod tests {
             addr_bytes[0] = i;
             let address = alloy::primitives::Address::from(addr_bytes);
             let key_bytes = vec![i; 32];
-            cache.insert(address, key_bytes).unwrap();
+            cache.insert(address, SecretBuffer::from_slice(&key_bytes).unwrap());
         }
 
         assert_eq!(cache.len(), 5);
@@ -146,9 +146,9 @@ mod tests {
             "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
         );
 
-        let keychain1 = Box::new(vaughan::security::keychain::OSKeychain::new("test".to_string()).unwrap());
-        let keychain2 = Box::new(vaughan::security::keychain::OSKeychain::new("test".to_string()).unwrap());
-        let keychain3 = Box::new(vaughan::security::keychain::OSKeychain::new("test".to_string()).unwrap());
+        let keychain1 = Box::new(vaughan::security::TestKeychain::new());
+        let keychain2 = Box::new(vaughan::security::TestKeychain::new());
+        let keychain3 = Box::new(vaughan::security::TestKeychain::new());
 
         // Derive with different paths
         let wallet1 = derive_wallet_from_seed(keychain1, &seed_phrase, Some("m/44'/60'/0'/0/0")).unwrap();
@@ -185,7 +185,7 @@ mod tests {
     #[tokio::test]
     async fn test_seed_storage_encryption_decryption() {
         // Test encrypting and decrypting a seed phrase
-        let keychain = Box::new(vaughan::security::keychain::OSKeychain::new("test".to_string()).unwrap());
+        let keychain = Box::new(vaughan::security::TestKeychain::new());
         let seed_storage = SecureSeedStorage::new(keychain);
 
         let seed_phrase = SecretString::new(
@@ -216,7 +216,7 @@ mod tests {
     #[tokio::test]
     async fn test_wrong_password_fails() {
         // Test that wrong password fails to decrypt
-        let keychain = Box::new(vaughan::security::keychain::OSKeychain::new("test".to_string()).unwrap());
+        let keychain = Box::new(vaughan::security::TestKeychain::new());
         let seed_storage = SecureSeedStorage::new(keychain);
 
         let seed_phrase = SecretString::new(